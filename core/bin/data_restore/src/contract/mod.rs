@@ -16,7 +16,7 @@ pub mod v4;
 pub mod v6;
 pub mod version;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ZkSyncDeployedContract<T: Transport> {
     pub web3_contract: web3::contract::Contract<T>,
     pub abi: ethabi::Contract,
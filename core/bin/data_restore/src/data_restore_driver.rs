@@ -1,6 +1,6 @@
-use std::collections::HashMap;
-
 // External deps
+use futures::future;
+use tokio::sync::mpsc;
 use web3::{
     contract::Contract,
     types::{H160, H256},
@@ -13,7 +13,8 @@ use zksync_crypto::{
     Fr,
 };
 use zksync_types::{
-    Account, AccountId, AccountMap, AccountUpdate, BlockNumber, SerialId, Token, TokenKind,
+    block::Block, Account, AccountId, AccountMap, AccountUpdate, BlockNumber, SerialId, Token,
+    TokenKind,
 };
 
 // Local deps
@@ -21,9 +22,12 @@ use crate::{
     contract::{get_genesis_account, ZkSyncDeployedContract},
     eth_tx_helpers::get_ethereum_transaction,
     events_state::EventsState,
+    pipeline::{fetch_rollup_ops_blocks, EventsBatch, EventsFetcher, OpsBlocksParser},
     rollup_ops::RollupOpsBlock,
-    storage_interactor::StorageInteractor,
+    snapshot::StateSnapshot,
+    storage_interactor::{DataRestoreCheckpoint, StorageInteractor},
     tree_state::TreeState,
+    CHECKPOINT_INTERVAL, PIPELINE_CAPACITY,
 };
 
 /// Storage state update:
@@ -46,6 +50,10 @@ pub enum StorageUpdateState {
 /// - Operations - The operations and events has been fetched and saved successfully and firstly driver will load
 ///   state from storage and update merkle tree by last saved operations
 ///
+/// Every `checkpoint_interval` blocks the driver saves a checkpoint: the last applied block,
+/// the last watched Ethereum block and the tree root hash along with the tree cache. After
+/// restart the state is loaded from the storage and checked against the latest checkpoint.
+///
 /// Driver can interact with other restoring components for their updating:
 /// - Events
 /// - Operations
@@ -81,6 +89,13 @@ pub struct DataRestoreDriver<T: Transport> {
     /// Serial id of the last priority operation processed by the driver. It's necessary to manually
     /// keep track of it since it's impossible to restore it from the contract.
     pub last_priority_op_serial_id: SerialId,
+    /// Amount of blocks applied between the checkpoints.
+    pub checkpoint_interval: u32,
+    /// The block of the latest saved checkpoint.
+    pub last_checkpoint_block: BlockNumber,
+    /// Root hash of the state snapshot encoded for the Ethereum smart contract. It's not verified
+    /// until the next block committed on top of it is restored.
+    pub unverified_snapshot_root_hash: Option<H256>,
 }
 
 impl<T: Transport> DataRestoreDriver<T> {
@@ -134,6 +149,9 @@ impl<T: Transport> DataRestoreDriver<T> {
             finite_mode,
             final_hash,
             last_priority_op_serial_id: 0,
+            checkpoint_interval: CHECKPOINT_INTERVAL,
+            last_checkpoint_block: BlockNumber(0),
+            unverified_snapshot_root_hash: None,
         }
     }
 
@@ -248,20 +266,87 @@ impl<T: Transport> DataRestoreDriver<T> {
         self.tree_state = tree_state;
     }
 
-    async fn update_tree_cache(&mut self, interactor: &mut StorageInteractor<'_>) {
+    /// Sets the state from the trusted snapshot instead of restoring it from genesis.
+    /// The snapshot accounts are checked against the root hash of the snapshot block,
+    /// and this root hash is verified once the next block is restored from the contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - State of the network after the verified block
+    ///
+    pub async fn set_state_from_snapshot(
+        &mut self,
+        interactor: &mut StorageInteractor<'_>,
+        snapshot: StateSnapshot,
+    ) {
+        let tree_state = snapshot.tree_state();
+        assert_eq!(
+            tree_state.root_hash(),
+            snapshot.block.new_root_hash,
+            "Snapshot accounts don't match the root hash of the block {}",
+            snapshot.block.block_number
+        );
         vlog::info!(
-            "Updating the tree cache, block number: {}",
+            "Snapshot block: {}, root hash: {:?}, last watched Ethereum block: {}",
+            snapshot.block.block_number,
+            tree_state.root_hash(),
+            snapshot.last_watched_eth_block
+        );
+
+        let mut transaction = interactor.start_transaction().await;
+
+        transaction
+            .save_events_state(&[], &[], &[], snapshot.last_watched_eth_block)
+            .await;
+        for token in snapshot.tokens.iter().cloned() {
+            transaction.save_special_token(token).await;
+        }
+        vlog::info!("Snapshot tokens added");
+
+        transaction
+            .update_tree_state(snapshot.block.clone(), snapshot.account_updates())
+            .await;
+
+        self.events_state.last_watched_eth_block_number = snapshot.last_watched_eth_block;
+        self.last_priority_op_serial_id = tree_state.current_unprocessed_priority_op;
+        self.unverified_snapshot_root_hash =
+            Some(Block::encode_fr_for_eth(snapshot.block.new_root_hash));
+        self.tree_state = tree_state;
+        self.save_checkpoint(&mut transaction).await;
+
+        transaction.commit().await;
+
+        vlog::info!("Saved snapshot tree state\n");
+    }
+
+    /// Saves the checkpoint of the current state along with the tree cache.
+    async fn save_checkpoint(&mut self, interactor: &mut StorageInteractor<'_>) {
+        vlog::info!(
+            "Saving the checkpoint, block number: {}",
             self.tree_state.block_number
         );
 
-        self.tree_state.state.root_hash();
+        let checkpoint = DataRestoreCheckpoint {
+            block_number: self.tree_state.block_number,
+            last_watched_eth_block: self.events_state.last_watched_eth_block_number,
+            root_hash: self.tree_state.root_hash(),
+        };
         let tree_cache = self.tree_state.state.get_balance_tree().get_internals();
         interactor
-            .update_tree_cache(
-                self.tree_state.block_number,
+            .save_checkpoint(
+                &checkpoint,
                 serde_json::to_string(&tree_cache).expect("failed to serialize tree cache"),
             )
             .await;
+        self.last_checkpoint_block = checkpoint.block_number;
+    }
+
+    /// Checks whether enough blocks were applied since the last checkpoint to save a new one.
+    fn is_checkpoint_due(&self) -> bool {
+        self.tree_state
+            .block_number
+            .saturating_sub(*self.last_checkpoint_block)
+            >= self.checkpoint_interval
     }
 
     /// Stops states from storage
@@ -296,6 +381,24 @@ impl<T: Transport> DataRestoreDriver<T> {
                 tree_state.fee_acc_id,
             )
         };
+        // The tree must match the checkpoint saved for its block (if any), otherwise
+        // the stored state is inconsistent and the restoring can't be resumed.
+        if let Some(checkpoint) = transaction.get_last_checkpoint().await {
+            if checkpoint.block_number == self.tree_state.block_number {
+                assert_eq!(
+                    checkpoint.root_hash,
+                    self.tree_state.root_hash(),
+                    "Tree root hash doesn't match the checkpoint for the block {}",
+                    checkpoint.block_number
+                );
+            }
+            vlog::info!(
+                "The last checkpoint is for the block {}, last watched Ethereum block: {}",
+                checkpoint.block_number,
+                checkpoint.last_watched_eth_block
+            );
+            self.last_checkpoint_block = checkpoint.block_number;
+        }
         match state {
             StorageUpdateState::Events => {
                 // Update operations
@@ -315,7 +418,12 @@ impl<T: Transport> DataRestoreDriver<T> {
             StorageUpdateState::None => {}
         }
 
-        self.last_priority_op_serial_id = transaction.get_max_priority_op_serial_id().await;
+        self.last_priority_op_serial_id = match transaction.get_max_priority_op_serial_id().await {
+            // The state restored from a snapshot has no executed priority operations
+            // in the storage until the first one is restored after the snapshot block.
+            0 => self.tree_state.current_unprocessed_priority_op,
+            serial_id => serial_id,
+        };
         let total_verified_blocks = self.zksync_contract.get_total_verified_blocks().await;
 
         let last_verified_block = self.tree_state.block_number;
@@ -332,122 +440,156 @@ impl<T: Transport> DataRestoreDriver<T> {
         // Save tree cache if necessary.
         if !is_cached {
             vlog::info!("Saving tree cache for future re-uses");
-            self.update_tree_cache(interactor).await;
+            self.save_checkpoint(interactor).await;
         }
         is_finished
     }
 
     /// Activates states updates
     pub async fn run_state_update(&mut self, interactor: &mut StorageInteractor<'_>) {
-        let mut last_watched_block: u64 = self.events_state.last_watched_eth_block_number;
-        let mut final_hash_was_found = false;
-        loop {
-            vlog::info!("Last watched ethereum block: {:?}", last_watched_block);
+        let (events_sender, events_receiver) = mpsc::channel(PIPELINE_CAPACITY);
+        let (blocks_sender, blocks_receiver) = mpsc::channel(PIPELINE_CAPACITY);
+
+        let events_fetcher = EventsFetcher {
+            web3: self.web3.clone(),
+            zksync_contract: self.zksync_contract.clone(),
+            governance_contract: self.governance_contract.clone(),
+            contract_upgrade_eth_blocks: self.contract_upgrade_eth_blocks.clone(),
+            init_contract_version: self.init_contract_version,
+            eth_blocks_step: self.eth_blocks_step,
+            end_eth_blocks_offset: self.end_eth_blocks_offset,
+            events_state: self.events_state.clone(),
+        };
+        let ops_blocks_parser = OpsBlocksParser {
+            web3: self.web3.clone(),
+            last_processed_block: self.tree_state.block_number,
+        };
+        let fetching = future::join(
+            events_fetcher.run(events_sender),
+            ops_blocks_parser.run(events_receiver, blocks_sender),
+        );
+
+        // Fetching stages only stop once the tree application stage drops its receiver.
+        tokio::select! {
+            _ = fetching => unreachable!("data restore fetching stages stopped unexpectedly"),
+            _ = self.apply_events_batches(interactor, blocks_receiver) => {}
+        }
+    }
 
+    /// The last stage of the pipeline: stores the fetched events and operations
+    /// and applies the new blocks to the tree. Returns once the restoring is finished.
+    async fn apply_events_batches(
+        &mut self,
+        interactor: &mut StorageInteractor<'_>,
+        mut receiver: mpsc::Receiver<EventsBatch>,
+    ) {
+        let mut final_hash_was_found = false;
+        while let Some(batch) = receiver.recv().await {
             // Update events
-            if self.update_events_state(interactor).await {
-                // Update operations
-                let new_ops_blocks = self.update_operations_state(interactor).await;
+            if !self.update_events_state(interactor, &batch).await {
+                continue;
+            }
 
-                if !new_ops_blocks.is_empty() {
-                    let mut transaction = interactor.start_transaction().await;
+            // Update operations
+            let new_ops_blocks = batch.ops_blocks;
+            interactor.save_rollup_ops(&new_ops_blocks).await;
+            vlog::debug!("Updated operations storage");
+            if new_ops_blocks.is_empty() {
+                continue;
+            }
 
-                    // Update tree
-                    self.update_tree_state(&mut transaction, new_ops_blocks)
-                        .await;
+            let mut transaction = interactor.start_transaction().await;
 
-                    let total_verified_blocks =
-                        self.zksync_contract.get_total_verified_blocks().await;
+            // Update tree
+            self.update_tree_state(&mut transaction, new_ops_blocks)
+                .await;
 
-                    let last_verified_block = self.tree_state.block_number;
+            let total_verified_blocks = self.zksync_contract.get_total_verified_blocks().await;
 
-                    // We must update the Ethereum stats table to match the actual stored state
-                    // to keep the `state_keeper` consistent with the `eth_sender`.
-                    transaction.update_eth_state().await;
+            let last_verified_block = self.tree_state.block_number;
 
-                    // We update tree cache for each load of updates to allow fast restart.
-                    self.update_tree_cache(&mut transaction).await;
+            // We must update the Ethereum stats table to match the actual stored state
+            // to keep the `state_keeper` consistent with the `eth_sender`.
+            transaction.update_eth_state().await;
 
-                    transaction.commit().await;
+            let is_finished = self.finite_mode && *last_verified_block == total_verified_blocks;
+            // Tree cache is only saved with the checkpoints, since it's expensive to
+            // serialize the whole tree for every load of updates.
+            if is_finished || self.is_checkpoint_due() {
+                self.save_checkpoint(&mut transaction).await;
+            }
+
+            transaction.commit().await;
+
+            vlog::info!(
+                "State updated\nProcessed {:?} blocks of total {:?} verified on contract\nRoot hash: {:?}\n",
+                last_verified_block,
+                total_verified_blocks,
+                self.tree_state.root_hash()
+            );
 
+            // If there is an expected root hash, check if current root hash matches the observed
+            // one.
+            // We check it after every block, since provided final hash may be not the latest hash
+            // by the time when it was processed.
+            if let Some(root_hash) = self.final_hash {
+                if root_hash == self.tree_state.root_hash() {
+                    final_hash_was_found = true;
                     vlog::info!(
-                        "State updated\nProcessed {:?} blocks of total {:?} verified on contract\nRoot hash: {:?}\n",
-                        last_verified_block,
-                        total_verified_blocks,
-                        self.tree_state.root_hash()
+                        "Correct expected root hash was met on the block {} out of {}",
+                        *last_verified_block,
+                        total_verified_blocks
                     );
-
-                    // If there is an expected root hash, check if current root hash matches the observed
-                    // one.
-                    // We check it after every block, since provided final hash may be not the latest hash
-                    // by the time when it was processed.
-                    if let Some(root_hash) = self.final_hash {
-                        if root_hash == self.tree_state.root_hash() {
-                            final_hash_was_found = true;
-                            vlog::info!(
-                                "Correct expected root hash was met on the block {} out of {}",
-                                *last_verified_block,
-                                total_verified_blocks
-                            );
-                        }
-                    }
-
-                    if self.finite_mode && *last_verified_block == total_verified_blocks {
-                        // Check if the final hash was found and panic otherwise.
-                        if self.final_hash.is_some() && !final_hash_was_found {
-                            panic!("Final hash was not met during the state restoring process");
-                        }
-                        break;
-                    }
                 }
             }
 
-            if last_watched_block == self.events_state.last_watched_eth_block_number {
-                vlog::info!("sleep block");
-                std::thread::sleep(std::time::Duration::from_secs(5));
-            } else {
-                last_watched_block = self.events_state.last_watched_eth_block_number;
+            if is_finished {
+                // Check if the final hash was found and panic otherwise.
+                if self.final_hash.is_some() && !final_hash_was_found {
+                    panic!("Final hash was not met during the state restoring process");
+                }
+                return;
             }
         }
     }
 
-    /// Updates events state, saves new blocks, tokens events and the last watched eth block number in storage
+    /// Updates events state from the fetched batch, saves new blocks, tokens events and the last
+    /// watched eth block number in storage.
     /// Returns bool flag, true if there are new block events
-    async fn update_events_state(&mut self, interactor: &mut StorageInteractor<'_>) -> bool {
-        let (
-            block_events,
-            token_events,
-            priority_op_data,
-            withdrawal_pending_events,
-            withdrawal_events,
-            last_watched_eth_block_number,
-        ) = self
+    async fn update_events_state(
+        &mut self,
+        interactor: &mut StorageInteractor<'_>,
+        batch: &EventsBatch,
+    ) -> bool {
+        // Priority operations are kept until the blocks containing them are applied,
+        // so they're accumulated here rather than taken from the batch events state.
+        let mut priority_op_data = std::mem::take(&mut self.events_state.priority_op_data);
+        for priority_op in &batch.priority_ops {
+            priority_op_data.insert(priority_op.serial_id, priority_op.clone());
+        }
+        self.events_state = batch.events_state.clone();
+        self.events_state.priority_op_data = priority_op_data;
+
+        let priority_op_data: Vec<_> = self
             .events_state
-            .update_events_state(
-                &self.web3,
-                &self.zksync_contract,
-                &self.governance_contract,
-                &self.contract_upgrade_eth_blocks,
-                self.eth_blocks_step,
-                self.end_eth_blocks_offset,
-                self.init_contract_version,
-            )
-            .await
-            .expect("Updating events state: cant update events state");
+            .priority_op_data
+            .values()
+            .cloned()
+            .collect();
         interactor
             .save_events_state(
-                &block_events,
-                &token_events,
+                &batch.block_events,
+                &batch.token_events,
                 &priority_op_data,
-                last_watched_eth_block_number,
+                self.events_state.last_watched_eth_block_number,
             )
             .await;
 
         interactor
-            .save_withdrawals(&withdrawal_events, &withdrawal_pending_events)
+            .save_withdrawals(&batch.withdrawal_events, &batch.withdrawal_pending_events)
             .await;
 
-        !block_events.is_empty()
+        !batch.block_events.is_empty()
     }
 
     /// Updates tree state from the new Rollup operations blocks, saves it in storage
@@ -466,6 +608,17 @@ impl<T: Transport> DataRestoreDriver<T> {
         let mut count = 0;
 
         for op_block in new_ops_blocks {
+            if let Some(root_hash) = self.unverified_snapshot_root_hash.take() {
+                assert_eq!(
+                    op_block.previous_block_root_hash, root_hash,
+                    "Block {} is not committed on top of the snapshot root hash",
+                    op_block.block_num
+                );
+                vlog::info!(
+                    "Snapshot root hash is verified by the block {}",
+                    op_block.block_num
+                );
+            }
             // Take the contract version into account when choosing block chunk sizes.
             let available_block_chunk_sizes = op_block
                 .contract_version
@@ -531,56 +684,11 @@ impl<T: Transport> DataRestoreDriver<T> {
 
     /// Returns operations blocks from verified op blocks events.
     pub async fn get_new_operation_blocks_from_events(&mut self) -> Vec<RollupOpsBlock> {
-        let mut blocks = Vec::new();
-
-        let mut last_event_tx_hash = None;
-        // The HashMap from block_num to the RollupOpsBlock data for the tx represented by last_event_tx_hash.
-        // It is used as a cache to reuse the fetched data.
-        let mut last_tx_blocks = HashMap::new();
-
-        // TODO (ZKS-722): either due to Ethereum node lag or unknown
-        // bug in the events state, we have to additionally filter out
-        // already processed rollup blocks.
-        let mut last_processed_block = self.tree_state.block_number;
-        for event in self
-            .events_state
-            .get_only_verified_committed_events()
-            .iter()
-            .filter(|bl| bl.block_num > self.tree_state.block_number)
-        {
-            // For some reasons, we have a bug where event state contains duplicates for blocks
-            if last_processed_block >= event.block_num {
-                continue;
-            }
-            // We use an aggregated block in contracts, which means that several BlockEvent can include the same tx_hash,
-            // but for correct restore we need to generate RollupBlocks from this tx only once.
-            // These blocks go one after the other, and checking only the last transaction hash is safe.
-
-            // If the previous tx hash does not exist or it is not equal to the current one, we should
-            // re-fetch the blocks for the new tx hash.
-            if !last_event_tx_hash
-                .map(|tx| tx == event.transaction_hash)
-                .unwrap_or_default()
-            {
-                let blocks = RollupOpsBlock::get_rollup_ops_blocks(&self.web3, event)
-                    .await
-                    .expect("Cant get new operation blocks from events");
-
-                last_tx_blocks = blocks
-                    .into_iter()
-                    .map(|block| (block.block_num, block))
-                    .collect();
-                last_event_tx_hash = Some(event.transaction_hash);
-            }
-
-            if let Some(rollup_block) = last_tx_blocks.remove(&event.block_num) {
-                blocks.push(rollup_block);
-                last_processed_block = event.block_num;
-            } else {
-                panic!("Block not found")
-            }
-        }
-
-        blocks
+        fetch_rollup_ops_blocks(
+            &self.web3,
+            &self.events_state.get_only_verified_committed_events(),
+            self.tree_state.block_number,
+        )
+        .await
     }
 }
@@ -1,8 +1,9 @@
 // Built-in deps
 use std::str::FromStr;
 // Workspace deps
+use zksync_crypto::convert::FeConvert;
 use zksync_storage::{
-    data_restore::records::{NewBlockEvent, NewRollupOpsBlock},
+    data_restore::records::{NewBlockEvent, NewDataRestoreCheckpoint, NewRollupOpsBlock},
     StorageProcessor,
 };
 use zksync_types::withdrawals::{WithdrawalEvent, WithdrawalPendingEvent};
@@ -22,7 +23,7 @@ use crate::{
     rollup_ops::RollupOpsBlock,
     storage_interactor::{
         block_event_into_stored_block_event, stored_block_event_into_block_event,
-        stored_ops_block_into_ops_block, CachedTreeState,
+        stored_ops_block_into_ops_block, CachedTreeState, DataRestoreCheckpoint,
     },
 };
 
@@ -449,6 +450,42 @@ impl<'a> DatabaseStorageInteractor<'a> {
         }
     }
 
+    pub async fn save_checkpoint(
+        &mut self,
+        checkpoint: &DataRestoreCheckpoint,
+        tree_cache: String,
+    ) {
+        let mut transaction = self.start_transaction().await;
+        transaction
+            .update_tree_cache(checkpoint.block_number, tree_cache)
+            .await;
+        transaction
+            .storage
+            .data_restore_schema()
+            .save_checkpoint(NewDataRestoreCheckpoint {
+                block_number: i64::from(*checkpoint.block_number),
+                last_watched_eth_block: checkpoint.last_watched_eth_block as i64,
+                root_hash: checkpoint.root_hash.to_bytes(),
+            })
+            .await
+            .expect("Failed to save the checkpoint");
+        transaction.commit().await;
+    }
+
+    pub async fn get_last_checkpoint(&mut self) -> Option<DataRestoreCheckpoint> {
+        self.storage
+            .data_restore_schema()
+            .load_last_checkpoint()
+            .await
+            .expect("Failed to load the last checkpoint")
+            .map(|checkpoint| DataRestoreCheckpoint {
+                block_number: BlockNumber(checkpoint.block_number as u32),
+                last_watched_eth_block: checkpoint.last_watched_eth_block as u64,
+                root_hash: FeConvert::from_bytes(&checkpoint.root_hash)
+                    .expect("Invalid root hash of the checkpoint"),
+            })
+    }
+
     pub async fn get_max_priority_op_serial_id(&mut self) -> SerialId {
         self.storage
             .chain()
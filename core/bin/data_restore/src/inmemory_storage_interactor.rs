@@ -17,7 +17,7 @@ use crate::{
     events::{BlockEvent, EventType},
     events_state::EventsState,
     rollup_ops::RollupOpsBlock,
-    storage_interactor::{CachedTreeState, StoredTreeState},
+    storage_interactor::{CachedTreeState, DataRestoreCheckpoint, StoredTreeState},
 };
 
 #[derive(Debug)]
@@ -31,6 +31,7 @@ struct Inner {
     last_committed_block: BlockNumber,
     last_verified_block: BlockNumber,
     accounts: AccountMap,
+    last_checkpoint: Option<DataRestoreCheckpoint>,
 }
 
 impl Default for Inner {
@@ -44,6 +45,7 @@ impl Default for Inner {
             last_committed_block: BlockNumber(0),
             last_verified_block: BlockNumber(0),
             accounts: Default::default(),
+            last_checkpoint: None,
         }
     }
 }
@@ -329,6 +331,19 @@ impl InMemoryStorageInteractor {
         // Inmemory storage doesn't support caching.
     }
 
+    pub async fn save_checkpoint(
+        &mut self,
+        checkpoint: &DataRestoreCheckpoint,
+        _tree_cache: String,
+    ) {
+        // Inmemory storage doesn't support caching, so only the checkpoint itself is saved.
+        self.inner.borrow_mut().last_checkpoint = Some(checkpoint.clone());
+    }
+
+    pub async fn get_last_checkpoint(&mut self) -> Option<DataRestoreCheckpoint> {
+        self.inner.borrow().last_checkpoint.clone()
+    }
+
     pub async fn get_max_priority_op_serial_id(&mut self) -> SerialId {
         let number_of_priority_ops = self
             .inner
@@ -5,7 +5,9 @@ pub mod eth_tx_helpers;
 pub mod events;
 pub mod events_state;
 pub mod inmemory_storage_interactor;
+pub mod pipeline;
 pub mod rollup_ops;
+pub mod snapshot;
 pub mod storage_interactor;
 pub mod tree_state;
 
@@ -18,6 +20,12 @@ use zksync_types::{tokens::get_genesis_token_list, TokenId};
 // How many blocks we will process at once.
 pub const ETH_BLOCKS_STEP: u64 = 100;
 pub const END_ETH_BLOCKS_OFFSET: u64 = 40;
+// How many blocks are applied between the checkpoints of the restoring progress.
+pub const CHECKPOINT_INTERVAL: u32 = 100;
+// How many batches of events can be fetched ahead of the tree application.
+pub const PIPELINE_CAPACITY: usize = 4;
+// How many Ethereum transactions are fetched concurrently to parse the rollup blocks.
+pub const CALLDATA_FETCH_CONCURRENCY: usize = 8;
 
 pub async fn add_tokens_to_storage(interactor: &mut StorageInteractor<'_>, eth_network: &str) {
     let genesis_tokens = get_genesis_token_list(eth_network).expect("Initial token list not found");
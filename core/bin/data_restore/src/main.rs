@@ -10,8 +10,8 @@ use web3::Web3;
 use zksync_data_restore::contract::ZkSyncDeployedContract;
 use zksync_data_restore::{
     add_tokens_to_storage, data_restore_driver::DataRestoreDriver,
-    database_storage_interactor::DatabaseStorageInteractor, storage_interactor::StorageInteractor,
-    END_ETH_BLOCKS_OFFSET, ETH_BLOCKS_STEP,
+    database_storage_interactor::DatabaseStorageInteractor, snapshot::StateSnapshot,
+    storage_interactor::StorageInteractor, END_ETH_BLOCKS_OFFSET, ETH_BLOCKS_STEP,
 };
 use zksync_types::network::Network;
 
@@ -26,6 +26,11 @@ struct Opt {
     #[structopt(long)]
     genesis: bool,
 
+    /// Restores data starting from the state snapshot at the provided path instead of genesis.
+    /// The snapshot root hash is verified against the next block committed to the contract
+    #[structopt(long, conflicts_with = "genesis")]
+    snapshot: Option<String>,
+
     /// Continues data restoring
     #[structopt(long = "continue", name = "continue")]
     continue_mode: bool,
@@ -45,6 +50,10 @@ struct Opt {
     /// Provides a path to the configuration file for data restore
     #[structopt(long = "config", name = "config")]
     config_path: Option<String>,
+
+    /// Amount of blocks applied between the checkpoints of the restoring progress
+    #[structopt(long)]
+    checkpoint_interval: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -122,10 +131,30 @@ async fn main() {
         final_hash,
         contract,
     );
+    if let Some(checkpoint_interval) = opt.checkpoint_interval {
+        driver.checkpoint_interval = checkpoint_interval;
+    }
 
     let mut interactor = StorageInteractor::Database(DatabaseStorageInteractor::new(storage));
+    // If the previous run was interrupted, resume it from the last checkpoint instead of
+    // restoring the state from genesis again.
+    let mut continue_mode = opt.continue_mode;
+    if opt.genesis || opt.snapshot.is_some() {
+        if let Some(checkpoint) = interactor.get_last_checkpoint().await {
+            vlog::warn!(
+                "Found the checkpoint for the block {}, resuming the restoring instead of starting over",
+                checkpoint.block_number
+            );
+            continue_mode = true;
+        }
+    }
     // If genesis is argument is present - there will be fetching contracts creation transactions to get first eth block and genesis acc address
-    if opt.genesis {
+    if let Some(path) = opt.snapshot.filter(|_| !continue_mode) {
+        let snapshot = StateSnapshot::from_file(&path).expect("Can't read the state snapshot");
+        driver
+            .set_state_from_snapshot(&mut interactor, snapshot)
+            .await;
+    } else if opt.genesis && !continue_mode {
         // We have to load pre-defined tokens into the database before restoring state,
         // since these tokens do not have a corresponding Ethereum events.
         add_tokens_to_storage(&mut interactor, &config.eth_network.to_string()).await;
@@ -135,7 +164,7 @@ async fn main() {
             .await;
     }
 
-    if opt.continue_mode && driver.load_state_from_storage(&mut interactor).await {
+    if continue_mode && driver.load_state_from_storage(&mut interactor).await {
         std::process::exit(0);
     }
 
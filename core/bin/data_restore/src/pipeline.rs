@@ -0,0 +1,211 @@
+//! Stages of the data restore pipeline.
+//!
+//! Restoring is split into three stages connected by the bounded channels:
+//! - [`EventsFetcher`] fetches the zkSync contract events from the Ethereum blocks range by range.
+//! - [`OpsBlocksParser`] fetches the transactions committing the verified blocks and parses
+//!   the rollup operations from their calldata, several transactions are fetched concurrently.
+//! - The tree application stage of the [`DataRestoreDriver`](crate::data_restore_driver::DataRestoreDriver)
+//!   stores the fetched data and applies the blocks to the tree.
+//!
+//! Stages run concurrently, so the next ranges of events are fetched and parsed while the
+//! current one is being applied. Channels capacity limits how far the fetching stages can get
+//! ahead of the tree. All the data is stored by the last stage in the same order as before,
+//! so the restoring can be resumed after the crash from the stored state.
+
+// Built-in deps
+use std::collections::HashMap;
+use std::time::Duration;
+// External deps
+use futures::{stream, StreamExt};
+use tokio::{sync::mpsc, time};
+use web3::{contract::Contract, Transport, Web3};
+// Workspace deps
+use zksync_types::withdrawals::{WithdrawalEvent, WithdrawalPendingEvent};
+use zksync_types::{BlockNumber, NewTokenEvent, PriorityOp};
+// Local deps
+use crate::{
+    contract::ZkSyncDeployedContract, events::BlockEvent, events_state::EventsState,
+    rollup_ops::RollupOpsBlock, CALLDATA_FETCH_CONCURRENCY,
+};
+
+/// Sleep time of the events fetcher if there are no new Ethereum blocks.
+const NO_NEW_BLOCKS_SLEEP: Duration = Duration::from_secs(5);
+
+/// Events fetched from a range of Ethereum blocks along with the rollup blocks committed in them.
+#[derive(Debug)]
+pub struct EventsBatch {
+    /// State of the events after processing the range.
+    /// Priority operations are passed separately in `priority_ops`.
+    pub events_state: EventsState,
+    pub block_events: Vec<BlockEvent>,
+    pub token_events: Vec<NewTokenEvent>,
+    /// Priority operations emitted in the range.
+    pub priority_ops: Vec<PriorityOp>,
+    pub withdrawal_pending_events: Vec<WithdrawalPendingEvent>,
+    pub withdrawal_events: Vec<WithdrawalEvent>,
+    /// New verified rollup blocks, filled by the [`OpsBlocksParser`].
+    pub ops_blocks: Vec<RollupOpsBlock>,
+}
+
+/// The first stage of the pipeline, fetches the zkSync contract events.
+pub struct EventsFetcher<T: Transport> {
+    pub web3: Web3<T>,
+    pub zksync_contract: ZkSyncDeployedContract<T>,
+    pub governance_contract: (ethabi::Contract, Contract<T>),
+    pub contract_upgrade_eth_blocks: Vec<u64>,
+    pub init_contract_version: u32,
+    pub eth_blocks_step: u64,
+    pub end_eth_blocks_offset: u64,
+    pub events_state: EventsState,
+}
+
+impl<T: Transport> EventsFetcher<T> {
+    /// Fetches the events until the receiver is dropped.
+    pub async fn run(mut self, sender: mpsc::Sender<EventsBatch>) {
+        loop {
+            let last_watched_block = self.events_state.last_watched_eth_block_number;
+            vlog::info!("Last watched ethereum block: {:?}", last_watched_block);
+
+            let (
+                block_events,
+                token_events,
+                priority_ops,
+                withdrawal_pending_events,
+                withdrawal_events,
+                _,
+            ) = self
+                .events_state
+                .update_events_state(
+                    &self.web3,
+                    &self.zksync_contract,
+                    &self.governance_contract,
+                    &self.contract_upgrade_eth_blocks,
+                    self.eth_blocks_step,
+                    self.end_eth_blocks_offset,
+                    self.init_contract_version,
+                )
+                .await
+                .expect("Updating events state: cant update events state");
+            // Priority operations are accumulated by the tree application stage,
+            // so every batch contains only the new ones.
+            self.events_state.priority_op_data.clear();
+
+            let batch = EventsBatch {
+                events_state: self.events_state.clone(),
+                block_events,
+                token_events,
+                priority_ops,
+                withdrawal_pending_events,
+                withdrawal_events,
+                ops_blocks: Vec::new(),
+            };
+            if sender.send(batch).await.is_err() {
+                // The restoring is finished.
+                return;
+            }
+
+            if last_watched_block == self.events_state.last_watched_eth_block_number {
+                vlog::info!("sleep block");
+                time::sleep(NO_NEW_BLOCKS_SLEEP).await;
+            }
+        }
+    }
+}
+
+/// The second stage of the pipeline, parses the rollup blocks from the Ethereum transactions.
+pub struct OpsBlocksParser<T: Transport> {
+    pub web3: Web3<T>,
+    /// The last rollup block passed to the next stage.
+    pub last_processed_block: BlockNumber,
+}
+
+impl<T: Transport> OpsBlocksParser<T> {
+    /// Parses the rollup blocks of the received batches until either of the channels is closed.
+    pub async fn run(
+        mut self,
+        mut receiver: mpsc::Receiver<EventsBatch>,
+        sender: mpsc::Sender<EventsBatch>,
+    ) {
+        while let Some(mut batch) = receiver.recv().await {
+            if !batch.block_events.is_empty() {
+                batch.ops_blocks = fetch_rollup_ops_blocks(
+                    &self.web3,
+                    &batch.events_state.get_only_verified_committed_events(),
+                    self.last_processed_block,
+                )
+                .await;
+                if let Some(block) = batch.ops_blocks.last() {
+                    self.last_processed_block = block.block_num;
+                }
+            }
+            if sender.send(batch).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Returns the rollup blocks following the `last_processed_block` from the verified blocks events.
+pub async fn fetch_rollup_ops_blocks<T: Transport>(
+    web3: &Web3<T>,
+    verified_events: &[BlockEvent],
+    mut last_processed_block: BlockNumber,
+) -> Vec<RollupOpsBlock> {
+    // TODO (ZKS-722): either due to Ethereum node lag or unknown
+    // bug in the events state, we have to additionally filter out
+    // already processed rollup blocks.
+    // For some reasons, we have a bug where event state contains duplicates for blocks.
+    let mut new_events = Vec::new();
+    for event in verified_events {
+        if event.block_num > last_processed_block {
+            new_events.push(*event);
+            last_processed_block = event.block_num;
+        }
+    }
+
+    // We use an aggregated block in contracts, which means that several BlockEvent can include the same tx_hash,
+    // but for correct restore we need to generate RollupBlocks from this tx only once.
+    // These blocks go one after the other, and checking only the previous transaction hash is safe.
+    let mut transaction_events: Vec<BlockEvent> = Vec::new();
+    for event in &new_events {
+        if transaction_events
+            .last()
+            .map(|last| last.transaction_hash != event.transaction_hash)
+            .unwrap_or(true)
+        {
+            transaction_events.push(*event);
+        }
+    }
+    // Transactions are fetched concurrently, but the results are yielded in the original order.
+    let mut fetched_transactions = Box::pin(
+        stream::iter(transaction_events)
+            .map(|event| async move { RollupOpsBlock::get_rollup_ops_blocks(web3, &event).await })
+            .buffered(CALLDATA_FETCH_CONCURRENCY),
+    );
+
+    let mut blocks = Vec::with_capacity(new_events.len());
+    let mut last_event_tx_hash = None;
+    // The HashMap from block_num to the RollupOpsBlock data for the tx represented by last_event_tx_hash.
+    let mut last_tx_blocks = HashMap::new();
+    for event in new_events {
+        if last_event_tx_hash != Some(event.transaction_hash) {
+            last_tx_blocks = fetched_transactions
+                .next()
+                .await
+                .expect("Transaction must be fetched for every event")
+                .expect("Cant get new operation blocks from events")
+                .into_iter()
+                .map(|block| (block.block_num, block))
+                .collect();
+            last_event_tx_hash = Some(event.transaction_hash);
+        }
+
+        if let Some(rollup_block) = last_tx_blocks.remove(&event.block_num) {
+            blocks.push(rollup_block);
+        } else {
+            panic!("Block not found")
+        }
+    }
+
+    blocks
+}
@@ -0,0 +1,203 @@
+//! Restoring from a state snapshot.
+//!
+//! Replaying every block since genesis takes a lot of time, so a new operator can bootstrap
+//! the state from a snapshot published by a trusted party instead. The snapshot contains
+//! the state of the accounts after some verified block along with the header of this block.
+//!
+//! The snapshot is not trusted blindly: the tree built from the snapshot accounts must have
+//! the root hash of the snapshot block, and the next block restored from the contract must be
+//! committed on top of the same root hash, otherwise the restoring is aborted.
+
+// Built-in deps
+use std::collections::HashMap;
+// External deps
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_types::{
+    block::Block, Account, AccountId, AccountMap, AccountUpdate, AccountUpdates, PubKeyHash, Token,
+};
+// Local deps
+use crate::tree_state::TreeState;
+
+/// State of the network after the verified block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    /// The last block included into the snapshot. Transactions of the block are not used.
+    pub block: Block,
+    /// Ethereum block to start fetching the contract events from. It must precede the Ethereum
+    /// block with the commitment of the next rollup block, otherwise its events are missed.
+    pub last_watched_eth_block: u64,
+    /// All the accounts of the network, including the NFT storage account.
+    pub accounts: Vec<(AccountId, Account)>,
+    /// Tokens listed before the snapshot block, including the special NFT token.
+    /// NFTs themselves are restored from the minted NFTs of their creators.
+    pub tokens: Vec<Token>,
+}
+
+impl StateSnapshot {
+    /// Reads the snapshot from the JSON file.
+    pub fn from_file(path: &str) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Returns the updates creating the snapshot accounts from scratch.
+    pub fn account_updates(&self) -> AccountUpdates {
+        let mut updates = Vec::new();
+        let mut mint_nft_updates = Vec::new();
+        for (id, account) in &self.accounts {
+            let nonce = account.nonce;
+            updates.push((
+                *id,
+                AccountUpdate::Create {
+                    address: account.address,
+                    nonce,
+                },
+            ));
+            for (token, balance) in account.get_nonzero_balances() {
+                updates.push((
+                    *id,
+                    AccountUpdate::UpdateBalance {
+                        old_nonce: nonce,
+                        new_nonce: nonce,
+                        balance_update: (token, BigUint::from(0u64), balance.0),
+                    },
+                ));
+            }
+            if account.pub_key_hash != PubKeyHash::default() {
+                updates.push((
+                    *id,
+                    AccountUpdate::ChangePubKeyHash {
+                        old_pub_key_hash: PubKeyHash::default(),
+                        new_pub_key_hash: account.pub_key_hash,
+                        old_nonce: nonce,
+                        new_nonce: nonce,
+                    },
+                ));
+            }
+            // NFTs are minted once all the accounts are created, since the minted token
+            // may belong to another account.
+            for nft in account.minted_nfts.values() {
+                mint_nft_updates.push((
+                    *id,
+                    AccountUpdate::MintNFT {
+                        token: nft.clone(),
+                        nonce,
+                    },
+                ));
+            }
+        }
+        updates.extend(mint_nft_updates);
+        updates
+    }
+
+    /// Returns the tree state built from the snapshot accounts.
+    pub fn tree_state(&self) -> TreeState {
+        let account_map: AccountMap = self.accounts.iter().cloned().collect();
+        let nfts: HashMap<_, _> = self
+            .accounts
+            .iter()
+            .flat_map(|(_, account)| account.minted_nfts.values().cloned())
+            .map(|nft| (nft.id, nft))
+            .collect();
+
+        let mut tree_state = TreeState::load(
+            self.block.block_number,
+            account_map,
+            self.block.processed_priority_ops.1,
+            self.block.fee_account,
+        );
+        tree_state.state.nfts = nfts;
+        tree_state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_crypto::params::{
+        MIN_NFT_TOKEN_ID, NFT_STORAGE_ACCOUNT_ADDRESS, NFT_STORAGE_ACCOUNT_ID, NFT_TOKEN_ID,
+    };
+    use zksync_types::{Address, BlockNumber, Nonce, TokenId, H256, NFT};
+
+    fn snapshot() -> StateSnapshot {
+        let mut fee_account = Account::default_with_address(&Address::repeat_byte(1));
+        fee_account.nonce = Nonce(3);
+        fee_account.pub_key_hash = PubKeyHash::from_bytes(&[7u8; 20]).unwrap();
+        fee_account.set_balance(TokenId(0), BigUint::from(100u64));
+        fee_account.minted_nfts.insert(
+            TokenId(MIN_NFT_TOKEN_ID),
+            NFT::new(
+                TokenId(MIN_NFT_TOKEN_ID),
+                0,
+                AccountId(0),
+                fee_account.address,
+                Address::repeat_byte(2),
+                None,
+                H256::repeat_byte(3),
+            ),
+        );
+        let mut special_account = Account::default_with_address(&NFT_STORAGE_ACCOUNT_ADDRESS);
+        special_account.set_balance(NFT_TOKEN_ID, BigUint::from(MIN_NFT_TOKEN_ID + 1));
+        let accounts = vec![
+            (AccountId(0), fee_account),
+            (NFT_STORAGE_ACCOUNT_ID, special_account),
+        ];
+
+        let root_hash = TreeState::load(
+            BlockNumber(5),
+            accounts.iter().cloned().collect(),
+            0,
+            AccountId(0),
+        )
+        .root_hash();
+        let block = Block::new(
+            BlockNumber(5),
+            root_hash,
+            AccountId(0),
+            Vec::new(),
+            (2, 4),
+            10,
+            0.into(),
+            0.into(),
+            H256::default(),
+            0,
+        );
+        StateSnapshot {
+            block,
+            last_watched_eth_block: 100,
+            accounts,
+            tokens: Vec::new(),
+        }
+    }
+
+    /// Checks that the account updates recreate the snapshot accounts.
+    #[test]
+    fn account_updates() {
+        let snapshot = snapshot();
+        let mut accounts = AccountMap::default();
+        for (id, update) in snapshot.account_updates() {
+            let account = Account::apply_update(accounts.remove(&id), update);
+            accounts.insert(id, account.unwrap());
+        }
+
+        for (id, account) in &snapshot.accounts {
+            assert_eq!(&accounts[id], account);
+        }
+    }
+
+    #[test]
+    fn tree_state() {
+        let snapshot = snapshot();
+        let tree_state = snapshot.tree_state();
+
+        assert_eq!(tree_state.root_hash(), snapshot.block.new_root_hash);
+        assert_eq!(tree_state.block_number, BlockNumber(5));
+        assert_eq!(tree_state.current_unprocessed_priority_op, 4);
+        assert!(tree_state
+            .state
+            .nfts
+            .contains_key(&TokenId(MIN_NFT_TOKEN_ID)));
+    }
+}
@@ -2,6 +2,7 @@ use std::{collections::HashMap, convert::TryFrom};
 
 use web3::types::H256;
 
+use zksync_crypto::Fr;
 use zksync_storage::data_restore::records::{
     NewBlockEvent, StoredBlockEvent, StoredRollupOpsBlock,
 };
@@ -35,6 +36,16 @@ pub struct CachedTreeState {
     pub nfts: HashMap<TokenId, NFT>,
 }
 
+/// Checkpoint of the restoring progress, the driver resumes from the latest one after restart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataRestoreCheckpoint {
+    /// The last block applied to the tree.
+    pub block_number: BlockNumber,
+    pub last_watched_eth_block: u64,
+    /// Root hash of the tree after applying the block.
+    pub root_hash: Fr,
+}
+
 #[allow(clippy::large_enum_variant)]
 pub enum StorageInteractor<'a> {
     Database(DatabaseStorageInteractor<'a>),
@@ -216,6 +227,26 @@ impl StorageInteractor<'_> {
         storage_interact!(self.update_tree_cache(block_number, tree_cache))
     }
 
+    /// Saves the checkpoint of the restoring progress along with the tree cache for its block.
+    ///
+    /// # Arguments
+    ///
+    /// * `checkpoint` - Checkpoint to be saved
+    /// * `tree_cache` - Merkle tree cache at the checkpoint block
+    ///
+    pub async fn save_checkpoint(
+        &mut self,
+        checkpoint: &DataRestoreCheckpoint,
+        tree_cache: String,
+    ) {
+        storage_interact!(self.save_checkpoint(checkpoint, tree_cache))
+    }
+
+    /// Returns the latest checkpoint of the restoring progress, if any.
+    pub async fn get_last_checkpoint(&mut self) -> Option<DataRestoreCheckpoint> {
+        storage_interact!(self.get_last_checkpoint())
+    }
+
     /// Retrieves the maximum serial id of a priority requests
     pub async fn get_max_priority_op_serial_id(&mut self) -> SerialId {
         storage_interact!(self.get_max_priority_op_serial_id())
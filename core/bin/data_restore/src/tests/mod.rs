@@ -87,6 +87,7 @@ fn create_deposit(from: Address, to: Address, amount: u32) -> ExecutedOperations
         eth_hash: H256::zero(),
         eth_block: 0,
         eth_block_index: None,
+        l1_sender: None,
     };
     let executed_deposit_op = ExecutedPriorityOp {
         priority_op: priority_operation,
@@ -445,6 +446,14 @@ async fn test_run_state_update(mut storage: StorageProcessor<'_>) {
 
     assert_eq!(driver.events_state.committed_events.len(), events.len());
 
+    // The checkpoint must be saved once the restoring is finished.
+    let checkpoint = interactor
+        .get_last_checkpoint()
+        .await
+        .expect("checkpoint must be saved");
+    assert_eq!(*checkpoint.block_number, 2);
+    assert_eq!(checkpoint.root_hash, driver.tree_state.root_hash());
+
     // Nullify the state of driver
     let eth = Eth::new(transport.clone());
 
@@ -463,7 +472,8 @@ async fn test_run_state_update(mut storage: StorageProcessor<'_>) {
     // Load state from db and check it
     assert!(driver.load_state_from_storage(&mut interactor).await);
     assert_eq!(driver.events_state.committed_events.len(), events.len());
-    assert_eq!(*driver.tree_state.block_number, 2)
+    assert_eq!(*driver.tree_state.block_number, 2);
+    assert_eq!(*driver.last_checkpoint_block, 2);
 }
 
 // TODO: Find a way to restore this test (ZKS-694)
@@ -518,6 +518,7 @@ impl TreeState {
                 eth_hash: H256::zero(),
                 eth_block: 0,
                 eth_block_index: None,
+                l1_sender: None,
             },
             block_index,
             created_at: chrono::Utc::now(),
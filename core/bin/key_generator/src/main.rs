@@ -46,7 +46,7 @@ struct Opt {
 }
 
 fn main() {
-    vlog::init();
+    let _vlog_guard = vlog::init();
 
     let opt = Opt::from_args();
     let config = ChainConfig::from_env();
@@ -0,0 +1,489 @@
+//! Mass payout of the tokens from the operator account to a list of recipients, e.g. for airdrops.
+//!
+//! The list of `(address, token, amount)` entries is stored as a named payout, and the transfers
+//! are sent in batches of the configured size: the batch fee is paid once by the zero transfer
+//! to the sender itself, so the larger the batches are, the less is spent on the fees.
+//!
+//! Each batch is signed with the explicit nonce and stored along with the hashes of its transfers
+//! before it's submitted, so the payout can be resumed by its name after the tool is stopped at any
+//! moment: the stored batches are resubmitted unchanged, and the recipients are only returned to
+//! the pending ones once the nonce of their batch is taken by another transaction.
+
+// Built-in uses
+use std::{collections::BTreeSet, io::Read, path::Path, str::FromStr, time::Duration};
+// External uses
+use anyhow::{ensure, format_err, Context};
+use num::{BigUint, Zero};
+use serde::Deserialize;
+// Workspace uses
+use zksync::{
+    error::ClientError, operations::SyncTransactionHandle, provider::Provider, types::Commitment,
+    Wallet,
+};
+use zksync_eth_signer::EthereumSigner;
+use zksync_storage::{
+    mass_payouts::{
+        records::StoredMassPayoutRecipient, RECIPIENT_STATUS_EXECUTED, RECIPIENT_STATUS_FAILED,
+        RECIPIENT_STATUS_PENDING, RECIPIENT_STATUS_SUBMITTED,
+    },
+    StorageProcessor,
+};
+use zksync_types::{
+    helpers::is_token_amount_packable,
+    tx::{TxEthSignature, TxHash},
+    Address, Token, TokenId, TokenLike, ZkSyncTx,
+};
+
+/// Entry of the payout list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoutEntry {
+    pub address: Address,
+    pub token: TokenLike,
+    /// Amount in the smallest units of the token.
+    pub amount: BigUint,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPayoutEntry {
+    address: String,
+    token: String,
+    amount: String,
+}
+
+impl RawPayoutEntry {
+    fn parse(self) -> anyhow::Result<PayoutEntry> {
+        let address = Address::from_str(self.address.trim_start_matches("0x"))
+            .map_err(|_| format_err!("invalid address `{}`", self.address))?;
+        let amount = BigUint::from_str(&self.amount)
+            .map_err(|_| format_err!("invalid amount `{}`", self.amount))?;
+        ensure!(!amount.is_zero(), "amount must be positive");
+        // The SDK would round the amount down, so the recipient would get less than intended.
+        ensure!(
+            is_token_amount_packable(&amount),
+            "amount {} is not packable",
+            amount
+        );
+        Ok(PayoutEntry {
+            address,
+            token: TokenLike::parse(&self.token),
+            amount,
+        })
+    }
+}
+
+fn parse_entries(
+    raw_entries: impl IntoIterator<Item = anyhow::Result<RawPayoutEntry>>,
+) -> anyhow::Result<Vec<PayoutEntry>> {
+    let entries = raw_entries
+        .into_iter()
+        .enumerate()
+        .map(|(idx, entry)| {
+            entry
+                .and_then(RawPayoutEntry::parse)
+                .with_context(|| format!("entry #{} is invalid", idx + 1))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    ensure!(!entries.is_empty(), "payout list is empty");
+    Ok(entries)
+}
+
+/// Parses the CSV payout list with the `address,token,amount` header.
+pub fn parse_csv_payout_list(reader: impl Read) -> anyhow::Result<Vec<PayoutEntry>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    parse_entries(
+        reader
+            .deserialize()
+            .map(|entry| entry.map_err(anyhow::Error::from)),
+    )
+}
+
+/// Parses the JSON array of the `{"address", "token", "amount"}` objects.
+pub fn parse_json_payout_list(reader: impl Read) -> anyhow::Result<Vec<PayoutEntry>> {
+    let raw_entries: Vec<RawPayoutEntry> = serde_json::from_reader(reader)?;
+    parse_entries(raw_entries.into_iter().map(Ok))
+}
+
+/// Reads the payout list, the format is chosen by the file extension.
+pub fn read_payout_list(path: &Path) -> anyhow::Result<Vec<PayoutEntry>> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("can't open the payout list {}", path.display()))?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("csv") => parse_csv_payout_list(file),
+        Some("json") => parse_json_payout_list(file),
+        _ => anyhow::bail!("payout list must be a `.csv` or `.json` file"),
+    }
+}
+
+/// Amounts of the payout recipients per status.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PayoutSummary {
+    pub pending: usize,
+    pub submitted: usize,
+    pub executed: usize,
+    pub failed: usize,
+}
+
+impl PayoutSummary {
+    pub fn new(recipients: &[StoredMassPayoutRecipient]) -> Self {
+        let mut summary = Self::default();
+        for recipient in recipients {
+            match recipient.status.as_str() {
+                RECIPIENT_STATUS_PENDING => summary.pending += 1,
+                RECIPIENT_STATUS_SUBMITTED => summary.submitted += 1,
+                RECIPIENT_STATUS_EXECUTED => summary.executed += 1,
+                RECIPIENT_STATUS_FAILED => summary.failed += 1,
+                status => vlog::warn!("Unknown status of the recipient: {}", status),
+            }
+        }
+        summary
+    }
+}
+
+/// Executor of the payouts sent from the account of the wallet.
+#[derive(Debug)]
+pub struct MassPayout<S: EthereumSigner, P: Provider> {
+    wallet: Wallet<S, P>,
+    fee_token: Token,
+    /// Maximum amount of the transactions in the batch, including the fee-paying one.
+    batch_size: usize,
+    /// Batch is not sent if its fee is higher.
+    max_batch_fee: Option<BigUint>,
+    /// Time to wait for the batch to be committed.
+    receipt_timeout: Duration,
+}
+
+impl<S, P> MassPayout<S, P>
+where
+    S: EthereumSigner,
+    P: Provider + Clone,
+{
+    pub fn new(
+        wallet: Wallet<S, P>,
+        fee_token: TokenLike,
+        batch_size: usize,
+        max_batch_fee: Option<BigUint>,
+        receipt_timeout: Duration,
+    ) -> anyhow::Result<Self> {
+        ensure!(
+            batch_size >= 2,
+            "batch must fit at least one transfer and the fee payment"
+        );
+        let fee_token = wallet
+            .tokens
+            .resolve(fee_token.clone())
+            .ok_or_else(|| format_err!("fee token {:?} is not supported", fee_token))?;
+        Ok(Self {
+            wallet,
+            fee_token,
+            batch_size,
+            max_batch_fee,
+            receipt_timeout,
+        })
+    }
+
+    /// Stores the new payout of the entries, returns its id.
+    pub async fn create_payout(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        name: &str,
+        entries: Vec<PayoutEntry>,
+    ) -> anyhow::Result<i64> {
+        let recipients = entries
+            .into_iter()
+            .map(|entry| {
+                let token = self
+                    .wallet
+                    .tokens
+                    .resolve(entry.token.clone())
+                    .filter(|token| !token.is_nft)
+                    .ok_or_else(|| format_err!("token {:?} is not supported", entry.token))?;
+                Ok((entry.address, token.id, entry.amount))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let payout_id = storage
+            .mass_payouts_schema()
+            .create_payout(name, self.wallet.address(), &recipients)
+            .await?;
+        Ok(payout_id)
+    }
+
+    /// Pays the pending recipients of the payout. The batches submitted before the restart are
+    /// awaited first. Returns the summary of the payout once there are no pending recipients left.
+    pub async fn run(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        payout_id: i64,
+    ) -> anyhow::Result<PayoutSummary> {
+        ensure!(
+            self.wallet.is_signing_key_set().await?,
+            "signing key of the sender account is not set"
+        );
+
+        let recipients = storage
+            .mass_payouts_schema()
+            .load_recipients(payout_id)
+            .await?;
+        let submitted_batches: BTreeSet<_> = recipients
+            .iter()
+            .filter(|recipient| recipient.status == RECIPIENT_STATUS_SUBMITTED)
+            .filter_map(|recipient| recipient.batch_id)
+            .collect();
+        for batch_id in submitted_batches {
+            vlog::info!("Resuming the batch {}", batch_id);
+            self.resume_batch(storage, batch_id).await?;
+        }
+
+        loop {
+            let recipients = storage
+                .mass_payouts_schema()
+                .load_recipients(payout_id)
+                .await?;
+            let pending: Vec<_> = recipients
+                .iter()
+                .filter(|recipient| recipient.status == RECIPIENT_STATUS_PENDING)
+                .take(self.batch_size - 1)
+                .collect();
+            if pending.is_empty() {
+                return Ok(PayoutSummary::new(&recipients));
+            }
+            self.send_batch(storage, payout_id, &pending).await?;
+        }
+    }
+
+    /// Signs, stores and submits the batch paying the recipients, then awaits its execution.
+    async fn send_batch(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        payout_id: i64,
+        recipients: &[&StoredMassPayoutRecipient],
+    ) -> anyhow::Result<()> {
+        let nonce = self
+            .wallet
+            .provider
+            .account_info(self.wallet.address())
+            .await?
+            .committed
+            .nonce;
+        let mut builder = self
+            .wallet
+            .start_batch()
+            .nonce(nonce)
+            .fee_token(self.fee_token.id)?;
+        for recipient in recipients {
+            let amount = BigUint::from_str(&recipient.amount.to_string())?;
+            builder = builder.add_transfer(
+                TokenId(recipient.token_id as u32),
+                amount,
+                Address::from_slice(&recipient.address),
+            )?;
+        }
+        let (txs, eth_signature) = builder.txs().await?;
+
+        // The fee is paid by the last transaction appended by the builder.
+        let fee = match txs.last() {
+            Some((ZkSyncTx::Transfer(transfer), _)) => transfer.fee.clone(),
+            _ => anyhow::bail!("batch has no fee-paying transfer"),
+        };
+        if let Some(max_batch_fee) = &self.max_batch_fee {
+            ensure!(
+                fee <= *max_batch_fee,
+                "batch fee {} exceeds the limit {}",
+                fee,
+                max_batch_fee
+            );
+        }
+
+        let transfers: Vec<_> = recipients
+            .iter()
+            .zip(&txs)
+            .map(|(recipient, (tx, _))| (recipient.idx, tx.hash()))
+            .collect();
+        let tx_hash = transfers[0].1;
+        let batch_id = storage
+            .mass_payouts_schema()
+            .store_batch(
+                payout_id,
+                nonce,
+                &serde_json::to_value(&txs)?,
+                eth_signature
+                    .as_ref()
+                    .map(serde_json::to_value)
+                    .transpose()?
+                    .as_ref(),
+                &transfers,
+            )
+            .await?;
+        vlog::info!(
+            "Sending the batch {} of {} transfers with the nonce {} and the fee {} {}",
+            batch_id,
+            transfers.len(),
+            nonce,
+            fee,
+            self.fee_token.symbol
+        );
+
+        match self
+            .wallet
+            .provider
+            .send_txs_batch(txs, eth_signature)
+            .await
+        {
+            Ok(_) => {}
+            // The batch is rejected by the server, so its nonce is not taken.
+            Err(ClientError::RpcError(failure)) => {
+                let error = format!("rejected by the server: {:?}", failure.error);
+                storage
+                    .mass_payouts_schema()
+                    .discard_batch(batch_id, &error)
+                    .await?;
+                anyhow::bail!("batch {} is {}", batch_id, error);
+            }
+            // The batch may have been received, it's resubmitted once the payout is resumed.
+            Err(err) => return Err(err).context("can't send the batch, resume the payout"),
+        }
+        self.await_batch(storage, batch_id, tx_hash).await
+    }
+
+    /// Resubmits the batch stored before the restart and awaits its execution.
+    async fn resume_batch(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        batch_id: i64,
+    ) -> anyhow::Result<()> {
+        let batch = storage
+            .mass_payouts_schema()
+            .get_batch(batch_id)
+            .await?
+            .ok_or_else(|| format_err!("batch {} is not stored", batch_id))?;
+        let txs: Vec<(ZkSyncTx, Option<TxEthSignature>)> = serde_json::from_value(batch.txs)?;
+        let eth_signature: Option<TxEthSignature> = batch
+            .eth_signature
+            .map(serde_json::from_value)
+            .transpose()?;
+        let tx_hash = txs
+            .first()
+            .map(|(tx, _)| tx.hash())
+            .ok_or_else(|| format_err!("batch {} is empty", batch_id))?;
+
+        let provider = &self.wallet.provider;
+        if !provider.tx_info(tx_hash).await?.executed {
+            // The batch may have never reached the server, resubmitting it is harmless,
+            // since it's either rejected as a duplicate or executed only once.
+            if let Err(err) = provider.send_txs_batch(txs, eth_signature).await {
+                vlog::warn!("Batch {} is not resubmitted: {}", batch_id, err);
+                let nonce = provider
+                    .account_info(self.wallet.address())
+                    .await?
+                    .committed
+                    .nonce;
+                // The status is checked after the nonce, so the batch executed in between
+                // is not discarded.
+                if *nonce > batch.nonce as u32 && !provider.tx_info(tx_hash).await?.executed {
+                    storage
+                        .mass_payouts_schema()
+                        .discard_batch(batch_id, "nonce of the batch is taken")
+                        .await?;
+                    return Ok(());
+                }
+            }
+        }
+        self.await_batch(storage, batch_id, tx_hash).await
+    }
+
+    async fn await_batch(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        batch_id: i64,
+        tx_hash: TxHash,
+    ) -> anyhow::Result<()> {
+        let handle = SyncTransactionHandle::new(tx_hash, self.wallet.provider.clone());
+        match handle
+            .wait_for_receipt(Commitment::Committed, Some(self.receipt_timeout))
+            .await
+        {
+            Ok(_) => {
+                vlog::info!("Batch {} is executed", batch_id);
+                storage
+                    .mass_payouts_schema()
+                    .complete_batch(batch_id, None)
+                    .await?;
+            }
+            // Transactions of the batch will never be executed, so the recipients are paid again.
+            Err(err @ ClientError::TransactionReplaced)
+            | Err(err @ ClientError::TransactionExpired) => {
+                vlog::warn!("Batch {} is discarded: {}", batch_id, err);
+                storage
+                    .mass_payouts_schema()
+                    .discard_batch(batch_id, &err.to_string())
+                    .await?;
+            }
+            Err(ClientError::TransactionRejected(reason)) => {
+                vlog::error!("Batch {} has failed: {}", batch_id, reason);
+                storage
+                    .mass_payouts_schema()
+                    .complete_batch(batch_id, Some(&reason))
+                    .await?;
+            }
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!("batch {} is not executed yet, resume the payout", batch_id)
+                })
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payout_list_parsing() {
+        let csv = "address,token,amount\n\
+            0x0101010101010101010101010101010101010101, ETH, 1000\n\
+            0202020202020202020202020202020202020202,1,2000\n";
+        let entries = parse_csv_payout_list(csv.as_bytes()).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                PayoutEntry {
+                    address: Address::repeat_byte(0x01),
+                    token: TokenLike::Symbol("ETH".to_string()),
+                    amount: BigUint::from(1000u32),
+                },
+                PayoutEntry {
+                    address: Address::repeat_byte(0x02),
+                    token: TokenLike::Id(TokenId(1)),
+                    amount: BigUint::from(2000u32),
+                },
+            ]
+        );
+
+        let json = r#"[
+            {"address": "0x0101010101010101010101010101010101010101", "token": "ETH", "amount": "1000"},
+            {"address": "0x0202020202020202020202020202020202020202", "token": "1", "amount": "2000"}
+        ]"#;
+        assert_eq!(parse_json_payout_list(json.as_bytes()).unwrap(), entries);
+    }
+
+    #[test]
+    fn invalid_payout_list() {
+        let parse = |rows: &str| {
+            parse_csv_payout_list(format!("address,token,amount\n{}", rows).as_bytes())
+        };
+        let address = "0x0101010101010101010101010101010101010101";
+
+        assert!(parse("").is_err());
+        assert!(parse(&format!("{},ETH,0", address)).is_err());
+        assert!(parse(&format!("{},ETH,-1", address)).is_err());
+        assert!(parse("0x01,ETH,1000").is_err());
+        // Amount is not packable.
+        assert!(parse(&format!("{},ETH,123456789123456789123456789", address)).is_err());
+        // The error points to the invalid entry.
+        let err = parse(&format!("{},ETH,1000\n{},ETH,abc", address, address)).unwrap_err();
+        assert!(err.to_string().contains("#2"));
+    }
+}
@@ -0,0 +1,127 @@
+use std::{path::PathBuf, str::FromStr, time::Duration};
+
+use anyhow::{ensure, format_err};
+use num::BigUint;
+use structopt::StructOpt;
+use zksync::{utils::private_key_from_seed, Network, RpcProvider, Wallet, WalletCredentials};
+use zksync_eth_signer::PrivateKeySigner;
+use zksync_storage::StorageProcessor;
+use zksync_types::{tx::PackedEthSignature, Address, TokenLike, H256};
+
+use mass_payout::{read_payout_list, MassPayout};
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync mass payout", author = "Matter Labs")]
+#[structopt(
+    about = "Tool to pay the tokens to the list of recipients in batches, resumable by the payout name"
+)]
+struct Opt {
+    /// Name of the payout, the stopped payout is resumed by it.
+    #[structopt(long)]
+    name: String,
+    /// CSV or JSON list of the `address`, `token` and `amount` entries, amounts are in the smallest
+    /// units of the tokens. Required to create the payout, ignored when it's resumed.
+    #[structopt(long)]
+    input: Option<PathBuf>,
+    /// URL of the database the payout progress is stored in.
+    #[structopt(long, env = "DATABASE_URL")]
+    db_url: String,
+    #[structopt(long, env = "ZKSYNC_RPC_ADDR", default_value = "http://127.0.0.1:3030")]
+    rpc_addr: String,
+    #[structopt(long, default_value = "localhost")]
+    network: String,
+    /// Ethereum private key of the sender account.
+    #[structopt(long, env = "MASS_PAYOUT_PRIVATE_KEY", hide_env_values = true)]
+    private_key: String,
+    /// Token the batch fees are paid in.
+    #[structopt(long, default_value = "ETH")]
+    fee_token: String,
+    /// Maximum amount of the transactions in the batch, including the fee-paying one.
+    #[structopt(long, default_value = "50")]
+    batch_size: usize,
+    /// Maximum fee of the batch in the smallest units of the fee token, the payout is stopped
+    /// if the fee is higher.
+    #[structopt(long)]
+    max_batch_fee: Option<String>,
+    /// Time to wait for the batch to be committed, in seconds.
+    #[structopt(long, default_value = "600")]
+    receipt_timeout: u64,
+    /// Pays the recipients of the failed batches again.
+    #[structopt(long)]
+    retry_failed: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let _vlog_guard = vlog::init();
+    let opt = Opt::from_args();
+
+    let network = Network::from_str(&opt.network)
+        .map_err(|_| format_err!("unknown network {}", opt.network))?;
+    let provider = RpcProvider::from_addr_and_network(&opt.rpc_addr, network);
+    let eth_pk = H256::from_str(opt.private_key.trim_start_matches("0x"))?;
+    let address: Address = PackedEthSignature::address_from_private_key(&eth_pk)?;
+    let zksync_pk = private_key_from_seed(eth_pk.as_bytes())?;
+    let credentials =
+        WalletCredentials::<PrivateKeySigner>::from_pk(address, zksync_pk, Some(eth_pk));
+    let wallet = Wallet::new(provider, credentials).await?;
+
+    let max_batch_fee = opt
+        .max_batch_fee
+        .as_deref()
+        .map(BigUint::from_str)
+        .transpose()?;
+    let payout = MassPayout::new(
+        wallet,
+        TokenLike::parse(&opt.fee_token),
+        opt.batch_size,
+        max_batch_fee,
+        Duration::from_secs(opt.receipt_timeout),
+    )?;
+
+    let mut storage = StorageProcessor::establish_connection_to(&opt.db_url).await?;
+    let payout_id = match storage.mass_payouts_schema().get_payout(&opt.name).await? {
+        Some(stored) => {
+            ensure!(
+                stored.sender == address.as_bytes(),
+                "payout `{}` is sent from another account",
+                opt.name
+            );
+            if opt.input.is_some() {
+                vlog::warn!("Payout `{}` is resumed, the input is ignored", opt.name);
+            }
+            if opt.retry_failed {
+                let retried = storage
+                    .mass_payouts_schema()
+                    .retry_failed_recipients(stored.id)
+                    .await?;
+                vlog::info!("{} failed recipients are paid again", retried);
+            }
+            stored.id
+        }
+        None => {
+            let input = opt
+                .input
+                .as_ref()
+                .ok_or_else(|| format_err!("input is required to create the payout"))?;
+            let entries = read_payout_list(input)?;
+            vlog::info!(
+                "Creating the payout `{}` of {} recipients",
+                opt.name,
+                entries.len()
+            );
+            payout
+                .create_payout(&mut storage, &opt.name, entries)
+                .await?
+        }
+    };
+
+    let summary = payout.run(&mut storage, payout_id).await?;
+    vlog::info!(
+        "Payout `{}` is finished: {} recipients are paid, {} have failed",
+        opt.name,
+        summary.executed,
+        summary.failed
+    );
+    Ok(())
+}
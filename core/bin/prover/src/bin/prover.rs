@@ -0,0 +1,6 @@
+use zksync_prover::cli_utils::main_for_configured_prover;
+
+#[tokio::main]
+async fn main() {
+    main_for_configured_prover().await;
+}
@@ -3,10 +3,13 @@ use std::time::Duration;
 // External deps
 use structopt::StructOpt;
 // Workspace deps
-use zksync_config::configs::ProverConfig as EnvProverConfig;
+use zksync_config::configs::{prover::ProverBackend, ProverConfig as EnvProverConfig};
 use zksync_utils::{get_env, parse_env};
 // Local deps
-use crate::{client, prover_work_cycle, ProverConfig, ProverImpl, ShutdownRequest};
+use crate::{
+    client, dummy_prover::DummyProver, plonk_step_by_step_prover::PlonkStepByStepProver,
+    prover_work_cycle, remote_prover::RemoteProver, ProverConfig, ProverImpl, ShutdownRequest,
+};
 use zksync_config::configs::api::PrometheusConfig;
 use zksync_prometheus_exporter::run_prometheus_exporter;
 
@@ -82,3 +85,19 @@ where
     )
     .await;
 }
+
+/// Runs the prover with the backend chosen in the configuration.
+pub async fn main_for_configured_prover() {
+    let prover_options = EnvProverConfig::from_env();
+    match prover_options.prover.backend {
+        ProverBackend::PlonkStepByStep => {
+            main_for_prover_impl::<PlonkStepByStepProver>(true).await;
+        }
+        ProverBackend::Dummy => {
+            main_for_prover_impl::<DummyProver>(false).await;
+        }
+        ProverBackend::Remote => {
+            main_for_prover_impl::<RemoteProver>(true).await;
+        }
+    }
+}
@@ -3,6 +3,7 @@ pub mod cli_utils;
 pub mod client;
 pub mod dummy_prover;
 pub mod plonk_step_by_step_prover;
+pub mod remote_prover;
 
 // Built-in deps
 use futures::{pin_mut, FutureExt};
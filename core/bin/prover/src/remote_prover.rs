@@ -0,0 +1,81 @@
+// Built-in deps
+use std::time::Duration;
+// External deps
+use anyhow::format_err;
+// Workspace deps
+use zksync_config::configs::ProverConfig as EnvProverConfig;
+use zksync_prover_utils::api::{JobRequestData, JobResultData};
+// Local deps
+use crate::{ProverConfig, ProverImpl};
+
+#[derive(Debug)]
+pub struct RemoteProverConfig {
+    /// URL of the proving service.
+    pub url: String,
+    /// Timeout for the single proof generation request.
+    pub request_timeout: Duration,
+}
+
+impl ProverConfig for RemoteProverConfig {
+    fn from_env() -> Self {
+        let env_config = EnvProverConfig::from_env();
+
+        Self {
+            url: env_config.remote.url.clone(),
+            request_timeout: env_config.remote.request_timeout(),
+        }
+    }
+}
+
+/// Prover that delegates the proof generation to an external proving service
+/// (e.g. GPU prover service or remote proving marketplace).
+///
+/// The job data is sent as is to the `/prove` endpoint of the service, which is
+/// expected to respond with the `JobResultData` for the job.
+#[derive(Debug)]
+pub struct RemoteProver {
+    config: RemoteProverConfig,
+    client: reqwest::blocking::Client,
+}
+
+impl ProverImpl for RemoteProver {
+    type Config = RemoteProverConfig;
+
+    fn create_from_config(config: Self::Config) -> Self {
+        // Proof is generated in the dedicated thread, so the blocking client is used
+        // to not depend on the async runtime. The client can't be built within the runtime
+        // either, so it's built in the dedicated thread as well.
+        let request_timeout = config.request_timeout;
+        let client = std::thread::spawn(move || {
+            reqwest::blocking::Client::builder()
+                .timeout(request_timeout)
+                .build()
+        })
+        .join()
+        .expect("proving service client thread panicked")
+        .expect("failed to build the proving service client");
+
+        Self { config, client }
+    }
+
+    fn create_proof(&self, data: JobRequestData) -> anyhow::Result<JobResultData> {
+        let url = format!("{}/prove", self.config.url.trim_end_matches('/'));
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&data)
+            .send()
+            .map_err(|e| format_err!("failed to send request to the proving service: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "proving service responded with status {}",
+                response.status()
+            ));
+        }
+
+        response
+            .json()
+            .map_err(|e| format_err!("failed to parse proving service response: {}", e))
+    }
+}
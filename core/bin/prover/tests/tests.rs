@@ -1,6 +1,9 @@
 // Built-in deps
 use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::TcpListener;
 use std::sync::Arc;
+use std::thread::JoinHandle;
 use std::time::Duration;
 // External deps
 use futures::{pin_mut, FutureExt};
@@ -15,15 +18,17 @@ use zksync_config::configs::{ChainConfig, ProverConfig};
 use zksync_crypto::{
     circuit::{account::CircuitAccount, CircuitAccountTree},
     pairing::ff::PrimeField,
+    proof::SingleProof,
     Fr,
 };
 use zksync_prover::dummy_prover::{DummyProver, DummyProverConfig};
 use zksync_prover::plonk_step_by_step_prover::{
     PlonkStepByStepProver, PlonkStepByStepProverConfig,
 };
+use zksync_prover::remote_prover::{RemoteProver, RemoteProverConfig};
 use zksync_prover::{ProverImpl, ShutdownRequest};
 use zksync_prover_utils::api::{
-    JobRequestData, ProverInputRequest, ProverInputResponse, ProverOutputRequest,
+    JobRequestData, JobResultData, ProverInputRequest, ProverInputResponse, ProverOutputRequest,
 };
 use zksync_types::{
     block::smallest_block_size_for_chunks, operations::DepositOp, Account, AccountId, Address,
@@ -57,6 +62,7 @@ impl Default for MockProverConfigs {
                 cycle_wait: 500,
                 request_timeout: 1,
                 die_after_proof: false,
+                backend: zksync_config::configs::prover::ProverBackend::PlonkStepByStep,
             },
             core: zksync_config::configs::prover::Core {
                 gone_timeout: 2,
@@ -66,6 +72,10 @@ impl Default for MockProverConfigs {
                 prepare_data_interval: 5000,
                 witness_generators: 2,
             },
+            remote: zksync_config::configs::prover::Remote {
+                url: "http://127.0.0.1:8089".to_string(),
+                request_timeout: 10,
+            },
         };
 
         Self {
@@ -256,6 +266,85 @@ async fn test_publishing_proof() {
     };
 }
 
+/// Serves the requests with the given responses one by one, like the proving service would.
+/// Returns the URL of the service and the handle returning the request lines of the served requests.
+fn spawn_proving_service(
+    responses: Vec<(&'static str, Vec<u8>)>,
+) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut request_lines = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            // Read the headers, then the body of the announced length.
+            let mut request = Vec::new();
+            let mut buffer = [0u8; 4096];
+            let headers_end = loop {
+                let read = stream.read(&mut buffer).unwrap();
+                assert_ne!(read, 0, "request is cut off");
+                request.extend_from_slice(&buffer[..read]);
+                if let Some(pos) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                    break pos + 4;
+                }
+            };
+            let headers = String::from_utf8_lossy(&request[..headers_end]).to_string();
+            let content_length: usize = headers
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse().unwrap())
+                })
+                .unwrap_or(0);
+            while request.len() < headers_end + content_length {
+                let read = stream.read(&mut buffer).unwrap();
+                assert_ne!(read, 0, "request is cut off");
+                request.extend_from_slice(&buffer[..read]);
+            }
+            request_lines.push(headers.lines().next().unwrap().to_string());
+
+            let head = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                status,
+                body.len()
+            );
+            stream.write_all(head.as_bytes()).unwrap();
+            stream.write_all(&body).unwrap();
+        }
+        request_lines
+    });
+    (url, handle)
+}
+
+/// Checks that the job is sent to the proving service and its result is returned,
+/// and that the error statuses of the service are reported.
+#[test]
+fn test_remote_prover() {
+    let proof = serde_json::to_vec(&JobResultData::BlockProof(SingleProof::default())).unwrap();
+    let (url, service) = spawn_proving_service(vec![
+        ("200 OK", proof),
+        ("500 Internal Server Error", Vec::new()),
+    ]);
+    let prover = RemoteProver::create_from_config(RemoteProverConfig {
+        url: format!("{}/", url),
+        request_timeout: Duration::from_secs(10),
+    });
+
+    let result = prover
+        .create_proof(JobRequestData::AggregatedBlockProof(Vec::new()))
+        .unwrap();
+    assert!(matches!(result, JobResultData::BlockProof(_)));
+
+    let err = prover
+        .create_proof(JobRequestData::AggregatedBlockProof(Vec::new()))
+        .unwrap_err();
+    assert!(err.to_string().contains("500"));
+
+    let request_lines = service.join().unwrap();
+    assert_eq!(request_lines, vec!["POST /prove HTTP/1.1"; 2]);
+}
+
 #[derive(Debug, Clone, Default)]
 struct MockApiClient {
     /// All published proofs are saved by `job_id`.
@@ -14,17 +14,22 @@ use zksync_gateway_watcher::run_gateway_watcher_if_multiplexed;
 use zksync_witness_generator::run_prover_server;
 
 use tokio::task::JoinHandle;
-use zksync_config::configs::api::{PrivateApiConfig, PrometheusConfig, TokenConfig};
+use zksync_config::configs::api::{GrpcConfig, PrivateApiConfig, PrometheusConfig, TokenConfig};
 use zksync_config::{
     configs::api::{CommonApiConfig, JsonRpcConfig, ProverApiConfig, RestApiConfig, Web3Config},
     ChainConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
-    ForcedExitRequestsConfig, GatewayWatcherConfig, ProverConfig, TickerConfig, ZkSyncConfig,
+    ForcedExitRequestsConfig, GatewayWatcherConfig, ObjectStoreConfig, ProverConfig, TickerConfig,
+    ZkSyncConfig,
+};
+use zksync_core::{
+    consistency_checker::run_consistency_checker, events_cleaner::run_events_cleaner,
+    leadership::Leadership, pruner::run_pruner, rejected_tx_cleaner::run_rejected_tx_cleaner,
+    runtime_config::run_runtime_config_watcher, slow_queries_cleaner::run_slow_queries_cleaner,
 };
-use zksync_core::rejected_tx_cleaner::run_rejected_tx_cleaner;
 use zksync_mempool::run_mempool_tx_handler;
 use zksync_prometheus_exporter::{run_operation_counter, run_prometheus_exporter};
-use zksync_storage::ConnectionPool;
-use zksync_types::ChainId;
+use zksync_storage::{slow_queries::run_slow_queries_recorder, ConnectionPool};
+use zksync_types::{runtime_config::SharedRuntimeConfig, ChainId};
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
 
@@ -41,6 +46,8 @@ pub enum Component {
     Web3Api,
     RpcApi,
     RpcWebSocketApi,
+    /// gRPC interface for the internal services, not started by default.
+    GrpcApi,
 
     // Core components
     Fetchers,
@@ -53,6 +60,9 @@ pub enum Component {
     Prometheus,
     PrometheusPeriodicMetrics,
     RejectedTaskCleaner,
+    EventsCleaner,
+    Pruner,
+    ConsistencyChecker,
 }
 
 impl FromStr for Component {
@@ -64,6 +74,7 @@ impl FromStr for Component {
             "web3-api" => Ok(Component::Web3Api),
             "rpc-api" => Ok(Component::RpcApi),
             "rpc-websocket-api" => Ok(Component::RpcWebSocketApi),
+            "grpc-api" => Ok(Component::GrpcApi),
             "eth-sender" => Ok(Component::EthSender),
             "witness-generator" => Ok(Component::WitnessGenerator),
             "forced-exit" => Ok(Component::ForcedExit),
@@ -71,6 +82,9 @@ impl FromStr for Component {
             "fetchers" => Ok(Component::Fetchers),
             "core" => Ok(Component::Core),
             "rejected-task-cleaner" => Ok(Component::RejectedTaskCleaner),
+            "events-cleaner" => Ok(Component::EventsCleaner),
+            "pruner" => Ok(Component::Pruner),
+            "consistency-checker" => Ok(Component::ConsistencyChecker),
             "prometheus-periodic-metrics" => Ok(Component::PrometheusPeriodicMetrics),
             other => Err(format!("{} is not a valid component name", other)),
         }
@@ -93,6 +107,7 @@ impl Default for ComponentsToRun {
             Component::Prometheus,
             Component::Core,
             Component::RejectedTaskCleaner,
+            Component::ConsistencyChecker,
             Component::Fetchers,
             Component::PrometheusPeriodicMetrics,
         ])
@@ -152,24 +167,56 @@ async fn main() -> anyhow::Result<()> {
 }
 
 async fn run_server(components: &ComponentsToRun) {
-    let connection_pool = ConnectionPool::new(None);
-    let read_only_connection_pool = ConnectionPool::new_readonly_pool(None);
+    // Workloads use dedicated pools, so e.g. a burst of the API requests can't starve
+    // the state keeper of the connections.
+    let db_config = DBConfig::from_env();
+    let connection_pool = ConnectionPool::new_partition("core", db_config.pool_size as u32);
+    let read_only_connection_pool =
+        ConnectionPool::new_readonly_pool(Some(db_config.api_pool_size));
+    let background_connection_pool =
+        ConnectionPool::new_partition("background", db_config.background_pool_size);
     let (stop_signal_sender, mut stop_signal_receiver) = mpsc::channel(256);
 
     let mut tasks = vec![];
 
+    // The state keeper and the Ethereum sender are run by the leader instance only.
+    let leadership = if components.0.contains(&Component::Core)
+        || components.0.contains(&Component::EthSender)
+    {
+        let (leadership, election_task) =
+            Leadership::start(ChainConfig::from_env().leadership.check_interval());
+        tasks.push(election_task);
+        Some(leadership)
+    } else {
+        None
+    };
+
+    // Parameters overridden via the private API are reloaded by every instance.
+    let runtime_config = SharedRuntimeConfig::default();
+    tasks.push(run_runtime_config_watcher(
+        background_connection_pool.clone(),
+        runtime_config.clone(),
+    ));
+
     if components.0.contains(&Component::Web3Api) {
         // Run web3 api
         tasks.push(zksync_api::api_server::web3::start_rpc_server(
-            connection_pool.clone(),
+            read_only_connection_pool.clone(),
             &Web3Config::from_env(),
             &TokenConfig::from_env(),
         ));
     }
 
+    if components.0.contains(&Component::GrpcApi) {
+        tasks.push(zksync_api::api_server::grpc::start_grpc_server(
+            read_only_connection_pool.clone(),
+            &GrpcConfig::from_env(),
+        ));
+    }
+
     if components.0.contains(&Component::Fetchers) {
         // Run price fetchers
-        let mut price_tasks = run_price_updaters(connection_pool.clone());
+        let mut price_tasks = run_price_updaters(background_connection_pool.clone());
         tasks.append(&mut price_tasks);
     }
 
@@ -207,12 +254,15 @@ async fn run_server(components: &ComponentsToRun) {
         let eth_client_config = ETHClientConfig::from_env();
         let ticker_info = Box::new(TickerInfo::new(read_only_connection_pool.clone()));
 
-        let ticker = FeeTicker::new_with_default_validator(
+        let mut ticker = FeeTicker::new_with_default_validator(
             ticker_info,
             fee_ticker_config,
             chain_config.max_blocks_to_aggregate(),
+            chain_config.backpressure.clone(),
+            chain_config.gas_prediction.predictor(),
             read_only_connection_pool.clone(),
         );
+        ticker.set_runtime_config(runtime_config.clone());
 
         if components.0.contains(&Component::RpcWebSocketApi) {
             let (mempool_tx_request_sender, mempool_tx_request_receiver) =
@@ -221,6 +271,7 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes.clone(),
+                chain_config.mempool.admission_shards,
             ));
             tasks.push(zksync_api::api_server::rpc_subscriptions::start_ws_server(
                 read_only_connection_pool.clone(),
@@ -243,6 +294,7 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes.clone(),
+                chain_config.mempool.admission_shards,
             ));
             tasks.push(zksync_api::api_server::rpc_server::start_rpc_server(
                 read_only_connection_pool.clone(),
@@ -264,6 +316,7 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes,
+                chain_config.mempool.admission_shards,
             ));
             let private_config = PrivateApiConfig::from_env();
             tasks.push(zksync_api::api_server::rest::start_server_thread_detached(
@@ -281,7 +334,10 @@ async fn run_server(components: &ComponentsToRun) {
     }
 
     if components.0.contains(&Component::EthSender) {
-        tasks.push(run_eth_sender(connection_pool.clone()))
+        tasks.push(run_eth_sender(
+            connection_pool.clone(),
+            leadership.clone().expect("leader election is started"),
+        ))
     }
 
     if components.0.contains(&Component::Core) {
@@ -293,6 +349,8 @@ async fn run_server(components: &ComponentsToRun) {
                 read_only_connection_pool.clone(),
                 &ZkSyncConfig::from_env(),
                 eth_gateway.clone(),
+                leadership.expect("leader election is started"),
+                runtime_config.clone(),
             )
             .await
             .unwrap(),
@@ -310,18 +368,52 @@ async fn run_server(components: &ComponentsToRun) {
         tasks.push(prometheus_task_handle);
         // We can run them only with active prometheus
         if components.0.contains(&Component::PrometheusPeriodicMetrics) {
-            let counter_task_handle = run_operation_counter(read_only_connection_pool.clone());
+            let counter_task_handle = run_operation_counter(background_connection_pool.clone());
             tasks.push(counter_task_handle);
         }
     }
 
     if components.0.contains(&Component::ForcedExit) {
-        tasks.append(&mut run_forced_exit(connection_pool.clone()));
+        tasks.append(&mut run_forced_exit(background_connection_pool.clone()));
+    }
+
+    if components.0.contains(&Component::EventsCleaner) {
+        tasks.push(run_events_cleaner(
+            &db_config,
+            background_connection_pool.clone(),
+        ));
+    }
+
+    if components.0.contains(&Component::Pruner) {
+        tasks.push(run_pruner(
+            &db_config,
+            &ObjectStoreConfig::from_env(),
+            background_connection_pool.clone(),
+        ));
+    }
+
+    if components.0.contains(&Component::ConsistencyChecker) {
+        tasks.push(run_consistency_checker(
+            &db_config,
+            background_connection_pool.clone(),
+        ));
+    }
+
+    if db_config.record_slow_queries {
+        tasks.push(run_slow_queries_recorder(
+            background_connection_pool.clone(),
+        ));
+        tasks.push(run_slow_queries_cleaner(
+            &db_config,
+            background_connection_pool.clone(),
+        ));
     }
 
     if components.0.contains(&Component::RejectedTaskCleaner) {
-        let config = DBConfig::from_env();
-        tasks.push(run_rejected_tx_cleaner(&config, connection_pool));
+        tasks.push(run_rejected_tx_cleaner(
+            &db_config,
+            background_connection_pool,
+        ));
     }
 
     {
@@ -357,6 +449,7 @@ pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
         connection_pool.clone(),
         mempool_tx_request_receiver,
         chain_config.state_keeper.block_chunk_sizes,
+        chain_config.mempool.admission_shards,
     );
     let forced_exit_task = run_forced_exit_requests_actors(
         connection_pool,
@@ -377,18 +470,35 @@ pub fn run_witness_generator(connection_pool: ConnectionPool) -> JoinHandle<()>
     run_prover_server(database, prover_api_config, prover_config)
 }
 
-pub fn run_eth_sender(connection_pool: ConnectionPool) -> JoinHandle<()> {
-    vlog::info!("Starting the Ethereum sender actors");
-    let eth_client_config = ETHClientConfig::from_env();
-    let eth_sender_config = ETHSenderConfig::from_env();
-    let contracts = ContractsConfig::from_env();
-    let eth_gateway = EthereumGateway::from_config(
-        &eth_client_config,
-        &eth_sender_config,
-        contracts.contract_addr,
-    );
+pub fn run_eth_sender(connection_pool: ConnectionPool, leadership: Leadership) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        leadership.wait_for_leadership().await;
+        vlog::info!("Starting the Ethereum sender actors");
+        let eth_client_config = ETHClientConfig::from_env();
+        let eth_sender_config = ETHSenderConfig::from_env();
+        let contracts = ContractsConfig::from_env();
+        let eth_gateway = EthereumGateway::from_config(
+            &eth_client_config,
+            &eth_sender_config,
+            contracts.contract_addr,
+        );
 
-    zksync_eth_sender::run_eth_sender(connection_pool, eth_gateway, eth_sender_config)
+        let mut tasks = Vec::new();
+        if eth_sender_config.withdrawal_hooks.is_enabled {
+            tasks.push(zksync_eth_sender::run_withdrawal_hook_sender(
+                connection_pool.clone(),
+                &eth_client_config,
+                contracts.contract_addr,
+                &eth_sender_config,
+            ));
+        }
+        tasks.push(zksync_eth_sender::run_eth_sender(
+            connection_pool,
+            eth_gateway,
+            eth_sender_config,
+        ));
+        wait_for_tasks(tasks).await;
+    })
 }
 
 pub fn run_price_updaters(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
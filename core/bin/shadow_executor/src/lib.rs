@@ -0,0 +1,325 @@
+//! Shadow execution of the blocks with the candidate release of the state transition code.
+//!
+//! The shadow executor follows the blocks sealed by the primary server, replays their operations
+//! on its own copy of the state and compares the resulting root hash, the set of the executed
+//! operations and the updated balances against the primary database. It only reads from the
+//! database, so operators can run the new release next to the primary one and validate the upgrade
+//! before switching.
+//!
+//! Operations are replayed in the order the primary executed them, including the failed ones,
+//! so that the transactions rejected by one release and accepted by the other are reported.
+//! Once a divergence is found, the shadow state is reloaded from the primary database to keep
+//! comparing the following blocks.
+
+// External uses
+use anyhow::Context;
+// Workspace uses
+use snapshot_diff::{diff_blocks, load_block_snapshot, BlockSnapshot, Divergence};
+use zksync_crypto::convert::FeConvert;
+use zksync_state::state::ZkSyncState;
+use zksync_storage::StorageProcessor;
+use zksync_types::{
+    block::{ExecutedOperations, ExecutedTx},
+    AccountId, AccountUpdate, BlockNumber, SignedZkSyncTx,
+};
+
+/// Block replayed by the shadow executor.
+#[derive(Debug)]
+pub struct ShadowBlock {
+    pub snapshot: BlockSnapshot,
+    /// Operations of the block with the outcome of the shadow execution.
+    pub executed_ops: Vec<ExecutedOperations>,
+}
+
+/// Replays the operations of the block executed by the primary server on the shadow state.
+/// Fees are collected to the fee account of the primary block.
+pub fn replay_block(
+    state: &mut ZkSyncState,
+    primary_ops: &[ExecutedOperations],
+    block_timestamp: u64,
+    fee_account_id: AccountId,
+) -> ShadowBlock {
+    let mut primary_ops = primary_ops.to_vec();
+    // Failed transactions are stored without the position in the block, so the execution order
+    // is restored from the creation time. The sort is stable, so the ties keep the block order.
+    primary_ops.sort_by_key(|op| match op {
+        ExecutedOperations::Tx(tx) => tx.created_at,
+        ExecutedOperations::PriorityOp(op) => op.created_at,
+    });
+
+    let mut executed_ops = Vec::with_capacity(primary_ops.len());
+    let mut updates: Vec<(AccountId, AccountUpdate)> = Vec::new();
+    let mut fees = Vec::new();
+    let mut block_index = 0;
+
+    let mut ops = primary_ops.into_iter().peekable();
+    while let Some(op) = ops.next() {
+        let tx = match op {
+            ExecutedOperations::PriorityOp(mut op) => {
+                let success = state.execute_priority_op(op.priority_op.data.clone());
+                updates.extend(success.updates);
+                op.op = success.executed_op;
+                op.block_index = block_index;
+                block_index += 1;
+                executed_ops.push(ExecutedOperations::PriorityOp(op));
+                continue;
+            }
+            ExecutedOperations::Tx(tx) => tx,
+        };
+
+        let batch_id = match tx.batch_id {
+            Some(batch_id) => batch_id,
+            None => {
+                let result = state.execute_tx(tx.signed_tx.tx.clone(), block_timestamp);
+                let tx = match result {
+                    Ok(success) => {
+                        updates.extend(success.updates);
+                        fees.extend(success.fee);
+                        block_index += 1;
+                        executed_tx(*tx, Ok(block_index - 1))
+                    }
+                    Err(err) => executed_tx(*tx, Err(err.to_string())),
+                };
+                executed_ops.push(ExecutedOperations::Tx(Box::new(tx)));
+                continue;
+            }
+        };
+
+        let mut batch = vec![*tx];
+        while let Some(ExecutedOperations::Tx(next)) = ops.peek() {
+            if next.batch_id != Some(batch_id) {
+                break;
+            }
+            match ops.next() {
+                Some(ExecutedOperations::Tx(next)) => batch.push(*next),
+                _ => unreachable!("the next operation is a transaction"),
+            }
+        }
+        let txs: Vec<SignedZkSyncTx> = batch.iter().map(|tx| tx.signed_tx.clone()).collect();
+        let results = state.execute_txs_batch(&txs, block_timestamp);
+        for (tx, result) in batch.into_iter().zip(results) {
+            let tx = match result {
+                Ok(success) => {
+                    updates.extend(success.updates);
+                    fees.extend(success.fee);
+                    block_index += 1;
+                    executed_tx(tx, Ok(block_index - 1))
+                }
+                Err(err) => executed_tx(tx, Err(err.to_string())),
+            };
+            executed_ops.push(ExecutedOperations::Tx(Box::new(tx)));
+        }
+    }
+
+    if !fees.is_empty() {
+        updates.extend(state.collect_fee(&fees, fee_account_id));
+    }
+
+    let snapshot = BlockSnapshot::new(state.root_hash().to_bytes(), &executed_ops, &updates, true);
+    ShadowBlock {
+        snapshot,
+        executed_ops,
+    }
+}
+
+/// Sets the outcome of the shadow execution: the position in the block or the failure reason.
+fn executed_tx(mut tx: ExecutedTx, result: Result<u32, String>) -> ExecutedTx {
+    match result {
+        Ok(block_index) => {
+            tx.success = true;
+            tx.block_index = Some(block_index);
+            tx.fail_reason = None;
+        }
+        Err(fail_reason) => {
+            tx.success = false;
+            tx.block_index = None;
+            tx.op = None;
+            tx.fail_reason = Some(fail_reason);
+        }
+    }
+    tx
+}
+
+/// Follows the blocks of the primary database and replays them on the shadow state.
+pub struct ShadowExecutor {
+    state: ZkSyncState,
+    /// Next block to be replayed.
+    next_block: BlockNumber,
+}
+
+impl ShadowExecutor {
+    /// Loads the state before the given block from the primary database.
+    pub async fn new(
+        storage: &mut StorageProcessor<'_>,
+        from_block: BlockNumber,
+    ) -> anyhow::Result<Self> {
+        let mut executor = Self {
+            state: ZkSyncState::empty(),
+            next_block: from_block,
+        };
+        executor.resync(storage).await?;
+        Ok(executor)
+    }
+
+    pub fn next_block(&self) -> BlockNumber {
+        self.next_block
+    }
+
+    /// Replaces the shadow state with the state of the primary database after the last replayed block.
+    async fn resync(&mut self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        let last_block = BlockNumber(self.next_block.saturating_sub(1));
+        let (state_block, accounts) = storage
+            .chain()
+            .state_schema()
+            .load_committed_state(Some(last_block))
+            .await?;
+        anyhow::ensure!(
+            state_block == last_block,
+            "state of the block {} is not available, the last one is {}",
+            last_block,
+            state_block
+        );
+        self.state = ZkSyncState::from_acc_map(accounts);
+        Ok(())
+    }
+
+    /// Replays the next block and compares it against the primary database.
+    /// Returns `None` if the primary server hasn't sealed the block yet.
+    pub async fn replay_next_block(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Option<Vec<Divergence>>> {
+        let block_number = self.next_block;
+        let block = match storage
+            .chain()
+            .block_schema()
+            .get_storage_block(block_number)
+            .await?
+        {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+        let primary = load_block_snapshot(storage, block_number, true)
+            .await?
+            .with_context(|| format!("block {} is removed during the replay", block_number))?;
+        let primary_ops = storage
+            .chain()
+            .block_schema()
+            .get_block_executed_ops(block_number)
+            .await?;
+
+        let shadow = replay_block(
+            &mut self.state,
+            &primary_ops,
+            block.timestamp.unwrap_or_default() as u64,
+            AccountId(block.fee_account_id as u32),
+        );
+        let divergences = diff_blocks(&primary, &shadow.snapshot);
+
+        self.next_block = block_number + 1;
+        if !divergences.is_empty() {
+            self.resync(storage).await?;
+        }
+        Ok(Some(divergences))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use num::BigUint;
+    use zksync_types::{
+        block::ExecutedPriorityOp, Address, Deposit, PriorityOp, TokenId, ZkSyncPriorityOp,
+    };
+
+    use super::*;
+
+    fn deposit(to: Address, amount: u64, created_at_secs: i64) -> ExecutedOperations {
+        let priority_op = PriorityOp {
+            serial_id: 0,
+            data: ZkSyncPriorityOp::Deposit(Deposit {
+                from: to,
+                to,
+                token: TokenId(0),
+                amount: BigUint::from(amount),
+            }),
+            deadline_block: 0,
+            eth_hash: Default::default(),
+            eth_block: 0,
+            eth_block_index: None,
+            l1_sender: None,
+        };
+        let mut state = ZkSyncState::empty();
+        let op = state
+            .execute_priority_op(priority_op.data.clone())
+            .executed_op;
+        ExecutedOperations::PriorityOp(Box::new(ExecutedPriorityOp {
+            priority_op,
+            op,
+            // Positions are restored by the replay.
+            block_index: 0,
+            created_at: Utc::now() + Duration::seconds(created_at_secs),
+        }))
+    }
+
+    #[test]
+    fn replays_in_execution_order() {
+        let (first, second) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        // Operations are loaded in the block order, the replay restores the execution order.
+        let primary_ops = vec![deposit(second, 20, 1), deposit(first, 10, 0)];
+
+        let mut state = ZkSyncState::empty();
+        let shadow = replay_block(&mut state, &primary_ops, 0, AccountId(0));
+        assert_eq!(state.get_account(AccountId(0)).unwrap().address, first);
+        assert_eq!(state.get_account(AccountId(1)).unwrap().address, second);
+
+        let block_indices: Vec<_> = shadow
+            .executed_ops
+            .iter()
+            .map(|op| match op {
+                ExecutedOperations::PriorityOp(op) => {
+                    (op.block_index, op.op.get_updated_account_ids())
+                }
+                ExecutedOperations::Tx(_) => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            block_indices,
+            vec![(0, vec![AccountId(0)]), (1, vec![AccountId(1)])]
+        );
+        assert_eq!(shadow.snapshot.root_hash, state.root_hash().to_bytes());
+        assert_eq!(
+            shadow.snapshot.balances.get(&(AccountId(1), TokenId(0))),
+            Some(&BigUint::from(20u64))
+        );
+    }
+
+    #[test]
+    fn reports_divergent_balances() {
+        let primary_ops = vec![deposit(Address::repeat_byte(1), 10, 0)];
+        let primary = replay_block(&mut ZkSyncState::empty(), &primary_ops, 0, AccountId(0));
+
+        // The shadow state is out of sync with the primary one.
+        let mut state = ZkSyncState::empty();
+        replay_block(
+            &mut state,
+            &[deposit(Address::repeat_byte(1), 5, 0)],
+            0,
+            AccountId(0),
+        );
+        let shadow = replay_block(&mut state, &primary_ops, 0, AccountId(0));
+
+        let divergences = diff_blocks(&primary.snapshot, &shadow.snapshot);
+        assert_eq!(divergences.len(), 2);
+        assert!(matches!(divergences[0], Divergence::RootHash { .. }));
+        assert_eq!(
+            divergences[1],
+            Divergence::Balance {
+                account_id: AccountId(0),
+                token: TokenId(0),
+                left: Some(BigUint::from(10u64)),
+                right: Some(BigUint::from(15u64)),
+            }
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use structopt::StructOpt;
+use zksync_storage::StorageProcessor;
+use zksync_types::BlockNumber;
+
+use shadow_executor::ShadowExecutor;
+use snapshot_diff::BlockDivergence;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync shadow executor", author = "Matter Labs")]
+#[structopt(
+    about = "Tool to replay the blocks of the primary server with the candidate release and report the divergences"
+)]
+struct Opt {
+    /// URL of the primary database, a read replica is preferred.
+    #[structopt(long, env = "DATABASE_URL")]
+    db_url: String,
+    /// First block to replay, the next block after the last saved one by default.
+    #[structopt(long)]
+    from_block: Option<u32>,
+    /// Last block to replay, new blocks are followed until the tool is stopped by default.
+    #[structopt(long)]
+    to_block: Option<u32>,
+    /// Interval of polling the new blocks, in milliseconds.
+    #[structopt(long, default_value = "1000")]
+    poll_interval: u64,
+    /// Stops at the first block with the divergences.
+    #[structopt(long)]
+    exit_on_divergence: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let _vlog_guard = vlog::init();
+    let opt = Opt::from_args();
+    let mut storage = StorageProcessor::establish_connection_to(&opt.db_url).await?;
+
+    let from_block = match opt.from_block {
+        Some(from_block) => BlockNumber(from_block),
+        None => {
+            storage
+                .chain()
+                .block_schema()
+                .get_last_saved_block()
+                .await?
+                + 1
+        }
+    };
+    let mut executor = ShadowExecutor::new(&mut storage, from_block).await?;
+    vlog::info!("Shadow execution is started from the block {}", from_block);
+
+    let mut diverged_blocks = 0;
+    while opt
+        .to_block
+        .map_or(true, |to_block| *executor.next_block() <= to_block)
+    {
+        let block_number = executor.next_block();
+        let divergences = match executor.replay_next_block(&mut storage).await? {
+            Some(divergences) => divergences,
+            // The block is not sealed yet.
+            None => {
+                tokio::time::sleep(Duration::from_millis(opt.poll_interval)).await;
+                continue;
+            }
+        };
+
+        if divergences.is_empty() {
+            vlog::info!("Block {} is consistent", block_number);
+            continue;
+        }
+        diverged_blocks += 1;
+        // The primary database is the left side of the comparison, the shadow execution is the right one.
+        for divergence in divergences {
+            vlog::error!(
+                "{}",
+                BlockDivergence {
+                    block_number,
+                    divergence
+                }
+            );
+        }
+        if opt.exit_on_divergence {
+            break;
+        }
+    }
+
+    anyhow::ensure!(
+        diverged_blocks == 0,
+        "{} blocks have diverged from the primary server",
+        diverged_blocks
+    );
+    println!("Shadow execution is consistent with the primary server");
+    Ok(())
+}
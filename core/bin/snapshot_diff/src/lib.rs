@@ -0,0 +1,390 @@
+//! Comparison of two zkSync databases block by block, e.g. the primary database and the one
+//! restored from L1 by the data restore tool, or the disaster recovery copy.
+//!
+//! For every block the root hash, the set of the successfully executed operations and the
+//! balances updated in the block are compared. Transactions restored from L1 don't have the
+//! signatures and the validity time ranges, so their hashes may differ from the original ones,
+//! and the operations can be compared by their position and type only.
+
+// Built-in uses
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+// External uses
+use num::BigUint;
+// Workspace uses
+use zksync_storage::StorageProcessor;
+use zksync_types::{
+    block::ExecutedOperations, tx::TxHash, AccountId, AccountUpdate, BlockNumber, TokenId,
+};
+
+/// Database the data was loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Left => write!(f, "left"),
+            Self::Right => write!(f, "right"),
+        }
+    }
+}
+
+/// Successfully executed operation of the block.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BlockOp {
+    pub block_index: u32,
+    /// Name of the operation type, e.g. `Transfer` or `Deposit`.
+    pub op_type: String,
+    /// Hash of the operation, `None` if the hashes are not compared.
+    pub tx_hash: Option<TxHash>,
+}
+
+impl fmt::Display for BlockOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} {}", self.block_index, self.op_type)?;
+        if let Some(tx_hash) = &self.tx_hash {
+            write!(f, " 0x{}", hex::encode(tx_hash))?;
+        }
+        Ok(())
+    }
+}
+
+/// Data of the block compared between the databases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockSnapshot {
+    pub root_hash: Vec<u8>,
+    pub ops: BTreeSet<BlockOp>,
+    /// Balances after the block, for the balances updated in the block only.
+    pub balances: BTreeMap<(AccountId, TokenId), BigUint>,
+}
+
+impl BlockSnapshot {
+    pub fn new(
+        root_hash: Vec<u8>,
+        executed_ops: &[ExecutedOperations],
+        updates: &[(AccountId, AccountUpdate)],
+        compare_tx_hashes: bool,
+    ) -> Self {
+        let ops = executed_ops
+            .iter()
+            .filter_map(|op| match op {
+                ExecutedOperations::Tx(tx) if tx.success => Some(BlockOp {
+                    block_index: tx.block_index?,
+                    op_type: tx.signed_tx.tx.variance_name(),
+                    tx_hash: Some(tx.signed_tx.tx.hash()).filter(|_| compare_tx_hashes),
+                }),
+                ExecutedOperations::Tx(_) => None,
+                ExecutedOperations::PriorityOp(op) => Some(BlockOp {
+                    block_index: op.block_index,
+                    op_type: op.priority_op.data.variance_name(),
+                    tx_hash: Some(op.priority_op.tx_hash()).filter(|_| compare_tx_hashes),
+                }),
+            })
+            .collect();
+
+        // Updates are applied in order, so the last one of the balance is its final value.
+        let mut balances = BTreeMap::new();
+        for (account_id, update) in updates {
+            if let AccountUpdate::UpdateBalance {
+                balance_update: (token, _, new_balance),
+                ..
+            } = update
+            {
+                balances.insert((*account_id, *token), new_balance.clone());
+            }
+        }
+
+        Self {
+            root_hash,
+            ops,
+            balances,
+        }
+    }
+}
+
+/// Difference of the block data between the databases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    /// Block exists in one database only.
+    MissingBlock {
+        missing_in: Side,
+    },
+    RootHash {
+        left: Vec<u8>,
+        right: Vec<u8>,
+    },
+    /// Operation was executed in the block of one database only.
+    Op {
+        op: BlockOp,
+        missing_in: Side,
+    },
+    /// Balance is different after the block, `None` means that it's not updated in the block.
+    Balance {
+        account_id: AccountId,
+        token: TokenId,
+        left: Option<BigUint>,
+        right: Option<BigUint>,
+    },
+}
+
+/// Divergence found in the block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockDivergence {
+    pub block_number: BlockNumber,
+    pub divergence: Divergence,
+}
+
+impl fmt::Display for BlockDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let balance = |balance: &Option<BigUint>| {
+            balance
+                .as_ref()
+                .map_or_else(|| "not updated".to_string(), ToString::to_string)
+        };
+        write!(f, "block {}: ", self.block_number)?;
+        match &self.divergence {
+            Divergence::MissingBlock { missing_in } => {
+                write!(f, "missing in the {} database", missing_in)
+            }
+            Divergence::RootHash { left, right } => write!(
+                f,
+                "root hash 0x{} != 0x{}",
+                hex::encode(left),
+                hex::encode(right)
+            ),
+            Divergence::Op { op, missing_in } => {
+                write!(
+                    f,
+                    "operation {} is missing in the {} database",
+                    op, missing_in
+                )
+            }
+            Divergence::Balance {
+                account_id,
+                token,
+                left,
+                right,
+            } => write!(
+                f,
+                "balance of the account {} in the token {}: {} != {}",
+                account_id,
+                token,
+                balance(left),
+                balance(right)
+            ),
+        }
+    }
+}
+
+/// Compares the data of the same block loaded from two databases.
+pub fn diff_blocks(left: &BlockSnapshot, right: &BlockSnapshot) -> Vec<Divergence> {
+    let mut divergences = Vec::new();
+    if left.root_hash != right.root_hash {
+        divergences.push(Divergence::RootHash {
+            left: left.root_hash.clone(),
+            right: right.root_hash.clone(),
+        });
+    }
+
+    for op in left.ops.difference(&right.ops) {
+        divergences.push(Divergence::Op {
+            op: op.clone(),
+            missing_in: Side::Right,
+        });
+    }
+    for op in right.ops.difference(&left.ops) {
+        divergences.push(Divergence::Op {
+            op: op.clone(),
+            missing_in: Side::Left,
+        });
+    }
+
+    let keys: BTreeSet<_> = left.balances.keys().chain(right.balances.keys()).collect();
+    for key in keys {
+        let (left_balance, right_balance) = (left.balances.get(key), right.balances.get(key));
+        if left_balance != right_balance {
+            divergences.push(Divergence::Balance {
+                account_id: key.0,
+                token: key.1,
+                left: left_balance.cloned(),
+                right: right_balance.cloned(),
+            });
+        }
+    }
+    divergences
+}
+
+/// Loads the data of the block to be compared, returns `None` if there is no such block.
+pub async fn load_block_snapshot(
+    storage: &mut StorageProcessor<'_>,
+    block_number: BlockNumber,
+    compare_tx_hashes: bool,
+) -> anyhow::Result<Option<BlockSnapshot>> {
+    let block = match storage
+        .chain()
+        .block_schema()
+        .get_storage_block(block_number)
+        .await?
+    {
+        Some(block) => block,
+        None => return Ok(None),
+    };
+    let executed_ops = storage
+        .chain()
+        .block_schema()
+        .get_block_executed_ops(block_number)
+        .await?;
+    let updates = storage
+        .chain()
+        .state_schema()
+        .load_state_diff_for_block(block_number)
+        .await?;
+
+    Ok(Some(BlockSnapshot::new(
+        block.root_hash,
+        &executed_ops,
+        &updates,
+        compare_tx_hashes,
+    )))
+}
+
+/// Compares the blocks in the given range, blocks after the last one of both databases
+/// are not compared. Returns the found divergences in the order of blocks.
+pub async fn diff_databases(
+    left: &mut StorageProcessor<'_>,
+    right: &mut StorageProcessor<'_>,
+    from_block: BlockNumber,
+    to_block: Option<BlockNumber>,
+    compare_tx_hashes: bool,
+) -> anyhow::Result<Vec<BlockDivergence>> {
+    let last_block = std::cmp::max(
+        left.chain().block_schema().get_last_saved_block().await?,
+        right.chain().block_schema().get_last_saved_block().await?,
+    );
+    let to_block = to_block.map_or(last_block, |to_block| to_block.min(last_block));
+
+    let mut divergences = Vec::new();
+    for block_number in *from_block..=*to_block {
+        let block_number = BlockNumber(block_number);
+        let left_block = load_block_snapshot(left, block_number, compare_tx_hashes).await?;
+        let right_block = load_block_snapshot(right, block_number, compare_tx_hashes).await?;
+        let block_divergences = match (left_block, right_block) {
+            (Some(left_block), Some(right_block)) => diff_blocks(&left_block, &right_block),
+            (Some(_), None) => vec![Divergence::MissingBlock {
+                missing_in: Side::Right,
+            }],
+            (None, Some(_)) => vec![Divergence::MissingBlock {
+                missing_in: Side::Left,
+            }],
+            (None, None) => Vec::new(),
+        };
+        divergences.extend(
+            block_divergences
+                .into_iter()
+                .map(|divergence| BlockDivergence {
+                    block_number,
+                    divergence,
+                }),
+        );
+    }
+    Ok(divergences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(block_index: u32, op_type: &str) -> BlockOp {
+        BlockOp {
+            block_index,
+            op_type: op_type.to_string(),
+            tx_hash: None,
+        }
+    }
+
+    fn snapshot(root_hash: u8, ops: Vec<BlockOp>, balances: Vec<(u32, u32, u64)>) -> BlockSnapshot {
+        BlockSnapshot {
+            root_hash: vec![root_hash; 32],
+            ops: ops.into_iter().collect(),
+            balances: balances
+                .into_iter()
+                .map(|(account_id, token, balance)| {
+                    (
+                        (AccountId(account_id), TokenId(token)),
+                        BigUint::from(balance),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn last_balance_update_is_compared() {
+        let update = |old: u64, new: u64| AccountUpdate::UpdateBalance {
+            old_nonce: Default::default(),
+            new_nonce: Default::default(),
+            balance_update: (TokenId(0), BigUint::from(old), BigUint::from(new)),
+        };
+        let block = BlockSnapshot::new(
+            vec![0; 32],
+            &[],
+            &[(AccountId(1), update(0, 10)), (AccountId(1), update(10, 7))],
+            true,
+        );
+        assert_eq!(
+            block.balances[&(AccountId(1), TokenId(0))],
+            BigUint::from(7u32)
+        );
+    }
+
+    #[test]
+    fn block_divergences() {
+        let left = snapshot(
+            1,
+            vec![op(0, "Deposit"), op(1, "Transfer")],
+            vec![(1, 0, 10)],
+        );
+        assert!(diff_blocks(&left, &left.clone()).is_empty());
+
+        let right = snapshot(2, vec![op(0, "Deposit")], vec![(1, 0, 9), (2, 0, 1)]);
+        let divergences = diff_blocks(&left, &right);
+        assert_eq!(
+            divergences,
+            vec![
+                Divergence::RootHash {
+                    left: vec![1; 32],
+                    right: vec![2; 32]
+                },
+                Divergence::Op {
+                    op: op(1, "Transfer"),
+                    missing_in: Side::Right
+                },
+                Divergence::Balance {
+                    account_id: AccountId(1),
+                    token: TokenId(0),
+                    left: Some(BigUint::from(10u32)),
+                    right: Some(BigUint::from(9u32)),
+                },
+                Divergence::Balance {
+                    account_id: AccountId(2),
+                    token: TokenId(0),
+                    left: None,
+                    right: Some(BigUint::from(1u32)),
+                },
+            ]
+        );
+
+        let report = BlockDivergence {
+            block_number: BlockNumber(5),
+            divergence: divergences[3].clone(),
+        };
+        assert_eq!(
+            report.to_string(),
+            "block 5: balance of the account 2 in the token 0: not updated != 1"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use structopt::StructOpt;
+use zksync_storage::StorageProcessor;
+use zksync_types::BlockNumber;
+
+use snapshot_diff::diff_databases;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync snapshot diff tool", author = "Matter Labs")]
+#[structopt(about = "Tool to compare two zkSync databases block by block")]
+struct Opt {
+    /// URL of the first database, the main one by default.
+    #[structopt(long, env = "DATABASE_URL")]
+    left_db_url: String,
+    /// URL of the second database, e.g. the one restored from L1.
+    #[structopt(long)]
+    right_db_url: String,
+    /// First block to compare.
+    #[structopt(long, default_value = "1")]
+    from_block: u32,
+    /// Last block to compare, the last block of both databases by default.
+    #[structopt(long)]
+    to_block: Option<u32>,
+    /// Compares the operations by their position and type only, which is required
+    /// for the databases restored from L1, since they lack the transaction signatures.
+    #[structopt(long)]
+    skip_tx_hashes: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+    let mut left = StorageProcessor::establish_connection_to(&opt.left_db_url).await?;
+    let mut right = StorageProcessor::establish_connection_to(&opt.right_db_url).await?;
+
+    let divergences = diff_databases(
+        &mut left,
+        &mut right,
+        BlockNumber(opt.from_block),
+        opt.to_block.map(BlockNumber),
+        !opt.skip_tx_hashes,
+    )
+    .await?;
+    for divergence in &divergences {
+        println!("{}", divergence);
+    }
+    anyhow::ensure!(
+        divergences.is_empty(),
+        "{} divergences are found",
+        divergences.len()
+    );
+    println!("Databases are consistent");
+    Ok(())
+}
@@ -0,0 +1,813 @@
+//! Command line tool for the zkSync operators.
+//!
+//! Queries are served directly from the database, while the actions changing the server
+//! state are performed via the core private API, so they are recorded to the audit log.
+
+use anyhow::{anyhow, Context};
+use chrono::Utc;
+use reqwest::Method;
+use serde::Serialize;
+use serde_json::json;
+use structopt::StructOpt;
+use zksync_api_types::{
+    private::{
+        AddAddressLabelRequest, AddCreate2FactoryRequest, AddToDenylistRequest,
+        ApiKeysMaxPageSizeRequest, BackfillProgress, ConsistencyCheckQuery, ConsistencyReport,
+        CpkSponsorshipsQuery, CpkSponsorshipsResponse, Create2FactoryEntry, DenylistEntry,
+        EnableMaintenanceModeRequest, ExecutionTracingRequest, ExecutionTracingStatus,
+        FeeFreeTransfersQuery, FeeFreeTransfersResponse, LabelApiKeyRequest, LeadershipStatus,
+        MaintenanceMode, PendingProverJob, PriorityBlockRequest, PriorityOpDeadlines,
+        PriorityOpDeadlinesQuery, ReassignProverJobsRequest, RemoveAddressLabelRequest,
+        RemoveCreate2FactoryRequest, RemoveFromDenylistRequest, RemoveRuntimeParamRequest,
+        RemoveTokenPriceOverrideRequest, RuntimeParam, RuntimeParamChange,
+        RuntimeParamChangesQuery, ScheduledJobStatus, ScreeningMatch, ScreeningMatchesQuery,
+        SetRuntimeParamRequest, SetTokenPriceOverrideRequest, TokenPriceOverride,
+    },
+    v02::{label::AddressLabelCategory, pagination::MAX_LIMIT},
+};
+use zksync_storage::{
+    migrations::{
+        backfill::{run_backfill, Backfill, CanonicalTxsBackfill, StoredTxsTable},
+        LockImpact, Migration,
+    },
+    ConnectionPool, StorageProcessor,
+};
+use zksync_types::{tx::TxHash, Address, BlockNumber, TokenLike, H256};
+use zksync_utils::UnsignedRatioSerializeAsDecimal;
+
+use crate::private_api::PrivateApiClient;
+
+mod private_api;
+
+#[derive(Debug, StructOpt)]
+enum MaintenanceCommand {
+    /// Shows whether the maintenance mode is enabled
+    Status,
+    /// Enables the maintenance mode, so the API servers stop accepting the transactions
+    Enable {
+        /// Reason of the maintenance, returned to the users submitting the transactions.
+        #[structopt(long)]
+        reason: String,
+    },
+    /// Disables the maintenance mode
+    Disable,
+}
+
+#[derive(Debug, StructOpt)]
+enum TracingCommand {
+    /// Shows whether the state keeper emits the execution traces of the blocks
+    Status,
+    /// Enables the execution traces of the sealed blocks, logged with the `execution_trace` target
+    Enable,
+    /// Disables the execution traces of the sealed blocks
+    Disable,
+}
+
+#[derive(Debug, StructOpt)]
+enum ProverCommand {
+    /// Lists the prover jobs which are not completed yet in the order they will be served
+    Jobs,
+    /// Returns the jobs taken by the (possibly stalled) prover back to the queue
+    Requeue {
+        #[structopt(long)]
+        prover_name: String,
+    },
+    /// Marks the block as a high priority one, so the jobs containing it are served first
+    Prioritize {
+        #[structopt(long)]
+        block_number: u32,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+enum LabelsCommand {
+    /// Adds the label visible to everyone
+    Add {
+        #[structopt(long)]
+        address: Address,
+        #[structopt(long)]
+        label: String,
+        /// Either `exchangeHotWallet` or `knownContract`.
+        #[structopt(long)]
+        category: AddressLabelCategory,
+    },
+    /// Removes the label visible to everyone
+    Remove {
+        #[structopt(long)]
+        address: Address,
+        #[structopt(long)]
+        label: String,
+    },
+    /// Issues the API key allowing to manage the user-defined labels of the scope
+    IssueKey {
+        #[structopt(long)]
+        scope: String,
+    },
+    /// Revokes all the API keys of the scope
+    RevokeKeys {
+        #[structopt(long)]
+        scope: String,
+    },
+    /// Overrides the maximum page size of the REST API for the API keys of the scope
+    SetKeysMaxPageSize {
+        #[structopt(long)]
+        scope: String,
+        /// Restores the server limits if omitted.
+        #[structopt(long)]
+        max_page_size: Option<u32>,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+enum ScreeningCommand {
+    /// Lists the addresses denied by the operator
+    Denylist,
+    /// Denies the transactions from and to the address
+    Deny {
+        #[structopt(long)]
+        address: Address,
+        /// Reason returned to the users of the denied address.
+        #[structopt(long)]
+        reason: String,
+    },
+    /// Allows the transactions from and to the denied address again
+    Allow {
+        #[structopt(long)]
+        address: Address,
+    },
+    /// Lists the transactions rejected by the screening, newest first
+    Matches {
+        /// Only the matches of this address are listed.
+        #[structopt(long)]
+        address: Option<Address>,
+        /// Only the matches stored before the one with this id are listed.
+        #[structopt(long)]
+        before: Option<i64>,
+        #[structopt(long, default_value = "100")]
+        limit: u32,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+enum RuntimeConfigCommand {
+    /// Lists the parameters overridden at runtime
+    Show,
+    /// Overrides the reload-safe parameter, the servers apply it within a few seconds
+    Set {
+        #[structopt(long)]
+        key: String,
+        /// Value in the JSON format, e.g. `150` or `"zksync_core=debug"`.
+        #[structopt(long)]
+        value: String,
+    },
+    /// Restores the value of the parameter from the environment config
+    Remove {
+        #[structopt(long)]
+        key: String,
+    },
+    /// Lists the changes of the runtime parameters, newest first
+    Changes {
+        /// Only the changes recorded before the one with this id are listed.
+        #[structopt(long)]
+        before: Option<i64>,
+        #[structopt(long, default_value = "100")]
+        limit: u32,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+enum PriceOverridesCommand {
+    /// Lists the manual token prices, including the expired ones
+    List,
+    /// Sets the manual token price used by the fee ticker instead of the fetched one
+    Set {
+        /// Token id, address or symbol.
+        #[structopt(long)]
+        token: String,
+        /// Price in USD, e.g. `1.05`.
+        #[structopt(long)]
+        usd_price: String,
+        #[structopt(long)]
+        reason: String,
+        /// The fetched price is used again after this amount of minutes.
+        #[structopt(long, default_value = "60")]
+        expires_in_mins: i64,
+    },
+    /// Removes the manual token price before it expires
+    Remove {
+        /// Token id, address or symbol.
+        #[structopt(long)]
+        token: String,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+enum Create2FactoriesCommand {
+    /// Lists the registered factories
+    List,
+    /// Registers the factory and code hash pair, or renames the registered one
+    Add {
+        #[structopt(long)]
+        creator_address: Address,
+        #[structopt(long)]
+        code_hash: H256,
+        /// Name of the wallet vendor.
+        #[structopt(long)]
+        name: String,
+    },
+    /// Removes the factory and code hash pair
+    Remove {
+        #[structopt(long)]
+        creator_address: Address,
+        #[structopt(long)]
+        code_hash: H256,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Shows the block details
+    Block { block_number: u32 },
+    /// Shows the executed or the pending transaction
+    Tx { tx_hash: String },
+    /// Manages the maintenance mode
+    Maintenance(MaintenanceCommand),
+    /// Lists the priority operations received from L1 which are not executed for too long
+    StuckPriorityOps {
+        /// Operations received earlier than this amount of minutes ago are listed.
+        #[structopt(long, default_value = "30")]
+        older_than_mins: i64,
+    },
+    /// Lists the priority operations which are not executed yet along with the time left until their deadlines
+    PriorityOpDeadlines {
+        #[structopt(long, default_value = "100")]
+        limit: u32,
+    },
+    /// Manages the prover jobs
+    Prover(ProverCommand),
+    /// Checks the invariants of the stored data
+    Consistency {
+        /// Blocks starting from this one up to the last verified block are checked.
+        #[structopt(long, default_value = "1")]
+        from_block: u32,
+        /// Maximum amount of the reported violations of each invariant.
+        #[structopt(long, default_value = "100")]
+        limit: u32,
+    },
+    /// Lists the ChangePubKey transactions with the fee paid by the operator, newest first
+    CpkSponsorships {
+        /// Only the sponsorships stored before the one with this id are listed.
+        #[structopt(long)]
+        before: Option<i64>,
+        #[structopt(long, default_value = "100")]
+        limit: u32,
+    },
+    /// Lists the transfers of the operator addresses with the fee waived, newest first
+    FeeFreeTransfers {
+        /// Only the transfers of this address are listed, along with its quota used today.
+        #[structopt(long)]
+        address: Option<Address>,
+        /// Only the transfers stored before the one with this id are listed.
+        #[structopt(long)]
+        before: Option<i64>,
+        #[structopt(long, default_value = "100")]
+        limit: u32,
+    },
+    /// Manages the address labels shown in the explorer
+    Labels(LabelsCommand),
+    /// Manages the screening of the transactions on the submission
+    Screening(ScreeningCommand),
+    /// Applies the pending migrations to the copy of the database and rolls them back,
+    /// reporting the execution time and the locks taken by every migration
+    MigrationsDryRun {
+        /// URL of the database copy, the main database must never be used.
+        #[structopt(long)]
+        database_url: String,
+        #[structopt(long, default_value = "core/lib/storage/migrations")]
+        migrations_dir: std::path::PathBuf,
+    },
+    /// Shows the progress of the online data migrations
+    Backfills,
+    /// Shows the health of the periodic background jobs
+    Jobs,
+    /// Shows the role of the core server instance and the recent leadership changes
+    Leadership,
+    /// Manages the execution tracing of the state keeper of the core server instance
+    Tracing(TracingCommand),
+    /// Manages the parameters overridden at runtime without restarting the servers
+    RuntimeConfig(RuntimeConfigCommand),
+    /// Manages the manual token prices used by the fee ticker while the price sources misbehave
+    PriceOverrides(PriceOverridesCommand),
+    /// Manages the CREATE2 factories of the smart wallets allowed to set their signing keys
+    Create2Factories(Create2FactoriesCommand),
+    /// Rewrites the stored transactions to the canonical JSON form,
+    /// the interrupted run continues from the last processed batch
+    CanonicalizeTxs {
+        #[structopt(long, default_value = "1000")]
+        batch_size: u32,
+        /// Delay between the batches in milliseconds.
+        #[structopt(long, default_value = "100")]
+        batch_delay_ms: u64,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync admin tool", author = "Matter Labs")]
+#[structopt(about = "Tool for querying and maintaining the zkSync server")]
+struct Opt {
+    /// URL of the core private API.
+    #[structopt(long, env = "API_PRIVATE_URL", default_value = "http://127.0.0.1:8090")]
+    private_api_url: String,
+    /// Operator performing the actions, recorded to the audit log.
+    #[structopt(long, env = "ZKSYNC_ADMIN_ACTOR")]
+    actor: Option<String>,
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+fn print_json(value: &impl Serialize) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+async fn show_block(storage: &mut StorageProcessor<'_>, block_number: u32) -> anyhow::Result<()> {
+    let block = storage
+        .chain()
+        .block_schema()
+        .load_block_range_desc(BlockNumber(block_number), 1)
+        .await?
+        .into_iter()
+        .find(|block| block.block_number == block_number as i64)
+        .ok_or_else(|| anyhow!("Block {} is not found", block_number))?;
+    print_json(&block)
+}
+
+async fn show_tx(storage: &mut StorageProcessor<'_>, tx_hash: &str) -> anyhow::Result<()> {
+    // Priority operations are identified by the hashes of their L1 transactions.
+    let hash = tx_hash
+        .trim_start_matches("sync-tx:")
+        .trim_start_matches("0x");
+    let hash = hex::decode(hash).context("Invalid transaction hash")?;
+
+    let tx = storage
+        .chain()
+        .operations_ext_schema()
+        .get_tx_by_hash(&hash)
+        .await?;
+    if let Some(tx) = tx {
+        return print_json(&tx);
+    }
+
+    let pending_tx = match TxHash::from_slice(&hash) {
+        Some(tx_hash) => {
+            storage
+                .chain()
+                .mempool_schema()
+                .get_tx(tx_hash.as_ref())
+                .await?
+        }
+        None => None,
+    };
+    match pending_tx {
+        Some(tx) => print_json(&json!({ "status": "pending", "tx": tx.tx })),
+        None => Err(anyhow!("Transaction {} is not found", tx_hash)),
+    }
+}
+
+async fn show_stuck_priority_ops(
+    storage: &mut StorageProcessor<'_>,
+    older_than_mins: i64,
+) -> anyhow::Result<()> {
+    let received_before = Utc::now() - chrono::Duration::minutes(older_than_mins);
+    let ops: Vec<_> = storage
+        .chain()
+        .mempool_schema()
+        .get_stuck_priority_ops(received_before)
+        .await?
+        .into_iter()
+        .map(|(op, received_at)| {
+            json!({
+                "serial_id": op.serial_id,
+                "type": op.data.variance_name(),
+                "eth_hash": op.eth_hash,
+                "eth_block": op.eth_block,
+                "received_at": received_at,
+            })
+        })
+        .collect();
+    print_json(&ops)
+}
+
+async fn migrations_dry_run(
+    storage: &mut StorageProcessor<'_>,
+    migrations_dir: &std::path::Path,
+) -> anyhow::Result<()> {
+    let migrations = Migration::load_dir(migrations_dir)?;
+    let reports = storage.migrations_schema().dry_run(&migrations).await?;
+    let failed = reports.iter().any(|report| report.error.is_some());
+    let reports: Vec<_> = reports
+        .into_iter()
+        .map(|report| {
+            let impact = match report.impact() {
+                LockImpact::None => "none",
+                LockImpact::BlocksWrites => "blocks_writes",
+                LockImpact::BlocksReads => "blocks_reads",
+            };
+            let locks: Vec<_> = report
+                .locks
+                .iter()
+                .map(|lock| json!({ "relation": lock.relation, "mode": lock.mode }))
+                .collect();
+            json!({
+                "migration": report.name,
+                "duration_ms": report.duration.as_millis() as u64,
+                "impact": impact,
+                "locks": locks,
+                "error": report.error,
+            })
+        })
+        .collect();
+    print_json(&reports)?;
+    if failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn run_maintenance_command(
+    api: &PrivateApiClient,
+    command: MaintenanceCommand,
+) -> anyhow::Result<()> {
+    match command {
+        MaintenanceCommand::Status => {
+            let maintenance_mode: Option<MaintenanceMode> = api.get("maintenance", &()).await?;
+            match maintenance_mode {
+                Some(maintenance_mode) => print_json(&maintenance_mode)?,
+                None => println!("Maintenance mode is disabled"),
+            }
+        }
+        MaintenanceCommand::Enable { reason } => {
+            let request = EnableMaintenanceModeRequest { reason };
+            api.act(Method::POST, "maintenance", Some(&request)).await?;
+            println!("Maintenance mode is enabled");
+        }
+        MaintenanceCommand::Disable => {
+            api.act(Method::DELETE, "maintenance", None::<&()>).await?;
+            println!("Maintenance mode is disabled");
+        }
+    }
+    Ok(())
+}
+
+async fn run_tracing_command(
+    api: &PrivateApiClient,
+    command: TracingCommand,
+) -> anyhow::Result<()> {
+    let enabled = match command {
+        TracingCommand::Status => {
+            let status: ExecutionTracingStatus = api.get("state_keeper/tracing", &()).await?;
+            return print_json(&status);
+        }
+        TracingCommand::Enable => true,
+        TracingCommand::Disable => false,
+    };
+    let request = ExecutionTracingRequest { enabled };
+    let response = api
+        .act(Method::PUT, "state_keeper/tracing", Some(&request))
+        .await?;
+    println!("{}", response);
+    Ok(())
+}
+
+async fn run_prover_command(api: &PrivateApiClient, command: ProverCommand) -> anyhow::Result<()> {
+    match command {
+        ProverCommand::Jobs => {
+            let jobs: Vec<PendingProverJob> = api.get("prover/jobs", &()).await?;
+            print_json(&jobs)?;
+        }
+        ProverCommand::Requeue { prover_name } => {
+            let request = ReassignProverJobsRequest { prover_name };
+            let response = api
+                .act(Method::POST, "prover/reassign", Some(&request))
+                .await?;
+            println!("{}", response);
+        }
+        ProverCommand::Prioritize { block_number } => {
+            let request = PriorityBlockRequest {
+                block_number: BlockNumber(block_number),
+            };
+            api.act(Method::POST, "prover/priority_blocks", Some(&request))
+                .await?;
+            println!("Block {} is marked as high priority", block_number);
+        }
+    }
+    Ok(())
+}
+
+async fn run_labels_command(api: &PrivateApiClient, command: LabelsCommand) -> anyhow::Result<()> {
+    match command {
+        LabelsCommand::Add {
+            address,
+            label,
+            category,
+        } => {
+            let request = AddAddressLabelRequest {
+                address,
+                label,
+                category,
+            };
+            api.act(Method::POST, "labels", Some(&request)).await?;
+            println!("Label is added");
+        }
+        LabelsCommand::Remove { address, label } => {
+            let request = RemoveAddressLabelRequest { address, label };
+            api.act(Method::DELETE, "labels", Some(&request)).await?;
+            println!("Label is removed");
+        }
+        LabelsCommand::IssueKey { scope } => {
+            let request = LabelApiKeyRequest { scope };
+            let response = api
+                .act(Method::POST, "labels/api_keys", Some(&request))
+                .await?;
+            println!("{}", response);
+        }
+        LabelsCommand::RevokeKeys { scope } => {
+            let request = LabelApiKeyRequest { scope };
+            let response = api
+                .act(Method::DELETE, "labels/api_keys", Some(&request))
+                .await?;
+            println!("{}", response);
+        }
+        LabelsCommand::SetKeysMaxPageSize {
+            scope,
+            max_page_size,
+        } => {
+            let request = ApiKeysMaxPageSizeRequest {
+                scope,
+                max_page_size,
+            };
+            let response = api
+                .act(Method::PUT, "labels/api_keys/max_page_size", Some(&request))
+                .await?;
+            println!("{}", response);
+        }
+    }
+    Ok(())
+}
+
+async fn run_screening_command(
+    api: &PrivateApiClient,
+    command: ScreeningCommand,
+) -> anyhow::Result<()> {
+    match command {
+        ScreeningCommand::Denylist => {
+            let denylist: Vec<DenylistEntry> = api.get("screening/denylist", &()).await?;
+            print_json(&denylist)?;
+        }
+        ScreeningCommand::Deny { address, reason } => {
+            let request = AddToDenylistRequest { address, reason };
+            api.act(Method::POST, "screening/denylist", Some(&request))
+                .await?;
+            println!("Address is denied");
+        }
+        ScreeningCommand::Allow { address } => {
+            let request = RemoveFromDenylistRequest { address };
+            api.act(Method::DELETE, "screening/denylist", Some(&request))
+                .await?;
+            println!("Address is allowed");
+        }
+        ScreeningCommand::Matches {
+            address,
+            before,
+            limit,
+        } => {
+            let query = ScreeningMatchesQuery {
+                address,
+                before,
+                limit: limit.min(MAX_LIMIT),
+            };
+            let matches: Vec<ScreeningMatch> = api.get("screening/matches", &query).await?;
+            print_json(&matches)?;
+        }
+    }
+    Ok(())
+}
+
+async fn run_runtime_config_command(
+    api: &PrivateApiClient,
+    command: RuntimeConfigCommand,
+) -> anyhow::Result<()> {
+    match command {
+        RuntimeConfigCommand::Show => {
+            let params: Vec<RuntimeParam> = api.get("runtime_config", &()).await?;
+            print_json(&params)?;
+        }
+        RuntimeConfigCommand::Set { key, value } => {
+            let value = serde_json::from_str(&value).context("value must be a valid JSON")?;
+            let request = SetRuntimeParamRequest { key, value };
+            api.act(Method::POST, "runtime_config", Some(&request))
+                .await?;
+            println!("Parameter is overridden");
+        }
+        RuntimeConfigCommand::Remove { key } => {
+            let request = RemoveRuntimeParamRequest { key };
+            api.act(Method::DELETE, "runtime_config", Some(&request))
+                .await?;
+            println!("Parameter is restored from the config");
+        }
+        RuntimeConfigCommand::Changes { before, limit } => {
+            let query = RuntimeParamChangesQuery {
+                before,
+                limit: limit.min(MAX_LIMIT),
+            };
+            let changes: Vec<RuntimeParamChange> =
+                api.get("runtime_config/changes", &query).await?;
+            print_json(&changes)?;
+        }
+    }
+    Ok(())
+}
+
+async fn run_price_overrides_command(
+    api: &PrivateApiClient,
+    command: PriceOverridesCommand,
+) -> anyhow::Result<()> {
+    match command {
+        PriceOverridesCommand::List => {
+            let overrides: Vec<TokenPriceOverride> = api.get("ticker/price_overrides", &()).await?;
+            print_json(&overrides)?;
+        }
+        PriceOverridesCommand::Set {
+            token,
+            usd_price,
+            reason,
+            expires_in_mins,
+        } => {
+            let request = SetTokenPriceOverrideRequest {
+                token: TokenLike::parse(&token),
+                usd_price: UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(
+                    &usd_price,
+                )
+                .context("price must be a non-negative decimal")?,
+                reason,
+                expires_at: Utc::now() + chrono::Duration::minutes(expires_in_mins),
+            };
+            api.act(Method::POST, "ticker/price_overrides", Some(&request))
+                .await?;
+            println!("Token price is overridden until {}", request.expires_at);
+        }
+        PriceOverridesCommand::Remove { token } => {
+            let request = RemoveTokenPriceOverrideRequest {
+                token: TokenLike::parse(&token),
+            };
+            api.act(Method::DELETE, "ticker/price_overrides", Some(&request))
+                .await?;
+            println!("Token price override is removed");
+        }
+    }
+    Ok(())
+}
+
+async fn run_create2_factories_command(
+    api: &PrivateApiClient,
+    command: Create2FactoriesCommand,
+) -> anyhow::Result<()> {
+    match command {
+        Create2FactoriesCommand::List => {
+            let factories: Vec<Create2FactoryEntry> = api.get("create2_factories", &()).await?;
+            print_json(&factories)?;
+        }
+        Create2FactoriesCommand::Add {
+            creator_address,
+            code_hash,
+            name,
+        } => {
+            let request = AddCreate2FactoryRequest {
+                creator_address,
+                code_hash,
+                name,
+            };
+            api.act(Method::POST, "create2_factories", Some(&request))
+                .await?;
+            println!("CREATE2 factory is registered");
+        }
+        Create2FactoriesCommand::Remove {
+            creator_address,
+            code_hash,
+        } => {
+            let request = RemoveCreate2FactoryRequest {
+                creator_address,
+                code_hash,
+            };
+            api.act(Method::DELETE, "create2_factories", Some(&request))
+                .await?;
+            println!("CREATE2 factory is removed");
+        }
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+    let api = PrivateApiClient::new(opt.private_api_url, opt.actor);
+
+    match opt.command {
+        Command::Block { block_number } => {
+            let mut storage = StorageProcessor::establish_connection().await?;
+            show_block(&mut storage, block_number).await?;
+        }
+        Command::Tx { tx_hash } => {
+            let mut storage = StorageProcessor::establish_connection().await?;
+            show_tx(&mut storage, &tx_hash).await?;
+        }
+        Command::StuckPriorityOps { older_than_mins } => {
+            let mut storage = StorageProcessor::establish_connection().await?;
+            show_stuck_priority_ops(&mut storage, older_than_mins).await?;
+        }
+        Command::PriorityOpDeadlines { limit } => {
+            let query = PriorityOpDeadlinesQuery {
+                limit: limit.min(MAX_LIMIT),
+            };
+            let deadlines: PriorityOpDeadlines = api.get("priority_ops/deadlines", &query).await?;
+            print_json(&deadlines)?;
+        }
+        Command::Maintenance(command) => run_maintenance_command(&api, command).await?,
+        Command::Prover(command) => run_prover_command(&api, command).await?,
+        Command::Consistency { from_block, limit } => {
+            let query = ConsistencyCheckQuery {
+                from_block: BlockNumber(from_block),
+                limit: limit.min(MAX_LIMIT),
+            };
+            let report: ConsistencyReport = api.get("consistency", &query).await?;
+            print_json(&report)?;
+            if !report.violations.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::CpkSponsorships { before, limit } => {
+            let query = CpkSponsorshipsQuery {
+                before,
+                limit: limit.min(MAX_LIMIT),
+            };
+            let sponsorships: CpkSponsorshipsResponse = api.get("cpk_sponsorships", &query).await?;
+            print_json(&sponsorships)?;
+        }
+        Command::FeeFreeTransfers {
+            address,
+            before,
+            limit,
+        } => {
+            let query = FeeFreeTransfersQuery {
+                address,
+                before,
+                limit: limit.min(MAX_LIMIT),
+            };
+            let transfers: FeeFreeTransfersResponse = api.get("fee_free_transfers", &query).await?;
+            print_json(&transfers)?;
+        }
+        Command::Labels(command) => run_labels_command(&api, command).await?,
+        Command::Screening(command) => run_screening_command(&api, command).await?,
+        Command::MigrationsDryRun {
+            database_url,
+            migrations_dir,
+        } => {
+            let mut storage = StorageProcessor::establish_connection_to(&database_url).await?;
+            migrations_dry_run(&mut storage, &migrations_dir).await?;
+        }
+        Command::Backfills => {
+            let backfills: Vec<BackfillProgress> = api.get("backfills", &()).await?;
+            print_json(&backfills)?;
+        }
+        Command::Jobs => {
+            let jobs: Vec<ScheduledJobStatus> = api.get("jobs", &()).await?;
+            print_json(&jobs)?;
+        }
+        Command::Leadership => {
+            let leadership: LeadershipStatus = api.get("leadership", &()).await?;
+            print_json(&leadership)?;
+        }
+        Command::Tracing(command) => run_tracing_command(&api, command).await?,
+        Command::RuntimeConfig(command) => run_runtime_config_command(&api, command).await?,
+        Command::PriceOverrides(command) => run_price_overrides_command(&api, command).await?,
+        Command::Create2Factories(command) => run_create2_factories_command(&api, command).await?,
+        Command::CanonicalizeTxs {
+            batch_size,
+            batch_delay_ms,
+        } => {
+            let pool = ConnectionPool::new(Some(1));
+            let batch_delay = std::time::Duration::from_millis(batch_delay_ms);
+            for table in [
+                StoredTxsTable::ExecutedTransactions,
+                StoredTxsTable::MempoolTxs,
+            ]
+            .iter()
+            {
+                let backfill = CanonicalTxsBackfill(*table);
+                run_backfill(&pool, &backfill, batch_size, batch_delay).await?;
+                println!("Backfill {} is finished", backfill.name());
+            }
+        }
+    }
+    Ok(())
+}
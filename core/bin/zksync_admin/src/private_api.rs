@@ -0,0 +1,80 @@
+//! Client of the core private API.
+//!
+//! All the actions changing the server state are performed via the private API,
+//! so they are recorded to the audit log.
+
+use anyhow::{bail, Context};
+use reqwest::{Method, RequestBuilder};
+use serde::{de::DeserializeOwned, Serialize};
+use zksync_api_types::private::ACTOR_HEADER;
+
+#[derive(Debug)]
+pub struct PrivateApiClient {
+    url: String,
+    /// Operator performing the actions.
+    actor: Option<String>,
+    client: reqwest::Client,
+}
+
+impl PrivateApiClient {
+    pub fn new(url: impl Into<String>, actor: Option<String>) -> Self {
+        Self {
+            url: url.into(),
+            actor,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn get<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        query: &impl Serialize,
+    ) -> anyhow::Result<T> {
+        let request = self.request(Method::GET, endpoint).query(query);
+        let response = Self::send(request).await?;
+        response
+            .json()
+            .await
+            .with_context(|| format!("Malformed response of {}", endpoint))
+    }
+
+    /// Performs the action changing the server state, returns the response body.
+    pub async fn act(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: Option<&impl Serialize>,
+    ) -> anyhow::Result<String> {
+        let actor = match &self.actor {
+            Some(actor) => actor,
+            None => bail!("Operator must be specified with --actor to change the server state"),
+        };
+
+        let mut request = self
+            .request(method, endpoint)
+            .header(ACTOR_HEADER, actor.as_str());
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let response = Self::send(request).await?;
+        Ok(response.text().await?)
+    }
+
+    fn request(&self, method: Method, endpoint: &str) -> RequestBuilder {
+        let url = format!("{}/{}", self.url.trim_end_matches('/'), endpoint);
+        self.client.request(method, &url)
+    }
+
+    async fn send(request: RequestBuilder) -> anyhow::Result<reqwest::Response> {
+        let response = request
+            .send()
+            .await
+            .context("Private API is not available")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("Private API responded with {}: {}", status, body);
+        }
+        Ok(response)
+    }
+}
@@ -13,6 +13,7 @@ fn generate_transactions(number: usize) -> BatchFeeRequest {
         transactions.push(TxInBatchFeeRequest {
             tx_type: ApiTxFeeTypes::Withdraw,
             address: Address::random(),
+            withdrawal_hook: false,
         });
     }
     BatchFeeRequest {
@@ -35,6 +36,7 @@ fn get_txs_fee(client: Client, url: String) {
         tx_type: ApiTxFeeTypes::Withdraw,
         address: Address::random(),
         token_like: TokenLike::Id(TokenId(2)), // id of wBTC on localhost
+        withdrawal_hook: false,
     };
 
     let res = client
@@ -0,0 +1,7 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Server part of the gRPC API, see `api_server::grpc`.
+    tonic_build::configure()
+        .build_client(false)
+        .compile(&["proto/zksync.proto"], &["proto"])?;
+    Ok(())
+}
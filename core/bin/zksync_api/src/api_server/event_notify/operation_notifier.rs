@@ -1,8 +1,9 @@
 use crate::api_server::rpc_server::types::{
-    BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+    ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
 };
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
 use std::time::{Duration, Instant};
+use zksync_api_types::tx_status::TxStatus;
 use zksync_storage::ConnectionPool;
 use zksync_types::aggregated_operations::AggregatedOperation;
 use zksync_types::tx::TxHash;
@@ -133,28 +134,19 @@ impl OperationNotifier {
             match tx {
                 ExecutedOperations::Tx(tx) => {
                     let hash = tx.signed_tx.hash();
-                    let resp = TransactionInfoResp {
-                        executed: true,
-                        success: Some(tx.success),
-                        fail_reason: tx.fail_reason,
-                        block: Some(BlockInfo {
-                            block_number: i64::from(*block_number),
-                            committed: true,
-                            verified: action == ActionType::VERIFY,
-                        }),
-                    };
+                    let resp = TransactionInfoResp::new(
+                        TxStatus::executed(tx.success, action == ActionType::VERIFY),
+                        Some(i64::from(*block_number)),
+                        tx.fail_reason,
+                    );
                     self.tx_subs.notify(hash, action, resp);
                 }
                 ExecutedOperations::PriorityOp(prior_op) => {
                     let id = prior_op.priority_op.serial_id;
-                    let resp = ETHOpInfoResp {
-                        executed: true,
-                        block: Some(BlockInfo {
-                            block_number: i64::from(*block_number),
-                            committed: true,
-                            verified: action == ActionType::VERIFY,
-                        }),
-                    };
+                    let resp = ETHOpInfoResp::new(
+                        TxStatus::executed(true, action == ActionType::VERIFY),
+                        Some(i64::from(*block_number)),
+                    );
                     self.prior_op_subs.notify(PriorityOpId(id), action, resp);
                 }
             }
@@ -248,23 +240,16 @@ impl OperationNotifier {
         let tx_receipt = self.state.get_tx_receipt(&hash).await?;
 
         if let Some(receipt) = tx_receipt {
-            let tx_info_resp = TransactionInfoResp {
-                executed: true,
-                success: Some(receipt.success),
-                fail_reason: receipt.fail_reason,
-                block: Some(BlockInfo {
-                    block_number: receipt.block_number,
-                    committed: receipt.success,
-                    verified: receipt.verified,
-                }),
-            };
+            let status = receipt.status();
+            let tx_info_resp =
+                TransactionInfoResp::new(status, Some(receipt.block_number), receipt.fail_reason);
             match action {
                 ActionType::COMMIT => {
                     self.tx_subs.respond_once(sub_id, sub, tx_info_resp)?;
                     return Ok(());
                 }
                 ActionType::VERIFY => {
-                    if receipt.verified {
+                    if status.is_verified() {
                         self.tx_subs.respond_once(sub_id, sub, tx_info_resp)?;
                         return Ok(());
                     }
@@ -0,0 +1,275 @@
+//! gRPC server for the internal services, e.g. the matching engines and the risk systems.
+//!
+//! Exposes the read paths of the REST API v0.2 (receipts, account states and blocks) with
+//! the protobuf schemas from `proto/zksync.proto`. The server has no authentication, so it
+//! should be reachable from the internal network only.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use tokio::{runtime::Runtime, task::JoinHandle};
+use tonic::{transport::Server, Request, Response, Status};
+
+// Workspace uses
+use zksync_api_types::v02::transaction::{Receipt, TxInBlockStatus};
+use zksync_config::configs::api::GrpcConfig;
+use zksync_storage::{
+    chain::block::records::StorageBlockDetails, ConnectionPool, StorageProcessor,
+};
+use zksync_types::{Account, Address, BlockNumber};
+use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
+
+// Local uses
+use self::proto::{
+    zk_sync_server::{ZkSync, ZkSyncServer},
+    GetAccountRequest, GetAccountResponse, GetBlockRequest, GetBlockResponse, GetReceiptRequest,
+    GetReceiptResponse,
+};
+
+pub mod proto {
+    tonic::include_proto!("zksync.v1");
+}
+
+fn tx_status(status: TxInBlockStatus) -> proto::TxStatus {
+    match status {
+        TxInBlockStatus::Queued => proto::TxStatus::Queued,
+        TxInBlockStatus::Committed => proto::TxStatus::Committed,
+        TxInBlockStatus::Finalized => proto::TxStatus::Finalized,
+        TxInBlockStatus::Rejected => proto::TxStatus::Rejected,
+        TxInBlockStatus::Pruned => proto::TxStatus::Pruned,
+        TxInBlockStatus::Expired => proto::TxStatus::Expired,
+    }
+}
+
+fn proto_receipt(receipt: Receipt) -> proto::Receipt {
+    match receipt {
+        Receipt::L1(receipt) => proto::Receipt {
+            status: tx_status(receipt.status) as i32,
+            block_number: receipt.rollup_block.map_or(0, |block| *block),
+            fail_reason: String::new(),
+            priority_op: true,
+            eth_block: receipt.eth_block.0,
+            serial_id: receipt.id,
+        },
+        Receipt::L2(receipt) => proto::Receipt {
+            status: tx_status(receipt.status) as i32,
+            block_number: receipt.rollup_block.map_or(0, |block| *block),
+            fail_reason: receipt.fail_reason.unwrap_or_default(),
+            priority_op: false,
+            eth_block: 0,
+            serial_id: 0,
+        },
+    }
+}
+
+fn proto_account_state(account: Account) -> proto::AccountState {
+    proto::AccountState {
+        nonce: *account.nonce,
+        pub_key_hash: account.pub_key_hash.data.to_vec(),
+        balances: account
+            .get_nonzero_balances()
+            .into_iter()
+            .map(|(token, balance)| (*token, balance.0.to_string()))
+            .collect(),
+    }
+}
+
+fn proto_block(details: StorageBlockDetails) -> proto::Block {
+    let status = if details.is_verified() {
+        proto::BlockStatus::Finalized
+    } else {
+        proto::BlockStatus::Committed
+    };
+    proto::Block {
+        block_number: details.block_number as u32,
+        new_state_root: details.new_state_root,
+        block_size: details.block_size as u64,
+        commit_tx_hash: details.commit_tx_hash.unwrap_or_default(),
+        verify_tx_hash: details.verify_tx_hash.unwrap_or_default(),
+        committed_at: details.committed_at.timestamp_millis(),
+        finalized_at: details
+            .verified_at
+            .map_or(0, |finalized_at| finalized_at.timestamp_millis()),
+        status: status as i32,
+    }
+}
+
+fn internal_error(err: impl std::fmt::Display) -> Status {
+    vlog::warn!("Internal error in the gRPC API: {}", err);
+    Status::internal("internal error")
+}
+
+#[derive(Clone)]
+pub struct GrpcApp {
+    connection_pool: ConnectionPool,
+}
+
+impl GrpcApp {
+    pub fn new(connection_pool: ConnectionPool) -> Self {
+        Self { connection_pool }
+    }
+
+    async fn access_storage(&self) -> Result<StorageProcessor<'_>, Status> {
+        self.connection_pool
+            .access_storage()
+            .await
+            .map_err(internal_error)
+    }
+}
+
+#[tonic::async_trait]
+impl ZkSync for GrpcApp {
+    async fn get_receipt(
+        &self,
+        request: Request<GetReceiptRequest>,
+    ) -> Result<Response<GetReceiptResponse>, Status> {
+        let start = Instant::now();
+        let tx_hash = request.into_inner().tx_hash;
+        if tx_hash.len() != 32 {
+            return Err(Status::invalid_argument("tx_hash should be 32 bytes long"));
+        }
+
+        let receipt = self
+            .access_storage()
+            .await?
+            .chain()
+            .operations_ext_schema()
+            .tx_receipt_api_v02(&tx_hash)
+            .await
+            .map_err(internal_error)?;
+        metrics::histogram!("api", start.elapsed(), "type" => "grpc", "endpoint_name" => "get_receipt");
+        Ok(Response::new(GetReceiptResponse {
+            receipt: receipt.map(proto_receipt),
+        }))
+    }
+
+    async fn get_account(
+        &self,
+        request: Request<GetAccountRequest>,
+    ) -> Result<Response<GetAccountResponse>, Status> {
+        let start = Instant::now();
+        let address = request.into_inner().address;
+        if address.len() != 20 {
+            return Err(Status::invalid_argument("address should be 20 bytes long"));
+        }
+
+        let state = self
+            .access_storage()
+            .await?
+            .chain()
+            .account_schema()
+            .account_state_by_address(Address::from_slice(&address))
+            .await
+            .map_err(internal_error)?;
+        let verified = state.verified;
+        let account = state.committed.map(|(account_id, committed)| {
+            let finalized = verified
+                .filter(|(verified_id, _)| *verified_id == account_id)
+                .map(|(_, account)| proto_account_state(account));
+            proto::Account {
+                account_id: *account_id,
+                committed: Some(proto_account_state(committed)),
+                finalized,
+            }
+        });
+        metrics::histogram!("api", start.elapsed(), "type" => "grpc", "endpoint_name" => "get_account");
+        Ok(Response::new(GetAccountResponse { account }))
+    }
+
+    async fn get_block(
+        &self,
+        request: Request<GetBlockRequest>,
+    ) -> Result<Response<GetBlockResponse>, Status> {
+        let start = Instant::now();
+        let block_number = BlockNumber(request.into_inner().block_number);
+
+        let block = self
+            .access_storage()
+            .await?
+            .chain()
+            .block_schema()
+            .load_block_range_desc(block_number, 1)
+            .await
+            .map_err(internal_error)?
+            .into_iter()
+            .next()
+            // The range query returns the previous block if the requested one doesn't exist.
+            .filter(|details| details.block_number == *block_number as i64)
+            .map(proto_block);
+        metrics::histogram!("api", start.elapsed(), "type" => "grpc", "endpoint_name" => "get_block");
+        Ok(Response::new(GetBlockResponse { block }))
+    }
+}
+
+pub fn start_grpc_server(connection_pool: ConnectionPool, config: &GrpcConfig) -> JoinHandle<()> {
+    let addr = config.bind_addr();
+    let app = GrpcApp::new(connection_pool);
+    let (handler, panic_sender) = spawn_panic_handler();
+
+    std::thread::Builder::new()
+        .name("grpc-api".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_sender);
+
+            let runtime = Runtime::new().expect("tokio runtime creation");
+            runtime.block_on(async move {
+                Server::builder()
+                    .add_service(ZkSyncServer::new(app))
+                    .serve(addr)
+                    .await
+                    .expect("gRPC server failure");
+            });
+        })
+        .expect("gRPC server thread");
+    handler
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use num::BigUint;
+    use zksync_types::{Nonce, TokenId};
+
+    #[test]
+    fn account_state_conversion() {
+        let mut account = Account::default_with_address(&Address::repeat_byte(0x11));
+        account.nonce = Nonce(3);
+        account.set_balance(TokenId(0), BigUint::from(10u32).pow(18));
+        account.set_balance(TokenId(1), BigUint::from(0u32));
+
+        let state = proto_account_state(account);
+        assert_eq!(state.nonce, 3);
+        assert_eq!(state.pub_key_hash, vec![0; 20]);
+        assert_eq!(state.balances.len(), 1);
+        assert_eq!(state.balances[&0], "1000000000000000000");
+    }
+
+    #[test]
+    fn block_conversion() {
+        let committed_at = Utc::now();
+        let mut details = StorageBlockDetails {
+            block_number: 5,
+            new_state_root: vec![1; 32],
+            block_size: 10,
+            commit_tx_hash: Some(vec![2; 32]),
+            verify_tx_hash: None,
+            committed_at,
+            verified_at: None,
+        };
+
+        let block = proto_block(details.clone());
+        assert_eq!(block.block_number, 5);
+        assert_eq!(block.status, proto::BlockStatus::Committed as i32);
+        assert_eq!(block.committed_at, committed_at.timestamp_millis());
+        assert_eq!(block.finalized_at, 0);
+        assert!(block.verify_tx_hash.is_empty());
+
+        details.verify_tx_hash = Some(vec![3; 32]);
+        details.verified_at = Some(committed_at);
+        let block = proto_block(details);
+        assert_eq!(block.status, proto::BlockStatus::Finalized as i32);
+        assert_eq!(block.verify_tx_hash, vec![3; 32]);
+    }
+}
@@ -0,0 +1,288 @@
+//! Cache of the account ids and addresses resolved by the API handlers.
+//!
+//! The mapping of the account id to its address only changes if the account is deleted,
+//! e.g. once the blocks are reverted, and its id is reused by the new account. So the resolved
+//! accounts are kept in the LRU cache, and the entries are invalidated once the account creation
+//! or deletion events are stored for their ids.
+
+// Built-in uses
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// External uses
+use futures::channel::mpsc;
+use lru_cache::LruCache;
+use tokio::{runtime::Runtime, time};
+
+// Workspace uses
+use zksync_api_types::v02::account::AccountAddressOrId;
+use zksync_storage::{event::records::EventType, ConnectionPool, QueryResult};
+use zksync_types::{
+    event::{account::AccountStateChangeType, EventData, ZkSyncEvent},
+    AccountId, Address, EventId,
+};
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+/// Maximum amount of the events loaded by the updater at once.
+const EVENTS_BATCH_SIZE: u32 = 1000;
+
+/// Both directions of the mapping, evicted together.
+#[derive(Debug)]
+struct ResolvedAccounts {
+    by_id: LruCache<AccountId, Address>,
+    by_address: HashMap<Address, AccountId>,
+}
+
+impl ResolvedAccounts {
+    fn insert(&mut self, account_id: AccountId, address: Address) {
+        self.remove(account_id);
+        if self.by_id.len() >= self.by_id.capacity() {
+            if let Some((_, evicted_address)) = self.by_id.remove_lru() {
+                self.by_address.remove(&evicted_address);
+            }
+        }
+        self.by_id.insert(account_id, address);
+        self.by_address.insert(address, account_id);
+    }
+
+    fn remove(&mut self, account_id: AccountId) {
+        if let Some(address) = self.by_id.remove(&account_id) {
+            self.by_address.remove(&address);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.by_id.clear();
+        self.by_address.clear();
+    }
+
+    fn get(&mut self, account: &AccountAddressOrId) -> Option<(AccountId, Address)> {
+        let account_id = match account {
+            AccountAddressOrId::Id(account_id) => *account_id,
+            AccountAddressOrId::Address(address) => *self.by_address.get(address)?,
+        };
+        let address = *self.by_id.get_mut(&account_id)?;
+        Some((account_id, address))
+    }
+}
+
+/// Resolver of the account ids and addresses shared between the API server workers.
+/// Only the existing accounts are cached, unknown ones are looked up in the database every time.
+#[derive(Debug, Clone)]
+pub struct SharedAccountResolver(Arc<Mutex<ResolvedAccounts>>);
+
+impl SharedAccountResolver {
+    pub fn new(capacity: usize) -> Self {
+        Self(Arc::new(Mutex::new(ResolvedAccounts {
+            by_id: LruCache::new(capacity),
+            by_address: HashMap::new(),
+        })))
+    }
+
+    /// Resolves the accounts with a single database query for the ones missing from the cache.
+    /// Returns the id and the address of every account in the order of the request,
+    /// `None` for the accounts which don't exist.
+    pub async fn resolve(
+        &self,
+        pool: &ConnectionPool,
+        accounts: &[AccountAddressOrId],
+    ) -> QueryResult<Vec<Option<(AccountId, Address)>>> {
+        let mut resolved: Vec<_> = {
+            let mut cache = self.0.lock().unwrap();
+            accounts.iter().map(|account| cache.get(account)).collect()
+        };
+
+        let mut missing_ids = Vec::new();
+        let mut missing_addresses = Vec::new();
+        for (account, resolved) in accounts.iter().zip(&resolved) {
+            match (account, resolved) {
+                (_, Some(_)) => {}
+                (AccountAddressOrId::Id(account_id), None) => missing_ids.push(*account_id),
+                (AccountAddressOrId::Address(address), None) => missing_addresses.push(*address),
+            }
+        }
+        if missing_ids.is_empty() && missing_addresses.is_empty() {
+            metrics::increment_counter!("api.account_resolver.hit");
+            return Ok(resolved);
+        }
+        metrics::increment_counter!("api.account_resolver.miss");
+
+        let mut storage = pool.access_storage().await?;
+        let found = storage
+            .chain()
+            .account_schema()
+            .resolve_accounts(&missing_ids, &missing_addresses)
+            .await?;
+
+        let mut cache = self.0.lock().unwrap();
+        for (account_id, address) in found {
+            cache.insert(account_id, address);
+        }
+        for (account, resolved) in accounts.iter().zip(&mut resolved) {
+            if resolved.is_none() {
+                *resolved = cache.get(account);
+            }
+        }
+        Ok(resolved)
+    }
+
+    pub async fn account_id(
+        &self,
+        pool: &ConnectionPool,
+        address: Address,
+    ) -> QueryResult<Option<AccountId>> {
+        let resolved = self
+            .resolve(pool, &[AccountAddressOrId::Address(address)])
+            .await?;
+        Ok(resolved[0].map(|(account_id, _)| account_id))
+    }
+
+    pub async fn address(
+        &self,
+        pool: &ConnectionPool,
+        account_id: AccountId,
+    ) -> QueryResult<Option<Address>> {
+        let resolved = self
+            .resolve(pool, &[AccountAddressOrId::Id(account_id)])
+            .await?;
+        Ok(resolved[0].map(|(_, address)| address))
+    }
+
+    /// Removes the cached accounts created or deleted by the events.
+    fn invalidate(&self, events: &[ZkSyncEvent]) {
+        let mut cache = self.0.lock().unwrap();
+        for event in events {
+            if let EventData::Account(account_event) = &event.data {
+                if let AccountStateChangeType::Create | AccountStateChangeType::Delete =
+                    account_event.update_type
+                {
+                    cache.remove(account_event.update_details.account_id);
+                }
+            }
+        }
+    }
+
+    /// Processes the events stored after `last_event_id`, returns the id of the last one.
+    async fn update(
+        &self,
+        connection_pool: &ConnectionPool,
+        mut last_event_id: EventId,
+    ) -> anyhow::Result<EventId> {
+        let mut storage = connection_pool.access_storage().await?;
+        loop {
+            let stored_events = match storage
+                .event_schema()
+                .load_events_after(last_event_id, EVENTS_BATCH_SIZE)
+                .await?
+            {
+                Some(stored_events) => stored_events,
+                None => {
+                    // The events following the processed ones may have been removed
+                    // by the retention policy, so none of the cached accounts can be trusted.
+                    vlog::warn!(
+                        "Events after {} were removed, resetting the account resolver cache",
+                        *last_event_id
+                    );
+                    self.0.lock().unwrap().clear();
+                    return last_stored_event_id(connection_pool).await;
+                }
+            };
+            let batch_size = stored_events.len();
+            if let Some(event) = stored_events.last() {
+                last_event_id = EventId(event.id as u64);
+            }
+
+            let events = stored_events
+                .into_iter()
+                .filter(|event| event.event_type == EventType::Account)
+                .map(ZkSyncEvent::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            self.invalidate(&events);
+
+            if batch_size < EVENTS_BATCH_SIZE as usize {
+                return Ok(last_event_id);
+            }
+        }
+    }
+
+    pub fn start_updater_detached(
+        self,
+        panic_notify: mpsc::Sender<bool>,
+        connection_pool: ConnectionPool,
+        update_interval: Duration,
+    ) {
+        std::thread::Builder::new()
+            .name("rest-account-resolver-updater".to_string())
+            .spawn(move || {
+                let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+
+                let runtime = Runtime::new().expect("tokio runtime creation");
+
+                let resolver_update_task = async move {
+                    // Accounts resolved before the updater is started were created earlier,
+                    // so only the events stored from now on can invalidate them.
+                    let mut last_event_id = None;
+                    let mut timer = time::interval(update_interval);
+                    loop {
+                        timer.tick().await;
+                        let result = match last_event_id {
+                            Some(event_id) => self.update(&connection_pool, event_id).await,
+                            None => last_stored_event_id(&connection_pool).await,
+                        };
+                        match result {
+                            Ok(event_id) => last_event_id = Some(event_id),
+                            Err(err) => {
+                                vlog::error!("Can't update the account resolver cache: {}", err)
+                            }
+                        }
+                    }
+                };
+                runtime.block_on(resolver_update_task);
+            })
+            .expect("Account resolver update thread");
+    }
+}
+
+async fn last_stored_event_id(connection_pool: &ConnectionPool) -> anyhow::Result<EventId> {
+    let mut storage = connection_pool.access_storage().await?;
+    let last_event_id = storage.event_schema().get_last_visible_event_id().await?;
+    Ok(last_event_id.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolved_accounts_eviction() {
+        let resolver = SharedAccountResolver::new(2);
+        let mut cache = resolver.0.lock().unwrap();
+        let address = Address::repeat_byte;
+        cache.insert(AccountId(1), address(1));
+        cache.insert(AccountId(2), address(2));
+        // Touch the first account, so the second one is evicted.
+        assert_eq!(
+            cache.get(&AccountAddressOrId::Address(address(1))),
+            Some((AccountId(1), address(1)))
+        );
+        cache.insert(AccountId(3), address(3));
+
+        assert_eq!(cache.get(&AccountAddressOrId::Id(AccountId(2))), None);
+        assert_eq!(cache.get(&AccountAddressOrId::Address(address(2))), None);
+        assert_eq!(
+            cache.get(&AccountAddressOrId::Id(AccountId(3))),
+            Some((AccountId(3), address(3)))
+        );
+
+        // The id is reused by the new account once the old one is deleted.
+        cache.remove(AccountId(1));
+        cache.insert(AccountId(1), address(4));
+        assert_eq!(cache.get(&AccountAddressOrId::Address(address(1))), None);
+        assert_eq!(
+            cache.get(&AccountAddressOrId::Address(address(4))),
+            Some((AccountId(1), address(4)))
+        );
+    }
+}
@@ -0,0 +1,357 @@
+//! Relayer of the deposits requested through the `api/v0.2/deposit_relays` endpoints.
+//!
+//! For each job the relayer submits the permit signed by the owner, transfers the tokens to the
+//! relayer account and deposits the amount less the fee to the owner. The fee is deposited to
+//! the relayer account afterwards. Transactions are sent one by one, each one is confirmed before
+//! the next is sent, so the relayer should be enabled only on one API server.
+
+// Built-in uses
+use std::time::{Duration, Instant};
+
+// External uses
+use anyhow::Context;
+use chrono::Utc;
+use futures::channel::mpsc;
+use tokio::{runtime::Runtime, time};
+use web3::contract::{tokens::Tokenize, Options};
+
+// Workspace uses
+use zksync_config::ZkSyncConfig;
+use zksync_contracts::{erc20_contract, erc20_permit_contract, zksync_contract};
+use zksync_eth_client::{ETHDirectClient, EthereumGateway};
+use zksync_eth_signer::PrivateKeySigner;
+use zksync_storage::{deposit_relay::records::StoredDepositRelayJob, ConnectionPool};
+use zksync_types::{tx::PackedEthSignature, Address, ChainId, H256, U256};
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+/// Type of the EIP-2612 permit, as it's hashed for the signature.
+const PERMIT_TYPE: &str =
+    "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+
+fn encode_call(
+    contract: &ethabi::Contract,
+    func: &str,
+    params: impl Tokenize,
+) -> anyhow::Result<Vec<u8>> {
+    Ok(contract
+        .function(func)?
+        .encode_input(&params.into_tokens())?)
+}
+
+struct DepositRelayer {
+    connection_pool: ConnectionPool,
+    eth_gateway: EthereumGateway,
+    erc20_abi: ethabi::Contract,
+    permit_abi: ethabi::Contract,
+    /// The relayer account, it sends the transactions and receives the fees on L2.
+    relayer: Address,
+    /// Address of the zkSync contract, the tokens are deposited through it.
+    contract_address: Address,
+    wait_confirmations: u64,
+    poll_interval: Duration,
+    tx_timeout: Duration,
+    tx_gas_limit: u64,
+}
+
+impl DepositRelayer {
+    fn new(connection_pool: ConnectionPool, config: &ZkSyncConfig) -> Self {
+        let relayer_config = &config.deposit_relayer;
+        let transport = web3::transports::Http::new(&config.eth_client.web3_url()).unwrap();
+        let eth_gateway = EthereumGateway::Direct(ETHDirectClient::new(
+            transport,
+            zksync_contract(),
+            relayer_config.sender_address,
+            PrivateKeySigner::new(relayer_config.sender_eth_private_key),
+            config.contracts.contract_addr,
+            ChainId(config.eth_client.chain_id),
+            config.eth_client.gas_price_factor,
+        ));
+
+        Self {
+            connection_pool,
+            eth_gateway,
+            erc20_abi: erc20_contract(),
+            permit_abi: erc20_permit_contract(),
+            relayer: relayer_config.sender_address,
+            contract_address: config.contracts.contract_addr,
+            wait_confirmations: relayer_config.wait_confirmations,
+            poll_interval: relayer_config.poll_interval(),
+            tx_timeout: relayer_config.tx_timeout(),
+            tx_gas_limit: relayer_config.tx_gas_limit,
+        }
+    }
+
+    /// Checks that the permit is signed by the owner for the current nonce, so the relayer
+    /// doesn't pay for the transactions which would be reverted anyway.
+    async fn check_permit(
+        &self,
+        token_address: Address,
+        owner: Address,
+        amount: U256,
+        deadline: U256,
+        signature: &PackedEthSignature,
+    ) -> anyhow::Result<()> {
+        let domain_separator: H256 = self
+            .eth_gateway
+            .call_contract_function(
+                "DOMAIN_SEPARATOR",
+                (),
+                None,
+                Options::default(),
+                None,
+                token_address,
+                self.permit_abi.clone(),
+            )
+            .await
+            .context("token doesn't support permits")?;
+        let nonce: U256 = self
+            .eth_gateway
+            .call_contract_function(
+                "nonces",
+                owner,
+                None,
+                Options::default(),
+                None,
+                token_address,
+                self.permit_abi.clone(),
+            )
+            .await
+            .context("token doesn't support permits")?;
+
+        let struct_hash = tiny_keccak::keccak256(&ethabi::encode(&[
+            ethabi::Token::FixedBytes(tiny_keccak::keccak256(PERMIT_TYPE.as_bytes()).to_vec()),
+            ethabi::Token::Address(owner),
+            ethabi::Token::Address(self.relayer),
+            ethabi::Token::Uint(amount),
+            ethabi::Token::Uint(nonce),
+            ethabi::Token::Uint(deadline),
+        ]));
+        let mut message = b"\x19\x01".to_vec();
+        message.extend_from_slice(domain_separator.as_bytes());
+        message.extend_from_slice(&struct_hash);
+        let signer = signature
+            .signature_recover_signer_from_hash(H256(tiny_keccak::keccak256(&message)))
+            .context("invalid permit signature")?;
+        anyhow::ensure!(signer == owner, "permit is not signed by the owner");
+        Ok(())
+    }
+
+    async fn send_tx(&self, to: Address, data: Vec<u8>) -> anyhow::Result<H256> {
+        let gas_limit = self.tx_gas_limit;
+        let signed_tx = self
+            .eth_gateway
+            .sign_prepared_tx_for_addr(
+                data,
+                to,
+                Options::with(|opt| opt.gas = Some(gas_limit.into())),
+            )
+            .await?;
+        self.eth_gateway.send_raw_tx(signed_tx.raw_tx).await?;
+        Ok(signed_tx.hash)
+    }
+
+    async fn wait_for_tx(&self, hash: H256) -> anyhow::Result<()> {
+        let started_at = Instant::now();
+        loop {
+            if let Some(status) = self.eth_gateway.get_tx_status(hash, None).await? {
+                if !status.success {
+                    let reason = self
+                        .eth_gateway
+                        .failure_reason(hash)
+                        .await?
+                        .map(|failure| failure.revert_reason)
+                        .unwrap_or_else(|| "unknown".to_string());
+                    anyhow::bail!("transaction {:?} has failed: {}", hash, reason);
+                }
+                if status.confirmations >= self.wait_confirmations {
+                    return Ok(());
+                }
+            }
+            if started_at.elapsed() > self.tx_timeout {
+                anyhow::bail!("transaction {:?} is not confirmed in time", hash);
+            }
+            time::sleep(self.poll_interval).await;
+        }
+    }
+
+    async fn send_and_wait(&self, to: Address, data: Vec<u8>) -> anyhow::Result<()> {
+        let hash = self.send_tx(to, data).await?;
+        self.wait_for_tx(hash).await
+    }
+
+    /// Performs the deposit to the owner. Returns the relayed amount and the fee.
+    async fn relay_deposit(&self, job: &StoredDepositRelayJob) -> anyhow::Result<(U256, U256)> {
+        anyhow::ensure!(job.deadline > Utc::now(), "permit has expired");
+        let owner = Address::from_slice(&job.owner);
+        let token_address = Address::from_slice(&job.token_address);
+        let amount = U256::from_dec_str(&job.amount.to_string())?;
+        let fee = U256::from_dec_str(&job.fee.to_string())?;
+        let deadline = U256::from(job.deadline.timestamp() as u64);
+        let signature = PackedEthSignature::deserialize_packed(&job.signature)?;
+        self.check_permit(token_address, owner, amount, deadline, &signature)
+            .await?;
+
+        let signature = signature.serialize_packed();
+        let data = encode_call(
+            &self.permit_abi,
+            "permit",
+            (
+                owner,
+                self.relayer,
+                amount,
+                deadline,
+                U256::from(signature[64]),
+                H256::from_slice(&signature[0..32]),
+                H256::from_slice(&signature[32..64]),
+            ),
+        )?;
+        self.send_and_wait(token_address, data)
+            .await
+            .context("permit is not accepted")?;
+        let data = encode_call(
+            &self.erc20_abi,
+            "transferFrom",
+            (owner, self.relayer, amount),
+        )?;
+        self.send_and_wait(token_address, data)
+            .await
+            .context("tokens are not transferred from the owner")?;
+
+        // From now on the tokens are held by the relayer, so the errors need the operator's
+        // attention.
+        let allowance = self
+            .eth_gateway
+            .allowance(token_address, self.erc20_abi.clone())
+            .await?;
+        if allowance < amount {
+            let data = encode_call(
+                &self.erc20_abi,
+                "approve",
+                (self.contract_address, U256::max_value()),
+            )?;
+            self.send_and_wait(token_address, data)
+                .await
+                .context("tokens are held by the relayer, approval has failed")?;
+        }
+        let data = self
+            .eth_gateway
+            .encode_tx_data("depositERC20", (token_address, amount - fee, owner));
+        let hash = self
+            .send_tx(self.contract_address, data)
+            .await
+            .context("tokens are held by the relayer, deposit has failed")?;
+        self.connection_pool
+            .access_storage()
+            .await?
+            .deposit_relay_schema()
+            .store_deposit_hash(job.id, hash)
+            .await?;
+        self.wait_for_tx(hash)
+            .await
+            .context("tokens are held by the relayer, deposit has failed")?;
+
+        Ok((amount, fee))
+    }
+
+    /// Processes the earliest pending job. Returns `false` if there are no pending jobs.
+    async fn process_next_job(&self) -> anyhow::Result<bool> {
+        let job = match self
+            .connection_pool
+            .access_storage()
+            .await?
+            .deposit_relay_schema()
+            .take_next_pending_job()
+            .await?
+        {
+            Some(job) => job,
+            None => return Ok(false),
+        };
+
+        match self.relay_deposit(&job).await {
+            Ok((amount, fee)) => {
+                self.connection_pool
+                    .access_storage()
+                    .await?
+                    .deposit_relay_schema()
+                    .complete_job(job.id)
+                    .await?;
+                metrics::increment_counter!("api.deposit_relayer.completed");
+
+                if !fee.is_zero() {
+                    // The owner has already got the deposit, so the failed fee deposit
+                    // only leaves the fee on the relayer account on L1.
+                    let data = self.eth_gateway.encode_tx_data(
+                        "depositERC20",
+                        (Address::from_slice(&job.token_address), fee, self.relayer),
+                    );
+                    if let Err(err) = self.send_and_wait(self.contract_address, data).await {
+                        vlog::error!(
+                            "Fee of the relayed deposit {} ({} of {}) is not deposited: {}",
+                            job.id,
+                            fee,
+                            amount,
+                            err
+                        );
+                    }
+                }
+            }
+            Err(err) => {
+                vlog::warn!("Relayed deposit {} has failed: {:#}", job.id, err);
+                self.connection_pool
+                    .access_storage()
+                    .await?
+                    .deposit_relay_schema()
+                    .fail_job(job.id, &format!("{:#}", err))
+                    .await?;
+                metrics::increment_counter!("api.deposit_relayer.failed");
+            }
+        }
+        Ok(true)
+    }
+}
+
+pub fn start_deposit_relayer_detached(
+    panic_notify: mpsc::Sender<bool>,
+    connection_pool: ConnectionPool,
+    config: &ZkSyncConfig,
+) {
+    let relayer = DepositRelayer::new(connection_pool, config);
+    std::thread::Builder::new()
+        .name("rest-deposit-relayer".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+
+            let runtime = Runtime::new().expect("tokio runtime creation");
+
+            let relayer_task = async move {
+                let interrupted = relayer
+                    .connection_pool
+                    .access_storage()
+                    .await
+                    .expect("Can't access storage")
+                    .deposit_relay_schema()
+                    .fail_interrupted_jobs()
+                    .await
+                    .expect("Can't fail the interrupted relay jobs");
+                if interrupted > 0 {
+                    vlog::error!(
+                        "{} relayed deposits were interrupted and need to be reviewed",
+                        interrupted
+                    );
+                }
+
+                loop {
+                    match relayer.process_next_job().await {
+                        Ok(true) => {}
+                        Ok(false) => time::sleep(relayer.poll_interval).await,
+                        Err(err) => {
+                            vlog::error!("Can't process relayed deposit: {}", err);
+                            time::sleep(relayer.poll_interval).await;
+                        }
+                    }
+                }
+            };
+            runtime.block_on(relayer_task);
+        })
+        .expect("Deposit relayer thread");
+}
@@ -0,0 +1,129 @@
+//! Generator of the exit proofs requested through the API.
+//!
+//! Requests are processed one by one against the state of the last verified block,
+//! the generation of a single proof takes minutes.
+
+// Built-in uses
+use std::time::Duration;
+
+// External uses
+use anyhow::format_err;
+use futures::channel::mpsc;
+use tokio::{runtime::Runtime, time};
+
+// Workspace uses
+use zksync_prover_utils::exit_proof::create_exit_proof_data;
+use zksync_storage::ConnectionPool;
+use zksync_types::{exit_proof::ExitProofData, AccountId, TokenId, TokenLike};
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+/// Interval of checking for the new requests when there are no pending ones.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+async fn generate_exit_proof(
+    connection_pool: &ConnectionPool,
+    account_id: AccountId,
+    token_id: TokenId,
+) -> anyhow::Result<ExitProofData> {
+    let mut storage = connection_pool.access_storage().await?;
+    let (block_number, accounts) = storage.chain().state_schema().load_verified_state().await?;
+    let block = storage
+        .chain()
+        .block_schema()
+        .get_block(block_number)
+        .await?
+        .ok_or_else(|| format_err!("Block {} is not stored", block_number))?;
+    let token = storage
+        .tokens_schema()
+        .get_token(TokenLike::Id(token_id))
+        .await?
+        .ok_or_else(|| format_err!("Token {} is not found", token_id))?;
+    let nft = if token.is_nft {
+        storage.tokens_schema().get_nft(token_id).await?
+    } else {
+        None
+    };
+    drop(storage);
+
+    tokio::task::spawn_blocking(move || {
+        create_exit_proof_data(accounts, &block, account_id, &token, nft.as_ref())
+    })
+    .await?
+}
+
+/// Generates the proof for the oldest pending request. Returns `false` if there are no requests.
+async fn process_next_request(connection_pool: &ConnectionPool) -> anyhow::Result<bool> {
+    let request = connection_pool
+        .access_storage()
+        .await?
+        .exit_proofs_schema()
+        .take_pending_exit_proof()
+        .await?;
+    let (account_id, token_id) = match request {
+        Some(request) => (
+            AccountId(request.account_id as u32),
+            TokenId(request.token_id as u32),
+        ),
+        None => return Ok(false),
+    };
+
+    vlog::info!(
+        "Generating exit proof for account {}, token {}",
+        account_id,
+        token_id
+    );
+    let result = generate_exit_proof(connection_pool, account_id, token_id).await;
+
+    let mut storage = connection_pool.access_storage().await?;
+    match result {
+        Ok(proof_data) => {
+            storage
+                .exit_proofs_schema()
+                .store_exit_proof(&proof_data)
+                .await?;
+            metrics::increment_counter!("api.exit_proofs.generated");
+        }
+        Err(err) => {
+            vlog::warn!(
+                "Failed to generate exit proof for account {}, token {}: {}",
+                account_id,
+                token_id,
+                err
+            );
+            storage
+                .exit_proofs_schema()
+                .store_exit_proof_error(account_id, token_id, &err.to_string())
+                .await?;
+            metrics::increment_counter!("api.exit_proofs.failed");
+        }
+    }
+    Ok(true)
+}
+
+pub fn start_exit_proof_generator_detached(
+    panic_notify: mpsc::Sender<bool>,
+    connection_pool: ConnectionPool,
+) {
+    std::thread::Builder::new()
+        .name("rest-exit-proof-generator".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+
+            let runtime = Runtime::new().expect("tokio runtime creation");
+
+            let generator_task = async move {
+                loop {
+                    match process_next_request(&connection_pool).await {
+                        Ok(true) => {}
+                        Ok(false) => time::sleep(POLL_INTERVAL).await,
+                        Err(err) => {
+                            vlog::error!("Can't process exit proof request: {}", err);
+                            time::sleep(POLL_INTERVAL).await;
+                        }
+                    }
+                }
+            };
+            runtime.block_on(generator_task);
+        })
+        .expect("Exit proof generator thread");
+}
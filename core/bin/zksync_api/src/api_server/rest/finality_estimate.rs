@@ -0,0 +1,224 @@
+//! Estimation of the time left until the operation is finalized, based on the recent
+//! commit and execute cadence of the blocks.
+
+// Built-in uses
+use std::sync::Arc;
+use std::time::Duration;
+
+// External uses
+use chrono::{DateTime, Utc};
+use futures::channel::mpsc;
+use tokio::sync::RwLock;
+use tokio::{runtime::Runtime, time};
+
+// Workspace uses
+use zksync_api_types::v02::transaction::TxInBlockStatus;
+use zksync_storage::{chain::block::records::StorageBlockDetails, ConnectionPool};
+use zksync_types::BlockNumber;
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+/// Amount of the last blocks the averages are computed over.
+const BLOCKS_WINDOW: u32 = 100;
+/// Interval between the updates of the averages.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Rolling averages of the block cadence.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct FinalityEstimate {
+    /// Average interval between the commitments of the consecutive blocks.
+    pub block_interval: Option<Duration>,
+    /// Average delay between the commitment of the block and its execution.
+    pub execute_delay: Option<Duration>,
+}
+
+fn average(durations: impl Iterator<Item = chrono::Duration>) -> Option<Duration> {
+    let (count, total) = durations
+        .filter_map(|duration| duration.to_std().ok())
+        .fold((0u32, Duration::default()), |(count, total), duration| {
+            (count + 1, total + duration)
+        });
+    if count == 0 {
+        None
+    } else {
+        Some(total / count)
+    }
+}
+
+impl FinalityEstimate {
+    /// Computes the averages from the blocks sorted in the descending order.
+    pub fn from_blocks(blocks: &[StorageBlockDetails]) -> Self {
+        let block_interval = average(
+            blocks
+                .windows(2)
+                .map(|pair| pair[0].committed_at - pair[1].committed_at),
+        );
+        let execute_delay = average(blocks.iter().filter_map(|block| {
+            block
+                .verified_at
+                .map(|verified_at| verified_at - block.committed_at)
+        }));
+        Self {
+            block_interval,
+            execute_delay,
+        }
+    }
+
+    /// Estimates the time left until the operation with the given status is finalized.
+    /// `committed_at` is the time of the commitment of the operation block, if it's known.
+    /// Returns `None` for the operations which will not change their status anymore
+    /// or if there is not enough data to estimate.
+    pub fn time_left(
+        &self,
+        status: TxInBlockStatus,
+        committed_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Option<Duration> {
+        let execute_delay = self.execute_delay?;
+        match status {
+            TxInBlockStatus::Queued => Some(self.block_interval? + execute_delay),
+            TxInBlockStatus::Committed => match committed_at {
+                Some(committed_at) => {
+                    let elapsed = (now - committed_at).to_std().unwrap_or_default();
+                    Some(execute_delay.checked_sub(elapsed).unwrap_or_default())
+                }
+                // The block is created, but its commitment is not sent yet.
+                None => Some(execute_delay),
+            },
+            TxInBlockStatus::Finalized
+            | TxInBlockStatus::Rejected
+            | TxInBlockStatus::Pruned
+            | TxInBlockStatus::Expired => None,
+        }
+    }
+}
+
+/// Finality estimate shared between the API server workers.
+#[derive(Debug, Clone, Default)]
+pub struct SharedFinalityEstimate(Arc<RwLock<FinalityEstimate>>);
+
+impl SharedFinalityEstimate {
+    pub async fn read(&self) -> FinalityEstimate {
+        *self.0.read().await
+    }
+
+    async fn update(&self, connection_pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = connection_pool.access_storage().await?;
+        let last_committed = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_confirmed_block()
+            .await?;
+        let blocks = storage
+            .chain()
+            .block_schema()
+            .load_block_range_desc(last_committed, BLOCKS_WINDOW)
+            .await?;
+
+        *self.0.write().await = FinalityEstimate::from_blocks(&blocks);
+        Ok(())
+    }
+
+    pub fn start_updater_detached(
+        self,
+        panic_notify: mpsc::Sender<bool>,
+        connection_pool: ConnectionPool,
+    ) {
+        std::thread::Builder::new()
+            .name("rest-finality-estimate-updater".to_string())
+            .spawn(move || {
+                let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+
+                let runtime = Runtime::new().expect("tokio runtime creation");
+
+                let estimate_update_task = async move {
+                    let mut timer = time::interval(UPDATE_INTERVAL);
+                    loop {
+                        timer.tick().await;
+                        if let Err(err) = self.update(&connection_pool).await {
+                            vlog::error!("Can't update the finality estimate: {}", err);
+                        }
+                    }
+                };
+                runtime.block_on(estimate_update_task);
+            })
+            .expect("Finality estimate update thread");
+    }
+}
+
+/// Returns the time of the commitment of the given block, if it's already sent.
+pub async fn block_committed_at(
+    connection_pool: &ConnectionPool,
+    block_number: BlockNumber,
+) -> anyhow::Result<Option<DateTime<Utc>>> {
+    let mut storage = connection_pool.access_storage().await?;
+    let block = storage
+        .chain()
+        .block_schema()
+        .load_block_range_desc(block_number, 1)
+        .await?
+        .into_iter()
+        .find(|block| block.block_number == i64::from(*block_number));
+    Ok(block.map(|block| block.committed_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(
+        block_number: i64,
+        committed_at: DateTime<Utc>,
+        verified_at: Option<DateTime<Utc>>,
+    ) -> StorageBlockDetails {
+        StorageBlockDetails {
+            block_number,
+            new_state_root: Vec::new(),
+            block_size: 0,
+            commit_tx_hash: None,
+            verify_tx_hash: None,
+            committed_at,
+            verified_at,
+        }
+    }
+
+    #[test]
+    fn finality_estimate() {
+        let now = Utc::now();
+        let minutes = chrono::Duration::minutes;
+        let blocks = vec![
+            block(3, now - minutes(10), None),
+            block(2, now - minutes(20), Some(now - minutes(5))),
+            block(1, now - minutes(30), Some(now - minutes(5))),
+        ];
+        let estimate = FinalityEstimate::from_blocks(&blocks);
+        assert_eq!(estimate.block_interval, Some(Duration::from_secs(10 * 60)));
+        assert_eq!(estimate.execute_delay, Some(Duration::from_secs(20 * 60)));
+
+        assert_eq!(
+            estimate.time_left(TxInBlockStatus::Queued, None, now),
+            Some(Duration::from_secs(30 * 60))
+        );
+        assert_eq!(
+            estimate.time_left(TxInBlockStatus::Committed, None, now),
+            Some(Duration::from_secs(20 * 60))
+        );
+        assert_eq!(
+            estimate.time_left(TxInBlockStatus::Committed, Some(now - minutes(15)), now),
+            Some(Duration::from_secs(5 * 60))
+        );
+        // The execution is late, but it's still expected to happen.
+        assert_eq!(
+            estimate.time_left(TxInBlockStatus::Committed, Some(now - minutes(25)), now),
+            Some(Duration::default())
+        );
+        assert_eq!(
+            estimate.time_left(TxInBlockStatus::Finalized, None, now),
+            None
+        );
+
+        // There are no executed blocks yet.
+        let estimate = FinalityEstimate::from_blocks(&blocks[..1]);
+        assert_eq!(estimate, FinalityEstimate::default());
+        assert_eq!(estimate.time_left(TxInBlockStatus::Queued, None, now), None);
+    }
+}
@@ -86,6 +86,9 @@ pub enum SumbitErrorCode {
     IncorrectTx = 104,
     TxAdd = 105,
     InappropriateFeeToken = 106,
+    MaintenanceMode = 107,
+    WithdrawalPolicy = 108,
+    OrderBook = 109,
 
     Internal = 110,
     CommunicationCoreServer = 111,
@@ -101,6 +104,16 @@ impl SumbitErrorCode {
             SubmitError::IncorrectTx(_) => Self::IncorrectTx,
             SubmitError::TxAdd(_) => Self::TxAdd,
             SubmitError::InappropriateFeeToken => Self::InappropriateFeeToken,
+            SubmitError::MaintenanceMode(_) => Self::MaintenanceMode,
+            SubmitError::WithdrawalPolicy(_) => Self::WithdrawalPolicy,
+            SubmitError::OrderBook(_) => Self::OrderBook,
+            SubmitError::StandingOrder(_) => Self::Other,
+            SubmitError::SessionKey(_) => Self::Other,
+            SubmitError::CoSignedTx(_) => Self::Other,
+            SubmitError::Attestation(_) => Self::Other,
+            SubmitError::NonceReservation(_) => Self::Other,
+            SubmitError::Screening(_) => Self::Other,
+            SubmitError::AlreadyKnown(_) => Self::Other,
             SubmitError::MempoolCommunication(_) => Self::CommunicationCoreServer,
             SubmitError::Internal(_) => Self::Internal,
             SubmitError::Other(_) => Self::Other,
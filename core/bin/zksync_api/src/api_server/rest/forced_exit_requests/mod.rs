@@ -21,6 +21,7 @@ pub(crate) fn api_scope(
     forced_exit_minimum_account_age_secs: u64,
     config: &ForcedExitRequestsConfig,
     contract: Address,
+    require_attestation: bool,
 ) -> Scope {
     let fe_age_checker = ForcedExitChecker::new(forced_exit_minimum_account_age_secs);
     web::scope("/api/forced_exit_requests").service(v01::api_scope(
@@ -28,5 +29,6 @@ pub(crate) fn api_scope(
         config,
         contract,
         Box::new(fe_age_checker),
+        require_attestation,
     ))
 }
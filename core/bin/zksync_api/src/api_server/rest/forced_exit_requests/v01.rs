@@ -9,24 +9,26 @@ use actix_web::{
 };
 
 use bigdecimal::{BigDecimal, FromPrimitive};
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use num::{bigint::ToBigInt, BigUint};
+use std::collections::HashSet;
 use std::time::Instant;
 use std::{convert::TryInto, ops::Add};
 // Workspace uses
 pub use zksync_api_client::rest::forced_exit_requests::{
-    ForcedExitRegisterRequest, ForcedExitRequestStatus,
+    ForcedExitEta, ForcedExitRegisterRequest, ForcedExitRequestLifecycle, ForcedExitRequestStage,
+    ForcedExitRequestStatus, ForcedExitTxState,
 };
 
 use zksync_api_client::rest::forced_exit_requests::ConfigInfo;
 use zksync_config::ForcedExitRequestsConfig;
-use zksync_storage::ConnectionPool;
+use zksync_storage::{forced_exit_requests::records::ForcedExitProcessingBacklog, ConnectionPool};
 use zksync_types::{
     forced_exit_requests::{
         ForcedExitEligibilityResponse, ForcedExitRequest, ForcedExitRequestId,
         SaveForcedExitRequestQuery,
     },
-    Address, TokenLike,
+    Address, BlockNumber, TokenLike,
 };
 
 // Local uses
@@ -46,6 +48,8 @@ pub struct ApiForcedExitRequestsData {
     pub(crate) price_per_token: i64,
     pub(crate) forced_exit_contract_address: Address,
     pub(crate) wait_confirmations: u64,
+    /// Whether the requests are accepted only for the attested target addresses.
+    pub(crate) require_attestation: bool,
 }
 
 impl ApiForcedExitRequestsData {
@@ -54,6 +58,7 @@ impl ApiForcedExitRequestsData {
         config: &ForcedExitRequestsConfig,
         contract: Address,
         forced_exit_checker: Box<dyn ForcedExitAccountAgeChecker>,
+        require_attestation: bool,
     ) -> Self {
         Self {
             connection_pool,
@@ -67,6 +72,7 @@ impl ApiForcedExitRequestsData {
             forced_exit_contract_address: contract,
             digits_in_id: config.digits_in_id,
             wait_confirmations: config.wait_confirmations,
+            require_attestation,
         }
     }
 }
@@ -111,6 +117,26 @@ pub async fn submit_request(
         ));
     }
 
+    let unique_tokens: HashSet<_> = params.tokens.iter().collect();
+    if unique_tokens.len() != params.tokens.len() {
+        return Err(ApiError::bad_request(
+            "Tokens of the ForcedExit request must be unique",
+        ));
+    }
+
+    if data.require_attestation {
+        let attested = storage
+            .address_attestations_schema()
+            .is_address_attested(params.target)
+            .await
+            .map_err(ApiError::internal)?;
+        if !attested {
+            return Err(ApiError::bad_request(
+                "Target address of the ForcedExit request should be attested",
+            ));
+        }
+    }
+
     data.forced_exit_checker
         .validate_forced_exit(&mut storage, params.target)
         .await
@@ -152,6 +178,7 @@ pub async fn submit_request(
             price_in_wei: params.price_in_wei.clone(),
             created_at,
             valid_until,
+            refund_address: params.refund_address,
         })
         .await
         .map_err(|err| {
@@ -192,6 +219,138 @@ pub async fn get_request_by_id(
     }
 }
 
+pub async fn get_request_lifecycle(
+    data: web::Data<ApiForcedExitRequestsData>,
+    request_id: web::Path<ForcedExitRequestId>,
+) -> JsonResult<ForcedExitRequestLifecycle> {
+    let start = Instant::now();
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(warn_err)
+        .map_err(ApiError::internal)?;
+
+    let request = storage
+        .forced_exit_requests_schema()
+        .get_request_by_id(*request_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::not_found("Request with such id does not exist"))?;
+
+    let mut txs = Vec::new();
+    for tx_hash in request.fulfilled_by.clone().unwrap_or_default() {
+        let receipt = storage
+            .chain()
+            .operations_ext_schema()
+            .tx_receipt(tx_hash.as_ref())
+            .await
+            .map_err(ApiError::internal)?;
+        let withdrawal_tx_hash = match &receipt {
+            Some(receipt) if receipt.success => storage
+                .chain()
+                .operations_schema()
+                .eth_tx_for_withdrawal(&tx_hash)
+                .await
+                .map_err(ApiError::internal)?,
+            _ => None,
+        };
+
+        txs.push(ForcedExitTxState {
+            tx_hash,
+            block_number: receipt
+                .as_ref()
+                .map(|receipt| BlockNumber(receipt.block_number as u32)),
+            success: receipt.as_ref().map(|receipt| receipt.success),
+            fail_reason: receipt.and_then(|receipt| receipt.fail_reason),
+            withdrawal_tx_hash,
+        });
+    }
+
+    let stage = lifecycle_stage(&request, &txs);
+    let eta = match stage {
+        ForcedExitRequestStage::Paid
+        | ForcedExitRequestStage::Queued
+        | ForcedExitRequestStage::Executed => {
+            let block_number = txs.iter().filter_map(|tx| tx.block_number).max();
+            let backlog = storage
+                .forced_exit_requests_schema()
+                .get_processing_backlog(block_number)
+                .await
+                .map_err(ApiError::internal)?;
+            Some(estimate_eta(&backlog, Utc::now()))
+        }
+        _ => None,
+    };
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "get_forced_exit_request_lifecycle");
+    Ok(Json(ForcedExitRequestLifecycle {
+        request,
+        stage,
+        txs,
+        eta,
+    }))
+}
+
+fn lifecycle_stage(
+    request: &ForcedExitRequest,
+    txs: &[ForcedExitTxState],
+) -> ForcedExitRequestStage {
+    if txs.is_empty() {
+        return if request.paid_at.is_some() {
+            ForcedExitRequestStage::Paid
+        } else {
+            ForcedExitRequestStage::Requested
+        };
+    }
+
+    if txs.iter().any(|tx| tx.success.is_none()) {
+        ForcedExitRequestStage::Queued
+    } else if txs.iter().all(|tx| tx.success == Some(false)) {
+        ForcedExitRequestStage::Failed
+    } else if txs
+        .iter()
+        .filter(|tx| tx.success == Some(true))
+        .all(|tx| tx.withdrawal_tx_hash.is_some())
+    {
+        ForcedExitRequestStage::Completed
+    } else {
+        ForcedExitRequestStage::Executed
+    }
+}
+
+// The backlogs are expected to be processed with the same throughput as during the last hour.
+fn estimate_eta(backlog: &ForcedExitProcessingBacklog, now: DateTime<Utc>) -> ForcedExitEta {
+    fn processing_time(pending: i64, processed_last_hour: i64) -> Option<Duration> {
+        if pending == 0 {
+            Some(Duration::zero())
+        } else if processed_last_hour == 0 {
+            // The backlog is not processed at the moment
+            None
+        } else {
+            Some(Duration::seconds(pending * 3600 / processed_last_hour))
+        }
+    }
+
+    let proving_time = processing_time(
+        backlog.pending_prover_jobs,
+        backlog.prover_jobs_done_last_hour,
+    );
+    let sending_time = processing_time(
+        backlog.pending_eth_operations,
+        backlog.eth_operations_confirmed_last_hour,
+    );
+
+    ForcedExitEta {
+        pending_prover_jobs: backlog.pending_prover_jobs as u64,
+        pending_eth_operations: backlog.pending_eth_operations as u64,
+        estimated_completion_at: proving_time
+            .zip(sending_time)
+            .map(|(proving_time, sending_time)| now + proving_time + sending_time),
+    }
+}
+
 // Checks if the account is eligible for forced_exit in terms of
 // existing enough time
 pub async fn check_account_eligibility(
@@ -223,8 +382,15 @@ pub fn api_scope(
     config: &ForcedExitRequestsConfig,
     contract: Address,
     fe_checker: Box<dyn ForcedExitAccountAgeChecker>,
+    require_attestation: bool,
 ) -> Scope {
-    let data = ApiForcedExitRequestsData::new(connection_pool, config, contract, fe_checker);
+    let data = ApiForcedExitRequestsData::new(
+        connection_pool,
+        config,
+        contract,
+        fe_checker,
+        require_attestation,
+    );
 
     // `enabled` endpoint should always be there
     let scope = web::scope("v0.1")
@@ -235,6 +401,10 @@ pub fn api_scope(
         scope
             .route("/submit", web::post().to(submit_request))
             .route("/requests/{id}", web::get().to(get_request_by_id))
+            .route(
+                "/requests/{id}/lifecycle",
+                web::get().to(get_request_lifecycle),
+            )
             .route(
                 "/checks/eligibility/{account}",
                 web::get().to(check_account_eligibility),
@@ -280,6 +450,10 @@ mod tests {
                         &cfg.config.forced_exit_requests,
                         cfg.config.contracts.forced_exit_addr,
                         Box::new(DummyForcedExitChecker {}),
+                        cfg.config
+                            .api
+                            .common
+                            .forced_exit_requests_require_attestation,
                     )
                 },
                 Option::<SharedData>::None,
@@ -330,6 +504,7 @@ mod tests {
             target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
             tokens: vec![TokenId(0)],
             price_in_wei: BigUint::from_str("1212").unwrap(),
+            refund_address: None,
         };
 
         client
@@ -402,6 +577,7 @@ mod tests {
             target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
             tokens,
             price_in_wei,
+            refund_address: None,
         };
 
         client
@@ -413,6 +589,42 @@ mod tests {
         Ok(())
     }
 
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_forced_exit_requests_duplicate_tokens() -> anyhow::Result<()> {
+        let forced_exit_requests_config = ForcedExitRequestsConfig::from_env();
+        let test_config = get_test_config_from_forced_exit_requests(ForcedExitRequestsConfig {
+            max_tokens_per_request: 5,
+            ..forced_exit_requests_config
+        });
+
+        let (client, server) = TestServer::from_config(test_config).await?;
+
+        let price_per_token = forced_exit_requests_config.price_per_token;
+        let tokens = vec![TokenId(0), TokenId(1), TokenId(0)];
+        let price_in_wei = BigUint::from_i64(price_per_token)
+            .unwrap()
+            .mul(tokens.len());
+
+        let register_request = ForcedExitRegisterRequest {
+            target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
+            tokens,
+            price_in_wei,
+            refund_address: None,
+        };
+
+        client
+            .submit_forced_exit_request(register_request)
+            .await
+            .expect_err("Api accepts the duplicate tokens");
+
+        server.stop().await;
+        Ok(())
+    }
+
     #[actix_rt::test]
     #[cfg_attr(
         not(feature = "api_test"),
@@ -441,10 +653,13 @@ mod tests {
 
         let target = Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap();
 
+        let refund_address = Address::random();
+
         let fe_request = ForcedExitRegisterRequest {
             target,
             tokens: tokens.clone(),
             price_in_wei: price_in_wei.clone(),
+            refund_address: Some(refund_address),
         };
 
         let submit_result = client.submit_forced_exit_request(fe_request).await?;
@@ -452,10 +667,97 @@ mod tests {
         assert_eq!(submit_result.price_in_wei, price_in_wei);
         assert_eq!(submit_result.tokens, tokens);
         assert_eq!(submit_result.target, target);
+        assert_eq!(submit_result.refund_address, Some(refund_address));
 
         server.stop().await;
         Ok(())
     }
+
+    #[test]
+    fn forced_exit_request_lifecycle_stage() {
+        let now = Utc::now();
+        let mut request = ForcedExitRequest {
+            id: 1,
+            target: Address::random(),
+            tokens: vec![TokenId(0)],
+            price_in_wei: BigUint::from(1u32),
+            valid_until: now,
+            created_at: now,
+            fulfilled_by: None,
+            fulfilled_at: None,
+            payment_tx_hash: None,
+            paid_at: None,
+            refund_address: None,
+            refund: None,
+        };
+        assert_eq!(
+            lifecycle_stage(&request, &[]),
+            ForcedExitRequestStage::Requested
+        );
+
+        request.paid_at = Some(now);
+        assert_eq!(lifecycle_stage(&request, &[]), ForcedExitRequestStage::Paid);
+
+        let mut tx = ForcedExitTxState {
+            tx_hash: Default::default(),
+            block_number: None,
+            success: None,
+            fail_reason: None,
+            withdrawal_tx_hash: None,
+        };
+        assert_eq!(
+            lifecycle_stage(&request, &[tx.clone()]),
+            ForcedExitRequestStage::Queued
+        );
+
+        tx.block_number = Some(BlockNumber(1));
+        tx.success = Some(false);
+        assert_eq!(
+            lifecycle_stage(&request, &[tx.clone()]),
+            ForcedExitRequestStage::Failed
+        );
+
+        tx.success = Some(true);
+        assert_eq!(
+            lifecycle_stage(&request, &[tx.clone()]),
+            ForcedExitRequestStage::Executed
+        );
+
+        tx.withdrawal_tx_hash = Some(Default::default());
+        assert_eq!(
+            lifecycle_stage(&request, &[tx]),
+            ForcedExitRequestStage::Completed
+        );
+    }
+
+    #[test]
+    fn forced_exit_eta_estimation() {
+        let now = Utc::now();
+        let mut backlog = ForcedExitProcessingBacklog {
+            pending_prover_jobs: 2,
+            prover_jobs_done_last_hour: 4,
+            pending_eth_operations: 3,
+            eth_operations_confirmed_last_hour: 6,
+        };
+        let eta = estimate_eta(&backlog, now);
+        assert_eq!(eta.pending_prover_jobs, 2);
+        assert_eq!(eta.pending_eth_operations, 3);
+        assert_eq!(eta.estimated_completion_at, Some(now + Duration::hours(1)));
+
+        // Nothing is pending, so the rates don't matter.
+        backlog.pending_prover_jobs = 0;
+        backlog.prover_jobs_done_last_hour = 0;
+        let eta = estimate_eta(&backlog, now);
+        assert_eq!(
+            eta.estimated_completion_at,
+            Some(now + Duration::minutes(30))
+        );
+
+        // The pending operations are not being sent, so the time can't be estimated.
+        backlog.eth_operations_confirmed_last_hour = 0;
+        let eta = estimate_eta(&backlog, now);
+        assert_eq!(eta.estimated_completion_at, None);
+    }
 }
 
 fn warn_err<T: std::fmt::Display>(err: T) -> T {
@@ -128,6 +128,9 @@ pub fn priority_op_to_tx_history(
         verified: false,
         created_at: current_time,
         batch_id: None,
+        l1_sender: op
+            .l1_sender
+            .map(|l1_sender| format!("0x{}", hex::encode(l1_sender))),
     }
 }
 
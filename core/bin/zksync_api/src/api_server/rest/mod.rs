@@ -1,7 +1,8 @@
 use actix_cors::Cors;
-use actix_web::{web, App, HttpResponse, HttpServer};
+use actix_web::{dev::Service, web, App, HttpResponse, HttpServer};
 use futures::channel::mpsc;
 use std::net::SocketAddr;
+use tracing::Instrument;
 use zksync_storage::ConnectionPool;
 use zksync_types::{ChainId, SequentialTxId, H160};
 
@@ -12,18 +13,31 @@ use crate::signature_checker::VerifySignatureRequest;
 
 use super::tx_sender::TxSender;
 
+use crate::api_server::rest::account_resolver::SharedAccountResolver;
+use crate::api_server::rest::finality_estimate::SharedFinalityEstimate;
 use crate::api_server::rest::network_status::SharedNetworkStatus;
+use crate::api_server::rest::route_stats::{SharedRouteStats, UNMATCHED_ROUTE};
+use crate::api_server::rest::verified_tree::SharedVerifiedTree;
 use crate::fee_ticker::FeeTicker;
 use tokio::task::JoinHandle;
 use zksync_config::ZkSyncConfig;
 use zksync_mempool::MempoolTransactionRequest;
 
+mod account_resolver;
+mod deposit_relayer;
+mod exit_proof_generator;
+mod finality_estimate;
 mod forced_exit_requests;
 mod helpers;
 pub mod network_status;
+mod payment_request_watcher;
+mod route_stats;
+mod standing_order_scheduler;
 mod v01;
 pub mod v02;
+mod verified_tree;
 
+#[allow(clippy::too_many_arguments)]
 async fn start_server(
     api_v01: ApiV01,
     fee_ticker: FeeTicker,
@@ -31,6 +45,10 @@ async fn start_server(
     bind_to: SocketAddr,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     chain_id: ChainId,
+    verified_tree: Option<SharedVerifiedTree>,
+    finality_estimate: SharedFinalityEstimate,
+    account_resolver: SharedAccountResolver,
+    route_stats: SharedRouteStats,
 ) {
     HttpServer::new(move || {
         let api_v01 = api_v01.clone();
@@ -44,6 +62,11 @@ async fn start_server(
                 .forced_exit_minimum_account_age_secs,
             &api_v01.config.forced_exit_requests,
             api_v01.config.contracts.forced_exit_addr,
+            api_v01
+                .config
+                .api
+                .common
+                .forced_exit_requests_require_attestation,
         );
 
         let api_v02_scope = {
@@ -56,9 +79,26 @@ async fn start_server(
                 mempool_tx_sender.clone(),
                 chain_id,
             );
-            v02::api_scope(tx_sender, &api_v01.config, api_v01.network_status.clone())
+            v02::api_scope(
+                tx_sender,
+                api_v01.main_database_connection_pool.clone(),
+                &api_v01.config,
+                api_v01.network_status.clone(),
+                verified_tree.clone(),
+                finality_estimate.clone(),
+                account_resolver.clone(),
+            )
         };
+        let route_stats = route_stats.clone();
         App::new()
+            // Every request is recorded to the statistics of its route.
+            .wrap_fn(move |req, srv| {
+                let route = req
+                    .request()
+                    .match_pattern()
+                    .unwrap_or_else(|| UNMATCHED_ROUTE.to_owned());
+                route_stats::record_request(route_stats.clone(), route, srv.call(req))
+            })
             .wrap(
                 Cors::default()
                     .send_wildcard()
@@ -67,6 +107,21 @@ async fn start_server(
                     .allow_any_header()
                     .allow_any_method(),
             )
+            // Every request is traced, continuing the trace of the client if it was provided.
+            .wrap_fn(|req, srv| {
+                let span = tracing::info_span!(
+                    "http_request",
+                    method = %req.method(),
+                    path = %req.path()
+                );
+                vlog::set_remote_parent(
+                    &span,
+                    req.headers()
+                        .iter()
+                        .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?))),
+                );
+                srv.call(req).instrument(span)
+            })
             .service(api_v01.into_scope())
             .service(forced_exit_requests_api_scope)
             .service(api_v02_scope)
@@ -132,7 +187,79 @@ pub fn start_server_thread_detached(
                     network_status,
                 );
 
-                api_v01.spawn_network_status_updater(panic_sender, last_tx_id);
+                api_v01.spawn_network_status_updater(panic_sender.clone(), last_tx_id);
+
+                let finality_estimate = SharedFinalityEstimate::default();
+                finality_estimate
+                    .clone()
+                    .start_updater_detached(panic_sender.clone(), api_v01.connection_pool.clone());
+
+                if api_v01.config.api.common.exit_proofs_enabled {
+                    exit_proof_generator::start_exit_proof_generator_detached(
+                        panic_sender.clone(),
+                        api_v01.main_database_connection_pool.clone(),
+                    );
+                }
+
+                if api_v01.config.api.common.payment_requests_enabled {
+                    payment_request_watcher::start_payment_request_watcher_detached(
+                        panic_sender.clone(),
+                        api_v01.main_database_connection_pool.clone(),
+                    );
+                }
+
+                if api_v01.config.deposit_relayer.enabled {
+                    deposit_relayer::start_deposit_relayer_detached(
+                        panic_sender.clone(),
+                        api_v01.main_database_connection_pool.clone(),
+                        &api_v01.config,
+                    );
+                }
+
+                if api_v01.config.api.common.standing_orders_enabled {
+                    // Payments are submitted the same way as the transactions sent by the users,
+                    // but the state of the payments is kept in the main database.
+                    let tx_sender = TxSender::new(
+                        api_v01.main_database_connection_pool.clone(),
+                        sign_verifier.clone(),
+                        fee_ticker.clone(),
+                        &api_v01.config.api.common,
+                        &api_v01.config.api.token_config,
+                        mempool_tx_sender.clone(),
+                        chain_id,
+                    );
+                    standing_order_scheduler::start_standing_order_scheduler_detached(
+                        panic_sender.clone(),
+                        api_v01.main_database_connection_pool.clone(),
+                        tx_sender,
+                    );
+                }
+
+                let account_resolver =
+                    SharedAccountResolver::new(api_v01.config.api.common.caches_size);
+                account_resolver.clone().start_updater_detached(
+                    panic_sender.clone(),
+                    api_v01.connection_pool.clone(),
+                    api_v01.config.api.common.finalized_cache_poll_interval(),
+                );
+
+                let route_stats = SharedRouteStats::new(&api_v01.config.api.rest);
+                route_stats.clone().start_flusher_detached(
+                    panic_sender.clone(),
+                    api_v01.main_database_connection_pool.clone(),
+                );
+
+                let verified_tree = if api_v01.config.api.common.merkle_proofs_enabled {
+                    let verified_tree = SharedVerifiedTree::default();
+                    verified_tree.clone().start_updater_detached(
+                        panic_sender,
+                        api_v01.connection_pool.clone(),
+                        api_v01.config.api.common.finalized_cache_poll_interval(),
+                    );
+                    Some(verified_tree)
+                } else {
+                    None
+                };
 
                 start_server(
                     api_v01,
@@ -141,6 +268,10 @@ pub fn start_server_thread_detached(
                     listen_addr,
                     mempool_tx_sender.clone(),
                     chain_id,
+                    verified_tree,
+                    finality_estimate,
+                    account_resolver,
+                    route_stats,
                 )
                 .await;
             });
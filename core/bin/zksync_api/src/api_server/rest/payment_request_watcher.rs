@@ -0,0 +1,95 @@
+//! Watcher of the payment requests fulfillment.
+//!
+//! Transfers of the saved blocks are matched with the pending payment requests block by block.
+//! The last matched block is locked in the database while the blocks are processed, so several
+//! API servers can run the watcher at once.
+
+// Built-in uses
+use std::{cmp, time::Duration};
+
+// External uses
+use futures::channel::mpsc;
+use tokio::{runtime::Runtime, time};
+
+// Workspace uses
+use zksync_storage::ConnectionPool;
+use zksync_types::BlockNumber;
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+/// Interval of checking for the new blocks when all the saved ones are processed.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Maximum number of blocks processed in one database transaction.
+const MAX_BLOCKS_PER_ITERATION: u32 = 100;
+
+/// Matches the transfers of the next unprocessed blocks with the payment requests.
+/// Returns `false` if there are no unprocessed blocks left.
+async fn process_new_blocks(connection_pool: &ConnectionPool) -> anyhow::Result<bool> {
+    let mut storage = connection_pool.access_storage().await?;
+    let last_saved_block = storage
+        .chain()
+        .block_schema()
+        .get_last_saved_block()
+        .await?;
+
+    let mut transaction = storage.start_transaction().await?;
+    let last_matched_block = transaction
+        .payment_requests_schema()
+        .lock_last_matched_block()
+        .await?;
+    if last_matched_block >= last_saved_block {
+        return Ok(false);
+    }
+    let to_block = BlockNumber(cmp::min(
+        *last_saved_block,
+        *last_matched_block + MAX_BLOCKS_PER_ITERATION,
+    ));
+
+    let transfers = transaction
+        .payment_requests_schema()
+        .load_executed_transfers(last_matched_block + 1, to_block)
+        .await?;
+    for transfer in &transfers {
+        if let Some(payment_request_id) = transaction
+            .payment_requests_schema()
+            .fulfill_payment_request(transfer)
+            .await?
+        {
+            vlog::debug!("Payment request {} is fulfilled", payment_request_id);
+            metrics::increment_counter!("api.payment_requests.fulfilled");
+        }
+    }
+    transaction
+        .payment_requests_schema()
+        .update_last_matched_block(to_block)
+        .await?;
+    transaction.commit().await?;
+    Ok(to_block < last_saved_block)
+}
+
+pub fn start_payment_request_watcher_detached(
+    panic_notify: mpsc::Sender<bool>,
+    connection_pool: ConnectionPool,
+) {
+    std::thread::Builder::new()
+        .name("rest-payment-request-watcher".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+
+            let runtime = Runtime::new().expect("tokio runtime creation");
+
+            let watcher_task = async move {
+                loop {
+                    match process_new_blocks(&connection_pool).await {
+                        Ok(true) => {}
+                        Ok(false) => time::sleep(POLL_INTERVAL).await,
+                        Err(err) => {
+                            vlog::error!("Can't match payment requests: {}", err);
+                            time::sleep(POLL_INTERVAL).await;
+                        }
+                    }
+                }
+            };
+            runtime.block_on(watcher_task);
+        })
+        .expect("Payment request watcher thread");
+}
@@ -0,0 +1,370 @@
+//! Statistics of the REST API routes.
+//!
+//! Every request is recorded under the pattern of its route along with the storage time spent
+//! by its handler. The latencies are reported to the `api.route.latency` and `api.route.storage`
+//! histograms, so their percentiles are exposed by the metrics endpoint.
+//!
+//! Requests are also aggregated per minute to check the service level objectives of the routes:
+//! the requests failed because of the server or served slower than the latency threshold spend
+//! the error budget of the route. The burn rates of the budget over the last minutes and hour
+//! are reported to the `api.route.slo_burn_rate` gauge, and the routes burning it too fast in
+//! both windows are reported as alerts. The per-minute statistics are stored to the database,
+//! so the private API can summarize them across all the API servers.
+
+// Built-in uses
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// External uses
+use actix_web::dev::ServiceResponse;
+use chrono::{DateTime, Utc};
+use futures::channel::mpsc;
+use tokio::{runtime::Runtime, time};
+
+// Workspace uses
+use zksync_api_types::private::ROUTE_LATENCY_BUCKETS_MS;
+use zksync_config::configs::api::{RestApiConfig, RouteSlo};
+use zksync_storage::{
+    api_route_stats::records::NewRouteStats,
+    slow_queries::profile::{profile_queries, QueryProfile},
+    ConnectionPool,
+};
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+/// Route of the requests not matching any of the registered routes.
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Interval between the flushes of the per-minute statistics.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+/// Windows (in minutes) the burn rates of the error budgets are checked over.
+const SHORT_WINDOW: usize = 5;
+const LONG_WINDOW: usize = 60;
+/// Burn rate spending the monthly error budget in two days. Routes burning the budget
+/// faster in both windows are reported as alerts.
+const ALERT_BURN_RATE: f64 = 14.4;
+/// Period the stored statistics are kept for.
+const STATS_RETENTION_DAYS: i64 = 7;
+
+/// Marker of the responses failed because of the server. Inserted into the extensions
+/// of the responses which have the successful status anyway, like the API v02 errors.
+#[derive(Debug, Clone, Copy)]
+pub struct ServerError;
+
+#[derive(Debug, Clone, PartialEq)]
+struct MinuteStats {
+    requests: u64,
+    errors: u64,
+    slow_requests: u64,
+    /// Amounts of the requests per bucket of `ROUTE_LATENCY_BUCKETS_MS`, with the overflow bucket.
+    latency_buckets: Vec<u64>,
+    storage: Duration,
+    queries: HashMap<String, Duration>,
+}
+
+impl Default for MinuteStats {
+    fn default() -> Self {
+        Self {
+            requests: 0,
+            errors: 0,
+            slow_requests: 0,
+            latency_buckets: vec![0; ROUTE_LATENCY_BUCKETS_MS.len() + 1],
+            storage: Duration::default(),
+            queries: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct RouteStats {
+    current: MinuteStats,
+    /// Amounts of the requests and the bad ones of the last minutes, the latest last.
+    history: VecDeque<(u64, u64)>,
+}
+
+impl RouteStats {
+    /// Returns the burn rate of the error budget over the last minutes.
+    fn burn_rate(&self, minutes: usize, slo: RouteSlo) -> f64 {
+        let (requests, bad_requests) = self
+            .history
+            .iter()
+            .rev()
+            .take(minutes)
+            .fold((0, 0), |(requests, bad), minute| {
+                (requests + minute.0, bad + minute.1)
+            });
+        let error_budget = (100.0 - slo.target) / 100.0;
+        if requests == 0 || error_budget <= 0.0 {
+            return 0.0;
+        }
+        bad_requests as f64 / requests as f64 / error_budget
+    }
+}
+
+/// Burn rates of the error budget of the route.
+#[derive(Debug, Clone, PartialEq)]
+struct BurnRates {
+    route: String,
+    short_window: f64,
+    long_window: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SharedRouteStats {
+    routes: Arc<Mutex<HashMap<String, RouteStats>>>,
+    default_slo: RouteSlo,
+    route_slos: Arc<HashMap<String, RouteSlo>>,
+}
+
+impl SharedRouteStats {
+    pub fn new(config: &RestApiConfig) -> Self {
+        Self {
+            routes: Arc::default(),
+            default_slo: config.default_slo(),
+            route_slos: Arc::new(config.route_slos()),
+        }
+    }
+
+    fn slo(&self, route: &str) -> RouteSlo {
+        self.route_slos
+            .get(route)
+            .copied()
+            .unwrap_or(self.default_slo)
+    }
+
+    fn record(&self, route: String, latency: Duration, server_error: bool, profile: &QueryProfile) {
+        let slow = !server_error && latency > self.slo(&route).latency_threshold;
+        let result = if server_error {
+            "error"
+        } else if slow {
+            "slow"
+        } else {
+            "ok"
+        };
+        metrics::histogram!("api.route.latency", latency, "route" => route.clone());
+        metrics::histogram!("api.route.storage", profile.total(), "route" => route.clone());
+        metrics::increment_counter!("api.route.requests", "route" => route.clone(), "result" => result);
+
+        let bucket = ROUTE_LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| latency <= Duration::from_millis(bound))
+            .unwrap_or(ROUTE_LATENCY_BUCKETS_MS.len());
+        let mut routes = self.routes.lock().unwrap();
+        let stats = &mut routes.entry(route).or_default().current;
+        stats.requests += 1;
+        stats.errors += server_error as u64;
+        stats.slow_requests += slow as u64;
+        stats.latency_buckets[bucket] += 1;
+        stats.storage += profile.total();
+        for (method, duration) in profile.methods() {
+            *stats.queries.entry(method).or_default() += duration;
+        }
+    }
+
+    /// Completes the current minute, returns the statistics of the routes requested within it
+    /// and the burn rates of all the routes.
+    fn complete_minute(&self) -> (Vec<(String, MinuteStats)>, Vec<BurnRates>) {
+        let mut routes = self.routes.lock().unwrap();
+        let mut minute_stats = Vec::new();
+        let mut burn_rates = Vec::new();
+        routes.retain(|route, stats| {
+            let current = std::mem::take(&mut stats.current);
+            stats
+                .history
+                .push_back((current.requests, current.errors + current.slow_requests));
+            if stats.history.len() > LONG_WINDOW {
+                stats.history.pop_front();
+            }
+            if current.requests > 0 {
+                minute_stats.push((route.clone(), current));
+            }
+
+            let slo = self.slo(route);
+            burn_rates.push(BurnRates {
+                route: route.clone(),
+                short_window: stats.burn_rate(SHORT_WINDOW, slo),
+                long_window: stats.burn_rate(LONG_WINDOW, slo),
+            });
+            // Routes which were not requested for the whole long window are forgotten.
+            stats.history.iter().any(|&(requests, _)| requests > 0)
+        });
+        (minute_stats, burn_rates)
+    }
+
+    /// Completes the current minute, reports the burn rates and stores the statistics.
+    async fn flush(&self, pool: &ConnectionPool, minute: DateTime<Utc>) -> anyhow::Result<()> {
+        let (minute_stats, burn_rates) = self.complete_minute();
+        for rates in burn_rates {
+            metrics::gauge!("api.route.slo_burn_rate", rates.short_window, "route" => rates.route.clone(), "window" => "short");
+            metrics::gauge!("api.route.slo_burn_rate", rates.long_window, "route" => rates.route.clone(), "window" => "long");
+            if rates.short_window > ALERT_BURN_RATE && rates.long_window > ALERT_BURN_RATE {
+                vlog::warn!(
+                    "Route {} burns its error budget too fast: {:.1}x over {} minutes, {:.1}x over {} minutes",
+                    rates.route,
+                    rates.short_window,
+                    SHORT_WINDOW,
+                    rates.long_window,
+                    LONG_WINDOW
+                );
+                metrics::increment_counter!("api.route.slo_burn_alerts", "route" => rates.route);
+            }
+        }
+
+        let stats: Vec<_> = minute_stats
+            .into_iter()
+            .map(|(route, stats)| {
+                let slo = self.slo(&route);
+                let queries = stats
+                    .queries
+                    .into_iter()
+                    .map(|(method, duration)| (method, (duration.as_millis() as u64).into()))
+                    .collect::<serde_json::Map<_, _>>();
+                NewRouteStats {
+                    route,
+                    minute,
+                    requests: stats.requests as i64,
+                    errors: stats.errors as i64,
+                    slow_requests: stats.slow_requests as i64,
+                    latency_buckets: stats
+                        .latency_buckets
+                        .into_iter()
+                        .map(|requests| requests as i64)
+                        .collect(),
+                    storage_ms: stats.storage.as_millis() as i64,
+                    queries: queries.into(),
+                    latency_threshold_ms: slo.latency_threshold.as_millis() as i64,
+                    slo_target: slo.target,
+                }
+            })
+            .collect();
+        pool.access_storage()
+            .await?
+            .api_route_stats_schema()
+            .store_route_stats(&stats)
+            .await?;
+        Ok(())
+    }
+
+    pub fn start_flusher_detached(self, panic_notify: mpsc::Sender<bool>, pool: ConnectionPool) {
+        std::thread::Builder::new()
+            .name("rest-route-stats-flusher".to_string())
+            .spawn(move || {
+                let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+
+                let runtime = Runtime::new().expect("tokio runtime creation");
+
+                let flusher_task = async move {
+                    let mut minute = Utc::now();
+                    let mut timer =
+                        time::interval_at(time::Instant::now() + FLUSH_INTERVAL, FLUSH_INTERVAL);
+                    for flushes in 1u64.. {
+                        timer.tick().await;
+                        if let Err(err) = self.flush(&pool, minute).await {
+                            vlog::error!("Can't store the statistics of the API routes: {}", err);
+                        }
+                        minute = Utc::now();
+                        // Old statistics are removed once per hour.
+                        if flushes % 60 == 0 {
+                            if let Err(err) = remove_old_stats(&pool).await {
+                                vlog::error!(
+                                    "Can't remove the old statistics of the API routes: {}",
+                                    err
+                                );
+                            }
+                        }
+                    }
+                };
+                runtime.block_on(flusher_task);
+            })
+            .expect("Route stats flusher thread");
+    }
+}
+
+async fn remove_old_stats(pool: &ConnectionPool) -> anyhow::Result<()> {
+    let before = Utc::now() - chrono::Duration::days(STATS_RETENTION_DAYS);
+    pool.access_storage()
+        .await?
+        .api_route_stats_schema()
+        .remove_route_stats(before)
+        .await?;
+    Ok(())
+}
+
+/// Serves the request, recording it to the statistics of the route.
+pub async fn record_request<B>(
+    stats: SharedRouteStats,
+    route: String,
+    response: impl Future<Output = Result<ServiceResponse<B>, actix_web::Error>>,
+) -> Result<ServiceResponse<B>, actix_web::Error> {
+    let start = Instant::now();
+    let (response, profile) = profile_queries(response).await;
+    let server_error = match &response {
+        Ok(response) => {
+            response.status().is_server_error()
+                || response.response().extensions().contains::<ServerError>()
+        }
+        Err(err) => err.as_response_error().status_code().is_server_error(),
+    };
+    stats.record(route, start.elapsed(), server_error, &profile);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_stats() {
+        let config = RestApiConfig {
+            port: 3001,
+            url: "http://127.0.0.1:3001".to_owned(),
+            max_page_size: 100,
+            endpoint_max_page_sizes: Vec::new(),
+            slo_latency_threshold: 100,
+            slo_target: 99.0,
+            route_slos: vec!["/tokens:1000:90".to_owned()],
+        };
+        let stats = SharedRouteStats::new(&config);
+        let profile = QueryProfile::default();
+
+        let ms = Duration::from_millis;
+        for _ in 0..97 {
+            stats.record("/blocks".to_owned(), ms(20), false, &profile);
+        }
+        stats.record("/blocks".to_owned(), ms(20), true, &profile);
+        stats.record("/blocks".to_owned(), ms(500), false, &profile);
+        stats.record("/blocks".to_owned(), ms(50_000), false, &profile);
+        // The latency threshold of the route is overridden.
+        stats.record("/tokens".to_owned(), ms(500), false, &profile);
+
+        let (mut minute_stats, mut burn_rates) = stats.complete_minute();
+        minute_stats.sort_by(|a, b| a.0.cmp(&b.0));
+        burn_rates.sort_by(|a, b| a.route.cmp(&b.route));
+        assert_eq!(minute_stats.len(), 2);
+        let blocks = &minute_stats[0].1;
+        assert_eq!(
+            (blocks.requests, blocks.errors, blocks.slow_requests),
+            (100, 1, 2)
+        );
+        assert_eq!(blocks.latency_buckets[2], 98);
+        assert_eq!(blocks.latency_buckets[6], 1);
+        assert_eq!(blocks.latency_buckets[12], 1);
+        assert_eq!(minute_stats[1].1.slow_requests, 0);
+
+        // 3% of the requests are bad with the 1% budget.
+        assert!((burn_rates[0].short_window - 3.0).abs() < 1e-9);
+        assert!((burn_rates[0].long_window - 3.0).abs() < 1e-9);
+        assert_eq!(burn_rates[1].short_window, 0.0);
+
+        // Idle minutes are not stored, but they are counted in the burn rates.
+        for _ in 0..SHORT_WINDOW {
+            let (minute_stats, burn_rates) = stats.complete_minute();
+            assert!(minute_stats.is_empty());
+            assert_eq!(burn_rates.len(), 2);
+        }
+        let (_, burn_rates) = stats.complete_minute();
+        assert!(burn_rates.iter().all(|rates| rates.short_window == 0.0));
+        assert!(burn_rates.iter().all(|rates| rates.long_window > 0.0));
+    }
+}
@@ -0,0 +1,129 @@
+//! Scheduler of the standing orders payments.
+//!
+//! Due payments are submitted one by one through the `TxSender`, as if they were sent
+//! by the users themselves at the scheduled times. The payment is locked in the database
+//! while it's submitted, so several API servers can run the scheduler at once.
+
+// Built-in uses
+use std::time::Duration;
+
+// External uses
+use futures::channel::mpsc;
+use tokio::{runtime::Runtime, time};
+
+// Workspace uses
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    tx::{error::TxAddError, TxEthSignature, TxEthSignatureVariant},
+    Transfer, ZkSyncTx,
+};
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+// Local uses
+use crate::api_server::tx_sender::{SubmitError, TxSender};
+
+/// Interval of checking for the due payments when there are no pending ones.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Checks whether the payment was rejected because the server can't accept it for now,
+/// rather than because of the payment itself.
+fn is_transient(err: &SubmitError) -> bool {
+    matches!(
+        err,
+        SubmitError::MaintenanceMode(_)
+            | SubmitError::MempoolCommunication(_)
+            | SubmitError::Internal(_)
+            | SubmitError::TxAdd(TxAddError::DbError)
+    )
+}
+
+/// Submits the earliest due payment. Returns `false` if there are no due payments.
+async fn process_next_payment(
+    connection_pool: &ConnectionPool,
+    tx_sender: &TxSender,
+) -> anyhow::Result<bool> {
+    let mut storage = connection_pool.access_storage().await?;
+    let mut transaction = storage.start_transaction().await?;
+    let payment = match transaction
+        .standing_orders_schema()
+        .lock_due_payment()
+        .await?
+    {
+        Some(payment) => payment,
+        None => return Ok(false),
+    };
+    let tx: Transfer = serde_json::from_value(payment.tx)?;
+    let eth_signature: Option<TxEthSignature> = payment
+        .eth_signature
+        .map(serde_json::from_value)
+        .transpose()?;
+
+    let result = tx_sender
+        .submit_tx(
+            ZkSyncTx::Transfer(Box::new(tx)),
+            TxEthSignatureVariant::Single(eth_signature),
+            None,
+            None,
+            None,
+        )
+        .await;
+    match result {
+        Ok(tx_hash) => {
+            transaction
+                .standing_orders_schema()
+                .store_payment_submitted(payment.standing_order_id, payment.sequence, tx_hash)
+                .await?;
+            metrics::increment_counter!("api.standing_orders.submitted");
+        }
+        // The payment is retried on the next poll.
+        Err(err) if is_transient(&err) => return Err(err.into()),
+        Err(err) => {
+            vlog::warn!(
+                "Payment {} of standing order {} is rejected: {}",
+                payment.sequence,
+                payment.standing_order_id,
+                err
+            );
+            transaction
+                .standing_orders_schema()
+                .store_payment_failure(
+                    payment.standing_order_id,
+                    payment.sequence,
+                    &err.to_string(),
+                )
+                .await?;
+            metrics::increment_counter!("api.standing_orders.failed");
+        }
+    }
+    transaction.commit().await?;
+    Ok(true)
+}
+
+pub fn start_standing_order_scheduler_detached(
+    panic_notify: mpsc::Sender<bool>,
+    connection_pool: ConnectionPool,
+    tx_sender: TxSender,
+) {
+    std::thread::Builder::new()
+        .name("rest-standing-order-scheduler".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+
+            let runtime = Runtime::new().expect("tokio runtime creation");
+
+            let scheduler_task = async move {
+                loop {
+                    match process_next_payment(&connection_pool, &tx_sender).await {
+                        Ok(true) => {}
+                        Ok(false) => time::sleep(POLL_INTERVAL).await,
+                        Err(err) => {
+                            vlog::error!("Can't process standing order payment: {}", err);
+                            time::sleep(POLL_INTERVAL).await;
+                        }
+                    }
+                }
+            };
+            runtime.block_on(scheduler_task);
+        })
+        .expect("Standing order scheduler thread");
+}
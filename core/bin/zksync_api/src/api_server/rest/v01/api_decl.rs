@@ -147,7 +147,7 @@ impl ApiV01 {
 
         if let Some(tx_receipt) = tx_receipt.clone() {
             // Unverified blocks can still change, so we can't cache them.
-            if tx_receipt.verified {
+            if tx_receipt.status().is_verified() {
                 self.caches
                     .transaction_receipts
                     .insert(transaction_hash, tx_receipt);
@@ -177,7 +177,7 @@ impl ApiV01 {
             })?;
 
         // Unverified blocks can still change, so we can't cache them.
-        if receipt.verified {
+        if receipt.status().is_verified() {
             self.caches.priority_op_receipts.insert(id, receipt.clone());
         }
 
@@ -1,33 +1,58 @@
 //! Account part of API implementation.
 
 // Built-in uses
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use std::time::Instant;
 
 // External uses
-use actix_web::{web, Scope};
+use actix_web::{web, HttpRequest, Scope};
 
 // Workspace uses
 use zksync_api_types::v02::{
-    account::{Account, AccountAddressOrId, AccountState, IncomingAccountTxsQuery},
+    account::{
+        Account, AccountAddressOrId, AccountNonceAt, AccountNonceQuery, AccountState,
+        AccountTxsByMemoQuery, AccountsActivityRequest, AccountsResolveRequest,
+        IncomingAccountTxsQuery, PubKeyHashAccount, ResolvedAccount,
+    },
+    fields::{shape_txs, FieldsQuery, ShapedTransaction, TxFields},
     pagination::{
-        parse_query, AccountTxsRequest, ApiEither, Paginated, PaginationQuery, PendingOpsRequest,
+        parse_query, AccountTxsRequest, ApiEither, Paginated, PaginationDirection, PaginationQuery,
+        PendingOpsRequest, MAX_LIMIT,
     },
+    proof::AccountBalanceProof,
+    signature::{MaybeSigned, SignatureQuery},
     transaction::{Transaction, TxHashSerializeWrapper},
 };
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId, TokenLike};
+use zksync_types::{
+    tx::TxHash, AccountId, Address, BlockNumber, PubKeyHash, SerialId, TokenLike, ZkSyncPriorityOp,
+};
 
 // Local uses
 use super::{
     error::{Error, InvalidDataError},
-    paginate_trait::Paginate,
+    fields::{format_tx_amounts, requested_tx_fields},
+    label::visible_labels,
+    page_size::max_page_size,
+    paginate_impl::pending_op_transaction,
+    paginate_trait::{check_pagination_limit, Paginate},
     response::ApiResult,
+    signature::{maybe_sign, ResponseSigner},
+};
+use crate::{
+    api_server::{
+        helpers::get_depositing,
+        rest::{account_resolver::SharedAccountResolver, verified_tree::SharedVerifiedTree},
+    },
+    api_try,
+    fee_ticker::PriceError,
 };
-use crate::{api_server::helpers::get_depositing, api_try, fee_ticker::PriceError};
+
+/// Maximum number of the transfers returned by the memo lookup.
+const MAX_TXS_BY_MEMO: u32 = 100;
 
 /// Shared data between `api/v02/accounts` endpoints.
 #[derive(Clone)]
@@ -35,14 +60,30 @@ struct ApiAccountData {
     pool: ConnectionPool,
     tokens: TokenDBCache,
     confirmations_for_eth_event: u64,
+    signer: Option<ResponseSigner>,
+    verified_tree: Option<SharedVerifiedTree>,
+    resolver: SharedAccountResolver,
+    response_shaping_enabled: bool,
 }
 
 impl ApiAccountData {
-    fn new(pool: ConnectionPool, tokens: TokenDBCache, confirmations_for_eth_event: u64) -> Self {
+    fn new(
+        pool: ConnectionPool,
+        tokens: TokenDBCache,
+        confirmations_for_eth_event: u64,
+        signer: Option<ResponseSigner>,
+        verified_tree: Option<SharedVerifiedTree>,
+        resolver: SharedAccountResolver,
+        response_shaping_enabled: bool,
+    ) -> Self {
         Self {
             pool,
             tokens,
             confirmations_for_eth_event,
+            signer,
+            verified_tree,
+            resolver,
+            response_shaping_enabled,
         }
     }
 
@@ -52,16 +93,11 @@ impl ApiAccountData {
     ) -> Result<Option<AccountId>, Error> {
         match account_address_or_id {
             AccountAddressOrId::Id(account_id) => Ok(Some(account_id)),
-            AccountAddressOrId::Address(address) => {
-                let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
-                let account_id = storage
-                    .chain()
-                    .account_schema()
-                    .account_id_by_address(address)
-                    .await
-                    .map_err(Error::storage)?;
-                Ok(account_id)
-            }
+            AccountAddressOrId::Address(address) => self
+                .resolver
+                .account_id(&self.pool, address)
+                .await
+                .map_err(Error::storage),
         }
     }
 
@@ -71,11 +107,9 @@ impl ApiAccountData {
     ) -> Result<Address, Error> {
         match account_address_or_id {
             AccountAddressOrId::Id(account_id) => {
-                let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
-                let address = storage
-                    .chain()
-                    .account_schema()
-                    .account_address_by_id(account_id)
+                let address = self
+                    .resolver
+                    .address(&self.pool, account_id)
                     .await
                     .map_err(Error::storage)?;
                 address.ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))
@@ -84,6 +118,67 @@ impl ApiAccountData {
         }
     }
 
+    async fn resolve_accounts(
+        &self,
+        accounts: &[AccountAddressOrId],
+    ) -> Result<Vec<Option<ResolvedAccount>>, Error> {
+        if accounts.len() > MAX_LIMIT as usize {
+            return Err(Error::from(InvalidDataError::TooManyAccounts));
+        }
+        let resolved = self
+            .resolver
+            .resolve(&self.pool, accounts)
+            .await
+            .map_err(Error::storage)?;
+        Ok(resolved
+            .into_iter()
+            .map(|account| {
+                account.map(|(account_id, address)| ResolvedAccount {
+                    account_id,
+                    address,
+                })
+            })
+            .collect())
+    }
+
+    async fn accounts_activity(&self, addresses: &[Address]) -> Result<Vec<bool>, Error> {
+        if addresses.len() > MAX_LIMIT as usize {
+            return Err(Error::from(InvalidDataError::TooManyAccounts));
+        }
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .chain()
+            .operations_ext_schema()
+            .address_has_activity(addresses)
+            .await
+            .map_err(Error::storage)
+    }
+
+    async fn accounts_by_pub_key_hash(
+        &self,
+        pub_key_hash: &str,
+    ) -> Result<Vec<PubKeyHashAccount>, Error> {
+        let pub_key_hash = PubKeyHash::from_hex(pub_key_hash)
+            .map_err(|_| Error::from(InvalidDataError::InvalidPubKeyHash))?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let accounts = storage
+            .chain()
+            .account_schema()
+            .accounts_by_pubkey_hash(&pub_key_hash)
+            .await
+            .map_err(Error::storage)?;
+        Ok(accounts
+            .into_iter()
+            .map(|account| PubKeyHashAccount {
+                account_id: AccountId(account.account_id as u32),
+                address: Address::from_slice(&account.address),
+                is_current: account.is_current,
+                first_block: BlockNumber(account.first_block as u32),
+                last_block: BlockNumber(account.last_block as u32),
+            })
+            .collect())
+    }
+
     fn parse_account_id_or_address(
         &self,
         account_address_or_id: &str,
@@ -171,18 +266,47 @@ impl ApiAccountData {
 
     async fn account_committed_info(
         &self,
-        account_id: AccountId,
-    ) -> Result<Option<Account>, Error> {
+        account_id: Option<AccountId>,
+        query: SignatureQuery,
+    ) -> Result<MaybeSigned<Option<Account>>, Error> {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
-        let ((last_block, _), account) = transaction
+        // Committed state includes the changes of all the saved blocks.
+        let last_saved_block = transaction
+            .chain()
+            .block_schema()
+            .get_last_saved_block()
+            .await
+            .map_err(Error::storage)?;
+        let account = match account_id {
+            Some(account_id) => self.committed_account(account_id, &mut transaction).await?,
+            None => None,
+        };
+        let result = maybe_sign(
+            self.signer.as_ref(),
+            &mut transaction,
+            query,
+            account,
+            last_saved_block,
+        )
+        .await?;
+        transaction.commit().await.map_err(Error::storage)?;
+        Ok(result)
+    }
+
+    async fn committed_account(
+        &self,
+        account_id: AccountId,
+        storage: &mut StorageProcessor<'_>,
+    ) -> Result<Option<Account>, Error> {
+        let ((last_block, _), account) = storage
             .chain()
             .account_schema()
             .last_committed_state_for_account(account_id)
             .await
             .map_err(Error::storage)?;
-        let result = if let Some(account) = account {
-            let last_block = transaction
+        if let Some(account) = account {
+            let last_block = storage
                 .chain()
                 .account_schema()
                 .last_committed_block_with_update_for_acc(
@@ -192,52 +316,77 @@ impl ApiAccountData {
                 .await
                 .map_err(Error::storage)?;
             Ok(Some(
-                self.api_account(account, account_id, last_block, &mut transaction)
+                self.api_account(account, account_id, last_block, storage)
                     .await?,
             ))
         } else {
             Ok(None)
-        };
-        transaction.commit().await.map_err(Error::storage)?;
-        result
+        }
     }
 
     async fn account_finalized_info(
         &self,
-        account_id: AccountId,
-    ) -> Result<Option<Account>, Error> {
+        account_id: Option<AccountId>,
+        query: SignatureQuery,
+    ) -> Result<MaybeSigned<Option<Account>>, Error> {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
-        let (last_block, account) = transaction
+        let last_verified_block = transaction
             .chain()
-            .account_schema()
-            .account_and_last_block(account_id)
+            .block_schema()
+            .get_last_verified_confirmed_block()
             .await
             .map_err(Error::storage)?;
-        let result = if let Some(account) = account {
-            Ok(Some(
-                self.api_account(
-                    account,
-                    account_id,
-                    BlockNumber(last_block as u32),
-                    &mut transaction,
-                )
-                .await?,
-            ))
-        } else {
-            Ok(None)
+        let account = match account_id {
+            Some(account_id) => {
+                let (last_block, account) = transaction
+                    .chain()
+                    .account_schema()
+                    .account_and_last_block(account_id)
+                    .await
+                    .map_err(Error::storage)?;
+                match account {
+                    Some(account) => Some(
+                        self.api_account(
+                            account,
+                            account_id,
+                            BlockNumber(last_block as u32),
+                            &mut transaction,
+                        )
+                        .await?,
+                    ),
+                    None => None,
+                }
+            }
+            None => None,
         };
+        let result = maybe_sign(
+            self.signer.as_ref(),
+            &mut transaction,
+            query,
+            account,
+            last_verified_block,
+        )
+        .await?;
         transaction.commit().await.map_err(Error::storage)?;
-        result
+        Ok(result)
     }
 
     async fn account_full_info(
         &self,
+        http_request: &HttpRequest,
         address: Address,
         account_id: Option<AccountId>,
-    ) -> Result<AccountState, Error> {
+        query: SignatureQuery,
+    ) -> Result<MaybeSigned<AccountState>, Error> {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+        let last_saved_block = transaction
+            .chain()
+            .block_schema()
+            .get_last_saved_block()
+            .await
+            .map_err(Error::storage)?;
 
         let depositing = get_depositing(
             &mut transaction,
@@ -282,12 +431,63 @@ impl ApiAccountData {
         } else {
             (None, None)
         };
-        transaction.commit().await.map_err(Error::storage)?;
-        Ok(AccountState {
+        let labels = visible_labels(&mut transaction, http_request, &[address]).await?;
+        let state = AccountState {
             depositing,
             committed,
             finalized,
-        })
+            labels,
+        };
+        let result = maybe_sign(
+            self.signer.as_ref(),
+            &mut transaction,
+            query,
+            state,
+            last_saved_block,
+        )
+        .await?;
+        transaction.commit().await.map_err(Error::storage)?;
+        Ok(result)
+    }
+
+    async fn account_balance_proof(
+        &self,
+        account_id: Option<AccountId>,
+        token_like: TokenLike,
+    ) -> Result<AccountBalanceProof, Error> {
+        let verified_tree = self
+            .verified_tree
+            .as_ref()
+            .ok_or_else(|| Error::from(InvalidDataError::MerkleProofsDisabled))?;
+        let account_id =
+            account_id.ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))?;
+        let token_id = match token_like {
+            // NFTs are not stored in the tokens cache, so the ID is used as is.
+            TokenLike::Id(token_id) => token_id,
+            token_like => {
+                let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+                self.tokens
+                    .get_token(&mut storage, token_like)
+                    .await
+                    .map_err(Error::storage)?
+                    .ok_or_else(|| {
+                        Error::from(PriceError::token_not_found("Token not found in storage"))
+                    })?
+                    .id
+            }
+        };
+
+        let verified_tree = verified_tree.read().await;
+        let verified_tree = verified_tree
+            .as_ref()
+            .ok_or_else(|| Error::from(InvalidDataError::MerkleProofsDisabled))?;
+        AccountBalanceProof::new(
+            &verified_tree.tree,
+            verified_tree.block_number,
+            account_id,
+            token_id,
+        )
+        .ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))
     }
 
     async fn account_txs(
@@ -296,6 +496,8 @@ impl ApiAccountData {
         address: Address,
         token_like: Option<TokenLike>,
         second_address: Option<Address>,
+        include_pending: bool,
+        fields: TxFields,
     ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         let token = if let Some(token_like) = token_like {
@@ -310,6 +512,10 @@ impl ApiAccountData {
         } else {
             None
         };
+        // Pending operations are newer than any executed one, so they belong to the first page only.
+        let include_pending = include_pending
+            && query.from.inner.is_right()
+            && query.direction == PaginationDirection::Older;
         let new_query = PaginationQuery {
             from: AccountTxsRequest {
                 tx_hash: query.from,
@@ -320,7 +526,109 @@ impl ApiAccountData {
             limit: query.limit,
             direction: query.direction,
         };
-        storage.paginate_checked(&new_query).await
+        let mut paginated = storage.paginate(&new_query).await?;
+        if fields.contains("memo") {
+            let tx_hashes: Vec<_> = paginated.list.iter().map(|tx| tx.tx_hash).collect();
+            let memos: HashMap<_, _> = storage
+                .tx_memos_schema()
+                .get_tx_memos(&tx_hashes)
+                .await
+                .map_err(Error::storage)?
+                .into_iter()
+                .map(|memo| (memo.tx_hash, memo.memo))
+                .collect();
+            for tx in &mut paginated.list {
+                tx.memo = memos.get(tx.tx_hash.as_ref()).cloned();
+            }
+        }
+
+        if include_pending {
+            let last_executed_serial_id = storage
+                .chain()
+                .operations_schema()
+                .get_max_priority_op_serial_id()
+                .await
+                .map_err(Error::storage)?;
+            let pending_txs = storage
+                .chain()
+                .mempool_schema()
+                .get_pending_deposits(address)
+                .await
+                .map_err(Error::storage)?
+                .into_iter()
+                .rev()
+                .filter(|op| Some(op.serial_id) > last_executed_serial_id)
+                .filter(|op| match &op.data {
+                    ZkSyncPriorityOp::Deposit(deposit) => {
+                        token.map_or(true, |token| deposit.token == token)
+                            && second_address.map_or(true, |address| deposit.from == address)
+                    }
+                    ZkSyncPriorityOp::FullExit(_) => false,
+                })
+                .map(pending_op_transaction);
+            paginated.list.splice(0..0, pending_txs);
+        }
+        Ok(paginated)
+    }
+
+    /// Returns the transfers received by the account with the given memo, the latest first.
+    async fn account_txs_by_memo(
+        &self,
+        address: Address,
+        memo: &str,
+    ) -> Result<Vec<Transaction>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let memos = storage
+            .tx_memos_schema()
+            .find_tx_memos(address, memo, MAX_TXS_BY_MEMO)
+            .await
+            .map_err(Error::storage)?;
+        let mut txs = Vec::with_capacity(memos.len());
+        for memo in memos {
+            // The memo is stored on submission, so the transaction may be rejected by the mempool.
+            if let Some(data) = storage
+                .chain()
+                .operations_ext_schema()
+                .tx_data_api_v02(&memo.tx_hash)
+                .await
+                .map_err(Error::storage)?
+            {
+                let mut tx = data.tx;
+                tx.memo = Some(memo.memo);
+                txs.push(tx);
+            }
+        }
+        Ok(txs)
+    }
+
+    /// Returns the nonce of the account after the given block. Blocks which are not
+    /// committed yet are rejected, since their transactions may still change.
+    async fn account_nonce_at(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> Result<AccountNonceAt, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let last_committed_block = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_confirmed_block()
+            .await
+            .map_err(Error::storage)?;
+        if block_number > last_committed_block {
+            return Err(Error::from(InvalidDataError::BlockNotCommitted));
+        }
+        let nonce = storage
+            .chain()
+            .account_schema()
+            .get_account_nonce_at(address, block_number)
+            .await
+            .map_err(Error::storage)?;
+        Ok(AccountNonceAt {
+            address,
+            block_number,
+            nonce,
+        })
     }
 
     /// Pending deposits can be matched only with addresses,
@@ -343,22 +651,19 @@ impl ApiAccountData {
             direction: query.direction,
         };
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
-        storage.paginate_checked(&new_query).await
+        storage.paginate(&new_query).await
     }
 }
 
 async fn account_committed_info(
     data: web::Data<ApiAccountData>,
     account_id_or_address: web::Path<String>,
-) -> ApiResult<Option<Account>> {
+    web::Query(query): web::Query<SignatureQuery>,
+) -> ApiResult<MaybeSigned<Option<Account>>> {
     let start = Instant::now();
     let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
-    let res = if let Some(account_id) = account_id {
-        data.account_committed_info(account_id).await.into()
-    } else {
-        ApiResult::Ok(None)
-    };
+    let res = data.account_committed_info(account_id, query).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_committed_info");
     res
 }
@@ -366,23 +671,22 @@ async fn account_committed_info(
 async fn account_finalized_info(
     data: web::Data<ApiAccountData>,
     account_id_or_address: web::Path<String>,
-) -> ApiResult<Option<Account>> {
+    web::Query(query): web::Query<SignatureQuery>,
+) -> ApiResult<MaybeSigned<Option<Account>>> {
     let start = Instant::now();
     let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
-    let res = if let Some(account_id) = account_id {
-        data.account_finalized_info(account_id).await.into()
-    } else {
-        ApiResult::Ok(None)
-    };
+    let res = data.account_finalized_info(account_id, query).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_finalized_info");
     res
 }
 
 async fn account_full_info(
     data: web::Data<ApiAccountData>,
+    http_request: HttpRequest,
     account_id_or_address: web::Path<String>,
-) -> ApiResult<AccountState> {
+    web::Query(query): web::Query<SignatureQuery>,
+) -> ApiResult<MaybeSigned<AccountState>> {
     let start = Instant::now();
     let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
     let address = api_try!(
@@ -390,23 +694,50 @@ async fn account_full_info(
             .await
     );
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
-    let res = data.account_full_info(address, account_id).await.into();
+    let res = data
+        .account_full_info(&http_request, address, account_id, query)
+        .await
+        .into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_full_info");
     res
 }
 
+async fn account_balance_proof(
+    data: web::Data<ApiAccountData>,
+    path: web::Path<(String, String)>,
+) -> ApiResult<AccountBalanceProof> {
+    let start = Instant::now();
+    let (account_id_or_address, token_like) = path.into_inner();
+    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = data
+        .account_balance_proof(account_id, TokenLike::parse(&token_like))
+        .await
+        .into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_balance_proof");
+    res
+}
+
 async fn account_txs(
     data: web::Data<ApiAccountData>,
+    http_request: HttpRequest,
     account_id_or_address: web::Path<String>,
     web::Query(query): web::Query<IncomingAccountTxsQuery>,
-) -> ApiResult<Paginated<Transaction, TxHashSerializeWrapper>> {
+    web::Query(fields_query): web::Query<FieldsQuery>,
+) -> ApiResult<Paginated<ShapedTransaction, TxHashSerializeWrapper>> {
     let start = Instant::now();
+    let fields = api_try!(requested_tx_fields(
+        &fields_query,
+        data.response_shaping_enabled
+    ));
     let pagination = api_try!(parse_query(PaginationQuery {
         from: query.from,
         limit: query.limit,
         direction: query.direction
     })
     .map_err(Error::from));
+    let max_page_size = api_try!(max_page_size(&http_request, "account_txs").await);
+    api_try!(check_pagination_limit(pagination.limit, max_page_size));
 
     let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
     let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
@@ -422,32 +753,116 @@ async fn account_txs(
 
     let token_like = query.token.map(|token| TokenLike::parse(&token));
 
+    let mut page = api_try!(
+        data.account_txs(
+            pagination,
+            address,
+            token_like,
+            second_address,
+            query.include_pending.unwrap_or(false),
+            fields,
+        )
+        .await
+    )
+    .shaped(fields);
+    api_try!(format_tx_amounts(&fields_query, &data.pool, &data.tokens, &mut page.list).await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_txs");
+    ApiResult::Ok(page)
+}
+
+async fn account_txs_by_memo(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+    web::Query(query): web::Query<AccountTxsByMemoQuery>,
+    web::Query(fields_query): web::Query<FieldsQuery>,
+) -> ApiResult<Vec<ShapedTransaction>> {
+    let start = Instant::now();
+    let fields = api_try!(requested_tx_fields(
+        &fields_query,
+        data.response_shaping_enabled
+    ));
+    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
+    let mut txs = shape_txs(
+        api_try!(data.account_txs_by_memo(address, &query.memo).await),
+        fields,
+    );
+    api_try!(format_tx_amounts(&fields_query, &data.pool, &data.tokens, &mut txs).await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_txs_by_memo");
+    ApiResult::Ok(txs)
+}
+
+async fn account_nonce(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+    web::Query(query): web::Query<AccountNonceQuery>,
+) -> ApiResult<AccountNonceAt> {
+    let start = Instant::now();
+    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
     let res = data
-        .account_txs(pagination, address, token_like, second_address)
+        .account_nonce_at(address, query.block_number)
         .await
         .into();
-    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_txs");
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_nonce");
     res
 }
 
 async fn account_pending_txs(
     data: web::Data<ApiAccountData>,
+    http_request: HttpRequest,
     account_id_or_address: web::Path<String>,
     web::Query(query): web::Query<PaginationQuery<String>>,
-) -> ApiResult<Paginated<Transaction, SerialId>> {
+    web::Query(fields_query): web::Query<FieldsQuery>,
+) -> ApiResult<Paginated<ShapedTransaction, SerialId>> {
     let start = Instant::now();
     let query = api_try!(parse_query(query).map_err(Error::from));
+    let max_page_size = api_try!(max_page_size(&http_request, "account_pending_txs").await);
+    api_try!(check_pagination_limit(query.limit, max_page_size));
+    let fields = api_try!(requested_tx_fields(
+        &fields_query,
+        data.response_shaping_enabled
+    ));
     let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
     let address = api_try!(
         data.get_address_by_address_or_id(address_or_id.clone())
             .await
     );
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
-    let res = data
-        .account_pending_txs(query, address, account_id)
-        .await
-        .into();
+    let mut page =
+        api_try!(data.account_pending_txs(query, address, account_id).await).shaped(fields);
+    api_try!(format_tx_amounts(&fields_query, &data.pool, &data.tokens, &mut page.list).await);
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_pending_txs");
+    ApiResult::Ok(page)
+}
+
+async fn resolve_accounts(
+    data: web::Data<ApiAccountData>,
+    web::Json(request): web::Json<AccountsResolveRequest>,
+) -> ApiResult<Vec<Option<ResolvedAccount>>> {
+    let start = Instant::now();
+    let res = data.resolve_accounts(&request.accounts).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "resolve_accounts");
+    res
+}
+
+async fn accounts_by_pub_key_hash(
+    data: web::Data<ApiAccountData>,
+    pub_key_hash: web::Path<String>,
+) -> ApiResult<Vec<PubKeyHashAccount>> {
+    let start = Instant::now();
+    let res = data.accounts_by_pub_key_hash(&pub_key_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "accounts_by_pub_key_hash");
+    res
+}
+
+async fn accounts_activity(
+    data: web::Data<ApiAccountData>,
+    web::Json(request): web::Json<AccountsActivityRequest>,
+) -> ApiResult<Vec<bool>> {
+    let start = Instant::now();
+    let res = data.accounts_activity(&request.addresses).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "accounts_activity");
     res
 }
 
@@ -455,11 +870,29 @@ pub fn api_scope(
     pool: ConnectionPool,
     tokens: TokenDBCache,
     confirmations_for_eth_event: u64,
+    signer: Option<ResponseSigner>,
+    verified_tree: Option<SharedVerifiedTree>,
+    resolver: SharedAccountResolver,
+    response_shaping_enabled: bool,
 ) -> Scope {
-    let data = ApiAccountData::new(pool, tokens, confirmations_for_eth_event);
+    let data = ApiAccountData::new(
+        pool,
+        tokens,
+        confirmations_for_eth_event,
+        signer,
+        verified_tree,
+        resolver,
+        response_shaping_enabled,
+    );
 
     web::scope("accounts")
         .app_data(web::Data::new(data))
+        .route("resolve", web::post().to(resolve_accounts))
+        .route("activity", web::post().to(accounts_activity))
+        .route(
+            "pub_key_hash/{pub_key_hash}",
+            web::get().to(accounts_by_pub_key_hash),
+        )
         .route(
             "{account_id_or_address}/committed",
             web::get().to(account_committed_info),
@@ -469,6 +902,14 @@ pub fn api_scope(
             web::get().to(account_finalized_info),
         )
         .route("{account_id_or_address}", web::get().to(account_full_info))
+        .route(
+            "{account_id_or_address}/balances/{token}/proof",
+            web::get().to(account_balance_proof),
+        )
+        .route(
+            "{account_id_or_address}/nonce",
+            web::get().to(account_nonce),
+        )
         .route(
             "{account_id_or_address}/transactions",
             web::get().to(account_txs),
@@ -477,14 +918,21 @@ pub fn api_scope(
             "{account_id_or_address}/transactions/pending",
             web::get().to(account_pending_txs),
         )
+        .route(
+            "{account_id_or_address}/transactions/memo",
+            web::get().to(account_txs_by_memo),
+        )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api_server::rest::v02::{
-        test_utils::{deserialize_response_result, TestServerConfig},
-        SharedData,
+    use crate::api_server::rest::{
+        v02::{
+            test_utils::{deserialize_response_result, TestServerConfig},
+            SharedData,
+        },
+        verified_tree::VerifiedTree,
     };
     use num::BigUint;
     use serde::Deserialize;
@@ -495,7 +943,7 @@ mod tests {
         transaction::{L1Transaction, TransactionData},
         ApiVersion,
     };
-    use zksync_storage::StorageProcessor;
+    use zksync_storage::{tx_memos::records::TxMemo, StorageProcessor};
     use zksync_types::{AccountId, Address, Deposit, PriorityOp, TokenId, ZkSyncPriorityOp, H256};
 
     // While the values of the PendingOpsFlattenRequest's fields are never directly
@@ -522,6 +970,14 @@ mod tests {
             cfg.fill_database().await?;
 
             let pool = cfg.pool.clone();
+            let (block_number, accounts) = pool
+                .access_storage()
+                .await?
+                .chain()
+                .state_schema()
+                .load_verified_state()
+                .await?;
+            let verified_tree = SharedVerifiedTree::new(VerifiedTree::new(block_number, accounts));
 
             let shared_data = SharedData {
                 net: cfg.config.chain.eth.network,
@@ -535,6 +991,10 @@ mod tests {
                             cfg.config.api.token_config.invalidate_token_cache_period(),
                         ),
                         cfg.config.eth_watch.confirmations_for_eth_event,
+                        ResponseSigner::new(&cfg.config.api.common),
+                        Some(verified_tree.clone()),
+                        SharedAccountResolver::new(cfg.config.api.common.caches_size),
+                        true,
                     )
                 },
                 Some(shared_data),
@@ -611,6 +1071,17 @@ mod tests {
             .await?;
         let account_finalized_info: Option<Account> = deserialize_response_result(response)?;
 
+        let response = client
+            .account_balance_proof(&account_id.to_string(), "0")
+            .await?;
+        let proof: AccountBalanceProof = deserialize_response_result(response)?;
+        assert_eq!(proof.account_id, account_id);
+        assert_eq!(proof.token_id, TokenId(0));
+        assert!(proof.verify());
+        let mut tampered = proof;
+        tampered.balance += 1u32;
+        assert!(!tampered.verify());
+
         {
             let mut storage = server.pool.access_storage().await?;
             storage
@@ -630,6 +1101,7 @@ mod tests {
                             eth_hash: H256::from_slice(&[0u8; 32]),
                             eth_block: 25,
                             eth_block_index: Some(1),
+                            l1_sender: None,
                         },
                         PriorityOp {
                             serial_id: 11,
@@ -643,6 +1115,7 @@ mod tests {
                             eth_hash: H256::from_slice(&[0u8; 32]),
                             eth_block: 25,
                             eth_block_index: Some(1),
+                            l1_sender: None,
                         },
                         PriorityOp {
                             serial_id: 12,
@@ -656,6 +1129,7 @@ mod tests {
                             eth_hash: H256::from_slice(&[0u8; 32]),
                             eth_block: 25,
                             eth_block_index: Some(1),
+                            l1_sender: None,
                         },
                     ],
                     false,
@@ -682,6 +1156,18 @@ mod tests {
         assert_eq!(account_full_info.finalized, account_finalized_info);
         assert_eq!(account_full_info.depositing, expected_depositing);
 
+        server
+            .pool
+            .access_storage()
+            .await?
+            .tx_memos_schema()
+            .store_tx_memos(&[TxMemo {
+                tx_hash,
+                to_address: address,
+                memo: "INV-1".to_string(),
+            }])
+            .await?;
+
         let query = PaginationQuery {
             from: ApiEither::from(tx_hash),
             limit: 1,
@@ -690,6 +1176,26 @@ mod tests {
         let response = client.account_txs(&query, &account_id.to_string()).await?;
         let txs: Paginated<Transaction, TxHash> = deserialize_response_result(response)?;
         assert_eq!(txs.list[0].tx_hash, tx_hash);
+        assert_eq!(txs.list[0].memo.as_deref(), Some("INV-1"));
+
+        let response = client
+            .account_txs_with_fields(&query, &account_id.to_string(), "txHash,status")
+            .await?;
+        let txs: Paginated<serde_json::Value, TxHash> = deserialize_response_result(response)?;
+        let tx = txs.list[0].as_object().unwrap();
+        assert_eq!(tx.len(), 2);
+        assert!(tx.contains_key("txHash") && tx.contains_key("status"));
+        let response = client
+            .account_txs_with_fields(&query, &account_id.to_string(), "txHash,body")
+            .await?;
+        assert!(response.error.is_some());
+
+        let response = client
+            .account_txs_by_memo(&format!("{:?}", address), "INV-1")
+            .await?;
+        let txs: Vec<Transaction> = deserialize_response_result(response)?;
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].tx_hash, tx_hash);
 
         let query = PaginationQuery {
             from: ApiEither::from_str("latest").unwrap(),
@@ -711,6 +1217,48 @@ mod tests {
             _ => panic!("account_pending_txs returned L2 tx"),
         }
 
+        let request = AccountsResolveRequest {
+            accounts: vec![
+                AccountAddressOrId::Address(address),
+                AccountAddressOrId::Address(Address::repeat_byte(0xff)),
+                AccountAddressOrId::Id(account_id),
+            ],
+        };
+        let response = client.resolve_accounts(&request).await?;
+        let resolved: Vec<Option<ResolvedAccount>> = deserialize_response_result(response)?;
+        let expected = Some(ResolvedAccount {
+            account_id,
+            address,
+        });
+        assert_eq!(resolved, vec![expected, None, expected]);
+
+        let request = AccountsResolveRequest {
+            accounts: vec![AccountAddressOrId::Id(account_id); MAX_LIMIT as usize + 1],
+        };
+        let response = client.resolve_accounts(&request).await?;
+        assert!(response.error.is_some());
+
+        let request = AccountsActivityRequest {
+            addresses: vec![address, Address::repeat_byte(0xff)],
+        };
+        let response = client.accounts_activity(&request).await?;
+        let activity: Vec<bool> = deserialize_response_result(response)?;
+        assert_eq!(activity, vec![true, false]);
+
+        let request = AccountsActivityRequest {
+            addresses: vec![address; MAX_LIMIT as usize + 1],
+        };
+        let response = client.accounts_activity(&request).await?;
+        assert!(response.error.is_some());
+
+        let response = client
+            .accounts_by_pub_key_hash("sync:ffffffffffffffffffffffffffffffffffffffff")
+            .await?;
+        let accounts: Vec<PubKeyHashAccount> = deserialize_response_result(response)?;
+        assert!(accounts.is_empty());
+        let response = client.accounts_by_pub_key_hash("ffff").await?;
+        assert!(response.error.is_some());
+
         server.stop().await;
         Ok(())
     }
@@ -0,0 +1,195 @@
+//! Address attestations part of API implementation.
+//!
+//! Users prove the control of their addresses by signing the attestation with them, so
+//! the operator can require it for some of the endpoints, e.g. for its KYC workflow. Only
+//! the opaque reference of the operator's records is stored alongside the attestation.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+
+// Workspace uses
+use zksync_api_types::v02::attestation::{AddressAttestation, AttestationInfo};
+use zksync_storage::address_attestations::records::StoredAddressAttestation;
+use zksync_types::Address;
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::{api_server::tx_sender::TxSender, api_try};
+
+fn api_attestation(attestation: StoredAddressAttestation) -> AttestationInfo {
+    AttestationInfo {
+        address: Address::from_slice(&attestation.address),
+        reference: attestation.reference,
+        signed_at: attestation.signed_at,
+        created_at: attestation.created_at,
+    }
+}
+
+/// Shared data between `api/v0.2/attestations` endpoints.
+#[derive(Clone)]
+struct ApiAttestationsData {
+    tx_sender: TxSender,
+}
+
+impl ApiAttestationsData {
+    fn new(tx_sender: TxSender) -> Self {
+        Self { tx_sender }
+    }
+
+    async fn attestation(&self, address: Address) -> Result<AttestationInfo, Error> {
+        if !self.tx_sender.address_attestations_enabled {
+            return Err(Error::from(InvalidDataError::AttestationsDisabled));
+        }
+        let attestation = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?
+            .address_attestations_schema()
+            .get_attestation(address)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::AttestationNotFound))?;
+        Ok(api_attestation(attestation))
+    }
+}
+
+// Server implementation
+
+async fn attest_address(
+    data: web::Data<ApiAttestationsData>,
+    Json(body): Json<AddressAttestation>,
+) -> ApiResult<AttestationInfo> {
+    let start = Instant::now();
+    let address = body.address;
+    api_try!(data
+        .tx_sender
+        .attest_address(body)
+        .await
+        .map_err(Error::from));
+    let res = data.attestation(address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "attest_address");
+    res
+}
+
+async fn attestation(
+    data: web::Data<ApiAttestationsData>,
+    address: web::Path<Address>,
+) -> ApiResult<AttestationInfo> {
+    let start = Instant::now();
+    let res = data.attestation(address.into_inner()).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "attestation");
+    res
+}
+
+pub fn api_scope(tx_sender: TxSender) -> Scope {
+    let data = ApiAttestationsData::new(tx_sender);
+
+    web::scope("attestations")
+        .app_data(web::Data::new(data))
+        .route("", web::post().to(attest_address))
+        .route("{address}", web::get().to(attestation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{
+            deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
+        },
+        SharedData,
+    };
+    use chrono::Utc;
+    use futures::channel::mpsc;
+    use zksync_api_types::v02::ApiVersion;
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{
+        tx::{PackedEthSignature, TxEthSignature},
+        ChainId,
+    };
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn attestations_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let (mempool_tx_request_sender, _mempool_tx_request_receiver) = mpsc::channel(100);
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                let mut tx_sender = TxSender::new(
+                    cfg.pool.clone(),
+                    dummy_sign_verifier(),
+                    dummy_fee_ticker(&[], None),
+                    &cfg.config.api.common,
+                    &cfg.config.api.token_config,
+                    mempool_tx_request_sender.clone(),
+                    ChainId(cfg.config.eth_client.chain_id),
+                );
+                tx_sender.address_attestations_enabled = true;
+                api_scope(tx_sender)
+            },
+            Some(shared_data),
+        );
+
+        let account = ZkSyncAccount::rand();
+        let eth_private_key = account
+            .try_get_eth_private_key()
+            .expect("Should have ETH private key");
+
+        let response = client.attestation(account.address).await?;
+        assert!(response.error.is_some());
+
+        // Reference can't be empty.
+        let mut attestation = AddressAttestation {
+            address: account.address,
+            reference: Some(String::new()),
+            timestamp: Utc::now(),
+            signature: TxEthSignature::EthereumSignature(PackedEthSignature::sign(
+                eth_private_key,
+                b"",
+            )?),
+        };
+        attestation.signature = TxEthSignature::EthereumSignature(PackedEthSignature::sign(
+            eth_private_key,
+            attestation.get_ethereum_sign_message().as_bytes(),
+        )?);
+        let response = client.attest_address(&attestation).await?;
+        assert!(response.error.is_some());
+
+        attestation.reference = Some("kyc-session-1".to_string());
+        attestation.signature = TxEthSignature::EthereumSignature(PackedEthSignature::sign(
+            eth_private_key,
+            attestation.get_ethereum_sign_message().as_bytes(),
+        )?);
+        let response = client.attest_address(&attestation).await?;
+        let info: AttestationInfo = deserialize_response_result(response)?;
+        assert_eq!(info.address, account.address);
+        assert_eq!(info.reference.as_deref(), Some("kyc-session-1"));
+
+        let response = client.attestation(account.address).await?;
+        let stored_info: AttestationInfo = deserialize_response_result(response)?;
+        assert_eq!(stored_info, info);
+
+        server.stop().await;
+        Ok(())
+    }
+}
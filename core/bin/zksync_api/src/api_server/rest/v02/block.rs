@@ -5,25 +5,38 @@ use std::str::FromStr;
 use std::time::Instant;
 
 // External uses
-use actix_web::{web, Scope};
+use actix_web::{web, HttpRequest, Scope};
 
 // Workspace uses
 use zksync_api_types::v02::{
-    block::{BlockInfo, BlockStatus},
+    block::{
+        BlockFinality, BlockInfo, BlockPubdata, BlockStatus, L1VerificationStatus,
+        PendingBlockInfo, ProofReference, PubdataOperation,
+    },
+    fields::{FieldsQuery, ShapedTransaction, ShapedTxData},
     pagination::{parse_query, ApiEither, BlockAndTxHash, Paginated, PaginationQuery},
     transaction::{Transaction, TxData, TxHashSerializeWrapper},
 };
 use zksync_crypto::{convert::FeConvert, Fr};
-use zksync_storage::{chain::block::records::StorageBlockDetails, ConnectionPool, QueryResult};
-use zksync_types::{tx::TxHash, BlockNumber, H256};
+use zksync_storage::{
+    chain::block::records::{StorageBlockDetails, StorageBlockFinality},
+    ConnectionPool,
+};
+use zksync_types::{gas_prediction::GasPredictor, tx::TxHash, BlockNumber, H256};
 
 // Local uses
 use super::{
     error::{Error, InvalidDataError},
-    paginate_trait::Paginate,
+    fields::requested_tx_fields,
+    page_size::max_page_size,
+    paginate_trait::check_pagination_limit,
     response::ApiResult,
+    storage::{BlockStorage, DbBlockStorage},
+};
+use crate::{
+    api_try,
+    utils::{block_details_cache::BlockDetailsCache, finalized_cache::FinalizedCache},
 };
-use crate::{api_try, utils::block_details_cache::BlockDetailsCache};
 
 pub fn block_info_from_details(details: StorageBlockDetails) -> BlockInfo {
     let status = if details.is_verified() {
@@ -48,35 +61,63 @@ pub fn block_info_from_details(details: StorageBlockDetails) -> BlockInfo {
     }
 }
 
+fn block_finality_from_storage(finality: StorageBlockFinality) -> BlockFinality {
+    let to_hash = |bytes: Option<Vec<u8>>| bytes.map(|bytes| H256::from_slice(&bytes));
+    let commit_tx_hash = to_hash(finality.commit_tx_hash);
+    let prove_tx_hash = to_hash(finality.prove_tx_hash);
+    let execute_tx_hash = to_hash(finality.execute_tx_hash);
+    let status = if execute_tx_hash.is_some() {
+        Some(L1VerificationStatus::Executed)
+    } else if prove_tx_hash.is_some() {
+        Some(L1VerificationStatus::Proven)
+    } else if commit_tx_hash.is_some() {
+        Some(L1VerificationStatus::Committed)
+    } else {
+        None
+    };
+    let proof = finality
+        .proof_first_block
+        .zip(finality.proof_last_block)
+        .map(|(first_block, last_block)| ProofReference {
+            first_block: BlockNumber(first_block as u32),
+            last_block: BlockNumber(last_block as u32),
+        });
+    BlockFinality {
+        block_number: BlockNumber(finality.block_number as u32),
+        commitment: H256::from_slice(&finality.commitment),
+        commit_tx_hash,
+        prove_tx_hash,
+        execute_tx_hash,
+        proof,
+        status,
+    }
+}
+
 /// Shared data between `api/v0.2/blocks` endpoints.
 #[derive(Debug, Clone)]
-struct ApiBlockData {
-    pool: ConnectionPool,
-    verified_blocks_cache: BlockDetailsCache,
+struct ApiBlockData<S> {
+    storage: S,
+    response_shaping_enabled: bool,
+    gas_predictor: GasPredictor,
 }
 
-impl ApiBlockData {
-    fn new(pool: ConnectionPool, verified_blocks_cache: BlockDetailsCache) -> Self {
+impl<S: BlockStorage> ApiBlockData<S> {
+    fn new(storage: S, response_shaping_enabled: bool, gas_predictor: GasPredictor) -> Self {
         Self {
-            pool,
-            verified_blocks_cache,
+            storage,
+            response_shaping_enabled,
+            gas_predictor,
         }
     }
 
     /// Returns information about block with the specified number.
-    ///
-    /// This method caches some of the verified blocks.
     async fn block_info(&self, block_number: BlockNumber) -> Result<Option<BlockInfo>, Error> {
         let details = self
-            .verified_blocks_cache
-            .get(&self.pool, block_number)
+            .storage
+            .block_details(block_number)
             .await
             .map_err(Error::storage)?;
-        if let Some(details) = details {
-            Ok(Some(block_info_from_details(details)))
-        } else {
-            Ok(None)
-        }
+        Ok(details.map(block_info_from_details))
     }
 
     async fn get_block_number_by_position(
@@ -88,11 +129,13 @@ impl ApiBlockData {
         } else {
             match block_position {
                 "lastCommitted" => self
-                    .get_last_committed_block_number()
+                    .storage
+                    .last_committed_block()
                     .await
                     .map_err(Error::storage),
                 "lastFinalized" => self
-                    .get_last_finalized_block_number()
+                    .storage
+                    .last_finalized_block()
                     .await
                     .map_err(Error::storage),
                 _ => Err(Error::from(InvalidDataError::InvalidBlockPosition)),
@@ -103,18 +146,19 @@ impl ApiBlockData {
     async fn block_page(
         &self,
         query: PaginationQuery<ApiEither<BlockNumber>>,
+        max_page_size: u32,
     ) -> Result<Paginated<BlockInfo, BlockNumber>, Error> {
-        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
-        storage.paginate_checked(&query).await
+        check_pagination_limit(query.limit, max_page_size)?;
+        self.storage.block_page(&query).await
     }
 
     async fn transaction_page(
         &self,
         block_number: BlockNumber,
         query: PaginationQuery<ApiEither<TxHash>>,
+        max_page_size: u32,
     ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
-        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
-
+        check_pagination_limit(query.limit, max_page_size)?;
         let new_query = PaginationQuery {
             from: BlockAndTxHash {
                 block_number,
@@ -123,8 +167,7 @@ impl ApiBlockData {
             limit: query.limit,
             direction: query.direction,
         };
-
-        storage.paginate_checked(&new_query).await
+        self.storage.block_transactions_page(&new_query).await
     }
 
     async fn tx_data(
@@ -132,51 +175,103 @@ impl ApiBlockData {
         block_number: BlockNumber,
         block_index: u64,
     ) -> Result<Option<TxData>, Error> {
-        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
-        Ok(storage
-            .chain()
-            .operations_ext_schema()
-            .tx_data_by_block_and_index_api_v02(block_number, block_index)
+        self.storage
+            .tx_data(block_number, block_index)
             .await
-            .map_err(Error::storage)?)
+            .map_err(Error::storage)
     }
 
-    async fn get_last_committed_block_number(&self) -> QueryResult<BlockNumber> {
-        let mut storage = self.pool.access_storage().await?;
-        storage
-            .chain()
-            .block_schema()
-            .get_last_committed_confirmed_block()
+    async fn block_finality(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Option<BlockFinality>, Error> {
+        let finality = self
+            .storage
+            .block_finality(block_number)
             .await
+            .map_err(Error::storage)?;
+        Ok(finality.map(block_finality_from_storage))
     }
 
-    async fn get_last_finalized_block_number(&self) -> QueryResult<BlockNumber> {
-        let mut storage = self.pool.access_storage().await?;
-        storage
-            .chain()
-            .block_schema()
-            .get_last_verified_confirmed_block()
+    /// Returns the public data of the block committed on L1, `None` if the commit transaction
+    /// is not confirmed yet.
+    async fn block_pubdata(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Option<BlockPubdata>, Error> {
+        let last_committed_block = self
+            .storage
+            .last_committed_block()
+            .await
+            .map_err(Error::storage)?;
+        if block_number > last_committed_block {
+            return Ok(None);
+        }
+        let block = self
+            .storage
+            .block(block_number)
             .await
+            .map_err(Error::storage)?;
+        Ok(block.map(|block| {
+            let operations = block
+                .get_public_data_offsets()
+                .into_iter()
+                .map(|(op, offset)| PubdataOperation {
+                    tx_hash: op.tx_hash(),
+                    block_index: op.block_index(),
+                    op_type: op.variance_name(),
+                    offset,
+                    size: op.get_eth_public_data().len() as u32,
+                })
+                .collect();
+            BlockPubdata {
+                block_number,
+                public_data: block.get_eth_public_data(),
+                operations,
+            }
+        }))
+    }
+
+    /// Returns the block which is being formed along with the prediction of its L1 gas.
+    async fn pending_block_info(&self) -> Result<Option<PendingBlockInfo>, Error> {
+        let pending_block = self.storage.pending_block().await.map_err(Error::storage)?;
+        Ok(pending_block.map(|block| {
+            let gas_prediction = self.gas_predictor.predict(
+                block
+                    .success_operations
+                    .iter()
+                    .filter_map(|op| op.get_executed_op()),
+            );
+            PendingBlockInfo {
+                block_number: block.number,
+                chunks_left: block.chunks_left as u64,
+                operations: block.success_operations.len() as u64,
+                failed_txs: block.failed_txs.len() as u64,
+                gas_prediction,
+            }
+        }))
     }
 }
 
 // Server implementation
 
-async fn block_pagination(
-    data: web::Data<ApiBlockData>,
+async fn block_pagination<S: BlockStorage>(
+    data: web::Data<ApiBlockData<S>>,
+    http_request: HttpRequest,
     web::Query(query): web::Query<PaginationQuery<String>>,
 ) -> ApiResult<Paginated<BlockInfo, BlockNumber>> {
     let start = Instant::now();
     let query = api_try!(parse_query(query).map_err(Error::from));
-    let res = data.block_page(query).await.into();
+    let max_page_size = api_try!(max_page_size(&http_request, "block_pagination").await);
+    let res = data.block_page(query, max_page_size).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_pagination");
     res
 }
 
 // TODO: take `block_position` as enum.
 // Currently actix path extractor doesn't work with enums: https://github.com/actix/actix-web/issues/318 (ZKS-628)
-async fn block_by_position(
-    data: web::Data<ApiBlockData>,
+async fn block_by_position<S: BlockStorage>(
+    data: web::Data<ApiBlockData<S>>,
     block_position: web::Path<String>,
 ) -> ApiResult<Option<BlockInfo>> {
     let start = Instant::now();
@@ -186,44 +281,108 @@ async fn block_by_position(
     res
 }
 
-async fn block_transactions(
-    data: web::Data<ApiBlockData>,
+async fn block_finality<S: BlockStorage>(
+    data: web::Data<ApiBlockData<S>>,
+    block_position: web::Path<String>,
+) -> ApiResult<Option<BlockFinality>> {
+    let start = Instant::now();
+    let block_number = api_try!(data.get_block_number_by_position(&block_position).await);
+    let res = data.block_finality(block_number).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_finality");
+    res
+}
+
+async fn block_pubdata<S: BlockStorage>(
+    data: web::Data<ApiBlockData<S>>,
+    block_position: web::Path<String>,
+) -> ApiResult<Option<BlockPubdata>> {
+    let start = Instant::now();
+    let block_number = api_try!(data.get_block_number_by_position(&block_position).await);
+    let res = data.block_pubdata(block_number).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_pubdata");
+    res
+}
+
+async fn pending_block<S: BlockStorage>(
+    data: web::Data<ApiBlockData<S>>,
+) -> ApiResult<Option<PendingBlockInfo>> {
+    let start = Instant::now();
+    let res = data.pending_block_info().await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "pending_block");
+    res
+}
+
+async fn block_transactions<S: BlockStorage>(
+    data: web::Data<ApiBlockData<S>>,
+    http_request: HttpRequest,
     block_position: web::Path<String>,
     web::Query(query): web::Query<PaginationQuery<String>>,
-) -> ApiResult<Paginated<Transaction, TxHashSerializeWrapper>> {
+    web::Query(fields): web::Query<FieldsQuery>,
+) -> ApiResult<Paginated<ShapedTransaction, TxHashSerializeWrapper>> {
     let start = Instant::now();
     let block_number = api_try!(data.get_block_number_by_position(&block_position).await);
     let query = api_try!(parse_query(query).map_err(Error::from));
-    let res = data.transaction_page(block_number, query).await.into();
+    let fields = api_try!(requested_tx_fields(&fields, data.response_shaping_enabled));
+    let max_page_size = api_try!(max_page_size(&http_request, "block_transactions").await);
+    let res = data
+        .transaction_page(block_number, query, max_page_size)
+        .await
+        .map(|page| page.shaped(fields))
+        .into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_transactions");
     res
 }
 
-async fn transaction_in_block(
-    data: web::Data<ApiBlockData>,
+async fn transaction_in_block<S: BlockStorage>(
+    data: web::Data<ApiBlockData<S>>,
     path: web::Path<(BlockNumber, u64)>,
-) -> ApiResult<Option<TxData>> {
+    web::Query(fields): web::Query<FieldsQuery>,
+) -> ApiResult<Option<ShapedTxData>> {
     let start = Instant::now();
     let (block_number, block_index) = *path;
-    let res = api_try!(data.tx_data(block_number, block_index).await);
+    let fields = api_try!(requested_tx_fields(&fields, data.response_shaping_enabled));
+    let res = api_try!(data.tx_data(block_number, block_index).await).map(|data| ShapedTxData {
+        data,
+        fields,
+        amounts: None,
+    });
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "transaction_in_block");
     ApiResult::Ok(res)
 }
 
-pub fn api_scope(pool: ConnectionPool, cache: BlockDetailsCache) -> Scope {
-    let data = ApiBlockData::new(pool, cache);
+pub fn api_scope(
+    pool: ConnectionPool,
+    cache: BlockDetailsCache,
+    finalized_cache: FinalizedCache,
+    response_shaping_enabled: bool,
+    gas_predictor: GasPredictor,
+) -> Scope {
+    let storage = DbBlockStorage::new(pool, cache, finalized_cache);
+    let data = ApiBlockData::new(storage, response_shaping_enabled, gas_predictor);
 
     web::scope("blocks")
         .app_data(web::Data::new(data))
-        .route("", web::get().to(block_pagination))
-        .route("{block_position}", web::get().to(block_by_position))
+        .route("", web::get().to(block_pagination::<DbBlockStorage>))
+        .route("pending", web::get().to(pending_block::<DbBlockStorage>))
+        .route(
+            "{block_position}",
+            web::get().to(block_by_position::<DbBlockStorage>),
+        )
+        .route(
+            "{block_position}/finality",
+            web::get().to(block_finality::<DbBlockStorage>),
+        )
+        .route(
+            "{block_position}/pubdata",
+            web::get().to(block_pubdata::<DbBlockStorage>),
+        )
         .route(
             "{block_position}/transactions",
-            web::get().to(block_transactions),
+            web::get().to(block_transactions::<DbBlockStorage>),
         )
         .route(
             "{block_position}/transactions/{block_index}",
-            web::get().to(transaction_in_block),
+            web::get().to(transaction_in_block::<DbBlockStorage>),
         )
 }
 
@@ -231,12 +390,226 @@ pub fn api_scope(pool: ConnectionPool, cache: BlockDetailsCache) -> Scope {
 mod tests {
     use super::*;
     use crate::api_server::rest::v02::{
-        test_utils::{deserialize_response_result, TestServerConfig},
+        error::ErrorCode,
+        paginate_trait::Paginate,
+        test_utils::{deserialize_response_result, MockBlockStorage, TestServerConfig},
         SharedData,
     };
-    use zksync_api_types::v02::{
-        pagination::PaginationDirection, transaction::TransactionData, ApiVersion,
+    use std::time::Duration;
+    use zksync_api_types::{
+        v02::{
+            pagination::{Latest, PaginationDirection, MAX_LIMIT},
+            transaction::TransactionData,
+            ApiVersion,
+        },
+        Either,
     };
+    use zksync_crypto::params::CHUNK_BYTES;
+    use zksync_types::{
+        block::{ExecutedOperations, ExecutedPriorityOp, PendingBlock},
+        AccountId, Deposit, DepositOp, PriorityOp, TokenId, ZkSyncOp, ZkSyncPriorityOp,
+    };
+
+    fn latest<T: serde::Serialize>() -> ApiEither<T> {
+        ApiEither {
+            inner: Either::Right(Latest),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn block_positions() {
+        let data = ApiBlockData::new(MockBlockStorage::new(5, 3), true, GasPredictor::default());
+        for (position, expected) in &[("4", 4), ("lastCommitted", 5), ("lastFinalized", 3)] {
+            let block_number = data.get_block_number_by_position(position).await.unwrap();
+            assert_eq!(block_number, BlockNumber(*expected), "{}", position);
+        }
+        let err = data
+            .get_block_number_by_position("latest")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidBlockPosition);
+
+        let block = data.block_info(BlockNumber(3)).await.unwrap().unwrap();
+        assert_eq!(block.status, BlockStatus::Finalized);
+        let block = data.block_info(BlockNumber(4)).await.unwrap().unwrap();
+        assert_eq!(block.status, BlockStatus::Committed);
+        assert!(data.block_info(BlockNumber(6)).await.unwrap().is_none());
+    }
+
+    #[actix_rt::test]
+    async fn block_pages() {
+        let data = ApiBlockData::new(MockBlockStorage::new(5, 3), true, GasPredictor::default());
+        let query = PaginationQuery {
+            from: latest(),
+            limit: 2,
+            direction: PaginationDirection::Older,
+        };
+        let page = data.block_page(query, MAX_LIMIT).await.unwrap();
+        let numbers: Vec<_> = page.list.iter().map(|block| block.block_number).collect();
+        assert_eq!(numbers, vec![BlockNumber(5), BlockNumber(4)]);
+        assert_eq!(page.pagination.from, BlockNumber(5));
+        assert_eq!(page.pagination.count, 5);
+
+        // The configured limit is enforced instead of the default one.
+        let query = PaginationQuery {
+            from: latest(),
+            limit: 2,
+            direction: PaginationDirection::Older,
+        };
+        let err = data.block_page(query, 1).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::PaginationLimitTooBig);
+
+        let query = PaginationQuery {
+            from: ApiEither::from(BlockNumber(1)),
+            limit: MAX_LIMIT + 1,
+            direction: PaginationDirection::Newer,
+        };
+        let err = data.block_page(query, MAX_LIMIT).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::PaginationLimitTooBig);
+
+        let query = PaginationQuery {
+            from: latest(),
+            limit: MAX_LIMIT + 1,
+            direction: PaginationDirection::Older,
+        };
+        let err = data
+            .transaction_page(BlockNumber(1), query, MAX_LIMIT)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::PaginationLimitTooBig);
+    }
+
+    #[actix_rt::test]
+    async fn pending_block_prediction() {
+        let data = ApiBlockData::new(MockBlockStorage::new(5, 3), true, GasPredictor::default());
+        assert!(data.pending_block_info().await.unwrap().is_none());
+
+        let deposit = Deposit {
+            from: Default::default(),
+            token: TokenId(0),
+            amount: Default::default(),
+            to: Default::default(),
+        };
+        let op = ZkSyncOp::from(DepositOp {
+            priority_op: deposit.clone(),
+            account_id: AccountId(1),
+        });
+        let executed_op = ExecutedOperations::PriorityOp(Box::new(ExecutedPriorityOp {
+            priority_op: PriorityOp {
+                serial_id: 0,
+                data: ZkSyncPriorityOp::Deposit(deposit),
+                deadline_block: 0,
+                eth_hash: Default::default(),
+                eth_block: 0,
+                eth_block_index: None,
+                l1_sender: None,
+            },
+            op: op.clone(),
+            block_index: 0,
+            created_at: chrono::Utc::now(),
+        }));
+        let storage = MockBlockStorage {
+            pending_block: Some(PendingBlock {
+                number: BlockNumber(6),
+                chunks_left: 10,
+                unprocessed_priority_op_before: 0,
+                pending_block_iteration: 0,
+                success_operations: vec![executed_op.clone(), executed_op],
+                failed_txs: Vec::new(),
+                timestamp: 0,
+            }),
+            ..MockBlockStorage::new(5, 3)
+        };
+        let data = ApiBlockData::new(storage, true, GasPredictor::default());
+
+        let info = data.pending_block_info().await.unwrap().unwrap();
+        assert_eq!(info.block_number, BlockNumber(6));
+        assert_eq!(info.chunks_left, 10);
+        assert_eq!(info.operations, 2);
+        assert_eq!(
+            info.gas_prediction,
+            GasPredictor::default().predict(&[op.clone(), op])
+        );
+    }
+
+    #[actix_rt::test]
+    async fn block_finality_status() {
+        let data = ApiBlockData::new(MockBlockStorage::new(5, 3), true, GasPredictor::default());
+
+        let finality = data.block_finality(BlockNumber(3)).await.unwrap().unwrap();
+        assert_eq!(finality.status, Some(L1VerificationStatus::Executed));
+        assert_eq!(
+            finality.proof,
+            Some(ProofReference {
+                first_block: BlockNumber(3),
+                last_block: BlockNumber(3),
+            })
+        );
+
+        let finality = data.block_finality(BlockNumber(4)).await.unwrap().unwrap();
+        assert_eq!(finality.status, Some(L1VerificationStatus::Committed));
+        assert!(finality.prove_tx_hash.is_none());
+        assert!(finality.proof.is_none());
+        assert!(data.block_finality(BlockNumber(6)).await.unwrap().is_none());
+
+        // The block is proven but not executed yet.
+        let finality = block_finality_from_storage(StorageBlockFinality {
+            block_number: 4,
+            commitment: vec![0; 32],
+            commit_tx_hash: Some(vec![1; 32]),
+            prove_tx_hash: Some(vec![2; 32]),
+            execute_tx_hash: None,
+            proof_first_block: Some(4),
+            proof_last_block: Some(5),
+        });
+        assert_eq!(finality.status, Some(L1VerificationStatus::Proven));
+        assert_eq!(finality.prove_tx_hash, Some(H256::repeat_byte(2)));
+    }
+
+    #[actix_rt::test]
+    async fn block_pubdata_of_committed_blocks() {
+        let data = ApiBlockData::new(MockBlockStorage::new(5, 3), true, GasPredictor::default());
+
+        let pubdata = data.block_pubdata(BlockNumber(5)).await.unwrap().unwrap();
+        assert_eq!(pubdata.block_number, BlockNumber(5));
+        assert_eq!(pubdata.public_data, vec![0; 10 * CHUNK_BYTES]);
+        assert!(pubdata.operations.is_empty());
+        assert!(data.block_pubdata(BlockNumber(6)).await.unwrap().is_none());
+    }
+
+    #[actix_rt::test]
+    async fn storage_errors() {
+        let storage = MockBlockStorage {
+            unavailable: true,
+            ..MockBlockStorage::new(5, 3)
+        };
+        let data = ApiBlockData::new(storage, true, GasPredictor::default());
+
+        // Positions given by numbers don't need the storage.
+        assert!(data.get_block_number_by_position("1").await.is_ok());
+        let err = data
+            .get_block_number_by_position("lastCommitted")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::StorageError);
+        let err = data.block_info(BlockNumber(1)).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::StorageError);
+        let query = PaginationQuery {
+            from: latest(),
+            limit: 10,
+            direction: PaginationDirection::Older,
+        };
+        let err = data.block_page(query, MAX_LIMIT).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::StorageError);
+        let err = data.tx_data(BlockNumber(1), 0).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::StorageError);
+        let err = data.pending_block_info().await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::StorageError);
+        let err = data.block_finality(BlockNumber(1)).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::StorageError);
+        let err = data.block_pubdata(BlockNumber(1)).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::StorageError);
+    }
 
     #[actix_rt::test]
     #[cfg_attr(
@@ -252,7 +625,15 @@ mod tests {
             api_version: ApiVersion::V02,
         };
         let (client, server) = cfg.start_server(
-            |cfg: &TestServerConfig| api_scope(cfg.pool.clone(), BlockDetailsCache::new(10)),
+            |cfg: &TestServerConfig| {
+                api_scope(
+                    cfg.pool.clone(),
+                    BlockDetailsCache::new(10),
+                    FinalizedCache::new(cfg.pool.clone(), 10, Duration::from_secs(1), None),
+                    true,
+                    cfg.config.chain.gas_prediction.predictor(),
+                )
+            },
             Some(shared_data),
         );
 
@@ -264,7 +645,7 @@ mod tests {
         let expected_blocks: Paginated<BlockInfo, BlockNumber> = {
             let mut storage = cfg.pool.access_storage().await?;
             storage
-                .paginate_checked(&query)
+                .paginate_checked(&query, MAX_LIMIT)
                 .await
                 .map_err(|err| anyhow::anyhow!(err.message))?
         };
@@ -273,6 +654,36 @@ mod tests {
         let block: BlockInfo = deserialize_response_result(response)?;
         assert_eq!(block, expected_blocks.list[1]);
 
+        let response = client.block_finality("1").await?;
+        let finality: BlockFinality = deserialize_response_result(response)?;
+        assert_eq!(finality.block_number, BlockNumber(1));
+        assert_eq!(
+            finality.commit_tx_hash,
+            expected_blocks.list[0].commit_tx_hash
+        );
+        assert_eq!(
+            finality.execute_tx_hash,
+            expected_blocks.list[0].verify_tx_hash
+        );
+
+        let response = client.block_pubdata("3").await?;
+        let pubdata: BlockPubdata = deserialize_response_result(response)?;
+        let expected_block = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .chain()
+                .block_schema()
+                .get_block(BlockNumber(3))
+                .await?
+                .unwrap()
+        };
+        assert_eq!(pubdata.public_data, expected_block.get_eth_public_data());
+        for operation in &pubdata.operations {
+            let start = operation.offset as usize;
+            let end = start + operation.size as usize;
+            assert!(end <= pubdata.public_data.len());
+        }
+
         let response = client.block_pagination(&query).await?;
         let paginated: Paginated<BlockInfo, BlockNumber> = deserialize_response_result(response)?;
         assert_eq!(paginated, expected_blocks);
@@ -13,7 +13,7 @@ use zksync_config::ZkSyncConfig;
 use zksync_types::{network::Network, Address};
 
 // Local uses
-use super::response::ApiResult;
+use super::{response::ApiResult, signature::ResponseSigner};
 
 /// Shared data between `api/v0.2/config` endpoints.
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,6 +24,8 @@ pub struct ApiConfigData {
     gov_contract: Address,
     deposit_confirmations: u64,
     zksync_version: ZksyncVersion,
+    /// Address of the key signing the responses, `None` if the signing is disabled.
+    response_signer: Option<Address>,
     // TODO: server_version (ZKS-627)
 }
 
@@ -35,6 +37,7 @@ impl ApiConfigData {
             gov_contract: config.contracts.governance_addr,
             deposit_confirmations: config.eth_watch.confirmations_for_eth_event,
             zksync_version: ZksyncVersion::ContractV4,
+            response_signer: ResponseSigner::new(&config.api.common).map(|signer| signer.address()),
         }
     }
 }
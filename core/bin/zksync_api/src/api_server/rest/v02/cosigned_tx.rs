@@ -0,0 +1,283 @@
+//! Co-signed transactions part of API implementation.
+//!
+//! Transactions signed by the account owner are held by the server until the required number
+//! of the co-signers have approved them, and are submitted as soon as the threshold is met.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+use chrono::Utc;
+
+// Workspace uses
+use zksync_api_types::v02::cosigned_tx::{
+    CoSignedTxInfo, CoSignedTxStatus, CoSigningPolicy, IncomingCoSignature, IncomingCoSignedTx,
+};
+use zksync_storage::cosigned_txs::records::{StoredCoSignature, StoredCoSignedTx};
+use zksync_types::{tx::TxHash, AccountId, Address};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::{api_server::tx_sender::TxSender, api_try};
+
+fn api_cosigned_tx(
+    tx: StoredCoSignedTx,
+    cosignatures: Vec<StoredCoSignature>,
+) -> Result<CoSignedTxInfo, Error> {
+    let status = if tx.submitted_at.is_some() {
+        CoSignedTxStatus::Submitted
+    } else if tx.expires_at <= Utc::now() {
+        CoSignedTxStatus::Expired
+    } else {
+        CoSignedTxStatus::Pending
+    };
+    let tx_hash = TxHash::from_slice(&tx.tx_hash)
+        .ok_or_else(|| Error::storage("invalid co-signed transaction hash is stored"))?;
+
+    Ok(CoSignedTxInfo {
+        tx_hash,
+        account_id: AccountId(tx.account_id as u32),
+        policy: CoSigningPolicy {
+            cosigners: tx
+                .cosigners
+                .iter()
+                .map(|cosigner| Address::from_slice(cosigner))
+                .collect(),
+            threshold: tx.threshold as u32,
+        },
+        cosigned_by: cosignatures
+            .iter()
+            .map(|cosignature| Address::from_slice(&cosignature.signer))
+            .collect(),
+        status,
+        error: tx.error,
+        expires_at: tx.expires_at,
+        submitted_at: tx.submitted_at,
+        created_at: tx.created_at,
+    })
+}
+
+/// Shared data between `api/v0.2/cosigned_txs` endpoints.
+#[derive(Clone)]
+struct ApiCoSignedTxsData {
+    tx_sender: TxSender,
+}
+
+impl ApiCoSignedTxsData {
+    fn new(tx_sender: TxSender) -> Self {
+        Self { tx_sender }
+    }
+
+    fn check_enabled(&self) -> Result<(), Error> {
+        if self.tx_sender.cosigned_txs_enabled {
+            Ok(())
+        } else {
+            Err(Error::from(InvalidDataError::CoSignedTxsDisabled))
+        }
+    }
+
+    async fn cosigned_tx(&self, tx_hash: TxHash) -> Result<CoSignedTxInfo, Error> {
+        self.check_enabled()?;
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let tx = storage
+            .cosigned_txs_schema()
+            .get_cosigned_tx(tx_hash)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::CoSignedTxNotFound))?;
+        let cosignatures = storage
+            .cosigned_txs_schema()
+            .get_cosignatures(tx_hash)
+            .await
+            .map_err(Error::storage)?;
+        api_cosigned_tx(tx, cosignatures)
+    }
+}
+
+// Server implementation
+
+async fn create_cosigned_tx(
+    data: web::Data<ApiCoSignedTxsData>,
+    Json(body): Json<IncomingCoSignedTx>,
+) -> ApiResult<CoSignedTxInfo> {
+    let start = Instant::now();
+    let tx_hash = api_try!(data
+        .tx_sender
+        .create_cosigned_tx(body)
+        .await
+        .map_err(Error::from));
+    let res = data.cosigned_tx(tx_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "create_cosigned_tx");
+    res
+}
+
+async fn cosign_tx(
+    data: web::Data<ApiCoSignedTxsData>,
+    tx_hash: web::Path<TxHash>,
+    Json(body): Json<IncomingCoSignature>,
+) -> ApiResult<CoSignedTxInfo> {
+    let start = Instant::now();
+    let tx_hash = tx_hash.into_inner();
+    api_try!(data
+        .tx_sender
+        .cosign_tx(tx_hash, body.signature)
+        .await
+        .map_err(Error::from));
+    let res = data.cosigned_tx(tx_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "cosign_tx");
+    res
+}
+
+async fn cosigned_tx(
+    data: web::Data<ApiCoSignedTxsData>,
+    tx_hash: web::Path<TxHash>,
+) -> ApiResult<CoSignedTxInfo> {
+    let start = Instant::now();
+    let res = data.cosigned_tx(tx_hash.into_inner()).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "cosigned_tx");
+    res
+}
+
+pub fn api_scope(tx_sender: TxSender) -> Scope {
+    let data = ApiCoSignedTxsData::new(tx_sender);
+
+    web::scope("cosigned_txs")
+        .app_data(web::Data::new(data))
+        .route("", web::post().to(create_cosigned_tx))
+        .route("{tx_hash}", web::get().to(cosigned_tx))
+        .route("{tx_hash}/signatures", web::post().to(cosign_tx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{
+            deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
+        },
+        SharedData,
+    };
+    use chrono::Duration;
+    use futures::channel::mpsc;
+    use num::BigUint;
+    use zksync_api_types::v02::ApiVersion;
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{
+        tx::{PackedEthSignature, TxEthSignature, TxEthSignatureVariant},
+        ChainId, Nonce, TokenId, ZkSyncTx,
+    };
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn cosigned_txs_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let (mempool_tx_request_sender, _mempool_tx_request_receiver) = mpsc::channel(100);
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                let mut tx_sender = TxSender::new(
+                    cfg.pool.clone(),
+                    dummy_sign_verifier(),
+                    dummy_fee_ticker(&[], None),
+                    &cfg.config.api.common,
+                    &cfg.config.api.token_config,
+                    mempool_tx_request_sender.clone(),
+                    ChainId(cfg.config.eth_client.chain_id),
+                );
+                tx_sender.cosigned_txs_enabled = true;
+                api_scope(tx_sender)
+            },
+            Some(shared_data),
+        );
+
+        let account = ZkSyncAccount::rand();
+        account.set_account_id(Some(AccountId(1)));
+        let (transfer, _) = account.sign_transfer(
+            TokenId(0),
+            "ETH",
+            BigUint::from(100u32),
+            BigUint::from(1u32),
+            &Address::repeat_byte(0xff),
+            Some(Nonce(0)),
+            false,
+            Default::default(),
+        );
+        let tx = ZkSyncTx::Transfer(Box::new(transfer));
+        let tx_hash = tx.hash();
+        let cosigner = ZkSyncAccount::rand();
+        let cosigner_private_key = cosigner
+            .try_get_eth_private_key()
+            .expect("Should have ETH private key");
+
+        // Threshold can't be greater than the number of the co-signers.
+        let mut request = IncomingCoSignedTx {
+            tx,
+            signature: TxEthSignatureVariant::Single(None),
+            policy: CoSigningPolicy {
+                cosigners: vec![cosigner.address],
+                threshold: 2,
+            },
+            expires_at: Utc::now() + Duration::hours(1),
+        };
+        let response = client.create_cosigned_tx(&request).await?;
+        assert!(response.error.is_some());
+
+        request.policy.threshold = 1;
+        let response = client.create_cosigned_tx(&request).await?;
+        let info: CoSignedTxInfo = deserialize_response_result(response)?;
+        assert_eq!(info.tx_hash, tx_hash);
+        assert_eq!(info.account_id, AccountId(1));
+        assert_eq!(info.status, CoSignedTxStatus::Pending);
+        assert!(info.cosigned_by.is_empty());
+
+        // Only the co-signers from the policy can sign the transaction.
+        let message = IncomingCoSignature::get_ethereum_sign_message(tx_hash);
+        let stranger_signature = TxEthSignature::EthereumSignature(PackedEthSignature::sign(
+            account
+                .try_get_eth_private_key()
+                .expect("Should have ETH private key"),
+            message.as_bytes(),
+        )?);
+        let response = client.cosign_tx(tx_hash, stranger_signature).await?;
+        assert!(response.error.is_some());
+
+        let response = client.cosigned_tx(tx_hash).await?;
+        let info: CoSignedTxInfo = deserialize_response_result(response)?;
+        assert_eq!(info.policy.cosigners, vec![cosigner.address]);
+        assert!(info.cosigned_by.is_empty());
+
+        let signature = TxEthSignature::EthereumSignature(PackedEthSignature::sign(
+            cosigner_private_key,
+            message.as_bytes(),
+        )?);
+        // The submission is expected to be rejected, since the account isn't funded,
+        // but the co-signature is kept.
+        client.cosign_tx(tx_hash, signature).await?;
+        let response = client.cosigned_tx(tx_hash).await?;
+        let info: CoSignedTxInfo = deserialize_response_result(response)?;
+        assert_eq!(info.cosigned_by, vec![cosigner.address]);
+
+        server.stop().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+//! CREATE2 factories part of API implementation.
+//!
+//! Smart wallets deployed with CREATE2 set their signing keys without the Ethereum signature,
+//! so the operator may restrict them to the factory and code hash pairs of the known vendors.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_types::v02::create2_factory::{Create2Factories, Create2Factory};
+use zksync_storage::create2_factories::records::StoredCreate2Factory;
+use zksync_types::{Address, H256};
+
+// Local uses
+use super::{error::Error, response::ApiResult};
+use crate::api_server::tx_sender::TxSender;
+
+fn api_create2_factory(factory: StoredCreate2Factory) -> Create2Factory {
+    Create2Factory {
+        creator_address: Address::from_slice(&factory.creator_address),
+        code_hash: H256::from_slice(&factory.code_hash),
+        name: factory.name,
+    }
+}
+
+/// Shared data between `api/v0.2/create2_factories` endpoints.
+#[derive(Clone)]
+struct ApiCreate2FactoriesData {
+    tx_sender: TxSender,
+}
+
+impl ApiCreate2FactoriesData {
+    fn new(tx_sender: TxSender) -> Self {
+        Self { tx_sender }
+    }
+
+    async fn create2_factories(&self) -> Result<Create2Factories, Error> {
+        let factories = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?
+            .create2_factories_schema()
+            .load_factories()
+            .await
+            .map_err(Error::storage)?;
+        Ok(Create2Factories {
+            registry_enabled: self.tx_sender.create2_factory_registry_enabled,
+            factories: factories.into_iter().map(api_create2_factory).collect(),
+        })
+    }
+}
+
+// Server implementation
+
+async fn create2_factories(
+    data: web::Data<ApiCreate2FactoriesData>,
+) -> ApiResult<Create2Factories> {
+    let start = Instant::now();
+    let res = data.create2_factories().await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "create2_factories");
+    res
+}
+
+pub fn api_scope(tx_sender: TxSender) -> Scope {
+    let data = ApiCreate2FactoriesData::new(tx_sender);
+
+    web::scope("create2_factories")
+        .app_data(web::Data::new(data))
+        .route("", web::get().to(create2_factories))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{
+            deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
+        },
+        SharedData,
+    };
+    use futures::channel::mpsc;
+    use zksync_api_types::v02::ApiVersion;
+    use zksync_types::ChainId;
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn create2_factories_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let (mempool_tx_request_sender, _mempool_tx_request_receiver) = mpsc::channel(100);
+
+        let factory = Create2Factory {
+            creator_address: Address::repeat_byte(0x21),
+            code_hash: H256::repeat_byte(0x22),
+            name: "Vendor wallet".to_string(),
+        };
+        cfg.pool
+            .access_storage()
+            .await?
+            .create2_factories_schema()
+            .store_factory(
+                factory.creator_address,
+                factory.code_hash,
+                &factory.name,
+                "admin",
+            )
+            .await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                let mut tx_sender = TxSender::new(
+                    cfg.pool.clone(),
+                    dummy_sign_verifier(),
+                    dummy_fee_ticker(&[], None),
+                    &cfg.config.api.common,
+                    &cfg.config.api.token_config,
+                    mempool_tx_request_sender.clone(),
+                    ChainId(cfg.config.eth_client.chain_id),
+                );
+                tx_sender.create2_factory_registry_enabled = true;
+                api_scope(tx_sender)
+            },
+            Some(shared_data),
+        );
+
+        let response = client.create2_factories().await?;
+        let factories: Create2Factories = deserialize_response_result(response)?;
+        assert!(factories.registry_enabled);
+        assert!(factories.factories.contains(&factory));
+
+        server.stop().await;
+        Ok(())
+    }
+}
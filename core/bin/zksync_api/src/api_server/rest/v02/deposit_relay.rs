@@ -0,0 +1,374 @@
+//! Deposit relay part of API implementation.
+//!
+//! Users without ETH on L1 sign the ERC20 permit for the relayer account instead of depositing
+//! the tokens themselves. The jobs are stored here and processed by the `deposit_relayer`
+//! module, which charges the fee from the deposited amount.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+use bigdecimal::BigDecimal;
+use chrono::{Duration, TimeZone, Utc};
+use num::{BigUint, Zero};
+
+// Workspace uses
+use zksync_api_types::v02::deposit_relay::{
+    DepositRelayFee, DepositRelayJobInfo, DepositRelayStatus, IncomingDepositRelay,
+};
+use zksync_config::DepositRelayerConfig;
+use zksync_storage::{deposit_relay::records::StoredDepositRelayJob, ConnectionPool};
+use zksync_token_db_cache::TokenDBCache;
+use zksync_types::{Address, Token, TokenId, TokenLike, H256};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::fee_ticker::{FeeTicker, PriceError};
+
+/// Shared data between `api/v0.2/deposit_relays` endpoints.
+#[derive(Clone)]
+struct ApiDepositRelayData {
+    /// Jobs are stored, so the main database connection is used.
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    ticker: FeeTicker,
+    config: DepositRelayerConfig,
+}
+
+impl ApiDepositRelayData {
+    fn new(
+        pool: ConnectionPool,
+        tokens: TokenDBCache,
+        ticker: FeeTicker,
+        config: DepositRelayerConfig,
+    ) -> Self {
+        Self {
+            pool,
+            tokens,
+            ticker,
+            config,
+        }
+    }
+
+    fn check_enabled(&self) -> Result<(), Error> {
+        if self.config.enabled {
+            Ok(())
+        } else {
+            Err(Error::from(InvalidDataError::DepositRelayDisabled))
+        }
+    }
+
+    /// Loads the token, which should be an ERC20 token, since ETH can't be permitted.
+    async fn relayed_token(&self, token: TokenId) -> Result<Token, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let token = self
+            .tokens
+            .get_token(&mut storage, TokenLike::Id(token))
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| {
+                Error::from(PriceError::token_not_found("Token not found in storage"))
+            })?;
+        if token.id == TokenId(0) || token.is_nft {
+            return Err(Error::from(InvalidDataError::InvalidDepositRelay(
+                "only ERC20 tokens can be relayed".to_string(),
+            )));
+        }
+        Ok(token)
+    }
+
+    async fn relay_fee(&self, token: TokenId) -> Result<BigUint, Error> {
+        self.ticker
+            .get_l1_gas_cost_in_token(TokenLike::Id(token), self.config.relay_gas_amount)
+            .await
+            .map_err(Error::from)
+    }
+
+    fn api_job(job: StoredDepositRelayJob) -> Result<DepositRelayJobInfo, Error> {
+        let to_biguint = |value: &BigDecimal| {
+            value
+                .to_bigint()
+                .and_then(|value| value.to_biguint())
+                .ok_or_else(|| Error::storage("invalid deposit relay amount is stored"))
+        };
+
+        Ok(DepositRelayJobInfo {
+            job_id: job.id as u64,
+            owner: Address::from_slice(&job.owner),
+            token: TokenId(job.token_id as u32),
+            amount: to_biguint(&job.amount)?,
+            fee: to_biguint(&job.fee)?,
+            status: DepositRelayStatus::parse(&job.status)
+                .ok_or_else(|| Error::storage("invalid deposit relay status is stored"))?,
+            eth_tx_hash: job.eth_tx_hash.as_deref().map(H256::from_slice),
+            error: job.error,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+        })
+    }
+
+    async fn fee(&self, token: TokenId) -> Result<DepositRelayFee, Error> {
+        self.check_enabled()?;
+        let token = self.relayed_token(token).await?;
+        let fee = self.relay_fee(token.id).await?;
+        Ok(DepositRelayFee {
+            token: token.id,
+            fee,
+            relayer: self.config.sender_address,
+        })
+    }
+
+    async fn create_job(
+        &self,
+        request: IncomingDepositRelay,
+    ) -> Result<DepositRelayJobInfo, Error> {
+        self.check_enabled()?;
+        if request.amount.is_zero() {
+            return Err(Error::from(InvalidDataError::InvalidDepositRelay(
+                "amount should be positive".to_string(),
+            )));
+        }
+        let now = Utc::now();
+        let deadline = Utc.timestamp_opt(request.deadline as i64, 0).single();
+        let min_deadline = now
+            + Duration::from_std(self.config.min_permit_ttl())
+                .expect("permit TTL should fit into the chrono duration");
+        let deadline = match deadline {
+            Some(deadline) if deadline >= min_deadline => deadline,
+            _ => {
+                return Err(Error::from(InvalidDataError::InvalidDepositRelay(format!(
+                    "permit should be valid for at least {} seconds",
+                    self.config.min_permit_ttl
+                ))));
+            }
+        };
+        let token = self.relayed_token(request.token).await?;
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        // Relayer only helps with the first deposit, the existing accounts can deposit
+        // on their own or pay the fees on L2.
+        let account_id = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(request.owner)
+            .await
+            .map_err(Error::storage)?;
+        if account_id.is_some() {
+            return Err(Error::from(InvalidDataError::InvalidDepositRelay(
+                "only the first deposit of the account can be relayed".to_string(),
+            )));
+        }
+        let recent_jobs = storage
+            .deposit_relay_schema()
+            .count_jobs_since(request.owner, now - Duration::days(1))
+            .await
+            .map_err(Error::storage)?;
+        if recent_jobs >= i64::from(self.config.max_jobs_per_address) {
+            return Err(Error::from(InvalidDataError::DepositRelayLimitExceeded(
+                format!(
+                    "at most {} requests per day are accepted from the same address",
+                    self.config.max_jobs_per_address
+                ),
+            )));
+        }
+        let pending_jobs = storage
+            .deposit_relay_schema()
+            .count_pending_jobs()
+            .await
+            .map_err(Error::storage)?;
+        if pending_jobs >= i64::from(self.config.max_pending_jobs) {
+            return Err(Error::from(InvalidDataError::DepositRelayLimitExceeded(
+                "relayer is busy, try again later".to_string(),
+            )));
+        }
+
+        let fee = self.relay_fee(token.id).await?;
+        if fee > request.max_fee {
+            return Err(Error::from(InvalidDataError::InvalidDepositRelay(format!(
+                "relay fee is {}, which exceeds the max fee",
+                fee
+            ))));
+        }
+        if fee >= request.amount {
+            return Err(Error::from(InvalidDataError::InvalidDepositRelay(format!(
+                "amount should exceed the relay fee of {}",
+                fee
+            ))));
+        }
+
+        let id = storage
+            .deposit_relay_schema()
+            .store_job(
+                request.owner,
+                token.id,
+                token.address,
+                &request.amount,
+                &fee,
+                deadline,
+                &request.signature,
+            )
+            .await
+            .map_err(Error::storage)?;
+        let job = storage
+            .deposit_relay_schema()
+            .get_job(id)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::storage("deposit relay job is not stored"))?;
+        Self::api_job(job)
+    }
+
+    async fn job(&self, id: u64) -> Result<DepositRelayJobInfo, Error> {
+        self.check_enabled()?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let job = storage
+            .deposit_relay_schema()
+            .get_job(id as i64)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::DepositRelayJobNotFound))?;
+        Self::api_job(job)
+    }
+}
+
+// Server implementation
+
+async fn deposit_relay_fee(
+    data: web::Data<ApiDepositRelayData>,
+    token: web::Path<u32>,
+) -> ApiResult<DepositRelayFee> {
+    let start = Instant::now();
+    let res = data.fee(TokenId(*token)).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "deposit_relay_fee");
+    res
+}
+
+async fn create_deposit_relay(
+    data: web::Data<ApiDepositRelayData>,
+    Json(body): Json<IncomingDepositRelay>,
+) -> ApiResult<DepositRelayJobInfo> {
+    let start = Instant::now();
+    let res = data.create_job(body).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "create_deposit_relay");
+    res
+}
+
+async fn deposit_relay(
+    data: web::Data<ApiDepositRelayData>,
+    id: web::Path<u64>,
+) -> ApiResult<DepositRelayJobInfo> {
+    let start = Instant::now();
+    let res = data.job(*id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "deposit_relay");
+    res
+}
+
+pub fn api_scope(
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    ticker: FeeTicker,
+    config: DepositRelayerConfig,
+) -> Scope {
+    let data = ApiDepositRelayData::new(pool, tokens, ticker, config);
+
+    web::scope("deposit_relays")
+        .app_data(web::Data::new(data))
+        .route("", web::post().to(create_deposit_relay))
+        .route("fee/{token}", web::get().to(deposit_relay_fee))
+        .route("{id}", web::get().to(deposit_relay))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, dummy_fee_ticker, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::ApiVersion;
+    use zksync_types::tx::PackedEthSignature;
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn deposit_relays_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let prices = vec![
+            (TokenLike::Id(TokenId(0)), 10_u64.into()),
+            (TokenLike::Id(TokenId(1)), 10_u64.into()),
+        ];
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                api_scope(
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    dummy_fee_ticker(&prices, None),
+                    DepositRelayerConfig {
+                        enabled: true,
+                        max_jobs_per_address: 1,
+                        ..cfg.config.deposit_relayer.clone()
+                    },
+                )
+            },
+            Some(shared_data),
+        );
+
+        // ETH can't be permitted.
+        let response = client.deposit_relay_fee(TokenId(0)).await?;
+        assert!(response.error.is_some());
+
+        let response = client.deposit_relay_fee(TokenId(1)).await?;
+        let fee: DepositRelayFee = deserialize_response_result(response)?;
+        assert_eq!(fee.relayer, cfg.config.deposit_relayer.sender_address);
+
+        let mut request = IncomingDepositRelay {
+            owner: Address::random(),
+            token: TokenId(1),
+            amount: &fee.fee + BigUint::from(1000u32),
+            max_fee: fee.fee.clone(),
+            deadline: (Utc::now() + Duration::days(1)).timestamp() as u64,
+            signature: PackedEthSignature::deserialize_packed(&[0x1b; 65])?,
+        };
+        // The permit expires too soon.
+        request.deadline = Utc::now().timestamp() as u64;
+        let response = client.create_deposit_relay(&request).await?;
+        assert!(response.error.is_some());
+
+        request.deadline = (Utc::now() + Duration::days(1)).timestamp() as u64;
+        let response = client.create_deposit_relay(&request).await?;
+        let info: DepositRelayJobInfo = deserialize_response_result(response)?;
+        assert_eq!(info.status, DepositRelayStatus::Pending);
+        assert_eq!(info.owner, request.owner);
+        assert_eq!(info.fee, fee.fee);
+
+        // Only one request per day is accepted from the same address.
+        let response = client.create_deposit_relay(&request).await?;
+        assert!(response.error.is_some());
+
+        let response = client.deposit_relay(info.job_id).await?;
+        let stored_info: DepositRelayJobInfo = deserialize_response_result(response)?;
+        assert_eq!(stored_info.amount, request.amount);
+        assert!(stored_info.eth_tx_hash.is_none());
+
+        let response = client.deposit_relay(info.job_id + 1).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
@@ -11,6 +11,7 @@ use zksync_api_types::v02::pagination::{UnknownFromParameter, MAX_LIMIT};
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 
 // Local uses
+use super::priority_op::MAX_DEPOSIT_AGGREGATES_DAYS;
 use crate::{api_server::tx_sender::SubmitError, fee_ticker::PriceError};
 
 #[derive(Serialize_repr, Debug, Deserialize_repr, Clone, PartialEq)]
@@ -27,6 +28,45 @@ pub enum ErrorCode {
     PaginationLimitTooBig = 206,
     QueryDeserializationError = 207,
     InvalidNFTTokenId = 208,
+    EventsPruned = 209,
+    TransactionPruned = 210,
+    InvalidApiKey = 211,
+    InvalidLabel = 212,
+    LabelNotFound = 213,
+    ResponseSigningDisabled = 214,
+    MerkleProofsDisabled = 215,
+    ExitProofsDisabled = 216,
+    ExitProofNotFound = 217,
+    InvalidStatementPeriod = 218,
+    StatementTooLarge = 219,
+    OrderBookDisabled = 220,
+    OrderNotFound = 221,
+    StandingOrdersDisabled = 222,
+    StandingOrderNotFound = 223,
+    SessionKeysDisabled = 224,
+    SessionKeyNotFound = 225,
+    PaymentRequestsDisabled = 226,
+    PaymentRequestNotFound = 227,
+    InvalidPaymentRequest = 228,
+    CoSignedTxsDisabled = 229,
+    CoSignedTxNotFound = 230,
+    AttestationsDisabled = 231,
+    AttestationNotFound = 232,
+    AttestationRequired = 233,
+    DepositRelayDisabled = 234,
+    DepositRelayJobNotFound = 235,
+    InvalidDepositRelay = 236,
+    DepositRelayLimitExceeded = 237,
+    ResponseShapingDisabled = 238,
+    UnknownTxField = 239,
+    TxCallbacksDisabled = 240,
+    TxCallbackEndpointNotFound = 241,
+    InvalidTxCallbackUrl = 242,
+    TooManyAccounts = 243,
+    NonceReservationsDisabled = 244,
+    InvalidDepositAggregatesPeriod = 245,
+    InvalidPubKeyHash = 246,
+    BlockNotCommitted = 247,
     StorageError = 300,
     TokenNotFound = 500,
     ExternalApiError = 501,
@@ -39,6 +79,16 @@ pub enum ErrorCode {
     InappropriateFeeToken = 606,
     CommunicationCoreServer = 607,
     Toggle2FAError = 608,
+    MaintenanceMode = 609,
+    WithdrawalPolicy = 610,
+    OrderBook = 611,
+    StandingOrder = 612,
+    SessionKey = 613,
+    CoSignedTx = 614,
+    Attestation = 615,
+    NonceReservation = 616,
+    Screening = 617,
+    AlreadyKnown = 618,
     Other = 60_000,
 }
 
@@ -49,6 +99,9 @@ pub struct Error {
     pub error_type: String,
     pub code: ErrorCode,
     pub message: String,
+    /// Structured details of the error, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
 }
 
 /// Trait that can be used to map custom errors to the object.
@@ -60,6 +113,10 @@ pub trait ApiError: std::fmt::Display {
     fn message(&self) -> String {
         self.to_string()
     }
+
+    fn data(&self) -> Option<serde_json::Value> {
+        None
+    }
 }
 
 impl<T> From<T> for Error
@@ -71,11 +128,26 @@ where
             error_type: t.error_type(),
             code: t.code(),
             message: t.message(),
+            data: t.data(),
         }
     }
 }
 
 impl Error {
+    /// Whether the error is caused by the server rather than by the request,
+    /// so it's counted against the objective of the route.
+    pub fn is_server_error(&self) -> bool {
+        matches!(
+            self.code,
+            ErrorCode::UnreacheableError
+                | ErrorCode::CoreApiError
+                | ErrorCode::StorageError
+                | ErrorCode::ExternalApiError
+                | ErrorCode::InternalError
+                | ErrorCode::CommunicationCoreServer
+        )
+    }
+
     pub fn storage(err: impl Display) -> Error {
         Error::from(StorageError::new(err))
     }
@@ -99,10 +171,91 @@ pub enum InvalidDataError {
     InvalidCurrency,
     #[error("Transaction is not found")]
     TransactionNotFound,
-    #[error("Limit for pagination should be less than or equal to {}", MAX_LIMIT)]
-    PaginationLimitTooBig,
+    #[error("Limit for pagination should be less than or equal to {0}")]
+    PaginationLimitTooBig(u32),
     #[error("NFT token ID should be greater than or equal to {}", MIN_NFT_TOKEN_ID)]
     InvalidNFTTokenId,
+    #[error("Requested events were removed by the retention policy, use `after=0` to start from the earliest retained event")]
+    EventsPruned,
+    #[error("Requested transaction data was moved to the archive by the pruning policy")]
+    TransactionPruned,
+    #[error("API key is missing or invalid")]
+    InvalidApiKey,
+    #[error("Invalid label: {0}")]
+    InvalidLabel(String),
+    #[error("Label is not found")]
+    LabelNotFound,
+    #[error("Response signing is not enabled on the server")]
+    ResponseSigningDisabled,
+    #[error("Merkle proofs are not enabled on the server or not loaded yet")]
+    MerkleProofsDisabled,
+    #[error("Exit proofs are not enabled on the server")]
+    ExitProofsDisabled,
+    #[error("Exit proof was not requested")]
+    ExitProofNotFound,
+    #[error("Statement can be generated only for the valid months that have already started")]
+    InvalidStatementPeriod,
+    #[error("Statement has too many transactions to be generated")]
+    StatementTooLarge,
+    #[error("Order book is not enabled on the server")]
+    OrderBookDisabled,
+    #[error("Order is not found")]
+    OrderNotFound,
+    #[error("Standing orders are not enabled on the server")]
+    StandingOrdersDisabled,
+    #[error("Standing order is not found")]
+    StandingOrderNotFound,
+    #[error("Session keys are not enabled on the server")]
+    SessionKeysDisabled,
+    #[error("Session key is not registered")]
+    SessionKeyNotFound,
+    #[error("Payment requests are not enabled on the server")]
+    PaymentRequestsDisabled,
+    #[error("Payment request is not found")]
+    PaymentRequestNotFound,
+    #[error("Invalid payment request: {0}")]
+    InvalidPaymentRequest(String),
+    #[error("Co-signed transactions are not enabled on the server")]
+    CoSignedTxsDisabled,
+    #[error("Co-signed transaction is not found")]
+    CoSignedTxNotFound,
+    #[error("Address attestations are not enabled on the server")]
+    AttestationsDisabled,
+    #[error("Address is not attested")]
+    AttestationNotFound,
+    #[error("Address should be attested to use this endpoint")]
+    AttestationRequired,
+    #[error("Deposit relayer is not enabled on the server")]
+    DepositRelayDisabled,
+    #[error("Deposit relay job is not found")]
+    DepositRelayJobNotFound,
+    #[error("Invalid deposit relay request: {0}")]
+    InvalidDepositRelay(String),
+    #[error("Deposit relay limit is exceeded: {0}")]
+    DepositRelayLimitExceeded(String),
+    #[error("Response shaping is not enabled on the server")]
+    ResponseShapingDisabled,
+    #[error("Unknown transaction field: {0}")]
+    UnknownTxField(String),
+    #[error("Tx callbacks are not enabled on the server")]
+    TxCallbacksDisabled,
+    #[error("Tx callback endpoint is not registered")]
+    TxCallbackEndpointNotFound,
+    #[error("Invalid tx callback URL: {0}")]
+    InvalidTxCallbackUrl(String),
+    #[error("Up to {} accounts can be resolved at once", MAX_LIMIT)]
+    TooManyAccounts,
+    #[error("Nonce reservations are not enabled on the server")]
+    NonceReservationsDisabled,
+    #[error(
+        "Deposit aggregates can be requested for up to {} days, starting not later than the last one",
+        MAX_DEPOSIT_AGGREGATES_DAYS
+    )]
+    InvalidDepositAggregatesPeriod,
+    #[error("Cannot parse the public key hash, it should be formatted as `sync:<hex>`")]
+    InvalidPubKeyHash,
+    #[error("Block is not committed yet")]
+    BlockNotCommitted,
 }
 
 impl ApiError for InvalidDataError {
@@ -118,8 +271,47 @@ impl ApiError for InvalidDataError {
             Self::AccountNotFound => ErrorCode::AccountNotFound,
             Self::InvalidCurrency => ErrorCode::InvalidCurrency,
             Self::TransactionNotFound => ErrorCode::TransactionNotFound,
-            Self::PaginationLimitTooBig => ErrorCode::PaginationLimitTooBig,
+            Self::PaginationLimitTooBig(_) => ErrorCode::PaginationLimitTooBig,
             Self::InvalidNFTTokenId => ErrorCode::InvalidNFTTokenId,
+            Self::EventsPruned => ErrorCode::EventsPruned,
+            Self::TransactionPruned => ErrorCode::TransactionPruned,
+            Self::InvalidApiKey => ErrorCode::InvalidApiKey,
+            Self::InvalidLabel(_) => ErrorCode::InvalidLabel,
+            Self::LabelNotFound => ErrorCode::LabelNotFound,
+            Self::ResponseSigningDisabled => ErrorCode::ResponseSigningDisabled,
+            Self::MerkleProofsDisabled => ErrorCode::MerkleProofsDisabled,
+            Self::ExitProofsDisabled => ErrorCode::ExitProofsDisabled,
+            Self::ExitProofNotFound => ErrorCode::ExitProofNotFound,
+            Self::InvalidStatementPeriod => ErrorCode::InvalidStatementPeriod,
+            Self::StatementTooLarge => ErrorCode::StatementTooLarge,
+            Self::OrderBookDisabled => ErrorCode::OrderBookDisabled,
+            Self::OrderNotFound => ErrorCode::OrderNotFound,
+            Self::StandingOrdersDisabled => ErrorCode::StandingOrdersDisabled,
+            Self::StandingOrderNotFound => ErrorCode::StandingOrderNotFound,
+            Self::SessionKeysDisabled => ErrorCode::SessionKeysDisabled,
+            Self::SessionKeyNotFound => ErrorCode::SessionKeyNotFound,
+            Self::PaymentRequestsDisabled => ErrorCode::PaymentRequestsDisabled,
+            Self::PaymentRequestNotFound => ErrorCode::PaymentRequestNotFound,
+            Self::InvalidPaymentRequest(_) => ErrorCode::InvalidPaymentRequest,
+            Self::CoSignedTxsDisabled => ErrorCode::CoSignedTxsDisabled,
+            Self::CoSignedTxNotFound => ErrorCode::CoSignedTxNotFound,
+            Self::AttestationsDisabled => ErrorCode::AttestationsDisabled,
+            Self::AttestationNotFound => ErrorCode::AttestationNotFound,
+            Self::AttestationRequired => ErrorCode::AttestationRequired,
+            Self::DepositRelayDisabled => ErrorCode::DepositRelayDisabled,
+            Self::DepositRelayJobNotFound => ErrorCode::DepositRelayJobNotFound,
+            Self::InvalidDepositRelay(_) => ErrorCode::InvalidDepositRelay,
+            Self::DepositRelayLimitExceeded(_) => ErrorCode::DepositRelayLimitExceeded,
+            Self::ResponseShapingDisabled => ErrorCode::ResponseShapingDisabled,
+            Self::UnknownTxField(_) => ErrorCode::UnknownTxField,
+            Self::TxCallbacksDisabled => ErrorCode::TxCallbacksDisabled,
+            Self::TxCallbackEndpointNotFound => ErrorCode::TxCallbackEndpointNotFound,
+            Self::InvalidTxCallbackUrl(_) => ErrorCode::InvalidTxCallbackUrl,
+            Self::TooManyAccounts => ErrorCode::TooManyAccounts,
+            Self::NonceReservationsDisabled => ErrorCode::NonceReservationsDisabled,
+            Self::InvalidDepositAggregatesPeriod => ErrorCode::InvalidDepositAggregatesPeriod,
+            Self::InvalidPubKeyHash => ErrorCode::InvalidPubKeyHash,
+            Self::BlockNotCommitted => ErrorCode::BlockNotCommitted,
         }
     }
 }
@@ -197,6 +389,16 @@ impl ApiError for SubmitError {
             Self::IncorrectTx(_) => ErrorCode::IncorrectTx,
             Self::TxAdd(_) => ErrorCode::TxAddError,
             Self::InappropriateFeeToken => ErrorCode::InappropriateFeeToken,
+            Self::MaintenanceMode(_) => ErrorCode::MaintenanceMode,
+            Self::WithdrawalPolicy(_) => ErrorCode::WithdrawalPolicy,
+            Self::OrderBook(_) => ErrorCode::OrderBook,
+            Self::StandingOrder(_) => ErrorCode::StandingOrder,
+            Self::SessionKey(_) => ErrorCode::SessionKey,
+            Self::CoSignedTx(_) => ErrorCode::CoSignedTx,
+            Self::Attestation(_) => ErrorCode::Attestation,
+            Self::NonceReservation(_) => ErrorCode::NonceReservation,
+            Self::Screening(_) => ErrorCode::Screening,
+            Self::AlreadyKnown(_) => ErrorCode::AlreadyKnown,
             Self::MempoolCommunication(_) => ErrorCode::CommunicationCoreServer,
             Self::Internal(_) => ErrorCode::InternalError,
             Self::Toggle2FA(_) => ErrorCode::Toggle2FAError,
@@ -204,6 +406,13 @@ impl ApiError for SubmitError {
             Self::PriceError(_) => ErrorCode::InternalError,
         }
     }
+
+    fn data(&self) -> Option<serde_json::Value> {
+        match self {
+            Self::AlreadyKnown(known_tx) => serde_json::to_value(known_tx).ok(),
+            _ => None,
+        }
+    }
 }
 
 impl ApiError for PriceError {
@@ -0,0 +1,175 @@
+//! Events part of API implementation.
+//!
+//! Gives access to the stored events log, so the consumers (e.g. indexers) can recover
+//! after the downtime by fetching the events after the last processed one instead of
+//! re-scanning the blocks. Events are returned in the order of their commit, which may
+//! differ from the order of their ids.
+
+// Built-in uses
+use std::convert::TryFrom;
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, HttpRequest, Scope};
+
+// Workspace uses
+use zksync_api_types::v02::event::{EventsPage, EventsQuery};
+use zksync_storage::ConnectionPool;
+use zksync_types::event::{VersionedEvent, ZkSyncEvent};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    page_size::max_page_size,
+    paginate_trait::check_pagination_limit,
+    response::ApiResult,
+};
+use crate::api_try;
+
+/// Shared data between `api/v0.2/events` endpoints.
+#[derive(Debug, Clone)]
+struct ApiEventData {
+    pool: ConnectionPool,
+}
+
+impl ApiEventData {
+    fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+
+    async fn events_page(
+        &self,
+        query: EventsQuery,
+        max_page_size: u32,
+    ) -> Result<EventsPage, Error> {
+        check_pagination_limit(query.limit, max_page_size)?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let events = storage
+            .event_schema()
+            .load_events_after(query.after, query.limit)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::EventsPruned))?;
+        let first_event_id = storage
+            .event_schema()
+            .get_first_event_id()
+            .await
+            .map_err(Error::storage)?;
+
+        let events = events
+            .into_iter()
+            .map(|event| ZkSyncEvent::try_from(event).map(VersionedEvent::from))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Error::storage)?;
+        let next_cursor = events
+            .last()
+            .map(|event| event.event_id)
+            .unwrap_or(query.after);
+
+        Ok(EventsPage {
+            events,
+            next_cursor,
+            first_event_id,
+        })
+    }
+}
+
+// Server implementation
+
+async fn events_after(
+    data: web::Data<ApiEventData>,
+    http_request: HttpRequest,
+    web::Query(query): web::Query<EventsQuery>,
+) -> ApiResult<EventsPage> {
+    let start = Instant::now();
+    let max_page_size = api_try!(max_page_size(&http_request, "events_after").await);
+    let res = data.events_page(query, max_page_size).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "events_after");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiEventData::new(pool);
+
+    web::scope("events")
+        .app_data(web::Data::new(data))
+        .route("", web::get().to(events_after))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::{pagination::MAX_LIMIT, ApiVersion, Response};
+    use zksync_types::event::{EventId, EVENT_SCHEMA_VERSION};
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn events_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| api_scope(cfg.pool.clone()),
+            Some(shared_data),
+        );
+
+        let expected_ids: Vec<_> = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .event_schema()
+                .load_events_after(EventId(0), MAX_LIMIT)
+                .await?
+                .unwrap_or_default()
+                .into_iter()
+                .map(|event| EventId(event.id as u64))
+                .collect()
+        };
+
+        // Read the events one by one.
+        let mut cursor = EventId(0);
+        for expected_id in &expected_ids {
+            let query = EventsQuery {
+                after: cursor,
+                limit: 1,
+            };
+            let response = client.events_after(&query).await?;
+            let page: EventsPage = deserialize_response_result(response)?;
+            assert_eq!(page.events.len(), 1);
+            assert_eq!(page.events[0].event_id, *expected_id);
+            assert_eq!(page.events[0].schema_version, EVENT_SCHEMA_VERSION);
+            assert_eq!(page.next_cursor, *expected_id);
+            assert_eq!(page.first_event_id, expected_ids.first().copied());
+            cursor = page.next_cursor;
+        }
+        // No events after the last one, the cursor stays the same.
+        let query = EventsQuery {
+            after: cursor,
+            limit: MAX_LIMIT,
+        };
+        let response = client.events_after(&query).await?;
+        let page: EventsPage = deserialize_response_result(response)?;
+        assert!(page.events.is_empty());
+        assert_eq!(page.next_cursor, cursor);
+
+        let query = EventsQuery {
+            after: EventId(0),
+            limit: MAX_LIMIT + 1,
+        };
+        let response: Response = client.events_after(&query).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+//! Exit proofs part of API implementation.
+//!
+//! The exit proofs are required to withdraw the funds in the exodus mode. They are requested
+//! through the API and generated in the background, so the users don't have to restore
+//! the state and run the prover themselves.
+
+// Built-in uses
+use std::str::FromStr;
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_types::v02::exit_proof::{ExitProofInfo, ExitProofStatus};
+use zksync_storage::{exit_proofs::records::StoredExitProof, ConnectionPool};
+use zksync_token_db_cache::TokenDBCache;
+use zksync_types::{AccountId, Address, TokenId, TokenLike};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::{api_try, fee_ticker::PriceError};
+
+fn api_exit_proof(exit_proof: StoredExitProof) -> Result<ExitProofInfo, Error> {
+    let status = if exit_proof.error.is_some() {
+        ExitProofStatus::Failed
+    } else if exit_proof.proof_data.is_some() {
+        ExitProofStatus::Ready
+    } else if exit_proof.started_at.is_some() {
+        ExitProofStatus::InProgress
+    } else {
+        ExitProofStatus::Queued
+    };
+    let data = exit_proof
+        .proof_data
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(Error::storage)?;
+
+    Ok(ExitProofInfo {
+        account_id: AccountId(exit_proof.account_id as u32),
+        token_id: TokenId(exit_proof.token_id as u32),
+        status,
+        requested_at: exit_proof.created_at,
+        data,
+        error: exit_proof.error,
+    })
+}
+
+/// Shared data between `api/v0.2/exit_proofs` endpoints.
+#[derive(Debug, Clone)]
+struct ApiExitProofData {
+    /// Requests are stored, so the main database connection is used.
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    enabled: bool,
+}
+
+impl ApiExitProofData {
+    fn new(pool: ConnectionPool, tokens: TokenDBCache, enabled: bool) -> Self {
+        Self {
+            pool,
+            tokens,
+            enabled,
+        }
+    }
+
+    async fn account_and_token(
+        &self,
+        account_id_or_address: &str,
+        token_like: &str,
+    ) -> Result<(AccountId, TokenId), Error> {
+        if !self.enabled {
+            return Err(Error::from(InvalidDataError::ExitProofsDisabled));
+        }
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+
+        let account_id = if let Ok(account_id) = u32::from_str(account_id_or_address) {
+            AccountId(account_id)
+        } else {
+            let address = account_id_or_address
+                .strip_prefix("0x")
+                .unwrap_or(account_id_or_address);
+            let address = Address::from_str(address)
+                .map_err(|_| Error::from(InvalidDataError::InvalidAccountIdOrAddress))?;
+            storage
+                .chain()
+                .account_schema()
+                .account_id_by_address(address)
+                .await
+                .map_err(Error::storage)?
+                .ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))?
+        };
+        let token = self
+            .tokens
+            .get_token(&mut storage, TokenLike::parse(token_like))
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| {
+                Error::from(PriceError::token_not_found("Token not found in storage"))
+            })?;
+
+        Ok((account_id, token.id))
+    }
+
+    async fn request_exit_proof(
+        &self,
+        account_id: AccountId,
+        token_id: TokenId,
+    ) -> Result<ExitProofInfo, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let last_verified_block = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await
+            .map_err(Error::storage)?;
+        storage
+            .exit_proofs_schema()
+            .request_exit_proof(account_id, token_id, last_verified_block)
+            .await
+            .map_err(Error::storage)?;
+
+        let exit_proof = storage
+            .exit_proofs_schema()
+            .get_exit_proof(account_id, token_id)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::storage("exit proof request is not stored"))?;
+        api_exit_proof(exit_proof)
+    }
+
+    async fn exit_proof(
+        &self,
+        account_id: AccountId,
+        token_id: TokenId,
+    ) -> Result<ExitProofInfo, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let exit_proof = storage
+            .exit_proofs_schema()
+            .get_exit_proof(account_id, token_id)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::ExitProofNotFound))?;
+        api_exit_proof(exit_proof)
+    }
+}
+
+// Server implementation
+
+async fn request_exit_proof(
+    data: web::Data<ApiExitProofData>,
+    path: web::Path<(String, String)>,
+) -> ApiResult<ExitProofInfo> {
+    let start = Instant::now();
+    let (account_id_or_address, token_like) = path.into_inner();
+    let (account_id, token_id) = api_try!(
+        data.account_and_token(&account_id_or_address, &token_like)
+            .await
+    );
+    let res = data.request_exit_proof(account_id, token_id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "request_exit_proof");
+    res
+}
+
+async fn exit_proof(
+    data: web::Data<ApiExitProofData>,
+    path: web::Path<(String, String)>,
+) -> ApiResult<ExitProofInfo> {
+    let start = Instant::now();
+    let (account_id_or_address, token_like) = path.into_inner();
+    let (account_id, token_id) = api_try!(
+        data.account_and_token(&account_id_or_address, &token_like)
+            .await
+    );
+    let res = data.exit_proof(account_id, token_id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "exit_proof");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool, tokens: TokenDBCache, enabled: bool) -> Scope {
+    let data = ApiExitProofData::new(pool, tokens, enabled);
+
+    web::scope("exit_proofs")
+        .app_data(web::Data::new(data))
+        .route(
+            "{account_id_or_address}/{token}",
+            web::post().to(request_exit_proof),
+        )
+        .route("{account_id_or_address}/{token}", web::get().to(exit_proof))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::{ApiVersion, Response};
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn exit_proofs_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                api_scope(
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    true,
+                )
+            },
+            Some(shared_data),
+        );
+
+        let response: Response = client.exit_proof("1", "PHNX").await?;
+        assert!(response.error.is_some());
+
+        let response = client.request_exit_proof("1", "ETH").await?;
+        let info: ExitProofInfo = deserialize_response_result(response)?;
+        assert_eq!(info.account_id, AccountId(1));
+        assert_eq!(info.token_id, TokenId(0));
+        assert_eq!(info.status, ExitProofStatus::Queued);
+        assert!(info.data.is_none());
+
+        let response = client.exit_proof("1", "ETH").await?;
+        let stored_info: ExitProofInfo = deserialize_response_result(response)?;
+        assert_eq!(stored_info, info);
+
+        server.stop().await;
+        Ok(())
+    }
+}
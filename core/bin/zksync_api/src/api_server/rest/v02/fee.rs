@@ -8,9 +8,10 @@ use actix_web::{
     web::{self, Json},
     Scope,
 };
+use num::BigUint;
 
 // Workspace uses
-use zksync_api_types::v02::fee::{ApiFee, BatchFeeRequest, TxFeeRequest};
+use zksync_api_types::v02::fee::{ApiFee, ApiTxFeeTypes, BatchFeeRequest, TxFeeRequest};
 
 // Local uses
 use super::{error::Error, response::ApiResult};
@@ -31,6 +32,29 @@ impl ApiFeeData {
     }
 }
 
+/// Returns the gas of the calls attached to the withdrawals, which is included in the fee.
+fn withdrawal_hooks_gas<'a>(
+    tx_sender: &TxSender,
+    txs: impl Iterator<Item = (&'a ApiTxFeeTypes, bool)>,
+) -> Result<BigUint, SubmitError> {
+    let mut hooks_count = 0;
+    for (tx_type, withdrawal_hook) in txs {
+        if !withdrawal_hook {
+            continue;
+        }
+        if !matches!(
+            tx_type,
+            ApiTxFeeTypes::Withdraw | ApiTxFeeTypes::FastWithdraw
+        ) {
+            return Err(SubmitError::IncorrectTx(
+                "L1 calldata can be attached to the withdrawals only".to_string(),
+            ));
+        }
+        hooks_count += 1;
+    }
+    tx_sender.withdrawal_hooks_gas(hooks_count)
+}
+
 async fn get_tx_fee(
     data: web::Data<ApiFeeData>,
     Json(body): Json<TxFeeRequest>,
@@ -45,11 +69,21 @@ async fn get_tx_fee(
     if !token_allowed {
         return Error::from(SubmitError::InappropriateFeeToken).into();
     }
+    let extra_gas = api_try!(withdrawal_hooks_gas(
+        &data.tx_sender,
+        std::iter::once((&body.tx_type, body.withdrawal_hook))
+    )
+    .map_err(Error::from));
     // TODO implement subsidies for v02 api ZKS-888
     let res = data
         .tx_sender
         .ticker
-        .get_fee_from_ticker_in_wei(body.tx_type.into(), body.token_like, body.address)
+        .get_fee_with_extra_gas_in_wei(
+            body.tx_type.into(),
+            body.token_like,
+            body.address,
+            extra_gas,
+        )
         .await
         .map(|fee| fee.normal_fee.into())
         .map_err(Error::from)
@@ -72,6 +106,13 @@ async fn get_batch_fee(
     if !token_allowed {
         return Error::from(SubmitError::InappropriateFeeToken).into();
     }
+    let extra_gas = api_try!(withdrawal_hooks_gas(
+        &data.tx_sender,
+        body.transactions
+            .iter()
+            .map(|tx| (&tx.tx_type, tx.withdrawal_hook))
+    )
+    .map_err(Error::from));
     let txs = body
         .transactions
         .into_iter()
@@ -80,7 +121,7 @@ async fn get_batch_fee(
     let res = data
         .tx_sender
         .ticker
-        .get_batch_from_ticker_in_wei(body.token_like, txs)
+        .get_batch_with_extra_gas_in_wei(body.token_like, txs, extra_gas)
         .await
         .map(|fee| fee.normal_fee.into())
         .map_err(Error::from)
@@ -202,6 +243,7 @@ mod tests {
         let tx = TxInBatchFeeRequest {
             tx_type: ApiTxFeeTypes::Withdraw,
             address: Address::default(),
+            withdrawal_hook: false,
         };
         let txs = vec![tx.clone(), tx.clone(), tx];
 
@@ -0,0 +1,175 @@
+//! Response shaping of the endpoints returning the transactions.
+
+// Built-in uses
+use std::collections::HashMap;
+
+// Workspace uses
+use zksync_api_types::v02::{
+    amounts::{FormattedAmounts, TxAmounts},
+    fields::{FieldsQuery, ShapedTransaction, ShapedTxData, TxFields},
+};
+use zksync_storage::ConnectionPool;
+use zksync_token_db_cache::TokenDBCache;
+
+// Local uses
+use super::error::{Error, InvalidDataError};
+
+/// Returns the transaction fields requested with the `fields` query parameter,
+/// all the fields are returned if it's not set.
+pub fn requested_tx_fields(query: &FieldsQuery, enabled: bool) -> Result<TxFields, Error> {
+    match &query.fields {
+        None => Ok(TxFields::ALL),
+        Some(_) if !enabled => Err(Error::from(InvalidDataError::ResponseShapingDisabled)),
+        Some(fields) => TxFields::parse(fields)
+            .map_err(|field| Error::from(InvalidDataError::UnknownTxField(field))),
+    }
+}
+
+/// Formats the amounts with the decimals of their tokens. Tokens are taken from the cache,
+/// so the connection is only acquired if some of them are missing there.
+async fn formatted_amounts(
+    pool: &ConnectionPool,
+    tokens: &TokenDBCache,
+    amounts: Vec<TxAmounts>,
+) -> Result<Vec<FormattedAmounts>, Error> {
+    let mut known_tokens = HashMap::new();
+    let mut missing_tokens = Vec::new();
+    for token_id in amounts.iter().flat_map(|amounts| amounts.tokens()) {
+        if known_tokens.contains_key(&token_id) || missing_tokens.contains(&token_id) {
+            continue;
+        }
+        match tokens.try_get_token_from_cache(token_id).await {
+            Some(token) => {
+                known_tokens.insert(token_id, token);
+            }
+            None => missing_tokens.push(token_id),
+        }
+    }
+    if !missing_tokens.is_empty() {
+        let mut storage = pool.access_storage().await.map_err(Error::storage)?;
+        for token_id in missing_tokens {
+            if let Some(token) = tokens
+                .get_token(&mut storage, token_id)
+                .await
+                .map_err(Error::storage)?
+            {
+                known_tokens.insert(token_id, token);
+            }
+        }
+    }
+
+    Ok(amounts
+        .iter()
+        .map(|amounts| amounts.format(&known_tokens))
+        .collect())
+}
+
+/// Sets the formatted amounts of the transactions if they're requested with
+/// the `formatAmounts` query parameter.
+pub async fn format_tx_amounts(
+    query: &FieldsQuery,
+    pool: &ConnectionPool,
+    tokens: &TokenDBCache,
+    txs: &mut [ShapedTransaction],
+) -> Result<(), Error> {
+    if !query.format_amounts() {
+        return Ok(());
+    }
+    let amounts = txs.iter().map(|tx| TxAmounts::new(&tx.tx.op)).collect();
+    let amounts = formatted_amounts(pool, tokens, amounts).await?;
+    for (tx, amounts) in txs.iter_mut().zip(amounts) {
+        tx.amounts = Some(amounts);
+    }
+    Ok(())
+}
+
+/// Sets the formatted amounts of the transaction data if they're requested with
+/// the `formatAmounts` query parameter.
+pub async fn format_tx_data_amounts(
+    query: &FieldsQuery,
+    pool: &ConnectionPool,
+    tokens: &TokenDBCache,
+    data: &mut ShapedTxData,
+) -> Result<(), Error> {
+    if !query.format_amounts() {
+        return Ok(());
+    }
+    let amounts = vec![TxAmounts::new(&data.data.tx.op)];
+    data.amounts = formatted_amounts(pool, tokens, amounts).await?.pop();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigUint;
+    use zksync_api_types::v02::{
+        amounts::FormattedAmount,
+        fields::TX_FIELDS,
+        transaction::{ApiDeposit, L1Transaction, TransactionData},
+    };
+    use zksync_types::{tx::TxHash, Address, Token, TokenId, TokenKind, H256};
+
+    fn query(fields: Option<&str>) -> FieldsQuery {
+        FieldsQuery {
+            fields: fields.map(String::from),
+            format_amounts: None,
+        }
+    }
+
+    #[test]
+    fn requested_fields() {
+        assert_eq!(
+            requested_tx_fields(&query(None), false).unwrap(),
+            TxFields::ALL
+        );
+        assert!(requested_tx_fields(&query(Some("txHash")), false).is_err());
+        assert!(requested_tx_fields(&query(Some("txHash,body")), true).is_err());
+
+        let fields = requested_tx_fields(&query(Some("txHash, status,createdAt,")), true).unwrap();
+        assert!(fields.contains("txHash"));
+        assert!(fields.contains("status"));
+        assert!(fields.contains("createdAt"));
+        assert!(!fields.contains("op"));
+        assert!(!fields.contains("ethSignature"));
+
+        let fields = requested_tx_fields(&query(Some(&TX_FIELDS.join(","))), true).unwrap();
+        assert!(fields.is_all());
+    }
+
+    /// Checks that the amounts are formatted with the decimals of their tokens
+    /// and the amounts in the unknown tokens are omitted.
+    #[test]
+    fn tx_amounts() {
+        let deposit = |token_id: u32, amount: u64| {
+            TransactionData::L1(L1Transaction::Deposit(ApiDeposit {
+                from: Address::zero(),
+                token_id: TokenId(token_id),
+                amount: BigUint::from(amount),
+                to: Address::zero(),
+                account_id: None,
+                eth_hash: H256::zero(),
+                id: 0,
+                tx_hash: TxHash::default(),
+            }))
+        };
+        let usdc = Token::new(TokenId(2), Address::zero(), "USDC", 6, TokenKind::ERC20);
+        let tokens: HashMap<_, _> = vec![(usdc.id, usdc)].into_iter().collect();
+
+        let amounts = TxAmounts::new(&deposit(2, 1_500_000));
+        assert_eq!(amounts.tokens().collect::<Vec<_>>(), vec![TokenId(2)]);
+        assert_eq!(
+            amounts.format(&tokens),
+            FormattedAmounts {
+                amount: Some(FormattedAmount {
+                    symbol: "USDC".to_string(),
+                    value: "1.5".to_string(),
+                }),
+                fee: None,
+            }
+        );
+
+        let amounts = TxAmounts::new(&deposit(3, 1_500_000));
+        assert_eq!(amounts.format(&tokens), FormattedAmounts::default());
+    }
+}
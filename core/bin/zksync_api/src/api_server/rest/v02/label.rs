@@ -0,0 +1,279 @@
+//! Address labels part of API implementation.
+//!
+//! Labels set by the operator (e.g. of the exchange hot wallets) are visible to everyone,
+//! while the user-defined ones are managed with the API key and visible only in its scope.
+
+// Built-in uses
+use std::str::FromStr;
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, HttpRequest, Scope};
+
+// Workspace uses
+use zksync_api_types::v02::label::{
+    validate_label, AddressLabel, AddressLabelCategory, AddressLabelRequest, LABELS_API_KEY_HEADER,
+};
+use zksync_storage::{
+    labels::records::{self, StoredAddressLabel},
+    ConnectionPool, StorageProcessor,
+};
+use zksync_types::Address;
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::api_try;
+
+/// Returns the scope of the API key provided in the request, if any.
+pub(super) async fn api_key_scope(
+    storage: &mut StorageProcessor<'_>,
+    http_request: &HttpRequest,
+) -> Result<Option<String>, Error> {
+    let api_key = match http_request.headers().get(LABELS_API_KEY_HEADER) {
+        Some(api_key) => api_key
+            .to_str()
+            .map_err(|_| Error::from(InvalidDataError::InvalidApiKey))?,
+        None => return Ok(None),
+    };
+    let scope = storage
+        .labels_schema()
+        .get_api_key_scope(&tiny_keccak::keccak256(api_key.as_bytes()))
+        .await
+        .map_err(Error::storage)?
+        .ok_or_else(|| Error::from(InvalidDataError::InvalidApiKey))?;
+    Ok(Some(scope))
+}
+
+fn api_label(label: StoredAddressLabel) -> Result<AddressLabel, Error> {
+    Ok(AddressLabel {
+        address: Address::from_slice(&label.address),
+        category: AddressLabelCategory::from_str(&label.category).map_err(Error::storage)?,
+        label: label.label,
+    })
+}
+
+/// Loads the labels of the addresses visible to the caller: the ones set by the operator
+/// and the ones of the API key scope, if the key is provided.
+pub(super) async fn visible_labels(
+    storage: &mut StorageProcessor<'_>,
+    http_request: &HttpRequest,
+    addresses: &[Address],
+) -> Result<Vec<AddressLabel>, Error> {
+    let scope = api_key_scope(storage, http_request).await?;
+    storage
+        .labels_schema()
+        .load_labels(addresses, scope.as_deref())
+        .await
+        .map_err(Error::storage)?
+        .into_iter()
+        .map(api_label)
+        .collect()
+}
+
+/// Shared data between `api/v0.2/labels` endpoints.
+#[derive(Debug, Clone)]
+struct ApiLabelData {
+    pool: ConnectionPool,
+}
+
+impl ApiLabelData {
+    fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_address(&self, address: &str) -> Result<Address, Error> {
+        let address = address.strip_prefix("0x").unwrap_or(address);
+        Address::from_str(address)
+            .map_err(|_| Error::from(InvalidDataError::InvalidAccountIdOrAddress))
+    }
+
+    async fn address_labels(
+        &self,
+        http_request: &HttpRequest,
+        address: Address,
+    ) -> Result<Vec<AddressLabel>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        visible_labels(&mut storage, http_request, &[address]).await
+    }
+
+    async fn add_label(
+        &self,
+        http_request: &HttpRequest,
+        request: AddressLabelRequest,
+    ) -> Result<AddressLabel, Error> {
+        validate_label(&request.label)
+            .map_err(|err| Error::from(InvalidDataError::InvalidLabel(err)))?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let scope = api_key_scope(&mut storage, http_request)
+            .await?
+            .ok_or_else(|| Error::from(InvalidDataError::InvalidApiKey))?;
+
+        let label = records::AddressLabel {
+            address: request.address,
+            label: request.label,
+            category: AddressLabelCategory::UserDefined.to_string(),
+            scope: Some(scope),
+        };
+        // Adding the existing label is not an error.
+        storage
+            .labels_schema()
+            .store_label(&label)
+            .await
+            .map_err(Error::storage)?;
+
+        Ok(AddressLabel {
+            address: label.address,
+            label: label.label,
+            category: AddressLabelCategory::UserDefined,
+        })
+    }
+
+    async fn remove_label(
+        &self,
+        http_request: &HttpRequest,
+        request: AddressLabelRequest,
+    ) -> Result<(), Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let scope = api_key_scope(&mut storage, http_request)
+            .await?
+            .ok_or_else(|| Error::from(InvalidDataError::InvalidApiKey))?;
+
+        let removed = storage
+            .labels_schema()
+            .remove_label(request.address, &request.label, Some(&scope))
+            .await
+            .map_err(Error::storage)?;
+        if removed {
+            Ok(())
+        } else {
+            Err(Error::from(InvalidDataError::LabelNotFound))
+        }
+    }
+}
+
+// Server implementation
+
+async fn address_labels(
+    data: web::Data<ApiLabelData>,
+    http_request: HttpRequest,
+    address: web::Path<String>,
+) -> ApiResult<Vec<AddressLabel>> {
+    let start = Instant::now();
+    let address = api_try!(data.parse_address(&address));
+    let res = data.address_labels(&http_request, address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "address_labels");
+    res
+}
+
+async fn add_label(
+    data: web::Data<ApiLabelData>,
+    http_request: HttpRequest,
+    web::Json(request): web::Json<AddressLabelRequest>,
+) -> ApiResult<AddressLabel> {
+    let start = Instant::now();
+    let res = data.add_label(&http_request, request).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "add_label");
+    res
+}
+
+async fn remove_label(
+    data: web::Data<ApiLabelData>,
+    http_request: HttpRequest,
+    web::Json(request): web::Json<AddressLabelRequest>,
+) -> ApiResult<()> {
+    let start = Instant::now();
+    let res = data.remove_label(&http_request, request).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "remove_label");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiLabelData::new(pool);
+
+    web::scope("labels")
+        .app_data(web::Data::new(data))
+        .route("", web::post().to(add_label))
+        .route("remove", web::post().to(remove_label))
+        .route("{address}", web::get().to(address_labels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::{ApiVersion, Response};
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn labels_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let address = Address::repeat_byte(0x11);
+        let api_key = "test-api-key";
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .labels_schema()
+                .store_api_key(&tiny_keccak::keccak256(api_key.as_bytes()), "test")
+                .await?;
+            storage
+                .labels_schema()
+                .store_label(&records::AddressLabel {
+                    address,
+                    label: String::from("Exchange"),
+                    category: AddressLabelCategory::ExchangeHotWallet.to_string(),
+                    scope: None,
+                })
+                .await?;
+        }
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| api_scope(cfg.pool.clone()),
+            Some(shared_data),
+        );
+
+        let request = AddressLabelRequest {
+            address,
+            label: String::from("My friend"),
+        };
+        let response = client.add_address_label(&request, api_key).await?;
+        let label: AddressLabel = deserialize_response_result(response)?;
+        assert_eq!(label.category, AddressLabelCategory::UserDefined);
+
+        // User-defined labels are visible only with the API key of their scope.
+        let response = client.address_labels(address, Some(api_key)).await?;
+        let labels: Vec<AddressLabel> = deserialize_response_result(response)?;
+        assert_eq!(labels.len(), 2);
+        let response = client.address_labels(address, None).await?;
+        let labels: Vec<AddressLabel> = deserialize_response_result(response)?;
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].category, AddressLabelCategory::ExchangeHotWallet);
+
+        let response: Response = client.address_labels(address, Some("unknown")).await?;
+        assert!(response.error.is_some());
+        let response: Response = client.add_address_label(&request, "unknown").await?;
+        assert!(response.error.is_some());
+
+        let response = client.remove_address_label(&request, api_key).await?;
+        let _: () = deserialize_response_result(response)?;
+        let response: Response = client.remove_address_label(&request, api_key).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
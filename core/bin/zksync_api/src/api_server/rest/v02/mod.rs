@@ -4,27 +4,52 @@ use actix_web::{
     Scope,
 };
 // Workspace uses
-use crate::api_server::rest::network_status::SharedNetworkStatus;
+use crate::api_server::rest::{
+    account_resolver::SharedAccountResolver, finality_estimate::SharedFinalityEstimate,
+    network_status::SharedNetworkStatus, verified_tree::SharedVerifiedTree,
+};
 use zksync_api_types::v02::ApiVersion;
 use zksync_config::ZkSyncConfig;
+use zksync_object_store::ObjectStore;
+use zksync_storage::ConnectionPool;
 use zksync_types::network::Network;
 
 // Local uses
+use self::{page_size::PageSizeLimits, signature::ResponseSigner};
 use crate::api_server::tx_sender::TxSender;
 
 mod account;
+mod attestation;
 mod block;
 mod config;
+mod cosigned_tx;
+mod create2_factory;
+mod deposit_relay;
 pub mod error;
+mod event;
+mod exit_proof;
 mod fee;
+mod fields;
+mod label;
+mod nonce_reservation;
+mod order_book;
+mod page_size;
 mod paginate_impl;
 mod paginate_trait;
+mod payment_request;
+mod priority_op;
 mod response;
+mod session_key;
+mod signature;
+mod standing_order;
+mod statement;
 mod status;
+mod storage;
 #[cfg(test)]
 pub mod test_utils;
 mod token;
 mod transaction;
+mod tx_callback;
 
 #[derive(Debug, Clone, Copy)]
 pub struct SharedData {
@@ -34,32 +59,93 @@ pub struct SharedData {
 
 pub(crate) fn api_scope(
     tx_sender: TxSender,
+    main_database_connection_pool: ConnectionPool,
     zk_config: &ZkSyncConfig,
     network_status: SharedNetworkStatus,
+    verified_tree: Option<SharedVerifiedTree>,
+    finality_estimate: SharedFinalityEstimate,
+    account_resolver: SharedAccountResolver,
 ) -> Scope {
     let data = SharedData {
         net: zk_config.chain.eth.network,
         api_version: ApiVersion::V02,
     };
+    let signer = ResponseSigner::new(&zk_config.api.common);
+    let page_size_limits = PageSizeLimits::new(tx_sender.pool.clone(), &zk_config.api.rest);
     web::scope("/api/v0.2")
         .app_data(web::Data::new(data))
+        .app_data(web::Data::new(page_size_limits))
         .service(account::api_scope(
             tx_sender.pool.clone(),
             tx_sender.tokens.clone(),
             zk_config.eth_watch.confirmations_for_eth_event,
+            signer.clone(),
+            verified_tree,
+            account_resolver,
+            zk_config.api.common.response_shaping_enabled,
         ))
+        .service(attestation::api_scope(tx_sender.clone()))
         .service(block::api_scope(
             tx_sender.pool.clone(),
             tx_sender.blocks.clone(),
+            tx_sender.finalized_cache.clone(),
+            zk_config.api.common.response_shaping_enabled,
+            zk_config.chain.gas_prediction.predictor(),
         ))
         .service(config::api_scope(zk_config))
+        .service(cosigned_tx::api_scope(tx_sender.clone()))
+        .service(create2_factory::api_scope(tx_sender.clone()))
+        .service(deposit_relay::api_scope(
+            main_database_connection_pool.clone(),
+            tx_sender.tokens.clone(),
+            tx_sender.ticker.clone(),
+            zk_config.deposit_relayer.clone(),
+        ))
+        .service(event::api_scope(tx_sender.pool.clone()))
+        .service(exit_proof::api_scope(
+            main_database_connection_pool.clone(),
+            tx_sender.tokens.clone(),
+            zk_config.api.common.exit_proofs_enabled,
+        ))
         .service(fee::api_scope(tx_sender.clone()))
+        .service(label::api_scope(tx_sender.pool.clone()))
+        .service(nonce_reservation::api_scope(tx_sender.clone()))
+        .service(order_book::api_scope(tx_sender.clone()))
+        .service(payment_request::api_scope(
+            main_database_connection_pool,
+            tx_sender.tokens.clone(),
+            zk_config.api.rest.url.clone(),
+            zk_config.api.common.payment_requests_enabled,
+        ))
+        .service(priority_op::api_scope(
+            tx_sender.pool.clone(),
+            zk_config.eth_watch.confirmations_for_eth_event,
+        ))
+        .service(session_key::api_scope(tx_sender.clone()))
+        .service(standing_order::api_scope(tx_sender.clone()))
+        .service(statement::api_scope(
+            tx_sender.pool.clone(),
+            tx_sender.tokens.clone(),
+            zk_config.api.common.statements_require_attestation,
+        ))
         .service(status::api_scope(network_status))
         .service(token::api_scope(
             zk_config,
             tx_sender.pool.clone(),
             tx_sender.tokens.clone(),
             tx_sender.ticker.clone(),
+            tx_sender.finalized_cache.clone(),
+        ))
+        .service(transaction::api_scope(
+            tx_sender.clone(),
+            signer,
+            finality_estimate,
+            zk_config.api.common.response_shaping_enabled,
+            zk_config.api.common.tx_callbacks_enabled,
+            ObjectStore::new(&zk_config.object_store),
+        ))
+        .service(tx_callback::api_scope(
+            tx_sender.pool,
+            zk_config.api.common.tx_callbacks_enabled,
         ))
-        .service(transaction::api_scope(tx_sender))
 }
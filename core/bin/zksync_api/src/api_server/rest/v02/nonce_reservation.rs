@@ -0,0 +1,236 @@
+//! Nonce reservations part of API implementation.
+//!
+//! Market makers reserve the ranges of the account nonces to pre-sign the transactions
+//! and submit them out of order. The mempool holds the transactions following a missing
+//! nonce of the reserved range until the gap is filled or the reservation expires.
+
+// Built-in uses
+use std::str::FromStr;
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+
+// Workspace uses
+use zksync_api_types::v02::nonce_reservation::{NonceReservation, NonceReservationInfo};
+use zksync_storage::nonce_reservations::records::StoredNonceReservation;
+use zksync_types::{AccountId, Address, Nonce};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::{api_server::tx_sender::TxSender, api_try};
+
+fn api_nonce_reservation(reservation: StoredNonceReservation) -> NonceReservationInfo {
+    NonceReservationInfo {
+        account_id: AccountId(reservation.account_id as u32),
+        first_nonce: Nonce(reservation.first_nonce as u32),
+        last_nonce: Nonce(reservation.last_nonce as u32),
+        expires_at: reservation.expires_at,
+        created_at: reservation.created_at,
+    }
+}
+
+/// Shared data between `api/v0.2/nonce_reservations` endpoints.
+#[derive(Clone)]
+struct ApiNonceReservationsData {
+    tx_sender: TxSender,
+}
+
+impl ApiNonceReservationsData {
+    fn new(tx_sender: TxSender) -> Self {
+        Self { tx_sender }
+    }
+
+    async fn account_nonce_reservations(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<NonceReservationInfo>, Error> {
+        if !self.tx_sender.nonce_reservations_enabled {
+            return Err(Error::from(InvalidDataError::NonceReservationsDisabled));
+        }
+        let reservations = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?
+            .nonce_reservations_schema()
+            .get_account_reservations(account_id)
+            .await
+            .map_err(Error::storage)?;
+        Ok(reservations
+            .into_iter()
+            .map(api_nonce_reservation)
+            .collect())
+    }
+
+    async fn account_id(&self, account_id_or_address: &str) -> Result<AccountId, Error> {
+        if let Ok(account_id) = u32::from_str(account_id_or_address) {
+            return Ok(AccountId(account_id));
+        }
+        let address = account_id_or_address
+            .strip_prefix("0x")
+            .unwrap_or(account_id_or_address);
+        let address = Address::from_str(address)
+            .map_err(|_| Error::from(InvalidDataError::InvalidAccountIdOrAddress))?;
+        self.tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))
+    }
+}
+
+// Server implementation
+
+async fn reserve_nonces(
+    data: web::Data<ApiNonceReservationsData>,
+    Json(body): Json<NonceReservation>,
+) -> ApiResult<NonceReservationInfo> {
+    let start = Instant::now();
+    let res = data
+        .tx_sender
+        .reserve_nonces(body)
+        .await
+        .map(api_nonce_reservation)
+        .map_err(Error::from)
+        .into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "reserve_nonces");
+    res
+}
+
+async fn account_nonce_reservations(
+    data: web::Data<ApiNonceReservationsData>,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Vec<NonceReservationInfo>> {
+    let start = Instant::now();
+    let account_id = api_try!(data.account_id(&account_id_or_address).await);
+    let res = data.account_nonce_reservations(account_id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_nonce_reservations");
+    res
+}
+
+pub fn api_scope(tx_sender: TxSender) -> Scope {
+    let data = ApiNonceReservationsData::new(tx_sender);
+
+    web::scope("nonce_reservations")
+        .app_data(web::Data::new(data))
+        .route("", web::post().to(reserve_nonces))
+        .route(
+            "accounts/{account_id_or_address}",
+            web::get().to(account_nonce_reservations),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{
+            deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
+        },
+        SharedData,
+    };
+    use chrono::Utc;
+    use futures::channel::mpsc;
+    use zksync_api_types::v02::ApiVersion;
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{
+        tx::{PackedEthSignature, TxEthSignature},
+        ChainId,
+    };
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn nonce_reservations_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let (mempool_tx_request_sender, _mempool_tx_request_receiver) = mpsc::channel(100);
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                let mut tx_sender = TxSender::new(
+                    cfg.pool.clone(),
+                    dummy_sign_verifier(),
+                    dummy_fee_ticker(&[], None),
+                    &cfg.config.api.common,
+                    &cfg.config.api.token_config,
+                    mempool_tx_request_sender.clone(),
+                    ChainId(cfg.config.eth_client.chain_id),
+                );
+                tx_sender.nonce_reservations_enabled = true;
+                api_scope(tx_sender)
+            },
+            Some(shared_data),
+        );
+
+        let account = ZkSyncAccount::rand();
+        let eth_private_key = account
+            .try_get_eth_private_key()
+            .expect("Should have ETH private key");
+        let account_nonce = cfg
+            .pool
+            .access_storage()
+            .await?
+            .chain()
+            .account_schema()
+            .estimate_nonce(AccountId(1))
+            .await?
+            .expect("Account should exist");
+
+        // Reservation of no nonces is rejected.
+        let mut reservation = NonceReservation {
+            account_id: AccountId(1),
+            count: 0,
+            ttl_secs: 600,
+            timestamp: Utc::now(),
+            signature: TxEthSignature::EthereumSignature(PackedEthSignature::sign(
+                eth_private_key,
+                b"",
+            )?),
+        };
+        let response = client.reserve_nonces(&reservation).await?;
+        assert!(response.error.is_some());
+
+        reservation.count = 10;
+        reservation.signature = TxEthSignature::EthereumSignature(PackedEthSignature::sign(
+            eth_private_key,
+            reservation.get_ethereum_sign_message().as_bytes(),
+        )?);
+        let response = client.reserve_nonces(&reservation).await?;
+        let first: NonceReservationInfo = deserialize_response_result(response)?;
+        assert_eq!(first.first_nonce, account_nonce);
+        assert_eq!(*first.last_nonce, *account_nonce + 9);
+
+        // The next reservation follows the previous one.
+        let response = client.reserve_nonces(&reservation).await?;
+        let second: NonceReservationInfo = deserialize_response_result(response)?;
+        assert_eq!(*second.first_nonce, *first.last_nonce + 1);
+
+        let response = client.account_nonce_reservations("1").await?;
+        let reservations: Vec<NonceReservationInfo> = deserialize_response_result(response)?;
+        assert_eq!(reservations, vec![first, second]);
+
+        server.stop().await;
+        Ok(())
+    }
+}
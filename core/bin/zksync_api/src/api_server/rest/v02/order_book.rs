@@ -0,0 +1,408 @@
+//! Order book part of API implementation.
+//!
+//! The signed swap orders are stored by the server until they are matched into the `Swap`
+//! transactions, so the order-matching service and the trading frontends can query them.
+
+// Built-in uses
+use std::str::FromStr;
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    HttpRequest, Scope,
+};
+use chrono::Utc;
+
+// Workspace uses
+use zksync_api_types::v02::{
+    order_book::{IncomingOrder, IncomingOrderCancellation, OrderInfo, OrderStatus},
+    pagination::{
+        parse_query, AccountOrdersRequest, OpenOrdersRequest, Paginated, PaginationQuery,
+    },
+    transaction::TxHashSerializeWrapper,
+};
+use zksync_storage::order_book::records::StoredSwapOrder;
+use zksync_token_db_cache::TokenDBCache;
+use zksync_types::{tx::TxHash, AccountId, Address, TokenId, TokenLike};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    page_size::max_page_size,
+    paginate_trait::Paginate,
+    response::ApiResult,
+};
+use crate::{api_server::tx_sender::TxSender, api_try, fee_ticker::PriceError};
+
+/// Converts the stored order into its API representation. Open orders which were not
+/// matched before their `valid_until` time are reported as expired.
+pub(super) fn api_order(order: StoredSwapOrder) -> Result<OrderInfo, Error> {
+    let status = match order.status.as_str() {
+        "open" if order.valid_until < Utc::now().timestamp() => OrderStatus::Expired,
+        "open" => OrderStatus::Open,
+        "matched" => OrderStatus::Matched,
+        "filled" => OrderStatus::Filled,
+        "cancelled" => OrderStatus::Cancelled,
+        status => {
+            return Err(Error::storage(format!(
+                "unknown status of the order: {}",
+                status
+            )))
+        }
+    };
+    let filled_amount = order
+        .filled_amount
+        .to_bigint()
+        .and_then(|amount| amount.to_biguint())
+        .ok_or_else(|| Error::storage("invalid filled amount is stored"))?;
+    let swap_tx_hash = order
+        .swap_tx_hash
+        .as_deref()
+        .and_then(TxHash::from_slice)
+        .map(TxHashSerializeWrapper);
+
+    Ok(OrderInfo {
+        order_id: order.id as u64,
+        order_hash: TxHash::from_slice(&order.order_hash)
+            .ok_or_else(|| Error::storage("invalid order hash is stored"))?,
+        order: serde_json::from_value(order.order).map_err(Error::storage)?,
+        status,
+        filled_amount,
+        swap_tx_hash,
+        created_at: order.created_at,
+        updated_at: order.updated_at,
+    })
+}
+
+/// Shared data between `api/v0.2/orders` endpoints.
+#[derive(Clone)]
+struct ApiOrderBookData {
+    tx_sender: TxSender,
+    tokens: TokenDBCache,
+}
+
+impl ApiOrderBookData {
+    fn new(tx_sender: TxSender) -> Self {
+        let tokens = tx_sender.tokens.clone();
+        Self { tx_sender, tokens }
+    }
+
+    fn check_enabled(&self) -> Result<(), Error> {
+        if self.tx_sender.order_book_enabled {
+            Ok(())
+        } else {
+            Err(Error::from(InvalidDataError::OrderBookDisabled))
+        }
+    }
+
+    async fn order(&self, order_hash: TxHash) -> Result<OrderInfo, Error> {
+        self.check_enabled()?;
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let order = storage
+            .order_book_schema()
+            .get_order(order_hash)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::OrderNotFound))?;
+        api_order(order)
+    }
+
+    async fn token_pair(
+        &self,
+        token_sell: &str,
+        token_buy: &str,
+    ) -> Result<(TokenId, TokenId), Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let mut token_ids = Vec::with_capacity(2);
+        for token_like in [token_sell, token_buy].iter() {
+            let token = self
+                .tokens
+                .get_token(&mut storage, TokenLike::parse(token_like))
+                .await
+                .map_err(Error::storage)?
+                .ok_or_else(|| {
+                    Error::from(PriceError::token_not_found("Token not found in storage"))
+                })?;
+            token_ids.push(token.id);
+        }
+        Ok((token_ids[0], token_ids[1]))
+    }
+
+    async fn account_id(&self, account_id_or_address: &str) -> Result<AccountId, Error> {
+        if let Ok(account_id) = u32::from_str(account_id_or_address) {
+            return Ok(AccountId(account_id));
+        }
+        let address = account_id_or_address
+            .strip_prefix("0x")
+            .unwrap_or(account_id_or_address);
+        let address = Address::from_str(address)
+            .map_err(|_| Error::from(InvalidDataError::InvalidAccountIdOrAddress))?;
+        self.tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))
+    }
+}
+
+// Server implementation
+
+async fn submit_order(
+    data: web::Data<ApiOrderBookData>,
+    Json(body): Json<IncomingOrder>,
+) -> ApiResult<TxHashSerializeWrapper> {
+    let start = Instant::now();
+    let res = data
+        .tx_sender
+        .submit_order(body.order, body.eth_signature)
+        .await
+        .map(TxHashSerializeWrapper)
+        .map_err(Error::from);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "submit_order");
+    res.into()
+}
+
+async fn cancel_order(
+    data: web::Data<ApiOrderBookData>,
+    order_hash: web::Path<TxHash>,
+    Json(body): Json<IncomingOrderCancellation>,
+) -> ApiResult<OrderInfo> {
+    let start = Instant::now();
+    api_try!(data
+        .tx_sender
+        .cancel_order(*order_hash, body.signature)
+        .await
+        .map_err(Error::from));
+    let res = data.order(*order_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "cancel_order");
+    res
+}
+
+async fn order(
+    data: web::Data<ApiOrderBookData>,
+    order_hash: web::Path<TxHash>,
+) -> ApiResult<OrderInfo> {
+    let start = Instant::now();
+    let res = data.order(*order_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "order");
+    res
+}
+
+async fn open_orders(
+    data: web::Data<ApiOrderBookData>,
+    http_request: HttpRequest,
+    path: web::Path<(String, String)>,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<OrderInfo, u64>> {
+    let start = Instant::now();
+    api_try!(data.check_enabled());
+    let (token_sell, token_buy) = path.into_inner();
+    let (token_sell, token_buy) = api_try!(data.token_pair(&token_sell, &token_buy).await);
+    let query = api_try!(parse_query(query).map_err(Error::from));
+    let query = PaginationQuery {
+        from: OpenOrdersRequest {
+            token_sell,
+            token_buy,
+            order_id: query.from,
+        },
+        limit: query.limit,
+        direction: query.direction,
+    };
+    let max_page_size = api_try!(max_page_size(&http_request, "open_orders").await);
+    let mut storage = api_try!(data
+        .tx_sender
+        .pool
+        .access_storage()
+        .await
+        .map_err(Error::storage));
+    let res = storage.paginate_checked(&query, max_page_size).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "open_orders");
+    res
+}
+
+async fn account_orders(
+    data: web::Data<ApiOrderBookData>,
+    http_request: HttpRequest,
+    account_id_or_address: web::Path<String>,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<OrderInfo, u64>> {
+    let start = Instant::now();
+    api_try!(data.check_enabled());
+    let account_id = api_try!(data.account_id(&account_id_or_address).await);
+    let query = api_try!(parse_query(query).map_err(Error::from));
+    let query = PaginationQuery {
+        from: AccountOrdersRequest {
+            account_id,
+            order_id: query.from,
+        },
+        limit: query.limit,
+        direction: query.direction,
+    };
+    let max_page_size = api_try!(max_page_size(&http_request, "account_orders").await);
+    let mut storage = api_try!(data
+        .tx_sender
+        .pool
+        .access_storage()
+        .await
+        .map_err(Error::storage));
+    let res = storage.paginate_checked(&query, max_page_size).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_orders");
+    res
+}
+
+pub fn api_scope(tx_sender: TxSender) -> Scope {
+    let data = ApiOrderBookData::new(tx_sender);
+
+    web::scope("orders")
+        .app_data(web::Data::new(data))
+        .route("", web::post().to(submit_order))
+        .route("open/{token_sell}/{token_buy}", web::get().to(open_orders))
+        .route(
+            "accounts/{account_id_or_address}",
+            web::get().to(account_orders),
+        )
+        .route("{order_hash}", web::get().to(order))
+        .route("{order_hash}/cancel", web::post().to(cancel_order))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{
+            deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
+        },
+        SharedData,
+    };
+    use futures::channel::mpsc;
+    use num::BigUint;
+    use zksync_api_types::v02::{
+        pagination::{ApiEither, PaginationDirection},
+        ApiVersion,
+    };
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{
+        tx::{TimeRange, TxSignature},
+        ChainId,
+    };
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn orders_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let (mempool_tx_request_sender, _mempool_tx_request_receiver) = mpsc::channel(100);
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                let mut tx_sender = TxSender::new(
+                    cfg.pool.clone(),
+                    dummy_sign_verifier(),
+                    dummy_fee_ticker(&[], None),
+                    &cfg.config.api.common,
+                    &cfg.config.api.token_config,
+                    mempool_tx_request_sender.clone(),
+                    ChainId(cfg.config.eth_client.chain_id),
+                );
+                tx_sender.order_book_enabled = true;
+                api_scope(tx_sender)
+            },
+            Some(shared_data),
+        );
+
+        let account = ZkSyncAccount::rand();
+        account.set_account_id(Some(AccountId(1)));
+        let order = account.sign_order(
+            TokenId(0),
+            TokenId(1),
+            BigUint::from(1u32),
+            BigUint::from(2u32),
+            BigUint::from(100u32),
+            &account.address,
+            None,
+            false,
+            TimeRange::new(0, u64::MAX),
+        );
+        let order_hash = order.hash();
+
+        // The order is not signed with the signing key of the account.
+        let response = client.submit_order(order.clone(), None).await?;
+        assert!(response.error.is_some());
+
+        cfg.pool
+            .access_storage()
+            .await?
+            .order_book_schema()
+            .store_order(&order, None)
+            .await?;
+
+        let response = client.order(order_hash).await?;
+        let info: OrderInfo = deserialize_response_result(response)?;
+        assert_eq!(info.order_hash, order_hash);
+        assert_eq!(info.status, OrderStatus::Open);
+        assert_eq!(info.filled_amount, BigUint::from(0u32));
+
+        let query = PaginationQuery {
+            from: ApiEither::from(info.order_id),
+            limit: 1,
+            direction: PaginationDirection::Older,
+        };
+        let response = client.open_orders(&query, "ETH", "PHNX").await?;
+        let orders: Paginated<OrderInfo, u64> = deserialize_response_result(response)?;
+        assert_eq!(orders.list.len(), 1);
+        assert_eq!(orders.list[0].order_hash, order_hash);
+        let response = client.open_orders(&query, "PHNX", "ETH").await?;
+        let orders: Paginated<OrderInfo, u64> = deserialize_response_result(response)?;
+        assert!(orders
+            .list
+            .iter()
+            .all(|order| order.order_hash != order_hash));
+
+        let response = client.account_orders(&query, "1").await?;
+        let orders: Paginated<OrderInfo, u64> = deserialize_response_result(response)?;
+        assert_eq!(orders.list[0].order_hash, order_hash);
+
+        // Only the owner of the order can cancel it.
+        let other_account = ZkSyncAccount::rand();
+        let signature =
+            TxSignature::sign_musig(&other_account.private_key, &order.get_cancellation_bytes());
+        let response = client.cancel_order(order_hash, signature).await?;
+        assert!(response.error.is_some());
+
+        let signature =
+            TxSignature::sign_musig(&account.private_key, &order.get_cancellation_bytes());
+        let response = client.cancel_order(order_hash, signature.clone()).await?;
+        let info: OrderInfo = deserialize_response_result(response)?;
+        assert_eq!(info.status, OrderStatus::Cancelled);
+        // The order can't be cancelled twice.
+        let response = client.cancel_order(order_hash, signature).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+//! Maximum page sizes of the paginated endpoints.
+
+// Built-in uses
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// External uses
+use actix_web::{web::Data, HttpRequest};
+
+// Workspace uses
+use zksync_api_types::v02::label::LABELS_API_KEY_HEADER;
+use zksync_config::configs::api::RestApiConfig;
+use zksync_storage::ConnectionPool;
+
+// Local uses
+use super::error::{Error, InvalidDataError};
+
+/// Maximum page sizes enforced by the server for all the `api/v0.2` endpoints.
+#[derive(Debug, Clone)]
+pub struct PageSizeLimits {
+    pool: ConnectionPool,
+    max_page_size: u32,
+    endpoint_max_page_sizes: Arc<HashMap<String, u32>>,
+}
+
+impl PageSizeLimits {
+    pub fn new(pool: ConnectionPool, config: &RestApiConfig) -> Self {
+        Self {
+            pool,
+            max_page_size: config.max_page_size,
+            endpoint_max_page_sizes: Arc::new(config.endpoint_max_page_sizes()),
+        }
+    }
+
+    /// Returns the maximum page size of the endpoint. The limit set for the API key provided
+    /// in the request takes precedence over the configured ones.
+    pub async fn max_page_size(
+        &self,
+        http_request: &HttpRequest,
+        endpoint: &str,
+    ) -> Result<u32, Error> {
+        if let Some(api_key) = http_request.headers().get(LABELS_API_KEY_HEADER) {
+            let api_key = api_key
+                .to_str()
+                .map_err(|_| Error::from(InvalidDataError::InvalidApiKey))?;
+            let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+            let key_max_page_size = storage
+                .labels_schema()
+                .get_api_key_max_page_size(&tiny_keccak::keccak256(api_key.as_bytes()))
+                .await
+                .map_err(Error::storage)?;
+            if let Some(max_page_size) = key_max_page_size {
+                return Ok(max_page_size);
+            }
+        }
+
+        Ok(self
+            .endpoint_max_page_sizes
+            .get(endpoint)
+            .copied()
+            .unwrap_or(self.max_page_size))
+    }
+}
+
+/// Returns the maximum page size for the request to the endpoint.
+pub(super) async fn max_page_size(
+    http_request: &HttpRequest,
+    endpoint: &str,
+) -> Result<u32, Error> {
+    http_request
+        .app_data::<Data<PageSizeLimits>>()
+        .expect("Wrong app data type")
+        .max_page_size(http_request, endpoint)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::test_utils::TestServerConfig;
+    use actix_web::test::TestRequest;
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn page_size_limits() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let config = RestApiConfig {
+            max_page_size: 100,
+            endpoint_max_page_sizes: vec!["account_txs:20".to_owned()],
+            ..cfg.config.api.rest.clone()
+        };
+        let limits = PageSizeLimits::new(cfg.pool.clone(), &config);
+
+        let request = TestRequest::default().to_http_request();
+        assert_eq!(
+            limits.max_page_size(&request, "account_txs").await.unwrap(),
+            20
+        );
+        assert_eq!(
+            limits
+                .max_page_size(&request, "block_pagination")
+                .await
+                .unwrap(),
+            100
+        );
+
+        let api_key = "test-page-size-key";
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .labels_schema()
+                .store_api_key(&tiny_keccak::keccak256(api_key.as_bytes()), "indexer")
+                .await?;
+            storage
+                .labels_schema()
+                .set_api_keys_max_page_size("indexer", Some(500))
+                .await?;
+        }
+        let request = TestRequest::default()
+            .insert_header((LABELS_API_KEY_HEADER, api_key))
+            .to_http_request();
+        assert_eq!(
+            limits.max_page_size(&request, "account_txs").await.unwrap(),
+            500
+        );
+        assert_eq!(
+            limits
+                .max_page_size(&request, "block_pagination")
+                .await
+                .unwrap(),
+            500
+        );
+
+        // Keys without the override get the server limits.
+        let request = TestRequest::default()
+            .insert_header((LABELS_API_KEY_HEADER, "unknown-key"))
+            .to_http_request();
+        assert_eq!(
+            limits.max_page_size(&request, "account_txs").await.unwrap(),
+            20
+        );
+
+        cfg.pool
+            .access_storage()
+            .await?
+            .labels_schema()
+            .remove_api_keys("indexer")
+            .await?;
+        Ok(())
+    }
+}
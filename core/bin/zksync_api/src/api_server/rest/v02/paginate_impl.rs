@@ -6,26 +6,49 @@
 use zksync_api_types::{
     v02::{
         block::BlockInfo,
+        order_book::OrderInfo,
         pagination::{
-            AccountTxsRequest, ApiEither, BlockAndTxHash, Paginated, PaginationQuery,
-            PendingOpsRequest,
+            AccountOrdersRequest, AccountStandingOrdersRequest, AccountTxsRequest, ApiEither,
+            BlockAndTxHash, OpenOrdersRequest, Paginated, PaginationQuery, PendingOpsRequest,
+            PendingPriorityOpsRequest,
         },
+        priority_op::PendingPriorityOp,
+        standing_order::StandingOrderInfo,
         transaction::{Transaction, TxHashSerializeWrapper},
     },
     Either,
 };
 use zksync_storage::StorageProcessor;
-use zksync_types::{BlockNumber, SerialId, Token, TokenId};
+use zksync_types::{BlockNumber, PriorityOp, SerialId, Token, TokenId};
 
 // Local uses
 use super::{
     block::block_info_from_details,
     error::{Error, InvalidDataError},
+    order_book::api_order,
     paginate_trait::Paginate,
+    standing_order::api_standing_order,
 };
 
 use zksync_api_types::v02::transaction::{L1Transaction, TransactionData, TxInBlockStatus};
 
+/// Represents the priority operation which is not executed yet as a queued transaction.
+pub(super) fn pending_op_transaction(op: PriorityOp) -> Transaction {
+    let tx_hash = op.tx_hash();
+    let tx = L1Transaction::from_pending_op(op.data, op.eth_hash, op.serial_id, tx_hash);
+    Transaction {
+        tx_hash,
+        block_index: None,
+        block_number: None,
+        op: TransactionData::L1(tx),
+        status: TxInBlockStatus::Queued,
+        fail_reason: None,
+        created_at: None,
+        batch_id: None,
+        memo: None,
+    }
+}
+
 #[async_trait::async_trait]
 impl Paginate<ApiEither<TokenId>> for StorageProcessor<'_> {
     type OutputObj = Token;
@@ -306,31 +329,64 @@ impl Paginate<PendingOpsRequest> for StorageProcessor<'_> {
             .map_err(Error::storage)?;
 
         let count = result.len() as u32;
-        let txs = result
-            .into_iter()
-            .map(|op| {
-                let tx_hash = op.tx_hash();
-                let tx = L1Transaction::from_pending_op(
-                    op.data.clone(),
-                    op.eth_hash,
-                    op.serial_id,
-                    tx_hash,
-                );
-                Transaction {
-                    tx_hash,
-                    block_index: None,
-                    block_number: None,
-                    op: TransactionData::L1(tx),
-                    status: TxInBlockStatus::Queued,
-                    fail_reason: None,
-                    created_at: None,
-                    batch_id: None,
+        let txs = result.into_iter().map(pending_op_transaction).collect();
+
+        Ok(Paginated::new(
+            txs,
+            serial_id,
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl Paginate<PendingPriorityOpsRequest> for StorageProcessor<'_> {
+    type OutputObj = PendingPriorityOp;
+    type OutputId = SerialId;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<PendingPriorityOpsRequest>,
+    ) -> Result<Paginated<PendingPriorityOp, SerialId>, Error> {
+        let serial_id = match query.from.serial_id.inner {
+            Either::Left(serial_id) => serial_id,
+            Either::Right(_) => {
+                if let Some(serial_id) = self
+                    .chain()
+                    .mempool_schema()
+                    .get_max_pending_priority_op_serial_id()
+                    .await
+                    .map_err(Error::storage)?
+                {
+                    serial_id
+                } else {
+                    return Ok(Paginated::new(
+                        Vec::new(),
+                        Default::default(),
+                        query.limit,
+                        query.direction,
+                        0,
+                    ));
                 }
+            }
+        };
+        let ops: Vec<_> = self
+            .chain()
+            .mempool_schema()
+            .get_pending_priority_ops_page(serial_id, query.limit, query.direction)
+            .await
+            .map_err(Error::storage)?
+            .into_iter()
+            .map(|(op, confirmed)| {
+                PendingPriorityOp::new(op, confirmed, query.from.confirmations_for_eth_event)
             })
             .collect();
 
+        let count = ops.len() as u32;
         Ok(Paginated::new(
-            txs,
+            ops,
             serial_id,
             query.limit,
             query.direction,
@@ -338,3 +394,190 @@ impl Paginate<PendingOpsRequest> for StorageProcessor<'_> {
         ))
     }
 }
+
+#[async_trait::async_trait]
+impl Paginate<OpenOrdersRequest> for StorageProcessor<'_> {
+    type OutputObj = OrderInfo;
+    type OutputId = u64;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<OpenOrdersRequest>,
+    ) -> Result<Paginated<OrderInfo, u64>, Error> {
+        let (token_sell, token_buy) = (query.from.token_sell, query.from.token_buy);
+        let order_id = match query.from.order_id.inner {
+            Either::Left(order_id) => order_id,
+            Either::Right(_) => {
+                if let Some(order_id) = self
+                    .order_book_schema()
+                    .get_max_open_order_id(token_sell, token_buy)
+                    .await
+                    .map_err(Error::storage)?
+                {
+                    order_id as u64
+                } else {
+                    return Ok(Paginated::new(
+                        Vec::new(),
+                        Default::default(),
+                        query.limit,
+                        query.direction,
+                        0,
+                    ));
+                }
+            }
+        };
+        let orders = self
+            .order_book_schema()
+            .get_open_orders_page(
+                token_sell,
+                token_buy,
+                order_id.min(i64::MAX as u64) as i64,
+                query.limit,
+                query.direction,
+            )
+            .await
+            .map_err(Error::storage)?
+            .into_iter()
+            .map(api_order)
+            .collect::<Result<Vec<_>, _>>()?;
+        let count = self
+            .order_book_schema()
+            .get_open_orders_count(token_sell, token_buy)
+            .await
+            .map_err(Error::storage)?;
+
+        Ok(Paginated::new(
+            orders,
+            order_id,
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl Paginate<AccountOrdersRequest> for StorageProcessor<'_> {
+    type OutputObj = OrderInfo;
+    type OutputId = u64;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<AccountOrdersRequest>,
+    ) -> Result<Paginated<OrderInfo, u64>, Error> {
+        let account_id = query.from.account_id;
+        let order_id = match query.from.order_id.inner {
+            Either::Left(order_id) => order_id,
+            Either::Right(_) => {
+                if let Some(order_id) = self
+                    .order_book_schema()
+                    .get_max_account_order_id(account_id)
+                    .await
+                    .map_err(Error::storage)?
+                {
+                    order_id as u64
+                } else {
+                    return Ok(Paginated::new(
+                        Vec::new(),
+                        Default::default(),
+                        query.limit,
+                        query.direction,
+                        0,
+                    ));
+                }
+            }
+        };
+        let orders = self
+            .order_book_schema()
+            .get_account_orders_page(
+                account_id,
+                order_id.min(i64::MAX as u64) as i64,
+                query.limit,
+                query.direction,
+            )
+            .await
+            .map_err(Error::storage)?
+            .into_iter()
+            .map(api_order)
+            .collect::<Result<Vec<_>, _>>()?;
+        let count = self
+            .order_book_schema()
+            .get_account_orders_count(account_id)
+            .await
+            .map_err(Error::storage)?;
+
+        Ok(Paginated::new(
+            orders,
+            order_id,
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl Paginate<AccountStandingOrdersRequest> for StorageProcessor<'_> {
+    type OutputObj = StandingOrderInfo;
+    type OutputId = u64;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<AccountStandingOrdersRequest>,
+    ) -> Result<Paginated<StandingOrderInfo, u64>, Error> {
+        let account_id = query.from.account_id;
+        let standing_order_id = match query.from.standing_order_id.inner {
+            Either::Left(standing_order_id) => standing_order_id,
+            Either::Right(_) => {
+                if let Some(standing_order_id) = self
+                    .standing_orders_schema()
+                    .get_max_account_standing_order_id(account_id)
+                    .await
+                    .map_err(Error::storage)?
+                {
+                    standing_order_id as u64
+                } else {
+                    return Ok(Paginated::new(
+                        Vec::new(),
+                        Default::default(),
+                        query.limit,
+                        query.direction,
+                        0,
+                    ));
+                }
+            }
+        };
+        let stored_orders = self
+            .standing_orders_schema()
+            .get_account_standing_orders_page(
+                account_id,
+                standing_order_id.min(i64::MAX as u64) as i64,
+                query.limit,
+                query.direction,
+            )
+            .await
+            .map_err(Error::storage)?;
+        let mut orders = Vec::with_capacity(stored_orders.len());
+        for order in stored_orders {
+            let payments = self
+                .standing_orders_schema()
+                .get_standing_order_payments(order.id)
+                .await
+                .map_err(Error::storage)?;
+            orders.push(api_standing_order(order, &payments)?);
+        }
+        let count = self
+            .standing_orders_schema()
+            .get_account_standing_orders_count(account_id)
+            .await
+            .map_err(Error::storage)?;
+
+        Ok(Paginated::new(
+            orders,
+            standing_order_id,
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
@@ -4,7 +4,7 @@
 use serde::Serialize;
 
 // Workspace uses
-use zksync_api_types::v02::pagination::{Paginated, PaginationQuery, MAX_LIMIT};
+use zksync_api_types::v02::pagination::{Paginated, PaginationQuery};
 
 // Local uses
 use super::error::{Error, InvalidDataError};
@@ -22,11 +22,20 @@ pub trait Paginate<I: Serialize + Send + Sync + 'static> {
     async fn paginate_checked(
         &mut self,
         query: &PaginationQuery<I>,
+        max_page_size: u32,
     ) -> Result<Paginated<Self::OutputObj, Self::OutputId>, Error> {
-        if query.limit > MAX_LIMIT {
-            Err(Error::from(InvalidDataError::PaginationLimitTooBig))
-        } else {
-            self.paginate(query).await
-        }
+        check_pagination_limit(query.limit, max_page_size)?;
+        self.paginate(query).await
+    }
+}
+
+/// Checks that the requested page is not too big.
+pub fn check_pagination_limit(limit: u32, max_page_size: u32) -> Result<(), Error> {
+    if limit > max_page_size {
+        Err(Error::from(InvalidDataError::PaginationLimitTooBig(
+            max_page_size,
+        )))
+    } else {
+        Ok(())
     }
 }
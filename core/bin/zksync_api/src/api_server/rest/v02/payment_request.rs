@@ -0,0 +1,278 @@
+//! Payment requests part of API implementation.
+//!
+//! Payment request is created by the merchant and shared with the payer as the link. The request
+//! is marked fulfilled once the matching transfer is executed, see the `payment_request_watcher`
+//! module.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+use chrono::{Duration, Utc};
+use num::Zero;
+
+// Workspace uses
+use zksync_api_types::v02::{
+    payment_request::{IncomingPaymentRequest, PaymentRequestInfo, PaymentRequestStatus},
+    transaction::TxHashSerializeWrapper,
+};
+use zksync_storage::{payment_requests::records::StoredPaymentRequest, ConnectionPool};
+use zksync_token_db_cache::TokenDBCache;
+use zksync_types::{tx::TxHash, Address, TokenId, TokenLike};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::fee_ticker::PriceError;
+
+/// Maximum lifetime of the payment request.
+const MAX_PAYMENT_REQUEST_TTL_DAYS: i64 = 90;
+/// Maximum length of the reference, in bytes. Same as the limit of the transfer memos,
+/// since the reference is carried as the memo.
+const MAX_REFERENCE_LENGTH: usize = 256;
+
+/// Shared data between `api/v0.2/payment_requests` endpoints.
+#[derive(Debug, Clone)]
+struct ApiPaymentRequestsData {
+    /// Requests are stored, so the main database connection is used.
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    /// Public URL of the REST API, the links to the requests are built upon it.
+    url: String,
+    enabled: bool,
+}
+
+impl ApiPaymentRequestsData {
+    fn new(pool: ConnectionPool, tokens: TokenDBCache, url: String, enabled: bool) -> Self {
+        Self {
+            pool,
+            tokens,
+            url,
+            enabled,
+        }
+    }
+
+    fn check_enabled(&self) -> Result<(), Error> {
+        if self.enabled {
+            Ok(())
+        } else {
+            Err(Error::from(InvalidDataError::PaymentRequestsDisabled))
+        }
+    }
+
+    fn api_payment_request(
+        &self,
+        request: StoredPaymentRequest,
+    ) -> Result<PaymentRequestInfo, Error> {
+        let status = if request.fulfilled_tx_hash.is_some() {
+            PaymentRequestStatus::Fulfilled
+        } else if request.expires_at < Utc::now() {
+            PaymentRequestStatus::Expired
+        } else {
+            PaymentRequestStatus::Pending
+        };
+        let amount = request
+            .amount
+            .to_bigint()
+            .and_then(|amount| amount.to_biguint())
+            .ok_or_else(|| Error::storage("invalid payment request amount is stored"))?;
+
+        Ok(PaymentRequestInfo {
+            payment_request_id: request.id as u64,
+            url: format!(
+                "{}/api/v0.2/payment_requests/{}",
+                self.url.trim_end_matches('/'),
+                request.id
+            ),
+            recipient: Address::from_slice(&request.recipient),
+            token: TokenId(request.token_id as u32),
+            amount,
+            reference: request.reference,
+            expires_at: request.expires_at,
+            status,
+            tx_hash: request
+                .fulfilled_tx_hash
+                .as_deref()
+                .and_then(TxHash::from_slice)
+                .map(TxHashSerializeWrapper),
+            fulfilled_at: request.fulfilled_at,
+            created_at: request.created_at,
+        })
+    }
+
+    async fn create_payment_request(
+        &self,
+        request: IncomingPaymentRequest,
+    ) -> Result<PaymentRequestInfo, Error> {
+        self.check_enabled()?;
+        if request.amount.is_zero() {
+            return Err(Error::from(InvalidDataError::InvalidPaymentRequest(
+                "amount should be positive".to_string(),
+            )));
+        }
+        let now = Utc::now();
+        if request.expires_at <= now
+            || request.expires_at > now + Duration::days(MAX_PAYMENT_REQUEST_TTL_DAYS)
+        {
+            return Err(Error::from(InvalidDataError::InvalidPaymentRequest(
+                format!(
+                    "request should expire within {} days",
+                    MAX_PAYMENT_REQUEST_TTL_DAYS
+                ),
+            )));
+        }
+        if let Some(reference) = &request.reference {
+            if reference.is_empty() || reference.len() > MAX_REFERENCE_LENGTH {
+                return Err(Error::from(InvalidDataError::InvalidPaymentRequest(
+                    format!(
+                        "reference should be from 1 to {} bytes long",
+                        MAX_REFERENCE_LENGTH
+                    ),
+                )));
+            }
+        }
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        self.tokens
+            .get_token(&mut storage, TokenLike::Id(request.token))
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| {
+                Error::from(PriceError::token_not_found("Token not found in storage"))
+            })?;
+        let id = storage
+            .payment_requests_schema()
+            .store_payment_request(
+                request.recipient,
+                request.token,
+                &request.amount,
+                request.reference.as_deref(),
+                request.expires_at,
+            )
+            .await
+            .map_err(Error::storage)?;
+        let stored = storage
+            .payment_requests_schema()
+            .get_payment_request(id)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::storage("payment request is not stored"))?;
+        self.api_payment_request(stored)
+    }
+
+    async fn payment_request(&self, id: u64) -> Result<PaymentRequestInfo, Error> {
+        self.check_enabled()?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let request = storage
+            .payment_requests_schema()
+            .get_payment_request(id as i64)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::PaymentRequestNotFound))?;
+        self.api_payment_request(request)
+    }
+}
+
+// Server implementation
+
+async fn create_payment_request(
+    data: web::Data<ApiPaymentRequestsData>,
+    Json(body): Json<IncomingPaymentRequest>,
+) -> ApiResult<PaymentRequestInfo> {
+    let start = Instant::now();
+    let res = data.create_payment_request(body).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "create_payment_request");
+    res
+}
+
+async fn payment_request(
+    data: web::Data<ApiPaymentRequestsData>,
+    id: web::Path<u64>,
+) -> ApiResult<PaymentRequestInfo> {
+    let start = Instant::now();
+    let res = data.payment_request(*id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "payment_request");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool, tokens: TokenDBCache, url: String, enabled: bool) -> Scope {
+    let data = ApiPaymentRequestsData::new(pool, tokens, url, enabled);
+
+    web::scope("payment_requests")
+        .app_data(web::Data::new(data))
+        .route("", web::post().to(create_payment_request))
+        .route("{id}", web::get().to(payment_request))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use num::BigUint;
+    use zksync_api_types::v02::ApiVersion;
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn payment_requests_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                api_scope(
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    cfg.config.api.rest.url.clone(),
+                    true,
+                )
+            },
+            Some(shared_data),
+        );
+
+        let mut request = IncomingPaymentRequest {
+            recipient: Address::repeat_byte(0x01),
+            token: TokenId(0),
+            amount: BigUint::from(0u32),
+            reference: Some("INV-1".to_string()),
+            expires_at: Utc::now() + Duration::days(1),
+        };
+        let response = client.create_payment_request(&request).await?;
+        assert!(response.error.is_some());
+
+        request.amount = BigUint::from(1000u32);
+        let response = client.create_payment_request(&request).await?;
+        let info: PaymentRequestInfo = deserialize_response_result(response)?;
+        assert_eq!(info.status, PaymentRequestStatus::Pending);
+        assert_eq!(info.reference.as_deref(), Some("INV-1"));
+        assert!(info
+            .url
+            .ends_with(&format!("/payment_requests/{}", info.payment_request_id)));
+
+        let response = client.payment_request(info.payment_request_id).await?;
+        let stored_info: PaymentRequestInfo = deserialize_response_result(response)?;
+        assert_eq!(stored_info.amount, BigUint::from(1000u32));
+        assert!(stored_info.tx_hash.is_none());
+
+        let response = client.payment_request(info.payment_request_id + 1).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+//! Priority operations part of API implementation.
+//!
+//! Priority operations are observed by the server before they get enough confirmations on L1,
+//! so the depositors can track their operations until they are included into a block.
+//! The executed deposits are also served aggregated per day and token, so the bridges can
+//! reconcile the bridged value without scanning the operations.
+
+// Built-in uses
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, HttpRequest, Scope};
+use num::BigUint;
+
+// Workspace uses
+use zksync_api_types::v02::{
+    pagination::{parse_query, Paginated, PaginationQuery, PendingPriorityOpsRequest},
+    priority_op::{
+        DailyDeposits, DepositAggregates, DepositAggregatesQuery, DepositTotal, PendingPriorityOp,
+    },
+};
+use zksync_storage::ConnectionPool;
+use zksync_types::{SerialId, TokenId};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    page_size::max_page_size,
+    paginate_trait::Paginate,
+    response::ApiResult,
+};
+use crate::api_try;
+
+/// Maximum length of the period the deposit aggregates are requested for, in days.
+pub const MAX_DEPOSIT_AGGREGATES_DAYS: i64 = 366;
+
+/// Shared data between `api/v0.2/priority_ops` endpoints.
+#[derive(Debug, Clone)]
+struct ApiPriorityOpData {
+    pool: ConnectionPool,
+    confirmations_for_eth_event: u64,
+}
+
+impl ApiPriorityOpData {
+    fn new(pool: ConnectionPool, confirmations_for_eth_event: u64) -> Self {
+        Self {
+            pool,
+            confirmations_for_eth_event,
+        }
+    }
+
+    async fn deposit_aggregates(
+        &self,
+        query: DepositAggregatesQuery,
+    ) -> Result<DepositAggregates, Error> {
+        let days = (query.to_day - query.from_day).num_days() + 1;
+        if !(1..=MAX_DEPOSIT_AGGREGATES_DAYS).contains(&days) {
+            return Err(Error::from(
+                InvalidDataError::InvalidDepositAggregatesPeriod,
+            ));
+        }
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let stored = storage
+            .deposit_aggregates_schema()
+            .load_daily_deposits(query.sender, query.token, query.from_day, query.to_day)
+            .await
+            .map_err(Error::storage)?;
+
+        let mut daily = Vec::with_capacity(stored.len());
+        let mut totals: BTreeMap<TokenId, DepositTotal> = BTreeMap::new();
+        for deposits in stored {
+            let token_id = TokenId(deposits.token_id as u32);
+            let amount = deposits
+                .amount
+                .to_bigint()
+                .and_then(|amount| amount.to_biguint())
+                .ok_or_else(|| Error::storage("invalid deposits amount is stored"))?;
+            let total = totals.entry(token_id).or_insert_with(|| DepositTotal {
+                token_id,
+                amount: BigUint::default(),
+                deposits_count: 0,
+            });
+            total.amount += &amount;
+            total.deposits_count += deposits.deposits_count as u64;
+            daily.push(DailyDeposits {
+                day: deposits.day,
+                token_id,
+                amount,
+                deposits_count: deposits.deposits_count as u64,
+                senders_count: deposits.senders_count as u64,
+            });
+        }
+
+        Ok(DepositAggregates {
+            from_day: query.from_day,
+            to_day: query.to_day,
+            sender: query.sender,
+            daily,
+            totals: totals.into_values().collect(),
+        })
+    }
+}
+
+// Server implementation
+
+async fn pending_priority_ops(
+    data: web::Data<ApiPriorityOpData>,
+    http_request: HttpRequest,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<PendingPriorityOp, SerialId>> {
+    let start = Instant::now();
+    let query = api_try!(parse_query(query).map_err(Error::from));
+    let query = PaginationQuery {
+        from: PendingPriorityOpsRequest {
+            serial_id: query.from,
+            confirmations_for_eth_event: data.confirmations_for_eth_event,
+        },
+        limit: query.limit,
+        direction: query.direction,
+    };
+    let max_page_size = api_try!(max_page_size(&http_request, "pending_priority_ops").await);
+    let mut storage = api_try!(data.pool.access_storage().await.map_err(Error::storage));
+    let res = storage.paginate_checked(&query, max_page_size).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "pending_priority_ops");
+    res
+}
+
+async fn deposit_aggregates(
+    data: web::Data<ApiPriorityOpData>,
+    web::Query(query): web::Query<DepositAggregatesQuery>,
+) -> ApiResult<DepositAggregates> {
+    let start = Instant::now();
+    let res = data.deposit_aggregates(query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "deposit_aggregates");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool, confirmations_for_eth_event: u64) -> Scope {
+    let data = ApiPriorityOpData::new(pool, confirmations_for_eth_event);
+
+    web::scope("priority_ops")
+        .app_data(web::Data::new(data))
+        .route("pending", web::get().to(pending_priority_ops))
+        .route("deposits/aggregates", web::get().to(deposit_aggregates))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::{
+        pagination::{ApiEither, PaginationDirection},
+        priority_op::PendingPriorityOpStatus,
+        ApiVersion,
+    };
+    use zksync_types::{Address, Deposit, PriorityOp, TokenId, ZkSyncPriorityOp, H256};
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn priority_ops_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let confirmations_for_eth_event = cfg.config.eth_watch.confirmations_for_eth_event;
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| api_scope(cfg.pool.clone(), confirmations_for_eth_event),
+            Some(shared_data),
+        );
+
+        // Serial id is chosen to be greater than the ones of the executed operations.
+        let serial_id = 10_000;
+        let op = PriorityOp {
+            serial_id,
+            data: ZkSyncPriorityOp::Deposit(Deposit {
+                from: Address::random(),
+                token: TokenId(0),
+                amount: 100u32.into(),
+                to: Address::random(),
+            }),
+            deadline_block: 0,
+            eth_hash: H256::random(),
+            eth_block: 10,
+            eth_block_index: Some(1),
+            l1_sender: None,
+        };
+        cfg.pool
+            .access_storage()
+            .await?
+            .chain()
+            .mempool_schema()
+            .insert_priority_ops(&[op.clone()], false)
+            .await?;
+
+        let query = PaginationQuery {
+            from: ApiEither::from(serial_id),
+            limit: 1,
+            direction: PaginationDirection::Older,
+        };
+        let response = client.pending_priority_ops(&query).await?;
+        let paginated: Paginated<PendingPriorityOp, SerialId> =
+            deserialize_response_result(response)?;
+        assert_eq!(paginated.list.len(), 1);
+        let pending_op = &paginated.list[0];
+        assert_eq!(pending_op.serial_id, serial_id);
+        assert_eq!(pending_op.eth_hash, op.eth_hash);
+        assert_eq!(
+            pending_op.expected_accept_block,
+            op.eth_block + confirmations_for_eth_event
+        );
+        assert_eq!(
+            pending_op.status,
+            PendingPriorityOpStatus::AwaitingConfirmations
+        );
+
+        cfg.pool
+            .access_storage()
+            .await?
+            .chain()
+            .mempool_schema()
+            .remove_priority_ops_from_mempool(&[serial_id])
+            .await?;
+
+        // The deposit of the test data is aggregated.
+        let today = chrono::Utc::today().naive_utc();
+        let query = DepositAggregatesQuery {
+            from_day: today,
+            to_day: today,
+            sender: Some(Address::default()),
+            token: Some(TokenId(0)),
+        };
+        let response = client.deposit_aggregates(&query).await?;
+        let aggregates: DepositAggregates = deserialize_response_result(response)?;
+        assert_eq!(aggregates.daily.len(), 1);
+        assert_eq!(aggregates.totals.len(), 1);
+        assert_eq!(aggregates.totals[0].deposits_count, 1);
+
+        let query = DepositAggregatesQuery {
+            from_day: today,
+            to_day: today - chrono::Duration::days(1),
+            ..query
+        };
+        let response = client.deposit_aggregates(&query).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@ use zksync_api_types::v02::{Request, Response, ResultStatus};
 
 // Local uses
 use super::{error::Error, SharedData};
+use crate::api_server::rest::route_stats::ServerError;
 
 // This struct is needed to wrap all api responses is `Response` struct by implementing `Responder` trait for it.
 // We can't use simple `Result`, because `actix-web` has already `Responder` implementation for it.
@@ -45,6 +46,7 @@ impl<R: Serialize> Responder for ApiResult<R> {
             timestamp: Utc::now(),
         };
 
+        let server_error = matches!(&self, ApiResult::Error(err) if err.is_server_error());
         let response = match self {
             ApiResult::Ok(res) => Response {
                 request,
@@ -62,9 +64,15 @@ impl<R: Serialize> Responder for ApiResult<R> {
 
         let body = serde_json::to_string(&response).expect("Should be correct serializable");
 
-        HttpResponse::Ok()
+        let mut response = HttpResponse::Ok()
             .content_type("application/json")
-            .body(body)
+            .body(body);
+        // Errors are responded with the successful status, so the ones caused by the server
+        // are marked for the statistics of the route.
+        if server_error {
+            response.extensions_mut().insert(ServerError);
+        }
+        response
     }
 }
 
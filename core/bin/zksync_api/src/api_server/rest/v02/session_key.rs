@@ -0,0 +1,355 @@
+//! Session keys part of API implementation.
+//!
+//! Session keys are the Ethereum addresses the account owner allows to authorize
+//! the transactions of the account within the spending caps, so the dApps can
+//! transact on the user's behalf without asking for the signature every time.
+
+// Built-in uses
+use std::str::FromStr;
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+
+// Workspace uses
+use zksync_api_types::v02::session_key::{
+    SessionKeyInfo, SessionKeyRegistration, SessionKeyRevocation, SessionKeySpending, SessionTxType,
+};
+use zksync_storage::session_keys::records::{StoredSessionKey, StoredSessionKeyLimit};
+use zksync_types::{AccountId, Address, TokenId};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::{api_server::tx_sender::TxSender, api_try};
+
+fn api_session_key(
+    session_key: StoredSessionKey,
+    limits: Vec<StoredSessionKeyLimit>,
+) -> Result<SessionKeyInfo, Error> {
+    let allowed_tx_types = session_key
+        .allowed_tx_types
+        .iter()
+        .map(|tx_type| {
+            SessionTxType::parse(tx_type).ok_or_else(|| {
+                Error::storage(format!("unknown session transaction type: {}", tx_type))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let limits = limits
+        .into_iter()
+        .map(|limit| {
+            let max_amount = limit.max_amount.to_bigint().and_then(|v| v.to_biguint());
+            let spent = limit.spent.to_bigint().and_then(|v| v.to_biguint());
+            match (max_amount, spent) {
+                (Some(max_amount), Some(spent)) => Ok(SessionKeySpending {
+                    token: TokenId(limit.token_id as u32),
+                    max_amount,
+                    spent,
+                }),
+                _ => Err(Error::storage("invalid session key limit is stored")),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(SessionKeyInfo {
+        account_id: AccountId(session_key.account_id as u32),
+        session_address: Address::from_slice(&session_key.session_address),
+        allowed_tx_types,
+        limits,
+        expires_at: session_key.expires_at,
+        revoked: session_key.revoked,
+        created_at: session_key.created_at,
+    })
+}
+
+/// Shared data between `api/v0.2/session_keys` endpoints.
+#[derive(Clone)]
+struct ApiSessionKeysData {
+    tx_sender: TxSender,
+}
+
+impl ApiSessionKeysData {
+    fn new(tx_sender: TxSender) -> Self {
+        Self { tx_sender }
+    }
+
+    fn check_enabled(&self) -> Result<(), Error> {
+        if self.tx_sender.session_keys_enabled {
+            Ok(())
+        } else {
+            Err(Error::from(InvalidDataError::SessionKeysDisabled))
+        }
+    }
+
+    async fn session_key(
+        &self,
+        account_id: AccountId,
+        session_address: Address,
+    ) -> Result<SessionKeyInfo, Error> {
+        self.check_enabled()?;
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let session_key = storage
+            .session_keys_schema()
+            .get_session_key(account_id, session_address)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::SessionKeyNotFound))?;
+        let limits = storage
+            .session_keys_schema()
+            .get_session_key_limits(account_id, session_address)
+            .await
+            .map_err(Error::storage)?;
+        api_session_key(session_key, limits)
+    }
+
+    async fn account_session_keys(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<SessionKeyInfo>, Error> {
+        self.check_enabled()?;
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let session_keys = storage
+            .session_keys_schema()
+            .get_account_session_keys(account_id)
+            .await
+            .map_err(Error::storage)?;
+        let mut result = Vec::with_capacity(session_keys.len());
+        for session_key in session_keys {
+            let session_address = Address::from_slice(&session_key.session_address);
+            let limits = storage
+                .session_keys_schema()
+                .get_session_key_limits(account_id, session_address)
+                .await
+                .map_err(Error::storage)?;
+            result.push(api_session_key(session_key, limits)?);
+        }
+        Ok(result)
+    }
+
+    async fn account_id(&self, account_id_or_address: &str) -> Result<AccountId, Error> {
+        if let Ok(account_id) = u32::from_str(account_id_or_address) {
+            return Ok(AccountId(account_id));
+        }
+        let address = account_id_or_address
+            .strip_prefix("0x")
+            .unwrap_or(account_id_or_address);
+        let address = Address::from_str(address)
+            .map_err(|_| Error::from(InvalidDataError::InvalidAccountIdOrAddress))?;
+        self.tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))
+    }
+}
+
+// Server implementation
+
+async fn register_session_key(
+    data: web::Data<ApiSessionKeysData>,
+    Json(body): Json<SessionKeyRegistration>,
+) -> ApiResult<SessionKeyInfo> {
+    let start = Instant::now();
+    let (account_id, session_address) = (body.account_id, body.session_address);
+    api_try!(data
+        .tx_sender
+        .register_session_key(body)
+        .await
+        .map_err(Error::from));
+    let res = data.session_key(account_id, session_address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "register_session_key");
+    res
+}
+
+async fn revoke_session_key(
+    data: web::Data<ApiSessionKeysData>,
+    Json(body): Json<SessionKeyRevocation>,
+) -> ApiResult<SessionKeyInfo> {
+    let start = Instant::now();
+    let (account_id, session_address) = (body.account_id, body.session_address);
+    api_try!(data
+        .tx_sender
+        .revoke_session_key(body)
+        .await
+        .map_err(Error::from));
+    let res = data.session_key(account_id, session_address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "revoke_session_key");
+    res
+}
+
+async fn account_session_keys(
+    data: web::Data<ApiSessionKeysData>,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Vec<SessionKeyInfo>> {
+    let start = Instant::now();
+    let account_id = api_try!(data.account_id(&account_id_or_address).await);
+    let res = data.account_session_keys(account_id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_session_keys");
+    res
+}
+
+async fn session_key(
+    data: web::Data<ApiSessionKeysData>,
+    path: web::Path<(String, Address)>,
+) -> ApiResult<SessionKeyInfo> {
+    let start = Instant::now();
+    let (account_id_or_address, session_address) = path.into_inner();
+    let account_id = api_try!(data.account_id(&account_id_or_address).await);
+    let res = data.session_key(account_id, session_address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "session_key");
+    res
+}
+
+pub fn api_scope(tx_sender: TxSender) -> Scope {
+    let data = ApiSessionKeysData::new(tx_sender);
+
+    web::scope("session_keys")
+        .app_data(web::Data::new(data))
+        .route("", web::post().to(register_session_key))
+        .route("revoke", web::post().to(revoke_session_key))
+        .route(
+            "accounts/{account_id_or_address}",
+            web::get().to(account_session_keys),
+        )
+        .route(
+            "accounts/{account_id_or_address}/{session_address}",
+            web::get().to(session_key),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{
+            deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
+        },
+        SharedData,
+    };
+    use chrono::{Duration, Utc};
+    use futures::channel::mpsc;
+    use num::BigUint;
+    use zksync_api_types::v02::{session_key::SessionKeyLimit, ApiVersion};
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{
+        tx::{PackedEthSignature, TxEthSignature},
+        ChainId,
+    };
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn session_keys_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let (mempool_tx_request_sender, _mempool_tx_request_receiver) = mpsc::channel(100);
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                let mut tx_sender = TxSender::new(
+                    cfg.pool.clone(),
+                    dummy_sign_verifier(),
+                    dummy_fee_ticker(&[], None),
+                    &cfg.config.api.common,
+                    &cfg.config.api.token_config,
+                    mempool_tx_request_sender.clone(),
+                    ChainId(cfg.config.eth_client.chain_id),
+                );
+                tx_sender.session_keys_enabled = true;
+                api_scope(tx_sender)
+            },
+            Some(shared_data),
+        );
+
+        let account = ZkSyncAccount::rand();
+        let eth_private_key = account
+            .try_get_eth_private_key()
+            .expect("Should have ETH private key");
+        let session_address = Address::repeat_byte(0x5e);
+        let expires_at = Utc::now() + Duration::days(1);
+
+        // Registration without the allowed transaction types is rejected.
+        let mut registration = SessionKeyRegistration {
+            account_id: AccountId(1),
+            session_address,
+            allowed_tx_types: Vec::new(),
+            limits: vec![SessionKeyLimit {
+                token: TokenId(0),
+                max_amount: BigUint::from(1000u32),
+            }],
+            expires_at,
+            timestamp: Utc::now(),
+            signature: TxEthSignature::EthereumSignature(PackedEthSignature::sign(
+                eth_private_key,
+                b"",
+            )?),
+        };
+        registration.signature = TxEthSignature::EthereumSignature(PackedEthSignature::sign(
+            eth_private_key,
+            registration.get_ethereum_sign_message().as_bytes(),
+        )?);
+        let response = client.register_session_key(&registration).await?;
+        assert!(response.error.is_some());
+
+        cfg.pool
+            .access_storage()
+            .await?
+            .session_keys_schema()
+            .store_session_key(
+                AccountId(1),
+                session_address,
+                &[SessionTxType::Transfer.as_str().to_string()],
+                &[(TokenId(0), BigUint::from(1000u32))],
+                expires_at,
+                Utc::now(),
+            )
+            .await?;
+
+        let response = client.session_key("1", session_address).await?;
+        let info: SessionKeyInfo = deserialize_response_result(response)?;
+        assert_eq!(info.session_address, session_address);
+        assert_eq!(info.allowed_tx_types, vec![SessionTxType::Transfer]);
+        assert_eq!(info.limits.len(), 1);
+        assert_eq!(info.limits[0].max_amount, BigUint::from(1000u32));
+        assert_eq!(info.limits[0].spent, BigUint::from(0u32));
+        assert!(!info.revoked);
+
+        let response = client.account_session_keys("1").await?;
+        let keys: Vec<SessionKeyInfo> = deserialize_response_result(response)?;
+        assert_eq!(keys.len(), 1);
+
+        let response = client.session_key("1", Address::repeat_byte(0x11)).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
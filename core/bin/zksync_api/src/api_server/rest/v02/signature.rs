@@ -0,0 +1,127 @@
+//! Signing of the API responses.
+//!
+//! Light clients can request the account state and receipt responses to be signed by the server.
+//! The signature binds the payload to the root hash of the block, so the downstream services
+//! can verify that the API server doesn't tamper with the data without running a full node.
+
+// External uses
+use serde::Serialize;
+
+// Workspace uses
+use zksync_api_types::v02::signature::{MaybeSigned, SignatureQuery, SignedData, SignedResponse};
+use zksync_config::configs::api::CommonApiConfig;
+use zksync_crypto::{convert::FeConvert, Fr};
+use zksync_storage::StorageProcessor;
+use zksync_types::{tx::PackedEthSignature, Address, BlockNumber, H256};
+
+// Local uses
+use super::error::{Error, InvalidDataError};
+
+/// Signer of the responses. Doesn't implement `Debug` to not leak the key to the logs.
+#[derive(Clone)]
+pub struct ResponseSigner {
+    private_key: H256,
+    address: Address,
+}
+
+impl ResponseSigner {
+    pub fn new(config: &CommonApiConfig) -> Option<Self> {
+        config.response_signing_private_key.map(|private_key| Self {
+            private_key,
+            address: PackedEthSignature::address_from_private_key(&private_key)
+                .expect("failed to get the address of the response signing key"),
+        })
+    }
+
+    /// Address which the light clients should expect to recover from the signatures.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Signs the payload along with the root hash of the given block.
+    async fn sign<T: Serialize>(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        payload: T,
+        block_number: BlockNumber,
+    ) -> Result<SignedResponse<T>, Error> {
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_storage_block(block_number)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::storage(format!("block {} is not found", block_number)))?;
+        let root_hash = Fr::from_bytes(&block.root_hash).map_err(Error::storage)?;
+
+        self.sign_data(SignedData {
+            payload,
+            block_number,
+            root_hash,
+        })
+    }
+
+    fn sign_data<T: Serialize>(&self, data: SignedData<T>) -> Result<SignedResponse<T>, Error> {
+        let message = data.canonical_encoding().map_err(Error::storage)?;
+        let signature =
+            PackedEthSignature::sign(&self.private_key, &message).map_err(Error::storage)?;
+
+        Ok(SignedResponse {
+            data,
+            signature,
+            signer: self.address,
+        })
+    }
+}
+
+/// Signs the payload if it's requested, the signature binds it to the given block.
+pub(super) async fn maybe_sign<T: Serialize>(
+    signer: Option<&ResponseSigner>,
+    storage: &mut StorageProcessor<'_>,
+    query: SignatureQuery,
+    payload: T,
+    block_number: BlockNumber,
+) -> Result<MaybeSigned<T>, Error> {
+    if !query.signed {
+        return Ok(MaybeSigned::Plain(payload));
+    }
+    let signer = signer.ok_or_else(|| Error::from(InvalidDataError::ResponseSigningDisabled))?;
+    let response = signer.sign(storage, payload, block_number).await?;
+    Ok(MaybeSigned::Signed(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_api_types::v02::account::DepositingAccountBalances;
+
+    #[test]
+    fn signed_response_verification() {
+        let private_key = H256::repeat_byte(0x11);
+        let signer = ResponseSigner {
+            private_key,
+            address: PackedEthSignature::address_from_private_key(&private_key).unwrap(),
+        };
+        let data = SignedData {
+            payload: DepositingAccountBalances::default(),
+            block_number: BlockNumber(5),
+            root_hash: Fr::from_bytes(&[1; 32]).unwrap(),
+        };
+        let response = signer.sign_data(data).unwrap();
+        assert!(response.verify());
+
+        // The response is verified after the round trip through JSON.
+        let json = serde_json::to_value(&response).unwrap();
+        let response: SignedResponse<serde_json::Value> = serde_json::from_value(json).unwrap();
+        assert!(response.verify());
+        assert_eq!(response.signer, signer.address());
+
+        // Data bound to another block is not accepted.
+        let mut tampered = response.clone();
+        tampered.data.block_number = BlockNumber(6);
+        assert!(!tampered.verify());
+        let mut tampered = response;
+        tampered.signer = Address::repeat_byte(0x22);
+        assert!(!tampered.verify());
+    }
+}
@@ -0,0 +1,447 @@
+//! Standing orders part of API implementation.
+//!
+//! Standing order is a recurring payment registered as the set of pre-signed transfers
+//! of the consecutive nonces. The transfers are submitted by the server at the scheduled
+//! times, see the `standing_order_scheduler` module.
+
+// Built-in uses
+use std::str::FromStr;
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    HttpRequest, Scope,
+};
+
+// Workspace uses
+use zksync_api_types::v02::{
+    pagination::{parse_query, AccountStandingOrdersRequest, Paginated, PaginationQuery},
+    standing_order::{
+        IncomingStandingOrder, IncomingStandingOrderCancellation, StandingOrderInfo,
+        StandingOrderStatus, StandingPaymentInfo, StandingPaymentStatus,
+    },
+    transaction::TxHashSerializeWrapper,
+};
+use zksync_storage::standing_orders::records::{StoredStandingOrder, StoredStandingOrderPayment};
+use zksync_types::{tx::TxHash, AccountId, Address, Nonce, TokenId};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    page_size::max_page_size,
+    paginate_trait::Paginate,
+    response::ApiResult,
+};
+use crate::{api_server::tx_sender::TxSender, api_try};
+
+fn api_standing_payment(
+    payment: &StoredStandingOrderPayment,
+) -> Result<StandingPaymentInfo, Error> {
+    let status = match payment.status.as_str() {
+        "scheduled" => StandingPaymentStatus::Scheduled,
+        "submitted" => StandingPaymentStatus::Submitted,
+        "failed" => StandingPaymentStatus::Failed,
+        "skipped" => StandingPaymentStatus::Skipped,
+        status => {
+            return Err(Error::storage(format!(
+                "unknown status of the standing order payment: {}",
+                status
+            )))
+        }
+    };
+    Ok(StandingPaymentInfo {
+        sequence: payment.sequence as u32,
+        nonce: Nonce(payment.nonce as u32),
+        scheduled_at: payment.scheduled_at,
+        status,
+        tx_hash: payment
+            .tx_hash
+            .as_deref()
+            .and_then(TxHash::from_slice)
+            .map(TxHashSerializeWrapper),
+        error: payment.error.clone(),
+        submitted_at: payment.submitted_at,
+    })
+}
+
+/// Converts the stored order into its API representation, `payments` are used
+/// to report the progress of the order.
+pub(super) fn api_standing_order(
+    order: StoredStandingOrder,
+    payments: &[StoredStandingOrderPayment],
+) -> Result<StandingOrderInfo, Error> {
+    let status = match order.status.as_str() {
+        "active" => StandingOrderStatus::Active,
+        "completed" => StandingOrderStatus::Completed,
+        "cancelled" => StandingOrderStatus::Cancelled,
+        "failed" => StandingOrderStatus::Failed,
+        status => {
+            return Err(Error::storage(format!(
+                "unknown status of the standing order: {}",
+                status
+            )))
+        }
+    };
+    let amount = order
+        .amount
+        .to_bigint()
+        .and_then(|amount| amount.to_biguint())
+        .ok_or_else(|| Error::storage("invalid amount is stored"))?;
+    let fee = order
+        .fee
+        .to_bigint()
+        .and_then(|fee| fee.to_biguint())
+        .ok_or_else(|| Error::storage("invalid fee is stored"))?;
+    let payments_submitted = payments
+        .iter()
+        .filter(|payment| payment.status == "submitted")
+        .count();
+    let next_payment_at = payments
+        .iter()
+        .find(|payment| payment.status == "scheduled")
+        .map(|payment| payment.scheduled_at);
+
+    Ok(StandingOrderInfo {
+        standing_order_id: order.id as u64,
+        account_id: AccountId(order.account_id as u32),
+        from: Address::from_slice(&order.from_address),
+        to: Address::from_slice(&order.to_address),
+        token: TokenId(order.token_id as u32),
+        amount,
+        fee,
+        first_payment_at: order.first_payment_at,
+        interval_secs: order.interval_secs as u64,
+        expires_at: order.expires_at,
+        status,
+        failure_reason: order.failure_reason,
+        payments_total: payments.len() as u32,
+        payments_submitted: payments_submitted as u32,
+        next_payment_at,
+        created_at: order.created_at,
+        updated_at: order.updated_at,
+    })
+}
+
+/// Shared data between `api/v0.2/standing_orders` endpoints.
+#[derive(Clone)]
+struct ApiStandingOrdersData {
+    tx_sender: TxSender,
+}
+
+impl ApiStandingOrdersData {
+    fn new(tx_sender: TxSender) -> Self {
+        Self { tx_sender }
+    }
+
+    fn check_enabled(&self) -> Result<(), Error> {
+        if self.tx_sender.standing_orders_enabled {
+            Ok(())
+        } else {
+            Err(Error::from(InvalidDataError::StandingOrdersDisabled))
+        }
+    }
+
+    async fn payments(&self, id: u64) -> Result<Vec<StoredStandingOrderPayment>, Error> {
+        self.tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?
+            .standing_orders_schema()
+            .get_standing_order_payments(id.min(i64::MAX as u64) as i64)
+            .await
+            .map_err(Error::storage)
+    }
+
+    async fn standing_order(&self, id: u64) -> Result<StandingOrderInfo, Error> {
+        self.check_enabled()?;
+        let order = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?
+            .standing_orders_schema()
+            .get_standing_order(id.min(i64::MAX as u64) as i64)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::StandingOrderNotFound))?;
+        let payments = self.payments(id).await?;
+        api_standing_order(order, &payments)
+    }
+
+    async fn standing_order_payments(&self, id: u64) -> Result<Vec<StandingPaymentInfo>, Error> {
+        self.check_enabled()?;
+        let payments = self.payments(id).await?;
+        if payments.is_empty() {
+            return Err(Error::from(InvalidDataError::StandingOrderNotFound));
+        }
+        payments.iter().map(api_standing_payment).collect()
+    }
+
+    async fn account_id(&self, account_id_or_address: &str) -> Result<AccountId, Error> {
+        if let Ok(account_id) = u32::from_str(account_id_or_address) {
+            return Ok(AccountId(account_id));
+        }
+        let address = account_id_or_address
+            .strip_prefix("0x")
+            .unwrap_or(account_id_or_address);
+        let address = Address::from_str(address)
+            .map_err(|_| Error::from(InvalidDataError::InvalidAccountIdOrAddress))?;
+        self.tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))
+    }
+}
+
+// Server implementation
+
+async fn register_standing_order(
+    data: web::Data<ApiStandingOrdersData>,
+    Json(body): Json<IncomingStandingOrder>,
+) -> ApiResult<StandingOrderInfo> {
+    let start = Instant::now();
+    let id = api_try!(data
+        .tx_sender
+        .register_standing_order(body)
+        .await
+        .map_err(Error::from));
+    let res = data.standing_order(id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "register_standing_order");
+    res
+}
+
+async fn cancel_standing_order(
+    data: web::Data<ApiStandingOrdersData>,
+    id: web::Path<u64>,
+    Json(body): Json<IncomingStandingOrderCancellation>,
+) -> ApiResult<StandingOrderInfo> {
+    let start = Instant::now();
+    api_try!(data
+        .tx_sender
+        .cancel_standing_order(*id, body.signature)
+        .await
+        .map_err(Error::from));
+    let res = data.standing_order(*id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "cancel_standing_order");
+    res
+}
+
+async fn standing_order(
+    data: web::Data<ApiStandingOrdersData>,
+    id: web::Path<u64>,
+) -> ApiResult<StandingOrderInfo> {
+    let start = Instant::now();
+    let res = data.standing_order(*id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "standing_order");
+    res
+}
+
+async fn standing_order_payments(
+    data: web::Data<ApiStandingOrdersData>,
+    id: web::Path<u64>,
+) -> ApiResult<Vec<StandingPaymentInfo>> {
+    let start = Instant::now();
+    let res = data.standing_order_payments(*id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "standing_order_payments");
+    res
+}
+
+async fn account_standing_orders(
+    data: web::Data<ApiStandingOrdersData>,
+    http_request: HttpRequest,
+    account_id_or_address: web::Path<String>,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<StandingOrderInfo, u64>> {
+    let start = Instant::now();
+    api_try!(data.check_enabled());
+    let account_id = api_try!(data.account_id(&account_id_or_address).await);
+    let query = api_try!(parse_query(query).map_err(Error::from));
+    let query = PaginationQuery {
+        from: AccountStandingOrdersRequest {
+            account_id,
+            standing_order_id: query.from,
+        },
+        limit: query.limit,
+        direction: query.direction,
+    };
+    let max_page_size = api_try!(max_page_size(&http_request, "account_standing_orders").await);
+    let mut storage = api_try!(data
+        .tx_sender
+        .pool
+        .access_storage()
+        .await
+        .map_err(Error::storage));
+    let res = storage.paginate_checked(&query, max_page_size).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_standing_orders");
+    res
+}
+
+pub fn api_scope(tx_sender: TxSender) -> Scope {
+    let data = ApiStandingOrdersData::new(tx_sender);
+
+    web::scope("standing_orders")
+        .app_data(web::Data::new(data))
+        .route("", web::post().to(register_standing_order))
+        .route(
+            "accounts/{account_id_or_address}",
+            web::get().to(account_standing_orders),
+        )
+        .route("{id}", web::get().to(standing_order))
+        .route("{id}/payments", web::get().to(standing_order_payments))
+        .route("{id}/cancel", web::post().to(cancel_standing_order))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{
+            deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
+        },
+        SharedData,
+    };
+    use chrono::{Duration, Utc};
+    use futures::channel::mpsc;
+    use num::BigUint;
+    use zksync_api_types::v02::{
+        pagination::{ApiEither, PaginationDirection},
+        standing_order::StandingPaymentTx,
+        ApiVersion,
+    };
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{
+        tx::{TimeRange, TxSignature},
+        ChainId, PubKeyHash,
+    };
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn standing_orders_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let (mempool_tx_request_sender, _mempool_tx_request_receiver) = mpsc::channel(100);
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                let mut tx_sender = TxSender::new(
+                    cfg.pool.clone(),
+                    dummy_sign_verifier(),
+                    dummy_fee_ticker(&[], None),
+                    &cfg.config.api.common,
+                    &cfg.config.api.token_config,
+                    mempool_tx_request_sender.clone(),
+                    ChainId(cfg.config.eth_client.chain_id),
+                );
+                tx_sender.standing_orders_enabled = true;
+                api_scope(tx_sender)
+            },
+            Some(shared_data),
+        );
+
+        let account = ZkSyncAccount::rand();
+        account.set_account_id(Some(AccountId(1)));
+        let transfers: Vec<_> = (0..2)
+            .map(|nonce| {
+                let (tx, _) = account.sign_transfer(
+                    TokenId(0),
+                    "ETH",
+                    BigUint::from(100u32),
+                    BigUint::from(1u32),
+                    &Address::repeat_byte(0xff),
+                    Some(Nonce(nonce)),
+                    false,
+                    TimeRange::default(),
+                );
+                (tx, None)
+            })
+            .collect();
+        let first_payment_at = Utc::now() + Duration::days(1);
+        let expires_at = first_payment_at + Duration::days(2);
+
+        // The transfers are not signed with the signing key of the account.
+        let order = IncomingStandingOrder {
+            first_payment_at,
+            interval_secs: 86400,
+            expires_at,
+            payments: transfers
+                .iter()
+                .cloned()
+                .map(|(tx, eth_signature)| StandingPaymentTx { tx, eth_signature })
+                .collect(),
+        };
+        let response = client.register_standing_order(&order).await?;
+        assert!(response.error.is_some());
+
+        let id = cfg
+            .pool
+            .access_storage()
+            .await?
+            .standing_orders_schema()
+            .store_standing_order(
+                &transfers,
+                PubKeyHash::from_privkey(&account.private_key),
+                first_payment_at,
+                86400,
+                expires_at,
+            )
+            .await? as u64;
+
+        let response = client.standing_order(id).await?;
+        let info: StandingOrderInfo = deserialize_response_result(response)?;
+        assert_eq!(info.status, StandingOrderStatus::Active);
+        assert_eq!(info.payments_total, 2);
+        assert_eq!(info.payments_submitted, 0);
+        assert_eq!(info.amount, BigUint::from(100u32));
+
+        let response = client.standing_order_payments(id).await?;
+        let payments: Vec<StandingPaymentInfo> = deserialize_response_result(response)?;
+        assert_eq!(payments.len(), 2);
+        assert_eq!(payments[1].nonce, Nonce(1));
+        assert_eq!(payments[1].status, StandingPaymentStatus::Scheduled);
+
+        let query = PaginationQuery {
+            from: ApiEither::from(id),
+            limit: 1,
+            direction: PaginationDirection::Older,
+        };
+        let response = client.account_standing_orders(&query, "1").await?;
+        let orders: Paginated<StandingOrderInfo, u64> = deserialize_response_result(response)?;
+        assert_eq!(orders.list[0].standing_order_id, id);
+
+        // Only the owner of the order can cancel it.
+        let message = IncomingStandingOrderCancellation::message(id);
+        let other_account = ZkSyncAccount::rand();
+        let signature = TxSignature::sign_musig(&other_account.private_key, &message);
+        let response = client.cancel_standing_order(id, signature).await?;
+        assert!(response.error.is_some());
+
+        let signature = TxSignature::sign_musig(&account.private_key, &message);
+        let response = client.cancel_standing_order(id, signature.clone()).await?;
+        let info: StandingOrderInfo = deserialize_response_result(response)?;
+        assert_eq!(info.status, StandingOrderStatus::Cancelled);
+        assert_eq!(info.next_payment_at, None);
+        // The order can't be cancelled twice.
+        let response = client.cancel_standing_order(id, signature).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,679 @@
+//! Account statements part of API implementation.
+//!
+//! Statements are generated on request from the account history and the hourly token price
+//! history, and are returned as downloadable documents rather than API responses.
+
+// Built-in uses
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use std::time::Instant;
+
+// External uses
+use actix_web::{http::header, web, Either as ResponseEither, HttpResponse, Scope};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, TimeZone, Utc};
+use num::{rational::Ratio, BigUint, Zero};
+
+// Workspace uses
+use zksync_api_types::{
+    v02::{
+        pagination::{AccountTxsRequest, ApiEither, Latest, PaginationDirection, PaginationQuery},
+        statement::{
+            AccountStatement, StatementBalance, StatementFee, StatementFormat, StatementQuery,
+            StatementTransaction,
+        },
+        transaction::{
+            L1Transaction, L2Transaction, Transaction, TransactionData, TxInBlockStatus,
+        },
+    },
+    Either,
+};
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_token_db_cache::TokenDBCache;
+use zksync_types::{AccountId, Address, BlockNumber, Token, TokenId};
+use zksync_utils::{format_units, ratio_to_big_decimal};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::fee_ticker::PriceError;
+
+/// Number of transactions loaded from the account history at once.
+const HISTORY_PAGE_SIZE: u32 = 100;
+/// Limit on the number of transactions in the statement, so a single request
+/// can't load an arbitrarily large part of the history.
+const MAX_STATEMENT_TXS: usize = 10_000;
+/// Precision of the fiat values in the statement.
+const USD_VALUE_PRECISION: usize = 6;
+
+/// Returns the fee paid by the initiator of the transaction: the initiator account,
+/// the fee token and the fee amount.
+fn paid_fee(tx: &L2Transaction) -> Option<(AccountId, TokenId, &BigUint)> {
+    match tx {
+        L2Transaction::Transfer(tx) => Some((tx.account_id, tx.token, &tx.fee)),
+        L2Transaction::Withdraw(tx) => Some((tx.tx.account_id, tx.tx.token, &tx.tx.fee)),
+        L2Transaction::Close(_) => None,
+        L2Transaction::ChangePubKey(tx) => Some((tx.account_id, tx.fee_token, &tx.fee)),
+        L2Transaction::ForcedExit(tx) => {
+            Some((tx.tx.initiator_account_id, tx.tx.token, &tx.tx.fee))
+        }
+        L2Transaction::MintNFT(tx) => Some((tx.creator_id, tx.fee_token, &tx.fee)),
+        L2Transaction::Swap(tx) => Some((tx.submitter_id, tx.fee_token, &tx.fee)),
+        L2Transaction::WithdrawNFT(tx) => Some((tx.tx.account_id, tx.tx.fee_token, &tx.tx.fee)),
+    }
+}
+
+fn tx_type(op: &TransactionData) -> &'static str {
+    match op {
+        TransactionData::L1(L1Transaction::Deposit(_)) => "Deposit",
+        TransactionData::L1(L1Transaction::FullExit(_)) => "FullExit",
+        TransactionData::L2(L2Transaction::Transfer(_)) => "Transfer",
+        TransactionData::L2(L2Transaction::Withdraw(_)) => "Withdraw",
+        TransactionData::L2(L2Transaction::Close(_)) => "Close",
+        TransactionData::L2(L2Transaction::ChangePubKey(_)) => "ChangePubKey",
+        TransactionData::L2(L2Transaction::ForcedExit(_)) => "ForcedExit",
+        TransactionData::L2(L2Transaction::MintNFT(_)) => "MintNFT",
+        TransactionData::L2(L2Transaction::Swap(_)) => "Swap",
+        TransactionData::L2(L2Transaction::WithdrawNFT(_)) => "WithdrawNFT",
+    }
+}
+
+/// Returns the start and the end of the month. The statement can't be requested
+/// for the month that hasn't started yet.
+fn statement_period(
+    year: i32,
+    month: u32,
+    now: DateTime<Utc>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), Error> {
+    let period_start = Utc
+        .ymd_opt(year, month, 1)
+        .single()
+        .filter(|date| date.and_hms(0, 0, 0) <= now)
+        .ok_or_else(|| Error::from(InvalidDataError::InvalidStatementPeriod))?
+        .and_hms(0, 0, 0);
+    let period_end = if month == 12 {
+        Utc.ymd(year + 1, 1, 1)
+    } else {
+        Utc.ymd(year, month + 1, 1)
+    }
+    .and_hms(0, 0, 0);
+    Ok((period_start, period_end))
+}
+
+fn usd_value(amount: &BigUint, token: &Token, usd_price: &Ratio<BigUint>) -> BigDecimal {
+    let amount = Ratio::new(
+        amount.clone(),
+        BigUint::from(10u32).pow(token.decimals as u32),
+    );
+    ratio_to_big_decimal(&(amount * usd_price), USD_VALUE_PRECISION)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(|c| matches!(c, ',' | '"' | '\n')) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn optional_value(value: &Option<BigDecimal>) -> String {
+    value.as_ref().map(ToString::to_string).unwrap_or_default()
+}
+
+/// Renders the statement as a CSV document: the header, the table of balances and
+/// the table of transactions separated by empty lines.
+fn statement_csv(statement: &AccountStatement) -> String {
+    let tokens: HashMap<_, _> = statement
+        .balances
+        .iter()
+        .map(|balance| (balance.token_id, balance))
+        .collect();
+
+    let mut lines = vec![
+        format!(
+            "address,{:?},period_start,{},period_end,{}",
+            statement.address,
+            statement.period_start.to_rfc3339(),
+            statement.period_end.to_rfc3339()
+        ),
+        String::new(),
+        String::from("token,opening_balance,opening_value_usd,closing_balance,closing_value_usd,fees_paid,fees_paid_usd"),
+    ];
+    for balance in &statement.balances {
+        lines.push(format!(
+            "{},{},{},{},{},{},{}",
+            csv_field(&balance.symbol),
+            format_units(&balance.opening_balance, balance.decimals),
+            optional_value(&balance.opening_value_usd),
+            format_units(&balance.closing_balance, balance.decimals),
+            optional_value(&balance.closing_value_usd),
+            format_units(&balance.fees_paid, balance.decimals),
+            optional_value(&balance.fees_paid_usd),
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(String::from(
+        "created_at,tx_hash,block_number,type,status,fee_token,fee,fee_value_usd",
+    ));
+    for tx in &statement.transactions {
+        let (fee_token, fee, fee_value) = match &tx.fee {
+            Some(fee) => {
+                let token = tokens[&fee.token_id];
+                (
+                    csv_field(&token.symbol),
+                    format_units(&fee.amount, token.decimals),
+                    optional_value(&fee.value_usd),
+                )
+            }
+            None => Default::default(),
+        };
+        lines.push(format!(
+            "{},0x{},{},{},{:?},{},{},{}",
+            tx.tx
+                .created_at
+                .map(|created_at| created_at.to_rfc3339())
+                .unwrap_or_default(),
+            hex::encode(tx.tx.tx_hash),
+            tx.tx
+                .block_number
+                .map(|block| block.to_string())
+                .unwrap_or_default(),
+            tx_type(&tx.tx.op),
+            tx.tx.status,
+            fee_token,
+            fee,
+            fee_value,
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn statement_document(statement: AccountStatement, format: StatementFormat) -> HttpResponse {
+    let (body, content_type, extension) = match format {
+        StatementFormat::Json => (
+            serde_json::to_string(&statement).expect("Should be correct serializable"),
+            "application/json",
+            "json",
+        ),
+        StatementFormat::Csv => (statement_csv(&statement), "text/csv", "csv"),
+    };
+    let file_name = format!(
+        "statement-{:?}-{}.{}",
+        statement.address,
+        statement.period_start.format("%Y-%m"),
+        extension
+    );
+
+    HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file_name),
+        ))
+        .body(body)
+}
+
+/// Totals of the single token accumulated while generating the statement.
+#[derive(Debug)]
+struct TokenTotals {
+    opening_balance: BigUint,
+    closing_balance: BigUint,
+    fees_paid: BigUint,
+    /// Becomes `None` once the value of any of the fees is unknown.
+    fees_paid_usd: Option<BigDecimal>,
+}
+
+impl Default for TokenTotals {
+    fn default() -> Self {
+        Self {
+            opening_balance: BigUint::zero(),
+            closing_balance: BigUint::zero(),
+            fees_paid: BigUint::zero(),
+            fees_paid_usd: Some(BigDecimal::from(0)),
+        }
+    }
+}
+
+impl TokenTotals {
+    fn add_fee(&mut self, amount: &BigUint, value_usd: Option<&BigDecimal>) {
+        self.fees_paid += amount;
+        self.fees_paid_usd = match (self.fees_paid_usd.take(), value_usd) {
+            (Some(total), Some(value)) => Some(total + value),
+            _ => None,
+        };
+    }
+}
+
+/// Shared data between `api/v0.2/statements` endpoints.
+#[derive(Debug, Clone)]
+struct ApiStatementData {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    /// Whether the statements are exported only for the attested addresses.
+    require_attestation: bool,
+}
+
+impl ApiStatementData {
+    fn new(pool: ConnectionPool, tokens: TokenDBCache, require_attestation: bool) -> Self {
+        Self {
+            pool,
+            tokens,
+            require_attestation,
+        }
+    }
+
+    fn parse_address(&self, address: &str) -> Result<Address, Error> {
+        let address = address.strip_prefix("0x").unwrap_or(address);
+        Address::from_str(address)
+            .map_err(|_| Error::from(InvalidDataError::InvalidAccountIdOrAddress))
+    }
+
+    /// Loads the transactions of the account executed in the blocks after `opening_block`
+    /// up to `closing_block` inclusive, from the oldest one.
+    ///
+    /// The history is walked back from the latest transaction page by page, so the cost
+    /// of the statement depends on how old it is.
+    async fn period_transactions(
+        storage: &mut StorageProcessor<'_>,
+        address: Address,
+        opening_block: BlockNumber,
+        closing_block: BlockNumber,
+    ) -> Result<Vec<Transaction>, Error> {
+        let mut transactions = Vec::new();
+        let mut from = ApiEither {
+            inner: Either::Right(Latest),
+        };
+        loop {
+            // Pages start with the transaction the previous page has ended with.
+            let skip = if from.inner.is_left() { 1 } else { 0 };
+            let query = PaginationQuery {
+                from: AccountTxsRequest {
+                    address,
+                    tx_hash: from,
+                    token: None,
+                    second_address: None,
+                },
+                limit: HISTORY_PAGE_SIZE,
+                direction: PaginationDirection::Older,
+            };
+            let page = storage
+                .chain()
+                .operations_ext_schema()
+                .get_account_transactions(&query)
+                .await
+                .map_err(Error::storage)?
+                .unwrap_or_default();
+            let is_last_page = page.len() < HISTORY_PAGE_SIZE as usize;
+
+            let mut last_tx_hash = None;
+            for tx in page.into_iter().skip(skip) {
+                last_tx_hash = Some(tx.tx_hash);
+                let block_number = tx.block_number.unwrap_or_default();
+                if block_number <= opening_block {
+                    transactions.reverse();
+                    return Ok(transactions);
+                }
+                if block_number <= closing_block {
+                    transactions.push(tx);
+                }
+            }
+            if transactions.len() > MAX_STATEMENT_TXS {
+                return Err(Error::from(InvalidDataError::StatementTooLarge));
+            }
+
+            match last_tx_hash {
+                Some(tx_hash) if !is_last_page => from = ApiEither::from(tx_hash),
+                _ => break,
+            }
+        }
+        transactions.reverse();
+        Ok(transactions)
+    }
+
+    async fn account_statement(
+        &self,
+        address: Address,
+        year: i32,
+        month: u32,
+    ) -> Result<AccountStatement, Error> {
+        let now = Utc::now();
+        let (period_start, period_end) = statement_period(year, month, now)?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        if self.require_attestation
+            && !storage
+                .address_attestations_schema()
+                .is_address_attested(address)
+                .await
+                .map_err(Error::storage)?
+        {
+            return Err(Error::from(InvalidDataError::AttestationRequired));
+        }
+
+        let opening_block = storage
+            .chain()
+            .block_schema()
+            .get_last_block_before(period_start.timestamp().max(0) as u64)
+            .await
+            .map_err(Error::storage)?;
+        let closing_block = storage
+            .chain()
+            .block_schema()
+            .get_last_block_before(period_end.timestamp().max(0) as u64)
+            .await
+            .map_err(Error::storage)?;
+        let account_id = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(Error::storage)?;
+
+        // The statement contains all the tokens the account has held or paid fees in.
+        let mut totals: BTreeMap<TokenId, TokenTotals> = BTreeMap::new();
+        if let (Some(account_id), Some(closing_block)) = (account_id, closing_block) {
+            let mut account_schema = storage.chain().account_schema();
+            let opening_balances = account_schema
+                .get_account_balances_for_block(account_id, opening_block.unwrap_or_default())
+                .await
+                .map_err(Error::storage)?;
+            let closing_balances = account_schema
+                .get_account_balances_for_block(account_id, closing_block)
+                .await
+                .map_err(Error::storage)?;
+            for (token_id, balance) in opening_balances {
+                totals.entry(token_id).or_default().opening_balance = balance;
+            }
+            for (token_id, balance) in closing_balances {
+                totals.entry(token_id).or_default().closing_balance = balance;
+            }
+        }
+
+        let transactions = match (account_id, closing_block) {
+            (Some(_), Some(closing_block)) => {
+                Self::period_transactions(
+                    &mut storage,
+                    address,
+                    opening_block.unwrap_or_default(),
+                    closing_block,
+                )
+                .await?
+            }
+            _ => Vec::new(),
+        };
+
+        let mut prices = PriceHistory::default();
+        let mut statement_txs = Vec::with_capacity(transactions.len());
+        for tx in transactions {
+            let fee = match &tx.op {
+                TransactionData::L2(op) if tx.status != TxInBlockStatus::Rejected => paid_fee(op)
+                    .filter(|(initiator_id, _, amount)| {
+                        Some(*initiator_id) == account_id && !amount.is_zero()
+                    })
+                    .map(|(_, token_id, amount)| (token_id, amount.clone())),
+                _ => None,
+            };
+            let fee = match fee {
+                Some((token_id, amount)) => {
+                    let token = self.token(&mut storage, token_id).await?;
+                    let value_usd = match tx.created_at {
+                        Some(created_at) => {
+                            prices
+                                .usd_value(&mut storage, &amount, &token, created_at)
+                                .await?
+                        }
+                        None => None,
+                    };
+                    totals
+                        .entry(token_id)
+                        .or_default()
+                        .add_fee(&amount, value_usd.as_ref());
+                    Some(StatementFee {
+                        token_id,
+                        amount,
+                        value_usd,
+                    })
+                }
+                None => None,
+            };
+            statement_txs.push(StatementTransaction { tx, fee });
+        }
+
+        let closing_moment = std::cmp::min(period_end, now);
+        let mut balances = Vec::with_capacity(totals.len());
+        for (token_id, totals) in totals {
+            let token = self.token(&mut storage, token_id).await?;
+            let opening_value_usd = prices
+                .usd_value(&mut storage, &totals.opening_balance, &token, period_start)
+                .await?;
+            let closing_value_usd = prices
+                .usd_value(
+                    &mut storage,
+                    &totals.closing_balance,
+                    &token,
+                    closing_moment,
+                )
+                .await?;
+            balances.push(StatementBalance {
+                token_id,
+                symbol: token.symbol,
+                decimals: token.decimals,
+                opening_balance: totals.opening_balance,
+                opening_value_usd,
+                closing_balance: totals.closing_balance,
+                closing_value_usd,
+                fees_paid: totals.fees_paid,
+                fees_paid_usd: totals.fees_paid_usd,
+            });
+        }
+
+        Ok(AccountStatement {
+            address,
+            account_id,
+            period_start,
+            period_end,
+            opening_block,
+            closing_block,
+            balances,
+            transactions: statement_txs,
+            generated_at: now,
+        })
+    }
+
+    async fn token(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        token_id: TokenId,
+    ) -> Result<Token, Error> {
+        self.tokens
+            .get_token(storage, token_id)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(PriceError::token_not_found("Token not found in storage")))
+    }
+}
+
+/// Token prices loaded from the hourly price history while generating the statement.
+#[derive(Debug, Default)]
+struct PriceHistory(HashMap<(TokenId, i64), Option<Ratio<BigUint>>>);
+
+impl PriceHistory {
+    /// Returns the value of the amount in USD at the given moment, or `None` if the price
+    /// of the token at the moment is unknown.
+    async fn usd_value(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        amount: &BigUint,
+        token: &Token,
+        moment: DateTime<Utc>,
+    ) -> Result<Option<BigDecimal>, Error> {
+        if amount.is_zero() {
+            return Ok(Some(BigDecimal::from(0)));
+        }
+        // Prices are recorded per hour.
+        let key = (token.id, moment.timestamp() / 3600);
+        let usd_price = match self.0.get(&key) {
+            Some(usd_price) => usd_price.clone(),
+            None => {
+                let usd_price = storage
+                    .tokens_schema()
+                    .get_ticker_price_at(token.id, moment)
+                    .await
+                    .map_err(Error::storage)?
+                    .map(|price| price.usd_price)
+                    // Prices of the tokens not listed on the price sources are stored as zeros.
+                    .filter(|usd_price| !usd_price.is_zero());
+                self.0.insert(key, usd_price.clone());
+                usd_price
+            }
+        };
+        Ok(usd_price.map(|usd_price| usd_value(amount, token, &usd_price)))
+    }
+}
+
+// Server implementation
+
+async fn account_statement(
+    data: web::Data<ApiStatementData>,
+    path: web::Path<(String, i32, u32)>,
+    web::Query(query): web::Query<StatementQuery>,
+) -> ResponseEither<HttpResponse, ApiResult<()>> {
+    let start = Instant::now();
+    let (address, year, month) = path.into_inner();
+    let res = match data.parse_address(&address) {
+        Ok(address) => data.account_statement(address, year, month).await,
+        Err(err) => Err(err),
+    };
+    let res = match res {
+        Ok(statement) => ResponseEither::Left(statement_document(
+            statement,
+            query.format.unwrap_or_default(),
+        )),
+        Err(err) => ResponseEither::Right(ApiResult::Error(err)),
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_statement");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool, tokens: TokenDBCache, require_attestation: bool) -> Scope {
+    let data = ApiStatementData::new(pool, tokens, require_attestation);
+
+    web::scope("statements")
+        .app_data(web::Data::new(data))
+        .route("{address}/{year}/{month}", web::get().to(account_statement))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::{ApiVersion, Response};
+
+    #[test]
+    fn statement_periods() {
+        let now = Utc.ymd(2021, 12, 15).and_hms(12, 0, 0);
+        assert_eq!(
+            statement_period(2021, 12, now).unwrap(),
+            (
+                Utc.ymd(2021, 12, 1).and_hms(0, 0, 0),
+                Utc.ymd(2022, 1, 1).and_hms(0, 0, 0)
+            )
+        );
+        assert_eq!(
+            statement_period(2021, 2, now).unwrap().1,
+            Utc.ymd(2021, 3, 1).and_hms(0, 0, 0)
+        );
+        // Invalid and future months.
+        assert!(statement_period(2021, 13, now).is_err());
+        assert!(statement_period(2022, 1, now).is_err());
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn statements_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        // Test blocks have zero timestamps, so all of them are within January 1970.
+        let address = cfg
+            .pool
+            .access_storage()
+            .await?
+            .chain()
+            .account_schema()
+            .account_address_by_id(AccountId(1))
+            .await?
+            .expect("Account should exist");
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                api_scope(
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    false,
+                )
+            },
+            Some(shared_data),
+        );
+
+        let document = client
+            .account_statement(address, 1970, 1, StatementFormat::Json)
+            .await?;
+        let statement: AccountStatement = serde_json::from_str(&document)?;
+        assert_eq!(statement.address, address);
+        assert_eq!(statement.account_id, Some(AccountId(1)));
+        assert_eq!(statement.opening_block, None);
+        assert!(statement.closing_block.is_some());
+        assert!(!statement.balances.is_empty());
+        assert!(!statement.transactions.is_empty());
+        let blocks: Vec<_> = statement
+            .transactions
+            .iter()
+            .map(|tx| tx.tx.block_number)
+            .collect();
+        let mut sorted_blocks = blocks.clone();
+        sorted_blocks.sort();
+        assert_eq!(blocks, sorted_blocks);
+
+        let document = client
+            .account_statement(address, 1970, 1, StatementFormat::Csv)
+            .await?;
+        // Header, balances and transactions.
+        assert_eq!(
+            document.lines().count(),
+            statement.balances.len() + statement.transactions.len() + 5
+        );
+
+        // There are no blocks before the statement period.
+        let document = client
+            .account_statement(address, 1969, 12, StatementFormat::Json)
+            .await?;
+        let statement: AccountStatement = serde_json::from_str(&document)?;
+        assert!(statement.closing_block.is_none());
+        assert!(statement.transactions.is_empty());
+
+        let document = client
+            .account_statement(address, 1970, 13, StatementFormat::Json)
+            .await?;
+        let response: Response = serde_json::from_str(&document)?;
+        assert!(response.error.is_some());
+        let result: Result<(), _> = deserialize_response_result(response);
+        assert!(result.is_err());
+
+        server.stop().await;
+        Ok(())
+    }
+}
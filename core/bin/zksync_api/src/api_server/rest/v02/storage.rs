@@ -0,0 +1,204 @@
+//! Storage access of the API handlers.
+//!
+//! Handlers get the data through the traits of this module instead of the `StorageProcessor`,
+//! so their own logic, e.g. the pagination checks or the mapping of errors, can be tested
+//! with the in-memory mocks from the `test_utils` module without a database.
+
+// Built-in uses
+
+// External uses
+
+// Workspace uses
+use zksync_api_types::v02::{
+    block::BlockInfo,
+    pagination::{ApiEither, BlockAndTxHash, Paginated, PaginationQuery},
+    transaction::{Transaction, TxData, TxHashSerializeWrapper},
+};
+use zksync_storage::{
+    chain::block::records::{StorageBlockDetails, StorageBlockFinality},
+    ConnectionPool, QueryResult,
+};
+use zksync_types::{
+    block::{Block, PendingBlock},
+    BlockNumber,
+};
+
+// Local uses
+use super::{error::Error, paginate_trait::Paginate};
+use crate::utils::{
+    block_details_cache::BlockDetailsCache,
+    finalized_cache::{query_key, Finality, FinalizedCache},
+};
+
+/// Data used by the `api/v0.2/blocks` endpoints.
+///
+/// Pages are requested with the already checked limits.
+#[async_trait::async_trait]
+pub trait BlockStorage: Send + Sync + 'static {
+    /// Returns the block with the given number, `None` if there is no such block.
+    async fn block_details(
+        &self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<StorageBlockDetails>>;
+
+    async fn last_committed_block(&self) -> QueryResult<BlockNumber>;
+
+    async fn last_finalized_block(&self) -> QueryResult<BlockNumber>;
+
+    async fn block_page(
+        &self,
+        query: &PaginationQuery<ApiEither<BlockNumber>>,
+    ) -> Result<Paginated<BlockInfo, BlockNumber>, Error>;
+
+    async fn block_transactions_page(
+        &self,
+        query: &PaginationQuery<BlockAndTxHash>,
+    ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error>;
+
+    /// Returns the transaction with the given position in the block.
+    async fn tx_data(
+        &self,
+        block_number: BlockNumber,
+        block_index: u64,
+    ) -> QueryResult<Option<TxData>>;
+
+    /// Returns the block which is being formed by the state keeper, `None` if it's not started yet.
+    async fn pending_block(&self) -> QueryResult<Option<PendingBlock>>;
+
+    /// Returns the block with its executed operations, `None` if there is no such block.
+    async fn block(&self, block_number: BlockNumber) -> QueryResult<Option<Block>>;
+
+    /// Returns the L1 transactions and the proof of the block, `None` if there is no such block.
+    async fn block_finality(
+        &self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<StorageBlockFinality>>;
+}
+
+/// Implementation of the blocks data access backed by the database and the API caches.
+#[derive(Debug, Clone)]
+pub struct DbBlockStorage {
+    pool: ConnectionPool,
+    verified_blocks_cache: BlockDetailsCache,
+    finalized_cache: FinalizedCache,
+}
+
+impl DbBlockStorage {
+    pub fn new(
+        pool: ConnectionPool,
+        verified_blocks_cache: BlockDetailsCache,
+        finalized_cache: FinalizedCache,
+    ) -> Self {
+        Self {
+            pool,
+            verified_blocks_cache,
+            finalized_cache,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockStorage for DbBlockStorage {
+    /// Some of the verified blocks are cached.
+    async fn block_details(
+        &self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<StorageBlockDetails>> {
+        self.verified_blocks_cache
+            .get(&self.pool, block_number)
+            .await
+    }
+
+    async fn last_committed_block(&self) -> QueryResult<BlockNumber> {
+        let mut storage = self.pool.access_storage().await?;
+        storage
+            .chain()
+            .block_schema()
+            .get_last_committed_confirmed_block()
+            .await
+    }
+
+    async fn last_finalized_block(&self) -> QueryResult<BlockNumber> {
+        let mut storage = self.pool.access_storage().await?;
+        storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await
+    }
+
+    async fn block_page(
+        &self,
+        query: &PaginationQuery<ApiEither<BlockNumber>>,
+    ) -> Result<Paginated<BlockInfo, BlockNumber>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage.paginate(query).await
+    }
+
+    async fn block_transactions_page(
+        &self,
+        query: &PaginationQuery<BlockAndTxHash>,
+    ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
+        // Transactions of the finalized blocks never change, so their pages are cached.
+        let last_finalized_block = self
+            .finalized_cache
+            .last_finalized_block()
+            .await
+            .map_err(Error::storage)?;
+        let cache_key = query_key("block_transactions", query);
+        let is_finalized = query.from.block_number <= last_finalized_block;
+        if is_finalized {
+            if let Some(page) = self
+                .finalized_cache
+                .get(Finality::Finalized, &cache_key)
+                .await
+            {
+                return Ok(page);
+            }
+        }
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let page = storage.paginate(query).await?;
+        if is_finalized {
+            self.finalized_cache
+                .insert(Finality::Finalized, &cache_key, &page)
+                .await;
+        }
+        Ok(page)
+    }
+
+    async fn tx_data(
+        &self,
+        block_number: BlockNumber,
+        block_index: u64,
+    ) -> QueryResult<Option<TxData>> {
+        let mut storage = self.pool.access_storage().await?;
+        storage
+            .chain()
+            .operations_ext_schema()
+            .tx_data_by_block_and_index_api_v02(block_number, block_index)
+            .await
+    }
+
+    async fn pending_block(&self) -> QueryResult<Option<PendingBlock>> {
+        let mut storage = self.pool.access_storage().await?;
+        storage.chain().block_schema().load_pending_block().await
+    }
+
+    async fn block(&self, block_number: BlockNumber) -> QueryResult<Option<Block>> {
+        let mut storage = self.pool.access_storage().await?;
+        storage.chain().block_schema().get_block(block_number).await
+    }
+
+    async fn block_finality(
+        &self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<StorageBlockFinality>> {
+        let mut storage = self.pool.access_storage().await?;
+        storage
+            .chain()
+            .block_schema()
+            .load_block_finality(block_number)
+            .await
+    }
+}
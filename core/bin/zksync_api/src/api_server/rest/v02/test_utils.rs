@@ -3,6 +3,7 @@
 // Built-in uses
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::time::Duration;
 
 // External uses
 use actix_web::{web, App, Scope};
@@ -17,10 +18,19 @@ use tokio::sync::Mutex;
 
 // Workspace uses
 use zksync_api_client::rest::client::Client;
-use zksync_api_types::v02::Response;
+use zksync_api_types::{
+    v02::{
+        block::BlockInfo,
+        pagination::{ApiEither, BlockAndTxHash, Paginated, PaginationDirection, PaginationQuery},
+        transaction::{Transaction, TxData, TxHashSerializeWrapper},
+        Response,
+    },
+    Either,
+};
 use zksync_config::ZkSyncConfig;
 use zksync_crypto::rand::{Rng, SeedableRng, XorShiftRng};
 use zksync_storage::{
+    chain::block::records::{StorageBlockDetails, StorageBlockFinality},
     chain::operations::records::NewExecutedPriorityOperation,
     chain::operations::OperationsSchema,
     prover::ProverSchema,
@@ -29,11 +39,13 @@ use zksync_storage::{
         gen_unique_aggregated_operation_with_txs, generate_nft, get_sample_aggregated_proof,
         get_sample_single_proof, BLOCK_SIZE_CHUNKS,
     },
-    ConnectionPool,
+    ConnectionPool, QueryResult,
 };
 use zksync_test_account::ZkSyncAccount;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
+    block::{Block, PendingBlock},
+    gas_prediction::GasPredictor,
     helpers::{apply_updates, closest_packable_fee_amount, closest_packable_token_amount},
     operations::{ChangePubKeyOp, TransferToNewOp},
     prover::ProverJobType,
@@ -46,9 +58,13 @@ use zksync_types::{
 use zksync_utils::{big_decimal_to_ratio, scaled_u64_to_ratio, UnsignedRatioSerializeAsDecimal};
 
 // Local uses
+use super::{
+    block::block_info_from_details, error::Error as ApiError, page_size::PageSizeLimits,
+    storage::BlockStorage,
+};
 use crate::fee_ticker::{
     tests::TestToken,
-    ticker_info::BlocksInFutureAggregatedOperations,
+    ticker_info::{BlocksInFutureAggregatedOperations, ProverLoad},
     validator::{cache::TokenInMemoryCache, FeeTokenValidator},
     {FeeTicker, FeeTickerInfo, GasOperationsCost, PriceError, TickerConfig},
 };
@@ -108,6 +124,10 @@ impl TestServerConfig {
             } else {
                 app
             };
+            let app = app.app_data(web::Data::new(PageSizeLimits::new(
+                this.pool.clone(),
+                &this.config.api.rest,
+            )));
             app.service(web::scope(scope.as_ref()).service(scope_factory(&this)))
         });
 
@@ -668,6 +688,7 @@ impl TestServerConfig {
                 tx_hash: dummy_ethereum_tx_hash(VERIFIED_OP_SERIAL_ID as i64)
                     .as_bytes()
                     .to_vec(),
+                l1_sender: None,
                 affected_accounts: vec![Default::default()],
                 token: 0,
             },
@@ -693,6 +714,7 @@ impl TestServerConfig {
                 tx_hash: dummy_ethereum_tx_hash(COMMITTED_OP_SERIAL_ID as i64)
                     .as_bytes()
                     .to_vec(),
+                l1_sender: None,
                 affected_accounts: vec![Default::default()],
                 token: 0,
             },
@@ -754,6 +776,7 @@ pub fn dummy_deposit_op(
             eth_hash: H256::default(),
             eth_block: 10,
             eth_block_index: Some(1),
+            l1_sender: None,
         },
         op: deposit_op,
         block_index,
@@ -790,6 +813,7 @@ pub fn dummy_full_exit_op(
             eth_hash: H256::default(),
             eth_block: 10,
             eth_block_index: Some(1),
+            l1_sender: None,
         },
         op: deposit_op,
         block_index,
@@ -854,6 +878,10 @@ impl FeeTickerInfo for DummyFeeTickerInfo {
         Ok(None)
     }
 
+    async fn prover_load(&self) -> anyhow::Result<ProverLoad> {
+        Ok(ProverLoad::default())
+    }
+
     async fn get_last_token_price(&self, token: TokenLike) -> Result<TokenPrice, PriceError> {
         if let Some(price) = self.prices.get(&token) {
             Ok(TokenPrice {
@@ -912,6 +940,10 @@ pub fn get_test_ticker_config() -> TickerConfig {
         scale_fee_coefficient: Ratio::new(BigUint::from(150u32), BigUint::from(100u32)),
         max_blocks_to_aggregate: 5,
         subsidy_cpk_price_usd: scaled_u64_to_ratio(SUBSIDY_CPK_PRICE_USD_SCALED),
+        max_prover_queue_depth: 100,
+        max_unproven_block_age: Duration::from_secs(3600),
+        prover_overload_fee_coefficient: Ratio::from_integer(BigUint::from(1u32)),
+        gas_predictor: GasPredictor::default(),
     }
 }
 pub fn dummy_fee_ticker(
@@ -932,3 +964,196 @@ pub fn dummy_fee_ticker(
         validator,
     )
 }
+
+/// In-memory blocks data for the unit tests of the `api/v0.2/blocks` handlers.
+#[derive(Debug, Default)]
+pub struct MockBlockStorage {
+    /// Committed blocks in ascending order, blocks don't have transactions.
+    pub blocks: Vec<StorageBlockDetails>,
+    /// Makes every query fail, e.g. to check the mapping of the storage errors.
+    pub unavailable: bool,
+    pub pending_block: Option<PendingBlock>,
+}
+
+impl MockBlockStorage {
+    /// Creates the chain of the committed blocks, the first `finalized` of them are finalized.
+    pub fn new(committed: u32, finalized: u32) -> Self {
+        let blocks = (1..=committed)
+            .map(|number| {
+                let is_finalized = number <= finalized;
+                StorageBlockDetails {
+                    block_number: number.into(),
+                    new_state_root: vec![0; 32],
+                    block_size: 10,
+                    commit_tx_hash: Some(vec![1; 32]),
+                    verify_tx_hash: Some(vec![2; 32]).filter(|_| is_finalized),
+                    committed_at: Utc::now(),
+                    verified_at: Some(Utc::now()).filter(|_| is_finalized),
+                }
+            })
+            .collect();
+        Self {
+            blocks,
+            unavailable: false,
+            pending_block: None,
+        }
+    }
+
+    fn check_available(&self) -> QueryResult<()> {
+        if self.unavailable {
+            Err(anyhow::anyhow!("Database is unavailable"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn last_block(&self, finalized: bool) -> BlockNumber {
+        self.blocks
+            .iter()
+            .rev()
+            .find(|block| !finalized || block.is_verified())
+            .map_or(BlockNumber(0), |block| {
+                BlockNumber(block.block_number as u32)
+            })
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockStorage for MockBlockStorage {
+    async fn block_details(
+        &self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<StorageBlockDetails>> {
+        self.check_available()?;
+        Ok(self
+            .blocks
+            .iter()
+            .find(|block| block.block_number == i64::from(*block_number))
+            .cloned())
+    }
+
+    async fn last_committed_block(&self) -> QueryResult<BlockNumber> {
+        self.check_available()?;
+        Ok(self.last_block(false))
+    }
+
+    async fn last_finalized_block(&self) -> QueryResult<BlockNumber> {
+        self.check_available()?;
+        Ok(self.last_block(true))
+    }
+
+    async fn block_page(
+        &self,
+        query: &PaginationQuery<ApiEither<BlockNumber>>,
+    ) -> Result<Paginated<BlockInfo, BlockNumber>, ApiError> {
+        self.check_available().map_err(ApiError::storage)?;
+        let last_block = self.last_block(false);
+        let from = match query.from.inner {
+            Either::Left(block_number) => block_number,
+            Either::Right(_) => last_block,
+        };
+        let from_number = i64::from(*from);
+        let limit = query.limit as usize;
+        let blocks = match query.direction {
+            PaginationDirection::Newer => self
+                .blocks
+                .iter()
+                .filter(|block| block.block_number >= from_number)
+                .take(limit)
+                .cloned()
+                .map(block_info_from_details)
+                .collect(),
+            PaginationDirection::Older => self
+                .blocks
+                .iter()
+                .rev()
+                .filter(|block| block.block_number <= from_number)
+                .take(limit)
+                .cloned()
+                .map(block_info_from_details)
+                .collect(),
+        };
+        Ok(Paginated::new(
+            blocks,
+            from,
+            query.limit,
+            query.direction,
+            *last_block,
+        ))
+    }
+
+    async fn block_transactions_page(
+        &self,
+        query: &PaginationQuery<BlockAndTxHash>,
+    ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, ApiError> {
+        self.check_available().map_err(ApiError::storage)?;
+        Ok(Paginated::new(
+            Vec::new(),
+            Default::default(),
+            query.limit,
+            query.direction,
+            0,
+        ))
+    }
+
+    async fn tx_data(
+        &self,
+        _block_number: BlockNumber,
+        _block_index: u64,
+    ) -> QueryResult<Option<TxData>> {
+        self.check_available()?;
+        Ok(None)
+    }
+
+    async fn pending_block(&self) -> QueryResult<Option<PendingBlock>> {
+        self.check_available()?;
+        Ok(self.pending_block.clone())
+    }
+
+    /// Blocks are empty, so their public data consists of the padding only.
+    async fn block(&self, block_number: BlockNumber) -> QueryResult<Option<Block>> {
+        self.check_available()?;
+        Ok(self
+            .blocks
+            .iter()
+            .find(|block| block.block_number == i64::from(*block_number))
+            .map(|block| {
+                Block::new(
+                    block_number,
+                    Default::default(),
+                    AccountId(0),
+                    Vec::new(),
+                    (0, 0),
+                    block.block_size as usize,
+                    1_000_000.into(),
+                    1_500_000.into(),
+                    H256::zero(),
+                    0,
+                )
+            }))
+    }
+
+    /// Finalized blocks are proven one by one and executed in the same transaction as proven.
+    async fn block_finality(
+        &self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<StorageBlockFinality>> {
+        self.check_available()?;
+        Ok(self
+            .blocks
+            .iter()
+            .find(|block| block.block_number == i64::from(*block_number))
+            .map(|block| {
+                let proof_block = Some(block.block_number).filter(|_| block.is_verified());
+                StorageBlockFinality {
+                    block_number: block.block_number,
+                    commitment: vec![0; 32],
+                    commit_tx_hash: block.commit_tx_hash.clone(),
+                    prove_tx_hash: block.verify_tx_hash.clone(),
+                    execute_tx_hash: block.verify_tx_hash.clone(),
+                    proof_first_block: proof_block,
+                    proof_last_block: proof_block,
+                }
+            }))
+    }
+}
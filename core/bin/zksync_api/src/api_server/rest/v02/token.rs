@@ -7,7 +7,7 @@ use std::time::Instant;
 // External uses
 use actix_web::{
     web::{self},
-    Scope,
+    HttpRequest, Scope,
 };
 use bigdecimal::{BigDecimal, Zero};
 use num::{rational::Ratio, BigUint, FromPrimitive};
@@ -26,12 +26,14 @@ use zksync_types::{tx::TxHash, AccountId, Token, TokenId, TokenLike};
 // Local uses
 use super::{
     error::{Error, InvalidDataError},
-    paginate_trait::Paginate,
+    page_size::max_page_size,
+    paginate_trait::{check_pagination_limit, Paginate},
     response::ApiResult,
 };
 use crate::{
     api_try,
     fee_ticker::{FeeTicker, PriceError, TokenPriceRequestType},
+    utils::finalized_cache::{query_key, Finality, FinalizedCache},
 };
 
 /// Shared data between `api/v0.2/tokens` endpoints.
@@ -41,6 +43,7 @@ struct ApiTokenData {
     fee_ticker: FeeTicker,
     tokens: TokenDBCache,
     pool: ConnectionPool,
+    finalized_cache: FinalizedCache,
 }
 
 impl ApiTokenData {
@@ -49,6 +52,7 @@ impl ApiTokenData {
         pool: ConnectionPool,
         tokens: TokenDBCache,
         fee_ticker: FeeTicker,
+        finalized_cache: FinalizedCache,
     ) -> Self {
         Self {
             min_market_volume: Ratio::from(
@@ -58,6 +62,7 @@ impl ApiTokenData {
             pool,
             tokens,
             fee_ticker,
+            finalized_cache,
         }
     }
 }
@@ -79,10 +84,34 @@ impl ApiTokenData {
     async fn token_page(
         &self,
         query: PaginationQuery<ApiEither<TokenId>>,
+        max_page_size: u32,
+    ) -> Result<Paginated<ApiToken, TokenId>, Error> {
+        // Cached pages are shared by all the callers, so the limit is checked beforehand.
+        check_pagination_limit(query.limit, max_page_size)?;
+        // The list of tokens is refreshed once the next block is finalized.
+        let cache_key = query_key("tokens", &query);
+        if let Some(page) = self
+            .finalized_cache
+            .get(Finality::LastFinalizedBlock, &cache_key)
+            .await
+        {
+            return Ok(page);
+        }
+
+        let page = self.load_token_page(query).await?;
+        self.finalized_cache
+            .insert(Finality::LastFinalizedBlock, &cache_key, &page)
+            .await;
+        Ok(page)
+    }
+
+    async fn load_token_page(
+        &self,
+        query: PaginationQuery<ApiEither<TokenId>>,
     ) -> Result<Paginated<ApiToken, TokenId>, Error> {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         let paginated_tokens: Result<Paginated<Token, TokenId>, Error> =
-            storage.paginate_checked(&query).await;
+            storage.paginate(&query).await;
         match paginated_tokens {
             Ok(paginated_tokens) => {
                 let tokens_to_check: Vec<TokenId> =
@@ -192,11 +221,13 @@ impl ApiTokenData {
 
 async fn token_pagination(
     data: web::Data<ApiTokenData>,
+    http_request: HttpRequest,
     web::Query(query): web::Query<PaginationQuery<String>>,
 ) -> ApiResult<Paginated<ApiToken, TokenId>> {
     let start = Instant::now();
     let query = api_try!(parse_query(query).map_err(Error::from));
-    let res = data.token_page(query).await.into();
+    let max_page_size = api_try!(max_page_size(&http_request, "token_pagination").await);
+    let res = data.token_page(query, max_page_size).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "token_pagination");
     res
 }
@@ -293,8 +324,9 @@ pub fn api_scope(
     pool: ConnectionPool,
     tokens_db: TokenDBCache,
     fee_ticker: FeeTicker,
+    finalized_cache: FinalizedCache,
 ) -> Scope {
-    let data = ApiTokenData::new(config, pool, tokens_db, fee_ticker);
+    let data = ApiTokenData::new(config, pool, tokens_db, fee_ticker, finalized_cache);
 
     web::scope("tokens")
         .app_data(web::Data::new(data))
@@ -319,7 +351,11 @@ mod tests {
         test_utils::{deserialize_response_result, dummy_fee_ticker, TestServerConfig},
         SharedData,
     };
-    use zksync_api_types::v02::{pagination::PaginationDirection, ApiVersion};
+    use std::time::Duration;
+    use zksync_api_types::v02::{
+        pagination::{PaginationDirection, MAX_LIMIT},
+        ApiVersion,
+    };
     use zksync_types::{Address, BlockNumber, ZkSyncTx};
 
     async fn is_token_enabled_for_fees(
@@ -367,6 +403,7 @@ mod tests {
                     cfg.pool.clone(),
                     TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
                     fee_ticker.clone(),
+                    FinalizedCache::new(cfg.pool.clone(), 10, Duration::from_secs(1), None),
                 )
             },
             Some(shared_data),
@@ -403,7 +440,7 @@ mod tests {
         let expected_pagination = {
             let mut storage = cfg.pool.access_storage().await?;
             let paginated_tokens: Paginated<Token, TokenId> = storage
-                .paginate_checked(&query)
+                .paginate_checked(&query, MAX_LIMIT)
                 .await
                 .map_err(|err| anyhow::anyhow!(err.message))?;
             let mut list = Vec::new();
@@ -5,36 +5,261 @@ use std::time::Instant;
 // External uses
 use actix_web::{
     web::{self, Json},
-    Scope,
+    HttpRequest, Scope,
 };
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use num::BigUint;
 
 // Workspace uses
 use zksync_api_types::{
-    v02::transaction::{
-        ApiTxBatch, IncomingTxBatch, L1Receipt, L1Transaction, Receipt, SubmitBatchResponse,
-        Toggle2FA, Toggle2FAResponse, Transaction, TransactionData, TxData, TxHashSerializeWrapper,
-        TxInBlockStatus,
+    v02::{
+        fields::{FieldsQuery, ShapedTxData, TxFields},
+        signature::{MaybeSigned, SignatureQuery},
+        transaction::{
+            ApiTxBatch, IncomingTxBatch, L1Receipt, L1Transaction, L1TxActivity, Receipt,
+            SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, Transaction, TransactionData,
+            TxData, TxFeeBreakdown, TxHashSerializeWrapper, TxInBlockStatus, WithdrawalHook,
+            WithdrawalHookStatus,
+        },
     },
     TxWithSignature,
 };
-use zksync_types::{tx::TxHash, EthBlockId};
+use zksync_crypto::{convert::FeConvert, Fr};
+use zksync_object_store::{archived_tx_key, ObjectStore};
+use zksync_storage::{
+    chain::operations_ext::records::StorageTxData,
+    fee_breakdowns::records::StoredTxFeeBreakdown,
+    withdrawal_hooks::{
+        HOOK_STATUS_CANCELLED, HOOK_STATUS_FAILED, HOOK_STATUS_PENDING, HOOK_STATUS_READY,
+        HOOK_STATUS_SENT, HOOK_STATUS_SUCCEEDED,
+    },
+    StorageProcessor,
+};
+use zksync_types::{
+    block::{Block, TxInclusionProof},
+    tx::TxHash,
+    EthBlockId, TokenId, H256,
+};
+use zksync_utils::big_decimal_to_ratio;
 
 // Local uses
-use super::{error::Error, response::ApiResult};
-use crate::api_server::tx_sender::{SubmitError, TxSender};
+use super::{
+    error::{Error, InvalidDataError},
+    fields::{format_tx_data_amounts, requested_tx_fields},
+    response::ApiResult,
+    signature::{maybe_sign, ResponseSigner},
+    tx_callback::{track_submitted_txs, untrack_rejected_txs},
+};
+use crate::{
+    api_server::{
+        rest::finality_estimate::{block_committed_at, SharedFinalityEstimate},
+        tx_sender::{SubmitError, TxSender},
+    },
+    api_try,
+    utils::finalized_cache::Finality,
+};
+
+/// Converts the stored fee breakdown, returns `None` if the transaction is not executed yet.
+fn api_fee_breakdown(breakdown: StoredTxFeeBreakdown) -> Result<Option<TxFeeBreakdown>, Error> {
+    let to_biguint = |amount: &BigDecimal| -> Result<BigUint, Error> {
+        Ok(big_decimal_to_ratio(amount)
+            .map_err(Error::storage)?
+            .to_integer())
+    };
+    let (total_fee, zkp_fee, gas_fee) = match (
+        &breakdown.charged_fee,
+        &breakdown.zkp_fee,
+        &breakdown.gas_fee,
+    ) {
+        (Some(total_fee), Some(zkp_fee), Some(gas_fee)) => (total_fee, zkp_fee, gas_fee),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(TxFeeBreakdown {
+        token_id: TokenId(breakdown.token_id as u32),
+        total_fee: to_biguint(total_fee)?,
+        zkp_fee: to_biguint(zkp_fee)?,
+        gas_fee: to_biguint(gas_fee)?,
+        subsidy: to_biguint(&breakdown.subsidy)?,
+    }))
+}
 
 /// Shared data between `api/v0.2/transactions` endpoints.
 #[derive(Clone)]
 struct ApiTransactionData {
     tx_sender: TxSender,
+    signer: Option<ResponseSigner>,
+    finality_estimate: SharedFinalityEstimate,
+    response_shaping_enabled: bool,
+    tx_callbacks_enabled: bool,
+    /// Object storage archive of the pruned transactions.
+    archive: Option<ObjectStore>,
 }
 
 impl ApiTransactionData {
-    fn new(tx_sender: TxSender) -> Self {
-        Self { tx_sender }
+    fn new(
+        tx_sender: TxSender,
+        signer: Option<ResponseSigner>,
+        finality_estimate: SharedFinalityEstimate,
+        response_shaping_enabled: bool,
+        tx_callbacks_enabled: bool,
+        archive: Option<ObjectStore>,
+    ) -> Self {
+        Self {
+            tx_sender,
+            signer,
+            finality_estimate,
+            response_shaping_enabled,
+            tx_callbacks_enabled,
+            archive,
+        }
+    }
+
+    /// Starts tracking the transactions for the status callbacks, if the request has
+    /// the API key of the scope with the callback endpoint. Returns the scope.
+    async fn track_txs(
+        &self,
+        http_request: &HttpRequest,
+        tx_hashes: &[TxHash],
+    ) -> Result<Option<String>, Error> {
+        if !self.tx_callbacks_enabled {
+            return Ok(None);
+        }
+        track_submitted_txs(&self.tx_sender.pool, http_request, tx_hashes).await
+    }
+
+    async fn untrack_txs(&self, scope: Option<String>, tx_hashes: &[TxHash]) {
+        if let Some(scope) = scope {
+            if let Err(err) = untrack_rejected_txs(&self.tx_sender.pool, &scope, tx_hashes).await {
+                vlog::warn!(
+                    "Can't stop tracking the rejected transactions: {}",
+                    err.message
+                );
+            }
+        }
+    }
+
+    /// Fills the expected time until the operation is finalized, based on the recent blocks cadence.
+    async fn estimate_finality(&self, receipt: &mut Receipt) -> Result<(), Error> {
+        let status = receipt.status();
+        let committed_at = match (status, receipt.rollup_block()) {
+            (TxInBlockStatus::Committed, Some(block_number)) => {
+                block_committed_at(&self.tx_sender.pool, block_number)
+                    .await
+                    .map_err(Error::storage)?
+            }
+            _ => None,
+        };
+        let time_left =
+            self.finality_estimate
+                .read()
+                .await
+                .time_left(status, committed_at, Utc::now());
+        receipt.set_expected_finality_secs(time_left.map(|time_left| time_left.as_secs()));
+        Ok(())
+    }
+
+    /// Returns the receipt of the transaction, signed if requested. The signature binds
+    /// the receipt to the block which includes the transaction, or to the last saved block
+    /// if the transaction is not executed yet.
+    async fn signed_tx_status(
+        &self,
+        tx_hash: TxHash,
+        query: SignatureQuery,
+    ) -> Result<MaybeSigned<Option<Receipt>>, Error> {
+        let mut receipt = self.tx_status(tx_hash).await?;
+        if let Some(receipt) = receipt.as_mut() {
+            self.estimate_finality(receipt).await?;
+        }
+        if !query.signed {
+            return Ok(MaybeSigned::Plain(receipt));
+        }
+
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let rollup_block = receipt.as_ref().and_then(Receipt::rollup_block);
+        let block_number = match rollup_block {
+            Some(block_number) => block_number,
+            None => storage
+                .chain()
+                .block_schema()
+                .get_last_saved_block()
+                .await
+                .map_err(Error::storage)?,
+        };
+        maybe_sign(
+            self.signer.as_ref(),
+            &mut storage,
+            query,
+            receipt,
+            block_number,
+        )
+        .await
     }
 
     async fn tx_status(&self, tx_hash: TxHash) -> Result<Option<Receipt>, Error> {
+        let mut receipt = self.tx_receipt(tx_hash).await?;
+        // Hooks are called after the withdrawals are finalized, so their status isn't cached.
+        if let Some(Receipt::L2(receipt)) = &mut receipt {
+            if self.tx_sender.withdrawal_hooks_enabled {
+                receipt.withdrawal_hook = self.withdrawal_hook(tx_hash).await?;
+            }
+        }
+        Ok(receipt)
+    }
+
+    async fn withdrawal_hook(&self, tx_hash: TxHash) -> Result<Option<WithdrawalHook>, Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let hook = storage
+            .withdrawal_hooks_schema()
+            .get_withdrawal_hooks(&[tx_hash])
+            .await
+            .map_err(Error::storage)?
+            .pop();
+        let hook = match hook {
+            Some(hook) => hook,
+            None => return Ok(None),
+        };
+
+        let status = match hook.status.as_str() {
+            HOOK_STATUS_PENDING => WithdrawalHookStatus::Pending,
+            HOOK_STATUS_READY => WithdrawalHookStatus::Ready,
+            HOOK_STATUS_SENT => WithdrawalHookStatus::Sent,
+            HOOK_STATUS_SUCCEEDED => WithdrawalHookStatus::Succeeded,
+            HOOK_STATUS_FAILED => WithdrawalHookStatus::Failed,
+            HOOK_STATUS_CANCELLED => WithdrawalHookStatus::Cancelled,
+            status => {
+                return Err(Error::storage(format!(
+                    "Unknown withdrawal hook status: {}",
+                    status
+                )))
+            }
+        };
+        Ok(Some(WithdrawalHook {
+            status,
+            eth_tx_hash: hook.eth_tx_hash.as_deref().map(H256::from_slice),
+            fail_reason: hook.fail_reason,
+        }))
+    }
+
+    async fn tx_receipt(&self, tx_hash: TxHash) -> Result<Option<Receipt>, Error> {
+        // Receipts of the finalized operations never change, so they are cached.
+        let cache_key = format!("tx_receipt:{}", tx_hash);
+        let cache = &self.tx_sender.finalized_cache;
+        if let Some(receipt) = cache.get(Finality::Finalized, &cache_key).await {
+            return Ok(Some(receipt));
+        }
+
         // Try to find in the DB.
         let mut storage = self
             .tx_sender
@@ -51,6 +276,11 @@ impl ApiTransactionData {
             .await
             .map_err(Error::storage)?
         {
+            if receipt.status() == TxInBlockStatus::Finalized {
+                cache
+                    .insert(Finality::Finalized, &cache_key, &receipt)
+                    .await;
+            }
             Ok(Some(receipt))
         }
         // 2. Try to find the pending operation.
@@ -66,28 +296,58 @@ impl ApiTransactionData {
                 eth_block: EthBlockId(op.eth_block),
                 rollup_block: None,
                 id: op.serial_id,
+                expected_finality_secs: None,
             })))
         }
-        // 3. No operation found, return nothing.
+        // 3. Try to find the operation moved to the archive by the pruner.
+        else if let Some(receipt) = storage
+            .pruning_schema()
+            .archived_tx_receipt(tx_hash.as_ref())
+            .await
+            .map_err(Error::storage)?
+        {
+            Ok(Some(receipt))
+        }
+        // 4. No operation found, return nothing.
         else {
             Ok(None)
         }
     }
 
-    async fn tx_data(&self, tx_hash: TxHash) -> Result<Option<TxData>, Error> {
+    /// Returns the transaction data, the fee breakdown and the memo are loaded only if requested.
+    async fn tx_data(&self, tx_hash: TxHash, fields: TxFields) -> Result<Option<TxData>, Error> {
         let mut storage = self
             .tx_sender
             .pool
             .access_storage()
             .await
             .map_err(Error::storage)?;
-        if let Some(data) = storage
+        if let Some(mut data) = storage
             .chain()
             .operations_ext_schema()
             .tx_data_api_v02(tx_hash.as_ref())
             .await
             .map_err(Error::storage)?
         {
+            if fields.contains("feeBreakdown") {
+                data.fee_breakdown = storage
+                    .fee_breakdowns_schema()
+                    .get_fee_breakdown(tx_hash)
+                    .await
+                    .map_err(Error::storage)?
+                    .map(api_fee_breakdown)
+                    .transpose()?
+                    .flatten();
+            }
+            if fields.contains("memo") {
+                data.tx.memo = storage
+                    .tx_memos_schema()
+                    .get_tx_memos(&[tx_hash])
+                    .await
+                    .map_err(Error::storage)?
+                    .pop()
+                    .map(|memo| memo.memo);
+            }
             Ok(Some(data))
         } else if let Some(op) = storage
             .chain()
@@ -111,17 +371,59 @@ impl ApiTransactionData {
                 fail_reason: None,
                 created_at: None,
                 batch_id: None,
+                memo: None,
             };
 
             Ok(Some(TxData {
                 tx,
                 eth_signature: None,
+                fee_breakdown: None,
             }))
+        } else if storage
+            .pruning_schema()
+            .archived_tx_receipt(tx_hash.as_ref())
+            .await
+            .map_err(Error::storage)?
+            .is_some()
+        {
+            match self.archived_tx_data(&mut storage, tx_hash).await? {
+                Some(data) => Ok(Some(data)),
+                None => Err(Error::from(InvalidDataError::TransactionPruned)),
+            }
         } else {
             Ok(None)
         }
     }
 
+    /// Loads the data of the pruned transaction from the object storage archive.
+    /// Returns `None` if the archive is not configured or doesn't have the transaction.
+    async fn archived_tx_data(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        tx_hash: TxHash,
+    ) -> Result<Option<TxData>, Error> {
+        let archive = match &self.archive {
+            Some(archive) => archive,
+            None => return Ok(None),
+        };
+        let body = match archive
+            .get(&archived_tx_key(tx_hash.as_ref()))
+            .await
+            .map_err(Error::storage)?
+        {
+            Some(body) => body,
+            None => return Ok(None),
+        };
+        let data: StorageTxData = serde_json::from_slice(&body).map_err(Error::storage)?;
+        let data = storage
+            .chain()
+            .operations_ext_schema()
+            .archived_tx_data(data)
+            .await
+            .map_err(Error::storage)?;
+        Ok(Some(data))
+    }
+
     async fn get_batch(&self, batch_hash: TxHash) -> Result<Option<ApiTxBatch>, Error> {
         let mut storage = self
             .tx_sender
@@ -136,6 +438,76 @@ impl ApiTransactionData {
             .await
             .map_err(Error::storage)
     }
+
+    /// Returns the proof that the operation is a part of the public data of the committed block,
+    /// `None` if the operation is not executed successfully or its block is not committed yet.
+    async fn tx_inclusion_proof(&self, tx_hash: TxHash) -> Result<Option<TxInclusionProof>, Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let tx = match storage
+            .chain()
+            .operations_ext_schema()
+            .tx_data_api_v02(tx_hash.as_ref())
+            .await
+            .map_err(Error::storage)?
+        {
+            Some(data) => data.tx,
+            None => return Ok(None),
+        };
+        let block_number = match tx.block_number {
+            Some(block_number) if tx.status != TxInBlockStatus::Rejected => block_number,
+            _ => return Ok(None),
+        };
+        let last_committed_block = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_confirmed_block()
+            .await
+            .map_err(Error::storage)?;
+        if block_number > last_committed_block {
+            return Ok(None);
+        }
+
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_block(block_number)
+            .await
+            .map_err(Error::storage)?;
+        let previous_block = storage
+            .chain()
+            .block_schema()
+            .get_storage_block(block_number - 1)
+            .await
+            .map_err(Error::storage)?;
+        match (block, previous_block) {
+            (Some(block), Some(previous_block)) => {
+                let previous_root_hash =
+                    Fr::from_bytes(&previous_block.root_hash).map_err(Error::storage)?;
+                Ok(block.tx_inclusion_proof(Block::encode_fr_for_eth(previous_root_hash), tx_hash))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn tx_by_l1_hash(&self, eth_hash: H256) -> Result<Option<L1TxActivity>, Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        storage
+            .chain()
+            .operations_ext_schema()
+            .find_by_l1_hash(eth_hash)
+            .await
+            .map_err(Error::storage)
+    }
 }
 
 // Server implementation
@@ -143,9 +515,10 @@ impl ApiTransactionData {
 async fn tx_status(
     data: web::Data<ApiTransactionData>,
     tx_hash: web::Path<TxHash>,
-) -> ApiResult<Option<Receipt>> {
+    web::Query(query): web::Query<SignatureQuery>,
+) -> ApiResult<MaybeSigned<Option<Receipt>>> {
     let start = Instant::now();
-    let res = data.tx_status(*tx_hash).await.into();
+    let res = data.signed_tx_status(*tx_hash, query).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_status");
     res
 }
@@ -153,24 +526,48 @@ async fn tx_status(
 async fn tx_data(
     data: web::Data<ApiTransactionData>,
     tx_hash: web::Path<TxHash>,
-) -> ApiResult<Option<TxData>> {
+    web::Query(fields_query): web::Query<FieldsQuery>,
+) -> ApiResult<Option<ShapedTxData>> {
     let start = Instant::now();
-    let res = data.tx_data(*tx_hash).await.into();
+    let fields = api_try!(requested_tx_fields(
+        &fields_query,
+        data.response_shaping_enabled
+    ));
+    let mut tx_data = api_try!(data.tx_data(*tx_hash, fields).await).map(|data| ShapedTxData {
+        data,
+        fields,
+        amounts: None,
+    });
+    if let Some(tx_data) = &mut tx_data {
+        api_try!(
+            format_tx_data_amounts(
+                &fields_query,
+                &data.tx_sender.pool,
+                &data.tx_sender.tokens,
+                tx_data
+            )
+            .await
+        );
+    }
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_data");
-    res
+    ApiResult::Ok(tx_data)
 }
 
 async fn submit_tx(
     data: web::Data<ApiTransactionData>,
+    http_request: HttpRequest,
     Json(body): Json<TxWithSignature>,
 ) -> ApiResult<TxHashSerializeWrapper> {
     let start = Instant::now();
+    let tx_hashes = [body.tx.hash()];
+    let tracked_scope = api_try!(data.track_txs(&http_request, &tx_hashes).await);
     let tx_hash = data
         .tx_sender
-        .submit_tx(body.tx, body.signature, None)
+        .submit_tx(body.tx, body.signature, body.memo, body.l1_calldata, None)
         .await;
 
     if let Err(err) = &tx_hash {
+        data.untrack_txs(tracked_scope, &tx_hashes).await;
         let err_label = match err {
             SubmitError::IncorrectTx(err) => err.clone(),
             SubmitError::TxAdd(err) => err.to_string(),
@@ -187,15 +584,19 @@ async fn submit_tx(
 
 async fn submit_batch(
     data: web::Data<ApiTransactionData>,
+    http_request: HttpRequest,
     Json(body): Json<IncomingTxBatch>,
 ) -> ApiResult<SubmitBatchResponse> {
     let start = Instant::now();
+    let tx_hashes: Vec<_> = body.txs.iter().map(|tx| tx.tx.hash()).collect();
+    let tracked_scope = api_try!(data.track_txs(&http_request, &tx_hashes).await);
     let response = data
         .tx_sender
         .submit_txs_batch(body.txs, body.signature, None)
         .await;
 
     if let Err(err) = &response {
+        data.untrack_txs(tracked_scope, &tx_hashes).await;
         let err_label = match err {
             SubmitError::IncorrectTx(err) => err.clone(),
             SubmitError::TxAdd(err) => err.to_string(),
@@ -235,16 +636,55 @@ async fn get_batch(
     res
 }
 
-pub fn api_scope(tx_sender: TxSender) -> Scope {
-    let data = ApiTransactionData::new(tx_sender);
+async fn tx_by_l1_hash(
+    data: web::Data<ApiTransactionData>,
+    eth_hash: web::Path<H256>,
+) -> ApiResult<Option<L1TxActivity>> {
+    let start = Instant::now();
+    let res = data.tx_by_l1_hash(*eth_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_by_l1_hash");
+    res
+}
+
+async fn tx_inclusion_proof(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<TxHash>,
+) -> ApiResult<Option<TxInclusionProof>> {
+    let start = Instant::now();
+    let res = data.tx_inclusion_proof(*tx_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_inclusion_proof");
+    res
+}
+
+pub fn api_scope(
+    tx_sender: TxSender,
+    signer: Option<ResponseSigner>,
+    finality_estimate: SharedFinalityEstimate,
+    response_shaping_enabled: bool,
+    tx_callbacks_enabled: bool,
+    archive: Option<ObjectStore>,
+) -> Scope {
+    let data = ApiTransactionData::new(
+        tx_sender,
+        signer,
+        finality_estimate,
+        response_shaping_enabled,
+        tx_callbacks_enabled,
+        archive,
+    );
 
     web::scope("transactions")
         .app_data(web::Data::new(data))
         .route("", web::post().to(submit_tx))
         .route("{tx_hash}", web::get().to(tx_status))
         .route("{tx_hash}/data", web::get().to(tx_data))
+        .route(
+            "{tx_hash}/inclusionProof",
+            web::get().to(tx_inclusion_proof),
+        )
         .route("/batches", web::post().to(submit_batch))
         .route("/batches/{batch_hash}", web::get().to(get_batch))
+        .route("/byL1Hash/{eth_hash}", web::get().to(tx_by_l1_hash))
         .route("/toggle2FA", web::post().to(toggle_2fa))
 }
 
@@ -254,7 +694,7 @@ mod tests {
     use crate::api_server::rest::v02::{
         test_utils::{
             deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
-            TestTransactions,
+            TestTransactions, COMMITTED_OP_SERIAL_ID,
         },
         SharedData,
     };
@@ -270,6 +710,7 @@ mod tests {
         ApiVersion,
     };
     use zksync_mempool::MempoolTransactionRequest;
+    use zksync_storage::test_data::dummy_ethereum_tx_hash;
     use zksync_types::{
         tokens::{Token, TokenMarketVolume},
         tx::{
@@ -285,13 +726,22 @@ mod tests {
         let task = tokio::spawn(async move {
             while let Some(tx) = mempool_tx_request_receiver.next().await {
                 match tx {
-                    MempoolTransactionRequest::NewTx(_, resp) => {
+                    MempoolTransactionRequest::NewTx(_, resp, _) => {
+                        resp.send(Ok(())).unwrap_or_default()
+                    }
+                    MempoolTransactionRequest::NewSponsoredTx(_, _, resp, _) => {
+                        resp.send(Ok(())).unwrap_or_default()
+                    }
+                    MempoolTransactionRequest::NewFeeFreeTx(_, _, _, resp, _) => {
+                        resp.send(Ok(())).unwrap_or_default()
+                    }
+                    MempoolTransactionRequest::NewWithdrawalTx(_, _, resp, _) => {
                         resp.send(Ok(())).unwrap_or_default()
                     }
                     MempoolTransactionRequest::NewPriorityOps(_, _, resp) => {
                         resp.send(Ok(())).unwrap_or_default()
                     }
-                    MempoolTransactionRequest::NewTxsBatch(_, _, resp) => {
+                    MempoolTransactionRequest::NewTxsBatch(_, _, _, resp, _) => {
                         resp.send(Ok(())).unwrap_or_default()
                     }
                 }
@@ -346,15 +796,22 @@ mod tests {
 
         let (client, server) = cfg.start_server(
             move |cfg: &TestServerConfig| {
-                api_scope(TxSender::new(
-                    cfg.pool.clone(),
-                    dummy_sign_verifier(),
-                    dummy_fee_ticker(&prices, Some(cache.clone())),
-                    &cfg.config.api.common,
-                    &cfg.config.api.token_config,
-                    sender.clone(),
-                    ChainId(cfg.config.eth_client.chain_id),
-                ))
+                api_scope(
+                    TxSender::new(
+                        cfg.pool.clone(),
+                        dummy_sign_verifier(),
+                        dummy_fee_ticker(&prices, Some(cache.clone())),
+                        &cfg.config.api.common,
+                        &cfg.config.api.token_config,
+                        sender.clone(),
+                        ChainId(cfg.config.eth_client.chain_id),
+                    ),
+                    ResponseSigner::new(&cfg.config.api.common),
+                    SharedFinalityEstimate::default(),
+                    true,
+                    true,
+                    None,
+                )
             },
             Some(shared_data),
         );
@@ -376,6 +833,8 @@ mod tests {
                     TxWithSignature {
                         tx,
                         signature: TxEthSignatureVariant::Single(None),
+                        memo: None,
+                        l1_calldata: None,
                     },
                     tx_hash,
                 )
@@ -456,6 +915,9 @@ mod tests {
             rollup_block: Some(BlockNumber(1)),
             status: TxInBlockStatus::Finalized,
             fail_reason: None,
+            expected_finality_secs: None,
+            valid_until: None,
+            withdrawal_hook: None,
         });
         assert_eq!(tx_status, expected_tx_status);
 
@@ -463,6 +925,12 @@ mod tests {
         let tx_data: Option<TxData> = deserialize_response_result(response)?;
         assert_eq!(tx_data.unwrap().tx.tx_hash, tx_hash);
 
+        let response = client.tx_inclusion_proof(tx_hash).await?;
+        let proof: Option<TxInclusionProof> = deserialize_response_result(response)?;
+        let proof = proof.unwrap();
+        assert_eq!(proof.block_number, BlockNumber(1));
+        assert!(proof.op_public_data().is_some());
+
         let pending_tx_hash = {
             let mut storage = cfg.pool.access_storage().await?;
 
@@ -487,6 +955,9 @@ mod tests {
             rollup_block: None,
             status: TxInBlockStatus::Queued,
             fail_reason: None,
+            expected_finality_secs: None,
+            valid_until: None,
+            withdrawal_hook: None,
         });
         assert_eq!(tx_status, expected_tx_status);
 
@@ -499,6 +970,27 @@ mod tests {
         let tx_data: Option<TxData> = deserialize_response_result(response)?;
         assert!(tx_data.is_none());
 
+        let response = client.tx_inclusion_proof(pending_tx_hash).await?;
+        let proof: Option<TxInclusionProof> = deserialize_response_result(response)?;
+        assert!(proof.is_none());
+
+        let eth_hash = dummy_ethereum_tx_hash(COMMITTED_OP_SERIAL_ID as i64);
+        let response = client.tx_by_l1_hash(eth_hash).await?;
+        let activity: Option<L1TxActivity> = deserialize_response_result(response)?;
+        let activity = activity.unwrap();
+        assert_eq!(activity.eth_hash, eth_hash);
+        assert_eq!(
+            activity.priority_ops,
+            vec![TxHashSerializeWrapper(
+                TxHash::from_slice(eth_hash.as_bytes()).unwrap()
+            )]
+        );
+        assert!(activity.completed_withdrawals.is_empty());
+
+        let response = client.tx_by_l1_hash(H256::repeat_byte(0xff)).await?;
+        let activity: Option<L1TxActivity> = deserialize_response_result(response)?;
+        assert!(activity.is_none());
+
         server.stop().await;
         task.abort();
         Ok(())
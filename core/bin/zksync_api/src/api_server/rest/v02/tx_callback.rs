@@ -0,0 +1,281 @@
+//! Tx callbacks part of API implementation.
+//!
+//! Exchanges register the callback endpoint with their API key, and the status changes of the
+//! transactions submitted with the key are delivered to it by the `tx_callback_sender` of the
+//! core, so they don't need to poll the status of every deposit of their users.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    HttpRequest, Scope,
+};
+
+// Workspace uses
+use zksync_api_types::v02::tx_callback::{TxCallbackEndpoint, TxCallbackEndpointRequest};
+use zksync_crypto::rand::{thread_rng, Rng};
+use zksync_storage::{tx_callbacks::records::StoredTxCallbackEndpoint, ConnectionPool};
+use zksync_types::tx::TxHash;
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    label::api_key_scope,
+    response::ApiResult,
+};
+
+/// Starts tracking the submitted transactions if the request is authorized with the API key
+/// of the scope having the callback endpoint. Returns the scope of the tracked transactions.
+///
+/// Transactions are tracked before they are submitted, since the failed ones may be
+/// executed before the submission request returns.
+pub(super) async fn track_submitted_txs(
+    pool: &ConnectionPool,
+    http_request: &HttpRequest,
+    tx_hashes: &[TxHash],
+) -> Result<Option<String>, Error> {
+    let mut storage = pool.access_storage().await.map_err(Error::storage)?;
+    let scope = match api_key_scope(&mut storage, http_request).await? {
+        Some(scope) => scope,
+        None => return Ok(None),
+    };
+    let tracked = storage
+        .tx_callbacks_schema()
+        .track_txs(&scope, tx_hashes)
+        .await
+        .map_err(Error::storage)?;
+    Ok(if tracked { Some(scope) } else { None })
+}
+
+/// Stops tracking the transactions which were not accepted by the server.
+pub(super) async fn untrack_rejected_txs(
+    pool: &ConnectionPool,
+    scope: &str,
+    tx_hashes: &[TxHash],
+) -> Result<(), Error> {
+    pool.access_storage()
+        .await
+        .map_err(Error::storage)?
+        .tx_callbacks_schema()
+        .untrack_txs(scope, tx_hashes)
+        .await
+        .map_err(Error::storage)
+}
+
+fn api_endpoint(endpoint: StoredTxCallbackEndpoint, with_secret: bool) -> TxCallbackEndpoint {
+    TxCallbackEndpoint {
+        url: endpoint.url,
+        secret: if with_secret {
+            Some(endpoint.secret)
+        } else {
+            None
+        },
+        created_at: endpoint.created_at,
+    }
+}
+
+/// Shared data between `api/v0.2/tx_callbacks` endpoints.
+#[derive(Clone)]
+struct ApiTxCallbackData {
+    pool: ConnectionPool,
+    enabled: bool,
+}
+
+impl ApiTxCallbackData {
+    fn new(pool: ConnectionPool, enabled: bool) -> Self {
+        Self { pool, enabled }
+    }
+
+    fn check_enabled(&self) -> Result<(), Error> {
+        if self.enabled {
+            Ok(())
+        } else {
+            Err(Error::from(InvalidDataError::TxCallbacksDisabled))
+        }
+    }
+
+    async fn scope(&self, http_request: &HttpRequest) -> Result<String, Error> {
+        self.check_enabled()?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        api_key_scope(&mut storage, http_request)
+            .await?
+            .ok_or_else(|| Error::from(InvalidDataError::InvalidApiKey))
+    }
+
+    async fn endpoint(&self, http_request: &HttpRequest) -> Result<TxCallbackEndpoint, Error> {
+        let scope = self.scope(http_request).await?;
+        let endpoint = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?
+            .tx_callbacks_schema()
+            .get_endpoint(&scope)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::TxCallbackEndpointNotFound))?;
+        Ok(api_endpoint(endpoint, false))
+    }
+
+    /// Registers the endpoint with the new secret, which is returned only once.
+    async fn set_endpoint(
+        &self,
+        http_request: &HttpRequest,
+        request: TxCallbackEndpointRequest,
+    ) -> Result<TxCallbackEndpoint, Error> {
+        let scope = self.scope(http_request).await?;
+        let url = reqwest::Url::parse(&request.url)
+            .map_err(|err| Error::from(InvalidDataError::InvalidTxCallbackUrl(err.to_string())))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(Error::from(InvalidDataError::InvalidTxCallbackUrl(
+                "only HTTP(S) endpoints are supported".to_string(),
+            )));
+        }
+
+        let secret: [u8; 32] = thread_rng().gen();
+        let endpoint = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?
+            .tx_callbacks_schema()
+            .set_endpoint(&scope, url.as_str(), &hex::encode(secret))
+            .await
+            .map_err(Error::storage)?;
+        Ok(api_endpoint(endpoint, true))
+    }
+
+    /// Removes the endpoint, the undelivered callbacks are dropped.
+    async fn remove_endpoint(&self, http_request: &HttpRequest) -> Result<(), Error> {
+        let scope = self.scope(http_request).await?;
+        let removed = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?
+            .tx_callbacks_schema()
+            .remove_endpoint(&scope)
+            .await
+            .map_err(Error::storage)?;
+        if removed {
+            Ok(())
+        } else {
+            Err(Error::from(InvalidDataError::TxCallbackEndpointNotFound))
+        }
+    }
+}
+
+// Server implementation
+
+async fn tx_callback_endpoint(
+    data: web::Data<ApiTxCallbackData>,
+    http_request: HttpRequest,
+) -> ApiResult<TxCallbackEndpoint> {
+    let start = Instant::now();
+    let res = data.endpoint(&http_request).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_callback_endpoint");
+    res
+}
+
+async fn set_tx_callback_endpoint(
+    data: web::Data<ApiTxCallbackData>,
+    http_request: HttpRequest,
+    Json(request): Json<TxCallbackEndpointRequest>,
+) -> ApiResult<TxCallbackEndpoint> {
+    let start = Instant::now();
+    let res = data.set_endpoint(&http_request, request).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "set_tx_callback_endpoint");
+    res
+}
+
+async fn remove_tx_callback_endpoint(
+    data: web::Data<ApiTxCallbackData>,
+    http_request: HttpRequest,
+) -> ApiResult<()> {
+    let start = Instant::now();
+    let res = data.remove_endpoint(&http_request).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "remove_tx_callback_endpoint");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool, enabled: bool) -> Scope {
+    let data = ApiTxCallbackData::new(pool, enabled);
+
+    web::scope("tx_callbacks")
+        .app_data(web::Data::new(data))
+        .route("", web::get().to(tx_callback_endpoint))
+        .route("", web::post().to(set_tx_callback_endpoint))
+        .route("remove", web::post().to(remove_tx_callback_endpoint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::{ApiVersion, Response};
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn tx_callbacks_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let api_key = "test-tx-callbacks-key";
+        cfg.pool
+            .access_storage()
+            .await?
+            .labels_schema()
+            .store_api_key(&tiny_keccak::keccak256(api_key.as_bytes()), "exchange")
+            .await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| api_scope(cfg.pool.clone(), true),
+            Some(shared_data),
+        );
+
+        let response: Response = client.tx_callback_endpoint(api_key).await?;
+        assert!(response.error.is_some());
+
+        let request = TxCallbackEndpointRequest {
+            url: "ftp://exchange.example".to_string(),
+        };
+        let response: Response = client.set_tx_callback_endpoint(&request, api_key).await?;
+        assert!(response.error.is_some());
+
+        let request = TxCallbackEndpointRequest {
+            url: "https://exchange.example/callbacks".to_string(),
+        };
+        let response: Response = client.set_tx_callback_endpoint(&request, "unknown").await?;
+        assert!(response.error.is_some());
+        let response = client.set_tx_callback_endpoint(&request, api_key).await?;
+        let registered: TxCallbackEndpoint = deserialize_response_result(response)?;
+        assert_eq!(registered.url, request.url);
+        assert_eq!(registered.secret.as_ref().map(String::len), Some(64));
+
+        // The secret is not returned after the registration.
+        let response = client.tx_callback_endpoint(api_key).await?;
+        let endpoint: TxCallbackEndpoint = deserialize_response_result(response)?;
+        assert_eq!(endpoint.url, request.url);
+        assert!(endpoint.secret.is_none());
+
+        let response = client.remove_tx_callback_endpoint(api_key).await?;
+        let _: () = deserialize_response_result(response)?;
+        let response: Response = client.remove_tx_callback_endpoint(api_key).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
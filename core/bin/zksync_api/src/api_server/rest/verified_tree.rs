@@ -0,0 +1,145 @@
+//! Account tree of the last verified block, kept in memory to serve the Merkle proofs
+//! of the account balances.
+
+// Built-in uses
+use std::sync::Arc;
+use std::time::Duration;
+
+// External uses
+use futures::channel::mpsc;
+use tokio::sync::{RwLock, RwLockReadGuard};
+use tokio::{runtime::Runtime, time};
+
+// Workspace uses
+use zksync_crypto::{convert::FeConvert, params::account_tree_depth, Fr};
+use zksync_storage::ConnectionPool;
+use zksync_types::{Account, AccountMap, AccountTree, AccountUpdates, BlockNumber};
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+#[derive(Debug)]
+pub struct VerifiedTree {
+    pub block_number: BlockNumber,
+    pub tree: AccountTree,
+}
+
+impl VerifiedTree {
+    pub fn new(block_number: BlockNumber, accounts: AccountMap) -> Self {
+        let mut tree = AccountTree::new(account_tree_depth());
+        for (account_id, account) in accounts {
+            tree.insert(*account_id, account);
+        }
+        Self { block_number, tree }
+    }
+
+    fn apply_updates(&mut self, block_number: BlockNumber, updates: AccountUpdates) {
+        for (account_id, update) in updates {
+            let account = self.tree.remove(*account_id);
+            if let Some(account) = Account::apply_update(account, update) {
+                self.tree.insert(*account_id, account);
+            }
+        }
+        self.block_number = block_number;
+    }
+}
+
+/// Verified account tree shared between the API server workers.
+/// It's `None` until the tree is loaded from the database.
+#[derive(Debug, Clone, Default)]
+pub struct SharedVerifiedTree(Arc<RwLock<Option<VerifiedTree>>>);
+
+impl SharedVerifiedTree {
+    #[cfg(test)]
+    pub fn new(verified_tree: VerifiedTree) -> Self {
+        Self(Arc::new(RwLock::new(Some(verified_tree))))
+    }
+
+    pub async fn read(&self) -> RwLockReadGuard<'_, Option<VerifiedTree>> {
+        self.0.read().await
+    }
+
+    /// Brings the tree to the last verified block. The tree is reloaded from scratch
+    /// if its root hash doesn't match the one stored for the block.
+    async fn update(&self, connection_pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = connection_pool.access_storage().await?;
+        let current_block = self.read().await.as_ref().map(|tree| tree.block_number);
+
+        let block_number = match current_block {
+            Some(current_block) => {
+                let last_verified = storage
+                    .chain()
+                    .block_schema()
+                    .get_last_verified_confirmed_block()
+                    .await?;
+                if last_verified <= current_block {
+                    return Ok(());
+                }
+                let updates = storage
+                    .chain()
+                    .state_schema()
+                    .load_state_diff(current_block, Some(last_verified))
+                    .await?
+                    .map(|(_, updates)| updates)
+                    .unwrap_or_default();
+
+                let mut verified_tree = self.0.write().await;
+                let verified_tree = verified_tree.as_mut().expect("tree is loaded");
+                verified_tree.apply_updates(last_verified, updates);
+                last_verified
+            }
+            None => {
+                let (block_number, accounts) =
+                    storage.chain().state_schema().load_verified_state().await?;
+                *self.0.write().await = Some(VerifiedTree::new(block_number, accounts));
+                block_number
+            }
+        };
+
+        let expected_root_hash = match storage
+            .chain()
+            .block_schema()
+            .get_storage_block(block_number)
+            .await?
+        {
+            Some(block) => Fr::from_bytes(&block.root_hash)?,
+            // There are no blocks yet, the tree is empty.
+            None => return Ok(()),
+        };
+        let root_hash = self
+            .read()
+            .await
+            .as_ref()
+            .map(|verified_tree| verified_tree.tree.root_hash());
+        if root_hash != Some(expected_root_hash) {
+            *self.0.write().await = None;
+            anyhow::bail!("root hash of the block {} doesn't match", block_number);
+        }
+        Ok(())
+    }
+
+    pub fn start_updater_detached(
+        self,
+        panic_notify: mpsc::Sender<bool>,
+        connection_pool: ConnectionPool,
+        update_interval: Duration,
+    ) {
+        std::thread::Builder::new()
+            .name("rest-verified-tree-updater".to_string())
+            .spawn(move || {
+                let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+
+                let runtime = Runtime::new().expect("tokio runtime creation");
+
+                let tree_update_task = async move {
+                    let mut timer = time::interval(update_interval);
+                    loop {
+                        timer.tick().await;
+                        if let Err(err) = self.update(&connection_pool).await {
+                            vlog::error!("Can't update the verified account tree: {}", err);
+                        }
+                    }
+                };
+                runtime.block_on(tree_update_task);
+            })
+            .expect("Verified tree update thread");
+    }
+}
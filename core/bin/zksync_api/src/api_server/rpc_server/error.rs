@@ -22,6 +22,16 @@ pub enum RpcErrorCodes {
     OperationsLimitReached = 302,
     UnsupportedFastProcessing = 303,
     Toggle2FA = 304,
+    MaintenanceMode = 305,
+    WithdrawalPolicy = 306,
+    OrderBook = 307,
+    StandingOrder = 308,
+    SessionKey = 309,
+    CoSignedTx = 310,
+    Attestation = 311,
+    NonceReservation = 312,
+    Screening = 313,
+    AlreadyKnown = 314,
 }
 
 impl From<TxAddError> for RpcErrorCodes {
@@ -87,6 +97,56 @@ impl From<SubmitError> for jsonrpc_core::Error {
                 message: inner.to_string(),
                 data: None,
             },
+            SubmitError::MaintenanceMode(_) => Self {
+                code: RpcErrorCodes::MaintenanceMode.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::WithdrawalPolicy(_) => Self {
+                code: RpcErrorCodes::WithdrawalPolicy.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::OrderBook(_) => Self {
+                code: RpcErrorCodes::OrderBook.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::StandingOrder(_) => Self {
+                code: RpcErrorCodes::StandingOrder.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::SessionKey(_) => Self {
+                code: RpcErrorCodes::SessionKey.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::CoSignedTx(_) => Self {
+                code: RpcErrorCodes::CoSignedTx.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::Attestation(_) => Self {
+                code: RpcErrorCodes::Attestation.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::NonceReservation(_) => Self {
+                code: RpcErrorCodes::NonceReservation.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::Screening(ref rejection) => Self {
+                code: RpcErrorCodes::Screening.into(),
+                message: inner.to_string(),
+                data: serde_json::to_value(rejection).ok(),
+            },
+            SubmitError::AlreadyKnown(ref known_tx) => Self {
+                code: RpcErrorCodes::AlreadyKnown.into(),
+                message: inner.to_string(),
+                data: serde_json::to_value(known_tx).ok(),
+            },
             SubmitError::MempoolCommunication(reason) => Self {
                 code: RpcErrorCodes::Other.into(),
                 message: "Error communicating core server".to_string(),
@@ -169,7 +169,7 @@ impl RpcApp {
                 })?;
 
             if let Some(tx_receipt) = tx_receipt.clone() {
-                if tx_receipt.verified {
+                if tx_receipt.status().is_verified() {
                     self.cache_of_transaction_receipts
                         .insert(tx_hash.as_ref().to_vec(), tx_receipt)
                         .await;
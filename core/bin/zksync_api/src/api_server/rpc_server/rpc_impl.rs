@@ -5,6 +5,7 @@ use bigdecimal::BigDecimal;
 use jsonrpc_core::{Error, Result};
 // Workspace uses
 use zksync_api_types::{
+    tx_status::TxStatus,
     v02::{
         fee::ApiTxFeeTypes,
         token::ApiNFT,
@@ -88,19 +89,13 @@ impl RpcApp {
         let executed_op = self.get_executed_priority_operation(serial_id).await?;
         let result = if let Some(executed_op) = executed_op {
             let block = self.get_block_info(executed_op.block_number).await?;
-            ETHOpInfoResp {
-                executed: true,
-                block: Some(BlockInfo {
-                    block_number: executed_op.block_number,
-                    committed: true,
-                    verified: block.map(|b| b.verified_at.is_some()).unwrap_or_default(),
-                }),
-            }
+            let block_finalized = block.map(|b| b.verified_at.is_some()).unwrap_or_default();
+            ETHOpInfoResp::new(
+                TxStatus::executed(true, block_finalized),
+                Some(executed_op.block_number),
+            )
         } else {
-            ETHOpInfoResp {
-                executed: false,
-                block: None,
-            }
+            ETHOpInfoResp::new(TxStatus::Queued, None)
         };
 
         metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "ethop_info");
@@ -116,23 +111,13 @@ impl RpcApp {
         let stored_receipt = self.get_tx_receipt(tx_hash).await?;
         metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "tx_info");
         Ok(if let Some(stored_receipt) = stored_receipt {
-            TransactionInfoResp {
-                executed: true,
-                success: Some(stored_receipt.success),
-                fail_reason: stored_receipt.fail_reason,
-                block: Some(BlockInfo {
-                    block_number: stored_receipt.block_number,
-                    committed: true,
-                    verified: stored_receipt.verified,
-                }),
-            }
+            TransactionInfoResp::new(
+                stored_receipt.status(),
+                Some(stored_receipt.block_number),
+                stored_receipt.fail_reason,
+            )
         } else {
-            TransactionInfoResp {
-                executed: false,
-                success: None,
-                fail_reason: None,
-                block: None,
-            }
+            TransactionInfoResp::new(TxStatus::Queued, None, None)
         })
     }
 
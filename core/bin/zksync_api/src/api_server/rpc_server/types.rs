@@ -5,9 +5,12 @@ use jsonrpc_core::{Error, Result};
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
-use zksync_api_types::v02::{
-    account::{DepositingAccountBalances, EthAccountType},
-    token::NFT,
+use zksync_api_types::{
+    tx_status::TxStatus,
+    v02::{
+        account::{DepositingAccountBalances, EthAccountType},
+        token::NFT,
+    },
 };
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
 use zksync_storage::StorageProcessor;
@@ -112,6 +115,28 @@ pub struct TransactionInfoResp {
     pub block: Option<BlockInfo>,
 }
 
+impl BlockInfo {
+    pub fn new(block_number: i64, status: TxStatus) -> Self {
+        Self {
+            block_number,
+            committed: status.is_committed(),
+            verified: status.is_verified(),
+        }
+    }
+}
+
+impl TransactionInfoResp {
+    /// Builds the response for the transaction, `block_number` is `None` until it's executed.
+    pub fn new(status: TxStatus, block_number: Option<i64>, fail_reason: Option<String>) -> Self {
+        Self {
+            executed: status.is_committed(),
+            success: status.success(),
+            fail_reason,
+            block: block_number.map(|block_number| BlockInfo::new(block_number, status)),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ETHOpInfoResp {
@@ -119,6 +144,17 @@ pub struct ETHOpInfoResp {
     pub block: Option<BlockInfo>,
 }
 
+impl ETHOpInfoResp {
+    /// Builds the response for the priority operation, `block_number` is `None` until
+    /// it's executed.
+    pub fn new(status: TxStatus, block_number: Option<i64>) -> Self {
+        Self {
+            executed: status.is_committed(),
+            block: block_number.map(|block_number| BlockInfo::new(block_number, status)),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ContractAddressResp {
@@ -0,0 +1,375 @@
+//! Screening of the senders and the recipients of the transactions on the submission,
+//! e.g. against the sanctions lists, required by the regulated operators.
+//!
+//! Addresses are checked against the denylist maintained by the operator through the admin CLI
+//! and then by the external screening service. Transaction matched by either of them is rejected
+//! with the structured error, and the match is stored to the database for the audit.
+
+// Built-in uses
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+
+// External uses
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_config::configs::api::CommonApiConfig;
+use zksync_storage::{screening::records::ScreeningMatch, StorageProcessor};
+use zksync_types::{tx::TxHash, Address, ZkSyncTx};
+
+// Local uses
+use crate::api_server::tx_sender::SubmitError;
+
+/// Role of the screened address in the transaction.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreeningRole {
+    Sender,
+    Recipient,
+}
+
+impl ScreeningRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sender => "sender",
+            Self::Recipient => "recipient",
+        }
+    }
+}
+
+/// Source of the screening match.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreeningSource {
+    Denylist,
+    ScreeningApi,
+}
+
+impl ScreeningSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Denylist => "denylist",
+            Self::ScreeningApi => "screening_api",
+        }
+    }
+}
+
+/// Reason of the transaction rejection, returned to the user.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreeningRejection {
+    pub tx_hash: TxHash,
+    pub address: Address,
+    pub role: ScreeningRole,
+    pub source: ScreeningSource,
+    pub reason: String,
+}
+
+impl Display for ScreeningRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:?} is denied by the {}: {}",
+            self.role.as_str(),
+            self.address,
+            self.source.as_str(),
+            self.reason
+        )
+    }
+}
+
+/// Request body of the screening service.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScreeningApiRequest {
+    tx_hash: TxHash,
+    address: Address,
+    role: ScreeningRole,
+}
+
+/// Response of the screening service. The reason is returned to the user if the address is denied.
+#[derive(Debug, Deserialize)]
+struct ScreeningApiResponse {
+    allowed: bool,
+    reason: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct ScreeningApi {
+    url: String,
+    /// Whether the transactions are accepted if the service is not available.
+    fail_open: bool,
+    client: reqwest::Client,
+}
+
+#[derive(Debug, Clone)]
+pub struct Screening {
+    enabled: bool,
+    api: Option<ScreeningApi>,
+}
+
+impl Screening {
+    pub fn new(config: &CommonApiConfig) -> Self {
+        let api = config.screening_api_url.clone().map(|url| ScreeningApi {
+            url,
+            fail_open: config.screening_api_fail_open,
+            client: reqwest::Client::builder()
+                .timeout(config.screening_api_timeout())
+                .build()
+                .expect("failed to build the screening service client"),
+        });
+
+        Self {
+            enabled: config.screening_enabled,
+            api,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Screens the addresses of the single transaction or the batch and stores the matches.
+    /// Transactions of the batch are rejected all together if any of them is matched.
+    pub async fn screen_txs<'t>(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        txs: impl Iterator<Item = &'t ZkSyncTx>,
+    ) -> Result<(), SubmitError> {
+        let mut screened = Vec::new();
+        for tx in txs {
+            let tx_hash = tx.hash();
+            screened.extend(
+                screened_addresses(tx)
+                    .into_iter()
+                    .map(|(address, role)| (tx_hash, address, role)),
+            );
+        }
+        if screened.is_empty() {
+            return Ok(());
+        }
+
+        let addresses: Vec<_> = screened
+            .iter()
+            .map(|(_, address, _)| *address)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let denylisted: HashMap<_, _> = storage
+            .screening_schema()
+            .get_denylisted(&addresses)
+            .await
+            .map_err(SubmitError::internal)?
+            .into_iter()
+            .map(|entry| (Address::from_slice(&entry.address), entry.reason))
+            .collect();
+
+        let mut rejections: Vec<_> = screened
+            .iter()
+            .filter_map(|(tx_hash, address, role)| {
+                denylisted.get(address).map(|reason| ScreeningRejection {
+                    tx_hash: *tx_hash,
+                    address: *address,
+                    role: *role,
+                    source: ScreeningSource::Denylist,
+                    reason: reason.clone(),
+                })
+            })
+            .collect();
+        // The screening service is only asked about the addresses not denied by the operator.
+        if rejections.is_empty() {
+            let mut checked = HashSet::new();
+            for (tx_hash, address, role) in &screened {
+                if !checked.insert((*address, *role)) {
+                    continue;
+                }
+                if let (false, reason) = self.call_api(*tx_hash, *address, *role).await {
+                    rejections.push(ScreeningRejection {
+                        tx_hash: *tx_hash,
+                        address: *address,
+                        role: *role,
+                        source: ScreeningSource::ScreeningApi,
+                        reason,
+                    });
+                    break;
+                }
+            }
+        }
+        if rejections.is_empty() {
+            return Ok(());
+        }
+
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(SubmitError::internal)?;
+        for rejection in &rejections {
+            let screening_match = ScreeningMatch {
+                tx_hash: rejection.tx_hash,
+                address: rejection.address,
+                role: rejection.role.as_str().to_string(),
+                source: rejection.source.as_str().to_string(),
+                reason: rejection.reason.clone(),
+            };
+            transaction
+                .screening_schema()
+                .store_match(&screening_match)
+                .await
+                .map_err(SubmitError::internal)?;
+        }
+        transaction.commit().await.map_err(SubmitError::internal)?;
+
+        metrics::increment_counter!("screening.rejected_txs", "source" => rejections[0].source.as_str());
+        Err(SubmitError::Screening(rejections.swap_remove(0)))
+    }
+
+    /// Asks the screening service whether the address is allowed, returns the decision
+    /// and the reason of the rejection.
+    async fn call_api(
+        &self,
+        tx_hash: TxHash,
+        address: Address,
+        role: ScreeningRole,
+    ) -> (bool, String) {
+        let api = match &self.api {
+            Some(api) => api,
+            None => return (true, String::new()),
+        };
+
+        let request = ScreeningApiRequest {
+            tx_hash,
+            address,
+            role,
+        };
+        let response = async {
+            api.client
+                .post(&api.url)
+                .json(&request)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<ScreeningApiResponse>()
+                .await
+        }
+        .await;
+
+        match response {
+            Ok(response) if response.allowed => (true, String::new()),
+            Ok(response) => (
+                false,
+                response
+                    .reason
+                    .unwrap_or_else(|| String::from("address is not allowed")),
+            ),
+            Err(err) => {
+                vlog::warn!("Screening service is not available: {}", err);
+                metrics::increment_counter!("screening.api_failures");
+                (
+                    api.fail_open,
+                    String::from("screening service is not available"),
+                )
+            }
+        }
+    }
+}
+
+/// Returns the addresses of the transaction to be screened along with their roles.
+fn screened_addresses(tx: &ZkSyncTx) -> Vec<(Address, ScreeningRole)> {
+    let (sender, recipients) = match tx {
+        ZkSyncTx::Transfer(tx) => (Some(tx.from), vec![tx.to]),
+        ZkSyncTx::Withdraw(tx) => (Some(tx.from), vec![tx.to]),
+        ZkSyncTx::WithdrawNFT(tx) => (Some(tx.from), vec![tx.to]),
+        ZkSyncTx::MintNFT(tx) => (Some(tx.creator_address), vec![tx.recipient]),
+        // The initiator of the forced exit is only known by the account ID.
+        ZkSyncTx::ForcedExit(tx) => (None, vec![tx.target]),
+        ZkSyncTx::Swap(tx) => (
+            Some(tx.submitter_address),
+            vec![tx.orders.0.recipient_address, tx.orders.1.recipient_address],
+        ),
+        ZkSyncTx::ChangePubKey(tx) => (Some(tx.account), Vec::new()),
+        ZkSyncTx::Close(tx) => (Some(tx.account), Vec::new()),
+    };
+
+    let mut addresses: Vec<_> = sender
+        .into_iter()
+        .map(|address| (address, ScreeningRole::Sender))
+        .collect();
+    for recipient in recipients {
+        if Some(recipient) != sender && !addresses.iter().any(|(address, _)| *address == recipient)
+        {
+            addresses.push((recipient, ScreeningRole::Recipient));
+        }
+    }
+    addresses
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use zksync_types::{tx::Transfer, AccountId, Nonce, TokenId};
+
+    use super::*;
+
+    fn screening_with_api(fail_open: bool) -> Screening {
+        Screening {
+            enabled: true,
+            api: Some(ScreeningApi {
+                // Nothing is expected to listen on this port.
+                url: String::from("http://127.0.0.1:1/screen"),
+                fail_open,
+                client: reqwest::Client::builder()
+                    .timeout(Duration::from_secs(1))
+                    .build()
+                    .unwrap(),
+            }),
+        }
+    }
+
+    #[test]
+    fn transfer_addresses() {
+        let (from, to) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let transfer = |to| {
+            ZkSyncTx::Transfer(Box::new(Transfer::new(
+                AccountId(1),
+                from,
+                to,
+                TokenId(0),
+                500u32.into(),
+                20u32.into(),
+                Nonce(0),
+                Default::default(),
+                None,
+            )))
+        };
+
+        assert_eq!(
+            screened_addresses(&transfer(to)),
+            vec![
+                (from, ScreeningRole::Sender),
+                (to, ScreeningRole::Recipient)
+            ]
+        );
+        // Transfers to self are screened once.
+        assert_eq!(
+            screened_addresses(&transfer(from)),
+            vec![(from, ScreeningRole::Sender)]
+        );
+    }
+
+    #[tokio::test]
+    async fn unavailable_api() {
+        let (tx_hash, address) = (TxHash::default(), Address::repeat_byte(1));
+
+        let (allowed, _) = screening_with_api(true)
+            .call_api(tx_hash, address, ScreeningRole::Sender)
+            .await;
+        assert!(allowed);
+
+        let (allowed, reason) = screening_with_api(false)
+            .call_api(tx_hash, address, ScreeningRole::Sender)
+            .await;
+        assert!(!allowed);
+        assert_eq!(reason, "screening service is not available");
+    }
+}
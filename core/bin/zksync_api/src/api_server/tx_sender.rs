@@ -10,7 +10,7 @@ use std::{
 
 // External uses
 use bigdecimal::BigDecimal;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use futures::{
     channel::{mpsc, oneshot},
     prelude::*,
@@ -19,36 +19,61 @@ use itertools::izip;
 use num::rational::Ratio;
 use num::{bigint::ToBigInt, BigUint, Zero};
 use thiserror::Error;
+use tracing::{field, Span};
 
 // Workspace uses
 use zksync_api_types::{
-    v02::transaction::{SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, TxHashSerializeWrapper},
+    v02::{
+        attestation::AddressAttestation,
+        cosigned_tx::{IncomingCoSignature, IncomingCoSignedTx},
+        nonce_reservation::NonceReservation,
+        session_key::{SessionKeyRegistration, SessionKeyRevocation, SessionTxType},
+        standing_order::{IncomingStandingOrder, IncomingStandingOrderCancellation},
+        transaction::{
+            KnownTx, SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, TxHashSerializeWrapper,
+        },
+    },
     TxWithSignature,
 };
+use zksync_storage::eip1271_verifications::records::EIP1271Verification;
+use zksync_storage::fee_breakdowns::records::TxFeeQuote;
 use zksync_storage::misc::records::Subsidy;
+use zksync_storage::nonce_reservations::records::StoredNonceReservation;
+use zksync_storage::sponsorship::records::{CpkSponsorship, FeeFreeTransfer};
+use zksync_storage::tx_memos::records::TxMemo;
+use zksync_storage::withdrawal_hooks::records::NewWithdrawalHook;
 use zksync_storage::{chain::account::records::EthAccountType, ConnectionPool};
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
     tx::{
-        EthBatchSignData, EthBatchSignatures, EthSignData, Order, SignedZkSyncTx, TxEthSignature,
-        TxEthSignatureVariant, TxHash,
+        ChangePubKeyEthAuthData, EthBatchSignData, EthBatchSignatures, EthSignData, Order,
+        SignedZkSyncTx, TxEthSignature, TxEthSignatureVariant, TxHash, TxSignature,
     },
     AccountId, Address, ChainId, PubKeyHash, Token, TokenId, TokenLike, TxFeeTypes, ZkSyncTx, H160,
+    H256,
 };
 use zksync_utils::{
     big_decimal_to_ratio, biguint_to_big_decimal, ratio_to_scaled_u64, scaled_big_decimal_to_ratio,
+    scaled_u64_to_ratio,
 };
 
 // Local uses
 use crate::{
-    api_server::forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+    api_server::{
+        forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+        screening::{Screening, ScreeningRejection},
+        withdrawal_policy::{PolicyWithdrawal, WithdrawalPolicy},
+    },
     fee_ticker::{ResponseBatchFee, ResponseFee, TokenPriceRequestType},
     signature_checker::{
-        BatchRequest, OrderRequest, RequestData, Toggle2FARequest, TxRequest, VerifiedTx,
-        VerifySignatureRequest,
+        AttestationRequest, BatchRequest, NonceReservationRequest, OrderRequest, RequestData,
+        SessionKeyRequest, Toggle2FARequest, TxRequest, VerifiedTx, VerifySignatureRequest,
     },
     tx_error::Toggle2FAError,
-    utils::block_details_cache::BlockDetailsCache,
+    utils::{
+        block_details_cache::BlockDetailsCache, finalized_cache::FinalizedCache,
+        maintenance_mode::MaintenanceModeCache,
+    },
 };
 use zksync_config::configs::api::{CommonApiConfig, TokenConfig};
 use zksync_mempool::MempoolTransactionRequest;
@@ -58,6 +83,24 @@ use super::rpc_server::types::RequestMetadata;
 use crate::fee_ticker::{FeeTicker, PriceError};
 
 const VALIDNESS_INTERVAL_MINUTES: i64 = 40;
+/// Limits of the standing orders schedule.
+const MAX_STANDING_ORDER_PAYMENTS: usize = 120;
+const MIN_STANDING_ORDER_INTERVAL_SECS: u64 = 60 * 60;
+const MAX_STANDING_ORDER_INTERVAL_SECS: u64 = 366 * 24 * 60 * 60;
+/// Maximum number of the tokens the session key can spend.
+const MAX_SESSION_KEY_LIMITS: usize = 16;
+/// Maximum length of the memo attached to the transfer, in bytes.
+const MAX_TX_MEMO_LENGTH: usize = 256;
+/// Maximum size of the calldata attached to the withdrawal, in bytes.
+const MAX_WITHDRAWAL_HOOK_CALLDATA_SIZE: usize = 4096;
+/// Limits of the co-signing policy of the held transactions.
+const MAX_COSIGNERS: usize = 16;
+const MAX_COSIGNED_TX_TTL_HOURS: i64 = 7 * 24;
+/// Maximum length of the reference attached to the address attestation, in bytes.
+const MAX_ATTESTATION_REFERENCE_LENGTH: usize = 256;
+/// Limits of the nonce reservations.
+const MAX_RESERVED_NONCES: u32 = 1000;
+const MAX_NONCE_RESERVATION_TTL_SECS: u64 = 60 * 60;
 
 #[derive(Clone)]
 pub struct TxSender {
@@ -70,6 +113,14 @@ pub struct TxSender {
 
     pub forced_exit_checker: ForcedExitChecker,
     pub blocks: BlockDetailsCache,
+    /// Cache of the immutable API results, e.g. receipts of the finalized transactions.
+    pub finalized_cache: FinalizedCache,
+    /// Transactions are rejected while the maintenance mode is enabled.
+    pub maintenance_mode: MaintenanceModeCache,
+    /// Policies applied to the withdrawals, e.g. the daily withdrawal limits.
+    pub withdrawal_policy: WithdrawalPolicy,
+    /// Screening of the senders and the recipients, e.g. against the sanctions lists.
+    pub screening: Screening,
     /// List of account IDs that do not have to pay fees for operations.
     pub fee_free_accounts: HashSet<AccountId>,
     pub enforce_pubkey_change_fee: bool,
@@ -80,6 +131,33 @@ pub struct TxSender {
     pub current_subsidy_type: String,
     pub max_subsidy_usd: Ratio<BigUint>,
     pub subsidized_ips: HashSet<String>,
+
+    /// Whether the operator pays the `ChangePubKey` fee for the new accounts.
+    pub cpk_sponsorship_enabled: bool,
+    pub cpk_sponsorship_min_deposit_usd: Ratio<BigUint>,
+    pub cpk_sponsorship_max_total_usd: Ratio<BigUint>,
+    /// Operator addresses allowed to submit the transfers without the fee.
+    pub fee_free_transfer_addresses: HashSet<Address>,
+    pub fee_free_transfer_daily_quota: u64,
+    /// Whether the signed swap orders are accepted into the order book.
+    pub order_book_enabled: bool,
+    /// Whether the users can register the standing orders.
+    pub standing_orders_enabled: bool,
+    /// Whether the users can register the session keys.
+    pub session_keys_enabled: bool,
+    /// Whether the transactions can be held until they are co-signed.
+    pub cosigned_txs_enabled: bool,
+    /// Whether the users can attest the control of their addresses.
+    pub address_attestations_enabled: bool,
+    /// Whether the accounts can reserve the ranges of their nonces.
+    pub nonce_reservations_enabled: bool,
+    /// Whether the CREATE2 `ChangePubKey` transactions are accepted only for the registered
+    /// factory and code hash pairs.
+    pub create2_factory_registry_enabled: bool,
+    /// Whether the withdrawals can carry the calldata of the call made to the recipient on L1.
+    pub withdrawal_hooks_enabled: bool,
+    /// Gas of the call made to the recipient, paid by the withdrawal.
+    pub withdrawal_hook_gas_limit: u64,
     pub chain_id: ChainId,
 }
 
@@ -97,6 +175,30 @@ pub enum SubmitError {
     TxAdd(#[from] TxAddError),
     #[error("Chosen token is not suitable for paying fees.")]
     InappropriateFeeToken,
+    #[error("Transactions are not accepted during the maintenance: {0}.")]
+    MaintenanceMode(String),
+    #[error("Withdrawal is rejected by the policy: {0}.")]
+    WithdrawalPolicy(String),
+    #[error("Order book error: {0}.")]
+    OrderBook(String),
+    #[error("Standing order error: {0}.")]
+    StandingOrder(String),
+    #[error("Session key error: {0}.")]
+    SessionKey(String),
+    #[error("Co-signed transaction error: {0}.")]
+    CoSignedTx(String),
+    #[error("Address attestation error: {0}.")]
+    Attestation(String),
+    #[error("Nonce reservation error: {0}.")]
+    NonceReservation(String),
+    #[error("Transaction is rejected by the screening: {0}.")]
+    Screening(ScreeningRejection),
+    #[error(
+        "Transaction {} is already known, its status is {:?}.",
+        .0.tx_hash,
+        .0.status
+    )]
+    AlreadyKnown(KnownTx),
     // Not all TxAddErrors would apply to Toggle2FA, but
     // it is helpful to re-use IncorrectEthSignature and DbError
     #[error("Failed to toggle 2FA: {0}.")]
@@ -156,6 +258,13 @@ impl TxSender {
             config.max_number_of_transactions_per_batch as usize;
         let max_number_of_authors_per_batch = config.max_number_of_authors_per_batch as usize;
 
+        let finalized_cache = FinalizedCache::new(
+            connection_pool.clone(),
+            config.caches_size,
+            config.finalized_cache_poll_interval(),
+            config.cache_redis_url.as_deref(),
+        );
+
         Self {
             mempool_tx_sender,
             pool: connection_pool,
@@ -167,6 +276,10 @@ impl TxSender {
             ),
             enforce_pubkey_change_fee: config.enforce_pubkey_change_fee,
             blocks: BlockDetailsCache::new(config.caches_size),
+            finalized_cache,
+            maintenance_mode: MaintenanceModeCache::new(),
+            withdrawal_policy: WithdrawalPolicy::new(config),
+            screening: Screening::new(config),
 
             fee_free_accounts: HashSet::from_iter(config.fee_free_accounts.clone()),
             max_number_of_transactions_per_batch,
@@ -174,6 +287,22 @@ impl TxSender {
             current_subsidy_type: config.subsidy_name.clone(),
             max_subsidy_usd: config.max_subsidy_usd(),
             subsidized_ips: config.subsidized_ips.clone().into_iter().collect(),
+            cpk_sponsorship_enabled: config.cpk_sponsorship_enabled,
+            cpk_sponsorship_min_deposit_usd: config.cpk_sponsorship_min_deposit_usd(),
+            cpk_sponsorship_max_total_usd: config.cpk_sponsorship_max_total_usd(),
+            fee_free_transfer_addresses: HashSet::from_iter(
+                config.fee_free_transfer_addresses.clone(),
+            ),
+            fee_free_transfer_daily_quota: config.fee_free_transfer_daily_quota,
+            order_book_enabled: config.order_book_enabled,
+            standing_orders_enabled: config.standing_orders_enabled,
+            session_keys_enabled: config.session_keys_enabled,
+            cosigned_txs_enabled: config.cosigned_txs_enabled,
+            address_attestations_enabled: config.address_attestations_enabled,
+            nonce_reservations_enabled: config.nonce_reservations_enabled,
+            create2_factory_registry_enabled: config.create2_factory_registry_enabled,
+            withdrawal_hooks_enabled: config.withdrawal_hooks_enabled,
+            withdrawal_hook_gas_limit: config.withdrawal_hook_gas_limit,
             chain_id,
         }
     }
@@ -198,6 +327,133 @@ impl TxSender {
             .ok_or_else(|| anyhow::anyhow!("Order signer account id not found in db"))
     }
 
+    /// Rejects the transactions while the maintenance mode is enabled.
+    async fn check_maintenance_mode(&self) -> Result<(), SubmitError> {
+        let reason = self
+            .maintenance_mode
+            .get(&self.pool)
+            .await
+            .map_err(SubmitError::internal)?;
+        match reason {
+            Some(reason) => Err(SubmitError::MaintenanceMode(reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// Rejects the transaction which is already queued or executed, reporting its status
+    /// instead of letting it fail later on the nonce check.
+    async fn check_known_txs(
+        &self,
+        tx_hashes: impl Iterator<Item = TxHash>,
+    ) -> Result<(), SubmitError> {
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        for tx_hash in tx_hashes {
+            let known_tx = storage
+                .chain()
+                .operations_ext_schema()
+                .known_tx(tx_hash)
+                .await
+                .map_err(SubmitError::internal)?;
+            if let Some((status, created_at)) = known_tx {
+                return Err(SubmitError::AlreadyKnown(KnownTx {
+                    tx_hash,
+                    status,
+                    created_at,
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the withdrawals of the transaction or the batch against the withdrawal policies.
+    /// Withdrawals of the tokens without the known price are rejected if the policies are enabled.
+    ///
+    /// `ForcedExit` is checked as the withdrawal of the whole committed balance of the target
+    /// to the same address on L1.
+    async fn check_withdrawal_policy<'t>(
+        &self,
+        txs: impl Iterator<Item = &'t ZkSyncTx>,
+    ) -> Result<(), SubmitError> {
+        if !self.withdrawal_policy.is_enabled() {
+            return Ok(());
+        }
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let mut withdrawals = Vec::new();
+        for tx in txs {
+            let (from, to, token, amount) = match tx {
+                ZkSyncTx::Withdraw(withdraw) => (
+                    withdraw.from,
+                    withdraw.to,
+                    withdraw.token,
+                    withdraw.amount.clone(),
+                ),
+                ZkSyncTx::ForcedExit(forced_exit) => {
+                    let amount = storage
+                        .chain()
+                        .account_schema()
+                        .account_state_by_address(forced_exit.target)
+                        .await
+                        .map_err(SubmitError::internal)?
+                        .committed
+                        .map(|(_, account)| account.get_balance(forced_exit.token))
+                        .unwrap_or_default();
+                    (
+                        forced_exit.target,
+                        forced_exit.target,
+                        forced_exit.token,
+                        amount,
+                    )
+                }
+                _ => continue,
+            };
+            let token_price_in_usd = self
+                .ticker
+                .get_token_price(TokenLike::Id(token), TokenPriceRequestType::USDForOneWei)
+                .await?;
+            withdrawals.push(PolicyWithdrawal {
+                tx_hash: tx.hash(),
+                from,
+                to,
+                token,
+                amount_usd: big_decimal_to_ratio(&token_price_in_usd)? * &amount,
+                amount,
+            });
+        }
+        if withdrawals.is_empty() {
+            return Ok(());
+        }
+
+        self.withdrawal_policy
+            .check_withdrawals(&mut storage, &withdrawals)
+            .await
+    }
+
+    /// Screens the senders and the recipients of the transaction or the batch.
+    async fn screen_txs<'t>(
+        &self,
+        txs: impl Iterator<Item = &'t ZkSyncTx>,
+    ) -> Result<(), SubmitError> {
+        if !self.screening.is_enabled() {
+            return Ok(());
+        }
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        self.screening.screen_txs(&mut storage, txs).await
+    }
+
     async fn get_tx_sender_type(&self, tx: &ZkSyncTx) -> Result<EthAccountType, SubmitError> {
         self.get_sender_type(tx.account_id().or(Err(SubmitError::AccountCloseDisabled))?)
             .await
@@ -321,40 +577,953 @@ impl TxSender {
             // We don't verify signatures for accounts with no 2FA
             return Ok(());
         }
-        if let EthAccountType::No2FA(Some(unchecked_hash)) = signer_type {
-            let order_pub_key_hash = PubKeyHash::from_pubkey(&order.signature.pub_key.0);
-            // We don't scheck the signature only if the order was signed with the same
-            // is the same as unchecked PubKey
-            if order_pub_key_hash == unchecked_hash {
-                return Ok(());
-            }
+        if let EthAccountType::No2FA(Some(unchecked_hash)) = signer_type {
+            let order_pub_key_hash = PubKeyHash::from_pubkey(&order.signature.pub_key.0);
+            // We don't scheck the signature only if the order was signed with the same
+            // is the same as unchecked PubKey
+            if order_pub_key_hash == unchecked_hash {
+                return Ok(());
+            }
+        }
+
+        let signature = signature.ok_or(SubmitError::TxAdd(TxAddError::MissingEthSignature))?;
+        let signer = self
+            .get_address_by_id(order.account_id)
+            .await
+            .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
+
+        let token_sell = self.token_info_from_id(order.token_sell).await?;
+        let token_buy = self.token_info_from_id(order.token_buy).await?;
+        let message = order
+            .get_ethereum_sign_message(&token_sell.symbol, &token_buy.symbol, token_sell.decimals)
+            .into_bytes();
+        let eth_sign_data = EthSignData { signature, message };
+        let (sender, receiever) = oneshot::channel();
+
+        let request = VerifySignatureRequest {
+            data: RequestData::Order(OrderRequest {
+                order: Box::new(order.clone()),
+                sign_data: eth_sign_data,
+                sender: signer,
+            }),
+            response: sender,
+        };
+
+        send_verify_request_and_recv(request, self.sign_verify_requests.clone(), receiever).await?;
+        Ok(())
+    }
+
+    /// Submits the signed swap order to the order book, so it can be matched by the
+    /// order-matching service. Returns the hash identifying the order.
+    pub async fn submit_order(
+        &self,
+        order: Order,
+        eth_signature: Option<TxEthSignature>,
+    ) -> Result<TxHash, SubmitError> {
+        if !self.order_book_enabled {
+            return Err(SubmitError::OrderBook(
+                "order book is disabled on this server".to_string(),
+            ));
+        }
+        self.check_maintenance_mode().await?;
+
+        order
+            .check_correctness()
+            .map_err(|err| SubmitError::IncorrectTx(err.to_string()))?;
+        if order.time_range.valid_until < Utc::now().timestamp().max(0) as u64 {
+            return Err(SubmitError::IncorrectTx("order is expired".to_string()));
+        }
+        let signer_pub_key_hash = order
+            .verify_signature()
+            .ok_or_else(|| SubmitError::IncorrectTx("invalid order signature".to_string()))?;
+
+        let (_, account) = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(order.account_id)
+            .await
+            .map_err(SubmitError::internal)?;
+        let account = account.ok_or_else(|| SubmitError::invalid_params("account not found"))?;
+        if account.pub_key_hash != signer_pub_key_hash {
+            return Err(SubmitError::IncorrectTx(
+                "order is not signed with the account signing key".to_string(),
+            ));
+        }
+        self.verify_order_eth_signature(&order, eth_signature.clone())
+            .await?;
+
+        let stored = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .order_book_schema()
+            .store_order(&order, eth_signature.as_ref())
+            .await
+            .map_err(SubmitError::internal)?;
+        if !stored {
+            return Err(SubmitError::OrderBook(
+                "order is already submitted".to_string(),
+            ));
+        }
+        Ok(order.hash())
+    }
+
+    /// Cancels the open order in the order book. The cancellation should be signed
+    /// with the same key as the order itself.
+    pub async fn cancel_order(
+        &self,
+        order_hash: TxHash,
+        signature: TxSignature,
+    ) -> Result<(), SubmitError> {
+        if !self.order_book_enabled {
+            return Err(SubmitError::OrderBook(
+                "order book is disabled on this server".to_string(),
+            ));
+        }
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let stored_order = storage
+            .order_book_schema()
+            .get_order(order_hash)
+            .await
+            .map_err(SubmitError::internal)?
+            .ok_or_else(|| SubmitError::OrderBook("order not found".to_string()))?;
+        let order: Order =
+            serde_json::from_value(stored_order.order).map_err(SubmitError::internal)?;
+
+        let signer = signature
+            .verify_musig(&order.get_cancellation_bytes())
+            .map(|pub_key| PubKeyHash::from_pubkey(&pub_key));
+        if signer != Some(PubKeyHash::from_pubkey(&order.signature.pub_key.0)) {
+            return Err(SubmitError::IncorrectTx(
+                "invalid cancellation signature".to_string(),
+            ));
+        }
+
+        let cancelled = storage
+            .order_book_schema()
+            .cancel_order(order_hash)
+            .await
+            .map_err(SubmitError::internal)?;
+        if !cancelled {
+            return Err(SubmitError::OrderBook(
+                "only open orders can be cancelled".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Registers the standing order, so its pre-signed transfers are submitted by the server
+    /// at the scheduled times. Returns the id of the order.
+    pub async fn register_standing_order(
+        &self,
+        order: IncomingStandingOrder,
+    ) -> Result<u64, SubmitError> {
+        if !self.standing_orders_enabled {
+            return Err(SubmitError::StandingOrder(
+                "standing orders are disabled on this server".to_string(),
+            ));
+        }
+        self.check_maintenance_mode().await?;
+
+        let IncomingStandingOrder {
+            first_payment_at,
+            interval_secs,
+            expires_at,
+            payments,
+        } = order;
+        if payments.is_empty() || payments.len() > MAX_STANDING_ORDER_PAYMENTS {
+            return Err(SubmitError::StandingOrder(format!(
+                "the number of payments should be from 1 to {}",
+                MAX_STANDING_ORDER_PAYMENTS
+            )));
+        }
+        if !(MIN_STANDING_ORDER_INTERVAL_SECS..=MAX_STANDING_ORDER_INTERVAL_SECS)
+            .contains(&interval_secs)
+        {
+            return Err(SubmitError::StandingOrder(format!(
+                "interval between the payments should be from {} to {} seconds",
+                MIN_STANDING_ORDER_INTERVAL_SECS, MAX_STANDING_ORDER_INTERVAL_SECS
+            )));
+        }
+        if first_payment_at < Utc::now() {
+            return Err(SubmitError::StandingOrder(
+                "first payment can't be scheduled in the past".to_string(),
+            ));
+        }
+        let schedule: Vec<_> = (0..payments.len())
+            .map(|sequence| {
+                first_payment_at + Duration::seconds(interval_secs as i64 * sequence as i64)
+            })
+            .collect();
+        if schedule[schedule.len() - 1] > expires_at {
+            return Err(SubmitError::StandingOrder(
+                "payments are scheduled after the order expiration".to_string(),
+            ));
+        }
+
+        let template = payments[0].tx.clone();
+        let (_, account) = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(template.account_id)
+            .await
+            .map_err(SubmitError::internal)?;
+        let account = account.ok_or_else(|| SubmitError::invalid_params("account not found"))?;
+        if template.nonce < account.nonce {
+            return Err(SubmitError::StandingOrder(
+                "nonce of the first payment is already used".to_string(),
+            ));
+        }
+        let token = self.token_info_from_id(template.token).await?;
+        let account_type = self
+            .get_sender_type(template.account_id)
+            .await
+            .map_err(|_| SubmitError::TxAdd(TxAddError::DbError))?;
+
+        let mut transfers = Vec::with_capacity(payments.len());
+        for (sequence, (payment, scheduled_at)) in payments.into_iter().zip(schedule).enumerate() {
+            let mut tx = payment.tx;
+            if (tx.account_id, tx.from, tx.to, tx.token, &tx.amount, &tx.fee)
+                != (
+                    template.account_id,
+                    template.from,
+                    template.to,
+                    template.token,
+                    &template.amount,
+                    &template.fee,
+                )
+            {
+                return Err(SubmitError::StandingOrder(
+                    "all the payments should be the same transfer".to_string(),
+                ));
+            }
+            if u64::from(*tx.nonce) != u64::from(*template.nonce) + sequence as u64 {
+                return Err(SubmitError::StandingOrder(
+                    "payments should have the consecutive nonces".to_string(),
+                ));
+            }
+            let scheduled_at = scheduled_at.timestamp().max(0) as u64;
+            if !tx.time_range.unwrap_or_default().is_valid(scheduled_at) {
+                return Err(SubmitError::StandingOrder(format!(
+                    "payment {} is not valid at its scheduled time",
+                    sequence
+                )));
+            }
+            tx.check_correctness()
+                .map_err(|err| SubmitError::IncorrectTx(err.to_string()))?;
+            let signer_pub_key_hash = tx.verify_signature().map(|(pub_key_hash, _)| pub_key_hash);
+            if signer_pub_key_hash != Some(account.pub_key_hash) {
+                return Err(SubmitError::IncorrectTx(
+                    "payment is not signed with the account signing key".to_string(),
+                ));
+            }
+
+            let zksync_tx = ZkSyncTx::Transfer(Box::new(tx.clone()));
+            let msg_to_sign = zksync_tx
+                .get_ethereum_sign_message(token.clone())
+                .map(String::into_bytes);
+            verify_tx_info_message_signature(
+                &zksync_tx,
+                template.from,
+                token.clone(),
+                account_type,
+                payment.eth_signature.clone(),
+                msg_to_sign,
+                self.sign_verify_requests.clone(),
+            )
+            .await?;
+            transfers.push((tx, payment.eth_signature));
+        }
+
+        let id = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .standing_orders_schema()
+            .store_standing_order(
+                &transfers,
+                account.pub_key_hash,
+                first_payment_at,
+                interval_secs,
+                expires_at,
+            )
+            .await
+            .map_err(SubmitError::internal)?;
+        Ok(id as u64)
+    }
+
+    /// Cancels the active standing order. The cancellation should be signed with the same key
+    /// as the transfers of the order.
+    pub async fn cancel_standing_order(
+        &self,
+        standing_order_id: u64,
+        signature: TxSignature,
+    ) -> Result<(), SubmitError> {
+        if !self.standing_orders_enabled {
+            return Err(SubmitError::StandingOrder(
+                "standing orders are disabled on this server".to_string(),
+            ));
+        }
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let stored_order = storage
+            .standing_orders_schema()
+            .get_standing_order(standing_order_id as i64)
+            .await
+            .map_err(SubmitError::internal)?
+            .ok_or_else(|| SubmitError::StandingOrder("standing order not found".to_string()))?;
+
+        let signer = signature
+            .verify_musig(&IncomingStandingOrderCancellation::message(
+                standing_order_id,
+            ))
+            .map(|pub_key| PubKeyHash::from_pubkey(&pub_key));
+        if signer.map(|signer| signer.data.to_vec()) != Some(stored_order.signer_pub_key_hash) {
+            return Err(SubmitError::IncorrectTx(
+                "invalid cancellation signature".to_string(),
+            ));
+        }
+
+        let cancelled = storage
+            .standing_orders_schema()
+            .cancel_standing_order(standing_order_id as i64)
+            .await
+            .map_err(SubmitError::internal)?;
+        if !cancelled {
+            return Err(SubmitError::StandingOrder(
+                "only active standing orders can be cancelled".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Registers the session key of the account, or replaces its previous registration.
+    /// The request should be signed by the owner of the account.
+    pub async fn register_session_key(
+        &self,
+        registration: SessionKeyRegistration,
+    ) -> Result<(), SubmitError> {
+        if !self.session_keys_enabled {
+            return Err(SubmitError::SessionKey(
+                "session keys are disabled on this server".to_string(),
+            ));
+        }
+        check_request_timestamp(registration.timestamp)?;
+        if registration.expires_at <= Utc::now() {
+            return Err(SubmitError::SessionKey(
+                "session key is already expired".to_string(),
+            ));
+        }
+        if registration.allowed_tx_types.is_empty() {
+            return Err(SubmitError::SessionKey(
+                "no transaction types are allowed".to_string(),
+            ));
+        }
+        let tokens: HashSet<_> = registration
+            .limits
+            .iter()
+            .map(|limit| limit.token)
+            .collect();
+        if tokens.len() != registration.limits.len() || tokens.len() > MAX_SESSION_KEY_LIMITS {
+            return Err(SubmitError::SessionKey(format!(
+                "limits should be set for at most {} distinct tokens",
+                MAX_SESSION_KEY_LIMITS
+            )));
+        }
+
+        let owner = self
+            .verify_session_key_request_eth_signature(
+                registration.account_id,
+                registration.signature.clone(),
+                registration.get_ethereum_sign_message(),
+            )
+            .await?;
+        if owner == registration.session_address {
+            return Err(SubmitError::SessionKey(
+                "session key can't be the address of the account".to_string(),
+            ));
+        }
+
+        let allowed_tx_types: Vec<_> = registration
+            .allowed_tx_types
+            .iter()
+            .map(|tx_type| tx_type.as_str().to_string())
+            .collect();
+        let limits: Vec<_> = registration
+            .limits
+            .into_iter()
+            .map(|limit| (limit.token, limit.max_amount))
+            .collect();
+        let stored = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .session_keys_schema()
+            .store_session_key(
+                registration.account_id,
+                registration.session_address,
+                &allowed_tx_types,
+                &limits,
+                registration.expires_at,
+                registration.timestamp,
+            )
+            .await
+            .map_err(SubmitError::internal)?;
+        if !stored {
+            return Err(SubmitError::SessionKey(
+                "session key was updated by a newer request".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Revokes the session key of the account. The request should be signed by the owner
+    /// of the account.
+    pub async fn revoke_session_key(
+        &self,
+        revocation: SessionKeyRevocation,
+    ) -> Result<(), SubmitError> {
+        if !self.session_keys_enabled {
+            return Err(SubmitError::SessionKey(
+                "session keys are disabled on this server".to_string(),
+            ));
+        }
+        check_request_timestamp(revocation.timestamp)?;
+        self.verify_session_key_request_eth_signature(
+            revocation.account_id,
+            revocation.signature.clone(),
+            revocation.get_ethereum_sign_message(),
+        )
+        .await?;
+
+        let revoked = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .session_keys_schema()
+            .revoke_session_key(
+                revocation.account_id,
+                revocation.session_address,
+                revocation.timestamp,
+            )
+            .await
+            .map_err(SubmitError::internal)?;
+        if !revoked {
+            return Err(SubmitError::SessionKey(
+                "session key is not registered or was updated by a newer request".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the session key request is signed by the owner of the account.
+    /// Returns the address of the owner.
+    async fn verify_session_key_request_eth_signature(
+        &self,
+        account_id: AccountId,
+        signature: TxEthSignature,
+        message: String,
+    ) -> Result<Address, SubmitError> {
+        let account_type = self
+            .get_sender_type(account_id)
+            .await
+            .map_err(|_| SubmitError::TxAdd(TxAddError::DbError))?;
+        if matches!(account_type, EthAccountType::CREATE2) {
+            return Err(SubmitError::SessionKey(
+                "session keys are not supported for CREATE2 accounts".to_string(),
+            ));
+        }
+        let owner = self
+            .get_address_by_id(account_id)
+            .await
+            .map_err(|_| SubmitError::invalid_params("account not found"))?;
+
+        let eth_sign_data = EthSignData {
+            signature,
+            message: message.into_bytes(),
+        };
+        let (sender, receiever) = oneshot::channel();
+        let request = VerifySignatureRequest {
+            data: RequestData::SessionKey(SessionKeyRequest {
+                sign_data: eth_sign_data,
+                sender: owner,
+            }),
+            response: sender,
+        };
+        send_verify_request_and_recv(request, self.sign_verify_requests.clone(), receiever).await?;
+        Ok(owner)
+    }
+
+    /// Checks whether the transaction is authorized by the Ethereum signature of the registered
+    /// session key rather than the one of the account owner. Returns `None` if the signature
+    /// should be checked as usual.
+    async fn authorize_by_session_key(
+        &self,
+        tx: &ZkSyncTx,
+        eth_signature: &Option<TxEthSignature>,
+        msg_to_sign: Option<&[u8]>,
+    ) -> Result<Option<SessionKeyAuthorization>, SubmitError> {
+        if !self.session_keys_enabled {
+            return Ok(None);
+        }
+        let tx_type = match SessionTxType::from_tx(tx) {
+            Some(tx_type) => tx_type,
+            None => return Ok(None),
+        };
+        let (signature, message) = match (eth_signature, msg_to_sign) {
+            (Some(TxEthSignature::EthereumSignature(signature)), Some(message)) => {
+                (signature, message)
+            }
+            _ => return Ok(None),
+        };
+        let session_address = match signature.signature_recover_signer_from_raw_message(message) {
+            Ok(address) if address != tx.account() => address,
+            _ => return Ok(None),
+        };
+        let account_id = tx.account_id().or(Err(SubmitError::AccountCloseDisabled))?;
+
+        let session_key = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .session_keys_schema()
+            .get_session_key(account_id, session_address)
+            .await
+            .map_err(SubmitError::internal)?;
+        let session_key = match session_key {
+            Some(session_key) => session_key,
+            None => return Ok(None),
+        };
+        if session_key.revoked || session_key.expires_at <= Utc::now() {
+            return Err(SubmitError::SessionKey(
+                "session key is expired or revoked".to_string(),
+            ));
+        }
+        let allowed = session_key
+            .allowed_tx_types
+            .iter()
+            .any(|allowed_type| SessionTxType::parse(allowed_type) == Some(tx_type));
+        if !allowed {
+            return Err(SubmitError::SessionKey(format!(
+                "session key can't authorize {} transactions",
+                tx_type.as_str()
+            )));
+        }
+
+        Ok(Some(SessionKeyAuthorization {
+            account_id,
+            session_address,
+            amounts: session_key_spendings(tx),
+        }))
+    }
+
+    /// Spends the caps of the session key which has authorized the transaction.
+    async fn spend_session_key(
+        &self,
+        authorization: &SessionKeyAuthorization,
+    ) -> Result<(), SubmitError> {
+        let within_limits = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .session_keys_schema()
+            .spend_session_key(
+                authorization.account_id,
+                authorization.session_address,
+                &authorization.amounts,
+            )
+            .await
+            .map_err(SubmitError::internal)?;
+        if !within_limits {
+            return Err(SubmitError::SessionKey(
+                "spending limit of the session key is exceeded".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the spent caps if the transaction was rejected by the mempool. Failures are
+    /// only logged, since the transaction is rejected anyway.
+    async fn refund_session_key(&self, authorization: &SessionKeyAuthorization) {
+        let result: anyhow::Result<()> = async {
+            self.pool
+                .access_storage()
+                .await?
+                .session_keys_schema()
+                .refund_session_key(
+                    authorization.account_id,
+                    authorization.session_address,
+                    &authorization.amounts,
+                )
+                .await?;
+            Ok(())
+        }
+        .await;
+        if let Err(err) = result {
+            vlog::warn!(
+                "Failed to refund session key {:?} of account {}: {}",
+                authorization.session_address,
+                authorization.account_id,
+                err
+            );
+            metrics::increment_counter!("tx_sender.refund_session_key_fail");
+        }
+    }
+
+    /// Stores the attestation of the control of the address, or replaces its previous one.
+    /// The attestation should be signed by the address itself.
+    pub async fn attest_address(&self, attestation: AddressAttestation) -> Result<(), SubmitError> {
+        if !self.address_attestations_enabled {
+            return Err(SubmitError::Attestation(
+                "address attestations are disabled on this server".to_string(),
+            ));
+        }
+        check_request_timestamp(attestation.timestamp)?;
+        if let Some(reference) = &attestation.reference {
+            if reference.is_empty() || reference.len() > MAX_ATTESTATION_REFERENCE_LENGTH {
+                return Err(SubmitError::Attestation(format!(
+                    "reference should be from 1 to {} bytes long",
+                    MAX_ATTESTATION_REFERENCE_LENGTH
+                )));
+            }
+        }
+
+        let eth_sign_data = EthSignData {
+            signature: attestation.signature.clone(),
+            message: attestation.get_ethereum_sign_message().into_bytes(),
+        };
+        let (sender, receiever) = oneshot::channel();
+        let request = VerifySignatureRequest {
+            data: RequestData::Attestation(AttestationRequest {
+                sign_data: eth_sign_data,
+                sender: attestation.address,
+            }),
+            response: sender,
+        };
+        send_verify_request_and_recv(request, self.sign_verify_requests.clone(), receiever).await?;
+
+        let stored = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .address_attestations_schema()
+            .store_attestation(
+                attestation.address,
+                attestation.reference.as_deref(),
+                &attestation.signature,
+                attestation.timestamp,
+            )
+            .await
+            .map_err(SubmitError::internal)?;
+        if !stored {
+            return Err(SubmitError::Attestation(
+                "address was attested by a newer request".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reserves the range of the account nonces following both the current nonce of the account
+    /// and its active reservations. The request should be signed by the owner of the account.
+    pub async fn reserve_nonces(
+        &self,
+        request: NonceReservation,
+    ) -> Result<StoredNonceReservation, SubmitError> {
+        if !self.nonce_reservations_enabled {
+            return Err(SubmitError::NonceReservation(
+                "nonce reservations are disabled on this server".to_string(),
+            ));
+        }
+        check_request_timestamp(request.timestamp)?;
+        if request.count == 0 || request.count > MAX_RESERVED_NONCES {
+            return Err(SubmitError::NonceReservation(format!(
+                "from 1 to {} nonces can be reserved at once",
+                MAX_RESERVED_NONCES
+            )));
+        }
+        if request.ttl_secs == 0 || request.ttl_secs > MAX_NONCE_RESERVATION_TTL_SECS {
+            return Err(SubmitError::NonceReservation(format!(
+                "reservation TTL should be from 1 to {} seconds",
+                MAX_NONCE_RESERVATION_TTL_SECS
+            )));
+        }
+
+        let account_type = self
+            .get_sender_type(request.account_id)
+            .await
+            .map_err(|_| SubmitError::TxAdd(TxAddError::DbError))?;
+        if matches!(account_type, EthAccountType::CREATE2) {
+            return Err(SubmitError::NonceReservation(
+                "nonce reservations are not supported for CREATE2 accounts".to_string(),
+            ));
+        }
+        let owner = self
+            .get_address_by_id(request.account_id)
+            .await
+            .map_err(|_| SubmitError::invalid_params("account not found"))?;
+
+        let eth_sign_data = EthSignData {
+            signature: request.signature.clone(),
+            message: request.get_ethereum_sign_message().into_bytes(),
+        };
+        let (sender, receiever) = oneshot::channel();
+        let verify_request = VerifySignatureRequest {
+            data: RequestData::NonceReservation(NonceReservationRequest {
+                sign_data: eth_sign_data,
+                sender: owner,
+            }),
+            response: sender,
+        };
+        send_verify_request_and_recv(verify_request, self.sign_verify_requests.clone(), receiever)
+            .await?;
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let nonce = storage
+            .chain()
+            .account_schema()
+            .estimate_nonce(request.account_id)
+            .await
+            .map_err(SubmitError::internal)?
+            .ok_or_else(|| SubmitError::invalid_params("account not found"))?;
+        let expires_at = Utc::now() + Duration::seconds(request.ttl_secs as i64);
+        storage
+            .nonce_reservations_schema()
+            .reserve_nonces(request.account_id, nonce, request.count, expires_at)
+            .await
+            .map_err(SubmitError::internal)
+    }
+
+    /// Holds the transaction signed by the account owner until it's co-signed by the required
+    /// number of the co-signers. The transaction is checked for correctness only, the rest
+    /// of the checks are performed on the submission. Returns the hash of the transaction.
+    pub async fn create_cosigned_tx(
+        &self,
+        request: IncomingCoSignedTx,
+    ) -> Result<TxHash, SubmitError> {
+        if !self.cosigned_txs_enabled {
+            return Err(SubmitError::CoSignedTx(
+                "co-signed transactions are disabled on this server".to_string(),
+            ));
+        }
+        self.check_maintenance_mode().await?;
+
+        let IncomingCoSignedTx {
+            mut tx,
+            signature,
+            policy,
+            expires_at,
+        } = request;
+        let unique_cosigners: HashSet<_> = policy.cosigners.iter().collect();
+        if policy.cosigners.is_empty()
+            || policy.cosigners.len() > MAX_COSIGNERS
+            || unique_cosigners.len() != policy.cosigners.len()
+        {
+            return Err(SubmitError::CoSignedTx(format!(
+                "there should be from 1 to {} distinct co-signers",
+                MAX_COSIGNERS
+            )));
+        }
+        if policy.threshold == 0 || policy.threshold as usize > policy.cosigners.len() {
+            return Err(SubmitError::CoSignedTx(
+                "threshold should be from 1 to the number of the co-signers".to_string(),
+            ));
+        }
+        let now = Utc::now();
+        if expires_at <= now || expires_at > now + Duration::hours(MAX_COSIGNED_TX_TTL_HOURS) {
+            return Err(SubmitError::CoSignedTx(format!(
+                "transaction should expire within {} hours",
+                MAX_COSIGNED_TX_TTL_HOURS
+            )));
+        }
+
+        if tx.is_close() {
+            return Err(SubmitError::AccountCloseDisabled);
+        }
+        // The chain id is set the same way as on the submission, so the hash doesn't change.
+        if let ZkSyncTx::ChangePubKey(change_pub_key) = &mut tx {
+            change_pub_key.chain_id = Some(self.chain_id)
+        };
+        tx.check_correctness()
+            .map_err(|err| SubmitError::IncorrectTx(err.to_string()))?;
+        let account_id = tx.account_id().or(Err(SubmitError::AccountCloseDisabled))?;
+
+        let stored = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .cosigned_txs_schema()
+            .store_cosigned_tx(
+                &tx,
+                account_id,
+                &signature,
+                &policy.cosigners,
+                policy.threshold,
+                expires_at,
+            )
+            .await
+            .map_err(SubmitError::internal)?;
+        if !stored {
+            return Err(SubmitError::CoSignedTx(
+                "transaction is already awaiting the co-signatures".to_string(),
+            ));
+        }
+        Ok(tx.hash())
+    }
+
+    /// Adds the co-signature to the held transaction and submits the transaction once
+    /// the threshold is met. If the submission fails, its error is returned and stored,
+    /// and the submission is retried on the next co-signature, including a repeated one.
+    pub async fn cosign_tx(
+        &self,
+        tx_hash: TxHash,
+        signature: TxEthSignature,
+    ) -> Result<(), SubmitError> {
+        if !self.cosigned_txs_enabled {
+            return Err(SubmitError::CoSignedTx(
+                "co-signed transactions are disabled on this server".to_string(),
+            ));
+        }
+        let message = IncomingCoSignature::get_ethereum_sign_message(tx_hash);
+        let signer = match &signature {
+            TxEthSignature::EthereumSignature(signature) => signature
+                .signature_recover_signer_from_raw_message(message.as_bytes())
+                .map_err(|_| SubmitError::IncorrectTx("invalid co-signature".to_string()))?,
+            TxEthSignature::EIP1271Signature(_) => {
+                return Err(SubmitError::CoSignedTx(
+                    "co-signatures of the smart contracts are not supported".to_string(),
+                ))
+            }
+        };
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(SubmitError::internal)?;
+        let stored_tx = transaction
+            .cosigned_txs_schema()
+            .lock_cosigned_tx(tx_hash)
+            .await
+            .map_err(SubmitError::internal)?
+            .ok_or_else(|| SubmitError::CoSignedTx("transaction not found".to_string()))?;
+        if stored_tx.submitted_at.is_some() {
+            return Err(SubmitError::CoSignedTx(
+                "transaction is already submitted".to_string(),
+            ));
+        }
+        if stored_tx.expires_at <= Utc::now() {
+            return Err(SubmitError::CoSignedTx(
+                "transaction is expired".to_string(),
+            ));
+        }
+        if !stored_tx
+            .cosigners
+            .iter()
+            .any(|cosigner| cosigner.as_slice() == signer.as_bytes())
+        {
+            return Err(SubmitError::IncorrectTx(
+                "signer is not a co-signer of the transaction".to_string(),
+            ));
         }
 
-        let signature = signature.ok_or(SubmitError::TxAdd(TxAddError::MissingEthSignature))?;
-        let signer = self
-            .get_address_by_id(order.account_id)
+        transaction
+            .cosigned_txs_schema()
+            .store_cosignature(tx_hash, signer, &signature)
             .await
-            .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
-
-        let token_sell = self.token_info_from_id(order.token_sell).await?;
-        let token_buy = self.token_info_from_id(order.token_buy).await?;
-        let message = order
-            .get_ethereum_sign_message(&token_sell.symbol, &token_buy.symbol, token_sell.decimals)
-            .into_bytes();
-        let eth_sign_data = EthSignData { signature, message };
-        let (sender, receiever) = oneshot::channel();
+            .map_err(SubmitError::internal)?;
+        let cosignatures = transaction
+            .cosigned_txs_schema()
+            .get_cosignatures(tx_hash)
+            .await
+            .map_err(SubmitError::internal)?;
+        let mut result = Ok(());
+        if cosignatures.len() >= stored_tx.threshold as usize {
+            let tx: ZkSyncTx =
+                serde_json::from_value(stored_tx.tx).map_err(SubmitError::internal)?;
+            let eth_signature: TxEthSignatureVariant =
+                serde_json::from_value(stored_tx.eth_signature).map_err(SubmitError::internal)?;
+            result = self
+                .submit_tx(tx, eth_signature, None, None, None)
+                .await
+                .map(|_| ());
+            let error = result.as_ref().err().map(ToString::to_string);
+            transaction
+                .cosigned_txs_schema()
+                .store_submission_result(tx_hash, error.as_deref())
+                .await
+                .map_err(SubmitError::internal)?;
+            if result.is_ok() {
+                metrics::increment_counter!("tx_sender.cosigned_tx_submitted");
+            }
+        }
+        transaction.commit().await.map_err(SubmitError::internal)?;
+        result
+    }
 
-        let request = VerifySignatureRequest {
-            data: RequestData::Order(OrderRequest {
-                order: Box::new(order.clone()),
-                sign_data: eth_sign_data,
-                sender: signer,
-            }),
-            response: sender,
-        };
+    /// Returns the hashes of the orders matched in the swap, if the order book is enabled.
+    fn matched_orders(&self, tx: &ZkSyncTx) -> Option<(TxHash, Vec<TxHash>)> {
+        match tx {
+            ZkSyncTx::Swap(swap) if self.order_book_enabled => {
+                Some((tx.hash(), vec![swap.orders.0.hash(), swap.orders.1.hash()]))
+            }
+            _ => None,
+        }
+    }
 
-        send_verify_request_and_recv(request, self.sign_verify_requests.clone(), receiever).await?;
-        Ok(())
+    /// Marks the orders of the accepted swaps as matched in the order book.
+    /// The swaps are accepted already, so the failure is only logged.
+    async fn mark_orders_matched(&self, swaps: &[(TxHash, Vec<TxHash>)]) {
+        if swaps.is_empty() {
+            return;
+        }
+        let result: anyhow::Result<()> = async {
+            let mut storage = self.pool.access_storage().await?;
+            for (swap_tx_hash, order_hashes) in swaps {
+                storage
+                    .order_book_schema()
+                    .mark_orders_matched(order_hashes, *swap_tx_hash)
+                    .await?;
+            }
+            Ok(())
+        }
+        .await;
+        if let Err(err) = result {
+            vlog::warn!("Failed to mark the swap orders as matched: {}", err);
+            metrics::increment_counter!("tx_sender.mark_orders_matched_fail");
+        }
     }
 
     // This method is left for RPC API
@@ -383,7 +1552,7 @@ impl TxSender {
         }
 
         let result = self
-            .submit_tx(tx, signature, extracted_request_metadata)
+            .submit_tx(tx, signature, None, None, extracted_request_metadata)
             .await;
 
         if let Err(err) = &result {
@@ -484,10 +1653,204 @@ impl TxSender {
         Ok(())
     }
 
+    /// Stores the fee quotes the transactions are accepted against, so the fees charged can be
+    /// decomposed once the transactions are executed. Transactions are already accepted at this
+    /// point, so the failure is only reported.
+    async fn store_fee_quotes(&self, fee_quotes: &[TxFeeQuote]) {
+        let result: anyhow::Result<()> = async {
+            self.pool
+                .access_storage()
+                .await?
+                .fee_breakdowns_schema()
+                .store_fee_quotes(fee_quotes)
+                .await?;
+            Ok(())
+        }
+        .await;
+        if let Err(err) = result {
+            vlog::warn!("Failed to store the fee quotes: {}", err);
+            metrics::increment_counter!("tx_sender.store_fee_quotes_fail");
+        }
+    }
+
+    /// Stores the memos attached to the accepted transfers. Transactions are already accepted
+    /// at this point, so the failure is only reported.
+    async fn store_tx_memos(&self, memos: &[TxMemo]) {
+        if memos.is_empty() {
+            return;
+        }
+        let result: anyhow::Result<()> = async {
+            self.pool
+                .access_storage()
+                .await?
+                .tx_memos_schema()
+                .store_tx_memos(memos)
+                .await?;
+            Ok(())
+        }
+        .await;
+        if let Err(err) = result {
+            vlog::warn!("Failed to store the transaction memos: {}", err);
+            metrics::increment_counter!("tx_sender.store_tx_memos_fail");
+        }
+    }
+
+    /// Stores the evidence of the EIP-1271 signatures of the accepted transactions. Transactions
+    /// are already accepted at this point, so the failure is only reported.
+    async fn store_eip1271_verifications(&self, verifications: &[EIP1271Verification]) {
+        if verifications.is_empty() {
+            return;
+        }
+        let result: anyhow::Result<()> = async {
+            self.pool
+                .access_storage()
+                .await?
+                .eip1271_verifications_schema()
+                .store_verifications(verifications)
+                .await?;
+            Ok(())
+        }
+        .await;
+        if let Err(err) = result {
+            vlog::warn!("Failed to store the EIP-1271 verifications: {}", err);
+            metrics::increment_counter!("tx_sender.store_eip1271_verifications_fail");
+        }
+    }
+
+    /// Returns the gas of the calls attached to the withdrawals, which is added to the fee
+    /// since the calls are paid by the operator.
+    pub fn withdrawal_hooks_gas(&self, hooks_count: usize) -> Result<BigUint, SubmitError> {
+        if hooks_count > 0 && !self.withdrawal_hooks_enabled {
+            return Err(SubmitError::IncorrectTx(
+                "Withdrawal hooks are disabled on this server".to_string(),
+            ));
+        }
+        Ok(BigUint::from(self.withdrawal_hook_gas_limit) * BigUint::from(hooks_count))
+    }
+
+    /// Returns the sponsorship of the `ChangePubKey` fee if the account is eligible for it:
+    /// the transaction is the first one of the account, the first deposit to the account is
+    /// large enough and the sponsorship budget is not exhausted yet.
+    ///
+    /// Transactions in batches are never sponsored.
+    async fn get_cpk_sponsorship(
+        &self,
+        tx: &ZkSyncTx,
+        required_fee: &BigUint,
+    ) -> Result<Option<CpkSponsorship>, SubmitError> {
+        let change_pub_key = match tx {
+            ZkSyncTx::ChangePubKey(change_pub_key) if self.cpk_sponsorship_enabled => {
+                change_pub_key
+            }
+            _ => return Ok(None),
+        };
+        if *change_pub_key.nonce != 0 {
+            return Ok(None);
+        }
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let (deposit_token, deposit_amount) = match storage
+            .chain()
+            .operations_schema()
+            .get_first_deposit(change_pub_key.account)
+            .await
+            .map_err(SubmitError::internal)?
+        {
+            Some(deposit) => deposit,
+            None => return Ok(None),
+        };
+        let deposit_token_price = self
+            .ticker
+            .get_token_price(
+                TokenLike::Id(deposit_token),
+                TokenPriceRequestType::USDForOneWei,
+            )
+            .await?;
+        let deposit_usd =
+            big_decimal_to_ratio(&deposit_token_price)? * big_decimal_to_ratio(&deposit_amount)?;
+        if deposit_usd < self.cpk_sponsorship_min_deposit_usd {
+            return Ok(None);
+        }
+
+        let waived_fee = if *required_fee > change_pub_key.fee {
+            required_fee - &change_pub_key.fee
+        } else {
+            BigUint::zero()
+        };
+        let fee_token_price = self
+            .ticker
+            .get_token_price(
+                TokenLike::Id(change_pub_key.fee_token),
+                TokenPriceRequestType::USDForOneWei,
+            )
+            .await?;
+        let waived_fee_usd = big_decimal_to_ratio(&fee_token_price)? * &waived_fee;
+
+        let sponsored_usd = storage
+            .sponsorship_schema()
+            .get_total_sponsored_usd()
+            .await
+            .map_err(SubmitError::internal)?;
+        if scaled_u64_to_ratio(sponsored_usd) + &waived_fee_usd > self.cpk_sponsorship_max_total_usd
+        {
+            metrics::increment_counter!("tx_sender.cpk_sponsorship_budget_exhausted");
+            return Ok(None);
+        }
+
+        Ok(Some(CpkSponsorship {
+            account_id: change_pub_key.account_id,
+            tx_hash: tx.hash(),
+            fee_token: change_pub_key.fee_token,
+            waived_fee: biguint_to_big_decimal(waived_fee),
+            waived_fee_usd_scaled: ratio_to_scaled_u64(waived_fee_usd),
+        }))
+    }
+
+    /// Returns the waived fee of the transfer if it's sent by one of the operator addresses.
+    /// The daily quota of the address is checked when the transfer is added to the mempool.
+    ///
+    /// Transactions in batches never have their fee waived.
+    fn get_fee_free_transfer(
+        &self,
+        tx: &ZkSyncTx,
+        required_fee: &BigUint,
+    ) -> Option<FeeFreeTransfer> {
+        let transfer = match tx {
+            ZkSyncTx::Transfer(transfer)
+                if self.fee_free_transfer_addresses.contains(&transfer.from) =>
+            {
+                transfer
+            }
+            _ => return None,
+        };
+        let waived_fee = if *required_fee > transfer.fee {
+            required_fee - &transfer.fee
+        } else {
+            BigUint::zero()
+        };
+
+        Some(FeeFreeTransfer {
+            address: transfer.from,
+            tx_hash: tx.hash(),
+            fee_token: transfer.token,
+            waived_fee: biguint_to_big_decimal(waived_fee),
+        })
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(tx_type = %tx.variance_name(), tx_hash = field::Empty)
+    )]
     pub async fn submit_tx(
         &self,
         mut tx: ZkSyncTx,
         signature: TxEthSignatureVariant,
+        memo: Option<String>,
+        l1_calldata: Option<Vec<u8>>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<TxHash, SubmitError> {
         let labels = vec![
@@ -501,13 +1864,25 @@ impl TxSender {
         if tx.is_close() {
             return Err(SubmitError::AccountCloseDisabled);
         }
+        self.check_maintenance_mode().await?;
 
         if let ZkSyncTx::ForcedExit(forced_exit) = &tx {
             self.check_forced_exit(forced_exit).await?;
         }
+        self.check_create2_factory(&tx).await?;
         if let ZkSyncTx::ChangePubKey(change_pub_key) = &mut tx {
             change_pub_key.chain_id = Some(self.chain_id)
         };
+        // The hash is recorded after the chain id is set, so the span can be correlated
+        // with the ones of the state keeper.
+        Span::current().record("tx_hash", &field::display(tx.hash().to_string()));
+        self.check_known_txs(std::iter::once(tx.hash())).await?;
+        let memos = tx_memos(std::iter::once((&tx, memo)))?;
+        let mut withdrawal_hooks = withdrawal_hooks(
+            self.withdrawal_hooks_enabled,
+            std::iter::once((&tx, l1_calldata)),
+        )?;
+        let withdrawal_hooks_gas = self.withdrawal_hooks_gas(withdrawal_hooks.len())?;
 
         // Resolve the token.
         let token = self.token_info_from_id(tx.token_id()).await?;
@@ -529,6 +1904,9 @@ impl TxSender {
         let sign_verify_channel = self.sign_verify_requests.clone();
 
         let mut fee_data_for_subsidy: Option<ResponseFee> = None;
+        let mut cpk_sponsorship: Option<CpkSponsorship> = None;
+        let mut fee_free_transfer: Option<FeeFreeTransfer> = None;
+        let mut fee_quote: Option<TxFeeQuote> = None;
 
         if let Some((tx_type, token, address, provided_fee)) = tx_fee_info {
             let should_enforce_fee = !matches!(tx_type, TxFeeTypes::ChangePubKey { .. })
@@ -542,7 +1920,12 @@ impl TxSender {
 
             let required_fee_data = self
                 .ticker
-                .get_fee_from_ticker_in_wei(tx_type, token.clone(), address)
+                .get_fee_with_extra_gas_in_wei(
+                    tx_type,
+                    token.clone(),
+                    address,
+                    withdrawal_hooks_gas,
+                )
                 .await?;
 
             let required_fee_data = if self
@@ -566,8 +1949,40 @@ impl TxSender {
             // Scaling the fee required since the price may change between signing the transaction and sending it to the server.
             let scaled_provided_fee = scale_user_fee_up(provided_fee);
             if required_fee >= scaled_provided_fee && should_enforce_fee {
-                return Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow));
+                // The fee of the new accounts may be paid by the operator.
+                cpk_sponsorship = self
+                    .get_cpk_sponsorship(&tx, &required_fee_data.total_fee)
+                    .await?;
+                // The transfers of the operator addresses are free within their quotas.
+                if cpk_sponsorship.is_none() {
+                    fee_free_transfer =
+                        self.get_fee_free_transfer(&tx, &required_fee_data.total_fee);
+                }
+                if cpk_sponsorship.is_none() && fee_free_transfer.is_none() {
+                    return Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow));
+                }
+            }
+
+            let mut subsidy = fee_data_for_subsidy
+                .as_ref()
+                .map(|fee_data| {
+                    biguint_to_big_decimal(fee_data.normal_fee.total_fee.clone())
+                        - biguint_to_big_decimal(fee_data.subsidized_fee.total_fee.clone())
+                })
+                .unwrap_or_else(|| BigDecimal::from(0));
+            if let Some(sponsorship) = &cpk_sponsorship {
+                subsidy += &sponsorship.waived_fee;
+            }
+            if let Some(transfer) = &fee_free_transfer {
+                subsidy += &transfer.waived_fee;
             }
+            fee_quote = Some(TxFeeQuote {
+                tx_hash: tx.hash(),
+                token_id: tx.token_id(),
+                zkp_fee: biguint_to_big_decimal(required_fee_data.zkp_fee),
+                gas_fee: biguint_to_big_decimal(required_fee_data.gas_fee),
+                subsidy,
+            });
         }
 
         let tx_sender = self
@@ -575,17 +1990,30 @@ impl TxSender {
             .await
             .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
 
+        // Transactions authorized by the session key don't need the signature of the owner.
+        let session_key = self
+            .authorize_by_session_key(&tx, signature.tx_signature(), msg_to_sign.as_deref())
+            .await?;
+        let (account_type, eth_signature) = match &session_key {
+            Some(_) => (EthAccountType::No2FA(None), None),
+            None => (
+                self.get_tx_sender_type(&tx).await?,
+                signature.tx_signature().clone(),
+            ),
+        };
         let verified_tx = verify_tx_info_message_signature(
             &tx,
             tx_sender,
             token.clone(),
-            self.get_tx_sender_type(&tx).await?,
-            signature.tx_signature().clone(),
+            account_type,
+            eth_signature,
             msg_to_sign,
             sign_verify_channel,
         )
-        .await?
-        .unwrap_tx();
+        .await?;
+        let eip1271_verifications = verified_tx.eip1271_verifications().to_vec();
+        let verified_tx = verified_tx.unwrap_tx();
+        check_withdrawal_hooks_signed(std::iter::once(&verified_tx), false)?;
 
         if let ZkSyncTx::Swap(tx) = &tx {
             if signature.is_single() {
@@ -598,15 +2026,53 @@ impl TxSender {
                 .await?;
         }
 
+        self.screen_txs(std::iter::once(&tx)).await?;
+        self.check_withdrawal_policy(std::iter::once(&tx)).await?;
+        let matched_orders: Vec<_> = self.matched_orders(&tx).into_iter().collect();
+        if let Some(authorization) = &session_key {
+            self.spend_session_key(authorization).await?;
+        }
+
         let (sender, receiver) = oneshot::channel();
-        let item = MempoolTransactionRequest::NewTx(Box::new(verified_tx), sender);
+        let item = match (cpk_sponsorship, fee_free_transfer) {
+            (Some(sponsorship), _) => MempoolTransactionRequest::NewSponsoredTx(
+                Box::new(verified_tx),
+                sponsorship,
+                sender,
+                Span::current(),
+            ),
+            (None, Some(transfer)) => MempoolTransactionRequest::NewFeeFreeTx(
+                Box::new(verified_tx),
+                transfer,
+                self.fee_free_transfer_daily_quota,
+                sender,
+                Span::current(),
+            ),
+            // The hook is stored along with the transaction, so it's not left behind if
+            // the transaction is rejected by the mempool.
+            (None, None) => match withdrawal_hooks.pop() {
+                Some(hook) => MempoolTransactionRequest::NewWithdrawalTx(
+                    Box::new(verified_tx),
+                    hook,
+                    sender,
+                    Span::current(),
+                ),
+                None => {
+                    MempoolTransactionRequest::NewTx(Box::new(verified_tx), sender, Span::current())
+                }
+            },
+        };
         let mut mempool_sender = self.mempool_tx_sender.clone();
         mempool_sender
             .send(item)
             .await
             .map_err(SubmitError::internal)?;
 
-        receiver.await.map_err(SubmitError::internal)??;
+        let mempool_result = receiver.await.map_err(SubmitError::internal)?;
+        if let (Err(_), Some(authorization)) = (&mempool_result, &session_key) {
+            self.refund_session_key(authorization).await;
+        }
+        mempool_result?;
 
         // fee_data_for_subsidy has Some value only if the batch of transactions is subsidised
         if let Some(fee_data_for_subsidy) = fee_data_for_subsidy {
@@ -632,10 +2098,19 @@ impl TxSender {
             })?;
         }
 
+        if let Some(fee_quote) = fee_quote {
+            self.store_fee_quotes(&[fee_quote]).await;
+        }
+        self.store_tx_memos(&memos).await;
+        self.store_eip1271_verifications(&eip1271_verifications)
+            .await;
+        self.mark_orders_matched(&matched_orders).await;
+
         // if everything is OK, return the transactions hashes.
         Ok(tx.hash())
     }
 
+    #[tracing::instrument(skip_all, fields(batch_size = txs.len()))]
     pub async fn submit_txs_batch(
         &self,
         txs: Vec<TxWithSignature>,
@@ -654,6 +2129,7 @@ impl TxSender {
         if txs.len() > self.max_number_of_transactions_per_batch {
             return Err(SubmitError::TxAdd(TxAddError::BatchTooBig));
         }
+        self.check_maintenance_mode().await?;
 
         for tx in &txs {
             let labels = vec![
@@ -672,6 +2148,17 @@ impl TxSender {
         if txs.iter().any(|tx| tx.tx.is_close()) {
             return Err(SubmitError::AccountCloseDisabled);
         }
+        for tx in &txs {
+            self.check_create2_factory(&tx.tx).await?;
+        }
+        self.check_known_txs(txs.iter().map(|tx| tx.tx.hash()))
+            .await?;
+        let memos = tx_memos(txs.iter().map(|tx| (&tx.tx, tx.memo.clone())))?;
+        let withdrawal_hooks = withdrawal_hooks(
+            self.withdrawal_hooks_enabled,
+            txs.iter().map(|tx| (&tx.tx, tx.l1_calldata.clone())),
+        )?;
+        let withdrawal_hooks_gas = self.withdrawal_hooks_gas(withdrawal_hooks.len())?;
 
         // Checking fees data
         let mut provided_total_usd_fee = BigDecimal::from(0);
@@ -728,13 +2215,20 @@ impl TxSender {
         }
 
         let mut fee_data_for_subsidy: Option<ResponseBatchFee> = None;
+        // Zero-knowledge proof and gas parts of the batch fee quote.
+        let batch_fee_quote: (BigUint, BigUint);
+        let single_fee_token = token_fees.len() == 1;
 
         // Only one token in batch
-        if token_fees.len() == 1 {
+        if single_fee_token {
             let (batch_token, fee_paid) = token_fees.into_iter().next().unwrap();
             let batch_token_fee = self
                 .ticker
-                .get_batch_from_ticker_in_wei(batch_token.into(), transaction_types.clone())
+                .get_batch_with_extra_gas_in_wei(
+                    batch_token.into(),
+                    transaction_types.clone(),
+                    withdrawal_hooks_gas,
+                )
                 .await?;
 
             let required_fee = if self
@@ -751,6 +2245,17 @@ impl TxSender {
             } else {
                 batch_token_fee.normal_fee.total_fee
             };
+            batch_fee_quote = if fee_data_for_subsidy.is_some() {
+                (
+                    batch_token_fee.subsidized_fee.zkp_fee,
+                    batch_token_fee.subsidized_fee.gas_fee,
+                )
+            } else {
+                (
+                    batch_token_fee.normal_fee.zkp_fee,
+                    batch_token_fee.normal_fee.gas_fee,
+                )
+            };
 
             let user_provided_fee =
                 scale_user_fee_up(BigDecimal::from(fee_paid.to_bigint().unwrap()));
@@ -769,7 +2274,11 @@ impl TxSender {
             // Calculate required fee for ethereum token
             let required_eth_fee = self
                 .ticker
-                .get_batch_from_ticker_in_wei(eth_token.clone(), transaction_types)
+                .get_batch_with_extra_gas_in_wei(
+                    eth_token.clone(),
+                    transaction_types,
+                    withdrawal_hooks_gas,
+                )
                 .await?;
 
             let required_fee = if self
@@ -786,6 +2295,17 @@ impl TxSender {
             } else {
                 required_eth_fee.normal_fee.total_fee
             };
+            batch_fee_quote = if fee_data_for_subsidy.is_some() {
+                (
+                    required_eth_fee.subsidized_fee.zkp_fee,
+                    required_eth_fee.subsidized_fee.gas_fee,
+                )
+            } else {
+                (
+                    required_eth_fee.normal_fee.zkp_fee,
+                    required_eth_fee.normal_fee.gas_fee,
+                )
+            };
 
             let eth_price_in_usd = self
                 .ticker
@@ -865,7 +2385,7 @@ impl TxSender {
         } else {
             None
         };
-        let (verified_batch, sign_data) = verify_txs_batch_signature(
+        let verified_batch = verify_txs_batch_signature(
             txs,
             tx_senders,
             tokens,
@@ -874,18 +2394,45 @@ impl TxSender {
             messages_to_sign,
             self.sign_verify_requests.clone(),
         )
-        .await?
-        .unwrap_batch();
+        .await?;
+        let eip1271_verifications = verified_batch.eip1271_verifications().to_vec();
+        let (verified_batch, sign_data) = verified_batch.unwrap_batch();
+        check_withdrawal_hooks_signed(verified_batch.iter(), sign_data.is_some())?;
         if let Some(sign_data) = sign_data {
             verified_signatures.extend(sign_data.signatures.into_iter());
         }
         verified_txs.extend(verified_batch.into_iter());
 
         let tx_hashes: Vec<TxHash> = verified_txs.iter().map(|tx| tx.tx.hash()).collect();
+        let fee_payments: Vec<_> = verified_txs
+            .iter()
+            .filter_map(|tx| {
+                let (_, _, _, fee) = tx.tx.get_fee_info()?;
+                if fee.is_zero() {
+                    None
+                } else {
+                    Some((tx.tx.hash(), tx.tx.token_id(), fee))
+                }
+            })
+            .collect();
+
+        self.screen_txs(verified_txs.iter().map(|tx| &tx.tx))
+            .await?;
+        self.check_withdrawal_policy(verified_txs.iter().map(|tx| &tx.tx))
+            .await?;
+        let matched_orders: Vec<_> = verified_txs
+            .iter()
+            .filter_map(|tx| self.matched_orders(&tx.tx))
+            .collect();
 
         let (sender, receiver) = oneshot::channel();
-        let item =
-            MempoolTransactionRequest::NewTxsBatch(verified_txs, verified_signatures, sender);
+        let item = MempoolTransactionRequest::NewTxsBatch(
+            verified_txs,
+            verified_signatures,
+            withdrawal_hooks,
+            sender,
+            Span::current(),
+        );
         let mut mempool_sender = self.mempool_tx_sender.clone();
         mempool_sender
             .send(item)
@@ -896,6 +2443,31 @@ impl TxSender {
 
         let batch_hash = TxHash::batch_hash(&tx_hashes);
 
+        // The subsidy is split between the transactions paying the fee proportionally to their fees.
+        // If the fee is paid in several tokens, the subsidy is only known in ETH, so it's not split.
+        let batch_subsidy = match &fee_data_for_subsidy {
+            Some(fee_data) if single_fee_token => {
+                &fee_data.normal_fee.total_fee - &fee_data.subsidized_fee.total_fee
+            }
+            _ => BigUint::zero(),
+        };
+        let total_fee_paid: BigUint = fee_payments.iter().map(|(_, _, fee)| fee).sum();
+        let fee_quotes: Vec<_> = fee_payments
+            .into_iter()
+            .map(|(tx_hash, token_id, fee)| TxFeeQuote {
+                tx_hash,
+                token_id,
+                zkp_fee: biguint_to_big_decimal(batch_fee_quote.0.clone()),
+                gas_fee: biguint_to_big_decimal(batch_fee_quote.1.clone()),
+                subsidy: biguint_to_big_decimal(&batch_subsidy * fee / &total_fee_paid),
+            })
+            .collect();
+        self.store_fee_quotes(&fee_quotes).await;
+        self.store_tx_memos(&memos).await;
+        self.store_eip1271_verifications(&eip1271_verifications)
+            .await;
+        self.mark_orders_matched(&matched_orders).await;
+
         // fee_data_for_subsidy has Some value only if the batch of transactions is subsidised
         if let Some(fee_data) = fee_data_for_subsidy {
             let subsidy_token_id = if token_fees_ids.len() == 1 {
@@ -957,6 +2529,39 @@ impl TxSender {
             .await
     }
 
+    /// If the registry of the CREATE2 factories is enabled, the `ChangePubKey` of the CREATE2
+    /// smart wallet is accepted only if its factory and code hash pair is registered.
+    async fn check_create2_factory(&self, tx: &ZkSyncTx) -> Result<(), SubmitError> {
+        if !self.create2_factory_registry_enabled {
+            return Ok(());
+        }
+        let create2_data = match tx {
+            ZkSyncTx::ChangePubKey(tx) => match &tx.eth_auth_data {
+                Some(ChangePubKeyEthAuthData::CREATE2(create2_data)) => create2_data,
+                _ => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let registered = storage
+            .create2_factories_schema()
+            .is_factory_registered(create2_data.creator_address, create2_data.code_hash)
+            .await
+            .map_err(SubmitError::internal)?;
+        if !registered {
+            return Err(SubmitError::IncorrectTx(format!(
+                "CREATE2 factory {:?} with code hash {:?} is not registered",
+                create2_data.creator_address, create2_data.code_hash
+            )));
+        }
+        Ok(())
+    }
+
     /// Returns a message that user has to sign to send the transaction.
     /// If the transaction doesn't need a message signature, returns `None`.
     /// If any error is encountered during the message generation, returns `jsonrpc_core::Error`.
@@ -1194,6 +2799,166 @@ async fn verify_txs_batch_signature(
     send_verify_request_and_recv(request, req_channel, receiver).await
 }
 
+/// Transaction authorized by the session key, with the amounts it spends per token.
+struct SessionKeyAuthorization {
+    account_id: AccountId,
+    session_address: Address,
+    amounts: Vec<(TokenId, BigUint)>,
+}
+
+/// Returns the amounts spent by the transaction per token, including the fee.
+fn session_key_spendings(tx: &ZkSyncTx) -> Vec<(TokenId, BigUint)> {
+    let spendings = match tx {
+        ZkSyncTx::Transfer(tx) => vec![(tx.token, &tx.amount), (tx.token, &tx.fee)],
+        ZkSyncTx::Withdraw(tx) => vec![(tx.token, &tx.amount), (tx.token, &tx.fee)],
+        ZkSyncTx::MintNFT(tx) => vec![(tx.fee_token, &tx.fee)],
+        _ => Vec::new(),
+    };
+    let mut amounts: Vec<(TokenId, BigUint)> = Vec::new();
+    for (token, amount) in spendings {
+        if amount.is_zero() {
+            continue;
+        }
+        match amounts
+            .iter_mut()
+            .find(|(spent_token, _)| *spent_token == token)
+        {
+            Some((_, total)) => *total += amount,
+            None => amounts.push((token, amount.clone())),
+        }
+    }
+    amounts
+}
+
+/// Checks the memos attached to the transactions. Memos are allowed for the transfers only,
+/// since they are meant to be the payment references.
+fn tx_memos<'a>(
+    txs: impl IntoIterator<Item = (&'a ZkSyncTx, Option<String>)>,
+) -> Result<Vec<TxMemo>, SubmitError> {
+    let mut memos = Vec::new();
+    for (tx, memo) in txs {
+        let memo = match memo {
+            Some(memo) => memo,
+            None => continue,
+        };
+        let transfer = match tx {
+            ZkSyncTx::Transfer(transfer) => transfer,
+            _ => {
+                return Err(SubmitError::IncorrectTx(
+                    "Memo can be attached to the transfers only".to_string(),
+                ))
+            }
+        };
+        if memo.is_empty() || memo.len() > MAX_TX_MEMO_LENGTH {
+            return Err(SubmitError::IncorrectTx(format!(
+                "Memo should be from 1 to {} bytes long",
+                MAX_TX_MEMO_LENGTH
+            )));
+        }
+        memos.push(TxMemo {
+            tx_hash: tx.hash(),
+            to_address: transfer.to,
+            memo,
+        });
+    }
+    Ok(memos)
+}
+
+/// Checks the calldata attached to the withdrawals. The calls are made to the recipients of
+/// the withdrawals only, so the operator account can't be used to call arbitrary contracts.
+/// The calldata must match the hash set in the withdrawal, so it's covered by the signature.
+fn withdrawal_hooks<'a>(
+    enabled: bool,
+    txs: impl IntoIterator<Item = (&'a ZkSyncTx, Option<Vec<u8>>)>,
+) -> Result<Vec<NewWithdrawalHook>, SubmitError> {
+    let mut hooks = Vec::new();
+    for (tx, calldata) in txs {
+        let l1_calldata_hash = match tx {
+            ZkSyncTx::Withdraw(withdraw) => withdraw.l1_calldata_hash,
+            _ => None,
+        };
+        let calldata = match (calldata, l1_calldata_hash) {
+            (Some(calldata), _) => calldata,
+            (None, Some(_)) => {
+                return Err(SubmitError::IncorrectTx(
+                    "L1 calldata is missing for the withdrawal with the L1 calldata hash"
+                        .to_string(),
+                ))
+            }
+            (None, None) => continue,
+        };
+        if !enabled {
+            return Err(SubmitError::IncorrectTx(
+                "Withdrawal hooks are disabled on this server".to_string(),
+            ));
+        }
+        let withdraw = match tx {
+            ZkSyncTx::Withdraw(withdraw) => withdraw,
+            _ => {
+                return Err(SubmitError::IncorrectTx(
+                    "L1 calldata can be attached to the withdrawals only".to_string(),
+                ))
+            }
+        };
+        if calldata.is_empty() || calldata.len() > MAX_WITHDRAWAL_HOOK_CALLDATA_SIZE {
+            return Err(SubmitError::IncorrectTx(format!(
+                "L1 calldata should be from 1 to {} bytes long",
+                MAX_WITHDRAWAL_HOOK_CALLDATA_SIZE
+            )));
+        }
+        if l1_calldata_hash != Some(H256::from(tiny_keccak::keccak256(&calldata))) {
+            return Err(SubmitError::IncorrectTx(
+                "L1 calldata doesn't match the L1 calldata hash of the withdrawal".to_string(),
+            ));
+        }
+        hooks.push(NewWithdrawalHook {
+            tx_hash: tx.hash(),
+            recipient: withdraw.to,
+            calldata,
+        });
+    }
+    Ok(hooks)
+}
+
+/// Checks that the L1 calldata hashes of the withdrawals are authorized by the Ethereum
+/// signature of the owner, either of the transaction itself or of the whole batch.
+/// The zkSync signature doesn't cover the hash, so the withdrawals authorized otherwise
+/// (e.g. by the session key or by the CREATE2 account) can't carry the calldata.
+fn check_withdrawal_hooks_signed<'a>(
+    txs: impl IntoIterator<Item = &'a SignedZkSyncTx>,
+    batch_signed: bool,
+) -> Result<(), SubmitError> {
+    for tx in txs {
+        let has_hook = matches!(
+            &tx.tx,
+            ZkSyncTx::Withdraw(withdraw) if withdraw.l1_calldata_hash.is_some()
+        );
+        if has_hook && tx.eth_sign_data.is_none() && !batch_signed {
+            return Err(SubmitError::IncorrectTx(
+                "L1 calldata of the withdrawal must be authorized by the Ethereum signature"
+                    .to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Rejects the signed requests whose timestamp differs too much from the current time,
+/// so they can't be replayed later.
+fn check_request_timestamp(timestamp: DateTime<Utc>) -> Result<(), SubmitError> {
+    let current_time = Utc::now();
+    let validness_interval = Duration::minutes(VALIDNESS_INTERVAL_MINUTES);
+    if current_time - validness_interval > timestamp
+        || current_time + validness_interval < timestamp
+    {
+        return Err(SubmitError::InvalidParams(format!(
+            "Timestamp differs by more than {} minutes",
+            VALIDNESS_INTERVAL_MINUTES
+        )));
+    }
+    Ok(())
+}
+
 /// Scales the fee provided by user up to check whether the provided fee is enough to cover our expenses for
 /// maintaining the protocol.
 ///
@@ -1231,6 +2996,7 @@ fn scale_user_fee_up(provided_total_usd_fee: BigDecimal) -> BigDecimal {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use zksync_types::{tx::PackedEthSignature, MintNFT, Nonce, Transfer, Withdraw};
 
     #[test]
     fn test_scaling_user_fee_by_two() {
@@ -1261,4 +3027,184 @@ mod tests {
 
         assert_eq!(provided_fee_scaled_by_five_percent, scaled_fee);
     }
+
+    #[test]
+    fn test_session_key_spendings() {
+        let transfer = Transfer::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(1),
+            BigUint::from(100u32),
+            BigUint::from(3u32),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+        // The fee is paid in the transferred token.
+        assert_eq!(
+            session_key_spendings(&ZkSyncTx::from(transfer)),
+            vec![(TokenId(1), BigUint::from(103u32))]
+        );
+
+        let mint_nft = MintNFT::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Default::default(),
+            Address::repeat_byte(2),
+            BigUint::from(0u32),
+            TokenId(0),
+            Nonce(0),
+            None,
+        );
+        assert!(session_key_spendings(&ZkSyncTx::from(mint_nft)).is_empty());
+    }
+
+    #[test]
+    fn test_tx_memos() {
+        let transfer = ZkSyncTx::from(Transfer::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(1),
+            BigUint::from(100u32),
+            BigUint::from(3u32),
+            Nonce(0),
+            Default::default(),
+            None,
+        ));
+        let memos = tx_memos(vec![
+            (&transfer, Some("INV-1".to_string())),
+            (&transfer, None),
+        ])
+        .unwrap();
+        assert_eq!(memos.len(), 1);
+        assert_eq!(memos[0].to_address, Address::repeat_byte(2));
+        assert_eq!(memos[0].memo, "INV-1");
+
+        let too_long = "x".repeat(MAX_TX_MEMO_LENGTH + 1);
+        assert!(tx_memos(std::iter::once((&transfer, Some(too_long)))).is_err());
+
+        // Memos are the payment references, so they can't be attached to other transactions.
+        let mint_nft = ZkSyncTx::from(MintNFT::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Default::default(),
+            Address::repeat_byte(2),
+            BigUint::from(0u32),
+            TokenId(0),
+            Nonce(0),
+            None,
+        ));
+        assert!(tx_memos(std::iter::once((&mint_nft, Some("INV-1".to_string())))).is_err());
+    }
+
+    fn withdraw_with_l1_calldata(calldata: &[u8]) -> ZkSyncTx {
+        let mut withdraw = Withdraw::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(1),
+            BigUint::from(100u32),
+            BigUint::from(3u32),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+        withdraw.l1_calldata_hash = Some(H256::from(tiny_keccak::keccak256(calldata)));
+        ZkSyncTx::from(withdraw)
+    }
+
+    #[test]
+    fn test_withdrawal_hooks() {
+        let withdraw = withdraw_with_l1_calldata(&[0xab; 4]);
+        let plain_withdraw = ZkSyncTx::from(Withdraw::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(1),
+            BigUint::from(100u32),
+            BigUint::from(3u32),
+            Nonce(1),
+            Default::default(),
+            None,
+        ));
+        let hooks = withdrawal_hooks(
+            true,
+            vec![(&withdraw, Some(vec![0xab; 4])), (&plain_withdraw, None)],
+        )
+        .unwrap();
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].tx_hash, withdraw.hash());
+        assert_eq!(hooks[0].recipient, Address::repeat_byte(2));
+        assert_eq!(hooks[0].calldata, vec![0xab; 4]);
+
+        assert!(
+            withdrawal_hooks(false, std::iter::once((&withdraw, Some(vec![0xab; 4])))).is_err()
+        );
+        let empty = withdraw_with_l1_calldata(&[]);
+        assert!(withdrawal_hooks(true, std::iter::once((&empty, Some(Vec::new())))).is_err());
+        let too_large = vec![0; MAX_WITHDRAWAL_HOOK_CALLDATA_SIZE + 1];
+        let large = withdraw_with_l1_calldata(&too_large);
+        assert!(withdrawal_hooks(true, std::iter::once((&large, Some(too_large)))).is_err());
+        // The calldata must match the signed hash, and the signed hash requires the calldata.
+        assert!(withdrawal_hooks(true, std::iter::once((&withdraw, Some(vec![0xcd; 4])))).is_err());
+        assert!(withdrawal_hooks(
+            true,
+            std::iter::once((&plain_withdraw, Some(vec![0xab; 4])))
+        )
+        .is_err());
+        assert!(withdrawal_hooks(true, std::iter::once((&withdraw, None))).is_err());
+        // Hooks are not allowed for the other transactions, e.g. to call the transfer recipients.
+        let transfer = ZkSyncTx::from(Transfer::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(1),
+            BigUint::from(100u32),
+            BigUint::from(3u32),
+            Nonce(0),
+            Default::default(),
+            None,
+        ));
+        assert!(withdrawal_hooks(true, std::iter::once((&transfer, Some(vec![0xab])))).is_err());
+        // Disabled hooks don't affect the transactions without the calldata.
+        assert!(withdrawal_hooks(false, std::iter::once((&transfer, None)))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_withdrawal_hooks_signed() {
+        let signed_tx = |tx: ZkSyncTx, eth_signed: bool| SignedZkSyncTx {
+            tx,
+            eth_sign_data: eth_signed.then(|| EthSignData {
+                signature: TxEthSignature::EthereumSignature(
+                    PackedEthSignature::deserialize_packed(&[0; 65]).unwrap(),
+                ),
+                message: Vec::new(),
+            }),
+            created_at: Utc::now(),
+        };
+        let withdraw = withdraw_with_l1_calldata(&[0xab; 4]);
+
+        assert!(check_withdrawal_hooks_signed(&[signed_tx(withdraw.clone(), true)], false).is_ok());
+        // The batch signature covers the hashes of all the withdrawals in the batch.
+        assert!(check_withdrawal_hooks_signed(&[signed_tx(withdraw.clone(), false)], true).is_ok());
+        // Without the Ethereum signature, e.g. for the session keys, the hash isn't authorized.
+        assert!(check_withdrawal_hooks_signed(&[signed_tx(withdraw, false)], false).is_err());
+        // The withdrawals without the calldata don't need the Ethereum signature.
+        let plain_withdraw = ZkSyncTx::from(Withdraw::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(1),
+            BigUint::from(100u32),
+            BigUint::from(3u32),
+            Nonce(0),
+            Default::default(),
+            None,
+        ));
+        assert!(check_withdrawal_hooks_signed(&[signed_tx(plain_withdraw, false)], false).is_ok());
+    }
 }
@@ -0,0 +1,310 @@
+//! Policies applied to the withdrawals on the transactions submission, required by the regulated operators.
+//!
+//! Withdrawals are checked against the daily limit of the value withdrawn from an address and
+//! then approved by the external policy hook. Every decision is stored to the database, and
+//! the decision on a transaction is reused if the transaction is submitted again.
+
+// Built-in uses
+use std::collections::{hash_map::Entry, BTreeSet, HashMap};
+
+// External uses
+use chrono::Utc;
+use num::{rational::Ratio, BigUint};
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_config::configs::api::CommonApiConfig;
+use zksync_storage::{withdrawal_policy::records::WithdrawalPolicyDecision, StorageProcessor};
+use zksync_types::{tx::TxHash, Address, TokenId};
+use zksync_utils::{
+    biguint_to_big_decimal, ratio_to_scaled_u64, scaled_u64_to_ratio, BigUintSerdeAsRadix10Str,
+    UnsignedRatioSerializeAsDecimal,
+};
+
+// Local uses
+use crate::api_server::tx_sender::SubmitError;
+
+/// Withdrawal checked by the policies.
+#[derive(Debug, Clone)]
+pub struct PolicyWithdrawal {
+    pub tx_hash: TxHash,
+    pub from: Address,
+    pub to: Address,
+    pub token: TokenId,
+    pub amount: BigUint,
+    pub amount_usd: Ratio<BigUint>,
+}
+
+/// Request body of the policy hook.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PolicyHookRequest {
+    tx_hash: TxHash,
+    from: Address,
+    to: Address,
+    token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    amount: BigUint,
+    amount_usd: String,
+}
+
+/// Response of the policy hook. The reason is returned to the user if the withdrawal is rejected.
+#[derive(Debug, Deserialize)]
+struct PolicyHookResponse {
+    allowed: bool,
+    reason: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct PolicyHook {
+    url: String,
+    /// Whether the withdrawals are accepted if the hook is not available.
+    fail_open: bool,
+    client: reqwest::Client,
+}
+
+#[derive(Debug, Clone)]
+pub struct WithdrawalPolicy {
+    daily_limit_usd: Option<Ratio<BigUint>>,
+    hook: Option<PolicyHook>,
+}
+
+impl WithdrawalPolicy {
+    pub fn new(config: &CommonApiConfig) -> Self {
+        let hook = config
+            .withdrawal_policy_hook_url
+            .clone()
+            .map(|url| PolicyHook {
+                url,
+                fail_open: config.withdrawal_policy_hook_fail_open,
+                client: reqwest::Client::builder()
+                    .timeout(config.withdrawal_policy_hook_timeout())
+                    .build()
+                    .expect("failed to build the withdrawal policy hook client"),
+            });
+
+        Self {
+            daily_limit_usd: config.withdrawal_daily_limit_usd(),
+            hook,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.daily_limit_usd.is_some() || self.hook.is_some()
+    }
+
+    /// Checks the withdrawals of the single transaction or the batch and stores the decisions.
+    /// Withdrawals of the batch are either accepted or rejected all together.
+    ///
+    /// Withdrawals accepted here are counted to the daily limit even if the transaction
+    /// is rejected by the mempool afterwards.
+    pub async fn check_withdrawals(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        withdrawals: &[PolicyWithdrawal],
+    ) -> Result<(), SubmitError> {
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(SubmitError::internal)?;
+        // The checks of the withdrawals from the same address are serialized until the decisions
+        // are stored. The addresses are locked in the same order to avoid the deadlocks.
+        let addresses: BTreeSet<_> = withdrawals
+            .iter()
+            .map(|withdrawal| withdrawal.from)
+            .collect();
+        for address in addresses {
+            transaction
+                .withdrawal_policy_schema()
+                .lock_address(address)
+                .await
+                .map_err(SubmitError::internal)?;
+        }
+
+        let mut withdrawn_usd = HashMap::new();
+        let mut decisions = Vec::with_capacity(withdrawals.len());
+        for withdrawal in withdrawals {
+            let stored_decision = transaction
+                .withdrawal_policy_schema()
+                .get_decision(withdrawal.tx_hash)
+                .await
+                .map_err(SubmitError::internal)?;
+            // The transaction is submitted again, it's already counted to the daily limit.
+            if let Some(decision) = stored_decision {
+                decisions.push((decision.allowed, decision.reason));
+                continue;
+            }
+
+            let decision = match self
+                .check_daily_limit(&mut transaction, withdrawal, &mut withdrawn_usd)
+                .await?
+            {
+                Some(reason) => (false, Some(reason)),
+                None => self.call_hook(withdrawal).await,
+            };
+            decisions.push(decision);
+        }
+        let rejection = decisions
+            .iter()
+            .find(|(allowed, _)| !allowed)
+            .map(|(_, reason)| reason.clone().unwrap_or_default());
+
+        for (withdrawal, (allowed, reason)) in withdrawals.iter().zip(decisions) {
+            let decision = WithdrawalPolicyDecision {
+                tx_hash: withdrawal.tx_hash,
+                address: withdrawal.from,
+                token_id: withdrawal.token,
+                amount: biguint_to_big_decimal(withdrawal.amount.clone()),
+                amount_usd_scaled: ratio_to_scaled_u64(withdrawal.amount_usd.clone()),
+                allowed: allowed && rejection.is_none(),
+                reason,
+            };
+            transaction
+                .withdrawal_policy_schema()
+                .store_decision(&decision)
+                .await
+                .map_err(SubmitError::internal)?;
+        }
+        transaction.commit().await.map_err(SubmitError::internal)?;
+
+        match rejection {
+            Some(reason) => {
+                metrics::increment_counter!("withdrawal_policy.rejected_withdrawals");
+                Err(SubmitError::WithdrawalPolicy(reason))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the reason of the rejection if the daily limit of the sender is exceeded.
+    async fn check_daily_limit(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        withdrawal: &PolicyWithdrawal,
+        withdrawn_usd: &mut HashMap<Address, Ratio<BigUint>>,
+    ) -> Result<Option<String>, SubmitError> {
+        let daily_limit_usd = match &self.daily_limit_usd {
+            Some(daily_limit_usd) => daily_limit_usd,
+            None => return Ok(None),
+        };
+
+        // Earlier withdrawals of the same batch are taken into account as well.
+        let withdrawn = match withdrawn_usd.entry(withdrawal.from) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let withdrawn = storage
+                    .withdrawal_policy_schema()
+                    .get_withdrawn_usd(withdrawal.from, Utc::now() - chrono::Duration::days(1))
+                    .await
+                    .map_err(SubmitError::internal)?;
+                entry.insert(scaled_u64_to_ratio(withdrawn))
+            }
+        };
+        *withdrawn = &*withdrawn + &withdrawal.amount_usd;
+
+        if *withdrawn > *daily_limit_usd {
+            Ok(Some(format!(
+                "daily withdrawal limit of ${} is exceeded",
+                UnsignedRatioSerializeAsDecimal::serialize_to_str_with_dot(daily_limit_usd, 2)
+            )))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Asks the policy hook whether the withdrawal is allowed, returns the decision and its reason.
+    async fn call_hook(&self, withdrawal: &PolicyWithdrawal) -> (bool, Option<String>) {
+        let hook = match &self.hook {
+            Some(hook) => hook,
+            None => return (true, None),
+        };
+
+        let request = PolicyHookRequest {
+            tx_hash: withdrawal.tx_hash,
+            from: withdrawal.from,
+            to: withdrawal.to,
+            token: withdrawal.token,
+            amount: withdrawal.amount.clone(),
+            amount_usd: UnsignedRatioSerializeAsDecimal::serialize_to_str_with_dot(
+                &withdrawal.amount_usd,
+                6,
+            ),
+        };
+        let response = async {
+            hook.client
+                .post(&hook.url)
+                .json(&request)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<PolicyHookResponse>()
+                .await
+        }
+        .await;
+
+        match response {
+            Ok(response) if response.allowed => (true, response.reason),
+            Ok(response) => (
+                false,
+                Some(
+                    response
+                        .reason
+                        .unwrap_or_else(|| String::from("withdrawal is not allowed")),
+                ),
+            ),
+            Err(err) => {
+                vlog::warn!("Withdrawal policy hook is not available: {}", err);
+                metrics::increment_counter!("withdrawal_policy.hook_failures");
+                (
+                    hook.fail_open,
+                    Some(String::from("withdrawal policy check is not available")),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn policy_with_hook(fail_open: bool) -> WithdrawalPolicy {
+        WithdrawalPolicy {
+            daily_limit_usd: None,
+            hook: Some(PolicyHook {
+                // Nothing is expected to listen on this port.
+                url: String::from("http://127.0.0.1:1/check"),
+                fail_open,
+                client: reqwest::Client::builder()
+                    .timeout(Duration::from_secs(1))
+                    .build()
+                    .unwrap(),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn unavailable_hook() {
+        let withdrawal = PolicyWithdrawal {
+            tx_hash: TxHash::default(),
+            from: Address::repeat_byte(1),
+            to: Address::repeat_byte(2),
+            token: TokenId(0),
+            amount: BigUint::from(1000u32),
+            amount_usd: Ratio::from_integer(BigUint::from(1u32)),
+        };
+
+        let (allowed, reason) = policy_with_hook(true).call_hook(&withdrawal).await;
+        assert!(allowed);
+        assert!(reason.is_some());
+
+        let (allowed, reason) = policy_with_hook(false).call_hook(&withdrawal).await;
+        assert!(!allowed);
+        assert_eq!(
+            reason.as_deref(),
+            Some("withdrawal policy check is not available")
+        );
+    }
+}
@@ -119,7 +119,7 @@ async fn handle_graphql(
 }
 
 fn main() {
-    vlog::init();
+    let _vlog_guard = vlog::init();
 
     let runtime = actix_rt::System::new();
     let config = DevLiquidityTokenWatcherConfig::from_env();
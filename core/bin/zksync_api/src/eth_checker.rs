@@ -2,7 +2,12 @@
 //! onchain `ChangePubKey` authorization or EIP1271 signature
 //! verification.
 
-use web3::{contract::Options, types::Address};
+use std::time::{Duration, Instant};
+
+use web3::{
+    contract::Options,
+    types::{Address, BlockId, BlockNumber, H256},
+};
 use zksync_contracts::eip1271_contract;
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
 use zksync_types::{
@@ -10,18 +15,40 @@ use zksync_types::{
     {Nonce, PubKeyHash},
 };
 
+use crate::utils::shared_lru_cache::SharedLruCache;
+
 /// isValidSignature return value according to EIP1271 standard
 /// bytes4(keccak256("isValidSignature(bytes32,bytes)")
 pub const EIP1271_SUCCESS_RETURN_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
 
+/// Amount of the successful EIP1271 signature checks kept in the cache.
+const EIP1271_CACHE_SIZE: usize = 10_000;
+/// Cached EIP1271 signature checks are repeated after this time, since the wallet
+/// may revoke the signature, e.g. by changing its owner.
+const EIP1271_CACHE_TTL: Duration = Duration::from_secs(600);
+
+/// Successful `isValidSignature` call of the smart wallet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EIP1271Check {
+    /// Hash passed to the `isValidSignature` method.
+    pub message_hash: H256,
+    /// L1 block the wallet was called at.
+    pub l1_block_number: u64,
+}
+
 #[derive(Clone)]
 pub struct EthereumChecker {
     client: EthereumGateway,
+    /// Successful checks by the wallet address, the message hash and the signature.
+    eip1271_cache: SharedLruCache<(Address, H256, Vec<u8>), (EIP1271Check, Instant)>,
 }
 
 impl EthereumChecker {
     pub fn new(client: EthereumGateway) -> Self {
-        Self { client }
+        Self {
+            client,
+            eip1271_cache: SharedLruCache::new(EIP1271_CACHE_SIZE),
+        }
     }
 
     /// Transforms the message into an array expected by EIP-1271 standard.
@@ -34,22 +61,33 @@ impl EthereumChecker {
         tiny_keccak::keccak256(&bytes)
     }
 
-    pub async fn is_eip1271_signature_correct(
+    /// Checks the signature with the `isValidSignature` call of the smart wallet.
+    /// The call is pinned to the latest L1 block, so the check can be reproduced later.
+    /// Returns `None` if the signature is incorrect.
+    pub async fn check_eip1271_signature(
         &self,
         address: Address,
         message: &[u8],
-        signature: EIP1271Signature,
-    ) -> Result<bool, anyhow::Error> {
-        let sign_message = Self::get_sign_message(message);
+        signature: &EIP1271Signature,
+    ) -> Result<Option<EIP1271Check>, anyhow::Error> {
+        let message_hash = H256(Self::get_sign_message(message));
+        let cache_key = (address, message_hash, signature.0.clone());
+        if let Some((check, checked_at)) = self.eip1271_cache.get(&cache_key) {
+            if checked_at.elapsed() < EIP1271_CACHE_TTL {
+                metrics::increment_counter!("eth_checker.eip1271_cache_hit");
+                return Ok(Some(check));
+            }
+        }
 
+        let l1_block_number = self.client.block_number().await?.as_u64();
         let call_result = self
             .client
             .call_contract_function(
                 "isValidSignature",
-                (sign_message, signature.0),
+                (message_hash.0, signature.0.clone()),
                 Some(address),
                 Options::default(),
-                None,
+                Some(BlockId::Number(BlockNumber::Number(l1_block_number.into()))),
                 address,
                 eip1271_contract(),
             )
@@ -61,11 +99,20 @@ impl EthereumChecker {
                 // One error of this kind will mean that user provided incorrect signature.
                 // Many errors will likely mean that something is wrong with our implementation.
                 vlog::warn!("EIP1271 signature check failed: {:#?}", error);
-                return Ok(false);
+                return Ok(None);
             }
         };
+        if received != EIP1271_SUCCESS_RETURN_VALUE {
+            return Ok(None);
+        }
 
-        Ok(received == EIP1271_SUCCESS_RETURN_VALUE)
+        let check = EIP1271Check {
+            message_hash,
+            l1_block_number,
+        };
+        self.eip1271_cache
+            .insert(cache_key, (check, Instant::now()));
+        Ok(Some(check))
     }
 
     pub async fn is_new_pubkey_hash_authorized(
@@ -154,16 +201,26 @@ mod tests {
 
         let eth_checker = EthereumChecker::new(client);
 
-        let result = eth_checker
-            .is_eip1271_signature_correct(
+        let check = eth_checker
+            .check_eip1271_signature(
                 config.eip1271.contract_address,
                 message.as_bytes(),
-                signature,
+                &signature,
             )
             .await
-            .expect("Check failed");
+            .expect("Check failed")
+            .expect("Signature is incorrect");
 
-        assert!(result, "Signature is incorrect");
+        // The repeated check is served from the cache.
+        let cached_check = eth_checker
+            .check_eip1271_signature(
+                config.eip1271.contract_address,
+                message.as_bytes(),
+                &signature,
+            )
+            .await
+            .expect("Check failed");
+        assert_eq!(cached_check, Some(check));
     }
 
     /// This test checks that the actual signature data taken from
@@ -24,13 +24,14 @@ use tokio::time::Instant;
 
 // Workspace deps
 
-use zksync_config::configs::ticker::TokenPriceSource;
+use zksync_config::configs::{chain::Backpressure, ticker::TokenPriceSource};
 use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
-    gas_counter::GasCounter, tokens::ChangePubKeyFeeTypeArg, tx::ChangePubKeyType, Address,
-    BatchFee, ChangePubKeyOp, Fee, MintNFTOp, OutputFeeType, SwapOp, Token, TokenId, TokenLike,
-    TransferOp, TransferToNewOp, TxFeeTypes, WithdrawNFTOp, WithdrawOp,
+    gas_counter::GasCounter, gas_prediction::GasPredictor, runtime_config::SharedRuntimeConfig,
+    tokens::ChangePubKeyFeeTypeArg, tx::ChangePubKeyType, Address, BatchFee, ChangePubKeyOp, Fee,
+    MintNFTOp, OutputFeeType, SwapOp, Token, TokenId, TokenLike, TransferOp, TransferToNewOp,
+    TxFeeTypes, WithdrawNFTOp, WithdrawOp,
 };
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 
@@ -150,6 +151,14 @@ pub struct TickerConfig {
     pub scale_fee_coefficient: Ratio<BigUint>,
     pub max_blocks_to_aggregate: u32,
     pub subsidy_cpk_price_usd: Ratio<BigUint>,
+    /// Amount of unfinished prover jobs above which the prover is considered overloaded.
+    pub max_prover_queue_depth: u32,
+    /// Age of the oldest unproven block above which the prover is considered overloaded.
+    pub max_unproven_block_age: Duration,
+    /// Coefficient applied to the fees while the prover is overloaded.
+    pub prover_overload_fee_coefficient: Ratio<BigUint>,
+    /// Calibrated costs of the blocks, used to bound the cost of sealing the pending block early.
+    pub gas_predictor: GasPredictor,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -201,6 +210,8 @@ pub struct FeeTicker {
     info: Box<dyn FeeTickerInfo>,
     config: TickerConfig,
     validator: FeeTokenValidator,
+    /// Fee scale overridden at runtime, which takes precedence over the config.
+    runtime_config: SharedRuntimeConfig,
 }
 
 const CPK_CREATE2_FEE_TYPE: OutputFeeType = OutputFeeType::ChangePubKey(
@@ -262,13 +273,21 @@ impl FeeTicker {
             info,
             config,
             validator,
+            runtime_config: SharedRuntimeConfig::default(),
         }
     }
 
+    /// Sets the parameters overridden at runtime.
+    pub fn set_runtime_config(&mut self, runtime_config: SharedRuntimeConfig) {
+        self.runtime_config = runtime_config;
+    }
+
     pub fn new_with_default_validator(
         info: Box<dyn FeeTickerInfo>,
         config: zksync_config::TickerConfig,
         max_blocks_to_aggregate: u32,
+        prover_backpressure: Backpressure,
+        gas_predictor: GasPredictor,
         connection_pool: ConnectionPool,
     ) -> Self {
         let cache = (connection_pool, TokenDBCache::new(TOKEN_INVALIDATE_CACHE));
@@ -282,6 +301,13 @@ impl FeeTicker {
             ),
             max_blocks_to_aggregate,
             subsidy_cpk_price_usd: config.subsidy_cpk_price_usd(),
+            max_prover_queue_depth: prover_backpressure.max_prover_queue_depth,
+            max_unproven_block_age: prover_backpressure.max_unproven_block_age(),
+            prover_overload_fee_coefficient: Ratio::new(
+                BigUint::from(prover_backpressure.fee_scale_percent),
+                BigUint::from(100u32),
+            ),
+            gas_predictor,
         };
         let validator = FeeTokenValidator::new(
             cache,
@@ -301,6 +327,14 @@ impl FeeTicker {
         gas_price * BigUint::from(130u32) / BigUint::from(100u32)
     }
 
+    /// Returns the coefficient applied to the gas fees of the L2 operations.
+    fn scale_fee_coefficient(&self) -> Ratio<BigUint> {
+        match self.runtime_config.get().fee_scale_percent {
+            Some(percent) => Ratio::new(BigUint::from(percent), BigUint::from(100u32)),
+            None => self.config.scale_fee_coefficient.clone(),
+        }
+    }
+
     pub async fn get_token_price(
         &self,
         token: TokenLike,
@@ -334,9 +368,23 @@ impl FeeTicker {
         tx_type: TxFeeTypes,
         token: TokenLike,
         recipient: Address,
+    ) -> Result<ResponseFee, anyhow::Error> {
+        self.get_fee_with_extra_gas_in_wei(tx_type, token, recipient, BigUint::zero())
+            .await
+    }
+
+    /// Returns the fee of the transaction which makes the operator spend `extra_gas` on L1
+    /// in addition to the operation itself, e.g. for the call attached to the withdrawal.
+    pub async fn get_fee_with_extra_gas_in_wei(
+        &self,
+        tx_type: TxFeeTypes,
+        token: TokenLike,
+        recipient: Address,
+        extra_gas: BigUint,
     ) -> Result<ResponseFee, anyhow::Error> {
         let start = Instant::now();
-        let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone();
+        let prover_load_coefficient = self.prover_load_fee_coefficient().await;
+        let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone() * &prover_load_coefficient;
         let token = self.info.get_token(token).await?;
 
         let gas_price_wei = self.info.get_gas_price_wei().await?;
@@ -345,6 +393,7 @@ impl FeeTicker {
         let token_usd_risk = self.token_usd_risk(&token).await?;
 
         let (fee_type, gas_tx_amount, op_chunks) = self.gas_tx_amount(tx_type, recipient).await?;
+        let gas_tx_amount = gas_tx_amount + extra_gas;
 
         let zkp_fee = (zkp_cost_chunk * op_chunks) * &token_usd_risk;
         let mut normal_gas_fee =
@@ -358,8 +407,9 @@ impl FeeTicker {
                 | OutputFeeType::MintNFT
                 | OutputFeeType::Swap
         ) {
-            normal_gas_fee *= self.config.scale_fee_coefficient.clone();
+            normal_gas_fee *= self.scale_fee_coefficient();
         }
+        normal_gas_fee *= prover_load_coefficient;
 
         let normal_fee = Fee::new(
             fee_type,
@@ -416,9 +466,22 @@ impl FeeTicker {
         &self,
         token: TokenLike,
         txs: Vec<(TxFeeTypes, Address)>,
+    ) -> anyhow::Result<ResponseBatchFee> {
+        self.get_batch_with_extra_gas_in_wei(token, txs, BigUint::zero())
+            .await
+    }
+
+    /// Returns the fee of the batch which makes the operator spend `extra_gas` on L1
+    /// in addition to the operations themselves.
+    pub async fn get_batch_with_extra_gas_in_wei(
+        &self,
+        token: TokenLike,
+        txs: Vec<(TxFeeTypes, Address)>,
+        extra_gas: BigUint,
     ) -> anyhow::Result<ResponseBatchFee> {
         let start = Instant::now();
-        let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone();
+        let prover_load_coefficient = self.prover_load_fee_coefficient().await;
+        let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone() * &prover_load_coefficient;
 
         let token = self.info.get_token(token).await?;
 
@@ -427,9 +490,10 @@ impl FeeTicker {
         let wei_price_usd = self.wei_price_usd().await?;
         let token_usd_risk = self.token_usd_risk(&token).await?;
 
-        let mut total_normal_gas_tx_amount = Ratio::from(BigUint::zero());
+        let extra_gas = Ratio::from(extra_gas) * &prover_load_coefficient;
+        let mut total_normal_gas_tx_amount = extra_gas.clone();
         let mut total_op_chunks = Ratio::from(BigUint::zero());
-        let mut total_subsidized_gas_tx_amount = Ratio::from(BigUint::zero());
+        let mut total_subsidized_gas_tx_amount = extra_gas;
         let mut total_subsidized_op_chunks = Ratio::from(BigUint::zero());
 
         /*
@@ -478,10 +542,11 @@ impl FeeTicker {
                     | OutputFeeType::Swap
                     | OutputFeeType::MintNFT
             ) {
-                self.config.scale_fee_coefficient.clone() * gas_tx_amount
+                self.scale_fee_coefficient() * gas_tx_amount
             } else {
                 gas_tx_amount.into()
             };
+            let gas_tx_amount = gas_tx_amount * &prover_load_coefficient;
 
             total_normal_gas_tx_amount += &gas_tx_amount;
             total_op_chunks += &op_chunks;
@@ -525,6 +590,51 @@ impl FeeTicker {
         })
     }
 
+    /// Returns the cost of the given amount of L1 gas in the smallest units of the token,
+    /// e.g. to charge the gas spent by the server on behalf of the user.
+    pub async fn get_l1_gas_cost_in_token(
+        &self,
+        token: TokenLike,
+        gas_amount: u64,
+    ) -> anyhow::Result<BigUint> {
+        let start = Instant::now();
+        let token = self.info.get_token(token).await?;
+
+        let gas_price_wei = self.info.get_gas_price_wei().await?;
+        let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei);
+        let wei_price_usd = self.wei_price_usd().await?;
+        let token_usd_risk = self.token_usd_risk(&token).await?;
+
+        let cost = (wei_price_usd * BigUint::from(gas_amount) * scale_gas_price) * token_usd_risk;
+        metrics::histogram!("ticker.get_l1_gas_cost_in_token", start.elapsed());
+        Ok(cost.ceil().to_integer())
+    }
+
+    /// Returns the coefficient by which the fees are increased while the prover is overloaded,
+    /// so the users are discouraged from sending new transactions until the prover catches up.
+    /// Fees are not increased if the prover load can't be loaded.
+    async fn prover_load_fee_coefficient(&self) -> Ratio<BigUint> {
+        let prover_load = match self.info.prover_load().await {
+            Ok(prover_load) => prover_load,
+            Err(err) => {
+                vlog::warn!("Failed to load the prover load: {}", err);
+                return Ratio::from_integer(BigUint::from(1u32));
+            }
+        };
+        let overloaded = prover_load.queue_depth > self.config.max_prover_queue_depth
+            || prover_load.oldest_unproven_block_age > self.config.max_unproven_block_age;
+        metrics::gauge!(
+            "ticker.prover_overloaded",
+            if overloaded { 1.0 } else { 0.0 }
+        );
+
+        if overloaded {
+            self.config.prover_overload_fee_coefficient.clone()
+        } else {
+            Ratio::from_integer(BigUint::from(1u32))
+        }
+    }
+
     pub async fn wei_price_usd(&self) -> anyhow::Result<Ratio<BigUint>> {
         let start = Instant::now();
         let res = self
@@ -615,11 +725,16 @@ impl FeeTicker {
         let start = Instant::now();
         let future_blocks = self.info.blocks_in_future_aggregated_operations().await?;
         let remaining_pending_chunks = self.info.remaining_chunks_in_pending_block().await?;
+        // Sealing the pending block early can't cost more than the fixed cost of the whole block.
+        let max_additional_cost = self.config.gas_predictor.fixed_cost().total() as usize;
         let additional_cost = remaining_pending_chunks.map_or(0, |chunks| {
             if chunk_size > chunks {
                 0
             } else {
-                chunks * AMORTIZED_COST_PER_CHUNK as usize
+                std::cmp::min(
+                    chunks * AMORTIZED_COST_PER_CHUNK as usize,
+                    max_additional_cost,
+                )
             }
         });
 
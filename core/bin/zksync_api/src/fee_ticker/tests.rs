@@ -5,7 +5,7 @@ use bigdecimal::BigDecimal;
 use chrono::Utc;
 use futures::executor::block_on;
 use std::str::FromStr;
-use zksync_types::{Address, Token, TokenId, TokenKind, TokenPrice};
+use zksync_types::{gas_prediction::GasPredictor, Address, Token, TokenId, TokenKind, TokenPrice};
 use zksync_utils::{
     ratio_to_big_decimal, ratio_to_scaled_u64, scaled_u64_to_ratio, UnsignedRatioSerializeAsDecimal,
 };
@@ -16,7 +16,7 @@ use crate::fee_ticker::{
 };
 
 use super::*;
-use crate::fee_ticker::ticker_info::BlocksInFutureAggregatedOperations;
+use crate::fee_ticker::ticker_info::{BlocksInFutureAggregatedOperations, ProverLoad};
 
 const TEST_FAST_WITHDRAW_COEFF: f64 = 10.0;
 
@@ -114,6 +114,10 @@ pub fn get_test_ticker_config() -> TickerConfig {
         scale_fee_coefficient: Ratio::new(BigUint::from(150u32), BigUint::from(100u32)),
         max_blocks_to_aggregate: 5,
         subsidy_cpk_price_usd: scaled_u64_to_ratio(SUBSIDY_CPK_PRICE_USD_SCALED),
+        max_prover_queue_depth: 100,
+        max_unproven_block_age: Duration::from_secs(3600),
+        prover_overload_fee_coefficient: Ratio::new(BigUint::from(200u32), BigUint::from(100u32)),
+        gas_predictor: GasPredictor::default(),
     }
 }
 
@@ -130,6 +134,8 @@ impl FeeTickerAPI for MockApiProvider {
 struct MockTickerInfo {
     pub future_blocks: BlocksInFutureAggregatedOperations,
     pub remaining_chunks: Option<usize>,
+    /// Prover load, `None` if it can't be loaded.
+    pub prover_load: Option<ProverLoad>,
 }
 
 impl Default for MockTickerInfo {
@@ -141,6 +147,7 @@ impl Default for MockTickerInfo {
                 blocks_to_execute: 0,
             },
             remaining_chunks: None,
+            prover_load: Some(Default::default()),
         }
     }
 }
@@ -162,6 +169,12 @@ impl FeeTickerInfo for MockTickerInfo {
         Ok(self.remaining_chunks)
     }
 
+    async fn prover_load(&self) -> anyhow::Result<ProverLoad> {
+        self.prover_load
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Database is not available"))
+    }
+
     async fn get_last_token_price(&self, token: TokenLike) -> Result<TokenPrice, PriceError> {
         for test_token in TestToken::all_tokens() {
             if TokenLike::Id(test_token.id) == token {
@@ -711,3 +724,138 @@ fn test_zero_price_token_fee() {
     ))
     .unwrap_err();
 }
+
+#[test]
+fn test_prover_overload_fee() {
+    let validator = FeeTokenValidator::new(
+        TokenInMemoryCache::new(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+    );
+
+    let config = get_test_ticker_config();
+    #[allow(clippy::box_default)]
+    let mut ticker = FeeTicker::new(Box::new(MockTickerInfo::default()), config, validator);
+
+    let mut transfer_fee = |prover_load: Option<ProverLoad>| {
+        let mut info: Box<MockTickerInfo> = ticker.info.clone().into_any().downcast().unwrap();
+        info.prover_load = prover_load;
+        ticker.info = info;
+
+        get_normal_and_subsidy_fee(
+            &mut ticker,
+            TxFeeTypes::Transfer,
+            TokenId(0).into(),
+            Address::default(),
+            None,
+            None,
+        )
+        .0
+    };
+
+    let normal_fee = transfer_fee(Some(ProverLoad::default()));
+    // Both the queue depth and the unproven block age are checked.
+    let deep_queue_fee = transfer_fee(Some(ProverLoad {
+        queue_depth: 101,
+        oldest_unproven_block_age: Duration::from_secs(0),
+    }));
+    let old_block_fee = transfer_fee(Some(ProverLoad {
+        queue_depth: 0,
+        oldest_unproven_block_age: Duration::from_secs(3601),
+    }));
+    let unknown_load_fee = transfer_fee(None);
+
+    // Fee is increased according to the `prover_overload_fee_coefficient` in the test config.
+    assert!(deep_queue_fee > normal_fee);
+    assert_eq!(old_block_fee, deep_queue_fee);
+    // Fees are not increased if the prover load can't be loaded.
+    assert_eq!(unknown_load_fee, normal_fee);
+}
+
+#[test]
+fn test_extra_gas_fee() {
+    let validator = FeeTokenValidator::new(
+        TokenInMemoryCache::new(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+    );
+
+    let config = get_test_ticker_config();
+    #[allow(clippy::box_default)]
+    let ticker = FeeTicker::new(Box::new(MockTickerInfo::default()), config, validator);
+
+    let withdraw = vec![(TxFeeTypes::Withdraw, Address::default())];
+    let normal_fee = block_on(ticker.get_fee_from_ticker_in_wei(
+        TxFeeTypes::Withdraw,
+        TokenId(0).into(),
+        Address::default(),
+    ))
+    .unwrap()
+    .normal_fee;
+    let extra_gas_fee = block_on(ticker.get_fee_with_extra_gas_in_wei(
+        TxFeeTypes::Withdraw,
+        TokenId(0).into(),
+        Address::default(),
+        BigUint::from(300_000u32),
+    ))
+    .unwrap()
+    .normal_fee;
+    let batch_extra_gas_fee = block_on(ticker.get_batch_with_extra_gas_in_wei(
+        TokenId(0).into(),
+        withdraw,
+        BigUint::from(300_000u32),
+    ))
+    .unwrap()
+    .normal_fee;
+
+    // The extra gas is paid as the gas of the operation, the proof cost is the same.
+    assert_eq!(
+        extra_gas_fee.gas_tx_amount,
+        normal_fee.gas_tx_amount + BigUint::from(300_000u32)
+    );
+    assert!(extra_gas_fee.gas_fee > normal_fee.gas_fee);
+    assert_eq!(extra_gas_fee.zkp_fee, normal_fee.zkp_fee);
+    assert_eq!(extra_gas_fee.total_fee, batch_extra_gas_fee.total_fee);
+}
+
+#[test]
+fn test_runtime_fee_scale() {
+    let validator = FeeTokenValidator::new(
+        TokenInMemoryCache::new(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+    );
+
+    let config = get_test_ticker_config();
+    #[allow(clippy::box_default)]
+    let mut ticker = FeeTicker::new(Box::new(MockTickerInfo::default()), config, validator);
+    let runtime_config = SharedRuntimeConfig::default();
+    ticker.set_runtime_config(runtime_config.clone());
+
+    let mut transfer_fee = || {
+        get_normal_and_subsidy_fee(
+            &mut ticker,
+            TxFeeTypes::Transfer,
+            TokenId(0).into(),
+            Address::default(),
+            None,
+            None,
+        )
+        .0
+    };
+
+    // The test config scales the gas fee by 150%.
+    let config_fee = transfer_fee();
+    runtime_config.replace(zksync_types::runtime_config::RuntimeOverrides {
+        fee_scale_percent: Some(300),
+        ..Default::default()
+    });
+    let overridden_fee = transfer_fee();
+    assert!(overridden_fee > config_fee);
+
+    runtime_config.replace(Default::default());
+    assert_eq!(transfer_fee(), config_fee);
+}
@@ -5,13 +5,15 @@
 #[cfg(test)]
 use std::any::Any;
 
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 // External deps
 use anyhow::format_err;
 use async_trait::async_trait;
 use chrono::Utc;
 use num::rational::Ratio;
 use num::BigUint;
+use tokio::sync::Mutex;
 // Workspace deps
 use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
@@ -52,6 +54,9 @@ pub trait FeeTickerInfo: FeeTickerClone + Send + Sync + 'static {
 
     async fn remaining_chunks_in_pending_block(&self) -> anyhow::Result<Option<usize>>;
 
+    /// Get the current load of the prover.
+    async fn prover_load(&self) -> anyhow::Result<ProverLoad>;
+
     /// Get last price for token from ticker info
     async fn get_last_token_price(&self, token: TokenLike) -> Result<TokenPrice, PriceError>;
 
@@ -65,10 +70,15 @@ pub trait FeeTickerInfo: FeeTickerClone + Send + Sync + 'static {
     fn into_any(self: Box<Self>) -> Box<dyn Any>;
 }
 
+/// Interval of refreshing the cached prover load, so the fee requests don't query the prover jobs.
+const PROVER_LOAD_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
 #[derive(Clone)]
 pub struct TickerInfo {
     db: ConnectionPool,
     token_db_cache: TokenDBCache,
+    /// Last loaded prover load along with the moment it was loaded.
+    prover_load: Arc<Mutex<Option<(Instant, ProverLoad)>>>,
 }
 
 impl TickerInfo {
@@ -76,8 +86,31 @@ impl TickerInfo {
         Self {
             db,
             token_db_cache: Default::default(),
+            prover_load: Default::default(),
         }
     }
+
+    async fn load_prover_load(&self) -> anyhow::Result<ProverLoad> {
+        let start = Instant::now();
+        let mut storage = self.db.access_storage().await?;
+        let queue_depth = storage.prover_schema().pending_jobs_count().await?;
+        let oldest_unproven_block_age = storage
+            .prover_schema()
+            .oldest_unproven_block_timestamp()
+            .await?
+            .map(|timestamp| {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("failed to get system time");
+                now.saturating_sub(Duration::from_secs(timestamp))
+            })
+            .unwrap_or_default();
+        metrics::histogram!("ticker_info.prover_load", start.elapsed());
+        Ok(ProverLoad {
+            queue_depth,
+            oldest_unproven_block_age,
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -87,6 +120,14 @@ pub struct BlocksInFutureAggregatedOperations {
     pub blocks_to_execute: u32,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct ProverLoad {
+    /// Amount of unfinished prover jobs.
+    pub queue_depth: u32,
+    /// Age of the oldest unproven block.
+    pub oldest_unproven_block_age: Duration,
+}
+
 #[async_trait]
 impl FeeTickerInfo for TickerInfo {
     async fn is_account_new(&self, address: Address) -> anyhow::Result<bool> {
@@ -162,6 +203,21 @@ impl FeeTickerInfo for TickerInfo {
         Ok(remaining_chunks)
     }
 
+    async fn prover_load(&self) -> anyhow::Result<ProverLoad> {
+        // The lock is held while the load is refreshed, so concurrent requests wait for
+        // a single refresh instead of querying the database each.
+        let mut cached = self.prover_load.lock().await;
+        if let Some((loaded_at, prover_load)) = cached.as_ref() {
+            if loaded_at.elapsed() < PROVER_LOAD_REFRESH_INTERVAL {
+                return Ok(prover_load.clone());
+            }
+        }
+
+        let prover_load = self.load_prover_load().await?;
+        *cached = Some((Instant::now(), prover_load.clone()));
+        Ok(prover_load)
+    }
+
     /// Get last price from ticker
     async fn get_last_token_price(&self, token: TokenLike) -> Result<TokenPrice, PriceError> {
         let start = Instant::now();
@@ -276,6 +332,17 @@ impl TickerInfo {
             .await
             .map_err(|e| format_err!("Can't access storage: {}", e))?;
 
+        // The manual price set by the operator takes precedence until it expires.
+        let price_override = storage
+            .tokens_schema()
+            .get_ticker_price_override(token_id)
+            .await
+            .map_err(|e| format_err!("Can't load ticker price override from storage: {}", e))?;
+        if price_override.is_some() {
+            metrics::increment_counter!("ticker.price_overrides_used");
+            return Ok(price_override);
+        }
+
         let result = storage
             .tokens_schema()
             .get_historical_ticker_price(token_id)
@@ -17,12 +17,13 @@ use tokio::task::JoinHandle;
 
 // Workspace uses
 use zksync_eth_client::EthereumGateway;
+use zksync_storage::eip1271_verifications::records::EIP1271Verification;
 use zksync_types::{
-    tx::{error::TxAddError, EthBatchSignData, EthSignData, TxEthSignature},
+    tx::{error::TxAddError, EthBatchSignData, EthSignData, TxEthSignature, TxHash},
     Address, Order, SignedZkSyncTx, Token, ZkSyncTx,
 };
 // Local uses
-use crate::eth_checker::EthereumChecker;
+use crate::eth_checker::{EIP1271Check, EthereumChecker};
 use zksync_types::tx::TransactionError;
 
 /// `TxVariant` is used to form a verify request. It is possible to wrap
@@ -33,6 +34,9 @@ pub enum TxVariant {
     Batch(Vec<SignedZkSyncTx>, Option<EthBatchSignData>),
     Order(Box<Order>),
     Toggle2FA,
+    SessionKey,
+    Attestation,
+    NonceReservation,
 }
 
 /// Wrapper on a `TxVariant` which guarantees that (a batch of)
@@ -41,8 +45,11 @@ pub enum TxVariant {
 ///
 /// Underlying `TxVariant` is a private field, thus no such
 /// object can be created without verification.
+///
+/// EIP-1271 signatures of the transactions are verified by the L1 calls, the evidence
+/// of these calls is kept alongside to be stored once the transactions are accepted.
 #[derive(Debug, Clone)]
-pub struct VerifiedTx(TxVariant);
+pub struct VerifiedTx(TxVariant, Vec<EIP1271Verification>);
 
 impl VerifiedTx {
     /// Checks the (batch of) transaction(s) correctness by verifying its
@@ -51,17 +58,22 @@ impl VerifiedTx {
         request_data: RequestData,
         eth_checker: &EthereumChecker,
     ) -> Result<Self, TxAddError> {
-        verify_eth_signature(&request_data, eth_checker).await?;
+        let eip1271_verifications = verify_eth_signature(&request_data, eth_checker).await?;
         let mut tx_variant = request_data.get_tx_variant();
         verify_tx_correctness(&mut tx_variant)?;
 
-        Ok(Self(tx_variant))
+        Ok(Self(tx_variant, eip1271_verifications))
     }
 
     /// Creates a verified wrapper without actually verifying the original data.
     #[cfg(test)]
     pub(crate) fn unverified(inner: TxVariant) -> Self {
-        Self(inner)
+        Self(inner, Vec::new())
+    }
+
+    /// Returns the evidence of the EIP-1271 signatures of the transaction(s).
+    pub fn eip1271_verifications(&self) -> &[EIP1271Verification] {
+        &self.1
     }
 
     /// Takes the `TxVariant` out of the wrapper.
@@ -71,6 +83,11 @@ impl VerifiedTx {
             TxVariant::Batch(_, _) => panic!("called `unwrap_tx` on a `Batch` value"),
             TxVariant::Order(_) => panic!("called `unwrap_tx` on an `Order` value"),
             TxVariant::Toggle2FA => panic!("called `unwrap_tx` on an `Toggle2FA` value"),
+            TxVariant::SessionKey => panic!("called `unwrap_tx` on an `SessionKey` value"),
+            TxVariant::Attestation => panic!("called `unwrap_tx` on an `Attestation` value"),
+            TxVariant::NonceReservation => {
+                panic!("called `unwrap_tx` on an `NonceReservation` value")
+            }
         }
     }
 
@@ -81,15 +98,22 @@ impl VerifiedTx {
             TxVariant::Tx(_) => panic!("called `unwrap_batch` on a `Tx` value"),
             TxVariant::Order(_) => panic!("called `unwrap_batch` on an `Order` value"),
             TxVariant::Toggle2FA => panic!("called `unwrap_batch` on an `Toggle2FA` value"),
+            TxVariant::SessionKey => panic!("called `unwrap_batch` on an `SessionKey` value"),
+            TxVariant::Attestation => panic!("called `unwrap_batch` on an `Attestation` value"),
+            TxVariant::NonceReservation => {
+                panic!("called `unwrap_batch` on an `NonceReservation` value")
+            }
         }
     }
 }
 
 /// Verifies the Ethereum signature of the (batch of) transaction(s).
+/// Returns the evidence of the verified EIP-1271 signatures of the transactions.
 async fn verify_eth_signature(
     request_data: &RequestData,
     eth_checker: &EthereumChecker,
-) -> Result<(), TxAddError> {
+) -> Result<Vec<EIP1271Verification>, TxAddError> {
+    let mut eip1271_verifications = Vec::new();
     match request_data {
         RequestData::Tx(request) => {
             verify_eth_signature_single_tx(
@@ -97,6 +121,7 @@ async fn verify_eth_signature(
                 request.sender,
                 request.token.clone(),
                 eth_checker,
+                &mut eip1271_verifications,
             )
             .await?;
         }
@@ -109,14 +134,28 @@ async fn verify_eth_signature(
                 return Err(TxAddError::Other);
             }
             if let Some(batch_sign_data) = &request.batch_sign_data {
-                verify_eth_signature_txs_batch(txs, accounts, batch_sign_data, eth_checker).await?;
+                verify_eth_signature_txs_batch(
+                    txs,
+                    accounts,
+                    batch_sign_data,
+                    eth_checker,
+                    &mut eip1271_verifications,
+                )
+                .await?;
             }
             // In case there're signatures provided for some of transactions
             // we still verify them.
             for ((tx, &account), token) in
                 txs.iter().zip(accounts.iter()).zip(tokens.iter().cloned())
             {
-                verify_eth_signature_single_tx(tx, account, token, eth_checker).await?;
+                verify_eth_signature_single_tx(
+                    tx,
+                    account,
+                    token,
+                    eth_checker,
+                    &mut eip1271_verifications,
+                )
+                .await?;
             }
         }
         RequestData::Order(request) => {
@@ -126,7 +165,8 @@ async fn verify_eth_signature(
                 request.sender,
                 eth_checker,
             )
-            .await;
+            .await
+            .is_correct();
             if !signature_correct {
                 return Err(TxAddError::IncorrectEthSignature);
             }
@@ -138,14 +178,90 @@ async fn verify_eth_signature(
                 request.sender,
                 eth_checker,
             )
-            .await;
+            .await
+            .is_correct();
+            if !signature_correct {
+                return Err(TxAddError::IncorrectEthSignature);
+            }
+        }
+        RequestData::SessionKey(request) => {
+            let signature_correct = verify_ethereum_signature(
+                &request.sign_data.signature,
+                &request.sign_data.message,
+                request.sender,
+                eth_checker,
+            )
+            .await
+            .is_correct();
+            if !signature_correct {
+                return Err(TxAddError::IncorrectEthSignature);
+            }
+        }
+        RequestData::Attestation(request) => {
+            let signature_correct = verify_ethereum_signature(
+                &request.sign_data.signature,
+                &request.sign_data.message,
+                request.sender,
+                eth_checker,
+            )
+            .await
+            .is_correct();
+            if !signature_correct {
+                return Err(TxAddError::IncorrectEthSignature);
+            }
+        }
+        RequestData::NonceReservation(request) => {
+            let signature_correct = verify_ethereum_signature(
+                &request.sign_data.signature,
+                &request.sign_data.message,
+                request.sender,
+                eth_checker,
+            )
+            .await
+            .is_correct();
             if !signature_correct {
                 return Err(TxAddError::IncorrectEthSignature);
             }
         }
     }
 
-    Ok(())
+    Ok(eip1271_verifications)
+}
+
+/// Result of the Ethereum signature check.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SignatureCheck {
+    Incorrect,
+    Correct,
+    /// EIP-1271 signature confirmed by the smart wallet.
+    CorrectEIP1271(EIP1271Check),
+}
+
+impl SignatureCheck {
+    fn is_correct(&self) -> bool {
+        !matches!(self, Self::Incorrect)
+    }
+
+    /// Returns the evidence of the EIP-1271 signature of the transaction, if it's one.
+    fn eip1271_verification(
+        &self,
+        tx_hash: TxHash,
+        address: Address,
+        signature: &TxEthSignature,
+    ) -> Option<EIP1271Verification> {
+        match (self, signature) {
+            (Self::CorrectEIP1271(check), TxEthSignature::EIP1271Signature(signature)) => {
+                Some(EIP1271Verification {
+                    tx_hash,
+                    address,
+                    message_hash: check.message_hash,
+                    signature: signature.0.clone(),
+                    l1_block_number: check.l1_block_number,
+                })
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Given a single Ethereum signature and a message, checks that it
@@ -155,21 +271,25 @@ async fn verify_ethereum_signature(
     message: &[u8],
     sender_address: Address,
     eth_checker: &EthereumChecker,
-) -> bool {
+) -> SignatureCheck {
     let signer_account = match eth_signature {
         TxEthSignature::EthereumSignature(packed_signature) => {
             packed_signature.signature_recover_signer_from_raw_message(message)
         }
         TxEthSignature::EIP1271Signature(signature) => {
-            return eth_checker
-                .is_eip1271_signature_correct(sender_address, message, signature.clone())
+            return match eth_checker
+                .check_eip1271_signature(sender_address, message, signature)
                 .await
                 .expect("Unable to check EIP1271 signature")
+            {
+                Some(check) => SignatureCheck::CorrectEIP1271(check),
+                None => SignatureCheck::Incorrect,
+            };
         }
     };
     match signer_account {
-        Ok(address) => address == sender_address,
-        Err(_) => false,
+        Ok(address) if address == sender_address => SignatureCheck::Correct,
+        _ => SignatureCheck::Incorrect,
     }
 }
 
@@ -178,6 +298,7 @@ async fn verify_eth_signature_single_tx(
     sender_address: Address,
     token: Token,
     eth_checker: &EthereumChecker,
+    eip1271_verifications: &mut Vec<EIP1271Verification>,
 ) -> Result<(), TxAddError> {
     let start = Instant::now();
     // Check if the tx is a `ChangePubKey` operation without an Ethereum signature.
@@ -202,13 +323,13 @@ async fn verify_eth_signature_single_tx(
     // Check the signature.
     if let Some(sign_data) = &tx.eth_sign_data {
         let signature = &sign_data.signature;
-        let mut signature_correct =
+        let mut signature_check =
             verify_ethereum_signature(signature, &sign_data.message, sender_address, eth_checker)
                 .await;
-        if !signature_correct {
+        if !signature_check.is_correct() {
             let old_message = tx.get_old_ethereum_sign_message(token);
             if let Some(message) = old_message {
-                signature_correct = verify_ethereum_signature(
+                signature_check = verify_ethereum_signature(
                     signature,
                     message.as_bytes(),
                     sender_address,
@@ -217,9 +338,14 @@ async fn verify_eth_signature_single_tx(
                 .await;
             }
         }
-        if !signature_correct {
+        if !signature_check.is_correct() {
             return Err(TxAddError::IncorrectEthSignature);
         }
+        eip1271_verifications.extend(signature_check.eip1271_verification(
+            tx.hash(),
+            sender_address,
+            signature,
+        ));
     }
 
     metrics::histogram!(
@@ -234,6 +360,7 @@ async fn verify_eth_signature_txs_batch(
     senders: &[Address],
     batch_sign_data: &EthBatchSignData,
     eth_checker: &EthereumChecker,
+    eip1271_verifications: &mut Vec<EIP1271Verification>,
 ) -> Result<(), TxAddError> {
     let start = Instant::now();
     // Cache for verified senders.
@@ -257,16 +384,16 @@ async fn verify_eth_signature_txs_batch(
         // This block will set the `sender_correct` variable to `true` at the first match.
         let mut sender_correct = false;
         for signature in &batch_sign_data.signatures {
-            let mut signature_correct = verify_ethereum_signature(
+            let mut signature_check = verify_ethereum_signature(
                 signature,
                 &batch_sign_data.message,
                 *sender,
                 eth_checker,
             )
             .await;
-            if !signature_correct {
+            if !signature_check.is_correct() {
                 if let Some(old_message) = &old_message {
-                    signature_correct = verify_ethereum_signature(
+                    signature_check = verify_ethereum_signature(
                         signature,
                         old_message.as_slice(),
                         *sender,
@@ -275,7 +402,16 @@ async fn verify_eth_signature_txs_batch(
                     .await;
                 }
             }
-            if signature_correct {
+            if signature_check.is_correct() {
+                // The batch signature authorizes all the transactions of the sender.
+                eip1271_verifications.extend(
+                    txs.iter()
+                        .zip(senders)
+                        .filter(|(_, tx_sender)| *tx_sender == sender)
+                        .filter_map(|(tx, _)| {
+                            signature_check.eip1271_verification(tx.hash(), *sender, signature)
+                        }),
+                );
                 signers.insert(sender);
                 sender_correct = true;
                 break;
@@ -309,6 +445,9 @@ fn verify_tx_correctness(tx: &mut TxVariant) -> Result<(), TxAddError> {
             .check_correctness()
             .map_err(|err| TxAddError::IncorrectTx(TransactionError::OrderError(err)))?,
         TxVariant::Toggle2FA => {} // There is no data to check correctness of
+        TxVariant::SessionKey => {}
+        TxVariant::Attestation => {}
+        TxVariant::NonceReservation => {}
     }
     Ok(())
 }
@@ -346,6 +485,27 @@ pub struct Toggle2FARequest {
     pub sender: Address,
 }
 
+/// Registration or revocation of the session key, signed by the owner of the account.
+#[derive(Debug)]
+pub struct SessionKeyRequest {
+    pub sign_data: EthSignData,
+    pub sender: Address,
+}
+
+/// Attestation of the control of the address, signed by the address itself.
+#[derive(Debug)]
+pub struct AttestationRequest {
+    pub sign_data: EthSignData,
+    pub sender: Address,
+}
+
+/// Reservation of the account nonces, signed by the account owner.
+#[derive(Debug)]
+pub struct NonceReservationRequest {
+    pub sign_data: EthSignData,
+    pub sender: Address,
+}
+
 /// Request for the signature check.
 #[derive(Debug)]
 pub struct VerifySignatureRequest {
@@ -360,6 +520,9 @@ pub enum RequestData {
     Batch(BatchRequest),
     Order(OrderRequest),
     Toggle2FA(Toggle2FARequest),
+    SessionKey(SessionKeyRequest),
+    Attestation(AttestationRequest),
+    NonceReservation(NonceReservationRequest),
 }
 
 impl RequestData {
@@ -371,6 +534,9 @@ impl RequestData {
             }
             RequestData::Order(request) => TxVariant::Order(request.order.clone()),
             RequestData::Toggle2FA(_) => TxVariant::Toggle2FA,
+            RequestData::SessionKey(_) => TxVariant::SessionKey,
+            RequestData::Attestation(_) => TxVariant::Attestation,
+            RequestData::NonceReservation(_) => TxVariant::NonceReservation,
         }
     }
 }
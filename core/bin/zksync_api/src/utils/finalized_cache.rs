@@ -0,0 +1,259 @@
+//! Cache of the API results which don't change once the corresponding blocks are finalized,
+//! e.g. receipts of the finalized transactions, transactions of the old blocks or the list of tokens.
+//!
+//! Values are stored as JSON in the in-process LRU cache and, if configured, in Redis,
+//! so the cache can be shared between the API server instances. Keys include the finality
+//! of the value: values which may change once the next block is finalized are keyed by
+//! the last finalized block, so they are invalidated as soon as a new block is verified.
+
+// Built-in uses
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+// External uses
+use redis::aio::MultiplexedConnection;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::Mutex as TokioMutex;
+// Workspace uses
+use zksync_storage::{ConnectionPool, QueryResult};
+use zksync_types::BlockNumber;
+// Local uses
+use super::shared_lru_cache::SharedLruCache;
+
+/// Expiration time of the values keyed by the last finalized block in Redis.
+/// Such values can't be read once the next block is finalized, so they only waste memory.
+const LATEST_VALUES_TTL: Duration = Duration::from_secs(60 * 60);
+/// Expiration time of the finalized values in Redis.
+const FINALIZED_VALUES_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Returns the cache key of the request with the given query parameters.
+pub fn query_key(prefix: &str, query: &impl Serialize) -> String {
+    let query = serde_json::to_string(query).expect("Query parameters are always serializable");
+    format!("{}:{}", prefix, query)
+}
+
+/// Finality of the cached value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finality {
+    /// The value never changes, e.g. the receipt of the transaction from the finalized block.
+    Finalized,
+    /// The value may change once the next block is finalized, e.g. the list of tokens.
+    LastFinalizedBlock,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LastFinalizedBlock {
+    number: BlockNumber,
+    updated_at: Instant,
+}
+
+/// Shared cache backend, the connection is established on the first use
+/// and re-established after the failures.
+#[derive(Clone)]
+struct RedisCache {
+    client: redis::Client,
+    connection: Arc<TokioMutex<Option<MultiplexedConnection>>>,
+}
+
+impl fmt::Debug for RedisCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisCache")
+            .field("client", &self.client)
+            .finish()
+    }
+}
+
+impl RedisCache {
+    fn new(url: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            client: redis::Client::open(url)?,
+            connection: Arc::new(TokioMutex::new(None)),
+        })
+    }
+
+    async fn connection(&self) -> redis::RedisResult<MultiplexedConnection> {
+        let mut connection = self.connection.lock().await;
+        if let Some(connection) = connection.as_ref() {
+            return Ok(connection.clone());
+        }
+        let new_connection = self.client.get_multiplexed_tokio_connection().await?;
+        *connection = Some(new_connection.clone());
+        Ok(new_connection)
+    }
+
+    async fn get(&self, key: &str) -> redis::RedisResult<Option<String>> {
+        let mut connection = self.connection().await?;
+        let result = redis::cmd("GET")
+            .arg(key)
+            .query_async(&mut connection)
+            .await;
+        self.reset_on_error(result).await
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> redis::RedisResult<()> {
+        let mut connection = self.connection().await?;
+        let result = redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("EX")
+            .arg(ttl.as_secs())
+            .query_async(&mut connection)
+            .await;
+        self.reset_on_error(result).await
+    }
+
+    /// Drops the connection if the command has failed, so it's re-established on the next use.
+    async fn reset_on_error<T>(&self, result: redis::RedisResult<T>) -> redis::RedisResult<T> {
+        if result.is_err() {
+            *self.connection.lock().await = None;
+        }
+        result
+    }
+}
+
+/// Cache of the API results keyed by their finality.
+#[derive(Debug, Clone)]
+pub struct FinalizedCache {
+    pool: ConnectionPool,
+    /// Values which never change.
+    finalized: SharedLruCache<String, String>,
+    /// Values valid until the next block is finalized, cleared once it happens.
+    latest: SharedLruCache<String, String>,
+    last_finalized_block: Arc<Mutex<Option<LastFinalizedBlock>>>,
+    /// How often the last finalized block is reloaded from the database.
+    poll_interval: Duration,
+    redis: Option<RedisCache>,
+}
+
+impl FinalizedCache {
+    /// Creates a cache of the given capacity, the values are also stored to Redis if its URL is provided.
+    pub fn new(
+        pool: ConnectionPool,
+        capacity: usize,
+        poll_interval: Duration,
+        redis_url: Option<&str>,
+    ) -> Self {
+        let redis = redis_url.map(|url| RedisCache::new(url).expect("Invalid Redis URL"));
+        Self {
+            pool,
+            finalized: SharedLruCache::new(capacity),
+            latest: SharedLruCache::new(capacity),
+            last_finalized_block: Arc::new(Mutex::new(None)),
+            poll_interval,
+            redis,
+        }
+    }
+
+    /// Returns the number of the last finalized block. The number is reloaded from the database
+    /// at most once per poll interval, and once it changes, the values keyed by the previous
+    /// last finalized block are invalidated.
+    pub async fn last_finalized_block(&self) -> QueryResult<BlockNumber> {
+        let cached = *self.last_finalized_block.lock().unwrap();
+        if let Some(block) = cached {
+            if block.updated_at.elapsed() < self.poll_interval {
+                return Ok(block.number);
+            }
+        }
+
+        let number = self
+            .pool
+            .access_storage()
+            .await?
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+
+        let mut last_finalized_block = self.last_finalized_block.lock().unwrap();
+        if last_finalized_block.map(|block| block.number) != Some(number) {
+            self.latest.clear();
+        }
+        *last_finalized_block = Some(LastFinalizedBlock {
+            number,
+            updated_at: Instant::now(),
+        });
+        Ok(number)
+    }
+
+    /// Returns the cached value. Cache failures are not propagated and result in a miss.
+    pub async fn get<T: DeserializeOwned>(&self, finality: Finality, key: &str) -> Option<T> {
+        let key = self.full_key(finality, key).await?;
+        let local = match finality {
+            Finality::Finalized => &self.finalized,
+            Finality::LastFinalizedBlock => &self.latest,
+        };
+
+        let value = match local.get(&key) {
+            Some(value) => Some(value),
+            None => {
+                let value = self.get_from_redis(&key).await;
+                if let Some(value) = &value {
+                    local.insert(key.clone(), value.clone());
+                }
+                value
+            }
+        };
+
+        let hit = if value.is_some() { "true" } else { "false" };
+        metrics::increment_counter!("api.finalized_cache.requests", "hit" => hit);
+        value.and_then(|value| serde_json::from_str(&value).ok())
+    }
+
+    /// Stores the value with the given finality. The caller is responsible for the value
+    /// to actually have this finality, e.g. only receipts of the finalized transactions
+    /// may be stored as `Finality::Finalized`.
+    pub async fn insert<T: Serialize>(&self, finality: Finality, key: &str, value: &T) {
+        let key = match self.full_key(finality, key).await {
+            Some(key) => key,
+            None => return,
+        };
+        let value = match serde_json::to_string(value) {
+            Ok(value) => value,
+            Err(err) => {
+                vlog::warn!("Failed to serialize the cached value {}: {}", key, err);
+                return;
+            }
+        };
+
+        if let Some(redis) = &self.redis {
+            let ttl = match finality {
+                Finality::Finalized => FINALIZED_VALUES_TTL,
+                Finality::LastFinalizedBlock => LATEST_VALUES_TTL,
+            };
+            if let Err(err) = redis.set(&key, &value, ttl).await {
+                vlog::warn!("Failed to store the value {} to Redis: {}", key, err);
+                metrics::increment_counter!("api.finalized_cache.redis_errors");
+            }
+        }
+        match finality {
+            Finality::Finalized => self.finalized.insert(key, value),
+            Finality::LastFinalizedBlock => self.latest.insert(key, value),
+        }
+    }
+
+    async fn get_from_redis(&self, key: &str) -> Option<String> {
+        let redis = self.redis.as_ref()?;
+        redis.get(key).await.unwrap_or_else(|err| {
+            vlog::warn!("Failed to load the value {} from Redis: {}", key, err);
+            metrics::increment_counter!("api.finalized_cache.redis_errors");
+            None
+        })
+    }
+
+    /// Returns the key including the finality of the value, or `None`
+    /// if the last finalized block can't be loaded.
+    async fn full_key(&self, finality: Finality, key: &str) -> Option<String> {
+        match finality {
+            Finality::Finalized => Some(format!("zksync:finalized:{}", key)),
+            Finality::LastFinalizedBlock => match self.last_finalized_block().await {
+                Ok(block) => Some(format!("zksync:block_{}:{}", *block, key)),
+                Err(err) => {
+                    vlog::warn!("Failed to load the last finalized block: {}", err);
+                    None
+                }
+            },
+        }
+    }
+}
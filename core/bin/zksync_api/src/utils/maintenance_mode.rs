@@ -0,0 +1,47 @@
+// Built-in uses
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+// External uses
+use tokio::sync::RwLock;
+
+// Workspace uses
+use zksync_storage::{ConnectionPool, QueryResult};
+
+// Local uses
+
+/// Period after which the maintenance mode is reloaded from the database.
+const MAINTENANCE_MODE_UPDATE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Maintenance mode of the server set by the operator via the private API.
+/// It's reloaded periodically, so the transactions submission doesn't query
+/// the database every time.
+#[derive(Clone, Debug, Default)]
+pub struct MaintenanceModeCache(Arc<RwLock<Option<(Option<String>, Instant)>>>);
+
+impl MaintenanceModeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the reason of the maintenance if the maintenance mode is enabled.
+    pub async fn get(&self, pool: &ConnectionPool) -> QueryResult<Option<String>> {
+        if let Some((reason, updated_at)) = self.0.read().await.as_ref() {
+            if updated_at.elapsed() < MAINTENANCE_MODE_UPDATE_INTERVAL {
+                return Ok(reason.clone());
+            }
+        }
+
+        let reason = pool
+            .access_storage()
+            .await?
+            .misc_schema()
+            .load_maintenance_mode()
+            .await?
+            .map(|maintenance_mode| maintenance_mode.reason);
+        *self.0.write().await = Some((reason.clone(), Instant::now()));
+        Ok(reason)
+    }
+}
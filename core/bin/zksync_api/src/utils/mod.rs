@@ -1,2 +1,4 @@
 pub mod block_details_cache;
+pub mod finalized_cache;
+pub mod maintenance_mode;
 pub mod shared_lru_cache;
@@ -4,7 +4,7 @@ use zksync_gateway_watcher::MultiplexedGatewayWatcher;
 
 #[tokio::main]
 async fn main() {
-    vlog::init();
+    let _vlog_guard = vlog::init();
     let contracts = ContractsConfig::from_env();
     let eth_client_config = ETHClientConfig::from_env();
     let eth_sender_config = ETHSenderConfig::from_env();
@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use std::{cmp::max, time::Duration};
-use zksync_config::ChainConfig;
+use zksync_config::{configs::chain::ProofAggregationPolicy, ChainConfig};
 use zksync_crypto::proof::AggregatedProof;
 use zksync_storage::{
     chain::{block::BlockSchema, operations::OperationsSchema},
@@ -11,6 +11,7 @@ use zksync_types::{
     aggregated_operations::{
         AggregatedActionType, AggregatedOperation, BlocksCommitOperation,
         BlocksCreateProofOperation, BlocksExecuteOperation, BlocksProofOperation,
+        ProofAggregationTrigger,
     },
     block::Block,
     gas_counter::GasCounter,
@@ -75,19 +76,23 @@ fn create_new_commit_operation(
 
 fn create_new_create_proof_operation(
     new_blocks_with_proofs: &[Block],
-    available_aggregate_proof_sizes: &[usize],
+    policy: &ProofAggregationPolicy,
     current_time: DateTime<Utc>,
-    block_verify_deadline: Duration,
     _max_gas_for_tx: U256,
     fast_processing: bool,
-) -> Option<BlocksCreateProofOperation> {
-    let max_aggregate_size = available_aggregate_proof_sizes
+) -> Option<(BlocksCreateProofOperation, ProofAggregationTrigger)> {
+    if new_blocks_with_proofs.is_empty() {
+        return None;
+    }
+
+    let max_aggregate_size = policy
+        .proof_sizes
         .last()
         .cloned()
         .expect("should have at least one aggregate proof size");
 
     let any_block_verify_deadline_triggered = {
-        let block_verify_deadline = block_verify_deadline.as_secs() as i64;
+        let block_verify_deadline = policy.max_wait.as_secs() as i64;
         new_blocks_with_proofs
             .iter()
             .take(max_aggregate_size)
@@ -102,22 +107,28 @@ fn create_new_create_proof_operation(
             })
     };
 
-    let can_create_max_aggregate_proof = new_blocks_with_proofs.len() >= max_aggregate_size;
-
-    let should_create_aggregate_proof =
-        any_block_verify_deadline_triggered || can_create_max_aggregate_proof || fast_processing;
-
-    if !should_create_aggregate_proof {
+    let blocks_to_aggregate = std::cmp::min(new_blocks_with_proofs.len(), max_aggregate_size);
+    let min_fill_reached = blocks_to_aggregate as u64 * 100
+        >= max_aggregate_size as u64 * u64::from(policy.min_fill_percent);
+
+    let trigger = if blocks_to_aggregate == max_aggregate_size {
+        ProofAggregationTrigger::Full
+    } else if fast_processing {
+        ProofAggregationTrigger::FastProcessing
+    } else if any_block_verify_deadline_triggered {
+        ProofAggregationTrigger::Deadline
+    } else if min_fill_reached {
+        ProofAggregationTrigger::MinFill
+    } else {
         return None;
-    }
+    };
 
     // get max possible aggregate size
-    let aggregate_proof_size = available_aggregate_proof_sizes
+    let aggregate_proof_size = policy
+        .proof_sizes
         .iter()
         .rev()
-        .find(|aggregate_size| {
-            *aggregate_size >= &std::cmp::min(new_blocks_with_proofs.len(), max_aggregate_size)
-        })
+        .find(|aggregate_size| *aggregate_size >= &blocks_to_aggregate)
         .cloned()
         .expect("failed to find correct aggregate proof size");
 
@@ -131,10 +142,13 @@ fn create_new_create_proof_operation(
         .checked_sub(blocks.len())
         .expect("incorrect aggregate proof size");
 
-    Some(BlocksCreateProofOperation {
-        blocks,
-        proofs_to_pad,
-    })
+    Some((
+        BlocksCreateProofOperation {
+            blocks,
+            proofs_to_pad,
+        },
+        trigger,
+    ))
 }
 
 fn create_publish_proof_operation(
@@ -311,13 +325,15 @@ async fn create_aggregated_prover_task_storage(
 
     let create_proof_operation = create_new_create_proof_operation(
         &blocks_with_proofs,
-        &config.state_keeper.aggregated_proof_sizes,
+        &config.state_keeper.proof_aggregation_policy(),
         Utc::now(),
-        config.state_keeper.block_prove_deadline(),
         config.state_keeper.max_aggregated_tx_gas.into(),
         fast_processing_requested,
     );
-    let result = if let Some(operation) = create_proof_operation {
+    let result = if let Some((operation, trigger)) = create_proof_operation {
+        ProverSchema(&mut transaction)
+            .store_aggregated_proof_composition(&operation, trigger)
+            .await?;
         let aggregated_op = operation.into();
         log_aggregated_op_creation(&aggregated_op);
         OperationsSchema(&mut transaction)
@@ -475,3 +491,78 @@ fn log_aggregated_op_creation(aggregated_op: &AggregatedOperation) {
         last
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_crypto::Fr;
+    use zksync_types::{AccountId, H256};
+
+    use super::*;
+
+    const PROVE_DEADLINE: Duration = Duration::from_secs(60);
+
+    fn block(block_number: u32, timestamp: DateTime<Utc>) -> Block {
+        Block::new(
+            BlockNumber(block_number),
+            Fr::default(),
+            AccountId(0),
+            Vec::new(),
+            (0, 0),
+            10,
+            U256::default(),
+            U256::default(),
+            H256::default(),
+            timestamp.timestamp() as u64,
+        )
+    }
+
+    fn policy(min_fill_percent: u32) -> ProofAggregationPolicy {
+        ProofAggregationPolicy {
+            proof_sizes: vec![1, 4, 8],
+            max_wait: PROVE_DEADLINE,
+            min_fill_percent,
+        }
+    }
+
+    fn create_proof_operation(
+        blocks_count: u32,
+        block_age: Duration,
+        min_fill_percent: u32,
+    ) -> Option<(BlocksCreateProofOperation, ProofAggregationTrigger)> {
+        let now = Utc::now();
+        let created_at = now - chrono::Duration::from_std(block_age).unwrap();
+        let blocks: Vec<_> = (1..=blocks_count)
+            .map(|number| block(number, created_at))
+            .collect();
+
+        create_new_create_proof_operation(
+            &blocks,
+            &policy(min_fill_percent),
+            now,
+            U256::default(),
+            false,
+        )
+    }
+
+    /// Checks that the aggregated proof is created only if the policy allows it.
+    #[test]
+    fn create_proof_operation_policy() {
+        // Not enough blocks to fill the proof and the deadline is not reached.
+        assert!(create_proof_operation(3, Duration::from_secs(0), 100).is_none());
+        assert!(create_proof_operation(0, PROVE_DEADLINE * 2, 0).is_none());
+
+        let (operation, trigger) = create_proof_operation(9, Duration::from_secs(0), 100).unwrap();
+        assert_eq!(trigger, ProofAggregationTrigger::Full);
+        assert_eq!(operation.blocks.len(), 8);
+        assert_eq!(operation.proofs_to_pad, 0);
+
+        let (operation, trigger) = create_proof_operation(4, Duration::from_secs(0), 50).unwrap();
+        assert_eq!(trigger, ProofAggregationTrigger::MinFill);
+        assert_eq!(operation.aggregated_proof_size(), 8);
+        assert_eq!(operation.proofs_to_pad, 4);
+
+        let (operation, trigger) = create_proof_operation(3, PROVE_DEADLINE * 2, 50).unwrap();
+        assert_eq!(trigger, ProofAggregationTrigger::Deadline);
+        assert_eq!(operation.blocks.len(), 3);
+    }
+}
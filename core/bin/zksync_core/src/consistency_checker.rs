@@ -0,0 +1,124 @@
+//! The consistency checker periodically checks the invariants of the stored data
+//! which span several tables, e.g. that every executed transaction is included
+//! into the account history.
+//!
+//! Each run covers the blocks verified since the previous one. Blocks with violations
+//! are checked again on the next run, so the violations are reported until they are fixed.
+//! The amount of violations of each invariant is exported as a metric, and the check
+//! for an arbitrary range of blocks can be run on demand via the private API.
+
+// External uses
+use tokio::task::JoinHandle;
+
+// Workspace deps
+use zksync_config::DBConfig;
+use zksync_storage::{
+    consistency::{ConsistencyReport, Invariant},
+    ConnectionPool,
+};
+use zksync_types::BlockNumber;
+
+// Local deps
+use crate::scheduler::{run_scheduled_job, JobSchedule, ScheduledJob};
+
+/// Maximum amount of the violations of each invariant loaded by a single check.
+const VIOLATIONS_LIMIT: u32 = 100;
+
+struct ConsistencyChecker {
+    db_pool: ConnectionPool,
+    /// Amount of the last verified blocks checked by the first run.
+    depth: u32,
+    /// First block to be checked by the next run, `None` until the first run.
+    next_block: Option<BlockNumber>,
+}
+
+impl ConsistencyChecker {
+    fn new(config: &DBConfig, db_pool: ConnectionPool) -> Self {
+        Self {
+            db_pool,
+            depth: config.consistency_check_depth,
+            next_block: None,
+        }
+    }
+
+    async fn check(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.db_pool.access_storage().await?;
+        let from_block = match self.next_block {
+            Some(block) => block,
+            None => {
+                let last_verified_block = storage
+                    .chain()
+                    .block_schema()
+                    .get_last_verified_confirmed_block()
+                    .await?;
+                BlockNumber(last_verified_block.saturating_sub(self.depth).max(1))
+            }
+        };
+
+        let report = storage
+            .consistency_schema()
+            .check_invariants(from_block, VIOLATIONS_LIMIT)
+            .await?;
+        Self::report(&report);
+
+        self.next_block = Some(
+            report
+                .violations
+                .iter()
+                .map(|violation| violation.block_number)
+                .min()
+                .unwrap_or_else(|| (report.last_checked_block + 1).max(from_block)),
+        );
+        Ok(())
+    }
+
+    fn report(report: &ConsistencyReport) {
+        for invariant in [
+            Invariant::TxFilters,
+            Invariant::ExecutedBlocks,
+            Invariant::Balances,
+        ]
+        .iter()
+        {
+            let violations = report
+                .violations
+                .iter()
+                .filter(|violation| violation.invariant == *invariant)
+                .count();
+            metrics::gauge!("consistency.violations", violations as f64, "invariant" => invariant.name());
+        }
+        metrics::gauge!(
+            "consistency.last_checked_block",
+            *report.last_checked_block as f64
+        );
+
+        for violation in &report.violations {
+            vlog::error!(
+                "Consistency violation ({}) in the block {}: {}",
+                violation.invariant.name(),
+                violation.block_number,
+                violation.description
+            );
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ScheduledJob for ConsistencyChecker {
+    fn name(&self) -> &'static str {
+        "consistency_checker"
+    }
+
+    async fn run(&mut self) -> anyhow::Result<()> {
+        self.check().await
+    }
+}
+
+#[must_use]
+pub fn run_consistency_checker(config: &DBConfig, db_pool: ConnectionPool) -> JoinHandle<()> {
+    let checker = ConsistencyChecker::new(config, db_pool);
+    run_scheduled_job(
+        checker,
+        JobSchedule::new(config.consistency_check_interval()),
+    )
+}
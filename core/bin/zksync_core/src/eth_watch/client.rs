@@ -1,4 +1,4 @@
-use std::{convert::TryFrom, time::Instant};
+use std::{collections::HashMap, convert::TryFrom, time::Instant};
 
 use anyhow::format_err;
 use ethabi::Hash;
@@ -13,7 +13,7 @@ use web3::{
 use zksync_contracts::{governance_contract, zksync_contract};
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
 use zksync_types::{
-    Address, NewTokenEvent, Nonce, PriorityOp, RegisterNFTFactoryEvent, H160, U256,
+    Address, NewTokenEvent, Nonce, PriorityOp, RegisterNFTFactoryEvent, H160, H256, U256,
 };
 
 struct ContractTopics {
@@ -124,6 +124,34 @@ impl EthHttpClient {
             })
             .collect()
     }
+
+    /// Sets the senders of the Ethereum transactions of the priority operations,
+    /// the transaction is loaded once for all the operations it contains.
+    /// The sender is left unknown if the transaction can't be loaded.
+    pub(super) async fn load_l1_senders(&self, priority_ops: &mut [PriorityOp]) {
+        let mut senders: HashMap<H256, Option<Address>> = HashMap::new();
+        for priority_op in priority_ops {
+            let l1_sender = match senders.get(&priority_op.eth_hash) {
+                Some(l1_sender) => *l1_sender,
+                None => {
+                    let l1_sender = match self.client.get_tx(priority_op.eth_hash).await {
+                        Ok(tx) => tx.and_then(|tx| tx.from),
+                        Err(err) => {
+                            vlog::warn!(
+                                "Failed to load the L1 sender of the priority operation {}: {}",
+                                priority_op.serial_id,
+                                err
+                            );
+                            None
+                        }
+                    };
+                    senders.insert(priority_op.eth_hash, l1_sender);
+                    l1_sender
+                }
+            };
+            priority_op.l1_sender = l1_sender;
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -188,7 +216,9 @@ impl EthClient for EthHttpClient {
         }
 
         metrics::histogram!("eth_watcher.get_priority_op_events", start.elapsed());
-        result
+        let mut priority_ops = result?;
+        self.load_l1_senders(&mut priority_ops).await;
+        Ok(priority_ops)
     }
 
     async fn get_new_register_nft_factory_events(
@@ -29,6 +29,7 @@ use zksync_types::{NewTokenEvent, PriorityOp, RegisterNFTFactoryEvent, SerialId}
 
 // Local deps
 use self::{client::EthClient, eth_state::ETHState, received_ops::sift_outdated_ops};
+use crate::priority_op_deadlines::PriorityOpDeadlineTracker;
 
 mod client;
 mod eth_state;
@@ -84,6 +85,8 @@ pub struct EthWatch<W: EthClient> {
     /// All ethereum events are accepted after sufficient confirmations to eliminate risk of block reorg.
     number_of_confirmations_for_event: u64,
     mode: WatcherMode,
+    /// Last L1 block shared with the state keeper to process the priority operations before their deadlines.
+    deadline_tracker: PriorityOpDeadlineTracker,
 }
 
 impl<W: EthClient> EthWatch<W> {
@@ -98,9 +101,14 @@ impl<W: EthClient> EthWatch<W> {
             eth_state: ETHState::default(),
             mode: WatcherMode::Working,
             number_of_confirmations_for_event,
+            deadline_tracker: PriorityOpDeadlineTracker::default(),
         }
     }
 
+    pub fn set_deadline_tracker(&mut self, deadline_tracker: PriorityOpDeadlineTracker) {
+        self.deadline_tracker = deadline_tracker;
+    }
+
     /// Atomically replaces the stored Ethereum state.
     fn set_new_state(&mut self, new_state: ETHState) {
         self.eth_state = new_state;
@@ -324,6 +332,7 @@ impl<W: EthClient> EthWatch<W> {
     async fn poll_eth_node(&mut self) -> anyhow::Result<()> {
         let start = Instant::now();
         let last_block_number = self.client.block_number().await?;
+        self.deadline_tracker.set_last_eth_block(last_block_number);
 
         if last_block_number > self.eth_state.last_ethereum_block() {
             self.process_new_blocks(last_block_number).await?;
@@ -389,6 +398,7 @@ impl<W: EthClient> EthWatch<W> {
         // can interact with the node again. We're not expecting the rate limiting to be applied
         // immediately after that, thus any error on this stage is considered critical and
         // irrecoverable.
+        self.deadline_tracker.set_last_eth_block(block);
         self.restore_state_from_eth(block)
             .await
             .expect("Unable to restore ETHWatcher state");
@@ -448,6 +458,7 @@ pub async fn start_eth_watch(
     contract_config: &ContractsConfig,
     eth_watcher_config: &ETHWatchConfig,
     mempool_req_sender: mpsc::Sender<MempoolTransactionRequest>,
+    deadline_tracker: PriorityOpDeadlineTracker,
 ) -> JoinHandle<()> {
     let eth_client = EthHttpClient::new(
         eth_gateway,
@@ -460,6 +471,7 @@ pub async fn start_eth_watch(
         mempool_req_sender,
         eth_watcher_config.confirmations_for_eth_event,
     );
+    eth_watch.set_deadline_tracker(deadline_tracker);
 
     eth_watch.restore_from_eth_using_latest_block_number().await;
 
@@ -3,7 +3,7 @@ use std::cmp::max;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use web3::types::{Address, BlockNumber};
+use web3::types::{Address, BlockNumber, Transaction};
 
 use zksync_types::{
     AccountId, Deposit, FullExit, NewTokenEvent, Nonce, PriorityOp, RegisterNFTFactoryEvent,
@@ -13,10 +13,14 @@ use zksync_types::{
 use futures::channel::mpsc;
 use futures::StreamExt;
 use tokio::sync::RwLock;
+use zksync_eth_client::{clients::mock::MockEthereum, EthereumGateway};
 use zksync_mempool::MempoolTransactionRequest;
 
 use super::is_missing_priority_op_error;
-use crate::eth_watch::{client::EthClient, EthWatch};
+use crate::eth_watch::{
+    client::{EthClient, EthHttpClient},
+    EthWatch,
+};
 
 struct FakeEthClientData {
     priority_ops: HashMap<u64, Vec<PriorityOp>>,
@@ -142,9 +146,12 @@ async fn fake_mempool(
 ) {
     while let Some(a) = receiver.next().await {
         match a {
-            MempoolTransactionRequest::NewTx(_, _) => {
+            MempoolTransactionRequest::NewTx(_, _, _) => {
                 unreachable!()
             }
+            MempoolTransactionRequest::NewSponsoredTx(_, _, _, _) => unreachable!(),
+            MempoolTransactionRequest::NewFeeFreeTx(_, _, _, _, _) => unreachable!(),
+            MempoolTransactionRequest::NewWithdrawalTx(_, _, _, _) => unreachable!(),
             MempoolTransactionRequest::NewPriorityOps(ops, conf, channel) => {
                 for op in &ops {
                     let mut lock = data.write().await;
@@ -152,7 +159,7 @@ async fn fake_mempool(
                 }
                 channel.send(Ok(())).unwrap_or_default()
             }
-            MempoolTransactionRequest::NewTxsBatch(_, _, _) => unreachable!(),
+            MempoolTransactionRequest::NewTxsBatch(_, _, _, _, _) => unreachable!(),
         }
     }
 }
@@ -180,6 +187,7 @@ async fn test_operation_queues() {
             eth_hash: [2; 32].into(),
             eth_block: 3,
             eth_block_index: Some(1),
+            l1_sender: None,
         },
         PriorityOp {
             serial_id: 1,
@@ -193,6 +201,7 @@ async fn test_operation_queues() {
             eth_hash: [3; 32].into(),
             eth_block: 4,
             eth_block_index: Some(1),
+            l1_sender: None,
         },
         PriorityOp {
             serial_id: 2,
@@ -206,6 +215,7 @@ async fn test_operation_queues() {
             eth_block: 4,
             eth_hash: [4; 32].into(),
             eth_block_index: Some(2),
+            l1_sender: None,
         },
     ];
 
@@ -265,6 +275,7 @@ async fn test_operation_queues_time_lag() {
                 eth_hash: [2; 32].into(),
                 eth_block: 1, // <- First operation goes to the first block.
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
             PriorityOp {
                 serial_id: 1,
@@ -278,6 +289,7 @@ async fn test_operation_queues_time_lag() {
                 eth_hash: [3; 32].into(),
                 eth_block: 100, // <-- Note 100th block, it will set the network block to 100.
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
             PriorityOp {
                 serial_id: 2,
@@ -291,6 +303,7 @@ async fn test_operation_queues_time_lag() {
                 eth_hash: [3; 32].into(),
                 eth_block: 110, // <-- This operation will get to the unconfirmed queue.
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
         ])
         .await;
@@ -338,6 +351,7 @@ async fn test_restore_and_poll() {
                 eth_hash: [2; 32].into(),
                 eth_block: 4,
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
             PriorityOp {
                 serial_id: 1,
@@ -351,6 +365,7 @@ async fn test_restore_and_poll() {
                 eth_hash: [3; 32].into(),
                 eth_block: 3,
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
         ])
         .await;
@@ -371,6 +386,7 @@ async fn test_restore_and_poll() {
                 eth_hash: [2; 32].into(),
                 eth_block: 5,
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
             PriorityOp {
                 serial_id: 4,
@@ -384,6 +400,7 @@ async fn test_restore_and_poll() {
                 eth_hash: [3; 32].into(),
                 eth_block: 5,
                 eth_block_index: Some(2),
+                l1_sender: None,
             },
         ])
         .await;
@@ -423,6 +440,7 @@ async fn test_restore_and_poll_time_lag() {
                 eth_hash: [2; 32].into(),
                 eth_block: 1,
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
             PriorityOp {
                 serial_id: 1,
@@ -436,6 +454,7 @@ async fn test_restore_and_poll_time_lag() {
                 eth_hash: [3; 32].into(),
                 eth_block: 100,
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
         ])
         .await;
@@ -471,6 +490,7 @@ async fn test_serial_id_gaps() {
                 eth_hash: [2; 32].into(),
                 eth_block: 1,
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
             PriorityOp {
                 serial_id: 1,
@@ -479,6 +499,7 @@ async fn test_serial_id_gaps() {
                 eth_hash: [3; 32].into(),
                 eth_block: 1,
                 eth_block_index: Some(2),
+                l1_sender: None,
             },
         ])
         .await;
@@ -507,6 +528,7 @@ async fn test_serial_id_gaps() {
                 eth_hash: [2; 32].into(),
                 eth_block: 2,
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
             PriorityOp {
                 serial_id: 4, // Then next id is expected to be 3.
@@ -515,6 +537,7 @@ async fn test_serial_id_gaps() {
                 eth_hash: [3; 32].into(),
                 eth_block: 2,
                 eth_block_index: Some(3),
+                l1_sender: None,
             },
         ])
         .await;
@@ -539,6 +562,7 @@ async fn test_serial_id_gaps() {
             eth_hash: [2; 32].into(),
             eth_block: 2,
             eth_block_index: Some(2),
+            l1_sender: None,
         }])
         .await;
     watcher.poll_eth_node().await.unwrap();
@@ -547,3 +571,49 @@ async fn test_serial_id_gaps() {
     assert_eq!(watcher.eth_state.last_ethereum_block_backup(), 0);
     assert_eq!(watcher.eth_state.last_ethereum_block(), 3);
 }
+
+/// Checks that the priority operation is kept with the unknown L1 sender
+/// if its Ethereum transaction can't be loaded.
+#[tokio::test]
+async fn test_l1_sender_lookup_failure() {
+    let l1_sender = Address::repeat_byte(0x01);
+    let mut ethereum = MockEthereum::default();
+    ethereum
+        .add_tx(Transaction {
+            hash: H256::repeat_byte(0x02),
+            from: Some(l1_sender),
+            ..Default::default()
+        })
+        .await;
+    let client = EthHttpClient::new(
+        EthereumGateway::Mock(ethereum),
+        Default::default(),
+        Default::default(),
+    );
+
+    let deposit = ZkSyncPriorityOp::Deposit(Deposit {
+        from: Default::default(),
+        token: TokenId(0),
+        amount: Default::default(),
+        to: Default::default(),
+    });
+    let mut priority_ops: Vec<_> = [0x02, 0x03, 0x02]
+        .iter()
+        .enumerate()
+        .map(|(serial_id, byte)| PriorityOp {
+            serial_id: serial_id as SerialId,
+            data: deposit.clone(),
+            deadline_block: 0,
+            eth_hash: H256::repeat_byte(*byte),
+            eth_block: 1,
+            eth_block_index: Some(serial_id as u64),
+            l1_sender: None,
+        })
+        .collect();
+    client.load_l1_senders(&mut priority_ops).await;
+
+    assert_eq!(priority_ops.len(), 3);
+    assert_eq!(priority_ops[0].l1_sender, Some(l1_sender));
+    assert_eq!(priority_ops[1].l1_sender, None);
+    assert_eq!(priority_ops[2].l1_sender, Some(l1_sender));
+}
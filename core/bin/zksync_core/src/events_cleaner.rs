@@ -0,0 +1,51 @@
+//! The cleaner is responsible for enforcing the retention policy of the event log:
+//! events stored earlier than the configured amount of hours ago are removed from the database.
+//! The cleaner is opt-in (the `events-cleaner` server component), events are kept forever otherwise.
+//!
+//! Consumers replaying the event log are expected to keep up within the retention period,
+//! requests for the removed events are rejected by the API.
+
+// External uses
+use tokio::task::JoinHandle;
+
+// Workspace deps
+use zksync_config::DBConfig;
+use zksync_storage::ConnectionPool;
+
+// Local deps
+use crate::scheduler::{run_scheduled_job, JobSchedule, ScheduledJob};
+
+struct EventsCleaner {
+    db_pool: ConnectionPool,
+    max_age: chrono::Duration,
+}
+
+#[async_trait::async_trait]
+impl ScheduledJob for EventsCleaner {
+    fn name(&self) -> &'static str {
+        "events_cleaner"
+    }
+
+    async fn run(&mut self) -> anyhow::Result<()> {
+        let removed = self
+            .db_pool
+            .access_storage()
+            .await?
+            .event_schema()
+            .remove_old_events(self.max_age)
+            .await?;
+        if removed > 0 {
+            vlog::info!("Removed {} old events", removed);
+        }
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn run_events_cleaner(config: &DBConfig, db_pool: ConnectionPool) -> JoinHandle<()> {
+    let cleaner = EventsCleaner {
+        db_pool,
+        max_age: chrono::Duration::from_std(config.events_max_age()).unwrap(),
+    };
+    run_scheduled_job(cleaner, JobSchedule::new(config.events_cleaner_interval()))
+}
@@ -0,0 +1,121 @@
+//! Leader election between the core server instances.
+//!
+//! Several instances of the core server can be run at the same time, but only the leader
+//! runs the state keeper, the Ethereum sender and the other core actors changing the chain state.
+//! The rest of the instances stay in the standby mode: they serve the private API and wait to take
+//! over the leadership, without writing anything to the database.
+//!
+//! The leadership is held via the Postgres advisory lock taken by a dedicated connection, so it's
+//! released as soon as the leader's session is closed. The leader pings its session periodically
+//! and stops the instance if the session is lost, after which one of the standby instances takes
+//! the lock and restores the state keeper from the database. Every leadership change is recorded
+//! in the database and served by the private API.
+
+// Built-in uses
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+// External uses
+use chrono::{DateTime, Utc};
+use tokio::{sync::watch, task::JoinHandle, time};
+
+// Workspace deps
+use zksync_api_types::private::LeadershipRole;
+use zksync_storage::StorageProcessor;
+
+/// Handle to the leadership of the server instance.
+#[derive(Debug, Clone)]
+pub struct Leadership {
+    instance_id: String,
+    role: Arc<RwLock<(LeadershipRole, DateTime<Utc>)>>,
+    is_leader: watch::Receiver<bool>,
+}
+
+impl Leadership {
+    /// Starts the leader election for this instance.
+    /// Returns the handle to the leadership along with the election task.
+    #[must_use]
+    pub fn start(check_interval: Duration) -> (Self, JoinHandle<()>) {
+        let instance_id = format!(
+            "{}-{}",
+            std::env::var("HOSTNAME").unwrap_or_else(|_| "core".to_string()),
+            std::process::id()
+        );
+        let (is_leader_sender, is_leader) = watch::channel(false);
+        let leadership = Self {
+            instance_id,
+            role: Arc::new(RwLock::new((LeadershipRole::Standby, Utc::now()))),
+            is_leader,
+        };
+        metrics::gauge!("leadership.is_leader", 0.0);
+
+        let task = tokio::spawn(leadership.clone().run(check_interval, is_leader_sender));
+        (leadership, task)
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    /// Returns the current role of the instance and the time it was taken.
+    pub fn role(&self) -> (LeadershipRole, DateTime<Utc>) {
+        *self.role.read().expect("leadership lock is poisoned")
+    }
+
+    /// Waits until this instance becomes the leader.
+    pub async fn wait_for_leadership(&self) {
+        let mut is_leader = self.is_leader.clone();
+        while !*is_leader.borrow() {
+            is_leader
+                .changed()
+                .await
+                .expect("leader election task is stopped");
+        }
+    }
+
+    async fn run(self, check_interval: Duration, is_leader_sender: watch::Sender<bool>) {
+        vlog::info!("Instance {} is in the standby mode", self.instance_id);
+        // The lock is held by the connection, so it's not taken from the pool.
+        let mut storage = loop {
+            match Self::try_acquire().await {
+                Ok(Some(storage)) => break storage,
+                Ok(None) => {}
+                Err(e) => vlog::warn!("Can't take over the leadership {:?}", e),
+            }
+            time::sleep(check_interval).await;
+        };
+
+        if let Err(e) = storage
+            .leadership_schema()
+            .record_leadership_change(&self.instance_id)
+            .await
+        {
+            vlog::warn!("Can't record the leadership change {:?}", e);
+        }
+        *self.role.write().expect("leadership lock is poisoned") =
+            (LeadershipRole::Leader, Utc::now());
+        metrics::gauge!("leadership.is_leader", 1.0);
+        vlog::info!("Instance {} became the leader", self.instance_id);
+        is_leader_sender
+            .send(true)
+            .expect("leadership handles are dropped");
+
+        let mut timer = time::interval(check_interval);
+        loop {
+            timer.tick().await;
+            // Another instance may take over the leadership as soon as the session is lost,
+            // so the instance must stop performing the leader duties.
+            if let Err(e) = storage.leadership_schema().ping().await {
+                panic!("Instance {} lost the leadership: {:?}", self.instance_id, e);
+            }
+        }
+    }
+
+    async fn try_acquire() -> anyhow::Result<Option<StorageProcessor<'static>>> {
+        let mut storage = StorageProcessor::establish_connection().await?;
+        if storage.leadership_schema().try_acquire_leadership().await? {
+            Ok(Some(storage))
+        } else {
+            Ok(None)
+        }
+    }
+}
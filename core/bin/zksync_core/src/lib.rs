@@ -1,10 +1,17 @@
+use crate::leadership::Leadership;
 use crate::register_factory_handler::run_register_factory_handler;
 use crate::state_keeper::ZkSyncStateInitParams;
 use crate::{
     committer::run_committer,
     eth_watch::start_eth_watch,
-    state_keeper::{start_root_hash_calculator, start_state_keeper, ZkSyncStateKeeper},
+    priority_op_deadlines::PriorityOpDeadlineTracker,
+    prover_backpressure::{run_prover_backpressure_monitor, ProverBackpressure},
+    state_keeper::{
+        start_root_hash_calculator, start_state_keeper, ExecutionTracing, ZkSyncStateKeeper,
+    },
     token_handler::run_token_handler,
+    tx_callback_sender::run_tx_callback_sender,
+    webhook_sender::{run_outbox_cleaner, run_webhook_sender},
 };
 use futures::{channel::mpsc, future};
 use tokio::task::JoinHandle;
@@ -12,17 +19,30 @@ use zksync_config::{ChainConfig, ZkSyncConfig};
 use zksync_eth_client::EthereumGateway;
 use zksync_mempool::{run_mempool_block_handler, run_mempool_tx_handler};
 use zksync_storage::ConnectionPool;
-use zksync_types::{tokens::get_genesis_token_list, Token, TokenId, TokenKind};
+use zksync_types::{
+    runtime_config::SharedRuntimeConfig, tokens::get_genesis_token_list, Token, TokenId, TokenKind,
+};
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
 
 pub mod committer;
+pub mod consistency_checker;
 pub mod eth_watch;
+pub mod events_cleaner;
+pub mod leadership;
+pub mod priority_op_deadlines;
+pub mod prover_backpressure;
+pub mod pruner;
 pub mod register_factory_handler;
 pub mod rejected_tx_cleaner;
+pub mod runtime_config;
+pub mod scheduler;
+pub mod slow_queries_cleaner;
 pub mod state_keeper;
 pub mod token_handler;
+pub mod tx_callback_sender;
 pub mod tx_event_emitter;
+pub mod webhook_sender;
 
 mod genesis;
 mod private_api;
@@ -86,12 +106,60 @@ pub async fn genesis_init(config: &ChainConfig) {
 /// - mempool, module to organize incoming transactions.
 /// - block proposer, module to create block proposals for state keeper.
 /// - committer, module to store pending and completed blocks into the database.
+/// - webhook sender, module to deliver the outbox events to the operator-configured webhooks,
+///   along with the cleaner removing the old events.
+/// - tx callback sender, module to deliver the status callbacks of the transactions submitted
+///   with the API keys.
 /// - private Core API server.
+///
+/// All the sub-modules except for the private API are started once the instance becomes the leader.
 pub async fn run_core(
     connection_pool: ConnectionPool,
     read_only_connection_pool: ConnectionPool,
     config: &ZkSyncConfig,
     eth_gateway: EthereumGateway,
+    leadership: Leadership,
+    runtime_config: SharedRuntimeConfig,
+) -> anyhow::Result<Vec<JoinHandle<()>>> {
+    // Execution tracing is toggled via the private API, which is served by the standby instance as well.
+    let execution_tracing = ExecutionTracing::default();
+
+    // Run health check api for core, it's served by the standby instance as well.
+    let private_api_task = private_api::start_private_core_api(
+        connection_pool.clone(),
+        read_only_connection_pool,
+        eth_gateway.clone(),
+        config.api.private.clone(),
+        leadership.clone(),
+        execution_tracing.clone(),
+        config.chain.priority_op_deadlines.clone(),
+    );
+
+    let config = config.clone();
+    let leader_task = tokio::spawn(async move {
+        leadership.wait_for_leadership().await;
+        let task_futures = start_leader_tasks(
+            connection_pool,
+            &config,
+            eth_gateway,
+            execution_tracing,
+            runtime_config,
+        )
+        .await
+        .expect("failed to start the core");
+        wait_for_tasks(task_futures).await;
+    });
+
+    Ok(vec![private_api_task, leader_task])
+}
+
+/// Starts the sub-modules of the core application run by the leader only.
+async fn start_leader_tasks(
+    connection_pool: ConnectionPool,
+    config: &ZkSyncConfig,
+    eth_gateway: EthereumGateway,
+    execution_tracing: ExecutionTracing,
+    runtime_config: SharedRuntimeConfig,
 ) -> anyhow::Result<Vec<JoinHandle<()>>> {
     let (proposed_blocks_sender, proposed_blocks_receiver) =
         mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
@@ -108,15 +176,12 @@ pub async fn run_core(
         connection_pool.clone(),
         mempool_tx_request_receiver,
         config.chain.state_keeper.block_chunk_sizes.clone(),
+        config.chain.mempool.admission_shards,
     );
 
-    // Run health check api for core
-    let private_api_task = private_api::start_private_core_api(
-        connection_pool.clone(),
-        read_only_connection_pool,
-        eth_gateway.clone(),
-        config.api.private.clone(),
-    );
+    // The state keeper seals the blocks with the priority operations close to their deadlines right away.
+    let deadline_tracker =
+        PriorityOpDeadlineTracker::new(config.chain.priority_op_deadlines.escalation_blocks);
 
     // Start Ethereum Watcher.
     let eth_watch_task = start_eth_watch(
@@ -126,6 +191,7 @@ pub async fn run_core(
         &config.contracts,
         &config.eth_watch,
         mempool_tx_request_sender.clone(),
+        deadline_tracker.clone(),
     )
     .await;
 
@@ -151,6 +217,23 @@ pub async fn run_core(
         processed_tx_events_sender,
     );
 
+    // Let the state keeper seal blocks less often while the prover is overloaded.
+    let prover_backpressure =
+        ProverBackpressure::new(config.chain.backpressure.sealing_slowdown_factor as usize);
+    state_keeper.set_prover_backpressure(prover_backpressure.clone());
+    let prover_backpressure_task = run_prover_backpressure_monitor(
+        connection_pool.clone(),
+        config.chain.backpressure.clone(),
+        prover_backpressure,
+    );
+    state_keeper.set_execution_tracing(execution_tracing);
+    state_keeper.set_deadline_tracker(deadline_tracker);
+    state_keeper.set_gas_prediction(
+        config.chain.gas_prediction.predictor(),
+        config.chain.gas_prediction.max_block_gas(),
+    );
+    state_keeper.set_runtime_config(runtime_config);
+
     // Execute reverted blocks before start
     state_keeper.execute_reverted_blocks().await;
 
@@ -194,6 +277,14 @@ pub async fn run_core(
         processed_tx_events_receiver,
     );
 
+    // Start webhook sender.
+    let webhook_sender_task = run_webhook_sender(connection_pool.clone(), config.webhooks.clone());
+    let outbox_cleaner_task = run_outbox_cleaner(connection_pool.clone(), &config.webhooks);
+
+    // Start tx callback sender, callbacks are retried the same way as the webhooks.
+    let tx_callback_sender_task =
+        run_tx_callback_sender(connection_pool.clone(), config.webhooks.clone());
+
     let task_futures = vec![
         eth_watch_task,
         state_keeper_task,
@@ -202,9 +293,12 @@ pub async fn run_core(
         token_handler_task,
         register_factory_task,
         tx_event_emitter_task,
+        prover_backpressure_task,
+        webhook_sender_task,
+        outbox_cleaner_task,
+        tx_callback_sender_task,
         mempool_block_handler_task,
         mempool_tx_handler_task,
-        private_api_task,
     ];
 
     Ok(task_futures)
@@ -0,0 +1,78 @@
+//! Priority operations must be executed on L1 before their deadline block, otherwise the contract
+//! enters the exodus mode. The Ethereum watcher shares the last known L1 block with the state keeper,
+//! which seals the block containing an operation close to its deadline right away, even if the block
+//! is under-filled, and reports how close to the deadline the operations are included.
+//!
+//! The escalation threshold is configured in the `CHAIN_PRIORITY_OP_DEADLINES_` section.
+
+// Built-in uses
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// Last L1 block known to the Ethereum watcher, which is updated by the watcher and read by the state keeper.
+#[derive(Debug, Clone)]
+pub struct PriorityOpDeadlineTracker {
+    last_eth_block: Arc<AtomicU64>,
+    escalation_blocks: u64,
+}
+
+impl Default for PriorityOpDeadlineTracker {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl PriorityOpDeadlineTracker {
+    pub fn new(escalation_blocks: u64) -> Self {
+        Self {
+            last_eth_block: Arc::new(AtomicU64::new(0)),
+            escalation_blocks,
+        }
+    }
+
+    /// Returns the last L1 block known to the Ethereum watcher, `None` until the watcher polls the node.
+    pub fn last_eth_block(&self) -> Option<u64> {
+        Some(self.last_eth_block.load(Ordering::Relaxed)).filter(|block| *block != 0)
+    }
+
+    pub fn set_last_eth_block(&self, block: u64) {
+        self.last_eth_block.store(block, Ordering::Relaxed);
+    }
+
+    /// Returns the amount of L1 blocks left until the deadline.
+    pub fn remaining_blocks(&self, deadline_block: u64) -> Option<u64> {
+        self.last_eth_block()
+            .map(|last_eth_block| deadline_block.saturating_sub(last_eth_block))
+    }
+
+    /// Returns `true` if the operation with the given deadline must be processed without delay.
+    pub fn is_close(&self, deadline_block: u64) -> bool {
+        self.remaining_blocks(deadline_block)
+            .map_or(false, |remaining_blocks| {
+                remaining_blocks <= self.escalation_blocks
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn close_deadlines() {
+        let tracker = PriorityOpDeadlineTracker::new(100);
+        // Nothing is escalated until the last L1 block is known.
+        assert_eq!(tracker.remaining_blocks(1000), None);
+        assert!(!tracker.is_close(0));
+
+        // The block is shared between the clones.
+        tracker.clone().set_last_eth_block(850);
+        assert_eq!(tracker.remaining_blocks(1000), Some(150));
+        assert!(!tracker.is_close(1000));
+        assert!(tracker.is_close(950));
+        assert_eq!(tracker.remaining_blocks(800), Some(0));
+        assert!(tracker.is_close(800));
+    }
+}
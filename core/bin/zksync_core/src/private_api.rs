@@ -6,22 +6,70 @@
 //!
 //! All the incoming data is assumed to be correct and not double-checked
 //! for correctness.
+//!
+//! Every administrative action changing the server state is recorded to the
+//! append-only audit log along with the operator who performed it, so the
+//! endpoints performing such actions require the `X-Zksync-Actor` header.
 
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 
-use actix_web::{web, App, HttpResponse, HttpServer};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
 use futures::{channel::mpsc, StreamExt};
+use num::{BigUint, Zero};
+use serde::Serialize;
+use serde_json::json;
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
-use zksync_api_types::CoreStatus;
+use zksync_api_types::{
+    private::{
+        latency_percentile, AddAddressLabelRequest, AddCreate2FactoryRequest, AddToDenylistRequest,
+        AddWebhookSubscriptionRequest, AddWebhookSubscriptionResponse, ApiKeysMaxPageSizeRequest,
+        ApiKeysMaxPageSizeResponse, ApiRouteStats, ApiRouteStatsQuery, ApiRouteStatsReport,
+        AuditAction, AuditEntry, AuditLogQuery, BackfillProgress, ConsistencyCheckQuery,
+        ConsistencyReport, ConsistencyViolation, CpkSponsorship, CpkSponsorshipsQuery,
+        CpkSponsorshipsResponse, Create2FactoryEntry, DailyL1GasSpent, DailyTokenFees,
+        DeadWebhookDelivery, DenylistEntry, DroppedTx, DroppedTxsQuery,
+        EnableMaintenanceModeRequest, ExecutionTracingRequest, ExecutionTracingStatus,
+        FeeFreeTransfer, FeeFreeTransfersQuery, FeeFreeTransfersResponse, IssueLabelApiKeyResponse,
+        LabelApiKeyRequest, LeadershipChange, LeadershipStatus, MaintenanceMode, PendingProverJob,
+        PriorityBlockRequest, PriorityOpDeadline, PriorityOpDeadlines, PriorityOpDeadlinesQuery,
+        ProverJobPriorityRequest, ReassignProverJobsRequest, ReassignProverJobsResponse,
+        RemoveAddressLabelRequest, RemoveCreate2FactoryRequest, RemoveFromDenylistRequest,
+        RemoveRuntimeParamRequest, RemoveTokenPriceOverrideRequest, ReportInfo, ReportResult,
+        RetryWebhookDeliveriesResponse, RevenueQuery, RevenueReport, RevokeLabelApiKeysResponse,
+        RouteQueryTime, RuntimeParam, RuntimeParamChange, RuntimeParamChangesQuery,
+        ScheduledJobStatus, ScreeningMatch, ScreeningMatchesQuery, SetRuntimeParamRequest,
+        SetTokenPriceOverrideRequest, TokenPriceOverride, WebhookSubscription, ACTOR_HEADER,
+    },
+    v02::{
+        label::{validate_label, AddressLabelCategory},
+        pagination::{PaginationDirection, MAX_LIMIT},
+    },
+    CoreStatus,
+};
 
-use zksync_config::configs::api::PrivateApiConfig;
+use zksync_config::configs::{
+    api::{PrivateApiConfig, ReportDefinition},
+    chain::PriorityOpDeadlines as DeadlinesConfig,
+};
+use zksync_crypto::rand::{OsRng, Rng};
 use zksync_eth_client::EthereumGateway;
-use zksync_storage::ConnectionPool;
-use zksync_utils::panic_notify::ThreadPanicNotify;
+use zksync_storage::{labels::records::AddressLabel, BigDecimal, ConnectionPool, StorageProcessor};
+use zksync_types::{
+    event::outbox::OutboxEventType, runtime_config::RuntimeOverrides, tx::TxHash, AccountId,
+    Address, BlockNumber, TokenId, TokenLike, H256,
+};
+use zksync_utils::{big_decimal_to_ratio, panic_notify::ThreadPanicNotify};
+
+use crate::{leadership::Leadership, state_keeper::ExecutionTracing};
 
 const STATUS_INVALIDATION_PERIOD: Duration = Duration::from_secs(60);
+/// Amount of the recent leadership changes returned along with the leadership status.
+const LEADERSHIP_CHANGES_LIMIT: u32 = 10;
 
 #[derive(Debug)]
 struct AppState {
@@ -29,6 +77,43 @@ struct AppState {
     read_only_connection_pool: ConnectionPool,
     eth_client: EthereumGateway,
     status_cache: RwLock<Option<(CoreStatus, Instant)>>,
+    leadership: Leadership,
+    execution_tracing: ExecutionTracing,
+    deadlines_config: DeadlinesConfig,
+    reports: Arc<HashMap<String, ReportDefinition>>,
+    reports_timeout: Duration,
+    reports_max_rows: u32,
+}
+
+/// Returns the operator performing the action from the request headers.
+fn actor(request: &HttpRequest) -> actix_web::Result<String> {
+    request
+        .headers()
+        .get(ACTOR_HEADER)
+        .and_then(|actor| actor.to_str().ok())
+        .filter(|actor| !actor.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            actix_web::error::ErrorBadRequest(format!("{} header is required", ACTOR_HEADER))
+        })
+}
+
+/// Appends the action to the audit log.
+/// Must be called within the same database transaction as the action itself.
+async fn log_action(
+    storage: &mut StorageProcessor<'_>,
+    actor: &str,
+    action: AuditAction,
+    parameters: impl Serialize,
+) -> actix_web::Result<()> {
+    let parameters =
+        serde_json::to_value(parameters).map_err(actix_web::error::ErrorInternalServerError)?;
+    storage
+        .audit_schema()
+        .store_audit_entry(actor, &action.to_string(), &parameters)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(())
 }
 
 /// Health check.
@@ -63,13 +148,1945 @@ async fn status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Returns the role of the instance in the leader election and the recent leadership changes.
+#[actix_web::get("/leadership")]
+async fn leadership_status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let changes = storage
+        .leadership_schema()
+        .load_leadership_changes(LEADERSHIP_CHANGES_LIMIT)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|change| LeadershipChange {
+            instance_id: change.instance_id,
+            acquired_at: change.acquired_at,
+        })
+        .collect();
+
+    let (role, role_since) = data.leadership.role();
+    Ok(HttpResponse::Ok().json(LeadershipStatus {
+        instance_id: data.leadership.instance_id().to_string(),
+        role,
+        role_since,
+        changes,
+    }))
+}
+
+/// Returns whether the state keeper of this instance emits the execution traces of the blocks.
+#[actix_web::get("/state_keeper/tracing")]
+async fn execution_tracing_status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(ExecutionTracingStatus {
+        instance_id: data.leadership.instance_id().to_string(),
+        enabled: data.execution_tracing.is_enabled(),
+    }))
+}
+
+/// Toggles the execution traces of the blocks sealed by the state keeper of this instance.
+/// Only the leader seals the blocks, but the switch is kept if the instance takes over the leadership.
+#[actix_web::put("/state_keeper/tracing")]
+async fn set_execution_tracing(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<ExecutionTracingRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    log_action(
+        &mut storage,
+        &actor,
+        AuditAction::SetExecutionTracing,
+        &*request,
+    )
+    .await?;
+    data.execution_tracing.set_enabled(request.enabled);
+    vlog::info!(
+        "Execution tracing of the instance {} was set to {}",
+        data.leadership.instance_id(),
+        request.enabled
+    );
+
+    Ok(HttpResponse::Ok().json(ExecutionTracingStatus {
+        instance_id: data.leadership.instance_id().to_string(),
+        enabled: request.enabled,
+    }))
+}
+
+/// Returns the priority operations which are not executed yet along with the time left until their deadlines.
+/// The remaining time is estimated from the current L1 block, so it's available on the standby instances as well.
+#[actix_web::get("/priority_ops/deadlines")]
+async fn priority_op_deadlines(
+    data: web::Data<AppState>,
+    query: web::Query<PriorityOpDeadlinesQuery>,
+) -> actix_web::Result<HttpResponse> {
+    if query.limit > MAX_LIMIT {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "limit must not exceed {}",
+            MAX_LIMIT
+        )));
+    }
+    let last_eth_block = data
+        .eth_client
+        .block_number()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .as_u64();
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let ops = storage
+        .chain()
+        .mempool_schema()
+        .get_pending_priority_ops_page(0, query.limit, PaginationDirection::Newer)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|(op, confirmed)| {
+            let remaining_blocks = op.deadline_block.saturating_sub(last_eth_block);
+            PriorityOpDeadline {
+                serial_id: op.serial_id,
+                tx_hash: op.tx_hash(),
+                op_type: op.data.variance_name(),
+                confirmed,
+                eth_block: op.eth_block,
+                deadline_block: op.deadline_block,
+                remaining_blocks,
+                remaining_secs: data
+                    .deadlines_config
+                    .eth_blocks_duration(remaining_blocks)
+                    .as_secs(),
+                escalated: remaining_blocks <= data.deadlines_config.escalation_blocks,
+            }
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(PriorityOpDeadlines {
+        last_eth_block,
+        escalation_blocks: data.deadlines_config.escalation_blocks,
+        ops,
+    }))
+}
+
+/// Returns the prover jobs that are not completed yet in the order they will be served.
+#[actix_web::get("/prover/jobs")]
+async fn pending_prover_jobs(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let jobs: Vec<_> = storage
+        .prover_schema()
+        .load_pending_prover_jobs()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|job| PendingProverJob {
+            id: job.id,
+            job_type: job.job_type,
+            job_status: job.job_status,
+            job_priority: job.job_priority,
+            first_block: BlockNumber(job.first_block as u32),
+            last_block: BlockNumber(job.last_block as u32),
+            updated_by: job.updated_by,
+            updated_at: job.updated_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(jobs))
+}
+
+/// Marks the block as a high priority one, so the prover jobs containing it are served first.
+#[actix_web::post("/prover/priority_blocks")]
+async fn mark_block_as_high_priority(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<PriorityBlockRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    transaction
+        .prover_schema()
+        .mark_block_as_high_priority(request.block_number)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::MarkBlockAsHighPriority,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!("Block {} was marked as high priority", request.block_number);
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Changes the priority of an idle prover job.
+#[actix_web::post("/prover/jobs/{job_id}/priority")]
+async fn set_prover_job_priority(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    job_id: web::Path<i32>,
+    request: web::Json<ProverJobPriorityRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let job_id = job_id.into_inner();
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let updated = transaction
+        .prover_schema()
+        .set_prover_job_priority(job_id, request.job_priority)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !updated {
+        return Err(actix_web::error::ErrorNotFound("idle prover job not found"));
+    }
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::SetProverJobPriority,
+        json!({ "job_id": job_id, "job_priority": request.job_priority }),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Priority of the prover job {} was set to {}",
+        job_id,
+        request.job_priority
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Returns the jobs taken by a stalled prover back to the queue.
+#[actix_web::post("/prover/reassign")]
+async fn reassign_prover_jobs(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<ReassignProverJobsRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let reassigned_jobs = transaction
+        .prover_schema()
+        .reassign_prover_jobs(&request.prover_name)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::ReassignProverJobs,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "{} jobs of the prover '{}' were returned to the queue",
+        reassigned_jobs,
+        request.prover_name
+    );
+
+    Ok(HttpResponse::Ok().json(ReassignProverJobsResponse { reassigned_jobs }))
+}
+
+/// Returns the registered webhooks.
+#[actix_web::get("/webhooks")]
+async fn webhook_subscriptions(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let subscriptions = storage
+        .outbox_schema()
+        .load_webhook_subscriptions()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|subscription| {
+            let event_types = subscription
+                .event_types
+                .iter()
+                .map(|event_type| OutboxEventType::from_str(event_type))
+                .collect::<Result<_, _>>()?;
+            Ok(WebhookSubscription {
+                id: subscription.id,
+                url: subscription.url,
+                event_types,
+                created_at: subscription.created_at,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().json(subscriptions))
+}
+
+/// Registers a new webhook. Only the events stored after the registration are delivered to it.
+#[actix_web::post("/webhooks")]
+async fn add_webhook_subscription(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<AddWebhookSubscriptionRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    reqwest::Url::parse(&request.url).map_err(actix_web::error::ErrorBadRequest)?;
+    if request.secret.is_empty() {
+        return Err(actix_web::error::ErrorBadRequest(
+            "webhook secret must not be empty",
+        ));
+    }
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let id = transaction
+        .outbox_schema()
+        .add_webhook_subscription(&request.url, &request.secret, &request.event_types)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    // The secret is not logged.
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::AddWebhookSubscription,
+        json!({ "id": id, "url": request.url, "event_types": request.event_types }),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!("Webhook {} was registered for {}", id, request.url);
+
+    Ok(HttpResponse::Ok().json(AddWebhookSubscriptionResponse { id }))
+}
+
+/// Removes the webhook along with its undelivered events.
+#[actix_web::delete("/webhooks/{subscription_id}")]
+async fn remove_webhook_subscription(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    subscription_id: web::Path<i64>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let subscription_id = subscription_id.into_inner();
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let removed = transaction
+        .outbox_schema()
+        .remove_webhook_subscription(subscription_id)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound("webhook not found"));
+    }
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::RemoveWebhookSubscription,
+        json!({ "id": subscription_id }),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!("Webhook {} was removed", subscription_id);
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Returns the deliveries of the webhook that ran out of attempts.
+#[actix_web::get("/webhooks/{subscription_id}/dead_letters")]
+async fn dead_webhook_deliveries(
+    data: web::Data<AppState>,
+    subscription_id: web::Path<i64>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let deliveries: Vec<_> = storage
+        .outbox_schema()
+        .load_dead_webhook_deliveries(subscription_id.into_inner())
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|delivery| DeadWebhookDelivery {
+            id: delivery.id,
+            event_id: delivery.event_id,
+            attempts: delivery.attempts,
+            last_error: delivery.last_error,
+            updated_at: delivery.updated_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(deliveries))
+}
+
+/// Returns the dead deliveries of the webhook back to the queue.
+#[actix_web::post("/webhooks/{subscription_id}/retry")]
+async fn retry_dead_webhook_deliveries(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    subscription_id: web::Path<i64>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let subscription_id = subscription_id.into_inner();
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let requeued_deliveries = transaction
+        .outbox_schema()
+        .retry_dead_webhook_deliveries(subscription_id)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::RetryDeadWebhookDeliveries,
+        json!({ "id": subscription_id }),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "{} dead deliveries of the webhook {} were returned to the queue",
+        requeued_deliveries,
+        subscription_id
+    );
+
+    Ok(HttpResponse::Ok().json(RetryWebhookDeliveriesResponse {
+        requeued_deliveries,
+    }))
+}
+
+/// Returns the audit log entries, newest first.
+#[actix_web::get("/audit")]
+async fn audit_log(
+    data: web::Data<AppState>,
+    query: web::Query<AuditLogQuery>,
+) -> actix_web::Result<HttpResponse> {
+    if query.limit > MAX_LIMIT {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "limit must not exceed {}",
+            MAX_LIMIT
+        )));
+    }
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let entries: Vec<_> = storage
+        .audit_schema()
+        .load_audit_entries(query.before, query.limit)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|entry| AuditEntry {
+            id: entry.id,
+            actor: entry.actor,
+            action: entry.action,
+            parameters: entry.parameters,
+            created_at: entry.created_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+/// Checks the invariants of the stored data for the blocks starting from the given one.
+#[actix_web::get("/consistency")]
+async fn check_consistency(
+    data: web::Data<AppState>,
+    query: web::Query<ConsistencyCheckQuery>,
+) -> actix_web::Result<HttpResponse> {
+    if query.limit > MAX_LIMIT {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "limit must not exceed {}",
+            MAX_LIMIT
+        )));
+    }
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let report = storage
+        .consistency_schema()
+        .check_invariants(query.from_block, query.limit)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let violations = report
+        .violations
+        .into_iter()
+        .map(|violation| ConsistencyViolation {
+            invariant: violation.invariant.name().to_string(),
+            block_number: violation.block_number,
+            description: violation.description,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ConsistencyReport {
+        from_block: report.from_block,
+        last_checked_block: report.last_checked_block,
+        violations,
+    }))
+}
+
+/// Returns the progress of the online data migrations, the recently started ones first.
+#[actix_web::get("/backfills")]
+async fn backfills(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let backfills: Vec<_> = storage
+        .migrations_schema()
+        .load_backfills_progress()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|progress| BackfillProgress {
+            name: progress.name,
+            cursor: progress.cursor,
+            processed_rows: progress.processed_rows as u64,
+            estimated_rows: progress.estimated_rows.map(|rows| rows as u64),
+            started_at: progress.started_at,
+            updated_at: progress.updated_at,
+            finished_at: progress.finished_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(backfills))
+}
+
+/// Returns the health of the periodic background jobs which were ever run.
+#[actix_web::get("/jobs")]
+async fn scheduled_jobs(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let now = chrono::Utc::now();
+    let jobs: Vec<_> = storage
+        .jobs_schema()
+        .load_jobs()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|job| {
+            let max_gap = chrono::Duration::seconds(job.interval_secs * 2);
+            let healthy = job.last_error.is_none()
+                && job
+                    .last_started_at
+                    .map_or(false, |started_at| now - started_at <= max_gap);
+            ScheduledJobStatus {
+                name: job.name,
+                interval_secs: job.interval_secs as u64,
+                last_started_at: job.last_started_at,
+                last_finished_at: job.last_finished_at,
+                last_error: job.last_error,
+                consecutive_failures: job.consecutive_failures as u32,
+                runs_count: job.runs_count as u64,
+                healthy,
+            }
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(jobs))
+}
+
+/// Returns the `ChangePubKey` transactions sponsored by the operator, newest first.
+#[actix_web::get("/cpk_sponsorships")]
+async fn cpk_sponsorships(
+    data: web::Data<AppState>,
+    query: web::Query<CpkSponsorshipsQuery>,
+) -> actix_web::Result<HttpResponse> {
+    if query.limit > MAX_LIMIT {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "limit must not exceed {}",
+            MAX_LIMIT
+        )));
+    }
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let total_sponsored_usd_scaled = storage
+        .sponsorship_schema()
+        .get_total_sponsored_usd()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let sponsorships = storage
+        .sponsorship_schema()
+        .load_cpk_sponsorships(query.before, query.limit)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|sponsorship| CpkSponsorship {
+            id: sponsorship.id,
+            account_id: AccountId(sponsorship.account_id as u32),
+            tx_hash: TxHash::from_slice(&sponsorship.tx_hash).unwrap_or_default(),
+            fee_token: TokenId(sponsorship.fee_token as u32),
+            waived_fee: sponsorship
+                .waived_fee
+                .to_bigint()
+                .unwrap()
+                .to_biguint()
+                .unwrap(),
+            waived_fee_usd_scaled: sponsorship.waived_fee_usd_scale6 as u64,
+            created_at: sponsorship.created_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(CpkSponsorshipsResponse {
+        total_sponsored_usd_scaled,
+        sponsorships,
+    }))
+}
+
+/// Returns the transfers of the operator addresses with the fee waived, newest first.
+#[actix_web::get("/fee_free_transfers")]
+async fn fee_free_transfers(
+    data: web::Data<AppState>,
+    query: web::Query<FeeFreeTransfersQuery>,
+) -> actix_web::Result<HttpResponse> {
+    if query.limit > MAX_LIMIT {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "limit must not exceed {}",
+            MAX_LIMIT
+        )));
+    }
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let quota_used = match query.address {
+        Some(address) => Some(
+            storage
+                .sponsorship_schema()
+                .get_fee_free_transfers_used(address)
+                .await
+                .map_err(actix_web::error::ErrorInternalServerError)?,
+        ),
+        None => None,
+    };
+    let transfers = storage
+        .sponsorship_schema()
+        .load_fee_free_transfers(query.address, query.before, query.limit)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|transfer| FeeFreeTransfer {
+            id: transfer.id,
+            address: Address::from_slice(&transfer.address),
+            tx_hash: TxHash::from_slice(&transfer.tx_hash).unwrap_or_default(),
+            fee_token: TokenId(transfer.fee_token as u32),
+            waived_fee: transfer
+                .waived_fee
+                .to_bigint()
+                .unwrap()
+                .to_biguint()
+                .unwrap(),
+            created_at: transfer.created_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(FeeFreeTransfersResponse {
+        quota_used,
+        transfers,
+    }))
+}
+
+/// Returns the maintenance mode of the server, `null` if it's disabled.
+#[actix_web::get("/maintenance")]
+async fn maintenance_mode(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let maintenance_mode = storage
+        .misc_schema()
+        .load_maintenance_mode()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .map(|maintenance_mode| MaintenanceMode {
+            reason: maintenance_mode.reason,
+            enabled_at: maintenance_mode.enabled_at,
+        });
+
+    Ok(HttpResponse::Ok().json(maintenance_mode))
+}
+
+/// Enables the maintenance mode, so the API servers stop accepting the transactions.
+#[actix_web::post("/maintenance")]
+async fn enable_maintenance_mode(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<EnableMaintenanceModeRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    if request.reason.is_empty() {
+        return Err(actix_web::error::ErrorBadRequest(
+            "maintenance reason must not be empty",
+        ));
+    }
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    transaction
+        .misc_schema()
+        .enable_maintenance_mode(&request.reason)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::EnableMaintenanceMode,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!("Maintenance mode was enabled: {}", request.reason);
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Disables the maintenance mode.
+#[actix_web::delete("/maintenance")]
+async fn disable_maintenance_mode(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let disabled = transaction
+        .misc_schema()
+        .disable_maintenance_mode()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !disabled {
+        return Err(actix_web::error::ErrorNotFound(
+            "maintenance mode is not enabled",
+        ));
+    }
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::DisableMaintenanceMode,
+        json!({}),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!("Maintenance mode was disabled");
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Adds the label visible to everyone, e.g. of the exchange hot wallet or the known contract.
+#[actix_web::post("/labels")]
+async fn add_address_label(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<AddAddressLabelRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    validate_label(&request.label).map_err(actix_web::error::ErrorBadRequest)?;
+    if request.category == AddressLabelCategory::UserDefined {
+        return Err(actix_web::error::ErrorBadRequest(
+            "user-defined labels can only be added with the API key",
+        ));
+    }
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let label = AddressLabel {
+        address: request.address,
+        label: request.label.clone(),
+        category: request.category.to_string(),
+        scope: None,
+    };
+    let added = transaction
+        .labels_schema()
+        .store_label(&label)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !added {
+        return Err(actix_web::error::ErrorConflict("label already exists"));
+    }
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::AddAddressLabel,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Removes the label visible to everyone.
+#[actix_web::delete("/labels")]
+async fn remove_address_label(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<RemoveAddressLabelRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let removed = transaction
+        .labels_schema()
+        .remove_label(request.address, &request.label, None)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound("label not found"));
+    }
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::RemoveAddressLabel,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Issues the API key allowing to manage the user-defined labels of the scope.
+/// The key itself is neither stored nor recorded to the audit log.
+#[actix_web::post("/labels/api_keys")]
+async fn issue_label_api_key(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<LabelApiKeyRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    if request.scope.is_empty() {
+        return Err(actix_web::error::ErrorBadRequest("scope must not be empty"));
+    }
+
+    let key: [u8; 32] = OsRng::new()
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .gen();
+    let api_key = hex::encode(key);
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    transaction
+        .labels_schema()
+        .store_api_key(&tiny_keccak::keccak256(api_key.as_bytes()), &request.scope)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::IssueLabelApiKey,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!("Label API key was issued for the scope {}", request.scope);
+
+    Ok(HttpResponse::Ok().json(IssueLabelApiKeyResponse { api_key }))
+}
+
+/// Revokes all the API keys of the scope. Labels created with them are kept.
+#[actix_web::delete("/labels/api_keys")]
+async fn revoke_label_api_keys(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<LabelApiKeyRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let revoked_keys = transaction
+        .labels_schema()
+        .remove_api_keys(&request.scope)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::RevokeLabelApiKeys,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "{} label API keys were revoked for the scope {}",
+        revoked_keys,
+        request.scope
+    );
+
+    Ok(HttpResponse::Ok().json(RevokeLabelApiKeysResponse { revoked_keys }))
+}
+
+/// Overrides the maximum page size of the paginated REST API endpoints for the API keys
+/// of the scope, e.g. to let the partners load the history with larger pages.
+#[actix_web::put("/labels/api_keys/max_page_size")]
+async fn set_api_keys_max_page_size(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<ApiKeysMaxPageSizeRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    if request.max_page_size == Some(0) {
+        return Err(actix_web::error::ErrorBadRequest(
+            "max_page_size must be positive",
+        ));
+    }
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let updated_keys = transaction
+        .labels_schema()
+        .set_api_keys_max_page_size(&request.scope, request.max_page_size)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::SetApiKeysMaxPageSize,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Max page size of {} API keys of the scope {} was set to {:?}",
+        updated_keys,
+        request.scope,
+        request.max_page_size
+    );
+
+    Ok(HttpResponse::Ok().json(ApiKeysMaxPageSizeResponse { updated_keys }))
+}
+
+/// Returns the addresses denied by the operator, newest first.
+#[actix_web::get("/screening/denylist")]
+async fn screening_denylist(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let denylist: Vec<_> = storage
+        .screening_schema()
+        .load_denylist()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|entry| DenylistEntry {
+            address: Address::from_slice(&entry.address),
+            reason: entry.reason,
+            created_at: entry.created_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(denylist))
+}
+
+/// Denies the transactions from and to the address on the submission.
+#[actix_web::post("/screening/denylist")]
+async fn add_to_denylist(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<AddToDenylistRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    if request.reason.is_empty() {
+        return Err(actix_web::error::ErrorBadRequest(
+            "reason must not be empty",
+        ));
+    }
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let added = transaction
+        .screening_schema()
+        .add_to_denylist(request.address, &request.reason)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !added {
+        return Err(actix_web::error::ErrorConflict("address is already denied"));
+    }
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::AddToDenylist,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!("Address {:?} was added to the denylist", request.address);
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Allows the transactions from and to the denied address again.
+#[actix_web::delete("/screening/denylist")]
+async fn remove_from_denylist(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<RemoveFromDenylistRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let removed = transaction
+        .screening_schema()
+        .remove_from_denylist(request.address)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound("address is not denied"));
+    }
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::RemoveFromDenylist,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Address {:?} was removed from the denylist",
+        request.address
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Returns the transactions rejected by the screening, newest first.
+#[actix_web::get("/screening/matches")]
+async fn screening_matches(
+    data: web::Data<AppState>,
+    query: web::Query<ScreeningMatchesQuery>,
+) -> actix_web::Result<HttpResponse> {
+    if query.limit > MAX_LIMIT {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "limit must not exceed {}",
+            MAX_LIMIT
+        )));
+    }
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let matches: Vec<_> = storage
+        .screening_schema()
+        .load_matches(query.address, query.before, query.limit)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|screening_match| ScreeningMatch {
+            id: screening_match.id,
+            tx_hash: TxHash::from_slice(&screening_match.tx_hash).unwrap_or_default(),
+            address: Address::from_slice(&screening_match.address),
+            role: screening_match.role,
+            source: screening_match.source,
+            reason: screening_match.reason,
+            created_at: screening_match.created_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(matches))
+}
+
+/// Returns the transactions rejected on the execution or evicted from the mempool as expired,
+/// newest first.
+#[actix_web::get("/dropped_txs")]
+async fn dropped_txs(
+    data: web::Data<AppState>,
+    query: web::Query<DroppedTxsQuery>,
+) -> actix_web::Result<HttpResponse> {
+    if query.limit > MAX_LIMIT {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "limit must not exceed {}",
+            MAX_LIMIT
+        )));
+    }
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let txs: Vec<_> = storage
+        .dropped_txs_schema()
+        .load_dropped_txs(query.address, query.tx_hash, query.before, query.limit)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|tx| DroppedTx {
+            id: tx.id,
+            tx_hash: TxHash::from_slice(&tx.tx_hash).unwrap_or_default(),
+            address: Address::from_slice(&tx.address),
+            kind: tx.kind,
+            reason: tx.reason,
+            tx: tx.tx,
+            created_at: tx.created_at,
+            dropped_at: tx.dropped_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(txs))
+}
+
+/// Returns the parameters overridden at runtime.
+#[actix_web::get("/runtime_config")]
+async fn runtime_config(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let params: Vec<_> = storage
+        .runtime_config_schema()
+        .load_params()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|param| RuntimeParam {
+            key: param.key,
+            value: param.value,
+            updated_by: param.updated_by,
+            updated_at: param.updated_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(params))
+}
+
+/// Overrides the reload-safe parameter. The components apply it on the next reload of the runtime config.
+#[actix_web::post("/runtime_config")]
+async fn set_runtime_param(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<SetRuntimeParamRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    RuntimeOverrides::validate_param(&request.key, &request.value)
+        .map_err(actix_web::error::ErrorBadRequest)?;
+    if let Some(directives) = request
+        .value
+        .as_str()
+        .filter(|_| request.key == "log_filter")
+    {
+        vlog::validate_log_filter(directives).map_err(|err| {
+            actix_web::error::ErrorBadRequest(format!("invalid log filter: {}", err))
+        })?;
+    }
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    transaction
+        .runtime_config_schema()
+        .set_param(&request.key, &request.value, &actor)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::SetRuntimeParam,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Runtime parameter `{}` was set to {} by {}",
+        request.key,
+        request.value,
+        actor
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Restores the value of the parameter from the environment config.
+#[actix_web::delete("/runtime_config")]
+async fn remove_runtime_param(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<RemoveRuntimeParamRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let removed = transaction
+        .runtime_config_schema()
+        .remove_param(&request.key, &actor)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if removed.is_none() {
+        return Err(actix_web::error::ErrorNotFound(
+            "parameter is not overridden",
+        ));
+    }
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::RemoveRuntimeParam,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Runtime parameter `{}` was restored by {}",
+        request.key,
+        actor
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Returns the changes of the runtime parameters, newest first.
+#[actix_web::get("/runtime_config/changes")]
+async fn runtime_config_changes(
+    data: web::Data<AppState>,
+    query: web::Query<RuntimeParamChangesQuery>,
+) -> actix_web::Result<HttpResponse> {
+    if query.limit > MAX_LIMIT {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "limit must not exceed {}",
+            MAX_LIMIT
+        )));
+    }
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let changes: Vec<_> = storage
+        .runtime_config_schema()
+        .load_changes(query.before, query.limit)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|change| RuntimeParamChange {
+            id: change.id,
+            key: change.key,
+            old_value: change.old_value,
+            new_value: change.new_value,
+            changed_by: change.changed_by,
+            changed_at: change.changed_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(changes))
+}
+
+/// Resolves the fungible token the manual price is set for.
+async fn price_override_token(
+    storage: &mut StorageProcessor<'_>,
+    token: &TokenLike,
+) -> actix_web::Result<TokenId> {
+    let token = storage
+        .tokens_schema()
+        .get_token(token.clone())
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .filter(|token| !token.is_nft)
+        .ok_or_else(|| actix_web::error::ErrorNotFound("token not found"))?;
+    Ok(token.id)
+}
+
+/// Returns the manual token prices, including the expired ones.
+#[actix_web::get("/ticker/price_overrides")]
+async fn token_price_overrides(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let now = chrono::Utc::now();
+    let overrides = storage
+        .tokens_schema()
+        .load_ticker_price_overrides()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|price_override| {
+            Ok(TokenPriceOverride {
+                token_id: TokenId(price_override.token_id as u32),
+                usd_price: big_decimal_to_ratio(&price_override.usd_price)?,
+                reason: price_override.reason,
+                created_by: price_override.created_by,
+                created_at: price_override.created_at,
+                expires_at: price_override.expires_at,
+                expired: price_override.expires_at <= now,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().json(overrides))
+}
+
+/// Sets the manual token price used by the fee ticker until it expires.
+#[actix_web::post("/ticker/price_overrides")]
+async fn set_token_price_override(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<SetTokenPriceOverrideRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    if request.usd_price.is_zero() {
+        return Err(actix_web::error::ErrorBadRequest(
+            "price must be greater than zero",
+        ));
+    }
+    if request.expires_at <= chrono::Utc::now() {
+        return Err(actix_web::error::ErrorBadRequest(
+            "expiration must be in the future",
+        ));
+    }
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let token_id = price_override_token(&mut transaction, &request.token).await?;
+    transaction
+        .tokens_schema()
+        .store_ticker_price_override(
+            token_id,
+            &request.usd_price,
+            &request.reason,
+            &actor,
+            request.expires_at,
+        )
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::SetTokenPriceOverride,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Price of the token {} was overridden until {} by {}: {}",
+        token_id,
+        request.expires_at,
+        actor,
+        request.reason
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Removes the manual token price, so the fee ticker uses the fetched one again.
+#[actix_web::delete("/ticker/price_overrides")]
+async fn remove_token_price_override(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<RemoveTokenPriceOverrideRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let token_id = price_override_token(&mut transaction, &request.token).await?;
+    let removed = transaction
+        .tokens_schema()
+        .remove_ticker_price_override(token_id)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound(
+            "price of the token is not overridden",
+        ));
+    }
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::RemoveTokenPriceOverride,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Price override of the token {} was removed by {}",
+        token_id,
+        actor
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Returns the registered factory and code hash pairs of the CREATE2 smart wallets.
+#[actix_web::get("/create2_factories")]
+async fn create2_factories(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let factories: Vec<_> = storage
+        .create2_factories_schema()
+        .load_factories()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|factory| Create2FactoryEntry {
+            creator_address: Address::from_slice(&factory.creator_address),
+            code_hash: H256::from_slice(&factory.code_hash),
+            name: factory.name,
+            created_by: factory.created_by,
+            created_at: factory.created_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(factories))
+}
+
+/// Registers the factory and code hash pair, so the `ChangePubKey` transactions of its wallets
+/// are accepted.
+#[actix_web::post("/create2_factories")]
+async fn add_create2_factory(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<AddCreate2FactoryRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    if request.name.trim().is_empty() {
+        return Err(actix_web::error::ErrorBadRequest("name must not be empty"));
+    }
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    transaction
+        .create2_factories_schema()
+        .store_factory(
+            request.creator_address,
+            request.code_hash,
+            &request.name,
+            &actor,
+        )
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::AddCreate2Factory,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "CREATE2 factory {:?} with code hash {:?} was registered as `{}` by {}",
+        request.creator_address,
+        request.code_hash,
+        request.name,
+        actor
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Removes the factory and code hash pair, so the `ChangePubKey` transactions of its wallets
+/// are rejected.
+#[actix_web::delete("/create2_factories")]
+async fn remove_create2_factory(
+    data: web::Data<AppState>,
+    http_request: HttpRequest,
+    request: web::Json<RemoveCreate2FactoryRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let actor = actor(&http_request)?;
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let removed = transaction
+        .create2_factories_schema()
+        .remove_factory(request.creator_address, request.code_hash)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound(
+            "CREATE2 factory is not registered",
+        ));
+    }
+    log_action(
+        &mut transaction,
+        &actor,
+        AuditAction::RemoveCreate2Factory,
+        &*request,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "CREATE2 factory {:?} with code hash {:?} was removed by {}",
+        request.creator_address,
+        request.code_hash,
+        actor
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Maximum amount of days covered by the single revenue report.
+const MAX_REVENUE_REPORT_DAYS: i64 = 366;
+
+fn big_decimal_to_biguint(value: BigDecimal) -> BigUint {
+    value.to_bigint().unwrap().to_biguint().unwrap()
+}
+
+/// Returns the fees charged per token and day along with the L1 gas spent per day.
+#[actix_web::get("/revenue")]
+async fn revenue_report(
+    data: web::Data<AppState>,
+    query: web::Query<RevenueQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let days = (query.to_day - query.from_day).num_days() + 1;
+    if days <= 0 || days > MAX_REVENUE_REPORT_DAYS {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "days range must be non-empty and must not exceed {} days",
+            MAX_REVENUE_REPORT_DAYS
+        )));
+    }
+    // The report scans the whole days range, so it's served by the replica.
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let fees = storage
+        .revenue_schema()
+        .load_daily_fees(query.from_day, query.to_day)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|fees| DailyTokenFees {
+            day: fees.day,
+            token_id: TokenId(fees.token_id as u32),
+            transactions_count: fees.transactions_count as u64,
+            charged_fee: big_decimal_to_biguint(fees.charged_fee),
+            zkp_fee: big_decimal_to_biguint(fees.zkp_fee),
+            gas_fee: big_decimal_to_biguint(fees.gas_fee),
+            subsidy: big_decimal_to_biguint(fees.subsidy),
+        })
+        .collect();
+    let l1_gas = storage
+        .revenue_schema()
+        .load_daily_gas_spent(query.from_day, query.to_day)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|gas_spent| DailyL1GasSpent {
+            day: gas_spent.day,
+            transactions_count: gas_spent.transactions_count as u64,
+            gas_used: big_decimal_to_biguint(gas_spent.gas_used),
+            gas_cost: big_decimal_to_biguint(gas_spent.gas_cost),
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(RevenueReport { fees, l1_gas }))
+}
+
+/// Maximum period of the API route statistics report.
+const MAX_API_ROUTE_STATS_HOURS: u32 = 7 * 24;
+/// Amount of the slowest storage methods reported per route.
+const API_ROUTE_TOP_QUERIES: usize = 5;
+
+/// Returns the statistics of the REST API routes collected by all the API servers
+/// within the last hours, the most requested routes first.
+#[actix_web::get("/api_routes")]
+async fn api_route_stats(
+    data: web::Data<AppState>,
+    query: web::Query<ApiRouteStatsQuery>,
+) -> actix_web::Result<HttpResponse> {
+    if query.hours == 0 || query.hours > MAX_API_ROUTE_STATS_HOURS {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "hours must be positive and must not exceed {}",
+            MAX_API_ROUTE_STATS_HOURS
+        )));
+    }
+    let since = chrono::Utc::now() - chrono::Duration::hours(i64::from(query.hours));
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let stats = storage
+        .api_route_stats_schema()
+        .load_route_stats(since)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut queries = HashMap::<_, Vec<_>>::new();
+    for query in storage
+        .api_route_stats_schema()
+        .load_route_queries(since)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+    {
+        queries.entry(query.route.clone()).or_default().push(query);
+    }
+
+    let mut routes: Vec<_> = stats
+        .into_iter()
+        .map(|stats| {
+            let requests = stats.requests.max(1) as f64;
+            let bad_requests = (stats.errors + stats.slow_requests) as f64;
+            let error_budget = (100.0 - stats.slo_target) / 100.0;
+            let buckets: Vec<_> = stats
+                .latency_buckets
+                .iter()
+                .map(|&requests| requests as u64)
+                .collect();
+            let storage_ms = stats.storage_ms.max(1) as f64;
+            let top_queries = queries
+                .remove(&stats.route)
+                .unwrap_or_default()
+                .into_iter()
+                .take(API_ROUTE_TOP_QUERIES)
+                .map(|query| RouteQueryTime {
+                    method: query.method,
+                    duration_ms: query.duration_ms as u64,
+                    share: query.duration_ms as f64 / storage_ms,
+                })
+                .collect();
+            ApiRouteStats {
+                requests: stats.requests as u64,
+                errors: stats.errors as u64,
+                slow_requests: stats.slow_requests as u64,
+                error_rate: stats.errors as f64 / requests,
+                p50_ms: latency_percentile(&buckets, 50.0),
+                p95_ms: latency_percentile(&buckets, 95.0),
+                p99_ms: latency_percentile(&buckets, 99.0),
+                storage_ms_per_request: stats.storage_ms as f64 / requests,
+                latency_threshold_ms: stats.latency_threshold_ms as u64,
+                slo_target: stats.slo_target,
+                slo_attainment: 100.0 * (1.0 - bad_requests / requests),
+                error_budget_spent: if error_budget > 0.0 {
+                    bad_requests / requests / error_budget
+                } else {
+                    0.0
+                },
+                top_queries,
+                route: stats.route,
+            }
+        })
+        .collect();
+    routes.sort_by(|a, b| b.requests.cmp(&a.requests));
+
+    Ok(HttpResponse::Ok().json(ApiRouteStatsReport { since, routes }))
+}
+
+/// Returns the read-only SQL reports available to the operators.
+#[actix_web::get("/reports")]
+async fn reports(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut reports: Vec<_> = data
+        .reports
+        .values()
+        .map(|report| ReportInfo {
+            name: report.name.clone(),
+            description: report.description.clone(),
+            parameters: report.parameters.clone(),
+        })
+        .collect();
+    reports.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(HttpResponse::Ok().json(reports))
+}
+
+/// Runs the report against the replica, the report parameters are passed as the query parameters.
+#[actix_web::get("/reports/{name}")]
+async fn run_report(
+    data: web::Data<AppState>,
+    name: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> actix_web::Result<HttpResponse> {
+    let report = data
+        .reports
+        .get(name.as_str())
+        .ok_or_else(|| actix_web::error::ErrorNotFound("report not found"))?;
+    if let Some(unknown) = query
+        .keys()
+        .find(|parameter| !report.parameters.contains(parameter))
+    {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "unknown parameter {}",
+            unknown
+        )));
+    }
+    let parameters = report
+        .parameters
+        .iter()
+        .map(|parameter| {
+            query.get(parameter).cloned().ok_or_else(|| {
+                actix_web::error::ErrorBadRequest(format!("parameter {} is required", parameter))
+            })
+        })
+        .collect::<actix_web::Result<Vec<_>>>()?;
+
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let rows = storage
+        .reports_schema()
+        .run_report(
+            &report.sql,
+            &parameters,
+            data.reports_max_rows,
+            data.reports_timeout,
+        )
+        .await
+        .map_err(|err| {
+            // Reports are defined by the operator, so their failures are most likely caused
+            // by the parameters or the timeout rather than by the server.
+            vlog::info!("Report {} failed: {}", report.name, err);
+            actix_web::error::ErrorBadRequest(format!("Report {} failed: {}", report.name, err))
+        })?;
+
+    Ok(HttpResponse::Ok().json(ReportResult {
+        name: report.name.clone(),
+        columns: rows.columns,
+        rows: rows.rows,
+        truncated: rows.truncated,
+    }))
+}
+
 pub fn start_private_core_api(
     connection_pool: ConnectionPool,
     read_only_connection_pool: ConnectionPool,
     eth_client: EthereumGateway,
     config: PrivateApiConfig,
+    leadership: Leadership,
+    execution_tracing: ExecutionTracing,
+    deadlines_config: DeadlinesConfig,
 ) -> JoinHandle<()> {
     let (panic_sender, mut panic_receiver) = mpsc::channel(1);
+    let reports = Arc::new(config.report_definitions());
+    let reports_timeout = config.reports_timeout();
+    let reports_max_rows = config.reports_max_rows;
 
     thread::Builder::new()
         .name("core-private-api".to_string())
@@ -85,6 +2102,12 @@ pub fn start_private_core_api(
                         read_only_connection_pool: read_only_connection_pool.clone(),
                         eth_client: eth_client.clone(),
                         status_cache: Default::default(),
+                        leadership: leadership.clone(),
+                        execution_tracing: execution_tracing.clone(),
+                        deadlines_config: deadlines_config.clone(),
+                        reports: reports.clone(),
+                        reports_timeout,
+                        reports_max_rows,
                     };
 
                     // By calling `register_data` instead of `data` we're avoiding double
@@ -94,6 +2117,52 @@ pub fn start_private_core_api(
                         .app_data(web::Data::new(app_state))
                         .app_data(web::JsonConfig::default().limit(2usize.pow(32)))
                         .service(status)
+                        .service(leadership_status)
+                        .service(execution_tracing_status)
+                        .service(set_execution_tracing)
+                        .service(priority_op_deadlines)
+                        .service(pending_prover_jobs)
+                        .service(mark_block_as_high_priority)
+                        .service(set_prover_job_priority)
+                        .service(reassign_prover_jobs)
+                        .service(webhook_subscriptions)
+                        .service(add_webhook_subscription)
+                        .service(remove_webhook_subscription)
+                        .service(dead_webhook_deliveries)
+                        .service(retry_dead_webhook_deliveries)
+                        .service(audit_log)
+                        .service(check_consistency)
+                        .service(backfills)
+                        .service(scheduled_jobs)
+                        .service(cpk_sponsorships)
+                        .service(fee_free_transfers)
+                        .service(maintenance_mode)
+                        .service(enable_maintenance_mode)
+                        .service(disable_maintenance_mode)
+                        .service(add_address_label)
+                        .service(remove_address_label)
+                        .service(issue_label_api_key)
+                        .service(revoke_label_api_keys)
+                        .service(set_api_keys_max_page_size)
+                        .service(screening_denylist)
+                        .service(add_to_denylist)
+                        .service(remove_from_denylist)
+                        .service(screening_matches)
+                        .service(dropped_txs)
+                        .service(runtime_config)
+                        .service(set_runtime_param)
+                        .service(remove_runtime_param)
+                        .service(runtime_config_changes)
+                        .service(token_price_overrides)
+                        .service(set_token_price_override)
+                        .service(remove_token_price_override)
+                        .service(create2_factories)
+                        .service(add_create2_factory)
+                        .service(remove_create2_factory)
+                        .service(revenue_report)
+                        .service(api_route_stats)
+                        .service(reports)
+                        .service(run_report)
                 })
                 .bind(&config.bind_addr())
                 .expect("failed to bind")
@@ -107,3 +2176,25 @@ pub fn start_private_core_api(
         panic_receiver.next().await.unwrap();
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn actor_header() {
+        let request = TestRequest::default()
+            .insert_header((ACTOR_HEADER, "alice"))
+            .to_http_request();
+        assert_eq!(actor(&request).unwrap(), "alice");
+
+        let request = TestRequest::default()
+            .insert_header((ACTOR_HEADER, ""))
+            .to_http_request();
+        assert!(actor(&request).is_err());
+
+        let request = TestRequest::default().to_http_request();
+        assert!(actor(&request).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! The prover backpressure monitor periodically checks the prover load (the amount of unfinished
+//! prover jobs and the age of the oldest unproven block) and signals the state keeper to seal
+//! blocks less often while the prover is not able to keep up with the block production.
+//!
+//! Thresholds and the strength of the reaction are configured in the `CHAIN_BACKPRESSURE_` section.
+
+// Built-in uses
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+// External uses
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+use zksync_config::configs::chain::Backpressure;
+use zksync_storage::ConnectionPool;
+
+/// Shared signal of the prover load, which is updated by the monitor and read by the state keeper.
+#[derive(Debug, Clone)]
+pub struct ProverBackpressure {
+    overloaded: Arc<AtomicBool>,
+    sealing_slowdown_factor: usize,
+}
+
+impl Default for ProverBackpressure {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl ProverBackpressure {
+    pub fn new(sealing_slowdown_factor: usize) -> Self {
+        Self {
+            overloaded: Arc::new(AtomicBool::new(false)),
+            sealing_slowdown_factor: sealing_slowdown_factor.max(1),
+        }
+    }
+
+    pub fn is_overloaded(&self) -> bool {
+        // `Relaxed` is enough since the signal is only a hint for the state keeper.
+        self.overloaded.load(Ordering::Relaxed)
+    }
+
+    fn set_overloaded(&self, overloaded: bool) {
+        self.overloaded.store(overloaded, Ordering::Relaxed);
+    }
+
+    /// Returns the amount of miniblock iterations before sealing the block adjusted to the prover load.
+    pub fn miniblock_iterations(&self, max_miniblock_iterations: usize) -> usize {
+        if self.is_overloaded() {
+            max_miniblock_iterations.saturating_mul(self.sealing_slowdown_factor)
+        } else {
+            max_miniblock_iterations
+        }
+    }
+}
+
+async fn update_prover_backpressure(
+    db_pool: &ConnectionPool,
+    config: &Backpressure,
+    backpressure: &ProverBackpressure,
+) -> anyhow::Result<()> {
+    let mut storage = db_pool.access_storage().await?;
+    let queue_depth = storage.prover_schema().pending_jobs_count().await?;
+    let oldest_unproven_block_age = storage
+        .prover_schema()
+        .oldest_unproven_block_timestamp()
+        .await?
+        .map(|timestamp| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("failed to get system time");
+            now.saturating_sub(Duration::from_secs(timestamp))
+        })
+        .unwrap_or_default();
+
+    let overloaded = config.is_prover_overloaded(queue_depth, oldest_unproven_block_age);
+    if overloaded != backpressure.is_overloaded() {
+        vlog::info!(
+            "Prover overload status changed to {}: {} pending jobs, oldest unproven block age {}s",
+            overloaded,
+            queue_depth,
+            oldest_unproven_block_age.as_secs()
+        );
+    }
+    backpressure.set_overloaded(overloaded);
+
+    metrics::gauge!("prover_backpressure.queue_depth", queue_depth as f64);
+    metrics::gauge!(
+        "prover_backpressure.oldest_unproven_block_age",
+        oldest_unproven_block_age.as_secs() as f64
+    );
+    metrics::gauge!(
+        "prover_backpressure.overloaded",
+        if overloaded { 1.0 } else { 0.0 }
+    );
+    Ok(())
+}
+
+#[must_use]
+pub fn run_prover_backpressure_monitor(
+    db_pool: ConnectionPool,
+    config: Backpressure,
+    backpressure: ProverBackpressure,
+) -> JoinHandle<()> {
+    let mut timer = time::interval(config.check_interval());
+
+    tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+            if let Err(e) = update_prover_backpressure(&db_pool, &config, &backpressure).await {
+                vlog::error!("Can't update the prover backpressure signal: {:?}", e);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn miniblock_iterations_depend_on_load() {
+        let backpressure = ProverBackpressure::new(3);
+        assert_eq!(backpressure.miniblock_iterations(10), 10);
+
+        backpressure.set_overloaded(true);
+        assert_eq!(backpressure.miniblock_iterations(10), 30);
+
+        // The signal is shared between the clones.
+        let clone = backpressure.clone();
+        backpressure.set_overloaded(false);
+        assert_eq!(clone.miniblock_iterations(10), 10);
+    }
+}
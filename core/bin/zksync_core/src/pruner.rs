@@ -0,0 +1,250 @@
+//! The pruner is responsible for enforcing the retention policies of the block data:
+//! rows of the tables which are older than the configured amount of finalized blocks
+//! are moved to the archive schema or removed from the database.
+//!
+//! Rows are pruned in batches with a delay in between, so the pruning of a big backlog
+//! doesn't affect the other database users. The API returns the `pruned` status for
+//! the archived transactions.
+//!
+//! If the history database is configured, executed transactions are moved there instead
+//! of the archive, and the API keeps serving them from the history database.
+//!
+//! If the object storage is configured, the bodies of the pruned transactions and priority
+//! operations are uploaded there before they are pruned, so the API can still serve them
+//! from the object storage archive at a higher latency.
+//!
+//! The pruner is opt-in (the `pruner` server component): archived transactions are only served
+//! by hash, while the account and block transaction listings don't include them.
+
+// Built-in uses
+use std::time::Duration;
+// External uses
+use tokio::{task::JoinHandle, time};
+
+// Workspace deps
+use zksync_config::{DBConfig, ObjectStoreConfig};
+use zksync_object_store::{archived_tx_key, ObjectStore};
+use zksync_storage::{
+    connection::{access_history_storage, history_sharding_enabled},
+    pruning::PrunedTable,
+    ConnectionPool,
+};
+use zksync_types::BlockNumber;
+
+// Local deps
+use crate::scheduler::{run_scheduled_job, JobSchedule, ScheduledJob};
+
+/// Retention of the table data in the finalized blocks.
+#[derive(Debug, Clone, Copy)]
+struct RetentionPolicy {
+    table: PrunedTable,
+    retained_blocks: u32,
+}
+
+struct Pruner {
+    db_pool: ConnectionPool,
+    policies: Vec<RetentionPolicy>,
+    /// Retention of the executed transactions in the main database, `None` if they are not
+    /// moved to the history database.
+    history_retention: Option<u32>,
+    /// Object storage the bodies of the pruned transactions are uploaded to.
+    archive: Option<ObjectStore>,
+    batch_size: u32,
+    batch_delay: Duration,
+}
+
+impl Pruner {
+    fn new(
+        config: &DBConfig,
+        object_store_config: &ObjectStoreConfig,
+        db_pool: ConnectionPool,
+    ) -> Self {
+        let history_retention = Some(config.history_retention)
+            .filter(|&retained_blocks| retained_blocks > 0 && history_sharding_enabled());
+        let policies = vec![
+            RetentionPolicy {
+                table: PrunedTable::ExecutedTransactions,
+                retained_blocks: config.executed_transactions_retention,
+            },
+            RetentionPolicy {
+                table: PrunedTable::ExecutedPriorityOperations,
+                retained_blocks: config.priority_operations_retention,
+            },
+            RetentionPolicy {
+                table: PrunedTable::BlockWitness,
+                retained_blocks: config.witness_retention,
+            },
+        ]
+        .into_iter()
+        .filter(|policy| policy.retained_blocks > 0)
+        // Sharded transactions are not archived, they are moved to the history database instead.
+        .filter(|policy| {
+            history_retention.is_none() || policy.table != PrunedTable::ExecutedTransactions
+        })
+        .collect();
+
+        Self {
+            db_pool,
+            policies,
+            history_retention,
+            archive: ObjectStore::new(object_store_config),
+            batch_size: config.pruning_batch_size,
+            batch_delay: config.pruning_batch_delay(),
+        }
+    }
+
+    async fn prune(&self) -> anyhow::Result<()> {
+        let last_finalized_block = self
+            .db_pool
+            .access_storage()
+            .await?
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+
+        if let Some(retained_blocks) = self.history_retention {
+            match last_finalized_block.checked_sub(retained_blocks) {
+                Some(last_block) if last_block > 0 => {
+                    self.move_to_history(BlockNumber(last_block)).await?
+                }
+                _ => {}
+            }
+        }
+
+        for policy in &self.policies {
+            let last_block = match last_finalized_block.checked_sub(policy.retained_blocks) {
+                Some(last_block) if last_block > 0 => BlockNumber(last_block),
+                _ => continue,
+            };
+
+            let mut total_pruned = 0;
+            loop {
+                self.archive_batch(policy.table, last_block).await?;
+                // Connection is released between the batches.
+                let pruned = self
+                    .db_pool
+                    .access_storage()
+                    .await?
+                    .pruning_schema()
+                    .prune_table(policy.table, last_block, self.batch_size)
+                    .await?;
+                total_pruned += pruned;
+                if pruned < self.batch_size as u64 {
+                    break;
+                }
+                time::sleep(self.batch_delay).await;
+            }
+            if total_pruned > 0 {
+                vlog::info!(
+                    "Pruned {} rows of the '{}' table up to the block {}",
+                    total_pruned,
+                    policy.table.table_name(),
+                    last_block
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Uploads the bodies of the transactions pruned by the next batch to the object storage.
+    /// Priority operations are also stored under their Ethereum hashes, since the API looks
+    /// them up by both hashes. Does nothing if the object storage is not configured.
+    async fn archive_batch(
+        &self,
+        table: PrunedTable,
+        last_block: BlockNumber,
+    ) -> anyhow::Result<()> {
+        let archive = match &self.archive {
+            Some(archive) => archive,
+            None => return Ok(()),
+        };
+        let txs = self
+            .db_pool
+            .access_storage()
+            .await?
+            .pruning_schema()
+            .load_txs_to_prune(table, last_block, self.batch_size)
+            .await?;
+
+        for tx in &txs {
+            let body = serde_json::to_vec(tx)?;
+            // Hashes of the old priority operations may be empty.
+            let hashes = std::iter::once(&tx.tx_hash)
+                .chain(&tx.eth_hash)
+                .filter(|hash| !hash.is_empty());
+            for hash in hashes {
+                archive.put(&archived_tx_key(hash), body.clone()).await?;
+            }
+        }
+        metrics::counter!("pruner.archived_txs", txs.len() as u64, "table" => table.table_name());
+        Ok(())
+    }
+
+    /// Moves the executed transactions of the blocks up to `last_block` to the history database.
+    /// Every batch is stored to the history database before it's removed from the main one,
+    /// so an interrupted move is completed by the next run.
+    async fn move_to_history(&self, last_block: BlockNumber) -> anyhow::Result<()> {
+        let mut total_moved = 0;
+        loop {
+            let mut storage = self.db_pool.access_storage().await?;
+            let transactions = storage
+                .history_schema()
+                .load_transactions_to_move(last_block, self.batch_size)
+                .await?;
+            if transactions.is_empty() {
+                break;
+            }
+
+            let mut history = access_history_storage()
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("History database is not configured"))?;
+            history
+                .history_schema()
+                .store_transactions(&transactions)
+                .await?;
+
+            let tx_hashes: Vec<_> = transactions.iter().map(|tx| tx.tx_hash.clone()).collect();
+            total_moved += storage
+                .history_schema()
+                .remove_moved_transactions(&tx_hashes)
+                .await?;
+            if transactions.len() < self.batch_size as usize {
+                break;
+            }
+            // Connections are released between the batches.
+            drop(storage);
+            drop(history);
+            time::sleep(self.batch_delay).await;
+        }
+        if total_moved > 0 {
+            vlog::info!(
+                "Moved {} executed transactions up to the block {} to the history database",
+                total_moved,
+                last_block
+            );
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ScheduledJob for Pruner {
+    fn name(&self) -> &'static str {
+        "pruner"
+    }
+
+    async fn run(&mut self) -> anyhow::Result<()> {
+        self.prune().await
+    }
+}
+
+#[must_use]
+pub fn run_pruner(
+    config: &DBConfig,
+    object_store_config: &ObjectStoreConfig,
+    db_pool: ConnectionPool,
+) -> JoinHandle<()> {
+    let pruner = Pruner::new(config, object_store_config, db_pool);
+    run_scheduled_job(pruner, JobSchedule::new(config.pruning_interval()))
+}
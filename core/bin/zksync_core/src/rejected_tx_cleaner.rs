@@ -1,5 +1,7 @@
 //! The cleaner is responsible for removing rejected transactions from the database
 //! that were stored 2 or more weeks ago (this value is configurable as well as the actor's sleep time).
+//! It also removes the old dropped transactions kept searchable for the support, which are bounded
+//! both by the age and by the amount.
 //!
 //! The purpose is not to store the information about the failed transaction execution
 //! which is useful only for a short period of time. Since such transactions are not actually
@@ -7,33 +9,57 @@
 //! them forever.
 
 // External uses
-use tokio::{task::JoinHandle, time};
+use tokio::task::JoinHandle;
 
 // Workspace deps
 use zksync_config::DBConfig;
 use zksync_storage::ConnectionPool;
 
-#[must_use]
-pub fn run_rejected_tx_cleaner(config: &DBConfig, db_pool: ConnectionPool) -> JoinHandle<()> {
-    let max_age = chrono::Duration::from_std(config.rejected_transactions_max_age()).unwrap();
-    let interval = config.rejected_transactions_cleaner_interval();
-    let mut timer = time::interval(interval);
+// Local deps
+use crate::scheduler::{run_scheduled_job, JobSchedule, ScheduledJob};
+
+struct RejectedTxCleaner {
+    db_pool: ConnectionPool,
+    max_age: chrono::Duration,
+    dropped_txs_max_age: chrono::Duration,
+    dropped_txs_max_count: u32,
+}
+
+#[async_trait::async_trait]
+impl ScheduledJob for RejectedTxCleaner {
+    fn name(&self) -> &'static str {
+        "rejected_tx_cleaner"
+    }
 
-    tokio::spawn(async move {
-        loop {
-            let mut storage = db_pool
-                .access_storage()
-                .await
-                .expect("transactions cleaner couldn't access the database");
-            if let Err(e) = storage
-                .chain()
-                .operations_schema()
-                .remove_rejected_transactions(max_age)
-                .await
-            {
-                vlog::error!("Can't delete rejected transactions {:?}", e);
-            }
-            timer.tick().await;
+    async fn run(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.db_pool.access_storage().await?;
+        storage
+            .chain()
+            .operations_schema()
+            .remove_rejected_transactions(self.max_age)
+            .await?;
+        let removed = storage
+            .dropped_txs_schema()
+            .remove_old_dropped_txs(self.dropped_txs_max_age, self.dropped_txs_max_count)
+            .await?;
+        if removed > 0 {
+            vlog::info!("Removed {} old dropped transactions", removed);
         }
-    })
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn run_rejected_tx_cleaner(config: &DBConfig, db_pool: ConnectionPool) -> JoinHandle<()> {
+    let cleaner = RejectedTxCleaner {
+        db_pool,
+        max_age: chrono::Duration::from_std(config.rejected_transactions_max_age()).unwrap(),
+        dropped_txs_max_age: chrono::Duration::from_std(config.dropped_transactions_max_age())
+            .unwrap(),
+        dropped_txs_max_count: config.dropped_transactions_max_count,
+    };
+    run_scheduled_job(
+        cleaner,
+        JobSchedule::new(config.rejected_transactions_cleaner_interval()),
+    )
 }
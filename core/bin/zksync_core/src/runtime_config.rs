@@ -0,0 +1,70 @@
+//! The runtime config watcher periodically reloads the parameters overridden via the private API
+//! and applies them to the components sharing the `SharedRuntimeConfig`: the fee scale is read by
+//! the fee ticker, the sealing parameters by the state keeper, and the log filter is applied to
+//! the logger right away.
+//!
+//! Invalid parameters are skipped, so a bad override can't break the reload of the others.
+
+// Built-in uses
+use std::time::Duration;
+// External uses
+use serde_json::Value;
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+use zksync_storage::ConnectionPool;
+use zksync_types::runtime_config::{RuntimeOverrides, SharedRuntimeConfig};
+
+/// Interval of reloading the overrides from the database.
+const RUNTIME_CONFIG_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+async fn reload_runtime_config(
+    db_pool: &ConnectionPool,
+    runtime_config: &SharedRuntimeConfig,
+) -> anyhow::Result<()> {
+    let mut storage = db_pool.access_storage().await?;
+    let params: Vec<(String, Value)> = storage
+        .runtime_config_schema()
+        .load_params()
+        .await?
+        .into_iter()
+        .filter_map(
+            |param| match RuntimeOverrides::validate_param(&param.key, &param.value) {
+                Ok(()) => Some((param.key, param.value)),
+                Err(err) => {
+                    vlog::warn!("Runtime parameter `{}` is skipped: {}", param.key, err);
+                    None
+                }
+            },
+        )
+        .collect();
+    let overrides = RuntimeOverrides::from_params(params)?;
+
+    let old_log_filter = runtime_config.get().log_filter;
+    if !runtime_config.replace(overrides.clone()) {
+        return Ok(());
+    }
+    vlog::info!("Runtime config is reloaded: {:?}", overrides);
+    if overrides.log_filter != old_log_filter {
+        if let Err(err) = vlog::set_log_filter(overrides.log_filter.as_deref()) {
+            vlog::warn!("Can't apply the log filter: {}", err);
+        }
+    }
+    Ok(())
+}
+
+#[must_use]
+pub fn run_runtime_config_watcher(
+    db_pool: ConnectionPool,
+    runtime_config: SharedRuntimeConfig,
+) -> JoinHandle<()> {
+    let mut timer = time::interval(RUNTIME_CONFIG_RELOAD_INTERVAL);
+
+    tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+            if let Err(e) = reload_runtime_config(&db_pool, &runtime_config).await {
+                vlog::error!("Can't reload the runtime config: {:?}", e);
+            }
+        }
+    })
+}
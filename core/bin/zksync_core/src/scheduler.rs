@@ -0,0 +1,133 @@
+//! The scheduler runs the periodic background jobs of the server, e.g. the pruner
+//! or the events cleaner.
+//!
+//! Every server instance schedules all the jobs of its components, but each run
+//! is performed by a single instance only: the run is guarded by the Postgres advisory lock
+//! of the job taken via a dedicated connection, so the lock is released even if the instance
+//! crashes in the middle of the run. The start and the result of every run are recorded
+//! in the database, and the instance taking the lock skips the run if the job was completed
+//! by another instance recently. The recorded runs are served by the private API as the job health.
+//!
+//! The delay between the runs is randomized by the configured jitter, so the instances
+//! don't compete for the lock at the same time.
+
+// Built-in uses
+use std::time::{Duration, Instant};
+// External uses
+use tokio::{task::JoinHandle, time};
+use zksync_crypto::rand::{thread_rng, Rng};
+
+// Workspace deps
+use zksync_storage::StorageProcessor;
+
+/// Periodic background job run by the scheduler.
+#[async_trait::async_trait]
+pub trait ScheduledJob: Send + 'static {
+    /// Unique name of the job, used for the leader election and the bookkeeping of the runs.
+    fn name(&self) -> &'static str;
+
+    /// Performs a single run of the job.
+    async fn run(&mut self) -> anyhow::Result<()>;
+}
+
+/// Interval between the runs of the job.
+#[derive(Debug, Clone, Copy)]
+pub struct JobSchedule {
+    interval: Duration,
+    jitter: Duration,
+}
+
+impl JobSchedule {
+    /// Creates the schedule with the jitter of 10% of the interval.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            jitter: interval / 10,
+        }
+    }
+
+    /// Sets the maximum deviation of the delay between the runs from the interval.
+    /// The jitter can't exceed the half of the interval.
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        assert!(
+            jitter <= self.interval / 2,
+            "Jitter can't exceed the half of the interval"
+        );
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns the randomized delay before the next run.
+    fn next_delay(&self) -> Duration {
+        let jitter = self.jitter.as_millis() as u64;
+        if jitter == 0 {
+            return self.interval;
+        }
+        let interval = self.interval.as_millis() as u64;
+        Duration::from_millis(thread_rng().gen_range(interval - jitter, interval + jitter + 1))
+    }
+
+    /// Minimal time passed since the last completed run for the job to be run again.
+    /// Lower than the interval, so the runs of the instance holding the lock are not skipped.
+    fn min_gap(&self) -> Duration {
+        self.interval / 2
+    }
+}
+
+#[must_use]
+pub fn run_scheduled_job<J: ScheduledJob>(mut job: J, schedule: JobSchedule) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = run_once(&mut job, &schedule).await {
+                vlog::error!("Can't schedule the job '{}' {:?}", job.name(), e);
+            }
+            time::sleep(schedule.next_delay()).await;
+        }
+    })
+}
+
+/// Runs the job if this instance takes its lock and the job wasn't completed recently.
+async fn run_once<J: ScheduledJob>(job: &mut J, schedule: &JobSchedule) -> anyhow::Result<()> {
+    let name = job.name();
+    // The lock is held by the connection, so it's not taken from the pool.
+    let mut storage = StorageProcessor::establish_connection().await?;
+    if !storage.jobs_schema().try_lock_job(name).await? {
+        vlog::debug!("Job '{}' is run by another instance", name);
+        return Ok(());
+    }
+
+    let last_finished_at = storage
+        .jobs_schema()
+        .load_job(name)
+        .await?
+        .and_then(|job| job.last_finished_at);
+    if let Some(last_finished_at) = last_finished_at {
+        let min_gap = chrono::Duration::from_std(schedule.min_gap())?;
+        if chrono::Utc::now() - last_finished_at < min_gap {
+            vlog::debug!("Job '{}' was run recently by another instance", name);
+            storage.jobs_schema().unlock_job(name).await?;
+            return Ok(());
+        }
+    }
+
+    storage
+        .jobs_schema()
+        .start_job_run(name, schedule.interval)
+        .await?;
+    let start = Instant::now();
+    let result = job.run().await;
+    metrics::histogram!("scheduler.job_run", start.elapsed(), "job" => name);
+
+    let error = result.err().map(|e| format!("{:?}", e));
+    storage
+        .jobs_schema()
+        .finish_job_run(name, error.as_deref())
+        .await?;
+    storage.jobs_schema().unlock_job(name).await?;
+
+    if let Some(error) = error {
+        metrics::increment_counter!("scheduler.job_failures", "job" => name);
+        vlog::error!("Job '{}' failed: {}", name, error);
+    }
+    Ok(())
+}
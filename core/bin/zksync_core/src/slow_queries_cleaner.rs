@@ -0,0 +1,50 @@
+//! The cleaner is responsible for removing the slow queries recorded earlier than the configured
+//! amount of hours ago, so the `slow_queries` table doesn't grow while the recording is enabled.
+
+// External uses
+use tokio::task::JoinHandle;
+
+// Workspace deps
+use zksync_config::DBConfig;
+use zksync_storage::ConnectionPool;
+
+// Local deps
+use crate::scheduler::{run_scheduled_job, JobSchedule, ScheduledJob};
+
+struct SlowQueriesCleaner {
+    db_pool: ConnectionPool,
+    max_age: chrono::Duration,
+}
+
+#[async_trait::async_trait]
+impl ScheduledJob for SlowQueriesCleaner {
+    fn name(&self) -> &'static str {
+        "slow_queries_cleaner"
+    }
+
+    async fn run(&mut self) -> anyhow::Result<()> {
+        let removed = self
+            .db_pool
+            .access_storage()
+            .await?
+            .slow_queries_schema()
+            .remove_old_slow_queries(chrono::Utc::now() - self.max_age)
+            .await?;
+        if removed > 0 {
+            vlog::info!("Removed {} old slow queries", removed);
+        }
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn run_slow_queries_cleaner(config: &DBConfig, db_pool: ConnectionPool) -> JoinHandle<()> {
+    let cleaner = SlowQueriesCleaner {
+        db_pool,
+        max_age: chrono::Duration::from_std(config.slow_queries_max_age()).unwrap(),
+    };
+    run_scheduled_job(
+        cleaner,
+        JobSchedule::new(config.slow_queries_cleaner_interval()),
+    )
+}
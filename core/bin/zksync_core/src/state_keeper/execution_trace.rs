@@ -0,0 +1,162 @@
+//! Opt-in execution trace of the sealed blocks, used to diagnose the transactions which behave
+//! differently in production.
+//!
+//! When the tracing is enabled via the private API, the state keeper emits the trace of every sealed
+//! block as the structured log with the `execution_trace` target keyed by the block number. The trace
+//! contains the applied operations along with the failure reasons, the net balance deltas of the
+//! accounts and the account updates applied to the state tree. The state keeper has no access
+//! to the database, so the trace is not persisted.
+
+// Built-in uses
+use std::collections::BTreeMap;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+// External uses
+use num::BigUint;
+use serde::Serialize;
+// Workspace uses
+use zksync_types::{
+    block::ExecutedOperations, tx::TxHash, AccountId, AccountUpdate, AccountUpdates, BlockNumber,
+    TokenId,
+};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+// Local uses
+use super::pending_block::PendingBlock;
+
+/// Shared switch of the execution tracing, toggled by the private API and read by the state keeper.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionTracing {
+    enabled: Arc<AtomicBool>,
+}
+
+impl ExecutionTracing {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// Operation applied by the state keeper.
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct TracedOperation {
+    pub tx_hash: TxHash,
+    pub op_type: String,
+    /// Index of the operation in the block, absent for the failed transactions.
+    pub block_index: Option<u32>,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+}
+
+/// Net change of the account balance in the block.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub(super) struct BalanceDelta {
+    pub account_id: AccountId,
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub old_balance: BigUint,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub new_balance: BigUint,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct BlockExecutionTrace {
+    pub block_number: BlockNumber,
+    pub timestamp: u64,
+    pub operations: Vec<TracedOperation>,
+    pub balance_deltas: Vec<BalanceDelta>,
+    /// Account updates in the order they are applied to the state tree, including the collected fees.
+    pub account_updates: AccountUpdates,
+}
+
+impl BlockExecutionTrace {
+    /// Creates the trace of the pending block being sealed.
+    pub(super) fn new(block: &PendingBlock) -> Self {
+        let mut operations: Vec<_> = block
+            .success_operations
+            .iter()
+            .map(|op| match op {
+                ExecutedOperations::Tx(tx) => TracedOperation {
+                    tx_hash: tx.signed_tx.hash(),
+                    op_type: op.variance_name(),
+                    block_index: tx.block_index,
+                    success: true,
+                    fail_reason: None,
+                },
+                ExecutedOperations::PriorityOp(priority_op) => TracedOperation {
+                    tx_hash: priority_op.priority_op.tx_hash(),
+                    op_type: op.variance_name(),
+                    block_index: Some(priority_op.block_index),
+                    success: true,
+                    fail_reason: None,
+                },
+            })
+            .collect();
+        operations.extend(block.failed_txs.iter().map(|tx| TracedOperation {
+            tx_hash: tx.signed_tx.hash(),
+            op_type: tx.signed_tx.tx.variance_name(),
+            block_index: None,
+            success: false,
+            fail_reason: tx.fail_reason.clone(),
+        }));
+
+        Self {
+            block_number: block.number,
+            timestamp: block.timestamp,
+            operations,
+            balance_deltas: balance_deltas(&block.account_updates),
+            account_updates: block.account_updates.clone(),
+        }
+    }
+
+    pub(super) fn emit(&self) {
+        match serde_json::to_string(self) {
+            Ok(trace) => vlog::info!(
+                target: "execution_trace",
+                block_number = *self.block_number,
+                trace = %trace,
+                "Execution trace of the block {}",
+                *self.block_number
+            ),
+            Err(e) => vlog::warn!(
+                "Can't serialize the execution trace of the block {}: {}",
+                *self.block_number,
+                e
+            ),
+        }
+    }
+}
+
+/// Folds the balance updates into the net change of every account balance, skipping the unchanged ones.
+pub(super) fn balance_deltas(updates: &[(AccountId, AccountUpdate)]) -> Vec<BalanceDelta> {
+    let mut deltas: BTreeMap<(AccountId, TokenId), (BigUint, BigUint)> = BTreeMap::new();
+    for (account_id, update) in updates {
+        if let AccountUpdate::UpdateBalance {
+            balance_update: (token, old_balance, new_balance),
+            ..
+        } = update
+        {
+            deltas
+                .entry((*account_id, *token))
+                .or_insert_with(|| (old_balance.clone(), new_balance.clone()))
+                .1 = new_balance.clone();
+        }
+    }
+
+    deltas
+        .into_iter()
+        .filter(|(_, (old_balance, new_balance))| old_balance != new_balance)
+        .map(
+            |((account_id, token), (old_balance, new_balance))| BalanceDelta {
+                account_id,
+                token,
+                old_balance,
+                new_balance,
+            },
+        )
+        .collect()
+}
@@ -19,12 +19,15 @@ use zksync_types::{
         PendingBlock as SendablePendingBlock,
     },
     gas_counter::GasCounter,
+    gas_prediction::GasPredictor,
     mempool::SignedTxVariant,
+    runtime_config::SharedRuntimeConfig,
     tx::ZkSyncTx,
-    Address, PriorityOp, SignedZkSyncTx,
+    Address, PriorityOp, SignedZkSyncTx, ZkSyncOp,
 };
 // Local uses
 use self::{
+    execution_trace::BlockExecutionTrace,
     pending_block::PendingBlock,
     root_hash_calculator::{BlockRootHashJob, RootHashCalculator},
     types::{ApplyOutcome, StateKeeperConfig},
@@ -32,15 +35,19 @@ use self::{
 };
 use crate::{
     committer::{BlockCommitRequest, CommitRequest},
+    priority_op_deadlines::PriorityOpDeadlineTracker,
+    prover_backpressure::ProverBackpressure,
     tx_event_emitter::ProcessedOperations,
 };
 
 pub use self::{
+    execution_trace::ExecutionTracing,
     init_params::ZkSyncStateInitParams,
     root_hash_calculator::{start_root_hash_calculator, BlockRootHashJobQueue},
     types::StateKeeperTestkitRequest,
 };
 
+mod execution_trace;
 mod init_params;
 mod pending_block;
 mod root_hash_calculator;
@@ -68,6 +75,19 @@ pub struct ZkSyncStateKeeper {
     /// Contains blocks that were sealed but for which root hash has not been calculated yet.
     root_hash_queue: BlockRootHashJobQueue,
 
+    /// Prover load signal. Blocks are sealed less often while the prover is overloaded.
+    prover_backpressure: ProverBackpressure,
+    /// Switch of the execution traces of the sealed blocks.
+    execution_tracing: ExecutionTracing,
+    /// Last known L1 block used to seal the blocks with the priority operations close to their deadlines.
+    deadline_tracker: PriorityOpDeadlineTracker,
+    /// Calibrated costs used to predict the L1 gas of the pending block.
+    gas_predictor: GasPredictor,
+    /// Predicted L1 gas of the block above which the block is sealed.
+    max_block_gas: Option<u64>,
+    /// Sealing parameters overridden at runtime, which take precedence over the config.
+    runtime_config: SharedRuntimeConfig,
+
     /// Queue of reverted blocks
     /// They will be executed before the start of the StateKeeper
     reverted_blocks: VecDeque<IncompleteBlock>,
@@ -158,6 +178,12 @@ impl ZkSyncStateKeeper {
             processed_tx_events_sender,
 
             root_hash_queue,
+            prover_backpressure: ProverBackpressure::default(),
+            execution_tracing: ExecutionTracing::default(),
+            deadline_tracker: PriorityOpDeadlineTracker::default(),
+            gas_predictor: GasPredictor::default(),
+            max_block_gas: None,
+            runtime_config: SharedRuntimeConfig::default(),
             reverted_blocks: initial_state.reverted_blocks.clone(),
         };
         keeper.initialize(initial_state.pending_block);
@@ -244,6 +270,56 @@ impl ZkSyncStateKeeper {
         metrics::histogram!("state_keeper.initialize", start.elapsed());
     }
 
+    /// Sets the prover load signal used to adjust the block sealing rate.
+    pub fn set_prover_backpressure(&mut self, prover_backpressure: ProverBackpressure) {
+        self.prover_backpressure = prover_backpressure;
+    }
+
+    /// Sets the switch of the execution traces toggled via the private API.
+    pub fn set_execution_tracing(&mut self, execution_tracing: ExecutionTracing) {
+        self.execution_tracing = execution_tracing;
+    }
+
+    /// Sets the last L1 block shared by the Ethereum watcher.
+    pub fn set_deadline_tracker(&mut self, deadline_tracker: PriorityOpDeadlineTracker) {
+        self.deadline_tracker = deadline_tracker;
+    }
+
+    /// Sets the costs used to predict the L1 gas of the blocks and the limit of the predicted gas.
+    pub fn set_gas_prediction(&mut self, gas_predictor: GasPredictor, max_block_gas: Option<u64>) {
+        self.gas_predictor = gas_predictor;
+        self.max_block_gas = max_block_gas;
+    }
+
+    /// Sets the parameters overridden at runtime.
+    pub fn set_runtime_config(&mut self, runtime_config: SharedRuntimeConfig) {
+        self.runtime_config = runtime_config;
+    }
+
+    /// Checks that the operations won't bring the predicted L1 gas of the pending block over the limit.
+    /// Operations always fit into an empty block, otherwise they would never be included.
+    fn fits_predicted_gas(&self, ops: &[ZkSyncOp]) -> bool {
+        // The zero limit overridden at runtime disables the limit from the config.
+        let max_block_gas = match self.runtime_config.get().max_block_gas {
+            Some(0) => None,
+            Some(max_block_gas) => Some(max_block_gas),
+            None => self.max_block_gas,
+        };
+        let max_block_gas = match max_block_gas {
+            Some(max_block_gas) => max_block_gas,
+            None => return true,
+        };
+        if self.pending_block.success_operations.is_empty() {
+            return true;
+        }
+
+        let prediction = ops.iter().fold(
+            self.gas_predictor.fixed_cost() + self.pending_block.predicted_ops_gas,
+            |prediction, op| prediction + self.gas_predictor.op_cost(op),
+        );
+        prediction.total() <= max_block_gas
+    }
+
     pub async fn execute_reverted_blocks(&mut self) {
         while let Some(block) = self.reverted_blocks.pop_front() {
             self.execute_incomplete_block(block).await;
@@ -384,6 +460,13 @@ impl ZkSyncStateKeeper {
             .expect("committer receiver dropped");
     }
 
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            priority_ops = proposed_block.priority_ops.len(),
+            txs = proposed_block.txs.len()
+        )
+    )]
     async fn execute_proposed_block(&mut self, proposed_block: ProposedBlock) {
         if !proposed_block.is_empty() {
             vlog::info!(
@@ -472,11 +555,26 @@ impl ZkSyncStateKeeper {
         // Iteration is complete, increment it in the pending block.
         self.pending_block.increment_iteration();
 
-        // If pending block contains withdrawals we seal it faster
-        let max_miniblock_iterations = if self.pending_block.fast_processing_required {
-            self.config.fast_miniblock_iterations
+        // If pending block contains priority operations close to their deadlines, we seal it right away.
+        // If it contains withdrawals we seal it faster.
+        // Otherwise the block is sealed slower if the prover can't keep up with the block production.
+        let overrides = self.runtime_config.get();
+        let max_miniblock_iterations = if self.pending_block.deadline_escalation_required {
+            0
+        } else if self.pending_block.fast_processing_required {
+            overrides
+                .fast_block_miniblock_iterations
+                .map_or(self.config.fast_miniblock_iterations, |iterations| {
+                    iterations as usize
+                })
         } else {
-            self.config.max_miniblock_iterations
+            let max_miniblock_iterations = overrides
+                .miniblock_iterations
+                .map_or(self.config.max_miniblock_iterations, |iterations| {
+                    iterations as usize
+                });
+            self.prover_backpressure
+                .miniblock_iterations(max_miniblock_iterations)
         };
 
         // Check whether we should seal this block and start processing the next one, or we just need
@@ -497,6 +595,7 @@ impl ZkSyncStateKeeper {
     }
 
     // Err if there is no space in current block
+    #[tracing::instrument(skip_all, fields(serial_id = priority_op.serial_id))]
     fn apply_priority_op(&mut self, priority_op: &PriorityOp) -> ApplyOutcome<ExecutedOperations> {
         let start = Instant::now();
         let chunks_needed = priority_op.data.chunks();
@@ -509,6 +608,9 @@ impl ZkSyncStateKeeper {
         let non_executed_op = self
             .state
             .priority_op_to_zksync_op(priority_op.data.clone());
+        if !self.fits_predicted_gas(std::slice::from_ref(&non_executed_op)) {
+            return ApplyOutcome::NotIncluded;
+        }
 
         #[allow(clippy::question_mark)] // False positive, we aren't returning `Result`.
         if self
@@ -527,8 +629,29 @@ impl ZkSyncStateKeeper {
             updates,
             executed_op,
         } = self.state.execute_priority_op(priority_op.data.clone());
+        self.pending_block.predicted_ops_gas += self.gas_predictor.op_cost(&executed_op);
         let block_index = self.pending_block.pending_op_block_index;
 
+        if let Some(remaining_blocks) = self
+            .deadline_tracker
+            .remaining_blocks(priority_op.deadline_block)
+        {
+            metrics::histogram!(
+                "state_keeper.priority_op_remaining_blocks",
+                remaining_blocks as f64
+            );
+            if self.deadline_tracker.is_close(priority_op.deadline_block) {
+                vlog::warn!(
+                    "Priority operation #{} is {} L1 blocks away from its deadline, sealing the block {} right away",
+                    priority_op.serial_id,
+                    remaining_blocks,
+                    *self.pending_block.number
+                );
+                metrics::increment_counter!("state_keeper.priority_op_deadline_escalations");
+                self.pending_block.deadline_escalation_required = true;
+            }
+        }
+
         let exec_result = ExecutedOperations::PriorityOp(Box::new(ExecutedPriorityOp {
             op: executed_op,
             priority_op: priority_op.clone(),
@@ -547,6 +670,7 @@ impl ZkSyncStateKeeper {
         ApplyOutcome::Included(exec_result)
     }
 
+    #[tracing::instrument(skip_all, fields(batch_id = batch_id, batch_size = txs.len()))]
     fn apply_batch(
         &mut self,
         txs: &[SignedZkSyncTx],
@@ -594,7 +718,7 @@ impl ZkSyncStateKeeper {
 
         // If we can't add the tx to the block due to the gas limit, we return this tx,
         // seal the block and execute it again.
-        if !self.pending_block.gas_counter.can_include(&ops) {
+        if !self.pending_block.gas_counter.can_include(&ops) || !self.fits_predicted_gas(&ops) {
             return ApplyOutcome::NotIncluded;
         }
 
@@ -613,6 +737,8 @@ impl ZkSyncStateKeeper {
                         .gas_counter
                         .add_op(&executed_op)
                         .expect("We have already checked that we can include this tx");
+                    self.pending_block.predicted_ops_gas +=
+                        self.gas_predictor.op_cost(&executed_op);
                     let chunks_used = executed_op.chunks();
 
                     let block_index = self.pending_block.pending_op_block_index;
@@ -664,6 +790,7 @@ impl ZkSyncStateKeeper {
         ApplyOutcome::Included(executed_operations)
     }
 
+    #[tracing::instrument(skip_all, fields(tx_hash = %tx.hash().to_string()))]
     fn apply_tx(&mut self, tx: &SignedZkSyncTx) -> ApplyOutcome<ExecutedOperations> {
         let start = Instant::now();
         let chunks_needed = self.state.chunks_for_tx(tx);
@@ -683,7 +810,8 @@ impl ZkSyncStateKeeper {
             if !self
                 .pending_block
                 .gas_counter
-                .can_include(&[non_executed_op])
+                .can_include(std::slice::from_ref(&non_executed_op))
+                || !self.fits_predicted_gas(&[non_executed_op])
             {
                 // We've reached the gas limit, seal the block.
                 // This transaction will go into the next one.
@@ -712,6 +840,7 @@ impl ZkSyncStateKeeper {
                     .gas_counter
                     .add_op(&executed_op)
                     .expect("We have already checked that we can include this tx");
+                self.pending_block.predicted_ops_gas += self.gas_predictor.op_cost(&executed_op);
 
                 let block_index = self.pending_block.pending_op_block_index;
                 let exec_result = ExecutedOperations::Tx(Box::new(ExecutedTx {
@@ -756,6 +885,7 @@ impl ZkSyncStateKeeper {
     }
 
     /// Finalizes the pending block, transforming it into a full block.
+    #[tracing::instrument(skip_all)]
     async fn seal_pending_block(&mut self) {
         let start = Instant::now();
 
@@ -796,6 +926,12 @@ impl ZkSyncStateKeeper {
                 .map(|tx| ExecutedOperations::Tx(Box::new(tx))),
         );
 
+        let predicted_gas = self.gas_predictor.fixed_cost() + self.pending_block.predicted_ops_gas;
+        metrics::histogram!(
+            "state_keeper.predicted_block_gas",
+            predicted_gas.total() as f64
+        );
+
         let commit_gas_limit = self.pending_block.gas_counter.commit_gas_limit();
         let verify_gas_limit = self.pending_block.gas_counter.verify_gas_limit();
 
@@ -841,6 +977,9 @@ impl ZkSyncStateKeeper {
             block: current_block,
             updates: self.pending_block.account_updates.clone(),
         };
+        if self.execution_tracing.is_enabled() {
+            BlockExecutionTrace::new(&self.pending_block).emit();
+        }
 
         vlog::info!(
             "Creating full block: {}, operations: {}, chunks_left: {}, miniblock iterations: {}",
@@ -868,6 +1007,7 @@ impl ZkSyncStateKeeper {
 
     /// Stores intermediate representation of a pending block in the database,
     /// so the executed transactions are persisted and won't be lost.
+    #[tracing::instrument(skip_all)]
     async fn store_pending_block(&mut self) {
         let start = Instant::now();
 
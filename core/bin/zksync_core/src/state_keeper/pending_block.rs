@@ -4,6 +4,7 @@ use zksync_state::state::CollectedFee;
 use zksync_types::{
     block::{ExecutedOperations, ExecutedTx, PendingBlock as SendablePendingBlock},
     gas_counter::GasCounter,
+    gas_prediction::BlockGasPrediction,
     AccountUpdates, BlockNumber,
 };
 
@@ -23,8 +24,13 @@ pub(super) struct PendingBlock {
     pub(super) unprocessed_priority_op_current: u64,
     pub(super) pending_block_iteration: usize,
     pub(super) gas_counter: GasCounter,
+    /// Predicted L1 gas of the operations in the block, excluding the per-block costs.
+    pub(super) predicted_ops_gas: BlockGasPrediction,
     /// Option denoting if this block should be generated faster than usual.
     pub(super) fast_processing_required: bool,
+    /// Option denoting if this block contains a priority operation close to its deadline,
+    /// and thus should be sealed right away.
+    pub(super) deadline_escalation_required: bool,
     /// Fee should be applied only when sealing the block (because of corresponding logic in the circuit)
     pub(super) collected_fees: Vec<CollectedFee>,
     /// Number of stored account updates in the db (from `account_updates` field)
@@ -57,7 +63,9 @@ impl PendingBlock {
             unprocessed_priority_op_current: unprocessed_priority_op_before,
             pending_block_iteration: 0,
             gas_counter: GasCounter::new(),
+            predicted_ops_gas: BlockGasPrediction::default(),
             fast_processing_required: false,
+            deadline_escalation_required: false,
             collected_fees: Vec::new(),
             stored_account_updates: 0,
             timestamp,
@@ -1,6 +1,7 @@
 use zksync_types::TokenId;
 
 use super::utils::*;
+use crate::priority_op_deadlines::PriorityOpDeadlineTracker;
 
 /// Checks if deposit is processed correctly by the state_keeper.
 #[test]
@@ -29,3 +30,39 @@ fn not_enough_chunks() {
     let result = tester.state_keeper.apply_priority_op(&deposit);
     assert!(result.is_not_included());
 }
+
+/// Checks if deposit close to its deadline makes the block to be sealed right away.
+#[test]
+fn close_deadline() {
+    let mut tester = StateKeeperTester::new(8, 1, 1);
+    let deadline_tracker = PriorityOpDeadlineTracker::new(100);
+    deadline_tracker.set_last_eth_block(1000);
+    tester
+        .state_keeper
+        .set_deadline_tracker(deadline_tracker.clone());
+
+    let mut deposit = create_deposit(TokenId(0), 145u32);
+    deposit.deadline_block = 1200;
+    assert!(tester
+        .state_keeper
+        .apply_priority_op(&deposit)
+        .is_included());
+    assert!(
+        !tester
+            .state_keeper
+            .pending_block
+            .deadline_escalation_required
+    );
+
+    deadline_tracker.set_last_eth_block(1150);
+    assert!(tester
+        .state_keeper
+        .apply_priority_op(&deposit)
+        .is_included());
+    assert!(
+        tester
+            .state_keeper
+            .pending_block
+            .deadline_escalation_required
+    );
+}
@@ -0,0 +1,58 @@
+use num::BigUint;
+use zksync_types::{AccountId, TokenId};
+
+use super::utils::*;
+use crate::state_keeper::execution_trace::BlockExecutionTrace;
+
+/// Checks that the execution trace contains all the applied operations and the net balance deltas.
+#[test]
+fn execution_trace() {
+    let mut tester = StateKeeperTester::new(20, 3, 3);
+    let good_withdraw = create_account_and_withdrawal(
+        &mut tester,
+        TokenId(0),
+        AccountId(1),
+        200u32,
+        145u32,
+        Default::default(),
+    );
+    let bad_withdraw = create_account_and_withdrawal(
+        &mut tester,
+        TokenId(2),
+        AccountId(2),
+        100u32,
+        145u32,
+        Default::default(),
+    );
+    let deposit = create_deposit(TokenId(0), 12u32);
+
+    assert!(tester.state_keeper.apply_tx(&good_withdraw).is_included());
+    assert!(tester.state_keeper.apply_tx(&bad_withdraw).is_included());
+    assert!(tester
+        .state_keeper
+        .apply_priority_op(&deposit)
+        .is_included());
+
+    let trace = BlockExecutionTrace::new(&tester.state_keeper.pending_block);
+    assert_eq!(trace.block_number, tester.state_keeper.pending_block.number);
+    assert_eq!(trace.operations.len(), 3);
+    // Failed transactions are traced after the successful operations.
+    assert_eq!(trace.operations[0].tx_hash, good_withdraw.hash());
+    assert_eq!(trace.operations[1].tx_hash, deposit.tx_hash());
+    assert_eq!(trace.operations[2].tx_hash, bad_withdraw.hash());
+    assert!(!trace.operations[2].success);
+    assert!(trace.operations[2].fail_reason.is_some());
+    assert_eq!(
+        trace.account_updates,
+        tester.state_keeper.pending_block.account_updates
+    );
+
+    // Fees are not collected until the block is sealed.
+    assert_eq!(trace.balance_deltas.len(), 2);
+    let withdrawal_delta = &trace.balance_deltas[0];
+    assert_eq!(withdrawal_delta.account_id, AccountId(1));
+    assert_eq!(withdrawal_delta.token, TokenId(0));
+    assert_eq!(withdrawal_delta.old_balance, BigUint::from(200u32));
+    assert_eq!(withdrawal_delta.new_balance, BigUint::from(54u32));
+    assert_eq!(trace.balance_deltas[1].new_balance, BigUint::from(12u32));
+}
@@ -1,5 +1,6 @@
 use zksync_mempool::ProposedBlock;
-use zksync_types::gas_counter::{GasCounter, VerifyCost, TX_GAS_LIMIT};
+use zksync_types::gas_counter::{CommitCost, GasCounter, VerifyCost, TX_GAS_LIMIT};
+use zksync_types::gas_prediction::GasPredictor;
 use zksync_types::{
     mempool::SignedTxVariant, mempool::SignedTxsBatch, AccountId, ExecutedOperations, TokenId,
 };
@@ -236,3 +237,37 @@ async fn batch_gas_limit() {
         })
         .await;
 }
+
+/// Checks that the block is sealed once the predicted L1 gas reaches the limit.
+#[test]
+fn predicted_gas_limit_reached() {
+    let mut tester = StateKeeperTester::new(100, 5, 5);
+    let predictor = GasPredictor::default();
+    // The fixed cost of the block and two withdrawals fit into the limit.
+    let max_block_gas = predictor.fixed_cost().total()
+        + 2 * (CommitCost::WITHDRAW_COST + VerifyCost::WITHDRAW_COST);
+    tester
+        .state_keeper
+        .set_gas_prediction(predictor.clone(), Some(max_block_gas));
+
+    for i in 1..=3 {
+        let withdrawal = create_account_and_withdrawal(
+            &mut tester,
+            TokenId(0),
+            AccountId(i),
+            200u32,
+            145u32,
+            Default::default(),
+        );
+        let result = tester.state_keeper.apply_tx(&withdrawal);
+        if i <= 2 {
+            assert!(result.is_included(), "i: {}", i);
+        } else {
+            assert!(result.is_not_included(), "i: {}", i);
+        }
+    }
+    assert_eq!(
+        (predictor.fixed_cost() + tester.state_keeper.pending_block.predicted_ops_gas).total(),
+        max_block_gas
+    );
+}
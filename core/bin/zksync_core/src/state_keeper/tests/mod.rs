@@ -5,6 +5,7 @@ use zksync_types::{AccountId, H160, *};
 mod apply_priority_op;
 mod apply_tx;
 mod execute_proposed_block;
+mod execution_trace;
 mod gas_limit;
 mod pending_block;
 mod utils;
@@ -304,6 +304,7 @@ pub fn create_deposit(token: TokenId, amount: impl Into<BigUint>) -> PriorityOp
         eth_hash: H256::zero(),
         eth_block: 0,
         eth_block_index: None,
+        l1_sender: None,
     }
 }
 
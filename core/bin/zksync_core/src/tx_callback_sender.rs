@@ -0,0 +1,144 @@
+//! Tx callback sender delivers the status callbacks of the transactions submitted with the
+//! API keys to the endpoints registered for their scopes via the REST API.
+//!
+//! Callbacks are signed and retried the same way as the webhooks (see `webhook_sender`), with the
+//! `X-Zksync-Callback-Id` header instead of the event id. Callbacks are enqueued by the storage
+//! once per status of the transaction, so the receivers only have to deduplicate the retries of
+//! the same callback, e.g. when the response was lost.
+
+// Built-in uses
+use std::time::Instant;
+// External uses
+use chrono::Utc;
+use futures::future;
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+use zksync_api_types::v02::tx_callback::{TxCallback, TxCallbackStatus, TX_CALLBACK_ID_HEADER};
+use zksync_config::WebhooksConfig;
+use zksync_storage::{tx_callbacks::records::PendingTxCallback, ConnectionPool};
+use zksync_types::{tx::TxHash, BlockNumber};
+// Local uses
+use crate::webhook_sender::{sign_payload, SIGNATURE_HEADER};
+
+fn callback_body(callback: &PendingTxCallback) -> anyhow::Result<TxCallback> {
+    Ok(TxCallback {
+        id: callback.id as u64,
+        tx_hash: TxHash::from_slice(&callback.tx_hash)
+            .ok_or_else(|| anyhow::anyhow!("invalid tx hash is stored"))?,
+        status: TxCallbackStatus::parse(&callback.tx_status)
+            .ok_or_else(|| anyhow::anyhow!("invalid tx status is stored"))?,
+        block_number: BlockNumber(callback.block_number as u32),
+        fail_reason: callback.fail_reason.clone(),
+        created_at: callback.created_at,
+    })
+}
+
+#[derive(Debug)]
+struct TxCallbackSender {
+    db_pool: ConnectionPool,
+    client: reqwest::Client,
+    config: WebhooksConfig,
+}
+
+impl TxCallbackSender {
+    fn new(db_pool: ConnectionPool, config: WebhooksConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout())
+            .build()
+            .expect("failed to build the tx callback HTTP client");
+        Self {
+            db_pool,
+            client,
+            config,
+        }
+    }
+
+    async fn send(&self, callback: &PendingTxCallback) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(&callback_body(callback)?)?;
+        let signature = sign_payload(&callback.secret, &body);
+
+        let response = self
+            .client
+            .post(&callback.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(TX_CALLBACK_ID_HEADER, callback.id)
+            .body(body)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("callback endpoint responded with {}", response.status());
+        }
+        Ok(())
+    }
+
+    async fn process_callbacks(&self) -> anyhow::Result<()> {
+        let start = Instant::now();
+        let callbacks = self
+            .db_pool
+            .access_storage()
+            .await?
+            .tx_callbacks_schema()
+            .load_due_callbacks(self.config.batch_size)
+            .await?;
+        if callbacks.is_empty() {
+            return Ok(());
+        }
+
+        let results = future::join_all(callbacks.iter().map(|callback| self.send(callback))).await;
+
+        let mut storage = self.db_pool.access_storage().await?;
+        for (callback, result) in callbacks.iter().zip(results) {
+            match result {
+                Ok(()) => {
+                    storage
+                        .tx_callbacks_schema()
+                        .confirm_callback(callback.id)
+                        .await?;
+                    metrics::increment_counter!("tx_callback_sender.delivered");
+                }
+                Err(err) => {
+                    let failed_attempts = callback.attempts as u32 + 1;
+                    let retry_at = self.config.retry_delay(failed_attempts).map(|delay| {
+                        Utc::now()
+                            + chrono::Duration::from_std(delay).expect("retry delay overflow")
+                    });
+                    if retry_at.is_some() {
+                        metrics::increment_counter!("tx_callback_sender.failed");
+                    } else {
+                        vlog::warn!(
+                            "Tx callback {} to {} is dead after {} attempts: {}",
+                            callback.id,
+                            callback.url,
+                            failed_attempts,
+                            err
+                        );
+                        metrics::increment_counter!("tx_callback_sender.dead");
+                    }
+                    storage
+                        .tx_callbacks_schema()
+                        .fail_callback(callback.id, &err.to_string(), retry_at)
+                        .await?;
+                }
+            }
+        }
+
+        metrics::histogram!("tx_callback_sender.process_callbacks", start.elapsed());
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn run_tx_callback_sender(db_pool: ConnectionPool, config: WebhooksConfig) -> JoinHandle<()> {
+    let mut timer = time::interval(config.poll_interval());
+    let sender = TxCallbackSender::new(db_pool, config);
+
+    tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+            if let Err(e) = sender.process_callbacks().await {
+                vlog::error!("Can't process the tx callbacks: {:?}", e);
+            }
+        }
+    })
+}
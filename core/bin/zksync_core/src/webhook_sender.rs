@@ -0,0 +1,212 @@
+//! Webhook sender delivers the events from the durable event outbox to the webhooks
+//! registered by the operator via the private API.
+//!
+//! Every event is sent as a `POST` request with the JSON body signed by the subscription secret:
+//! the `X-Zksync-Signature` header contains the hex-encoded HMAC-SHA256 of the request body.
+//! Deliveries are performed at least once, so receivers should deduplicate the events by the
+//! `X-Zksync-Event-Id` header. Failed deliveries are retried with the exponential backoff and
+//! are moved to the dead letters once they run out of attempts.
+//!
+//! The outbox cleaner removes the events older than the configured retention period
+//! once they have no pending deliveries.
+
+// Built-in uses
+use std::time::Instant;
+// External uses
+use chrono::{DateTime, Utc};
+use futures::future;
+use hmac::{Hmac, Mac, NewMac};
+use serde::Serialize;
+use sha2::Sha256;
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+use zksync_config::WebhooksConfig;
+use zksync_storage::{outbox::records::PendingWebhookDelivery, ConnectionPool};
+// Local uses
+use crate::scheduler::{run_scheduled_job, JobSchedule, ScheduledJob};
+
+pub const SIGNATURE_HEADER: &str = "X-Zksync-Signature";
+pub const EVENT_ID_HEADER: &str = "X-Zksync-Event-Id";
+
+/// Body of the webhook request.
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    id: i64,
+    #[serde(rename = "type")]
+    event_type: &'a str,
+    data: &'a serde_json::Value,
+    created_at: DateTime<Utc>,
+}
+
+/// Returns the hex-encoded HMAC-SHA256 of the body.
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[derive(Debug)]
+struct WebhookSender {
+    db_pool: ConnectionPool,
+    client: reqwest::Client,
+    config: WebhooksConfig,
+}
+
+impl WebhookSender {
+    fn new(db_pool: ConnectionPool, config: WebhooksConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout())
+            .build()
+            .expect("failed to build the webhook HTTP client");
+        Self {
+            db_pool,
+            client,
+            config,
+        }
+    }
+
+    async fn send(&self, delivery: &PendingWebhookDelivery) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(&WebhookPayload {
+            id: delivery.event_id,
+            event_type: &delivery.event_type,
+            data: &delivery.payload,
+            created_at: delivery.created_at,
+        })?;
+        let signature = sign_payload(&delivery.secret, &body);
+
+        let response = self
+            .client
+            .post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(EVENT_ID_HEADER, delivery.event_id)
+            .body(body)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("webhook responded with {}", response.status());
+        }
+        Ok(())
+    }
+
+    async fn process_deliveries(&self) -> anyhow::Result<()> {
+        let start = Instant::now();
+        let deliveries = self
+            .db_pool
+            .access_storage()
+            .await?
+            .outbox_schema()
+            .load_due_webhook_deliveries(self.config.batch_size)
+            .await?;
+        if deliveries.is_empty() {
+            return Ok(());
+        }
+
+        let results = future::join_all(deliveries.iter().map(|delivery| self.send(delivery))).await;
+
+        let mut storage = self.db_pool.access_storage().await?;
+        for (delivery, result) in deliveries.iter().zip(results) {
+            match result {
+                Ok(()) => {
+                    storage
+                        .outbox_schema()
+                        .confirm_webhook_delivery(delivery.id)
+                        .await?;
+                    metrics::increment_counter!("webhook_sender.delivered");
+                }
+                Err(err) => {
+                    let failed_attempts = delivery.attempts as u32 + 1;
+                    let retry_at = self.config.retry_delay(failed_attempts).map(|delay| {
+                        Utc::now()
+                            + chrono::Duration::from_std(delay).expect("retry delay overflow")
+                    });
+                    if retry_at.is_some() {
+                        metrics::increment_counter!("webhook_sender.failed");
+                    } else {
+                        vlog::warn!(
+                            "Webhook delivery {} of the event {} to {} is dead after {} attempts: {}",
+                            delivery.id,
+                            delivery.event_id,
+                            delivery.url,
+                            failed_attempts,
+                            err
+                        );
+                        metrics::increment_counter!("webhook_sender.dead");
+                    }
+                    storage
+                        .outbox_schema()
+                        .fail_webhook_delivery(delivery.id, &err.to_string(), retry_at)
+                        .await?;
+                }
+            }
+        }
+
+        metrics::histogram!("webhook_sender.process_deliveries", start.elapsed());
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn run_webhook_sender(db_pool: ConnectionPool, config: WebhooksConfig) -> JoinHandle<()> {
+    let mut timer = time::interval(config.poll_interval());
+    let sender = WebhookSender::new(db_pool, config);
+
+    tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+            if let Err(e) = sender.process_deliveries().await {
+                vlog::error!("Can't process the webhook deliveries: {:?}", e);
+            }
+        }
+    })
+}
+
+struct OutboxCleaner {
+    db_pool: ConnectionPool,
+    retention: chrono::Duration,
+}
+
+#[async_trait::async_trait]
+impl ScheduledJob for OutboxCleaner {
+    fn name(&self) -> &'static str {
+        "outbox_cleaner"
+    }
+
+    async fn run(&mut self) -> anyhow::Result<()> {
+        let removed = self
+            .db_pool
+            .access_storage()
+            .await?
+            .outbox_schema()
+            .remove_old_outbox_events(Utc::now() - self.retention)
+            .await?;
+        if removed > 0 {
+            vlog::info!("Removed {} old outbox events", removed);
+        }
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn run_outbox_cleaner(db_pool: ConnectionPool, config: &WebhooksConfig) -> JoinHandle<()> {
+    let cleaner = OutboxCleaner {
+        db_pool,
+        retention: chrono::Duration::from_std(config.outbox_retention()).unwrap(),
+    };
+    run_scheduled_job(cleaner, JobSchedule::new(config.outbox_cleaner_interval()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the signature against the HMAC-SHA256 test vector from RFC 4231 (test case 2).
+    #[test]
+    fn payload_signature() {
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
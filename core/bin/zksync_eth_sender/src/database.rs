@@ -8,7 +8,7 @@ use zksync_basic_types::{H256, U256};
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
 // Local uses
-use super::transactions::ETHStats;
+use super::transactions::{ETHStats, GasSpent};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::block::Block;
 
@@ -81,6 +81,7 @@ pub(super) trait DatabaseInterface {
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        gas_spent: Option<GasSpent>,
     ) -> anyhow::Result<()>;
 
     /// Loads the stored Ethereum operations stats.
@@ -256,6 +257,7 @@ impl DatabaseInterface for Database {
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        gas_spent: Option<GasSpent>,
     ) -> anyhow::Result<()> {
         let mut transaction = connection.start_transaction().await?;
 
@@ -308,10 +310,22 @@ impl DatabaseInterface for Database {
                         AggregatedActionType::ExecuteBlocks,
                     )
                     .await?;
+                // Withdrawals of the executed blocks are completed, so their recipients
+                // can be notified.
+                transaction
+                    .withdrawal_hooks_schema()
+                    .schedule_withdrawal_hooks(first_block, last_block)
+                    .await?;
             }
             _ => {}
         }
 
+        if let Some(gas_spent) = gas_spent {
+            transaction
+                .ethereum_schema()
+                .store_gas_spent(hash, gas_spent.gas_used, gas_spent.gas_price)
+                .await?;
+        }
         transaction.ethereum_schema().confirm_eth_tx(hash).await?;
         transaction.commit().await?;
 
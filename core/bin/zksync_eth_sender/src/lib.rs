@@ -31,10 +31,13 @@ use zksync_types::{
     gas_counter::GasCounter,
 };
 
+pub use self::withdrawal_hooks::run_withdrawal_hook_sender;
+
 mod database;
 mod gas_adjuster;
 mod transactions;
 mod tx_queue;
+mod withdrawal_hooks;
 
 #[cfg(test)]
 mod tests;
@@ -43,6 +46,8 @@ mod tests;
 const RATE_LIMIT_BACKOFF_PERIOD: Duration = Duration::from_secs(30);
 /// Rate limit error will contain this response code
 const RATE_LIMIT_HTTP_CODE: &str = "429";
+/// Minimum interval between the nonce lag reports, each of them requests the operator nonce from Ethereum.
+const NONCE_LAG_REPORT_INTERVAL: Duration = Duration::from_secs(60);
 
 /// `TxCheckMode` enum determines the policy on the obtaining the tx status.
 /// The latest sent transaction can be pending (we're still waiting for it),
@@ -122,6 +127,8 @@ struct ETHSender<DB: DatabaseInterface> {
     gas_adjuster: GasAdjuster<DB>,
     /// Settings for the `ETHSender`.
     options: ETHSenderConfig,
+    /// Time of the last nonce lag report which requested the operator nonce.
+    nonce_lag_reported_at: Option<Instant>,
 }
 
 impl<DB: DatabaseInterface> ETHSender<DB> {
@@ -180,6 +187,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             tx_queue,
             gas_adjuster,
             options,
+            nonce_lag_reported_at: None,
         }
     }
 
@@ -316,12 +324,39 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             );
             // Store the ongoing operations for the next round.
             self.ongoing_ops = new_ongoing_ops;
+
+            if let Err(e) = self.report_nonce_lag().await {
+                Self::process_error(e).await;
+            }
         }
 
         metrics::histogram!("eth_sender.proceed_next_operations", start.elapsed());
         current_block
     }
 
+    /// Reports the amount of the sent transactions which are not yet mined, i.e. the gap
+    /// between the last nonce assigned by `ETHSender` and the nonce of the operator account.
+    /// The operator nonce is requested at most once per `NONCE_LAG_REPORT_INTERVAL`.
+    async fn report_nonce_lag(&mut self) -> anyhow::Result<()> {
+        let last_sent_nonce = match self.ongoing_ops.iter().map(|op| op.nonce).max() {
+            Some(nonce) => nonce,
+            None => {
+                metrics::gauge!("eth_sender.nonce_lag", 0.0);
+                return Ok(());
+            }
+        };
+        if let Some(reported_at) = self.nonce_lag_reported_at {
+            if reported_at.elapsed() < NONCE_LAG_REPORT_INTERVAL {
+                return Ok(());
+            }
+        }
+        self.nonce_lag_reported_at = Some(Instant::now());
+        let mined_nonce = self.ethereum.current_nonce().await?;
+        let nonce_lag = (last_sent_nonce + 1).saturating_sub(mined_nonce);
+        metrics::gauge!("eth_sender.nonce_lag", nonce_lag.low_u64() as f64);
+        Ok(())
+    }
+
     async fn process_error(err: anyhow::Error) {
         vlog::warn!("Error while trying to complete uncommitted op: {}", err);
         if err.to_string().contains(RATE_LIMIT_HTTP_CODE) {
@@ -473,7 +508,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     // Transaction is pending, nothing to do yet.
                     return Ok(OperationCommitment::Pending);
                 }
-                TxCheckOutcome::Committed => {
+                TxCheckOutcome::Committed(gas_spent) => {
                     let mut connection = self.db.acquire_connection().await?;
                     let mut transaction = connection.start_transaction().await?;
 
@@ -507,7 +542,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                         op.id, op.op_type, tx_hash, self.zksync_operation_description(op),
                     );
                     self.db
-                        .confirm_operation(&mut transaction, tx_hash, op)
+                        .confirm_operation(&mut transaction, tx_hash, op, gas_spent)
                         .await?;
                     transaction.commit().await?;
                     return Ok(OperationCommitment::Committed);
@@ -603,7 +638,11 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             Some(status) if status.success => {
                 // Check if transaction has enough confirmations.
                 if status.confirmations >= self.options.sender.wait_confirmations {
-                    TxCheckOutcome::Committed
+                    let gas_spent = status.gas_used.map(|gas_used| GasSpent {
+                        gas_used,
+                        gas_price: status.effective_gas_price.unwrap_or(op.last_used_gas_price),
+                    });
+                    TxCheckOutcome::Committed(gas_spent)
                 } else {
                     TxCheckOutcome::Pending
                 }
@@ -8,7 +8,7 @@ use tokio::sync::RwLock;
 use web3::contract::Options;
 use zksync_basic_types::{BlockNumber, H256, U256};
 // Workspace uses
-use zksync_config::configs::eth_sender::{ETHSenderConfig, GasLimit, Sender};
+use zksync_config::configs::eth_sender::{ETHSenderConfig, GasLimit, Sender, WithdrawalHooks};
 use zksync_eth_client::EthereumGateway;
 use zksync_storage::{ethereum::records::ETHParams, StorageProcessor};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
@@ -16,7 +16,7 @@ use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
 // Local uses
 use super::ETHSender;
 use crate::database::DatabaseInterface;
-use crate::transactions::ETHStats;
+use crate::transactions::{ETHStats, GasSpent};
 use zksync_eth_client::clients::mock::MockEthereum;
 
 /// Mock database is capable of recording all the incoming requests for the further analysis.
@@ -282,6 +282,7 @@ impl DatabaseInterface for MockDatabase {
         _connection: &mut StorageProcessor<'_>,
         hash: &H256,
         _op: &ETHOperation,
+        _gas_spent: Option<GasSpent>,
     ) -> anyhow::Result<()> {
         let mut eth_operations = self.eth_operations.write().await;
         let mut op_idx: Option<i64> = None;
@@ -449,6 +450,13 @@ async fn build_eth_sender(
             update_interval: 15,
             scale_factor: 1.0f64,
         },
+        withdrawal_hooks: WithdrawalHooks {
+            is_enabled: false,
+            sender_eth_private_key: Default::default(),
+            sender_address: Default::default(),
+            tx_timeout: 600,
+            tx_gas_limit: 300000,
+        },
     };
 
     ETHSender::new(options, db, ethereum).await
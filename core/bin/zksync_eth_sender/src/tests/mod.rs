@@ -3,7 +3,10 @@ use self::mock::{
     concurrent_eth_sender, create_signed_tx, default_eth_parameters, default_eth_sender,
     restored_eth_sender,
 };
-use super::{transactions::TxCheckOutcome, ETHSender, TxCheckMode};
+use super::{
+    transactions::{GasSpent, TxCheckOutcome},
+    ETHSender, TxCheckMode,
+};
 use web3::types::U64;
 use zksync_eth_client::ethereum_gateway::ExecutedTxStatus;
 
@@ -78,6 +81,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: true,
         receipt: None,
+        gas_used: Some(100_000.into()),
+        effective_gas_price: None,
     };
     eth_sender
         .ethereum
@@ -91,6 +96,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: true,
         receipt: None,
+        gas_used: None,
+        effective_gas_price: None,
     };
     eth_sender
         .ethereum
@@ -104,6 +111,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
+        effective_gas_price: None,
     };
     eth_sender
         .ethereum
@@ -117,6 +126,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
+        effective_gas_price: None,
     };
     eth_sender
         .ethereum
@@ -139,7 +150,10 @@ async fn transaction_state() {
             )
             .await
             .unwrap(),
-        TxCheckOutcome::Committed
+        TxCheckOutcome::Committed(Some(GasSpent {
+            gas_used: 100_000.into(),
+            gas_price: eth_operations[0].last_used_gas_price,
+        }))
     );
 
     // Pending operation (no enough confirmations).
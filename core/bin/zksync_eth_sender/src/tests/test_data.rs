@@ -38,6 +38,7 @@ fn gen_aggregated_operation(
                 eth_hash: H256::zero(),
                 eth_block: 0,
                 eth_block_index: None,
+                l1_sender: None,
             },
             op: ZkSyncOp::FullExit(Box::new(FullExitOp {
                 priority_op,
@@ -5,7 +5,7 @@
 
 // Built-in deps
 // External uses
-use zksync_basic_types::TransactionReceipt;
+use zksync_basic_types::{TransactionReceipt, U256};
 // Workspace uses
 use zksync_storage::ethereum::records::ETHStats as StorageETHStats;
 
@@ -36,8 +36,9 @@ impl From<StorageETHStats> for ETHStats {
 /// The result of the check for the Ethereum transaction commitment.
 #[derive(Debug, PartialEq)]
 pub enum TxCheckOutcome {
-    /// Transaction was committed and confirmed.
-    Committed,
+    /// Transaction was committed and confirmed. Contains the spent gas if it was reported
+    /// by the Ethereum node.
+    Committed(Option<GasSpent>),
     /// Transaction is pending yet.
     Pending,
     /// Transaction is considered stuck, a replacement should be made.
@@ -46,6 +47,14 @@ pub enum TxCheckOutcome {
     Failed(Box<TransactionReceipt>),
 }
 
+/// L1 gas spent by the committed Ethereum transaction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GasSpent {
+    pub gas_used: U256,
+    /// Effective price of the gas, in wei.
+    pub gas_price: U256,
+}
+
 /// Enumeration denoting if the operation was successfully committed, or not yet.
 #[derive(Debug, PartialEq, Eq)]
 pub enum OperationCommitment {
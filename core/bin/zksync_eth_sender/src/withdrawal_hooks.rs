@@ -0,0 +1,189 @@
+//! Sender of the calls attached to the withdrawals.
+//!
+//! Once the block with the withdrawal is executed on L1, `ETHSender` marks the hook of the
+//! withdrawal ready, and the recipient contract is called with the calldata provided on the
+//! withdrawal submission. Calls are sent one by one from the separate account, each one is
+//! confirmed before the next is sent, so the reverted or stuck calls can't delay the operator
+//! transactions. The sender should be enabled only on one server.
+
+// Built-in deps
+use std::time::Duration;
+// External uses
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use tokio::{task::JoinHandle, time};
+use web3::contract::Options;
+// Workspace uses
+use zksync_config::{ETHClientConfig, ETHSenderConfig};
+use zksync_contracts::zksync_contract;
+use zksync_eth_client::{ETHDirectClient, EthereumGateway};
+use zksync_eth_signer::PrivateKeySigner;
+use zksync_storage::{withdrawal_hooks::records::StoredWithdrawalHook, ConnectionPool};
+use zksync_types::{tx::TxHash, Address, ChainId, H256};
+// Local uses
+
+struct WithdrawalHookSender {
+    pool: ConnectionPool,
+    ethereum: EthereumGateway,
+    wait_confirmations: u64,
+    poll_interval: Duration,
+    tx_timeout: Duration,
+    tx_gas_limit: u64,
+}
+
+impl WithdrawalHookSender {
+    fn new(
+        pool: ConnectionPool,
+        eth_client_config: &ETHClientConfig,
+        contract_addr: Address,
+        options: &ETHSenderConfig,
+    ) -> Self {
+        let hooks_config = &options.withdrawal_hooks;
+        let transport = web3::transports::Http::new(&eth_client_config.web3_url()).unwrap();
+        let ethereum = EthereumGateway::Direct(ETHDirectClient::new(
+            transport,
+            zksync_contract(),
+            hooks_config.sender_address,
+            PrivateKeySigner::new(hooks_config.sender_eth_private_key),
+            contract_addr,
+            ChainId(eth_client_config.chain_id),
+            eth_client_config.gas_price_factor,
+        ));
+
+        Self {
+            pool,
+            ethereum,
+            wait_confirmations: options.sender.wait_confirmations,
+            poll_interval: options.sender.tx_poll_period(),
+            tx_timeout: hooks_config.tx_timeout(),
+            tx_gas_limit: hooks_config.tx_gas_limit,
+        }
+    }
+
+    /// Sends the call to the recipient, returns the hash of the sent transaction.
+    async fn send_hook(
+        &self,
+        tx_hash: TxHash,
+        hook: &StoredWithdrawalHook,
+    ) -> anyhow::Result<H256> {
+        let gas_limit = self.tx_gas_limit;
+        let signed_tx = self
+            .ethereum
+            .sign_prepared_tx_for_addr(
+                hook.calldata.clone(),
+                Address::from_slice(&hook.recipient),
+                Options::with(|opt| opt.gas = Some(gas_limit.into())),
+            )
+            .await?;
+        // The hash is stored before sending, so the call isn't sent twice after the restart.
+        self.pool
+            .access_storage()
+            .await?
+            .withdrawal_hooks_schema()
+            .mark_withdrawal_hook_sent(tx_hash, signed_tx.hash)
+            .await?;
+
+        vlog::info!(
+            "Sending the hook of the withdrawal {}: {:#x}",
+            tx_hash,
+            signed_tx.hash
+        );
+        if let Err(err) = self.ethereum.send_raw_tx(signed_tx.raw_tx).await {
+            // The call will be considered failed once the timeout has passed.
+            vlog::warn!("Error while sending the withdrawal hook: {}", err);
+        }
+        Ok(signed_tx.hash)
+    }
+
+    /// Waits for the call to be confirmed, returns the failure reason if it's reverted
+    /// or isn't confirmed in time.
+    async fn wait_for_hook(
+        &self,
+        eth_tx_hash: H256,
+        sent_at: DateTime<Utc>,
+    ) -> anyhow::Result<Option<String>> {
+        let tx_timeout = chrono::Duration::from_std(self.tx_timeout)?;
+        loop {
+            if let Some(status) = self.ethereum.get_tx_status(eth_tx_hash, None).await? {
+                if !status.success {
+                    let reason = self
+                        .ethereum
+                        .failure_reason(eth_tx_hash)
+                        .await?
+                        .map(|failure| failure.revert_reason)
+                        .unwrap_or_else(|| "unknown".to_string());
+                    return Ok(Some(reason));
+                }
+                if status.confirmations >= self.wait_confirmations {
+                    return Ok(None);
+                }
+            }
+            if Utc::now() - sent_at > tx_timeout {
+                return Ok(Some("call is not confirmed in time".to_string()));
+            }
+            time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Sends the earliest ready hook, or waits for the one sent before the restart.
+    /// Returns `false` if there are no hooks to send.
+    async fn process_next_hook(&self) -> anyhow::Result<bool> {
+        let hook = match self
+            .pool
+            .access_storage()
+            .await?
+            .withdrawal_hooks_schema()
+            .load_next_withdrawal_hook()
+            .await?
+        {
+            Some(hook) => hook,
+            None => return Ok(false),
+        };
+        let tx_hash =
+            TxHash::from_slice(&hook.tx_hash).context("invalid withdrawal hash in storage")?;
+
+        let (eth_tx_hash, sent_at) = match &hook.eth_tx_hash {
+            Some(eth_tx_hash) => (H256::from_slice(eth_tx_hash), hook.updated_at),
+            None => (self.send_hook(tx_hash, &hook).await?, Utc::now()),
+        };
+        let fail_reason = self.wait_for_hook(eth_tx_hash, sent_at).await?;
+        self.pool
+            .access_storage()
+            .await?
+            .withdrawal_hooks_schema()
+            .complete_withdrawal_hook(tx_hash, fail_reason.as_deref())
+            .await?;
+
+        match fail_reason {
+            Some(reason) => {
+                vlog::warn!("Hook of the withdrawal {} has failed: {}", tx_hash, reason);
+                metrics::increment_counter!("eth_sender.withdrawal_hooks.failed");
+            }
+            None => metrics::increment_counter!("eth_sender.withdrawal_hooks.succeeded"),
+        }
+        Ok(true)
+    }
+}
+
+#[must_use]
+pub fn run_withdrawal_hook_sender(
+    pool: ConnectionPool,
+    eth_client_config: &ETHClientConfig,
+    contract_addr: Address,
+    options: &ETHSenderConfig,
+) -> JoinHandle<()> {
+    let sender = WithdrawalHookSender::new(pool, eth_client_config, contract_addr, options);
+
+    tokio::spawn(async move {
+        loop {
+            match sender.process_next_hook().await {
+                Ok(true) => {}
+                Ok(false) => time::sleep(sender.poll_interval).await,
+                Err(err) => {
+                    vlog::error!("Can't process the withdrawal hook: {}", err);
+                    time::sleep(sender.poll_interval).await;
+                }
+            }
+        }
+    })
+}
@@ -1,6 +1,7 @@
 //! The `zksync_event_listener` is a stand-alone server-application responsible for
 //! fetching new events that happen in the zkSync network from the database
-//! and streaming them to the connected WebSocket clients.
+//! and streaming them to the connected WebSocket clients and, optionally,
+//! to an external message broker.
 
 // Built-in uses
 // Workspace uses
@@ -13,11 +14,13 @@ use actix_web_actors::ws;
 use listener::EventListener;
 use messages::RegisterServerHandle;
 use monitor::ServerMonitor;
+use publisher::EventPublisher;
 use subscriber::Subscriber;
 
 pub mod listener;
 pub mod messages;
 pub mod monitor;
+pub mod publisher;
 pub mod subscriber;
 
 #[derive(Debug)]
@@ -35,7 +38,8 @@ async fn ws_index(
 
 pub async fn run_event_server(config: ZkSyncConfig) {
     let monitor = ServerMonitor::new().start();
-    EventListener::new(monitor.clone(), &config)
+    let publisher = EventPublisher::from_config(&config.event_listener).map(Actor::start);
+    EventListener::new(monitor.clone(), publisher, &config)
         .await
         .unwrap()
         .start();
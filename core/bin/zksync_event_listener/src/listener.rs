@@ -10,6 +10,7 @@ use zksync_types::event::{EventId, ZkSyncEvent};
 // Local uses
 use crate::messages::{NewEvents, NewStorageEvent, Shutdown};
 use crate::monitor::ServerMonitor;
+use crate::publisher::EventPublisher;
 
 /// The main actor which is responsible for fetching new events from
 /// the database and sending them to the [`ServerMonitor`].
@@ -18,6 +19,8 @@ pub struct EventListener {
     db_pool: ConnectionPool,
     /// Address of the [`ServerMonitor`] actor for communication.
     server_monitor: Addr<ServerMonitor>,
+    /// Address of the [`EventPublisher`] actor, if publishing to the message broker is enabled.
+    publisher: Option<Addr<EventPublisher>>,
     /// A storage listener that gets notified about new database events.
     /// This field gets consumed at the start of the actor.
     listener: Option<StorageListener>,
@@ -73,6 +76,10 @@ impl StreamHandler<NotifyResult> for EventListener {
                     }
                     // We don't process new notifications until we send the message.
                     let msg = NewEvents(Arc::new(events));
+                    // The publisher processes events at the broker's pace, so don't wait for it.
+                    if let Some(publisher) = &act.publisher {
+                        publisher.do_send(msg.clone());
+                    }
                     Either::Left(act.server_monitor.send(msg))
                 }
                 Err(err) => {
@@ -130,6 +137,7 @@ impl EventListener {
 
     pub async fn new(
         server_monitor: Addr<ServerMonitor>,
+        publisher: Option<Addr<EventPublisher>>,
         config: &ZkSyncConfig,
     ) -> anyhow::Result<EventListener> {
         let mut listener = StorageListener::connect().await?;
@@ -151,6 +159,7 @@ impl EventListener {
         Ok(EventListener {
             db_pool,
             server_monitor,
+            publisher,
             listener: Some(listener),
             last_processed_event_id,
         })
@@ -0,0 +1,78 @@
+// Built-in uses
+// External uses
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+// Workspace uses
+// Local uses
+use super::{EventSink, SinkMessage};
+
+const KAFKA_JSON_CONTENT_TYPE: &str = "application/vnd.kafka.json.v2+json";
+
+#[derive(Debug, Serialize)]
+struct ProduceRecord<'a> {
+    key: &'a str,
+    value: &'a serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct ProduceRequest<'a> {
+    records: Vec<ProduceRecord<'a>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProduceOffset {
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProduceResponse {
+    offsets: Vec<ProduceOffset>,
+}
+
+/// Publishes the events to Kafka through the Kafka REST proxy (v2 API).
+#[derive(Debug)]
+pub struct KafkaSink {
+    client: reqwest::Client,
+    proxy_url: String,
+}
+
+impl KafkaSink {
+    pub fn new(proxy_url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            proxy_url: proxy_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaSink {
+    async fn publish(&mut self, topic: &str, messages: &[SinkMessage]) -> anyhow::Result<()> {
+        let request = ProduceRequest {
+            records: messages
+                .iter()
+                .map(|message| ProduceRecord {
+                    key: &message.key,
+                    value: &message.payload,
+                })
+                .collect(),
+        };
+        let response = self
+            .client
+            .post(format!("{}/topics/{}", self.proxy_url, topic))
+            .header(reqwest::header::CONTENT_TYPE, KAFKA_JSON_CONTENT_TYPE)
+            .body(serde_json::to_vec(&request)?)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Kafka REST proxy responded with {}", response.status());
+        }
+
+        // The proxy reports the errors for every record separately.
+        let response: ProduceResponse = response.json().await?;
+        if let Some(error) = response.offsets.into_iter().find_map(|offset| offset.error) {
+            anyhow::bail!("Kafka rejected the record: {}", error);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+//! Publisher streams the zkSync events to an external message broker (Kafka or NATS),
+//! so the downstream data platforms can consume them without polling the REST API.
+//!
+//! Every event is published as a JSON object of the [`VersionedEvent`] schema to the topic
+//! chosen by the event type: `<prefix>.blocks`, `<prefix>.transactions` or `<prefix>.accounts`.
+//! Events are published in the order they were stored in the database. If the broker is not
+//! available, the publisher keeps retrying and doesn't skip any events.
+
+// Built-in uses
+use std::time::Duration;
+// External uses
+use actix::{clock, prelude::*};
+use async_trait::async_trait;
+// Workspace uses
+use zksync_config::configs::event_listener::{EventListenerConfig, EventSinkType};
+use zksync_types::event::{EventData, VersionedEvent, ZkSyncEvent};
+// Local uses
+use crate::messages::NewEvents;
+use kafka::KafkaSink;
+use nats::NatsSink;
+
+mod kafka;
+mod nats;
+
+/// Maximum time to publish a batch of messages before the attempt is considered failed.
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Message to be published to the broker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinkMessage {
+    /// Key of the message, events of the same block share the key.
+    pub key: String,
+    pub payload: serde_json::Value,
+}
+
+impl From<&ZkSyncEvent> for SinkMessage {
+    fn from(event: &ZkSyncEvent) -> Self {
+        Self {
+            key: event.block_number.to_string(),
+            payload: serde_json::to_value(VersionedEvent::from(event.clone()))
+                .expect("event serialization can't fail"),
+        }
+    }
+}
+
+/// Client of the message broker.
+#[async_trait]
+pub trait EventSink: Send {
+    /// Publishes the messages to the topic. Either all the messages are published
+    /// or an error is returned.
+    async fn publish(&mut self, topic: &str, messages: &[SinkMessage]) -> anyhow::Result<()>;
+}
+
+/// Returns the topic name for the event.
+pub fn topic_name(prefix: &str, event: &ZkSyncEvent) -> String {
+    let suffix = match event.data {
+        EventData::Account(_) => "accounts",
+        EventData::Block(_) => "blocks",
+        EventData::Transaction(_) => "transactions",
+    };
+    format!("{}.{}", prefix, suffix)
+}
+
+/// Splits the events into the batches of the consecutive events with the same topic.
+fn group_by_topic(prefix: &str, events: &[ZkSyncEvent]) -> Vec<(String, Vec<SinkMessage>)> {
+    let mut batches: Vec<(String, Vec<SinkMessage>)> = Vec::new();
+    for event in events {
+        let topic = topic_name(prefix, event);
+        match batches.last_mut() {
+            Some((last_topic, messages)) if *last_topic == topic => messages.push(event.into()),
+            _ => batches.push((topic, vec![event.into()])),
+        }
+    }
+    batches
+}
+
+/// The actor which receives new events from the [`EventListener`](crate::listener::EventListener)
+/// and publishes them to the message broker.
+pub struct EventPublisher {
+    /// Broker client. Taken out of the actor while the events are being published.
+    sink: Option<Box<dyn EventSink>>,
+    topic_prefix: String,
+    retry_interval: Duration,
+}
+
+impl EventPublisher {
+    /// Creates the publisher for the configured broker, returns `None` if publishing is disabled.
+    pub fn from_config(config: &EventListenerConfig) -> Option<Self> {
+        let sink: Box<dyn EventSink> = match config.sink {
+            EventSinkType::None => return None,
+            EventSinkType::Kafka => Box::new(KafkaSink::new(config.sink_url.clone())),
+            EventSinkType::Nats => Box::new(NatsSink::new(config.sink_url.clone())),
+        };
+        Some(Self {
+            sink: Some(sink),
+            topic_prefix: config.sink_topic_prefix.clone(),
+            retry_interval: config.sink_retry_interval(),
+        })
+    }
+}
+
+impl Actor for EventPublisher {
+    type Context = Context<Self>;
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        vlog::warn!("EventPublisher actor has stopped");
+    }
+}
+
+impl Handler<NewEvents> for EventPublisher {
+    type Result = ();
+
+    fn handle(&mut self, msg: NewEvents, ctx: &mut Self::Context) {
+        let batches = group_by_topic(&self.topic_prefix, msg.0.as_ref());
+        if batches.is_empty() {
+            return;
+        }
+        let mut sink = self.sink.take().expect("events are published sequentially");
+        let retry_interval = self.retry_interval;
+        // Publishing is awaited on the actor's context, so the next batch of events
+        // is not processed until the current one is published.
+        async move {
+            for (topic, messages) in batches {
+                loop {
+                    let err = match clock::timeout(PUBLISH_TIMEOUT, sink.publish(&topic, &messages))
+                        .await
+                    {
+                        Ok(Ok(())) => break,
+                        Ok(Err(err)) => err,
+                        Err(_) => anyhow::anyhow!("publishing timed out"),
+                    };
+                    vlog::error!("Couldn't publish events to '{}', reason: {}", topic, err);
+                    metrics::increment_counter!("event_listener.publisher.errors");
+                    clock::sleep(retry_interval).await;
+                }
+                metrics::counter!("event_listener.publisher.published", messages.len() as u64);
+            }
+            sink
+        }
+        .into_actor(self)
+        .map(|sink, act, _| act.sink = Some(sink))
+        .wait(ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{
+        event::{
+            account::AccountStateChangeStatus, block::BlockStatus, test_data::*, EventId,
+            EVENT_SCHEMA_VERSION,
+        },
+        AccountId,
+    };
+
+    #[test]
+    fn published_event_schema() {
+        let mut event = get_block_event(BlockStatus::Committed);
+        event.id = EventId(42);
+
+        let message = SinkMessage::from(&event);
+        assert_eq!(message.key, "0");
+        assert_eq!(message.payload["schema_version"], EVENT_SCHEMA_VERSION);
+        assert_eq!(message.payload["event_id"], 42);
+        assert_eq!(message.payload["block_number"], 0);
+        assert_eq!(message.payload["type"], "block");
+        assert_eq!(message.payload["data"]["status"], "committed");
+    }
+
+    #[test]
+    fn events_grouped_by_topic() {
+        let block = get_block_event(BlockStatus::Committed);
+        let account = get_account_event(AccountId(1), None, AccountStateChangeStatus::Committed);
+        let events = vec![block.clone(), block.clone(), account, block];
+
+        let topics: Vec<_> = group_by_topic("zksync", &events)
+            .into_iter()
+            .map(|(topic, messages)| (topic, messages.len()))
+            .collect();
+        assert_eq!(
+            topics,
+            vec![
+                ("zksync.blocks".to_string(), 2),
+                ("zksync.accounts".to_string(), 1),
+                ("zksync.blocks".to_string(), 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,97 @@
+// Built-in uses
+// External uses
+use async_trait::async_trait;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufStream},
+    net::TcpStream,
+};
+// Workspace uses
+// Local uses
+use super::{EventSink, SinkMessage};
+
+const CONNECT_COMMAND: &[u8] =
+    b"CONNECT {\"verbose\":false,\"pedantic\":false,\"name\":\"zksync_event_listener\"}\r\n";
+
+type Connection = BufStream<TcpStream>;
+
+/// Publishes the events to NATS using the core client protocol.
+///
+/// NATS subjects have no message keys, so `SinkMessage::key` is not used.
+#[derive(Debug)]
+pub struct NatsSink {
+    address: String,
+    /// Established connection, dropped after any error and restored on the next publish attempt.
+    connection: Option<Connection>,
+}
+
+impl NatsSink {
+    pub fn new(address: String) -> Self {
+        Self {
+            address: address.trim_start_matches("nats://").to_string(),
+            connection: None,
+        }
+    }
+
+    async fn connect(&self) -> anyhow::Result<Connection> {
+        let mut connection = BufStream::new(TcpStream::connect(&self.address).await?);
+        // Server greets the client with the `INFO` message.
+        let mut info = String::new();
+        connection.read_line(&mut info).await?;
+        if !info.starts_with("INFO") {
+            anyhow::bail!("unexpected NATS greeting: {}", info.trim_end());
+        }
+        connection.write_all(CONNECT_COMMAND).await?;
+        Ok(connection)
+    }
+
+    async fn publish_messages(
+        connection: &mut Connection,
+        subject: &str,
+        messages: &[SinkMessage],
+    ) -> anyhow::Result<()> {
+        for message in messages {
+            let payload = serde_json::to_vec(&message.payload)?;
+            connection
+                .write_all(format!("PUB {} {}\r\n", subject, payload.len()).as_bytes())
+                .await?;
+            connection.write_all(&payload).await?;
+            connection.write_all(b"\r\n").await?;
+        }
+        // Server processes the commands in order, so receiving `PONG`
+        // means that all the published messages were accepted.
+        connection.write_all(b"PING\r\n").await?;
+        connection.flush().await?;
+
+        loop {
+            let mut line = String::new();
+            if connection.read_line(&mut line).await? == 0 {
+                anyhow::bail!("NATS server closed the connection");
+            }
+            match line.trim_end() {
+                "PONG" => return Ok(()),
+                "PING" => {
+                    connection.write_all(b"PONG\r\n").await?;
+                    connection.flush().await?;
+                }
+                error if error.starts_with("-ERR") => {
+                    anyhow::bail!("NATS server error: {}", error);
+                }
+                // `+OK` and `INFO` updates.
+                _ => {}
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    async fn publish(&mut self, topic: &str, messages: &[SinkMessage]) -> anyhow::Result<()> {
+        let mut connection = match self.connection.take() {
+            Some(connection) => connection,
+            None => self.connect().await?,
+        };
+        Self::publish_messages(&mut connection, topic, messages).await?;
+        self.connection = Some(connection);
+        Ok(())
+    }
+}
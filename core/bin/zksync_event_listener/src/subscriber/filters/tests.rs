@@ -62,6 +62,12 @@ fn test_filters_deserialize() {
                 "accounts": [1, 2, 3]
             }
         }"#,
+        // Amount is expected as a decimal string.
+        r#"{
+            "transaction": {
+                "min_amount": 1000
+            }
+        }"#,
     ];
     for (i, input) in INVALID.iter().enumerate() {
         let result = serde_json::from_str::<SubscriberFilters>(input);
@@ -92,6 +98,14 @@ fn test_filters_deserialize() {
                 "status": "rejected"
             }
         }"#,
+        r#"{
+            "transaction": {
+                "types": ["Transfer"],
+                "tokens": [1],
+                "min_amount": "1000000000000000000",
+                "addresses": ["0x2a0a81e257a2f5d6ed4f07b81dbda09f107bd026"]
+            }
+        }"#,
         r#"{
             "account": {
                 "status": "committed",
@@ -5,8 +5,9 @@ use serde::Deserialize;
 // Workspace uses
 use zksync_types::{
     event::{transaction::*, EventData, ZkSyncEvent},
-    AccountId, TokenId,
+    AccountId, Address, TokenId,
 };
+use zksync_utils::BigUintSerdeWrapper;
 // Local uses
 
 #[derive(Debug, Clone, Deserialize)]
@@ -16,6 +17,12 @@ pub struct TransactionFilter {
     pub accounts: Option<HashSet<AccountId>>,
     pub tokens: Option<HashSet<TokenId>>,
     pub status: Option<TransactionStatus>,
+    /// L1 addresses, at least one of which must be featured in the operation
+    /// (as a sender, a recipient, etc.).
+    pub addresses: Option<HashSet<Address>>,
+    /// Minimum amount of the operation, in the smallest units of the token.
+    /// Operations without an amount (e.g. `ChangePubKey`) never match.
+    pub min_amount: Option<BigUintSerdeWrapper>,
 }
 
 impl TransactionFilter {
@@ -47,6 +54,21 @@ impl TransactionFilter {
                 return false;
             }
         }
+        if let Some(min_amount) = &self.min_amount {
+            match tx_event.amount() {
+                Some(amount) if amount >= min_amount.0 => {}
+                _ => return false,
+            }
+        }
+        if let Some(addresses) = &self.addresses {
+            if !tx_event
+                .addresses()
+                .iter()
+                .any(|address| addresses.contains(address))
+            {
+                return false;
+            }
+        }
         true
     }
 }
@@ -54,8 +76,16 @@ impl TransactionFilter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
     use zksync_types::event::test_data::get_transaction_event;
 
+    fn with_tx(mut event: ZkSyncEvent, tx: serde_json::Value) -> ZkSyncEvent {
+        if let EventData::Transaction(tx_event) = &mut event.data {
+            tx_event.tx = tx;
+        }
+        event
+    }
+
     #[test]
     fn test_transaction_filter() {
         // Match all events.
@@ -64,6 +94,8 @@ mod tests {
             accounts: None,
             tokens: None,
             status: None,
+            addresses: None,
+            min_amount: None,
         };
 
         let event = get_transaction_event(
@@ -142,4 +174,68 @@ mod tests {
             assert!(tx_filter.matches(&event));
         }
     }
+
+    #[test]
+    fn test_transaction_filter_by_amount_and_address() {
+        let alice = Address::repeat_byte(0xaa);
+        let bob = Address::repeat_byte(0xbb);
+        let carol = Address::repeat_byte(0xcc);
+
+        let transfer = with_tx(
+            get_transaction_event(
+                TransactionType::Transfer,
+                AccountId(1),
+                TokenId(0),
+                TransactionStatus::Committed,
+            ),
+            json!({ "type": "Transfer", "from": alice, "to": bob, "amount": "1000" }),
+        );
+        let deposit = with_tx(
+            get_transaction_event(
+                TransactionType::Deposit,
+                AccountId(2),
+                TokenId(0),
+                TransactionStatus::Committed,
+            ),
+            json!({
+                "type": "Deposit",
+                "priority_op": { "from": carol, "to": carol, "amount": "500" }
+            }),
+        );
+        let change_pub_key = with_tx(
+            get_transaction_event(
+                TransactionType::ChangePubKey,
+                AccountId(1),
+                TokenId(0),
+                TransactionStatus::Committed,
+            ),
+            json!({ "type": "ChangePubKey", "account": alice }),
+        );
+
+        let mut tx_filter = TransactionFilter {
+            types: None,
+            accounts: None,
+            tokens: None,
+            status: None,
+            addresses: None,
+            min_amount: Some(serde_json::from_value(json!("1000")).unwrap()),
+        };
+        assert!(tx_filter.matches(&transfer));
+        // Amount of the priority operation is below the threshold.
+        assert!(!tx_filter.matches(&deposit));
+        // No amount at all.
+        assert!(!tx_filter.matches(&change_pub_key));
+
+        tx_filter.min_amount = None;
+        tx_filter.addresses = Some([bob].iter().copied().collect());
+        // The recipient matches.
+        assert!(tx_filter.matches(&transfer));
+        assert!(!tx_filter.matches(&deposit));
+        assert!(!tx_filter.matches(&change_pub_key));
+
+        tx_filter.addresses = Some([alice, carol].iter().copied().collect());
+        assert!(tx_filter.matches(&transfer));
+        assert!(tx_filter.matches(&deposit));
+        assert!(tx_filter.matches(&change_pub_key));
+    }
 }
@@ -3,6 +3,7 @@
 use actix::prelude::*;
 use actix_web_actors::ws;
 // Workspace uses
+use zksync_types::event::VersionedEvent;
 // Local uses
 use crate::messages::{NewEvents, RegisterSubscriber, RemoveSubscriber, Shutdown};
 use crate::monitor::ServerMonitor;
@@ -140,7 +141,7 @@ impl Handler<NewEvents> for Subscriber {
             if !filters.matches(event) {
                 continue;
             }
-            let json = serde_json::to_string(&event).unwrap();
+            let json = serde_json::to_string(&VersionedEvent::from(event.clone())).unwrap();
             ctx.text(json);
         }
     }
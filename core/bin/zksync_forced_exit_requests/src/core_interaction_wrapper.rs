@@ -3,19 +3,29 @@ use futures::{
     channel::{mpsc, oneshot},
     SinkExt,
 };
-use num::Zero;
+use num::{BigUint, Zero};
+use tracing::Span;
 
 use zksync_storage::{chain::operations_ext::records::TxReceiptResponse, ConnectionPool};
 use zksync_types::{
     forced_exit_requests::{ForcedExitRequest, ForcedExitRequestId},
     tx::TxHash,
-    AccountId, Nonce,
+    AccountId, Address, Nonce, H256,
 };
 
 use zksync_api::api_server::forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker};
 use zksync_mempool::MempoolTransactionRequest;
 use zksync_types::SignedZkSyncTx;
 
+/// Forced exit request fulfilled as a part of the batch.
+#[derive(Debug, Clone)]
+pub struct RequestFulfillment {
+    pub request: ForcedExitRequest,
+    pub txs: Vec<SignedZkSyncTx>,
+    /// Address that has paid for the request and the amount to be refunded to it.
+    pub refund: Option<(Address, BigUint)>,
+}
+
 // We could use `db reset` and test the db the same way as in rust_api
 // but it seemed to be an overkill here, so it was decided to use
 // traits for unit-testing. Also it gives a much broader level of control
@@ -25,6 +35,7 @@ pub trait CoreInteractionWrapper {
     async fn get_nonce(&self, account_id: AccountId) -> anyhow::Result<Option<Nonce>>;
     async fn get_unconfirmed_requests(&self) -> anyhow::Result<Vec<ForcedExitRequest>>;
     async fn set_fulfilled_at(&self, id: i64) -> anyhow::Result<()>;
+    async fn set_paid(&self, id: ForcedExitRequestId, payment_tx_hash: H256) -> anyhow::Result<()>;
     async fn set_fulfilled_by(
         &self,
         id: ForcedExitRequestId,
@@ -32,11 +43,24 @@ pub trait CoreInteractionWrapper {
     ) -> anyhow::Result<()>;
     async fn get_request_by_id(&self, id: i64) -> anyhow::Result<Option<ForcedExitRequest>>;
     async fn get_receipt(&self, tx_hash: TxHash) -> anyhow::Result<Option<TxReceiptResponse>>;
+    /// Sends the transactions of all the requests in a single batch and stores
+    /// the transactions and the refunds of the requests.
     async fn send_and_save_txs_batch(
         &mut self,
-        request: &ForcedExitRequest,
-        txs: Vec<SignedZkSyncTx>,
+        fulfillments: &[RequestFulfillment],
     ) -> anyhow::Result<Vec<TxHash>>;
+    async fn get_pending_refunds(&self) -> anyhow::Result<Vec<ForcedExitRequest>>;
+    async fn send_and_save_refund(
+        &mut self,
+        id: ForcedExitRequestId,
+        tx: SignedZkSyncTx,
+    ) -> anyhow::Result<TxHash>;
+    async fn set_refunded_by(
+        &self,
+        id: ForcedExitRequestId,
+        value: Option<TxHash>,
+    ) -> anyhow::Result<()>;
+    async fn set_refunded_at(&self, id: ForcedExitRequestId) -> anyhow::Result<()>;
     async fn get_oldest_unfulfilled_request(&self) -> anyhow::Result<Option<ForcedExitRequest>>;
     async fn delete_old_unfulfilled_requests(
         &self,
@@ -102,6 +126,16 @@ impl CoreInteractionWrapper for MempoolCoreInteractionWrapper {
         Ok(())
     }
 
+    async fn set_paid(&self, id: ForcedExitRequestId, payment_tx_hash: H256) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        storage
+            .forced_exit_requests_schema()
+            .set_paid(id, payment_tx_hash, Utc::now())
+            .await?;
+
+        Ok(())
+    }
+
     async fn set_fulfilled_by(
         &self,
         id: ForcedExitRequestId,
@@ -137,25 +171,96 @@ impl CoreInteractionWrapper for MempoolCoreInteractionWrapper {
 
     async fn send_and_save_txs_batch(
         &mut self,
-        request: &ForcedExitRequest,
-        txs: Vec<SignedZkSyncTx>,
+        fulfillments: &[RequestFulfillment],
     ) -> anyhow::Result<Vec<TxHash>> {
         let mut storage = self.connection_pool.access_storage().await?;
-        let mut schema = storage.forced_exit_requests_schema();
 
+        let txs: Vec<SignedZkSyncTx> = fulfillments
+            .iter()
+            .flat_map(|fulfillment| fulfillment.txs.iter().cloned())
+            .collect();
         let hashes: Vec<TxHash> = txs.iter().map(|tx| tx.hash()).collect();
 
         let (sender, receiver) = oneshot::channel();
-        let item = MempoolTransactionRequest::NewTxsBatch(txs, vec![], sender);
+        let item =
+            MempoolTransactionRequest::NewTxsBatch(txs, vec![], vec![], sender, Span::current());
         self.mempool_tx_sender.send(item).await?;
         receiver.await??;
-        schema
-            .set_fulfilled_by(request.id, Some(hashes.clone()))
-            .await?;
+
+        let mut transaction = storage.start_transaction().await?;
+        for fulfillment in fulfillments {
+            let request_hashes = fulfillment.txs.iter().map(|tx| tx.hash()).collect();
+            let mut schema = transaction.forced_exit_requests_schema();
+            schema
+                .set_fulfilled_by(fulfillment.request.id, Some(request_hashes))
+                .await?;
+            if let Some((address, amount)) = fulfillment.refund.clone() {
+                schema
+                    .set_refund(fulfillment.request.id, address, amount)
+                    .await?;
+            }
+        }
+        transaction.commit().await?;
 
         Ok(hashes)
     }
 
+    async fn get_pending_refunds(&self) -> anyhow::Result<Vec<ForcedExitRequest>> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let requests = storage
+            .forced_exit_requests_schema()
+            .get_pending_refunds()
+            .await?;
+
+        Ok(requests)
+    }
+
+    async fn send_and_save_refund(
+        &mut self,
+        id: ForcedExitRequestId,
+        tx: SignedZkSyncTx,
+    ) -> anyhow::Result<TxHash> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let hash = tx.hash();
+
+        let (sender, receiver) = oneshot::channel();
+        let item = MempoolTransactionRequest::NewTx(Box::new(tx), sender, Span::current());
+        self.mempool_tx_sender.send(item).await?;
+        receiver.await??;
+        storage
+            .forced_exit_requests_schema()
+            .set_refunded_by(id, Some(hash))
+            .await?;
+
+        Ok(hash)
+    }
+
+    async fn set_refunded_by(
+        &self,
+        id: ForcedExitRequestId,
+        value: Option<TxHash>,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        storage
+            .forced_exit_requests_schema()
+            .set_refunded_by(id, value)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn set_refunded_at(&self, id: ForcedExitRequestId) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        storage
+            .forced_exit_requests_schema()
+            .set_refunded_at(id, Utc::now())
+            .await?;
+
+        vlog::info!("Fee of the ForcedExit request with id {} was refunded", id);
+
+        Ok(())
+    }
+
     async fn get_oldest_unfulfilled_request(&self) -> anyhow::Result<Option<ForcedExitRequest>> {
         let mut storage = self.connection_pool.access_storage().await?;
         let request = storage
@@ -28,7 +28,7 @@ use zksync_types::forced_exit_requests::FundsReceivedEvent;
 use super::prepare_forced_exit_sender::prepare_forced_exit_sender_account;
 use crate::{
     core_interaction_wrapper::{CoreInteractionWrapper, MempoolCoreInteractionWrapper},
-    forced_exit_sender::MempoolForcedExitSender,
+    forced_exit_sender::{MempoolForcedExitSender, RequestPayment},
 };
 
 use super::ForcedExitSender;
@@ -37,6 +37,7 @@ use super::ForcedExitSender;
 /// before repeating the request.
 const RATE_LIMIT_DELAY: Duration = Duration::from_secs(30);
 
+#[derive(Debug, Clone)]
 struct ContractTopics {
     pub funds_received: Hash,
 }
@@ -324,11 +325,15 @@ where
             }
         };
 
-        for e in events {
-            self.forced_exit_sender
-                .process_request(e.amount, lower_bound_block_time(e.block_number, last_block))
-                .await;
-        }
+        let payments = events
+            .into_iter()
+            .map(|event| RequestPayment {
+                amount: event.amount,
+                submission_time: lower_bound_block_time(event.block_number, last_block),
+                eth_tx_hash: event.tx_hash,
+            })
+            .collect();
+        self.forced_exit_sender.process_payments(payments).await;
 
         self.last_viewed_block = last_confirmed_block;
 
@@ -481,7 +486,7 @@ mod test {
     use num::{BigUint, FromPrimitive};
     use std::{str::FromStr, sync::Mutex};
 
-    use zksync_types::{forced_exit_requests::ForcedExitRequest, Address, TokenId};
+    use zksync_types::{forced_exit_requests::ForcedExitRequest, Address, TokenId, H256};
 
     use super::*;
     use crate::test::{add_request, MockCoreInteractionWrapper};
@@ -512,8 +517,9 @@ mod test {
             Ok(self.current_block_number)
         }
     }
+
     struct DummyForcedExitSender {
-        pub processed_requests: Mutex<Vec<(BigUint, DateTime<Utc>)>>,
+        pub processed_requests: Mutex<Vec<RequestPayment>>,
     }
 
     impl DummyForcedExitSender {
@@ -526,12 +532,12 @@ mod test {
 
     #[async_trait::async_trait]
     impl ForcedExitSender for DummyForcedExitSender {
-        async fn process_request(&mut self, amount: BigUint, submission_time: DateTime<Utc>) {
+        async fn process_payments(&mut self, mut payments: Vec<RequestPayment>) {
             let mut write_lock = self
                 .processed_requests
                 .lock()
                 .expect("Failed to get write lock for processed_requests");
-            (*write_lock).push((amount, submission_time));
+            (*write_lock).append(&mut payments);
         }
     }
 
@@ -576,6 +582,10 @@ mod test {
             created_at: Utc::now().sub(week).sub(three_days),
             fulfilled_at: None,
             fulfilled_by: None,
+            payment_tx_hash: None,
+            paid_at: None,
+            refund_address: None,
+            refund: None,
         };
 
         add_request(
@@ -639,6 +649,10 @@ mod test {
             created_at: Utc::now().sub(chrono::Duration::milliseconds(1)),
             fulfilled_at: None,
             fulfilled_by: None,
+            payment_tx_hash: None,
+            paid_at: None,
+            refund_address: None,
+            refund: None,
         }]);
 
         watcher
@@ -664,6 +678,10 @@ mod test {
             created_at: Utc::now().sub(chrono::Duration::weeks(1)),
             fulfilled_at: None,
             fulfilled_by: None,
+            payment_tx_hash: None,
+            paid_at: None,
+            refund_address: None,
+            refund: None,
         }]);
 
         watcher
@@ -688,16 +706,19 @@ mod test {
                 // Should be processed
                 amount: BigUint::from_str("1000000001").unwrap(),
                 block_number: TEST_FIRST_CURRENT_BLOCK - 2 * wait_confirmations,
+                tx_hash: H256::random(),
             },
             FundsReceivedEvent {
                 amount: BigUint::from_str("1000000002").unwrap(),
                 // Should be processed
                 block_number: TEST_FIRST_CURRENT_BLOCK - wait_confirmations - 1,
+                tx_hash: H256::random(),
             },
             FundsReceivedEvent {
                 amount: BigUint::from_str("1000000003").unwrap(),
                 // Should not be processed
                 block_number: TEST_FIRST_CURRENT_BLOCK - 1,
+                tx_hash: H256::random(),
             },
         ];
 
@@ -722,11 +743,11 @@ mod test {
         // and it is easier to test this way
         assert_eq!(processed_requests.len(), 2);
         assert_eq!(
-            processed_requests[0].0,
+            processed_requests[0].amount,
             BigUint::from_str("1000000001").unwrap()
         );
         assert_eq!(
-            processed_requests[1].0,
+            processed_requests[1].amount,
             BigUint::from_str("1000000002").unwrap()
         );
     }
@@ -1,20 +1,23 @@
 use std::ops::AddAssign;
 
 use chrono::{DateTime, Utc};
-use num::BigUint;
+use num::{BigUint, Zero};
 use tokio::time;
 
 use zksync_config::ForcedExitRequestsConfig;
 
 use zksync_types::{
-    forced_exit_requests::ForcedExitRequest, tx::TimeRange, tx::TxHash, AccountId, Address, Nonce,
-    TokenId, ZkSyncTx,
+    forced_exit_requests::ForcedExitRequest, helpers::closest_packable_token_amount, tx::TimeRange,
+    tx::TxHash, AccountId, Address, Nonce, TokenId, Transfer, ZkSyncTx, H256,
 };
 
 use zksync_types::ForcedExit;
 use zksync_types::SignedZkSyncTx;
 
-use crate::{core_interaction_wrapper::CoreInteractionWrapper, utils};
+use crate::{
+    core_interaction_wrapper::{CoreInteractionWrapper, RequestFulfillment},
+    utils,
+};
 
 use super::utils::{Engine, PrivateKey};
 use crate::utils::read_signing_key;
@@ -22,9 +25,24 @@ use crate::utils::read_signing_key;
 // We try to process a request 3 times before sending warnings in the console
 const PROCESSING_ATTEMPTS: u32 = 3;
 
+// Maximum amount of ForcedExit transactions sent in a single batch.
+// Requests are never split between the batches, so a batch with a single request
+// may contain more transactions.
+const MAX_FORCED_EXITS_IN_BATCH: usize = 20;
+
+/// Payment for the forced exit request received by the ForcedExit contract.
+#[derive(Debug, Clone)]
+pub struct RequestPayment {
+    pub amount: BigUint,
+    /// Lower bound on the time of the payment.
+    pub submission_time: DateTime<Utc>,
+    pub eth_tx_hash: H256,
+}
+
 #[async_trait::async_trait]
 pub trait ForcedExitSender {
-    async fn process_request(&mut self, amount: BigUint, submission_time: DateTime<Utc>);
+    /// Fulfills the requests paid by the payments, the eligible ones are fulfilled in batches.
+    async fn process_payments(&mut self, payments: Vec<RequestPayment>);
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -44,20 +62,20 @@ pub struct MempoolForcedExitSender<T: CoreInteractionWrapper> {
 
 #[async_trait::async_trait]
 impl<T: CoreInteractionWrapper + Sync + Send> ForcedExitSender for MempoolForcedExitSender<T> {
-    async fn process_request(&mut self, amount: BigUint, submission_time: DateTime<Utc>) {
+    async fn process_payments(&mut self, payments: Vec<RequestPayment>) {
         let mut attempts: u32 = 0;
         // Typically this should not run any longer than 1 iteration
         // In case something bad happens we do not want the server crush because
         // of the forced_exit_requests component
         loop {
-            let processing_attempt = self
-                .try_process_request(amount.clone(), submission_time)
-                .await;
-
-            if processing_attempt.is_ok() {
-                return;
-            } else {
-                attempts += 1;
+            let processing_attempt = self.try_process_payments(&payments).await;
+
+            match processing_attempt {
+                Ok(()) => return,
+                Err(err) => {
+                    vlog::warn!("Failed to process ForcedExit requests: {}", err);
+                    attempts += 1;
+                }
             }
 
             if attempts >= PROCESSING_ATTEMPTS {
@@ -111,49 +129,107 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
         }
     }
 
-    pub async fn build_transactions(
-        &self,
-        // storage: &mut StorageProcessor<'_>,
-        fe_request: ForcedExitRequest,
-    ) -> anyhow::Result<Vec<SignedZkSyncTx>> {
-        let mut sender_nonce = self
+    pub fn build_refund(&self, nonce: Nonce, to: Address, amount: BigUint) -> SignedZkSyncTx {
+        let tx = Transfer::new_signed(
+            self.forced_exit_sender_account_id,
+            self.config.sender_account_address,
+            to,
+            // Request fees are paid in ETH
+            TokenId(0),
+            amount,
+            BigUint::from(0u32),
+            nonce,
+            TimeRange::default(),
+            &self.sender_private_key,
+        )
+        .expect("Failed to create signed Transfer transaction");
+
+        SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(tx)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    async fn get_nonce(&self) -> anyhow::Result<Nonce> {
+        let nonce = self
             .core_interaction_wrapper
             .get_nonce(self.forced_exit_sender_account_id)
             .await?
             .expect("Forced Exit sender account does not have nonce");
+        Ok(nonce)
+    }
 
+    pub fn build_transactions(
+        &self,
+        sender_nonce: &mut Nonce,
+        fe_request: &ForcedExitRequest,
+    ) -> Vec<SignedZkSyncTx> {
         let mut transactions: Vec<SignedZkSyncTx> = vec![];
 
-        for token in fe_request.tokens.into_iter() {
-            transactions.push(self.build_forced_exit(sender_nonce, fe_request.target, token));
+        for token in fe_request.tokens.iter() {
+            transactions.push(self.build_forced_exit(*sender_nonce, fe_request.target, *token));
             sender_nonce.add_assign(1);
         }
 
-        Ok(transactions)
+        transactions
     }
 
-    // Returns the id the request if it should be fulfilled,
-    // error otherwise
+    // Returns the request if it should be fulfilled
     pub fn check_request(
         &self,
-        amount: BigUint,
+        amount: &BigUint,
         submission_time: DateTime<Utc>,
         request: Option<ForcedExitRequest>,
-    ) -> bool {
+    ) -> Option<ForcedExitRequest> {
         let request = match request {
             Some(r) => r,
             None => {
                 // The request does not exit, we should not process it
-                return false;
+                return None;
             }
         };
 
         if request.fulfilled_at.is_some() {
             // We should not re-process requests that were fulfilled before
-            return false;
+            return None;
+        }
+
+        // The fee paid in excess is refunded once the request is fulfilled
+        if request.valid_until > submission_time && amount >= &request.price_in_wei {
+            Some(request)
+        } else {
+            None
+        }
+    }
+
+    // Returns the refund address of the request and the amount to refund to it.
+    // The sender of the payment is not refunded, since it may be a relayer or a contract
+    fn get_refund(
+        &self,
+        request: &ForcedExitRequest,
+        paid_amount: &BigUint,
+    ) -> Option<(Address, BigUint)> {
+        if paid_amount <= &request.price_in_wei {
+            return None;
+        }
+        let refund_address = match request.refund_address {
+            Some(address) => address,
+            None => {
+                vlog::info!(
+                    "Forced exit request {} is overpaid, but has no refund address",
+                    request.id
+                );
+                return None;
+            }
+        };
+        // Transfer amounts must be packable, the rest of the excess is not refunded
+        let amount = closest_packable_token_amount(&(paid_amount - &request.price_in_wei));
+        if amount.is_zero() {
+            return None;
         }
 
-        request.valid_until > submission_time && request.price_in_wei == amount
+        Some((refund_address, amount))
     }
 
     // Awaits until the request is complete
@@ -202,7 +278,7 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
             }
         }
 
-        Ok(())
+        self.process_refunds(None).await
     }
 
     pub async fn wait_until_comitted(
@@ -240,53 +316,194 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
         }
     }
 
-    pub async fn try_process_request(
+    // Sends the requests in a single batch and waits until it is executed.
+    // Returns `false` if the batch has failed.
+    async fn fulfill_requests(
         &mut self,
-        amount: BigUint,
-        submission_time: DateTime<Utc>,
-    ) -> anyhow::Result<()> {
-        let (id, amount) = utils::extract_id_from_amount(amount, self.config.digits_in_id as u32);
-
-        let fe_request = self.core_interaction_wrapper.get_request_by_id(id).await?;
-
-        let fe_request = if self.check_request(amount, submission_time, fe_request.clone()) {
-            // The self.check_request already checked that the fe_request is Some(_)
-            fe_request.unwrap()
-        } else {
-            // The request was not valid, that's fine
-            return Ok(());
-        };
-
-        let txs = self.build_transactions(fe_request.clone()).await?;
-
-        // Right before sending the transactions we must check if the request is possible at all
-        let is_request_possible = self
-            .core_interaction_wrapper
-            .check_forced_exit_request(&fe_request)
-            .await?;
-        if !is_request_possible {
-            // If not possible at all, return without sending any transactions
-            return Ok(());
+        sender_nonce: &mut Nonce,
+        mut fulfillments: Vec<RequestFulfillment>,
+    ) -> anyhow::Result<bool> {
+        let batch_nonce = *sender_nonce;
+        for fulfillment in fulfillments.iter_mut() {
+            fulfillment.txs = self.build_transactions(sender_nonce, &fulfillment.request);
         }
+
         let hashes = self
             .core_interaction_wrapper
-            .send_and_save_txs_batch(&fe_request, txs)
+            .send_and_save_txs_batch(&fulfillments)
             .await?;
 
         // We wait only for the first transaction to complete since the transactions
         // are sent in a batch
-        if let Err(ForcedExitRequestConfirmationError::DatabaseError(err)) =
-            self.wait_until_comitted(hashes[0]).await
-        {
-            // We have to return only if tx was failed because of database interaction.
-            // Failed tx in state keeper is legit.
-            anyhow::bail!(err);
+        match self.wait_until_comitted(hashes[0]).await {
+            Ok(()) => {}
+            Err(ForcedExitRequestConfirmationError::FailedTx(reason)) => {
+                // Nonces of the failed batch were not used
+                *sender_nonce = batch_nonce;
+                vlog::warn!("ForcedExit requests batch has failed: {}", reason);
+                if fulfillments.len() > 1 {
+                    return Ok(false);
+                }
+                // Failed tx of a single request in state keeper is legit.
+            }
+            Err(ForcedExitRequestConfirmationError::DatabaseError(err)) => {
+                // We have to return only if tx was failed because of database interaction.
+                anyhow::bail!(err);
+            }
+        }
+
+        for fulfillment in fulfillments.iter() {
+            self.core_interaction_wrapper
+                .set_fulfilled_at(fulfillment.request.id)
+                .await?;
         }
+        Ok(true)
+    }
 
-        self.core_interaction_wrapper.set_fulfilled_at(id).await?;
+    // Sends the refunds of the fulfilled requests which were not completed yet.
+    pub async fn process_refunds(&mut self, sender_nonce: Option<Nonce>) -> anyhow::Result<()> {
+        let requests = self.core_interaction_wrapper.get_pending_refunds().await?;
+        if requests.is_empty() {
+            return Ok(());
+        }
+        let mut sender_nonce = match sender_nonce {
+            Some(nonce) => nonce,
+            None => self.get_nonce().await?,
+        };
+
+        for request in requests {
+            let refund = request
+                .refund
+                .expect("Pending refund is not set for the request");
+
+            let tx_hash = match refund.refunded_by {
+                // The refund was sent before the restart
+                Some(tx_hash) => tx_hash,
+                None => {
+                    let tx = self.build_refund(sender_nonce, refund.address, refund.amount);
+                    let tx_hash = self
+                        .core_interaction_wrapper
+                        .send_and_save_refund(request.id, tx)
+                        .await?;
+                    sender_nonce.add_assign(1);
+                    tx_hash
+                }
+            };
+
+            match self.wait_until_comitted(tx_hash).await {
+                Ok(()) => {
+                    self.core_interaction_wrapper
+                        .set_refunded_at(request.id)
+                        .await?;
+                }
+                Err(ForcedExitRequestConfirmationError::FailedTx(reason)) => {
+                    // The refund is retried during the next processing, e.g. once
+                    // the operator tops up the balance of the forced exit sender.
+                    vlog::error!(
+                        "Refund for the ForcedExit request with id {} has failed: {}",
+                        request.id,
+                        reason
+                    );
+                    self.core_interaction_wrapper
+                        .set_refunded_by(request.id, None)
+                        .await?;
+                    sender_nonce = self.get_nonce().await?;
+                }
+                Err(ForcedExitRequestConfirmationError::DatabaseError(err)) => {
+                    anyhow::bail!(err);
+                }
+            }
+        }
 
         Ok(())
     }
+
+    pub async fn try_process_payments(
+        &mut self,
+        payments: &[RequestPayment],
+    ) -> anyhow::Result<()> {
+        let mut fulfillments: Vec<RequestFulfillment> = Vec::new();
+
+        for payment in payments {
+            let (id, amount) = utils::extract_id_from_amount(
+                payment.amount.clone(),
+                self.config.digits_in_id as u32,
+            );
+
+            let fe_request = self.core_interaction_wrapper.get_request_by_id(id).await?;
+            let fe_request = match self.check_request(&amount, payment.submission_time, fe_request)
+            {
+                Some(fe_request) => fe_request,
+                // The request was not valid, that's fine
+                None => continue,
+            };
+            if fulfillments
+                .iter()
+                .any(|fulfillment| fulfillment.request.id == id)
+            {
+                // The request was paid twice
+                continue;
+            }
+            self.core_interaction_wrapper
+                .set_paid(id, payment.eth_tx_hash)
+                .await?;
+
+            // Right before sending the transactions we must check if the request is possible at all
+            let is_request_possible = self
+                .core_interaction_wrapper
+                .check_forced_exit_request(&fe_request)
+                .await?;
+            if !is_request_possible {
+                // If not possible at all, skip without sending any transactions
+                continue;
+            }
+
+            let refund = self.get_refund(&fe_request, &amount);
+            fulfillments.push(RequestFulfillment {
+                request: fe_request,
+                txs: Vec::new(),
+                refund,
+            });
+        }
+
+        if fulfillments.is_empty() {
+            return self.process_refunds(None).await;
+        }
+        let mut sender_nonce = self.get_nonce().await?;
+
+        // Splitting the requests into the batches without splitting any of the requests
+        let mut batches: Vec<Vec<RequestFulfillment>> = Vec::new();
+        let mut batch_size = 0;
+        for fulfillment in fulfillments {
+            let request_size = fulfillment.request.tokens.len();
+            match batches.last_mut() {
+                Some(batch) if batch_size + request_size <= MAX_FORCED_EXITS_IN_BATCH => {
+                    batch_size += request_size;
+                    batch.push(fulfillment);
+                }
+                _ => {
+                    batch_size = request_size;
+                    batches.push(vec![fulfillment]);
+                }
+            }
+        }
+
+        for batch in batches {
+            let fulfilled = self
+                .fulfill_requests(&mut sender_nonce, batch.clone())
+                .await?;
+            if !fulfilled {
+                // A single ineligible request fails the whole batch,
+                // so the requests are retried one by one
+                for fulfillment in batch {
+                    self.fulfill_requests(&mut sender_nonce, vec![fulfillment])
+                        .await?;
+                }
+            }
+        }
+
+        self.process_refunds(Some(sender_nonce)).await
+    }
 }
 #[cfg(test)]
 mod test {
@@ -317,6 +534,14 @@ mod test {
         )
     }
 
+    fn payment(amount: &str, submission_time: DateTime<Utc>) -> Vec<RequestPayment> {
+        vec![RequestPayment {
+            amount: BigUint::from_str(amount).unwrap(),
+            submission_time,
+            eth_tx_hash: H256::random(),
+        }]
+    }
+
     #[tokio::test]
     async fn test_forced_exit_sender() {
         let day = chrono::Duration::days(1);
@@ -340,12 +565,16 @@ mod test {
                 created_at: Utc::now(),
                 fulfilled_by: None,
                 fulfilled_at: None,
+                payment_tx_hash: None,
+                paid_at: None,
+                refund_address: None,
+                refund: None,
             },
         );
 
         // Not the right amount, because not enough zeroes
         forced_exit_sender
-            .process_request(BigUint::from_str("1000000012").unwrap(), Utc::now())
+            .process_payments(payment("1000000012", Utc::now()))
             .await;
         assert_eq!(
             forced_exit_sender
@@ -359,7 +588,7 @@ mod test {
 
         // Not the right amount, because id is not correct
         forced_exit_sender
-            .process_request(BigUint::from_str("10000000001").unwrap(), Utc::now())
+            .process_payments(payment("10000000001", Utc::now()))
             .await;
         assert_eq!(
             forced_exit_sender
@@ -373,10 +602,7 @@ mod test {
 
         // The tranasction is correct, buuut it is expired
         forced_exit_sender
-            .process_request(
-                BigUint::from_str("10000000001").unwrap(),
-                Utc::now().add(day.mul(3)),
-            )
+            .process_payments(payment("10000000001", Utc::now().add(day.mul(3))))
             .await;
 
         assert_eq!(
@@ -391,7 +617,7 @@ mod test {
 
         // The transaction is correct
         forced_exit_sender
-            .process_request(BigUint::from_str("10000000012").unwrap(), Utc::now())
+            .process_payments(payment("10000000012", Utc::now()))
             .await;
 
         assert_eq!(
@@ -404,4 +630,79 @@ mod test {
             1
         );
     }
+
+    #[tokio::test]
+    async fn test_forced_exit_sender_batches_and_refunds() {
+        let day = chrono::Duration::days(1);
+
+        let forced_exit_requests = ForcedExitRequestsConfig {
+            digits_in_id: 10,
+            ..ForcedExitRequestsConfig::from_env()
+        };
+        let mut forced_exit_sender = get_test_forced_exit_sender(Some(forced_exit_requests));
+
+        let refund_address = Address::random();
+        for (id, tokens, refund_address) in [
+            (1, vec![TokenId(1), TokenId(2)], Some(refund_address)),
+            (2, vec![TokenId(3)], Some(refund_address)),
+            (3, vec![TokenId(4)], None),
+        ] {
+            add_request(
+                &forced_exit_sender.core_interaction_wrapper.requests,
+                ForcedExitRequest {
+                    id,
+                    target: Address::random(),
+                    tokens,
+                    price_in_wei: BigUint::from_str("10000000000").unwrap(),
+                    valid_until: Utc::now().add(day),
+                    created_at: Utc::now(),
+                    fulfilled_by: None,
+                    fulfilled_at: None,
+                    payment_tx_hash: None,
+                    paid_at: None,
+                    refund_address,
+                    refund: None,
+                },
+            );
+        }
+
+        // The first request is paid exactly, the other ones are overpaid
+        let mut payments = payment("10000000001", Utc::now());
+        payments.extend(payment("30000000002", Utc::now()));
+        payments.extend(payment("20000000003", Utc::now()));
+        forced_exit_sender.process_payments(payments).await;
+
+        let wrapper = &forced_exit_sender.core_interaction_wrapper;
+        // All the requests are fulfilled within a single batch
+        assert_eq!(*wrapper.sent_batch_sizes.lock().unwrap(), vec![4]);
+        let requests = wrapper.requests.lock().unwrap().clone();
+        assert!(requests
+            .iter()
+            .all(|request| request.fulfilled_at.is_some()));
+        assert!(requests.iter().all(|request| request.paid_at.is_some()));
+        assert_eq!(requests[0].fulfilled_by.as_ref().unwrap().len(), 2);
+        assert!(requests[0].refund.is_none());
+
+        // The excess is refunded to the refund address set in the request
+        let refund = requests[1].refund.clone().unwrap();
+        assert_eq!(refund.address, refund_address);
+        assert_eq!(refund.amount, BigUint::from_str("20000000000").unwrap());
+        assert!(refund.refunded_at.is_some());
+
+        // The excess is not refunded without the refund address,
+        // since the payment may be sent by a relayer or a contract
+        assert!(requests[2].refund.is_none());
+
+        let sent_refunds = wrapper.sent_refunds.lock().unwrap();
+        assert_eq!(sent_refunds.len(), 1);
+        match &sent_refunds[0].tx {
+            ZkSyncTx::Transfer(transfer) => {
+                assert_eq!(transfer.to, refund.address);
+                assert_eq!(transfer.amount, refund.amount);
+                // Refund is sent after the ForcedExit transactions
+                assert_eq!(transfer.nonce, Nonce(4));
+            }
+            tx => panic!("Unexpected refund transaction {:?}", tx),
+        }
+    }
 }
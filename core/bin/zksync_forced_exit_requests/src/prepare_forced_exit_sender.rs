@@ -17,6 +17,7 @@ use zksync_crypto::franklin_crypto::eddsa::PrivateKey;
 use futures::channel::{mpsc, oneshot};
 use futures::SinkExt;
 use tokio::time;
+use tracing::Span;
 
 use zksync_mempool::MempoolTransactionRequest;
 use zksync_test_account::{ZkSyncAccount, ZkSyncETHAccountData};
@@ -203,7 +204,7 @@ pub async fn register_signing_key(
     let tx_hash = tx.hash();
 
     let (sender, receiver) = oneshot::channel();
-    let item = MempoolTransactionRequest::NewTx(Box::new(tx.into()), sender);
+    let item = MempoolTransactionRequest::NewTx(Box::new(tx.into()), sender, Span::current());
 
     mempool_tx_sender
         .send(item)
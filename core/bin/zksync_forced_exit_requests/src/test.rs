@@ -4,18 +4,21 @@ use chrono::Utc;
 use zksync_storage::chain::operations_ext::records::TxReceiptResponse;
 use zksync_types::Nonce;
 use zksync_types::{
-    forced_exit_requests::{ForcedExitRequest, ForcedExitRequestId},
+    forced_exit_requests::{ForcedExitRefund, ForcedExitRequest, ForcedExitRequestId},
     tx::TxHash,
-    AccountId, SignedZkSyncTx,
+    AccountId, SignedZkSyncTx, H256,
 };
 
-use super::core_interaction_wrapper::CoreInteractionWrapper;
+use super::core_interaction_wrapper::{CoreInteractionWrapper, RequestFulfillment};
 
 pub struct MockCoreInteractionWrapper {
     pub nonce: Nonce,
     pub requests: Mutex<Vec<ForcedExitRequest>>,
     pub tx_receipt: Option<TxReceiptResponse>,
     pub sent_txs: Mutex<Vec<SignedZkSyncTx>>,
+    // Amounts of the transactions in each of the sent batches
+    pub sent_batch_sizes: Mutex<Vec<usize>>,
+    pub sent_refunds: Mutex<Vec<SignedZkSyncTx>>,
     // It is easier when keeping track of the deleted txs
     pub deleted_requests: Mutex<Vec<ForcedExitRequest>>,
 }
@@ -35,6 +38,8 @@ impl Default for MockCoreInteractionWrapper {
                 prover_run: None,
             }),
             sent_txs: Mutex::new(vec![]),
+            sent_batch_sizes: Mutex::new(vec![]),
+            sent_refunds: Mutex::new(vec![]),
             deleted_requests: Mutex::new(vec![]),
         }
     }
@@ -90,6 +95,15 @@ impl CoreInteractionWrapper for MockCoreInteractionWrapper {
 
         Ok(())
     }
+    async fn set_paid(&self, id: ForcedExitRequestId, payment_tx_hash: H256) -> anyhow::Result<()> {
+        let index = self.get_request_index_by_id(id)?;
+        let mut requests = self.lock_requests();
+
+        requests[index].payment_tx_hash = Some(payment_tx_hash);
+        requests[index].paid_at = Some(Utc::now());
+
+        Ok(())
+    }
     async fn set_fulfilled_by(
         &self,
         id: ForcedExitRequestId,
@@ -120,17 +134,84 @@ impl CoreInteractionWrapper for MockCoreInteractionWrapper {
 
     async fn send_and_save_txs_batch(
         &mut self,
-        request: &ForcedExitRequest,
-        mut txs: Vec<SignedZkSyncTx>,
+        fulfillments: &[RequestFulfillment],
     ) -> anyhow::Result<Vec<TxHash>> {
-        let hashes: Vec<TxHash> = txs.iter().map(|tx| tx.hash()).collect();
+        let mut hashes = Vec::new();
+        for fulfillment in fulfillments {
+            let request_hashes: Vec<TxHash> = fulfillment.txs.iter().map(|tx| tx.hash()).collect();
+            hashes.extend_from_slice(&request_hashes);
+            self.lock_sent_txs().extend(fulfillment.txs.iter().cloned());
+
+            let index = self.get_request_index_by_id(fulfillment.request.id)?;
+            let mut requests = self.lock_requests();
+            requests[index].fulfilled_by = Some(request_hashes);
+            requests[index].refund =
+                fulfillment
+                    .refund
+                    .clone()
+                    .map(|(address, amount)| ForcedExitRefund {
+                        address,
+                        amount,
+                        refunded_by: None,
+                        refunded_at: None,
+                    });
+        }
+        self.sent_batch_sizes.lock().unwrap().push(hashes.len());
+
+        Ok(hashes)
+    }
 
-        self.lock_sent_txs().append(&mut txs);
+    async fn get_pending_refunds(&self) -> anyhow::Result<Vec<ForcedExitRequest>> {
+        let requests = self.lock_requests();
 
-        self.set_fulfilled_by(request.id, Some(hashes.clone()))
-            .await?;
+        let pending_refunds = requests
+            .iter()
+            .filter(|r| {
+                r.fulfilled_at.is_some()
+                    && matches!(&r.refund, Some(refund) if refund.refunded_at.is_none())
+            })
+            .cloned()
+            .collect();
 
-        Ok(hashes)
+        Ok(pending_refunds)
+    }
+
+    async fn send_and_save_refund(
+        &mut self,
+        id: ForcedExitRequestId,
+        tx: SignedZkSyncTx,
+    ) -> anyhow::Result<TxHash> {
+        let hash = tx.hash();
+        self.sent_refunds.lock().unwrap().push(tx);
+        self.set_refunded_by(id, Some(hash)).await?;
+
+        Ok(hash)
+    }
+
+    async fn set_refunded_by(
+        &self,
+        id: ForcedExitRequestId,
+        value: Option<TxHash>,
+    ) -> anyhow::Result<()> {
+        let index = self.get_request_index_by_id(id)?;
+        let mut requests = self.lock_requests();
+
+        if let Some(refund) = requests[index].refund.as_mut() {
+            refund.refunded_by = value;
+        }
+
+        Ok(())
+    }
+
+    async fn set_refunded_at(&self, id: ForcedExitRequestId) -> anyhow::Result<()> {
+        let index = self.get_request_index_by_id(id)?;
+        let mut requests = self.lock_requests();
+
+        if let Some(refund) = requests[index].refund.as_mut() {
+            refund.refunded_at = Some(Utc::now());
+        }
+
+        Ok(())
     }
 
     async fn get_oldest_unfulfilled_request(&self) -> anyhow::Result<Option<ForcedExitRequest>> {
@@ -7,7 +7,7 @@ use num::BigUint;
 use zksync_config::{
     configs::{
         api::ProverApiConfig,
-        prover::{Core, Prover, WitnessGenerator},
+        prover::{Core, Prover, ProverBackend, Remote, WitnessGenerator},
     },
     ProverConfig,
 };
@@ -39,6 +39,7 @@ impl Default for MockProverOptions {
                 cycle_wait: 500,
                 request_timeout: 10,
                 die_after_proof: false,
+                backend: ProverBackend::PlonkStepByStep,
             },
             core: Core {
                 gone_timeout: 60000,
@@ -48,6 +49,10 @@ impl Default for MockProverOptions {
                 prepare_data_interval: 500,
                 witness_generators: 1,
             },
+            remote: Remote {
+                url: "http://127.0.0.1:8089".to_string(),
+                request_timeout: 10,
+            },
         };
 
         MockProverOptions(api, prover)
@@ -189,6 +194,7 @@ pub async fn get_test_block() -> Block {
                 eth_hash: H256::zero(),
                 eth_block: 10,
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
             block_index: 1,
             created_at: chrono::Utc::now(),
@@ -97,6 +97,18 @@ impl ClientRequestBuilder {
         }
     }
 
+    /// Add a header to the request.
+    ///
+    /// See [reqwest] documentation for details
+    ///
+    /// [reqwest]: https://docs.rs/reqwest/latest/reqwest/struct.RequestBuilder.html#method.header
+    pub fn header(self, key: &str, value: &str) -> Self {
+        Self {
+            inner: self.inner.header(key, value),
+            url: self.url,
+        }
+    }
+
     /// Send a JSON body.
     ///
     /// See [reqwest] documentation for details
@@ -129,4 +141,25 @@ impl ClientRequestBuilder {
             })
         }
     }
+
+    /// Constructs the Request and sends it to the target URL, returning the response body as is.
+    ///
+    /// Used for the endpoints returning documents instead of the API responses.
+    pub async fn send_raw(self) -> self::Result<String> {
+        let response = self.inner.send().await?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(response.text().await?)
+        } else {
+            if status == StatusCode::NOT_FOUND {
+                return Err(ClientError::NotFound(self.url));
+            }
+
+            Err(ClientError::BadRequest {
+                http_code: status,
+                body: response.json().await.map_err(ClientError::Parse)?,
+            })
+        }
+    }
 }
@@ -3,10 +3,15 @@
 // Built-in uses
 
 // External uses
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
-use zksync_types::{forced_exit_requests::ForcedExitRequest, Address, TokenId};
+use zksync_types::{
+    forced_exit_requests::{ForcedExitRequest, ForcedExitRequestId},
+    tx::TxHash,
+    Address, BlockNumber, TokenId, H256,
+};
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
 use num::BigUint;
@@ -41,6 +46,61 @@ pub struct ForcedExitRegisterRequest {
     // since the price might change (with config)
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub price_in_wei: BigUint,
+    // The request fee paid in excess is refunded to this address, the payment
+    // may be sent by a relayer or a contract, so the payer can't be refunded
+    #[serde(default)]
+    pub refund_address: Option<Address>,
+}
+
+/// Stage of the forced exit request lifecycle.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum ForcedExitRequestStage {
+    /// The request is waiting for the payment.
+    Requested,
+    /// The payment is seen at L1, but the ForcedExit transactions are not sent yet.
+    Paid,
+    /// The ForcedExit transactions are sent, but not executed yet.
+    Queued,
+    /// The ForcedExit transactions are executed, but the funds are not withdrawn on L1 yet.
+    Executed,
+    /// The funds are withdrawn on L1.
+    Completed,
+    /// All the ForcedExit transactions have failed.
+    Failed,
+}
+
+/// State of the ForcedExit transaction of the request.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedExitTxState {
+    pub tx_hash: TxHash,
+    pub block_number: Option<BlockNumber>,
+    pub success: Option<bool>,
+    pub fail_reason: Option<String>,
+    /// Hash of the Ethereum transaction in which the funds were withdrawn.
+    pub withdrawal_tx_hash: Option<H256>,
+}
+
+/// Estimation of the time when the funds are withdrawn on L1, derived from the backlogs
+/// of the prover and the Ethereum sender and their throughput during the last hour.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedExitEta {
+    pub pending_prover_jobs: u64,
+    pub pending_eth_operations: u64,
+    /// `None` if the backlog is not processed at the moment.
+    pub estimated_completion_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedExitRequestLifecycle {
+    pub request: ForcedExitRequest,
+    pub stage: ForcedExitRequestStage,
+    pub txs: Vec<ForcedExitTxState>,
+    /// Provided until the request is completed or failed once it is paid.
+    pub eta: Option<ForcedExitEta>,
 }
 
 const FORCED_EXIT_REQUESTS_SCOPE: &str = "/api/forced_exit_requests/v0.1/";
@@ -61,4 +121,16 @@ impl Client {
             .send()
             .await
     }
+
+    pub async fn get_forced_exit_request_lifecycle(
+        &self,
+        id: ForcedExitRequestId,
+    ) -> ClientResult<ForcedExitRequestLifecycle> {
+        self.get_with_scope(
+            FORCED_EXIT_REQUESTS_SCOPE,
+            &format!("requests/{}/lifecycle", id),
+        )
+        .send()
+        .await
+    }
 }
@@ -1,10 +1,14 @@
 use crate::rest::client::{Client, Result};
 
 use zksync_api_types::v02::{
+    account::{
+        AccountNonceQuery, AccountTxsByMemoQuery, AccountsActivityRequest, AccountsResolveRequest,
+    },
+    fields::FieldsQuery,
     pagination::{ApiEither, PaginationQuery},
     Response,
 };
-use zksync_types::{tx::TxHash, SerialId};
+use zksync_types::{tx::TxHash, BlockNumber, SerialId};
 
 impl Client {
     pub async fn account_info(
@@ -29,6 +33,22 @@ impl Client {
         .await
     }
 
+    pub async fn account_balance_proof(
+        &self,
+        account_id_or_address: &str,
+        token_like: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!(
+                "accounts/{}/balances/{}/proof",
+                account_id_or_address, token_like
+            ),
+        )
+        .send()
+        .await
+    }
+
     pub async fn account_txs(
         &self,
         pagination_query: &PaginationQuery<ApiEither<TxHash>>,
@@ -43,6 +63,27 @@ impl Client {
         .await
     }
 
+    /// Returns the transactions of the account with the requested fields only,
+    /// `fields` is the comma-separated list of the field names.
+    pub async fn account_txs_with_fields(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TxHash>>,
+        account_id_or_address: &str,
+        fields: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/transactions", account_id_or_address),
+        )
+        .query(&pagination_query)
+        .query(&FieldsQuery {
+            fields: Some(fields.to_string()),
+            format_amounts: None,
+        })
+        .send()
+        .await
+    }
+
     pub async fn account_pending_txs(
         &self,
         pagination_query: &PaginationQuery<ApiEither<SerialId>>,
@@ -56,4 +97,60 @@ impl Client {
         .send()
         .await
     }
+
+    /// Looks up the transfers received by the account with the given memo.
+    pub async fn account_txs_by_memo(
+        &self,
+        account_id_or_address: &str,
+        memo: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/transactions/memo", account_id_or_address),
+        )
+        .query(&AccountTxsByMemoQuery {
+            memo: memo.to_string(),
+        })
+        .send()
+        .await
+    }
+
+    /// Returns the nonce of the account after the given block.
+    pub async fn account_nonce_at(
+        &self,
+        account_id_or_address: &str,
+        block_number: BlockNumber,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/nonce", account_id_or_address),
+        )
+        .query(&AccountNonceQuery { block_number })
+        .send()
+        .await
+    }
+
+    /// Resolves the ids and the addresses of the accounts at once.
+    pub async fn resolve_accounts(&self, request: &AccountsResolveRequest) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "accounts/resolve")
+            .body(request)
+            .send()
+            .await
+    }
+
+    pub async fn accounts_by_pub_key_hash(&self, pub_key_hash: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/pub_key_hash/{}", pub_key_hash),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn accounts_activity(&self, request: &AccountsActivityRequest) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "accounts/activity")
+            .body(request)
+            .send()
+            .await
+    }
 }
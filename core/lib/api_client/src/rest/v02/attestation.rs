@@ -0,0 +1,19 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{attestation::AddressAttestation, Response};
+use zksync_types::Address;
+
+impl Client {
+    /// Stores the attestation signed over `AddressAttestation::get_ethereum_sign_message`.
+    pub async fn attest_address(&self, attestation: &AddressAttestation) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "attestations")
+            .body(attestation)
+            .send()
+            .await
+    }
+
+    pub async fn attestation(&self, address: Address) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, &format!("attestations/{:?}", address))
+            .send()
+            .await
+    }
+}
@@ -13,6 +13,30 @@ impl Client {
             .await
     }
 
+    pub async fn block_finality(&self, block_position: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("blocks/{}/finality", block_position),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn block_pubdata(&self, block_position: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("blocks/{}/pubdata", block_position),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn pending_block(&self) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "blocks/pending")
+            .send()
+            .await
+    }
+
     pub async fn transaction_in_block(&self, block_number: u32, tx_index: u32) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
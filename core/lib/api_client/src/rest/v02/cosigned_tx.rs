@@ -0,0 +1,37 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{
+    cosigned_tx::{IncomingCoSignature, IncomingCoSignedTx},
+    Response,
+};
+use zksync_types::tx::{TxEthSignature, TxHash};
+
+impl Client {
+    /// Submits the transaction to be held until it's co-signed according to its policy.
+    pub async fn create_cosigned_tx(&self, request: &IncomingCoSignedTx) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "cosigned_txs")
+            .body(request)
+            .send()
+            .await
+    }
+
+    /// Adds the co-signature over `IncomingCoSignature::get_ethereum_sign_message`.
+    /// The transaction is submitted once the threshold is met.
+    pub async fn cosign_tx(&self, tx_hash: TxHash, signature: TxEthSignature) -> Result<Response> {
+        self.post_with_scope(
+            super::API_V02_SCOPE,
+            &format!("cosigned_txs/{}/signatures", tx_hash.to_string()),
+        )
+        .body(&IncomingCoSignature { signature })
+        .send()
+        .await
+    }
+
+    pub async fn cosigned_tx(&self, tx_hash: TxHash) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("cosigned_txs/{}", tx_hash.to_string()),
+        )
+        .send()
+        .await
+    }
+}
@@ -0,0 +1,11 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::Response;
+
+impl Client {
+    /// Returns the CREATE2 factories accepted in the `ChangePubKey` transactions.
+    pub async fn create2_factories(&self) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "create2_factories")
+            .send()
+            .await
+    }
+}
@@ -0,0 +1,28 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{deposit_relay::IncomingDepositRelay, Response};
+use zksync_types::TokenId;
+
+impl Client {
+    /// Returns the current relay fee and the relayer address the permit should be signed for.
+    pub async fn deposit_relay_fee(&self, token: TokenId) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("deposit_relays/fee/{}", token),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn create_deposit_relay(&self, request: &IncomingDepositRelay) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "deposit_relays")
+            .body(request)
+            .send()
+            .await
+    }
+
+    pub async fn deposit_relay(&self, job_id: u64) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, &format!("deposit_relays/{}", job_id))
+            .send()
+            .await
+    }
+}
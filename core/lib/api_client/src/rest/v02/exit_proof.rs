@@ -0,0 +1,32 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::Response;
+
+impl Client {
+    /// Requests the exit proof of the account balance. The proof is generated in the background,
+    /// its status can be checked with `exit_proof`.
+    pub async fn request_exit_proof(
+        &self,
+        account_id_or_address: &str,
+        token_like: &str,
+    ) -> Result<Response> {
+        self.post_with_scope(
+            super::API_V02_SCOPE,
+            &format!("exit_proofs/{}/{}", account_id_or_address, token_like),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn exit_proof(
+        &self,
+        account_id_or_address: &str,
+        token_like: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("exit_proofs/{}/{}", account_id_or_address, token_like),
+        )
+        .send()
+        .await
+    }
+}
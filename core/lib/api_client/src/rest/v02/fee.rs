@@ -18,6 +18,25 @@ impl Client {
                 tx_type,
                 address,
                 token_like,
+                withdrawal_hook: false,
+            })
+            .send()
+            .await
+    }
+
+    /// Returns the fee of the withdrawal submitted with the calldata of the call made
+    /// to the recipient contract once the withdrawal is executed on L1.
+    pub async fn get_withdraw_with_l1_calldata_fee(
+        &self,
+        address: Address,
+        token_like: TokenLike,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "fee")
+            .body(&TxFeeRequest {
+                tx_type: ApiTxFeeTypes::Withdraw,
+                address,
+                token_like,
+                withdrawal_hook: true,
             })
             .send()
             .await
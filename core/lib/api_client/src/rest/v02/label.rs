@@ -0,0 +1,48 @@
+use crate::rest::client::{Client, ClientRequestBuilder, Result};
+use zksync_api_types::v02::{
+    label::{AddressLabelRequest, LABELS_API_KEY_HEADER},
+    Response,
+};
+use zksync_types::Address;
+
+fn with_api_key(request: ClientRequestBuilder, api_key: Option<&str>) -> ClientRequestBuilder {
+    match api_key {
+        Some(api_key) => request.header(LABELS_API_KEY_HEADER, api_key),
+        None => request,
+    }
+}
+
+impl Client {
+    pub async fn address_labels(
+        &self,
+        address: Address,
+        api_key: Option<&str>,
+    ) -> Result<Response> {
+        let request = self.get_with_scope(super::API_V02_SCOPE, &format!("labels/{:?}", address));
+        with_api_key(request, api_key).send().await
+    }
+
+    pub async fn add_address_label(
+        &self,
+        label: &AddressLabelRequest,
+        api_key: &str,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "labels")
+            .header(LABELS_API_KEY_HEADER, api_key)
+            .body(label)
+            .send()
+            .await
+    }
+
+    pub async fn remove_address_label(
+        &self,
+        label: &AddressLabelRequest,
+        api_key: &str,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "labels/remove")
+            .header(LABELS_API_KEY_HEADER, api_key)
+            .body(label)
+            .send()
+            .await
+    }
+}
@@ -1,9 +1,24 @@
 pub mod account;
+pub mod attestation;
 pub mod block;
 pub mod config;
+pub mod cosigned_tx;
+pub mod create2_factory;
+pub mod deposit_relay;
+pub mod event;
+pub mod exit_proof;
 pub mod fee;
+pub mod label;
+pub mod nonce_reservation;
+pub mod order_book;
+pub mod payment_request;
+pub mod priority_op;
+pub mod session_key;
+pub mod standing_order;
+pub mod statement;
 pub mod status;
 pub mod token;
 pub mod transaction;
+pub mod tx_callback;
 
 const API_V02_SCOPE: &str = "/api/v0.2/";
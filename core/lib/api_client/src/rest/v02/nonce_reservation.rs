@@ -0,0 +1,25 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{nonce_reservation::NonceReservation, Response};
+
+impl Client {
+    /// Reserves the range of the account nonces, signed over
+    /// `NonceReservation::get_ethereum_sign_message` by the account owner.
+    pub async fn reserve_nonces(&self, reservation: &NonceReservation) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "nonce_reservations")
+            .body(reservation)
+            .send()
+            .await
+    }
+
+    pub async fn account_nonce_reservations(
+        &self,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("nonce_reservations/accounts/{}", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+}
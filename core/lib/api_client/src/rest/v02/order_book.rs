@@ -0,0 +1,80 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{
+    order_book::{IncomingOrder, IncomingOrderCancellation},
+    pagination::{ApiEither, PaginationQuery},
+    Response,
+};
+use zksync_types::{
+    tx::{TxEthSignature, TxHash, TxSignature},
+    Order,
+};
+
+impl Client {
+    /// Submits the signed swap order to the order book.
+    pub async fn submit_order(
+        &self,
+        order: Order,
+        eth_signature: Option<TxEthSignature>,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "orders")
+            .body(&IncomingOrder {
+                order,
+                eth_signature,
+            })
+            .send()
+            .await
+    }
+
+    /// Cancels the open order. The cancellation is signed over `Order::get_cancellation_bytes`.
+    pub async fn cancel_order(
+        &self,
+        order_hash: TxHash,
+        signature: TxSignature,
+    ) -> Result<Response> {
+        self.post_with_scope(
+            super::API_V02_SCOPE,
+            &format!("orders/{}/cancel", order_hash.to_string()),
+        )
+        .body(&IncomingOrderCancellation { signature })
+        .send()
+        .await
+    }
+
+    pub async fn order(&self, order_hash: TxHash) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("orders/{}", order_hash.to_string()),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn open_orders(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<u64>>,
+        token_sell: &str,
+        token_buy: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("orders/open/{}/{}", token_sell, token_buy),
+        )
+        .query(pagination_query)
+        .send()
+        .await
+    }
+
+    pub async fn account_orders(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<u64>>,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("orders/accounts/{}", account_id_or_address),
+        )
+        .query(pagination_query)
+        .send()
+        .await
+    }
+}
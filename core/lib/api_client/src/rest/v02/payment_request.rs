@@ -0,0 +1,25 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{payment_request::IncomingPaymentRequest, Response};
+
+impl Client {
+    /// Creates the payment request. The request is marked fulfilled once the matching
+    /// transfer is executed.
+    pub async fn create_payment_request(
+        &self,
+        request: &IncomingPaymentRequest,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "payment_requests")
+            .body(request)
+            .send()
+            .await
+    }
+
+    pub async fn payment_request(&self, payment_request_id: u64) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("payment_requests/{}", payment_request_id),
+        )
+        .send()
+        .await
+    }
+}
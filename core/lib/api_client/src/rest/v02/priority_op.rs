@@ -0,0 +1,26 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{
+    pagination::{ApiEither, PaginationQuery},
+    priority_op::DepositAggregatesQuery,
+    Response,
+};
+use zksync_types::SerialId;
+
+impl Client {
+    pub async fn pending_priority_ops(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<SerialId>>,
+    ) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "priority_ops/pending")
+            .query(pagination_query)
+            .send()
+            .await
+    }
+
+    pub async fn deposit_aggregates(&self, query: &DepositAggregatesQuery) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "priority_ops/deposits/aggregates")
+            .query(query)
+            .send()
+            .await
+    }
+}
@@ -0,0 +1,52 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{
+    session_key::{SessionKeyRegistration, SessionKeyRevocation},
+    Response,
+};
+use zksync_types::Address;
+
+impl Client {
+    /// Registers the session key signed by the account owner. The registration
+    /// with the later timestamp replaces the previous one.
+    pub async fn register_session_key(
+        &self,
+        registration: &SessionKeyRegistration,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "session_keys")
+            .body(registration)
+            .send()
+            .await
+    }
+
+    pub async fn revoke_session_key(&self, revocation: &SessionKeyRevocation) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "session_keys/revoke")
+            .body(revocation)
+            .send()
+            .await
+    }
+
+    pub async fn account_session_keys(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("session_keys/accounts/{}", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn session_key(
+        &self,
+        account_id_or_address: &str,
+        session_address: Address,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!(
+                "session_keys/accounts/{}/{:?}",
+                account_id_or_address, session_address
+            ),
+        )
+        .send()
+        .await
+    }
+}
@@ -0,0 +1,66 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{
+    pagination::{ApiEither, PaginationQuery},
+    standing_order::{IncomingStandingOrder, IncomingStandingOrderCancellation},
+    Response,
+};
+use zksync_types::tx::TxSignature;
+
+impl Client {
+    /// Registers the standing order, so its pre-signed transfers are submitted
+    /// by the server at the scheduled times.
+    pub async fn register_standing_order(&self, order: &IncomingStandingOrder) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "standing_orders")
+            .body(order)
+            .send()
+            .await
+    }
+
+    /// Cancels the active standing order. The cancellation is signed over
+    /// `IncomingStandingOrderCancellation::message`.
+    pub async fn cancel_standing_order(
+        &self,
+        standing_order_id: u64,
+        signature: TxSignature,
+    ) -> Result<Response> {
+        self.post_with_scope(
+            super::API_V02_SCOPE,
+            &format!("standing_orders/{}/cancel", standing_order_id),
+        )
+        .body(&IncomingStandingOrderCancellation { signature })
+        .send()
+        .await
+    }
+
+    pub async fn standing_order(&self, standing_order_id: u64) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("standing_orders/{}", standing_order_id),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn standing_order_payments(&self, standing_order_id: u64) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("standing_orders/{}/payments", standing_order_id),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_standing_orders(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<u64>>,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("standing_orders/accounts/{}", account_id_or_address),
+        )
+        .query(pagination_query)
+        .send()
+        .await
+    }
+}
@@ -0,0 +1,25 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::statement::{StatementFormat, StatementQuery};
+use zksync_types::Address;
+
+impl Client {
+    /// Returns the monthly statement document of the account. Errors are returned
+    /// as the usual API responses.
+    pub async fn account_statement(
+        &self,
+        address: Address,
+        year: i32,
+        month: u32,
+        format: StatementFormat,
+    ) -> Result<String> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("statements/{:?}/{}/{}", address, year, month),
+        )
+        .query(&StatementQuery {
+            format: Some(format),
+        })
+        .send_raw()
+        .await
+    }
+}
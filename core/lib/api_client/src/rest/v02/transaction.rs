@@ -1,18 +1,75 @@
 use crate::rest::client::{Client, Result};
 use zksync_api_types::{
-    v02::{transaction::IncomingTxBatch, Response},
+    v02::{label::LABELS_API_KEY_HEADER, transaction::IncomingTxBatch, Response},
     TxWithSignature,
 };
-use zksync_types::tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx};
+use zksync_types::{
+    tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx},
+    H256,
+};
 
 impl Client {
     pub async fn submit_tx(
         &self,
         tx: ZkSyncTx,
         signature: TxEthSignatureVariant,
+    ) -> Result<Response> {
+        self.submit_tx_with_memo(tx, signature, None).await
+    }
+
+    /// Submits the transfer with the payment reference attached, e.g. the invoice number.
+    pub async fn submit_tx_with_memo(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        memo: Option<String>,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions")
+            .body(&TxWithSignature {
+                tx,
+                signature,
+                memo,
+                l1_calldata: None,
+            })
+            .send()
+            .await
+    }
+
+    /// Submits the withdrawal with the calldata of the call made to the recipient contract
+    /// once the withdrawal is executed on L1.
+    pub async fn submit_withdraw_with_l1_calldata(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        l1_calldata: Vec<u8>,
     ) -> Result<Response> {
         self.post_with_scope(super::API_V02_SCOPE, "transactions")
-            .body(&TxWithSignature { tx, signature })
+            .body(&TxWithSignature {
+                tx,
+                signature,
+                memo: None,
+                l1_calldata: Some(l1_calldata),
+            })
+            .send()
+            .await
+    }
+
+    /// Submits the transaction with the API key, so its status changes are delivered
+    /// to the callback endpoint of the key scope.
+    pub async fn submit_tx_with_api_key(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        api_key: &str,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions")
+            .header(LABELS_API_KEY_HEADER, api_key)
+            .body(&TxWithSignature {
+                tx,
+                signature,
+                memo: None,
+                l1_calldata: None,
+            })
             .send()
             .await
     }
@@ -46,6 +103,15 @@ impl Client {
         .await
     }
 
+    pub async fn tx_inclusion_proof(&self, tx_hash: TxHash) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}/inclusionProof", tx_hash.to_string()),
+        )
+        .send()
+        .await
+    }
+
     pub async fn get_batch(&self, batch_hash: TxHash) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
@@ -54,4 +120,13 @@ impl Client {
         .send()
         .await
     }
+
+    pub async fn tx_by_l1_hash(&self, eth_hash: H256) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/byL1Hash/{:?}", eth_hash),
+        )
+        .send()
+        .await
+    }
 }
@@ -0,0 +1,32 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{
+    label::LABELS_API_KEY_HEADER, tx_callback::TxCallbackEndpointRequest, Response,
+};
+
+impl Client {
+    pub async fn tx_callback_endpoint(&self, api_key: &str) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "tx_callbacks")
+            .header(LABELS_API_KEY_HEADER, api_key)
+            .send()
+            .await
+    }
+
+    pub async fn set_tx_callback_endpoint(
+        &self,
+        request: &TxCallbackEndpointRequest,
+        api_key: &str,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "tx_callbacks")
+            .header(LABELS_API_KEY_HEADER, api_key)
+            .body(request)
+            .send()
+            .await
+    }
+
+    pub async fn remove_tx_callback_endpoint(&self, api_key: &str) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "tx_callbacks/remove")
+            .header(LABELS_API_KEY_HEADER, api_key)
+            .send()
+            .await
+    }
+}
@@ -5,7 +5,10 @@ use zksync_types::{
     tx::{TxEthSignatureVariant, TxHash},
     ZkSyncTx, H256,
 };
+use zksync_utils::{OptionBytesToHexSerde, ZeroxPrefix};
 
+pub mod private;
+pub mod tx_status;
 pub mod v02;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +17,14 @@ pub struct TxWithSignature {
     pub tx: ZkSyncTx,
     #[serde(default)]
     pub signature: TxEthSignatureVariant,
+    /// Payment reference attached to the transfer, e.g. the invoice number.
+    /// It's stored alongside the transaction and isn't a part of the signed data.
+    #[serde(default)]
+    pub memo: Option<String>,
+    /// Calldata of the call made to the recipient contract once the withdrawal is executed on L1.
+    /// It's stored alongside the transaction, its hash is set in the withdrawal and signed.
+    #[serde(default, with = "OptionBytesToHexSerde::<ZeroxPrefix>")]
+    pub l1_calldata: Option<Vec<u8>>,
 }
 
 /// Combined identifier of the priority operations for the lookup.
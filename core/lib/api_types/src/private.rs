@@ -0,0 +1,745 @@
+//! Types used by the zkSync core private API.
+//! This API is expected to be used by the other components of zkSync stack
+//! and the operator tooling only.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use num::{rational::Ratio, BigUint};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use zksync_types::{
+    event::outbox::OutboxEventType, tx::TxHash, AccountId, Address, BlockNumber, TokenId,
+    TokenLike, H256,
+};
+use zksync_utils::{BigUintSerdeAsRadix10Str, UnsignedRatioSerializeAsDecimal};
+
+use crate::v02::label::AddressLabelCategory;
+
+/// Header identifying the operator who performs an administrative action.
+/// Required by all the private API endpoints changing the server state.
+pub const ACTOR_HEADER: &str = "X-Zksync-Actor";
+
+/// Prover job which is not completed yet.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PendingProverJob {
+    pub id: i32,
+    pub job_type: String,
+    pub job_status: i32,
+    /// Jobs with the lower value are served to the provers first.
+    pub job_priority: i32,
+    pub first_block: BlockNumber,
+    pub last_block: BlockNumber,
+    /// Name of the prover that works on the job or the name of the last server routine that updated it.
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to mark the block as the high priority one for the proving.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PriorityBlockRequest {
+    pub block_number: BlockNumber,
+}
+
+/// Request to change the priority of an idle prover job.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProverJobPriorityRequest {
+    pub job_priority: i32,
+}
+
+/// Request to return all the jobs of the (possibly stalled) prover back to the queue.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReassignProverJobsRequest {
+    pub prover_name: String,
+}
+
+/// Response for the prover jobs reassignment request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReassignProverJobsResponse {
+    pub reassigned_jobs: u64,
+}
+
+/// Webhook receiving the events from the event outbox. The secret is never returned.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebhookSubscription {
+    pub id: i64,
+    pub url: String,
+    /// Empty list means that events of all types are delivered.
+    pub event_types: Vec<OutboxEventType>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to register a new webhook.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AddWebhookSubscriptionRequest {
+    pub url: String,
+    /// Key used to sign the request bodies with HMAC-SHA256.
+    pub secret: String,
+    /// Types of the events to deliver, all the events are delivered if omitted.
+    #[serde(default)]
+    pub event_types: Vec<OutboxEventType>,
+}
+
+/// Response for the webhook registration request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AddWebhookSubscriptionResponse {
+    pub id: i64,
+}
+
+/// Event delivery that ran out of attempts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeadWebhookDelivery {
+    pub id: i64,
+    pub event_id: i64,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Response for the request to retry the dead deliveries of the webhook.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RetryWebhookDeliveriesResponse {
+    pub requeued_deliveries: u64,
+}
+
+/// Enabled maintenance mode of the server. Transactions are not accepted while it's enabled.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MaintenanceMode {
+    /// Reason of the maintenance, returned to the users submitting the transactions.
+    pub reason: String,
+    pub enabled_at: DateTime<Utc>,
+}
+
+/// Request to enable the maintenance mode.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EnableMaintenanceModeRequest {
+    pub reason: String,
+}
+
+/// Request to add the label visible to everyone, e.g. of the exchange hot wallet.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AddAddressLabelRequest {
+    pub address: Address,
+    pub label: String,
+    /// Category of the label, user-defined labels can't be added by the operator.
+    pub category: AddressLabelCategory,
+}
+
+/// Request to remove the label visible to everyone.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RemoveAddressLabelRequest {
+    pub address: Address,
+    pub label: String,
+}
+
+/// Request to issue the API key allowing to manage the user-defined labels of the scope.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LabelApiKeyRequest {
+    pub scope: String,
+}
+
+/// Issued API key. Only the hash of the key is stored, so it can't be obtained again.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IssueLabelApiKeyResponse {
+    pub api_key: String,
+}
+
+/// Response for the request to revoke the API keys of the scope.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RevokeLabelApiKeysResponse {
+    pub revoked_keys: u64,
+}
+
+/// Request to override the maximum page size of the paginated endpoints for the API keys
+/// of the scope. `None` restores the server limits.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiKeysMaxPageSizeRequest {
+    pub scope: String,
+    pub max_page_size: Option<u32>,
+}
+
+/// Response for the request to override the maximum page size of the API keys.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiKeysMaxPageSizeResponse {
+    pub updated_keys: u64,
+}
+
+/// Request to toggle the execution traces of the blocks sealed by the state keeper.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExecutionTracingRequest {
+    pub enabled: bool,
+}
+
+/// Whether the state keeper of the core server instance emits the execution traces of the blocks.
+/// The switch is not shared between the instances.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExecutionTracingStatus {
+    pub instance_id: String,
+    pub enabled: bool,
+}
+
+/// Request to deny the transactions from and to the address, e.g. the sanctioned one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AddToDenylistRequest {
+    pub address: Address,
+    /// Reason returned to the users of the denied address.
+    pub reason: String,
+}
+
+/// Request to allow the transactions from and to the denied address again.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RemoveFromDenylistRequest {
+    pub address: Address,
+}
+
+/// Administrative action performed via the private API.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    MarkBlockAsHighPriority,
+    SetProverJobPriority,
+    ReassignProverJobs,
+    AddWebhookSubscription,
+    RemoveWebhookSubscription,
+    RetryDeadWebhookDeliveries,
+    EnableMaintenanceMode,
+    DisableMaintenanceMode,
+    AddAddressLabel,
+    RemoveAddressLabel,
+    IssueLabelApiKey,
+    RevokeLabelApiKeys,
+    SetApiKeysMaxPageSize,
+    AddToDenylist,
+    RemoveFromDenylist,
+    SetExecutionTracing,
+    SetRuntimeParam,
+    RemoveRuntimeParam,
+    SetTokenPriceOverride,
+    RemoveTokenPriceOverride,
+    AddCreate2Factory,
+    RemoveCreate2Factory,
+}
+
+impl ToString for AuditAction {
+    fn to_string(&self) -> String {
+        match self {
+            AuditAction::MarkBlockAsHighPriority => String::from("mark_block_as_high_priority"),
+            AuditAction::SetProverJobPriority => String::from("set_prover_job_priority"),
+            AuditAction::ReassignProverJobs => String::from("reassign_prover_jobs"),
+            AuditAction::AddWebhookSubscription => String::from("add_webhook_subscription"),
+            AuditAction::RemoveWebhookSubscription => String::from("remove_webhook_subscription"),
+            AuditAction::RetryDeadWebhookDeliveries => {
+                String::from("retry_dead_webhook_deliveries")
+            }
+            AuditAction::EnableMaintenanceMode => String::from("enable_maintenance_mode"),
+            AuditAction::DisableMaintenanceMode => String::from("disable_maintenance_mode"),
+            AuditAction::AddAddressLabel => String::from("add_address_label"),
+            AuditAction::RemoveAddressLabel => String::from("remove_address_label"),
+            AuditAction::IssueLabelApiKey => String::from("issue_label_api_key"),
+            AuditAction::RevokeLabelApiKeys => String::from("revoke_label_api_keys"),
+            AuditAction::SetApiKeysMaxPageSize => String::from("set_api_keys_max_page_size"),
+            AuditAction::AddToDenylist => String::from("add_to_denylist"),
+            AuditAction::RemoveFromDenylist => String::from("remove_from_denylist"),
+            AuditAction::SetExecutionTracing => String::from("set_execution_tracing"),
+            AuditAction::SetRuntimeParam => String::from("set_runtime_param"),
+            AuditAction::RemoveRuntimeParam => String::from("remove_runtime_param"),
+            AuditAction::SetTokenPriceOverride => String::from("set_token_price_override"),
+            AuditAction::RemoveTokenPriceOverride => String::from("remove_token_price_override"),
+            AuditAction::AddCreate2Factory => String::from("add_create2_factory"),
+            AuditAction::RemoveCreate2Factory => String::from("remove_create2_factory"),
+        }
+    }
+}
+
+/// Entry of the audit log of the administrative actions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub id: i64,
+    /// Operator who performed the action, as stated in the `X-Zksync-Actor` header.
+    pub actor: String,
+    /// Name of the action, stored as a string so the entries of the removed actions can be read.
+    pub action: String,
+    /// Parameters of the action request.
+    pub parameters: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query for the audit log entries, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuditLogQuery {
+    /// Only the entries stored before the entry with this id are returned.
+    pub before: Option<i64>,
+    pub limit: u32,
+}
+
+/// Query for the on-demand check of the stored data invariants.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConsistencyCheckQuery {
+    /// Blocks starting from this one up to the last verified block are checked.
+    pub from_block: BlockNumber,
+    /// Maximum amount of the returned violations of each invariant.
+    pub limit: u32,
+}
+
+/// Violation of the stored data invariant.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConsistencyViolation {
+    /// Name of the violated invariant, e.g. `tx_filters`.
+    pub invariant: String,
+    pub block_number: BlockNumber,
+    pub description: String,
+}
+
+/// Result of the stored data invariants check.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    pub from_block: BlockNumber,
+    pub last_checked_block: BlockNumber,
+    pub violations: Vec<ConsistencyViolation>,
+}
+
+/// Progress of the batched data migration running online.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackfillProgress {
+    pub name: String,
+    /// Position after the last processed batch in the backfill-specific format.
+    pub cursor: Option<String>,
+    pub processed_rows: u64,
+    /// Estimation of the total amount of rows to process, if it's known.
+    pub estimated_rows: Option<u64>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// `None` if the backfill is still running or was interrupted.
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Health of the periodic background job run by the scheduler.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScheduledJobStatus {
+    pub name: String,
+    pub interval_secs: u64,
+    pub last_started_at: Option<DateTime<Utc>>,
+    /// `None` if the first run of the job is not finished yet.
+    pub last_finished_at: Option<DateTime<Utc>>,
+    /// Error of the last run, `None` if it succeeded.
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+    pub runs_count: u64,
+    /// Whether the last run succeeded and the job was started within two intervals.
+    pub healthy: bool,
+}
+
+/// Role of the core server instance in the leader election.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LeadershipRole {
+    /// The instance waits to take over the state keeper and the Ethereum sender.
+    Standby,
+    Leader,
+}
+
+/// Core server instance which became the leader.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LeadershipChange {
+    pub instance_id: String,
+    pub acquired_at: DateTime<Utc>,
+}
+
+/// Leadership of the core server instance serving the request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LeadershipStatus {
+    pub instance_id: String,
+    pub role: LeadershipRole,
+    /// Time when the instance took the current role.
+    pub role_since: DateTime<Utc>,
+    /// Recent leadership changes across all the instances, newest first.
+    pub changes: Vec<LeadershipChange>,
+}
+
+/// `ChangePubKey` transaction with the fee paid by the operator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CpkSponsorship {
+    pub id: i64,
+    pub account_id: AccountId,
+    pub tx_hash: TxHash,
+    pub fee_token: TokenId,
+    /// Part of the required fee which is not paid by the account.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub waived_fee: BigUint,
+    /// Value of the waived fee in USD, scaled by 10^6.
+    pub waived_fee_usd_scaled: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query for the sponsored `ChangePubKey` transactions, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CpkSponsorshipsQuery {
+    /// Only the sponsorships stored before the one with this id are returned.
+    pub before: Option<i64>,
+    pub limit: u32,
+}
+
+/// Sponsored `ChangePubKey` transactions along with the total spendings of the operator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CpkSponsorshipsResponse {
+    /// Total value of the sponsored fees in USD, scaled by 10^6.
+    pub total_sponsored_usd_scaled: u64,
+    pub sponsorships: Vec<CpkSponsorship>,
+}
+
+/// Transfer of the operator address with the fee waived.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeeFreeTransfer {
+    pub id: i64,
+    pub address: Address,
+    pub tx_hash: TxHash,
+    pub fee_token: TokenId,
+    /// Part of the required fee which is not paid by the operator address.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub waived_fee: BigUint,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query for the fee-free transfers of the operator addresses, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeeFreeTransfersQuery {
+    /// Only the transfers of this address are returned if it's set.
+    pub address: Option<Address>,
+    /// Only the transfers stored before the one with this id are returned.
+    pub before: Option<i64>,
+    pub limit: u32,
+}
+
+/// Fee-free transfers along with the quota used by the queried address.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeeFreeTransfersResponse {
+    /// Amount of the fee-free transfers of the queried address for the current day (in UTC).
+    pub quota_used: Option<u64>,
+    pub transfers: Vec<FeeFreeTransfer>,
+}
+
+/// Address denied by the operator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DenylistEntry {
+    pub address: Address,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query for the transactions rejected by the screening, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScreeningMatchesQuery {
+    /// Only the matches of this address are returned if it's set.
+    pub address: Option<Address>,
+    /// Only the matches stored before the one with this id are returned.
+    pub before: Option<i64>,
+    pub limit: u32,
+}
+
+/// Address of the submitted transaction matched by the screening.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScreeningMatch {
+    pub id: i64,
+    pub tx_hash: TxHash,
+    pub address: Address,
+    /// Role of the address in the transaction, either `sender` or `recipient`.
+    pub role: String,
+    /// Source of the match, either `denylist` or `screening_api`.
+    pub source: String,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query for the transactions dropped without being included into a block, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DroppedTxsQuery {
+    /// Only the transactions initiated by this address are returned if it's set.
+    pub address: Option<Address>,
+    pub tx_hash: Option<TxHash>,
+    /// Only the transactions dropped before the one with this id are returned.
+    pub before: Option<i64>,
+    pub limit: u32,
+}
+
+/// Transaction dropped without being included into a block.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DroppedTx {
+    pub id: i64,
+    pub tx_hash: TxHash,
+    /// Address of the account which initiated the transaction.
+    pub address: Address,
+    /// Either `rejected` if the transaction failed on the execution,
+    /// or `expired` if it was evicted from the mempool after its `valid_until` had passed.
+    pub kind: String,
+    pub reason: Option<String>,
+    pub tx: Value,
+    pub created_at: DateTime<Utc>,
+    pub dropped_at: DateTime<Utc>,
+}
+
+/// Query for the priority operations which are not executed yet, in the order of their deadlines.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PriorityOpDeadlinesQuery {
+    pub limit: u32,
+}
+
+/// Priority operation which is not executed yet along with the time left until its deadline.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PriorityOpDeadline {
+    pub serial_id: u64,
+    pub tx_hash: TxHash,
+    pub op_type: String,
+    /// Whether the operation has received enough confirmations to be included into a block.
+    pub confirmed: bool,
+    pub eth_block: u64,
+    /// L1 block until which the operation must be processed.
+    pub deadline_block: u64,
+    pub remaining_blocks: u64,
+    /// Estimation of the time left until the deadline, in seconds.
+    pub remaining_secs: u64,
+    /// Whether the block containing the operation is sealed right away.
+    pub escalated: bool,
+}
+
+/// Deadlines of the priority operations as of the last L1 block.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PriorityOpDeadlines {
+    pub last_eth_block: u64,
+    pub escalation_blocks: u64,
+    pub ops: Vec<PriorityOpDeadline>,
+}
+
+/// Reload-safe parameter overridden at runtime.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuntimeParam {
+    pub key: String,
+    pub value: Value,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to override the reload-safe parameter, applied by the components within a few seconds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SetRuntimeParamRequest {
+    pub key: String,
+    pub value: Value,
+}
+
+/// Request to restore the value of the parameter from the environment config.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RemoveRuntimeParamRequest {
+    pub key: String,
+}
+
+/// Query for the changes of the runtime parameters, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuntimeParamChangesQuery {
+    /// Only the changes recorded before the one with this id are returned.
+    pub before: Option<i64>,
+    pub limit: u32,
+}
+
+/// Change of the runtime parameter.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuntimeParamChange {
+    pub id: i64,
+    pub key: String,
+    pub old_value: Option<Value>,
+    /// `None` if the override is removed.
+    pub new_value: Option<Value>,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Manual token price used by the fee ticker instead of the fetched one until it expires.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TokenPriceOverride {
+    pub token_id: TokenId,
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub usd_price: Ratio<BigUint>,
+    pub reason: String,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Expired overrides are ignored by the fee ticker and kept until removed.
+    pub expired: bool,
+}
+
+/// Request to set the manual token price, replacing the previous one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SetTokenPriceOverrideRequest {
+    pub token: TokenLike,
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub usd_price: Ratio<BigUint>,
+    pub reason: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Request to remove the manual token price, so the fetched price is used again.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RemoveTokenPriceOverrideRequest {
+    pub token: TokenLike,
+}
+
+/// Registered factory and code hash pair of the CREATE2 smart wallets.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Create2FactoryEntry {
+    pub creator_address: Address,
+    pub code_hash: H256,
+    pub name: String,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to register the factory and code hash pair of the CREATE2 smart wallets,
+/// or to rename the registered one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AddCreate2FactoryRequest {
+    pub creator_address: Address,
+    pub code_hash: H256,
+    /// Name of the wallet vendor.
+    pub name: String,
+}
+
+/// Request to remove the factory and code hash pair, so the `ChangePubKey` transactions
+/// of its wallets are rejected.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RemoveCreate2FactoryRequest {
+    pub creator_address: Address,
+    pub code_hash: H256,
+}
+
+/// Query for the operator revenue report. Both days (in UTC) are inclusive.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RevenueQuery {
+    pub from_day: NaiveDate,
+    pub to_day: NaiveDate,
+}
+
+/// Fees charged in the token within the day.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DailyTokenFees {
+    pub day: NaiveDate,
+    pub token_id: TokenId,
+    pub transactions_count: u64,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub charged_fee: BigUint,
+    /// Part of the charged fee covering the proof generation.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub zkp_fee: BigUint,
+    /// Part of the charged fee covering the L1 gas.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub gas_fee: BigUint,
+    /// Part of the quoted fee paid by the operator.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub subsidy: BigUint,
+}
+
+/// L1 gas spent by the operator transactions confirmed within the day.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DailyL1GasSpent {
+    pub day: NaiveDate,
+    pub transactions_count: u64,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub gas_used: BigUint,
+    /// Total cost of the gas, in wei.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub gas_cost: BigUint,
+}
+
+/// Fees earned by the operator versus the L1 costs, per day.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RevenueReport {
+    pub fees: Vec<DailyTokenFees>,
+    pub l1_gas: Vec<DailyL1GasSpent>,
+}
+
+/// Upper bounds (in milliseconds) of the latency buckets of the REST API route statistics.
+/// Requests served longer than the last bound are counted in the additional last bucket.
+pub const ROUTE_LATENCY_BUCKETS_MS: [u64; 12] =
+    [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000, 30000];
+
+/// Returns the latency (in milliseconds) the given percentage of the requests were served within,
+/// estimated as the upper bound of the bucket of the corresponding request.
+/// Requests of the last bucket are assumed to be served within the last bound.
+pub fn latency_percentile(buckets: &[u64], percentage: f64) -> u64 {
+    let total: u64 = buckets.iter().sum();
+    let rank = (total as f64 * percentage / 100.0).ceil() as u64;
+    let mut served = 0;
+    for (bucket, requests) in buckets.iter().enumerate() {
+        served += requests;
+        if served >= rank.max(1) {
+            return ROUTE_LATENCY_BUCKETS_MS[bucket.min(ROUTE_LATENCY_BUCKETS_MS.len() - 1)];
+        }
+    }
+    0
+}
+
+/// Query for the REST API route statistics of the last hours.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiRouteStatsQuery {
+    pub hours: u32,
+}
+
+/// Time spent by the storage method in the requests of the route.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RouteQueryTime {
+    /// Storage method in the `schema::method` form.
+    pub method: String,
+    pub duration_ms: u64,
+    /// Part of the total storage time of the route. Methods called by other methods
+    /// are counted by both of them, so the shares may add up to more than 1.
+    pub share: f64,
+}
+
+/// Statistics of the REST API route across all the API servers.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiRouteStats {
+    /// Pattern of the route, e.g. `/api/v0.2/blocks/{block_position}`.
+    pub route: String,
+    pub requests: u64,
+    /// Requests failed because of the server, e.g. the storage errors.
+    pub errors: u64,
+    /// Requests served successfully, but slower than the latency threshold of the route.
+    pub slow_requests: u64,
+    pub error_rate: f64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub p99_ms: u64,
+    /// Average storage time per request.
+    pub storage_ms_per_request: f64,
+    pub latency_threshold_ms: u64,
+    /// Percentage of the requests expected to be served successfully within the latency threshold.
+    pub slo_target: f64,
+    /// Percentage of the requests actually served successfully within the latency threshold.
+    pub slo_attainment: f64,
+    /// Part of the error budget spent over the period, above 1 if the objective is missed.
+    pub error_budget_spent: f64,
+    /// Storage methods taking the most time, the slowest first.
+    pub top_queries: Vec<RouteQueryTime>,
+}
+
+/// Statistics of the REST API routes, the most requested ones first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiRouteStatsReport {
+    pub since: DateTime<Utc>,
+    pub routes: Vec<ApiRouteStats>,
+}
+
+/// Read-only SQL report defined by the operator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportInfo {
+    pub name: String,
+    pub description: String,
+    /// Names of the query parameters, bound to the query in order as `$1`, `$2`, etc.
+    pub parameters: Vec<String>,
+}
+
+/// Rows returned by the report.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportResult {
+    pub name: String,
+    /// Names of the columns, empty if there are no rows.
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// Whether the report returned more rows than allowed, the rest of them are omitted.
+    pub truncated: bool,
+}
@@ -0,0 +1,75 @@
+//! Status of the transactions and priority operations shared by all the API versions.
+//!
+//! The status is derived once by the storage, API v0.1 and JSON RPC represent it with
+//! the `committed`/`verified`/`success` flags, and API v0.2 with the [`TxInBlockStatus`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::v02::transaction::TxInBlockStatus;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum TxStatus {
+    /// Not included to a block yet.
+    Queued,
+    /// Executed in a block which is not finalized yet.
+    Committed,
+    /// Executed in a finalized block.
+    Finalized,
+    /// Failed during the execution, it still belongs to the block it was included to.
+    Rejected { block_finalized: bool },
+    /// Finalized, but its data was moved out of the database by the pruning policy.
+    Pruned,
+    /// Evicted from the mempool since its `valid_until` had passed, it will never be executed.
+    Expired,
+}
+
+impl TxStatus {
+    /// Returns the status of the operation included to a block.
+    pub fn executed(success: bool, block_finalized: bool) -> Self {
+        match (success, block_finalized) {
+            (true, true) => Self::Finalized,
+            (true, false) => Self::Committed,
+            (false, _) => Self::Rejected { block_finalized },
+        }
+    }
+
+    /// `committed` flag of the legacy APIs, i.e. the operation is included to a block.
+    pub fn is_committed(self) -> bool {
+        !matches!(self, Self::Queued | Self::Expired)
+    }
+
+    /// `verified` flag of the legacy APIs, i.e. the block of the operation is finalized.
+    pub fn is_verified(self) -> bool {
+        matches!(
+            self,
+            Self::Finalized
+                | Self::Pruned
+                | Self::Rejected {
+                    block_finalized: true
+                }
+        )
+    }
+
+    /// `success` flag of the legacy APIs, `None` until the operation is executed.
+    pub fn success(self) -> Option<bool> {
+        match self {
+            Self::Queued => None,
+            Self::Rejected { .. } | Self::Expired => Some(false),
+            Self::Committed | Self::Finalized | Self::Pruned => Some(true),
+        }
+    }
+}
+
+impl From<TxStatus> for TxInBlockStatus {
+    fn from(status: TxStatus) -> Self {
+        match status {
+            TxStatus::Queued => Self::Queued,
+            TxStatus::Committed => Self::Committed,
+            TxStatus::Finalized => Self::Finalized,
+            TxStatus::Rejected { .. } => Self::Rejected,
+            TxStatus::Pruned => Self::Pruned,
+            TxStatus::Expired => Self::Expired,
+        }
+    }
+}
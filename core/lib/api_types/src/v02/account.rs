@@ -8,6 +8,7 @@ use zksync_types::{
 };
 use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
 
+use super::label::AddressLabel;
 use super::pagination::PaginationDirection;
 use super::token::NFT;
 
@@ -17,6 +18,9 @@ pub struct AccountState {
     pub depositing: DepositingAccountBalances,
     pub committed: Option<Account>,
     pub finalized: Option<Account>,
+    /// Labels of the account address visible to the caller.
+    #[serde(default)]
+    pub labels: Vec<AddressLabel>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -114,4 +118,66 @@ pub struct IncomingAccountTxsQuery {
     pub direction: PaginationDirection,
     pub token: Option<String>,
     pub second_account: Option<String>,
+    /// Prepend the priority operations observed on L1 but not executed yet to the first page.
+    pub include_pending: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountTxsByMemoQuery {
+    pub memo: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNonceQuery {
+    pub block_number: BlockNumber,
+}
+
+/// Nonce of the account after the given block, reconstructed from its executed transactions.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNonceAt {
+    pub address: Address,
+    pub block_number: BlockNumber,
+    pub nonce: Nonce,
+}
+
+/// Accounts to be resolved by `POST /accounts/resolve`, ids and addresses can be mixed.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsResolveRequest {
+    pub accounts: Vec<AccountAddressOrId>,
+}
+
+/// Addresses checked by `POST /accounts/activity`, e.g. the ones derived by the HD wallet.
+/// The response contains the flags in the order of the request, `true` for the addresses
+/// affected by any executed transaction or priority operation.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsActivityRequest {
+    pub addresses: Vec<Address>,
+}
+
+/// Account which has ever set the public key hash, returned by
+/// `GET /accounts/pub_key_hash/{pubKeyHash}`. The accounts using the hash are listed first.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct PubKeyHashAccount {
+    pub account_id: AccountId,
+    pub address: Address,
+    /// Whether the account uses the public key hash in the committed state.
+    pub is_current: bool,
+    /// Committed blocks the public key hash was set in for the first and the last time.
+    pub first_block: BlockNumber,
+    pub last_block: BlockNumber,
+}
+
+/// Existing account resolved by `POST /accounts/resolve`. The response contains
+/// the accounts in the order of the request, `null` for the ones which don't exist.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedAccount {
+    pub account_id: AccountId,
+    pub address: Address,
 }
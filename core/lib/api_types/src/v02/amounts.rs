@@ -0,0 +1,96 @@
+//! Human-readable amounts of the transactions.
+//!
+//! Clients may pass the `formatAmounts=true` query parameter to the account history and
+//! the transaction data endpoints to receive the `formattedAmounts` object next to the raw
+//! integer amounts of every transaction. It contains the amounts formatted with the decimals
+//! of their tokens, so thin clients don't need to look up the tokens to display the history.
+
+use std::collections::HashMap;
+
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_types::{Token, TokenId};
+use zksync_utils::format_units;
+
+use super::transaction::{L1Transaction, L2Transaction, TransactionData};
+
+/// Amount formatted with the decimals of its token.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FormattedAmount {
+    pub symbol: String,
+    /// Amount with the decimal point, e.g. `1.5`.
+    pub value: String,
+}
+
+/// Formatted amounts of the transaction, the amounts in unknown tokens are omitted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FormattedAmounts {
+    /// Amount moved by the transaction. Not set for the swaps, since they move two amounts.
+    pub amount: Option<FormattedAmount>,
+    pub fee: Option<FormattedAmount>,
+}
+
+/// Raw amounts of the transaction with their tokens.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TxAmounts {
+    pub amount: Option<(TokenId, BigUint)>,
+    pub fee: Option<(TokenId, BigUint)>,
+}
+
+impl TxAmounts {
+    pub fn new(op: &TransactionData) -> Self {
+        let (amount, fee) = match op {
+            TransactionData::L1(L1Transaction::Deposit(deposit)) => {
+                (Some((deposit.token_id, deposit.amount.clone())), None)
+            }
+            TransactionData::L1(L1Transaction::FullExit(_)) => (None, None),
+            TransactionData::L2(tx) => match tx {
+                L2Transaction::Transfer(tx) => (
+                    Some((tx.token, tx.amount.clone())),
+                    Some((tx.token, tx.fee.clone())),
+                ),
+                L2Transaction::Withdraw(data) => (
+                    Some((data.tx.token, data.tx.amount.clone())),
+                    Some((data.tx.token, data.tx.fee.clone())),
+                ),
+                L2Transaction::Close(_) => (None, None),
+                L2Transaction::ChangePubKey(tx) => (None, Some((tx.fee_token, tx.fee.clone()))),
+                L2Transaction::ForcedExit(data) => {
+                    (None, Some((data.tx.token, data.tx.fee.clone())))
+                }
+                L2Transaction::MintNFT(tx) => (None, Some((tx.fee_token, tx.fee.clone()))),
+                L2Transaction::Swap(tx) => (None, Some((tx.fee_token, tx.fee.clone()))),
+                L2Transaction::WithdrawNFT(data) => {
+                    (None, Some((data.tx.fee_token, data.tx.fee.clone())))
+                }
+            },
+        };
+        Self { amount, fee }
+    }
+
+    /// Returns the tokens of the amounts.
+    pub fn tokens(&self) -> impl Iterator<Item = TokenId> + '_ {
+        self.amount
+            .iter()
+            .chain(&self.fee)
+            .map(|(token_id, _)| *token_id)
+    }
+
+    /// Formats the amounts with the decimals of the given tokens.
+    pub fn format(&self, tokens: &HashMap<TokenId, Token>) -> FormattedAmounts {
+        let format = |amount: &Option<(TokenId, BigUint)>| {
+            let (token_id, amount) = amount.as_ref()?;
+            let token = tokens.get(token_id)?;
+            Some(FormattedAmount {
+                symbol: token.symbol.clone(),
+                value: format_units(amount, token.decimals),
+            })
+        };
+        FormattedAmounts {
+            amount: format(&self.amount),
+            fee: format(&self.fee),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use chrono::{serde::ts_milliseconds, DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use zksync_types::{tx::TxEthSignature, Address};
+
+/// Proof of the control of the address, signed by the address itself.
+///
+/// The `reference` is an opaque identifier the operator links with its own records, e.g.
+/// the KYC session. It should not contain any personal data.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressAttestation {
+    pub address: Address,
+    pub reference: Option<String>,
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    pub signature: TxEthSignature,
+}
+
+impl AddressAttestation {
+    pub fn get_ethereum_sign_message(&self) -> String {
+        format!(
+            "By signing this message, you are confirming the control of this address to zkSync.\n\
+            Address: {:?}\n\
+            Reference: {}\n\
+            Timestamp: {}",
+            self.address,
+            self.reference.as_deref().unwrap_or(""),
+            self.timestamp.timestamp_millis()
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationInfo {
+    pub address: Address,
+    pub reference: Option<String>,
+    pub signed_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
@@ -1,7 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use zksync_crypto::{serialization::FrSerde, Fr};
-use zksync_types::{BlockNumber, H256};
+use zksync_types::{gas_prediction::BlockGasPrediction, tx::TxHash, BlockNumber, H256};
+use zksync_utils::{BytesToHexSerde, ZeroxPrefix};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
@@ -23,3 +24,72 @@ pub struct BlockInfo {
     pub finalized_at: Option<DateTime<Utc>>,
     pub status: BlockStatus,
 }
+
+/// Block which is being formed by the state keeper.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingBlockInfo {
+    pub block_number: BlockNumber,
+    pub chunks_left: u64,
+    /// Amount of the successfully executed operations.
+    pub operations: u64,
+    pub failed_txs: u64,
+    /// Predicted L1 gas of the block if it's sealed with its current operations.
+    pub gas_prediction: BlockGasPrediction,
+}
+
+/// Stage of the block processing confirmed on L1.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum L1VerificationStatus {
+    Committed,
+    /// The proof of the block is accepted by the contract.
+    Proven,
+    Executed,
+}
+
+/// Range of the blocks covered by the same aggregated proof.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofReference {
+    pub first_block: BlockNumber,
+    pub last_block: BlockNumber,
+}
+
+/// Everything needed to check the block on L1 without the other endpoints.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockFinality {
+    pub block_number: BlockNumber,
+    pub commitment: H256,
+    pub commit_tx_hash: Option<H256>,
+    pub prove_tx_hash: Option<H256>,
+    pub execute_tx_hash: Option<H256>,
+    /// `None` until the aggregated proof with the block is generated.
+    pub proof: Option<ProofReference>,
+    /// `None` until the commit transaction is confirmed.
+    pub status: Option<L1VerificationStatus>,
+}
+
+/// Position of the operation in the block public data.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PubdataOperation {
+    pub tx_hash: TxHash,
+    pub block_index: Option<u32>,
+    pub op_type: String,
+    /// Offset of the operation public data, in bytes.
+    pub offset: u32,
+    pub size: u32,
+}
+
+/// Public data of the block exactly as it's passed to the commit transaction on L1.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockPubdata {
+    pub block_number: BlockNumber,
+    /// Includes the padding with the noop chunks up to the block size.
+    #[serde(with = "BytesToHexSerde::<ZeroxPrefix>")]
+    pub public_data: Vec<u8>,
+    pub operations: Vec<PubdataOperation>,
+}
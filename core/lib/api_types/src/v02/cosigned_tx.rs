@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use zksync_types::{
+    tx::{TxEthSignature, TxEthSignatureVariant, TxHash},
+    AccountId, Address, ZkSyncTx,
+};
+
+/// Co-signers which should approve the transaction before it's submitted.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoSigningPolicy {
+    pub cosigners: Vec<Address>,
+    /// Number of the co-signatures required to submit the transaction.
+    pub threshold: u32,
+}
+
+/// Transaction signed by the account owner, which is held by the server until `threshold`
+/// of the co-signers have signed its hash.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingCoSignedTx {
+    pub tx: ZkSyncTx,
+    #[serde(default)]
+    pub signature: TxEthSignatureVariant,
+    pub policy: CoSigningPolicy,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingCoSignature {
+    pub signature: TxEthSignature,
+}
+
+impl IncomingCoSignature {
+    /// Message the co-signers sign. The hash binds the co-signature to the exact transaction.
+    pub fn get_ethereum_sign_message(tx_hash: TxHash) -> String {
+        format!(
+            "By signing this message, you are approving the zkSync transaction.\n\
+            Transaction: {}",
+            tx_hash.to_string()
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CoSignedTxStatus {
+    /// Transaction awaits the co-signatures, or the retry of the failed submission.
+    Pending,
+    Submitted,
+    /// Transaction has expired before it was submitted.
+    Expired,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CoSignedTxInfo {
+    pub tx_hash: TxHash,
+    pub account_id: AccountId,
+    pub policy: CoSigningPolicy,
+    /// Co-signers who have signed the transaction.
+    pub cosigned_by: Vec<Address>,
+    pub status: CoSignedTxStatus,
+    /// Error of the last submission attempt.
+    pub error: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
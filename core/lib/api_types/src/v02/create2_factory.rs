@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use zksync_types::{Address, H256};
+
+/// Factory and code hash pair of the CREATE2 smart wallets.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Create2Factory {
+    pub creator_address: Address,
+    pub code_hash: H256,
+    /// Name of the wallet vendor.
+    pub name: String,
+}
+
+/// CREATE2 factories accepted in the `ChangePubKey` transactions.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Create2Factories {
+    /// Whether only the listed factories are accepted, any factory is accepted otherwise.
+    pub registry_enabled: bool,
+    pub factories: Vec<Create2Factory>,
+}
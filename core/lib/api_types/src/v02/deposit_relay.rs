@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_types::{tx::PackedEthSignature, Address, TokenId, H256};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+/// Status of the relayed deposit.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DepositRelayStatus {
+    /// Job is waiting to be taken by the relayer.
+    Pending,
+    /// Relayer transactions are being sent.
+    Processing,
+    /// Deposit to the owner is confirmed on L1.
+    Completed,
+    /// Permit was rejected or one of the relayer transactions has failed.
+    Failed,
+}
+
+impl DepositRelayStatus {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "processing" => Some(Self::Processing),
+            "completed" => Some(Self::Completed),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// Request to deposit `amount` of `token` on behalf of `owner`, who has signed the EIP-2612
+/// permit allowing the relayer account to spend `amount` until `deadline`.
+///
+/// Relayer deposits `amount` less the fee to the owner and the fee to its own account,
+/// so the owner doesn't need any ETH for the first deposit.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingDepositRelay {
+    pub owner: Address,
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    /// Maximum fee the owner agrees to pay, in the units of the token.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub max_fee: BigUint,
+    /// Deadline of the permit, in seconds since the epoch.
+    pub deadline: u64,
+    /// Signature of the permit.
+    pub signature: PackedEthSignature,
+}
+
+/// Current fee of the relayed deposit. The permit should be signed for the `relayer` address.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositRelayFee {
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub fee: BigUint,
+    pub relayer: Address,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositRelayJobInfo {
+    pub job_id: u64,
+    pub owner: Address,
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub fee: BigUint,
+    pub status: DepositRelayStatus,
+    /// Hash of the L1 deposit to the owner, once it's sent.
+    pub eth_tx_hash: Option<H256>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
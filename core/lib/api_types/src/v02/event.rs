@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use zksync_types::event::{EventId, VersionedEvent};
+
+/// Query of the stored events log.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct EventsQuery {
+    /// Only the events following this one are returned, `0` stands for
+    /// the earliest event retained in the log.
+    pub after: EventId,
+    pub limit: u32,
+}
+
+/// Events in the order of their commit. Events have the same layout as the ones
+/// streamed by the event listener.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EventsPage {
+    pub events: Vec<VersionedEvent>,
+    /// Value of the `after` parameter to request the next page with.
+    pub next_cursor: EventId,
+    /// Id of the earliest event retained in the log, the older ones were removed
+    /// by the retention policy.
+    pub first_event_id: Option<EventId>,
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use zksync_types::{exit_proof::ExitProofData, AccountId, TokenId};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ExitProofStatus {
+    Queued,
+    InProgress,
+    Ready,
+    Failed,
+}
+
+/// Exit proof request and its result. Proofs are generated in the background against
+/// the state of the last verified block.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitProofInfo {
+    pub account_id: AccountId,
+    pub token_id: TokenId,
+    pub status: ExitProofStatus,
+    pub requested_at: DateTime<Utc>,
+    /// Inputs of the exit transaction, present once the proof is ready.
+    pub data: Option<ExitProofData>,
+    /// Reason of the failed generation.
+    pub error: Option<String>,
+}
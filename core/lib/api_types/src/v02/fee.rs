@@ -77,6 +77,10 @@ pub struct TxFeeRequest {
     pub tx_type: ApiTxFeeTypes,
     pub address: Address,
     pub token_like: TokenLike,
+    /// Whether the withdrawal is submitted with the L1 calldata, the gas of the call
+    /// made to the recipient is included in the fee.
+    #[serde(default)]
+    pub withdrawal_hook: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -84,6 +88,9 @@ pub struct TxFeeRequest {
 pub struct TxInBatchFeeRequest {
     pub tx_type: ApiTxFeeTypes,
     pub address: Address,
+    /// Whether the withdrawal is submitted with the L1 calldata.
+    #[serde(default)]
+    pub withdrawal_hook: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
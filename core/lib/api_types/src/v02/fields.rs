@@ -0,0 +1,219 @@
+//! Response shaping of the endpoints returning the transactions.
+//!
+//! Clients may pass the `fields` query parameter with the comma-separated list of the transaction
+//! fields to be returned, e.g. `fields=txHash,status,createdAt` for the history page of a mobile
+//! wallet, which doesn't need the raw transaction bodies. The set of the fields is resolved before
+//! the response is built, so the omitted parts are neither loaded nor serialized. Being a query
+//! parameter rather than a header, it keeps the requests simple in terms of CORS, so the browsers
+//! don't send the preflight requests.
+
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+
+use super::{
+    amounts::FormattedAmounts,
+    pagination::Paginated,
+    transaction::{Transaction, TxData, TxHashSerializeWrapper},
+};
+
+/// Names of the fields which can be requested, in the order of the bits of `TxFields`.
+/// The last two are the fields of `TxData`, which are ignored for the plain transactions.
+pub const TX_FIELDS: [&str; 11] = [
+    "txHash",
+    "blockIndex",
+    "blockNumber",
+    "op",
+    "status",
+    "failReason",
+    "createdAt",
+    "batchId",
+    "memo",
+    "ethSignature",
+    "feeBreakdown",
+];
+
+/// Query of the endpoints returning the transactions.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldsQuery {
+    /// Comma-separated list of the fields to be returned, all the fields are returned if not set.
+    pub fields: Option<String>,
+    /// Whether the amounts formatted with the decimals of their tokens should be returned.
+    pub format_amounts: Option<bool>,
+}
+
+impl FieldsQuery {
+    pub fn format_amounts(&self) -> bool {
+        self.format_amounts.unwrap_or(false)
+    }
+}
+
+/// Set of the transaction fields to be included into the response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxFields(u16);
+
+impl TxFields {
+    pub const ALL: Self = Self((1 << TX_FIELDS.len()) - 1);
+
+    /// Parses the comma-separated list of the field names.
+    /// Returns the first unknown field name on error.
+    pub fn parse(fields: &str) -> Result<Self, String> {
+        let mut mask = 0;
+        for field in fields.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            let index = TX_FIELDS
+                .iter()
+                .position(|name| *name == field)
+                .ok_or_else(|| field.to_string())?;
+            mask |= 1 << index;
+        }
+        Ok(Self(mask))
+    }
+
+    pub fn contains(self, field: &str) -> bool {
+        TX_FIELDS
+            .iter()
+            .position(|name| *name == field)
+            .map_or(false, |index| self.0 & (1 << index) != 0)
+    }
+
+    pub fn is_all(self) -> bool {
+        self == Self::ALL
+    }
+}
+
+impl Default for TxFields {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// View of the transaction with the selected fields only.
+struct TxView<'a>(&'a Transaction, TxFields);
+
+impl Serialize for TxView<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (tx, fields) = (self.0, self.1);
+        if fields.is_all() {
+            return tx.serialize(serializer);
+        }
+
+        let mut map = serializer.serialize_map(None)?;
+        if fields.contains("txHash") {
+            map.serialize_entry("txHash", &TxHashSerializeWrapper(tx.tx_hash))?;
+        }
+        if fields.contains("blockIndex") {
+            map.serialize_entry("blockIndex", &tx.block_index)?;
+        }
+        if fields.contains("blockNumber") {
+            map.serialize_entry("blockNumber", &tx.block_number)?;
+        }
+        if fields.contains("op") {
+            map.serialize_entry("op", &tx.op)?;
+        }
+        if fields.contains("status") {
+            map.serialize_entry("status", &tx.status)?;
+        }
+        if fields.contains("failReason") {
+            map.serialize_entry("failReason", &tx.fail_reason)?;
+        }
+        if fields.contains("createdAt") {
+            map.serialize_entry("createdAt", &tx.created_at)?;
+        }
+        if fields.contains("batchId") {
+            map.serialize_entry("batchId", &tx.batch_id)?;
+        }
+        if fields.contains("memo") {
+            map.serialize_entry("memo", &tx.memo)?;
+        }
+        map.end()
+    }
+}
+
+/// View of the transaction decorated with the formatted amounts.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DecoratedTxView<'a> {
+    #[serde(flatten)]
+    tx: TxView<'a>,
+    formatted_amounts: &'a FormattedAmounts,
+}
+
+/// Transaction serialized with the requested fields only.
+#[derive(Debug)]
+pub struct ShapedTransaction {
+    pub tx: Transaction,
+    pub fields: TxFields,
+    /// Formatted amounts, only set if requested.
+    pub amounts: Option<FormattedAmounts>,
+}
+
+impl Serialize for ShapedTransaction {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let tx = TxView(&self.tx, self.fields);
+        match &self.amounts {
+            Some(formatted_amounts) => DecoratedTxView {
+                tx,
+                formatted_amounts,
+            }
+            .serialize(serializer),
+            None => tx.serialize(serializer),
+        }
+    }
+}
+
+/// Transaction data serialized with the requested fields only.
+#[derive(Debug)]
+pub struct ShapedTxData {
+    pub data: TxData,
+    pub fields: TxFields,
+    /// Formatted amounts of the transaction, only set if requested.
+    pub amounts: Option<FormattedAmounts>,
+}
+
+impl Serialize for ShapedTxData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.fields.is_all() && self.amounts.is_none() {
+            return self.data.serialize(serializer);
+        }
+
+        let mut map = serializer.serialize_map(None)?;
+        let tx = TxView(&self.data.tx, self.fields);
+        match &self.amounts {
+            Some(formatted_amounts) => map.serialize_entry(
+                "tx",
+                &DecoratedTxView {
+                    tx,
+                    formatted_amounts,
+                },
+            )?,
+            None => map.serialize_entry("tx", &tx)?,
+        }
+        if self.fields.contains("ethSignature") {
+            map.serialize_entry("ethSignature", &self.data.eth_signature)?;
+        }
+        if self.fields.contains("feeBreakdown") {
+            map.serialize_entry("feeBreakdown", &self.data.fee_breakdown)?;
+        }
+        map.end()
+    }
+}
+
+/// Applies the requested fields to the transactions.
+pub fn shape_txs(txs: Vec<Transaction>, fields: TxFields) -> Vec<ShapedTransaction> {
+    txs.into_iter()
+        .map(|tx| ShapedTransaction {
+            tx,
+            fields,
+            amounts: None,
+        })
+        .collect()
+}
+
+impl<F: Serialize> Paginated<Transaction, F> {
+    /// Applies the requested fields to the transactions of the page.
+    pub fn shaped(self, fields: TxFields) -> Paginated<ShapedTransaction, F> {
+        Paginated {
+            list: shape_txs(self.list, fields),
+            pagination: self.pagination,
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use zksync_types::Address;
+
+/// Header with the API key allowing to manage the user-defined labels of its scope.
+pub const LABELS_API_KEY_HEADER: &str = "X-Api-Key";
+
+/// Maximum length of the label text.
+pub const MAX_LABEL_LENGTH: usize = 64;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AddressLabelCategory {
+    /// Hot wallet of the exchange, set by the operator.
+    ExchangeHotWallet,
+    /// Well-known contract, set by the operator.
+    KnownContract,
+    /// Tag created with the API key, visible only in the scope of the key.
+    UserDefined,
+}
+
+impl AddressLabelCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ExchangeHotWallet => "exchangeHotWallet",
+            Self::KnownContract => "knownContract",
+            Self::UserDefined => "userDefined",
+        }
+    }
+}
+
+impl fmt::Display for AddressLabelCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AddressLabelCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "exchangeHotWallet" => Ok(Self::ExchangeHotWallet),
+            "knownContract" => Ok(Self::KnownContract),
+            "userDefined" => Ok(Self::UserDefined),
+            _ => Err(format!("unknown address label category: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressLabel {
+    pub address: Address,
+    pub label: String,
+    pub category: AddressLabelCategory,
+}
+
+/// Request to add or remove the user-defined label in the scope of the API key.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressLabelRequest {
+    pub address: Address,
+    pub label: String,
+}
+
+/// Checks that the label is not empty and is not longer than `MAX_LABEL_LENGTH` characters.
+pub fn validate_label(label: &str) -> Result<(), String> {
+    if label.trim().is_empty() {
+        return Err(String::from("label must not be empty"));
+    }
+    if label.chars().count() > MAX_LABEL_LENGTH {
+        return Err(format!(
+            "label must not be longer than {} characters",
+            MAX_LABEL_LENGTH
+        ));
+    }
+    Ok(())
+}
@@ -5,12 +5,31 @@ use std::collections::HashMap;
 use zksync_types::network::Network;
 
 pub mod account;
+pub mod amounts;
+pub mod attestation;
 pub mod block;
+pub mod cosigned_tx;
+pub mod create2_factory;
+pub mod deposit_relay;
+pub mod event;
+pub mod exit_proof;
 pub mod fee;
+pub mod fields;
+pub mod label;
+pub mod nonce_reservation;
+pub mod order_book;
 pub mod pagination;
+pub mod payment_request;
+pub mod priority_op;
+pub mod proof;
+pub mod session_key;
+pub mod signature;
+pub mod standing_order;
+pub mod statement;
 pub mod status;
 pub mod token;
 pub mod transaction;
+pub mod tx_callback;
 
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
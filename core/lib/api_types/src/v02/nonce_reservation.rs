@@ -0,0 +1,47 @@
+use chrono::{serde::ts_milliseconds, DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use zksync_types::{tx::TxEthSignature, AccountId, Nonce};
+
+/// Request to reserve the range of the account nonces, signed by the account owner.
+///
+/// Transactions of the reserved range may be submitted in any order: the ones following
+/// a missing nonce are held in the mempool until the gap is filled or the reservation expires.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NonceReservation {
+    pub account_id: AccountId,
+    /// Number of the reserved nonces.
+    pub count: u32,
+    /// Lifetime of the reservation in seconds.
+    pub ttl_secs: u64,
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    pub signature: TxEthSignature,
+}
+
+impl NonceReservation {
+    pub fn get_ethereum_sign_message(&self) -> String {
+        format!(
+            "By signing this message, you are reserving the nonces of your zkSync account.\n\
+            Account: {}\n\
+            Count: {}\n\
+            TTL: {}\n\
+            Timestamp: {}",
+            self.account_id,
+            self.count,
+            self.ttl_secs,
+            self.timestamp.timestamp_millis()
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NonceReservationInfo {
+    pub account_id: AccountId,
+    pub first_nonce: Nonce,
+    /// Last reserved nonce, inclusive.
+    pub last_nonce: Nonce,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
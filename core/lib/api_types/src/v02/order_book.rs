@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_types::{
+    tx::{TxEthSignature, TxHash, TxSignature},
+    Order,
+};
+use zksync_utils::{BigUintSerdeAsRadix10Str, ZeroPrefixHexSerde};
+
+use super::transaction::TxHashSerializeWrapper;
+
+/// Status of the order in the order book.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderStatus {
+    /// Order can be matched in the swap.
+    Open,
+    /// Swap containing the order is accepted, but not executed yet.
+    Matched,
+    Filled,
+    Cancelled,
+    /// Order was not matched before its `validUntil` time.
+    Expired,
+}
+
+/// Signed swap order submitted to the order book.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingOrder {
+    pub order: Order,
+    /// Ethereum signature of the order, required for the accounts which can't authorize
+    /// the transactions with the zkSync signature only.
+    pub eth_signature: Option<TxEthSignature>,
+}
+
+/// Request to cancel the order, signed with the same key as the order itself.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingOrderCancellation {
+    pub signature: TxSignature,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderInfo {
+    pub order_id: u64,
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub order_hash: TxHash,
+    pub order: Order,
+    pub status: OrderStatus,
+    /// Total amount of the sold token in the executed swaps.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub filled_amount: BigUint,
+    /// Last swap transaction the order was matched in.
+    pub swap_tx_hash: Option<TxHashSerializeWrapper>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -4,6 +4,7 @@ use std::str::FromStr;
 use thiserror::Error;
 use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId, TokenId};
 
+/// Default maximum page size, the servers can be configured to enforce a different one.
 pub const MAX_LIMIT: u32 = 100;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
@@ -132,6 +133,31 @@ pub struct PendingOpsRequest {
     pub serial_id: ApiEither<SerialId>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct PendingPriorityOpsRequest {
+    pub serial_id: ApiEither<SerialId>,
+    pub confirmations_for_eth_event: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OpenOrdersRequest {
+    pub token_sell: TokenId,
+    pub token_buy: TokenId,
+    pub order_id: ApiEither<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountOrdersRequest {
+    pub account_id: AccountId,
+    pub order_id: ApiEither<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountStandingOrdersRequest {
+    pub account_id: AccountId,
+    pub standing_order_id: ApiEither<u64>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct AccountTxsRequest {
     pub address: Address,
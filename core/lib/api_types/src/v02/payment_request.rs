@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_types::{Address, TokenId};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+use super::transaction::TxHashSerializeWrapper;
+
+/// Status of the payment request.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PaymentRequestStatus {
+    /// Matching transfer is not executed yet.
+    Pending,
+    /// Matching transfer is executed.
+    Fulfilled,
+    /// Request has expired before the matching transfer was executed.
+    Expired,
+}
+
+/// Request to be paid with the transfer of at least `amount` of `token` to `recipient`.
+/// If the `reference` is set, the transfer should carry it as the memo.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingPaymentRequest {
+    pub recipient: Address,
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    pub reference: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRequestInfo {
+    pub payment_request_id: u64,
+    /// Link to the status of the request, to be shared with the payer.
+    pub url: String,
+    pub recipient: Address,
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    pub reference: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub status: PaymentRequestStatus,
+    /// Transfer the request was fulfilled with.
+    pub tx_hash: Option<TxHashSerializeWrapper>,
+    pub fulfilled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,97 @@
+use chrono::NaiveDate;
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_types::{tx::TxHash, Address, PriorityOp, SerialId, TokenId, H256};
+use zksync_utils::{BigUintSerdeAsRadix10Str, ZeroPrefixHexSerde};
+
+use super::transaction::L1Transaction;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PendingPriorityOpStatus {
+    /// The operation is observed on L1, but doesn't have enough confirmations yet.
+    AwaitingConfirmations,
+    /// The operation is confirmed and waits to be included into a block.
+    Confirmed,
+}
+
+/// Priority operation which is observed on L1 but not executed yet.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingPriorityOp {
+    pub serial_id: SerialId,
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub tx_hash: TxHash,
+    pub eth_hash: H256,
+    pub eth_block: u64,
+    /// L1 block after which the operation is accepted by the server.
+    pub expected_accept_block: u64,
+    pub status: PendingPriorityOpStatus,
+    pub op: L1Transaction,
+}
+
+impl PendingPriorityOp {
+    pub fn new(op: PriorityOp, confirmed: bool, confirmations_for_eth_event: u64) -> Self {
+        let tx_hash = op.tx_hash();
+        let status = if confirmed {
+            PendingPriorityOpStatus::Confirmed
+        } else {
+            PendingPriorityOpStatus::AwaitingConfirmations
+        };
+        Self {
+            serial_id: op.serial_id,
+            tx_hash,
+            eth_hash: op.eth_hash,
+            eth_block: op.eth_block,
+            expected_accept_block: op.eth_block + confirmations_for_eth_event,
+            status,
+            op: L1Transaction::from_pending_op(op.data, op.eth_hash, op.serial_id, tx_hash),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositAggregatesQuery {
+    pub from_day: NaiveDate,
+    pub to_day: NaiveDate,
+    /// Only the deposits sent from this L1 address are aggregated.
+    pub sender: Option<Address>,
+    pub token: Option<TokenId>,
+}
+
+/// Executed deposits of the token within the day (in UTC).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyDeposits {
+    pub day: NaiveDate,
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    pub deposits_count: u64,
+    /// Amount of the distinct L1 senders of the deposits.
+    pub senders_count: u64,
+}
+
+/// Executed deposits of the token within the whole requested period.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositTotal {
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    pub deposits_count: u64,
+}
+
+/// Executed deposits aggregated per day and token, used by the bridges to reconcile
+/// the bridged value against their own books.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositAggregates {
+    pub from_day: NaiveDate,
+    pub to_day: NaiveDate,
+    pub sender: Option<Address>,
+    /// Days without the deposits are omitted.
+    pub daily: Vec<DailyDeposits>,
+    pub totals: Vec<DepositTotal>,
+}
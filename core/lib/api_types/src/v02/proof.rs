@@ -0,0 +1,150 @@
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_crypto::{
+    circuit::{
+        account::{Balance, CircuitAccount},
+        utils::eth_address_to_fr,
+    },
+    franklin_crypto::bellman::pairing::ff::PrimeField,
+    merkle_tree::hasher::Hasher,
+    params::{self, RESCUE_HASHER},
+    primitives::GetBits,
+    serialization::FrSerde,
+    Engine, Fr,
+};
+use zksync_types::{AccountId, AccountTree, Address, BlockNumber, Nonce, PubKeyHash, TokenId};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+/// Node of the Merkle path, from the leaf to the root.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MerklePathNode {
+    /// Hash of the sibling node.
+    #[serde(with = "FrSerde")]
+    pub sibling_hash: Fr,
+    /// Whether the node of the path is the right child of its parent.
+    pub is_right: bool,
+}
+
+/// Fields of the account tree leaf.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountLeaf {
+    pub address: Address,
+    pub nonce: Nonce,
+    pub pub_key_hash: PubKeyHash,
+    /// Root hash of the account balance tree.
+    #[serde(with = "FrSerde")]
+    pub balance_root: Fr,
+}
+
+/// Proof of the account balance against the root hash of the verified block.
+///
+/// The balance is the leaf of the account balance tree, whose root is a part of the account leaf,
+/// so the proof consists of the path in the balance tree and the path in the account tree.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBalanceProof {
+    pub block_number: BlockNumber,
+    /// Root hash of the account tree after the block, as it's stored in the contract.
+    #[serde(with = "FrSerde")]
+    pub root_hash: Fr,
+    pub account_id: AccountId,
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub balance: BigUint,
+    pub account: AccountLeaf,
+    pub balance_path: Vec<MerklePathNode>,
+    pub account_path: Vec<MerklePathNode>,
+}
+
+impl AccountBalanceProof {
+    /// Builds the proof from the account tree of the block.
+    /// Returns `None` if there is no such account in the tree.
+    pub fn new(
+        tree: &AccountTree,
+        block_number: BlockNumber,
+        account_id: AccountId,
+        token_id: TokenId,
+    ) -> Option<Self> {
+        let account = tree.get(*account_id)?;
+        let circuit_account = CircuitAccount::from(account.clone());
+
+        Some(Self {
+            block_number,
+            root_hash: tree.root_hash(),
+            account_id,
+            token_id,
+            balance: account.get_balance(token_id),
+            account: AccountLeaf {
+                address: account.address,
+                nonce: account.nonce,
+                pub_key_hash: account.pub_key_hash,
+                balance_root: circuit_account.subtree.root_hash(),
+            },
+            balance_path: path_nodes(circuit_account.subtree.merkle_path(*token_id)),
+            account_path: path_nodes(tree.merkle_path(*account_id)),
+        })
+    }
+
+    /// Checks that the proof leads to its root hash. The root hash itself should be compared
+    /// with the one stored in the contract for the block.
+    pub fn verify(&self) -> bool {
+        if self.balance_path.len() != params::balance_tree_depth()
+            || self.account_path.len() != params::account_tree_depth()
+        {
+            return false;
+        }
+        let balance = match Fr::from_str(&self.balance.to_string()) {
+            Some(balance) => balance,
+            None => return false,
+        };
+        let nonce = Fr::from_str(&self.account.nonce.to_string()).expect("nonce fits into Fr");
+
+        let balance_leaf =
+            RESCUE_HASHER.hash_bits(Balance::<Engine> { value: balance }.get_bits_le());
+        let balance_root = match path_root(balance_leaf, &self.balance_path) {
+            Some((root, index)) if index == *self.token_id && root == self.account.balance_root => {
+                root
+            }
+            _ => return false,
+        };
+
+        let account_leaf = RESCUE_HASHER.hash_bits(CircuitAccount::<Engine>::leaf_bits_le(
+            &RESCUE_HASHER,
+            &nonce,
+            &self.account.pub_key_hash.as_fr(),
+            &eth_address_to_fr(&self.account.address),
+            balance_root,
+        ));
+        matches!(
+            path_root(account_leaf, &self.account_path),
+            Some((root, index)) if index == *self.account_id && root == self.root_hash
+        )
+    }
+}
+
+fn path_nodes(path: Vec<(Fr, bool)>) -> Vec<MerklePathNode> {
+    path.into_iter()
+        .map(|(sibling_hash, is_right)| MerklePathNode {
+            sibling_hash,
+            is_right,
+        })
+        .collect()
+}
+
+/// Computes the root hash and the leaf index from the leaf hash and its Merkle path.
+fn path_root(leaf_hash: Fr, path: &[MerklePathNode]) -> Option<(Fr, u32)> {
+    let mut index = 0u32;
+    let mut hash = leaf_hash;
+    for (level, node) in path.iter().enumerate() {
+        let (lhs, rhs) = if node.is_right {
+            index |= 1u32.checked_shl(level as u32)?;
+            (node.sibling_hash, hash)
+        } else {
+            (hash, node.sibling_hash)
+        };
+        hash = RESCUE_HASHER.compress(&lhs, &rhs, level);
+    }
+    Some((hash, index))
+}
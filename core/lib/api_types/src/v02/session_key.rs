@@ -0,0 +1,150 @@
+use chrono::{serde::ts_milliseconds, DateTime, Utc};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_types::{tx::TxEthSignature, AccountId, Address, TokenId, ZkSyncTx};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+/// Type of the transaction the session key can authorize.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SessionTxType {
+    Transfer,
+    Withdraw,
+    MintNFT,
+}
+
+impl SessionTxType {
+    /// Returns the type of the transaction, if the session keys can authorize it.
+    pub fn from_tx(tx: &ZkSyncTx) -> Option<Self> {
+        match tx {
+            ZkSyncTx::Transfer(_) => Some(Self::Transfer),
+            ZkSyncTx::Withdraw(_) => Some(Self::Withdraw),
+            ZkSyncTx::MintNFT(_) => Some(Self::MintNFT),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Transfer => "Transfer",
+            Self::Withdraw => "Withdraw",
+            Self::MintNFT => "MintNFT",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "Transfer" => Some(Self::Transfer),
+            "Withdraw" => Some(Self::Withdraw),
+            "MintNFT" => Some(Self::MintNFT),
+            _ => None,
+        }
+    }
+}
+
+/// Maximum amount of the token the session key can spend, including the fees.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionKeyLimit {
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub max_amount: BigUint,
+}
+
+/// Request to register the session key, signed by the owner of the account.
+///
+/// Transactions of the allowed types signed by the session key instead of the owner are
+/// accepted without the owner's Ethereum signature until the key expires or the caps
+/// are spent. The zkSync signature of the transactions is still required.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionKeyRegistration {
+    pub account_id: AccountId,
+    pub session_address: Address,
+    pub allowed_tx_types: Vec<SessionTxType>,
+    pub limits: Vec<SessionKeyLimit>,
+    #[serde(with = "ts_milliseconds")]
+    pub expires_at: DateTime<Utc>,
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    pub signature: TxEthSignature,
+}
+
+impl SessionKeyRegistration {
+    /// Limits are listed by the token ids, since the message is checked by the server
+    /// without resolving the tokens.
+    pub fn get_ethereum_sign_message(&self) -> String {
+        let tx_types: Vec<_> = self
+            .allowed_tx_types
+            .iter()
+            .map(SessionTxType::as_str)
+            .collect();
+        let limits: Vec<_> = self
+            .limits
+            .iter()
+            .map(|limit| format!("{} of token #{}", limit.max_amount, limit.token))
+            .collect();
+        format!(
+            "By signing this message, you are allowing the session key to send transactions on behalf of your zkSync account.\n\
+            Session key: {:?}\n\
+            Account: {}\n\
+            Allowed transactions: {}\n\
+            Limits: {}\n\
+            Expires at: {}\n\
+            Timestamp: {}",
+            self.session_address,
+            self.account_id,
+            tx_types.join(", "),
+            limits.join(", "),
+            self.expires_at.timestamp_millis(),
+            self.timestamp.timestamp_millis()
+        )
+    }
+}
+
+/// Request to revoke the session key, signed by the owner of the account.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionKeyRevocation {
+    pub account_id: AccountId,
+    pub session_address: Address,
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    pub signature: TxEthSignature,
+}
+
+impl SessionKeyRevocation {
+    pub fn get_ethereum_sign_message(&self) -> String {
+        format!(
+            "Revoke the session key of your zkSync account.\n\
+            Session key: {:?}\n\
+            Account: {}\n\
+            Timestamp: {}",
+            self.session_address,
+            self.account_id,
+            self.timestamp.timestamp_millis()
+        )
+    }
+}
+
+/// Cap of the session key with the amount already spent.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionKeySpending {
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub max_amount: BigUint,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub spent: BigUint,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionKeyInfo {
+    pub account_id: AccountId,
+    pub session_address: Address,
+    pub allowed_tx_types: Vec<SessionTxType>,
+    pub limits: Vec<SessionKeySpending>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked: bool,
+    pub created_at: DateTime<Utc>,
+}
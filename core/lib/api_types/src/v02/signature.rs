@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use zksync_crypto::{serialization::FrSerde, Fr};
+use zksync_types::{tx::PackedEthSignature, Address, BlockNumber};
+
+/// Query of the endpoints which can sign their responses.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureQuery {
+    /// Whether the response should be signed by the server.
+    #[serde(default)]
+    pub signed: bool,
+}
+
+/// Data signed by the API server: the response payload along with the block
+/// which state it corresponds to.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedData<T> {
+    pub payload: T,
+    pub block_number: BlockNumber,
+    /// Root hash of the account tree after the block.
+    #[serde(with = "FrSerde")]
+    pub root_hash: Fr,
+}
+
+impl<T: Serialize> SignedData<T> {
+    /// Canonical encoding of the data which is signed: compact JSON with the object keys
+    /// sorted in the lexicographical order.
+    pub fn canonical_encoding(&self) -> serde_json::Result<Vec<u8>> {
+        // Maps of `serde_json::Value` are sorted by keys.
+        serde_json::to_vec(&serde_json::to_value(self)?)
+    }
+}
+
+/// Response signed by the API server with its Ethereum key.
+///
+/// To verify the response without trusting the re-serialization of the payload,
+/// deserialize it with `T = serde_json::Value`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedResponse<T> {
+    #[serde(flatten)]
+    pub data: SignedData<T>,
+    /// Ethereum signature (`personal_sign`) of the canonical encoding of the data.
+    pub signature: PackedEthSignature,
+    /// Address of the server key, should be checked against the trusted one.
+    pub signer: Address,
+}
+
+impl<T: Serialize> SignedResponse<T> {
+    /// Checks that the data is signed by the stated signer.
+    pub fn verify(&self) -> bool {
+        self.data
+            .canonical_encoding()
+            .ok()
+            .and_then(|message| {
+                self.signature
+                    .signature_recover_signer_from_raw_message(&message)
+                    .ok()
+            })
+            .map_or(false, |signer| signer == self.signer)
+    }
+}
+
+/// Response of the endpoints which can sign their responses, the payload is returned as is
+/// if the signature is not requested.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum MaybeSigned<T> {
+    Signed(SignedResponse<T>),
+    Plain(T),
+}
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_types::{
+    tx::{TxEthSignature, TxSignature},
+    AccountId, Address, Nonce, TokenId, Transfer,
+};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+use super::transaction::TxHashSerializeWrapper;
+
+/// Status of the standing order.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StandingOrderStatus {
+    /// Order has payments which are not submitted yet.
+    Active,
+    /// All the payments of the order are submitted.
+    Completed,
+    Cancelled,
+    /// One of the payments was rejected, the remaining ones are skipped.
+    Failed,
+}
+
+/// Status of the single payment of the standing order.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StandingPaymentStatus {
+    Scheduled,
+    /// Transfer is accepted to the mempool.
+    Submitted,
+    /// Transfer was rejected at the submission.
+    Failed,
+    /// Payment won't be made, since the order is cancelled or failed.
+    Skipped,
+}
+
+/// Pre-signed transfer of the single payment.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingPaymentTx {
+    pub tx: Transfer,
+    /// Ethereum signature of the transfer, required for the accounts which can't authorize
+    /// the transactions with the zkSync signature only.
+    pub eth_signature: Option<TxEthSignature>,
+}
+
+/// Recurring payment registered as the set of the pre-signed transfers. The transfers must
+/// have the same recipient, token, amount and fee, and the consecutive nonces: payment `i`
+/// is submitted at `firstPaymentAt + i * intervalSecs`, and its transfer must be valid at
+/// that time.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingStandingOrder {
+    pub first_payment_at: DateTime<Utc>,
+    pub interval_secs: u64,
+    /// No payments are scheduled after this time.
+    pub expires_at: DateTime<Utc>,
+    pub payments: Vec<StandingPaymentTx>,
+}
+
+/// Request to cancel the standing order, signed with the same key as its transfers.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingStandingOrderCancellation {
+    pub signature: TxSignature,
+}
+
+impl IncomingStandingOrderCancellation {
+    /// Message to be signed to cancel the standing order with the given id.
+    pub fn message(standing_order_id: u64) -> Vec<u8> {
+        let mut message = b"cancel standing order".to_vec();
+        message.extend_from_slice(&standing_order_id.to_be_bytes());
+        message
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingOrderInfo {
+    pub standing_order_id: u64,
+    pub account_id: AccountId,
+    pub from: Address,
+    pub to: Address,
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub fee: BigUint,
+    pub first_payment_at: DateTime<Utc>,
+    pub interval_secs: u64,
+    pub expires_at: DateTime<Utc>,
+    pub status: StandingOrderStatus,
+    /// Error of the payment the order has failed on.
+    pub failure_reason: Option<String>,
+    pub payments_total: u32,
+    pub payments_submitted: u32,
+    pub next_payment_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingPaymentInfo {
+    pub sequence: u32,
+    pub nonce: Nonce,
+    pub scheduled_at: DateTime<Utc>,
+    pub status: StandingPaymentStatus,
+    pub tx_hash: Option<TxHashSerializeWrapper>,
+    pub error: Option<String>,
+    pub submitted_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,87 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_types::{AccountId, Address, BlockNumber, TokenId};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+use super::transaction::Transaction;
+
+/// Format of the generated statement document.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StatementFormat {
+    Json,
+    Csv,
+}
+
+impl Default for StatementFormat {
+    fn default() -> Self {
+        Self::Json
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementQuery {
+    pub format: Option<StatementFormat>,
+}
+
+/// Monthly statement of the account: balances at the start and at the end of the month,
+/// the transactions executed within it and the fees paid.
+///
+/// The month is mapped to the range of blocks created within it, so the transactions
+/// and the balances are consistent with each other.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStatement {
+    pub address: Address,
+    pub account_id: Option<AccountId>,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Last block created before the period, the opening balances are taken after it.
+    pub opening_block: Option<BlockNumber>,
+    /// Last block created within the period, the closing balances are taken after it.
+    pub closing_block: Option<BlockNumber>,
+    pub balances: Vec<StatementBalance>,
+    /// Transactions of the period, from the oldest one.
+    pub transactions: Vec<StatementTransaction>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Balance of the single token in the statement. Fiat values are in USD and are `None`
+/// if the price of the token at the moment is unknown.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementBalance {
+    pub token_id: TokenId,
+    pub symbol: String,
+    pub decimals: u8,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub opening_balance: BigUint,
+    pub opening_value_usd: Option<BigDecimal>,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub closing_balance: BigUint,
+    pub closing_value_usd: Option<BigDecimal>,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub fees_paid: BigUint,
+    /// Sum of the fees valued at the moments they were paid.
+    pub fees_paid_usd: Option<BigDecimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementTransaction {
+    pub tx: Transaction,
+    /// Fee paid by the account, if the account is the initiator of the transaction.
+    pub fee: Option<StatementFee>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementFee {
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    pub value_usd: Option<BigDecimal>,
+}
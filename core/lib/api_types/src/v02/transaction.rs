@@ -27,6 +27,11 @@ pub enum TxInBlockStatus {
     Committed,
     Finalized,
     Rejected,
+    /// The transaction is finalized, but its data was moved out of the database
+    /// by the pruning policy.
+    Pruned,
+    /// The transaction was evicted from the mempool since its `valid_until` had passed.
+    Expired,
 }
 
 impl From<BlockStatus> for TxInBlockStatus {
@@ -43,6 +48,27 @@ impl From<BlockStatus> for TxInBlockStatus {
 pub struct TxData {
     pub tx: Transaction,
     pub eth_signature: Option<String>,
+    /// Decomposition of the fee charged, present once the transaction is executed.
+    pub fee_breakdown: Option<TxFeeBreakdown>,
+}
+
+/// Fee charged by the executed transaction split into the parts it covers,
+/// in the fee token of the transaction.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxFeeBreakdown {
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub total_fee: BigUint,
+    /// Share of the zero-knowledge proof generation cost.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub zkp_fee: BigUint,
+    /// Share of the L1 gas cost.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub gas_fee: BigUint,
+    /// Part of the required fee paid by the operator.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub subsidy: BigUint,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -52,6 +78,8 @@ pub struct L1Receipt {
     pub eth_block: EthBlockId,
     pub rollup_block: Option<BlockNumber>,
     pub id: SerialId,
+    /// Estimated number of seconds until the operation is finalized.
+    pub expected_finality_secs: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -62,6 +90,38 @@ pub struct L2Receipt {
     pub rollup_block: Option<BlockNumber>,
     pub status: TxInBlockStatus,
     pub fail_reason: Option<String>,
+    /// Estimated number of seconds until the operation is finalized.
+    pub expected_finality_secs: Option<u64>,
+    /// Time the transaction expired at, set only for the expired ones.
+    pub valid_until: Option<DateTime<Utc>>,
+    /// Call made to the recipient contract, set only for the withdrawals submitted with
+    /// the L1 calldata.
+    #[serde(default)]
+    pub withdrawal_hook: Option<WithdrawalHook>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum WithdrawalHookStatus {
+    /// Withdrawal is not executed on L1 yet.
+    Pending,
+    /// Withdrawal is executed on L1, the call is waiting to be sent.
+    Ready,
+    Sent,
+    Succeeded,
+    /// Call is reverted or is not confirmed in time.
+    Failed,
+    /// Withdrawal has failed, so the call won't be made.
+    Cancelled,
+}
+
+/// Call made to the recipient contract once the withdrawal is executed on L1.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalHook {
+    pub status: WithdrawalHookStatus,
+    pub eth_tx_hash: Option<H256>,
+    pub fail_reason: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -71,6 +131,29 @@ pub enum Receipt {
     L2(L2Receipt),
 }
 
+impl Receipt {
+    pub fn status(&self) -> TxInBlockStatus {
+        match self {
+            Self::L1(receipt) => receipt.status,
+            Self::L2(receipt) => receipt.status,
+        }
+    }
+
+    pub fn rollup_block(&self) -> Option<BlockNumber> {
+        match self {
+            Self::L1(receipt) => receipt.rollup_block,
+            Self::L2(receipt) => receipt.rollup_block,
+        }
+    }
+
+    pub fn set_expected_finality_secs(&mut self, expected_finality_secs: Option<u64>) {
+        match self {
+            Self::L1(receipt) => receipt.expected_finality_secs = expected_finality_secs,
+            Self::L2(receipt) => receipt.expected_finality_secs = expected_finality_secs,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
@@ -83,6 +166,8 @@ pub struct Transaction {
     pub fail_reason: Option<String>,
     pub created_at: Option<DateTime<Utc>>,
     pub batch_id: Option<u32>,
+    /// Memo attached to the transfer on submission.
+    pub memo: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -233,6 +318,16 @@ pub struct SubmitBatchResponse {
     pub batch_hash: TxHash,
 }
 
+/// Transaction which is already queued or executed, returned on its repeated submission.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownTx {
+    pub tx_hash: TxHash,
+    pub status: TxInBlockStatus,
+    /// Time of the original submission.
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiTxBatch {
@@ -250,6 +345,17 @@ pub struct BatchStatus {
     pub last_state: TxInBlockStatus,
 }
 
+/// L2 activity linked to the Ethereum transaction.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct L1TxActivity {
+    pub eth_hash: H256,
+    /// Priority operations requested by the transaction, including the ones not executed yet.
+    pub priority_ops: Vec<TxHashSerializeWrapper>,
+    /// Withdrawals, forced exits and NFT withdrawals the funds of which were sent by the transaction.
+    pub completed_withdrawals: Vec<TxHashSerializeWrapper>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Toggle2FA {
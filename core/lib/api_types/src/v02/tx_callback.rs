@@ -0,0 +1,63 @@
+//! Status callbacks of the transactions submitted with the API keys.
+//!
+//! Exchanges register one callback endpoint per API key scope, and the server reports the status
+//! changes of every transaction submitted with the key of the scope: `committed` and `finalized`
+//! for the executed transactions, and `rejected` for the failed ones. Callbacks are signed the same
+//! way as the operator webhooks, with the secret issued on the registration.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use zksync_types::{tx::TxHash, BlockNumber};
+use zksync_utils::ZeroPrefixHexSerde;
+
+/// Header with the id of the callback, the same for all the delivery attempts,
+/// so the receivers can deduplicate the retries.
+pub const TX_CALLBACK_ID_HEADER: &str = "X-Zksync-Callback-Id";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TxCallbackEndpointRequest {
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxCallbackEndpoint {
+    pub url: String,
+    /// Secret the callbacks are signed with, returned only on the registration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TxCallbackStatus {
+    Committed,
+    Finalized,
+    Rejected,
+}
+
+impl TxCallbackStatus {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "committed" => Some(Self::Committed),
+            "finalized" => Some(Self::Finalized),
+            "rejected" => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+}
+
+/// Body of the callback request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxCallback {
+    pub id: u64,
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub tx_hash: TxHash,
+    pub status: TxCallbackStatus,
+    pub block_number: BlockNumber,
+    pub fail_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
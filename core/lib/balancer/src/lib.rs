@@ -14,6 +14,12 @@ pub trait BuildBalancedItem<R, S> {
     fn build_with_receiver(&self, receiver: Receiver<R>) -> S;
 }
 
+/// Request which must be processed by the same item as the other requests with the same key,
+/// e.g. to preserve the order of the requests related to the same entity.
+pub trait ShardedRequest {
+    fn shard_key(&self) -> u64;
+}
+
 impl<R> Balancer<R> {
     pub fn new<T, S>(
         balanced_item: T,
@@ -52,11 +58,28 @@ impl<R> Balancer<R> {
             metrics::histogram!("ticker.dispatcher.request", start.elapsed());
         }
     }
+
+    /// Sends all the requests with the same shard key to the same item,
+    /// so the item receives them in the order they were sent to the balancer.
+    pub async fn run_sharded(mut self)
+    where
+        R: ShardedRequest,
+    {
+        while let Some(request) = self.requests.next().await {
+            let channel_index = (request.shard_key() % self.channels.len() as u64) as usize;
+            let start = Instant::now();
+            self.channels[channel_index]
+                .send(request)
+                .await
+                .unwrap_or_default();
+            metrics::histogram!("balancer.sharded_dispatcher.request", start.elapsed());
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Balancer, BuildBalancedItem};
+    use crate::{Balancer, BuildBalancedItem, ShardedRequest};
     use futures::channel::mpsc;
     use futures::channel::mpsc::Receiver;
     use futures::{SinkExt, StreamExt};
@@ -88,4 +111,27 @@ mod tests {
             }
         }
     }
+
+    impl ShardedRequest for i32 {
+        fn shard_key(&self) -> u64 {
+            (*self / 10) as u64
+        }
+    }
+
+    #[tokio::test]
+    async fn sharded_load_balance() {
+        let (mut request_sender, request_receiver) = mpsc::channel(50);
+
+        let (balancer, mut items) = Balancer::new(SomeBalancedItemBuilder, request_receiver, 3, 50);
+
+        tokio::spawn(balancer.run_sharded());
+        for i in 0..50 {
+            request_sender.send(i).await.unwrap();
+        }
+        // Requests with the same key are received by the same item in the order they were sent.
+        for i in 0..50 {
+            let item = &mut items[((i / 10) % 3) as usize];
+            assert_eq!(item.receiver.next().await, Some(i));
+        }
+    }
 }
@@ -2,11 +2,13 @@ use num::{rational::Ratio, BigUint};
 /// External uses
 use serde::Deserialize;
 /// Built-in uses
+use std::collections::HashMap;
+use std::fs;
 use std::net::SocketAddr;
 use std::time::Duration;
 use zksync_utils::scaled_u64_to_ratio;
 // Workspace uses
-use zksync_types::AccountId;
+use zksync_types::{AccountId, Address, H256};
 // Local uses
 use crate::envy_load;
 
@@ -23,6 +25,8 @@ pub struct ApiConfig {
     pub json_rpc: JsonRpcConfig,
     /// Configuration options for the web3 JSON RPC server.
     pub web3: Web3Config,
+    /// Configuration options for the gRPC server.
+    pub grpc: GrpcConfig,
     /// Configuration options for the private core API.
     pub private: PrivateApiConfig,
     /// Configuration options for the prover server.
@@ -40,6 +44,7 @@ impl ApiConfig {
             rest: envy_load!("rest", "API_REST_"),
             json_rpc: envy_load!("json_rpc", "API_JSON_RPC_"),
             web3: envy_load!("web3", "API_WEB3_"),
+            grpc: envy_load!("grpc", "API_GRPC_"),
             private: envy_load!("private", "API_PRIVATE_"),
             prover: envy_load!("prover", "API_PROVER_"),
             prometheus: envy_load!("prometheus", "API_PROMETHEUS_"),
@@ -53,9 +58,34 @@ impl CommonApiConfig {
         scaled_u64_to_ratio(self.max_subsidy_usd_scaled)
     }
 
+    pub fn cpk_sponsorship_min_deposit_usd(&self) -> Ratio<BigUint> {
+        scaled_u64_to_ratio(self.cpk_sponsorship_min_deposit_usd_scaled)
+    }
+
+    pub fn cpk_sponsorship_max_total_usd(&self) -> Ratio<BigUint> {
+        scaled_u64_to_ratio(self.cpk_sponsorship_max_total_usd_scaled)
+    }
+
+    pub fn withdrawal_daily_limit_usd(&self) -> Option<Ratio<BigUint>> {
+        self.withdrawal_daily_limit_usd_scaled
+            .map(scaled_u64_to_ratio)
+    }
+
+    pub fn withdrawal_policy_hook_timeout(&self) -> Duration {
+        Duration::from_millis(self.withdrawal_policy_hook_timeout)
+    }
+
+    pub fn screening_api_timeout(&self) -> Duration {
+        Duration::from_millis(self.screening_api_timeout)
+    }
+
     pub fn from_env() -> Self {
         envy_load!("common", "API_COMMON_")
     }
+
+    pub fn finalized_cache_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.finalized_cache_poll_interval)
+    }
 }
 
 impl AdminApiConfig {
@@ -82,6 +112,12 @@ impl Web3Config {
     }
 }
 
+impl GrpcConfig {
+    pub fn from_env() -> Self {
+        envy_load!("grpc", "API_GRPC_")
+    }
+}
+
 impl PrivateApiConfig {
     pub fn from_env() -> Self {
         envy_load!("private", "API_PRIVATE_")
@@ -104,6 +140,12 @@ impl PrometheusConfig {
 pub struct CommonApiConfig {
     // Size of LRU caches for requests
     pub caches_size: usize,
+    /// URL of Redis used to share the cache of the finalized data between the API servers.
+    /// If not set, the cache is stored in memory only.
+    pub cache_redis_url: Option<String>,
+    /// Interval (in milliseconds) of checking for the newly finalized blocks, which invalidate
+    /// the cached data depending on the last finalized block.
+    pub finalized_cache_poll_interval: u64,
     // Determines the required minimum account age for `ForcedExit` operation to be allowed.
     // Type of value is seconds.
     pub forced_exit_minimum_account_age_secs: u64,
@@ -122,6 +164,92 @@ pub struct CommonApiConfig {
 
     /// The name of current subsidy. It is needed to conveniently fetch historical data regarding subsidies for different partners
     pub subsidy_name: String,
+
+    /// Whether the operator pays the `ChangePubKey` fee for the new accounts
+    pub cpk_sponsorship_enabled: bool,
+    /// Minimum value of the first deposit to the account for its `ChangePubKey` to be sponsored,
+    /// scaled by SUBSIDY_USD_AMOUNTS_SCALE
+    pub cpk_sponsorship_min_deposit_usd_scaled: u64,
+    /// Maximum total value of the sponsored fees, scaled by SUBSIDY_USD_AMOUNTS_SCALE
+    pub cpk_sponsorship_max_total_usd_scaled: u64,
+
+    /// Operator addresses allowed to submit the transfers without the fee, e.g. the rebates
+    /// and the airdrops
+    pub fee_free_transfer_addresses: Vec<Address>,
+    /// Maximum amount of the fee-free transfers of a single operator address per day (in UTC)
+    pub fee_free_transfer_daily_quota: u64,
+
+    /// Maximum value of the withdrawals from a single address during the last 24 hours,
+    /// scaled by SUBSIDY_USD_AMOUNTS_SCALE. Forced exits are counted as the withdrawals of
+    /// the whole target balance. Withdrawals are not limited if not set
+    pub withdrawal_daily_limit_usd_scaled: Option<u64>,
+    /// URL of the external service approving the withdrawals. Not called if not set
+    pub withdrawal_policy_hook_url: Option<String>,
+    /// Timeout (in milliseconds) of the withdrawal policy hook request
+    pub withdrawal_policy_hook_timeout: u64,
+    /// Whether the withdrawals are accepted if the withdrawal policy hook is not available
+    pub withdrawal_policy_hook_fail_open: bool,
+
+    /// Whether the senders and the recipients of the submitted transactions are screened
+    /// against the denylist maintained by the operator
+    pub screening_enabled: bool,
+    /// URL of the external screening service, checked after the denylist. Not called if not set
+    pub screening_api_url: Option<String>,
+    /// Timeout (in milliseconds) of the screening service request
+    pub screening_api_timeout: u64,
+    /// Whether the transactions are accepted if the screening service is not available
+    pub screening_api_fail_open: bool,
+
+    /// Private key signing the account state and receipt responses on request, so they can be
+    /// verified by the light clients. Responses can't be signed if not set
+    pub response_signing_private_key: Option<H256>,
+
+    /// Whether the account tree of the last verified block is kept in memory to serve
+    /// the Merkle proofs of the account balances
+    pub merkle_proofs_enabled: bool,
+    /// Whether the exit proofs can be requested through the API. The proofs are generated
+    /// in the background, which requires the exodus verification key and the universal setup
+    pub exit_proofs_enabled: bool,
+    /// Whether the signed swap orders are accepted into the order book, so the order-matching
+    /// service and the trading frontends can query the open orders
+    pub order_book_enabled: bool,
+    /// Whether the users can register the standing orders, so the server submits their
+    /// pre-signed transfers at the scheduled times
+    pub standing_orders_enabled: bool,
+    /// Whether the users can register the session keys, so the dApps can send the transactions
+    /// on their behalf within the limits without the Ethereum signature of the owner
+    pub session_keys_enabled: bool,
+    /// Whether the merchants can create the payment requests, which are marked fulfilled
+    /// once the matching transfer is executed
+    pub payment_requests_enabled: bool,
+    /// Whether the transactions can be held by the server until they are signed by the required
+    /// number of the co-signers
+    pub cosigned_txs_enabled: bool,
+    /// Whether the users can attest the control of their addresses
+    pub address_attestations_enabled: bool,
+    /// Whether the forced exit requests are accepted only for the attested target addresses
+    pub forced_exit_requests_require_attestation: bool,
+    /// Whether the account statements are exported only for the attested addresses
+    pub statements_require_attestation: bool,
+    /// Whether the clients can request only a part of the transaction fields with the `fields`
+    /// query parameter, e.g. to skip the raw transaction bodies on the history pages
+    pub response_shaping_enabled: bool,
+    /// Whether the API keys can register the endpoints receiving the status callbacks
+    /// of the transactions submitted with them
+    pub tx_callbacks_enabled: bool,
+    /// Whether the accounts can reserve the ranges of their nonces to submit the transactions
+    /// out of order
+    pub nonce_reservations_enabled: bool,
+    /// Whether the CREATE2 `ChangePubKey` transactions are accepted only for the factory and
+    /// code hash pairs registered in the database
+    pub create2_factory_registry_enabled: bool,
+    /// Whether the withdrawals can carry the calldata of the call made to the recipient contract
+    /// once the withdrawal is executed on L1. The calls are sent by the withdrawal hooks sender
+    /// of `eth_sender`
+    pub withdrawal_hooks_enabled: bool,
+    /// Gas of the call attached to the withdrawal charged in the withdrawal fee, should match
+    /// the gas limit of the calls sent by `eth_sender`
+    pub withdrawal_hook_gas_limit: u64,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -178,12 +306,65 @@ pub struct PrivateApiConfig {
     pub port: u16,
     /// URL to access API server.
     pub url: String,
+    /// Name of the file with the report definitions in `etc/reports`.
+    /// Reports are not available if not set.
+    pub reports_name: Option<String>,
+    /// Timeout of the report queries, in seconds.
+    pub reports_timeout: u64,
+    /// Maximum number of the rows returned by a report.
+    pub reports_max_rows: u32,
+}
+
+/// Named read-only SQL report which can be run via the private API.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ReportDefinition {
+    pub name: String,
+    pub description: String,
+    /// Names of the parameters bound to the `$1`, `$2`, ... placeholders of the query.
+    /// Parameters are bound as text, so the query must cast them, e.g. `$1::date`.
+    #[serde(default)]
+    pub parameters: Vec<String>,
+    /// Single `SELECT` statement of the report.
+    pub sql: String,
 }
 
 impl PrivateApiConfig {
     pub fn bind_addr(&self) -> SocketAddr {
         SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
     }
+
+    pub fn reports_timeout(&self) -> Duration {
+        Duration::from_secs(self.reports_timeout)
+    }
+
+    /// Loads the report definitions, keyed by the report name.
+    pub fn report_definitions(&self) -> HashMap<String, ReportDefinition> {
+        let reports_name = match &self.reports_name {
+            Some(reports_name) => reports_name,
+            None => return HashMap::new(),
+        };
+        let path = format!("./etc/reports/{}.json", reports_name);
+        let definitions: Vec<ReportDefinition> = serde_json::from_str(
+            &fs::read_to_string(&path).expect("File for report definitions not found"),
+        )
+        .expect("Invalid report definitions format");
+
+        let mut reports = HashMap::new();
+        for definition in definitions {
+            // Reports are run as subqueries, so several statements can't be executed at once anyway,
+            // but the error is better detected on the start.
+            if definition.sql.contains(';') {
+                panic!(
+                    "Report {} must consist of a single statement",
+                    definition.name
+                );
+            }
+            if let Some(previous) = reports.insert(definition.name.clone(), definition) {
+                panic!("Report {} is defined twice", previous.name);
+            }
+        }
+        reports
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -192,12 +373,88 @@ pub struct RestApiConfig {
     pub port: u16,
     /// URL to access API server.
     pub url: String,
+    /// Maximum number of the items in the page of the paginated endpoints.
+    pub max_page_size: u32,
+    /// Maximum page sizes overriding the global one for the particular endpoints,
+    /// in the `endpoint_name:max_page_size` form.
+    pub endpoint_max_page_sizes: Vec<String>,
+    /// Latency (in milliseconds) the requests are expected to be served within.
+    pub slo_latency_threshold: u64,
+    /// Percentage of the requests expected to be served successfully within the latency threshold.
+    pub slo_target: f64,
+    /// Objectives overriding the default one for the particular routes,
+    /// in the `route_pattern:latency_threshold:target` form.
+    pub route_slos: Vec<String>,
+}
+
+/// Service level objective of the REST API route.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RouteSlo {
+    pub latency_threshold: Duration,
+    /// Percentage of the requests expected to be served successfully within the latency threshold.
+    pub target: f64,
 }
 
 impl RestApiConfig {
     pub fn bind_addr(&self) -> SocketAddr {
         SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
     }
+
+    /// Returns the maximum page sizes of the endpoints, keyed by the endpoint name.
+    pub fn endpoint_max_page_sizes(&self) -> HashMap<String, u32> {
+        self.endpoint_max_page_sizes
+            .iter()
+            .map(|entry| {
+                let (endpoint, max_page_size) = entry
+                    .split_once(':')
+                    .unwrap_or_else(|| panic!("Invalid endpoint max page size entry: {}", entry));
+                let max_page_size = max_page_size.parse().unwrap_or_else(|err| {
+                    panic!(
+                        "Invalid max page size of the {} endpoint: {}",
+                        endpoint, err
+                    )
+                });
+                (endpoint.to_owned(), max_page_size)
+            })
+            .collect()
+    }
+
+    /// Returns the objective of the routes without their own one.
+    pub fn default_slo(&self) -> RouteSlo {
+        RouteSlo {
+            latency_threshold: Duration::from_millis(self.slo_latency_threshold),
+            target: self.slo_target,
+        }
+    }
+
+    /// Returns the objectives of the routes, keyed by the route pattern.
+    pub fn route_slos(&self) -> HashMap<String, RouteSlo> {
+        self.route_slos
+            .iter()
+            .map(|entry| {
+                // Route patterns may contain colons themselves.
+                let mut parts = entry.rsplitn(3, ':');
+                let (target, latency_threshold, route) =
+                    match (parts.next(), parts.next(), parts.next()) {
+                        (Some(target), Some(latency_threshold), Some(route)) => {
+                            (target, latency_threshold, route)
+                        }
+                        _ => panic!("Invalid route SLO entry: {}", entry),
+                    };
+                let latency_threshold = latency_threshold.parse().unwrap_or_else(|err| {
+                    panic!("Invalid latency threshold of the {} route: {}", route, err)
+                });
+                let target = target.parse().unwrap_or_else(|err| {
+                    panic!("Invalid SLO target of the {} route: {}", route, err)
+                });
+                let slo = RouteSlo {
+                    latency_threshold: Duration::from_millis(latency_threshold),
+                    target,
+                };
+                (route.to_owned(), slo)
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -239,6 +496,20 @@ impl Web3Config {
     }
 }
 
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct GrpcConfig {
+    /// Port to which the gRPC server is listening.
+    pub port: u16,
+    /// URL to access the gRPC server.
+    pub url: String,
+}
+
+impl GrpcConfig {
+    pub fn bind_addr(&self) -> SocketAddr {
+        SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct PrometheusConfig {
     /// Port to which the Prometheus exporter server is listening.
@@ -248,13 +519,15 @@ pub struct PrometheusConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::configs::test_utils::set_env;
+    use crate::configs::test_utils::{addr, hash, set_env};
     use std::net::IpAddr;
 
     fn expected_config() -> ApiConfig {
         ApiConfig {
             common: CommonApiConfig {
                 caches_size: 10_000,
+                cache_redis_url: Some("redis://127.0.0.1:6379".into()),
+                finalized_cache_poll_interval: 1000,
                 forced_exit_minimum_account_age_secs: 0,
                 enforce_pubkey_change_fee: true,
                 max_number_of_transactions_per_batch: 200,
@@ -263,6 +536,38 @@ mod tests {
                 subsidized_ips: vec!["127.0.0.1".to_owned()],
                 max_subsidy_usd_scaled: 20000,
                 subsidy_name: String::from("PartnerName"),
+                cpk_sponsorship_enabled: true,
+                cpk_sponsorship_min_deposit_usd_scaled: 100000000,
+                cpk_sponsorship_max_total_usd_scaled: 10000000000,
+                fee_free_transfer_addresses: vec![addr("de03a0b5963f75f1c8485b355ff6d30f3093bde7")],
+                fee_free_transfer_daily_quota: 1000,
+                withdrawal_daily_limit_usd_scaled: Some(50000000000),
+                withdrawal_policy_hook_url: Some("http://127.0.0.1:8091/check".into()),
+                withdrawal_policy_hook_timeout: 2000,
+                withdrawal_policy_hook_fail_open: false,
+                screening_enabled: true,
+                screening_api_url: Some("http://127.0.0.1:8092/screen".into()),
+                screening_api_timeout: 2000,
+                screening_api_fail_open: false,
+                response_signing_private_key: Some(hash(
+                    "c1783a9a8222e47778911c58bb5aac1343eb425159ff140799e0a283bfb8fa16",
+                )),
+                merkle_proofs_enabled: true,
+                exit_proofs_enabled: true,
+                order_book_enabled: true,
+                standing_orders_enabled: true,
+                session_keys_enabled: true,
+                payment_requests_enabled: true,
+                cosigned_txs_enabled: true,
+                address_attestations_enabled: true,
+                forced_exit_requests_require_attestation: true,
+                statements_require_attestation: true,
+                response_shaping_enabled: true,
+                tx_callbacks_enabled: true,
+                nonce_reservations_enabled: true,
+                create2_factory_registry_enabled: true,
+                withdrawal_hooks_enabled: true,
+                withdrawal_hook_gas_limit: 300000,
             },
             admin: AdminApiConfig {
                 port: 8080,
@@ -272,6 +577,14 @@ mod tests {
             rest: RestApiConfig {
                 port: 3001,
                 url: "http://127.0.0.1:3001".into(),
+                max_page_size: 100,
+                endpoint_max_page_sizes: vec![
+                    "block_transactions:50".to_owned(),
+                    "account_txs:20".to_owned(),
+                ],
+                slo_latency_threshold: 1000,
+                slo_target: 99.0,
+                route_slos: vec!["/api/v0.2/tokens:200:99.9".to_owned()],
             },
             json_rpc: JsonRpcConfig {
                 http_port: 3030,
@@ -285,9 +598,16 @@ mod tests {
                 max_block_range: 10,
                 chain_id: 240,
             },
+            grpc: GrpcConfig {
+                port: 3004,
+                url: "http://127.0.0.1:3004".into(),
+            },
             private: PrivateApiConfig {
                 port: 8090,
                 url: "http://127.0.0.1:8090".into(),
+                reports_name: Some("default".into()),
+                reports_timeout: 30,
+                reports_max_rows: 1000,
             },
             prover: ProverApiConfig {
                 port: 8088,
@@ -305,12 +625,44 @@ mod tests {
     fn from_env() {
         let config = r#"
 API_COMMON_CACHES_SIZE="10000"
+API_COMMON_CACHE_REDIS_URL="redis://127.0.0.1:6379"
+API_COMMON_FINALIZED_CACHE_POLL_INTERVAL="1000"
 API_COMMON_FORCED_EXIT_MINIMUM_ACCOUNT_AGE_SECS="0"
 API_COMMON_FEE_FREE_ACCOUNTS=4078,387
 API_COMMON_ENFORCE_PUBKEY_CHANGE_FEE=true
 API_COMMON_SUBSIDIZED_IPS="127.0.0.1"
 API_COMMON_MAX_SUBSIDY_USD_SCALED=20000
 API_COMMON_SUBSIDY_NAME=PartnerName
+API_COMMON_CPK_SPONSORSHIP_ENABLED=true
+API_COMMON_CPK_SPONSORSHIP_MIN_DEPOSIT_USD_SCALED=100000000
+API_COMMON_CPK_SPONSORSHIP_MAX_TOTAL_USD_SCALED=10000000000
+API_COMMON_FEE_FREE_TRANSFER_ADDRESSES="0xde03a0b5963f75f1c8485b355ff6d30f3093bde7"
+API_COMMON_FEE_FREE_TRANSFER_DAILY_QUOTA=1000
+API_COMMON_WITHDRAWAL_DAILY_LIMIT_USD_SCALED=50000000000
+API_COMMON_WITHDRAWAL_POLICY_HOOK_URL="http://127.0.0.1:8091/check"
+API_COMMON_WITHDRAWAL_POLICY_HOOK_TIMEOUT=2000
+API_COMMON_WITHDRAWAL_POLICY_HOOK_FAIL_OPEN=false
+API_COMMON_SCREENING_ENABLED=true
+API_COMMON_SCREENING_API_URL="http://127.0.0.1:8092/screen"
+API_COMMON_SCREENING_API_TIMEOUT=2000
+API_COMMON_SCREENING_API_FAIL_OPEN=false
+API_COMMON_RESPONSE_SIGNING_PRIVATE_KEY="0xc1783a9a8222e47778911c58bb5aac1343eb425159ff140799e0a283bfb8fa16"
+API_COMMON_MERKLE_PROOFS_ENABLED=true
+API_COMMON_EXIT_PROOFS_ENABLED=true
+API_COMMON_ORDER_BOOK_ENABLED=true
+API_COMMON_STANDING_ORDERS_ENABLED=true
+API_COMMON_SESSION_KEYS_ENABLED=true
+API_COMMON_PAYMENT_REQUESTS_ENABLED=true
+API_COMMON_COSIGNED_TXS_ENABLED=true
+API_COMMON_ADDRESS_ATTESTATIONS_ENABLED=true
+API_COMMON_FORCED_EXIT_REQUESTS_REQUIRE_ATTESTATION=true
+API_COMMON_STATEMENTS_REQUIRE_ATTESTATION=true
+API_COMMON_RESPONSE_SHAPING_ENABLED=true
+API_COMMON_TX_CALLBACKS_ENABLED=true
+API_COMMON_NONCE_RESERVATIONS_ENABLED=true
+API_COMMON_CREATE2_FACTORY_REGISTRY_ENABLED=true
+API_COMMON_WITHDRAWAL_HOOKS_ENABLED=true
+API_COMMON_WITHDRAWAL_HOOK_GAS_LIMIT=300000
 API_COMMON_MAX_NUMBER_OF_TRANSACTIONS_PER_BATCH=200
 API_COMMON_MAX_NUMBER_OF_AUTHORS_PER_BATCH=10
 API_TOKEN_INVALIDATE_TOKEN_CACHE_PERIOD_SEC="10"
@@ -319,6 +671,11 @@ API_ADMIN_URL="http://127.0.0.1:8080"
 API_ADMIN_SECRET_AUTH="sample"
 API_REST_PORT="3001"
 API_REST_URL="http://127.0.0.1:3001"
+API_REST_MAX_PAGE_SIZE="100"
+API_REST_ENDPOINT_MAX_PAGE_SIZES="block_transactions:50,account_txs:20"
+API_REST_SLO_LATENCY_THRESHOLD="1000"
+API_REST_SLO_TARGET="99.0"
+API_REST_ROUTE_SLOS="/api/v0.2/tokens:200:99.9"
 API_JSON_RPC_HTTP_PORT="3030"
 API_JSON_RPC_HTTP_URL="http://127.0.0.1:3030"
 API_JSON_RPC_WS_PORT="3031"
@@ -327,8 +684,13 @@ API_WEB3_PORT="3002"
 API_WEB3_URL="http://127.0.0.1:3002"
 API_WEB3_CHAIN_ID="240"
 API_WEB3_MAX_BLOCK_RANGE="10"
+API_GRPC_PORT="3004"
+API_GRPC_URL="http://127.0.0.1:3004"
 API_PRIVATE_PORT="8090"
 API_PRIVATE_URL="http://127.0.0.1:8090"
+API_PRIVATE_REPORTS_NAME="default"
+API_PRIVATE_REPORTS_TIMEOUT="30"
+API_PRIVATE_REPORTS_MAX_ROWS="1000"
 API_PROVER_PORT="8088"
 API_PROVER_URL="http://127.0.0.1:8088"
 API_PROVER_SECRET_AUTH="sample"
@@ -370,5 +732,35 @@ API_PROMETHEUS_PORT="3312"
             config.web3.bind_addr(),
             SocketAddr::new(bind_broadcast_addr, config.web3.port)
         );
+        assert_eq!(
+            config.grpc.bind_addr(),
+            SocketAddr::new(bind_broadcast_addr, config.grpc.port)
+        );
+        assert_eq!(
+            config.common.finalized_cache_poll_interval(),
+            Duration::from_millis(1000)
+        );
+        assert_eq!(config.private.reports_timeout(), Duration::from_secs(30));
+        assert_eq!(
+            config.rest.endpoint_max_page_sizes(),
+            vec![
+                ("block_transactions".to_owned(), 50),
+                ("account_txs".to_owned(), 20)
+            ]
+            .into_iter()
+            .collect()
+        );
+        assert_eq!(
+            config.rest.route_slos(),
+            vec![(
+                "/api/v0.2/tokens".to_owned(),
+                RouteSlo {
+                    latency_threshold: Duration::from_millis(200),
+                    target: 99.9,
+                }
+            )]
+            .into_iter()
+            .collect()
+        );
     }
 }
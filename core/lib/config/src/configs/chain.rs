@@ -3,6 +3,7 @@ use serde::Deserialize;
 /// Built-in uses
 use std::time::Duration;
 // Local uses
+use zksync_types::gas_prediction::GasPredictor;
 use zksync_types::network::Network;
 use zksync_types::Address;
 
@@ -16,6 +17,16 @@ pub struct ChainConfig {
     pub eth: Eth,
     /// State keeper / block generating configuration.
     pub state_keeper: StateKeeper,
+    /// Prover backpressure configuration.
+    pub backpressure: Backpressure,
+    /// Leader election between the core server instances.
+    pub leadership: Leadership,
+    /// Mempool admission configuration.
+    pub mempool: Mempool,
+    /// Monitoring of the priority operations deadlines.
+    pub priority_op_deadlines: PriorityOpDeadlines,
+    /// Prediction of the L1 gas of the blocks.
+    pub gas_prediction: GasPrediction,
 }
 
 impl ChainConfig {
@@ -24,6 +35,14 @@ impl ChainConfig {
             circuit: envy_load!("circuit", "CHAIN_CIRCUIT_"),
             eth: envy_load!("eth", "CHAIN_ETH_"),
             state_keeper: envy_load!("state_keeper", "CHAIN_STATE_KEEPER_"),
+            backpressure: envy_load!("backpressure", "CHAIN_BACKPRESSURE_"),
+            leadership: envy_load!("leadership", "CHAIN_LEADERSHIP_"),
+            mempool: envy_load!("mempool", "CHAIN_MEMPOOL_"),
+            priority_op_deadlines: envy_load!(
+                "priority_op_deadlines",
+                "CHAIN_PRIORITY_OP_DEADLINES_"
+            ),
+            gas_prediction: envy_load!("gas_prediction", "CHAIN_GAS_PREDICTION_"),
         }
     }
     pub fn max_blocks_to_aggregate(&self) -> u32 {
@@ -94,6 +113,20 @@ pub struct StateKeeper {
     pub block_prove_deadline: u64,
     pub block_execute_deadline: u64,
     pub max_aggregated_tx_gas: usize,
+    /// Minimal fill (in percent of the largest aggregated proof size) that is enough to create
+    /// an aggregated proof before the `block_prove_deadline` is reached.
+    pub aggregated_proof_min_fill_percent: u32,
+}
+
+/// Policy that determines when and how the block proofs are aggregated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofAggregationPolicy {
+    /// Available amounts of block proofs per aggregated proof.
+    pub proof_sizes: Vec<usize>,
+    /// Maximum time to wait for the aggregated proof to be filled since the block creation.
+    pub max_wait: Duration,
+    /// Minimal fill (in percent of the largest proof size) to create the aggregated proof without waiting.
+    pub min_fill_percent: u32,
 }
 
 impl StateKeeper {
@@ -113,6 +146,119 @@ impl StateKeeper {
     pub fn block_execute_deadline(&self) -> Duration {
         Duration::from_secs(self.block_execute_deadline)
     }
+
+    /// Returns the policy used to aggregate block proofs.
+    pub fn proof_aggregation_policy(&self) -> ProofAggregationPolicy {
+        ProofAggregationPolicy {
+            proof_sizes: self.aggregated_proof_sizes.clone(),
+            max_wait: self.block_prove_deadline(),
+            min_fill_percent: self.aggregated_proof_min_fill_percent,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Backpressure {
+    /// Amount of unfinished prover jobs above which the prover is considered overloaded.
+    pub max_prover_queue_depth: u32,
+    /// Age (in seconds) of the oldest unproven block above which the prover is considered overloaded.
+    pub max_unproven_block_age: u64,
+    /// Interval (in seconds) between two checks of the prover load.
+    pub check_interval: u64,
+    /// Multiplier for the amount of miniblock iterations before sealing the block while the prover
+    /// is overloaded. `1` means that the sealing rate doesn't depend on the prover load.
+    pub sealing_slowdown_factor: u64,
+    /// Scale (in percent) of the transaction fees while the prover is overloaded.
+    /// `100` means that the fees don't depend on the prover load.
+    pub fee_scale_percent: u64,
+}
+
+impl Backpressure {
+    pub fn max_unproven_block_age(&self) -> Duration {
+        Duration::from_secs(self.max_unproven_block_age)
+    }
+
+    pub fn check_interval(&self) -> Duration {
+        Duration::from_secs(self.check_interval)
+    }
+
+    /// Returns `true` if any of the prover load indicators exceeds its threshold.
+    pub fn is_prover_overloaded(
+        &self,
+        prover_queue_depth: u32,
+        oldest_unproven_block_age: Duration,
+    ) -> bool {
+        prover_queue_depth > self.max_prover_queue_depth
+            || oldest_unproven_block_age > self.max_unproven_block_age()
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Leadership {
+    /// Interval (in milliseconds) between the attempts of the standby instance to take over
+    /// the leadership, and between the checks of the leader that it still holds it.
+    pub check_interval: u64,
+}
+
+impl Leadership {
+    pub fn check_interval(&self) -> Duration {
+        Duration::from_millis(self.check_interval)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Mempool {
+    /// Amount of the workers admitting the transactions to the mempool in parallel.
+    /// Transactions of the same account are always admitted by the same worker.
+    pub admission_shards: u8,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PriorityOpDeadlines {
+    /// Amount of L1 blocks left until the deadline of the priority operation, below which the block
+    /// containing the operation is sealed right away, even if it's under-filled.
+    pub escalation_blocks: u64,
+    /// Average time (in seconds) between two L1 blocks, used to estimate the time left until the deadlines.
+    pub eth_block_time: u64,
+}
+
+impl PriorityOpDeadlines {
+    /// Estimates the time needed to produce the given amount of L1 blocks.
+    pub fn eth_blocks_duration(&self, blocks: u64) -> Duration {
+        Duration::from_secs(blocks.saturating_mul(self.eth_block_time))
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct GasPrediction {
+    /// Calibration of the per-operation costs of the `commitBlocks` call, in percents.
+    pub commit_scale_percent: u64,
+    /// Calibration of the per-operation costs of the `executeBlocks` call, in percents.
+    pub execute_scale_percent: u64,
+    /// Share of the `commitBlocks` call cost paid by an empty block.
+    pub commit_block_cost: u64,
+    /// Share of the `proveBlocks` call cost paid by a block.
+    pub prove_block_cost: u64,
+    /// Share of the `executeBlocks` call cost paid by an empty block.
+    pub execute_block_cost: u64,
+    /// Predicted L1 gas of the block above which the block is sealed. Zero disables the limit.
+    pub max_block_gas: u64,
+}
+
+impl GasPrediction {
+    pub fn predictor(&self) -> GasPredictor {
+        GasPredictor {
+            commit_scale_percent: self.commit_scale_percent,
+            execute_scale_percent: self.execute_scale_percent,
+            commit_block_cost: self.commit_block_cost,
+            prove_block_cost: self.prove_block_cost,
+            execute_block_cost: self.execute_block_cost,
+        }
+    }
+
+    pub fn max_block_gas(&self) -> Option<u64> {
+        Some(self.max_block_gas).filter(|gas| *gas != 0)
+    }
 }
 
 #[cfg(test)]
@@ -147,6 +293,32 @@ mod tests {
                 block_prove_deadline: 3_000,
                 block_execute_deadline: 4_000,
                 max_aggregated_tx_gas: 4_000_000,
+                aggregated_proof_min_fill_percent: 100,
+            },
+            backpressure: Backpressure {
+                max_prover_queue_depth: 1000,
+                max_unproven_block_age: 3600,
+                check_interval: 10,
+                sealing_slowdown_factor: 2,
+                fee_scale_percent: 150,
+            },
+            leadership: Leadership {
+                check_interval: 1000,
+            },
+            mempool: Mempool {
+                admission_shards: 4,
+            },
+            priority_op_deadlines: PriorityOpDeadlines {
+                escalation_blocks: 1000,
+                eth_block_time: 12,
+            },
+            gas_prediction: GasPrediction {
+                commit_scale_percent: 100,
+                execute_scale_percent: 100,
+                commit_block_cost: 90000,
+                prove_block_cost: 150000,
+                execute_block_cost: 90000,
+                max_block_gas: 0,
             },
         }
     }
@@ -175,6 +347,22 @@ CHAIN_STATE_KEEPER_BLOCK_COMMIT_DEADLINE="300"
 CHAIN_STATE_KEEPER_BLOCK_PROVE_DEADLINE="3000"
 CHAIN_STATE_KEEPER_BLOCK_EXECUTE_DEADLINE="4000"
 CHAIN_STATE_KEEPER_MAX_AGGREGATED_TX_GAS="4000000"
+CHAIN_STATE_KEEPER_AGGREGATED_PROOF_MIN_FILL_PERCENT="100"
+CHAIN_BACKPRESSURE_MAX_PROVER_QUEUE_DEPTH="1000"
+CHAIN_BACKPRESSURE_MAX_UNPROVEN_BLOCK_AGE="3600"
+CHAIN_BACKPRESSURE_CHECK_INTERVAL="10"
+CHAIN_BACKPRESSURE_SEALING_SLOWDOWN_FACTOR="2"
+CHAIN_BACKPRESSURE_FEE_SCALE_PERCENT="150"
+CHAIN_LEADERSHIP_CHECK_INTERVAL="1000"
+CHAIN_MEMPOOL_ADMISSION_SHARDS="4"
+CHAIN_PRIORITY_OP_DEADLINES_ESCALATION_BLOCKS="1000"
+CHAIN_PRIORITY_OP_DEADLINES_ETH_BLOCK_TIME="12"
+CHAIN_GAS_PREDICTION_COMMIT_SCALE_PERCENT="100"
+CHAIN_GAS_PREDICTION_EXECUTE_SCALE_PERCENT="100"
+CHAIN_GAS_PREDICTION_COMMIT_BLOCK_COST="90000"
+CHAIN_GAS_PREDICTION_PROVE_BLOCK_COST="150000"
+CHAIN_GAS_PREDICTION_EXECUTE_BLOCK_COST="90000"
+CHAIN_GAS_PREDICTION_MAX_BLOCK_GAS="0"
         "#;
         set_env(config);
 
@@ -191,5 +379,40 @@ CHAIN_STATE_KEEPER_MAX_AGGREGATED_TX_GAS="4000000"
             config.state_keeper.miniblock_iteration_interval(),
             Duration::from_millis(config.state_keeper.miniblock_iteration_interval)
         );
+        assert_eq!(
+            config.state_keeper.proof_aggregation_policy(),
+            ProofAggregationPolicy {
+                proof_sizes: config.state_keeper.aggregated_proof_sizes.clone(),
+                max_wait: Duration::from_secs(config.state_keeper.block_prove_deadline),
+                min_fill_percent: config.state_keeper.aggregated_proof_min_fill_percent,
+            }
+        );
+        assert_eq!(
+            config.backpressure.max_unproven_block_age(),
+            Duration::from_secs(config.backpressure.max_unproven_block_age)
+        );
+        assert_eq!(
+            config.backpressure.check_interval(),
+            Duration::from_secs(config.backpressure.check_interval)
+        );
+        assert_eq!(
+            config.leadership.check_interval(),
+            Duration::from_millis(config.leadership.check_interval)
+        );
+        assert_eq!(
+            config.priority_op_deadlines.eth_blocks_duration(100),
+            Duration::from_secs(1200)
+        );
+        assert_eq!(config.gas_prediction.predictor(), GasPredictor::default());
+        assert_eq!(config.gas_prediction.max_block_gas(), None);
+        assert!(!config
+            .backpressure
+            .is_prover_overloaded(1000, Duration::from_secs(3600)));
+        assert!(config
+            .backpressure
+            .is_prover_overloaded(1001, Duration::from_secs(0)));
+        assert!(config
+            .backpressure
+            .is_prover_overloaded(0, Duration::from_secs(3601)));
     }
 }
@@ -10,14 +10,59 @@ use crate::envy_load;
 /// Used database configuration.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct DBConfig {
-    /// Amount of open connections to the database held by server in the pool.
+    /// Amount of open connections to the database held by server in the pool of the core actors.
     pub pool_size: usize,
+    /// Amount of open connections held by server in the pool serving the API requests.
+    pub api_pool_size: u32,
+    /// Amount of open connections held by server in the pool of the background jobs.
+    pub background_pool_size: u32,
     /// Database URL.
     pub url: String,
     /// Rejected transactions will be stored in the database for this amount of hours.
     pub rejected_transactions_max_age: u64,
     /// Sleep time (in hours) of the actor responsible for deleting failed transactions from the database.
     pub rejected_transactions_cleaner_interval: u64,
+    /// Rejected and expired transactions are kept searchable for this amount of hours.
+    pub dropped_transactions_max_age: u64,
+    /// Maximum amount of the searchable rejected and expired transactions, the oldest ones are removed first.
+    pub dropped_transactions_max_count: u32,
+    /// Events will be stored in the database for this amount of hours, if the `events-cleaner` component is run.
+    pub events_max_age: u64,
+    /// Sleep time (in seconds) of the actor responsible for deleting old events from the database.
+    pub events_cleaner_interval: u64,
+    /// Storage methods taking longer than this amount of milliseconds are logged as slow queries.
+    pub slow_query_threshold: u64,
+    /// Whether to store the slow queries to the database for the later analysis.
+    pub record_slow_queries: bool,
+    /// Recorded slow queries will be stored in the database for this amount of hours.
+    pub slow_queries_max_age: u64,
+    /// Sleep time (in seconds) of the actor responsible for deleting old slow queries from the database.
+    pub slow_queries_cleaner_interval: u64,
+    /// One of this amount of storage method calls is reported to the `sql` histogram, 1 reports every call.
+    pub metrics_sample_rate: u32,
+    /// Storage method calls taking longer than this amount of milliseconds are always reported to the histogram.
+    pub metrics_full_fidelity_threshold: u64,
+    /// Statements of the API requests taking longer than this amount of milliseconds are cancelled, 0 disables it.
+    pub api_statement_timeout: u64,
+    /// Executed transactions are moved to the archive after this amount of finalized blocks, 0 disables it.
+    pub executed_transactions_retention: u32,
+    /// Executed priority operations are moved to the archive after this amount of finalized blocks, 0 disables it.
+    pub priority_operations_retention: u32,
+    /// Block witnesses are removed after this amount of finalized blocks, 0 disables it.
+    pub witness_retention: u32,
+    /// Sleep time (in seconds) of the actor responsible for pruning the old data.
+    pub pruning_interval: u64,
+    /// Maximum amount of rows pruned at once.
+    pub pruning_batch_size: u32,
+    /// Sleep time (in milliseconds) between the pruned batches to limit the database load.
+    pub pruning_batch_delay: u64,
+    /// Sleep time (in seconds) of the actor checking the invariants of the stored data.
+    pub consistency_check_interval: u64,
+    /// Amount of the last verified blocks checked once the consistency checker is started.
+    pub consistency_check_depth: u32,
+    /// Executed transactions are moved to the history database after this amount of finalized blocks,
+    /// 0 disables it. Only used if `DATABASE_HISTORY_URL` is set.
+    pub history_retention: u32,
 }
 
 impl DBConfig {
@@ -34,6 +79,52 @@ impl DBConfig {
     pub fn rejected_transactions_cleaner_interval(&self) -> time::Duration {
         time::Duration::from_secs(self.rejected_transactions_cleaner_interval)
     }
+
+    pub fn dropped_transactions_max_age(&self) -> time::Duration {
+        time::Duration::from_secs(self.dropped_transactions_max_age * Self::SECS_PER_HOUR)
+    }
+
+    pub fn events_max_age(&self) -> time::Duration {
+        time::Duration::from_secs(self.events_max_age * Self::SECS_PER_HOUR)
+    }
+
+    pub fn events_cleaner_interval(&self) -> time::Duration {
+        time::Duration::from_secs(self.events_cleaner_interval)
+    }
+
+    pub fn slow_query_threshold(&self) -> time::Duration {
+        time::Duration::from_millis(self.slow_query_threshold)
+    }
+
+    pub fn slow_queries_max_age(&self) -> time::Duration {
+        time::Duration::from_secs(self.slow_queries_max_age * Self::SECS_PER_HOUR)
+    }
+
+    pub fn slow_queries_cleaner_interval(&self) -> time::Duration {
+        time::Duration::from_secs(self.slow_queries_cleaner_interval)
+    }
+
+    pub fn metrics_full_fidelity_threshold(&self) -> time::Duration {
+        time::Duration::from_millis(self.metrics_full_fidelity_threshold)
+    }
+
+    pub fn api_statement_timeout(&self) -> Option<time::Duration> {
+        Some(self.api_statement_timeout)
+            .filter(|&timeout| timeout > 0)
+            .map(time::Duration::from_millis)
+    }
+
+    pub fn pruning_interval(&self) -> time::Duration {
+        time::Duration::from_secs(self.pruning_interval)
+    }
+
+    pub fn pruning_batch_delay(&self) -> time::Duration {
+        time::Duration::from_millis(self.pruning_batch_delay)
+    }
+
+    pub fn consistency_check_interval(&self) -> time::Duration {
+        time::Duration::from_secs(self.consistency_check_interval)
+    }
 }
 
 #[cfg(test)]
@@ -44,9 +135,31 @@ mod tests {
     fn expected_config() -> DBConfig {
         DBConfig {
             pool_size: 10,
+            api_pool_size: 20,
+            background_pool_size: 5,
             url: "postgres://postgres@localhost/plasma".into(),
             rejected_transactions_max_age: 336,
             rejected_transactions_cleaner_interval: 24,
+            dropped_transactions_max_age: 168,
+            dropped_transactions_max_count: 1000000,
+            events_max_age: 720,
+            events_cleaner_interval: 3600,
+            slow_query_threshold: 1000,
+            record_slow_queries: true,
+            slow_queries_max_age: 168,
+            slow_queries_cleaner_interval: 3600,
+            metrics_sample_rate: 10,
+            metrics_full_fidelity_threshold: 100,
+            api_statement_timeout: 30000,
+            executed_transactions_retention: 100000,
+            priority_operations_retention: 100000,
+            witness_retention: 1000,
+            pruning_interval: 600,
+            pruning_batch_size: 1000,
+            pruning_batch_delay: 100,
+            consistency_check_interval: 3600,
+            consistency_check_depth: 1000,
+            history_retention: 10000,
         }
     }
 
@@ -54,13 +167,83 @@ mod tests {
     fn from_env() {
         let config = r#"
 DATABASE_POOL_SIZE="10"
+DATABASE_API_POOL_SIZE="20"
+DATABASE_BACKGROUND_POOL_SIZE="5"
 DATABASE_URL="postgres://postgres@localhost/plasma"
 DATABASE_REJECTED_TRANSACTIONS_MAX_AGE="336"
 DATABASE_REJECTED_TRANSACTIONS_CLEANER_INTERVAL="24"
+DATABASE_DROPPED_TRANSACTIONS_MAX_AGE="168"
+DATABASE_DROPPED_TRANSACTIONS_MAX_COUNT="1000000"
+DATABASE_EVENTS_MAX_AGE="720"
+DATABASE_EVENTS_CLEANER_INTERVAL="3600"
+DATABASE_SLOW_QUERY_THRESHOLD="1000"
+DATABASE_RECORD_SLOW_QUERIES="true"
+DATABASE_SLOW_QUERIES_MAX_AGE="168"
+DATABASE_SLOW_QUERIES_CLEANER_INTERVAL="3600"
+DATABASE_METRICS_SAMPLE_RATE="10"
+DATABASE_METRICS_FULL_FIDELITY_THRESHOLD="100"
+DATABASE_API_STATEMENT_TIMEOUT="30000"
+DATABASE_EXECUTED_TRANSACTIONS_RETENTION="100000"
+DATABASE_PRIORITY_OPERATIONS_RETENTION="100000"
+DATABASE_WITNESS_RETENTION="1000"
+DATABASE_PRUNING_INTERVAL="600"
+DATABASE_PRUNING_BATCH_SIZE="1000"
+DATABASE_PRUNING_BATCH_DELAY="100"
+DATABASE_CONSISTENCY_CHECK_INTERVAL="3600"
+DATABASE_CONSISTENCY_CHECK_DEPTH="1000"
+DATABASE_HISTORY_RETENTION="10000"
         "#;
         set_env(config);
 
         let actual = DBConfig::from_env();
         assert_eq!(actual, expected_config());
     }
+
+    /// Checks the correctness of the config helper methods.
+    #[test]
+    fn methods() {
+        let config = expected_config();
+
+        assert_eq!(
+            config.dropped_transactions_max_age(),
+            time::Duration::from_secs(168 * 3600)
+        );
+        assert_eq!(
+            config.events_max_age(),
+            time::Duration::from_secs(720 * 3600)
+        );
+        assert_eq!(
+            config.events_cleaner_interval(),
+            time::Duration::from_secs(3600)
+        );
+        assert_eq!(
+            config.slow_query_threshold(),
+            time::Duration::from_millis(1000)
+        );
+        assert_eq!(
+            config.slow_queries_max_age(),
+            time::Duration::from_secs(168 * 3600)
+        );
+        assert_eq!(
+            config.slow_queries_cleaner_interval(),
+            time::Duration::from_secs(3600)
+        );
+        assert_eq!(
+            config.metrics_full_fidelity_threshold(),
+            time::Duration::from_millis(100)
+        );
+        assert_eq!(
+            config.api_statement_timeout(),
+            Some(time::Duration::from_millis(30000))
+        );
+        assert_eq!(config.pruning_interval(), time::Duration::from_secs(600));
+        assert_eq!(
+            config.pruning_batch_delay(),
+            time::Duration::from_millis(100)
+        );
+        assert_eq!(
+            config.consistency_check_interval(),
+            time::Duration::from_secs(3600)
+        );
+    }
 }
@@ -0,0 +1,108 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use serde::Deserialize;
+// Workspace uses
+use zksync_types::{Address, H256};
+// Local uses
+use crate::envy_load;
+
+/// Configuration for the relayer performing the first L1 deposits on behalf of the users.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct DepositRelayerConfig {
+    /// Whether the relay requests are accepted and processed.
+    pub enabled: bool,
+    /// L1 private key of the relayer account, which pays the gas of the relayed deposits.
+    pub sender_eth_private_key: H256,
+    /// Address of the relayer account. The relay fees are deposited to this address on L2.
+    pub sender_address: Address,
+    /// Amount of confirmations required for each of the relayer transactions.
+    pub wait_confirmations: u64,
+    /// Interval between two checks for the pending jobs and the sent transactions, in milliseconds.
+    pub poll_interval: u64,
+    /// Time to wait for the relayer transaction to be confirmed, in seconds.
+    pub tx_timeout: u64,
+    /// Gas limit of each of the relayer transactions.
+    pub tx_gas_limit: u64,
+    /// Total amount of gas charged for the relayed deposit.
+    pub relay_gas_amount: u64,
+    /// Minimum time left until the permit deadline for the request to be accepted, in seconds.
+    pub min_permit_ttl: u64,
+    /// Maximum amount of the relay requests from the same address per day.
+    pub max_jobs_per_address: u32,
+    /// Maximum amount of the jobs waiting to be processed.
+    pub max_pending_jobs: u32,
+}
+
+impl DepositRelayerConfig {
+    pub fn from_env() -> Self {
+        envy_load!("deposit_relayer", "DEPOSIT_RELAYER_")
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval)
+    }
+
+    pub fn tx_timeout(&self) -> Duration {
+        Duration::from_secs(self.tx_timeout)
+    }
+
+    pub fn min_permit_ttl(&self) -> Duration {
+        Duration::from_secs(self.min_permit_ttl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::{addr, hash, set_env};
+
+    fn expected_config() -> DepositRelayerConfig {
+        DepositRelayerConfig {
+            enabled: true,
+            sender_eth_private_key: hash(
+                "c8a14a94b5ed8c2e41274f57be42ad536738e9dd7b98dbb1e2aa72bea186806b",
+            ),
+            sender_address: addr("0a84e5f04c775360cb7ac2ad148ab7c93f7d470a"),
+            wait_confirmations: 1,
+            poll_interval: 1000,
+            tx_timeout: 600,
+            tx_gas_limit: 300000,
+            relay_gas_amount: 450000,
+            min_permit_ttl: 1800,
+            max_jobs_per_address: 1,
+            max_pending_jobs: 100,
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+DEPOSIT_RELAYER_ENABLED="true"
+DEPOSIT_RELAYER_SENDER_ETH_PRIVATE_KEY="0xc8a14a94b5ed8c2e41274f57be42ad536738e9dd7b98dbb1e2aa72bea186806b"
+DEPOSIT_RELAYER_SENDER_ADDRESS="0x0a84e5f04c775360cb7ac2ad148ab7c93f7d470a"
+DEPOSIT_RELAYER_WAIT_CONFIRMATIONS="1"
+DEPOSIT_RELAYER_POLL_INTERVAL="1000"
+DEPOSIT_RELAYER_TX_TIMEOUT="600"
+DEPOSIT_RELAYER_TX_GAS_LIMIT="300000"
+DEPOSIT_RELAYER_RELAY_GAS_AMOUNT="450000"
+DEPOSIT_RELAYER_MIN_PERMIT_TTL="1800"
+DEPOSIT_RELAYER_MAX_JOBS_PER_ADDRESS="1"
+DEPOSIT_RELAYER_MAX_PENDING_JOBS="100"
+        "#;
+        set_env(config);
+
+        let actual = DepositRelayerConfig::from_env();
+        assert_eq!(actual, expected_config());
+    }
+
+    /// Checks the correctness of the config helper methods.
+    #[test]
+    fn methods() {
+        let config = expected_config();
+
+        assert_eq!(config.poll_interval(), Duration::from_millis(1000));
+        assert_eq!(config.tx_timeout(), Duration::from_secs(600));
+        assert_eq!(config.min_permit_ttl(), Duration::from_secs(1800));
+    }
+}
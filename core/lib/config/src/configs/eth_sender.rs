@@ -14,6 +14,8 @@ pub struct ETHSenderConfig {
     pub sender: Sender,
     /// Options related to the `gas_adjuster` submodule.
     pub gas_price_limit: GasLimit,
+    /// Options related to the calls notifying the recipient contracts about the withdrawals.
+    pub withdrawal_hooks: WithdrawalHooks,
 }
 
 impl ETHSenderConfig {
@@ -24,6 +26,10 @@ impl ETHSenderConfig {
                 "eth_sender.gas_price_limit",
                 "ETH_SENDER_GAS_PRICE_LIMIT_"
             ),
+            withdrawal_hooks: envy_load!(
+                "eth_sender.withdrawal_hooks",
+                "ETH_SENDER_WITHDRAWAL_HOOKS_"
+            ),
         }
     }
 }
@@ -77,6 +83,28 @@ impl GasLimit {
     }
 }
 
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct WithdrawalHooks {
+    /// Whether the calls attached to the withdrawals are sent once the withdrawals are executed.
+    pub is_enabled: bool,
+    /// L1 private key of the account sending the calls. The operator account is not used,
+    /// so the failing calls can't delay the blocks.
+    pub sender_eth_private_key: H256,
+    /// Address of the account sending the calls.
+    pub sender_address: Address,
+    /// Time to wait for the call to be confirmed, in seconds.
+    pub tx_timeout: u64,
+    /// Gas limit of each of the calls.
+    pub tx_gas_limit: u64,
+}
+
+impl WithdrawalHooks {
+    /// Converts `self.tx_timeout` into `Duration`.
+    pub fn tx_timeout(&self) -> Duration {
+        Duration::from_secs(self.tx_timeout)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,6 +129,15 @@ mod tests {
                 sample_interval: 15,
                 scale_factor: 1.0f64,
             },
+            withdrawal_hooks: WithdrawalHooks {
+                is_enabled: true,
+                sender_eth_private_key: hash(
+                    "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+                ),
+                sender_address: addr("70997970C51812dc3A010C7d01b50e0d17dc79C8"),
+                tx_timeout: 600,
+                tx_gas_limit: 300000,
+            },
         }
     }
 
@@ -118,6 +155,11 @@ ETH_SENDER_GAS_PRICE_LIMIT_DEFAULT="400000000000"
 ETH_SENDER_GAS_PRICE_LIMIT_UPDATE_INTERVAL="150"
 ETH_SENDER_GAS_PRICE_LIMIT_SAMPLE_INTERVAL="15"
 ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
+ETH_SENDER_WITHDRAWAL_HOOKS_IS_ENABLED="true"
+ETH_SENDER_WITHDRAWAL_HOOKS_SENDER_ETH_PRIVATE_KEY="0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+ETH_SENDER_WITHDRAWAL_HOOKS_SENDER_ADDRESS="0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+ETH_SENDER_WITHDRAWAL_HOOKS_TX_TIMEOUT="600"
+ETH_SENDER_WITHDRAWAL_HOOKS_TX_GAS_LIMIT="300000"
         "#;
         set_env(config);
 
@@ -143,5 +185,9 @@ ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
             config.gas_price_limit.sample_interval(),
             Duration::from_secs(config.gas_price_limit.sample_interval)
         );
+        assert_eq!(
+            config.withdrawal_hooks.tx_timeout(),
+            Duration::from_secs(600)
+        );
     }
 }
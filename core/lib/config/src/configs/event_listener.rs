@@ -1,5 +1,5 @@
 // Built-in uses
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 
 // External uses
 use serde::Deserialize;
@@ -7,6 +7,18 @@ use serde::Deserialize;
 // Local uses
 use crate::envy_load;
 
+/// External message broker the events are published to.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EventSinkType {
+    /// Events are only streamed to the WebSocket clients.
+    None,
+    /// Events are published to Kafka through the Kafka REST proxy.
+    Kafka,
+    /// Events are published to NATS.
+    Nats,
+}
+
 /// Configuration for the Event listener crate.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct EventListenerConfig {
@@ -17,6 +29,15 @@ pub struct EventListenerConfig {
     /// PostgreSQL channel name to listen on. Must be equal to the one
     /// hardcoded into database migrations.
     pub channel_name: String,
+    /// Message broker to publish the events to.
+    pub sink: EventSinkType,
+    /// Address of the message broker: URL of the Kafka REST proxy or `host:port` of the NATS server.
+    pub sink_url: String,
+    /// Prefix of the topic (subject) names, events are published to `<prefix>.blocks`,
+    /// `<prefix>.transactions` and `<prefix>.accounts`.
+    pub sink_topic_prefix: String,
+    /// Delay before retrying to publish the events after a broker error, in milliseconds.
+    pub sink_retry_interval: u64,
 }
 
 impl EventListenerConfig {
@@ -27,6 +48,10 @@ impl EventListenerConfig {
     pub fn ws_bind_addr(&self) -> SocketAddr {
         SocketAddr::new("0.0.0.0".parse().unwrap(), self.ws_port)
     }
+
+    pub fn sink_retry_interval(&self) -> Duration {
+        Duration::from_millis(self.sink_retry_interval)
+    }
 }
 
 #[cfg(test)]
@@ -40,6 +65,10 @@ mod tests {
             ws_port: 65535,
             ws_url: "ws://localhost:12345".into(),
             channel_name: "zksync_event_channel".into(),
+            sink: EventSinkType::Nats,
+            sink_url: "127.0.0.1:4222".into(),
+            sink_topic_prefix: "zksync".into(),
+            sink_retry_interval: 5000,
         }
     }
 
@@ -49,6 +78,10 @@ mod tests {
 EVENT_LISTENER_WS_URL="ws://localhost:12345"
 EVENT_LISTENER_WS_PORT="65535"
 EVENT_LISTENER_CHANNEL_NAME="zksync_event_channel"
+EVENT_LISTENER_SINK="nats"
+EVENT_LISTENER_SINK_URL="127.0.0.1:4222"
+EVENT_LISTENER_SINK_TOPIC_PREFIX="zksync"
+EVENT_LISTENER_SINK_RETRY_INTERVAL="5000"
         "#;
         set_env(config);
 
@@ -65,5 +98,6 @@ EVENT_LISTENER_CHANNEL_NAME="zksync_event_channel"
             config.ws_bind_addr(),
             SocketAddr::new(bind_addr, config.ws_port)
         );
+        assert_eq!(config.sink_retry_interval(), Duration::from_millis(5000));
     }
 }
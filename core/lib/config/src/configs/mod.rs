@@ -1,17 +1,19 @@
 // Public re-exports
 pub use self::{
     api::ApiConfig, chain::ChainConfig, contracts::ContractsConfig, database::DBConfig,
+    deposit_relayer::DepositRelayerConfig,
     dev_liquidity_token_watcher::DevLiquidityTokenWatcherConfig, eth_client::ETHClientConfig,
     eth_sender::ETHSenderConfig, eth_watch::ETHWatchConfig, event_listener::EventListenerConfig,
     forced_exit_requests::ForcedExitRequestsConfig, gateway_watcher::GatewayWatcherConfig,
-    misc::MiscConfig, prover::ProverConfig, ticker::TickerConfig,
-    token_handler::TokenHandlerConfig,
+    misc::MiscConfig, object_store::ObjectStoreConfig, prover::ProverConfig, ticker::TickerConfig,
+    token_handler::TokenHandlerConfig, webhooks::WebhooksConfig,
 };
 
 pub mod api;
 pub mod chain;
 pub mod contracts;
 pub mod database;
+pub mod deposit_relayer;
 pub mod dev_liquidity_token_watcher;
 pub mod eth_client;
 pub mod eth_sender;
@@ -20,9 +22,11 @@ pub mod event_listener;
 pub mod forced_exit_requests;
 pub mod gateway_watcher;
 pub mod misc;
+pub mod object_store;
 pub mod prover;
 pub mod ticker;
 pub mod token_handler;
+pub mod webhooks;
 
 #[cfg(test)]
 pub(crate) mod test_utils;
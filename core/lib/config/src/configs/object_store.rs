@@ -0,0 +1,88 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use serde::Deserialize;
+// Local uses
+use crate::envy_load;
+
+/// Configuration for the S3-compatible object storage holding the archive of the pruned data.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ObjectStoreConfig {
+    /// URL of the object storage, e.g. `https://s3.eu-central-1.amazonaws.com`.
+    /// If empty, the archive is not written by the pruner and not read by the API.
+    pub endpoint: String,
+    /// Bucket the objects are stored in. Objects are addressed in the path style.
+    pub bucket: String,
+    /// Region used to sign the requests.
+    pub region: String,
+    /// Prefix prepended to the keys of all the objects.
+    pub key_prefix: String,
+    /// Access key ID of the object storage account.
+    pub access_key_id: String,
+    /// Secret access key of the object storage account.
+    pub secret_access_key: String,
+    /// Timeout of a single object storage request, in seconds.
+    pub request_timeout: u64,
+}
+
+impl ObjectStoreConfig {
+    pub fn from_env() -> Self {
+        envy_load!("object_store", "OBJECT_STORE_")
+    }
+
+    /// Returns `true` if the object storage is configured.
+    pub fn is_enabled(&self) -> bool {
+        !self.endpoint.trim().is_empty()
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::set_env;
+
+    fn expected_config() -> ObjectStoreConfig {
+        ObjectStoreConfig {
+            endpoint: "http://127.0.0.1:9000".into(),
+            bucket: "zksync-archive".into(),
+            region: "us-east-1".into(),
+            key_prefix: "localhost".into(),
+            access_key_id: "minioadmin".into(),
+            secret_access_key: "minioadmin".into(),
+            request_timeout: 30,
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+OBJECT_STORE_ENDPOINT="http://127.0.0.1:9000"
+OBJECT_STORE_BUCKET="zksync-archive"
+OBJECT_STORE_REGION="us-east-1"
+OBJECT_STORE_KEY_PREFIX="localhost"
+OBJECT_STORE_ACCESS_KEY_ID="minioadmin"
+OBJECT_STORE_SECRET_ACCESS_KEY="minioadmin"
+OBJECT_STORE_REQUEST_TIMEOUT="30"
+        "#;
+        set_env(config);
+
+        let actual = ObjectStoreConfig::from_env();
+        assert_eq!(actual, expected_config());
+    }
+
+    /// Checks the correctness of the config helper methods.
+    #[test]
+    fn methods() {
+        let mut config = expected_config();
+
+        assert!(config.is_enabled());
+        assert_eq!(config.request_timeout(), Duration::from_secs(30));
+
+        config.endpoint = String::new();
+        assert!(!config.is_enabled());
+    }
+}
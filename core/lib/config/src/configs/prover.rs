@@ -11,6 +11,7 @@ pub struct ProverConfig {
     pub prover: Prover,
     pub core: Core,
     pub witness_generator: WitnessGenerator,
+    pub remote: Remote,
 }
 
 impl ProverConfig {
@@ -19,10 +20,24 @@ impl ProverConfig {
             prover: envy_load!("prover.prover", "PROVER_PROVER_"),
             core: envy_load!("prover.core", "PROVER_CORE_"),
             witness_generator: envy_load!("prover.witness_generator", "PROVER_WITNESS_GENERATOR_"),
+            remote: envy_load!("prover.remote", "PROVER_REMOTE_"),
         }
     }
 }
 
+/// Backend that is used by the prover application to generate proofs.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProverBackend {
+    /// Local PLONK prover.
+    PlonkStepByStep,
+    /// Prover that returns precomputed proofs, used for development purposes.
+    Dummy,
+    /// External proving service (e.g. GPU prover service or proving marketplace)
+    /// which receives the prover job data over HTTP.
+    Remote,
+}
+
 /// Actual prover application settings.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Prover {
@@ -34,6 +49,8 @@ pub struct Prover {
     pub request_timeout: u64,
     /// Flag for dying after proving cycle
     pub die_after_proof: bool,
+    /// Backend used to generate proofs.
+    pub backend: ProverBackend,
 }
 
 impl Prover {
@@ -84,6 +101,22 @@ impl WitnessGenerator {
     }
 }
 
+/// Settings of the remote proving backend.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Remote {
+    /// URL of the proving service.
+    pub url: String,
+    /// Timeout for the single proof generation request in seconds.
+    pub request_timeout: u64,
+}
+
+impl Remote {
+    /// Converts `self.request_timeout` into `Duration`.
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,6 +129,7 @@ mod tests {
                 cycle_wait: 500,
                 request_timeout: 10,
                 die_after_proof: false,
+                backend: ProverBackend::PlonkStepByStep,
             },
             core: Core {
                 gone_timeout: 60000,
@@ -105,6 +139,10 @@ mod tests {
                 prepare_data_interval: 500,
                 witness_generators: 2,
             },
+            remote: Remote {
+                url: "http://127.0.0.1:8089".into(),
+                request_timeout: 3600,
+            },
         }
     }
 
@@ -115,10 +153,13 @@ PROVER_PROVER_HEARTBEAT_INTERVAL="1000"
 PROVER_PROVER_CYCLE_WAIT="500"
 PROVER_PROVER_REQUEST_TIMEOUT="10"
 PROVER_PROVER_DIE_AFTER_PROOF=false
+PROVER_PROVER_BACKEND="plonk_step_by_step"
 PROVER_CORE_GONE_TIMEOUT="60000"
 PROVER_CORE_IDLE_PROVERS="1"
 PROVER_WITNESS_GENERATOR_PREPARE_DATA_INTERVAL="500"
 PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
+PROVER_REMOTE_URL="http://127.0.0.1:8089"
+PROVER_REMOTE_REQUEST_TIMEOUT="3600"
         "#;
         set_env(config);
 
@@ -153,5 +194,10 @@ PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
             config.witness_generator.prepare_data_interval(),
             Duration::from_millis(config.witness_generator.prepare_data_interval)
         );
+
+        assert_eq!(
+            config.remote.request_timeout(),
+            Duration::from_secs(config.remote.request_timeout)
+        );
     }
 }
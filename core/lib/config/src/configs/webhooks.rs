@@ -0,0 +1,122 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use serde::Deserialize;
+// Local uses
+use crate::envy_load;
+
+/// Configuration for the webhook delivery service.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct WebhooksConfig {
+    /// Interval between two checks for the pending deliveries, in milliseconds.
+    pub poll_interval: u64,
+    /// Maximum amount of deliveries sent per one check.
+    pub batch_size: u32,
+    /// Timeout of a single delivery request, in seconds.
+    pub request_timeout: u64,
+    /// Amount of delivery attempts after which the delivery is moved to the dead letters.
+    pub max_attempts: u32,
+    /// Delay before the first retry, in seconds. Doubled on every next retry.
+    pub retry_backoff: u64,
+    /// Maximum delay between two retries, in seconds.
+    pub max_retry_backoff: u64,
+    /// Amount of hours the outbox events are kept after they are stored. Events are removed
+    /// after this period once they have no pending deliveries.
+    pub outbox_retention: u64,
+    /// Interval between two runs of the outbox cleaner, in seconds.
+    pub outbox_cleaner_interval: u64,
+}
+
+impl WebhooksConfig {
+    pub fn from_env() -> Self {
+        envy_load!("webhooks", "WEBHOOKS_")
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval)
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout)
+    }
+
+    pub fn outbox_retention(&self) -> Duration {
+        Duration::from_secs(self.outbox_retention * 60 * 60)
+    }
+
+    pub fn outbox_cleaner_interval(&self) -> Duration {
+        Duration::from_secs(self.outbox_cleaner_interval)
+    }
+
+    /// Returns the delay before the next attempt after the given amount of failed attempts,
+    /// or `None` if the delivery should not be retried anymore.
+    pub fn retry_delay(&self, failed_attempts: u32) -> Option<Duration> {
+        if failed_attempts == 0 || failed_attempts >= self.max_attempts {
+            return None;
+        }
+        let multiplier = 2u64.saturating_pow(failed_attempts - 1);
+        let delay = self
+            .retry_backoff
+            .saturating_mul(multiplier)
+            .min(self.max_retry_backoff);
+        Some(Duration::from_secs(delay))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::set_env;
+
+    fn expected_config() -> WebhooksConfig {
+        WebhooksConfig {
+            poll_interval: 1000,
+            batch_size: 100,
+            request_timeout: 10,
+            max_attempts: 5,
+            retry_backoff: 30,
+            max_retry_backoff: 100,
+            outbox_retention: 168,
+            outbox_cleaner_interval: 3600,
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+WEBHOOKS_POLL_INTERVAL="1000"
+WEBHOOKS_BATCH_SIZE="100"
+WEBHOOKS_REQUEST_TIMEOUT="10"
+WEBHOOKS_MAX_ATTEMPTS="5"
+WEBHOOKS_RETRY_BACKOFF="30"
+WEBHOOKS_MAX_RETRY_BACKOFF="100"
+WEBHOOKS_OUTBOX_RETENTION="168"
+WEBHOOKS_OUTBOX_CLEANER_INTERVAL="3600"
+        "#;
+        set_env(config);
+
+        let actual = WebhooksConfig::from_env();
+        assert_eq!(actual, expected_config());
+    }
+
+    /// Checks the correctness of the config helper methods.
+    #[test]
+    fn methods() {
+        let config = expected_config();
+
+        assert_eq!(config.poll_interval(), Duration::from_millis(1000));
+        assert_eq!(config.request_timeout(), Duration::from_secs(10));
+        assert_eq!(
+            config.outbox_retention(),
+            Duration::from_secs(168 * 60 * 60)
+        );
+        assert_eq!(config.outbox_cleaner_interval(), Duration::from_secs(3600));
+        assert_eq!(config.retry_delay(0), None);
+        assert_eq!(config.retry_delay(1), Some(Duration::from_secs(30)));
+        assert_eq!(config.retry_delay(2), Some(Duration::from_secs(60)));
+        // Capped by the `max_retry_backoff`.
+        assert_eq!(config.retry_delay(3), Some(Duration::from_secs(100)));
+        assert_eq!(config.retry_delay(4), Some(Duration::from_secs(100)));
+        assert_eq!(config.retry_delay(5), None);
+    }
+}
@@ -1,9 +1,9 @@
 #![allow(clippy::derive_partial_eq_without_eq)]
 pub use crate::configs::{
-    ApiConfig, ChainConfig, ContractsConfig, DBConfig, DevLiquidityTokenWatcherConfig,
-    ETHClientConfig, ETHSenderConfig, ETHWatchConfig, EventListenerConfig,
-    ForcedExitRequestsConfig, GatewayWatcherConfig, MiscConfig, ProverConfig, TickerConfig,
-    TokenHandlerConfig,
+    ApiConfig, ChainConfig, ContractsConfig, DBConfig, DepositRelayerConfig,
+    DevLiquidityTokenWatcherConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
+    EventListenerConfig, ForcedExitRequestsConfig, GatewayWatcherConfig, MiscConfig,
+    ObjectStoreConfig, ProverConfig, TickerConfig, TokenHandlerConfig, WebhooksConfig,
 };
 
 pub mod configs;
@@ -15,6 +15,7 @@ pub struct ZkSyncConfig {
     pub chain: ChainConfig,
     pub contracts: ContractsConfig,
     pub db: DBConfig,
+    pub deposit_relayer: DepositRelayerConfig,
     pub eth_client: ETHClientConfig,
     pub eth_sender: ETHSenderConfig,
     pub eth_watch: ETHWatchConfig,
@@ -24,6 +25,8 @@ pub struct ZkSyncConfig {
     pub prover: ProverConfig,
     pub ticker: TickerConfig,
     pub forced_exit_requests: ForcedExitRequestsConfig,
+    pub webhooks: WebhooksConfig,
+    pub object_store: ObjectStoreConfig,
 }
 
 impl ZkSyncConfig {
@@ -33,6 +36,7 @@ impl ZkSyncConfig {
             chain: ChainConfig::from_env(),
             contracts: ContractsConfig::from_env(),
             db: DBConfig::from_env(),
+            deposit_relayer: DepositRelayerConfig::from_env(),
             eth_client: ETHClientConfig::from_env(),
             eth_sender: ETHSenderConfig::from_env(),
             eth_watch: ETHWatchConfig::from_env(),
@@ -42,6 +46,8 @@ impl ZkSyncConfig {
             prover: ProverConfig::from_env(),
             ticker: TickerConfig::from_env(),
             forced_exit_requests: ForcedExitRequestsConfig::from_env(),
+            webhooks: WebhooksConfig::from_env(),
+            object_store: ObjectStoreConfig::from_env(),
         }
     }
 }
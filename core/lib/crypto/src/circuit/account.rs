@@ -41,6 +41,26 @@ pub struct CircuitAccount<E: RescueEngine> {
 
 impl<E: RescueEngine> GetBits for CircuitAccount<E> {
     fn get_bits_le(&self) -> Vec<bool> {
+        Self::leaf_bits_le(
+            &self.subtree.hasher,
+            &self.nonce,
+            &self.pub_key_hash,
+            &self.address,
+            self.subtree.root_hash(),
+        )
+    }
+}
+
+impl<E: RescueEngine> CircuitAccount<E> {
+    /// Returns the content of the account tree leaf with the given fields and the root hash
+    /// of the balance tree. Allows to check the account leaf without building its balance tree.
+    pub fn leaf_bits_le(
+        hasher: &RescueHasher<E>,
+        nonce: &E::Fr,
+        pub_key_hash: &E::Fr,
+        address: &E::Fr,
+        balance_root: E::Fr,
+    ) -> Vec<bool> {
         debug_assert_eq!(
             params::FR_BIT_WIDTH,
             E::Fr::NUM_BITS as usize,
@@ -48,17 +68,16 @@ impl<E: RescueEngine> GetBits for CircuitAccount<E> {
         );
         let mut leaf_content = Vec::new();
 
-        leaf_content.extend(self.nonce.get_bits_le_fixed(params::NONCE_BIT_WIDTH)); //32
+        leaf_content.extend(nonce.get_bits_le_fixed(params::NONCE_BIT_WIDTH)); //32
         leaf_content.extend(
-            self.pub_key_hash
-                .get_bits_le_fixed(params::NEW_PUBKEY_HASH_WIDTH), //160
+            pub_key_hash.get_bits_le_fixed(params::NEW_PUBKEY_HASH_WIDTH), //160
         );
         leaf_content.extend(
-            self.address.get_bits_le_fixed(params::ADDRESS_WIDTH), //160
+            address.get_bits_le_fixed(params::ADDRESS_WIDTH), //160
         );
 
         // calculate hash of the subroot using algebraic hash
-        let state_root = self.get_state_root();
+        let state_root = Self::state_root(hasher, balance_root);
 
         let mut state_tree_hash_bits = state_root.get_bits_le_fixed(params::FR_BIT_WIDTH);
         state_tree_hash_bits.resize(params::FR_BIT_WIDTH_PADDED, false);
@@ -73,17 +92,11 @@ impl<E: RescueEngine> GetBits for CircuitAccount<E> {
 
         leaf_content
     }
-}
-
-impl<E: RescueEngine> CircuitAccount<E> {
-    fn get_state_root(&self) -> E::Fr {
-        let balance_root = self.subtree.root_hash();
 
+    fn state_root(hasher: &RescueHasher<E>, balance_root: E::Fr) -> E::Fr {
         let state_root_padding = E::Fr::zero();
 
-        self.subtree
-            .hasher
-            .hash_elements(vec![balance_root, state_root_padding])
+        hasher.hash_elements(vec![balance_root, state_root_padding])
     }
 }
 
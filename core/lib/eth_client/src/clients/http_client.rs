@@ -443,6 +443,10 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                 };
                 let confirmations = current_block.saturating_sub(tx_block_number.as_u64());
                 let success = status.as_u64() == 1;
+                let gas_used = receipt.as_ref().and_then(|receipt| receipt.gas_used);
+                let effective_gas_price = receipt
+                    .as_ref()
+                    .and_then(|receipt| receipt.effective_gas_price);
 
                 // Set the receipt only for failures.
                 let receipt = if success {
@@ -455,6 +459,8 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                     confirmations,
                     success,
                     receipt,
+                    gas_used,
+                    effective_gas_price,
                 }))
             }
             _ => Ok(None),
@@ -16,12 +16,16 @@ use crate::{
     SignedCallResult,
 };
 
+/// Gas reported by the mock for every executed transaction.
+pub const MOCK_GAS_USED: u64 = 100_000;
+
 #[derive(Debug)]
 struct MockEthereumInner {
     block_number: u64,
     gas_price: U256,
     tx_statuses: Arc<RwLock<HashMap<H256, ExecutedTxStatus>>>,
     sent_txs: Arc<RwLock<HashSet<Vec<u8>>>>,
+    txs: Arc<RwLock<HashMap<H256, Transaction>>>,
 }
 
 /// Mock Ethereum client is capable of recording all the incoming requests for the further analysis.
@@ -37,6 +41,7 @@ impl Default for MockEthereumInner {
             gas_price: 100.into(),
             tx_statuses: Default::default(),
             sent_txs: Default::default(),
+            txs: Default::default(),
         }
     }
 }
@@ -83,6 +88,8 @@ impl MockEthereum {
             confirmations,
             success: true,
             receipt: None,
+            gas_used: Some(MOCK_GAS_USED.into()),
+            effective_gas_price: None,
         };
         self.inner.tx_statuses.write().await.insert(tx_hash, status);
     }
@@ -95,9 +102,16 @@ impl MockEthereum {
             confirmations,
             success: false,
             receipt: Some(Default::default()),
+            gas_used: Some(MOCK_GAS_USED.into()),
+            effective_gas_price: None,
         };
         self.inner.tx_statuses.write().await.insert(*hash, status);
     }
+
+    /// Adds a transaction to be returned by `get_tx`.
+    pub async fn add_tx(&mut self, tx: Transaction) {
+        self.inner.txs.write().await.insert(tx.hash, tx);
+    }
     pub async fn get_tx_status(
         &self,
         hash: H256,
@@ -168,8 +182,10 @@ impl MockEthereum {
         unreachable!()
     }
 
+    /// Returns the amount of the executed transactions, since the mock doesn't track
+    /// the sender nonces.
     pub async fn current_nonce(&self) -> Result<U256, Error> {
-        unreachable!()
+        Ok(self.inner.tx_statuses.read().await.len().into())
     }
 
     pub async fn sender_eth_balance(&self) -> Result<U256, Error> {
@@ -266,7 +282,11 @@ impl MockEthereum {
         unreachable!()
     }
 
-    pub async fn get_tx(&self, _hash: H256) -> Result<Option<Transaction>, anyhow::Error> {
-        unreachable!()
+    /// Returns the transaction added with `add_tx`, the lookup of any other transaction fails.
+    pub async fn get_tx(&self, hash: H256) -> Result<Option<Transaction>, anyhow::Error> {
+        match self.inner.txs.read().await.get(&hash) {
+            Some(tx) => Ok(Some(tx.clone())),
+            None => Err(anyhow::format_err!("Transaction {:?} is not known", hash)),
+        }
     }
 }
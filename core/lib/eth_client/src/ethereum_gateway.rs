@@ -31,6 +31,10 @@ pub struct ExecutedTxStatus {
     /// Receipt for a transaction. Will be set to `Some` only if the transaction
     /// failed during execution.
     pub receipt: Option<TransactionReceipt>,
+    /// Gas spent by the transaction, `None` if the node doesn't report it.
+    pub gas_used: Option<U256>,
+    /// Price paid per unit of gas, `None` if the node doesn't report it.
+    pub effective_gas_price: Option<U256>,
 }
 /// Information about transaction failure.
 #[derive(Debug, Clone)]
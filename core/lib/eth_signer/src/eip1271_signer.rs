@@ -0,0 +1,120 @@
+use crate::{EthereumSigner, RawTransaction, SignerError};
+
+use zksync_types::eip712_signature::{EIP712TypedStructure, Eip712Domain};
+use zksync_types::tx::{EIP1271Signature, PackedEthSignature, TxEthSignature};
+use zksync_types::Address;
+
+/// Signer of the smart contract wallet supporting the EIP-1271 standard.
+///
+/// Messages are signed by the owner of the wallet, and the signature is checked by the
+/// `isValidSignature` method of the wallet contract, so the signatures are returned as
+/// `EIP1271Signature`. Contract wallets can't produce the EIP-712 and ECDSA authorizations
+/// of `ChangePubKey`, so it must be authorized onchain.
+#[derive(Debug, Clone)]
+pub struct EIP1271Signer<S: EthereumSigner> {
+    wallet_address: Address,
+    owner: S,
+}
+
+impl<S: EthereumSigner> EIP1271Signer<S> {
+    pub fn new(wallet_address: Address, owner: S) -> Self {
+        Self {
+            wallet_address,
+            owner,
+        }
+    }
+
+    /// Returns the signer of the wallet owner.
+    pub fn owner(&self) -> &S {
+        &self.owner
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: EthereumSigner> EthereumSigner for EIP1271Signer<S> {
+    /// Signs the message with the owner key and wraps the signature into `EIP1271Signature`.
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        let signature = match self.owner.sign_message(message).await? {
+            TxEthSignature::EthereumSignature(signature) => signature.serialize_packed().to_vec(),
+            // The owner may be a contract wallet as well.
+            TxEthSignature::EIP1271Signature(signature) => signature.0,
+        };
+        Ok(TxEthSignature::EIP1271Signature(EIP1271Signature(
+            signature,
+        )))
+    }
+
+    /// Contract wallets can't sign the Ethereum transactions, they must be sent by the owner.
+    async fn sign_transaction(&self, _raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        Err(SignerError::CustomError(
+            "Contract wallet can't sign Ethereum transactions, they must be sent by the owner"
+                .to_string(),
+        ))
+    }
+
+    /// Returns the address of the wallet contract.
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.wallet_address)
+    }
+
+    /// EIP-712 signatures are only accepted from the externally owned accounts.
+    async fn sign_typed_data<T: EIP712TypedStructure + Sync>(
+        &self,
+        _eip712_domain: &Eip712Domain,
+        _typed_struct: &T,
+    ) -> Result<PackedEthSignature, SignerError> {
+        Err(SignerError::CustomError(
+            "Contract wallet can't produce EIP-712 signatures, use the onchain authorization"
+                .to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EIP1271Signer;
+    use crate::{EthereumSigner, PrivateKeySigner};
+    use zksync_types::tx::{eip712_signature::Eip712Domain, ChangePubKey, PackedEthSignature};
+    use zksync_types::tx::{EIP1271Signature, TxEthSignature};
+    use zksync_types::{AccountId, Address, ChainId, Nonce, H256};
+
+    #[tokio::test]
+    async fn test_eip1271_signer() {
+        let owner_key = H256::from([5; 32]);
+        let owner_address = PackedEthSignature::address_from_private_key(&owner_key).unwrap();
+        let wallet_address = Address::repeat_byte(0x42);
+        let signer = EIP1271Signer::new(wallet_address, PrivateKeySigner::new(owner_key));
+
+        assert_eq!(signer.get_address().await.unwrap(), wallet_address);
+
+        // The wallet contract checks the signature of the owner.
+        let message = b"message";
+        let signature = match signer.sign_message(message).await.unwrap() {
+            TxEthSignature::EIP1271Signature(EIP1271Signature(signature)) => signature,
+            TxEthSignature::EthereumSignature(_) => panic!("Expected EIP-1271 signature"),
+        };
+        let recovered = PackedEthSignature::deserialize_packed(&signature)
+            .unwrap()
+            .signature_recover_signer_from_raw_message(message)
+            .unwrap();
+        assert_eq!(recovered, owner_address);
+
+        let chain_id = ChainId(9);
+        let change_pub_key = ChangePubKey::new(
+            AccountId(1),
+            wallet_address,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Nonce(0),
+            Default::default(),
+            None,
+            None,
+            Some(chain_id),
+        );
+        assert!(signer
+            .sign_typed_data(&Eip712Domain::new(chain_id), &change_pub_key)
+            .await
+            .is_err());
+    }
+}
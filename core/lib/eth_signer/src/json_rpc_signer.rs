@@ -322,7 +322,7 @@ impl JsonRpcSigner {
     }
 }
 
-mod messages {
+pub(crate) mod messages {
     use crate::RawTransaction;
     use hex::encode;
     use zksync_types::eip712_signature::{
@@ -389,6 +389,31 @@ mod messages {
             Self::create("eth_signTypedData_v3", params)
         }
 
+        /// Signs the message with the `\x19Ethereum Signed Message:\n` prefix added by the wallet.
+        /// Unlike `eth_sign`, it's supported by the remote wallets.
+        pub fn personal_sign(address: Address, message: &[u8]) -> Self {
+            let params = vec![
+                serde_json::to_value(format!("0x{}", encode(message))).expect("serialization fail"),
+                serde_json::to_value(address).expect("serialization fail"),
+            ];
+            Self::create("personal_sign", params)
+        }
+
+        /// Signs typed struct according to the EIP-712 signature standard.
+        /// The typed data is passed as a JSON string, as expected by the remote wallets.
+        pub fn sign_typed_data_v4<S: EIP712TypedStructure + Sync>(
+            address: Address,
+            eip712_domain: &Eip712Domain,
+            typed_struct: &S,
+        ) -> Self {
+            let params = vec![
+                serde_json::to_value(address).expect("serialization fail"),
+                serde_json::Value::String(get_eip712_json(eip712_domain, typed_struct).to_string()),
+            ];
+
+            Self::create("eth_signTypedData_v4", params)
+        }
+
         /// Signs a transaction that can be submitted to the network.
         /// The address to sign with must be unlocked.
         pub fn sign_transaction(from: Address, tx_data: RawTransaction) -> Self {
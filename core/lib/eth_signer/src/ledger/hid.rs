@@ -0,0 +1,113 @@
+//! USB HID transport of the Ledger devices.
+
+use std::sync::Mutex;
+
+use hidapi::{HidApi, HidDevice};
+
+use super::LedgerTransport;
+use crate::error::SignerError;
+
+const LEDGER_VENDOR_ID: u16 = 0x2c97;
+/// Usage page of the interface exchanging the APDU commands.
+const LEDGER_USAGE_PAGE: u16 = 0xffa0;
+
+const CHANNEL: u16 = 0x0101;
+const TAG_APDU: u8 = 0x05;
+const PACKET_SIZE: usize = 64;
+const READ_TIMEOUT_MS: i32 = 60_000;
+
+/// Transport talking to the Ledger device connected via USB.
+///
+/// The USB calls are blocking, and the exchange blocks until the user confirms the request
+/// on the device, so it's better run on the thread allowed to block.
+pub struct HidTransport {
+    device: Mutex<HidDevice>,
+}
+
+impl HidTransport {
+    /// Opens the first connected Ledger device.
+    pub fn new() -> Result<Self, SignerError> {
+        let api = HidApi::new().map_err(hid_error)?;
+        let device_info = api
+            .device_list()
+            .find(|device| {
+                device.vendor_id() == LEDGER_VENDOR_ID && device.usage_page() == LEDGER_USAGE_PAGE
+            })
+            .ok_or_else(|| SignerError::CustomError("Ledger device is not found".to_string()))?;
+        let device = device_info.open_device(&api).map_err(hid_error)?;
+
+        Ok(Self {
+            device: Mutex::new(device),
+        })
+    }
+
+    fn write(device: &HidDevice, apdu: &[u8]) -> Result<(), SignerError> {
+        // The APDU is prefixed with its length and split into packets with the sequence indexes.
+        let mut data = (apdu.len() as u16).to_be_bytes().to_vec();
+        data.extend_from_slice(apdu);
+
+        for (sequence_idx, chunk) in data.chunks(PACKET_SIZE - 5).enumerate() {
+            // The leading zero is the report id.
+            let mut packet = vec![0x00];
+            packet.extend_from_slice(&CHANNEL.to_be_bytes());
+            packet.push(TAG_APDU);
+            packet.extend_from_slice(&(sequence_idx as u16).to_be_bytes());
+            packet.extend_from_slice(chunk);
+            packet.resize(PACKET_SIZE + 1, 0);
+
+            device.write(&packet).map_err(hid_error)?;
+        }
+        Ok(())
+    }
+
+    fn read(device: &HidDevice) -> Result<Vec<u8>, SignerError> {
+        let mut response = Vec::new();
+        let mut response_len = None;
+        let mut sequence_idx = 0u16;
+
+        while response_len.map_or(true, |len| response.len() < len) {
+            let mut packet = [0u8; PACKET_SIZE];
+            let read = device
+                .read_timeout(&mut packet, READ_TIMEOUT_MS)
+                .map_err(hid_error)?;
+            if read < 5
+                || packet[..2] != CHANNEL.to_be_bytes()
+                || packet[2] != TAG_APDU
+                || packet[3..5] != sequence_idx.to_be_bytes()
+            {
+                return Err(SignerError::SigningFailed(
+                    "Malformed response from Ledger".to_string(),
+                ));
+            }
+
+            let mut data = &packet[5..read];
+            if response_len.is_none() {
+                if data.len() < 2 {
+                    return Err(SignerError::SigningFailed(
+                        "Malformed response from Ledger".to_string(),
+                    ));
+                }
+                response_len = Some(u16::from_be_bytes([data[0], data[1]]) as usize);
+                data = &data[2..];
+            }
+            response.extend_from_slice(data);
+            sequence_idx += 1;
+        }
+
+        response.truncate(response_len.unwrap_or_default());
+        Ok(response)
+    }
+}
+
+#[async_trait::async_trait]
+impl LedgerTransport for HidTransport {
+    async fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, SignerError> {
+        let device = self.device.lock().unwrap();
+        Self::write(&device, apdu)?;
+        Self::read(&device)
+    }
+}
+
+fn hid_error(err: hidapi::HidError) -> SignerError {
+    SignerError::CustomError(format!("Ledger HID error: {}", err))
+}
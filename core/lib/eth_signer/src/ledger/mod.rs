@@ -0,0 +1,497 @@
+//! Signer backed by the Ledger hardware wallet running the Ethereum application.
+//!
+//! The signer talks to the device via the APDU commands of the Ethereum application,
+//! the transport delivering them is pluggable. The USB HID transport is available
+//! with the `ledger` feature enabled.
+
+use std::fmt;
+use std::sync::Arc;
+
+use web3::signing::Signature;
+use zksync_types::eip712_signature::{EIP712TypedStructure, Eip712Domain};
+use zksync_types::tx::{PackedEthSignature, TxEthSignature};
+use zksync_types::{Address, H256};
+
+use crate::error::SignerError;
+use crate::json_rpc_signer::is_signature_from_address;
+use crate::{EthereumSigner, RawTransaction};
+
+#[cfg(feature = "ledger")]
+pub mod hid;
+
+/// Derivation path of the first account, as used by Ledger Live.
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
+
+const CLA: u8 = 0xE0;
+const INS_GET_ADDRESS: u8 = 0x02;
+const INS_SIGN_TX: u8 = 0x04;
+const INS_SIGN_PERSONAL_MESSAGE: u8 = 0x08;
+const INS_SIGN_EIP712_HASHED: u8 = 0x0C;
+
+/// `P1` of the first data chunk of the command.
+const P1_FIRST_CHUNK: u8 = 0x00;
+/// `P1` of the subsequent data chunks of the command.
+const P1_MORE_CHUNKS: u8 = 0x80;
+/// Maximum size of the APDU command data.
+const CHUNK_SIZE: usize = 255;
+
+const SW_OK: u16 = 0x9000;
+const SW_REJECTED: u16 = 0x6985;
+const SW_APP_NOT_OPENED: u16 = 0x6D00;
+
+/// Transport delivering the APDU commands to the device.
+#[async_trait::async_trait]
+pub trait LedgerTransport: Send + Sync {
+    /// Sends the APDU command and returns the response including the trailing status word.
+    async fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, SignerError>;
+}
+
+/// Signer of the Ledger hardware wallet.
+///
+/// Every signature has to be confirmed on the device, so the signing methods wait
+/// for the user and fail with `SigningFailed` if the request is rejected.
+#[derive(Clone)]
+pub struct LedgerSigner {
+    transport: Arc<dyn LedgerTransport>,
+    derivation_path: Vec<u32>,
+    address: Address,
+}
+
+impl fmt::Debug for LedgerSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LedgerSigner")
+            .field("derivation_path", &self.derivation_path)
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+impl LedgerSigner {
+    /// Creates the signer for the account with the given derivation path,
+    /// e.g. [`DEFAULT_DERIVATION_PATH`]. The address of the account is requested from the device.
+    pub async fn new(
+        transport: Arc<dyn LedgerTransport>,
+        derivation_path: &str,
+    ) -> Result<Self, SignerError> {
+        let derivation_path = parse_derivation_path(derivation_path)?;
+        let mut signer = Self {
+            transport,
+            derivation_path,
+            address: Address::zero(),
+        };
+        signer.address = signer.request_address().await?;
+
+        Ok(signer)
+    }
+
+    async fn request_address(&self) -> Result<Address, SignerError> {
+        let response = self
+            .send(INS_GET_ADDRESS, self.encoded_path())
+            .await
+            .map_err(|_| SignerError::DefineAddress)?;
+
+        // The response is `pubkey_len || pubkey || address_len || address`,
+        // the address is hex encoded without the `0x` prefix.
+        let pubkey_len = *response.first().ok_or(SignerError::DefineAddress)? as usize;
+        let address_len = *response
+            .get(1 + pubkey_len)
+            .ok_or(SignerError::DefineAddress)? as usize;
+        let address_start = 2 + pubkey_len;
+        let address = response
+            .get(address_start..address_start + address_len)
+            .ok_or(SignerError::DefineAddress)?;
+        let address = std::str::from_utf8(address).map_err(|_| SignerError::DefineAddress)?;
+
+        address.parse().map_err(|_| SignerError::DefineAddress)
+    }
+
+    /// Sends the command splitting its data into chunks, returns the response to the last chunk.
+    async fn send(&self, ins: u8, data: Vec<u8>) -> Result<Vec<u8>, SignerError> {
+        let mut response = Vec::new();
+        for (idx, chunk) in data.chunks(CHUNK_SIZE).enumerate() {
+            let p1 = if idx == 0 {
+                P1_FIRST_CHUNK
+            } else {
+                P1_MORE_CHUNKS
+            };
+            let mut apdu = vec![CLA, ins, p1, 0x00, chunk.len() as u8];
+            apdu.extend_from_slice(chunk);
+
+            response = self.transport.exchange(&apdu).await?;
+            if response.len() < 2 {
+                return Err(SignerError::SigningFailed(
+                    "Malformed response from Ledger".to_string(),
+                ));
+            }
+            let status_start = response.len() - 2;
+            let status = u16::from_be_bytes([response[status_start], response[status_start + 1]]);
+            match status {
+                SW_OK => response.truncate(status_start),
+                SW_REJECTED => {
+                    return Err(SignerError::SigningFailed(
+                        "Request was rejected on Ledger".to_string(),
+                    ))
+                }
+                SW_APP_NOT_OPENED => {
+                    return Err(SignerError::SigningFailed(
+                        "Ethereum application is not opened on Ledger".to_string(),
+                    ))
+                }
+                status => {
+                    return Err(SignerError::SigningFailed(format!(
+                        "Ledger returned status {:#06x}",
+                        status
+                    )))
+                }
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Sends the signing command and parses the signature `v || r || s` from the response.
+    async fn sign(&self, ins: u8, data: Vec<u8>) -> Result<(u8, H256, H256), SignerError> {
+        let response = self.send(ins, data).await?;
+        if response.len() != 65 {
+            return Err(SignerError::SigningFailed(
+                "Malformed signature from Ledger".to_string(),
+            ));
+        }
+
+        Ok((
+            response[0],
+            H256::from_slice(&response[1..33]),
+            H256::from_slice(&response[33..65]),
+        ))
+    }
+
+    fn encoded_path(&self) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(1 + 4 * self.derivation_path.len());
+        encoded.push(self.derivation_path.len() as u8);
+        for index in &self.derivation_path {
+            encoded.extend_from_slice(&index.to_be_bytes());
+        }
+        encoded
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for LedgerSigner {
+    /// Signs the message with the `\x19Ethereum Signed Message:\n` prefix added by the device.
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        let mut data = self.encoded_path();
+        data.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        data.extend_from_slice(message);
+
+        let (v, r, s) = self.sign(INS_SIGN_PERSONAL_MESSAGE, data).await?;
+        let signature = packed_signature(v, r, s)?;
+
+        if is_signature_from_address(&signature, message, self.address)? {
+            Ok(TxEthSignature::EthereumSignature(signature))
+        } else {
+            Err(SignerError::SigningFailed(
+                "Invalid signature from LedgerSigner".to_string(),
+            ))
+        }
+    }
+
+    /// Signs and returns the RLP-encoded transaction.
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        let chain_id = raw_tx.chain_id;
+        let tx = raw_tx.into_transaction();
+
+        let mut data = self.encoded_path();
+        data.extend_from_slice(&tx.unsigned_payload(chain_id));
+        let (v, r, s) = self.sign(INS_SIGN_TX, data).await?;
+
+        let v = if tx.is_legacy() {
+            // The device returns the lowest byte of the EIP-155 `v` value only.
+            let base = chain_id * 2 + 35;
+            let parity = u64::from(v.wrapping_sub(base as u8));
+            if parity > 1 {
+                return Err(SignerError::SigningFailed(
+                    "Malformed signature from Ledger".to_string(),
+                ));
+            }
+            base + parity
+        } else {
+            u64::from(v)
+        };
+
+        Ok(tx.signed_payload(chain_id, &Signature { v, r, s }))
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+
+    /// Signs typed struct according to the EIP-712 signature standard.
+    /// The device only shows the hashes of the domain and the struct.
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        eip712_domain: &Eip712Domain,
+        typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        let mut data = self.encoded_path();
+        data.extend_from_slice(eip712_domain.hash_struct().as_bytes());
+        data.extend_from_slice(typed_struct.hash_struct().as_bytes());
+
+        let (v, r, s) = self.sign(INS_SIGN_EIP712_HASHED, data).await?;
+        let signature = packed_signature(v, r, s)?;
+
+        let signed_bytes =
+            PackedEthSignature::typed_data_to_signed_bytes(eip712_domain, typed_struct);
+        let recovered_address = signature
+            .signature_recover_signer_from_hash(signed_bytes)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        if recovered_address == self.address {
+            Ok(signature)
+        } else {
+            Err(SignerError::SigningFailed(
+                "Invalid signature from LedgerSigner".to_string(),
+            ))
+        }
+    }
+}
+
+fn packed_signature(v: u8, r: H256, s: H256) -> Result<PackedEthSignature, SignerError> {
+    let mut bytes = Vec::with_capacity(65);
+    bytes.extend_from_slice(r.as_bytes());
+    bytes.extend_from_slice(s.as_bytes());
+    bytes.push(v);
+
+    PackedEthSignature::deserialize_packed(&bytes)
+        .map_err(|err| SignerError::SigningFailed(err.to_string()))
+}
+
+/// Parses the BIP-32 derivation path like `m/44'/60'/0'/0/0`.
+fn parse_derivation_path(path: &str) -> Result<Vec<u32>, SignerError> {
+    const HARDENED: u32 = 0x8000_0000;
+
+    let invalid_path = || SignerError::CustomError(format!("Invalid derivation path: {}", path));
+    let mut components = path.split('/');
+    if components.next() != Some("m") {
+        return Err(invalid_path());
+    }
+
+    components
+        .map(|component| {
+            let (index, hardened) = match component.strip_suffix('\'') {
+                Some(index) => (index, true),
+                None => (component, false),
+            };
+            let index: u32 = index.parse().map_err(|_| invalid_path())?;
+            if index >= HARDENED {
+                return Err(invalid_path());
+            }
+            Ok(if hardened { index | HARDENED } else { index })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PrivateKeySigner;
+    use secp256k1::{Message, Secp256k1, SecretKey};
+    use std::sync::Mutex;
+    use web3::signing::keccak256;
+    use web3::types::U64;
+    use zksync_types::tx::ChangePubKey;
+    use zksync_types::{AccountId, ChainId, Nonce, H160, U256};
+
+    const CHAIN_ID: u64 = 270;
+
+    /// Device signing with the private key. Only supports the commands fitting into a single chunk.
+    struct MockTransport {
+        private_key: H256,
+        rejected: bool,
+        commands: Mutex<Vec<u8>>,
+    }
+
+    impl MockTransport {
+        fn new(private_key: H256, rejected: bool) -> Self {
+            Self {
+                private_key,
+                rejected,
+                commands: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn sign_hash(&self, hash: [u8; 32]) -> (u8, [u8; 64]) {
+            let secret_key = SecretKey::from_slice(self.private_key.as_bytes()).unwrap();
+            let message = Message::from_slice(&hash).unwrap();
+            let (recovery_id, signature) = Secp256k1::signing_only()
+                .sign_ecdsa_recoverable(&message, &secret_key)
+                .serialize_compact();
+            (recovery_id.to_i32() as u8, signature)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl LedgerTransport for MockTransport {
+        async fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, SignerError> {
+            assert_eq!(apdu[0], CLA);
+            assert_eq!(apdu[2], P1_FIRST_CHUNK);
+            assert_eq!(apdu[4] as usize, apdu.len() - 5);
+            self.commands.lock().unwrap().push(apdu[1]);
+
+            let ins = apdu[1];
+            let data = &apdu[5..];
+            if self.rejected && ins != INS_GET_ADDRESS {
+                return Ok(SW_REJECTED.to_be_bytes().to_vec());
+            }
+            // Skips the derivation path.
+            let payload = &data[1 + 4 * data[0] as usize..];
+
+            let (v, signature) = match ins {
+                INS_GET_ADDRESS => {
+                    let address =
+                        PackedEthSignature::address_from_private_key(&self.private_key).unwrap();
+                    let address = hex::encode(address);
+                    let mut response = vec![65];
+                    response.extend_from_slice(&[4; 65]);
+                    response.push(address.len() as u8);
+                    response.extend_from_slice(address.as_bytes());
+                    response.extend_from_slice(&SW_OK.to_be_bytes());
+                    return Ok(response);
+                }
+                INS_SIGN_TX => {
+                    let (parity, signature) = self.sign_hash(keccak256(payload));
+                    let v = if payload[0] < 0x7f {
+                        parity
+                    } else {
+                        (CHAIN_ID * 2 + 35 + u64::from(parity)) as u8
+                    };
+                    (v, signature)
+                }
+                INS_SIGN_PERSONAL_MESSAGE => {
+                    let message = &payload[4..];
+                    let mut bytes =
+                        format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+                    bytes.extend_from_slice(message);
+                    let (parity, signature) = self.sign_hash(keccak256(&bytes));
+                    (27 + parity, signature)
+                }
+                INS_SIGN_EIP712_HASHED => {
+                    let mut bytes = b"\x19\x01".to_vec();
+                    bytes.extend_from_slice(payload);
+                    let (parity, signature) = self.sign_hash(keccak256(&bytes));
+                    (27 + parity, signature)
+                }
+                _ => panic!("Unexpected command {}", ins),
+            };
+
+            let mut response = vec![v];
+            response.extend_from_slice(&signature);
+            response.extend_from_slice(&SW_OK.to_be_bytes());
+            Ok(response)
+        }
+    }
+
+    fn raw_transaction(transaction_type: Option<U64>) -> RawTransaction {
+        RawTransaction {
+            nonce: U256::from(1u32),
+            to: Some(H160::default()),
+            gas: Default::default(),
+            gas_price: U256::from(2u32),
+            max_fee_per_gas: Some(U256::from(2u32)),
+            max_priority_fee_per_gas: Some(U256::from(1u32)),
+            value: Default::default(),
+            data: vec![1, 2, 3],
+            chain_id: CHAIN_ID,
+            transaction_type,
+            access_list: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_derivation_path() {
+        assert_eq!(
+            parse_derivation_path(DEFAULT_DERIVATION_PATH).unwrap(),
+            vec![0x8000_002C, 0x8000_003C, 0x8000_0000, 0, 0]
+        );
+        assert!(parse_derivation_path("44'/60'").is_err());
+        assert!(parse_derivation_path("m/44'/x").is_err());
+        assert!(parse_derivation_path("m/2147483648").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ledger_signer() {
+        let private_key = H256::from([5; 32]);
+        let pk_signer = PrivateKeySigner::new(private_key);
+        let transport = Arc::new(MockTransport::new(private_key, false));
+        let signer = LedgerSigner::new(transport.clone(), DEFAULT_DERIVATION_PATH)
+            .await
+            .unwrap();
+        assert_eq!(
+            signer.get_address().await.unwrap(),
+            pk_signer.get_address().await.unwrap()
+        );
+
+        // Signatures are deterministic, so they must match the private key signer ones.
+        let message = b"message";
+        assert_eq!(
+            signer.sign_message(message).await.unwrap(),
+            pk_signer.sign_message(message).await.unwrap()
+        );
+
+        for transaction_type in &[None, Some(U64::from(1u32)), Some(U64::from(2u32))] {
+            let raw_tx = raw_transaction(*transaction_type);
+            assert_eq!(
+                signer.sign_transaction(raw_tx.clone()).await.unwrap(),
+                pk_signer.sign_transaction(raw_tx).await.unwrap()
+            );
+        }
+
+        let chain_id = ChainId(9);
+        let domain = Eip712Domain::new(chain_id);
+        let change_pub_key = ChangePubKey::new(
+            AccountId(1),
+            signer.get_address().await.unwrap(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Nonce(0),
+            Default::default(),
+            None,
+            None,
+            Some(chain_id),
+        );
+        assert_eq!(
+            signer
+                .sign_typed_data(&domain, &change_pub_key)
+                .await
+                .unwrap(),
+            pk_signer
+                .sign_typed_data(&domain, &change_pub_key)
+                .await
+                .unwrap()
+        );
+
+        assert_eq!(
+            *transport.commands.lock().unwrap(),
+            vec![
+                INS_GET_ADDRESS,
+                INS_SIGN_PERSONAL_MESSAGE,
+                INS_SIGN_TX,
+                INS_SIGN_TX,
+                INS_SIGN_TX,
+                INS_SIGN_EIP712_HASHED
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ledger_signer_rejected() {
+        let transport = Arc::new(MockTransport::new(H256::from([5; 32]), true));
+        let signer = LedgerSigner::new(transport, DEFAULT_DERIVATION_PATH)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            signer.sign_message(b"message").await.unwrap_err(),
+            SignerError::SigningFailed("Request was rejected on Ledger".to_string())
+        );
+    }
+}
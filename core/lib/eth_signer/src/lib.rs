@@ -8,15 +8,21 @@ use error::SignerError;
 use zksync_types::tx::{PackedEthSignature, TxEthSignature};
 use zksync_types::Address;
 
+pub use eip1271_signer::EIP1271Signer;
 pub use json_rpc_signer::JsonRpcSigner;
+pub use ledger::LedgerSigner;
 pub use pk_signer::PrivateKeySigner;
 pub use raw_ethereum_tx::RawTransaction;
+pub use remote_signer::{RemoteSession, RemoteSigner};
 use zksync_types::eip712_signature::{EIP712TypedStructure, Eip712Domain};
 
+pub mod eip1271_signer;
 pub mod error;
 pub mod json_rpc_signer;
+pub mod ledger;
 pub mod pk_signer;
 pub mod raw_ethereum_tx;
+pub mod remote_signer;
 
 #[async_trait]
 pub trait EthereumSigner: Send + Sync + Clone {
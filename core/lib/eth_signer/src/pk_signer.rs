@@ -1,4 +1,4 @@
-use crate::raw_ethereum_tx::{RawTransaction, Transaction};
+use crate::raw_ethereum_tx::RawTransaction;
 use crate::{EthereumSigner, SignerError};
 
 use secp256k1::SecretKey;
@@ -44,23 +44,10 @@ impl EthereumSigner for PrivateKeySigner {
     async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
         let key = SecretKey::from_slice(self.private_key.as_bytes()).unwrap();
 
-        let gas_price = match raw_tx.max_fee_per_gas {
-            Some(val) => val,
-            None => raw_tx.gas_price,
-        };
-        let tx = Transaction {
-            to: raw_tx.to,
-            nonce: raw_tx.nonce,
-            gas: raw_tx.gas,
-            gas_price,
-            value: raw_tx.value,
-            data: raw_tx.data,
-            transaction_type: raw_tx.transaction_type,
-            access_list: raw_tx.access_list.unwrap_or_default(),
-            max_priority_fee_per_gas: raw_tx.max_priority_fee_per_gas.unwrap_or_default(),
-        };
+        let chain_id = raw_tx.chain_id;
+        let tx = raw_tx.into_transaction();
 
-        let signed = tx.sign(&key, raw_tx.chain_id);
+        let signed = tx.sign(&key, chain_id);
         Ok(signed.raw_transaction.0)
     }
 
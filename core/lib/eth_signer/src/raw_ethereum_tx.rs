@@ -44,6 +44,27 @@ pub struct RawTransaction {
     pub max_priority_fee_per_gas: Option<U256>,
 }
 
+impl RawTransaction {
+    /// Converts the transaction into the form used for RLP encoding and signing.
+    pub(crate) fn into_transaction(self) -> Transaction {
+        let gas_price = match self.max_fee_per_gas {
+            Some(val) => val,
+            None => self.gas_price,
+        };
+        Transaction {
+            to: self.to,
+            nonce: self.nonce,
+            gas: self.gas,
+            gas_price,
+            value: self.value,
+            data: self.data,
+            transaction_type: self.transaction_type,
+            access_list: self.access_list.unwrap_or_default(),
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas.unwrap_or_default(),
+        }
+    }
+}
+
 /// A transaction used for RLP encoding, hashing and signing.
 #[derive(Debug)]
 pub struct Transaction {
@@ -185,12 +206,28 @@ impl Transaction {
         }
     }
 
-    /// Sign and return a raw signed transaction.
-    pub fn sign(self, sign: impl signing::Key, chain_id: u64) -> SignedTransaction {
-        let adjust_v_value = matches!(
+    /// Returns `true` for the legacy transactions, which include the chain id into
+    /// the `v` value of the signature according to EIP-155.
+    pub fn is_legacy(&self) -> bool {
+        matches!(
             self.transaction_type.map(|t| t.as_u64()),
             Some(LEGACY_TX_ID) | None
-        );
+        )
+    }
+
+    /// Returns the encoded transaction without the signature, which is signed by the external signers.
+    pub fn unsigned_payload(&self, chain_id: u64) -> Vec<u8> {
+        self.encode(chain_id, None)
+    }
+
+    /// Returns the encoded transaction with the signature obtained from the external signer.
+    pub fn signed_payload(&self, chain_id: u64, signature: &Signature) -> Vec<u8> {
+        self.encode(chain_id, Some(signature))
+    }
+
+    /// Sign and return a raw signed transaction.
+    pub fn sign(self, sign: impl signing::Key, chain_id: u64) -> SignedTransaction {
+        let adjust_v_value = self.is_legacy();
 
         let encoded = self.encode(chain_id, None);
 
@@ -0,0 +1,223 @@
+use crate::error::SignerError;
+use crate::json_rpc_signer::{is_signature_from_address, messages::JsonRpcRequest};
+use crate::EthereumSigner;
+use crate::RawTransaction;
+
+use serde_json::Value;
+use zksync_types::eip712_signature::{EIP712TypedStructure, Eip712Domain};
+use zksync_types::tx::{PackedEthSignature, TxEthSignature};
+use zksync_types::Address;
+
+/// Session with the remote wallet, e.g. the WalletConnect session or the browser extension bridge.
+///
+/// The session is responsible for the transport, pairing and encryption, the signer
+/// only relies on the wallet supporting the standard Ethereum JSON RPC signing methods.
+#[async_trait::async_trait]
+pub trait RemoteSession: Send + Sync + Clone {
+    /// Sends the JSON RPC request to the wallet and returns the result of the call.
+    /// The call may take a while, since the request is usually confirmed by the user.
+    async fn request(&self, method: &str, params: Vec<Value>) -> Result<Value, SignerError>;
+}
+
+/// Signer requesting the signatures from the remote wallet, so the private key never leaves it.
+///
+/// Unlike `JsonRpcSigner`, it doesn't rely on the node-specific methods like `eth_sign`
+/// and `personal_unlockAccount`, and uses the ones supported by the user wallets:
+/// `personal_sign`, `eth_signTypedData_v4` and `eth_signTransaction`.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner<T: RemoteSession> {
+    session: T,
+    address: Address,
+}
+
+impl<T: RemoteSession> RemoteSigner<T> {
+    pub fn new(session: T, address: Address) -> Self {
+        Self { session, address }
+    }
+
+    /// Creates the signer using the first account exposed by the wallet.
+    pub async fn connect(session: T) -> Result<Self, SignerError> {
+        let request = JsonRpcRequest::accounts();
+        let accounts: Vec<Address> =
+            serde_json::from_value(session.request(&request.method, request.params).await?)
+                .map_err(|_| SignerError::DefineAddress)?;
+        let address = accounts
+            .first()
+            .copied()
+            .ok_or(SignerError::DefineAddress)?;
+
+        Ok(Self::new(session, address))
+    }
+
+    async fn send(&self, request: JsonRpcRequest) -> Result<Value, SignerError> {
+        self.session
+            .request(&request.method, request.params)
+            .await
+            .map_err(|err| match err {
+                SignerError::SigningFailed(_) => err,
+                err => SignerError::SigningFailed(err.to_string()),
+            })
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: RemoteSession> EthereumSigner for RemoteSigner<T> {
+    /// Signs the message with the `\x19Ethereum Signed Message:\n` prefix added by the wallet.
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        let ret = self
+            .send(JsonRpcRequest::personal_sign(self.address, message))
+            .await?;
+        let signature: PackedEthSignature = serde_json::from_value(ret)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+
+        if is_signature_from_address(&signature, message, self.address)? {
+            Ok(TxEthSignature::EthereumSignature(signature))
+        } else {
+            Err(SignerError::SigningFailed(
+                "Invalid signature from RemoteSigner".to_string(),
+            ))
+        }
+    }
+
+    /// Signs and returns the RLP-encoded transaction.
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        let ret = self
+            .send(JsonRpcRequest::sign_transaction(self.address, raw_tx))
+            .await?;
+
+        // Wallets return either the raw transaction or the object containing it.
+        let raw_tx = ret
+            .as_str()
+            .or_else(|| ret.get("raw").and_then(|value| value.as_str()))
+            .ok_or_else(|| {
+                SignerError::DecodeRawTxFailed("Raw transaction is missing".to_string())
+            })?;
+        hex::decode(raw_tx.trim_start_matches("0x"))
+            .map_err(|err| SignerError::DecodeRawTxFailed(err.to_string()))
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+
+    /// Signs typed struct according to the EIP-712 signature standard.
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        eip712_domain: &Eip712Domain,
+        typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        let ret = self
+            .send(JsonRpcRequest::sign_typed_data_v4(
+                self.address,
+                eip712_domain,
+                typed_struct,
+            ))
+            .await?;
+        let signature: PackedEthSignature = serde_json::from_value(ret)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+
+        let signed_bytes =
+            PackedEthSignature::typed_data_to_signed_bytes(eip712_domain, typed_struct);
+        let recovered_address = signature
+            .signature_recover_signer_from_hash(signed_bytes)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        if recovered_address == self.address {
+            Ok(signature)
+        } else {
+            Err(SignerError::SigningFailed(
+                "Invalid signature from RemoteSigner".to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{RemoteSession, RemoteSigner};
+    use crate::error::SignerError;
+    use crate::{EthereumSigner, PrivateKeySigner, RawTransaction};
+    use serde_json::{json, Value};
+    use web3::types::U64;
+    use zksync_types::tx::{PackedEthSignature, TxEthSignature};
+    use zksync_types::{H160, H256, U256};
+
+    /// Wallet signing the requests with the private key.
+    #[derive(Debug, Clone)]
+    struct MockSession {
+        private_key: H256,
+        rejected: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl RemoteSession for MockSession {
+        async fn request(&self, method: &str, params: Vec<Value>) -> Result<Value, SignerError> {
+            if self.rejected {
+                return Err(SignerError::SigningFailed("User rejected".to_string()));
+            }
+            let address = PackedEthSignature::address_from_private_key(&self.private_key).unwrap();
+            match method {
+                "eth_accounts" => Ok(json!([address])),
+                "personal_sign" => {
+                    let message = params[0].as_str().unwrap().trim_start_matches("0x");
+                    let signature =
+                        PackedEthSignature::sign(&self.private_key, &hex::decode(message).unwrap())
+                            .unwrap();
+                    Ok(serde_json::to_value(signature).unwrap())
+                }
+                "eth_signTransaction" => Ok(json!({ "raw": "0x0102" })),
+                _ => panic!("Unexpected method {}", method),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer() {
+        let private_key = H256::from([5; 32]);
+        let session = MockSession {
+            private_key,
+            rejected: false,
+        };
+        let signer = RemoteSigner::connect(session.clone()).await.unwrap();
+        let pk_signer = PrivateKeySigner::new(private_key);
+        assert_eq!(
+            signer.get_address().await.unwrap(),
+            pk_signer.get_address().await.unwrap()
+        );
+
+        let message = b"message";
+        let signature = signer.sign_message(message).await.unwrap();
+        assert_eq!(signature, pk_signer.sign_message(message).await.unwrap());
+        assert!(matches!(signature, TxEthSignature::EthereumSignature(_)));
+
+        let raw_tx = RawTransaction {
+            nonce: U256::from(1u32),
+            to: Some(H160::default()),
+            gas: Default::default(),
+            gas_price: U256::from(2u32),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            value: Default::default(),
+            data: vec![1, 2, 3],
+            chain_id: 270,
+            transaction_type: Some(U64::from(0u32)),
+            access_list: None,
+        };
+        assert_eq!(signer.sign_transaction(raw_tx).await.unwrap(), vec![1, 2]);
+
+        // Signature of another account is rejected.
+        let signer = RemoteSigner::new(session, H160::repeat_byte(0x11));
+        assert!(signer.sign_message(message).await.is_err());
+
+        let signer = RemoteSigner::new(
+            MockSession {
+                private_key,
+                rejected: true,
+            },
+            H160::repeat_byte(0x11),
+        );
+        assert_eq!(
+            signer.sign_message(message).await.unwrap_err(),
+            SignerError::SigningFailed("User rejected".to_string())
+        );
+    }
+}
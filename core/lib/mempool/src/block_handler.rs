@@ -61,7 +61,7 @@ impl MempoolBlocksHandler {
         // will be empty unless the server is restarted after reverting blocks.
         let mut tx_queue = self
             .mempool_state
-            .get_transaction_queue(executed_txs)
+            .get_transaction_queue(executed_txs, block_timestamp)
             .await?;
 
         let (txs, priority_ops, chunks_left) = tx_queue
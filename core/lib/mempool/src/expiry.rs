@@ -0,0 +1,110 @@
+//! Eviction of the expired transactions.
+//!
+//! Transactions can't be executed once the block timestamp exceeds their `valid_until`,
+//! so instead of failing them in the block the mempool evicts them and records the expiry
+//! to be served by the receipts and searched by the initiator address. Batches expire as a whole along with their earliest transaction.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use zksync_types::{mempool::SignedTxVariant, tx::TxHash, Address};
+
+/// Removes the transactions expired by the block timestamp from the queue.
+/// Returns the hashes of the removed transactions along with their initiators and expiry.
+pub(crate) fn take_expired_txs(
+    txs: &mut VecDeque<SignedTxVariant>,
+    block_timestamp: u64,
+) -> Vec<(TxHash, Address, DateTime<Utc>)> {
+    let mut expired = Vec::new();
+    txs.retain(|variant| {
+        let valid_until = variant
+            .get_transactions()
+            .iter()
+            .map(|tx| tx.tx.time_range().valid_until)
+            .min()
+            .unwrap_or(u64::MAX);
+        if valid_until >= block_timestamp {
+            return true;
+        }
+        let valid_until = Utc.timestamp(valid_until as i64, 0);
+        expired.extend(
+            variant
+                .get_transactions()
+                .into_iter()
+                .map(|tx| (tx.hash(), tx.tx.account(), valid_until)),
+        );
+        false
+    });
+    expired
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        mempool::SignedTxsBatch,
+        tx::{TimeRange, Transfer},
+        AccountId, Address, Nonce, SignedZkSyncTx, TokenId, ZkSyncTx,
+    };
+
+    use super::*;
+
+    fn transfer(nonce: u32, valid_until: u64) -> SignedZkSyncTx {
+        let transfer = Transfer::new(
+            AccountId(1),
+            Address::random(),
+            Address::random(),
+            TokenId(0),
+            500u32.into(),
+            20u32.into(),
+            Nonce(nonce),
+            TimeRange::new(0, valid_until),
+            None,
+        );
+        SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn takes_expired_txs() {
+        let batch = SignedTxVariant::Batch(SignedTxsBatch {
+            txs: vec![transfer(3, u64::MAX), transfer(4, 90)],
+            batch_id: 0,
+            eth_signatures: Vec::new(),
+        });
+        let mut txs: VecDeque<_> = vec![
+            SignedTxVariant::Tx(transfer(0, 99)),
+            // Transactions are valid until the end of the `valid_until` second.
+            SignedTxVariant::Tx(transfer(1, 100)),
+            SignedTxVariant::Tx(transfer(2, u64::MAX)),
+            batch.clone(),
+        ]
+        .into_iter()
+        .collect();
+        let expired_tx_hash = txs[0].hashes()[0];
+        let initiators: Vec<_> = txs
+            .iter()
+            .flat_map(|tx| tx.get_transactions())
+            .map(|tx| tx.tx.account())
+            .collect();
+
+        let expired = take_expired_txs(&mut txs, 100);
+        assert_eq!(txs.len(), 2);
+        assert!(txs
+            .iter()
+            .all(|tx| tx.hashes() != vec![expired_tx_hash] && tx.hashes() != batch.hashes()));
+
+        let batch_expiry = Utc.timestamp(90, 0);
+        assert_eq!(
+            expired,
+            vec![
+                (expired_tx_hash, initiators[0], Utc.timestamp(99, 0)),
+                (batch.hashes()[0], initiators[3], batch_expiry),
+                (batch.hashes()[1], initiators[4], batch_expiry),
+            ]
+        );
+    }
+}
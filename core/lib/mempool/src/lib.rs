@@ -9,13 +9,19 @@
 //!
 //! Communication channel with other actors:
 //! Mempool does not push information to other actors, only accepts requests. (see `MempoolRequest`)
+//!
+//! Transactions are admitted to the mempool by several workers in parallel, each serving its own
+//! shard of the accounts. Transactions of the same account are always admitted by the same worker,
+//! so they are checked and inserted in the order they were submitted. Batches are admitted by the worker
+//! of the account of their first transaction.
 
 // External uses
-use futures::channel::mpsc;
+use futures::{channel::mpsc, future};
 
 use tokio::task::JoinHandle;
 
 // Workspace uses
+use zksync_balancer::Balancer;
 use zksync_storage::ConnectionPool;
 
 // Local uses
@@ -24,13 +30,18 @@ pub use crate::block_handler::{GetBlockRequest, MempoolBlocksRequest, ProposedBl
 use crate::mempool_transactions_queue::MempoolTransactionsQueue;
 use crate::state::MempoolState;
 pub use crate::transactions_handler::MempoolTransactionRequest;
-use crate::transactions_handler::MempoolTransactionsHandler;
+use crate::transactions_handler::MempoolTransactionsHandlerBuilder;
 
 mod block_handler;
+mod expiry;
 mod mempool_transactions_queue;
+mod nonce_gaps;
 mod state;
 mod transactions_handler;
 
+/// Capacity of the channel between the dispatcher and each of the admission workers.
+const SHARD_CHANNEL_CAPACITY: usize = 1024;
+
 // Due channel based nature, for better performance,
 // you need to run independent mempool_tx_handler for each actor, e.g. for each API actor
 #[must_use]
@@ -38,19 +49,39 @@ pub fn run_mempool_tx_handler(
     db_pool: ConnectionPool,
     tx_requests: mpsc::Receiver<MempoolTransactionRequest>,
     block_chunk_sizes: Vec<usize>,
+    admission_shards: u8,
 ) -> JoinHandle<()> {
     let mempool_state = MempoolState::new(db_pool.clone());
     let max_block_size_chunks = *block_chunk_sizes
         .iter()
         .max()
         .expect("failed to find max block chunks size");
-    let handler = MempoolTransactionsHandler {
+    let handler_builder = MempoolTransactionsHandlerBuilder {
         db_pool,
         mempool_state,
-        requests: tx_requests,
         max_block_size_chunks,
     };
-    tokio::spawn(handler.run())
+    let (balancer, handlers) = Balancer::new(
+        handler_builder,
+        tx_requests,
+        admission_shards.max(1),
+        SHARD_CHANNEL_CAPACITY,
+    );
+
+    let mut tasks: Vec<_> = handlers
+        .into_iter()
+        .map(|handler| tokio::spawn(handler.run()))
+        .collect();
+    tasks.push(tokio::spawn(balancer.run_sharded()));
+    tokio::spawn(async move {
+        // Workers are stopped once the dispatcher is, so any of the tasks finished
+        // means that the handler is stopped.
+        if let (Err(error), _, _) = future::select_all(tasks).await {
+            if error.is_panic() {
+                std::panic::resume_unwind(error.into_panic());
+            }
+        }
+    })
 }
 
 #[must_use]
@@ -271,6 +271,7 @@ mod tests {
                 eth_hash: Default::default(),
                 eth_block: 0,
                 eth_block_index: None,
+                l1_sender: None,
             },
             PriorityOp {
                 serial_id: 1,
@@ -284,6 +285,7 @@ mod tests {
                 eth_hash: Default::default(),
                 eth_block: 0,
                 eth_block_index: None,
+                l1_sender: None,
             },
             PriorityOp {
                 serial_id: 2,
@@ -297,6 +299,7 @@ mod tests {
                 eth_hash: Default::default(),
                 eth_block: 0,
                 eth_block_index: None,
+                l1_sender: None,
             },
         ]);
         transactions_queue.add_l1_transactions(vec![
@@ -312,6 +315,7 @@ mod tests {
                 eth_hash: Default::default(),
                 eth_block: 0,
                 eth_block_index: None,
+                l1_sender: None,
             },
             PriorityOp {
                 serial_id: 6,
@@ -325,6 +329,7 @@ mod tests {
                 eth_hash: Default::default(),
                 eth_block: 0,
                 eth_block_index: None,
+                l1_sender: None,
             },
             PriorityOp {
                 serial_id: 5,
@@ -338,6 +343,7 @@ mod tests {
                 eth_hash: Default::default(),
                 eth_block: 0,
                 eth_block_index: None,
+                l1_sender: None,
             },
         ]);
         let op = transactions_queue.pop_front_l1_transactions().unwrap();
@@ -0,0 +1,188 @@
+//! Gap-filling of the reserved nonces.
+//!
+//! Accounts holding the nonce reservations may submit the transactions of the reserved range
+//! out of order. The transactions following a missing nonce would fail if proposed, so they are
+//! held in the mempool until all the preceding nonces are committed. Once the reservation expires,
+//! the held transactions are proposed as usual.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use zksync_types::{mempool::SignedTxVariant, AccountId, Nonce};
+
+/// Active range of the reserved nonces of the account, both bounds are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct NonceReservation {
+    pub account_id: AccountId,
+    pub first_nonce: Nonce,
+    pub last_nonce: Nonce,
+}
+
+impl NonceReservation {
+    fn contains(&self, nonce: Nonce) -> bool {
+        self.first_nonce <= nonce && nonce <= self.last_nonce
+    }
+}
+
+/// Removes the transactions of the reserved ranges which follow a missing nonce from the queue.
+/// `committed_nonces` are the nonces of the reserved accounts as of the last committed block.
+pub(crate) fn hold_nonce_gaps(
+    txs: &mut VecDeque<SignedTxVariant>,
+    reservations: &[NonceReservation],
+    committed_nonces: &HashMap<AccountId, Nonce>,
+) {
+    if reservations.is_empty() {
+        return;
+    }
+    let reserved_accounts: HashSet<AccountId> = reservations.iter().map(|r| r.account_id).collect();
+
+    let mut present_nonces: HashMap<AccountId, HashSet<Nonce>> = HashMap::new();
+    for tx in txs.iter().flat_map(|variant| variant.get_transactions()) {
+        if let Ok(account_id) = tx.tx.account_id() {
+            if reserved_accounts.contains(&account_id) {
+                present_nonces
+                    .entry(account_id)
+                    .or_default()
+                    .insert(tx.tx.nonce());
+            }
+        }
+    }
+
+    // The first nonce of every account which is neither committed nor present in the queue.
+    let first_gaps: HashMap<AccountId, Nonce> = present_nonces
+        .into_iter()
+        .map(|(account_id, nonces)| {
+            let mut nonce = committed_nonces
+                .get(&account_id)
+                .copied()
+                .unwrap_or_default();
+            while nonces.contains(&nonce) {
+                *nonce += 1;
+            }
+            (account_id, nonce)
+        })
+        .collect();
+
+    let is_held = |variant: &SignedTxVariant| {
+        variant.get_transactions().into_iter().any(|tx| {
+            let account_id = match tx.tx.account_id() {
+                Ok(account_id) => account_id,
+                Err(_) => return false,
+            };
+            let nonce = tx.tx.nonce();
+            match first_gaps.get(&account_id) {
+                Some(first_gap) if nonce > *first_gap => reservations
+                    .iter()
+                    .any(|r| r.account_id == account_id && r.contains(nonce)),
+                _ => false,
+            }
+        })
+    };
+    let queue_size = txs.len();
+    txs.retain(|variant| !is_held(variant));
+
+    let held = queue_size - txs.len();
+    if held > 0 {
+        vlog::debug!(
+            "{} transactions are held until the nonce gaps are filled",
+            held
+        );
+        metrics::histogram!("mempool.held_nonce_gaps", held as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use zksync_types::{
+        mempool::SignedTxsBatch, tx::Transfer, Address, SignedZkSyncTx, TokenId, ZkSyncTx,
+    };
+
+    use super::*;
+
+    fn transfer(account_id: u32, nonce: u32) -> SignedZkSyncTx {
+        let transfer = Transfer::new(
+            AccountId(account_id),
+            Address::random(),
+            Address::random(),
+            TokenId(0),
+            500u32.into(),
+            20u32.into(),
+            Nonce(nonce),
+            Default::default(),
+            None,
+        );
+        SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn nonces(txs: &VecDeque<SignedTxVariant>) -> Vec<(u32, u32)> {
+        txs.iter()
+            .flat_map(|variant| variant.get_transactions())
+            .map(|tx| (*tx.tx.account_id().unwrap(), *tx.tx.nonce()))
+            .collect()
+    }
+
+    #[test]
+    fn holds_txs_after_gaps() {
+        let reservations = vec![NonceReservation {
+            account_id: AccountId(1),
+            first_nonce: Nonce(5),
+            last_nonce: Nonce(14),
+        }];
+        let committed_nonces = vec![(AccountId(1), Nonce(5))].into_iter().collect();
+
+        let mut txs: VecDeque<_> = vec![
+            SignedTxVariant::Tx(transfer(1, 5)),
+            SignedTxVariant::Tx(transfer(1, 6)),
+            SignedTxVariant::Tx(transfer(1, 8)),
+            SignedTxVariant::Tx(transfer(1, 10)),
+            // Nonces out of the reserved range are not held.
+            SignedTxVariant::Tx(transfer(1, 20)),
+            // Accounts without the reservations are not affected.
+            SignedTxVariant::Tx(transfer(2, 3)),
+        ]
+        .into_iter()
+        .collect();
+        hold_nonce_gaps(&mut txs, &reservations, &committed_nonces);
+        assert_eq!(nonces(&txs), vec![(1, 5), (1, 6), (1, 20), (2, 3)]);
+
+        // Gap at the committed nonce holds the whole range.
+        let mut txs: VecDeque<_> = vec![
+            SignedTxVariant::Tx(transfer(1, 6)),
+            SignedTxVariant::Tx(transfer(1, 7)),
+        ]
+        .into_iter()
+        .collect();
+        hold_nonce_gaps(&mut txs, &reservations, &committed_nonces);
+        assert!(txs.is_empty());
+    }
+
+    #[test]
+    fn holds_batches_after_gaps() {
+        let reservations = vec![NonceReservation {
+            account_id: AccountId(1),
+            first_nonce: Nonce(0),
+            last_nonce: Nonce(9),
+        }];
+        let batch = |txs| {
+            SignedTxVariant::Batch(SignedTxsBatch {
+                txs,
+                batch_id: 0,
+                eth_signatures: Vec::new(),
+            })
+        };
+
+        let mut txs: VecDeque<_> = vec![
+            batch(vec![transfer(1, 0), transfer(1, 1)]),
+            batch(vec![transfer(2, 0), transfer(1, 3)]),
+        ]
+        .into_iter()
+        .collect();
+        hold_nonce_gaps(&mut txs, &reservations, &HashMap::new());
+        assert_eq!(nonces(&txs), vec![(1, 0), (1, 1)]);
+    }
+}
@@ -1,13 +1,15 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
     mempool::{SignedTxVariant, SignedTxsBatch},
     tx::{error::TxAddError, TxHash},
-    Address, TransferOp, TransferToNewOp, ZkSyncTx,
+    AccountId, Address, Nonce, TransferOp, TransferToNewOp, ZkSyncTx,
 };
 
+use crate::expiry::take_expired_txs;
+use crate::nonce_gaps::{hold_nonce_gaps, NonceReservation};
 use crate::MempoolTransactionsQueue;
 
 #[derive(Debug, Clone)]
@@ -117,6 +119,7 @@ impl MempoolState {
     pub async fn get_transaction_queue(
         &self,
         executed_txs: &[TxHash],
+        block_timestamp: u64,
     ) -> Result<MempoolTransactionsQueue, TxAddError> {
         let mut storage = self
             .db_pool
@@ -137,13 +140,61 @@ impl MempoolState {
 
         // Load transactions that were not yet processed and are awaiting in the
         // mempool.
-        let mempool_txs = transaction
+        let mut mempool_txs = transaction
             .chain()
             .mempool_schema()
             .load_txs(executed_txs)
             .await
             .map_err(|_| TxAddError::DbError)?;
 
+        let expired_txs = take_expired_txs(&mut mempool_txs, block_timestamp);
+        if !expired_txs.is_empty() {
+            transaction
+                .chain()
+                .mempool_schema()
+                .expire_txs(&expired_txs)
+                .await
+                .map_err(|_| TxAddError::DbError)?;
+            vlog::debug!("Evicted {} expired transactions", expired_txs.len());
+            metrics::counter!("mempool.expired_txs", expired_txs.len() as u64);
+        }
+
+        // Transactions of the reserved nonces may be submitted out of order,
+        // the ones following the missing nonces are not proposed yet.
+        let reservations: Vec<_> = transaction
+            .nonce_reservations_schema()
+            .load_active_reservations()
+            .await
+            .map_err(|_| TxAddError::DbError)?
+            .into_iter()
+            .map(|reservation| NonceReservation {
+                account_id: AccountId(reservation.account_id as u32),
+                first_nonce: Nonce(reservation.first_nonce as u32),
+                last_nonce: Nonce(reservation.last_nonce as u32),
+            })
+            .collect();
+        let mut committed_nonces = HashMap::new();
+        for reservation in &reservations {
+            if committed_nonces.contains_key(&reservation.account_id) {
+                continue;
+            }
+            let nonce = transaction
+                .chain()
+                .account_schema()
+                .estimate_nonce(reservation.account_id)
+                .await
+                .map_err(|_| TxAddError::DbError)?;
+            if let Some(nonce) = nonce {
+                committed_nonces.insert(reservation.account_id, nonce);
+            }
+        }
+        hold_nonce_gaps(&mut mempool_txs, &reservations, &committed_nonces);
+
+        transaction
+            .commit()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
         let transactions_queue = MempoolTransactionsQueue::new(priority_ops, mempool_txs);
 
         Ok(transactions_queue)
@@ -1,7 +1,13 @@
 use futures::channel::{mpsc, oneshot};
 use futures::StreamExt;
+use tracing::{Instrument, Span};
 
-use zksync_storage::ConnectionPool;
+use zksync_balancer::{BuildBalancedItem, ShardedRequest};
+use zksync_storage::{
+    sponsorship::records::{CpkSponsorship, FeeFreeTransfer},
+    withdrawal_hooks::records::NewWithdrawalHook,
+    ConnectionPool, StorageProcessor,
+};
 use zksync_types::{
     mempool::SignedTxsBatch,
     tx::{error::TxAddError, TxEthSignature},
@@ -15,7 +21,40 @@ pub enum MempoolTransactionRequest {
     /// Add new transaction to mempool, transaction should be previously checked
     /// for correctness (including its Ethereum and ZKSync signatures).
     /// oneshot is used to receive tx add result.
-    NewTx(Box<SignedZkSyncTx>, oneshot::Sender<Result<(), TxAddError>>),
+    /// The span is the one of the request that submitted the transaction, so adding
+    /// the transaction to the mempool is traced as a part of this request.
+    NewTx(
+        Box<SignedZkSyncTx>,
+        oneshot::Sender<Result<(), TxAddError>>,
+        Span,
+    ),
+    /// Add new `ChangePubKey` transaction with the fee paid by the operator.
+    /// The sponsorship is stored along with the transaction, the transaction is rejected
+    /// if the account has already been sponsored.
+    NewSponsoredTx(
+        Box<SignedZkSyncTx>,
+        CpkSponsorship,
+        oneshot::Sender<Result<(), TxAddError>>,
+        Span,
+    ),
+    /// Add new transfer of the operator address with the fee waived.
+    /// The transfer is stored along with the transaction, the transaction is rejected
+    /// if the daily quota of the address (the second field) is exhausted.
+    NewFeeFreeTx(
+        Box<SignedZkSyncTx>,
+        FeeFreeTransfer,
+        u64,
+        oneshot::Sender<Result<(), TxAddError>>,
+        Span,
+    ),
+    /// Add new `Withdraw` transaction with the call made to the recipient on L1.
+    /// The hook is stored along with the transaction.
+    NewWithdrawalTx(
+        Box<SignedZkSyncTx>,
+        NewWithdrawalHook,
+        oneshot::Sender<Result<(), TxAddError>>,
+        Span,
+    ),
 
     /// Add new priority ops, confirmed or not
     NewPriorityOps(
@@ -26,14 +65,57 @@ pub enum MempoolTransactionRequest {
     /// Add a new batch of transactions to the mempool. All transactions in batch must
     /// be either executed successfully, or otherwise fail all together.
     /// Invariants for each individual transaction in the batch are the same as in
-    /// `NewTx` variant of this enum.
+    /// `NewTx` variant of this enum. The hooks of the withdrawals are stored along with the batch.
     NewTxsBatch(
         Vec<SignedZkSyncTx>,
         Vec<TxEthSignature>,
+        Vec<NewWithdrawalHook>,
         oneshot::Sender<Result<(), TxAddError>>,
+        Span,
     ),
 }
 
+impl ShardedRequest for MempoolTransactionRequest {
+    /// Transactions are sharded by the account, batches by the account of their first transaction.
+    /// Transactions of the other accounts of the batch are thus not ordered with the ones submitted
+    /// by these accounts separately, such a transaction may be rejected because of the nonce.
+    /// Priority operations are ordered by their serial ID, so they are always admitted by the same shard.
+    fn shard_key(&self) -> u64 {
+        let tx = match self {
+            Self::NewTx(tx, ..)
+            | Self::NewSponsoredTx(tx, ..)
+            | Self::NewFeeFreeTx(tx, ..)
+            | Self::NewWithdrawalTx(tx, ..) => Some(tx.as_ref()),
+            Self::NewTxsBatch(txs, ..) => txs.first(),
+            Self::NewPriorityOps(..) => None,
+        };
+        tx.and_then(|tx| tx.account_id().ok())
+            .map_or(0, |account_id| *account_id as u64)
+    }
+}
+
+pub(crate) struct MempoolTransactionsHandlerBuilder {
+    pub db_pool: ConnectionPool,
+    pub mempool_state: MempoolState,
+    pub max_block_size_chunks: usize,
+}
+
+impl BuildBalancedItem<MempoolTransactionRequest, MempoolTransactionsHandler>
+    for MempoolTransactionsHandlerBuilder
+{
+    fn build_with_receiver(
+        &self,
+        receiver: mpsc::Receiver<MempoolTransactionRequest>,
+    ) -> MempoolTransactionsHandler {
+        MempoolTransactionsHandler {
+            db_pool: self.db_pool.clone(),
+            mempool_state: self.mempool_state.clone(),
+            requests: receiver,
+            max_block_size_chunks: self.max_block_size_chunks,
+        }
+    }
+}
+
 pub(crate) struct MempoolTransactionsHandler {
     pub db_pool: ConnectionPool,
     pub mempool_state: MempoolState,
@@ -43,13 +125,132 @@ pub(crate) struct MempoolTransactionsHandler {
 
 impl MempoolTransactionsHandler {
     async fn add_tx(&mut self, tx: SignedZkSyncTx) -> Result<(), TxAddError> {
-        // Correctness should be checked by `signature_checker`, thus
-        // `tx.check_correctness()` is not invoked here.
         let mut storage = self.db_pool.access_storage().await.map_err(|err| {
             vlog::error!("Mempool storage access error: {}", err);
             TxAddError::DbError
         })?;
 
+        Self::insert_tx(&mut storage, &tx).await
+    }
+
+    async fn add_sponsored_tx(
+        &mut self,
+        tx: SignedZkSyncTx,
+        sponsorship: CpkSponsorship,
+    ) -> Result<(), TxAddError> {
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        let stored = transaction
+            .sponsorship_schema()
+            .store_cpk_sponsorship(&sponsorship)
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        // The fee is waived only once per account.
+        if !stored {
+            return Err(TxAddError::TxFeeTooLow);
+        }
+        Self::insert_tx(&mut transaction, &tx).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        metrics::increment_counter!("mempool.sponsored_cpk_count");
+        Ok(())
+    }
+
+    async fn add_fee_free_tx(
+        &mut self,
+        tx: SignedZkSyncTx,
+        transfer: FeeFreeTransfer,
+        daily_quota: u64,
+    ) -> Result<(), TxAddError> {
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        let stored = transaction
+            .sponsorship_schema()
+            .store_fee_free_transfer(&transfer, daily_quota)
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        // Transfers beyond the quota have to pay the fee.
+        if !stored {
+            metrics::increment_counter!("mempool.fee_free_quota_exhausted");
+            return Err(TxAddError::TxFeeTooLow);
+        }
+        Self::insert_tx(&mut transaction, &tx).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        metrics::increment_counter!("mempool.fee_free_transfer_count");
+        Ok(())
+    }
+
+    async fn add_withdrawal_tx(
+        &mut self,
+        tx: SignedZkSyncTx,
+        hook: NewWithdrawalHook,
+    ) -> Result<(), TxAddError> {
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        Self::insert_tx(&mut transaction, &tx).await?;
+        Self::store_withdrawal_hooks(&mut transaction, &[hook]).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        Ok(())
+    }
+
+    async fn store_withdrawal_hooks(
+        storage: &mut StorageProcessor<'_>,
+        hooks: &[NewWithdrawalHook],
+    ) -> Result<(), TxAddError> {
+        storage
+            .withdrawal_hooks_schema()
+            .store_withdrawal_hooks(hooks)
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })
+    }
+
+    async fn insert_tx(
+        storage: &mut StorageProcessor<'_>,
+        tx: &SignedZkSyncTx,
+    ) -> Result<(), TxAddError> {
+        // Correctness should be checked by `signature_checker`, thus
+        // `tx.check_correctness()` is not invoked here.
         let nonce = storage
             .chain()
             .account_schema()
@@ -66,7 +267,7 @@ impl MempoolTransactionsHandler {
         storage
             .chain()
             .mempool_schema()
-            .insert_tx(&tx)
+            .insert_tx(tx)
             .await
             .map_err(|err| {
                 vlog::error!("Mempool storage access error: {}", err);
@@ -137,6 +338,7 @@ impl MempoolTransactionsHandler {
         &mut self,
         txs: Vec<SignedZkSyncTx>,
         eth_signatures: Vec<TxEthSignature>,
+        withdrawal_hooks: Vec<NewWithdrawalHook>,
     ) -> Result<(), TxAddError> {
         let batch = SignedTxsBatch {
             txs: txs.clone(),
@@ -180,7 +382,11 @@ impl MempoolTransactionsHandler {
             metrics::histogram!("process_tx", tx.elapsed(), &labels);
         }
 
-        storage
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        transaction
             .chain()
             .mempool_schema()
             .insert_batch(&batch.txs, eth_signatures)
@@ -189,7 +395,12 @@ impl MempoolTransactionsHandler {
                 vlog::warn!("Mempool storage access error: {}", err);
                 TxAddError::DbError
             })?;
+        Self::store_withdrawal_hooks(&mut transaction, &withdrawal_hooks).await?;
 
+        transaction
+            .commit()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
         Ok(())
     }
 
@@ -197,12 +408,39 @@ impl MempoolTransactionsHandler {
         vlog::info!("Transaction mempool handler is running");
         while let Some(request) = self.requests.next().await {
             match request {
-                MempoolTransactionRequest::NewTx(tx, resp) => {
-                    let tx_add_result = self.add_tx(*tx).await;
+                MempoolTransactionRequest::NewTx(tx, resp, span) => {
+                    let tx_add_result = self.add_tx(*tx).instrument(span).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
-                MempoolTransactionRequest::NewTxsBatch(txs, eth_signatures, resp) => {
-                    let tx_add_result = self.add_batch(txs, eth_signatures).await;
+                MempoolTransactionRequest::NewSponsoredTx(tx, sponsorship, resp, span) => {
+                    let tx_add_result = self
+                        .add_sponsored_tx(*tx, sponsorship)
+                        .instrument(span)
+                        .await;
+                    resp.send(tx_add_result).unwrap_or_default();
+                }
+                MempoolTransactionRequest::NewFeeFreeTx(tx, transfer, daily_quota, resp, span) => {
+                    let tx_add_result = self
+                        .add_fee_free_tx(*tx, transfer, daily_quota)
+                        .instrument(span)
+                        .await;
+                    resp.send(tx_add_result).unwrap_or_default();
+                }
+                MempoolTransactionRequest::NewWithdrawalTx(tx, hook, resp, span) => {
+                    let tx_add_result = self.add_withdrawal_tx(*tx, hook).instrument(span).await;
+                    resp.send(tx_add_result).unwrap_or_default();
+                }
+                MempoolTransactionRequest::NewTxsBatch(
+                    txs,
+                    eth_signatures,
+                    withdrawal_hooks,
+                    resp,
+                    span,
+                ) => {
+                    let tx_add_result = self
+                        .add_batch(txs, eth_signatures, withdrawal_hooks)
+                        .instrument(span)
+                        .await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
                 MempoolTransactionRequest::NewPriorityOps(ops, confirmed, resp) => {
@@ -213,3 +451,62 @@ impl MempoolTransactionsHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use zksync_types::{
+        tx::{TimeRange, Transfer},
+        AccountId, Address, Nonce, TokenId, ZkSyncTx,
+    };
+
+    use super::*;
+
+    fn transfer(account_id: u32) -> SignedZkSyncTx {
+        let transfer = Transfer::new(
+            AccountId(account_id),
+            Address::random(),
+            Address::random(),
+            TokenId(0),
+            500u32.into(),
+            20u32.into(),
+            Nonce(0),
+            TimeRange::default(),
+            None,
+        );
+        SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn shards_batches_by_first_account() {
+        let tx = MempoolTransactionRequest::NewTx(
+            Box::new(transfer(7)),
+            oneshot::channel().0,
+            Span::none(),
+        );
+        assert_eq!(tx.shard_key(), 7);
+
+        let batch = MempoolTransactionRequest::NewTxsBatch(
+            vec![transfer(3), transfer(7)],
+            Vec::new(),
+            Vec::new(),
+            oneshot::channel().0,
+            Span::none(),
+        );
+        assert_eq!(batch.shard_key(), 3);
+
+        let empty_batch = MempoolTransactionRequest::NewTxsBatch(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            oneshot::channel().0,
+            Span::none(),
+        );
+        assert_eq!(empty_batch.shard_key(), 0);
+    }
+}
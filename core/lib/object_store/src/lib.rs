@@ -0,0 +1,158 @@
+//! Client of the S3-compatible object storage.
+//!
+//! The object storage holds the archive of the pruned data: the pruner uploads the bodies of
+//! the transactions before removing them from the database, and the API falls back to the
+//! archive for the transactions which are not found in the database anymore.
+//!
+//! Requests are authenticated with the AWS Signature Version 4 and the objects are addressed
+//! in the path style (`{endpoint}/{bucket}/{key}`), which is supported by all the major
+//! S3-compatible storages.
+
+// Built-in uses
+use std::{collections::BTreeMap, time::Instant};
+// External uses
+use chrono::{DateTime, Utc};
+use reqwest::{header, Method, StatusCode, Url};
+// Workspace uses
+use zksync_config::ObjectStoreConfig;
+// Local uses
+use self::signer::RequestSigner;
+
+mod signer;
+
+/// Error returned by the object storage requests.
+#[derive(Debug, thiserror::Error)]
+pub enum ObjectStoreError {
+    #[error("Object storage request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Object storage responded with {0}: {1}")]
+    Status(StatusCode, String),
+}
+
+/// Returns the key of the archived body of the pruned transaction or priority operation.
+pub fn archived_tx_key(tx_hash: &[u8]) -> String {
+    format!("pruned/txs/{}.json", hex::encode(tx_hash))
+}
+
+/// Client of the S3-compatible object storage.
+#[derive(Debug, Clone)]
+pub struct ObjectStore {
+    client: reqwest::Client,
+    endpoint: Url,
+    host: String,
+    bucket: String,
+    key_prefix: String,
+    signer: RequestSigner,
+}
+
+impl ObjectStore {
+    /// Creates the client, returns `None` if the object storage is not configured.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the endpoint is not a valid URL.
+    pub fn new(config: &ObjectStoreConfig) -> Option<Self> {
+        if !config.is_enabled() {
+            return None;
+        }
+        let endpoint = Url::parse(config.endpoint.trim_end_matches('/'))
+            .expect("invalid object storage endpoint");
+        let host = match endpoint.port() {
+            Some(port) => format!("{}:{}", endpoint.host_str()?, port),
+            None => endpoint.host_str()?.to_owned(),
+        };
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout())
+            .build()
+            .expect("failed to build the object storage HTTP client");
+
+        Some(Self {
+            client,
+            endpoint,
+            host,
+            bucket: config.bucket.clone(),
+            key_prefix: config.key_prefix.trim_matches('/').to_owned(),
+            signer: RequestSigner::new(
+                &config.access_key_id,
+                &config.secret_access_key,
+                &config.region,
+            ),
+        })
+    }
+
+    /// Uploads the object, overwriting the existing one with the same key.
+    pub async fn put(&self, key: &str, body: Vec<u8>) -> Result<(), ObjectStoreError> {
+        let start = Instant::now();
+        let response = self.send(Method::PUT, key, body).await?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(ObjectStoreError::Status(status, text));
+        }
+
+        metrics::histogram!("object_store.request", start.elapsed(), "method" => "put");
+        Ok(())
+    }
+
+    /// Downloads the object, returns `None` if there is no object with such key.
+    pub async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ObjectStoreError> {
+        let start = Instant::now();
+        let response = self.send(Method::GET, key, Vec::new()).await?;
+        let status = response.status();
+        let object = match status {
+            StatusCode::NOT_FOUND => None,
+            status if status.is_success() => Some(response.bytes().await?.to_vec()),
+            status => {
+                let text = response.text().await.unwrap_or_default();
+                return Err(ObjectStoreError::Status(status, text));
+            }
+        };
+
+        metrics::histogram!("object_store.request", start.elapsed(), "method" => "get");
+        Ok(object)
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response, ObjectStoreError> {
+        let path = self.object_path(key);
+        let mut url = self.endpoint.clone();
+        url.set_path(&path);
+
+        let now: DateTime<Utc> = Utc::now();
+        let payload_hash = signer::sha256_hex(&body);
+        let mut headers = BTreeMap::new();
+        headers.insert("host".to_owned(), self.host.clone());
+        headers.insert("x-amz-content-sha256".to_owned(), payload_hash.clone());
+        headers.insert("x-amz-date".to_owned(), signer::amz_date(now));
+        let authorization =
+            self.signer
+                .authorization(method.as_str(), &path, &headers, &payload_hash, now);
+
+        let response = self
+            .client
+            .request(method, url)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", signer::amz_date(now))
+            .header(header::AUTHORIZATION, authorization)
+            .body(body)
+            .send()
+            .await?;
+        Ok(response)
+    }
+
+    /// Returns the URI-encoded path of the object relative to the endpoint.
+    fn object_path(&self, key: &str) -> String {
+        let mut path = format!("/{}", signer::uri_encode(&self.bucket));
+        for segment in self.key_prefix.split('/').chain(key.split('/')) {
+            if !segment.is_empty() {
+                path.push('/');
+                path.push_str(&signer::uri_encode(segment));
+            }
+        }
+        path
+    }
+}
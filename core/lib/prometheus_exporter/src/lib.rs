@@ -58,6 +58,9 @@ async fn prometheus_exporter_iteration(connection_pool: ConnectionPool) -> Query
         .await?;
     metrics::gauge!("mempool_size", mempool_size as f64);
 
+    let prover_queue_depth = transaction.prover_schema().pending_jobs_count().await?;
+    metrics::gauge!("prover_queue_depth", prover_queue_depth as f64);
+
     transaction.commit().await?;
     Ok(())
 }
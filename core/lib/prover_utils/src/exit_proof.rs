@@ -8,7 +8,11 @@ use zksync_circuit::exit_circuit::create_exit_circuit_with_public_input;
 use zksync_crypto::circuit::account::CircuitAccount;
 use zksync_crypto::circuit::CircuitAccountTree;
 use zksync_crypto::proof::EncodedSingleProof;
-use zksync_types::{AccountId, AccountMap, Address, TokenId, H256};
+use zksync_types::{
+    block::Block,
+    exit_proof::{ExitProofData, StoredBlockInfo},
+    AccountId, AccountMap, Address, Token, TokenId, H256, NFT,
+};
 
 fn create_exit_proof(
     accounts: AccountMap,
@@ -97,3 +101,62 @@ pub fn create_exit_proof_nft(
         content_hash,
     )
 }
+
+/// Generates the exit proof for the account balance in the state after the given block,
+/// along with the rest of the exit transaction inputs.
+///
+/// `nft` should be provided if the token is an NFT.
+pub fn create_exit_proof_data(
+    accounts: AccountMap,
+    block: &Block,
+    account_id: AccountId,
+    token: &Token,
+    nft: Option<&NFT>,
+) -> Result<ExitProofData, anyhow::Error> {
+    let owner = accounts
+        .get(&account_id)
+        .map(|account| account.address)
+        .ok_or_else(|| format_err!("Fund account not found: id: {}", *account_id))?;
+
+    let (nft_creator_id, nft_creator_address, nft_serial_id, nft_content_hash) = match nft {
+        Some(nft) => (
+            nft.creator_id,
+            nft.creator_address,
+            nft.serial_id,
+            nft.content_hash,
+        ),
+        None => {
+            // The placeholder creator address should be the address
+            // of the account with id 0
+            let creator_address = accounts
+                .get(&AccountId(0))
+                .map(|account| account.address)
+                .ok_or_else(|| format_err!("Account with id 0 does not exist"))?;
+            (AccountId(0), creator_address, 0, H256::default())
+        }
+    };
+
+    let (proof, amount) = create_exit_proof(
+        accounts,
+        account_id,
+        owner,
+        token.id,
+        nft_creator_id,
+        nft_serial_id,
+        nft_content_hash,
+    )?;
+
+    Ok(ExitProofData {
+        stored_block_info: StoredBlockInfo::from_block(block),
+        owner,
+        account_id,
+        token_id: token.id,
+        amount,
+        nft_creator_id,
+        nft_creator_address,
+        nft_serial_id,
+        nft_content_hash,
+        proof,
+        token_address: token.address,
+    })
+}
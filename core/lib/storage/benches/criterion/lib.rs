@@ -0,0 +1,7 @@
+use criterion::criterion_main;
+
+use operations_ext::operations_ext_benches;
+
+mod operations_ext;
+
+criterion_main!(operations_ext_benches);
@@ -0,0 +1,171 @@
+//! Benchmarks for the hot `chain.operations_ext` queries on the large dataset.
+//!
+//! The database from the `DATABASE_URL` is populated with the load test data on the first run
+//! (see `zksync_storage::test_data::load_test`), and the next runs reuse it, so the results of
+//! different revisions can be compared with the criterion baselines.
+//! Use `zk test db-bench` to run the benchmarks against the dedicated database.
+
+// External uses
+use criterion::{criterion_group, Criterion};
+use tokio::runtime::Runtime;
+// Workspace uses
+use zksync_storage::{
+    test_data::load_test::{populate_load_test_data, LoadTestConfig},
+    ConnectionPool,
+};
+use zksync_types::{tx::TxHash, Address, BlockNumber, TokenId};
+
+/// Query arguments picked from the generated dataset.
+struct BenchData {
+    /// The most active account, e.g. an exchange.
+    hot_account: Address,
+    /// Account with a few dozens of transactions.
+    regular_account: Address,
+    executed_tx_hash: TxHash,
+}
+
+/// Populates the database unless it's populated by the previous runs.
+async fn prepare_data(pool: &ConnectionPool) -> BenchData {
+    let config = LoadTestConfig::default();
+    let mut storage = pool.access_storage().await.unwrap();
+    let last_block = storage
+        .chain()
+        .block_schema()
+        .get_last_saved_block()
+        .await
+        .unwrap();
+    if last_block == BlockNumber(0) {
+        populate_load_test_data(&mut storage, &config)
+            .await
+            .unwrap();
+    }
+
+    let accounts = config.accounts();
+    let regular_account = accounts[accounts.len() / 10];
+    let executed_tx_hash = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_last_tx_hash(regular_account)
+        .await
+        .unwrap()
+        .expect("database is populated with a different config");
+    BenchData {
+        hot_account: accounts[0],
+        regular_account,
+        executed_tx_hash,
+    }
+}
+
+fn bench_tx_receipt(c: &mut Criterion, runtime: &Runtime, pool: &ConnectionPool, data: &BenchData) {
+    let mut group = c.benchmark_group("tx_receipt_api_v02");
+
+    let tx_hash = data.executed_tx_hash;
+    group.bench_function("executed_tx", |b| {
+        b.to_async(runtime).iter(|| async move {
+            let mut storage = pool.access_storage().await.unwrap();
+            storage
+                .chain()
+                .operations_ext_schema()
+                .tx_receipt_api_v02(tx_hash.as_ref())
+                .await
+                .unwrap()
+                .expect("receipt of the executed tx")
+        })
+    });
+    // Receipts of the transactions which are not executed yet are polled the most.
+    group.bench_function("unknown_tx", |b| {
+        b.to_async(runtime).iter(|| async move {
+            let mut storage = pool.access_storage().await.unwrap();
+            storage
+                .chain()
+                .operations_ext_schema()
+                .tx_receipt_api_v02(&[0xff; 32])
+                .await
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_transactions_history(
+    c: &mut Criterion,
+    runtime: &Runtime,
+    pool: &ConnectionPool,
+    data: &BenchData,
+) {
+    let mut group = c.benchmark_group("get_account_transactions_history");
+
+    let cases = [
+        ("hot_account", data.hot_account, 0),
+        ("hot_account_deep_offset", data.hot_account, 10_000),
+        ("regular_account", data.regular_account, 0),
+    ];
+    for (name, address, offset) in cases.iter().copied() {
+        group.bench_function(name, |b| {
+            b.to_async(runtime).iter(|| async move {
+                let mut storage = pool.access_storage().await.unwrap();
+                storage
+                    .chain()
+                    .operations_ext_schema()
+                    .get_account_transactions_history(&address, offset, 25)
+                    .await
+                    .unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_transactions_count(
+    c: &mut Criterion,
+    runtime: &Runtime,
+    pool: &ConnectionPool,
+    data: &BenchData,
+) {
+    let mut group = c.benchmark_group("get_account_transactions_count");
+
+    let cases = [
+        ("hot_account", data.hot_account, None, None),
+        (
+            "hot_account_token",
+            data.hot_account,
+            Some(TokenId(0)),
+            None,
+        ),
+        (
+            "pair_of_accounts",
+            data.hot_account,
+            None,
+            Some(data.regular_account),
+        ),
+    ];
+    for (name, address, token, second_address) in cases.iter().copied() {
+        group.bench_function(name, |b| {
+            b.to_async(runtime).iter(|| async move {
+                let mut storage = pool.access_storage().await.unwrap();
+                storage
+                    .chain()
+                    .operations_ext_schema()
+                    .get_account_transactions_count(address, token, second_address)
+                    .await
+                    .unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+pub fn bench_operations_ext(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let pool = ConnectionPool::new(Some(1));
+    let data = runtime.block_on(prepare_data(&pool));
+
+    bench_tx_receipt(c, &runtime, &pool, &data);
+    bench_transactions_history(c, &runtime, &pool, &data);
+    bench_transactions_count(c, &runtime, &pool, &data);
+}
+
+criterion_group!(operations_ext_benches, bench_operations_ext);
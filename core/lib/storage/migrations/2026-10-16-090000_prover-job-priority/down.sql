@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS ix_prover_job_queue_job_status_job_priority;
+DROP TABLE IF EXISTS prover_priority_blocks;
@@ -0,0 +1,9 @@
+-- Blocks which were marked by the operator as high priority ones.
+-- Prover jobs containing any of these blocks are served before the others.
+CREATE TABLE prover_priority_blocks
+(
+    block_number bigint                   PRIMARY KEY,
+    created_at   timestamp with time zone NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS ix_prover_job_queue_job_status_job_priority ON prover_job_queue (job_status, job_priority, id);
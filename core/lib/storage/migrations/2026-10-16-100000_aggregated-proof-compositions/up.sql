@@ -0,0 +1,12 @@
+-- Composition of the aggregated proofs chosen by the aggregation policy, stored for the cost analysis.
+CREATE TABLE aggregated_proof_compositions
+(
+    first_block           bigint                   NOT NULL,
+    last_block            bigint                   NOT NULL,
+    aggregated_proof_size int                      NOT NULL,
+    blocks_count          int                      NOT NULL,
+    padded_proofs         int                      NOT NULL,
+    trigger               text                     NOT NULL,
+    created_at            timestamp with time zone NOT NULL DEFAULT now(),
+    PRIMARY KEY (first_block, last_block)
+);
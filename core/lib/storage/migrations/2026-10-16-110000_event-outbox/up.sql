@@ -0,0 +1,33 @@
+-- Durable log of the events to be delivered to the external consumers.
+CREATE TABLE event_outbox
+(
+    id         bigserial PRIMARY KEY,
+    event_type text                     NOT NULL,
+    payload    jsonb                    NOT NULL,
+    created_at timestamp with time zone NOT NULL DEFAULT now()
+);
+
+-- Webhooks registered by the operator. Empty `event_types` means that all the events are delivered.
+CREATE TABLE webhook_subscriptions
+(
+    id          bigserial PRIMARY KEY,
+    url         text                     NOT NULL,
+    secret      text                     NOT NULL,
+    event_types text[]                   NOT NULL,
+    created_at  timestamp with time zone NOT NULL DEFAULT now()
+);
+
+-- Delivery of every outbox event to every matching subscription.
+CREATE TABLE webhook_deliveries
+(
+    id              bigserial PRIMARY KEY,
+    subscription_id bigint                   NOT NULL REFERENCES webhook_subscriptions (id) ON DELETE CASCADE,
+    event_id        bigint                   NOT NULL REFERENCES event_outbox (id) ON DELETE CASCADE,
+    status          text                     NOT NULL,
+    attempts        int                      NOT NULL DEFAULT 0,
+    next_attempt_at timestamp with time zone NOT NULL DEFAULT now(),
+    last_error      text,
+    updated_at      timestamp with time zone NOT NULL DEFAULT now(),
+    UNIQUE (subscription_id, event_id)
+);
+CREATE INDEX ix_webhook_deliveries_status_next_attempt_at ON webhook_deliveries (status, next_attempt_at);
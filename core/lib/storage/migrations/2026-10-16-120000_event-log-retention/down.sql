@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS events_retention_state;
+ALTER TABLE events DROP COLUMN IF EXISTS created_at;
@@ -0,0 +1,9 @@
+ALTER TABLE events ADD COLUMN created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now();
+CREATE INDEX events_created_at_idx ON events (created_at);
+
+-- Id of the last event removed by the retention policy. Single-row table.
+CREATE TABLE events_retention_state (
+    id BOOLEAN PRIMARY KEY DEFAULT true CHECK (id),
+    last_pruned_event_id BIGINT NOT NULL
+);
+INSERT INTO events_retention_state (last_pruned_event_id) VALUES (0);
@@ -0,0 +1,9 @@
+-- Storage method calls which took longer than the configured threshold.
+CREATE TABLE slow_queries (
+    id BIGSERIAL PRIMARY KEY,
+    schema_name TEXT NOT NULL,
+    method TEXT NOT NULL,
+    duration_ms BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX slow_queries_created_at_idx ON slow_queries (created_at);
@@ -0,0 +1,7 @@
+-- Checkpoints of the data restore progress, the restoring is resumed from the latest one.
+CREATE TABLE data_restore_checkpoints (
+    block_number BIGINT PRIMARY KEY,
+    last_watched_eth_block BIGINT NOT NULL,
+    root_hash BYTEA NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
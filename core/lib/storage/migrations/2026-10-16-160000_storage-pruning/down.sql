@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS pruning_state;
+DROP SCHEMA IF EXISTS archive CASCADE;
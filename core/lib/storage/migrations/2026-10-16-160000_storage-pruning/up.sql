@@ -0,0 +1,20 @@
+-- Old rows moved out of the hot tables by the pruner.
+CREATE SCHEMA IF NOT EXISTS archive;
+
+CREATE TABLE archive.executed_transactions (LIKE executed_transactions);
+ALTER TABLE archive.executed_transactions ADD PRIMARY KEY (tx_hash);
+CREATE INDEX archive_executed_transactions_block_number_index
+    ON archive.executed_transactions (block_number);
+
+CREATE TABLE archive.executed_priority_operations (LIKE executed_priority_operations);
+ALTER TABLE archive.executed_priority_operations ADD PRIMARY KEY (priority_op_serialid);
+CREATE INDEX archive_executed_priority_operations_tx_hash_index
+    ON archive.executed_priority_operations (tx_hash);
+CREATE INDEX archive_executed_priority_operations_block_number_index
+    ON archive.executed_priority_operations (block_number);
+
+-- All the rows of the table from the blocks up to `last_pruned_block` are pruned.
+CREATE TABLE pruning_state (
+    table_name TEXT PRIMARY KEY,
+    last_pruned_block BIGINT NOT NULL
+);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS ix_executed_priority_operations_block_number_block_index;
+DROP INDEX IF EXISTS ix_executed_transactions_block_number_block_index;
+DROP INDEX IF EXISTS ix_tx_filters_address_token_sequence_number;
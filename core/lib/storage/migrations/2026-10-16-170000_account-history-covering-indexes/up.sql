@@ -0,0 +1,11 @@
+-- Account history filtered by the token, ordered by the sequence number.
+-- `is_priority` is included, so the sequence numbers are loaded by an index-only scan.
+CREATE INDEX IF NOT EXISTS ix_tx_filters_address_token_sequence_number
+    ON tx_filters USING btree (address, token, sequence_number) INCLUDE (is_priority);
+
+-- Lookup of the sequence number by the position of the operation in the block,
+-- used to paginate the account history starting from the given transaction.
+CREATE INDEX IF NOT EXISTS ix_executed_transactions_block_number_block_index
+    ON executed_transactions USING btree (block_number, block_index) INCLUDE (sequence_number);
+CREATE INDEX IF NOT EXISTS ix_executed_priority_operations_block_number_block_index
+    ON executed_priority_operations USING btree (block_number, block_index) INCLUDE (sequence_number);
@@ -0,0 +1,7 @@
+-- Maintenance mode of the server, the table has at most one row.
+-- Transactions are not accepted while the row is present.
+CREATE TABLE maintenance_mode (
+    id BOOLEAN PRIMARY KEY DEFAULT true CHECK (id),
+    reason TEXT NOT NULL,
+    enabled_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
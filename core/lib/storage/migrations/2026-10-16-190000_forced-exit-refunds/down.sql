@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS forced_exit_requests_pending_refunds_idx;
+
+ALTER TABLE forced_exit_requests DROP COLUMN refunded_at;
+ALTER TABLE forced_exit_requests DROP COLUMN refunded_by;
+ALTER TABLE forced_exit_requests DROP COLUMN refund_in_wei;
+ALTER TABLE forced_exit_requests DROP COLUMN refund_address;
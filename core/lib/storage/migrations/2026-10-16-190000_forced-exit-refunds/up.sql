@@ -0,0 +1,8 @@
+-- Refund of the fee paid in excess of the request price.
+ALTER TABLE forced_exit_requests ADD COLUMN refund_address TEXT;
+ALTER TABLE forced_exit_requests ADD COLUMN refund_in_wei NUMERIC;
+ALTER TABLE forced_exit_requests ADD COLUMN refunded_by TEXT; -- hash of the refund Transfer transaction
+ALTER TABLE forced_exit_requests ADD COLUMN refunded_at TIMESTAMP with time zone;
+
+CREATE INDEX forced_exit_requests_pending_refunds_idx ON forced_exit_requests (id)
+    WHERE refund_in_wei IS NOT NULL AND refunded_at IS NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE forced_exit_requests DROP COLUMN paid_at;
+ALTER TABLE forced_exit_requests DROP COLUMN payment_tx_hash;
@@ -0,0 +1,3 @@
+-- Payment of the request seen at L1.
+ALTER TABLE forced_exit_requests ADD COLUMN payment_tx_hash TEXT; -- hash of the Ethereum transaction
+ALTER TABLE forced_exit_requests ADD COLUMN paid_at TIMESTAMP with time zone;
@@ -0,0 +1,12 @@
+-- ChangePubKey transactions of the new accounts with the fee paid by the operator.
+-- Every account is sponsored only once.
+CREATE TABLE cpk_sponsorships (
+    id BIGSERIAL PRIMARY KEY,
+    account_id BIGINT NOT NULL UNIQUE,
+    tx_hash bytea NOT NULL,
+    fee_token INT NOT NULL,
+    waived_fee NUMERIC NOT NULL,
+    -- Scaled by 10^6, like the amounts of the subsidies
+    waived_fee_usd_scale6 BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1,16 @@
+-- Decisions of the withdrawal policies applied by the API servers on the transactions submission.
+CREATE TABLE withdrawal_policy_decisions (
+    id BIGSERIAL PRIMARY KEY,
+    tx_hash bytea NOT NULL,
+    address bytea NOT NULL,
+    token_id INT NOT NULL,
+    amount NUMERIC NOT NULL,
+    -- Scaled by 10^6, like the amounts of the subsidies
+    amount_usd_scale6 BIGINT NOT NULL,
+    allowed BOOLEAN NOT NULL,
+    reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX withdrawal_policy_decisions_address_idx
+    ON withdrawal_policy_decisions (address, created_at);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS label_api_keys;
+DROP TABLE IF EXISTS address_labels;
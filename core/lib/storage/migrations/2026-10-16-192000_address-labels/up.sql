@@ -0,0 +1,21 @@
+-- Labels of the addresses shown in the explorer-facing responses.
+-- Labels without the scope are set by the operator and visible to everyone,
+-- the scoped ones are user-defined tags visible only to the holders of the API keys of the scope.
+CREATE TABLE address_labels (
+    id BIGSERIAL PRIMARY KEY,
+    address bytea NOT NULL,
+    label TEXT NOT NULL,
+    category TEXT NOT NULL,
+    scope TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX address_labels_address_label_scope_idx
+    ON address_labels (address, label, (COALESCE(scope, '')));
+
+-- API keys allowed to manage the user-defined labels, only the SHA-256 hashes of the keys are stored.
+CREATE TABLE label_api_keys (
+    key_hash bytea PRIMARY KEY,
+    scope TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1,16 @@
+-- Exit proofs of the account balances requested through the API, generated in the background
+-- against the state of the last verified block.
+CREATE TABLE exit_proofs (
+    account_id BIGINT NOT NULL,
+    token_id INTEGER NOT NULL,
+    -- Block which state the proof is generated for, set once the generation is finished.
+    block_number BIGINT,
+    proof_data jsonb,
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    started_at TIMESTAMP WITH TIME ZONE,
+    finished_at TIMESTAMP WITH TIME ZONE,
+    PRIMARY KEY (account_id, token_id)
+);
+
+CREATE INDEX exit_proofs_pending_idx ON exit_proofs (created_at) WHERE finished_at IS NULL;
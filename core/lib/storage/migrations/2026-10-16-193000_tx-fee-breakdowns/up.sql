@@ -0,0 +1,18 @@
+-- Decomposition of the fees of the transactions. The fee quote the transaction was accepted
+-- against is stored on submission, and the fee actually charged is split according to it
+-- once the transaction is executed.
+CREATE TABLE tx_fee_breakdowns (
+    tx_hash bytea PRIMARY KEY,
+    token_id INTEGER NOT NULL,
+    -- Parts of the quote, only their ratio is used to split the charged fee.
+    quoted_zkp_fee NUMERIC NOT NULL,
+    quoted_gas_fee NUMERIC NOT NULL,
+    -- Amount of the fee paid by the operator, in the fee token.
+    subsidy NUMERIC NOT NULL,
+    -- Set once the transaction is executed.
+    charged_fee NUMERIC,
+    zkp_fee NUMERIC,
+    gas_fee NUMERIC,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    executed_at TIMESTAMP WITH TIME ZONE
+);
@@ -0,0 +1,9 @@
+-- Hourly history of the token prices, used to value the balances and the fees in the past.
+-- `ticker_price` keeps only the latest price of every token.
+CREATE TABLE ticker_price_history (
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    -- Start of the hour the price belongs to, the last price observed within the hour is kept.
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    usd_price NUMERIC NOT NULL,
+    PRIMARY KEY (token_id, recorded_at)
+);
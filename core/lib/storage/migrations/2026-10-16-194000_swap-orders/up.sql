@@ -0,0 +1,26 @@
+-- Signed swap orders submitted to the order book, so the order-matching service and the trading
+-- frontends can query the orders which are not matched yet.
+CREATE TABLE swap_orders (
+    id BIGSERIAL NOT NULL UNIQUE,
+    order_hash bytea PRIMARY KEY,
+    account_id BIGINT NOT NULL,
+    nonce BIGINT NOT NULL,
+    token_sell INTEGER NOT NULL,
+    token_buy INTEGER NOT NULL,
+    -- Zero for the limit orders, which can be filled partially by several swaps.
+    amount NUMERIC NOT NULL,
+    valid_until BIGINT NOT NULL,
+    "order" jsonb NOT NULL,
+    eth_signature jsonb,
+    -- One of `open`, `matched`, `filled` or `cancelled`. Open orders with the passed
+    -- `valid_until` are reported as expired.
+    status TEXT NOT NULL DEFAULT 'open',
+    filled_amount NUMERIC NOT NULL DEFAULT 0,
+    -- Last swap transaction the order was matched in.
+    swap_tx_hash bytea,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX swap_orders_open_idx ON swap_orders (token_sell, token_buy, id) WHERE status = 'open';
+CREATE INDEX swap_orders_account_id_idx ON swap_orders (account_id, id);
@@ -0,0 +1,40 @@
+-- Standing orders: recurring payments registered by the users as the sets of pre-signed
+-- transfers of the consecutive nonces, which are submitted by the server at the scheduled times.
+CREATE TABLE standing_orders (
+    id BIGSERIAL PRIMARY KEY,
+    account_id BIGINT NOT NULL,
+    from_address bytea NOT NULL,
+    to_address bytea NOT NULL,
+    token_id INTEGER NOT NULL,
+    amount NUMERIC NOT NULL,
+    fee NUMERIC NOT NULL,
+    signer_pub_key_hash bytea NOT NULL,
+    first_payment_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    interval_secs BIGINT NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- One of `active`, `completed`, `cancelled` or `failed`.
+    status TEXT NOT NULL DEFAULT 'active',
+    failure_reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX standing_orders_account_id_idx ON standing_orders (account_id, id);
+
+CREATE TABLE standing_order_payments (
+    standing_order_id BIGINT NOT NULL REFERENCES standing_orders (id) ON DELETE CASCADE,
+    sequence INTEGER NOT NULL,
+    nonce BIGINT NOT NULL,
+    scheduled_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    tx jsonb NOT NULL,
+    eth_signature jsonb,
+    -- One of `scheduled`, `submitted`, `failed` or `skipped`.
+    status TEXT NOT NULL DEFAULT 'scheduled',
+    tx_hash bytea,
+    error TEXT,
+    submitted_at TIMESTAMP WITH TIME ZONE,
+    PRIMARY KEY (standing_order_id, sequence)
+);
+
+CREATE INDEX standing_order_payments_scheduled_idx
+    ON standing_order_payments (scheduled_at) WHERE status = 'scheduled';
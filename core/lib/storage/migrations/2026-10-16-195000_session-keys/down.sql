@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS session_key_limits;
+DROP TABLE IF EXISTS session_keys;
@@ -0,0 +1,26 @@
+-- Session keys: Ethereum addresses the account owner has allowed to authorize its transactions
+-- instead of the owner's own Ethereum signature, within the registered limits.
+CREATE TABLE session_keys (
+    account_id BIGINT NOT NULL,
+    session_address bytea NOT NULL,
+    -- Names of the transaction types the session key can authorize.
+    allowed_tx_types TEXT[] NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    revoked BOOLEAN NOT NULL DEFAULT false,
+    -- Timestamp of the last request signed by the owner, older requests can't be replayed.
+    signed_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (account_id, session_address)
+);
+
+-- Spending caps of the session keys, the tokens without the cap can't be spent.
+CREATE TABLE session_key_limits (
+    account_id BIGINT NOT NULL,
+    session_address bytea NOT NULL,
+    token_id INTEGER NOT NULL,
+    max_amount NUMERIC NOT NULL,
+    spent NUMERIC NOT NULL DEFAULT 0,
+    PRIMARY KEY (account_id, session_address, token_id),
+    FOREIGN KEY (account_id, session_address)
+        REFERENCES session_keys (account_id, session_address) ON DELETE CASCADE
+);
@@ -0,0 +1,10 @@
+-- Memos attached to the transfers on submission, e.g. the invoice references.
+-- Memos are not a part of the signed transaction and are not sent to the circuit.
+CREATE TABLE tx_memos (
+    tx_hash bytea PRIMARY KEY,
+    -- Recipient of the transfer, payments are looked up by the recipient and the memo.
+    to_address bytea NOT NULL,
+    memo TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX tx_memos_to_address_memo_idx ON tx_memos (to_address, memo);
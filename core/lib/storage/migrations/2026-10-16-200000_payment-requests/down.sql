@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS payment_requests_watcher;
+DROP TABLE IF EXISTS payment_requests;
@@ -0,0 +1,24 @@
+-- Payment requests created by the merchants. The request is fulfilled by the first executed
+-- transfer of at least the requested amount to the recipient, carrying the reference as the memo
+-- if it's set.
+CREATE TABLE payment_requests (
+    id BIGSERIAL PRIMARY KEY,
+    recipient bytea NOT NULL,
+    token_id INTEGER NOT NULL,
+    amount NUMERIC NOT NULL,
+    reference TEXT,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    fulfilled_tx_hash bytea,
+    fulfilled_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX payment_requests_pending_idx ON payment_requests (recipient, token_id)
+    WHERE fulfilled_tx_hash IS NULL;
+
+-- The last block whose transfers were matched against the payment requests.
+-- The watcher starts with the blocks created after the migration.
+CREATE TABLE payment_requests_watcher (
+    last_block_number BIGINT NOT NULL
+);
+INSERT INTO payment_requests_watcher (last_block_number)
+    SELECT COALESCE(MAX(number), 0) FROM blocks;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS cosigned_tx_signatures;
+DROP TABLE IF EXISTS cosigned_txs;
@@ -0,0 +1,25 @@
+-- Co-signed transactions: transactions signed by the account owner which are held by the server
+-- until the required number of the co-signers have signed them, and are submitted afterwards.
+CREATE TABLE cosigned_txs (
+    tx_hash bytea PRIMARY KEY,
+    account_id BIGINT NOT NULL,
+    tx jsonb NOT NULL,
+    eth_signature jsonb NOT NULL,
+    -- Addresses allowed to co-sign the transaction.
+    cosigners bytea[] NOT NULL,
+    -- Number of the co-signatures required to submit the transaction.
+    threshold INTEGER NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    submitted_at TIMESTAMP WITH TIME ZONE,
+    -- Error of the last submission attempt, the submission is retried on the next co-signature.
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE TABLE cosigned_tx_signatures (
+    tx_hash bytea NOT NULL REFERENCES cosigned_txs (tx_hash) ON DELETE CASCADE,
+    signer bytea NOT NULL,
+    signature jsonb NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (tx_hash, signer)
+);
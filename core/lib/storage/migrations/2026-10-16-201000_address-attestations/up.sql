@@ -0,0 +1,10 @@
+-- Address attestations: proofs of the control of the addresses, signed by the addresses
+-- themselves. Only the opaque reference of the operator's records is stored, not the personal data.
+CREATE TABLE address_attestations (
+    address bytea PRIMARY KEY,
+    reference TEXT,
+    signature jsonb NOT NULL,
+    -- Timestamp of the attestation signed by the user, older attestations can't be replayed.
+    signed_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
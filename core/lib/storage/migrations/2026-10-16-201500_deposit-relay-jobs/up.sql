@@ -0,0 +1,23 @@
+-- Deposits performed by the relayer on behalf of the users, who have signed the ERC20 permit
+-- for the relayer account. The amount less the fee is deposited to the owner, the fee is
+-- deposited to the relayer account.
+CREATE TABLE deposit_relay_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    owner bytea NOT NULL,
+    token_id INTEGER NOT NULL,
+    token_address bytea NOT NULL,
+    amount NUMERIC NOT NULL,
+    fee NUMERIC NOT NULL,
+    deadline TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- Packed signature of the permit.
+    signature bytea NOT NULL,
+    -- One of `pending`, `processing`, `completed` or `failed`.
+    status TEXT NOT NULL DEFAULT 'pending',
+    -- Hash of the deposit to the owner, once it's sent.
+    eth_tx_hash bytea,
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX deposit_relay_jobs_owner_idx ON deposit_relay_jobs (owner, created_at);
+CREATE INDEX deposit_relay_jobs_pending_idx ON deposit_relay_jobs (id) WHERE status = 'pending';
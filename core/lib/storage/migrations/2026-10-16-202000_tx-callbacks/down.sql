@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS tx_callbacks;
+DROP TABLE IF EXISTS tx_callback_txs;
+DROP TABLE IF EXISTS tx_callback_endpoints;
@@ -0,0 +1,35 @@
+-- Endpoints receiving the status callbacks of the transactions submitted with the API keys,
+-- one per API key scope. Callbacks are signed with the secret issued on the registration.
+CREATE TABLE tx_callback_endpoints (
+    scope TEXT PRIMARY KEY,
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+-- Transactions submitted with the API keys of the scopes having the callback endpoint.
+CREATE TABLE tx_callback_txs (
+    tx_hash bytea NOT NULL,
+    scope TEXT NOT NULL REFERENCES tx_callback_endpoints (scope) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (tx_hash, scope)
+);
+-- Status callbacks, every status of the transaction is enqueued once per scope.
+-- The id is sent along with the callback, so the receivers can deduplicate the retries.
+CREATE TABLE tx_callbacks (
+    id BIGSERIAL PRIMARY KEY,
+    scope TEXT NOT NULL REFERENCES tx_callback_endpoints (scope) ON DELETE CASCADE,
+    tx_hash bytea NOT NULL,
+    -- One of `committed`, `finalized` or `rejected`.
+    tx_status TEXT NOT NULL,
+    block_number BIGINT NOT NULL,
+    fail_reason TEXT,
+    -- One of `pending`, `delivered` or `dead`.
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    last_error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    UNIQUE (scope, tx_hash, tx_status)
+);
+CREATE INDEX tx_callbacks_pending_idx ON tx_callbacks (next_attempt_at) WHERE status = 'pending';
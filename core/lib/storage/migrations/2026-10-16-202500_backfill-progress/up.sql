@@ -0,0 +1,13 @@
+-- Progress of the batched data migrations running online, one row per backfill.
+-- Every batch is committed along with the updated cursor, so interrupted backfills are resumed.
+CREATE TABLE backfill_progress (
+    name TEXT PRIMARY KEY,
+    -- Position after the last processed batch in the backfill-specific format, `NULL` before the first one.
+    cursor TEXT,
+    processed_rows BIGINT NOT NULL DEFAULT 0,
+    -- Estimation of the amount of rows to process, if it's known.
+    estimated_rows BIGINT,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    finished_at TIMESTAMP WITH TIME ZONE
+);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS executed_priority_operations_l1_sender_idx;
+ALTER TABLE archive.executed_priority_operations DROP COLUMN IF EXISTS l1_sender;
+ALTER TABLE executed_priority_operations DROP COLUMN IF EXISTS l1_sender;
+ALTER TABLE mempool_priority_operations DROP COLUMN IF EXISTS l1_sender;
@@ -0,0 +1,10 @@
+-- Sender of the Ethereum transaction of the priority operation, it differs from the operation
+-- initiator if the operation was requested by a contract. `NULL` for the operations received
+-- before the sender was tracked.
+ALTER TABLE mempool_priority_operations ADD COLUMN l1_sender BYTEA;
+ALTER TABLE executed_priority_operations ADD COLUMN l1_sender BYTEA;
+-- Archived rows are copied as is, so the columns of the tables must match.
+ALTER TABLE archive.executed_priority_operations ADD COLUMN l1_sender BYTEA;
+
+CREATE INDEX executed_priority_operations_l1_sender_idx
+    ON executed_priority_operations USING hash (l1_sender);
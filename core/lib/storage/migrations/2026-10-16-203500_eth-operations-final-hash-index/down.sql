@@ -0,0 +1 @@
+DROP INDEX IF EXISTS eth_operations_final_hash_idx;
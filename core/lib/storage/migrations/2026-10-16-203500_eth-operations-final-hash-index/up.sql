@@ -0,0 +1,2 @@
+-- Ethereum transactions are looked up by their hash to find the withdrawals completed by them.
+CREATE INDEX IF NOT EXISTS eth_operations_final_hash_idx ON eth_operations USING hash (final_hash);
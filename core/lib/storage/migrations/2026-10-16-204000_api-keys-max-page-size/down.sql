@@ -0,0 +1 @@
+ALTER TABLE label_api_keys DROP COLUMN max_page_size;
@@ -0,0 +1,2 @@
+-- Maximum page size of the paginated endpoints overriding the server limits for the key.
+ALTER TABLE label_api_keys ADD COLUMN max_page_size INTEGER;
@@ -0,0 +1,11 @@
+-- Bookkeeping of the periodic background jobs shared by all the server instances.
+CREATE TABLE scheduled_jobs (
+    name TEXT PRIMARY KEY,
+    interval_secs BIGINT NOT NULL,
+    last_started_at TIMESTAMP WITH TIME ZONE,
+    last_finished_at TIMESTAMP WITH TIME ZONE,
+    -- Error of the last run, `NULL` if it succeeded.
+    last_error TEXT,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    runs_count BIGINT NOT NULL DEFAULT 0
+);
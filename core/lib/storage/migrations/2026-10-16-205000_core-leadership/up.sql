@@ -0,0 +1,6 @@
+-- History of the core server instances taking over the leadership.
+CREATE TABLE leadership_changes (
+    id BIGSERIAL PRIMARY KEY,
+    instance_id TEXT NOT NULL,
+    acquired_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
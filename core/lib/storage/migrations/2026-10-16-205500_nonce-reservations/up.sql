@@ -0,0 +1,12 @@
+-- Ranges of the account nonces reserved for the out-of-order submission of the transactions.
+CREATE TABLE nonce_reservations (
+    id BIGSERIAL PRIMARY KEY,
+    account_id BIGINT NOT NULL,
+    first_nonce BIGINT NOT NULL,
+    -- Inclusive.
+    last_nonce BIGINT NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX nonce_reservations_account_id_idx ON nonce_reservations (account_id);
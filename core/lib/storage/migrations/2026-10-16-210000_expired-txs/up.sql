@@ -0,0 +1,7 @@
+-- Transactions evicted from the mempool after their `valid_until` had passed.
+CREATE TABLE expired_transactions (
+    tx_hash BYTEA PRIMARY KEY,
+    valid_until TIMESTAMP WITH TIME ZONE NOT NULL,
+    expired_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX expired_transactions_expired_at_idx ON expired_transactions (expired_at);
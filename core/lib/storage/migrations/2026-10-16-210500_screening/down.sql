@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS screening_matches;
+DROP TABLE IF EXISTS screening_denylist;
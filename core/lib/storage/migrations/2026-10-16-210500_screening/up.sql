@@ -0,0 +1,21 @@
+-- Addresses denied by the operator, e.g. the sanctioned ones.
+CREATE TABLE screening_denylist (
+    address bytea PRIMARY KEY,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Transactions rejected by the screening on the submission, kept for the audit.
+CREATE TABLE screening_matches (
+    id BIGSERIAL PRIMARY KEY,
+    tx_hash bytea NOT NULL,
+    address bytea NOT NULL,
+    -- Either `sender` or `recipient`
+    role TEXT NOT NULL,
+    -- Either `denylist` or `screening_api`
+    source TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX screening_matches_address_idx ON screening_matches (address);
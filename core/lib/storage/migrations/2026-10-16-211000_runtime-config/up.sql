@@ -0,0 +1,17 @@
+-- Reload-safe parameters overridden by the operator at runtime, keyed by the parameter name.
+CREATE TABLE runtime_config (
+    key TEXT PRIMARY KEY,
+    value JSONB NOT NULL,
+    updated_by TEXT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Audit trail of the changes of the overrides, `new_value` is `NULL` for the removed ones.
+CREATE TABLE runtime_config_changes (
+    id BIGSERIAL PRIMARY KEY,
+    key TEXT NOT NULL,
+    old_value JSONB,
+    new_value JSONB,
+    changed_by TEXT NOT NULL,
+    changed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
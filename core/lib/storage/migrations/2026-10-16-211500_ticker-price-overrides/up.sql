@@ -0,0 +1,10 @@
+-- Manual token prices set by the operator while the price sources misbehave.
+-- The fee ticker prefers them over the fetched prices until they expire.
+CREATE TABLE ticker_price_overrides (
+    token_id INTEGER PRIMARY KEY REFERENCES tokens(id) ON UPDATE CASCADE,
+    usd_price NUMERIC NOT NULL,
+    reason TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
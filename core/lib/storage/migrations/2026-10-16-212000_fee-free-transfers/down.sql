@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS fee_free_transfer_quotas;
+DROP TABLE IF EXISTS fee_free_transfers;
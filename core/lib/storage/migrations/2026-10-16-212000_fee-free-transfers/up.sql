@@ -0,0 +1,19 @@
+-- Transfers of the operator addresses with the fee waived, e.g. the rebates and the airdrops.
+CREATE TABLE fee_free_transfers (
+    id BIGSERIAL PRIMARY KEY,
+    address bytea NOT NULL,
+    tx_hash bytea NOT NULL UNIQUE,
+    fee_token INT NOT NULL,
+    waived_fee NUMERIC NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX fee_free_transfers_address_idx ON fee_free_transfers (address);
+
+-- Amount of the fee-free transfers of every operator address per day (in UTC).
+CREATE TABLE fee_free_transfer_quotas (
+    address bytea NOT NULL,
+    day DATE NOT NULL,
+    used BIGINT NOT NULL,
+    PRIMARY KEY (address, day)
+);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS mass_payout_recipients;
+DROP TABLE IF EXISTS mass_payout_batches;
+DROP TABLE IF EXISTS mass_payouts;
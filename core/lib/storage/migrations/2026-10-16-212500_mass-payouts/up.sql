@@ -0,0 +1,37 @@
+-- Payouts of the tokens to the lists of recipients, performed by the `mass_payout` tool.
+CREATE TABLE mass_payouts (
+    id BIGSERIAL PRIMARY KEY,
+    -- Name chosen by the operator, the payout is resumed by it.
+    name TEXT NOT NULL UNIQUE,
+    sender bytea NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Signed batches of the payout, kept to resubmit them unchanged after the restart.
+CREATE TABLE mass_payout_batches (
+    id BIGSERIAL PRIMARY KEY,
+    payout_id BIGINT NOT NULL REFERENCES mass_payouts (id) ON DELETE CASCADE,
+    -- Nonce of the first transaction of the batch.
+    nonce BIGINT NOT NULL,
+    txs jsonb NOT NULL,
+    eth_signature jsonb,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE TABLE mass_payout_recipients (
+    payout_id BIGINT NOT NULL REFERENCES mass_payouts (id) ON DELETE CASCADE,
+    -- Position of the recipient in the input list.
+    idx INTEGER NOT NULL,
+    address bytea NOT NULL,
+    token_id INTEGER NOT NULL,
+    amount NUMERIC NOT NULL,
+    -- One of `pending`, `submitted`, `executed` or `failed`.
+    status TEXT NOT NULL DEFAULT 'pending',
+    -- Batch and hash of the transfer, once it's submitted.
+    batch_id BIGINT REFERENCES mass_payout_batches (id),
+    tx_hash bytea,
+    error TEXT,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (payout_id, idx)
+);
+CREATE INDEX mass_payout_recipients_batch_idx ON mass_payout_recipients (batch_id);
@@ -0,0 +1,24 @@
+-- Executed deposits aggregated per L1 sender, token and day (in UTC), so the totals can be
+-- reconciled without scanning the executed priority operations, which may also be archived.
+-- Rows are updated along with the executed priority operations and on the block revert.
+CREATE TABLE deposit_aggregates (
+    l1_sender bytea NOT NULL,
+    token_id INTEGER NOT NULL,
+    day DATE NOT NULL,
+    amount NUMERIC NOT NULL,
+    deposits_count BIGINT NOT NULL,
+    PRIMARY KEY (l1_sender, token_id, day)
+);
+CREATE INDEX deposit_aggregates_day_idx ON deposit_aggregates (day, token_id);
+
+INSERT INTO deposit_aggregates (l1_sender, token_id, day, amount, deposits_count)
+SELECT from_account, (operation->'priority_op'->>'token')::INTEGER,
+    (created_at AT TIME ZONE 'UTC')::DATE, sum((operation->'priority_op'->>'amount')::NUMERIC),
+    count(*)
+FROM (
+    SELECT from_account, operation, created_at FROM executed_priority_operations
+    UNION ALL
+    SELECT from_account, operation, created_at FROM archive.executed_priority_operations
+) AS deposits
+WHERE operation->>'type' = 'Deposit'
+GROUP BY 1, 2, 3;
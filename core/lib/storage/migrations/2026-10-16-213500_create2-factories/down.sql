@@ -0,0 +1 @@
+DROP TABLE IF EXISTS create2_factories;
@@ -0,0 +1,9 @@
+-- Factory and code hash pairs of the CREATE2 smart wallets allowed to set their signing keys.
+CREATE TABLE create2_factories (
+    creator_address bytea NOT NULL,
+    code_hash bytea NOT NULL,
+    name TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (creator_address, code_hash)
+);
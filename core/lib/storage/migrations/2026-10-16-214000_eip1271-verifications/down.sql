@@ -0,0 +1 @@
+DROP TABLE IF EXISTS eip1271_verifications;
@@ -0,0 +1,11 @@
+-- Evidence of the EIP-1271 signatures of the submitted transactions verified by the
+-- `isValidSignature` call of the smart wallet at the given L1 block.
+CREATE TABLE eip1271_verifications (
+    tx_hash bytea NOT NULL,
+    address bytea NOT NULL,
+    message_hash bytea NOT NULL,
+    signature bytea NOT NULL,
+    l1_block_number BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (tx_hash, address)
+);
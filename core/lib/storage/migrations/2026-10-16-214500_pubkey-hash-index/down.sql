@@ -0,0 +1 @@
+DROP INDEX IF EXISTS account_pubkey_updates_new_pubkey_hash_idx;
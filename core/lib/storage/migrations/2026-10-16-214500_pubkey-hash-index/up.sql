@@ -0,0 +1,3 @@
+-- Lookup of the accounts which have ever used the public key hash.
+CREATE INDEX IF NOT EXISTS account_pubkey_updates_new_pubkey_hash_idx
+    ON account_pubkey_updates (new_pubkey_hash);
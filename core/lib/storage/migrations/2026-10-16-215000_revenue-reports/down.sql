@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS tx_fee_breakdowns_executed_at_idx;
+DROP TABLE IF EXISTS eth_gas_spent;
@@ -0,0 +1,14 @@
+-- L1 gas spent by the confirmed transactions of the operator. Rows are not removed
+-- on the blocks revert, since the gas is spent anyway.
+CREATE TABLE eth_gas_spent (
+    eth_op_id BIGINT PRIMARY KEY,
+    tx_hash bytea NOT NULL,
+    op_type TEXT NOT NULL,
+    gas_used NUMERIC NOT NULL,
+    -- Effective price of the gas, in wei.
+    gas_price NUMERIC NOT NULL,
+    confirmed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX eth_gas_spent_confirmed_at_idx ON eth_gas_spent (confirmed_at);
+
+CREATE INDEX tx_fee_breakdowns_executed_at_idx ON tx_fee_breakdowns (executed_at);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS dropped_transactions;
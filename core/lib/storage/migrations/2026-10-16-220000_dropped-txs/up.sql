@@ -0,0 +1,18 @@
+-- Transactions dropped without being included into a block: rejected on the execution
+-- or evicted from the mempool as expired. Kept for a limited time to reconstruct what happened.
+CREATE TABLE dropped_transactions (
+    id BIGSERIAL PRIMARY KEY,
+    tx_hash BYTEA NOT NULL,
+    -- Address of the account which initiated the transaction.
+    address BYTEA NOT NULL,
+    -- Either `rejected` or `expired`.
+    kind TEXT NOT NULL,
+    reason TEXT,
+    tx JSONB NOT NULL,
+    -- Time of the transaction submission.
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    dropped_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX dropped_transactions_tx_hash_idx ON dropped_transactions (tx_hash);
+CREATE INDEX dropped_transactions_address_idx ON dropped_transactions (address, id);
+CREATE INDEX dropped_transactions_dropped_at_idx ON dropped_transactions (dropped_at);
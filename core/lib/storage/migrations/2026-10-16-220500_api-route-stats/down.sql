@@ -0,0 +1 @@
+DROP TABLE IF EXISTS api_route_stats;
@@ -0,0 +1,22 @@
+-- Statistics of the REST API routes per minute. Every API server stores its own rows,
+-- so there may be several rows of the same route and minute.
+CREATE TABLE api_route_stats (
+    id BIGSERIAL PRIMARY KEY,
+    route TEXT NOT NULL,
+    minute TIMESTAMP WITH TIME ZONE NOT NULL,
+    requests BIGINT NOT NULL,
+    -- Requests failed because of the server, e.g. the storage errors.
+    errors BIGINT NOT NULL,
+    -- Requests served successfully, but slower than the latency objective of the route.
+    slow_requests BIGINT NOT NULL,
+    -- Amounts of the requests per latency bucket.
+    latency_buckets BIGINT[] NOT NULL,
+    -- Total time spent in the storage queries.
+    storage_ms BIGINT NOT NULL,
+    -- Total time spent per storage method, keyed by `schema::method`.
+    queries JSONB NOT NULL,
+    -- Service level objective of the route at the moment.
+    latency_threshold_ms BIGINT NOT NULL,
+    slo_target DOUBLE PRECISION NOT NULL
+);
+CREATE INDEX api_route_stats_minute_idx ON api_route_stats (minute);
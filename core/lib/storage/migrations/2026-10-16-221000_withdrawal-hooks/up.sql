@@ -0,0 +1,21 @@
+-- Calls to the recipient contracts made on L1 once the withdrawals are executed, so the protocols
+-- receiving the withdrawn funds can process them programmatically. The calldata is attached to
+-- the withdrawal on submission and is not a part of the signed transaction.
+CREATE TABLE withdrawal_hooks (
+    tx_hash bytea PRIMARY KEY,
+    -- Recipient of the withdrawal, the call is made to this address.
+    recipient bytea NOT NULL,
+    calldata bytea NOT NULL,
+    -- One of `pending`, `ready`, `sent`, `succeeded`, `failed` or `cancelled`.
+    status TEXT NOT NULL DEFAULT 'pending',
+    -- Block of the withdrawal, set once the block is executed on L1.
+    block_number BIGINT,
+    -- Hash of the call, once it's sent.
+    eth_tx_hash bytea,
+    fail_reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX withdrawal_hooks_pending_idx ON withdrawal_hooks (tx_hash) WHERE status = 'pending';
+CREATE INDEX withdrawal_hooks_ready_idx ON withdrawal_hooks (block_number)
+    WHERE status IN ('ready', 'sent');
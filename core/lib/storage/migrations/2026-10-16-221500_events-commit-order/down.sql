@@ -0,0 +1,8 @@
+CREATE TABLE events_retention_state (
+    id BOOLEAN PRIMARY KEY DEFAULT true CHECK (id),
+    last_pruned_event_id BIGINT NOT NULL
+);
+INSERT INTO events_retention_state (last_pruned_event_id) VALUES (0);
+
+DROP INDEX IF EXISTS events_txid_id_idx;
+ALTER TABLE events DROP COLUMN IF EXISTS txid;
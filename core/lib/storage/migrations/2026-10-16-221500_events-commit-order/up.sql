@@ -0,0 +1,8 @@
+-- Id of the top-level transaction that stored the event. Readers order the events
+-- by `(txid, id)` and only return the ones stored by the finished transactions,
+-- so the events committed out of the id order are not skipped.
+ALTER TABLE events ADD COLUMN txid BIGINT NOT NULL DEFAULT txid_current();
+CREATE INDEX events_txid_id_idx ON events (txid, id);
+
+-- Removed events are detected by the missing cursor event instead.
+DROP TABLE events_retention_state;
@@ -0,0 +1 @@
+ALTER TABLE slow_queries DROP COLUMN IF EXISTS sql;
@@ -0,0 +1,2 @@
+-- Normalized text of the slow statement executed by the method, if it was logged.
+ALTER TABLE slow_queries ADD COLUMN sql TEXT;
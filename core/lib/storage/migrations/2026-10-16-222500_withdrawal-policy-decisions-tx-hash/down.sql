@@ -0,0 +1 @@
+DROP INDEX IF EXISTS withdrawal_policy_decisions_tx_hash_idx;
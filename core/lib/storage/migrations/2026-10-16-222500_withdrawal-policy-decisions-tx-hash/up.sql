@@ -0,0 +1,7 @@
+-- The decision on the transaction is reused if the transaction is submitted again.
+DELETE FROM withdrawal_policy_decisions duplicate
+    USING withdrawal_policy_decisions original
+    WHERE duplicate.tx_hash = original.tx_hash AND duplicate.id > original.id;
+
+CREATE UNIQUE INDEX withdrawal_policy_decisions_tx_hash_idx
+    ON withdrawal_policy_decisions (tx_hash);
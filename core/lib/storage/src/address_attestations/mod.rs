@@ -0,0 +1,103 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+use zksync_types::{
+    event::outbox::{AddressAttestedEvent, OutboxEvent},
+    tx::TxEthSignature,
+    Address,
+};
+// Local imports
+use self::records::StoredAddressAttestation;
+use crate::{outbox::OutboxSchema, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the address attestations: proofs of the control of the addresses, signed by
+/// the addresses themselves.
+///
+/// Every stored attestation is announced with the `AddressAttested` outbox event, so the operator
+/// can link the address with its own off-chain records through the webhooks.
+#[derive(Debug)]
+pub struct AddressAttestationsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> AddressAttestationsSchema<'a, 'c> {
+    /// Stores the attestation or replaces the previous one of the address. Returns `false` if
+    /// the stored attestation was signed not earlier than `signed_at`.
+    #[tracing::instrument(skip_all, fields(schema = "address_attestations"))]
+    pub async fn store_attestation(
+        &mut self,
+        address: Address,
+        reference: Option<&str>,
+        signature: &TxEthSignature,
+        signed_at: DateTime<Utc>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let result = sqlx::query!(
+            "INSERT INTO address_attestations (address, reference, signature, signed_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (address) DO UPDATE
+            SET reference = $2, signature = $3, signed_at = $4
+            WHERE address_attestations.signed_at < $4",
+            address.as_bytes(),
+            reference,
+            serde_json::to_value(signature).expect("failed to serialize the attestation signature"),
+            signed_at,
+        )
+        .execute(transaction.conn())
+        .await?;
+        let stored = result.rows_affected() > 0;
+        if stored {
+            OutboxSchema(&mut transaction)
+                .store_outbox_events(&[OutboxEvent::AddressAttested(AddressAttestedEvent {
+                    address,
+                    reference: reference.map(str::to_string),
+                    signed_at: signed_at.timestamp_millis(),
+                })])
+                .await?;
+        }
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("address_attestations", "store_attestation", start);
+        Ok(stored)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "address_attestations"))]
+    pub async fn get_attestation(
+        &mut self,
+        address: Address,
+    ) -> QueryResult<Option<StoredAddressAttestation>> {
+        let start = Instant::now();
+        let attestation = sqlx::query_as!(
+            StoredAddressAttestation,
+            "SELECT * FROM address_attestations WHERE address = $1",
+            address.as_bytes(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("address_attestations", "get_attestation", start);
+        Ok(attestation)
+    }
+
+    /// Checks whether the control of the address is attested, for the endpoints which
+    /// require the attestation.
+    #[tracing::instrument(skip_all, fields(schema = "address_attestations"))]
+    pub async fn is_address_attested(&mut self, address: Address) -> QueryResult<bool> {
+        let start = Instant::now();
+        let attested = sqlx::query!(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM address_attestations WHERE address = $1
+            ) as "attested!""#,
+            address.as_bytes(),
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .attested;
+
+        crate::slow_queries::report_query("address_attestations", "is_address_attested", start);
+        Ok(attested)
+    }
+}
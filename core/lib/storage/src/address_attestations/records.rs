@@ -0,0 +1,17 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredAddressAttestation {
+    pub address: Vec<u8>,
+    /// Opaque identifier of the operator's records, e.g. the KYC session.
+    pub reference: Option<String>,
+    pub signature: Value,
+    /// Timestamp of the attestation signed by the user.
+    pub signed_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,135 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+// Local imports
+use self::records::{NewRouteStats, StoredRouteQueryTime, StoredRouteStats};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the REST API route statistics.
+///
+/// Every API server collects the statistics of the requests to its routes and stores them
+/// per minute, so the operators can see the latency and the error rates of the routes
+/// along with the storage methods taking the most time across all the servers.
+#[derive(Debug)]
+pub struct ApiRouteStatsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ApiRouteStatsSchema<'a, 'c> {
+    /// Stores the statistics of the routes collected within a minute.
+    #[tracing::instrument(skip_all, fields(schema = "api_route_stats"))]
+    pub async fn store_route_stats(&mut self, stats: &[NewRouteStats]) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        for stats in stats {
+            sqlx::query!(
+                "INSERT INTO api_route_stats (
+                    route, minute, requests, errors, slow_requests, latency_buckets,
+                    storage_ms, queries, latency_threshold_ms, slo_target
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+                stats.route,
+                stats.minute,
+                stats.requests,
+                stats.errors,
+                stats.slow_requests,
+                &stats.latency_buckets,
+                stats.storage_ms,
+                stats.queries,
+                stats.latency_threshold_ms,
+                stats.slo_target,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("api_route_stats", "store_route_stats", start);
+        Ok(())
+    }
+
+    /// Loads the statistics of every route summed since the given moment.
+    #[tracing::instrument(skip_all, fields(schema = "api_route_stats"))]
+    pub async fn load_route_stats(
+        &mut self,
+        since: DateTime<Utc>,
+    ) -> QueryResult<Vec<StoredRouteStats>> {
+        let start = Instant::now();
+        let stats = sqlx::query_as!(
+            StoredRouteStats,
+            r#"
+            WITH buckets AS (
+                SELECT route, bucket.idx, SUM(bucket.requests)::bigint AS requests
+                FROM api_route_stats,
+                    UNNEST(latency_buckets) WITH ORDINALITY AS bucket(requests, idx)
+                WHERE minute >= $1
+                GROUP BY route, bucket.idx
+            )
+            SELECT
+                stats.route AS "route!",
+                SUM(stats.requests)::bigint AS "requests!",
+                SUM(stats.errors)::bigint AS "errors!",
+                SUM(stats.slow_requests)::bigint AS "slow_requests!",
+                (
+                    SELECT array_agg(buckets.requests ORDER BY buckets.idx)
+                    FROM buckets WHERE buckets.route = stats.route
+                ) AS "latency_buckets!",
+                SUM(stats.storage_ms)::bigint AS "storage_ms!",
+                (array_agg(stats.latency_threshold_ms ORDER BY stats.minute DESC))[1]
+                    AS "latency_threshold_ms!",
+                (array_agg(stats.slo_target ORDER BY stats.minute DESC))[1] AS "slo_target!"
+            FROM api_route_stats stats
+            WHERE stats.minute >= $1
+            GROUP BY stats.route
+            ORDER BY stats.route
+            "#,
+            since
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("api_route_stats", "load_route_stats", start);
+        Ok(stats)
+    }
+
+    /// Loads the time spent by every storage method per route since the given moment,
+    /// the slowest methods of the route first.
+    #[tracing::instrument(skip_all, fields(schema = "api_route_stats"))]
+    pub async fn load_route_queries(
+        &mut self,
+        since: DateTime<Utc>,
+    ) -> QueryResult<Vec<StoredRouteQueryTime>> {
+        let start = Instant::now();
+        let queries = sqlx::query_as!(
+            StoredRouteQueryTime,
+            r#"
+            SELECT route, query.key AS "method!", SUM(query.value::bigint)::bigint AS "duration_ms!"
+            FROM api_route_stats, jsonb_each_text(queries) AS query
+            WHERE minute >= $1
+            GROUP BY route, query.key
+            ORDER BY route, 3 DESC, query.key
+            "#,
+            since
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("api_route_stats", "load_route_queries", start);
+        Ok(queries)
+    }
+
+    /// Removes the statistics collected before the given moment, returns the amount of removed rows.
+    #[tracing::instrument(skip_all, fields(schema = "api_route_stats"))]
+    pub async fn remove_route_stats(&mut self, before: DateTime<Utc>) -> QueryResult<u64> {
+        let start = Instant::now();
+        let removed = sqlx::query!("DELETE FROM api_route_stats WHERE minute < $1", before)
+            .execute(self.0.conn())
+            .await?
+            .rows_affected();
+
+        crate::slow_queries::report_query("api_route_stats", "remove_route_stats", start);
+        Ok(removed)
+    }
+}
@@ -0,0 +1,44 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+/// Statistics of the route collected by the API server within the minute.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewRouteStats {
+    pub route: String,
+    pub minute: DateTime<Utc>,
+    pub requests: i64,
+    pub errors: i64,
+    pub slow_requests: i64,
+    pub latency_buckets: Vec<i64>,
+    pub storage_ms: i64,
+    /// Total time spent per storage method, keyed by `schema::method`.
+    pub queries: Value,
+    pub latency_threshold_ms: i64,
+    pub slo_target: f64,
+}
+
+/// Statistics of the route summed over the period.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredRouteStats {
+    pub route: String,
+    pub requests: i64,
+    pub errors: i64,
+    pub slow_requests: i64,
+    pub latency_buckets: Vec<i64>,
+    pub storage_ms: i64,
+    /// The latest objective of the route within the period.
+    pub latency_threshold_ms: i64,
+    pub slo_target: f64,
+}
+
+/// Time spent by the storage method in the requests of the route over the period.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredRouteQueryTime {
+    pub route: String,
+    pub method: String,
+    pub duration_ms: i64,
+}
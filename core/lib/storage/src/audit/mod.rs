@@ -0,0 +1,69 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+// Local imports
+use self::records::StoredAuditEntry;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the audit log of the administrative actions.
+///
+/// The log is append-only: the database rejects any attempt to change or remove the stored
+/// entries. Entries are expected to be stored within the same database transaction as the
+/// action they describe, so no action is applied without being logged.
+#[derive(Debug)]
+pub struct AuditSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> AuditSchema<'a, 'c> {
+    /// Appends the action performed by the actor to the log, returns the id of the entry.
+    #[tracing::instrument(skip_all, fields(schema = "audit"))]
+    pub async fn store_audit_entry(
+        &mut self,
+        actor: &str,
+        action: &str,
+        parameters: &serde_json::Value,
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            "INSERT INTO audit_log (actor, action, parameters)
+            VALUES ($1, $2, $3)
+            RETURNING id",
+            actor,
+            action,
+            parameters,
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        crate::slow_queries::report_query("audit", "store_audit_entry", start);
+        Ok(id)
+    }
+
+    /// Loads the log entries stored before the entry with the `before` id (or the latest ones
+    /// if it's `None`), newest first.
+    #[tracing::instrument(skip_all, fields(schema = "audit"))]
+    pub async fn load_audit_entries(
+        &mut self,
+        before: Option<i64>,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredAuditEntry>> {
+        let start = Instant::now();
+        let entries = sqlx::query_as!(
+            StoredAuditEntry,
+            "SELECT * FROM audit_log
+            WHERE $1::bigint IS NULL OR id < $1
+            ORDER BY id DESC
+            LIMIT $2",
+            before,
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("audit", "load_audit_entries", start);
+        Ok(entries)
+    }
+}
@@ -0,0 +1,14 @@
+// External imports
+use chrono::prelude::*;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredAuditEntry {
+    pub id: i64,
+    pub actor: String,
+    pub action: String,
+    pub parameters: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,91 @@
+//! Canonical JSON representation of the stored transactions.
+//!
+//! `jsonb` columns don't keep the order of the object keys, but the rest of the representation
+//! depends on the code which serialized the transaction: rows written by the older server
+//! versions lack the fields added since then and keep the values in the outdated formats.
+//! Every transaction written to `executed_transactions` and `mempool_txs` is serialized with
+//! [`canonical_tx`], and the existing rows are rewritten by the
+//! [`CanonicalTxsBackfill`](crate::migrations::backfill::CanonicalTxsBackfill).
+//!
+//! The tooling comparing the stored transactions must hash the [`to_canonical_string`]
+//! of the value rather than its `jsonb` text, which orders the keys by their length.
+
+// Built-in deps
+use std::fmt::Write;
+// External imports
+use serde_json::Value;
+// Workspace imports
+use zksync_types::ZkSyncTx;
+// Local imports
+
+/// Serializes the transaction to be stored.
+pub fn canonical_tx(tx: &ZkSyncTx) -> Value {
+    serde_json::to_value(tx).expect("Unserializable TX provided to the database")
+}
+
+/// Returns the canonical form of the stored transaction, `None` if it's already canonical
+/// or can't be parsed as a transaction at all.
+pub fn canonicalize_stored_tx(stored: &Value) -> Option<Value> {
+    let tx: ZkSyncTx = serde_json::from_value(stored.clone()).ok()?;
+    let canonical = canonical_tx(&tx);
+    if &canonical == stored {
+        None
+    } else {
+        Some(canonical)
+    }
+}
+
+/// Writes the value without whitespace, with the object keys sorted by their bytes.
+pub fn to_canonical_string(value: &Value) -> String {
+    let mut output = String::new();
+    write_canonical(value, &mut output);
+    output
+}
+
+fn write_canonical(value: &Value, output: &mut String) {
+    match value {
+        Value::Array(items) => {
+            output.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                write_canonical(item, output);
+            }
+            output.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+            output.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                write!(output, "{}:", Value::String(key.clone())).unwrap();
+                write_canonical(item, output);
+            }
+            output.push('}');
+        }
+        scalar => write!(output, "{}", scalar).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn canonical_string() {
+        let value = json!({
+            "type": "Transfer",
+            "amount": "100",
+            "nested": { "b": [1, null, true], "a": "x\"y" },
+        });
+        assert_eq!(
+            to_canonical_string(&value),
+            r#"{"amount":"100","nested":{"a":"x\"y","b":[1,null,true]},"type":"Transfer"}"#
+        );
+    }
+}
@@ -12,7 +12,7 @@ use zksync_types::{
 use self::records::*;
 use crate::chain::block::BlockSchema;
 use crate::diff::StorageAccountDiff;
-use crate::{QueryResult, StorageProcessor};
+use crate::{connection::access_history_storage, QueryResult, StorageProcessor};
 
 pub mod records;
 pub mod restore_account;
@@ -30,6 +30,7 @@ pub struct AccountSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> AccountSchema<'a, 'c> {
     /// Stores account type in the databse
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn set_account_type(
         &mut self,
         account_id: AccountId,
@@ -76,13 +77,14 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.state.set_account_type", start.elapsed());
+        crate::slow_queries::report_query("chain.account", "set_account_type", start);
         Ok(())
     }
 
     /// Gets currently committed to the database nonce, if not exist return verified.
     /// After reverting blocks this nonce could be less than actual.
     /// Use this function only for verifying the lower bounds of a nonce.
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn estimate_nonce(&mut self, account_id: AccountId) -> QueryResult<Option<Nonce>> {
         let start = Instant::now();
 
@@ -107,11 +109,12 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
             verified_nonce.map(|nonce| nonce.nonce)
         };
 
-        metrics::histogram!("sql.chain.account.current_nonce", start.elapsed());
+        crate::slow_queries::report_query("chain.account", "current_nonce", start);
         Ok(current_nonce.map(|v| Nonce(v as u32)))
     }
 
     /// Fetches account type from the database
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn account_type_by_id(
         &mut self,
         account_id: AccountId,
@@ -150,11 +153,12 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
 
         let account_type =
             db_account_type.map(|db_type| EthAccountType::from_db(db_type, pub_key_hash));
-        metrics::histogram!("sql.chain.account.account_type_by_id", start.elapsed());
+        crate::slow_queries::report_query("chain.account", "account_type_by_id", start);
         Ok(account_type)
     }
 
     /// Obtains both committed and verified state for the account by its ID.
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn account_state_by_id(
         &mut self,
         account_id: AccountId,
@@ -168,7 +172,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
             .last_committed_state_for_account(account_id)
             .await?;
 
-        metrics::histogram!("sql.chain.account.account_state_by_id", start.elapsed());
+        crate::slow_queries::report_query("chain.account", "account_state_by_id", start);
         Ok(StoredAccountState {
             committed: committed_state.map(|a| (account_id, a)),
             verified: verified_state.1.map(|a| (account_id, a)),
@@ -177,6 +181,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
 
     /// Check the existence of an account by the address on the zksync network,
     /// will return true if the account exists
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn does_account_exist(&mut self, address: Address) -> QueryResult<bool> {
         let start = Instant::now();
 
@@ -189,11 +194,12 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!("sql.chain.account.does_account_exist", start.elapsed());
+        crate::slow_queries::report_query("chain.account", "does_account_exist", start);
         Ok(result.is_some())
     }
 
     /// Obtains both committed and verified state for the account by its address.
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn account_state_by_address(
         &mut self,
         address: Address,
@@ -209,16 +215,14 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
             })
         };
 
-        metrics::histogram!(
-            "sql.chain.account.account_state_by_address",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.account", "account_state_by_address", start);
         account_state
     }
 
     /// Loads the last committed (e.g. just added but no necessarily verified) state for
     /// account given its ID.
     /// Returns both verified and committed states.
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn last_committed_state_for_account(
         &mut self,
         account_id: AccountId,
@@ -320,29 +324,33 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.account.last_committed_state_for_account",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.account",
+            "last_committed_state_for_account",
+            start,
         );
         Ok(((last_block, account), account_state))
     }
 
     /// Loads the last verified state for the account (i.e. the one obtained in the last block
     /// which was both committed and verified).
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn last_verified_state_for_account(
         &mut self,
         account_id: AccountId,
     ) -> QueryResult<Option<Account>> {
         let start = Instant::now();
         let (_, account) = self.account_and_last_block(account_id).await?;
-        metrics::histogram!(
-            "sql.chain.account.last_verified_state_for_account",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.account",
+            "last_verified_state_for_account",
+            start,
         );
         Ok(account)
     }
 
     /// Obtains the last verified state of the account.
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn account_and_last_block(
         &mut self,
         account_id: AccountId,
@@ -399,13 +407,11 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         };
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.account.get_account_and_last_block",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.account", "get_account_and_last_block", start);
         result
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn account_id_by_address(
         &mut self,
         address: Address,
@@ -426,10 +432,11 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         .await?;
 
         let account_id = result.map(|record| AccountId(record.account_id as u32));
-        metrics::histogram!("sql.chain.account.account_id_by_address", start.elapsed());
+        crate::slow_queries::report_query("chain.account", "account_id_by_address", start);
         Ok(account_id)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn account_address_by_id(
         &mut self,
         account_id: AccountId,
@@ -444,11 +451,99 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         .await?;
 
         let address = result.map(|record| Address::from_slice(&record.address));
-        metrics::histogram!("sql.chain.account.account_address_by_id", start.elapsed());
+        crate::slow_queries::report_query("chain.account", "account_address_by_id", start);
         Ok(address)
     }
 
+    /// Resolves the accounts with the given ids or addresses at once.
+    /// Returns the pairs of the id and the address of every found account in no particular order,
+    /// the deleted accounts are not returned.
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
+    pub async fn resolve_accounts(
+        &mut self,
+        account_ids: &[AccountId],
+        addresses: &[Address],
+    ) -> QueryResult<Vec<(AccountId, Address)>> {
+        let start = Instant::now();
+        let account_ids: Vec<_> = account_ids.iter().map(|id| i64::from(**id)).collect();
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let accounts = sqlx::query!(
+            r#"
+            SELECT account_id AS "account_id!", address AS "address!" FROM (
+                SELECT DISTINCT ON (account_id) account_id, address, is_create
+                FROM account_creates
+                WHERE account_id = ANY($1) OR address = ANY($2)
+                ORDER BY account_id, block_number DESC, update_order_id DESC
+            ) AS latest
+            WHERE is_create
+            "#,
+            &account_ids,
+            &addresses
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|record| {
+            (
+                AccountId(record.account_id as u32),
+                Address::from_slice(&record.address),
+            )
+        })
+        .collect();
+
+        crate::slow_queries::report_query("chain.account", "resolve_accounts", start);
+        Ok(accounts)
+    }
+
+    /// Finds the accounts which have ever set the public key hash, the ones using it
+    /// in the committed state first. The deleted accounts are not returned.
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
+    pub async fn accounts_by_pubkey_hash(
+        &mut self,
+        pub_key_hash: &PubKeyHash,
+    ) -> QueryResult<Vec<StoredPubKeyHashAccount>> {
+        let start = Instant::now();
+        let accounts = sqlx::query_as!(
+            StoredPubKeyHashAccount,
+            r#"
+            WITH matched AS (
+                SELECT account_id, MIN(block_number) AS first_block, MAX(block_number) AS last_block
+                FROM account_pubkey_updates
+                WHERE new_pubkey_hash = $1
+                GROUP BY account_id
+            ), latest AS (
+                SELECT DISTINCT ON (account_id) account_id, new_pubkey_hash
+                FROM account_pubkey_updates
+                WHERE account_id IN (SELECT account_id FROM matched)
+                ORDER BY account_id, block_number DESC, update_order_id DESC
+            )
+            SELECT matched.account_id AS "account_id!", created.address AS "address!",
+                latest.new_pubkey_hash = $1 AS "is_current!",
+                matched.first_block AS "first_block!", matched.last_block AS "last_block!"
+            FROM matched
+            INNER JOIN latest ON latest.account_id = matched.account_id
+            INNER JOIN LATERAL (
+                SELECT address, is_create FROM account_creates
+                WHERE account_creates.account_id = matched.account_id
+                ORDER BY block_number DESC, update_order_id DESC
+                LIMIT 1
+            ) AS created ON created.is_create
+            ORDER BY 3 DESC, 1
+            "#,
+            &pub_key_hash.data[..],
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("chain.account", "accounts_by_pubkey_hash", start);
+        Ok(accounts)
+    }
+
     /// Obtains the last committed block that affects the account.
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn last_committed_block_with_update_for_acc(
         &mut self,
         account_id: AccountId,
@@ -479,13 +574,15 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         .greatest
         .unwrap_or(block_number);
 
-        metrics::histogram!(
-            "sql.chain.account.last_committed_block_with_update_for_acc",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.account",
+            "last_committed_block_with_update_for_acc",
+            start,
         );
         Ok(BlockNumber(block_number as u32))
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn get_account_balance_for_block(
         &mut self,
         address: Address,
@@ -527,14 +624,154 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
             .unwrap_or_else(BigUint::zero);
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.account.get_account_balance_for_block",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.account", "get_account_balance_for_block", start);
 
         Ok(result)
     }
 
+    /// Returns the balances of all the tokens the account has ever held, as of the given block.
+    /// Tokens are ordered by their IDs.
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
+    pub async fn get_account_balances_for_block(
+        &mut self,
+        account_id: AccountId,
+        block_number: BlockNumber,
+    ) -> QueryResult<Vec<(TokenId, BigUint)>> {
+        let start = Instant::now();
+
+        let balances = sqlx::query!(
+            r#"
+                SELECT DISTINCT ON (coin_id) coin_id, new_balance
+                FROM account_balance_updates
+                WHERE account_id = $1 AND block_number <= $2
+                ORDER BY coin_id, block_number DESC, update_order_id DESC
+            "#,
+            i64::from(account_id.0),
+            i64::from(block_number.0),
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|record| {
+            (
+                TokenId(record.coin_id as u32),
+                record
+                    .new_balance
+                    .to_bigint()
+                    .unwrap()
+                    .to_biguint()
+                    .unwrap(),
+            )
+        })
+        .collect();
+
+        crate::slow_queries::report_query("chain.account", "get_account_balances_for_block", start);
+        Ok(balances)
+    }
+
+    /// Returns the nonce of the account after the given block, reconstructed from the executed
+    /// transactions: it's the next one after the last nonce used by a successful transaction
+    /// of the account or a non-empty swap order of it. Archived transactions are taken into
+    /// account, as well as the ones moved to the history database.
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
+    pub async fn get_account_nonce_at(
+        &mut self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> QueryResult<Nonce> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let account_id = match AccountSchema(&mut transaction)
+            .account_id_by_address(address)
+            .await?
+        {
+            Some(account_id) => i64::from(*account_id),
+            None => return Ok(Nonce(0)),
+        };
+        let last_nonce = sqlx::query!(
+            r#"
+                WITH txs AS (
+                    SELECT tx, primary_account_address, nonce, block_number, success
+                    FROM executed_transactions
+                    WHERE tx_hash IN (SELECT tx_hash FROM tx_filters WHERE address = $1)
+                    UNION ALL
+                    SELECT tx, primary_account_address, nonce, block_number, success
+                    FROM archive.executed_transactions
+                    WHERE tx_hash IN (SELECT tx_hash FROM tx_filters WHERE address = $1)
+                )
+                SELECT MAX(nonce) as "nonce" FROM (
+                    SELECT nonce FROM txs
+                    WHERE primary_account_address = $1 AND block_number <= $2 AND success
+                    UNION ALL
+                    SELECT (orders.value->>'nonce')::bigint
+                    FROM txs, jsonb_array_elements(txs.tx->'orders') AS orders
+                    WHERE txs.tx->>'type' = 'Swap' AND block_number <= $2 AND success
+                        AND (orders.value->>'accountId')::bigint = $3
+                        AND orders.value->>'amount' <> '0'
+                ) nonces
+            "#,
+            address.as_bytes(),
+            i64::from(*block_number),
+            account_id
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .nonce;
+
+        // Transactions in the history database are older than the ones in the main database,
+        // so it's only checked if the latter has none.
+        let last_nonce = match last_nonce {
+            Some(nonce) => Some(nonce),
+            None => match access_history_storage().await? {
+                Some(mut history) => {
+                    let tx_hashes: Vec<Vec<u8>> = sqlx::query!(
+                        "SELECT tx_hash FROM tx_filters WHERE address = $1 AND NOT is_priority",
+                        address.as_bytes()
+                    )
+                    .fetch_all(transaction.conn())
+                    .await?
+                    .into_iter()
+                    .map(|record| record.tx_hash)
+                    .collect();
+                    sqlx::query!(
+                        r#"
+                            WITH txs AS (
+                                SELECT tx, primary_account_address, nonce, block_number, success
+                                FROM executed_transactions
+                                WHERE tx_hash = ANY($4)
+                            )
+                            SELECT MAX(nonce) as "nonce" FROM (
+                                SELECT nonce FROM txs
+                                WHERE primary_account_address = $1 AND block_number <= $2 AND success
+                                UNION ALL
+                                SELECT (orders.value->>'nonce')::bigint
+                                FROM txs, jsonb_array_elements(txs.tx->'orders') AS orders
+                                WHERE txs.tx->>'type' = 'Swap' AND block_number <= $2 AND success
+                                    AND (orders.value->>'accountId')::bigint = $3
+                                    AND orders.value->>'amount' <> '0'
+                            ) nonces
+                        "#,
+                        address.as_bytes(),
+                        i64::from(*block_number),
+                        account_id,
+                        &tx_hashes
+                    )
+                    .fetch_one(history.conn())
+                    .await?
+                    .nonce
+                }
+                None => None,
+            },
+        };
+        transaction.commit().await?;
+
+        let nonce = last_nonce.map_or(0, |nonce| nonce as u32 + 1);
+        crate::slow_queries::report_query("chain.account", "get_account_nonce_at", start);
+        Ok(Nonce(nonce))
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn get_account_nft_balance(&mut self, address: Address) -> QueryResult<u32> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -570,11 +807,12 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         .unwrap_or(0) as u32;
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.account.get_account_nft_balance", start.elapsed());
+        crate::slow_queries::report_query("chain.account", "get_account_nft_balance", start);
 
         Ok(balance)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.account"))]
     pub async fn get_nft_owner(&mut self, token_id: TokenId) -> QueryResult<Option<AccountId>> {
         let start = Instant::now();
 
@@ -590,7 +828,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         .await?;
         let owner_id = record.map(|record| AccountId(record.account_id as u32));
 
-        metrics::histogram!("sql.chain.account.get_nft_owner", start.elapsed());
+        crate::slow_queries::report_query("chain.account", "get_nft_owner", start);
         Ok(owner_id)
     }
 }
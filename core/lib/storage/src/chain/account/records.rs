@@ -133,3 +133,15 @@ impl EthAccountType {
         }
     }
 }
+
+/// Account which has ever set the public key hash, found by `accounts_by_pubkey_hash`.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredPubKeyHashAccount {
+    pub account_id: i64,
+    pub address: Vec<u8>,
+    /// Whether the account uses the public key hash in the committed state.
+    pub is_current: bool,
+    /// Committed blocks the public key hash was set in for the first and the last time.
+    pub first_block: i64,
+    pub last_block: i64,
+}
@@ -6,16 +6,18 @@
 use std::convert::TryFrom;
 // External imports
 // Workspace imports
-use zksync_api_types::v02::transaction::{
-    L1Transaction, Transaction, TransactionData, TxInBlockStatus,
+use zksync_api_types::{
+    tx_status::TxStatus,
+    v02::transaction::{L1Transaction, Transaction, TransactionData},
 };
 use zksync_types::{
     aggregated_operations::AggregatedOperation,
     block::{ExecutedPriorityOp, ExecutedTx},
     tx::TxHash,
-    BlockNumber, PriorityOp, SignedZkSyncTx, ZkSyncOp, ZkSyncTx, H256,
+    Address, BlockNumber, PriorityOp, SignedZkSyncTx, ZkSyncOp, ZkSyncTx, H256,
 };
 // Local imports
+use crate::canonical_json::canonical_tx;
 use crate::chain::operations::records::StoredAggregatedOperation;
 use crate::utils::affected_accounts;
 use crate::{
@@ -69,6 +71,9 @@ impl StoredExecutedPriorityOperation {
                 eth_hash: H256::from_slice(&self.eth_hash),
                 eth_block: self.eth_block as u64,
                 eth_block_index: self.eth_block_index.map(|index| index as u64),
+                l1_sender: self
+                    .l1_sender
+                    .map(|l1_sender| Address::from_slice(&l1_sender)),
             },
             op: franklin_op,
             block_index: self.block_index as u32,
@@ -122,6 +127,10 @@ impl NewExecutedPriorityOperation {
                 .eth_block_index
                 .map(|index| index as i64),
             tx_hash,
+            l1_sender: exec_prior_op
+                .priority_op
+                .l1_sender
+                .map(|l1_sender| l1_sender.as_bytes().to_vec()),
             affected_accounts,
             token,
         }
@@ -144,7 +153,7 @@ impl NewExecutedTransaction {
             }
         }
 
-        let tx = serde_json::to_value(&exec_tx.signed_tx.tx).expect("Cannot serialize tx");
+        let tx = canonical_tx(&exec_tx.signed_tx.tx);
         let operation = serde_json::to_value(&exec_tx.op).expect("Cannot serialize operation");
 
         let (from_account_hex, to_account_hex): (String, Option<String>) =
@@ -233,15 +242,7 @@ impl TransactionItem {
     ) -> Transaction {
         let tx_hash = TxHash::from_slice(&item.tx_hash).unwrap();
         let block_number = Some(BlockNumber(item.block_number as u32));
-        let status = if item.success {
-            if is_block_finalized {
-                TxInBlockStatus::Finalized
-            } else {
-                TxInBlockStatus::Committed
-            }
-        } else {
-            TxInBlockStatus::Rejected
-        };
+        let status = TxStatus::executed(item.success, is_block_finalized).into();
         let op = if let Some(eth_hash) = item.eth_hash {
             let eth_hash = H256::from_slice(&eth_hash);
             let id = item.priority_op_serialid.unwrap() as u64;
@@ -262,6 +263,7 @@ impl TransactionItem {
             fail_reason: item.fail_reason,
             created_at: Some(item.created_at),
             batch_id: item.batch_id.map(|id| id as u32),
+            memo: None,
         }
     }
 }
@@ -13,13 +13,13 @@ use zksync_crypto::convert::FeConvert;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
     block::{Block, BlockMetadata, ExecutedOperations, IncompleteBlock, PendingBlock},
-    event::block::BlockStatus,
-    AccountId, BlockNumber, Fr, ZkSyncOp, H256, U256,
+    event::{block::BlockStatus, outbox::OutboxEvent},
+    AccountId, BlockNumber, Fr, ZkSyncOp, ZkSyncTx, H256, U256,
 };
 // Local imports
 use self::records::{
-    BlockTransactionItem, StorageBlock, StorageBlockDetails, StorageBlockMetadata,
-    StoragePendingBlock, StorageRootHash, TransactionItem,
+    BlockTransactionItem, StorageBlock, StorageBlockDetails, StorageBlockFinality,
+    StorageBlockMetadata, StoragePendingBlock, StorageRootHash, TransactionItem,
 };
 use crate::{
     chain::operations::{
@@ -30,6 +30,7 @@ use crate::{
         OperationsSchema,
     },
     chain::{account::records::EthAccountType, block::records::StorageIncompleteBlock},
+    outbox::OutboxSchema,
     QueryResult, StorageProcessor,
 };
 
@@ -45,6 +46,7 @@ pub struct BlockSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> BlockSchema<'a, 'c> {
     /// Given a block, stores its transactions in the database.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn save_block_transactions(
         &mut self,
         block_number: BlockNumber,
@@ -53,9 +55,23 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
 
+        // Transactions are stored in batches. Executed transactions and priority operations
+        // share the sequence numbers, so the batch is flushed before every priority operation.
+        let mut executed_txs = Vec::new();
+        let mut charged_fees = Vec::new();
+        let mut executed_swaps = Vec::new();
         for block_tx in operations.into_iter() {
             match block_tx {
                 ExecutedOperations::Tx(tx) => {
+                    if tx.success {
+                        if let Some((_, _, _, fee)) = tx.signed_tx.tx.get_fee_info() {
+                            charged_fees.push((tx.signed_tx.tx.hash(), fee));
+                        }
+                    }
+                    if let ZkSyncTx::Swap(swap) = &tx.signed_tx.tx {
+                        let swap = (**swap).clone();
+                        executed_swaps.push((tx.signed_tx.tx.hash(), swap, tx.success));
+                    }
                     // Update account type
                     // This method is called in the committer, so account type update takes effect
                     // starting the next miniblock. If the user wishes to send ChangePubKey + another Tx from
@@ -95,11 +111,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                         &mut transaction,
                     )
                     .await?;
-                    transaction
-                        .chain()
-                        .operations_schema()
-                        .store_executed_tx(new_tx)
-                        .await?;
+                    executed_txs.push(new_tx);
                 }
                 ExecutedOperations::PriorityOp(prior_op) => {
                     // Store the executed operation in the corresponding schema.
@@ -109,6 +121,11 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                         block_number,
                     );
 
+                    transaction
+                        .chain()
+                        .operations_schema()
+                        .store_executed_txs(std::mem::take(&mut executed_txs))
+                        .await?;
                     // Store the executed operation in the corresponding schema.
                     transaction
                         .chain()
@@ -118,13 +135,27 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                 }
             }
         }
+        transaction
+            .chain()
+            .operations_schema()
+            .store_executed_txs(executed_txs)
+            .await?;
+        transaction
+            .fee_breakdowns_schema()
+            .store_charged_fees(&charged_fees)
+            .await?;
+        transaction
+            .order_book_schema()
+            .apply_executed_swaps(&executed_swaps)
+            .await?;
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.block.save_block_transactions", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "save_block_transactions", start);
         Ok(())
     }
 
     // Helper method for retrieving blocks from the database.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_storage_block(
         &mut self,
         block: BlockNumber,
@@ -138,13 +169,14 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.get_storage_block", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "get_storage_block", start);
 
         Ok(block)
     }
 
     /// Given the block number, attempts to retrieve it from the database.
     /// Returns `None` if the block with provided number does not exist yet.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_block(&mut self, block: BlockNumber) -> QueryResult<Option<Block>> {
         let start = Instant::now();
         // Load block header.
@@ -179,13 +211,14 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             stored_block.timestamp.unwrap_or_default() as u64,
         ));
 
-        metrics::histogram!("sql.chain.block.get_block", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "get_block", start);
 
         Ok(result)
     }
 
     /// Given the block number, attempts to get metadata related to block.
     /// Returns `None` if not found.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_block_metadata(
         &mut self,
         block: BlockNumber,
@@ -200,7 +233,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.get_block_metadata", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "get_block_metadata", start);
 
         let result = db_result.map(|md| BlockMetadata {
             fast_processing: md.fast_processing,
@@ -211,6 +244,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
 
     /// Same as `get_block_executed_ops`, but returns a vector of `ZkSyncOp` instead
     /// of `ExecutedOperations`.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_block_operations(&mut self, block: BlockNumber) -> QueryResult<Vec<ZkSyncOp>> {
         let start = Instant::now();
         let executed_ops = self.get_block_executed_ops(block).await?;
@@ -221,11 +255,12 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                 ExecutedOperations::PriorityOp(priorop) => Some(priorop.op),
             })
             .collect();
-        metrics::histogram!("sql.chain.block.get_block_operations", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "get_block_operations", start);
         Ok(result)
     }
 
     /// Retrieves both L1 and L2 operations stored in the block with the given number.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_block_transactions(
         &mut self,
         block: BlockNumber,
@@ -282,11 +317,12 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.get_block_transactions", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "get_block_transactions", start);
         Ok(block_txs)
     }
 
     /// Given the block number, loads all the operations that were executed in that block.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_block_executed_ops(
         &mut self,
         block: BlockNumber,
@@ -345,11 +381,12 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             }
         });
 
-        metrics::histogram!("sql.chain.block.get_block_executed_ops", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "get_block_executed_ops", start);
         Ok(executed_operations)
     }
 
     /// Loads the block headers for the given amount of blocks in the descending order.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn load_block_range_desc(
         &mut self,
         max_block: BlockNumber,
@@ -409,11 +446,12 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         ).fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.load_block_range", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "load_block_range", start);
         Ok(details)
     }
 
     /// Loads the block headers for the given amount of blocks in the ascending order.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn load_block_range_asc(
         &mut self,
         min_block: BlockNumber,
@@ -473,11 +511,12 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         ).fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.load_block_range_asc", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "load_block_range_asc", start);
         Ok(details)
     }
 
     /// Loads the block headers for the given pagination query
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn load_block_page(
         &mut self,
         query: &PaginationQuery<BlockNumber>,
@@ -501,6 +540,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
     ///
     /// Will return `None` if the query is malformed or there is no block that matches
     /// the query.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn find_block_by_height_or_hash(
         &mut self,
         query: String,
@@ -586,14 +626,12 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .ok()
             .flatten();
 
-        metrics::histogram!(
-            "sql.chain.block.find_block_by_height_or_hash",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.block", "find_block_by_height_or_hash", start);
         result
     }
 
     /// Returns the number of last block saved to the database.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_last_saved_block(&mut self) -> QueryResult<BlockNumber> {
         let start = Instant::now();
         let count = sqlx::query!("SELECT MAX(number) FROM blocks")
@@ -601,17 +639,39 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .await?
             .max
             .unwrap_or(0);
-        metrics::histogram!("sql.chain.block.get_last_committed_block", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "get_last_committed_block", start);
         Ok(BlockNumber(count as u32))
     }
 
+    /// Returns the number of the last saved block with the timestamp earlier than the given one
+    /// (in seconds), or `None` if there is no such block.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
+    pub async fn get_last_block_before(
+        &mut self,
+        timestamp: u64,
+    ) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        // Blocks are created in order, so the primary key index is scanned backwards
+        // until the first old enough block.
+        let block_number = sqlx::query!(
+            "SELECT number FROM blocks WHERE timestamp < $1 ORDER BY number DESC LIMIT 1",
+            timestamp as i64
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| BlockNumber(record.number as u32));
+        crate::slow_queries::report_query("chain.block", "get_last_block_before", start);
+        Ok(block_number)
+    }
+
     /// Returns the number of last block for which an aggregated operation exists.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_last_committed_block(&mut self) -> QueryResult<BlockNumber> {
         let start = Instant::now();
         let result = OperationsSchema(self.0)
             .get_last_block_by_aggregated_action(AggregatedActionType::CommitBlocks, None)
             .await;
-        metrics::histogram!("sql.chain.block.get_last_committed_block", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "get_last_committed_block", start);
         result
     }
 
@@ -629,19 +689,21 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .await?
             .max
             .map(|block| BlockNumber(block as u32));
-        metrics::histogram!("sql.chain.block.get_last_incomplete_block", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "get_last_incomplete_block", start);
         Ok(result)
     }
 
     /// Returns the number of last block which commit is confirmed on Ethereum.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_last_committed_confirmed_block(&mut self) -> QueryResult<BlockNumber> {
         let start = Instant::now();
         let result = OperationsSchema(self.0)
             .get_last_block_by_aggregated_action(AggregatedActionType::CommitBlocks, Some(true))
             .await;
-        metrics::histogram!(
-            "sql.chain.block.get_last_committed_confirmed_block",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.block",
+            "get_last_committed_confirmed_block",
+            start,
         );
         result
     }
@@ -651,16 +713,18 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
     /// Note: having a proof for the block doesn't mean that state was updated. Chain state
     /// is updated only after corresponding transaction is confirmed on the Ethereum blockchain.
     /// In order to see the last block with updated state, use `get_last_verified_confirmed_block` method.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_last_verified_block(&mut self) -> QueryResult<BlockNumber> {
         let start = Instant::now();
         let result = OperationsSchema(self.0)
             .get_last_block_by_aggregated_action(AggregatedActionType::ExecuteBlocks, None)
             .await;
-        metrics::histogram!("sql.chain.block.get_last_verified_block", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "get_last_verified_block", start);
         result
     }
 
     /// Returns the number of last block for which proof has been confirmed on Ethereum.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_last_proven_confirmed_block(&mut self) -> QueryResult<BlockNumber> {
         let start = Instant::now();
         let result = OperationsSchema(self.0)
@@ -669,27 +733,27 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                 Some(true),
             )
             .await;
-        metrics::histogram!(
-            "sql.chain.block.get_last_proven_confirmed_block",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.block", "get_last_proven_confirmed_block", start);
         result
     }
 
     /// Returns the number of last block for which executed operations has been confirmed on Ethereum .
     /// Essentially, it's number of last block for which updates were applied to the chain state.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_last_verified_confirmed_block(&mut self) -> QueryResult<BlockNumber> {
         let start = Instant::now();
         let result = OperationsSchema(self.0)
             .get_last_block_by_aggregated_action(AggregatedActionType::ExecuteBlocks, Some(true))
             .await;
-        metrics::histogram!(
-            "sql.chain.block.get_last_verified_confirmed_block",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.block",
+            "get_last_verified_confirmed_block",
+            start,
         );
         result
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn is_block_finalized(&mut self, block_number: BlockNumber) -> QueryResult<bool> {
         let last_finalized_block = self
             .0
@@ -700,6 +764,56 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(block_number <= last_finalized_block)
     }
 
+    /// Loads everything needed to check the block on L1 by itself: the commitment, the hashes
+    /// of the confirmed commit/prove/execute transactions and the range of the aggregated proof.
+    /// Returns `None` if the block is not saved yet.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
+    pub async fn load_block_finality(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<StorageBlockFinality>> {
+        let start = Instant::now();
+        let finality = sqlx::query_as!(
+            StorageBlockFinality,
+            r#"
+            WITH eth_txs AS (
+                SELECT aggregate_operations.action_type, eth_operations.final_hash
+                FROM aggregate_operations
+                    INNER JOIN eth_aggregated_ops_binding ON eth_aggregated_ops_binding.op_id = aggregate_operations.id
+                    INNER JOIN eth_operations ON eth_operations.id = eth_aggregated_ops_binding.eth_op_id
+                WHERE $1 BETWEEN aggregate_operations.from_block AND aggregate_operations.to_block
+                    AND eth_operations.confirmed = true
+            )
+            SELECT
+                blocks.number AS "block_number!",
+                blocks.commitment AS "commitment!",
+                (SELECT final_hash FROM eth_txs WHERE action_type = $2 LIMIT 1) AS "commit_tx_hash?",
+                (SELECT final_hash FROM eth_txs WHERE action_type = $3 LIMIT 1) AS "prove_tx_hash?",
+                (SELECT final_hash FROM eth_txs WHERE action_type = $4 LIMIT 1) AS "execute_tx_hash?",
+                proof.first_block AS "proof_first_block?",
+                proof.last_block AS "proof_last_block?"
+            FROM blocks
+                LEFT JOIN LATERAL (
+                    SELECT first_block, last_block FROM aggregated_proofs
+                    WHERE blocks.number BETWEEN first_block AND last_block
+                    ORDER BY created_at DESC
+                    LIMIT 1
+                ) proof ON true
+            WHERE blocks.number = $1
+            "#,
+            i64::from(*block_number),
+            AggregatedActionType::CommitBlocks.to_string(),
+            AggregatedActionType::PublishProofBlocksOnchain.to_string(),
+            AggregatedActionType::ExecuteBlocks.to_string(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("chain.block", "load_block_finality", start);
+        Ok(finality)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn pending_block_chunks_left(&mut self) -> QueryResult<Option<usize>> {
         let start = Instant::now();
         let maybe_block_chunks = sqlx::query!(
@@ -708,7 +822,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!("sql.chain.block.pending_block_chunks_left", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "pending_block_chunks_left", start);
 
         Ok(maybe_block_chunks.map(|val| val.chunks_left as usize))
     }
@@ -724,15 +838,13 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!(
-            "sql.chain.block.load_storage_pending_block",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.block", "load_storage_pending_block", start);
 
         Ok(maybe_block)
     }
 
     /// Retrieves the latest pending block from the database, if such is present.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn load_pending_block(&mut self) -> QueryResult<Option<PendingBlock>> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -776,20 +888,22 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.load_pending_block", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "load_pending_block", start);
         Ok(Some(result))
     }
 
     /// Returns `true` if there is a stored pending block in the database.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn pending_block_exists(&mut self) -> QueryResult<bool> {
         let start = Instant::now();
         let result = self.load_storage_pending_block().await?.is_some();
 
-        metrics::histogram!("sql.chain.block.pending_block_exists", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "pending_block_exists", start);
         Ok(result)
     }
 
     /// Stores given pending block into the database.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn save_pending_block(&mut self, pending_block: PendingBlock) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -832,12 +946,13 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .await?;
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.block.save_pending_block", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "save_pending_block", start);
 
         Ok(())
     }
 
     /// Returns the number of rejected_txs in executed_txs
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn count_rejected_txs(&mut self) -> QueryResult<i64> {
         let start = Instant::now();
         let count = sqlx::query!(
@@ -847,10 +962,11 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .await?
         .count;
 
-        metrics::histogram!("sql.chain.block.count_rejected_txs", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "count_rejected_txs", start);
         Ok(count)
     }
     /// Returns the number of aggregated operations with the given `action_type` and `is_confirmed` status.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn count_aggregated_operations(
         &mut self,
         aggregated_action_type: AggregatedActionType,
@@ -866,7 +982,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .await?
         .count;
 
-        metrics::histogram!("sql.chain.block.count_operations", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "count_operations", start);
         Ok(count)
     }
 
@@ -874,6 +990,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
     ///
     /// This method assumes that `Block` was created from the corresponding `IncompleteBlock`
     /// object from the DB, and doesn't do any checks regarding that.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn finish_incomplete_block(&mut self, block: Block) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -910,9 +1027,13 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .execute(transaction.conn())
         .await?;
 
+        OutboxSchema(&mut transaction)
+            .store_outbox_events(&OutboxEvent::from_block(&block))
+            .await?;
+
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.save_block", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "save_block", start);
         Ok(())
     }
 
@@ -920,6 +1041,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
     ///
     /// This method **does not** save block transactions.
     /// They are expected to be saved prior, during processing of previous pending blocks.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn save_incomplete_block(&mut self, block: &IncompleteBlock) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -968,7 +1090,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.save_incomplete_block", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "save_incomplete_block", start);
         Ok(())
     }
 
@@ -979,6 +1101,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
     /// - In some contexts, root hash for the block is known immediately (e.g. data restore).
     /// - In most DB/API tests, the process of block sealing doesn't really matter: these tests check the behavior
     ///   of blocks that are already stored in the DB, not *how* they are stored.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn save_full_block(&mut self, block: Block) -> anyhow::Result<()> {
         let full_block = block.clone();
         let incomplete_block = IncompleteBlock::new(
@@ -1012,6 +1135,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
 
     /// Returns the ID of the next expected priority operation.
     /// Performs a lookup in both incomplete and complete block tables.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn next_expected_serial_id(&mut self) -> QueryResult<u64> {
         let start = Instant::now();
 
@@ -1027,7 +1151,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .map(|val| val as u64)
         .unwrap_or_default();
 
-        metrics::histogram!("sql.chain.block.next_expected_serial_id", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "next_expected_serial_id", start);
         Ok(next_expected_serial_id)
     }
 
@@ -1035,6 +1159,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
     ///
     /// Returned range is *inclusive*, meaning that both returned blocks (if they were returned)
     /// exist in the database, and represent minimum and maximum existing blocks correspondingly.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn incomplete_blocks_range(
         &mut self,
     ) -> QueryResult<Option<(BlockNumber, BlockNumber)>> {
@@ -1057,7 +1182,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             }
         };
 
-        metrics::histogram!("sql.chain.block.incomplete_blocks_range", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "incomplete_blocks_range", start);
         Ok(block_numbers)
     }
 
@@ -1075,10 +1200,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.block.get_storage_incomplete_block",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.block", "get_storage_incomplete_block", start);
 
         Ok(block)
     }
@@ -1087,6 +1209,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
     /// Returns `None` if the block with provided number does not exist yet.
     ///
     /// Data to complete consists of `IncompleteBlock` object and the root hash of the previous block.
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_data_to_complete_block(
         &mut self,
         block_number: BlockNumber,
@@ -1129,14 +1252,12 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .await?
         .map(|entry| FeConvert::from_bytes(&entry.root_hash).expect("Unparsable root hash"));
 
-        metrics::histogram!(
-            "sql.chain.block.get_data_to_complete_block",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.block", "get_data_to_complete_block", start);
 
         Ok((block, previous_root_hash))
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn save_block_metadata(
         &mut self,
         block_number: BlockNumber,
@@ -1155,10 +1276,11 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.save_block_metadata", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "save_block_metadata", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn save_genesis_block(&mut self, root_hash: Fr) -> QueryResult<()> {
         let mut transaction = self.0.start_transaction().await?;
 
@@ -1184,6 +1306,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
     }
 
     /// Retrieves both L1 and L2 operations stored in the block for the given pagination query
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_block_transactions_page(
         &mut self,
         query: &PaginationQuery<BlockAndTxHash>,
@@ -1353,14 +1476,12 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         };
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.block.get_block_transactions_page",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.block", "get_block_transactions_page", start);
         Ok(block_txs)
     }
 
     /// Returns count of both L1 and L2 operations stored in the block
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_block_transactions_count(
         &mut self,
         block_number: BlockNumber,
@@ -1384,14 +1505,12 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .count;
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.block.get_block_transactions_count",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.block", "get_block_transactions_count", start);
         Ok((tx_count + priority_op_count) as u32)
     }
 
     // Removes blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn remove_blocks(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -1438,21 +1557,23 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .execute(transaction.conn())
         .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.block.remove_blocks", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "remove_blocks", start);
         Ok(())
     }
 
     // Removes pending block
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn remove_pending_block(&mut self) -> QueryResult<()> {
         let start = Instant::now();
         sqlx::query!("DELETE FROM pending_block")
             .execute(self.0.conn())
             .await?;
 
-        metrics::histogram!("sql.chain.block.remove_pending_block", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "remove_pending_block", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn store_factories_for_block_withdraw_nfts(
         &mut self,
         from_block: BlockNumber,
@@ -1495,13 +1616,15 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.block.store_factories_for_block_withdraw_nfts",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.block",
+            "store_factories_for_block_withdraw_nfts",
+            start,
         );
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_block_number_by_hash(
         &mut self,
         hash: &[u8],
@@ -1512,10 +1635,11 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .await?;
         let block_number = record.map(|r| BlockNumber(r.number as u32));
 
-        metrics::histogram!("sql.chain.block.get_block_number_by_hash", start.elapsed());
+        crate::slow_queries::report_query("chain.block", "get_block_number_by_hash", start);
         Ok(block_number)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.block"))]
     pub async fn get_block_transactions_hashes(
         &mut self,
         block_number: BlockNumber,
@@ -1546,10 +1670,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .await?;
         let hashes = records.into_iter().map(|record| record.tx_hash).collect();
 
-        metrics::histogram!(
-            "sql.chain.block.get_block_transactions_hashes",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.block", "get_block_transactions_hashes", start);
         Ok(hashes)
     }
 }
@@ -75,6 +75,20 @@ pub struct StorageBlockDetails {
     pub verified_at: Option<DateTime<Utc>>,
 }
 
+/// L1 footprint of the block: its commitment, the confirmed Ethereum transactions
+/// of every aggregated action and the aggregated proof which covers it.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageBlockFinality {
+    pub block_number: i64,
+    pub commitment: Vec<u8>,
+    pub commit_tx_hash: Option<Vec<u8>>,
+    pub prove_tx_hash: Option<Vec<u8>>,
+    pub execute_tx_hash: Option<Vec<u8>>,
+    /// Range of the blocks of the aggregated proof, `None` until the proof is generated.
+    pub proof_first_block: Option<i64>,
+    pub proof_last_block: Option<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
 pub struct BlockTransactionItem {
     pub tx_hash: String,
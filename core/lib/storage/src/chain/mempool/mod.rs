@@ -1,6 +1,7 @@
 // Built-in deps
 use std::{collections::VecDeque, convert::TryFrom, str::FromStr, time::Instant};
 // External imports
+use chrono::{DateTime, Utc};
 use itertools::Itertools;
 // Workspace imports
 use zksync_api_types::v02::pagination::PaginationDirection;
@@ -15,8 +16,11 @@ use zksync_types::{
     PriorityOp, SerialId, SignedZkSyncTx, ZkSyncPriorityOp, H256,
 };
 // Local imports
-use self::records::{MempoolPriorityOp, MempoolTx, QueuedBatchTx, RevertedBlock};
-use crate::{QueryResult, StorageProcessor};
+use self::records::{
+    MempoolPriorityOp, MempoolTx, PendingPriorityOp, QueuedBatchTx, RevertedBlock,
+};
+use crate::dropped_txs::EXPIRED_KIND;
+use crate::{canonical_json::canonical_tx, QueryResult, StorageProcessor};
 
 use crate::chain::operations::records::{
     StoredExecutedPriorityOperation, StoredExecutedTransaction,
@@ -38,6 +42,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
     /// We want to exclude txs that have already been processed in memory,
     /// due to asynchronous execution,
     /// these txs may be executed in memory and not yet saved to the database
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn load_txs(
         &mut self,
         executed_txs: &[TxHash],
@@ -117,10 +122,11 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             }
         }
 
-        metrics::histogram!("sql.chain.mempool.load_txs", start.elapsed());
+        crate::slow_queries::report_query("chain.mempool", "load_txs", start);
         Ok(txs.into())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn remove_reverted_block(&mut self, block_number: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -137,12 +143,13 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .execute(transaction.conn())
         .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.mempool.remove_reverted_block", start.elapsed());
+        crate::slow_queries::report_query("chain.mempool", "remove_reverted_block", start);
         Ok(())
     }
 
     /// Adds a new transactions batch to the mempool schema.
     /// Returns id of the inserted batch
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn insert_batch(
         &mut self,
         txs: &[SignedZkSyncTx],
@@ -163,8 +170,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         let batch_id = {
             let first_tx_data = txs[0].clone();
             let tx_hash = hex::encode(tx_hashes[0].as_ref());
-            let tx = serde_json::to_value(&first_tx_data.tx)
-                .expect("Unserializable TX provided to the database");
+            let tx = canonical_tx(&first_tx_data.tx);
             let eth_sign_data = first_tx_data
                 .eth_sign_data
                 .as_ref()
@@ -197,8 +203,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
         for (tx_data, tx_hash) in txs[1..].iter().zip(tx_hashes[1..].iter()) {
             let tx_hash = hex::encode(tx_hash.as_ref());
-            let tx = serde_json::to_value(&tx_data.tx)
-                .expect("Unserializable TX provided to the database");
+            let tx = canonical_tx(&tx_data.tx);
             let eth_sign_data = tx_data
                 .eth_sign_data
                 .as_ref()
@@ -240,15 +245,16 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.mempool.insert_batch", start.elapsed());
+        crate::slow_queries::report_query("chain.mempool", "insert_batch", start);
         Ok(batch_id)
     }
 
     /// Adds a new transaction to the mempool schema.
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn insert_tx(&mut self, tx_data: &SignedZkSyncTx) -> QueryResult<()> {
         let start = Instant::now();
         let tx_hash = hex::encode(tx_data.tx.hash().as_ref());
-        let tx = serde_json::to_value(&tx_data.tx)?;
+        let tx = canonical_tx(&tx_data.tx);
         let batch_id = 0; // Special case: batch_id == 0 <==> transaction is not a part of some batch
 
         let eth_sign_data = tx_data
@@ -268,10 +274,11 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.mempool.insert_tx", start.elapsed());
+        crate::slow_queries::report_query("chain.mempool", "insert_tx", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn remove_tx(&mut self, tx: &[u8]) -> QueryResult<()> {
         let start = Instant::now();
         let tx_hash = hex::encode(tx);
@@ -284,10 +291,11 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.mempool.remove_tx", start.elapsed());
+        crate::slow_queries::report_query("chain.mempool", "remove_tx", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn remove_txs(&mut self, txs: &[TxHash]) -> QueryResult<()> {
         let start = Instant::now();
         let tx_hashes: Vec<_> = txs.iter().map(hex::encode).collect();
@@ -300,11 +308,88 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.mempool.remove_txs", start.elapsed());
+        crate::slow_queries::report_query("chain.mempool", "remove_txs", start);
+        Ok(())
+    }
+
+    /// Evicts the transactions which can't be executed anymore since their `valid_until`
+    /// has passed, recording their expiry for the receipts. Evicted transactions are kept
+    /// searchable along with the address of their initiator.
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
+    pub async fn expire_txs(
+        &mut self,
+        txs: &[(TxHash, Address, DateTime<Utc>)],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut tx_hashes = Vec::with_capacity(txs.len());
+        let mut addresses = Vec::with_capacity(txs.len());
+        let mut valid_until = Vec::with_capacity(txs.len());
+        let mut reasons = Vec::with_capacity(txs.len());
+        for (tx_hash, address, tx_valid_until) in txs {
+            tx_hashes.push(tx_hash.as_ref().to_vec());
+            addresses.push(address.as_bytes().to_vec());
+            valid_until.push(*tx_valid_until);
+            reasons.push(format!(
+                "Transaction expired, valid_until {} has passed",
+                tx_valid_until.to_rfc3339()
+            ));
+        }
+        let hex_hashes: Vec<_> = tx_hashes.iter().map(hex::encode).collect();
+
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query!(
+            "INSERT INTO dropped_transactions (tx_hash, address, kind, reason, tx, created_at)
+            SELECT u.tx_hash, u.address, $5, u.reason, mempool_txs.tx, mempool_txs.created_at
+                FROM UNNEST($1::bytea[], $2::text[], $3::bytea[], $4::text[])
+                AS u(tx_hash, hex_hash, address, reason)
+                INNER JOIN mempool_txs ON mempool_txs.tx_hash = u.hex_hash",
+            &tx_hashes,
+            &hex_hashes,
+            &addresses,
+            &reasons,
+            EXPIRED_KIND,
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            "DELETE FROM mempool_txs WHERE tx_hash = ANY($1)",
+            &hex_hashes
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            "INSERT INTO expired_transactions (tx_hash, valid_until)
+            SELECT * FROM UNNEST($1::bytea[], $2::timestamptz[])
+            ON CONFLICT (tx_hash) DO NOTHING",
+            &tx_hashes,
+            &valid_until,
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("chain.mempool", "expire_txs", start);
         Ok(())
     }
 
+    /// Returns the `valid_until` of the transaction if it was evicted from the mempool as expired.
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
+    pub async fn get_expired_tx(&mut self, tx_hash: &[u8]) -> QueryResult<Option<DateTime<Utc>>> {
+        let start = Instant::now();
+        let valid_until = sqlx::query!(
+            "SELECT valid_until FROM expired_transactions WHERE tx_hash = $1",
+            tx_hash
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| record.valid_until);
+
+        crate::slow_queries::report_query("chain.mempool", "get_expired_tx", start);
+        Ok(valid_until)
+    }
+
     /// Checks if the memory pool contains transaction with the given hash.
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn contains_tx(&mut self, tx_hash: TxHash) -> QueryResult<bool> {
         let start = Instant::now();
 
@@ -321,17 +406,18 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
         let contains = row.filter(|&counter| counter > 0).is_some();
 
-        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "contains_tx");
+        crate::slow_queries::report_query("chain.mempool", "contains_tx", start);
         Ok(contains)
     }
 
     /// Returns zkSync transaction with the given hash.
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn get_tx(&mut self, tx_hash: &[u8]) -> QueryResult<Option<SignedZkSyncTx>> {
         let start = Instant::now();
 
         let mempool_tx = self.get_mempool_tx(tx_hash).await?;
 
-        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_tx");
+        crate::slow_queries::report_query("chain.mempool", "get_tx", start);
         mempool_tx
             .map(SignedZkSyncTx::try_from)
             .transpose()
@@ -353,7 +439,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_tx");
+        crate::slow_queries::report_query("chain.mempool", "get_tx", start);
         Ok(mempool_tx)
     }
 
@@ -366,6 +452,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
     ///
     /// This method is expected to be initially invoked on the server start, and then
     /// invoked periodically with a big interval (to prevent possible database bloating).
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn collect_garbage(&mut self) -> QueryResult<()> {
         let start = Instant::now();
         let all_txs = self.load_txs(&[]).await?;
@@ -421,10 +508,11 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         self.remove_priority_ops_from_mempool(&priority_ops_to_remove)
             .await?;
 
-        metrics::histogram!("sql.chain.mempool.collect_garbage", start.elapsed());
+        crate::slow_queries::report_query("chain.mempool", "collect_garbage", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn insert_priority_ops(
         &mut self,
         ops: &[PriorityOp],
@@ -446,6 +534,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             let eth_hash = op.eth_hash.as_bytes().to_vec();
             let eth_block = op.eth_block as i64;
             let eth_block_index = op.eth_block_index.map(|v| v as i32).unwrap_or_default();
+            let l1_sender = op.l1_sender.map(|l1_sender| l1_sender.as_bytes().to_vec());
             let op_type = op.data.variance_name();
             let (l1_address, l2_address) = match &op.data {
                 ZkSyncPriorityOp::Deposit(dep) => {
@@ -461,13 +550,14 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 "INSERT INTO mempool_priority_operations (
                     serial_id, data, deadline_block, eth_hash, tx_hash,
                     eth_block, eth_block_index, l1_address, 
-                    l2_address, type, created_at, confirmed
+                    l2_address, type, created_at, confirmed, l1_sender
                  )
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, now(), $11)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, now(), $11, $12)
                 ON CONFLICT (serial_id) DO UPDATE SET
                 data=$2, deadline_block=$3, eth_hash=$4, tx_hash=$5,
                 eth_block=$6, eth_block_index=$7, l1_address=$8,
-                l2_address=$9, type=$10, confirmed=$11
+                l2_address=$9, type=$10, confirmed=$11,
+                l1_sender=COALESCE($12, mempool_priority_operations.l1_sender)
                 ",
                 serial_id,
                 data,
@@ -479,26 +569,29 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 l1_address,
                 l2_address,
                 op_type,
-                confirmed
+                confirmed,
+                l1_sender
             )
             .execute(transaction.conn())
             .await?;
         }
         transaction.commit().await?;
-        metrics::histogram!("sql.chain", start.elapsed(), "schema" => "mempool", "method" => "insert_priority_ops");
+        crate::slow_queries::report_query("chain.mempool", "insert_priority_ops", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn get_confirmed_priority_ops(&mut self) -> QueryResult<VecDeque<PriorityOp>> {
         let ops = sqlx::query_as!(
             MempoolPriorityOp,
-            "SELECT serial_id,data,deadline_block,eth_hash,tx_hash,eth_block,eth_block_index,created_at FROM mempool_priority_operations WHERE confirmed AND reverted = false ORDER BY serial_id"
+            "SELECT serial_id,data,deadline_block,eth_hash,tx_hash,eth_block,eth_block_index,created_at,l1_sender FROM mempool_priority_operations WHERE confirmed AND reverted = false ORDER BY serial_id"
         )
         .fetch_all(self.0.conn())
         .await?;
         Ok(ops.into_iter().map(|op| op.into()).collect())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn remove_priority_op_from_mempool(&mut self, id: i64) -> QueryResult<()> {
         sqlx::query!(
             "DELETE FROM mempool_priority_operations WHERE serial_id=$1",
@@ -509,6 +602,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn get_max_serial_id_pending_deposits(
         &mut self,
         address: Address,
@@ -523,6 +617,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(serial_id.map(|v| v as u64))
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn get_pending_deposits_for(
         &mut self,
         address: Address,
@@ -530,7 +625,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         limit: u32,
         direction: PaginationDirection,
     ) -> QueryResult<Vec<PriorityOp>> {
-        let query = "SELECT serial_id,data,deadline_block,eth_hash,tx_hash,eth_block,eth_block_index,created_at FROM mempool_priority_operations WHERE l2_address = $1";
+        let query = "SELECT serial_id,data,deadline_block,eth_hash,tx_hash,eth_block,eth_block_index,created_at,l1_sender FROM mempool_priority_operations WHERE l2_address = $1";
         let query = match direction {
             PaginationDirection::Newer => {
                 format!("{} AND serial_id >= $2 ORDER BY serial_id LIMIT $3", query)
@@ -551,6 +646,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(ops.into_iter().map(|op| op.into()).collect())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn get_pending_operation_by_hash(
         &mut self,
         tx_hash: H256,
@@ -559,7 +655,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             MempoolPriorityOp,
             r#"
                 SELECT serial_id,data,deadline_block,eth_hash,
-                       tx_hash,eth_block,eth_block_index,created_at 
+                       tx_hash,eth_block,eth_block_index,created_at,l1_sender
                 FROM mempool_priority_operations 
                 WHERE eth_hash = $1
             "#,
@@ -570,12 +666,13 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .map(|op| op.into());
         Ok(op)
     }
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn get_pending_deposits(&mut self, address: Address) -> QueryResult<Vec<PriorityOp>> {
         let ops = sqlx::query_as!(
             MempoolPriorityOp,
             r#"
             SELECT serial_id,data,deadline_block,eth_hash,
-                   tx_hash,eth_block,eth_block_index,created_at 
+                   tx_hash,eth_block,eth_block_index,created_at,l1_sender
             FROM mempool_priority_operations 
             WHERE type = 'Deposit' AND l2_address = $1  
             ORDER BY serial_id"#,
@@ -586,6 +683,107 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(ops.into_iter().map(|op| op.into()).collect())
     }
 
+    /// Loads the confirmed priority operations received before the given time which are
+    /// still not executed, along with the time they were received at.
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
+    pub async fn get_stuck_priority_ops(
+        &mut self,
+        received_before: DateTime<Utc>,
+    ) -> QueryResult<Vec<(PriorityOp, DateTime<Utc>)>> {
+        let start = Instant::now();
+        let ops = sqlx::query_as!(
+            MempoolPriorityOp,
+            r#"
+            SELECT serial_id,data,deadline_block,eth_hash,
+                   tx_hash,eth_block,eth_block_index,created_at,l1_sender
+            FROM mempool_priority_operations
+            WHERE confirmed AND reverted = false AND created_at < $1
+                AND NOT EXISTS (
+                    SELECT 1 FROM executed_priority_operations
+                    WHERE priority_op_serialid = mempool_priority_operations.serial_id
+                )
+            ORDER BY serial_id"#,
+            received_before
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("chain.mempool", "get_stuck_priority_ops", start);
+        Ok(ops
+            .into_iter()
+            .map(|op| {
+                let received_at = op.created_at;
+                (op.into(), received_at)
+            })
+            .collect())
+    }
+
+    /// Returns the greatest serial id among the priority operations which are observed
+    /// on L1 but not executed yet.
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
+    pub async fn get_max_pending_priority_op_serial_id(&mut self) -> QueryResult<Option<SerialId>> {
+        let start = Instant::now();
+        let serial_id = sqlx::query!(
+            "SELECT max(serial_id) FROM mempool_priority_operations
+            WHERE reverted = false
+                AND NOT EXISTS (
+                    SELECT 1 FROM executed_priority_operations
+                    WHERE priority_op_serialid = mempool_priority_operations.serial_id
+                )"
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .max;
+
+        crate::slow_queries::report_query(
+            "chain.mempool",
+            "get_max_pending_priority_op_serial_id",
+            start,
+        );
+        Ok(serial_id.map(|v| v as u64))
+    }
+
+    /// Loads the page of priority operations which are observed on L1 but not executed yet.
+    /// Each operation is returned along with the flag showing whether it has received
+    /// enough confirmations to be included into a block.
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
+    pub async fn get_pending_priority_ops_page(
+        &mut self,
+        start_serial_id: SerialId,
+        limit: u32,
+        direction: PaginationDirection,
+    ) -> QueryResult<Vec<(PriorityOp, bool)>> {
+        let start = Instant::now();
+        let query =
+            "SELECT serial_id,data,deadline_block,eth_hash,eth_block,eth_block_index,confirmed,l1_sender
+            FROM mempool_priority_operations
+            WHERE reverted = false
+                AND NOT EXISTS (
+                    SELECT 1 FROM executed_priority_operations
+                    WHERE priority_op_serialid = mempool_priority_operations.serial_id
+                )";
+        let query = match direction {
+            PaginationDirection::Newer => {
+                format!("{} AND serial_id >= $1 ORDER BY serial_id LIMIT $2", query)
+            }
+            PaginationDirection::Older => {
+                format!(
+                    "{} AND serial_id <= $1 ORDER BY serial_id DESC LIMIT $2",
+                    query
+                )
+            }
+        };
+        let ops: Vec<PendingPriorityOp> = sqlx::query_as(query.as_str())
+            .bind(start_serial_id as i64)
+            .bind(limit as i64)
+            .fetch_all(self.0.conn())
+            .await?;
+
+        crate::slow_queries::report_query("chain.mempool", "get_pending_priority_ops_page", start);
+        Ok(ops.into_iter().map(|op| op.into()).collect())
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn remove_priority_ops_from_mempool(&mut self, ids: &[u64]) -> QueryResult<()> {
         let ids: Vec<_> = ids.iter().map(|v| *v as i64).collect();
         sqlx::query!(
@@ -598,6 +796,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
     }
 
     /// Returns mempool size.
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn get_mempool_size(&mut self) -> QueryResult<u32> {
         let start = Instant::now();
 
@@ -606,11 +805,12 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             .await?
             .count;
 
-        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_mempool_size");
+        crate::slow_queries::report_query("chain.mempool", "get_mempool_size", start);
         Ok(size.unwrap_or(0) as u32)
     }
 
     /// Get info about batch in mempool.
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn get_queued_batch_info(
         &mut self,
         batch_hash: TxHash,
@@ -652,10 +852,11 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             None
         };
 
-        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_queued_batch_info");
+        crate::slow_queries::report_query("chain.mempool", "get_queued_batch_info", start);
         Ok(result)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn get_reverted_blocks(
         &mut self,
         available_block_sizes: &[usize],
@@ -718,7 +919,8 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 mempool_priority_operations.eth_block,
                 mempool_priority_operations.created_at,
                 cast(mempool_priority_operations.eth_block_index as bigint) as "eth_block_index?",
-                mempool_reverted_txs_meta.tx_hash_bytes as tx_hash
+                mempool_reverted_txs_meta.tx_hash_bytes as tx_hash,
+                mempool_priority_operations.l1_sender
                  FROM mempool_priority_operations INNER JOIN mempool_reverted_txs_meta 
                 ON mempool_priority_operations.tx_hash = mempool_reverted_txs_meta.tx_hash 
                 WHERE mempool_reverted_txs_meta.block_number=$1 AND mempool_reverted_txs_meta.tx_type='L1'"#, 
@@ -762,6 +964,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
     }
 
     // Returns executed txs back to mempool for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.mempool"))]
     pub async fn return_executed_txs_to_mempool(
         &mut self,
         last_block_number: BlockNumber,
@@ -901,8 +1104,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
             let tx_hash_bytes = tx.hash().as_ref().to_vec();
             let tx_hash = hex::encode(&tx_hash_bytes);
-            let tx_value =
-                serde_json::to_value(tx).expect("Failed to serialize reverted transaction");
+            let tx_value = canonical_tx(&tx);
             let operation =
                 serde_json::to_value(op).expect("Failed to serialize reverted transaction");
             let eth_sign_data = eth_sign_data.as_ref().map(|sign_data| {
@@ -975,6 +1177,9 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             let eth_hash = priority_op.eth_hash.as_bytes().to_vec();
             let eth_block = priority_op.eth_block as i64;
             let eth_block_index = priority_op.eth_block_index.map(|a| a as i32);
+            let l1_sender = priority_op
+                .l1_sender
+                .map(|l1_sender| l1_sender.as_bytes().to_vec());
 
             sqlx::query!(
                 r#"INSERT INTO mempool_reverted_txs_meta (
@@ -999,9 +1204,9 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 "INSERT INTO mempool_priority_operations (
                     serial_id, data, l1_address, l2_address, 
                     type, deadline_block, eth_hash, tx_hash, eth_block, 
-                    eth_block_index, created_at, confirmed, reverted
+                    eth_block_index, created_at, l1_sender, confirmed, reverted
                 )
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, true, true)",
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, true, true)",
                 serial_id,
                 data,
                 l1_address,
@@ -1012,7 +1217,8 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 tx_hash,
                 eth_block,
                 eth_block_index,
-                created_at
+                created_at,
+                l1_sender
             )
             .execute(transaction.conn())
             .await?;
@@ -1026,6 +1232,10 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             .await?;
         }
 
+        transaction
+            .deposit_aggregates_schema()
+            .remove_reverted_deposits(last_block_number)
+            .await?;
         sqlx::query!(
             r"DELETE FROM executed_priority_operations 
             WHERE block_number > $1",
@@ -1042,10 +1252,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.mempool.return_executed_txs_to_mempool",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.mempool", "return_executed_txs_to_mempool", start);
         Ok(())
     }
 }
@@ -6,7 +6,7 @@ use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 
 // Workspace imports
-use zksync_types::{PriorityOp, SignedZkSyncTx, H256};
+use zksync_types::{Address, PriorityOp, SignedZkSyncTx, H256};
 
 // Local imports
 
@@ -63,11 +63,11 @@ pub(crate) struct MempoolPriorityOp {
     pub tx_hash: String,
     pub eth_hash: Vec<u8>,
     pub data: serde_json::Value,
-    #[allow(dead_code)]
     pub created_at: DateTime<Utc>,
     pub eth_block: i64,
     pub eth_block_index: Option<i32>,
     pub deadline_block: i64,
+    pub l1_sender: Option<Vec<u8>>,
 }
 
 impl From<MempoolPriorityOp> for PriorityOp {
@@ -79,6 +79,38 @@ impl From<MempoolPriorityOp> for PriorityOp {
             eth_hash: H256::from_slice(&value.eth_hash),
             eth_block: value.eth_block as u64,
             eth_block_index: value.eth_block_index.map(|i| i as u64),
+            l1_sender: value
+                .l1_sender
+                .map(|l1_sender| Address::from_slice(&l1_sender)),
         }
     }
 }
+
+#[derive(Debug, FromRow)]
+pub(crate) struct PendingPriorityOp {
+    pub serial_id: i64,
+    pub eth_hash: Vec<u8>,
+    pub data: serde_json::Value,
+    pub eth_block: i64,
+    pub eth_block_index: Option<i32>,
+    pub deadline_block: i64,
+    pub confirmed: bool,
+    pub l1_sender: Option<Vec<u8>>,
+}
+
+impl From<PendingPriorityOp> for (PriorityOp, bool) {
+    fn from(value: PendingPriorityOp) -> Self {
+        let op = PriorityOp {
+            serial_id: value.serial_id as u64,
+            data: serde_json::from_value(value.data).expect("Should be correctly stored"),
+            deadline_block: value.deadline_block as u64,
+            eth_hash: H256::from_slice(&value.eth_hash),
+            eth_block: value.eth_block as u64,
+            eth_block_index: value.eth_block_index.map(|i| i as u64),
+            l1_sender: value
+                .l1_sender
+                .map(|l1_sender| Address::from_slice(&l1_sender)),
+        };
+        (op, value.confirmed)
+    }
+}
@@ -1,12 +1,13 @@
 // Built-in deps
-use std::time::Instant;
+use std::{collections::HashMap, time::Instant};
 // External imports
 use chrono::{Duration, Utc};
+use sqlx::types::BigDecimal;
 // Workspace imports
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     tx::TxHash,
-    BlockNumber, PriorityOp, SerialId, H256,
+    Address, BlockNumber, PriorityOp, SerialId, TokenId, H256,
 };
 // Local imports
 use self::records::{
@@ -15,6 +16,8 @@ use self::records::{
 };
 use crate::chain::operations::records::StoredExecutedTransaction;
 use crate::chain::operations_ext::OperationsExtSchema;
+use crate::deposit_aggregates::DepositAggregatesSchema;
+use crate::dropped_txs::DroppedTxsSchema;
 use crate::ethereum::EthereumSchema;
 use crate::{chain::mempool::MempoolSchema, QueryResult, StorageProcessor};
 
@@ -28,6 +31,7 @@ pub struct OperationsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> OperationsSchema<'a, 'c> {
     /// Return the greatest block number with the given `action_type` and `confirmed` status.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn get_last_block_by_aggregated_action(
         &mut self,
         aggregated_action_type: AggregatedActionType,
@@ -44,13 +48,15 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .max
         .unwrap_or(0);
 
-        metrics::histogram!(
-            "sql.chain.operations.get_last_block_by_aggregated_action",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "get_last_block_by_aggregated_action",
+            start,
         );
         Ok(BlockNumber(max_block as u32))
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn get_stored_aggregated_operation(
         &mut self,
         block_number: BlockNumber,
@@ -69,9 +75,10 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .ok()
         .flatten();
 
-        metrics::histogram!(
-            "sql.chain.operations.get_stored_aggregated_operations",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "get_stored_aggregated_operations",
+            start,
         );
         result
     }
@@ -89,14 +96,12 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!(
-            "sql.chain.operations.get_executed_operation",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.operations", "get_executed_operation", start);
         Ok(op)
     }
 
     /// Retrieves priority operation from the database given its ID.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn get_executed_priority_operation(
         &mut self,
         priority_op_id: u32,
@@ -110,14 +115,16 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.get_executed_priority_operation",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "get_executed_priority_operation",
+            start,
         );
         Ok(op)
     }
 
     /// Retrieves priority operation from the database by its eth_hash.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn get_executed_priority_operation_by_eth_hash(
         &mut self,
         eth_hash: &[u8],
@@ -131,13 +138,43 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.get_executed_priority_operation_by_eth_hash",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "get_executed_priority_operation_by_eth_hash",
+            start,
         );
         Ok(op)
     }
 
+    /// Retrieves the priority operations sent by the Ethereum account, the newest ones first.
+    /// Operations received before the L1 sender was tracked are not returned.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
+    pub async fn get_executed_priority_operations_by_l1_sender(
+        &mut self,
+        l1_sender: Address,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredExecutedPriorityOperation>> {
+        let start = Instant::now();
+        let ops = sqlx::query_as!(
+            StoredExecutedPriorityOperation,
+            "SELECT * FROM executed_priority_operations WHERE l1_sender = $1
+            ORDER BY priority_op_serialid DESC
+            LIMIT $2",
+            l1_sender.as_bytes(),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "get_executed_priority_operations_by_l1_sender",
+            start,
+        );
+        Ok(ops)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn confirm_aggregated_operations(
         &mut self,
         first_block: BlockNumber,
@@ -156,9 +193,10 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         )
         .execute(self.0.conn())
         .await?;
-        metrics::histogram!(
-            "sql.chain.operations.confirm_aggregated_operations",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "confirm_aggregated_operations",
+            start,
         );
         Ok(())
     }
@@ -234,6 +272,9 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             .fetch_optional(transaction.conn())
             .await?.map(|a| a.sequence_number).flatten()
         };
+        DroppedTxsSchema(&mut transaction)
+            .store_rejected_txs(&[&operation])
+            .await?;
         if let Some(seq_no) = sequence_number {
             let mut addresses = Vec::new();
             let mut tokens = Vec::new();
@@ -260,7 +301,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             .await?;
         }
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.operations.store_executed_tx", start.elapsed());
+        crate::slow_queries::report_query("chain.operations", "store_executed_tx", start);
         // It's almost impossible situation, but it could be triggered in tests
         let tx_duration = (Utc::now() - operation.created_at)
             .to_std()
@@ -269,7 +310,141 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         Ok(())
     }
 
-    /// Removes all rejected transactions with an age greater than `max_age` from the database.
+    /// Stores the executed transactions of the block in the database at once.
+    ///
+    /// The result is the same as if the transactions were stored one by one via `store_executed_tx`
+    /// in the given order, but each table is updated with a single statement, which makes it
+    /// much faster for the large blocks. Either all of the transactions are stored or none of them.
+    pub(crate) async fn store_executed_txs(
+        &mut self,
+        operations: Vec<NewExecutedTransaction>,
+    ) -> QueryResult<()> {
+        if operations.is_empty() {
+            return Ok(());
+        }
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        // Every rejection is recorded, even if the transaction is executed later in the block.
+        let rejected: Vec<_> = operations.iter().collect();
+        DroppedTxsSchema(&mut transaction)
+            .store_rejected_txs(&rejected)
+            .await?;
+
+        // A single statement can't update the same row twice, so the duplicates are merged
+        // the way the sequential inserts would do it: the first stored row keeps its position,
+        // and only a successful transaction may replace it.
+        let mut positions = HashMap::with_capacity(operations.len());
+        let mut rows: Vec<NewExecutedTransaction> = Vec::with_capacity(operations.len());
+        for operation in operations {
+            match positions.get(&operation.tx_hash) {
+                Some(&position) if operation.success => rows[position] = operation,
+                Some(_) => {}
+                None => {
+                    positions.insert(operation.tx_hash.clone(), rows.len());
+                    rows.push(operation);
+                }
+            }
+        }
+
+        let tx_hashes: Vec<TxHash> = rows
+            .iter()
+            .filter_map(|operation| TxHash::from_slice(&operation.tx_hash))
+            .collect();
+        MempoolSchema(&mut transaction)
+            .remove_txs(&tx_hashes)
+            .await?;
+
+        // Rows are passed as a single JSON array, since some of the columns are nullable.
+        // Sequence numbers are assigned in the order of the array.
+        let records: Vec<serde_json::Value> = rows
+            .iter()
+            .map(|operation| {
+                serde_json::json!({
+                    "block_number": operation.block_number,
+                    "block_index": operation.block_index,
+                    "tx": operation.tx,
+                    "operation": operation.operation,
+                    "tx_hash": bytea_literal(&operation.tx_hash),
+                    "from_account": bytea_literal(&operation.from_account),
+                    "to_account": operation.to_account.as_deref().map(bytea_literal),
+                    "success": operation.success,
+                    "fail_reason": operation.fail_reason,
+                    "primary_account_address": bytea_literal(&operation.primary_account_address),
+                    "nonce": operation.nonce,
+                    "created_at": operation.created_at.to_rfc3339(),
+                    "eth_sign_data": operation.eth_sign_data,
+                    "batch_id": operation.batch_id,
+                })
+            })
+            .collect();
+
+        // Successful transactions replace the failed ones with the same hash,
+        // failed transactions never replace the stored ones (see `store_executed_tx`).
+        let stored = sqlx::query!(
+            "
+            INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
+            SELECT block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id
+                FROM jsonb_populate_recordset(NULL::executed_transactions, $1)
+            ON CONFLICT (tx_hash)
+            DO UPDATE
+            SET block_number = EXCLUDED.block_number, block_index = EXCLUDED.block_index, tx = EXCLUDED.tx, operation = EXCLUDED.operation, from_account = EXCLUDED.from_account, to_account = EXCLUDED.to_account, success = EXCLUDED.success, fail_reason = EXCLUDED.fail_reason, primary_account_address = EXCLUDED.primary_account_address, nonce = EXCLUDED.nonce, created_at = EXCLUDED.created_at, eth_sign_data = EXCLUDED.eth_sign_data, batch_id = EXCLUDED.batch_id
+            WHERE EXCLUDED.success
+            RETURNING tx_hash, sequence_number
+            ",
+            serde_json::Value::Array(records)
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let mut addresses = Vec::new();
+        let mut tokens = Vec::new();
+        let mut filter_hashes = Vec::new();
+        let mut sequence_numbers = Vec::new();
+        for stored_tx in stored {
+            let seq_no = match stored_tx.sequence_number {
+                Some(seq_no) => seq_no,
+                None => continue,
+            };
+            let operation = &rows[positions[&stored_tx.tx_hash]];
+            for address in operation.affected_accounts.iter() {
+                for token in operation.used_tokens.iter() {
+                    addresses.push(address.clone());
+                    tokens.push(*token);
+                    filter_hashes.push(stored_tx.tx_hash.clone());
+                    sequence_numbers.push(seq_no);
+                }
+            }
+        }
+        sqlx::query!(
+            "
+            INSERT INTO tx_filters (address, token, tx_hash, sequence_number, is_priority)
+            SELECT u.address, u.token, u.tx_hash, u.sequence_number, false
+                FROM UNNEST ($1::bytea[], $2::integer[], $3::bytea[], $4::bigint[])
+                AS u(address, token, tx_hash, sequence_number)
+            ON CONFLICT ON CONSTRAINT tx_filters_pkey DO NOTHING
+            ",
+            &addresses,
+            &tokens,
+            &filter_hashes,
+            &sequence_numbers
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+        crate::slow_queries::report_query("chain.operations", "store_executed_txs", start);
+        let now = Utc::now();
+        for operation in rows.iter() {
+            let tx_duration = (now - operation.created_at).to_std().unwrap_or_default();
+            metrics::histogram!("process_tx", tx_duration, "stage" => "execute");
+        }
+        Ok(())
+    }
+
+    /// Removes all rejected and expired transactions with an age greater than `max_age`
+    /// from the database.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn remove_rejected_transactions(&mut self, max_age: Duration) -> QueryResult<()> {
         let start = Instant::now();
 
@@ -300,12 +475,19 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         )
         .execute(transaction.conn())
         .await?;
+        sqlx::query!(
+            "DELETE FROM expired_transactions WHERE expired_at < $1",
+            offset
+        )
+        .execute(transaction.conn())
+        .await?;
 
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.remove_rejected_transactions",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "remove_rejected_transactions",
+            start,
         );
         Ok(())
     }
@@ -315,6 +497,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
     /// This method is made public to fill the database for tests, do not use it for
     /// any other purposes.
     #[doc = "hidden"]
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn store_executed_priority_op(
         &mut self,
         operation: NewExecutedPriorityOperation,
@@ -328,8 +511,8 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
 
         let sequence_number: Option<i64>= sqlx::query!(
             "INSERT INTO executed_priority_operations (block_number, block_index, operation, from_account, to_account,
-                priority_op_serialid, deadline_block, eth_hash, eth_block, created_at, eth_block_index, tx_hash)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                priority_op_serialid, deadline_block, eth_hash, eth_block, created_at, eth_block_index, tx_hash, l1_sender)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             ON CONFLICT (priority_op_serialid)
             DO NOTHING
             RETURNING sequence_number
@@ -346,6 +529,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             operation.created_at,
             operation.eth_block_index,
             operation.tx_hash,
+            operation.l1_sender,
         )
         .fetch_optional(transaction.conn())
         .await?.map(|a| a.sequence_number).flatten();
@@ -369,34 +553,70 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             )
             .execute(transaction.conn())
             .await?;
+
+            DepositAggregatesSchema(&mut transaction)
+                .add_executed_deposit(operation.priority_op_serialid as SerialId)
+                .await?;
         }
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations.store_executed_priority_op",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.operations", "store_executed_priority_op", start);
         Ok(())
     }
 
-    /// Returns the highest serial id of the executed priority ops
+    /// Returns the highest serial id of the executed priority ops,
+    /// including the ones moved to the archive by the pruner.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn get_max_priority_op_serial_id(&mut self) -> QueryResult<Option<SerialId>> {
         let start = Instant::now();
 
         let max_serial_id = sqlx::query!(
-            r#"SELECT max(priority_op_serialid) as "max" FROM executed_priority_operations"#
+            r#"
+            SELECT GREATEST(
+                (SELECT max(priority_op_serialid) FROM executed_priority_operations),
+                (SELECT max(priority_op_serialid) FROM archive.executed_priority_operations)
+            ) as "max"
+            "#
         )
         .fetch_one(self.0.conn())
         .await?;
         let max_serial_id = max_serial_id.max.map(|record| record as u64);
 
-        metrics::histogram!(
-            "sql.chain.operations.get_max_priority_op_serial_id",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "get_max_priority_op_serial_id",
+            start,
         );
         Ok(max_serial_id)
     }
 
+    /// Returns the token and the amount of the first executed deposit to the account.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
+    pub async fn get_first_deposit(
+        &mut self,
+        address: Address,
+    ) -> QueryResult<Option<(TokenId, BigDecimal)>> {
+        let start = Instant::now();
+
+        let deposit = sqlx::query!(
+            r#"
+            SELECT (operation->'priority_op'->>'token')::INT AS "token!",
+                (operation->'priority_op'->>'amount')::NUMERIC AS "amount!"
+            FROM executed_priority_operations
+            WHERE to_account = $1 AND operation->>'type' = 'Deposit'
+            ORDER BY priority_op_serialid
+            LIMIT 1
+            "#,
+            address.as_bytes()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|deposit| (TokenId(deposit.token as u32), deposit.amount));
+
+        crate::slow_queries::report_query("chain.operations", "get_first_deposit", start);
+        Ok(deposit)
+    }
+
     /// On old contracts, a separate operation was used to withdraw - `CompleteWithdrawals`.
     ///
     /// NOTE: Currently `CompleteWithdrawals` is deprecated but the information is still stored
@@ -437,9 +657,10 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             None => None,
         };
 
-        metrics::histogram!(
-            "sql.chain.operations.eth_withdraw_tx_for_complete_withdrawal",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "eth_withdraw_tx_for_complete_withdrawal",
+            start,
         );
         Ok(res)
     }
@@ -465,15 +686,17 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             .aggregated_op_final_hash(block_number)
             .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.eth_withdraw_tx_for_execute_block",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "eth_withdraw_tx_for_execute_block",
+            start,
         );
         Ok(withdrawal_hash)
     }
 
     /// Returns the hash of the Ethereum transaction in which the
     /// funds were withdrawn corresponding to the withdraw operation on L2.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn eth_tx_for_withdrawal(
         &mut self,
         withdrawal_hash: &TxHash,
@@ -492,14 +715,12 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         let eth_tx_hash =
             eth_withdraw_tx_for_execute_block.or(eth_withdraw_tx_for_complete_withdrawal);
 
-        metrics::histogram!(
-            "sql.chain.operations.eth_tx_for_withdrawal",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.operations", "eth_tx_for_withdrawal", start);
 
         Ok(eth_tx_hash)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn store_aggregated_action(
         &mut self,
         operation: AggregatedOperation,
@@ -566,6 +787,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn get_last_affected_block_by_aggregated_action(
         &mut self,
         aggregated_action: AggregatedActionType,
@@ -582,6 +804,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         Ok(block_number)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn get_aggregated_op_that_affects_block(
         &mut self,
         aggregated_action: AggregatedActionType,
@@ -606,20 +829,23 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
     }
 
     // Removes ethereum unprocessed aggregated operations
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn remove_eth_unprocessed_aggregated_ops(&mut self) -> QueryResult<()> {
         let start = Instant::now();
         sqlx::query!("TRUNCATE eth_unprocessed_aggregated_ops")
             .execute(self.0.conn())
             .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.remove_eth_unprocessed_aggregated_ops",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "remove_eth_unprocessed_aggregated_ops",
+            start,
         );
         Ok(())
     }
 
     // Removes executed priority operations for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn return_executed_priority_operations_to_mempool(
         &mut self,
         last_block: BlockNumber,
@@ -647,6 +873,9 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             .execute(transaction.conn())
             .await?;
 
+        DepositAggregatesSchema(&mut transaction)
+            .remove_reverted_deposits(last_block)
+            .await?;
         sqlx::query!(
             "DELETE FROM executed_priority_operations WHERE block_number > $1",
             *last_block as i64
@@ -655,14 +884,16 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .await?;
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations.remove_executed_priority_operations",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "remove_executed_priority_operations",
+            start,
         );
         Ok(())
     }
 
     // Removes aggregate operations and bindings for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn remove_aggregate_operations(
         &mut self,
         last_block: BlockNumber,
@@ -676,14 +907,12 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .execute(transaction.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.remove_aggregate_operations",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.operations", "remove_aggregate_operations", start);
         Ok(())
     }
 
     // Removes aggregate operations and bindings for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations"))]
     pub async fn remove_aggregate_operations_and_bindings(
         &mut self,
         last_block: BlockNumber,
@@ -739,10 +968,16 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.remove_aggregate_operations_and_bindings",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations",
+            "remove_aggregate_operations_and_bindings",
+            start,
         );
         Ok(())
     }
 }
+
+/// Formats the bytes as the `bytea` literal, so they can be passed to the database as JSON.
+pub(crate) fn bytea_literal(bytes: &[u8]) -> String {
+    format!("\\x{}", hex::encode(bytes))
+}
@@ -2,7 +2,7 @@
 use chrono::prelude::*;
 use serde_json::value::Value;
 use sqlx::FromRow;
-use zksync_types::{PriorityOp, H256};
+use zksync_types::{Address, PriorityOp, H256};
 // Workspace imports
 // Local imports
 
@@ -23,6 +23,7 @@ pub struct StoredExecutedPriorityOperation {
     /// This field must be optional because of backward compatibility.
     pub eth_block_index: Option<i64>,
     pub tx_hash: Vec<u8>,
+    pub l1_sender: Option<Vec<u8>>,
 }
 
 impl From<StoredExecutedPriorityOperation> for PriorityOp {
@@ -34,6 +35,9 @@ impl From<StoredExecutedPriorityOperation> for PriorityOp {
             eth_hash: H256::from_slice(&value.eth_hash),
             eth_block: value.eth_block as u64,
             eth_block_index: Some(value.block_index as u64),
+            l1_sender: value
+                .l1_sender
+                .map(|l1_sender| Address::from_slice(&l1_sender)),
         }
     }
 }
@@ -79,6 +83,7 @@ pub struct NewExecutedPriorityOperation {
     /// This field must be optional because of backward compatibility.
     pub eth_block_index: Option<i64>,
     pub tx_hash: Vec<u8>,
+    pub l1_sender: Option<Vec<u8>>,
     pub affected_accounts: Vec<Vec<u8>>,
     pub token: i32,
 }
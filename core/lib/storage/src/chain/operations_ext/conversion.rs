@@ -1,9 +1,12 @@
 // Built-in deps
 // External imports
 // Workspace imports
-use zksync_api_types::v02::transaction::{
-    ForcedExitData, L1Receipt, L1Transaction, L2Receipt, L2Transaction, Receipt, Transaction,
-    TransactionData, TxData, TxInBlockStatus, WithdrawData, WithdrawNFTData,
+use zksync_api_types::{
+    tx_status::TxStatus,
+    v02::transaction::{
+        ForcedExitData, L1Receipt, L1Transaction, L2Receipt, L2Transaction, Receipt, Transaction,
+        TransactionData, TxData, WithdrawData, WithdrawNFTData,
+    },
 };
 use zksync_types::{
     tx::{EthSignData, TxHash},
@@ -15,18 +18,10 @@ use super::records::{StorageTxData, StorageTxReceipt};
 impl StorageTxReceipt {
     pub(super) fn receipt_from_storage_receipt(
         receipt: StorageTxReceipt,
-        is_block_finalized: Option<bool>,
+        status: TxStatus,
     ) -> Receipt {
+        let status = status.into();
         if receipt.block_number.is_some() {
-            let status = if receipt.success.unwrap() {
-                if is_block_finalized.unwrap() {
-                    TxInBlockStatus::Finalized
-                } else {
-                    TxInBlockStatus::Committed
-                }
-            } else {
-                TxInBlockStatus::Rejected
-            };
             if receipt.eth_block.is_some() {
                 Receipt::L1(L1Receipt {
                     status,
@@ -35,6 +30,7 @@ impl StorageTxReceipt {
                         .block_number
                         .map(|number| BlockNumber(number as u32)),
                     id: receipt.priority_op_serialid.unwrap() as u64,
+                    expected_finality_secs: None,
                 })
             } else {
                 Receipt::L2(L2Receipt {
@@ -44,14 +40,20 @@ impl StorageTxReceipt {
                         .block_number
                         .map(|number| BlockNumber(number as u32)),
                     fail_reason: receipt.fail_reason,
+                    expected_finality_secs: None,
+                    valid_until: None,
+                    withdrawal_hook: None,
                 })
             }
         } else {
             Receipt::L2(L2Receipt {
-                status: TxInBlockStatus::Queued,
+                status,
                 tx_hash: TxHash::from_slice(&receipt.tx_hash).unwrap(),
                 rollup_block: None,
                 fail_reason: None,
+                expected_finality_secs: None,
+                valid_until: None,
+                withdrawal_hook: None,
             })
         }
     }
@@ -86,23 +88,14 @@ impl StorageTxData {
 
     pub(super) fn data_from_storage_data(
         data: StorageTxData,
-        is_block_finalized: Option<bool>,
+        status: TxStatus,
         complete_withdrawals_tx_hash: Option<H256>,
     ) -> TxData {
         let tx_hash = TxHash::from_slice(&data.tx_hash).unwrap();
         let batch_id = data.batch_id.map(|id| id as u32);
+        let status = status.into();
         let tx = if data.block_number.is_some() {
             let block_number = data.block_number.map(|number| BlockNumber(number as u32));
-            let status = if data.success.unwrap() {
-                if is_block_finalized.unwrap() {
-                    TxInBlockStatus::Finalized
-                } else {
-                    TxInBlockStatus::Committed
-                }
-            } else {
-                TxInBlockStatus::Rejected
-            };
-
             let op = if data.eth_hash.is_some() {
                 let operation: ZkSyncOp = serde_json::from_value(data.op).unwrap();
                 let eth_hash = H256::from_slice(&data.eth_hash.unwrap());
@@ -125,6 +118,7 @@ impl StorageTxData {
                 fail_reason: data.fail_reason,
                 created_at: Some(data.created_at),
                 batch_id,
+                memo: None,
             }
         } else {
             let tx_data = Self::tx_data_from_zksync_tx(
@@ -136,16 +130,21 @@ impl StorageTxData {
                 block_index: data.block_index.map(|i| i as u32),
                 block_number: None,
                 op: tx_data,
-                status: TxInBlockStatus::Queued,
+                status,
                 fail_reason: None,
                 created_at: Some(data.created_at),
                 batch_id,
+                memo: None,
             }
         };
         let eth_signature = data.eth_sign_data.map(|eth_sign_data| {
             let eth_sign_data: EthSignData = serde_json::from_value(eth_sign_data).unwrap();
             eth_sign_data.signature.to_string()
         });
-        TxData { tx, eth_signature }
+        TxData {
+            tx,
+            eth_signature,
+            fee_breakdown: None,
+        }
     }
 }
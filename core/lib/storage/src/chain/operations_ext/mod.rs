@@ -1,16 +1,17 @@
 // Built-in deps
-use std::time::Instant;
+use std::{collections::HashSet, time::Instant};
 
 // External imports
 use chrono::{DateTime, Utc};
 
 // Workspace imports
 use zksync_api_types::{
+    tx_status::TxStatus,
     v02::{
         pagination::{AccountTxsRequest, PaginationDirection, PaginationQuery},
         transaction::{
-            ApiTxBatch, BatchStatus, Receipt, Transaction, TxData, TxHashSerializeWrapper,
-            TxInBlockStatus,
+            ApiTxBatch, BatchStatus, L1TxActivity, L2Receipt, Receipt, Transaction, TxData,
+            TxHashSerializeWrapper, TxInBlockStatus,
         },
     },
     Either,
@@ -32,6 +33,7 @@ use crate::{
         block::records::TransactionItem,
         operations::{records::StoredExecutedPriorityOperation, OperationsSchema},
     },
+    connection::{access_history_storage, history_sharding_enabled},
     QueryResult, StorageProcessor,
 };
 use itertools::Itertools;
@@ -56,6 +58,7 @@ pub enum SearchDirection {
 pub struct OperationsExtSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> OperationsExtSchema<'a, 'c> {
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn tx_receipt(&mut self, hash: &[u8]) -> QueryResult<Option<TxReceiptResponse>> {
         let start = Instant::now();
         let tx = OperationsSchema(self.0)
@@ -63,32 +66,22 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .await?;
 
         let result = if let Some(tx) = tx {
-            // Check whether transaction was verified.
-            let verified = OperationsSchema(self.0)
-                .get_stored_aggregated_operation(
-                    BlockNumber(tx.block_number as u32),
-                    AggregatedActionType::ExecuteBlocks,
-                )
-                .await
-                .map(|operation| operation.confirmed)
-                .unwrap_or_default();
-
-            Ok(Some(TxReceiptResponse {
-                tx_hash: hex::encode(hash),
-                block_number: tx.block_number,
-                success: tx.success,
-                verified,
-                fail_reason: tx.fail_reason,
-                prover_run: None,
-            }))
+            let status = tx_status(self.0, Some(tx.block_number), Some(tx.success)).await?;
+            Ok(Some(TxReceiptResponse::new(
+                hex::encode(hash),
+                tx.block_number,
+                status,
+                tx.fail_reason,
+            )))
         } else {
             Ok(None)
         };
 
-        metrics::histogram!("sql.chain.operations_ext.tx_receipt", start.elapsed());
+        crate::slow_queries::report_query("chain.operations_ext", "tx_receipt", start);
         result
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn tx_receipt_api_v02(&mut self, hash: &[u8]) -> QueryResult<Option<Receipt>> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -148,27 +141,115 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         )
         .fetch_optional(transaction.conn())
         .await?;
+        // The transaction may be evicted from the mempool as expired.
+        let expired_valid_until = if receipt.is_none() {
+            transaction
+                .chain()
+                .mempool_schema()
+                .get_expired_tx(hash)
+                .await?
+        } else {
+            None
+        };
+        // The transaction may be moved to the history database.
+        let receipt = match receipt {
+            Some(receipt) => Some(receipt),
+            // Expired transactions are never moved to the history database.
+            None if expired_valid_until.is_some() => None,
+            None => match access_history_storage().await? {
+                Some(mut history) => history.history_schema().tx_receipt(hash).await?,
+                None => None,
+            },
+        };
 
         let result = if let Some(receipt) = receipt {
-            let is_block_finalized =
-                is_block_finalized(&mut transaction, receipt.block_number).await?;
+            let status = tx_status(&mut transaction, receipt.block_number, receipt.success).await?;
 
             Some(StorageTxReceipt::receipt_from_storage_receipt(
-                receipt,
-                is_block_finalized,
+                receipt, status,
             ))
         } else {
-            None
+            expired_valid_until.map(|valid_until| {
+                Receipt::L2(L2Receipt {
+                    tx_hash: TxHash::from_slice(hash).unwrap(),
+                    rollup_block: None,
+                    status: TxInBlockStatus::Expired,
+                    fail_reason: None,
+                    expected_finality_secs: None,
+                    valid_until: Some(valid_until),
+                    withdrawal_hook: None,
+                })
+            })
         };
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations_ext.tx_receipt_api_v02",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.operations_ext", "tx_receipt_api_v02", start);
         Ok(result)
     }
 
+    /// Checks if the transaction is already known, i.e. it's either queued in the mempool or
+    /// executed successfully (the pruned transactions included). Returns the current status of
+    /// the transaction along with the time of its original submission.
+    ///
+    /// Rejected and expired transactions are not reported, since they may be submitted again.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
+    pub async fn known_tx(
+        &mut self,
+        tx_hash: TxHash,
+    ) -> QueryResult<Option<(TxInBlockStatus, DateTime<Utc>)>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        // Every table is looked up by the index on the hash, executed transactions come first
+        // since the mempool may still contain the transaction which was just executed.
+        let known_tx = sqlx::query!(
+            r#"
+            SELECT block_number AS "block_number?", created_at AS "created_at!" FROM (
+                SELECT block_number, created_at FROM executed_transactions
+                    WHERE tx_hash = $1 AND success = true
+                UNION ALL
+                SELECT block_number, created_at FROM archive.executed_transactions
+                    WHERE tx_hash = $1 AND success = true
+                UNION ALL
+                SELECT NULL::bigint AS block_number, created_at FROM mempool_txs
+                    WHERE tx_hash = $2
+            ) AS known
+            ORDER BY block_number NULLS LAST
+            LIMIT 1
+            "#,
+            tx_hash.as_ref(),
+            hex::encode(tx_hash.as_ref()),
+        )
+        .fetch_optional(transaction.conn())
+        .await?;
+
+        let result = match known_tx {
+            Some(known_tx) => {
+                let status = match known_tx.block_number {
+                    Some(block_number) => {
+                        let finalized = transaction
+                            .chain()
+                            .block_schema()
+                            .is_block_finalized(BlockNumber(block_number as u32))
+                            .await?;
+                        if finalized {
+                            TxInBlockStatus::Finalized
+                        } else {
+                            TxInBlockStatus::Committed
+                        }
+                    }
+                    None => TxInBlockStatus::Queued,
+                };
+                Some((status, known_tx.created_at))
+            }
+            None => None,
+        };
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("chain.operations_ext", "known_tx", start);
+        Ok(result)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn tx_data_by_block_and_index_api_v02(
         &mut self,
         block_number: BlockNumber,
@@ -234,6 +315,18 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         )
         .fetch_optional(transaction.conn())
         .await?;
+        let data = match data {
+            Some(data) => Some(data),
+            None => match access_history_storage().await? {
+                Some(mut history) => {
+                    history
+                        .history_schema()
+                        .tx_data_by_block_and_index(block_number, block_index)
+                        .await?
+                }
+                None => None,
+            },
+        };
 
         let result = if let Some(data) = data {
             Some(tx_data_from_storage(&mut transaction, data).await?)
@@ -242,13 +335,26 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         };
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations_ext.tx_data_by_block_and_index_api_v02",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations_ext",
+            "tx_data_by_block_and_index_api_v02",
+            start,
         );
         Ok(result)
     }
 
+    /// Restores the API data of the pruned transaction from its body loaded from the archive.
+    /// The status is determined by the current state of the block, same as for the stored ones.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
+    pub async fn archived_tx_data(&mut self, data: StorageTxData) -> QueryResult<TxData> {
+        let start = Instant::now();
+        let result = tx_data_from_storage(self.0, data).await?;
+
+        crate::slow_queries::report_query("chain.operations_ext", "archived_tx_data", start);
+        Ok(result)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn tx_data_api_v02(&mut self, hash: &[u8]) -> QueryResult<Option<TxData>> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -328,6 +434,13 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         )
         .fetch_optional(transaction.conn())
         .await?;
+        let data = match data {
+            Some(data) => Some(data),
+            None => match access_history_storage().await? {
+                Some(mut history) => history.history_schema().tx_data(hash).await?,
+                None => None,
+            },
+        };
 
         let result = if let Some(data) = data {
             Some(tx_data_from_storage(&mut transaction, data).await?)
@@ -336,10 +449,11 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         };
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.operations_ext.tx_data_api_v02", start.elapsed());
+        crate::slow_queries::report_query("chain.operations_ext", "tx_data_api_v02", start);
         Ok(result)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_priority_op_receipt(
         &mut self,
         op_id: u32,
@@ -349,37 +463,15 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .get_executed_priority_operation(op_id)
             .await?;
 
-        let result = match stored_executed_prior_op {
-            Some(stored_executed_prior_op) => {
-                let verified = OperationsSchema(self.0)
-                    .get_stored_aggregated_operation(
-                        BlockNumber(stored_executed_prior_op.block_number as u32),
-                        AggregatedActionType::ExecuteBlocks,
-                    )
-                    .await
-                    .map(|operation| operation.confirmed)
-                    .unwrap_or_default();
+        let block_number = stored_executed_prior_op.map(|op| op.block_number);
+        let status = tx_status(self.0, block_number, Some(true)).await?;
+        let receipt = PriorityOpReceiptResponse::new(status);
 
-                Ok(PriorityOpReceiptResponse {
-                    committed: true,
-                    verified,
-                    prover_run: None,
-                })
-            }
-            None => Ok(PriorityOpReceiptResponse {
-                committed: false,
-                verified: false,
-                prover_run: None,
-            }),
-        };
-
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_priority_op_receipt",
-            start.elapsed()
-        );
-        result
+        crate::slow_queries::report_query("chain.operations_ext", "get_priority_op_receipt", start);
+        Ok(receipt)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_tx_by_hash(&mut self, hash: &[u8]) -> QueryResult<Option<TxByHashResponse>> {
         let start = Instant::now();
 
@@ -391,7 +483,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             self.find_priority_op_by_hash(hash).await?
         };
 
-        metrics::histogram!("sql.chain.operations_ext.get_tx_by_hash", start.elapsed());
+        crate::slow_queries::report_query("chain.operations_ext", "get_tx_by_hash", start);
         Ok(result)
     }
 
@@ -525,7 +617,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             None
         };
 
-        metrics::histogram!("sql.chain.operations_ext.find_tx_by_hash", start.elapsed());
+        crate::slow_queries::report_query("chain.operations_ext", "find_tx_by_hash", start);
         Ok(result)
     }
 
@@ -606,15 +698,17 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             None
         };
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.find_priority_op_by_hash",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations_ext",
+            "find_priority_op_by_hash",
+            start,
         );
         Ok(result)
     }
 
     /// Loads the date and time of the moment when the first transaction for the account was executed.
     /// Can be `None` if there were no transactions associated with provided address.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn account_created_on(
         &mut self,
         address: &Address,
@@ -658,15 +752,40 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.account_created_on",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.operations_ext", "account_created_on", start);
         Ok(first_history_entry.map(|entry| entry.created_at))
     }
 
+    /// Checks whether the addresses have ever been affected by the executed transactions or
+    /// priority operations. Returns the flags in the order of the addresses.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
+    pub async fn address_has_activity(&mut self, addresses: &[Address]) -> QueryResult<Vec<bool>> {
+        let start = Instant::now();
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let activity = sqlx::query!(
+            r#"
+            SELECT EXISTS(SELECT 1 FROM tx_filters WHERE tx_filters.address = u.address) AS "active!"
+            FROM UNNEST($1::bytea[]) WITH ORDINALITY AS u(address, idx)
+            ORDER BY u.idx
+            "#,
+            &addresses,
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| row.active)
+        .collect();
+
+        crate::slow_queries::report_query("chain.operations_ext", "address_has_activity", start);
+        Ok(activity)
+    }
+
     /// Loads the range of the transactions applied to the account starting
     /// from the block with number $(offset) up to $(offset + limit).
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_account_transactions_history(
         &mut self,
         address: &Address,
@@ -718,7 +837,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         block_number,
                         created_at,
                         executed_transactions.sequence_number,
-                        batch_id
+                        batch_id,
+                        Null::bytea as l1_sender
                     FROM executed_transactions
                     INNER JOIN tx_hashes
                         ON tx_hashes.sequence_number = executed_transactions.sequence_number
@@ -734,7 +854,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         block_number,
                         created_at,
                         executed_priority_operations.sequence_number,
-                        Null::bigint as batch_id
+                        Null::bigint as batch_id,
+                        l1_sender
                     FROM executed_priority_operations 
                     INNER JOIN tx_hashes
                         ON tx_hashes.sequence_number = executed_priority_operations.sequence_number
@@ -751,7 +872,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                 true as "commited!",
                 coalesce(verified.confirmed, false) as "verified!",
                 created_at as "created_at!",
-                batch_id as "batch_id?"
+                batch_id as "batch_id?",
+                '0x' || encode(l1_sender, 'hex') as "l1_sender?"
             FROM transactions
             LEFT JOIN aggr_exec verified ON transactions.block_number = verified.block_number
             ORDER BY transactions.block_number DESC, sequence_number DESC
@@ -799,9 +921,10 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         }
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_account_transactions_history",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations_ext",
+            "get_account_transactions_history",
+            start,
         );
         Ok(tx_history)
     }
@@ -860,6 +983,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
     /// Unlike `get_account_transactions_history`, this method does not use
     /// a relative offset, and thus not prone to report the same tx twice if new
     /// transactions were added to the database.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_account_transactions_history_from(
         &mut self,
         address: &Address,
@@ -950,7 +1074,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         block_number,
                         created_at,
                         executed_transactions.sequence_number,
-                        batch_id
+                        batch_id,
+                        Null::bytea as l1_sender
                     FROM executed_transactions
                     INNER JOIN tx_hashes
                         ON tx_hashes.sequence_number = executed_transactions.sequence_number
@@ -966,7 +1091,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         block_number,
                         created_at,
                         executed_priority_operations.sequence_number,
-                        Null::bigint as batch_id
+                        Null::bigint as batch_id,
+                        l1_sender
                     FROM
                         executed_priority_operations
                     INNER JOIN tx_hashes
@@ -984,7 +1110,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                 true as commited,
                 coalesce(verified.confirmed, false) as verified,
                 created_at ,
-                batch_id 
+                batch_id ,
+                '0x' || encode(l1_sender, 'hex') as l1_sender
             FROM transactions
             LEFT JOIN aggr_comm committed ON
                 committed.block_number = transactions.block_number AND committed.confirmed = true
@@ -1041,13 +1168,15 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         }
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_account_transactions_history_from",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations_ext",
+            "get_account_transactions_history_from",
+            start,
         );
         Ok(tx_history)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_account_transactions(
         &mut self,
         query: &PaginationQuery<AccountTxsRequest>,
@@ -1156,9 +1285,10 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         };
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_account_transactions",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations_ext",
+            "get_account_transactions",
+            start,
         );
         Ok(txs)
     }
@@ -1172,53 +1302,32 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         id_from: i64,
         direction: PaginationDirection,
     ) -> QueryResult<Vec<TransactionItem>> {
-        let query_direction = match direction {
-            PaginationDirection::Newer => {
-                "WHERE sequence_number >= $4 
-                ORDER BY sequence_number 
-                LIMIT $5"
-            }
-            PaginationDirection::Older => {
-                "WHERE sequence_number <= $4
-                ORDER BY sequence_number DESC
-                LIMIT $5"
-            }
-        };
+        if history_sharding_enabled() {
+            // Transactions may be moved to the history database, so they can't be joined
+            // with the filters and are loaded by the sequence numbers instead.
+            let query = two_accounts_sequence_numbers_query(token.is_some(), direction);
+            let sequence_numbers: Vec<i64> = sqlx::query_scalar(&query)
+                .bind(address.as_bytes())
+                .bind(second_address.as_bytes())
+                .bind(token.unwrap_or_default().0 as i32)
+                .bind(id_from)
+                .bind(limit)
+                .fetch_all(self.0.conn())
+                .await?;
 
-        let token_query = if token.is_some() {
-            "AND token = $3"
-        } else {
-            ""
-        };
+            let txs = self
+                .get_executed_txs_for_account(sequence_numbers)
+                .await?
+                .into_iter()
+                .sorted_by(|tx1, tx2| match direction {
+                    PaginationDirection::Newer => tx1.sequence_number.cmp(&tx2.sequence_number),
+                    PaginationDirection::Older => tx2.sequence_number.cmp(&tx1.sequence_number),
+                })
+                .collect();
+            return Ok(txs);
+        }
 
-        let query = format!(
-            r#"
-                WITH tx_hashes AS (
-                    SELECT DISTINCT tx_hash FROM tx_filters
-                    WHERE address = $1 {} 
-                    INTERSECT
-                    SELECT DISTINCT tx_hash FROM tx_filters
-                    WHERE address = $2 {}
-                )
-                SELECT                     
-                    executed_transactions.tx_hash,
-                    sequence_number,
-                    tx as op,
-                    block_number,
-                    created_at,
-                    success,
-                    fail_reason,
-                    Null::bytea as eth_hash,
-                    Null::bigint as priority_op_serialid,
-                    block_index,
-                    batch_id
-                FROM tx_hashes INNER JOIN executed_transactions 
-                    ON tx_hashes.tx_hash = executed_transactions.tx_hash
-                {}
-                
-            "#,
-            token_query, token_query, query_direction
-        );
+        let query = two_accounts_transactions_query(token.is_some(), direction);
 
         Ok(sqlx::query_as(&query)
             .bind(address.as_bytes())
@@ -1238,29 +1347,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         id_from: i64,
         direction: PaginationDirection,
     ) -> QueryResult<Vec<SequenceNumberRecord>> {
-        let query_direction = match direction {
-            PaginationDirection::Newer => {
-                "AND sequence_number  >= $3
-                ORDER BY sequence_number
-                LIMIT $4"
-            }
-            PaginationDirection::Older => {
-                "AND sequence_number <= $3
-                ORDER BY sequence_number DESC
-                LIMIT $4"
-            }
-        };
-
-        let token_query = if token.is_some() {
-            "AND token = $2"
-        } else {
-            ""
-        };
-
-        let query = format!(
-            "SELECT DISTINCT sequence_number, is_priority FROM tx_filters WHERE address = $1 {} {}",
-            token_query, query_direction
-        );
+        let query = account_sequence_numbers_query(token.is_some(), direction);
 
         Ok(sqlx::query_as(&query)
             .bind(address.as_bytes())
@@ -1302,11 +1389,13 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .await?)
     }
 
+    /// Loads the executed transactions with the given sequence numbers. Transactions missing
+    /// from the main database are loaded from the history one, if it's configured.
     async fn get_executed_txs_for_account(
         &mut self,
         sequence_numbers: Vec<i64>,
     ) -> QueryResult<Vec<TransactionItem>> {
-        Ok(sqlx::query_as!(
+        let mut txs = sqlx::query_as!(
             TransactionItem,
             r#"
                SELECT
@@ -1330,9 +1419,27 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             &sequence_numbers
         )
         .fetch_all(self.0.conn())
-        .await?)
+        .await?;
+
+        if txs.len() < sequence_numbers.len() {
+            if let Some(mut history) = access_history_storage().await? {
+                let loaded: HashSet<_> = txs.iter().filter_map(|tx| tx.sequence_number).collect();
+                let missing: Vec<_> = sequence_numbers
+                    .into_iter()
+                    .filter(|sequence_number| !loaded.contains(sequence_number))
+                    .collect();
+                txs.extend(
+                    history
+                        .history_schema()
+                        .txs_by_sequence_numbers(&missing)
+                        .await?,
+                );
+            }
+        }
+        Ok(txs)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_account_last_tx_hash(
         &mut self,
         address: Address,
@@ -1355,13 +1462,15 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .await?;
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_account_last_tx_hash",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations_ext",
+            "get_account_last_tx_hash",
+            start,
         );
         Ok(record.map(|record| TxHash::from_slice(&record.tx_hash).unwrap()))
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_block_last_tx_hash(
         &mut self,
         block_number: BlockNumber,
@@ -1393,14 +1502,12 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_block_last_tx_hash",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.operations_ext", "get_block_last_tx_hash", start);
         Ok(record.map(|record| TxHash::from_slice(&record.tx_hash).unwrap()))
     }
 
     // TODO Remove it after migration is complete
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_accounts_range(
         &mut self,
         start_account: Option<Address>,
@@ -1445,6 +1552,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
     }
 
     // TODO Remove it after migration is complete
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn update_txs_count(
         &mut self,
         start_account: Address,
@@ -1482,6 +1590,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_account_transactions_count(
         &mut self,
         address: Address,
@@ -1529,14 +1638,16 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .await?
             .count
         };
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_account_transactions_count",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.operations_ext",
+            "get_account_transactions_count",
+            start,
         );
         Ok(count as u32)
     }
 
     /// Returns `created_at` for `block_number` fields for transaction with given hash.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_tx_sequence_number_for_block(
         &mut self,
         tx_hash: TxHash,
@@ -1574,10 +1685,11 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .flatten();
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.get_tx_sequence_number", start.elapsed());
+        crate::slow_queries::report_query("chain.operations_ext", "get_tx_sequence_number", start);
         Ok(result)
     }
     /// Returns `created_at` and `block_number` fields for transaction with given hash.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_tx_sequence_number(&mut self, tx_hash: TxHash) -> QueryResult<Option<i64>> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -1608,10 +1720,11 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .flatten();
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.get_tx_sequence_number", start.elapsed());
+        crate::slow_queries::report_query("chain.operations_ext", "get_tx_sequence_number", start);
         Ok(result)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_in_block_batch_info(
         &mut self,
         batch_hash: TxHash,
@@ -1639,32 +1752,29 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                 .iter()
                 .map(|tx| TxHashSerializeWrapper(TxHash::from_slice(&tx.tx_hash).unwrap()))
                 .collect();
-            let block_number = BlockNumber(batch_data[0].block_number as u32);
-            let batch_status = if batch_data[0].success {
-                if let Some(op) = transaction
+            let block_number = batch_data[0].block_number;
+            let status = tx_status(
+                &mut transaction,
+                Some(block_number),
+                Some(batch_data[0].success),
+            )
+            .await?;
+            // The finalized batch was updated once its block was executed on Ethereum.
+            let updated_at = match status {
+                TxStatus::Finalized => transaction
                     .chain()
                     .operations_schema()
                     .get_stored_aggregated_operation(
-                        block_number,
+                        BlockNumber(block_number as u32),
                         AggregatedActionType::ExecuteBlocks,
                     )
                     .await
-                {
-                    BatchStatus {
-                        updated_at: op.created_at,
-                        last_state: TxInBlockStatus::Finalized,
-                    }
-                } else {
-                    BatchStatus {
-                        updated_at: created_at,
-                        last_state: TxInBlockStatus::Committed,
-                    }
-                }
-            } else {
-                BatchStatus {
-                    updated_at: created_at,
-                    last_state: TxInBlockStatus::Rejected,
-                }
+                    .map_or(created_at, |op| op.created_at),
+                _ => created_at,
+            };
+            let batch_status = BatchStatus {
+                updated_at,
+                last_state: status.into(),
             };
             Some(ApiTxBatch {
                 batch_hash,
@@ -1677,10 +1787,11 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         };
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.get_in_block_batch_info", start.elapsed());
+        crate::slow_queries::report_query("chain.operations_ext", "get_in_block_batch_info", start);
         Ok(result)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_batch_info(&mut self, batch_hash: TxHash) -> QueryResult<Option<ApiTxBatch>> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -1701,10 +1812,104 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         };
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.get_batch_info", start.elapsed());
+        crate::slow_queries::report_query("chain.operations_ext", "get_batch_info", start);
         Ok(result)
     }
 
+    /// Finds the L2 activity linked to the Ethereum transaction: the priority operations
+    /// requested by it and the withdrawals completed by it, either by executing the blocks
+    /// or by the deprecated `CompleteWithdrawals` operation. Returns `None` if there is none.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
+    pub async fn find_by_l1_hash(&mut self, eth_hash: H256) -> QueryResult<Option<L1TxActivity>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        // Executed operations are removed from the mempool, unless their blocks are reverted.
+        let priority_ops = sqlx::query!(
+            r#"
+            SELECT tx_hash AS "tx_hash!" FROM (
+                SELECT tx_hash, priority_op_serialid AS serial_id
+                FROM executed_priority_operations
+                WHERE eth_hash = $1
+                UNION
+                SELECT decode(tx_hash, 'hex') AS tx_hash, serial_id
+                FROM mempool_priority_operations
+                WHERE eth_hash = $1
+            ) priority_ops
+            ORDER BY serial_id
+            "#,
+            eth_hash.as_bytes()
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|op| op.tx_hash);
+
+        let executed_withdrawals = sqlx::query!(
+            r#"
+            WITH executed_blocks AS (
+                SELECT aggregate_operations.from_block, aggregate_operations.to_block
+                FROM eth_operations
+                INNER JOIN eth_aggregated_ops_binding
+                    ON eth_aggregated_ops_binding.eth_op_id = eth_operations.id
+                INNER JOIN aggregate_operations
+                    ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+                WHERE eth_operations.final_hash = $1 AND eth_operations.confirmed = true
+                    AND aggregate_operations.action_type = $2
+            )
+            SELECT tx_hash FROM executed_transactions
+            INNER JOIN executed_blocks
+                ON executed_transactions.block_number
+                    BETWEEN executed_blocks.from_block AND executed_blocks.to_block
+            WHERE success AND tx->>'type' IN ('Withdraw', 'ForcedExit', 'WithdrawNFT')
+            ORDER BY block_number, block_index
+            "#,
+            eth_hash.as_bytes(),
+            AggregatedActionType::ExecuteBlocks.to_string()
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|withdrawal| withdrawal.tx_hash);
+
+        let legacy_withdrawals = sqlx::query!(
+            "SELECT withdrawal_hash FROM pending_withdrawals
+            INNER JOIN complete_withdrawals_transactions
+                ON pending_withdrawals.id >= pending_withdrawals_queue_start_index
+                    AND pending_withdrawals.id < pending_withdrawals_queue_end_index
+            WHERE complete_withdrawals_transactions.tx_hash = $1
+            ORDER BY pending_withdrawals.id",
+            eth_hash.as_bytes()
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|withdrawal| withdrawal.withdrawal_hash);
+
+        let to_api_hash = |hash: Vec<u8>| {
+            TxHashSerializeWrapper(TxHash::from_slice(&hash).expect("Invalid stored tx hash"))
+        };
+        let activity = L1TxActivity {
+            eth_hash,
+            priority_ops: priority_ops.map(to_api_hash).collect(),
+            completed_withdrawals: executed_withdrawals
+                .chain(legacy_withdrawals)
+                .map(to_api_hash)
+                .collect(),
+        };
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("chain.operations_ext", "find_by_l1_hash", start);
+        if activity.priority_ops.is_empty() && activity.completed_withdrawals.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(activity))
+        }
+    }
+
+    /// Loads the transaction data for the web3 API. The sender of the priority operation is
+    /// the sender of its Ethereum transaction if it's known, rather than the operation initiator.
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn tx_data_for_web3(&mut self, hash: &[u8]) -> QueryResult<Option<Web3TxData>> {
         let start = Instant::now();
 
@@ -1727,7 +1932,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         block_number,
                         priority_op_serialid as nonce,
                         block_index,
-                        from_account,
+                        COALESCE(l1_sender, from_account) AS from_account,
                         to_account
                     FROM executed_priority_operations
                     WHERE tx_hash = $1 OR eth_hash = $1
@@ -1754,10 +1959,11 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.operations_ext.tx_data_for_web3", start.elapsed());
+        crate::slow_queries::report_query("chain.operations_ext", "tx_data_for_web3", start);
         Ok(result)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn web3_receipt_by_hash(
         &mut self,
         hash: &[u8],
@@ -1784,7 +1990,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         block_number,
                         operation,
                         block_index,
-                        from_account,
+                        COALESCE(l1_sender, from_account) AS from_account,
                         to_account,
                         true as success
                     FROM executed_priority_operations
@@ -1817,13 +2023,11 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .fetch_optional(self.0.conn())
             .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.web3_receipt_by_hash",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.operations_ext", "web3_receipt_by_hash", start);
         Ok(tx)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn web3_receipts(
         &mut self,
         from_block: BlockNumber,
@@ -1851,7 +2055,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         block_number,
                         operation,
                         block_index,
-                        from_account,
+                        COALESCE(l1_sender, from_account) AS from_account,
                         to_account,
                         true as success
                     FROM executed_priority_operations
@@ -1885,10 +2089,11 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .fetch_all(self.0.conn())
             .await?;
 
-        metrics::histogram!("sql.chain.operations_ext.web3_receipts", start.elapsed());
+        crate::slow_queries::report_query("chain.operations_ext", "web3_receipts", start);
         Ok(receipts)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn load_executed_txs_in_block_range(
         &mut self,
         from_block: BlockNumber,
@@ -1913,6 +2118,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(result)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn load_executed_priority_ops_in_block_range(
         &mut self,
         from_block: BlockNumber,
@@ -1937,6 +2143,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(result)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn last_block_with_updated_tx_filters(&mut self) -> QueryResult<BlockNumber> {
         let max1: i64 = sqlx::query!(
             r#"
@@ -1965,6 +2172,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
     }
 
     // TODO Delete it right after execution
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn set_unique_sequence_number_for_priority_operations(
         &mut self,
         last_seq_no: i64,
@@ -2003,6 +2211,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
     }
 
     // TODO Delete it right after execution
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn set_seq_no_for_executed_txs(&mut self, last_seq_no: i64) -> i64 {
         let values = sqlx::query!(
             r#"
@@ -2032,6 +2241,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
     }
 
     // TODO Delete it right after execution
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn get_last_seq_no(&mut self) -> i64 {
         sqlx::query!(
             r#"
@@ -2049,6 +2259,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
     }
 
     // TODO Delete it right after execution
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn update_non_unique_tx_filters_for_priority_ops(&mut self) -> Vec<Vec<u8>> {
         let mut tx_hash = vec![];
         let mut records = vec![];
@@ -2091,6 +2302,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
     }
 
     // TODO Delete it right after execution
+    #[tracing::instrument(skip_all, fields(schema = "chain.operations_ext"))]
     pub async fn update_executed_tx_filters(
         &mut self,
         records: Vec<(Vec<u8>, Vec<u8>, i32, i64)>,
@@ -2136,22 +2348,23 @@ async fn complete_withdrawals_tx_hash(
     Ok(result)
 }
 
-async fn is_block_finalized(
+/// Derives the status of the operation, `block_number` is `None` until it's executed.
+async fn tx_status(
     transaction: &mut StorageProcessor<'_>,
     block_number: Option<i64>,
-) -> QueryResult<Option<bool>> {
+    success: Option<bool>,
+) -> QueryResult<TxStatus> {
     // We always use Option<i64> for block number in cases with this module.
     // So it's much cleaner to keep this check here
     if let Some(block_number) = block_number {
-        Ok(Some(
-            transaction
-                .chain()
-                .block_schema()
-                .is_block_finalized(BlockNumber(block_number as u32))
-                .await?,
-        ))
+        let block_finalized = transaction
+            .chain()
+            .block_schema()
+            .is_block_finalized(BlockNumber(block_number as u32))
+            .await?;
+        Ok(TxStatus::executed(success.unwrap_or(true), block_finalized))
     } else {
-        Ok(None)
+        Ok(TxStatus::Queued)
     }
 }
 
@@ -2161,11 +2374,128 @@ async fn tx_data_from_storage(
 ) -> QueryResult<TxData> {
     let complete_withdrawals_tx_hash = complete_withdrawals_tx_hash(transaction, &data).await?;
 
-    let is_block_finalized = is_block_finalized(transaction, data.block_number).await?;
+    let status = tx_status(transaction, data.block_number, data.success).await?;
 
     Ok(StorageTxData::data_from_storage_data(
         data,
-        is_block_finalized,
+        status,
         complete_withdrawals_tx_hash,
     ))
 }
+
+/// Builds the query loading the executed transactions of both accounts.
+/// Parameters: `$1` and `$2` are the addresses, `$3` is the token, `$4` is the sequence number
+/// to start from, `$5` is the limit.
+pub(crate) fn two_accounts_transactions_query(
+    with_token: bool,
+    direction: PaginationDirection,
+) -> String {
+    let query_direction = match direction {
+        PaginationDirection::Newer => {
+            "WHERE sequence_number >= $4 
+            ORDER BY sequence_number 
+            LIMIT $5"
+        }
+        PaginationDirection::Older => {
+            "WHERE sequence_number <= $4
+            ORDER BY sequence_number DESC
+            LIMIT $5"
+        }
+    };
+
+    let token_query = if with_token { "AND token = $3" } else { "" };
+
+    format!(
+        r#"
+            WITH tx_hashes AS (
+                SELECT DISTINCT tx_hash FROM tx_filters
+                WHERE address = $1 {} 
+                INTERSECT
+                SELECT DISTINCT tx_hash FROM tx_filters
+                WHERE address = $2 {}
+            )
+            SELECT                     
+                executed_transactions.tx_hash,
+                sequence_number,
+                tx as op,
+                block_number,
+                created_at,
+                success,
+                fail_reason,
+                Null::bytea as eth_hash,
+                Null::bigint as priority_op_serialid,
+                block_index,
+                batch_id
+            FROM tx_hashes INNER JOIN executed_transactions 
+                ON tx_hashes.tx_hash = executed_transactions.tx_hash
+            {}
+            
+        "#,
+        token_query, token_query, query_direction
+    )
+}
+
+/// Builds the query loading the sequence numbers of the executed transactions of both accounts.
+/// Parameters are the same as in `two_accounts_transactions_query`.
+pub(crate) fn two_accounts_sequence_numbers_query(
+    with_token: bool,
+    direction: PaginationDirection,
+) -> String {
+    let query_direction = match direction {
+        PaginationDirection::Newer => {
+            "WHERE sequence_number >= $4
+            ORDER BY sequence_number
+            LIMIT $5"
+        }
+        PaginationDirection::Older => {
+            "WHERE sequence_number <= $4
+            ORDER BY sequence_number DESC
+            LIMIT $5"
+        }
+    };
+
+    let token_query = if with_token { "AND token = $3" } else { "" };
+
+    format!(
+        r#"
+            WITH sequence_numbers AS (
+                SELECT sequence_number FROM tx_filters
+                WHERE address = $1 AND NOT is_priority {}
+                INTERSECT
+                SELECT sequence_number FROM tx_filters
+                WHERE address = $2 AND NOT is_priority {}
+            )
+            SELECT sequence_number FROM sequence_numbers
+            {}
+        "#,
+        token_query, token_query, query_direction
+    )
+}
+
+/// Builds the query loading the sequence numbers of the account operations.
+/// Parameters: `$1` is the address, `$2` is the token, `$3` is the sequence number
+/// to start from, `$4` is the limit.
+pub(crate) fn account_sequence_numbers_query(
+    with_token: bool,
+    direction: PaginationDirection,
+) -> String {
+    let query_direction = match direction {
+        PaginationDirection::Newer => {
+            "AND sequence_number  >= $3
+            ORDER BY sequence_number
+            LIMIT $4"
+        }
+        PaginationDirection::Older => {
+            "AND sequence_number <= $3
+            ORDER BY sequence_number DESC
+            LIMIT $4"
+        }
+    };
+
+    let token_query = if with_token { "AND token = $2" } else { "" };
+
+    format!(
+        "SELECT DISTINCT sequence_number, is_priority FROM tx_filters WHERE address = $1 {} {}",
+        token_query, query_direction
+    )
+}
@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::value::Value;
 use sqlx::FromRow;
 // Workspace imports
+use zksync_api_types::tx_status::TxStatus;
+use zksync_utils::{BytesToHexSerde, OptionBytesToHexSerde, ZeroxPrefix};
 // Local imports
 use crate::prover::records::ProverRun;
 
@@ -34,6 +36,19 @@ pub struct TransactionsHistoryItem {
     pub verified: bool,
     pub created_at: DateTime<Utc>,
     pub batch_id: Option<i64>,
+    /// Sender of the Ethereum transaction of the priority operation, if it's known.
+    pub l1_sender: Option<String>,
+}
+
+impl TransactionsHistoryItem {
+    /// Status of the operation, the pending priority operations are not committed yet.
+    pub fn status(&self) -> TxStatus {
+        if self.commited {
+            TxStatus::executed(self.success.unwrap_or(true), self.verified)
+        } else {
+            TxStatus::Queued
+        }
+    }
 }
 
 /// Stored information resulted from executing the transaction.
@@ -48,6 +63,29 @@ pub struct TxReceiptResponse {
     pub prover_run: Option<ProverRun>,
 }
 
+impl TxReceiptResponse {
+    pub fn new(
+        tx_hash: String,
+        block_number: i64,
+        status: TxStatus,
+        fail_reason: Option<String>,
+    ) -> Self {
+        Self {
+            tx_hash,
+            block_number,
+            success: status.success().unwrap_or_default(),
+            verified: status.is_verified(),
+            fail_reason,
+            prover_run: None,
+        }
+    }
+
+    /// Status of the executed transaction.
+    pub fn status(&self) -> TxStatus {
+        TxStatus::executed(self.success, self.verified)
+    }
+}
+
 /// Stored information resulted from executing the priority operation.
 /// Obtained from the operations schema.
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -57,6 +95,25 @@ pub struct PriorityOpReceiptResponse {
     pub prover_run: Option<ProverRun>,
 }
 
+impl PriorityOpReceiptResponse {
+    pub fn new(status: TxStatus) -> Self {
+        Self {
+            committed: status.is_committed(),
+            verified: status.is_verified(),
+            prover_run: None,
+        }
+    }
+
+    /// Status of the priority operation, it's queued until executed.
+    pub fn status(&self) -> TxStatus {
+        if self.committed {
+            TxStatus::executed(true, self.verified)
+        } else {
+            TxStatus::Queued
+        }
+    }
+}
+
 /// Stored executed operation (can be both L1 or L2)
 /// unified under a single interface for the explorer.
 #[derive(Debug, Serialize, Deserialize)]
@@ -161,8 +218,11 @@ pub struct StorageTxReceipt {
     pub priority_op_serialid: Option<i64>,
 }
 
-#[derive(Debug, FromRow, PartialEq)]
+/// Transaction data as stored in the database. Also serves as the body of the pruned
+/// transaction in the object storage archive.
+#[derive(Debug, Serialize, Deserialize, FromRow, PartialEq)]
 pub struct StorageTxData {
+    #[serde(with = "BytesToHexSerde::<ZeroxPrefix>")]
     pub tx_hash: Vec<u8>,
     pub block_number: Option<i64>,
     pub block_index: Option<i32>,
@@ -170,6 +230,7 @@ pub struct StorageTxData {
     pub success: Option<bool>,
     pub fail_reason: Option<String>,
     pub created_at: DateTime<Utc>,
+    #[serde(with = "OptionBytesToHexSerde::<ZeroxPrefix>")]
     pub eth_hash: Option<Vec<u8>>,
     pub priority_op_serialid: Option<i64>,
     pub batch_id: Option<i64>,
@@ -43,6 +43,7 @@ pub struct StateSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 impl<'a, 'c> StateSchema<'a, 'c> {
     /// Stores the list of updates to the account map in the database.
     /// At this step, the changes are not verified yet, and thus are not applied.
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn commit_state_update(
         &mut self,
         block_number: BlockNumber,
@@ -217,10 +218,11 @@ impl<'a, 'c> StateSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.state.commit_state_update", start.elapsed());
+        crate::slow_queries::report_query("chain.state", "commit_state_update", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn clear_current_nonce_table(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         sqlx::query!(
             "DELETE FROM committed_nonce WHERE block_number > $1",
@@ -347,6 +349,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
     ///
     /// This method is invoked from the `zksync_eth_sender` after corresponding `Verify` transaction
     /// is confirmed on Ethereum blockchain.
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn apply_state_update(&mut self, block_number: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         vlog::info!("Applying state update for block: {}", block_number);
@@ -437,7 +440,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.state.apply_state_update", start.elapsed());
+        crate::slow_queries::report_query("chain.state", "apply_state_update", start);
         Ok(())
     }
 
@@ -445,6 +448,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
     /// with a block number to which this state applies.
     /// If the provided block number is `None`, then the latest committed
     /// state will be loaded.
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn load_committed_state(
         &mut self,
         block: Option<BlockNumber>,
@@ -475,7 +479,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.state.load_committed_state", start.elapsed());
+        crate::slow_queries::report_query("chain.state", "load_committed_state", start);
         result
     }
 
@@ -483,6 +487,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
     /// to which this state applies.
     /// If the provided block number is `None`, then the latest committed
     /// state will be loaded.
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn load_verified_state(&mut self) -> QueryResult<(BlockNumber, AccountMap)> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -525,7 +530,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         }
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.state.load_verified_state", start.elapsed());
+        crate::slow_queries::report_query("chain.state", "load_verified_state", start);
         Ok((last_block, account_map))
     }
 
@@ -535,6 +540,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
     /// Returned block number is either `to_block`, latest committed block before `to_block`.
     /// If `to_block` is `None`, then it will be assumed to be the number of the latest committed
     /// block.
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn load_state_diff(
         &mut self,
         from_block: BlockNumber,
@@ -666,7 +672,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         };
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.state.load_state_diff", start.elapsed());
+        crate::slow_queries::report_query("chain.state", "load_state_diff", start);
 
         // We don't want to return an empty list to avoid the confusion, so return
         // `None` if there are no changes.
@@ -678,6 +684,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
     }
 
     /// Loads the state of accounts updated in a specific block.
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn load_state_diff_for_block(
         &mut self,
         block_number: BlockNumber,
@@ -688,10 +695,11 @@ impl<'a, 'c> StateSchema<'a, 'c> {
             .await
             .map(|diff| diff.unwrap_or_default().1);
 
-        metrics::histogram!("sql.chain.state.load_state_diff", start.elapsed());
+        crate::slow_queries::report_query("chain.state", "load_state_diff", start);
         result
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn get_mint_nft_update(&mut self, token_id: TokenId) -> QueryResult<Option<NFT>> {
         let start = Instant::now();
         let nft = sqlx::query_as!(
@@ -705,10 +713,11 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.get_mint_nft_update", start.elapsed());
+        crate::slow_queries::report_query("chain.state", "get_mint_nft_update", start);
         Ok(nft.map(|p| p.into()))
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn get_mint_nft_update_by_creator_and_nonce(
         &mut self,
         creator_address: Address,
@@ -727,13 +736,15 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.state.get_mint_nft_update_by_creator_and_nonce",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.state",
+            "get_mint_nft_update_by_creator_and_nonce",
+            start,
         );
         Ok(nft.map(|p| p.into()))
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn get_nft_id_by_tx_hash(&mut self, tx_hash: TxHash) -> QueryResult<Option<TokenId>> {
         let start = Instant::now();
         let record = sqlx::query!(
@@ -749,10 +760,11 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.state.get_nft_id_by_tx_hash", start.elapsed());
+        crate::slow_queries::report_query("chain.state", "get_nft_id_by_tx_hash", start);
         Ok(record.map(|r| TokenId(r.token_id as u32)))
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn load_committed_nft_tokens(
         &mut self,
         block_number: Option<BlockNumber>,
@@ -774,6 +786,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
     }
 
     // Removes account balance updates for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn remove_account_balance_updates(
         &mut self,
         last_block: BlockNumber,
@@ -786,14 +799,12 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.state.remove_account_balance_updates",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.state", "remove_account_balance_updates", start);
         Ok(())
     }
 
     // Removes account creates for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn remove_account_creates(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         sqlx::query!(
@@ -803,11 +814,12 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.state.remove_account_creates", start.elapsed());
+        crate::slow_queries::report_query("chain.state", "remove_account_creates", start);
         Ok(())
     }
 
     // Removes mint_nft_updates for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn remove_mint_nft_updates(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         sqlx::query!(
@@ -817,11 +829,12 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.state.remove_mint_nft_updates", start.elapsed());
+        crate::slow_queries::report_query("chain.state", "remove_mint_nft_updates", start);
         Ok(())
     }
 
     // Removes account pubkey updates for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn remove_account_pubkey_updates(
         &mut self,
         last_block: BlockNumber,
@@ -834,13 +847,11 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.state.remove_account_pubkey_updates",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.state", "remove_account_pubkey_updates", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "chain.state"))]
     pub async fn mint_nft_updates_set_nonces(&mut self) -> QueryResult<()> {
         let records = sqlx::query!(
             r#"
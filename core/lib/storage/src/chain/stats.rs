@@ -14,6 +14,7 @@ pub struct StatsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> StatsSchema<'a, 'c> {
     /// Returns the amount of blocks that don't have proofs yet.
+    #[tracing::instrument(skip_all, fields(schema = "chain.stats"))]
     pub async fn count_outstanding_proofs(&mut self, after_block: BlockNumber) -> QueryResult<u32> {
         let start = Instant::now();
         let count = sqlx::query!(
@@ -25,12 +26,13 @@ impl<'a, 'c> StatsSchema<'a, 'c> {
         .count
         .unwrap_or(0);
 
-        metrics::histogram!("sql.chain.stats.count_outstanding_proofs", start.elapsed());
+        crate::slow_queries::report_query("chain.stats", "count_outstanding_proofs", start);
         Ok(count as u32)
     }
 
     /// Count total transactions after tx_id, and return count and max tx_id.
     /// It allows us to cache count of transactions and make these queries much faster
+    #[tracing::instrument(skip_all, fields(schema = "chain.stats"))]
     pub async fn count_total_transactions(
         &mut self,
         after_tx_id: SequentialTxId,
@@ -52,7 +54,7 @@ impl<'a, 'c> StatsSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.stats.count_total_transactions", start.elapsed());
+        crate::slow_queries::report_query("chain.stats", "count_total_transactions", start);
         Ok((
             (tx_res.count.unwrap_or_default() + prior_ops_res.count.unwrap_or_default()) as u32,
             SequentialTxId(max(
@@ -16,6 +16,7 @@ pub struct TreeCacheSchemaBincode<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
     /// Stores account tree cache for a block.
     /// Expects `tree_cache` to be a byte sequence encoded according to the `bincode` protocol.
+    #[tracing::instrument(skip_all, fields(schema = "chain.tree_cache.bincode"))]
     pub async fn store_account_tree_cache(
         &mut self,
         block: BlockNumber,
@@ -39,15 +40,17 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.bincode.store_account_tree_cache",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.tree_cache.bincode",
+            "store_account_tree_cache",
+            start,
         );
         Ok(())
     }
 
     /// Gets the number of the latest block that has a stored cache.
     /// Returns `None` if there are no caches in the database.
+    #[tracing::instrument(skip_all, fields(schema = "chain.tree_cache.bincode"))]
     pub async fn get_last_block_with_account_tree_cache(
         &mut self,
     ) -> QueryResult<Option<BlockNumber>> {
@@ -66,9 +69,10 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
         .await?
         .max;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.bincode.get_last_block_with_account_tree_cache",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.tree_cache.bincode",
+            "get_last_block_with_account_tree_cache",
+            start,
         );
         Ok(last_block_with_cache.map(|block| BlockNumber(block as u32)))
     }
@@ -76,6 +80,7 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
     /// Gets the latest stored account tree cache encoded in binary.
     /// Returns `None` if there are no caches in the database or they are encoded in JSON.
     /// Returns the block number and associated cache otherwise.
+    #[tracing::instrument(skip_all, fields(schema = "chain.tree_cache.bincode"))]
     pub async fn get_account_tree_cache(&mut self) -> QueryResult<Option<(BlockNumber, Vec<u8>)>> {
         let start = Instant::now();
 
@@ -90,15 +95,17 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
         } else {
             None
         };
-        metrics::histogram!(
-            "sql.chain.tree_cache.bincode.get_account_tree_cache",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.tree_cache.bincode",
+            "get_account_tree_cache",
+            start,
         );
         Ok(account_tree_cache)
     }
 
     /// Gets stored account tree cache for a certain block.
     /// Returns `None` if there is no cache for requested block or it's encoded in JSON.
+    #[tracing::instrument(skip_all, fields(schema = "chain.tree_cache.bincode"))]
     pub async fn get_account_tree_cache_block(
         &mut self,
         block: BlockNumber,
@@ -133,9 +140,10 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.bincode.get_account_tree_cache_block",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.tree_cache.bincode",
+            "get_account_tree_cache_block",
+            start,
         );
         Ok(account_tree_cache.map(|w| {
             w.tree_cache_binary
@@ -144,6 +152,7 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
     }
 
     // Removes account tree cache for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.tree_cache.bincode"))]
     pub async fn remove_new_account_tree_cache(
         &mut self,
         last_block: BlockNumber,
@@ -156,14 +165,16 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.bincode.remove_new_account_tree_cache",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.tree_cache.bincode",
+            "remove_new_account_tree_cache",
+            start,
         );
         Ok(())
     }
 
     // Removes account tree cache for blocks with number less than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.tree_cache.bincode"))]
     pub async fn remove_old_account_tree_cache(
         &mut self,
         last_block: BlockNumber,
@@ -193,9 +204,10 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
             }
         }
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.bincode.remove_old_account_tree_cache",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.tree_cache.bincode",
+            "remove_old_account_tree_cache",
+            start,
         );
         Ok(())
     }
@@ -16,6 +16,7 @@ pub struct TreeCacheSchemaJSON<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
     /// Stores account tree cache for a block.
     /// Expects `tree_cache` to be a valid encoded JSON.
+    #[tracing::instrument(skip_all, fields(schema = "chain.tree_cache.json"))]
     pub async fn store_account_tree_cache(
         &mut self,
         block: BlockNumber,
@@ -39,15 +40,17 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.json.store_account_tree_cache",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.tree_cache.json",
+            "store_account_tree_cache",
+            start,
         );
         Ok(())
     }
 
     /// Gets the number of the latest block that has a stored cache.
     /// Returns `None` if there are no caches in the database.
+    #[tracing::instrument(skip_all, fields(schema = "chain.tree_cache.json"))]
     pub async fn get_last_block_with_account_tree_cache(
         &mut self,
     ) -> QueryResult<Option<BlockNumber>> {
@@ -59,9 +62,10 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
                 .await?
                 .max;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.json.get_last_block_with_account_tree_cache",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.tree_cache.json",
+            "get_last_block_with_account_tree_cache",
+            start,
         );
         Ok(last_block_with_cache.map(|block| BlockNumber(block as u32)))
     }
@@ -69,6 +73,7 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
     /// Gets the latest stored account tree cache encoded in JSON.
     /// Returns `None` if there are no caches in the database or only existing caches are binary.
     /// Returns the block number and associated cache otherwise.
+    #[tracing::instrument(skip_all, fields(schema = "chain.tree_cache.json"))]
     pub async fn get_account_tree_cache(
         &mut self,
     ) -> QueryResult<Option<(BlockNumber, serde_json::Value)>> {
@@ -85,10 +90,7 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.json.get_account_tree_cache",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("chain.tree_cache.json", "get_account_tree_cache", start);
         Ok(account_tree_cache.map(|w| {
             assert!(
                 w.tree_cache.is_some(),
@@ -109,6 +111,7 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
 
     /// Gets stored account tree cache for a certain block.
     /// Returns `None` if there is no cache for requested block or it's encoded in binary.
+    #[tracing::instrument(skip_all, fields(schema = "chain.tree_cache.json"))]
     pub async fn get_account_tree_cache_block(
         &mut self,
         block: BlockNumber,
@@ -125,9 +128,10 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.json.get_account_tree_cache_block",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.tree_cache.json",
+            "get_account_tree_cache_block",
+            start,
         );
         Ok(account_tree_cache.map(|w| {
             serde_json::from_str(
@@ -139,6 +143,7 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
     }
 
     // Removes account tree cache for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.tree_cache.json"))]
     pub async fn remove_new_account_tree_cache(
         &mut self,
         last_block: BlockNumber,
@@ -151,14 +156,16 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.json.remove_new_account_tree_cache",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.tree_cache.json",
+            "remove_new_account_tree_cache",
+            start,
         );
         Ok(())
     }
 
     // Removes account tree cache for blocks with number less than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "chain.tree_cache.json"))]
     pub async fn remove_old_account_tree_cache(
         &mut self,
         last_block: BlockNumber,
@@ -171,9 +178,10 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.json.remove_old_account_tree_cache",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "chain.tree_cache.json",
+            "remove_old_account_tree_cache",
+            start,
         );
         Ok(())
     }
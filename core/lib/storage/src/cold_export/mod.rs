@@ -0,0 +1,354 @@
+// Built-in deps
+use std::{
+    path::{Path, PathBuf},
+    time::Instant,
+};
+// External imports
+// Workspace imports
+use zksync_types::BlockNumber;
+// Local imports
+use self::{
+    records::{ExportedBalanceUpdate, ExportedPriorityOperation, ExportedTransaction},
+    writer::{write_parquet_file, ColumnValues},
+};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+mod writer;
+
+/// Schema of the cold-storage export of the block history.
+///
+/// Executed transactions, priority operations and balance updates of a block range are written
+/// to the Parquet files, so the history can be queried by external analytical engines without
+/// hitting the production database. Files are partitioned by the block ranges using Hive-style
+/// directories: `<output>/<table>/blocks=<from>-<to>/data.parquet`.
+#[derive(Debug)]
+pub struct ColdExportSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ColdExportSchema<'a, 'c> {
+    /// Exports the blocks from `from_block` to `to_block` inclusively, splitting them into
+    /// partitions of `partition_size` blocks. Returns the paths of the written files.
+    #[tracing::instrument(skip_all, fields(schema = "cold_export"))]
+    pub async fn export_block_range(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        partition_size: u32,
+        output_dir: &Path,
+    ) -> QueryResult<Vec<PathBuf>> {
+        assert!(partition_size > 0, "Partition size must be positive");
+        let start = Instant::now();
+        let mut files = Vec::new();
+
+        let mut partition_start = *from_block;
+        while partition_start <= *to_block {
+            let partition_end = partition_start
+                .saturating_add(partition_size - 1)
+                .min(*to_block);
+            let (from, to) = (BlockNumber(partition_start), BlockNumber(partition_end));
+            let partition = format!("blocks={}-{}", partition_start, partition_end);
+
+            let transactions = self.load_executed_transactions(from, to).await?;
+            let path = partition_path(output_dir, "executed_transactions", &partition)?;
+            write_parquet_file(
+                &path,
+                "executed_transactions",
+                transactions_columns(transactions),
+            )?;
+            files.push(path);
+
+            let priority_ops = self.load_executed_priority_operations(from, to).await?;
+            let path = partition_path(output_dir, "executed_priority_operations", &partition)?;
+            write_parquet_file(
+                &path,
+                "executed_priority_operations",
+                priority_operations_columns(priority_ops),
+            )?;
+            files.push(path);
+
+            let balance_updates = self.load_balance_updates(from, to).await?;
+            let path = partition_path(output_dir, "account_balance_updates", &partition)?;
+            write_parquet_file(
+                &path,
+                "account_balance_updates",
+                balance_updates_columns(balance_updates),
+            )?;
+            files.push(path);
+
+            partition_start = match partition_end.checked_add(1) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        crate::slow_queries::report_query("cold_export", "export_block_range", start);
+        Ok(files)
+    }
+
+    /// Loads the executed transactions of the blocks range, ordered by their position in the blocks.
+    #[tracing::instrument(skip_all, fields(schema = "cold_export"))]
+    pub async fn load_executed_transactions(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<ExportedTransaction>> {
+        let start = Instant::now();
+        let transactions = sqlx::query_as!(
+            ExportedTransaction,
+            "SELECT
+                block_number, block_index, tx_hash, tx->>'type' as tx_type, from_account,
+                to_account, success, fail_reason, nonce, batch_id, created_at, tx
+            FROM executed_transactions
+            WHERE block_number BETWEEN $1 AND $2
+            ORDER BY block_number, block_index",
+            i64::from(*from_block),
+            i64::from(*to_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("cold_export", "load_executed_transactions", start);
+        Ok(transactions)
+    }
+
+    /// Loads the executed priority operations of the blocks range, ordered by their position in the blocks.
+    #[tracing::instrument(skip_all, fields(schema = "cold_export"))]
+    pub async fn load_executed_priority_operations(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<ExportedPriorityOperation>> {
+        let start = Instant::now();
+        let operations = sqlx::query_as!(
+            ExportedPriorityOperation,
+            "SELECT
+                block_number, block_index, tx_hash, operation->>'type' as op_type, from_account,
+                to_account, priority_op_serialid, eth_hash, eth_block, created_at, operation
+            FROM executed_priority_operations
+            WHERE block_number BETWEEN $1 AND $2
+            ORDER BY block_number, block_index",
+            i64::from(*from_block),
+            i64::from(*to_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query(
+            "cold_export",
+            "load_executed_priority_operations",
+            start,
+        );
+        Ok(operations)
+    }
+
+    /// Loads the account balance updates of the blocks range in the order they were applied.
+    #[tracing::instrument(skip_all, fields(schema = "cold_export"))]
+    pub async fn load_balance_updates(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<ExportedBalanceUpdate>> {
+        let start = Instant::now();
+        let updates = sqlx::query_as!(
+            ExportedBalanceUpdate,
+            "SELECT
+                block_number, update_order_id, account_id, coin_id,
+                old_balance, new_balance, old_nonce, new_nonce
+            FROM account_balance_updates
+            WHERE block_number BETWEEN $1 AND $2
+            ORDER BY block_number, update_order_id",
+            i64::from(*from_block),
+            i64::from(*to_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("cold_export", "load_balance_updates", start);
+        Ok(updates)
+    }
+}
+
+/// Creates the partition directory of the table and returns the path of the file in it.
+fn partition_path(output_dir: &Path, table_name: &str, partition: &str) -> QueryResult<PathBuf> {
+    let dir = output_dir.join(table_name).join(partition);
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir.join("data.parquet"))
+}
+
+fn transactions_columns(rows: Vec<ExportedTransaction>) -> Vec<(&'static str, ColumnValues)> {
+    vec![
+        (
+            "block_number",
+            ColumnValues::Int64(rows.iter().map(|row| Some(row.block_number)).collect()),
+        ),
+        (
+            "block_index",
+            ColumnValues::Int32(rows.iter().map(|row| row.block_index).collect()),
+        ),
+        (
+            "tx_hash",
+            ColumnValues::Bytes(rows.iter().map(|row| Some(row.tx_hash.clone())).collect()),
+        ),
+        (
+            "tx_type",
+            ColumnValues::Utf8(rows.iter().map(|row| row.tx_type.clone()).collect()),
+        ),
+        (
+            "from_account",
+            ColumnValues::Bytes(
+                rows.iter()
+                    .map(|row| Some(row.from_account.clone()))
+                    .collect(),
+            ),
+        ),
+        (
+            "to_account",
+            ColumnValues::Bytes(rows.iter().map(|row| row.to_account.clone()).collect()),
+        ),
+        (
+            "success",
+            ColumnValues::Boolean(rows.iter().map(|row| Some(row.success)).collect()),
+        ),
+        (
+            "fail_reason",
+            ColumnValues::Utf8(rows.iter().map(|row| row.fail_reason.clone()).collect()),
+        ),
+        (
+            "nonce",
+            ColumnValues::Int64(rows.iter().map(|row| Some(row.nonce)).collect()),
+        ),
+        (
+            "batch_id",
+            ColumnValues::Int64(rows.iter().map(|row| row.batch_id).collect()),
+        ),
+        (
+            "created_at",
+            ColumnValues::Timestamp(
+                rows.iter()
+                    .map(|row| Some(row.created_at.timestamp_millis()))
+                    .collect(),
+            ),
+        ),
+        (
+            "tx",
+            ColumnValues::Utf8(rows.iter().map(|row| Some(row.tx.to_string())).collect()),
+        ),
+    ]
+}
+
+fn priority_operations_columns(
+    rows: Vec<ExportedPriorityOperation>,
+) -> Vec<(&'static str, ColumnValues)> {
+    vec![
+        (
+            "block_number",
+            ColumnValues::Int64(rows.iter().map(|row| Some(row.block_number)).collect()),
+        ),
+        (
+            "block_index",
+            ColumnValues::Int32(rows.iter().map(|row| Some(row.block_index)).collect()),
+        ),
+        (
+            "tx_hash",
+            ColumnValues::Bytes(rows.iter().map(|row| Some(row.tx_hash.clone())).collect()),
+        ),
+        (
+            "op_type",
+            ColumnValues::Utf8(rows.iter().map(|row| row.op_type.clone()).collect()),
+        ),
+        (
+            "from_account",
+            ColumnValues::Bytes(
+                rows.iter()
+                    .map(|row| Some(row.from_account.clone()))
+                    .collect(),
+            ),
+        ),
+        (
+            "to_account",
+            ColumnValues::Bytes(
+                rows.iter()
+                    .map(|row| Some(row.to_account.clone()))
+                    .collect(),
+            ),
+        ),
+        (
+            "serial_id",
+            ColumnValues::Int64(
+                rows.iter()
+                    .map(|row| Some(row.priority_op_serialid))
+                    .collect(),
+            ),
+        ),
+        (
+            "eth_hash",
+            ColumnValues::Bytes(rows.iter().map(|row| Some(row.eth_hash.clone())).collect()),
+        ),
+        (
+            "eth_block",
+            ColumnValues::Int64(rows.iter().map(|row| Some(row.eth_block)).collect()),
+        ),
+        (
+            "created_at",
+            ColumnValues::Timestamp(
+                rows.iter()
+                    .map(|row| Some(row.created_at.timestamp_millis()))
+                    .collect(),
+            ),
+        ),
+        (
+            "operation",
+            ColumnValues::Utf8(
+                rows.iter()
+                    .map(|row| Some(row.operation.to_string()))
+                    .collect(),
+            ),
+        ),
+    ]
+}
+
+fn balance_updates_columns(rows: Vec<ExportedBalanceUpdate>) -> Vec<(&'static str, ColumnValues)> {
+    vec![
+        (
+            "block_number",
+            ColumnValues::Int64(rows.iter().map(|row| Some(row.block_number)).collect()),
+        ),
+        (
+            "update_order_id",
+            ColumnValues::Int32(rows.iter().map(|row| Some(row.update_order_id)).collect()),
+        ),
+        (
+            "account_id",
+            ColumnValues::Int64(rows.iter().map(|row| Some(row.account_id)).collect()),
+        ),
+        (
+            "token_id",
+            ColumnValues::Int32(rows.iter().map(|row| Some(row.coin_id)).collect()),
+        ),
+        // Balances don't fit into the integer types, so they're stored as decimal strings.
+        (
+            "old_balance",
+            ColumnValues::Utf8(
+                rows.iter()
+                    .map(|row| Some(row.old_balance.to_string()))
+                    .collect(),
+            ),
+        ),
+        (
+            "new_balance",
+            ColumnValues::Utf8(
+                rows.iter()
+                    .map(|row| Some(row.new_balance.to_string()))
+                    .collect(),
+            ),
+        ),
+        (
+            "old_nonce",
+            ColumnValues::Int64(rows.iter().map(|row| Some(row.old_nonce)).collect()),
+        ),
+        (
+            "new_nonce",
+            ColumnValues::Int64(rows.iter().map(|row| Some(row.new_nonce)).collect()),
+        ),
+    ]
+}
@@ -0,0 +1,48 @@
+// External imports
+use chrono::prelude::*;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ExportedTransaction {
+    pub block_number: i64,
+    pub block_index: Option<i32>,
+    pub tx_hash: Vec<u8>,
+    pub tx_type: Option<String>,
+    pub from_account: Vec<u8>,
+    pub to_account: Option<Vec<u8>>,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+    pub nonce: i64,
+    pub batch_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub tx: serde_json::Value,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ExportedPriorityOperation {
+    pub block_number: i64,
+    pub block_index: i32,
+    pub tx_hash: Vec<u8>,
+    pub op_type: Option<String>,
+    pub from_account: Vec<u8>,
+    pub to_account: Vec<u8>,
+    pub priority_op_serialid: i64,
+    pub eth_hash: Vec<u8>,
+    pub eth_block: i64,
+    pub created_at: DateTime<Utc>,
+    pub operation: serde_json::Value,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ExportedBalanceUpdate {
+    pub block_number: i64,
+    pub update_order_id: i32,
+    pub account_id: i64,
+    pub coin_id: i32,
+    pub old_balance: BigDecimal,
+    pub new_balance: BigDecimal,
+    pub old_nonce: i64,
+    pub new_nonce: i64,
+}
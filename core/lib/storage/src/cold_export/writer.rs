@@ -0,0 +1,123 @@
+//! Minimal columnar writer of the exported rows to the Parquet files.
+
+// Built-in deps
+use std::{fs::File, path::Path, sync::Arc};
+// External imports
+use parquet::{
+    basic::Compression,
+    column::writer::ColumnWriter,
+    data_type::ByteArray,
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::parser::parse_message_type,
+};
+// Workspace imports
+// Local imports
+use crate::QueryResult;
+
+/// Values of a single column, all the columns are optional in the written schema.
+#[derive(Debug)]
+pub(super) enum ColumnValues {
+    Boolean(Vec<Option<bool>>),
+    Int32(Vec<Option<i32>>),
+    Int64(Vec<Option<i64>>),
+    /// Milliseconds since the Unix epoch.
+    Timestamp(Vec<Option<i64>>),
+    Bytes(Vec<Option<Vec<u8>>>),
+    Utf8(Vec<Option<String>>),
+}
+
+impl ColumnValues {
+    fn schema_type(&self) -> &'static str {
+        match self {
+            Self::Boolean(_) => "BOOLEAN",
+            Self::Int32(_) => "INT32",
+            Self::Int64(_) => "INT64",
+            Self::Timestamp(_) => "INT64",
+            Self::Bytes(_) => "BINARY",
+            Self::Utf8(_) => "BINARY",
+        }
+    }
+
+    fn logical_type(&self) -> Option<&'static str> {
+        match self {
+            Self::Timestamp(_) => Some("TIMESTAMP_MILLIS"),
+            Self::Utf8(_) => Some("UTF8"),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the non-null values along with the definition levels of all the values.
+fn split_nulls<T: Clone, U>(values: &[Option<T>], convert: impl Fn(T) -> U) -> (Vec<U>, Vec<i16>) {
+    let definition_levels = values
+        .iter()
+        .map(|value| if value.is_some() { 1 } else { 0 })
+        .collect();
+    let values = values.iter().flatten().cloned().map(convert).collect();
+    (values, definition_levels)
+}
+
+/// Writes the columns to the Parquet file as a single row group.
+pub(super) fn write_parquet_file(
+    path: &Path,
+    table_name: &str,
+    columns: Vec<(&'static str, ColumnValues)>,
+) -> QueryResult<()> {
+    let fields: Vec<_> = columns
+        .iter()
+        .map(|(name, values)| match values.logical_type() {
+            Some(logical_type) => format!(
+                "OPTIONAL {} {} ({});",
+                values.schema_type(),
+                name,
+                logical_type
+            ),
+            None => format!("OPTIONAL {} {};", values.schema_type(), name),
+        })
+        .collect();
+    let schema = parse_message_type(&format!(
+        "message {} {{ {} }}",
+        table_name,
+        fields.join(" ")
+    ))?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+
+    let mut writer =
+        SerializedFileWriter::new(File::create(path)?, Arc::new(schema), Arc::new(properties))?;
+    let mut row_group = writer.next_row_group()?;
+    for (_, values) in columns {
+        let mut column_writer = row_group.next_column()?.expect("columns match the schema");
+        match (column_writer.untyped(), values) {
+            (ColumnWriter::BoolColumnWriter(typed), ColumnValues::Boolean(values)) => {
+                let (values, levels) = split_nulls(&values, |value| value);
+                typed.write_batch(&values, Some(&levels), None)?;
+            }
+            (ColumnWriter::Int32ColumnWriter(typed), ColumnValues::Int32(values)) => {
+                let (values, levels) = split_nulls(&values, |value| value);
+                typed.write_batch(&values, Some(&levels), None)?;
+            }
+            (ColumnWriter::Int64ColumnWriter(typed), ColumnValues::Int64(values))
+            | (ColumnWriter::Int64ColumnWriter(typed), ColumnValues::Timestamp(values)) => {
+                let (values, levels) = split_nulls(&values, |value| value);
+                typed.write_batch(&values, Some(&levels), None)?;
+            }
+            (ColumnWriter::ByteArrayColumnWriter(typed), ColumnValues::Bytes(values)) => {
+                let (values, levels) = split_nulls(&values, ByteArray::from);
+                typed.write_batch(&values, Some(&levels), None)?;
+            }
+            (ColumnWriter::ByteArrayColumnWriter(typed), ColumnValues::Utf8(values)) => {
+                let (values, levels) =
+                    split_nulls(&values, |value: String| ByteArray::from(value.into_bytes()));
+                typed.write_batch(&values, Some(&levels), None)?;
+            }
+            _ => unreachable!("column writer type matches the schema"),
+        }
+        column_writer.close()?;
+    }
+    row_group.close()?;
+    writer.close()?;
+
+    Ok(())
+}
@@ -19,19 +19,21 @@ pub struct ConfigSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> ConfigSchema<'a, 'c> {
     /// Loads the server configuration.
+    #[tracing::instrument(skip_all, fields(schema = "config"))]
     pub async fn load_config(&mut self) -> QueryResult<ServerConfig> {
         let start = Instant::now();
         let config = sqlx::query_as!(ServerConfig, "SELECT * FROM server_config")
             .fetch_one(self.0.conn())
             .await?;
 
-        metrics::histogram!("sql.load_config", start.elapsed());
+        crate::slow_queries::report_query("config", "load_config", start);
         Ok(config)
     }
 
     // Stores the server configuration for tests.
     #[doc(hidden)]
     #[allow(dead_code)]
+    #[tracing::instrument(skip_all, fields(schema = "config"))]
     pub async fn store_config(
         &mut self,
         contract_addr: Address,
@@ -49,7 +51,7 @@ impl<'a, 'c> ConfigSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.store_config", start.elapsed());
+        crate::slow_queries::report_query("config", "store_config", start);
         Ok(())
     }
 }
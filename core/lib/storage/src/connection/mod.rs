@@ -1,14 +1,30 @@
 // Built-in deps
-use std::{fmt, time::Duration, time::Instant};
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+    time::Instant,
+};
 // External imports
 use async_trait::async_trait;
 use deadpool::managed::{Manager, PoolConfig, RecycleResult, Timeouts};
 use deadpool::Runtime;
-use sqlx::{Connection, Error as SqlxError, PgConnection};
+use once_cell::sync::Lazy;
+use sqlx::{
+    postgres::PgConnectOptions, ConnectOptions, Connection, Error as SqlxError, PgConnection,
+};
 use tokio::time;
 // Local imports
 // use self::recoverable_connection::RecoverableConnection;
-use crate::{get_database_replica_url, get_database_url, StorageProcessor};
+use crate::{
+    get_database_history_url, get_database_replica_urls, get_database_url,
+    slow_queries::slow_query_threshold, QueryResult, StorageProcessor,
+};
 use zksync_utils::parse_env;
 
 pub mod holder;
@@ -19,31 +35,134 @@ pub type PooledConnection = deadpool::managed::Object<DbPool>;
 
 pub const DB_CONNECTION_RETRIES: u32 = 3;
 
+/// Statement timeout used if the `DATABASE_API_STATEMENT_TIMEOUT` variable is not set.
+const DEFAULT_API_STATEMENT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Time given to the returned connection to finish its statement before it gets cancelled.
+const RECYCLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+static API_STATEMENT_TIMEOUT: Lazy<Option<Duration>> = Lazy::new(|| {
+    let millis = std::env::var("DATABASE_API_STATEMENT_TIMEOUT")
+        .map(|timeout| {
+            timeout
+                .parse()
+                .expect("DATABASE_API_STATEMENT_TIMEOUT must be a number of milliseconds")
+        })
+        .unwrap_or(DEFAULT_API_STATEMENT_TIMEOUT.as_millis() as u64);
+    // Zero disables the timeout.
+    Some(millis)
+        .filter(|&millis| millis > 0)
+        .map(Duration::from_millis)
+});
+
+/// Number of connections to the history database, which is only used for the rare requests
+/// of the old transactions and for moving the transactions there.
+const HISTORY_POOL_SIZE: usize = 5;
+
+/// Pool of the connections to the history database, `None` if the history sharding is not configured.
+static HISTORY_POOL: Lazy<Option<Pool>> = Lazy::new(|| {
+    get_database_history_url()
+        .map(|url| DbPool::create(url, HISTORY_POOL_SIZE, api_statement_timeout()))
+});
+
+/// Returns the maximum execution time of the statements sent via the read-only pool
+/// serving the API requests, `None` if the statements are not limited.
+pub fn api_statement_timeout() -> Option<Duration> {
+    *API_STATEMENT_TIMEOUT
+}
+
+/// Connection to the database along with the ID of the backend process serving it,
+/// which is required to cancel the statement executed by the connection.
+pub struct DbConnection {
+    conn: PgConnection,
+    backend_pid: i32,
+}
+
+impl Deref for DbConnection {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        &self.conn
+    }
+}
+
+impl DerefMut for DbConnection {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        &mut self.conn
+    }
+}
+
 #[derive(Clone)]
 pub struct DbPool {
     url: String,
+    /// Value of the `statement_timeout` set for every connection of the pool.
+    statement_timeout: Option<Duration>,
 }
 
 impl DbPool {
-    fn create(url: impl Into<String>, max_size: usize) -> Pool {
+    fn create(
+        url: impl Into<String>,
+        max_size: usize,
+        statement_timeout: Option<Duration>,
+    ) -> Pool {
         let pool_config = PoolConfig {
             max_size,
             timeouts: Timeouts::wait_millis(20_000), // wait 20 seconds before returning error
             runtime: Runtime::Tokio1,
         };
-        Pool::from_config(DbPool { url: url.into() }, pool_config)
+        let manager = DbPool {
+            url: url.into(),
+            statement_timeout,
+        };
+        Pool::from_config(manager, pool_config)
+    }
+
+    /// Cancels the statement executed by the backend process using a separate connection.
+    async fn cancel_statement(&self, backend_pid: i32) -> Result<(), SqlxError> {
+        let mut conn = PgConnectOptions::from_str(&self.url)?.connect().await?;
+        sqlx::query("SELECT pg_cancel_backend($1)")
+            .bind(backend_pid)
+            .execute(&mut conn)
+            .await?;
+        metrics::increment_counter!("sql.cancelled_statements");
+        Ok(())
     }
 }
 
 #[async_trait]
 impl Manager for DbPool {
-    type Type = PgConnection;
+    type Type = DbConnection;
     type Error = SqlxError;
-    async fn create(&self) -> Result<PgConnection, SqlxError> {
-        PgConnection::connect(&self.url).await
+    async fn create(&self) -> Result<DbConnection, SqlxError> {
+        let mut options = PgConnectOptions::from_str(&self.url)?;
+        // Statements are logged within the span of the schema method executing them,
+        // which makes it possible to match the SQL text with the method.
+        options.log_slow_statements(log::LevelFilter::Warn, slow_query_threshold());
+        let mut conn = options.connect().await?;
+
+        if let Some(timeout) = self.statement_timeout {
+            sqlx::query(&format!("SET statement_timeout = {}", timeout.as_millis()))
+                .execute(&mut conn)
+                .await?;
+        }
+        let (backend_pid,): (i32,) = sqlx::query_as("SELECT pg_backend_pid()")
+            .fetch_one(&mut conn)
+            .await?;
+
+        Ok(DbConnection { conn, backend_pid })
     }
-    async fn recycle(&self, obj: &mut PgConnection) -> RecycleResult<SqlxError> {
-        Ok(obj.ping().await?)
+    async fn recycle(&self, obj: &mut DbConnection) -> RecycleResult<SqlxError> {
+        // If the future using the connection was dropped, e.g. because the HTTP client
+        // has disconnected, the connection may still be busy with the unfinished statement.
+        // Such a statement is cancelled instead of pinning the connection until it completes.
+        if let Ok(result) = time::timeout(RECYCLE_TIMEOUT, obj.conn.ping()).await {
+            return Ok(result?);
+        }
+        vlog::warn!(
+            "Connection is busy with the abandoned statement, cancelling it (backend pid {})",
+            obj.backend_pid
+        );
+        self.cancel_statement(obj.backend_pid).await?;
+        Ok(obj.conn.ping().await?)
     }
 }
 
@@ -52,9 +171,20 @@ impl Manager for DbPool {
 ///
 /// The size of the pool and the database URL are configured via environment
 /// variables `DATABASE_POOL_SIZE` and `DATABASE_URL` respectively.
+///
+/// The read-only pool routes the connections to the set of the database read replicas
+/// configured via `DATABASE_REPLICA_URLS`. Replicas are used in the round-robin order,
+/// and if none of them is available, the connection to the main database is used instead.
 #[derive(Clone)]
 pub struct ConnectionPool {
+    /// Pool of the connections to the main database.
     pool: Pool,
+    /// Name of the pool used as the metrics label: `master`, `replica` or the name of the workload.
+    name: &'static str,
+    /// Pools of the connections to the read replicas, empty for the main pool.
+    replicas: Arc<Vec<Pool>>,
+    /// Index of the replica to acquire the next connection from.
+    next_replica: Arc<AtomicUsize>,
 }
 
 impl fmt::Debug for ConnectionPool {
@@ -68,26 +198,54 @@ impl ConnectionPool {
     /// creates a new `ConnectionPool` object.
     /// pool_max_size - number of connections in pool, if not set env variable "DATABASE_POOL_SIZE" is going to be used.
     pub fn new(pool_max_size: Option<u32>) -> Self {
-        let database_url = get_database_url();
         let max_size = pool_max_size.unwrap_or_else(|| parse_env("DATABASE_POOL_SIZE"));
+        Self::new_partition("master", max_size)
+    }
+
+    /// Establishes a dedicated pool of the connections to the database for a single workload,
+    /// e.g. the core or the background jobs, so the workloads don't compete for the connections.
+    /// name - name of the workload used as the metrics label,
+    /// pool_max_size - number of connections in pool.
+    pub fn new_partition(name: &'static str, pool_max_size: u32) -> Self {
+        let database_url = get_database_url();
 
-        let pool = DbPool::create(database_url, max_size as usize);
+        // Statements of the write path are not limited.
+        let pool = DbPool::create(database_url, pool_max_size as usize, None);
 
-        Self { pool }
+        Self {
+            pool,
+            name,
+            replicas: Arc::new(Vec::new()),
+            next_replica: Arc::new(AtomicUsize::new(0)),
+        }
     }
 
-    /// Establishes a pool of the connections to the replica of database and
-    /// creates a new `ConnectionPool` object.
-    /// pool_max_size - number of connections in pool,
+    /// Establishes pools of the connections to the replicas of database and
+    /// creates a new `ConnectionPool` object. Connections to the main database
+    /// are only established if none of the replicas is available.
+    /// pool_max_size - number of connections in each pool,
     /// if not set env variable "DATABASE_POOL_SIZE" is going to be used.
+    ///
+    /// The pool is meant for the API requests, so its statements are limited by
+    /// the `DATABASE_API_STATEMENT_TIMEOUT` (see [`api_statement_timeout`]).
     pub fn new_readonly_pool(pool_max_size: Option<u32>) -> Self {
-        let database_url = get_database_replica_url();
         let max_size = pool_max_size.unwrap_or_else(|| parse_env("DATABASE_POOL_SIZE"));
+        let statement_timeout = api_statement_timeout();
 
-        let pool = DbPool::create(database_url, max_size as usize);
+        let replicas = get_database_replica_urls()
+            .into_iter()
+            .map(|url| DbPool::create(url, max_size as usize, statement_timeout))
+            .collect();
+        let pool = DbPool::create(get_database_url(), max_size as usize, statement_timeout);
 
-        Self { pool }
+        Self {
+            pool,
+            name: "replica",
+            replicas: Arc::new(replicas),
+            next_replica: Arc::new(AtomicUsize::new(0)),
+        }
     }
+
     /// Creates a `StorageProcessor` entity over a recoverable connection.
     /// Upon a database outage connection will block the thread until
     /// it will be able to recover the connection (or, if connection cannot
@@ -99,12 +257,51 @@ impl ConnectionPool {
     pub async fn access_storage(&self) -> Result<StorageProcessor<'_>, SqlxError> {
         let start = Instant::now();
         let connection = self.get_pooled_connection().await;
-        metrics::histogram!("sql.connection_acquire", start.elapsed());
+        metrics::histogram!("sql.connection_acquire", start.elapsed(), "pool" => self.name);
+        self.report_pool_status();
 
         Ok(StorageProcessor::from_pool(connection))
     }
 
+    /// Reports the amount of the open connections and the ones available for acquiring.
+    /// Negative amount of the available connections means that there are tasks waiting for one.
+    fn report_pool_status(&self) {
+        report_status(&self.pool, self.name, "none".to_string());
+        for (index, replica) in self.replicas.iter().enumerate() {
+            report_status(replica, self.name, index.to_string());
+        }
+    }
+
+    /// Acquires the connection from the next available replica in the round-robin order.
+    /// Returns `None` if there are no replicas or none of them is available.
+    async fn get_replica_connection(&self) -> Option<PooledConnection> {
+        let replicas_count = self.replicas.len();
+        if replicas_count == 0 {
+            return None;
+        }
+
+        let first = self.next_replica.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..replicas_count {
+            let index = (first + offset) % replicas_count;
+            match self.replicas[index].get().await {
+                Ok(connection) => return Some(connection),
+                Err(err) => {
+                    vlog::warn!("Failed to get connection to the replica {}: {}", index, err);
+                    metrics::increment_counter!("sql.replica.errors", "replica" => index.to_string());
+                }
+            }
+        }
+
+        vlog::warn!("None of the replicas is available, falling back to the main database");
+        metrics::increment_counter!("sql.replica.fallback");
+        None
+    }
+
     async fn get_pooled_connection(&self) -> PooledConnection {
+        if let Some(connection) = self.get_replica_connection().await {
+            return connection;
+        }
+
         let mut retry_count = 0;
 
         let mut one_second = time::interval(Duration::from_secs(1));
@@ -126,3 +323,34 @@ impl ConnectionPool {
         self.pool.get().await.unwrap()
     }
 }
+
+/// Returns `true` if the old executed transactions are moved to the separate history database.
+pub fn history_sharding_enabled() -> bool {
+    HISTORY_POOL.is_some()
+}
+
+/// Creates a `StorageProcessor` over the connection to the history database,
+/// or returns `None` if the history sharding is not configured.
+///
+/// The history database has the same schema as the main one, but only its
+/// `executed_transactions` table is populated.
+pub async fn access_history_storage() -> QueryResult<Option<StorageProcessor<'static>>> {
+    let pool = match HISTORY_POOL.as_ref() {
+        Some(pool) => pool,
+        None => return Ok(None),
+    };
+    let start = Instant::now();
+    let connection = pool.get().await?;
+    metrics::histogram!("sql.connection_acquire", start.elapsed(), "pool" => "history");
+    report_status(pool, "history", "none".to_string());
+
+    Ok(Some(StorageProcessor::from_pool(connection)))
+}
+
+/// Reports the amount of the open connections and the ones available for acquiring in the pool.
+fn report_status(pool: &Pool, name: &'static str, replica: String) {
+    let status = pool.status();
+    metrics::gauge!("sql.pool.max_size", status.max_size as f64, "pool" => name, "replica" => replica.clone());
+    metrics::gauge!("sql.pool.size", status.size as f64, "pool" => name, "replica" => replica.clone());
+    metrics::gauge!("sql.pool.available", status.available as f64, "pool" => name, "replica" => replica);
+}
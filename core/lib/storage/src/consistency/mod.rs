@@ -0,0 +1,271 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{aggregated_operations::AggregatedActionType, BlockNumber};
+// Local imports
+use self::records::{StoredBalanceMismatch, StoredUnfilteredTx};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Cross-table invariants checked by the consistency schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Invariant {
+    /// Every executed transaction and priority operation has the `tx_filters` rows,
+    /// otherwise it's missing from the account history.
+    TxFilters,
+    /// Every block up to the last verified one is covered by a confirmed `ExecuteBlocks` operation.
+    ExecutedBlocks,
+    /// Verified balances of the accounts match the last balance updates of the verified blocks.
+    Balances,
+}
+
+impl Invariant {
+    /// Name of the invariant used in the metrics and the reports.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::TxFilters => "tx_filters",
+            Self::ExecutedBlocks => "executed_blocks",
+            Self::Balances => "balances",
+        }
+    }
+}
+
+/// Violation of the invariant found by the check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsistencyViolation {
+    pub invariant: Invariant,
+    /// Block containing the inconsistent data.
+    pub block_number: BlockNumber,
+    pub description: String,
+}
+
+/// Result of checking all the invariants for the range of blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    pub from_block: BlockNumber,
+    /// The last verified block at the moment of the check, blocks after it are not checked.
+    pub last_checked_block: BlockNumber,
+    pub violations: Vec<ConsistencyViolation>,
+}
+
+/// Consistency schema checks the invariants which span several tables and thus
+/// can't be enforced by the database constraints.
+///
+/// Checks only read the data, so they can be run against the live database,
+/// but they are not cheap, so they should be limited to the recent blocks.
+#[derive(Debug)]
+pub struct ConsistencySchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ConsistencySchema<'a, 'c> {
+    /// Checks all the invariants for the blocks starting from `from_block` up to the last
+    /// verified block. At most `limit` violations of each invariant are returned.
+    #[tracing::instrument(skip_all, fields(schema = "consistency"))]
+    pub async fn check_invariants(
+        &mut self,
+        from_block: BlockNumber,
+        limit: u32,
+    ) -> QueryResult<ConsistencyReport> {
+        let start = Instant::now();
+        let last_checked_block = self
+            .0
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+
+        let mut violations = Vec::new();
+        if from_block <= last_checked_block {
+            violations.extend(
+                self.check_tx_filters(from_block, last_checked_block, limit)
+                    .await?,
+            );
+            violations.extend(
+                self.check_executed_blocks(from_block, last_checked_block, limit)
+                    .await?,
+            );
+            violations.extend(self.check_balances(from_block, limit).await?);
+        }
+
+        crate::slow_queries::report_query("consistency", "check_invariants", start);
+        Ok(ConsistencyReport {
+            from_block,
+            last_checked_block,
+            violations,
+        })
+    }
+
+    /// Returns the executed transactions and priority operations of the blocks
+    /// in the given range which have no `tx_filters` rows.
+    #[tracing::instrument(skip_all, fields(schema = "consistency"))]
+    pub async fn check_tx_filters(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        limit: u32,
+    ) -> QueryResult<Vec<ConsistencyViolation>> {
+        let start = Instant::now();
+        let unfiltered_txs = sqlx::query_as!(
+            StoredUnfilteredTx,
+            r#"
+            SELECT tx_hash as "tx_hash!", block_number as "block_number!", is_priority as "is_priority!" FROM (
+                SELECT tx_hash, block_number, false AS is_priority FROM executed_transactions
+                WHERE block_number BETWEEN $1 AND $2
+                    AND NOT EXISTS (
+                        SELECT 1 FROM tx_filters WHERE tx_filters.tx_hash = executed_transactions.tx_hash
+                    )
+                UNION ALL
+                SELECT tx_hash, block_number, true AS is_priority FROM executed_priority_operations
+                WHERE block_number BETWEEN $1 AND $2
+                    AND NOT EXISTS (
+                        SELECT 1 FROM tx_filters WHERE tx_filters.tx_hash = executed_priority_operations.tx_hash
+                    )
+            ) unfiltered
+            ORDER BY block_number
+            LIMIT $3
+            "#,
+            i64::from(*from_block),
+            i64::from(*to_block),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let violations = unfiltered_txs
+            .into_iter()
+            .map(|tx| {
+                let kind = if tx.is_priority {
+                    "Priority operation"
+                } else {
+                    "Transaction"
+                };
+                ConsistencyViolation {
+                    invariant: Invariant::TxFilters,
+                    block_number: BlockNumber(tx.block_number as u32),
+                    description: format!(
+                        "{} 0x{} has no tx_filters rows",
+                        kind,
+                        hex::encode(&tx.tx_hash)
+                    ),
+                }
+            })
+            .collect();
+
+        crate::slow_queries::report_query("consistency", "check_tx_filters", start);
+        Ok(violations)
+    }
+
+    /// Returns the blocks in the given range which are not covered by a confirmed
+    /// `ExecuteBlocks` operation. The range is expected to end with the last verified block.
+    #[tracing::instrument(skip_all, fields(schema = "consistency"))]
+    pub async fn check_executed_blocks(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        limit: u32,
+    ) -> QueryResult<Vec<ConsistencyViolation>> {
+        let start = Instant::now();
+        let blocks = sqlx::query!(
+            r#"
+            SELECT number FROM blocks
+            WHERE number BETWEEN $1 AND $2
+                AND NOT EXISTS (
+                    SELECT 1 FROM aggregate_operations
+                    WHERE action_type = $3 AND confirmed = true
+                        AND from_block <= blocks.number AND to_block >= blocks.number
+                )
+            ORDER BY number
+            LIMIT $4
+            "#,
+            i64::from(*from_block),
+            i64::from(*to_block),
+            AggregatedActionType::ExecuteBlocks.to_string(),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let violations = blocks
+            .into_iter()
+            .map(|block| ConsistencyViolation {
+                invariant: Invariant::ExecutedBlocks,
+                block_number: BlockNumber(block.number as u32),
+                description: format!(
+                    "Block {} is verified, but not executed by any confirmed operation",
+                    block.number
+                ),
+            })
+            .collect();
+
+        crate::slow_queries::report_query("consistency", "check_executed_blocks", start);
+        Ok(violations)
+    }
+
+    /// Returns the balances of the accounts updated since `from_block` which don't match
+    /// the last balance update of the verified blocks.
+    ///
+    /// The last verified block is loaded by the same statement, so the check is not affected
+    /// by the blocks verified concurrently.
+    #[tracing::instrument(skip_all, fields(schema = "consistency"))]
+    pub async fn check_balances(
+        &mut self,
+        from_block: BlockNumber,
+        limit: u32,
+    ) -> QueryResult<Vec<ConsistencyViolation>> {
+        let start = Instant::now();
+        let mismatches = sqlx::query_as!(
+            StoredBalanceMismatch,
+            r#"
+            WITH last_verified_block AS (
+                SELECT COALESCE(MAX(to_block), 0) AS number FROM aggregate_operations
+                WHERE action_type = $2 AND confirmed = true
+            ),
+            last_updates AS (
+                SELECT DISTINCT ON (account_id, coin_id) account_id, coin_id, block_number, new_balance
+                FROM account_balance_updates
+                WHERE block_number <= (SELECT number FROM last_verified_block)
+                    AND account_id IN (
+                        SELECT account_id FROM account_balance_updates
+                        WHERE block_number >= $1 AND block_number <= (SELECT number FROM last_verified_block)
+                    )
+                ORDER BY account_id, coin_id, block_number DESC, update_order_id DESC
+            )
+            SELECT
+                last_updates.account_id, last_updates.coin_id, last_updates.block_number,
+                last_updates.new_balance AS expected_balance,
+                COALESCE(balances.balance, 0) AS "balance!"
+            FROM last_updates
+            INNER JOIN accounts ON accounts.id = last_updates.account_id
+            LEFT JOIN balances
+                ON balances.account_id = last_updates.account_id AND balances.coin_id = last_updates.coin_id
+            WHERE COALESCE(balances.balance, 0) <> last_updates.new_balance
+            ORDER BY last_updates.block_number
+            LIMIT $3
+            "#,
+            i64::from(*from_block),
+            AggregatedActionType::ExecuteBlocks.to_string(),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let violations = mismatches
+            .into_iter()
+            .map(|mismatch| ConsistencyViolation {
+                invariant: Invariant::Balances,
+                block_number: BlockNumber(mismatch.block_number as u32),
+                description: format!(
+                    "Balance of the account {} in the token {} is {}, but the last update sets it to {}",
+                    mismatch.account_id,
+                    mismatch.coin_id,
+                    mismatch.balance,
+                    mismatch.expected_balance
+                ),
+            })
+            .collect();
+
+        crate::slow_queries::report_query("consistency", "check_balances", start);
+        Ok(violations)
+    }
+}
@@ -0,0 +1,20 @@
+// External imports
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredUnfilteredTx {
+    pub tx_hash: Vec<u8>,
+    pub block_number: i64,
+    pub is_priority: bool,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredBalanceMismatch {
+    pub account_id: i64,
+    pub coin_id: i32,
+    pub block_number: i64,
+    pub expected_balance: BigDecimal,
+    pub balance: BigDecimal,
+}
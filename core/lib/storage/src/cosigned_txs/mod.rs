@@ -0,0 +1,171 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+use zksync_types::{
+    tx::{TxEthSignature, TxEthSignatureVariant, TxHash},
+    AccountId, Address, ZkSyncTx,
+};
+// Local imports
+use self::records::{StoredCoSignature, StoredCoSignedTx};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the co-signed transactions: transactions signed by the account owner which are
+/// held until the required number of the co-signers have signed their hashes.
+///
+/// Transaction is submitted once the threshold is met. If the submission fails, its error is
+/// stored and the submission is retried on the next co-signature, until the transaction expires.
+#[derive(Debug)]
+pub struct CoSignedTxsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> CoSignedTxsSchema<'a, 'c> {
+    /// Stores the transaction awaiting the co-signatures. Returns `false` if the transaction
+    /// with the same hash is already stored.
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(skip_all, fields(schema = "cosigned_txs"))]
+    pub async fn store_cosigned_tx(
+        &mut self,
+        tx: &ZkSyncTx,
+        account_id: AccountId,
+        eth_signature: &TxEthSignatureVariant,
+        cosigners: &[Address],
+        threshold: u32,
+        expires_at: DateTime<Utc>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let cosigners: Vec<_> = cosigners
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let result = sqlx::query!(
+            "INSERT INTO cosigned_txs (
+                tx_hash, account_id, tx, eth_signature, cosigners, threshold, expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (tx_hash) DO NOTHING",
+            tx.hash().as_ref(),
+            i64::from(*account_id),
+            serde_json::to_value(tx).expect("failed to serialize the transaction"),
+            serde_json::to_value(eth_signature)
+                .expect("failed to serialize the transaction signature"),
+            &cosigners,
+            threshold as i32,
+            expires_at,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("cosigned_txs", "store_cosigned_tx", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "cosigned_txs"))]
+    pub async fn get_cosigned_tx(
+        &mut self,
+        tx_hash: TxHash,
+    ) -> QueryResult<Option<StoredCoSignedTx>> {
+        let start = Instant::now();
+        let tx = sqlx::query_as!(
+            StoredCoSignedTx,
+            "SELECT * FROM cosigned_txs WHERE tx_hash = $1",
+            tx_hash.as_ref(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("cosigned_txs", "get_cosigned_tx", start);
+        Ok(tx)
+    }
+
+    /// Loads the transaction and locks it until the end of the database transaction,
+    /// so the concurrent co-signatures don't submit it twice.
+    ///
+    /// Must be called within the transaction, which also stores the co-signature.
+    #[tracing::instrument(skip_all, fields(schema = "cosigned_txs"))]
+    pub async fn lock_cosigned_tx(
+        &mut self,
+        tx_hash: TxHash,
+    ) -> QueryResult<Option<StoredCoSignedTx>> {
+        let start = Instant::now();
+        let tx = sqlx::query_as!(
+            StoredCoSignedTx,
+            "SELECT * FROM cosigned_txs WHERE tx_hash = $1 FOR UPDATE",
+            tx_hash.as_ref(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("cosigned_txs", "lock_cosigned_tx", start);
+        Ok(tx)
+    }
+
+    /// Loads the co-signatures of the transaction in the order they were added.
+    #[tracing::instrument(skip_all, fields(schema = "cosigned_txs"))]
+    pub async fn get_cosignatures(
+        &mut self,
+        tx_hash: TxHash,
+    ) -> QueryResult<Vec<StoredCoSignature>> {
+        let start = Instant::now();
+        let signatures = sqlx::query_as!(
+            StoredCoSignature,
+            "SELECT * FROM cosigned_tx_signatures WHERE tx_hash = $1 ORDER BY created_at, signer",
+            tx_hash.as_ref(),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("cosigned_txs", "get_cosignatures", start);
+        Ok(signatures)
+    }
+
+    /// Stores the co-signature of the transaction. Returns `false` if the signer has already
+    /// co-signed it.
+    #[tracing::instrument(skip_all, fields(schema = "cosigned_txs"))]
+    pub async fn store_cosignature(
+        &mut self,
+        tx_hash: TxHash,
+        signer: Address,
+        signature: &TxEthSignature,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "INSERT INTO cosigned_tx_signatures (tx_hash, signer, signature)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (tx_hash, signer) DO NOTHING",
+            tx_hash.as_ref(),
+            signer.as_bytes(),
+            serde_json::to_value(signature).expect("failed to serialize the co-signature"),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("cosigned_txs", "store_cosignature", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Stores the result of the submission attempt: the transaction is marked submitted
+    /// if there is no `error`.
+    #[tracing::instrument(skip_all, fields(schema = "cosigned_txs"))]
+    pub async fn store_submission_result(
+        &mut self,
+        tx_hash: TxHash,
+        error: Option<&str>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE cosigned_txs
+            SET submitted_at = CASE WHEN $2::text IS NULL THEN now() ELSE NULL END, error = $2
+            WHERE tx_hash = $1",
+            tx_hash.as_ref(),
+            error,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("cosigned_txs", "store_submission_result", start);
+        Ok(())
+    }
+}
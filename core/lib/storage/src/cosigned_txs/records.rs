@@ -0,0 +1,29 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredCoSignedTx {
+    pub tx_hash: Vec<u8>,
+    pub account_id: i64,
+    pub tx: Value,
+    pub eth_signature: Value,
+    pub cosigners: Vec<Vec<u8>>,
+    pub threshold: i32,
+    pub expires_at: DateTime<Utc>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    /// Error of the last submission attempt.
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredCoSignature {
+    pub tx_hash: Vec<u8>,
+    pub signer: Vec<u8>,
+    pub signature: Value,
+    pub created_at: DateTime<Utc>,
+}
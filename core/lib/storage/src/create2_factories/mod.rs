@@ -0,0 +1,103 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{Address, H256};
+// Local imports
+use self::records::StoredCreate2Factory;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the CREATE2 factory and code hash pairs accepted in the `ChangePubKey`
+/// transactions of the smart wallets.
+#[derive(Debug)]
+pub struct Create2FactoriesSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> Create2FactoriesSchema<'a, 'c> {
+    /// Registers the factory and code hash pair, or renames it if it's already registered.
+    #[tracing::instrument(skip_all, fields(schema = "create2_factories"))]
+    pub async fn store_factory(
+        &mut self,
+        creator_address: Address,
+        code_hash: H256,
+        name: &str,
+        created_by: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO create2_factories (creator_address, code_hash, name, created_by)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (creator_address, code_hash) DO UPDATE SET name = $3",
+            creator_address.as_bytes(),
+            code_hash.as_bytes(),
+            name,
+            created_by,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("create2_factories", "store_factory", start);
+        Ok(())
+    }
+
+    /// Removes the factory and code hash pair. Returns `false` if it wasn't registered.
+    #[tracing::instrument(skip_all, fields(schema = "create2_factories"))]
+    pub async fn remove_factory(
+        &mut self,
+        creator_address: Address,
+        code_hash: H256,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM create2_factories WHERE creator_address = $1 AND code_hash = $2",
+            creator_address.as_bytes(),
+            code_hash.as_bytes(),
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        crate::slow_queries::report_query("create2_factories", "remove_factory", start);
+        Ok(removed)
+    }
+
+    /// Loads all the registered factory and code hash pairs.
+    #[tracing::instrument(skip_all, fields(schema = "create2_factories"))]
+    pub async fn load_factories(&mut self) -> QueryResult<Vec<StoredCreate2Factory>> {
+        let start = Instant::now();
+        let factories = sqlx::query_as!(
+            StoredCreate2Factory,
+            "SELECT * FROM create2_factories ORDER BY created_at, creator_address, code_hash",
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("create2_factories", "load_factories", start);
+        Ok(factories)
+    }
+
+    /// Checks whether the factory and code hash pair is registered.
+    #[tracing::instrument(skip_all, fields(schema = "create2_factories"))]
+    pub async fn is_factory_registered(
+        &mut self,
+        creator_address: Address,
+        code_hash: H256,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let registered = sqlx::query!(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM create2_factories WHERE creator_address = $1 AND code_hash = $2
+            ) AS "registered!""#,
+            creator_address.as_bytes(),
+            code_hash.as_bytes(),
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .registered;
+
+        crate::slow_queries::report_query("create2_factories", "is_factory_registered", start);
+        Ok(registered)
+    }
+}
@@ -0,0 +1,16 @@
+// External imports
+use chrono::prelude::*;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredCreate2Factory {
+    pub creator_address: Vec<u8>,
+    pub code_hash: Vec<u8>,
+    /// Name of the wallet vendor shown to the clients.
+    pub name: String,
+    /// Operator who registered the factory.
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
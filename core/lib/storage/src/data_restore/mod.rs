@@ -10,9 +10,9 @@ use zksync_types::{
 };
 // Local imports
 use self::records::{
-    NewBlockEvent, NewRollupOpsBlock, NewStorageState, NewTokenEvent, StoredBlockEvent,
-    StoredLastWatchedEthBlockNumber, StoredPriorityOpData, StoredRollupOpsBlock,
-    StoredStorageState,
+    NewBlockEvent, NewDataRestoreCheckpoint, NewRollupOpsBlock, NewStorageState, NewTokenEvent,
+    StoredBlockEvent, StoredDataRestoreCheckpoint, StoredLastWatchedEthBlockNumber,
+    StoredPriorityOpData, StoredRollupOpsBlock, StoredStorageState,
 };
 
 use crate::chain::operations::OperationsSchema;
@@ -32,6 +32,7 @@ pub mod records;
 pub struct DataRestoreSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> DataRestoreSchema<'a, 'c> {
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn save_block_operations(
         &mut self,
         commit_op: BlocksCommitOperation,
@@ -75,10 +76,11 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .update_storage_state(new_state)
             .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.data_restore.save_block_operations", start.elapsed());
+        crate::slow_queries::report_query("data_restore", "save_block_operations", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn save_genesis_state(
         &mut self,
         genesis_updates: &[(AccountId, AccountUpdate)],
@@ -92,10 +94,11 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .apply_state_update(BlockNumber(0))
             .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.data_restore.save_genesis_state", start.elapsed());
+        crate::slow_queries::report_query("data_restore", "save_genesis_state", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn load_rollup_ops_blocks(&mut self) -> QueryResult<Vec<StoredRollupOpsBlock>> {
         let start = Instant::now();
         // For each block aggregate its operations from the
@@ -122,7 +125,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         )
         .fetch_all(self.0.conn())
         .await?;
-        metrics::histogram!("sql.data_restore.load_rollup_ops_blocks", start.elapsed());
+        crate::slow_queries::report_query("data_restore", "load_rollup_ops_blocks", start);
         Ok(stored_blocks)
     }
 
@@ -145,14 +148,16 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.data_restore.update_last_watched_block_number",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "data_restore",
+            "update_last_watched_block_number",
+            start,
         );
         Ok(())
     }
 
     /// Loads the last seen Ethereum block number.
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn load_last_watched_block_number(
         &mut self,
     ) -> QueryResult<StoredLastWatchedEthBlockNumber> {
@@ -164,19 +169,61 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.data_restore.load_last_watched_block_number",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("data_restore", "load_last_watched_block_number", start);
         Ok(stored)
     }
 
+    /// Saves the checkpoint of the restoring progress. Expected to be called within the same
+    /// transaction as the update of the state it describes.
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
+    pub async fn save_checkpoint(
+        &mut self,
+        checkpoint: NewDataRestoreCheckpoint,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO data_restore_checkpoints
+                (block_number, last_watched_eth_block, root_hash)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (block_number) DO UPDATE
+            SET last_watched_eth_block = $2, root_hash = $3, created_at = now()",
+            checkpoint.block_number,
+            checkpoint.last_watched_eth_block,
+            checkpoint.root_hash,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("data_restore", "save_checkpoint", start);
+        Ok(())
+    }
+
+    /// Loads the checkpoint with the highest block number, if any.
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
+    pub async fn load_last_checkpoint(
+        &mut self,
+    ) -> QueryResult<Option<StoredDataRestoreCheckpoint>> {
+        let start = Instant::now();
+        let checkpoint = sqlx::query_as!(
+            StoredDataRestoreCheckpoint,
+            "SELECT * FROM data_restore_checkpoints
+            ORDER BY block_number DESC
+            LIMIT 1",
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("data_restore", "load_last_checkpoint", start);
+        Ok(checkpoint)
+    }
+
     fn new_storage_state(&self, state: impl ToString) -> NewStorageState {
         NewStorageState {
             storage_state: state.to_string(),
         }
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn save_events_state(
         &mut self,
         block_events: &[NewBlockEvent],
@@ -221,10 +268,11 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.data_restore.save_events_state", start.elapsed());
+        crate::slow_queries::report_query("data_restore", "save_events_state", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn save_priority_op_data(
         &mut self,
         priority_op_data: &[PriorityOp],
@@ -252,6 +300,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn get_priority_op_data(&mut self) -> QueryResult<HashMap<SerialId, PriorityOp>> {
         let priority_op_data = sqlx::query_as!(
             StoredPriorityOpData,
@@ -272,6 +321,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .collect())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn update_executed_priority_operations(
         &mut self,
         priority_op_data: impl Iterator<Item = &PriorityOp>,
@@ -308,6 +358,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         Ok(not_updated)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn save_rollup_ops(
         &mut self,
         rollup_blocks: &[NewRollupOpsBlock<'_>],
@@ -362,13 +413,14 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .update_storage_state(new_state)
             .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.data_restore.save_rollup_ops", start.elapsed());
+        crate::slow_queries::report_query("data_restore", "save_rollup_ops", start);
         Ok(())
     }
 
     /// Method that initializes the `eth_stats` table.
     /// Since `eth_sender` module uses this table to identify the expected next block numbers
     /// for sending operations to the Ethereum, we must initialize it with actual values.
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn initialize_eth_stats(
         &mut self,
         last_committed_block: BlockNumber,
@@ -388,7 +440,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.data_restore.initialize_eth_stats", start.elapsed());
+        crate::slow_queries::report_query("data_restore", "initialize_eth_stats", start);
         Ok(())
     }
 
@@ -404,18 +456,21 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.data_restore.load_events_state", start.elapsed());
+        crate::slow_queries::report_query("data_restore", "load_events_state", start);
         Ok(events)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn load_committed_events_state(&mut self) -> QueryResult<Vec<StoredBlockEvent>> {
         self.load_events_state("Committed").await
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn load_verified_events_state(&mut self) -> QueryResult<Vec<StoredBlockEvent>> {
         self.load_events_state("Verified").await
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "data_restore"))]
     pub async fn load_storage_state(&mut self) -> QueryResult<StoredStorageState> {
         let start = Instant::now();
         let state = sqlx::query_as!(
@@ -426,7 +481,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.data_restore.load_storage_state", start.elapsed());
+        crate::slow_queries::report_query("data_restore", "load_storage_state", start);
         Ok(state)
     }
 
@@ -445,7 +500,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.data_restore.update_storage_state", start.elapsed());
+        crate::slow_queries::report_query("data_restore", "update_storage_state", start);
         Ok(())
     }
 
@@ -468,7 +523,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .await?;
         }
         transaction.commit().await?;
-        metrics::histogram!("sql.data_restore.update_block_events", start.elapsed());
+        crate::slow_queries::report_query("data_restore", "update_block_events", start);
         Ok(())
     }
 }
@@ -1,4 +1,5 @@
 // External imports
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::FromRow;
@@ -71,3 +72,18 @@ pub struct StoredBlockEvent {
     pub block_num: i64,
     pub contract_version: i32,
 }
+
+#[derive(Debug, Clone)]
+pub struct NewDataRestoreCheckpoint {
+    pub block_number: i64,
+    pub last_watched_eth_block: i64,
+    pub root_hash: Vec<u8>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredDataRestoreCheckpoint {
+    pub block_number: i64,
+    pub last_watched_eth_block: i64,
+    pub root_hash: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+}
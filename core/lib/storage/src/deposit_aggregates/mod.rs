@@ -0,0 +1,116 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::NaiveDate;
+// Workspace imports
+use zksync_types::{Address, BlockNumber, SerialId, TokenId};
+// Local imports
+use self::records::StoredDailyDeposits;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the executed deposits aggregated per L1 sender, token and day.
+///
+/// The aggregates are updated in the same transactions which store and revert the executed
+/// priority operations, so they stay consistent with the chain even after the operations
+/// are moved to the archive.
+#[derive(Debug)]
+pub struct DepositAggregatesSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> DepositAggregatesSchema<'a, 'c> {
+    /// Adds the stored executed priority operation to the aggregates if it's a deposit.
+    #[tracing::instrument(skip_all, fields(schema = "deposit_aggregates"))]
+    pub(crate) async fn add_executed_deposit(&mut self, serial_id: SerialId) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO deposit_aggregates (l1_sender, token_id, day, amount, deposits_count)
+            SELECT from_account, (operation->'priority_op'->>'token')::INTEGER,
+                (created_at AT TIME ZONE 'UTC')::DATE,
+                (operation->'priority_op'->>'amount')::NUMERIC, 1
+            FROM executed_priority_operations
+            WHERE priority_op_serialid = $1 AND operation->>'type' = 'Deposit'
+            ON CONFLICT (l1_sender, token_id, day) DO UPDATE
+            SET amount = deposit_aggregates.amount + EXCLUDED.amount,
+                deposits_count = deposit_aggregates.deposits_count + 1",
+            serial_id as i64,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("deposit_aggregates", "add_executed_deposit", start);
+        Ok(())
+    }
+
+    /// Subtracts the deposits executed after the given block from the aggregates.
+    /// Must be called before the executed priority operations of the reverted blocks are removed.
+    #[tracing::instrument(skip_all, fields(schema = "deposit_aggregates"))]
+    pub(crate) async fn remove_reverted_deposits(
+        &mut self,
+        last_block: BlockNumber,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query!(
+            "UPDATE deposit_aggregates
+            SET amount = deposit_aggregates.amount - reverted.amount,
+                deposits_count = deposit_aggregates.deposits_count - reverted.deposits_count
+            FROM (
+                SELECT from_account AS l1_sender,
+                    (operation->'priority_op'->>'token')::INTEGER AS token_id,
+                    (created_at AT TIME ZONE 'UTC')::DATE AS day,
+                    sum((operation->'priority_op'->>'amount')::NUMERIC) AS amount,
+                    count(*) AS deposits_count
+                FROM executed_priority_operations
+                WHERE block_number > $1 AND operation->>'type' = 'Deposit'
+                GROUP BY 1, 2, 3
+            ) AS reverted
+            WHERE deposit_aggregates.l1_sender = reverted.l1_sender
+                AND deposit_aggregates.token_id = reverted.token_id
+                AND deposit_aggregates.day = reverted.day",
+            *last_block as i64,
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!("DELETE FROM deposit_aggregates WHERE deposits_count <= 0")
+            .execute(transaction.conn())
+            .await?;
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("deposit_aggregates", "remove_reverted_deposits", start);
+        Ok(())
+    }
+
+    /// Loads the deposits executed within the days range (inclusive) per day and token,
+    /// optionally filtered by the L1 sender and the token.
+    #[tracing::instrument(skip_all, fields(schema = "deposit_aggregates"))]
+    pub async fn load_daily_deposits(
+        &mut self,
+        l1_sender: Option<Address>,
+        token_id: Option<TokenId>,
+        from_day: NaiveDate,
+        to_day: NaiveDate,
+    ) -> QueryResult<Vec<StoredDailyDeposits>> {
+        let start = Instant::now();
+        let deposits = sqlx::query_as!(
+            StoredDailyDeposits,
+            r#"SELECT day, token_id, sum(amount) AS "amount!",
+                sum(deposits_count)::BIGINT AS "deposits_count!", count(*) AS "senders_count!"
+            FROM deposit_aggregates
+            WHERE day BETWEEN $1 AND $2
+                AND ($3::bytea IS NULL OR l1_sender = $3)
+                AND ($4::integer IS NULL OR token_id = $4)
+            GROUP BY day, token_id
+            ORDER BY day, token_id"#,
+            from_day,
+            to_day,
+            l1_sender.as_ref().map(|address| address.as_bytes()),
+            token_id.map(|token_id| *token_id as i32),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("deposit_aggregates", "load_daily_deposits", start);
+        Ok(deposits)
+    }
+}
@@ -0,0 +1,16 @@
+// External imports
+use chrono::NaiveDate;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+/// Deposits of the token executed within the day.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredDailyDeposits {
+    pub day: NaiveDate,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub deposits_count: i64,
+    /// Amount of the distinct L1 senders of the deposits.
+    pub senders_count: i64,
+}
@@ -0,0 +1,206 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+use num::BigUint;
+// Workspace imports
+use zksync_types::{tx::PackedEthSignature, Address, TokenId, H256};
+use zksync_utils::biguint_to_big_decimal;
+// Local imports
+use self::records::StoredDepositRelayJob;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Job is waiting to be taken by the relayer.
+pub const JOB_STATUS_PENDING: &str = "pending";
+/// Relayer transactions of the job are being sent.
+pub const JOB_STATUS_PROCESSING: &str = "processing";
+/// Deposit to the owner is confirmed.
+pub const JOB_STATUS_COMPLETED: &str = "completed";
+/// Job is rejected or one of its transactions has failed.
+pub const JOB_STATUS_FAILED: &str = "failed";
+
+/// Schema of the deposits relayed on behalf of the users, who have signed the ERC20 permit for
+/// the relayer account instead of depositing the tokens themselves.
+///
+/// Jobs are taken by the relayer one by one. The job which was being processed when the relayer
+/// has stopped is marked failed on start, since its transactions may have been sent already.
+#[derive(Debug)]
+pub struct DepositRelaySchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> DepositRelaySchema<'a, 'c> {
+    /// Stores the pending job, returns its id.
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(skip_all, fields(schema = "deposit_relay"))]
+    pub async fn store_job(
+        &mut self,
+        owner: Address,
+        token_id: TokenId,
+        token_address: Address,
+        amount: &BigUint,
+        fee: &BigUint,
+        deadline: DateTime<Utc>,
+        signature: &PackedEthSignature,
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            "INSERT INTO deposit_relay_jobs (
+                owner, token_id, token_address, amount, fee, deadline, signature
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id",
+            owner.as_bytes(),
+            *token_id as i32,
+            token_address.as_bytes(),
+            biguint_to_big_decimal(amount.clone()),
+            biguint_to_big_decimal(fee.clone()),
+            deadline,
+            &signature.serialize_packed()[..],
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        crate::slow_queries::report_query("deposit_relay", "store_job", start);
+        Ok(id)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "deposit_relay"))]
+    pub async fn get_job(&mut self, id: i64) -> QueryResult<Option<StoredDepositRelayJob>> {
+        let start = Instant::now();
+        let job = sqlx::query_as!(
+            StoredDepositRelayJob,
+            "SELECT * FROM deposit_relay_jobs WHERE id = $1",
+            id,
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("deposit_relay", "get_job", start);
+        Ok(job)
+    }
+
+    /// Returns the amount of the jobs requested by the owner since the given time.
+    #[tracing::instrument(skip_all, fields(schema = "deposit_relay"))]
+    pub async fn count_jobs_since(
+        &mut self,
+        owner: Address,
+        since: DateTime<Utc>,
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!" FROM deposit_relay_jobs
+            WHERE owner = $1 AND created_at >= $2"#,
+            owner.as_bytes(),
+            since,
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        crate::slow_queries::report_query("deposit_relay", "count_jobs_since", start);
+        Ok(count)
+    }
+
+    /// Returns the amount of the jobs waiting to be taken by the relayer.
+    #[tracing::instrument(skip_all, fields(schema = "deposit_relay"))]
+    pub async fn count_pending_jobs(&mut self) -> QueryResult<i64> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!" FROM deposit_relay_jobs WHERE status = 'pending'"#,
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        crate::slow_queries::report_query("deposit_relay", "count_pending_jobs", start);
+        Ok(count)
+    }
+
+    /// Marks the earliest pending job as processing and returns it.
+    #[tracing::instrument(skip_all, fields(schema = "deposit_relay"))]
+    pub async fn take_next_pending_job(&mut self) -> QueryResult<Option<StoredDepositRelayJob>> {
+        let start = Instant::now();
+        let job = sqlx::query_as!(
+            StoredDepositRelayJob,
+            "UPDATE deposit_relay_jobs SET status = 'processing', updated_at = now()
+            WHERE id = (
+                SELECT id FROM deposit_relay_jobs WHERE status = 'pending'
+                ORDER BY id
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *",
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("deposit_relay", "take_next_pending_job", start);
+        Ok(job)
+    }
+
+    /// Stores the hash of the deposit to the owner once it's sent.
+    #[tracing::instrument(skip_all, fields(schema = "deposit_relay"))]
+    pub async fn store_deposit_hash(&mut self, id: i64, eth_tx_hash: H256) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE deposit_relay_jobs SET eth_tx_hash = $2, updated_at = now() WHERE id = $1",
+            id,
+            eth_tx_hash.as_bytes(),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("deposit_relay", "store_deposit_hash", start);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "deposit_relay"))]
+    pub async fn complete_job(&mut self, id: i64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE deposit_relay_jobs SET status = 'completed', updated_at = now() WHERE id = $1",
+            id,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("deposit_relay", "complete_job", start);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "deposit_relay"))]
+    pub async fn fail_job(&mut self, id: i64, error: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE deposit_relay_jobs SET status = 'failed', error = $2, updated_at = now()
+            WHERE id = $1",
+            id,
+            error,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("deposit_relay", "fail_job", start);
+        Ok(())
+    }
+
+    /// Marks the jobs left processing by the stopped relayer as failed, so they are reviewed
+    /// by the operator instead of being sent twice. Returns the amount of such jobs.
+    #[tracing::instrument(skip_all, fields(schema = "deposit_relay"))]
+    pub async fn fail_interrupted_jobs(&mut self) -> QueryResult<u64> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "UPDATE deposit_relay_jobs
+            SET status = 'failed', error = 'relayer was stopped while processing the job',
+                updated_at = now()
+            WHERE status = 'processing'",
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("deposit_relay", "fail_interrupted_jobs", start);
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,22 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredDepositRelayJob {
+    pub id: i64,
+    pub owner: Vec<u8>,
+    pub token_id: i32,
+    pub token_address: Vec<u8>,
+    pub amount: BigDecimal,
+    pub fee: BigDecimal,
+    pub deadline: DateTime<Utc>,
+    pub signature: Vec<u8>,
+    pub status: String,
+    pub eth_tx_hash: Option<Vec<u8>>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,126 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{Duration, Utc};
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+use self::records::StoredDroppedTransaction;
+use crate::chain::operations::{bytea_literal, records::NewExecutedTransaction};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Kind of the transaction rejected on the execution.
+pub const REJECTED_KIND: &str = "rejected";
+/// Kind of the transaction evicted from the mempool after its `valid_until` had passed.
+pub const EXPIRED_KIND: &str = "expired";
+
+/// Schema of the transactions dropped without being included into a block, either rejected
+/// on the execution or evicted from the mempool as expired.
+///
+/// Unlike the rejected transactions stored along with the executed ones, the dropped transactions
+/// are never replaced by the successful resubmission, so every attempt can be traced.
+#[derive(Debug)]
+pub struct DroppedTxsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> DroppedTxsSchema<'a, 'c> {
+    /// Stores the transactions rejected on the execution. Successful transactions are skipped.
+    #[tracing::instrument(skip_all, fields(schema = "dropped_txs"))]
+    pub(crate) async fn store_rejected_txs(
+        &mut self,
+        operations: &[&NewExecutedTransaction],
+    ) -> QueryResult<()> {
+        // Rows are passed as a single JSON array, since the reason is nullable.
+        let records: Vec<serde_json::Value> = operations
+            .iter()
+            .filter(|operation| !operation.success)
+            .map(|operation| {
+                serde_json::json!({
+                    "tx_hash": bytea_literal(&operation.tx_hash),
+                    "address": bytea_literal(&operation.primary_account_address),
+                    "reason": operation.fail_reason,
+                    "tx": operation.tx,
+                    "created_at": operation.created_at.to_rfc3339(),
+                })
+            })
+            .collect();
+        if records.is_empty() {
+            return Ok(());
+        }
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO dropped_transactions (tx_hash, address, kind, reason, tx, created_at)
+            SELECT tx_hash, address, $2, reason, tx, created_at
+                FROM jsonb_populate_recordset(NULL::dropped_transactions, $1)",
+            serde_json::Value::Array(records),
+            REJECTED_KIND,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("dropped_txs", "store_rejected_txs", start);
+        Ok(())
+    }
+
+    /// Loads the dropped transactions, newest first.
+    #[tracing::instrument(skip_all, fields(schema = "dropped_txs"))]
+    pub async fn load_dropped_txs(
+        &mut self,
+        address: Option<Address>,
+        tx_hash: Option<TxHash>,
+        before: Option<i64>,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredDroppedTransaction>> {
+        let start = Instant::now();
+        let txs = sqlx::query_as!(
+            StoredDroppedTransaction,
+            "SELECT * FROM dropped_transactions
+            WHERE ($1::bytea IS NULL OR address = $1)
+                AND ($2::bytea IS NULL OR tx_hash = $2)
+                AND ($3::bigint IS NULL OR id < $3)
+            ORDER BY id DESC
+            LIMIT $4",
+            address.as_ref().map(|address| address.as_bytes()),
+            tx_hash.as_ref().map(|tx_hash| tx_hash.as_ref()),
+            before,
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("dropped_txs", "load_dropped_txs", start);
+        Ok(txs)
+    }
+
+    /// Removes the transactions dropped more than `max_age` ago, along with the oldest ones
+    /// exceeding `max_count`. Returns the amount of the removed transactions.
+    #[tracing::instrument(skip_all, fields(schema = "dropped_txs"))]
+    pub async fn remove_old_dropped_txs(
+        &mut self,
+        max_age: Duration,
+        max_count: u32,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let expired = sqlx::query!(
+            "DELETE FROM dropped_transactions WHERE dropped_at < $1",
+            Utc::now() - max_age
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+        let excess = sqlx::query!(
+            "DELETE FROM dropped_transactions
+            WHERE id <= (SELECT id FROM dropped_transactions ORDER BY id DESC OFFSET $1 LIMIT 1)",
+            i64::from(max_count)
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("dropped_txs", "remove_old_dropped_txs", start);
+        Ok(expired + excess)
+    }
+}
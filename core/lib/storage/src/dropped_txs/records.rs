@@ -0,0 +1,19 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+/// Transaction dropped without being included into a block.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredDroppedTransaction {
+    pub id: i64,
+    pub tx_hash: Vec<u8>,
+    pub address: Vec<u8>,
+    pub kind: String,
+    pub reason: Option<String>,
+    pub tx: Value,
+    pub created_at: DateTime<Utc>,
+    pub dropped_at: DateTime<Utc>,
+}
@@ -0,0 +1,90 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::tx::TxHash;
+// Local imports
+use self::records::{EIP1271Verification, StoredEIP1271Verification};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the evidence of the EIP-1271 signatures, so the acceptance of the transactions
+/// signed by the smart wallets can be audited after the wallets change their owners.
+#[derive(Debug)]
+pub struct EIP1271VerificationsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> EIP1271VerificationsSchema<'a, 'c> {
+    /// Stores the verifications of the submitted transactions. The verification of the
+    /// transaction which is resubmitted after the rejection is replaced.
+    #[tracing::instrument(skip_all, fields(schema = "eip1271_verifications"))]
+    pub async fn store_verifications(
+        &mut self,
+        verifications: &[EIP1271Verification],
+    ) -> QueryResult<()> {
+        if verifications.is_empty() {
+            return Ok(());
+        }
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = verifications
+            .iter()
+            .map(|verification| verification.tx_hash.as_ref().to_vec())
+            .collect();
+        let addresses: Vec<_> = verifications
+            .iter()
+            .map(|verification| verification.address.as_bytes().to_vec())
+            .collect();
+        let message_hashes: Vec<_> = verifications
+            .iter()
+            .map(|verification| verification.message_hash.as_bytes().to_vec())
+            .collect();
+        let signatures: Vec<_> = verifications
+            .iter()
+            .map(|verification| verification.signature.clone())
+            .collect();
+        let l1_block_numbers: Vec<_> = verifications
+            .iter()
+            .map(|verification| verification.l1_block_number as i64)
+            .collect();
+        sqlx::query!(
+            "INSERT INTO eip1271_verifications
+                (tx_hash, address, message_hash, signature, l1_block_number)
+            SELECT u.tx_hash, u.address, u.message_hash, u.signature, u.l1_block_number
+            FROM UNNEST($1::bytea[], $2::bytea[], $3::bytea[], $4::bytea[], $5::bigint[])
+                AS u(tx_hash, address, message_hash, signature, l1_block_number)
+            ON CONFLICT (tx_hash, address) DO UPDATE
+            SET (message_hash, signature, l1_block_number, created_at) = (
+                EXCLUDED.message_hash, EXCLUDED.signature, EXCLUDED.l1_block_number, now()
+            )",
+            &tx_hashes,
+            &addresses,
+            &message_hashes,
+            &signatures,
+            &l1_block_numbers,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("eip1271_verifications", "store_verifications", start);
+        Ok(())
+    }
+
+    /// Loads the verifications of the EIP-1271 signatures of the transaction.
+    #[tracing::instrument(skip_all, fields(schema = "eip1271_verifications"))]
+    pub async fn load_verifications(
+        &mut self,
+        tx_hash: TxHash,
+    ) -> QueryResult<Vec<StoredEIP1271Verification>> {
+        let start = Instant::now();
+        let verifications = sqlx::query_as!(
+            StoredEIP1271Verification,
+            "SELECT * FROM eip1271_verifications WHERE tx_hash = $1 ORDER BY address",
+            tx_hash.as_ref(),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("eip1271_verifications", "load_verifications", start);
+        Ok(verifications)
+    }
+}
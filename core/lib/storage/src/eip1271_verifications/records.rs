@@ -0,0 +1,29 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+use zksync_types::{tx::TxHash, Address, H256};
+// Local imports
+
+/// EIP-1271 signature of the transaction verified by the smart wallet.
+#[derive(Debug, Clone)]
+pub struct EIP1271Verification {
+    pub tx_hash: TxHash,
+    /// Address of the smart wallet.
+    pub address: Address,
+    /// Hash passed to the `isValidSignature` method of the wallet.
+    pub message_hash: H256,
+    pub signature: Vec<u8>,
+    /// L1 block the wallet was called at.
+    pub l1_block_number: u64,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredEIP1271Verification {
+    pub tx_hash: Vec<u8>,
+    pub address: Vec<u8>,
+    pub message_hash: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub l1_block_number: i64,
+    pub created_at: DateTime<Utc>,
+}
@@ -29,6 +29,7 @@ pub struct EthereumSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 impl<'a, 'c> EthereumSchema<'a, 'c> {
     /// Loads the list of operations that were not confirmed on Ethereum,
     /// each operation has a list of sent Ethereum transactions.
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn load_unconfirmed_operations(&mut self) -> QueryResult<VecDeque<ETHOperation>> {
         let start = Instant::now();
         // Load the operations with the associated Ethereum transactions
@@ -117,12 +118,13 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.load_unconfirmed_operations", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "load_unconfirmed_operations", start);
         Ok(ops)
     }
 
     /// Load all the aggregated operations that have no confirmation yet and have not yet been sent to Ethereum.
     /// Should be used after server restart only.
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn restore_unprocessed_operations(&mut self) -> QueryResult<()> {
         let start = Instant::now();
 
@@ -142,16 +144,14 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.ethereum.restore_unprocessed_operations",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("ethereum", "restore_unprocessed_operations", start);
 
         Ok(())
     }
 
     /// Loads the operations which were stored in `aggregate_operations` table,
     /// and are in `eth_unprocessed_aggregated_ops`.
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn load_unprocessed_operations(
         &mut self,
     ) -> QueryResult<Vec<(i64, AggregatedOperation)>> {
@@ -187,12 +187,13 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
             }
         }
 
-        metrics::histogram!("sql.ethereum.load_unprocessed_operations", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "load_unprocessed_operations", start);
         Ok(operations)
     }
 
     /// Removes the given IDs from `eth_unprocessed_aggregated_ops`.
     /// Used to indicate that operations have been successfully processed.
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn remove_unprocessed_operations(
         &mut self,
         operations_id: Vec<i64>,
@@ -206,15 +207,13 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.ethereum.remove_unprocessed_operations",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("ethereum", "remove_unprocessed_operations", start);
         Ok(())
     }
 
     /// Stores the sent (but not confirmed yet) Ethereum transaction in the database.
     /// Returns the `ETHOperation` object containing the assigned nonce and operation ID.
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn save_new_eth_tx(
         &mut self,
         op_type: AggregatedActionType,
@@ -270,12 +269,13 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.save_new_eth_tx", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "save_new_eth_tx", start);
         Ok(response)
     }
 
     /// Returns whether the operation with the given id was confirmed.
     /// If the operation with such id does not exist, then it returns Ok(false).
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn is_aggregated_op_confirmed(&mut self, id: i64) -> QueryResult<bool> {
         let start = Instant::now();
         let confirmed = sqlx::query_as!(
@@ -288,7 +288,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .map(|op| op.confirmed)
         .unwrap_or(false);
 
-        metrics::histogram!("sql.ethereum.is_aggregated_op_confirmed", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "is_aggregated_op_confirmed", start);
         Ok(confirmed)
     }
 
@@ -303,11 +303,12 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.ethereum.get_eth_op_id", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "get_eth_op_id", start);
         Ok(hash_entry.eth_op_id)
     }
 
     /// Adds a tx hash entry associated with some Ethereum operation to the database.
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn add_hash_entry(&mut self, eth_op_id: i64, hash: &H256) -> QueryResult<()> {
         let start = Instant::now();
         // Insert the new hash entry.
@@ -318,12 +319,13 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         )
         .execute(self.0.conn())
         .await?;
-        metrics::histogram!("sql.ethereum.add_hash_entry", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "add_hash_entry", start);
         Ok(())
     }
 
     /// Updates the Ethereum operation by adding a new tx data.
     /// The new deadline block / gas value are placed instead of old values to the main entry.
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn update_eth_tx(
         &mut self,
         eth_op_id: i64,
@@ -344,7 +346,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.ethereum.update_eth_tx", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "update_eth_tx", start);
         Ok(())
     }
 
@@ -411,7 +413,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.report_created_operation", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "report_created_operation", start);
         Ok(())
     }
 
@@ -419,6 +421,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
     ///
     /// This method expects the database to be initially prepared with inserting the actual
     /// gas limit value. The command responsible for that is `zk db insert eth-data`.
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn update_gas_price(
         &mut self,
         gas_price_limit: U256,
@@ -441,10 +444,11 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.ethereum.update_gas_price", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "update_gas_price", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn load_gas_price_limit(&mut self) -> QueryResult<U256> {
         let start = Instant::now();
         let params = self.load_eth_params().await?;
@@ -452,10 +456,11 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         let gas_price_limit =
             U256::try_from(params.gas_price_limit).expect("Negative gas limit value stored in DB");
 
-        metrics::histogram!("sql.ethereum.load_gas_price_limit", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "load_gas_price_limit", start);
         Ok(gas_price_limit)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn load_average_gas_price(&mut self) -> QueryResult<Option<U256>> {
         let start = Instant::now();
         let params = self.load_eth_params().await?;
@@ -464,16 +469,17 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
             .average_gas_price
             .map(|price| U256::try_from(price).expect("Negative average gas price stored in DB"));
 
-        metrics::histogram!("sql.ethereum.load_average_gas_price", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "load_average_gas_price", start);
         Ok(average_gas_price)
     }
 
     /// Loads the stored Ethereum operations stats.
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn load_stats(&mut self) -> QueryResult<ETHStats> {
         let start = Instant::now();
         let params = self.load_eth_params().await?;
 
-        metrics::histogram!("sql.ethereum.load_stats", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "load_stats", start);
         Ok(params.into())
     }
 
@@ -482,12 +488,44 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         let params = sqlx::query_as!(ETHParams, "SELECT * FROM eth_parameters WHERE id = true",)
             .fetch_one(self.0.conn())
             .await?;
-        metrics::histogram!("sql.ethereum.load_eth_params", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "load_eth_params", start);
         Ok(params)
     }
 
+    /// Stores the L1 gas spent by the confirmed Ethereum transaction.
+    /// Repeated calls for the same operation keep the first stored value.
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
+    pub async fn store_gas_spent(
+        &mut self,
+        hash: &H256,
+        gas_used: U256,
+        gas_price: U256,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let gas_used = BigDecimal::from(BigInt::from_str(&gas_used.to_string()).unwrap());
+        let gas_price = BigDecimal::from(BigInt::from_str(&gas_price.to_string()).unwrap());
+
+        sqlx::query!(
+            "INSERT INTO eth_gas_spent (eth_op_id, tx_hash, op_type, gas_used, gas_price)
+                SELECT eth_tx_hashes.eth_op_id, $1, eth_operations.op_type, $2, $3
+                FROM eth_tx_hashes
+                INNER JOIN eth_operations ON eth_operations.id = eth_tx_hashes.eth_op_id
+                WHERE eth_tx_hashes.tx_hash = $1
+            ON CONFLICT (eth_op_id) DO NOTHING",
+            hash.as_bytes(),
+            gas_used,
+            gas_price,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("ethereum", "store_gas_spent", start);
+        Ok(())
+    }
+
     /// Marks the stored Ethereum transaction as confirmed (and thus the associated `Operation`
     /// is marked as confirmed as well).
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn confirm_eth_tx(&mut self, hash: &H256) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -552,6 +590,10 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
                         )
                         .await?;
                 }
+                transaction
+                    .tx_callbacks_schema()
+                    .enqueue_block_callbacks(BlockNumber(from_block), BlockNumber(to_block), status)
+                    .await?;
             }
 
             if matches!(action_type, AggregatedActionType::ExecuteBlocks) {
@@ -576,7 +618,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.confirm_eth_tx", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "confirm_eth_tx", start);
         Ok(())
     }
 
@@ -587,6 +629,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
     /// nonce value. Currently the script `db-insert-eth-data.sh` is responsible for that
     /// and it's invoked within `db-reset` subcommand.
     #[doc = "hidden"]
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn get_next_nonce(&mut self) -> QueryResult<i64> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -608,7 +651,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.get_next_nonce", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "get_next_nonce", start);
         Ok(old_nonce_value)
     }
 
@@ -616,6 +659,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
     /// Since in db tests the database is empty, we must provide a possibility
     /// to initialize required db fields.
     #[doc = "hidden"]
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn initialize_eth_data(&mut self) -> QueryResult<()> {
         let start = Instant::now();
         #[derive(Debug)]
@@ -650,7 +694,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
             .await?;
         }
 
-        metrics::histogram!("sql.ethereum.initialize_eth_data", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "initialize_eth_data", start);
         Ok(())
     }
 
@@ -666,13 +710,11 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?
         .created_at;
-        metrics::histogram!(
-            "sql.ethereum.get_eth_operation_creation_time",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("ethereum", "get_eth_operation_creation_time", start);
         Ok(created_at)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn aggregated_op_final_hash(
         &mut self,
         block_number: BlockNumber,
@@ -700,6 +742,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
     // Updates eth_parameters with given nonce and last block.
     // It updates last_verified_block only if it is greater than given last block.
+    #[tracing::instrument(skip_all, fields(schema = "ethereum"))]
     pub async fn update_eth_parameters(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -718,7 +761,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.update_eth_parameters", start.elapsed());
+        crate::slow_queries::report_query("ethereum", "update_eth_parameters", start);
         Ok(())
     }
 }
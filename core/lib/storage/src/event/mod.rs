@@ -29,8 +29,15 @@ pub use records::{get_event_type, EventType};
 /// On every insert into this table a special PostgreSQL channel gets notified
 /// about it.
 ///
-/// Note, that all events should be created solely by other `storage` methods
-/// and they are persisted in the database forever.
+/// Note, that all events should be created solely by other `storage` methods.
+/// Events are persisted in the database until they are removed by the retention
+/// policy (see `remove_old_events`).
+///
+/// Ids are taken from the sequence on insert, so the events can be committed
+/// out of the id order. Cursor-based readers (see `load_events_after`) return
+/// the events in the order of the transactions that stored them and only once
+/// all the preceding transactions are finished, so the consumers may use the id
+/// of the last processed event as a cursor without skipping the late commits.
 #[derive(Debug)]
 pub struct EventSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
@@ -65,11 +72,12 @@ impl<'a, 'c> EventSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.event.store_event_data", start.elapsed());
+        crate::slow_queries::report_query("event", "store_event_data", start);
         Ok(())
     }
 
     /// Load all events from the database with the `id` greater than `from`.
+    #[tracing::instrument(skip_all, fields(schema = "event"))]
     pub async fn fetch_new_events(&mut self, from: EventId) -> QueryResult<Vec<StoredEvent>> {
         let start = Instant::now();
         // Don't deserialize JSONs, the event server is responsible for handling
@@ -90,12 +98,141 @@ impl<'a, 'c> EventSchema<'a, 'c> {
         .fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.event.fetch_new_events", start.elapsed());
+        crate::slow_queries::report_query("event", "fetch_new_events", start);
         Ok(events)
     }
 
+    /// Load at most `limit` events following the `after` one in the commit order.
+    /// `EventId(0)` stands for the start of the log. Events stored by the transactions
+    /// which are still in progress, and by the ones started after them, are held back
+    /// until these transactions finish.
+    /// Returns `None` if the `after` event was removed by the retention policy.
+    #[tracing::instrument(skip_all, fields(schema = "event"))]
+    pub async fn load_events_after(
+        &mut self,
+        after: EventId,
+        limit: u32,
+    ) -> QueryResult<Option<Vec<StoredEvent>>> {
+        let start = Instant::now();
+        let after_txid = if *after == 0 {
+            0
+        } else {
+            let txid = sqlx::query!("SELECT txid FROM events WHERE id = $1", *after as i64)
+                .fetch_optional(self.0.conn())
+                .await?;
+            match txid {
+                Some(record) => record.txid,
+                None => return Ok(None),
+            }
+        };
+        // Own events of the current transaction are returned as well, so the events
+        // can be read back before the commit.
+        let events = sqlx::query_as!(
+            StoredEvent,
+            r#"
+            SELECT
+                id,
+                block_number,
+                event_type as "event_type!: EventType",
+                event_data
+            FROM events
+            WHERE (txid, id) > ($1, $2)
+                AND (
+                    txid < txid_snapshot_xmin(txid_current_snapshot())
+                    OR txid = txid_current_if_assigned()
+                )
+            ORDER BY txid ASC, id ASC
+            LIMIT $3
+            "#,
+            after_txid,
+            *after as i64,
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("event", "load_events_after", start);
+        Ok(Some(events))
+    }
+
+    /// Load the id of the earliest event available to `load_events_after`.
+    /// Returns `None` if there are no such events.
+    #[tracing::instrument(skip_all, fields(schema = "event"))]
+    pub async fn get_first_event_id(&mut self) -> QueryResult<Option<EventId>> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            r#"
+            SELECT id FROM events
+            WHERE txid < txid_snapshot_xmin(txid_current_snapshot())
+                OR txid = txid_current_if_assigned()
+            ORDER BY txid ASC, id ASC
+            LIMIT 1
+            "#
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| EventId(record.id as u64));
+
+        crate::slow_queries::report_query("event", "get_first_event_id", start);
+        Ok(id)
+    }
+
+    /// Load the id of the latest event available to `load_events_after`.
+    /// Returns `None` if there are no such events.
+    #[tracing::instrument(skip_all, fields(schema = "event"))]
+    pub async fn get_last_visible_event_id(&mut self) -> QueryResult<Option<EventId>> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            r#"
+            SELECT id FROM events
+            WHERE txid < txid_snapshot_xmin(txid_current_snapshot())
+                OR txid = txid_current_if_assigned()
+            ORDER BY txid DESC, id DESC
+            LIMIT 1
+            "#
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| EventId(record.id as u64));
+
+        crate::slow_queries::report_query("event", "get_last_visible_event_id", start);
+        Ok(id)
+    }
+
+    /// Remove the events stored earlier than `max_age` ago. To keep the remaining log
+    /// continuous, all the events preceding the removed ones in the commit order
+    /// are removed as well. Returns the amount of removed events.
+    #[tracing::instrument(skip_all, fields(schema = "event"))]
+    pub async fn remove_old_events(&mut self, max_age: chrono::Duration) -> QueryResult<u64> {
+        let start = Instant::now();
+        let remove_before = chrono::Utc::now() - max_age;
+        let removed = sqlx::query!(
+            r#"
+            DELETE FROM events
+            WHERE (txid, id) <= (
+                SELECT txid, id FROM events
+                WHERE created_at < $1
+                    AND (
+                        txid < txid_snapshot_xmin(txid_current_snapshot())
+                        OR txid = txid_current_if_assigned()
+                    )
+                ORDER BY txid DESC, id DESC
+                LIMIT 1
+            )
+            "#,
+            remove_before,
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        crate::slow_queries::report_query("event", "remove_old_events", start);
+        Ok(removed)
+    }
+
     /// Load the id of the latest event in the database.
     /// Returns `None` if the `events` table is empty.
+    #[tracing::instrument(skip_all, fields(schema = "event"))]
     pub async fn get_last_event_id(&mut self) -> QueryResult<Option<EventId>> {
         let start = Instant::now();
         let id = sqlx::query!("SELECT MAX(id) as max FROM events")
@@ -104,7 +241,7 @@ impl<'a, 'c> EventSchema<'a, 'c> {
             .max
             .map(|id| EventId(id as u64));
 
-        metrics::histogram!("sql.event.get_last_event_id", start.elapsed());
+        crate::slow_queries::report_query("event", "get_last_event_id", start);
         Ok(id)
     }
 
@@ -112,6 +249,7 @@ impl<'a, 'c> EventSchema<'a, 'c> {
     /// This method relies on the `load_block_range` which may return `None`
     /// if there're no Ethereum transactions featuring this block (`Committed` or `Executed`).
     /// In such cases, it silently returns `Ok`.
+    #[tracing::instrument(skip_all, fields(schema = "event"))]
     pub async fn store_block_event(
         &mut self,
         block_number: BlockNumber,
@@ -157,11 +295,12 @@ impl<'a, 'c> EventSchema<'a, 'c> {
             .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.event.store_block_event", start.elapsed());
+        crate::slow_queries::report_query("event", "store_block_event", start);
         Ok(())
     }
 
     /// Creates new account events and stores them in the database.
+    #[tracing::instrument(skip_all, fields(schema = "event"))]
     pub async fn store_state_updated_event(
         &mut self,
         block_number: BlockNumber,
@@ -199,12 +338,13 @@ impl<'a, 'c> EventSchema<'a, 'c> {
             .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.event.store_state_updated_event", start.elapsed());
+        crate::slow_queries::report_query("event", "store_state_updated_event", start);
         Ok(())
     }
 
     /// Create new transaction events and store them in the database.
     /// The block is expected to be either committed or finalized.
+    #[tracing::instrument(skip_all, fields(schema = "event"))]
     pub async fn store_confirmed_transaction_event(
         &mut self,
         block_number: BlockNumber,
@@ -246,7 +386,7 @@ impl<'a, 'c> EventSchema<'a, 'c> {
             .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.event.store_transaction_event", start.elapsed());
+        crate::slow_queries::report_query("event", "store_transaction_event", start);
         Ok(())
     }
 
@@ -254,6 +394,7 @@ impl<'a, 'c> EventSchema<'a, 'c> {
     /// `Queued` or `Rejected` events in the database. These events are created by
     /// the state keeper and emitted by the special actor as soon as `block_operations`
     /// are processed.
+    #[tracing::instrument(skip_all, fields(schema = "event"))]
     pub async fn store_executed_transaction_event(
         &mut self,
         block_number: BlockNumber,
@@ -284,7 +425,7 @@ impl<'a, 'c> EventSchema<'a, 'c> {
             .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.event.store_queued_transaction_event", start.elapsed());
+        crate::slow_queries::report_query("event", "store_queued_transaction_event", start);
         Ok(())
     }
 }
@@ -0,0 +1,133 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{exit_proof::ExitProofData, AccountId, BlockNumber, TokenId};
+// Local imports
+use self::records::StoredExitProof;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the exit proofs requested through the API. The proofs are generated
+/// in the background, so the requests are stored along with their results.
+#[derive(Debug)]
+pub struct ExitProofsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ExitProofsSchema<'a, 'c> {
+    /// Stores the request of the exit proof. The existing request is restarted only
+    /// if it has failed or its proof was generated before the given verified block.
+    #[tracing::instrument(skip_all, fields(schema = "exit_proofs"))]
+    pub async fn request_exit_proof(
+        &mut self,
+        account_id: AccountId,
+        token_id: TokenId,
+        last_verified_block: BlockNumber,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO exit_proofs (account_id, token_id)
+            VALUES ($1, $2)
+            ON CONFLICT (account_id, token_id) DO UPDATE
+            SET (block_number, proof_data, error, created_at, started_at, finished_at) =
+                (NULL, NULL, NULL, now(), NULL, NULL)
+            WHERE exit_proofs.finished_at IS NOT NULL
+                AND (exit_proofs.error IS NOT NULL OR exit_proofs.block_number < $3)",
+            i64::from(*account_id),
+            *token_id as i32,
+            i64::from(*last_verified_block),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("exit_proofs", "request_exit_proof", start);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "exit_proofs"))]
+    pub async fn get_exit_proof(
+        &mut self,
+        account_id: AccountId,
+        token_id: TokenId,
+    ) -> QueryResult<Option<StoredExitProof>> {
+        let start = Instant::now();
+        let exit_proof = sqlx::query_as!(
+            StoredExitProof,
+            "SELECT * FROM exit_proofs WHERE account_id = $1 AND token_id = $2",
+            i64::from(*account_id),
+            *token_id as i32,
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("exit_proofs", "get_exit_proof", start);
+        Ok(exit_proof)
+    }
+
+    /// Marks the oldest pending request as started and returns it. Requests which generation
+    /// was started more than an hour ago are considered abandoned and are given out again.
+    #[tracing::instrument(skip_all, fields(schema = "exit_proofs"))]
+    pub async fn take_pending_exit_proof(&mut self) -> QueryResult<Option<StoredExitProof>> {
+        let start = Instant::now();
+        let exit_proof = sqlx::query_as!(
+            StoredExitProof,
+            "UPDATE exit_proofs SET started_at = now()
+            WHERE (account_id, token_id) = (
+                SELECT account_id, token_id FROM exit_proofs
+                WHERE finished_at IS NULL
+                    AND (started_at IS NULL OR started_at < now() - INTERVAL '1 hour')
+                ORDER BY created_at
+                FOR UPDATE SKIP LOCKED
+                LIMIT 1
+            )
+            RETURNING *",
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("exit_proofs", "take_pending_exit_proof", start);
+        Ok(exit_proof)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "exit_proofs"))]
+    pub async fn store_exit_proof(&mut self, proof_data: &ExitProofData) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE exit_proofs
+            SET (block_number, proof_data, error, finished_at) = ($3, $4, NULL, now())
+            WHERE account_id = $1 AND token_id = $2",
+            i64::from(*proof_data.account_id),
+            *proof_data.token_id as i32,
+            i64::from(*proof_data.stored_block_info.block_number),
+            serde_json::to_value(proof_data).expect("failed to serialize the exit proof"),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("exit_proofs", "store_exit_proof", start);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "exit_proofs"))]
+    pub async fn store_exit_proof_error(
+        &mut self,
+        account_id: AccountId,
+        token_id: TokenId,
+        error: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE exit_proofs
+            SET (proof_data, error, finished_at) = (NULL, $3, now())
+            WHERE account_id = $1 AND token_id = $2",
+            i64::from(*account_id),
+            *token_id as i32,
+            error,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("exit_proofs", "store_exit_proof_error", start);
+        Ok(())
+    }
+}
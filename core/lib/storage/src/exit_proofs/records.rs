@@ -0,0 +1,21 @@
+// External imports
+use chrono::prelude::*;
+use serde_json::Value;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredExitProof {
+    pub account_id: i64,
+    pub token_id: i32,
+    /// Block which state the proof is generated for, set once the generation is finished.
+    pub block_number: Option<i64>,
+    /// Serialized `ExitProofData`.
+    pub proof_data: Option<Value>,
+    /// Reason of the failed generation.
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,106 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use num::BigUint;
+// Workspace imports
+use zksync_types::tx::TxHash;
+use zksync_utils::biguint_to_big_decimal;
+// Local imports
+use self::records::{StoredTxFeeBreakdown, TxFeeQuote};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the fee decomposition of the transactions. The quotes are stored by the API
+/// on the submission, and the fees charged are split when the transactions are executed.
+#[derive(Debug)]
+pub struct FeeBreakdownsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> FeeBreakdownsSchema<'a, 'c> {
+    /// Stores the fee quotes of the submitted transactions. The quote of the transaction
+    /// which is not executed yet is replaced, since it may be resubmitted after the rejection.
+    #[tracing::instrument(skip_all, fields(schema = "fee_breakdowns"))]
+    pub async fn store_fee_quotes(&mut self, quotes: &[TxFeeQuote]) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        for quote in quotes {
+            sqlx::query!(
+                "INSERT INTO tx_fee_breakdowns (tx_hash, token_id, quoted_zkp_fee, quoted_gas_fee, subsidy)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (tx_hash) DO UPDATE
+                SET (token_id, quoted_zkp_fee, quoted_gas_fee, subsidy, created_at) = ($2, $3, $4, $5, now())
+                WHERE tx_fee_breakdowns.charged_fee IS NULL",
+                quote.tx_hash.as_ref(),
+                *quote.token_id as i32,
+                quote.zkp_fee,
+                quote.gas_fee,
+                quote.subsidy,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("fee_breakdowns", "store_fee_quotes", start);
+        Ok(())
+    }
+
+    /// Splits the fees charged by the executed transactions into the zero-knowledge proof
+    /// and the L1 gas parts according to their quotes. Transactions without the quote are skipped.
+    #[tracing::instrument(skip_all, fields(schema = "fee_breakdowns"))]
+    pub async fn store_charged_fees(&mut self, fees: &[(TxHash, BigUint)]) -> QueryResult<()> {
+        if fees.is_empty() {
+            return Ok(());
+        }
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = fees
+            .iter()
+            .map(|(tx_hash, _)| tx_hash.as_ref().to_vec())
+            .collect();
+        let charged_fees: Vec<_> = fees
+            .iter()
+            .map(|(_, fee)| biguint_to_big_decimal(fee.clone()))
+            .collect();
+        sqlx::query!(
+            "UPDATE tx_fee_breakdowns
+            SET charged_fee = charged.fee,
+                zkp_fee = charged.zkp_fee,
+                gas_fee = charged.fee - charged.zkp_fee,
+                executed_at = now()
+            FROM (
+                SELECT u.tx_hash, u.fee,
+                    CASE WHEN b.quoted_zkp_fee + b.quoted_gas_fee = 0 THEN 0
+                        ELSE trunc(u.fee * b.quoted_zkp_fee / (b.quoted_zkp_fee + b.quoted_gas_fee))
+                    END AS zkp_fee
+                FROM UNNEST($1::bytea[], $2::numeric[]) AS u(tx_hash, fee)
+                    INNER JOIN tx_fee_breakdowns b ON b.tx_hash = u.tx_hash
+            ) AS charged
+            WHERE tx_fee_breakdowns.tx_hash = charged.tx_hash",
+            &tx_hashes,
+            &charged_fees,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("fee_breakdowns", "store_charged_fees", start);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "fee_breakdowns"))]
+    pub async fn get_fee_breakdown(
+        &mut self,
+        tx_hash: TxHash,
+    ) -> QueryResult<Option<StoredTxFeeBreakdown>> {
+        let start = Instant::now();
+        let breakdown = sqlx::query_as!(
+            StoredTxFeeBreakdown,
+            "SELECT * FROM tx_fee_breakdowns WHERE tx_hash = $1",
+            tx_hash.as_ref(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("fee_breakdowns", "get_fee_breakdown", start);
+        Ok(breakdown)
+    }
+}
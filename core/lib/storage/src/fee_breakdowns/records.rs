@@ -0,0 +1,30 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+use zksync_types::{tx::TxHash, TokenId};
+// Local imports
+
+/// Fee quote the transaction was accepted against.
+#[derive(Debug, Clone)]
+pub struct TxFeeQuote {
+    pub tx_hash: TxHash,
+    pub token_id: TokenId,
+    pub zkp_fee: BigDecimal,
+    pub gas_fee: BigDecimal,
+    pub subsidy: BigDecimal,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredTxFeeBreakdown {
+    pub tx_hash: Vec<u8>,
+    pub token_id: i32,
+    pub quoted_zkp_fee: BigDecimal,
+    pub quoted_gas_fee: BigDecimal,
+    pub subsidy: BigDecimal,
+    pub charged_fee: Option<BigDecimal>,
+    pub zkp_fee: Option<BigDecimal>,
+    pub gas_fee: Option<BigDecimal>,
+    pub created_at: DateTime<Utc>,
+    pub executed_at: Option<DateTime<Utc>>,
+}
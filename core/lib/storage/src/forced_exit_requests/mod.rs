@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 // Built-in deps
-use num::BigInt;
+use num::{BigInt, BigUint};
 use sqlx::types::BigDecimal;
 use std::{ops::Sub, time::Instant};
 // External imports
@@ -11,13 +11,13 @@ use zksync_types::forced_exit_requests::{
     ForcedExitRequest, ForcedExitRequestId, SaveForcedExitRequestQuery,
 };
 
-use zksync_types::tx::TxHash;
+use zksync_types::{prover::ProverJobStatus, tx::TxHash, Address, BlockNumber, H256};
 
 pub mod records;
 
 mod utils;
 
-use records::DbForcedExitRequest;
+use records::{DbForcedExitRequest, ForcedExitProcessingBacklog};
 
 use crate::utils::address_to_stored_string;
 
@@ -26,6 +26,7 @@ use crate::utils::address_to_stored_string;
 pub struct ForcedExitRequestsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
     pub async fn store_request(
         &mut self,
         request: SaveForcedExitRequestQuery,
@@ -36,12 +37,16 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         let target_str = address_to_stored_string(&request.target);
 
         let tokens = utils::vec_to_comma_list(request.tokens.clone());
+        let refund_address = request
+            .refund_address
+            .as_ref()
+            .map(address_to_stored_string);
 
         let stored_request: DbForcedExitRequest = sqlx::query_as!(
             DbForcedExitRequest,
             r#"
-            INSERT INTO forced_exit_requests ( target, tokens, price_in_wei, created_at, valid_until )
-            VALUES ( $1, $2, $3, $4, $5 )
+            INSERT INTO forced_exit_requests ( target, tokens, price_in_wei, created_at, valid_until, refund_address )
+            VALUES ( $1, $2, $3, $4, $5, $6 )
             RETURNING *
             "#,
             target_str,
@@ -51,15 +56,17 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
             // However, since the valid_until is generated outside the db (using config params)
             // it was decided to set both values in the server for consistency
             request.created_at,
-            request.valid_until
+            request.valid_until,
+            refund_address
         )
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.forced_exit_requests.store_request", start.elapsed());
+        crate::slow_queries::report_query("forced_exit_requests", "store_request", start);
         Ok(stored_request.into())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
     pub async fn get_request_by_id(
         &mut self,
         id: ForcedExitRequestId,
@@ -78,14 +85,12 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .await?
         .map(|r| r.into());
 
-        metrics::histogram!(
-            "sql.forced_exit_requests.get_request_by_id",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("forced_exit_requests", "get_request_by_id", start);
 
         Ok(request)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
     pub async fn set_fulfilled_at(
         &mut self,
         id: ForcedExitRequestId,
@@ -105,11 +110,12 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.forced_exit_requests.set_fulfilled_at", start.elapsed());
+        crate::slow_queries::report_query("forced_exit_requests", "set_fulfilled_at", start);
 
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
     pub async fn get_oldest_unfulfilled_request(
         &mut self,
     ) -> QueryResult<Option<ForcedExitRequest>> {
@@ -130,14 +136,16 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .await?
         .map(|r| r.into());
 
-        metrics::histogram!(
-            "sql.forced_exit_requests.get_oldest_unfulfilled_request",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "forced_exit_requests",
+            "get_oldest_unfulfilled_request",
+            start,
         );
 
         Ok(request)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
     pub async fn set_fulfilled_by(
         &mut self,
         id: ForcedExitRequestId,
@@ -159,13 +167,14 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.forced_exit_requests.set_fulfilled_by", start.elapsed());
+        crate::slow_queries::report_query("forced_exit_requests", "set_fulfilled_by", start);
         Ok(())
     }
 
     // Normally this function should not return any more
     // than one request, but it was decided to make to more
     // general from the start
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
     pub async fn get_unconfirmed_requests(&mut self) -> QueryResult<Vec<ForcedExitRequest>> {
         let start = Instant::now();
 
@@ -182,14 +191,210 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .map(|rec| rec.into())
         .collect();
 
-        metrics::histogram!(
-            "sql.forced_exit_requests.get_unconfirmed_requests",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "forced_exit_requests",
+            "get_unconfirmed_requests",
+            start,
         );
 
         Ok(requests)
     }
 
+    /// Stores the payment of the request seen at L1.
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
+    pub async fn set_paid(
+        &mut self,
+        id: ForcedExitRequestId,
+        payment_tx_hash: H256,
+        paid_at: DateTime<Utc>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        let hash_str = format!("0x{}", hex::encode(payment_tx_hash.as_bytes()));
+
+        sqlx::query!(
+            r#"
+            UPDATE forced_exit_requests
+                SET payment_tx_hash = $1, paid_at = $2
+                WHERE id = $3
+            "#,
+            hash_str,
+            paid_at,
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("forced_exit_requests", "set_paid", start);
+        Ok(())
+    }
+
+    /// Stores the refund of the fee paid in excess of the request price.
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
+    pub async fn set_refund(
+        &mut self,
+        id: ForcedExitRequestId,
+        address: Address,
+        amount: BigUint,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        let address_str = address_to_stored_string(&address);
+        let amount = BigDecimal::from(BigInt::from(amount));
+
+        sqlx::query!(
+            r#"
+            UPDATE forced_exit_requests
+                SET refund_address = $1, refund_in_wei = $2, refunded_by = NULL, refunded_at = NULL
+                WHERE id = $3
+            "#,
+            address_str,
+            amount,
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("forced_exit_requests", "set_refund", start);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
+    pub async fn set_refunded_by(
+        &mut self,
+        id: ForcedExitRequestId,
+        tx_hash: Option<TxHash>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        let hash_str = tx_hash.map(|hash| hash.to_string());
+
+        sqlx::query!(
+            r#"
+            UPDATE forced_exit_requests
+                SET refunded_by = $1
+                WHERE id = $2
+            "#,
+            hash_str,
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("forced_exit_requests", "set_refunded_by", start);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
+    pub async fn set_refunded_at(
+        &mut self,
+        id: ForcedExitRequestId,
+        refunded_at: DateTime<Utc>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            UPDATE forced_exit_requests
+                SET refunded_at = $1
+                WHERE id = $2
+            "#,
+            refunded_at,
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("forced_exit_requests", "set_refunded_at", start);
+        Ok(())
+    }
+
+    /// Returns the fulfilled requests with the refunds that were not completed yet.
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
+    pub async fn get_pending_refunds(&mut self) -> QueryResult<Vec<ForcedExitRequest>> {
+        let start = Instant::now();
+
+        let requests: Vec<ForcedExitRequest> = sqlx::query_as!(
+            DbForcedExitRequest,
+            r#"
+            SELECT * FROM forced_exit_requests
+            WHERE refund_in_wei IS NOT NULL AND refunded_at IS NULL AND fulfilled_at IS NOT NULL
+            ORDER BY id
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|rec| rec.into())
+        .collect();
+
+        crate::slow_queries::report_query("forced_exit_requests", "get_pending_refunds", start);
+        Ok(requests)
+    }
+
+    /// Loads the backlogs of the prover and the Ethereum sender ahead of the withdrawals
+    /// of the given block. If the block is not created yet, all the backlogs are loaded.
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
+    pub async fn get_processing_backlog(
+        &mut self,
+        block_number: Option<BlockNumber>,
+    ) -> QueryResult<ForcedExitProcessingBacklog> {
+        let start = Instant::now();
+
+        let block_number = block_number.map(|block| i64::from(*block));
+        let done_status = ProverJobStatus::Done.to_number();
+
+        // Confirmation time of the operations is not stored, so the operations created
+        // during the last hour are used as the approximation.
+        let backlog = sqlx::query!(
+            r#"
+            SELECT
+                (
+                    SELECT COUNT(*) FROM prover_job_queue
+                    WHERE job_status != $2 AND ($1::bigint IS NULL OR first_block <= $1)
+                ) AS "pending_prover_jobs!",
+                (
+                    SELECT COUNT(*) FROM prover_job_queue
+                    WHERE job_status = $2 AND updated_at > now() - interval '1 hour'
+                ) AS "prover_jobs_done_last_hour!",
+                (
+                    SELECT COUNT(*) FROM aggregate_operations
+                    WHERE confirmed = false
+                        AND action_type IN ('CommitBlocks', 'PublishProofBlocksOnchain', 'ExecuteBlocks')
+                        AND ($1::bigint IS NULL OR from_block <= $1)
+                ) AS "pending_eth_operations!",
+                (
+                    SELECT COUNT(*) FROM aggregate_operations
+                    WHERE action_type IN ('CommitBlocks', 'PublishProofBlocksOnchain', 'ExecuteBlocks')
+                        AND $1::bigint IS NOT NULL AND from_block <= $1 AND to_block >= $1
+                ) AS "created_block_operations!",
+                (
+                    SELECT COUNT(*) FROM aggregate_operations
+                    WHERE confirmed = true AND created_at > now() - interval '1 hour'
+                        AND action_type IN ('CommitBlocks', 'PublishProofBlocksOnchain', 'ExecuteBlocks')
+                ) AS "eth_operations_confirmed_last_hour!"
+            "#,
+            block_number,
+            done_status
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        // Each block is committed, proven and executed on Ethereum.
+        const ETH_OPERATIONS_PER_BLOCK: i64 = 3;
+        let missing_block_operations =
+            (ETH_OPERATIONS_PER_BLOCK - backlog.created_block_operations).max(0);
+
+        crate::slow_queries::report_query("forced_exit_requests", "get_processing_backlog", start);
+        Ok(ForcedExitProcessingBacklog {
+            pending_prover_jobs: backlog.pending_prover_jobs,
+            prover_jobs_done_last_hour: backlog.prover_jobs_done_last_hour,
+            pending_eth_operations: backlog.pending_eth_operations + missing_block_operations,
+            eth_operations_confirmed_last_hour: backlog.eth_operations_confirmed_last_hour,
+        })
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "forced_exit_requests"))]
     pub async fn delete_old_unfulfilled_requests(
         &mut self,
         // The time that has to be passed since the
@@ -210,9 +415,10 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.forced_exit_requests.delete_old_unfulfilled_requests",
-            start.elapsed()
+        crate::slow_queries::report_query(
+            "forced_exit_requests",
+            "delete_old_unfulfilled_requests",
+            start,
         );
 
         Ok(())
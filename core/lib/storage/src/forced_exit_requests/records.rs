@@ -2,7 +2,11 @@ use crate::utils::{address_to_stored_string, stored_str_address_to_address};
 use chrono::{DateTime, Utc};
 use num::{bigint::ToBigInt, BigInt};
 use sqlx::types::BigDecimal;
-use zksync_types::{forced_exit_requests::ForcedExitRequest, tx::TxHash, TokenId};
+use zksync_types::{
+    forced_exit_requests::{ForcedExitRefund, ForcedExitRequest},
+    tx::TxHash,
+    TokenId, H256,
+};
 
 use super::utils;
 
@@ -16,6 +20,12 @@ pub struct DbForcedExitRequest {
     pub created_at: DateTime<Utc>,
     pub fulfilled_by: Option<String>,
     pub fulfilled_at: Option<DateTime<Utc>>,
+    pub refund_address: Option<String>,
+    pub refund_in_wei: Option<BigDecimal>,
+    pub refunded_by: Option<String>,
+    pub refunded_at: Option<DateTime<Utc>>,
+    pub payment_tx_hash: Option<String>,
+    pub paid_at: Option<DateTime<Utc>>,
 }
 
 impl From<ForcedExitRequest> for DbForcedExitRequest {
@@ -24,6 +34,7 @@ impl From<ForcedExitRequest> for DbForcedExitRequest {
 
         let tokens = utils::vec_to_comma_list(request.tokens);
         let fulfilled_by = request.fulfilled_by.map(utils::vec_to_comma_list);
+        let refund = request.refund;
         Self {
             id: request.id,
             target: address_to_stored_string(&request.target),
@@ -33,6 +44,23 @@ impl From<ForcedExitRequest> for DbForcedExitRequest {
             created_at: request.created_at,
             fulfilled_at: request.fulfilled_at,
             fulfilled_by,
+            refund_address: request
+                .refund_address
+                .or_else(|| refund.as_ref().map(|refund| refund.address))
+                .as_ref()
+                .map(address_to_stored_string),
+            refund_in_wei: refund
+                .as_ref()
+                .map(|refund| BigDecimal::from(BigInt::from(refund.amount.clone()))),
+            refunded_by: refund
+                .as_ref()
+                .and_then(|refund| refund.refunded_by)
+                .map(|hash| hash.to_string()),
+            refunded_at: refund.and_then(|refund| refund.refunded_at),
+            payment_tx_hash: request
+                .payment_tx_hash
+                .map(|hash| format!("0x{}", hex::encode(hash.as_bytes()))),
+            paid_at: request.paid_at,
         }
     }
 }
@@ -50,6 +78,31 @@ impl From<DbForcedExitRequest> for ForcedExitRequest {
 
         let tokens: Vec<TokenId> = utils::comma_list_to_vec(val.tokens);
         let fulfilled_by: Option<Vec<TxHash>> = val.fulfilled_by.map(utils::comma_list_to_vec);
+        let refund_address = val
+            .refund_address
+            .as_deref()
+            .map(stored_str_address_to_address);
+        let refund = match (refund_address, val.refund_in_wei) {
+            (Some(address), Some(amount)) => Some(ForcedExitRefund {
+                address,
+                amount: amount
+                    .to_bigint()
+                    .and_then(|int| int.to_biguint())
+                    .expect("Invalid forced exit refund has been stored"),
+                refunded_by: val.refunded_by.map(|hash| {
+                    hash.parse()
+                        .expect("Invalid forced exit refund hash has been stored")
+                }),
+                refunded_at: val.refunded_at,
+            }),
+            _ => None,
+        };
+
+        let payment_tx_hash = val.payment_tx_hash.map(|hash| {
+            let bytes = hex::decode(hash.trim_start_matches("0x"))
+                .expect("Invalid forced exit payment hash has been stored");
+            H256::from_slice(&bytes)
+        });
 
         ForcedExitRequest {
             id: val.id,
@@ -60,6 +113,23 @@ impl From<DbForcedExitRequest> for ForcedExitRequest {
             valid_until: val.valid_until,
             fulfilled_at: val.fulfilled_at,
             fulfilled_by,
+            payment_tx_hash,
+            paid_at: val.paid_at,
+            refund_address,
+            refund,
         }
     }
 }
+
+/// Backlogs of the server components processing the forced exit request
+/// up to the withdrawal of the funds on L1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForcedExitProcessingBacklog {
+    /// Prover jobs for the blocks up to the block of the request that are not completed yet.
+    pub pending_prover_jobs: i64,
+    pub prover_jobs_done_last_hour: i64,
+    /// Operations sent to Ethereum for the blocks up to the block of the request that are not
+    /// confirmed yet, including the ones for the block of the request not created yet.
+    pub pending_eth_operations: i64,
+    pub eth_operations_confirmed_last_hour: i64,
+}
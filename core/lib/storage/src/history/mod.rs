@@ -0,0 +1,230 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::BlockNumber;
+// Local imports
+use self::records::StoredHistoryTransaction;
+use crate::{
+    chain::{
+        block::records::TransactionItem,
+        operations_ext::records::{StorageTxData, StorageTxReceipt},
+    },
+    QueryResult, StorageProcessor,
+};
+
+pub mod records;
+
+/// History schema is responsible for the sharding of the executed transactions.
+///
+/// If the history database is configured (see [`crate::connection::access_history_storage`]),
+/// executed transactions of the old blocks are moved from the main database to it, so the main
+/// database stays small on the long-running chains. The history database has the same schema
+/// as the main one, but only its `executed_transactions` table is used. Transaction filters
+/// are kept in the main database, so the account history is still served from it, and
+/// `OperationsExtSchema` loads the moved transactions from the history database.
+///
+/// Some methods are meant for the main database and the rest for the history one,
+/// it's stated in the documentation of each method.
+#[derive(Debug)]
+pub struct HistorySchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> HistorySchema<'a, 'c> {
+    /// Loads at most `limit` executed transactions from the blocks up to `last_block` inclusively.
+    /// Called on the main database.
+    #[tracing::instrument(skip_all, fields(schema = "history"))]
+    pub async fn load_transactions_to_move(
+        &mut self,
+        last_block: BlockNumber,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredHistoryTransaction>> {
+        let start = Instant::now();
+        let transactions = sqlx::query_as!(
+            StoredHistoryTransaction,
+            r#"
+            SELECT tx_hash, to_jsonb(executed_transactions) as "row!" FROM executed_transactions
+            WHERE block_number <= $1
+            ORDER BY block_number
+            LIMIT $2
+            "#,
+            i64::from(*last_block),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("history", "load_transactions_to_move", start);
+        Ok(transactions)
+    }
+
+    /// Stores the transactions loaded by `load_transactions_to_move`.
+    /// Transactions stored before are skipped, so the batch can be safely stored again
+    /// if it wasn't removed from the main database. Called on the history database.
+    #[tracing::instrument(skip_all, fields(schema = "history"))]
+    pub async fn store_transactions(
+        &mut self,
+        transactions: &[StoredHistoryTransaction],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let rows: Vec<_> = transactions.iter().map(|tx| tx.row.clone()).collect();
+        // Sequence numbers are preserved, so the transactions keep their order in the history.
+        sqlx::query!(
+            "INSERT INTO executed_transactions
+            SELECT * FROM jsonb_populate_recordset(NULL::executed_transactions, $1)
+            ON CONFLICT (tx_hash) DO NOTHING",
+            serde_json::Value::Array(rows)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("history", "store_transactions", start);
+        Ok(())
+    }
+
+    /// Removes the transactions moved to the history database. Called on the main database.
+    #[tracing::instrument(skip_all, fields(schema = "history"))]
+    pub async fn remove_moved_transactions(&mut self, tx_hashes: &[Vec<u8>]) -> QueryResult<u64> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM executed_transactions WHERE tx_hash = ANY($1)",
+            tx_hashes
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        crate::slow_queries::report_query("history", "remove_moved_transactions", start);
+        Ok(removed)
+    }
+
+    /// Loads the receipt of the transaction. Called on the history database.
+    #[tracing::instrument(skip_all, fields(schema = "history"))]
+    pub(crate) async fn tx_receipt(
+        &mut self,
+        hash: &[u8],
+    ) -> QueryResult<Option<StorageTxReceipt>> {
+        let start = Instant::now();
+        let receipt = sqlx::query_as!(
+            StorageTxReceipt,
+            r#"
+            SELECT
+                tx_hash,
+                block_number as "block_number?",
+                success as "success?",
+                fail_reason,
+                Null::bigint as "eth_block?",
+                Null::bigint as "priority_op_serialid?"
+            FROM executed_transactions
+            WHERE tx_hash = $1
+            "#,
+            hash
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("history", "tx_receipt", start);
+        Ok(receipt)
+    }
+
+    /// Loads the data of the transaction. Called on the history database.
+    #[tracing::instrument(skip_all, fields(schema = "history"))]
+    pub(crate) async fn tx_data(&mut self, hash: &[u8]) -> QueryResult<Option<StorageTxData>> {
+        let start = Instant::now();
+        let data = sqlx::query_as!(
+            StorageTxData,
+            r#"
+            SELECT
+                tx_hash,
+                tx as op,
+                block_number as "block_number?",
+                block_index,
+                created_at,
+                success as "success?",
+                fail_reason,
+                Null::bytea as "eth_hash?",
+                Null::bigint as "priority_op_serialid?",
+                batch_id,
+                eth_sign_data
+            FROM executed_transactions
+            WHERE tx_hash = $1
+            "#,
+            hash
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("history", "tx_data", start);
+        Ok(data)
+    }
+
+    /// Loads the data of the transaction by its position in the block.
+    /// Called on the history database.
+    #[tracing::instrument(skip_all, fields(schema = "history"))]
+    pub(crate) async fn tx_data_by_block_and_index(
+        &mut self,
+        block_number: BlockNumber,
+        block_index: u64,
+    ) -> QueryResult<Option<StorageTxData>> {
+        let start = Instant::now();
+        let data = sqlx::query_as!(
+            StorageTxData,
+            r#"
+            SELECT
+                tx_hash,
+                tx as op,
+                block_number as "block_number?",
+                block_index,
+                created_at,
+                success as "success?",
+                fail_reason,
+                Null::bytea as "eth_hash?",
+                Null::bigint as "priority_op_serialid?",
+                batch_id,
+                eth_sign_data
+            FROM executed_transactions
+            WHERE block_number = $1 AND block_index = $2
+            "#,
+            i64::from(*block_number),
+            block_index as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("history", "tx_data_by_block_and_index", start);
+        Ok(data)
+    }
+
+    /// Loads the transactions with the given sequence numbers. Called on the history database.
+    #[tracing::instrument(skip_all, fields(schema = "history"))]
+    pub(crate) async fn txs_by_sequence_numbers(
+        &mut self,
+        sequence_numbers: &[i64],
+    ) -> QueryResult<Vec<TransactionItem>> {
+        let start = Instant::now();
+        let txs = sqlx::query_as!(
+            TransactionItem,
+            r#"
+            SELECT
+                sequence_number,
+                tx_hash as "tx_hash!",
+                tx as "op!",
+                block_number as "block_number!",
+                created_at as "created_at!",
+                success as "success!",
+                fail_reason,
+                Null::bytea as eth_hash,
+                Null::bigint as priority_op_serialid,
+                block_index,
+                batch_id
+            FROM executed_transactions
+            WHERE sequence_number = ANY($1)
+            "#,
+            sequence_numbers
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("history", "txs_by_sequence_numbers", start);
+        Ok(txs)
+    }
+}
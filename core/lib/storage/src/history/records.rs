@@ -0,0 +1,13 @@
+// External imports
+use serde_json::Value;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+/// Executed transaction to be moved to the history database.
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredHistoryTransaction {
+    pub tx_hash: Vec<u8>,
+    /// Whole row of the `executed_transactions` table.
+    pub row: Value,
+}
@@ -0,0 +1,123 @@
+// Built-in deps
+use std::time::{Duration, Instant};
+// External imports
+// Workspace imports
+// Local imports
+use self::records::StoredScheduledJob;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the periodic background jobs: the advisory locks electing the server instance
+/// running the job and the bookkeeping of the job runs.
+///
+/// Advisory locks are held by the database session, so they must be taken via the dedicated
+/// connection rather than the pooled one, which can be reused by other tasks.
+#[derive(Debug)]
+pub struct JobsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> JobsSchema<'a, 'c> {
+    /// Tries to take the lock of the job without waiting for it.
+    /// Returns `false` if the lock is held by another session.
+    #[tracing::instrument(skip_all, fields(schema = "jobs"))]
+    pub async fn try_lock_job(&mut self, name: &str) -> QueryResult<bool> {
+        let start = Instant::now();
+        let locked = sqlx::query!(
+            r#"SELECT pg_try_advisory_lock(hashtext($1)) AS "locked!""#,
+            name
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .locked;
+
+        crate::slow_queries::report_query("jobs", "try_lock_job", start);
+        Ok(locked)
+    }
+
+    /// Releases the lock of the job taken by this session.
+    #[tracing::instrument(skip_all, fields(schema = "jobs"))]
+    pub async fn unlock_job(&mut self, name: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"SELECT pg_advisory_unlock(hashtext($1)) AS "unlocked!""#,
+            name
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("jobs", "unlock_job", start);
+        Ok(())
+    }
+
+    /// Records the start of the job run.
+    #[tracing::instrument(skip_all, fields(schema = "jobs"))]
+    pub async fn start_job_run(&mut self, name: &str, interval: Duration) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO scheduled_jobs (name, interval_secs, last_started_at)
+            VALUES ($1, $2, now())
+            ON CONFLICT (name) DO UPDATE
+            SET interval_secs = $2, last_started_at = now()",
+            name,
+            interval.as_secs() as i64,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("jobs", "start_job_run", start);
+        Ok(())
+    }
+
+    /// Records the completion of the job run, `error` is `None` if the run succeeded.
+    #[tracing::instrument(skip_all, fields(schema = "jobs"))]
+    pub async fn finish_job_run(&mut self, name: &str, error: Option<&str>) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE scheduled_jobs
+            SET last_finished_at = now(), last_error = $2, runs_count = runs_count + 1,
+                consecutive_failures = CASE
+                    WHEN $2::text IS NULL THEN 0
+                    ELSE consecutive_failures + 1
+                END
+            WHERE name = $1",
+            name,
+            error,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("jobs", "finish_job_run", start);
+        Ok(())
+    }
+
+    /// Loads the bookkeeping of the job, `None` if it was never run.
+    #[tracing::instrument(skip_all, fields(schema = "jobs"))]
+    pub async fn load_job(&mut self, name: &str) -> QueryResult<Option<StoredScheduledJob>> {
+        let start = Instant::now();
+        let job = sqlx::query_as!(
+            StoredScheduledJob,
+            "SELECT * FROM scheduled_jobs WHERE name = $1",
+            name
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("jobs", "load_job", start);
+        Ok(job)
+    }
+
+    /// Loads the bookkeeping of all the jobs which were ever run, ordered by name.
+    #[tracing::instrument(skip_all, fields(schema = "jobs"))]
+    pub async fn load_jobs(&mut self) -> QueryResult<Vec<StoredScheduledJob>> {
+        let start = Instant::now();
+        let jobs = sqlx::query_as!(
+            StoredScheduledJob,
+            "SELECT * FROM scheduled_jobs ORDER BY name"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("jobs", "load_jobs", start);
+        Ok(jobs)
+    }
+}
@@ -0,0 +1,16 @@
+// External imports
+use chrono::prelude::*;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredScheduledJob {
+    pub name: String,
+    pub interval_secs: i64,
+    pub last_started_at: Option<DateTime<Utc>>,
+    pub last_finished_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: i32,
+    pub runs_count: i64,
+}
@@ -0,0 +1,179 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use self::records::{AddressLabel, StoredAddressLabel};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the address labels shown in the explorer-facing responses and of the API keys
+/// allowed to manage the user-defined labels.
+#[derive(Debug)]
+pub struct LabelsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> LabelsSchema<'a, 'c> {
+    /// Stores the label of the address. Returns `false` if the address already has
+    /// the same label in the same scope.
+    #[tracing::instrument(skip_all, fields(schema = "labels"))]
+    pub async fn store_label(&mut self, label: &AddressLabel) -> QueryResult<bool> {
+        let start = Instant::now();
+        let inserted = sqlx::query!(
+            "INSERT INTO address_labels (address, label, category, scope)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (address, label, (COALESCE(scope, ''))) DO NOTHING",
+            label.address.as_bytes(),
+            label.label,
+            label.category,
+            label.scope,
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        crate::slow_queries::report_query("labels", "store_label", start);
+        Ok(inserted)
+    }
+
+    /// Removes the label of the address from the scope (or the operator's label if the scope
+    /// is `None`). Returns `false` if there was no such label.
+    #[tracing::instrument(skip_all, fields(schema = "labels"))]
+    pub async fn remove_label(
+        &mut self,
+        address: Address,
+        label: &str,
+        scope: Option<&str>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM address_labels
+            WHERE address = $1 AND label = $2 AND scope IS NOT DISTINCT FROM $3",
+            address.as_bytes(),
+            label,
+            scope,
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        crate::slow_queries::report_query("labels", "remove_label", start);
+        Ok(removed)
+    }
+
+    /// Loads the labels of the addresses visible in the given scope: the ones set by the operator
+    /// and the ones created in the scope itself.
+    #[tracing::instrument(skip_all, fields(schema = "labels"))]
+    pub async fn load_labels(
+        &mut self,
+        addresses: &[Address],
+        scope: Option<&str>,
+    ) -> QueryResult<Vec<StoredAddressLabel>> {
+        let start = Instant::now();
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let labels = sqlx::query_as!(
+            StoredAddressLabel,
+            "SELECT * FROM address_labels
+            WHERE address = ANY($1) AND (scope IS NULL OR scope = $2)
+            ORDER BY id",
+            &addresses,
+            scope,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("labels", "load_labels", start);
+        Ok(labels)
+    }
+
+    /// Stores the hash of the API key issued for the scope.
+    #[tracing::instrument(skip_all, fields(schema = "labels"))]
+    pub async fn store_api_key(&mut self, key_hash: &[u8], scope: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO label_api_keys (key_hash, scope) VALUES ($1, $2)",
+            key_hash,
+            scope,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("labels", "store_api_key", start);
+        Ok(())
+    }
+
+    /// Returns the scope of the API key with the given hash, if it was issued.
+    #[tracing::instrument(skip_all, fields(schema = "labels"))]
+    pub async fn get_api_key_scope(&mut self, key_hash: &[u8]) -> QueryResult<Option<String>> {
+        let start = Instant::now();
+        let scope = sqlx::query!(
+            "SELECT scope FROM label_api_keys WHERE key_hash = $1",
+            key_hash,
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|row| row.scope);
+
+        crate::slow_queries::report_query("labels", "get_api_key_scope", start);
+        Ok(scope)
+    }
+
+    /// Sets the maximum page size of the paginated endpoints for all the API keys of the scope,
+    /// `None` restores the server limits. Returns the number of the updated keys.
+    #[tracing::instrument(skip_all, fields(schema = "labels"))]
+    pub async fn set_api_keys_max_page_size(
+        &mut self,
+        scope: &str,
+        max_page_size: Option<u32>,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let updated = sqlx::query!(
+            "UPDATE label_api_keys SET max_page_size = $2 WHERE scope = $1",
+            scope,
+            max_page_size.map(|size| size as i32),
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        crate::slow_queries::report_query("labels", "set_api_keys_max_page_size", start);
+        Ok(updated)
+    }
+
+    /// Returns the maximum page size set for the API key with the given hash, if any.
+    #[tracing::instrument(skip_all, fields(schema = "labels"))]
+    pub async fn get_api_key_max_page_size(&mut self, key_hash: &[u8]) -> QueryResult<Option<u32>> {
+        let start = Instant::now();
+        let max_page_size = sqlx::query!(
+            "SELECT max_page_size FROM label_api_keys WHERE key_hash = $1",
+            key_hash,
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .and_then(|row| row.max_page_size)
+        .map(|size| size as u32);
+
+        crate::slow_queries::report_query("labels", "get_api_key_max_page_size", start);
+        Ok(max_page_size)
+    }
+
+    /// Revokes all the API keys of the scope, the labels created in the scope are kept.
+    /// Returns the number of the revoked keys.
+    #[tracing::instrument(skip_all, fields(schema = "labels"))]
+    pub async fn remove_api_keys(&mut self, scope: &str) -> QueryResult<u64> {
+        let start = Instant::now();
+        let removed = sqlx::query!("DELETE FROM label_api_keys WHERE scope = $1", scope)
+            .execute(self.0.conn())
+            .await?
+            .rows_affected();
+
+        crate::slow_queries::report_query("labels", "remove_api_keys", start);
+        Ok(removed)
+    }
+}
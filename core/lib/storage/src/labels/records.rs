@@ -0,0 +1,27 @@
+// External imports
+use chrono::prelude::*;
+use sqlx::FromRow;
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+
+/// Label of the address to be stored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddressLabel {
+    pub address: Address,
+    pub label: String,
+    /// Category of the label, e.g. the exchange hot wallet or the known contract.
+    pub category: String,
+    /// Scope of the API key which created the label, `None` for the labels set by the operator.
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredAddressLabel {
+    pub id: i64,
+    pub address: Vec<u8>,
+    pub label: String,
+    pub category: String,
+    pub scope: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
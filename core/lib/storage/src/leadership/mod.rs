@@ -0,0 +1,92 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+// Local imports
+use self::records::StoredLeadershipChange;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the leader election between the core server instances.
+///
+/// The leader holds the advisory lock for as long as its session is alive, so the lock
+/// must be taken via the dedicated connection rather than the pooled one. The lock uses
+/// the two-key form, so it can't collide with the locks of the scheduled jobs.
+#[derive(Debug)]
+pub struct LeadershipSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> LeadershipSchema<'a, 'c> {
+    /// Tries to take the leadership lock without waiting for it.
+    /// Returns `false` if the lock is held by another session.
+    #[tracing::instrument(skip_all, fields(schema = "leadership"))]
+    pub async fn try_acquire_leadership(&mut self) -> QueryResult<bool> {
+        let start = Instant::now();
+        let locked =
+            sqlx::query!(r#"SELECT pg_try_advisory_lock(hashtext('core_leader'), 0) AS "locked!""#)
+                .fetch_one(self.0.conn())
+                .await?
+                .locked;
+
+        crate::slow_queries::report_query("leadership", "try_acquire_leadership", start);
+        Ok(locked)
+    }
+
+    /// Releases the leadership lock taken by this session.
+    #[tracing::instrument(skip_all, fields(schema = "leadership"))]
+    pub async fn release_leadership(&mut self) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(r#"SELECT pg_advisory_unlock(hashtext('core_leader'), 0) AS "unlocked!""#)
+            .fetch_one(self.0.conn())
+            .await?;
+
+        crate::slow_queries::report_query("leadership", "release_leadership", start);
+        Ok(())
+    }
+
+    /// Checks that the session is still alive, and thus the lock taken by it is still held.
+    #[tracing::instrument(skip_all, fields(schema = "leadership"))]
+    pub async fn ping(&mut self) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(r#"SELECT 1 AS "one!""#)
+            .fetch_one(self.0.conn())
+            .await?;
+
+        crate::slow_queries::report_query("leadership", "ping", start);
+        Ok(())
+    }
+
+    /// Records that the instance became the leader.
+    #[tracing::instrument(skip_all, fields(schema = "leadership"))]
+    pub async fn record_leadership_change(&mut self, instance_id: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO leadership_changes (instance_id) VALUES ($1)",
+            instance_id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("leadership", "record_leadership_change", start);
+        Ok(())
+    }
+
+    /// Loads the last `limit` leadership changes, newest first.
+    #[tracing::instrument(skip_all, fields(schema = "leadership"))]
+    pub async fn load_leadership_changes(
+        &mut self,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredLeadershipChange>> {
+        let start = Instant::now();
+        let changes = sqlx::query_as!(
+            StoredLeadershipChange,
+            "SELECT * FROM leadership_changes ORDER BY id DESC LIMIT $1",
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("leadership", "load_leadership_changes", start);
+        Ok(changes)
+    }
+}
@@ -0,0 +1,12 @@
+// External imports
+use chrono::prelude::*;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredLeadershipChange {
+    pub id: i64,
+    pub instance_id: String,
+    pub acquired_at: DateTime<Utc>,
+}
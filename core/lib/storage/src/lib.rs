@@ -32,11 +32,18 @@
 //!
 //! There are the following sets of schemas:
 //!
+//! - audit, for the append-only log of the administrative actions.
+//! - cold_export, for exporting the block history to the Parquet files for analytics.
 //! - config, for the server config.
+//! - consistency, for checking the invariants spanning several tables.
 //! - data_restore, for the data_restore crate.
 //! - ethereum, for the data associated with the Ethereum blockchain.
+//! - history, for moving the old executed transactions to the history database.
 //! - prover, for the data on prover jobs, proofs, etc.
+//! - pruning, for moving the data of the old blocks out of the hot tables.
 //! - tokens, for storing and loading known tokens.
+//! - outbox, for the durable event outbox and the webhook subscriptions.
+//! - slow_queries, for the log of the schema methods which took longer than the threshold.
 //! - chain - the biggest one, which includes several schemas for the ZKSync sidechain itself.
 //!
 //! The chain module includes the following schemas:
@@ -88,20 +95,57 @@ use crate::connection::{holder::ConnectionHolder, PooledConnection};
 #[cfg(test)]
 mod tests;
 
+pub mod address_attestations;
+pub mod api_route_stats;
+pub mod audit;
+pub mod canonical_json;
 pub mod chain;
+pub mod cold_export;
 pub mod config;
 pub mod connection;
+pub mod consistency;
+pub mod cosigned_txs;
+pub mod create2_factories;
 pub mod data_restore;
+pub mod deposit_aggregates;
+pub mod deposit_relay;
 pub mod diff;
+pub mod dropped_txs;
+pub mod eip1271_verifications;
 pub mod ethereum;
 pub mod event;
+pub mod exit_proofs;
+pub mod fee_breakdowns;
 pub mod forced_exit_requests;
+pub mod history;
+pub mod jobs;
+pub mod labels;
+pub mod leadership;
 pub mod listener;
+pub mod mass_payouts;
+pub mod migrations;
 pub mod misc;
+pub mod nonce_reservations;
+pub mod order_book;
+pub mod outbox;
+pub mod payment_requests;
 pub mod prover;
+pub mod pruning;
+pub mod reports;
+pub mod revenue;
+pub mod runtime_config;
+pub mod screening;
+pub mod session_keys;
+pub mod slow_queries;
+pub mod sponsorship;
+pub mod standing_orders;
 pub mod test_data;
 pub mod tokens;
+pub mod tx_callbacks;
+pub mod tx_memos;
 pub mod utils;
+pub mod withdrawal_hooks;
+pub mod withdrawal_policy;
 pub mod withdrawals;
 
 use forced_exit_requests::ForcedExitRequestsSchema;
@@ -120,6 +164,33 @@ pub fn get_database_replica_url() -> String {
     env::var("DATABASE_REPLICA_URL").unwrap_or_else(|_| get_database_url())
 }
 
+/// Obtains the URLs of the database read replicas from the comma-separated
+/// `DATABASE_REPLICA_URLS` environment variable, falling back to `DATABASE_REPLICA_URL`.
+/// Returns an empty list if no replicas are configured.
+pub fn get_database_replica_urls() -> Vec<String> {
+    let urls: Vec<_> = env::var("DATABASE_REPLICA_URLS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(String::from)
+        .collect();
+    if urls.is_empty() {
+        env::var("DATABASE_REPLICA_URL").into_iter().collect()
+    } else {
+        urls
+    }
+}
+
+/// Obtains the URL of the history database holding the old executed transactions
+/// from the `DATABASE_HISTORY_URL` environment variable.
+/// Returns `None` if the history sharding is not configured.
+pub fn get_database_history_url() -> Option<String> {
+    env::var("DATABASE_HISTORY_URL")
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+}
+
 /// Obtains the database URL from the environment variable.
 pub fn get_database_url() -> String {
     env::var("DATABASE_URL").expect("DATABASE_URL must be set")
@@ -153,8 +224,15 @@ impl From<ActionType> for StorageActionType {
 impl<'a> StorageProcessor<'a> {
     /// Creates a `StorageProcessor` using an unique sole connection to the database.
     pub async fn establish_connection<'b>() -> QueryResult<StorageProcessor<'b>> {
-        let database_url = get_database_url();
-        let connection = PgConnection::connect(&database_url).await?;
+        Self::establish_connection_to(&get_database_url()).await
+    }
+
+    /// Creates a `StorageProcessor` using an unique sole connection to the given database,
+    /// e.g. to compare it with the main one.
+    pub async fn establish_connection_to<'b>(
+        database_url: &str,
+    ) -> QueryResult<StorageProcessor<'b>> {
+        let connection = PgConnection::connect(database_url).await?;
         Ok(StorageProcessor {
             conn: ConnectionHolder::Direct(connection),
             in_transaction: false,
@@ -208,26 +286,109 @@ impl<'a> StorageProcessor<'a> {
         chain::ChainIntermediator(self)
     }
 
+    /// Gains access to the `AddressAttestations` schema.
+    pub fn address_attestations_schema(
+        &mut self,
+    ) -> address_attestations::AddressAttestationsSchema<'_, 'a> {
+        address_attestations::AddressAttestationsSchema(self)
+    }
+
+    /// Gains access to the `ApiRouteStats` schema.
+    pub fn api_route_stats_schema(&mut self) -> api_route_stats::ApiRouteStatsSchema<'_, 'a> {
+        api_route_stats::ApiRouteStatsSchema(self)
+    }
+
+    /// Gains access to the `Audit` schema.
+    pub fn audit_schema(&mut self) -> audit::AuditSchema<'_, 'a> {
+        audit::AuditSchema(self)
+    }
+
+    /// Gains access to the `ColdExport` schema.
+    pub fn cold_export_schema(&mut self) -> cold_export::ColdExportSchema<'_, 'a> {
+        cold_export::ColdExportSchema(self)
+    }
+
     /// Gains access to the `Config` schema.
     pub fn config_schema(&mut self) -> config::ConfigSchema<'_, 'a> {
         config::ConfigSchema(self)
     }
 
+    /// Gains access to the `Consistency` schema.
+    pub fn consistency_schema(&mut self) -> consistency::ConsistencySchema<'_, 'a> {
+        consistency::ConsistencySchema(self)
+    }
+
+    /// Gains access to the `CoSignedTxs` schema.
+    pub fn cosigned_txs_schema(&mut self) -> cosigned_txs::CoSignedTxsSchema<'_, 'a> {
+        cosigned_txs::CoSignedTxsSchema(self)
+    }
+
+    /// Gains access to the `Create2Factories` schema.
+    pub fn create2_factories_schema(
+        &mut self,
+    ) -> create2_factories::Create2FactoriesSchema<'_, 'a> {
+        create2_factories::Create2FactoriesSchema(self)
+    }
+
     /// Gains access to the `DataRestore` schema.
     pub fn data_restore_schema(&mut self) -> data_restore::DataRestoreSchema<'_, 'a> {
         data_restore::DataRestoreSchema(self)
     }
 
+    /// Gains access to the `DepositAggregates` schema.
+    pub fn deposit_aggregates_schema(
+        &mut self,
+    ) -> deposit_aggregates::DepositAggregatesSchema<'_, 'a> {
+        deposit_aggregates::DepositAggregatesSchema(self)
+    }
+
+    /// Gains access to the `DepositRelay` schema.
+    pub fn deposit_relay_schema(&mut self) -> deposit_relay::DepositRelaySchema<'_, 'a> {
+        deposit_relay::DepositRelaySchema(self)
+    }
+
+    /// Gains access to the `DroppedTxs` schema.
+    pub fn dropped_txs_schema(&mut self) -> dropped_txs::DroppedTxsSchema<'_, 'a> {
+        dropped_txs::DroppedTxsSchema(self)
+    }
+
+    /// Gains access to the `EIP1271Verifications` schema.
+    pub fn eip1271_verifications_schema(
+        &mut self,
+    ) -> eip1271_verifications::EIP1271VerificationsSchema<'_, 'a> {
+        eip1271_verifications::EIP1271VerificationsSchema(self)
+    }
+
     /// Gains access to the `Ethereum` schema.
     pub fn ethereum_schema(&mut self) -> ethereum::EthereumSchema<'_, 'a> {
         ethereum::EthereumSchema(self)
     }
 
+    /// Gains access to the `ExitProofs` schema.
+    pub fn exit_proofs_schema(&mut self) -> exit_proofs::ExitProofsSchema<'_, 'a> {
+        exit_proofs::ExitProofsSchema(self)
+    }
+
+    /// Gains access to the `FeeBreakdowns` schema.
+    pub fn fee_breakdowns_schema(&mut self) -> fee_breakdowns::FeeBreakdownsSchema<'_, 'a> {
+        fee_breakdowns::FeeBreakdownsSchema(self)
+    }
+
+    /// Gains access to the `History` schema.
+    pub fn history_schema(&mut self) -> history::HistorySchema<'_, 'a> {
+        history::HistorySchema(self)
+    }
+
     /// Gains access to the `Prover` schema.
     pub fn prover_schema(&mut self) -> prover::ProverSchema<'_, 'a> {
         prover::ProverSchema(self)
     }
 
+    /// Gains access to the `Pruning` schema.
+    pub fn pruning_schema(&mut self) -> pruning::PruningSchema<'_, 'a> {
+        pruning::PruningSchema(self)
+    }
+
     /// Gains access to the `Tokens` schema.
     pub fn tokens_schema(&mut self) -> tokens::TokensSchema<'_, 'a> {
         tokens::TokensSchema(self)
@@ -241,14 +402,123 @@ impl<'a> StorageProcessor<'a> {
         event::EventSchema(self)
     }
 
+    /// Gains access to the `WithdrawalHooks` schema.
+    pub fn withdrawal_hooks_schema(&mut self) -> withdrawal_hooks::WithdrawalHooksSchema<'_, 'a> {
+        withdrawal_hooks::WithdrawalHooksSchema(self)
+    }
+
+    /// Gains access to the `WithdrawalPolicy` schema.
+    pub fn withdrawal_policy_schema(
+        &mut self,
+    ) -> withdrawal_policy::WithdrawalPolicySchema<'_, 'a> {
+        withdrawal_policy::WithdrawalPolicySchema(self)
+    }
+
+    /// Gains access to the `Jobs` schema.
+    pub fn jobs_schema(&mut self) -> jobs::JobsSchema<'_, 'a> {
+        jobs::JobsSchema(self)
+    }
+
+    /// Gains access to the `Leadership` schema.
+    pub fn leadership_schema(&mut self) -> leadership::LeadershipSchema<'_, 'a> {
+        leadership::LeadershipSchema(self)
+    }
+
+    /// Gains access to the `NonceReservations` schema.
+    pub fn nonce_reservations_schema(
+        &mut self,
+    ) -> nonce_reservations::NonceReservationsSchema<'_, 'a> {
+        nonce_reservations::NonceReservationsSchema(self)
+    }
+
+    /// Gains access to the `Labels` schema.
+    pub fn labels_schema(&mut self) -> labels::LabelsSchema<'_, 'a> {
+        labels::LabelsSchema(self)
+    }
+
     pub fn withdrawals_schema(&mut self) -> withdrawals::WithdrawalsSchema<'_, 'a> {
         withdrawals::WithdrawalsSchema(self)
     }
 
+    /// Gains access to the `MassPayouts` schema.
+    pub fn mass_payouts_schema(&mut self) -> mass_payouts::MassPayoutsSchema<'_, 'a> {
+        mass_payouts::MassPayoutsSchema(self)
+    }
+
+    /// Gains access to the `Migrations` schema.
+    pub fn migrations_schema(&mut self) -> migrations::MigrationsSchema<'_, 'a> {
+        migrations::MigrationsSchema(self)
+    }
+
     pub fn misc_schema(&mut self) -> misc::MiscSchema<'_, 'a> {
         misc::MiscSchema(self)
     }
 
+    /// Gains access to the `OrderBook` schema.
+    pub fn order_book_schema(&mut self) -> order_book::OrderBookSchema<'_, 'a> {
+        order_book::OrderBookSchema(self)
+    }
+
+    /// Gains access to the `Outbox` schema.
+    pub fn outbox_schema(&mut self) -> outbox::OutboxSchema<'_, 'a> {
+        outbox::OutboxSchema(self)
+    }
+
+    /// Gains access to the `PaymentRequests` schema.
+    pub fn payment_requests_schema(&mut self) -> payment_requests::PaymentRequestsSchema<'_, 'a> {
+        payment_requests::PaymentRequestsSchema(self)
+    }
+
+    /// Gains access to the `Reports` schema.
+    pub fn reports_schema(&mut self) -> reports::ReportsSchema<'_, 'a> {
+        reports::ReportsSchema(self)
+    }
+
+    /// Gains access to the `Revenue` schema.
+    pub fn revenue_schema(&mut self) -> revenue::RevenueSchema<'_, 'a> {
+        revenue::RevenueSchema(self)
+    }
+
+    /// Gains access to the `RuntimeConfig` schema.
+    pub fn runtime_config_schema(&mut self) -> runtime_config::RuntimeConfigSchema<'_, 'a> {
+        runtime_config::RuntimeConfigSchema(self)
+    }
+
+    /// Gains access to the `Screening` schema.
+    pub fn screening_schema(&mut self) -> screening::ScreeningSchema<'_, 'a> {
+        screening::ScreeningSchema(self)
+    }
+
+    /// Gains access to the `SessionKeys` schema.
+    pub fn session_keys_schema(&mut self) -> session_keys::SessionKeysSchema<'_, 'a> {
+        session_keys::SessionKeysSchema(self)
+    }
+
+    /// Gains access to the `SlowQueries` schema.
+    pub fn slow_queries_schema(&mut self) -> slow_queries::SlowQueriesSchema<'_, 'a> {
+        slow_queries::SlowQueriesSchema(self)
+    }
+
+    /// Gains access to the `Sponsorship` schema.
+    pub fn sponsorship_schema(&mut self) -> sponsorship::SponsorshipSchema<'_, 'a> {
+        sponsorship::SponsorshipSchema(self)
+    }
+
+    /// Gains access to the `StandingOrders` schema.
+    pub fn standing_orders_schema(&mut self) -> standing_orders::StandingOrdersSchema<'_, 'a> {
+        standing_orders::StandingOrdersSchema(self)
+    }
+
+    /// Gains access to the `TxCallbacks` schema.
+    pub fn tx_callbacks_schema(&mut self) -> tx_callbacks::TxCallbacksSchema<'_, 'a> {
+        tx_callbacks::TxCallbacksSchema(self)
+    }
+
+    /// Gains access to the `TxMemos` schema.
+    pub fn tx_memos_schema(&mut self) -> tx_memos::TxMemosSchema<'_, 'a> {
+        tx_memos::TxMemosSchema(self)
+    }
+
     fn conn(&mut self) -> &mut PgConnection {
         match &mut self.conn {
             ConnectionHolder::Pooled(conn) => conn,
@@ -0,0 +1,242 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use num::BigUint;
+use serde_json::Value;
+// Workspace imports
+use zksync_types::{tx::TxHash, Address, Nonce, TokenId};
+use zksync_utils::biguint_to_big_decimal;
+// Local imports
+use self::records::{StoredMassPayout, StoredMassPayoutBatch, StoredMassPayoutRecipient};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Transfer to the recipient is not submitted yet.
+pub const RECIPIENT_STATUS_PENDING: &str = "pending";
+/// Batch with the transfer to the recipient is submitted to the server.
+pub const RECIPIENT_STATUS_SUBMITTED: &str = "submitted";
+/// Transfer to the recipient is executed.
+pub const RECIPIENT_STATUS_EXECUTED: &str = "executed";
+/// Batch with the transfer to the recipient has failed.
+pub const RECIPIENT_STATUS_FAILED: &str = "failed";
+
+/// Schema of the payouts performed by the `mass_payout` tool.
+///
+/// The signed batch is stored along with the hashes of its transfers before it's submitted,
+/// so the tool restarted at any moment can resubmit the same batch instead of paying twice.
+#[derive(Debug)]
+pub struct MassPayoutsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> MassPayoutsSchema<'a, 'c> {
+    /// Stores the payout with its pending recipients, returns its id.
+    #[tracing::instrument(skip_all, fields(schema = "mass_payouts"))]
+    pub async fn create_payout(
+        &mut self,
+        name: &str,
+        sender: Address,
+        recipients: &[(Address, TokenId, BigUint)],
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let id = sqlx::query!(
+            "INSERT INTO mass_payouts (name, sender) VALUES ($1, $2) RETURNING id",
+            name,
+            sender.as_bytes(),
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .id;
+
+        let addresses: Vec<_> = recipients
+            .iter()
+            .map(|(address, ..)| address.as_bytes().to_vec())
+            .collect();
+        let token_ids: Vec<_> = recipients
+            .iter()
+            .map(|(_, token_id, _)| **token_id as i32)
+            .collect();
+        let amounts: Vec<_> = recipients
+            .iter()
+            .map(|(.., amount)| biguint_to_big_decimal(amount.clone()))
+            .collect();
+        sqlx::query!(
+            "INSERT INTO mass_payout_recipients (payout_id, idx, address, token_id, amount)
+            SELECT $1, u.idx - 1, u.address, u.token_id, u.amount
+            FROM UNNEST($2::bytea[], $3::integer[], $4::numeric[])
+                WITH ORDINALITY AS u(address, token_id, amount, idx)",
+            id,
+            &addresses,
+            &token_ids,
+            &amounts,
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("mass_payouts", "create_payout", start);
+        Ok(id)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "mass_payouts"))]
+    pub async fn get_payout(&mut self, name: &str) -> QueryResult<Option<StoredMassPayout>> {
+        let start = Instant::now();
+        let payout = sqlx::query_as!(
+            StoredMassPayout,
+            "SELECT * FROM mass_payouts WHERE name = $1",
+            name,
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("mass_payouts", "get_payout", start);
+        Ok(payout)
+    }
+
+    /// Loads the recipients of the payout in the order of the input list.
+    #[tracing::instrument(skip_all, fields(schema = "mass_payouts"))]
+    pub async fn load_recipients(
+        &mut self,
+        payout_id: i64,
+    ) -> QueryResult<Vec<StoredMassPayoutRecipient>> {
+        let start = Instant::now();
+        let recipients = sqlx::query_as!(
+            StoredMassPayoutRecipient,
+            "SELECT * FROM mass_payout_recipients WHERE payout_id = $1 ORDER BY idx",
+            payout_id,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("mass_payouts", "load_recipients", start);
+        Ok(recipients)
+    }
+
+    /// Stores the signed batch and marks the recipients of its transfers as submitted,
+    /// returns the id of the batch. `transfers` are the indices of the recipients
+    /// paired with the hashes of the transfers to them.
+    #[tracing::instrument(skip_all, fields(schema = "mass_payouts"))]
+    pub async fn store_batch(
+        &mut self,
+        payout_id: i64,
+        nonce: Nonce,
+        txs: &Value,
+        eth_signature: Option<&Value>,
+        transfers: &[(i32, TxHash)],
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let batch_id = sqlx::query!(
+            "INSERT INTO mass_payout_batches (payout_id, nonce, txs, eth_signature)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id",
+            payout_id,
+            *nonce as i64,
+            txs,
+            eth_signature,
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .id;
+
+        let indices: Vec<_> = transfers.iter().map(|(idx, _)| *idx).collect();
+        let tx_hashes: Vec<_> = transfers
+            .iter()
+            .map(|(_, tx_hash)| tx_hash.as_ref().to_vec())
+            .collect();
+        sqlx::query!(
+            "UPDATE mass_payout_recipients
+            SET status = 'submitted', batch_id = $2, tx_hash = u.tx_hash, error = NULL,
+                updated_at = now()
+            FROM UNNEST($3::integer[], $4::bytea[]) AS u(idx, tx_hash)
+            WHERE mass_payout_recipients.payout_id = $1
+                AND mass_payout_recipients.idx = u.idx
+                AND mass_payout_recipients.status = 'pending'",
+            payout_id,
+            batch_id,
+            &indices,
+            &tx_hashes,
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("mass_payouts", "store_batch", start);
+        Ok(batch_id)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "mass_payouts"))]
+    pub async fn get_batch(&mut self, id: i64) -> QueryResult<Option<StoredMassPayoutBatch>> {
+        let start = Instant::now();
+        let batch = sqlx::query_as!(
+            StoredMassPayoutBatch,
+            "SELECT * FROM mass_payout_batches WHERE id = $1",
+            id,
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("mass_payouts", "get_batch", start);
+        Ok(batch)
+    }
+
+    /// Records the result of the executed batch for all of its recipients,
+    /// since the transactions of the batch either succeed or fail together.
+    #[tracing::instrument(skip_all, fields(schema = "mass_payouts"))]
+    pub async fn complete_batch(&mut self, batch_id: i64, error: Option<&str>) -> QueryResult<()> {
+        let start = Instant::now();
+        let status = if error.is_some() {
+            RECIPIENT_STATUS_FAILED
+        } else {
+            RECIPIENT_STATUS_EXECUTED
+        };
+        sqlx::query!(
+            "UPDATE mass_payout_recipients SET status = $2, error = $3, updated_at = now()
+            WHERE batch_id = $1 AND status = 'submitted'",
+            batch_id,
+            status,
+            error,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("mass_payouts", "complete_batch", start);
+        Ok(())
+    }
+
+    /// Returns the recipients of the batch, which will never be executed, to the pending ones.
+    #[tracing::instrument(skip_all, fields(schema = "mass_payouts"))]
+    pub async fn discard_batch(&mut self, batch_id: i64, error: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE mass_payout_recipients
+            SET status = 'pending', batch_id = NULL, tx_hash = NULL, error = $2, updated_at = now()
+            WHERE batch_id = $1 AND status = 'submitted'",
+            batch_id,
+            error,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("mass_payouts", "discard_batch", start);
+        Ok(())
+    }
+
+    /// Returns the failed recipients of the payout to the pending ones, so they are paid
+    /// once the cause of the failure is fixed. Returns the amount of such recipients.
+    #[tracing::instrument(skip_all, fields(schema = "mass_payouts"))]
+    pub async fn retry_failed_recipients(&mut self, payout_id: i64) -> QueryResult<u64> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "UPDATE mass_payout_recipients
+            SET status = 'pending', batch_id = NULL, tx_hash = NULL, updated_at = now()
+            WHERE payout_id = $1 AND status = 'failed'",
+            payout_id,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("mass_payouts", "retry_failed_recipients", start);
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,38 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredMassPayout {
+    pub id: i64,
+    pub name: String,
+    pub sender: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredMassPayoutBatch {
+    pub id: i64,
+    pub payout_id: i64,
+    pub nonce: i64,
+    pub txs: Value,
+    pub eth_signature: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredMassPayoutRecipient {
+    pub payout_id: i64,
+    pub idx: i32,
+    pub address: Vec<u8>,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub status: String,
+    pub batch_id: Option<i64>,
+    pub tx_hash: Option<Vec<u8>>,
+    pub error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,335 @@
+//! Data migrations performed online in small batches.
+//!
+//! Every batch is processed within its own transaction along with the update of the stored
+//! progress, so the backfill can be interrupted at any moment and resumed later from the last
+//! committed batch. The progress of the backfills is available via the core private API.
+
+// Built-in deps
+use std::time::Duration;
+// External imports
+use async_trait::async_trait;
+// Workspace imports
+// Local imports
+use crate::canonical_json::canonicalize_stored_tx;
+use crate::{ConnectionPool, QueryResult, StorageProcessor};
+
+/// Result of the processed batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackfillBatch {
+    /// Amount of the processed rows, only used to report the progress.
+    pub processed_rows: u64,
+    /// Position after the batch, `None` if there is nothing left to process.
+    pub next_cursor: Option<String>,
+}
+
+/// Data migration processing the rows in batches.
+#[async_trait]
+pub trait Backfill: Send + Sync {
+    /// Unique name of the backfill, its progress is stored under this name.
+    fn name(&self) -> &'static str;
+
+    /// Estimates the amount of rows to process, used to report the progress.
+    async fn estimate_rows(&self, storage: &mut StorageProcessor<'_>) -> QueryResult<Option<u64>>;
+
+    /// Processes at most `batch_size` rows after the cursor, `None` cursor means that
+    /// the first batch is processed.
+    async fn process_batch(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        cursor: Option<&str>,
+        batch_size: u32,
+    ) -> QueryResult<BackfillBatch>;
+}
+
+/// Runs the backfill until all the rows are processed, sleeping for `batch_delay` between the
+/// batches to limit the database load. Continues from the last stored cursor if the backfill was
+/// interrupted, and does nothing if it's already finished.
+pub async fn run_backfill(
+    pool: &ConnectionPool,
+    backfill: &dyn Backfill,
+    batch_size: u32,
+    batch_delay: Duration,
+) -> QueryResult<()> {
+    let name = backfill.name();
+    let mut storage = pool.access_storage().await?;
+    let progress = storage
+        .migrations_schema()
+        .load_backfill_progress(name)
+        .await?;
+    let mut cursor = match progress {
+        Some(progress) if progress.finished_at.is_some() => {
+            vlog::info!("Backfill {} is already finished", name);
+            return Ok(());
+        }
+        Some(progress) => progress.cursor,
+        None => {
+            let estimated_rows = backfill.estimate_rows(&mut storage).await?;
+            storage
+                .migrations_schema()
+                .start_backfill(name, estimated_rows)
+                .await?;
+            None
+        }
+    };
+    drop(storage);
+
+    vlog::info!("Running backfill {} from cursor {:?}", name, cursor);
+    loop {
+        let mut storage = pool.access_storage().await?;
+        let mut transaction = storage.start_transaction().await?;
+        let batch = backfill
+            .process_batch(&mut transaction, cursor.as_deref(), batch_size)
+            .await?;
+        transaction
+            .migrations_schema()
+            .save_backfill_batch(name, batch.next_cursor.as_deref(), batch.processed_rows)
+            .await?;
+        transaction.commit().await?;
+
+        metrics::counter!("backfill.processed_rows", batch.processed_rows, "backfill" => name);
+        cursor = match batch.next_cursor {
+            Some(next_cursor) => Some(next_cursor),
+            None => break,
+        };
+        tokio::time::sleep(batch_delay).await;
+    }
+
+    vlog::info!("Backfill {} is finished", name);
+    Ok(())
+}
+
+/// Fills the `txs_count` table with the amount of transactions of every account from the
+/// `tx_filters` table. Cursor is the hex-encoded address of the last processed account.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TxsCountBackfill;
+
+#[async_trait]
+impl Backfill for TxsCountBackfill {
+    fn name(&self) -> &'static str {
+        "txs_count"
+    }
+
+    /// Uses the planner statistics, since counting the distinct addresses takes too long.
+    async fn estimate_rows(&self, storage: &mut StorageProcessor<'_>) -> QueryResult<Option<u64>> {
+        let estimation = sqlx::query_scalar!(
+            r#"
+            SELECT (CASE
+                WHEN n_distinct >= 0 THEN n_distinct
+                ELSE -n_distinct * reltuples
+            END)::bigint AS "estimation!"
+            FROM pg_stats
+            INNER JOIN pg_class ON relname = tablename
+                AND relnamespace = 'public'::regnamespace
+            WHERE schemaname = 'public' AND tablename = 'tx_filters' AND attname = 'address'
+            "#
+        )
+        .fetch_optional(storage.conn())
+        .await?;
+        Ok(estimation.map(|estimation| estimation as u64))
+    }
+
+    async fn process_batch(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        cursor: Option<&str>,
+        batch_size: u32,
+    ) -> QueryResult<BackfillBatch> {
+        // Empty byte string precedes any address.
+        let start_address = cursor.map(hex::decode).transpose()?.unwrap_or_default();
+        let range = sqlx::query!(
+            r#"
+            SELECT address AS last_address, COUNT(*) OVER () AS "accounts!"
+            FROM (
+                SELECT DISTINCT address FROM tx_filters
+                WHERE address > $1
+                ORDER BY address
+                LIMIT $2
+            ) AS batch
+            ORDER BY address DESC
+            LIMIT 1
+            "#,
+            start_address,
+            i64::from(batch_size)
+        )
+        .fetch_optional(storage.conn())
+        .await?;
+        let (last_address, accounts) = match range {
+            Some(range) => (range.last_address, range.accounts),
+            None => {
+                return Ok(BackfillBatch {
+                    processed_rows: 0,
+                    next_cursor: None,
+                })
+            }
+        };
+
+        sqlx::query!(
+            r#"
+            INSERT INTO txs_count (address, token, count)
+            SELECT address, token, COUNT(DISTINCT tx_hash)
+            FROM tx_filters
+            WHERE address > $1 AND address <= $2
+            GROUP BY (address, token)
+            ON CONFLICT (address, token) DO UPDATE SET count = EXCLUDED.count
+            "#,
+            start_address,
+            last_address
+        )
+        .execute(storage.conn())
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO txs_count (address, token, count)
+            SELECT address, -1, COUNT(DISTINCT tx_hash)
+            FROM tx_filters
+            WHERE address > $1 AND address <= $2
+            GROUP BY (address)
+            ON CONFLICT (address, token) DO UPDATE SET count = EXCLUDED.count
+            "#,
+            start_address,
+            last_address
+        )
+        .execute(storage.conn())
+        .await?;
+
+        Ok(BackfillBatch {
+            processed_rows: accounts as u64,
+            next_cursor: Some(hex::encode(last_address)),
+        })
+    }
+}
+
+/// Table rewritten by the [`CanonicalTxsBackfill`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoredTxsTable {
+    ExecutedTransactions,
+    MempoolTxs,
+}
+
+/// Rewrites the transactions stored in the table to their canonical form, see the
+/// [`canonical_json`](crate::canonical_json) module. Cursor is the hex-encoded hash of the last
+/// processed transaction for `executed_transactions` and its id for `mempool_txs`.
+#[derive(Debug, Clone, Copy)]
+pub struct CanonicalTxsBackfill(pub StoredTxsTable);
+
+impl CanonicalTxsBackfill {
+    async fn process_executed_txs(
+        storage: &mut StorageProcessor<'_>,
+        cursor: Option<&str>,
+        batch_size: u32,
+    ) -> QueryResult<BackfillBatch> {
+        let start_hash = cursor.map(hex::decode).transpose()?.unwrap_or_default();
+        let rows = sqlx::query!(
+            "SELECT tx_hash, tx FROM executed_transactions
+            WHERE tx_hash > $1
+            ORDER BY tx_hash
+            LIMIT $2",
+            start_hash,
+            i64::from(batch_size)
+        )
+        .fetch_all(storage.conn())
+        .await?;
+
+        let (tx_hashes, txs): (Vec<_>, Vec<_>) = rows
+            .iter()
+            .filter_map(|row| Some((row.tx_hash.clone(), canonicalize_stored_tx(&row.tx)?)))
+            .unzip();
+        sqlx::query!(
+            "UPDATE executed_transactions SET tx = u.tx
+            FROM UNNEST($1::bytea[], $2::jsonb[]) AS u(tx_hash, tx)
+            WHERE executed_transactions.tx_hash = u.tx_hash",
+            &tx_hashes,
+            &txs
+        )
+        .execute(storage.conn())
+        .await?;
+
+        Ok(BackfillBatch {
+            processed_rows: rows.len() as u64,
+            next_cursor: rows.last().map(|row| hex::encode(&row.tx_hash)),
+        })
+    }
+
+    async fn process_mempool_txs(
+        storage: &mut StorageProcessor<'_>,
+        cursor: Option<&str>,
+        batch_size: u32,
+    ) -> QueryResult<BackfillBatch> {
+        let start_id: i64 = cursor.map(str::parse).transpose()?.unwrap_or(0);
+        let rows = sqlx::query!(
+            "SELECT id, tx FROM mempool_txs
+            WHERE id > $1
+            ORDER BY id
+            LIMIT $2",
+            start_id,
+            i64::from(batch_size)
+        )
+        .fetch_all(storage.conn())
+        .await?;
+
+        let (ids, txs): (Vec<_>, Vec<_>) = rows
+            .iter()
+            .filter_map(|row| Some((row.id, canonicalize_stored_tx(&row.tx)?)))
+            .unzip();
+        sqlx::query!(
+            "UPDATE mempool_txs SET tx = u.tx
+            FROM UNNEST($1::bigint[], $2::jsonb[]) AS u(id, tx)
+            WHERE mempool_txs.id = u.id",
+            &ids,
+            &txs
+        )
+        .execute(storage.conn())
+        .await?;
+
+        Ok(BackfillBatch {
+            processed_rows: rows.len() as u64,
+            next_cursor: rows.last().map(|row| row.id.to_string()),
+        })
+    }
+}
+
+#[async_trait]
+impl Backfill for CanonicalTxsBackfill {
+    fn name(&self) -> &'static str {
+        match self.0 {
+            StoredTxsTable::ExecutedTransactions => "canonical_executed_transactions",
+            StoredTxsTable::MempoolTxs => "canonical_mempool_txs",
+        }
+    }
+
+    async fn estimate_rows(&self, storage: &mut StorageProcessor<'_>) -> QueryResult<Option<u64>> {
+        let table = match self.0 {
+            StoredTxsTable::ExecutedTransactions => "executed_transactions",
+            StoredTxsTable::MempoolTxs => "mempool_txs",
+        };
+        let estimation = sqlx::query_scalar!(
+            r#"
+            SELECT reltuples::bigint AS "estimation!" FROM pg_class
+            WHERE relname = $1 AND relnamespace = 'public'::regnamespace
+            "#,
+            table
+        )
+        .fetch_optional(storage.conn())
+        .await?;
+        // Tables which were never analyzed have negative estimation.
+        Ok(estimation
+            .filter(|estimation| *estimation >= 0)
+            .map(|estimation| estimation as u64))
+    }
+
+    async fn process_batch(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        cursor: Option<&str>,
+        batch_size: u32,
+    ) -> QueryResult<BackfillBatch> {
+        match self.0 {
+            StoredTxsTable::ExecutedTransactions => {
+                Self::process_executed_txs(storage, cursor, batch_size).await
+            }
+            StoredTxsTable::MempoolTxs => {
+                Self::process_mempool_txs(storage, cursor, batch_size).await
+            }
+        }
+    }
+}
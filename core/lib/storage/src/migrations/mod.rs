@@ -0,0 +1,316 @@
+//! Tooling for the database schema migrations.
+//!
+//! Migrations are applied by `diesel`, each one within its own transaction, so the locks taken
+//! by the statements of the migration are held until all of them are executed. Before applying
+//! the migrations to the production database, they can be checked with
+//! [`MigrationsSchema::dry_run`] against its copy: the pending migrations are executed within
+//! a transaction which is rolled back afterwards, and the report contains the execution time of
+//! every migration along with the locks it takes, which is the estimation of the time the
+//! affected tables will be unavailable.
+//!
+//! Data migrations which are too long to be performed within a single transaction, e.g. filling
+//! a new column of a large table, are implemented as the [`backfill::Backfill`]s. They are run
+//! online in small batches, and their progress is stored to the database.
+
+// Built-in deps
+use std::{
+    collections::HashSet,
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+// External imports
+use anyhow::Context;
+use sqlx::Executor;
+// Workspace imports
+// Local imports
+use self::records::{StoredBackfillProgress, StoredRelationLock};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod backfill;
+pub mod records;
+
+/// Migration from the `migrations` directory of the storage crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    /// Version as it's stored by `diesel`, i.e. the digits of the directory name prefix.
+    pub version: String,
+    /// Directory name of the migration.
+    pub name: String,
+    pub up_sql: String,
+}
+
+impl Migration {
+    /// Loads the migrations from the directory in the order they are applied.
+    pub fn load_dir(dir: &Path) -> anyhow::Result<Vec<Self>> {
+        let mut migrations = Vec::new();
+        for entry in fs::read_dir(dir).with_context(|| format!("Can't read {:?}", dir))? {
+            let path = entry?.path();
+            let up_sql_path = path.join("up.sql");
+            if !up_sql_path.is_file() {
+                continue;
+            }
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .with_context(|| format!("Invalid migration directory {:?}", path))?
+                .to_string();
+            let version = name
+                .split('_')
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .filter(char::is_ascii_digit)
+                .collect();
+            let up_sql = fs::read_to_string(&up_sql_path)
+                .with_context(|| format!("Can't read {:?}", up_sql_path))?;
+            migrations.push(Self {
+                version,
+                name,
+                up_sql,
+            });
+        }
+        migrations.sort_by(|a, b| a.version.cmp(&b.version));
+        Ok(migrations)
+    }
+}
+
+/// Effect of the lock on the queries of the other connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LockImpact {
+    /// Only the concurrent schema changes and maintenance are blocked.
+    None,
+    /// Inserts, updates and deletes of the table are blocked.
+    BlocksWrites,
+    /// Any access to the table is blocked.
+    BlocksReads,
+}
+
+impl LockImpact {
+    /// Returns the impact of the Postgres table-level lock mode.
+    pub fn of(mode: &str) -> Self {
+        match mode {
+            "AccessExclusiveLock" => Self::BlocksReads,
+            "ShareLock" | "ShareRowExclusiveLock" | "ExclusiveLock" => Self::BlocksWrites,
+            _ => Self::None,
+        }
+    }
+}
+
+/// Table-level lock taken by the migration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationLock {
+    pub relation: String,
+    pub mode: String,
+    pub impact: LockImpact,
+}
+
+/// Result of the pending migration executed by the dry run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationDryRun {
+    pub version: String,
+    pub name: String,
+    /// Execution time of the whole migration.
+    pub duration: Duration,
+    /// Locks taken by the migration, the strongest ones first. All of them are held until
+    /// the migration is committed, so `duration` is the upper bound of the time they are held.
+    pub locks: Vec<MigrationLock>,
+    /// Error of the failed migration, it's always the last one in the report.
+    pub error: Option<String>,
+}
+
+impl MigrationDryRun {
+    /// Returns the strongest impact of the locks taken by the migration.
+    pub fn impact(&self) -> LockImpact {
+        self.locks
+            .iter()
+            .map(|lock| lock.impact)
+            .max()
+            .unwrap_or(LockImpact::None)
+    }
+}
+
+/// Schema of the migrations tooling.
+#[derive(Debug)]
+pub struct MigrationsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> MigrationsSchema<'a, 'c> {
+    /// Loads the versions of the migrations applied by `diesel`.
+    #[tracing::instrument(skip_all, fields(schema = "migrations"))]
+    pub async fn load_applied_versions(&mut self) -> QueryResult<HashSet<String>> {
+        let start = Instant::now();
+        let versions = sqlx::query_scalar!("SELECT version FROM __diesel_schema_migrations")
+            .fetch_all(self.0.conn())
+            .await?
+            .into_iter()
+            .collect();
+
+        crate::slow_queries::report_query("migrations", "load_applied_versions", start);
+        Ok(versions)
+    }
+
+    /// Executes the migrations which are not applied yet and rolls them back.
+    ///
+    /// Must only be used with a copy of the database: the statements are actually executed,
+    /// so the tables they lock are unavailable until the dry run is finished. The dry run
+    /// stops at the first failed migration.
+    #[tracing::instrument(skip_all, fields(schema = "migrations"))]
+    pub async fn dry_run(&mut self, migrations: &[Migration]) -> QueryResult<Vec<MigrationDryRun>> {
+        let start = Instant::now();
+        let applied_versions = self.load_applied_versions().await?;
+        let mut transaction = self.0.start_transaction().await?;
+
+        let mut reports = Vec::new();
+        let pending_migrations = migrations
+            .iter()
+            .filter(|migration| !applied_versions.contains(&migration.version));
+        for migration in pending_migrations {
+            let locks_before = transaction.migrations_schema().load_held_locks().await?;
+            let migration_start = Instant::now();
+            let result = transaction.conn().execute(migration.up_sql.as_str()).await;
+            let duration = migration_start.elapsed();
+
+            let (locks, error) = match result {
+                Ok(_) => {
+                    let mut locks: Vec<_> = transaction
+                        .migrations_schema()
+                        .load_held_locks()
+                        .await?
+                        .difference(&locks_before)
+                        .map(|lock| MigrationLock {
+                            relation: lock.relation.clone(),
+                            mode: lock.mode.clone(),
+                            impact: LockImpact::of(&lock.mode),
+                        })
+                        .collect();
+                    locks.sort_by(|a, b| {
+                        b.impact
+                            .cmp(&a.impact)
+                            .then_with(|| a.relation.cmp(&b.relation))
+                    });
+                    (locks, None)
+                }
+                Err(err) => (Vec::new(), Some(err.to_string())),
+            };
+            let failed = error.is_some();
+            reports.push(MigrationDryRun {
+                version: migration.version.clone(),
+                name: migration.name.clone(),
+                duration,
+                locks,
+                error,
+            });
+            if failed {
+                break;
+            }
+        }
+        // The transaction is not committed, so all the changes are rolled back once it's dropped.
+        drop(transaction);
+
+        crate::slow_queries::report_query("migrations", "dry_run", start);
+        Ok(reports)
+    }
+
+    /// Loads the table-level locks held by the current connection.
+    async fn load_held_locks(&mut self) -> QueryResult<HashSet<StoredRelationLock>> {
+        let locks = sqlx::query_as!(
+            StoredRelationLock,
+            r#"
+            SELECT relname AS "relation!", mode AS "mode!"
+            FROM pg_locks
+            INNER JOIN pg_class ON pg_class.oid = pg_locks.relation
+            WHERE pid = pg_backend_pid() AND locktype = 'relation' AND granted
+                AND relnamespace <> 'pg_catalog'::regnamespace
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .collect();
+        Ok(locks)
+    }
+
+    /// Loads the progress of the backfill, `None` if it was never started.
+    #[tracing::instrument(skip_all, fields(schema = "migrations"))]
+    pub async fn load_backfill_progress(
+        &mut self,
+        name: &str,
+    ) -> QueryResult<Option<StoredBackfillProgress>> {
+        let start = Instant::now();
+        let progress = sqlx::query_as!(
+            StoredBackfillProgress,
+            "SELECT * FROM backfill_progress WHERE name = $1",
+            name
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("migrations", "load_backfill_progress", start);
+        Ok(progress)
+    }
+
+    /// Loads the progress of all the started backfills, the recently started ones first.
+    #[tracing::instrument(skip_all, fields(schema = "migrations"))]
+    pub async fn load_backfills_progress(&mut self) -> QueryResult<Vec<StoredBackfillProgress>> {
+        let start = Instant::now();
+        let progress = sqlx::query_as!(
+            StoredBackfillProgress,
+            "SELECT * FROM backfill_progress ORDER BY started_at DESC, name"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("migrations", "load_backfills_progress", start);
+        Ok(progress)
+    }
+
+    /// Stores the progress of the backfill which is just started.
+    /// Does nothing if the backfill was already started.
+    #[tracing::instrument(skip_all, fields(schema = "migrations"))]
+    pub async fn start_backfill(
+        &mut self,
+        name: &str,
+        estimated_rows: Option<u64>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO backfill_progress (name, estimated_rows) VALUES ($1, $2)
+            ON CONFLICT (name) DO NOTHING",
+            name,
+            estimated_rows.map(|rows| rows as i64)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("migrations", "start_backfill", start);
+        Ok(())
+    }
+
+    /// Stores the cursor after the processed batch, `None` cursor marks the backfill as finished.
+    /// Must be called within the same transaction as the batch itself.
+    #[tracing::instrument(skip_all, fields(schema = "migrations"))]
+    pub async fn save_backfill_batch(
+        &mut self,
+        name: &str,
+        cursor: Option<&str>,
+        processed_rows: u64,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE backfill_progress
+            SET cursor = COALESCE($2, cursor),
+                processed_rows = processed_rows + $3,
+                updated_at = now(),
+                finished_at = CASE WHEN $2::text IS NULL THEN now() END
+            WHERE name = $1",
+            name,
+            cursor,
+            processed_rows as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("migrations", "save_backfill_batch", start);
+        Ok(())
+    }
+}
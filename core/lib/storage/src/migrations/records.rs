@@ -0,0 +1,22 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredBackfillProgress {
+    pub name: String,
+    pub cursor: Option<String>,
+    pub processed_rows: i64,
+    pub estimated_rows: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, FromRow)]
+pub struct StoredRelationLock {
+    pub relation: String,
+    pub mode: String,
+}
@@ -4,7 +4,7 @@ use std::time::Instant;
 use sqlx::types::BigDecimal;
 // Local imports
 
-use self::records::Subsidy;
+use self::records::{MaintenanceMode, Subsidy};
 use crate::{QueryResult, StorageProcessor};
 use num::ToPrimitive;
 
@@ -17,6 +17,7 @@ pub struct MiscSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> MiscSchema<'a, 'c> {
     /// Persists the new token in the database.
+    #[tracing::instrument(skip_all, fields(schema = "misc"))]
     pub async fn store_subsidy(&mut self, subsidy: Subsidy) -> QueryResult<()> {
         let start = Instant::now();
 
@@ -36,11 +37,12 @@ impl<'a, 'c> MiscSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.misc.store_subsidy", start.elapsed());
+        crate::slow_queries::report_query("misc", "store_subsidy", start);
         Ok(())
     }
 
     /// Loads tokens from the database starting from the given id with the given limit in the ascending order.
+    #[tracing::instrument(skip_all, fields(schema = "misc"))]
     pub async fn get_total_used_subsidy_for_type(
         &mut self,
         subsidy_type: &str,
@@ -61,7 +63,51 @@ impl<'a, 'c> MiscSchema<'a, 'c> {
         // It's better move this param to actor and check it periodically
         metrics::gauge!("tx_sender.store_subsidy_data.total_subsidy", sum.to_f64().unwrap_or_default(), "type" => subsidy_type.to_string());
 
-        metrics::histogram!("sql.token.get_total_used_subsidy_for_type", start.elapsed());
+        crate::slow_queries::report_query("misc", "get_total_used_subsidy_for_type", start);
         Ok(sum)
     }
+
+    /// Loads the maintenance mode of the server, `None` means that it's disabled.
+    #[tracing::instrument(skip_all, fields(schema = "misc"))]
+    pub async fn load_maintenance_mode(&mut self) -> QueryResult<Option<MaintenanceMode>> {
+        let start = Instant::now();
+        let maintenance_mode = sqlx::query_as!(
+            MaintenanceMode,
+            "SELECT reason, enabled_at FROM maintenance_mode"
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("misc", "load_maintenance_mode", start);
+        Ok(maintenance_mode)
+    }
+
+    /// Enables the maintenance mode. If it's already enabled, only the reason is updated.
+    #[tracing::instrument(skip_all, fields(schema = "misc"))]
+    pub async fn enable_maintenance_mode(&mut self, reason: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO maintenance_mode (reason) VALUES ($1)
+            ON CONFLICT (id) DO UPDATE SET reason = $1",
+            reason
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("misc", "enable_maintenance_mode", start);
+        Ok(())
+    }
+
+    /// Disables the maintenance mode. Returns `false` if it was not enabled.
+    #[tracing::instrument(skip_all, fields(schema = "misc"))]
+    pub async fn disable_maintenance_mode(&mut self) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!("DELETE FROM maintenance_mode")
+            .execute(self.0.conn())
+            .await?
+            .rows_affected();
+
+        crate::slow_queries::report_query("misc", "disable_maintenance_mode", start);
+        Ok(removed > 0)
+    }
 }
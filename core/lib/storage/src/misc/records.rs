@@ -1,5 +1,6 @@
 // External imports
-use sqlx::types::BigDecimal;
+use chrono::{DateTime, Utc};
+use sqlx::{types::BigDecimal, FromRow};
 // Workspace imports
 // Local imports
 use zksync_types::{tx::TxHash, TokenId};
@@ -13,3 +14,10 @@ pub struct Subsidy {
     pub full_cost_token: BigDecimal,
     pub subsidy_type: String,
 }
+
+/// Enabled maintenance mode of the server.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct MaintenanceMode {
+    pub reason: String,
+    pub enabled_at: DateTime<Utc>,
+}
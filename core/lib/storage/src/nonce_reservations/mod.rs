@@ -0,0 +1,110 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+use zksync_types::{AccountId, Nonce};
+// Local imports
+use self::records::StoredNonceReservation;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the nonce reservations: ranges of the account nonces reserved for a limited time,
+/// so the account transactions with these nonces can be submitted out of order.
+///
+/// Active reservations of the same account never overlap.
+#[derive(Debug)]
+pub struct NonceReservationsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> NonceReservationsSchema<'a, 'c> {
+    /// Reserves `count` nonces of the account following both `min_nonce` and the nonces
+    /// reserved by the active reservations of the account.
+    #[tracing::instrument(skip_all, fields(schema = "nonce_reservations"))]
+    pub async fn reserve_nonces(
+        &mut self,
+        account_id: AccountId,
+        min_nonce: Nonce,
+        count: u32,
+        expires_at: DateTime<Utc>,
+    ) -> QueryResult<StoredNonceReservation> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        // Concurrent reservations of the same account are serialized.
+        sqlx::query!(
+            r#"SELECT 1 AS "locked!" FROM pg_advisory_xact_lock(hashtext('nonce_reservations'), $1)"#,
+            *account_id as i32
+        )
+        .fetch_one(transaction.conn())
+        .await?;
+        sqlx::query!(
+            "DELETE FROM nonce_reservations WHERE account_id = $1 AND expires_at <= now()",
+            i64::from(*account_id)
+        )
+        .execute(transaction.conn())
+        .await?;
+        let last_reserved_nonce = sqlx::query!(
+            "SELECT MAX(last_nonce) AS last_nonce FROM nonce_reservations WHERE account_id = $1",
+            i64::from(*account_id)
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .last_nonce;
+
+        let first_nonce = last_reserved_nonce
+            .map_or(0, |nonce| nonce + 1)
+            .max(i64::from(*min_nonce));
+        let reservation = sqlx::query_as!(
+            StoredNonceReservation,
+            "INSERT INTO nonce_reservations (account_id, first_nonce, last_nonce, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *",
+            i64::from(*account_id),
+            first_nonce,
+            first_nonce + i64::from(count) - 1,
+            expires_at,
+        )
+        .fetch_one(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("nonce_reservations", "reserve_nonces", start);
+        Ok(reservation)
+    }
+
+    /// Loads the active reservations of the account, ordered by the reserved nonces.
+    #[tracing::instrument(skip_all, fields(schema = "nonce_reservations"))]
+    pub async fn get_account_reservations(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<Vec<StoredNonceReservation>> {
+        let start = Instant::now();
+        let reservations = sqlx::query_as!(
+            StoredNonceReservation,
+            "SELECT * FROM nonce_reservations
+            WHERE account_id = $1 AND expires_at > now()
+            ORDER BY first_nonce",
+            i64::from(*account_id)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("nonce_reservations", "get_account_reservations", start);
+        Ok(reservations)
+    }
+
+    /// Loads the active reservations of all the accounts.
+    #[tracing::instrument(skip_all, fields(schema = "nonce_reservations"))]
+    pub async fn load_active_reservations(&mut self) -> QueryResult<Vec<StoredNonceReservation>> {
+        let start = Instant::now();
+        let reservations = sqlx::query_as!(
+            StoredNonceReservation,
+            "SELECT * FROM nonce_reservations WHERE expires_at > now()"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("nonce_reservations", "load_active_reservations", start);
+        Ok(reservations)
+    }
+}
@@ -0,0 +1,15 @@
+// External imports
+use chrono::prelude::*;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredNonceReservation {
+    pub id: i64,
+    pub account_id: i64,
+    pub first_nonce: i64,
+    pub last_nonce: i64,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,329 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_api_types::v02::pagination::PaginationDirection;
+use zksync_types::{
+    tx::{TxEthSignature, TxHash},
+    AccountId, Order, Swap, TokenId,
+};
+use zksync_utils::biguint_to_big_decimal;
+// Local imports
+use self::records::StoredSwapOrder;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the order book: signed swap orders submitted by the users, so they can be
+/// matched into the `Swap` transactions by the order-matching service.
+///
+/// Order goes `open` -> `matched` once the swap containing it is accepted to the mempool,
+/// and `matched` -> `filled` once the swap is executed. Limit orders are opened again after
+/// every executed swap, as they can be filled by several swaps. Orders are opened again if
+/// the swap has failed, and only open orders can be cancelled.
+#[derive(Debug)]
+pub struct OrderBookSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> OrderBookSchema<'a, 'c> {
+    /// Stores the new open order. Returns `false` if the order is already stored.
+    #[tracing::instrument(skip_all, fields(schema = "order_book"))]
+    pub async fn store_order(
+        &mut self,
+        order: &Order,
+        eth_signature: Option<&TxEthSignature>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let valid_until = order.time_range.valid_until.min(i64::MAX as u64) as i64;
+        let result = sqlx::query!(
+            r#"INSERT INTO swap_orders (
+                order_hash, account_id, nonce, token_sell, token_buy, amount, valid_until, "order", eth_signature
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (order_hash) DO NOTHING"#,
+            order.hash().as_ref().to_vec(),
+            i64::from(*order.account_id),
+            i64::from(*order.nonce),
+            *order.token_sell as i32,
+            *order.token_buy as i32,
+            biguint_to_big_decimal(order.amount.clone()),
+            valid_until,
+            serde_json::to_value(order).expect("failed to serialize the order"),
+            eth_signature
+                .map(|signature| serde_json::to_value(signature)
+                    .expect("failed to serialize the order signature")),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("order_book", "store_order", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "order_book"))]
+    pub async fn get_order(&mut self, order_hash: TxHash) -> QueryResult<Option<StoredSwapOrder>> {
+        let start = Instant::now();
+        let order = sqlx::query_as!(
+            StoredSwapOrder,
+            "SELECT * FROM swap_orders WHERE order_hash = $1",
+            order_hash.as_ref().to_vec(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("order_book", "get_order", start);
+        Ok(order)
+    }
+
+    /// Cancels the open order. Returns `false` if the order is not open.
+    #[tracing::instrument(skip_all, fields(schema = "order_book"))]
+    pub async fn cancel_order(&mut self, order_hash: TxHash) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "UPDATE swap_orders SET status = 'cancelled', updated_at = now()
+            WHERE order_hash = $1 AND status = 'open'",
+            order_hash.as_ref().to_vec(),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("order_book", "cancel_order", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Marks the open orders as matched in the swap accepted to the mempool.
+    #[tracing::instrument(skip_all, fields(schema = "order_book"))]
+    pub async fn mark_orders_matched(
+        &mut self,
+        order_hashes: &[TxHash],
+        swap_tx_hash: TxHash,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let order_hashes: Vec<_> = order_hashes
+            .iter()
+            .map(|order_hash| order_hash.as_ref().to_vec())
+            .collect();
+        sqlx::query!(
+            "UPDATE swap_orders SET status = 'matched', swap_tx_hash = $2, updated_at = now()
+            WHERE order_hash = ANY($1) AND status = 'open'",
+            &order_hashes,
+            swap_tx_hash.as_ref().to_vec(),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("order_book", "mark_orders_matched", start);
+        Ok(())
+    }
+
+    /// Updates the orders matched in the executed swaps. The orders of the failed swaps
+    /// are opened again.
+    #[tracing::instrument(skip_all, fields(schema = "order_book"))]
+    pub async fn apply_executed_swaps(
+        &mut self,
+        swaps: &[(TxHash, Swap, bool)],
+    ) -> QueryResult<()> {
+        if swaps.is_empty() {
+            return Ok(());
+        }
+        let start = Instant::now();
+        let mut failed_orders = Vec::new();
+        let mut filled_orders = Vec::new();
+        let mut filled_amounts = Vec::new();
+        let mut swap_tx_hashes = Vec::new();
+        for (tx_hash, swap, success) in swaps {
+            let orders = [
+                (&swap.orders.0, &swap.amounts.0),
+                (&swap.orders.1, &swap.amounts.1),
+            ];
+            for (order, amount) in orders.iter() {
+                let order_hash = order.hash().as_ref().to_vec();
+                if *success {
+                    filled_orders.push(order_hash);
+                    filled_amounts.push(biguint_to_big_decimal((*amount).clone()));
+                    swap_tx_hashes.push(tx_hash.as_ref().to_vec());
+                } else {
+                    failed_orders.push(order_hash);
+                }
+            }
+        }
+
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query!(
+            "UPDATE swap_orders SET status = 'open', updated_at = now()
+            WHERE order_hash = ANY($1) AND status = 'matched'",
+            &failed_orders,
+        )
+        .execute(transaction.conn())
+        .await?;
+        // The same limit order may be filled by several swaps of the block.
+        sqlx::query!(
+            "UPDATE swap_orders
+            SET status = CASE WHEN swap_orders.amount = 0 THEN 'open' ELSE 'filled' END,
+                filled_amount = swap_orders.filled_amount + filled.amount,
+                swap_tx_hash = filled.swap_tx_hash,
+                updated_at = now()
+            FROM (
+                SELECT u.order_hash, sum(u.amount) AS amount,
+                    (array_agg(u.swap_tx_hash ORDER BY u.idx DESC))[1] AS swap_tx_hash
+                FROM UNNEST($1::bytea[], $2::numeric[], $3::bytea[])
+                    WITH ORDINALITY AS u(order_hash, amount, swap_tx_hash, idx)
+                GROUP BY u.order_hash
+            ) AS filled
+            WHERE swap_orders.order_hash = filled.order_hash
+                AND swap_orders.status IN ('open', 'matched')",
+            &filled_orders,
+            &filled_amounts,
+            &swap_tx_hashes,
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("order_book", "apply_executed_swaps", start);
+        Ok(())
+    }
+
+    /// Returns the id of the last open order selling `token_sell` for `token_buy`.
+    #[tracing::instrument(skip_all, fields(schema = "order_book"))]
+    pub async fn get_max_open_order_id(
+        &mut self,
+        token_sell: TokenId,
+        token_buy: TokenId,
+    ) -> QueryResult<Option<i64>> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            "SELECT max(id) FROM swap_orders
+            WHERE token_sell = $1 AND token_buy = $2 AND status = 'open'
+                AND valid_until >= EXTRACT(EPOCH FROM now())",
+            *token_sell as i32,
+            *token_buy as i32,
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .max;
+
+        crate::slow_queries::report_query("order_book", "get_max_open_order_id", start);
+        Ok(id)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "order_book"))]
+    pub async fn get_open_orders_count(
+        &mut self,
+        token_sell: TokenId,
+        token_buy: TokenId,
+    ) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            r#"SELECT count(*) as "count!" FROM swap_orders
+            WHERE token_sell = $1 AND token_buy = $2 AND status = 'open'
+                AND valid_until >= EXTRACT(EPOCH FROM now())"#,
+            *token_sell as i32,
+            *token_buy as i32,
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        crate::slow_queries::report_query("order_book", "get_open_orders_count", start);
+        Ok(count as u32)
+    }
+
+    /// Loads the page of the open orders selling `token_sell` for `token_buy`.
+    /// Expired orders are skipped.
+    #[tracing::instrument(skip_all, fields(schema = "order_book"))]
+    pub async fn get_open_orders_page(
+        &mut self,
+        token_sell: TokenId,
+        token_buy: TokenId,
+        from_id: i64,
+        limit: u32,
+        direction: PaginationDirection,
+    ) -> QueryResult<Vec<StoredSwapOrder>> {
+        let start = Instant::now();
+        let query = "SELECT * FROM swap_orders
+            WHERE token_sell = $1 AND token_buy = $2 AND status = 'open'
+                AND valid_until >= EXTRACT(EPOCH FROM now())";
+        let query = match direction {
+            PaginationDirection::Newer => {
+                format!("{} AND id >= $3 ORDER BY id LIMIT $4", query)
+            }
+            PaginationDirection::Older => {
+                format!("{} AND id <= $3 ORDER BY id DESC LIMIT $4", query)
+            }
+        };
+        let orders = sqlx::query_as(query.as_str())
+            .bind(*token_sell as i32)
+            .bind(*token_buy as i32)
+            .bind(from_id)
+            .bind(limit as i64)
+            .fetch_all(self.0.conn())
+            .await?;
+
+        crate::slow_queries::report_query("order_book", "get_open_orders_page", start);
+        Ok(orders)
+    }
+
+    /// Returns the id of the last order submitted by the account.
+    #[tracing::instrument(skip_all, fields(schema = "order_book"))]
+    pub async fn get_max_account_order_id(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<Option<i64>> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            "SELECT max(id) FROM swap_orders WHERE account_id = $1",
+            i64::from(*account_id),
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .max;
+
+        crate::slow_queries::report_query("order_book", "get_max_account_order_id", start);
+        Ok(id)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "order_book"))]
+    pub async fn get_account_orders_count(&mut self, account_id: AccountId) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            r#"SELECT count(*) as "count!" FROM swap_orders WHERE account_id = $1"#,
+            i64::from(*account_id),
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        crate::slow_queries::report_query("order_book", "get_account_orders_count", start);
+        Ok(count as u32)
+    }
+
+    /// Loads the page of the orders submitted by the account, regardless of their status.
+    #[tracing::instrument(skip_all, fields(schema = "order_book"))]
+    pub async fn get_account_orders_page(
+        &mut self,
+        account_id: AccountId,
+        from_id: i64,
+        limit: u32,
+        direction: PaginationDirection,
+    ) -> QueryResult<Vec<StoredSwapOrder>> {
+        let start = Instant::now();
+        let query = "SELECT * FROM swap_orders WHERE account_id = $1";
+        let query = match direction {
+            PaginationDirection::Newer => {
+                format!("{} AND id >= $2 ORDER BY id LIMIT $3", query)
+            }
+            PaginationDirection::Older => {
+                format!("{} AND id <= $2 ORDER BY id DESC LIMIT $3", query)
+            }
+        };
+        let orders = sqlx::query_as(query.as_str())
+            .bind(i64::from(*account_id))
+            .bind(from_id)
+            .bind(limit as i64)
+            .fetch_all(self.0.conn())
+            .await?;
+
+        crate::slow_queries::report_query("order_book", "get_account_orders_page", start);
+        Ok(orders)
+    }
+}
@@ -0,0 +1,28 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredSwapOrder {
+    pub id: i64,
+    pub order_hash: Vec<u8>,
+    pub account_id: i64,
+    pub nonce: i64,
+    pub token_sell: i32,
+    pub token_buy: i32,
+    pub amount: BigDecimal,
+    pub valid_until: i64,
+    /// Serialized signed `Order`.
+    pub order: Value,
+    /// Serialized `TxEthSignature` of the order, if provided.
+    pub eth_signature: Option<Value>,
+    pub status: String,
+    pub filled_amount: BigDecimal,
+    /// Last swap transaction the order was matched in.
+    pub swap_tx_hash: Option<Vec<u8>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
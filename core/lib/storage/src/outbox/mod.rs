@@ -0,0 +1,281 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+use zksync_types::event::outbox::{OutboxEvent, OutboxEventType};
+// Local imports
+use self::records::{
+    PendingWebhookDelivery, StoredWebhookDelivery, StoredWebhookSubscription, WebhookDeliveryStatus,
+};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the durable event outbox and the webhooks delivering its events.
+///
+/// Events are expected to be stored by other `storage` methods within the same
+/// database transaction as the change they describe. Every stored event is
+/// immediately scheduled for the delivery to all the matching webhook subscriptions,
+/// events without any matching subscription are not stored at all.
+#[derive(Debug)]
+pub struct OutboxSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> OutboxSchema<'a, 'c> {
+    /// Stores events in the outbox and schedules their delivery to the webhook subscribers.
+    /// Events no webhook is subscribed to are skipped.
+    #[tracing::instrument(skip_all, fields(schema = "outbox"))]
+    pub async fn store_outbox_events(&mut self, events: &[OutboxEvent]) -> QueryResult<()> {
+        let start = Instant::now();
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let event_types: Vec<_> = events
+            .iter()
+            .map(|event| event.event_type().to_string())
+            .collect();
+        let payloads: Vec<_> = events.iter().map(OutboxEvent::payload).collect();
+        sqlx::query!(
+            "
+            WITH new_events AS (
+                INSERT INTO event_outbox (event_type, payload)
+                SELECT u.event_type, u.payload
+                    FROM UNNEST ($1::text[], $2::jsonb[])
+                    AS u(event_type, payload)
+                    WHERE EXISTS (
+                        SELECT 1 FROM webhook_subscriptions
+                        WHERE cardinality(webhook_subscriptions.event_types) = 0
+                            OR u.event_type = ANY(webhook_subscriptions.event_types)
+                    )
+                RETURNING id, event_type
+            )
+            INSERT INTO webhook_deliveries (subscription_id, event_id, status)
+            SELECT webhook_subscriptions.id, new_events.id, $3
+                FROM new_events
+                INNER JOIN webhook_subscriptions
+                ON cardinality(webhook_subscriptions.event_types) = 0
+                    OR new_events.event_type = ANY(webhook_subscriptions.event_types)
+            ",
+            &event_types,
+            &payloads,
+            WebhookDeliveryStatus::Pending.to_string(),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("outbox", "store_outbox_events", start);
+        Ok(())
+    }
+
+    /// Registers a new webhook. Only the events stored after the registration are delivered to it.
+    /// Empty `event_types` means that events of all types are delivered.
+    #[tracing::instrument(skip_all, fields(schema = "outbox"))]
+    pub async fn add_webhook_subscription(
+        &mut self,
+        url: &str,
+        secret: &str,
+        event_types: &[OutboxEventType],
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let event_types: Vec<_> = event_types
+            .iter()
+            .map(|event_type| event_type.to_string())
+            .collect();
+        let id = sqlx::query!(
+            "INSERT INTO webhook_subscriptions (url, secret, event_types)
+            VALUES ($1, $2, $3)
+            RETURNING id",
+            url,
+            secret,
+            &event_types,
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        crate::slow_queries::report_query("outbox", "add_webhook_subscription", start);
+        Ok(id)
+    }
+
+    /// Removes the webhook along with its pending and dead deliveries.
+    /// Returns `false` if there is no such subscription.
+    #[tracing::instrument(skip_all, fields(schema = "outbox"))]
+    pub async fn remove_webhook_subscription(&mut self, subscription_id: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM webhook_subscriptions WHERE id = $1",
+            subscription_id
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        crate::slow_queries::report_query("outbox", "remove_webhook_subscription", start);
+        Ok(removed)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "outbox"))]
+    pub async fn load_webhook_subscriptions(
+        &mut self,
+    ) -> QueryResult<Vec<StoredWebhookSubscription>> {
+        let start = Instant::now();
+        let subscriptions = sqlx::query_as!(
+            StoredWebhookSubscription,
+            "SELECT * FROM webhook_subscriptions ORDER BY id ASC"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("outbox", "load_webhook_subscriptions", start);
+        Ok(subscriptions)
+    }
+
+    /// Loads the pending deliveries which should be attempted at the moment, oldest events first.
+    #[tracing::instrument(skip_all, fields(schema = "outbox"))]
+    pub async fn load_due_webhook_deliveries(
+        &mut self,
+        limit: u32,
+    ) -> QueryResult<Vec<PendingWebhookDelivery>> {
+        let start = Instant::now();
+        let deliveries = sqlx::query_as!(
+            PendingWebhookDelivery,
+            "
+            SELECT webhook_deliveries.id, webhook_deliveries.attempts,
+                webhook_subscriptions.url, webhook_subscriptions.secret,
+                event_outbox.id AS event_id, event_outbox.event_type,
+                event_outbox.payload, event_outbox.created_at
+            FROM webhook_deliveries
+            INNER JOIN webhook_subscriptions
+                ON webhook_subscriptions.id = webhook_deliveries.subscription_id
+            INNER JOIN event_outbox
+                ON event_outbox.id = webhook_deliveries.event_id
+            WHERE webhook_deliveries.status = $1 AND webhook_deliveries.next_attempt_at <= now()
+            ORDER BY event_outbox.id ASC
+            LIMIT $2
+            ",
+            WebhookDeliveryStatus::Pending.to_string(),
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("outbox", "load_due_webhook_deliveries", start);
+        Ok(deliveries)
+    }
+
+    /// Removes the successfully sent delivery.
+    #[tracing::instrument(skip_all, fields(schema = "outbox"))]
+    pub async fn confirm_webhook_delivery(&mut self, delivery_id: i64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!("DELETE FROM webhook_deliveries WHERE id = $1", delivery_id)
+            .execute(self.0.conn())
+            .await?;
+
+        crate::slow_queries::report_query("outbox", "confirm_webhook_delivery", start);
+        Ok(())
+    }
+
+    /// Records the failed delivery attempt. The delivery is retried at `retry_at`,
+    /// or moved to the dead letters if it's `None`.
+    #[tracing::instrument(skip_all, fields(schema = "outbox"))]
+    pub async fn fail_webhook_delivery(
+        &mut self,
+        delivery_id: i64,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let status = if retry_at.is_some() {
+            WebhookDeliveryStatus::Pending
+        } else {
+            WebhookDeliveryStatus::Dead
+        };
+        sqlx::query!(
+            "UPDATE webhook_deliveries
+            SET status = $2, attempts = attempts + 1, last_error = $3,
+                next_attempt_at = COALESCE($4, next_attempt_at), updated_at = now()
+            WHERE id = $1",
+            delivery_id,
+            status.to_string(),
+            error,
+            retry_at,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("outbox", "fail_webhook_delivery", start);
+        Ok(())
+    }
+
+    /// Loads the deliveries of the subscription that ran out of attempts.
+    #[tracing::instrument(skip_all, fields(schema = "outbox"))]
+    pub async fn load_dead_webhook_deliveries(
+        &mut self,
+        subscription_id: i64,
+    ) -> QueryResult<Vec<StoredWebhookDelivery>> {
+        let start = Instant::now();
+        let deliveries = sqlx::query_as!(
+            StoredWebhookDelivery,
+            "SELECT * FROM webhook_deliveries
+            WHERE subscription_id = $1 AND status = $2
+            ORDER BY event_id ASC",
+            subscription_id,
+            WebhookDeliveryStatus::Dead.to_string(),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("outbox", "load_dead_webhook_deliveries", start);
+        Ok(deliveries)
+    }
+
+    /// Returns the dead deliveries of the subscription back to the queue with the reset attempts counter.
+    /// Returns the amount of the requeued deliveries.
+    #[tracing::instrument(skip_all, fields(schema = "outbox"))]
+    pub async fn retry_dead_webhook_deliveries(
+        &mut self,
+        subscription_id: i64,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let requeued = sqlx::query!(
+            "UPDATE webhook_deliveries
+            SET status = $2, attempts = 0, next_attempt_at = now(), updated_at = now()
+            WHERE subscription_id = $1 AND status = $3",
+            subscription_id,
+            WebhookDeliveryStatus::Pending.to_string(),
+            WebhookDeliveryStatus::Dead.to_string(),
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        crate::slow_queries::report_query("outbox", "retry_dead_webhook_deliveries", start);
+        Ok(requeued)
+    }
+
+    /// Removes the events stored before the given moment that have no pending deliveries,
+    /// i.e. the events delivered to all the subscribers or the dead ones.
+    /// Returns the amount of removed events.
+    #[tracing::instrument(skip_all, fields(schema = "outbox"))]
+    pub async fn remove_old_outbox_events(&mut self, before: DateTime<Utc>) -> QueryResult<u64> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM event_outbox
+            WHERE created_at < $1 AND NOT EXISTS (
+                SELECT 1 FROM webhook_deliveries
+                WHERE webhook_deliveries.event_id = event_outbox.id
+                    AND webhook_deliveries.status = $2
+            )",
+            before,
+            WebhookDeliveryStatus::Pending.to_string(),
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        crate::slow_queries::report_query("outbox", "remove_old_outbox_events", start);
+        Ok(removed)
+    }
+}
@@ -0,0 +1,57 @@
+// External imports
+use chrono::prelude::*;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredWebhookSubscription {
+    pub id: i64,
+    pub url: String,
+    pub secret: String,
+    pub event_types: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredWebhookDelivery {
+    pub id: i64,
+    pub subscription_id: i64,
+    pub event_id: i64,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Webhook delivery along with the subscription and event data required to send it.
+#[derive(Debug, Clone, FromRow)]
+pub struct PendingWebhookDelivery {
+    pub id: i64,
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+    pub event_id: i64,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Status of the webhook delivery. Successfully delivered events are removed from the deliveries table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookDeliveryStatus {
+    /// Event is waiting to be delivered (for the first time or after a failed attempt).
+    Pending,
+    /// Event couldn't be delivered within the allowed amount of attempts.
+    Dead,
+}
+
+impl ToString for WebhookDeliveryStatus {
+    fn to_string(&self) -> String {
+        match self {
+            WebhookDeliveryStatus::Pending => String::from("pending"),
+            WebhookDeliveryStatus::Dead => String::from("dead"),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+use num::BigUint;
+// Workspace imports
+use zksync_types::{Address, BlockNumber, TokenId};
+use zksync_utils::biguint_to_big_decimal;
+// Local imports
+use self::records::{ExecutedTransfer, StoredPaymentRequest};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the payment requests created by the merchants. The executed transfers are
+/// matched against the pending requests block by block, the last matched block is stored
+/// along with the requests.
+#[derive(Debug)]
+pub struct PaymentRequestsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> PaymentRequestsSchema<'a, 'c> {
+    /// Stores the payment request, returns its id.
+    #[tracing::instrument(skip_all, fields(schema = "payment_requests"))]
+    pub async fn store_payment_request(
+        &mut self,
+        recipient: Address,
+        token: TokenId,
+        amount: &BigUint,
+        reference: Option<&str>,
+        expires_at: DateTime<Utc>,
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            "INSERT INTO payment_requests (recipient, token_id, amount, reference, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id",
+            recipient.as_bytes(),
+            *token as i32,
+            biguint_to_big_decimal(amount.clone()),
+            reference,
+            expires_at,
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        crate::slow_queries::report_query("payment_requests", "store_payment_request", start);
+        Ok(id)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "payment_requests"))]
+    pub async fn get_payment_request(
+        &mut self,
+        id: i64,
+    ) -> QueryResult<Option<StoredPaymentRequest>> {
+        let start = Instant::now();
+        let request = sqlx::query_as!(
+            StoredPaymentRequest,
+            "SELECT * FROM payment_requests WHERE id = $1",
+            id,
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("payment_requests", "get_payment_request", start);
+        Ok(request)
+    }
+
+    /// Returns the last block whose transfers were matched against the requests. The row is
+    /// locked until the end of the transaction, so the blocks are not processed twice.
+    #[tracing::instrument(skip_all, fields(schema = "payment_requests"))]
+    pub async fn lock_last_matched_block(&mut self) -> QueryResult<BlockNumber> {
+        let start = Instant::now();
+        let last_block_number =
+            sqlx::query!("SELECT last_block_number FROM payment_requests_watcher FOR UPDATE")
+                .fetch_one(self.0.conn())
+                .await?
+                .last_block_number;
+
+        crate::slow_queries::report_query("payment_requests", "lock_last_matched_block", start);
+        Ok(BlockNumber(last_block_number as u32))
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "payment_requests"))]
+    pub async fn update_last_matched_block(&mut self, block: BlockNumber) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE payment_requests_watcher SET last_block_number = $1",
+            i64::from(*block),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("payment_requests", "update_last_matched_block", start);
+        Ok(())
+    }
+
+    /// Loads the successful transfers of the blocks in the range, in the order of execution.
+    #[tracing::instrument(skip_all, fields(schema = "payment_requests"))]
+    pub async fn load_executed_transfers(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<ExecutedTransfer>> {
+        let start = Instant::now();
+        let transfers = sqlx::query_as!(
+            ExecutedTransfer,
+            r#"SELECT
+                e.tx_hash,
+                e.to_account AS "to_address!",
+                (e.tx->>'token')::integer AS "token_id!",
+                (e.tx->>'amount')::numeric AS "amount!",
+                m.memo AS "memo?",
+                e.created_at
+            FROM executed_transactions e
+            LEFT JOIN tx_memos m ON m.tx_hash = e.tx_hash
+            WHERE e.block_number BETWEEN $1 AND $2
+                AND e.success = true AND e.tx->>'type' = 'Transfer'
+            ORDER BY e.block_number, e.block_index"#,
+            i64::from(*from_block),
+            i64::from(*to_block),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("payment_requests", "load_executed_transfers", start);
+        Ok(transfers)
+    }
+
+    /// Marks the pending request matching the transfer fulfilled, returns its id.
+    ///
+    /// The transfer should be created while the request is valid. Requests with the reference
+    /// carried by the transfer are preferred, then the ones with the largest amount covered
+    /// by the transfer, so the request can't be taken over by a smaller one without the reference.
+    #[tracing::instrument(skip_all, fields(schema = "payment_requests"))]
+    pub async fn fulfill_payment_request(
+        &mut self,
+        transfer: &ExecutedTransfer,
+    ) -> QueryResult<Option<i64>> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            "UPDATE payment_requests SET fulfilled_tx_hash = $1, fulfilled_at = now()
+            WHERE id = (
+                SELECT id FROM payment_requests
+                WHERE recipient = $2 AND token_id = $3 AND fulfilled_tx_hash IS NULL
+                    AND amount <= $4
+                    AND created_at <= $5 AND expires_at >= $5
+                    AND (reference IS NULL OR reference = $6)
+                ORDER BY reference IS NULL, amount DESC, created_at
+                LIMIT 1
+                FOR UPDATE
+            )
+            RETURNING id",
+            &transfer.tx_hash,
+            &transfer.to_address,
+            transfer.token_id,
+            transfer.amount,
+            transfer.created_at,
+            transfer.memo,
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| record.id);
+
+        crate::slow_queries::report_query("payment_requests", "fulfill_payment_request", start);
+        Ok(id)
+    }
+}
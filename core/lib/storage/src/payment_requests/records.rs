@@ -0,0 +1,30 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredPaymentRequest {
+    pub id: i64,
+    pub recipient: Vec<u8>,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    /// Memo the transfer should carry to fulfill the request.
+    pub reference: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub fulfilled_tx_hash: Option<Vec<u8>>,
+    pub fulfilled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Successful transfer loaded for the matching with the payment requests.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct ExecutedTransfer {
+    pub tx_hash: Vec<u8>,
+    pub to_address: Vec<u8>,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub memo: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
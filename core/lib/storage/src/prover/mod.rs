@@ -5,14 +5,19 @@ use anyhow::format_err;
 // Workspace imports
 use zksync_types::BlockNumber;
 // Local imports
-use self::records::{StorageProverJobQueue, StoredAggregatedProof, StoredProof};
+use self::records::{
+    StoragePendingProverJob, StorageProverJobQueue, StoredAggregatedProof,
+    StoredAggregatedProofComposition, StoredProof,
+};
 use crate::chain::operations::OperationsSchema;
 use crate::prover::records::StorageBlockWitness;
 use crate::{QueryResult, StorageProcessor};
 use chrono::{TimeZone, Utc};
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
-use zksync_types::aggregated_operations::AggregatedActionType;
-use zksync_types::prover::{ProverJob, ProverJobStatus, ProverJobType};
+use zksync_types::aggregated_operations::{
+    AggregatedActionType, BlocksCreateProofOperation, ProofAggregationTrigger,
+};
+use zksync_types::prover::{ProverJob, ProverJobStatus, ProverJobType, HIGH_PRIORITY_JOB_PRIORITY};
 
 pub mod records;
 
@@ -24,6 +29,7 @@ pub struct ProverSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 impl<'a, 'c> ProverSchema<'a, 'c> {
     /// Returns the amount of blocks which await for proof, but have
     /// no assigned prover run.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn pending_jobs_count(&mut self) -> QueryResult<u32> {
         let start = Instant::now();
         let pending_jobs_count = sqlx::query!(
@@ -34,10 +40,28 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?
         .count
         .unwrap_or(0) as u32;
-        metrics::histogram!("sql", start.elapsed(), "prover" => "pending_jobs_count");
+        crate::slow_queries::report_query("prover", "pending_jobs_count", start);
         Ok(pending_jobs_count)
     }
 
+    /// Returns the timestamp of the oldest block which is not proven yet.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
+    pub async fn oldest_unproven_block_timestamp(&mut self) -> QueryResult<Option<u64>> {
+        let start = Instant::now();
+        let timestamp = sqlx::query!(
+            "SELECT timestamp FROM blocks
+            WHERE number > (SELECT COALESCE(MAX(block_number), 0) FROM proofs)
+            ORDER BY number ASC LIMIT 1"
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .and_then(|record| record.timestamp)
+        .map(|timestamp| timestamp as u64);
+        crate::slow_queries::report_query("prover", "oldest_unproven_block_timestamp", start);
+        Ok(timestamp)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn add_prover_job_to_job_queue(
         &mut self,
         first_block: BlockNumber,
@@ -47,10 +71,15 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         job_type: ProverJobType,
     ) -> QueryResult<()> {
         let start = Instant::now();
+        // Jobs that contain any of the blocks marked as high priority by the operator
+        // get the boosted priority regardless of the requested one.
         sqlx::query!(
         "
           WITH job_values as (
-            SELECT $1::int4, $2::int4, $3::text, 'server_add_job', $4::int8, $5::int8, $6::jsonb
+            SELECT $1::int4,
+              CASE WHEN EXISTS (SELECT 1 FROM prover_priority_blocks WHERE block_number BETWEEN $4 AND $5)
+                THEN LEAST($2::int4, $7::int4) ELSE $2::int4 END,
+              $3::text, 'server_add_job', $4::int8, $5::int8, $6::jsonb
             WHERE NOT EXISTS (SELECT * FROM prover_job_queue WHERE first_block = $4 and last_block = $5 and job_type = $3 LIMIT 1)
           )
           INSERT INTO prover_job_queue (job_status, job_priority, job_type, updated_by, first_block, last_block, job_data)
@@ -62,12 +91,14 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             i64::from(*first_block),
             i64::from(*last_block),
             job_data,
+            HIGH_PRIORITY_JOB_PRIORITY,
         ).execute(self.0.conn()).await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "add_prover_job_to_job_queue");
+        crate::slow_queries::report_query("prover", "add_prover_job_to_job_queue", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn mark_stale_jobs_as_idle(&mut self) -> QueryResult<()> {
         let start = Instant::now();
         let result = sqlx::query!(
@@ -79,10 +110,11 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .fetch_all(self.0.conn())
         .await?;
         metrics::counter!("stale_jobs", result.len() as u64);
-        metrics::histogram!("sql", start.elapsed(), "prover" => "mark_stale_jobs_as_idle");
+        crate::slow_queries::report_query("prover", "mark_stale_jobs_as_idle", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn get_idle_prover_job_from_job_queue(&mut self) -> QueryResult<Option<ProverJob>> {
         let start = Instant::now();
         // Select the block to prove.
@@ -125,11 +157,118 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             None
         };
         transaction.commit().await?;
-        metrics::histogram!("sql", start.elapsed(), "prover" => "get_idle_prover_job_from_job_queue");
+        crate::slow_queries::report_query("prover", "get_idle_prover_job_from_job_queue", start);
         Ok(prover_job)
     }
 
+    /// Returns all the prover jobs that are not completed yet, in the order
+    /// they're going to be served to the provers.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
+    pub async fn load_pending_prover_jobs(&mut self) -> QueryResult<Vec<StoragePendingProverJob>> {
+        let start = Instant::now();
+        let jobs = sqlx::query_as!(
+            StoragePendingProverJob,
+            r#"
+                SELECT id, job_status, job_priority, job_type, updated_by, updated_at, first_block, last_block
+                FROM prover_job_queue
+                WHERE job_status != $1
+                ORDER BY job_status DESC, job_priority, id, first_block
+            "#,
+            ProverJobStatus::Done.to_number()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("prover", "load_pending_prover_jobs", start);
+        Ok(jobs)
+    }
+
+    /// Marks the block as a high priority one, so the jobs containing it
+    /// will be served to the provers before the other ones.
+    /// Both the already queued idle jobs and the jobs which will be added later are affected.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
+    pub async fn mark_block_as_high_priority(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query!(
+            "INSERT INTO prover_priority_blocks (block_number)
+            VALUES ($1)
+            ON CONFLICT (block_number)
+            DO NOTHING",
+            i64::from(*block_number),
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            "UPDATE prover_job_queue
+            SET (job_priority, updated_at, updated_by) = ($1, now(), 'server_mark_priority')
+            WHERE job_status = $2 AND job_priority > $1 AND first_block <= $3 AND last_block >= $3",
+            HIGH_PRIORITY_JOB_PRIORITY,
+            ProverJobStatus::Idle.to_number(),
+            i64::from(*block_number),
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("prover", "mark_block_as_high_priority", start);
+        Ok(())
+    }
+
+    /// Changes the priority of the idle prover job, which allows the operator to reorder the queue.
+    /// Returns `false` if there is no idle job with the given ID.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
+    pub async fn set_prover_job_priority(
+        &mut self,
+        job_id: i32,
+        job_priority: i32,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let updated_rows = sqlx::query!(
+            "UPDATE prover_job_queue
+            SET (job_priority, updated_at, updated_by) = ($1, now(), 'server_set_priority')
+            WHERE id = $2 AND job_status = $3",
+            job_priority,
+            job_id,
+            ProverJobStatus::Idle.to_number(),
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        crate::slow_queries::report_query("prover", "set_prover_job_priority", start);
+        Ok(updated_rows == 1)
+    }
+
+    /// Returns the jobs taken by the given prover back to the queue, so they can be
+    /// picked up by other provers without waiting for the stale job timeout.
+    /// Returns the amount of reassigned jobs.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
+    pub async fn reassign_prover_jobs(&mut self, prover_name: &str) -> QueryResult<u64> {
+        let start = Instant::now();
+        let reassigned = sqlx::query!(
+            "UPDATE prover_job_queue
+            SET (job_status, updated_at, updated_by) = ($1, now(), 'server_reassign_job')
+            WHERE updated_by = $2 AND job_status = $3",
+            ProverJobStatus::Idle.to_number(),
+            prover_name,
+            ProverJobStatus::InProgress.to_number(),
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::counter!("reassigned_prover_jobs", reassigned);
+        crate::slow_queries::report_query("prover", "reassign_prover_jobs", start);
+        Ok(reassigned)
+    }
+
     /// Updates the state of ongoing prover job.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn record_prover_is_working(
         &mut self,
         job_id: i32,
@@ -146,11 +285,12 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_is_working");
+        crate::slow_queries::report_query("prover", "record_prover_is_working", start);
         Ok(())
     }
 
     /// Marks the prover as stopped.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn record_prover_stop(&mut self, prover_name: &str) -> QueryResult<()> {
         let start = Instant::now();
         sqlx::query!(
@@ -164,11 +304,12 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_stop");
+        crate::slow_queries::report_query("prover", "record_prover_stop", start);
         Ok(())
     }
 
     /// Stores the proof for a block.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn store_proof(
         &mut self,
         job_id: i32,
@@ -207,7 +348,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             .set_block_processing_metrics(block_number, block_number, "single_proof".to_string())
             .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql", start.elapsed(), "prover" => "store_proof");
+        crate::slow_queries::report_query("prover", "store_proof", start);
         Ok(())
     }
 
@@ -238,6 +379,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(())
     }
     /// Stores the aggregated proof for blocks.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn store_aggregated_proof(
         &mut self,
         job_id: i32,
@@ -278,11 +420,12 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "store_aggregated_proof");
+        crate::slow_queries::report_query("prover", "store_aggregated_proof", start);
         Ok(())
     }
 
     /// Gets the stored proof for a block.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn load_proof(
         &mut self,
         block_number: BlockNumber,
@@ -297,11 +440,12 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?
         .map(|stored| serde_json::from_value(stored.proof).unwrap());
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "load_proof");
+        crate::slow_queries::report_query("prover", "load_proof", start);
         Ok(proof)
     }
 
     /// Gets the stored proof for a block.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn load_aggregated_proof(
         &mut self,
         first_block: BlockNumber,
@@ -318,11 +462,62 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?
         .map(|stored| serde_json::from_value(stored.proof).unwrap());
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "load_aggregated_proof");
+        crate::slow_queries::report_query("prover", "load_aggregated_proof", start);
         Ok(proof)
     }
 
+    /// Stores the composition of the aggregated proof chosen by the aggregation policy.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
+    pub async fn store_aggregated_proof_composition(
+        &mut self,
+        operation: &BlocksCreateProofOperation,
+        trigger: ProofAggregationTrigger,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let (first_block, last_block) = operation.block_range();
+        sqlx::query!(
+            "INSERT INTO aggregated_proof_compositions
+            (first_block, last_block, aggregated_proof_size, blocks_count, padded_proofs, trigger)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (first_block, last_block)
+            DO NOTHING",
+            i64::from(*first_block),
+            i64::from(*last_block),
+            operation.aggregated_proof_size() as i32,
+            operation.blocks.len() as i32,
+            operation.proofs_to_pad as i32,
+            trigger.to_string(),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("prover", "store_aggregated_proof_composition", start);
+        Ok(())
+    }
+
+    /// Gets the stored composition of the aggregated proof for blocks.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
+    pub async fn load_aggregated_proof_composition(
+        &mut self,
+        first_block: BlockNumber,
+        last_block: BlockNumber,
+    ) -> QueryResult<Option<StoredAggregatedProofComposition>> {
+        let start = Instant::now();
+        let composition = sqlx::query_as!(
+            StoredAggregatedProofComposition,
+            "SELECT * FROM aggregated_proof_compositions WHERE first_block = $1 and last_block = $2",
+            i64::from(*first_block),
+            i64::from(*last_block)
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("prover", "load_aggregated_proof_composition", start);
+        Ok(composition)
+    }
+
     /// Stores witness for a block
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn store_witness(
         &mut self,
         block: BlockNumber,
@@ -341,11 +536,12 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "store_witness");
+        crate::slow_queries::report_query("prover", "store_witness", start);
         Ok(())
     }
 
     /// Gets stored witness for a block.
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn get_witness(
         &mut self,
         block_number: BlockNumber,
@@ -359,11 +555,12 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "get_witness");
+        crate::slow_queries::report_query("prover", "get_witness", start);
         Ok(block_witness
             .map(|w| serde_json::from_str(&w.witness).expect("Failed to deserialize witness")))
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn get_last_block_prover_job_queue(
         &mut self,
         action_type: ProverJobType,
@@ -402,11 +599,12 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             }
         };
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "get_last_block_prover_job_queue");
+        crate::slow_queries::report_query("prover", "get_last_block_prover_job_queue", start);
         Ok(result)
     }
 
     // Removes witnesses for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn remove_witnesses(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         sqlx::query!(
@@ -416,11 +614,12 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "remove_witnesses");
+        crate::slow_queries::report_query("prover", "remove_witnesses", start);
         Ok(())
     }
 
     // Removes proofs for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn remove_proofs(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         sqlx::query!(
@@ -430,25 +629,36 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "remove_proofs");
+        crate::slow_queries::report_query("prover", "remove_proofs", start);
         Ok(())
     }
 
     // Removes aggregated proofs for blocks with number greater than `last_block`
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn remove_aggregated_proofs(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
         sqlx::query!(
             "DELETE FROM aggregated_proofs WHERE last_block > $1",
             *last_block as i64
         )
-        .execute(self.0.conn())
+        .execute(transaction.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "remove_aggregated_proofs");
+        sqlx::query!(
+            "DELETE FROM aggregated_proof_compositions WHERE last_block > $1",
+            *last_block as i64
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("prover", "remove_aggregated_proofs", start);
         Ok(())
     }
 
     // Removes blocks with number greater than `last_block` from prover job queue
+    #[tracing::instrument(skip_all, fields(schema = "prover"))]
     pub async fn remove_prover_jobs(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -465,9 +675,16 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         )
         .execute(transaction.conn())
         .await?;
+
+        sqlx::query!(
+            "DELETE FROM prover_priority_blocks WHERE block_number > $1",
+            *last_block as i64
+        )
+        .execute(transaction.conn())
+        .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "remove_prover_jobs");
+        crate::slow_queries::report_query("prover", "remove_prover_jobs", start);
         Ok(())
     }
 }
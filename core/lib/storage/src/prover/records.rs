@@ -35,6 +35,17 @@ pub struct StoredAggregatedProof {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredAggregatedProofComposition {
+    pub first_block: i64,
+    pub last_block: i64,
+    pub aggregated_proof_size: i32,
+    pub blocks_count: i32,
+    pub padded_proofs: i32,
+    pub trigger: String,
+    pub created_at: DateTime<Utc>,
+}
+
 // Every time before a prover worker starts generating the proof, a prover run is recorded for monitoring purposes
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct ProverRun {
@@ -69,3 +80,16 @@ pub struct StorageProverJobQueue {
     pub last_block: i64,
     pub job_data: serde_json::Value,
 }
+
+/// Prover job queue entry without the job data, used to inspect the queue.
+#[derive(Debug, Clone, FromRow)]
+pub struct StoragePendingProverJob {
+    pub id: i32,
+    pub job_status: i32,
+    pub job_priority: i32,
+    pub job_type: String,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+    pub first_block: i64,
+    pub last_block: i64,
+}
@@ -0,0 +1,284 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_api_types::v02::transaction::{L1Receipt, L2Receipt, Receipt, TxInBlockStatus};
+use zksync_types::{tx::TxHash, BlockNumber, EthBlockId};
+// Local imports
+use self::records::StoredArchivedReceipt;
+use crate::{chain::operations_ext::records::StorageTxData, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Tables which can be pruned by the retention policies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrunedTable {
+    /// Executed L2 transactions, the rows are moved to the archive schema.
+    ExecutedTransactions,
+    /// Executed priority operations, the rows are moved to the archive schema.
+    ExecutedPriorityOperations,
+    /// Witnesses of the blocks, the rows are removed since they're only needed to generate proofs.
+    BlockWitness,
+}
+
+impl PrunedTable {
+    /// Name of the pruned table in the database.
+    pub fn table_name(self) -> &'static str {
+        match self {
+            Self::ExecutedTransactions => "executed_transactions",
+            Self::ExecutedPriorityOperations => "executed_priority_operations",
+            Self::BlockWitness => "block_witness",
+        }
+    }
+}
+
+/// Schema of the storage pruning.
+///
+/// Pruning keeps the hot tables small by removing the rows of the old blocks from them.
+/// Depending on the table, the rows are either moved to the `archive` database schema,
+/// where they can still be found by the hash, or removed completely. Transaction filters
+/// are not pruned, so the hashes of the archived transactions are still known.
+#[derive(Debug)]
+pub struct PruningSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> PruningSchema<'a, 'c> {
+    /// Prunes at most `limit` rows of the table from the blocks up to `last_block` inclusively.
+    /// Once all such rows are pruned, `last_block` is stored as the last pruned block of the table.
+    /// Returns the amount of pruned rows.
+    #[tracing::instrument(skip_all, fields(schema = "pruning"))]
+    pub async fn prune_table(
+        &mut self,
+        table: PrunedTable,
+        last_block: BlockNumber,
+        limit: u32,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let last_block = i64::from(*last_block);
+        let limit = i64::from(limit);
+
+        // Columns of the archive tables have the same order as the original ones.
+        // Rows are ordered the same way as in `load_txs_to_prune`.
+        let pruned = match table {
+            PrunedTable::ExecutedTransactions => sqlx::query!(
+                "WITH pruned AS (
+                    DELETE FROM executed_transactions
+                    WHERE tx_hash IN (
+                        SELECT tx_hash FROM executed_transactions
+                        WHERE block_number <= $1
+                        ORDER BY block_number, tx_hash
+                        LIMIT $2
+                    )
+                    RETURNING *
+                )
+                INSERT INTO archive.executed_transactions
+                SELECT * FROM pruned",
+                last_block,
+                limit
+            )
+            .execute(transaction.conn())
+            .await?
+            .rows_affected(),
+            PrunedTable::ExecutedPriorityOperations => sqlx::query!(
+                "WITH pruned AS (
+                    DELETE FROM executed_priority_operations
+                    WHERE priority_op_serialid IN (
+                        SELECT priority_op_serialid FROM executed_priority_operations
+                        WHERE block_number <= $1
+                        ORDER BY block_number, priority_op_serialid
+                        LIMIT $2
+                    )
+                    RETURNING *
+                )
+                INSERT INTO archive.executed_priority_operations
+                SELECT * FROM pruned",
+                last_block,
+                limit
+            )
+            .execute(transaction.conn())
+            .await?
+            .rows_affected(),
+            PrunedTable::BlockWitness => sqlx::query!(
+                "DELETE FROM block_witness
+                WHERE block IN (
+                    SELECT block FROM block_witness
+                    WHERE block <= $1
+                    ORDER BY block
+                    LIMIT $2
+                )",
+                last_block,
+                limit
+            )
+            .execute(transaction.conn())
+            .await?
+            .rows_affected(),
+        };
+
+        if pruned < limit as u64 {
+            sqlx::query!(
+                "INSERT INTO pruning_state (table_name, last_pruned_block)
+                VALUES ($1, $2)
+                ON CONFLICT (table_name)
+                DO UPDATE SET last_pruned_block = GREATEST(pruning_state.last_pruned_block, $2)",
+                table.table_name(),
+                last_block
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        metrics::counter!("sql.pruning.pruned_rows", pruned, "table" => table.table_name());
+        crate::slow_queries::report_query("pruning", "prune_table", start);
+        Ok(pruned)
+    }
+
+    /// Loads the data of the transactions which are pruned by the `prune_table` call with the same
+    /// arguments, so they can be uploaded to the object storage archive beforehand.
+    /// Returns nothing for the tables without the transactions.
+    #[tracing::instrument(skip_all, fields(schema = "pruning"))]
+    pub async fn load_txs_to_prune(
+        &mut self,
+        table: PrunedTable,
+        last_block: BlockNumber,
+        limit: u32,
+    ) -> QueryResult<Vec<StorageTxData>> {
+        let start = Instant::now();
+        let last_block = i64::from(*last_block);
+        let limit = i64::from(limit);
+
+        let txs = match table {
+            PrunedTable::ExecutedTransactions => {
+                sqlx::query_as!(
+                    StorageTxData,
+                    r#"
+                    SELECT
+                        tx_hash,
+                        tx as op,
+                        block_number as "block_number?",
+                        block_index,
+                        created_at,
+                        success as "success?",
+                        fail_reason,
+                        Null::bytea as "eth_hash?",
+                        Null::bigint as "priority_op_serialid?",
+                        batch_id,
+                        eth_sign_data
+                    FROM executed_transactions
+                    WHERE block_number <= $1
+                    ORDER BY block_number, tx_hash
+                    LIMIT $2
+                    "#,
+                    last_block,
+                    limit
+                )
+                .fetch_all(self.0.conn())
+                .await?
+            }
+            PrunedTable::ExecutedPriorityOperations => {
+                sqlx::query_as!(
+                    StorageTxData,
+                    r#"
+                    SELECT
+                        tx_hash,
+                        operation as op,
+                        block_number as "block_number?",
+                        block_index as "block_index?",
+                        created_at,
+                        true as "success?",
+                        Null::text as "fail_reason?",
+                        eth_hash as "eth_hash?",
+                        priority_op_serialid as "priority_op_serialid?",
+                        Null::bigint as "batch_id?",
+                        Null::jsonb as "eth_sign_data?"
+                    FROM executed_priority_operations
+                    WHERE block_number <= $1
+                    ORDER BY block_number, priority_op_serialid
+                    LIMIT $2
+                    "#,
+                    last_block,
+                    limit
+                )
+                .fetch_all(self.0.conn())
+                .await?
+            }
+            PrunedTable::BlockWitness => Vec::new(),
+        };
+
+        crate::slow_queries::report_query("pruning", "load_txs_to_prune", start);
+        Ok(txs)
+    }
+
+    /// Loads the last block all the rows of which are pruned from the table.
+    #[tracing::instrument(skip_all, fields(schema = "pruning"))]
+    pub async fn get_last_pruned_block(&mut self, table: PrunedTable) -> QueryResult<BlockNumber> {
+        let start = Instant::now();
+        let last_pruned_block = sqlx::query!(
+            "SELECT last_pruned_block FROM pruning_state WHERE table_name = $1",
+            table.table_name()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| BlockNumber(record.last_pruned_block as u32))
+        .unwrap_or_default();
+
+        crate::slow_queries::report_query("pruning", "get_last_pruned_block", start);
+        Ok(last_pruned_block)
+    }
+
+    /// Returns the receipt of the archived transaction or priority operation with the `Pruned` status.
+    #[tracing::instrument(skip_all, fields(schema = "pruning"))]
+    pub async fn archived_tx_receipt(&mut self, hash: &[u8]) -> QueryResult<Option<Receipt>> {
+        let start = Instant::now();
+        let receipt = sqlx::query_as!(
+            StoredArchivedReceipt,
+            r#"
+                SELECT
+                    tx_hash as "tx_hash!",
+                    block_number as "block_number!",
+                    success as "success!",
+                    fail_reason,
+                    Null::bigint as eth_block,
+                    Null::bigint as priority_op_serialid
+                FROM archive.executed_transactions
+                WHERE tx_hash = $1
+                UNION ALL
+                SELECT
+                    tx_hash,
+                    block_number,
+                    true,
+                    Null,
+                    eth_block,
+                    priority_op_serialid
+                FROM archive.executed_priority_operations
+                WHERE tx_hash = $1 OR eth_hash = $1
+                LIMIT 1
+            "#,
+            hash
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        let receipt = receipt.map(|receipt| match receipt.priority_op_serialid {
+            Some(serial_id) => Receipt::L1(L1Receipt {
+                status: TxInBlockStatus::Pruned,
+                eth_block: EthBlockId(receipt.eth_block.unwrap_or_default() as u64),
+                rollup_block: Some(BlockNumber(receipt.block_number as u32)),
+                id: serial_id as u64,
+                expected_finality_secs: None,
+            }),
+            None => Receipt::L2(L2Receipt {
+                tx_hash: TxHash::from_slice(&receipt.tx_hash).unwrap(),
+                rollup_block: Some(BlockNumber(receipt.block_number as u32)),
+                status: TxInBlockStatus::Pruned,
+                fail_reason: receipt.fail_reason,
+                expected_finality_secs: None,
+                valid_until: None,
+                withdrawal_hook: None,
+            }),
+        });
+
+        crate::slow_queries::report_query("pruning", "archived_tx_receipt", start);
+        Ok(receipt)
+    }
+}
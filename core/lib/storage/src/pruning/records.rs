@@ -0,0 +1,14 @@
+// External imports
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredArchivedReceipt {
+    pub tx_hash: Vec<u8>,
+    pub block_number: i64,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+    pub eth_block: Option<i64>,
+    pub priority_op_serialid: Option<i64>,
+}
@@ -0,0 +1,101 @@
+// Built-in deps
+use std::time::{Duration, Instant};
+// External imports
+use serde_json::Value;
+// Workspace imports
+// Local imports
+use crate::{QueryResult, StorageProcessor};
+
+/// Rows returned by the report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportRows {
+    /// Names of the columns, empty if there are no rows.
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    /// Whether there were more rows than requested.
+    pub truncated: bool,
+}
+
+/// Schema running the read-only SQL reports defined by the operator.
+///
+/// Reports are run as subqueries within the read-only transaction with the statement timeout,
+/// so they can't change the data or take the database connection for too long. Rows are returned
+/// as JSON, the numbers which don't fit into the double precision are returned as strings.
+#[derive(Debug)]
+pub struct ReportsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ReportsSchema<'a, 'c> {
+    /// Runs the report query with the parameters bound as text, returns at most `max_rows` rows.
+    #[tracing::instrument(skip_all, fields(schema = "reports"))]
+    pub async fn run_report(
+        &mut self,
+        sql: &str,
+        parameters: &[String],
+        max_rows: u32,
+        timeout: Duration,
+    ) -> QueryResult<ReportRows> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query("SET TRANSACTION READ ONLY")
+            .execute(transaction.conn())
+            .await?;
+        sqlx::query(&format!(
+            "SET LOCAL statement_timeout = {}",
+            timeout.as_millis()
+        ))
+        .execute(transaction.conn())
+        .await?;
+
+        // Fields are aggregated in the order of the columns, which is kept by the `json` type.
+        let query = format!(
+            r#"
+            WITH report_rows AS (
+                SELECT row_to_json(report) AS fields FROM ({}) report LIMIT {}
+            )
+            SELECT
+                COALESCE((
+                    SELECT json_agg(field.key ORDER BY field.idx)
+                    FROM json_each(fields) WITH ORDINALITY AS field(key, value, idx)
+                ), '[]') AS columns,
+                COALESCE((
+                    SELECT json_agg(
+                        CASE WHEN json_typeof(field.value) = 'number' AND length(field.value::text) > 15
+                            THEN to_json(field.value::text)
+                            ELSE field.value
+                        END
+                        ORDER BY field.idx
+                    )
+                    FROM json_each(fields) WITH ORDINALITY AS field(key, value, idx)
+                ), '[]') AS values
+            FROM report_rows
+            "#,
+            sql,
+            u64::from(max_rows) + 1
+        );
+        let mut report_query = sqlx::query_as::<_, (Value, Value)>(&query);
+        for parameter in parameters {
+            report_query = report_query.bind(parameter);
+        }
+        let records = report_query.fetch_all(transaction.conn()).await?;
+        // Nothing is changed, but the transaction is still completed explicitly.
+        transaction.commit().await?;
+
+        let truncated = records.len() > max_rows as usize;
+        let columns = match records.first() {
+            Some((columns, _)) => serde_json::from_value(columns.clone())?,
+            None => Vec::new(),
+        };
+        let rows = records
+            .into_iter()
+            .take(max_rows as usize)
+            .map(|(_, values)| serde_json::from_value(values))
+            .collect::<Result<_, _>>()?;
+
+        crate::slow_queries::report_query("reports", "run_report", start);
+        Ok(ReportRows {
+            columns,
+            rows,
+            truncated,
+        })
+    }
+}
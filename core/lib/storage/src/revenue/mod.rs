@@ -0,0 +1,76 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::NaiveDate;
+// Workspace imports
+// Local imports
+use self::records::{StoredDailyFees, StoredDailyGasSpent};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the operator revenue reports.
+///
+/// Fees are taken from the fee breakdowns of the executed transactions, the L1 costs are taken
+/// from the gas spent by the confirmed Ethereum transactions. Both are aggregated per day (in UTC).
+#[derive(Debug)]
+pub struct RevenueSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> RevenueSchema<'a, 'c> {
+    /// Loads the fees charged within the days range (inclusive) per day and token.
+    #[tracing::instrument(skip_all, fields(schema = "revenue"))]
+    pub async fn load_daily_fees(
+        &mut self,
+        from_day: NaiveDate,
+        to_day: NaiveDate,
+    ) -> QueryResult<Vec<StoredDailyFees>> {
+        let start = Instant::now();
+        let fees = sqlx::query_as!(
+            StoredDailyFees,
+            r#"SELECT (executed_at AT TIME ZONE 'UTC')::DATE AS "day!", token_id,
+                count(*) AS "transactions_count!", sum(charged_fee) AS "charged_fee!",
+                sum(zkp_fee) AS "zkp_fee!", sum(gas_fee) AS "gas_fee!", sum(subsidy) AS "subsidy!"
+            FROM tx_fee_breakdowns
+            WHERE executed_at >= $1::DATE::TIMESTAMP AT TIME ZONE 'UTC'
+                AND executed_at < ($2::DATE + 1)::TIMESTAMP AT TIME ZONE 'UTC'
+                AND charged_fee IS NOT NULL
+            GROUP BY 1, token_id
+            ORDER BY 1, token_id"#,
+            from_day,
+            to_day,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("revenue", "load_daily_fees", start);
+        Ok(fees)
+    }
+
+    /// Loads the L1 gas spent within the days range (inclusive) per day.
+    #[tracing::instrument(skip_all, fields(schema = "revenue"))]
+    pub async fn load_daily_gas_spent(
+        &mut self,
+        from_day: NaiveDate,
+        to_day: NaiveDate,
+    ) -> QueryResult<Vec<StoredDailyGasSpent>> {
+        let start = Instant::now();
+        let gas_spent = sqlx::query_as!(
+            StoredDailyGasSpent,
+            r#"SELECT (confirmed_at AT TIME ZONE 'UTC')::DATE AS "day!",
+                count(*) AS "transactions_count!", sum(gas_used) AS "gas_used!",
+                sum(gas_used * gas_price) AS "gas_cost!"
+            FROM eth_gas_spent
+            WHERE confirmed_at >= $1::DATE::TIMESTAMP AT TIME ZONE 'UTC'
+                AND confirmed_at < ($2::DATE + 1)::TIMESTAMP AT TIME ZONE 'UTC'
+            GROUP BY 1
+            ORDER BY 1"#,
+            from_day,
+            to_day,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("revenue", "load_daily_gas_spent", start);
+        Ok(gas_spent)
+    }
+}
@@ -0,0 +1,27 @@
+// External imports
+use chrono::NaiveDate;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+/// Fees charged in the token within the day.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredDailyFees {
+    pub day: NaiveDate,
+    pub token_id: i32,
+    pub transactions_count: i64,
+    pub charged_fee: BigDecimal,
+    pub zkp_fee: BigDecimal,
+    pub gas_fee: BigDecimal,
+    pub subsidy: BigDecimal,
+}
+
+/// L1 gas spent by the operator within the day.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredDailyGasSpent {
+    pub day: NaiveDate,
+    pub transactions_count: i64,
+    pub gas_used: BigDecimal,
+    /// Total cost of the gas, in wei.
+    pub gas_cost: BigDecimal,
+}
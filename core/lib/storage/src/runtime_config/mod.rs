@@ -0,0 +1,134 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use serde_json::Value;
+// Workspace imports
+// Local imports
+use self::records::{StoredRuntimeParam, StoredRuntimeParamChange};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the reload-safe parameters overridden by the operator at runtime.
+/// Every change of the overrides is recorded along with the previous value.
+#[derive(Debug)]
+pub struct RuntimeConfigSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> RuntimeConfigSchema<'a, 'c> {
+    /// Loads all the overridden parameters.
+    #[tracing::instrument(skip_all, fields(schema = "runtime_config"))]
+    pub async fn load_params(&mut self) -> QueryResult<Vec<StoredRuntimeParam>> {
+        let start = Instant::now();
+        let params = sqlx::query_as!(
+            StoredRuntimeParam,
+            "SELECT * FROM runtime_config ORDER BY key",
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("runtime_config", "load_params", start);
+        Ok(params)
+    }
+
+    /// Overrides the parameter and records the change. Returns the previous value of the override.
+    #[tracing::instrument(skip_all, fields(schema = "runtime_config"))]
+    pub async fn set_param(
+        &mut self,
+        key: &str,
+        value: &Value,
+        changed_by: &str,
+    ) -> QueryResult<Option<Value>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let old_value = sqlx::query!(
+            "SELECT value FROM runtime_config WHERE key = $1 FOR UPDATE",
+            key,
+        )
+        .fetch_optional(transaction.conn())
+        .await?
+        .map(|row| row.value);
+        sqlx::query!(
+            "INSERT INTO runtime_config (key, value, updated_by) VALUES ($1, $2, $3)
+            ON CONFLICT (key) DO UPDATE SET value = $2, updated_by = $3, updated_at = now()",
+            key,
+            value,
+            changed_by,
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            "INSERT INTO runtime_config_changes (key, old_value, new_value, changed_by)
+            VALUES ($1, $2, $3, $4)",
+            key,
+            old_value,
+            value,
+            changed_by,
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("runtime_config", "set_param", start);
+        Ok(old_value)
+    }
+
+    /// Removes the override of the parameter and records the change.
+    /// Returns the removed value, `None` if the parameter isn't overridden.
+    #[tracing::instrument(skip_all, fields(schema = "runtime_config"))]
+    pub async fn remove_param(
+        &mut self,
+        key: &str,
+        changed_by: &str,
+    ) -> QueryResult<Option<Value>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let old_value = sqlx::query!(
+            "DELETE FROM runtime_config WHERE key = $1 RETURNING value",
+            key,
+        )
+        .fetch_optional(transaction.conn())
+        .await?
+        .map(|row| row.value);
+        if old_value.is_some() {
+            sqlx::query!(
+                "INSERT INTO runtime_config_changes (key, old_value, changed_by) VALUES ($1, $2, $3)",
+                key,
+                old_value,
+                changed_by,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("runtime_config", "remove_param", start);
+        Ok(old_value)
+    }
+
+    /// Loads the changes recorded before the one with the `before` id (or the latest ones
+    /// if it's `None`), newest first.
+    #[tracing::instrument(skip_all, fields(schema = "runtime_config"))]
+    pub async fn load_changes(
+        &mut self,
+        before: Option<i64>,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredRuntimeParamChange>> {
+        let start = Instant::now();
+        let changes = sqlx::query_as!(
+            StoredRuntimeParamChange,
+            "SELECT * FROM runtime_config_changes
+            WHERE ($1::bigint IS NULL OR id < $1)
+            ORDER BY id DESC
+            LIMIT $2",
+            before,
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("runtime_config", "load_changes", start);
+        Ok(changes)
+    }
+}
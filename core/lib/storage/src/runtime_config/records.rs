@@ -0,0 +1,25 @@
+// External imports
+use chrono::prelude::*;
+use serde_json::Value;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredRuntimeParam {
+    pub key: String,
+    pub value: Value,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredRuntimeParamChange {
+    pub id: i64,
+    pub key: String,
+    pub old_value: Option<Value>,
+    /// `None` if the override is removed.
+    pub new_value: Option<Value>,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+}
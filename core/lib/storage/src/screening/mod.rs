@@ -0,0 +1,138 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use self::records::{ScreeningMatch, StoredDenylistedAddress, StoredScreeningMatch};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the screening of the transactions on the submission: the denylist of the addresses
+/// maintained by the operator and the matches recorded for the audit.
+#[derive(Debug)]
+pub struct ScreeningSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ScreeningSchema<'a, 'c> {
+    /// Adds the address to the denylist. Returns `false` if it's already denied.
+    #[tracing::instrument(skip_all, fields(schema = "screening"))]
+    pub async fn add_to_denylist(&mut self, address: Address, reason: &str) -> QueryResult<bool> {
+        let start = Instant::now();
+        let added = sqlx::query!(
+            "INSERT INTO screening_denylist (address, reason) VALUES ($1, $2)
+            ON CONFLICT (address) DO NOTHING",
+            address.as_bytes(),
+            reason,
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        crate::slow_queries::report_query("screening", "add_to_denylist", start);
+        Ok(added)
+    }
+
+    /// Removes the address from the denylist. Returns `false` if it's not denied.
+    #[tracing::instrument(skip_all, fields(schema = "screening"))]
+    pub async fn remove_from_denylist(&mut self, address: Address) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM screening_denylist WHERE address = $1",
+            address.as_bytes(),
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        crate::slow_queries::report_query("screening", "remove_from_denylist", start);
+        Ok(removed)
+    }
+
+    /// Loads the whole denylist, newest first.
+    #[tracing::instrument(skip_all, fields(schema = "screening"))]
+    pub async fn load_denylist(&mut self) -> QueryResult<Vec<StoredDenylistedAddress>> {
+        let start = Instant::now();
+        let denylist = sqlx::query_as!(
+            StoredDenylistedAddress,
+            "SELECT * FROM screening_denylist ORDER BY created_at DESC",
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("screening", "load_denylist", start);
+        Ok(denylist)
+    }
+
+    /// Returns the denied addresses out of the given ones.
+    #[tracing::instrument(skip_all, fields(schema = "screening"))]
+    pub async fn get_denylisted(
+        &mut self,
+        addresses: &[Address],
+    ) -> QueryResult<Vec<StoredDenylistedAddress>> {
+        let start = Instant::now();
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let denylisted = sqlx::query_as!(
+            StoredDenylistedAddress,
+            "SELECT * FROM screening_denylist WHERE address = ANY($1)",
+            &addresses,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("screening", "get_denylisted", start);
+        Ok(denylisted)
+    }
+
+    /// Stores the match of the submitted transaction.
+    #[tracing::instrument(skip_all, fields(schema = "screening"))]
+    pub async fn store_match(&mut self, screening_match: &ScreeningMatch) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO screening_matches (tx_hash, address, role, source, reason)
+            VALUES ($1, $2, $3, $4, $5)",
+            screening_match.tx_hash.as_ref(),
+            screening_match.address.as_bytes(),
+            screening_match.role,
+            screening_match.source,
+            screening_match.reason,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("screening", "store_match", start);
+        Ok(())
+    }
+
+    /// Loads the matches stored before the one with the `before` id (or the latest ones
+    /// if it's `None`), newest first. Only the matches of the address are loaded if it's given.
+    #[tracing::instrument(skip_all, fields(schema = "screening"))]
+    pub async fn load_matches(
+        &mut self,
+        address: Option<Address>,
+        before: Option<i64>,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredScreeningMatch>> {
+        let start = Instant::now();
+        let matches = sqlx::query_as!(
+            StoredScreeningMatch,
+            "SELECT * FROM screening_matches
+            WHERE ($1::bytea IS NULL OR address = $1) AND ($2::bigint IS NULL OR id < $2)
+            ORDER BY id DESC
+            LIMIT $3",
+            address.as_ref().map(|address| address.as_bytes()),
+            before,
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("screening", "load_matches", start);
+        Ok(matches)
+    }
+}
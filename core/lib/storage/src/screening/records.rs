@@ -0,0 +1,36 @@
+// External imports
+use chrono::prelude::*;
+use sqlx::FromRow;
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredDenylistedAddress {
+    pub address: Vec<u8>,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Address of the submitted transaction matched by the screening.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreeningMatch {
+    pub tx_hash: TxHash,
+    pub address: Address,
+    /// Role of the address in the transaction, either `sender` or `recipient`.
+    pub role: String,
+    /// Source of the match, either `denylist` or `screening_api`.
+    pub source: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredScreeningMatch {
+    pub id: i64,
+    pub tx_hash: Vec<u8>,
+    pub address: Vec<u8>,
+    pub role: String,
+    pub source: String,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,243 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+use num::BigUint;
+// Workspace imports
+use zksync_types::{AccountId, Address, TokenId};
+use zksync_utils::biguint_to_big_decimal;
+// Local imports
+use self::records::{StoredSessionKey, StoredSessionKeyLimit};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the session keys: Ethereum addresses the account owner has allowed to authorize
+/// the transactions of the account instead of the owner's own Ethereum signature.
+///
+/// Every session key has the spending cap per token, which is checked and increased atomically
+/// when the transaction authorized by the key is accepted. Each request of the owner carries
+/// the timestamp, so the older registrations and revocations can't be replayed.
+#[derive(Debug)]
+pub struct SessionKeysSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> SessionKeysSchema<'a, 'c> {
+    /// Registers the session key or replaces its previous registration, resetting the spent
+    /// amounts. Returns `false` if the stored key was registered or revoked by a request signed
+    /// not earlier than `signed_at`.
+    #[tracing::instrument(skip_all, fields(schema = "session_keys"))]
+    pub async fn store_session_key(
+        &mut self,
+        account_id: AccountId,
+        session_address: Address,
+        allowed_tx_types: &[String],
+        limits: &[(TokenId, BigUint)],
+        expires_at: DateTime<Utc>,
+        signed_at: DateTime<Utc>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let result = sqlx::query!(
+            "INSERT INTO session_keys (
+                account_id, session_address, allowed_tx_types, expires_at, signed_at
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (account_id, session_address) DO UPDATE
+            SET allowed_tx_types = $3, expires_at = $4, revoked = false, signed_at = $5
+            WHERE session_keys.signed_at < $5",
+            i64::from(*account_id),
+            session_address.as_bytes(),
+            allowed_tx_types,
+            expires_at,
+            signed_at,
+        )
+        .execute(transaction.conn())
+        .await?;
+        let stored = result.rows_affected() > 0;
+        if stored {
+            let token_ids: Vec<_> = limits.iter().map(|(token, _)| **token as i32).collect();
+            let max_amounts: Vec<_> = limits
+                .iter()
+                .map(|(_, amount)| biguint_to_big_decimal(amount.clone()))
+                .collect();
+            sqlx::query!(
+                "DELETE FROM session_key_limits WHERE account_id = $1 AND session_address = $2",
+                i64::from(*account_id),
+                session_address.as_bytes(),
+            )
+            .execute(transaction.conn())
+            .await?;
+            sqlx::query!(
+                "INSERT INTO session_key_limits (account_id, session_address, token_id, max_amount)
+                SELECT $1, $2, u.token_id, u.max_amount
+                FROM UNNEST($3::integer[], $4::numeric[]) AS u(token_id, max_amount)",
+                i64::from(*account_id),
+                session_address.as_bytes(),
+                &token_ids,
+                &max_amounts,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("session_keys", "store_session_key", start);
+        Ok(stored)
+    }
+
+    /// Revokes the session key. Returns `false` if the key is not registered, is already revoked
+    /// or was registered by a request signed not earlier than `signed_at`.
+    #[tracing::instrument(skip_all, fields(schema = "session_keys"))]
+    pub async fn revoke_session_key(
+        &mut self,
+        account_id: AccountId,
+        session_address: Address,
+        signed_at: DateTime<Utc>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "UPDATE session_keys SET revoked = true, signed_at = $3
+            WHERE account_id = $1 AND session_address = $2 AND NOT revoked AND signed_at < $3",
+            i64::from(*account_id),
+            session_address.as_bytes(),
+            signed_at,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("session_keys", "revoke_session_key", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "session_keys"))]
+    pub async fn get_session_key(
+        &mut self,
+        account_id: AccountId,
+        session_address: Address,
+    ) -> QueryResult<Option<StoredSessionKey>> {
+        let start = Instant::now();
+        let session_key = sqlx::query_as!(
+            StoredSessionKey,
+            "SELECT * FROM session_keys WHERE account_id = $1 AND session_address = $2",
+            i64::from(*account_id),
+            session_address.as_bytes(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("session_keys", "get_session_key", start);
+        Ok(session_key)
+    }
+
+    /// Loads the session keys of the account, including the expired and revoked ones.
+    #[tracing::instrument(skip_all, fields(schema = "session_keys"))]
+    pub async fn get_account_session_keys(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<Vec<StoredSessionKey>> {
+        let start = Instant::now();
+        let session_keys = sqlx::query_as!(
+            StoredSessionKey,
+            "SELECT * FROM session_keys WHERE account_id = $1 ORDER BY created_at",
+            i64::from(*account_id),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("session_keys", "get_account_session_keys", start);
+        Ok(session_keys)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "session_keys"))]
+    pub async fn get_session_key_limits(
+        &mut self,
+        account_id: AccountId,
+        session_address: Address,
+    ) -> QueryResult<Vec<StoredSessionKeyLimit>> {
+        let start = Instant::now();
+        let limits = sqlx::query_as!(
+            StoredSessionKeyLimit,
+            "SELECT * FROM session_key_limits
+            WHERE account_id = $1 AND session_address = $2
+            ORDER BY token_id",
+            i64::from(*account_id),
+            session_address.as_bytes(),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("session_keys", "get_session_key_limits", start);
+        Ok(limits)
+    }
+
+    /// Increases the amounts spent with the session key. Returns `false` and spends nothing
+    /// if any of the amounts exceeds the remaining cap of its token.
+    #[tracing::instrument(skip_all, fields(schema = "session_keys"))]
+    pub async fn spend_session_key(
+        &mut self,
+        account_id: AccountId,
+        session_address: Address,
+        amounts: &[(TokenId, BigUint)],
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let mut within_limits = true;
+        for (token, amount) in amounts {
+            let result = sqlx::query!(
+                "UPDATE session_key_limits SET spent = spent + $4
+                WHERE account_id = $1 AND session_address = $2 AND token_id = $3
+                    AND spent + $4 <= max_amount",
+                i64::from(*account_id),
+                session_address.as_bytes(),
+                **token as i32,
+                biguint_to_big_decimal(amount.clone()),
+            )
+            .execute(transaction.conn())
+            .await?;
+            if result.rows_affected() == 0 {
+                within_limits = false;
+                break;
+            }
+        }
+        // Otherwise the transaction is rolled back once dropped.
+        if within_limits {
+            transaction.commit().await?;
+        }
+
+        crate::slow_queries::report_query("session_keys", "spend_session_key", start);
+        Ok(within_limits)
+    }
+
+    /// Returns the amounts spent with the session key, if the transaction was not accepted.
+    #[tracing::instrument(skip_all, fields(schema = "session_keys"))]
+    pub async fn refund_session_key(
+        &mut self,
+        account_id: AccountId,
+        session_address: Address,
+        amounts: &[(TokenId, BigUint)],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let token_ids: Vec<_> = amounts.iter().map(|(token, _)| **token as i32).collect();
+        let amounts: Vec<_> = amounts
+            .iter()
+            .map(|(_, amount)| biguint_to_big_decimal(amount.clone()))
+            .collect();
+        sqlx::query!(
+            "UPDATE session_key_limits
+            SET spent = GREATEST(session_key_limits.spent - u.amount, 0)
+            FROM UNNEST($3::integer[], $4::numeric[]) AS u(token_id, amount)
+            WHERE session_key_limits.account_id = $1
+                AND session_key_limits.session_address = $2
+                AND session_key_limits.token_id = u.token_id",
+            i64::from(*account_id),
+            session_address.as_bytes(),
+            &token_ids,
+            &amounts,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("session_keys", "refund_session_key", start);
+        Ok(())
+    }
+}
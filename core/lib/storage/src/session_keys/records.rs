@@ -0,0 +1,26 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredSessionKey {
+    pub account_id: i64,
+    pub session_address: Vec<u8>,
+    pub allowed_tx_types: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked: bool,
+    /// Timestamp of the last request signed by the owner.
+    pub signed_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredSessionKeyLimit {
+    pub account_id: i64,
+    pub session_address: Vec<u8>,
+    pub token_id: i32,
+    pub max_amount: BigDecimal,
+    pub spent: BigDecimal,
+}
@@ -0,0 +1,268 @@
+//! Detection of the slow storage queries.
+//!
+//! Every schema method reports its execution time via [`report_query`]. The methods which
+//! took longer than the threshold configured by the `DATABASE_SLOW_QUERY_THRESHOLD` variable
+//! (in milliseconds) are logged along with the schema and method name, and, if the recorder
+//! is running (see [`run_slow_queries_recorder`]), stored to the `slow_queries` table.
+//!
+//! The SQL text of the slow statements is logged by `sqlx` itself using the same threshold.
+//! Such entries are emitted within the span of the calling schema method, so the last slow
+//! statement of the method is normalized (see [`normalize_sql`]) and recorded along with it.
+//! Methods which are slow overall, while none of their statements is, are recorded without SQL.
+//!
+//! The execution times are also reported to the `sql` histogram, which may be sampled to reduce
+//! the overhead (see the [`sampling`] module), and to the profile of the calling future if it's
+//! profiled (see the [`profile`] module).
+
+// Built-in deps
+use std::time::{Duration, Instant};
+// External imports
+use chrono::{DateTime, Utc};
+use once_cell::sync::{Lazy, OnceCell};
+use tokio::{sync::mpsc, task::JoinHandle};
+// Local imports
+use self::records::StoredSlowQuery;
+use crate::{ConnectionPool, QueryResult, StorageProcessor};
+
+pub mod profile;
+pub mod records;
+pub mod sampling;
+
+/// Threshold used if the `DATABASE_SLOW_QUERY_THRESHOLD` variable is not set.
+const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_secs(1);
+/// Maximum number of the slow queries waiting to be recorded.
+/// Slow queries reported while the queue is full are only logged.
+const RECORDER_QUEUE_SIZE: usize = 1024;
+
+static SLOW_QUERY_THRESHOLD: Lazy<Duration> = Lazy::new(|| {
+    std::env::var("DATABASE_SLOW_QUERY_THRESHOLD")
+        .map(|threshold| {
+            let millis = threshold
+                .parse()
+                .expect("DATABASE_SLOW_QUERY_THRESHOLD must be a number of milliseconds");
+            Duration::from_millis(millis)
+        })
+        .unwrap_or(DEFAULT_SLOW_QUERY_THRESHOLD)
+});
+
+static RECORDER: OnceCell<mpsc::Sender<SlowQuery>> = OnceCell::new();
+
+/// Returns the execution time after which the query is considered slow.
+pub fn slow_query_threshold() -> Duration {
+    *SLOW_QUERY_THRESHOLD
+}
+
+/// Schema method call which took longer than the threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlowQuery {
+    pub schema: &'static str,
+    pub method: &'static str,
+    pub duration: Duration,
+    pub created_at: DateTime<Utc>,
+    /// Normalized text of the last slow statement executed by the method.
+    pub sql: Option<String>,
+}
+
+/// Reports the execution time of the schema method started at `start`.
+/// Must be called by every schema method once all its queries are executed.
+pub(crate) fn report_query(schema: &'static str, method: &'static str, start: Instant) {
+    let end = Instant::now();
+    let duration = end - start;
+    profile::report(schema, method, start, end);
+    if sampling::is_recorded(duration) {
+        metrics::histogram!("sql", duration, "schema" => schema, "method" => method);
+    }
+    if duration < slow_query_threshold() {
+        return;
+    }
+
+    vlog::warn!(
+        "Slow query: {}::{} took {}ms",
+        schema,
+        method,
+        duration.as_millis()
+    );
+    metrics::increment_counter!("sql.slow_queries", "schema" => schema, "method" => method);
+    if let Some(recorder) = RECORDER.get() {
+        let query = SlowQuery {
+            schema,
+            method,
+            duration,
+            created_at: Utc::now(),
+            sql: vlog::slow_statement().map(|sql| normalize_sql(&sql)),
+        };
+        if recorder.try_send(query).is_err() {
+            metrics::increment_counter!("sql.slow_queries.dropped");
+        }
+    }
+}
+
+/// Normalizes the SQL text, so the same statements are grouped together regardless of their
+/// formatting and parameters: the whitespace is collapsed, and the string and numeric literals
+/// are replaced with `?` (bound parameters like `$1` are kept as is).
+pub fn normalize_sql(sql: &str) -> String {
+    let mut normalized = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut pending_space = false;
+    // Whether the previous character may be a part of the identifier or of the parameter.
+    let mut in_word = false;
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            pending_space = true;
+            in_word = false;
+            continue;
+        }
+        if pending_space && !normalized.is_empty() {
+            normalized.push(' ');
+        }
+        pending_space = false;
+
+        if c == '\'' {
+            // Quotes are escaped by doubling them.
+            while let Some(c) = chars.next() {
+                if c == '\'' && chars.next_if_eq(&'\'').is_none() {
+                    break;
+                }
+            }
+            normalized.push('?');
+            in_word = false;
+        } else if c.is_ascii_digit() && !in_word {
+            while chars.next_if(|c| c.is_ascii_digit() || *c == '.').is_some() {}
+            normalized.push('?');
+        } else {
+            normalized.push(c);
+            in_word = c.is_alphanumeric() || c == '_' || c == '$';
+        }
+    }
+    normalized
+}
+
+/// Schema of the slow queries log.
+#[derive(Debug)]
+pub struct SlowQueriesSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> SlowQueriesSchema<'a, 'c> {
+    /// Stores the slow queries to the log.
+    #[tracing::instrument(skip_all, fields(schema = "slow_queries"))]
+    pub async fn store_slow_queries(&mut self, queries: &[SlowQuery]) -> QueryResult<()> {
+        let start = Instant::now();
+        let schema_names: Vec<_> = queries
+            .iter()
+            .map(|query| query.schema.to_string())
+            .collect();
+        let methods: Vec<_> = queries
+            .iter()
+            .map(|query| query.method.to_string())
+            .collect();
+        let durations: Vec<_> = queries
+            .iter()
+            .map(|query| query.duration.as_millis() as i64)
+            .collect();
+        let created_at: Vec<_> = queries.iter().map(|query| query.created_at).collect();
+        // Missing statements are passed as empty strings and stored as `NULL`.
+        let sql: Vec<_> = queries
+            .iter()
+            .map(|query| query.sql.clone().unwrap_or_default())
+            .collect();
+        sqlx::query!(
+            "INSERT INTO slow_queries (schema_name, method, duration_ms, created_at, sql)
+            SELECT u.schema_name, u.method, u.duration_ms, u.created_at, NULLIF(u.sql, '')
+            FROM UNNEST ($1::text[], $2::text[], $3::bigint[], $4::timestamptz[], $5::text[])
+                AS u(schema_name, method, duration_ms, created_at, sql)",
+            &schema_names,
+            &methods,
+            &durations,
+            &created_at,
+            &sql,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        // Not reported via `report_query`, so the recording of the slow queries can't be
+        // considered slow itself and result in an endless loop.
+        metrics::histogram!("sql", start.elapsed(), "schema" => "slow_queries", "method" => "store_slow_queries");
+        Ok(())
+    }
+
+    /// Loads the most recent slow queries, newest first.
+    #[tracing::instrument(skip_all, fields(schema = "slow_queries"))]
+    pub async fn load_slow_queries(&mut self, limit: u32) -> QueryResult<Vec<StoredSlowQuery>> {
+        let start = Instant::now();
+        let queries = sqlx::query_as!(
+            StoredSlowQuery,
+            "SELECT * FROM slow_queries ORDER BY id DESC LIMIT $1",
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("slow_queries", "load_slow_queries", start);
+        Ok(queries)
+    }
+
+    /// Removes the slow queries recorded earlier than `before`.
+    /// Returns the amount of removed queries.
+    #[tracing::instrument(skip_all, fields(schema = "slow_queries"))]
+    pub async fn remove_old_slow_queries(&mut self, before: DateTime<Utc>) -> QueryResult<u64> {
+        let start = Instant::now();
+        let removed = sqlx::query!("DELETE FROM slow_queries WHERE created_at < $1", before)
+            .execute(self.0.conn())
+            .await?
+            .rows_affected();
+
+        report_query("slow_queries", "remove_old_slow_queries", start);
+        Ok(removed)
+    }
+}
+
+/// Runs the task storing the slow queries reported within the process to the database.
+/// Slow queries are only recorded while the task is running, and it may be started only once.
+#[must_use]
+pub fn run_slow_queries_recorder(pool: ConnectionPool) -> JoinHandle<()> {
+    let (sender, mut receiver) = mpsc::channel(RECORDER_QUEUE_SIZE);
+    RECORDER
+        .set(sender)
+        .expect("slow queries recorder is already running");
+
+    tokio::spawn(async move {
+        while let Some(query) = receiver.recv().await {
+            let mut queries = vec![query];
+            while let Ok(query) = receiver.try_recv() {
+                queries.push(query);
+            }
+
+            let result = match pool.access_storage().await {
+                Ok(mut storage) => {
+                    storage
+                        .slow_queries_schema()
+                        .store_slow_queries(&queries)
+                        .await
+                }
+                Err(err) => Err(err.into()),
+            };
+            if let Err(err) = result {
+                vlog::error!("Can't record {} slow queries: {}", queries.len(), err);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sql_normalization() {
+        let sql =
+            "SELECT *\n  FROM  tx_filters\n  WHERE address = $1 AND tx_hash = 'abc''d'\n  LIMIT 10";
+        assert_eq!(
+            normalize_sql(sql),
+            "SELECT * FROM tx_filters WHERE address = $1 AND tx_hash = ? LIMIT ?"
+        );
+        // Numbers within the identifiers are kept.
+        assert_eq!(
+            normalize_sql("SELECT block_2 FROM t1 WHERE x > 1.5"),
+            "SELECT block_2 FROM t1 WHERE x > ?"
+        );
+    }
+}
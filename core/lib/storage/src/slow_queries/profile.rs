@@ -0,0 +1,126 @@
+//! Profiling of the storage queries made by a future, e.g. by the API request handler.
+//!
+//! Schema methods called within [`profile_queries`] report their execution times to the
+//! profile of the future in addition to the `sql` histogram, so the callers can see which
+//! methods take the most time. Methods called by other methods are reported by both of them,
+//! so the total storage time is calculated as the time at least one method was running.
+
+// Built-in deps
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    time::{Duration, Instant},
+};
+// External imports
+// Local imports
+
+tokio::task_local! {
+    static QUERY_PROFILE: RefCell<QueryProfile>;
+}
+
+/// Storage time spent by the profiled future.
+#[derive(Debug, Clone, Default)]
+pub struct QueryProfile {
+    /// Execution intervals of the schema methods.
+    intervals: Vec<(Instant, Instant)>,
+    /// Total execution time of the every called schema method.
+    methods: HashMap<(&'static str, &'static str), Duration>,
+}
+
+impl QueryProfile {
+    fn add(&mut self, schema: &'static str, method: &'static str, start: Instant, end: Instant) {
+        self.intervals.push((start, end));
+        *self.methods.entry((schema, method)).or_default() += end - start;
+    }
+
+    /// Returns the time at least one schema method was running.
+    pub fn total(&self) -> Duration {
+        let mut intervals = self.intervals.clone();
+        intervals.sort_unstable();
+
+        let mut total = Duration::default();
+        let mut current: Option<(Instant, Instant)> = None;
+        for (start, end) in intervals {
+            current = match current {
+                Some((current_start, current_end)) if start <= current_end => {
+                    Some((current_start, current_end.max(end)))
+                }
+                Some((current_start, current_end)) => {
+                    total += current_end - current_start;
+                    Some((start, end))
+                }
+                None => Some((start, end)),
+            };
+        }
+        if let Some((start, end)) = current {
+            total += end - start;
+        }
+        total
+    }
+
+    /// Returns the total execution time of every called schema method, keyed by `schema::method`.
+    pub fn methods(&self) -> impl Iterator<Item = (String, Duration)> + '_ {
+        self.methods
+            .iter()
+            .map(|((schema, method), duration)| (format!("{}::{}", schema, method), *duration))
+    }
+}
+
+/// Runs the future, collecting the execution times of the schema methods it calls.
+pub async fn profile_queries<F: Future>(future: F) -> (F::Output, QueryProfile) {
+    QUERY_PROFILE
+        .scope(RefCell::default(), async move {
+            let output = future.await;
+            let profile = QUERY_PROFILE.with(|profile| profile.take());
+            (output, profile)
+        })
+        .await
+}
+
+/// Adds the schema method execution to the profile of the current future, if it's profiled.
+pub(super) fn report(schema: &'static str, method: &'static str, start: Instant, end: Instant) {
+    let _ = QUERY_PROFILE.try_with(|profile| profile.borrow_mut().add(schema, method, start, end));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn nested_methods_are_counted_once() {
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        let ((), profile) = profile_queries(async {
+            report("chain.account", "account_id_by_address", at(10), at(20));
+            report("chain.account", "get_account_nonce_at", at(0), at(30));
+            report("chain.block", "get_block", at(50), at(60));
+        })
+        .await;
+        assert_eq!(profile.total(), Duration::from_millis(40));
+
+        let mut methods: Vec<_> = profile.methods().collect();
+        methods.sort();
+        assert_eq!(
+            methods,
+            vec![
+                (
+                    "chain.account::account_id_by_address".to_owned(),
+                    Duration::from_millis(10)
+                ),
+                (
+                    "chain.account::get_account_nonce_at".to_owned(),
+                    Duration::from_millis(30)
+                ),
+                (
+                    "chain.block::get_block".to_owned(),
+                    Duration::from_millis(10)
+                ),
+            ]
+        );
+
+        // Methods called outside of the profiled futures are not collected.
+        report("chain.block", "get_block", at(0), at(10));
+    }
+}
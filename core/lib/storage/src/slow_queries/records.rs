@@ -0,0 +1,15 @@
+// External imports
+use chrono::prelude::*;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredSlowQuery {
+    pub id: i64,
+    pub schema_name: String,
+    pub method: String,
+    pub duration_ms: i64,
+    pub created_at: DateTime<Utc>,
+    pub sql: Option<String>,
+}
@@ -0,0 +1,120 @@
+//! Sampling of the `sql` histogram reported by every schema method call.
+//!
+//! Recording each call to the histogram has a measurable overhead under load, so only one of
+//! every `DATABASE_METRICS_SAMPLE_RATE` calls is recorded (every call by default). Calls which
+//! took longer than `DATABASE_METRICS_FULL_FIDELITY_THRESHOLD` milliseconds are always recorded,
+//! so the tail latencies are not affected by the sampling, while the number of the fast calls
+//! in the histogram must be multiplied by the rate.
+//!
+//! Both parameters may be changed while the server is running via [`set_query_metrics_sampling`].
+
+// Built-in deps
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+// External imports
+use once_cell::sync::Lazy;
+// Local imports
+
+/// Threshold used if the `DATABASE_METRICS_FULL_FIDELITY_THRESHOLD` variable is not set.
+const DEFAULT_FULL_FIDELITY_THRESHOLD: Duration = Duration::from_millis(100);
+
+static SAMPLE_RATE: Lazy<AtomicU32> = Lazy::new(|| {
+    let rate = std::env::var("DATABASE_METRICS_SAMPLE_RATE")
+        .map(|rate| {
+            rate.parse()
+                .expect("DATABASE_METRICS_SAMPLE_RATE must be a positive number")
+        })
+        .unwrap_or(1);
+    AtomicU32::new(rate)
+});
+
+/// Stored in microseconds, so the threshold can be updated atomically.
+static FULL_FIDELITY_THRESHOLD: Lazy<AtomicU64> = Lazy::new(|| {
+    let threshold = std::env::var("DATABASE_METRICS_FULL_FIDELITY_THRESHOLD")
+        .map(|threshold| {
+            let millis = threshold.parse().expect(
+                "DATABASE_METRICS_FULL_FIDELITY_THRESHOLD must be a number of milliseconds",
+            );
+            Duration::from_millis(millis)
+        })
+        .unwrap_or(DEFAULT_FULL_FIDELITY_THRESHOLD);
+    AtomicU64::new(threshold.as_micros() as u64)
+});
+
+/// Number of the calls faster than the threshold, used to pick the sampled ones.
+static FAST_CALLS: AtomicU64 = AtomicU64::new(0);
+
+/// Parameters of the `sql` histogram sampling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryMetricsSampling {
+    /// One of every `rate` calls is recorded, `1` means that every call is recorded.
+    pub rate: u32,
+    /// Calls taking at least this long are recorded regardless of the rate.
+    pub full_fidelity_threshold: Duration,
+}
+
+impl QueryMetricsSampling {
+    /// Returns whether the call with the given sequence number among the fast calls is recorded.
+    fn is_recorded(&self, duration: Duration, fast_call: impl FnOnce() -> u64) -> bool {
+        duration >= self.full_fidelity_threshold
+            || self.rate <= 1
+            || fast_call() % u64::from(self.rate) == 0
+    }
+}
+
+/// Returns the current sampling parameters.
+pub fn query_metrics_sampling() -> QueryMetricsSampling {
+    QueryMetricsSampling {
+        rate: SAMPLE_RATE.load(Ordering::Relaxed),
+        full_fidelity_threshold: Duration::from_micros(
+            FULL_FIDELITY_THRESHOLD.load(Ordering::Relaxed),
+        ),
+    }
+}
+
+/// Changes the sampling of the `sql` histogram for the whole process, rate `0` is treated as `1`.
+pub fn set_query_metrics_sampling(sampling: QueryMetricsSampling) {
+    let rate = sampling.rate.max(1);
+    SAMPLE_RATE.store(rate, Ordering::Relaxed);
+    FULL_FIDELITY_THRESHOLD.store(
+        sampling.full_fidelity_threshold.as_micros() as u64,
+        Ordering::Relaxed,
+    );
+    metrics::gauge!("sql.sample_rate", f64::from(rate));
+    vlog::info!(
+        "SQL metrics sampling: 1 of {} calls, calls longer than {}ms are always recorded",
+        rate,
+        sampling.full_fidelity_threshold.as_millis()
+    );
+}
+
+/// Returns whether the call which took `duration` must be recorded to the `sql` histogram.
+pub(super) fn is_recorded(duration: Duration) -> bool {
+    query_metrics_sampling().is_recorded(duration, || FAST_CALLS.fetch_add(1, Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sampled_calls() {
+        let sampling = QueryMetricsSampling {
+            rate: 4,
+            full_fidelity_threshold: Duration::from_millis(100),
+        };
+        let recorded: Vec<_> = (0..8)
+            .filter(|&call| sampling.is_recorded(Duration::from_millis(1), || call))
+            .collect();
+        assert_eq!(recorded, vec![0, 4]);
+
+        // Slow calls are recorded without affecting the sampling of the fast ones.
+        assert!(sampling.is_recorded(Duration::from_millis(100), || unreachable!()));
+
+        let full = QueryMetricsSampling {
+            rate: 1,
+            ..sampling
+        };
+        assert!((0..8).all(|call| full.is_recorded(Duration::from_millis(1), || call)));
+    }
+}
@@ -0,0 +1,183 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use self::records::{CpkSponsorship, FeeFreeTransfer, StoredCpkSponsorship, StoredFeeFreeTransfer};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the fees paid by the operator on behalf of the users.
+///
+/// The operator pays the ChangePubKey fee of the new accounts meeting the configured
+/// criteria, and waives the fee of the transfers of the operator addresses within their
+/// daily quotas. The sponsorship is stored along with the sponsored transaction, so the
+/// accounting is consistent with the mempool.
+#[derive(Debug)]
+pub struct SponsorshipSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> SponsorshipSchema<'a, 'c> {
+    /// Stores the sponsorship of the ChangePubKey transaction.
+    /// Returns `false` if the account has already been sponsored, nothing is stored in this case.
+    #[tracing::instrument(skip_all, fields(schema = "sponsorship"))]
+    pub async fn store_cpk_sponsorship(
+        &mut self,
+        sponsorship: &CpkSponsorship,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let stored = sqlx::query!(
+            "INSERT INTO cpk_sponsorships (account_id, tx_hash, fee_token, waived_fee, waived_fee_usd_scale6)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (account_id) DO NOTHING",
+            i64::from(*sponsorship.account_id),
+            sponsorship.tx_hash.as_ref(),
+            sponsorship.fee_token.0 as i32,
+            sponsorship.waived_fee.clone(),
+            sponsorship.waived_fee_usd_scaled as i64,
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        crate::slow_queries::report_query("sponsorship", "store_cpk_sponsorship", start);
+        Ok(stored > 0)
+    }
+
+    /// Returns the total value of the sponsored fees in USD, scaled by `SUBSIDY_USD_AMOUNTS_SCALE`.
+    #[tracing::instrument(skip_all, fields(schema = "sponsorship"))]
+    pub async fn get_total_sponsored_usd(&mut self) -> QueryResult<u64> {
+        let start = Instant::now();
+        let total = sqlx::query!(
+            r#"SELECT COALESCE(SUM(waived_fee_usd_scale6), 0)::BIGINT AS "total!" FROM cpk_sponsorships"#
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .total;
+
+        crate::slow_queries::report_query("sponsorship", "get_total_sponsored_usd", start);
+        Ok(total as u64)
+    }
+
+    /// Loads the sponsorships stored before the one with the `before` id (or the latest ones
+    /// if it's `None`), newest first.
+    #[tracing::instrument(skip_all, fields(schema = "sponsorship"))]
+    pub async fn load_cpk_sponsorships(
+        &mut self,
+        before: Option<i64>,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredCpkSponsorship>> {
+        let start = Instant::now();
+        let sponsorships = sqlx::query_as!(
+            StoredCpkSponsorship,
+            "SELECT * FROM cpk_sponsorships
+            WHERE $1::bigint IS NULL OR id < $1
+            ORDER BY id DESC
+            LIMIT $2",
+            before,
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("sponsorship", "load_cpk_sponsorships", start);
+        Ok(sponsorships)
+    }
+
+    /// Stores the transfer of the operator address with the fee waived, using up one transfer
+    /// of the address quota for the current day. Returns `false` if the quota is exhausted,
+    /// nothing is stored in this case. The resubmitted transfer doesn't use up the quota again.
+    #[tracing::instrument(skip_all, fields(schema = "sponsorship"))]
+    pub async fn store_fee_free_transfer(
+        &mut self,
+        transfer: &FeeFreeTransfer,
+        daily_quota: u64,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let stored = sqlx::query!(
+            "INSERT INTO fee_free_transfers (address, tx_hash, fee_token, waived_fee)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (tx_hash) DO NOTHING",
+            transfer.address.as_bytes(),
+            transfer.tx_hash.as_ref(),
+            transfer.fee_token.0 as i32,
+            transfer.waived_fee.clone(),
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+
+        if stored > 0 {
+            // The counter is updated by a single statement, so the quota can't be exceeded
+            // by the transfers admitted concurrently.
+            let counted = sqlx::query!(
+                "INSERT INTO fee_free_transfer_quotas (address, day, used)
+                SELECT $1, (now() AT TIME ZONE 'UTC')::date, 1
+                WHERE $2 > 0
+                ON CONFLICT (address, day) DO UPDATE
+                SET used = fee_free_transfer_quotas.used + 1
+                WHERE fee_free_transfer_quotas.used < $2",
+                transfer.address.as_bytes(),
+                daily_quota as i64,
+            )
+            .execute(transaction.conn())
+            .await?
+            .rows_affected();
+            if counted == 0 {
+                return Ok(false);
+            }
+        }
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("sponsorship", "store_fee_free_transfer", start);
+        Ok(true)
+    }
+
+    /// Returns the amount of the fee-free transfers of the operator address for the current day.
+    #[tracing::instrument(skip_all, fields(schema = "sponsorship"))]
+    pub async fn get_fee_free_transfers_used(&mut self, address: Address) -> QueryResult<u64> {
+        let start = Instant::now();
+        let used = sqlx::query!(
+            "SELECT used FROM fee_free_transfer_quotas
+            WHERE address = $1 AND day = (now() AT TIME ZONE 'UTC')::date",
+            address.as_bytes(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map_or(0, |row| row.used);
+
+        crate::slow_queries::report_query("sponsorship", "get_fee_free_transfers_used", start);
+        Ok(used as u64)
+    }
+
+    /// Loads the fee-free transfers (of the given address only if it's set) stored before
+    /// the one with the `before` id (or the latest ones if it's `None`), newest first.
+    #[tracing::instrument(skip_all, fields(schema = "sponsorship"))]
+    pub async fn load_fee_free_transfers(
+        &mut self,
+        address: Option<Address>,
+        before: Option<i64>,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredFeeFreeTransfer>> {
+        let start = Instant::now();
+        let address = address.map(|address| address.as_bytes().to_vec());
+        let transfers = sqlx::query_as!(
+            StoredFeeFreeTransfer,
+            "SELECT * FROM fee_free_transfers
+            WHERE ($1::bytea IS NULL OR address = $1)
+                AND ($2::bigint IS NULL OR id < $2)
+            ORDER BY id DESC
+            LIMIT $3",
+            address,
+            before,
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("sponsorship", "load_fee_free_transfers", start);
+        Ok(transfers)
+    }
+}
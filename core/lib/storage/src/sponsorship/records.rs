@@ -0,0 +1,49 @@
+// External imports
+use chrono::prelude::*;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+use zksync_types::{tx::TxHash, AccountId, Address, TokenId};
+// Local imports
+
+/// ChangePubKey transaction with the fee paid by the operator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CpkSponsorship {
+    pub account_id: AccountId,
+    pub tx_hash: TxHash,
+    pub fee_token: TokenId,
+    /// Part of the required fee which is not paid by the account.
+    pub waived_fee: BigDecimal,
+    /// Value of the waived fee in USD, scaled by `SUBSIDY_USD_AMOUNTS_SCALE`.
+    pub waived_fee_usd_scaled: u64,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredCpkSponsorship {
+    pub id: i64,
+    pub account_id: i64,
+    pub tx_hash: Vec<u8>,
+    pub fee_token: i32,
+    pub waived_fee: BigDecimal,
+    pub waived_fee_usd_scale6: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Transfer of the operator address with the fee waived.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeFreeTransfer {
+    pub address: Address,
+    pub tx_hash: TxHash,
+    pub fee_token: TokenId,
+    /// Part of the required fee which is not paid by the operator address.
+    pub waived_fee: BigDecimal,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredFeeFreeTransfer {
+    pub id: i64,
+    pub address: Vec<u8>,
+    pub tx_hash: Vec<u8>,
+    pub fee_token: i32,
+    pub waived_fee: BigDecimal,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,343 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Duration, Utc};
+// Workspace imports
+use zksync_api_types::v02::pagination::PaginationDirection;
+use zksync_types::{
+    tx::{TxEthSignature, TxHash},
+    AccountId, PubKeyHash, Transfer,
+};
+use zksync_utils::biguint_to_big_decimal;
+// Local imports
+use self::records::{StoredStandingOrder, StoredStandingOrderPayment};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the standing orders: recurring payments registered as the sets of pre-signed
+/// transfers of the consecutive nonces, one transfer per scheduled payment.
+///
+/// Order stays `active` until all of its payments are submitted (`completed`), the user
+/// cancels it (`cancelled`) or one of its payments is rejected (`failed`). In the last two
+/// cases the remaining payments are `skipped`, since their nonces can't be used anymore.
+#[derive(Debug)]
+pub struct StandingOrdersSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> StandingOrdersSchema<'a, 'c> {
+    /// Stores the new active order with the payment per each of the `transfers`, which
+    /// must not be empty. Payments are scheduled every `interval_secs` starting from
+    /// `first_payment_at`. Returns the id of the order.
+    #[tracing::instrument(skip_all, fields(schema = "standing_orders"))]
+    pub async fn store_standing_order(
+        &mut self,
+        transfers: &[(Transfer, Option<TxEthSignature>)],
+        signer_pub_key_hash: PubKeyHash,
+        first_payment_at: DateTime<Utc>,
+        interval_secs: u64,
+        expires_at: DateTime<Utc>,
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let template = &transfers[0].0;
+
+        let mut transaction = self.0.start_transaction().await?;
+        let id = sqlx::query!(
+            "INSERT INTO standing_orders (
+                account_id, from_address, to_address, token_id, amount, fee,
+                signer_pub_key_hash, first_payment_at, interval_secs, expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING id",
+            i64::from(*template.account_id),
+            template.from.as_bytes(),
+            template.to.as_bytes(),
+            *template.token as i32,
+            biguint_to_big_decimal(template.amount.clone()),
+            biguint_to_big_decimal(template.fee.clone()),
+            &signer_pub_key_hash.data[..],
+            first_payment_at,
+            interval_secs as i64,
+            expires_at,
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .id;
+
+        for (sequence, (tx, eth_signature)) in transfers.iter().enumerate() {
+            let scheduled_at =
+                first_payment_at + Duration::seconds(interval_secs as i64 * sequence as i64);
+            sqlx::query!(
+                "INSERT INTO standing_order_payments (
+                    standing_order_id, sequence, nonce, scheduled_at, tx, eth_signature
+                )
+                VALUES ($1, $2, $3, $4, $5, $6)",
+                id,
+                sequence as i32,
+                i64::from(*tx.nonce),
+                scheduled_at,
+                serde_json::to_value(tx).expect("failed to serialize the transfer"),
+                eth_signature.as_ref().map(|signature| {
+                    serde_json::to_value(signature)
+                        .expect("failed to serialize the transfer signature")
+                }),
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("standing_orders", "store_standing_order", start);
+        Ok(id)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "standing_orders"))]
+    pub async fn get_standing_order(
+        &mut self,
+        id: i64,
+    ) -> QueryResult<Option<StoredStandingOrder>> {
+        let start = Instant::now();
+        let order = sqlx::query_as!(
+            StoredStandingOrder,
+            "SELECT * FROM standing_orders WHERE id = $1",
+            id,
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("standing_orders", "get_standing_order", start);
+        Ok(order)
+    }
+
+    /// Loads the payments of the order, from the first one.
+    #[tracing::instrument(skip_all, fields(schema = "standing_orders"))]
+    pub async fn get_standing_order_payments(
+        &mut self,
+        id: i64,
+    ) -> QueryResult<Vec<StoredStandingOrderPayment>> {
+        let start = Instant::now();
+        let payments = sqlx::query_as!(
+            StoredStandingOrderPayment,
+            "SELECT * FROM standing_order_payments WHERE standing_order_id = $1 ORDER BY sequence",
+            id,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("standing_orders", "get_standing_order_payments", start);
+        Ok(payments)
+    }
+
+    /// Cancels the active order and skips its remaining payments.
+    /// Returns `false` if the order is not active.
+    #[tracing::instrument(skip_all, fields(schema = "standing_orders"))]
+    pub async fn cancel_standing_order(&mut self, id: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let result = sqlx::query!(
+            "UPDATE standing_orders SET status = 'cancelled', updated_at = now()
+            WHERE id = $1 AND status = 'active'",
+            id,
+        )
+        .execute(transaction.conn())
+        .await?;
+        let cancelled = result.rows_affected() > 0;
+        if cancelled {
+            sqlx::query!(
+                "UPDATE standing_order_payments SET status = 'skipped'
+                WHERE standing_order_id = $1 AND status = 'scheduled'",
+                id,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("standing_orders", "cancel_standing_order", start);
+        Ok(cancelled)
+    }
+
+    /// Locks the earliest due payment of the active orders, so it's not picked up by the other
+    /// servers until the transaction is finished. Payments are only returned after all of
+    /// the previous payments of the order are processed, since their nonces are consecutive.
+    ///
+    /// Must be called within the transaction, which also stores the result of the payment.
+    #[tracing::instrument(skip_all, fields(schema = "standing_orders"))]
+    pub async fn lock_due_payment(&mut self) -> QueryResult<Option<StoredStandingOrderPayment>> {
+        let start = Instant::now();
+        let payment = sqlx::query_as!(
+            StoredStandingOrderPayment,
+            "SELECT p.* FROM standing_order_payments p
+            INNER JOIN standing_orders o ON o.id = p.standing_order_id
+            WHERE p.status = 'scheduled' AND p.scheduled_at <= now() AND o.status = 'active'
+                AND NOT EXISTS (
+                    SELECT 1 FROM standing_order_payments prev
+                    WHERE prev.standing_order_id = p.standing_order_id
+                        AND prev.sequence < p.sequence AND prev.status = 'scheduled'
+                )
+            ORDER BY p.scheduled_at
+            LIMIT 1
+            FOR UPDATE OF p SKIP LOCKED",
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("standing_orders", "lock_due_payment", start);
+        Ok(payment)
+    }
+
+    /// Marks the payment as submitted to the mempool. The order is completed once its last
+    /// payment is submitted.
+    #[tracing::instrument(skip_all, fields(schema = "standing_orders"))]
+    pub async fn store_payment_submitted(
+        &mut self,
+        id: i64,
+        sequence: i32,
+        tx_hash: TxHash,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query!(
+            "UPDATE standing_order_payments
+            SET status = 'submitted', tx_hash = $3, submitted_at = now()
+            WHERE standing_order_id = $1 AND sequence = $2",
+            id,
+            sequence,
+            tx_hash.as_ref(),
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            "UPDATE standing_orders SET status = 'completed', updated_at = now()
+            WHERE id = $1 AND status = 'active' AND NOT EXISTS (
+                SELECT 1 FROM standing_order_payments
+                WHERE standing_order_id = $1 AND status = 'scheduled'
+            )",
+            id,
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("standing_orders", "store_payment_submitted", start);
+        Ok(())
+    }
+
+    /// Marks the payment as rejected. The order fails and its remaining payments are skipped.
+    #[tracing::instrument(skip_all, fields(schema = "standing_orders"))]
+    pub async fn store_payment_failure(
+        &mut self,
+        id: i64,
+        sequence: i32,
+        error: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query!(
+            "UPDATE standing_order_payments SET status = 'failed', error = $3
+            WHERE standing_order_id = $1 AND sequence = $2",
+            id,
+            sequence,
+            error,
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            "UPDATE standing_order_payments SET status = 'skipped'
+            WHERE standing_order_id = $1 AND status = 'scheduled'",
+            id,
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            "UPDATE standing_orders SET status = 'failed', failure_reason = $2, updated_at = now()
+            WHERE id = $1 AND status = 'active'",
+            id,
+            error,
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("standing_orders", "store_payment_failure", start);
+        Ok(())
+    }
+
+    /// Returns the id of the last order registered by the account.
+    #[tracing::instrument(skip_all, fields(schema = "standing_orders"))]
+    pub async fn get_max_account_standing_order_id(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<Option<i64>> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            "SELECT max(id) FROM standing_orders WHERE account_id = $1",
+            i64::from(*account_id),
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .max;
+
+        crate::slow_queries::report_query(
+            "standing_orders",
+            "get_max_account_standing_order_id",
+            start,
+        );
+        Ok(id)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "standing_orders"))]
+    pub async fn get_account_standing_orders_count(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            r#"SELECT count(*) as "count!" FROM standing_orders WHERE account_id = $1"#,
+            i64::from(*account_id),
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        crate::slow_queries::report_query(
+            "standing_orders",
+            "get_account_standing_orders_count",
+            start,
+        );
+        Ok(count as u32)
+    }
+
+    /// Loads the page of the orders registered by the account, regardless of their status.
+    #[tracing::instrument(skip_all, fields(schema = "standing_orders"))]
+    pub async fn get_account_standing_orders_page(
+        &mut self,
+        account_id: AccountId,
+        from_id: i64,
+        limit: u32,
+        direction: PaginationDirection,
+    ) -> QueryResult<Vec<StoredStandingOrder>> {
+        let start = Instant::now();
+        let query = "SELECT * FROM standing_orders WHERE account_id = $1";
+        let query = match direction {
+            PaginationDirection::Newer => {
+                format!("{} AND id >= $2 ORDER BY id LIMIT $3", query)
+            }
+            PaginationDirection::Older => {
+                format!("{} AND id <= $2 ORDER BY id DESC LIMIT $3", query)
+            }
+        };
+        let orders = sqlx::query_as(query.as_str())
+            .bind(i64::from(*account_id))
+            .bind(from_id)
+            .bind(limit as i64)
+            .fetch_all(self.0.conn())
+            .await?;
+
+        crate::slow_queries::report_query(
+            "standing_orders",
+            "get_account_standing_orders_page",
+            start,
+        );
+        Ok(orders)
+    }
+}
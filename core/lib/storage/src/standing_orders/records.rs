@@ -0,0 +1,43 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredStandingOrder {
+    pub id: i64,
+    pub account_id: i64,
+    pub from_address: Vec<u8>,
+    pub to_address: Vec<u8>,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub fee: BigDecimal,
+    /// Hash of the public key the transfers are signed with.
+    pub signer_pub_key_hash: Vec<u8>,
+    pub first_payment_at: DateTime<Utc>,
+    pub interval_secs: i64,
+    pub expires_at: DateTime<Utc>,
+    pub status: String,
+    /// Error of the payment the order has failed on.
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredStandingOrderPayment {
+    pub standing_order_id: i64,
+    pub sequence: i32,
+    pub nonce: i64,
+    pub scheduled_at: DateTime<Utc>,
+    /// Serialized signed `Transfer`.
+    pub tx: Value,
+    /// Serialized `TxEthSignature` of the transfer, if provided.
+    pub eth_signature: Option<Value>,
+    pub status: String,
+    pub tx_hash: Option<Vec<u8>>,
+    pub error: Option<String>,
+    pub submitted_at: Option<DateTime<Utc>>,
+}
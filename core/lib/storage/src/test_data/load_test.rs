@@ -0,0 +1,620 @@
+//! Generator of the large synthetic datasets used to measure the performance of the
+//! `chain.operations_ext` queries on the realistic amounts of data.
+//!
+//! The data is generated deterministically from the seed, so the same dataset can be recreated
+//! in any environment and the query timings of different revisions can be compared.
+//! Rows are inserted directly into the tables in large batches, bypassing the mempool and the
+//! state keeper, so only the tables used by the transaction queries are populated:
+//! `blocks`, `executed_transactions`, `executed_priority_operations`, `tx_filters` (along with
+//! `txs_count` maintained by its triggers) and the bindings of the commit and execute
+//! aggregated operations. Neither the accounts state nor the arguments of the aggregated
+//! operations are stored.
+//!
+//! Activity of the accounts follows the power law: a few accounts (e.g. exchanges) take part in
+//! a large share of the transactions, while most of the accounts have a handful of them.
+//! The same applies to the tokens.
+
+// Built-in uses
+use std::time::{Duration, Instant};
+// External imports
+use chrono::{DateTime, TimeZone, Utc};
+use num::BigUint;
+// Workspace imports
+use zksync_crypto::rand::{Rng, SeedableRng, XorShiftRng};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    block::{ExecutedPriorityOp, ExecutedTx},
+    tx::{TimeRange, TxHash},
+    AccountId, Address, BlockNumber, Deposit, DepositOp, FullExit, FullExitOp, Nonce, PriorityOp,
+    SignedZkSyncTx, TokenId, Transfer, TransferOp, Withdraw, WithdrawOp, ZkSyncOp,
+    ZkSyncPriorityOp, ZkSyncTx, H256,
+};
+// Local imports
+use crate::chain::operations::{
+    bytea_literal,
+    records::{NewExecutedPriorityOperation, NewExecutedTransaction},
+};
+use crate::{QueryResult, StorageProcessor};
+
+/// Approximate number of the executed operations stored within one database transaction.
+const OPERATIONS_PER_CHUNK: u32 = 10_000;
+/// Interval between the generated blocks.
+const BLOCK_INTERVAL: Duration = Duration::from_secs(30);
+/// Share of the withdrawals among the L2 transactions, the rest are transfers.
+const WITHDRAWALS_SHARE: f64 = 0.1;
+/// Share of the full exits among the priority operations, the rest are deposits.
+const FULL_EXITS_SHARE: f64 = 0.03;
+
+/// Parameters of the generated dataset.
+#[derive(Debug, Clone)]
+pub struct LoadTestConfig {
+    /// Seed of the generator, the same seed always produces the same data.
+    pub seed: [u32; 4],
+    pub blocks: u32,
+    pub txs_per_block: u32,
+    pub priority_ops_per_block: u32,
+    pub accounts: u32,
+    pub tokens: u32,
+    /// Share of the transactions rejected by the state keeper.
+    pub failed_txs_ratio: f64,
+    /// Skew of the accounts activity, `1.0` means the uniform distribution,
+    /// the larger values make the most active accounts take part in more transactions.
+    pub accounts_skew: f64,
+    /// Same as `accounts_skew`, but for the tokens.
+    pub tokens_skew: f64,
+    /// Number of the last generated blocks which are committed but not executed on L1.
+    pub pending_blocks: u32,
+}
+
+impl Default for LoadTestConfig {
+    /// About a million of operations applied to a hundred thousand accounts.
+    fn default() -> Self {
+        Self {
+            seed: [1, 2, 3, 4],
+            blocks: 10_000,
+            txs_per_block: 100,
+            priority_ops_per_block: 5,
+            accounts: 100_000,
+            tokens: 50,
+            failed_txs_ratio: 0.02,
+            accounts_skew: 3.0,
+            tokens_skew: 4.0,
+            pending_blocks: 10,
+        }
+    }
+}
+
+impl LoadTestConfig {
+    /// Addresses of the generated accounts, the most active ones first.
+    /// Allows to reuse the data generated by the previous runs with the same config.
+    pub fn accounts(&self) -> Vec<Address> {
+        generate_accounts(&mut XorShiftRng::from_seed(self.seed), self.accounts)
+    }
+}
+
+/// Description of the generated dataset, used to pick the query arguments.
+#[derive(Debug, Clone)]
+pub struct LoadTestData {
+    pub first_block: BlockNumber,
+    pub last_block: BlockNumber,
+    /// Addresses of the accounts, the most active ones first.
+    pub accounts: Vec<Address>,
+    /// Hash of a successful transaction of every block which has one.
+    pub tx_hashes: Vec<TxHash>,
+    /// Ethereum hash of a priority operation of every block which has one.
+    pub priority_op_eth_hashes: Vec<H256>,
+}
+
+fn generate_accounts<R: Rng>(rng: &mut R, count: u32) -> Vec<Address> {
+    (0..count)
+        .map(|_| Address::from(rng.gen::<[u8; 20]>()))
+        .collect()
+}
+
+/// Picks the index in `0..len`, the lower indices are picked more often the larger the skew is.
+fn skewed_index<R: Rng>(rng: &mut R, len: u32, skew: f64) -> u32 {
+    let index = (rng.gen::<f64>().powf(skew) * f64::from(len)) as u32;
+    index.min(len - 1)
+}
+
+/// Amounts are spread over several orders of magnitude, from `1e12` to `1e18` of the units.
+fn random_amount<R: Rng>(rng: &mut R) -> BigUint {
+    let exponent = rng.gen_range(12, 18);
+    BigUint::from(rng.gen_range(1u64, 10u64)) * BigUint::from(10u64).pow(exponent)
+}
+
+struct Generator<'a> {
+    config: &'a LoadTestConfig,
+    rng: XorShiftRng,
+    accounts: Vec<Address>,
+    nonces: Vec<u32>,
+    next_serial_id: u64,
+}
+
+impl<'a> Generator<'a> {
+    fn new(config: &'a LoadTestConfig, next_serial_id: u64) -> Self {
+        let mut rng = XorShiftRng::from_seed(config.seed);
+        let accounts = generate_accounts(&mut rng, config.accounts);
+        Self {
+            config,
+            rng,
+            accounts,
+            nonces: vec![0; config.accounts as usize],
+            next_serial_id,
+        }
+    }
+
+    /// Picks the account, returns its index along with the address.
+    fn account(&mut self) -> (u32, Address) {
+        let index = skewed_index(
+            &mut self.rng,
+            self.config.accounts,
+            self.config.accounts_skew,
+        );
+        (index, self.accounts[index as usize])
+    }
+
+    /// Picks the account different from the given one.
+    fn other_account(&mut self, index: u32) -> (u32, Address) {
+        let (other, address) = self.account();
+        if other != index {
+            return (other, address);
+        }
+        let other = (index + 1) % self.config.accounts;
+        (other, self.accounts[other as usize])
+    }
+
+    fn token(&mut self) -> TokenId {
+        TokenId(skewed_index(
+            &mut self.rng,
+            self.config.tokens,
+            self.config.tokens_skew,
+        ))
+    }
+
+    fn priority_op(&mut self, block_index: u32, created_at: DateTime<Utc>) -> ExecutedPriorityOp {
+        let (index, address) = self.account();
+        let account_id = AccountId(index + 1);
+        let token = self.token();
+        let amount = random_amount(&mut self.rng);
+
+        let (data, op) = if self.rng.gen::<f64>() < FULL_EXITS_SHARE {
+            let full_exit = FullExit {
+                account_id,
+                eth_address: address,
+                token,
+                is_legacy: false,
+            };
+            let op = ZkSyncOp::FullExit(Box::new(FullExitOp {
+                priority_op: full_exit.clone(),
+                withdraw_amount: Some(amount.into()),
+                creator_account_id: None,
+                creator_address: None,
+                serial_id: None,
+                content_hash: None,
+            }));
+            (ZkSyncPriorityOp::FullExit(full_exit), op)
+        } else {
+            let deposit = Deposit {
+                from: address,
+                token,
+                amount,
+                to: address,
+            };
+            let op = ZkSyncOp::Deposit(Box::new(DepositOp {
+                priority_op: deposit.clone(),
+                account_id,
+            }));
+            (ZkSyncPriorityOp::Deposit(deposit), op)
+        };
+
+        let serial_id = self.next_serial_id;
+        self.next_serial_id += 1;
+        ExecutedPriorityOp {
+            priority_op: PriorityOp {
+                serial_id,
+                data,
+                deadline_block: 0,
+                eth_hash: H256::from(self.rng.gen::<[u8; 32]>()),
+                eth_block: serial_id,
+                eth_block_index: Some(0),
+                l1_sender: None,
+            },
+            op,
+            block_index,
+            created_at,
+        }
+    }
+
+    fn tx(&mut self, block_index: u32, created_at: DateTime<Utc>) -> ExecutedTx {
+        let (index, from) = self.account();
+        let account_id = AccountId(index + 1);
+        let token = self.token();
+        let amount = random_amount(&mut self.rng);
+        let fee = random_amount(&mut self.rng) / BigUint::from(1000u32);
+        let nonce = Nonce(self.nonces[index as usize]);
+        let success = self.rng.gen::<f64>() >= self.config.failed_txs_ratio;
+
+        let (tx, op) = if self.rng.gen::<f64>() < WITHDRAWALS_SHARE {
+            let withdraw = Withdraw::new(
+                account_id,
+                from,
+                from,
+                token,
+                amount,
+                fee,
+                nonce,
+                TimeRange::default(),
+                None,
+            );
+            let op = ZkSyncOp::Withdraw(Box::new(WithdrawOp {
+                tx: withdraw.clone(),
+                account_id,
+            }));
+            (ZkSyncTx::Withdraw(Box::new(withdraw)), op)
+        } else {
+            let (to_index, to) = self.other_account(index);
+            let transfer = Transfer::new(
+                account_id,
+                from,
+                to,
+                token,
+                amount,
+                fee,
+                nonce,
+                TimeRange::default(),
+                None,
+            );
+            let op = ZkSyncOp::Transfer(Box::new(TransferOp {
+                tx: transfer.clone(),
+                from: account_id,
+                to: AccountId(to_index + 1),
+            }));
+            (ZkSyncTx::Transfer(Box::new(transfer)), op)
+        };
+
+        if success {
+            self.nonces[index as usize] += 1;
+        }
+        ExecutedTx {
+            signed_tx: SignedZkSyncTx {
+                tx,
+                eth_sign_data: None,
+                created_at,
+            },
+            success,
+            op: if success { Some(op) } else { None },
+            fail_reason: if success {
+                None
+            } else {
+                Some("Not enough balance".to_string())
+            },
+            block_index: if success { Some(block_index) } else { None },
+            created_at,
+            batch_id: None,
+        }
+    }
+}
+
+/// Executed operations of the chunk of blocks, in the order of execution.
+#[derive(Default)]
+struct Chunk {
+    txs: Vec<NewExecutedTransaction>,
+    priority_ops: Vec<NewExecutedPriorityOperation>,
+    /// `true` for the priority operation, in the same order as the operations were executed.
+    order: Vec<bool>,
+}
+
+/// Populates the database with the synthetic blocks and their operations, which are appended
+/// after the last saved block. See the module docs for the details.
+pub async fn populate_load_test_data(
+    storage: &mut StorageProcessor<'_>,
+    config: &LoadTestConfig,
+) -> QueryResult<LoadTestData> {
+    let start = Instant::now();
+    let first_block = storage
+        .chain()
+        .block_schema()
+        .get_last_saved_block()
+        .await?
+        + 1;
+    let last_block = BlockNumber(*first_block + config.blocks - 1);
+    let last_executed_block = BlockNumber(last_block.saturating_sub(config.pending_blocks));
+    let next_serial_id = storage
+        .chain()
+        .operations_schema()
+        .get_max_priority_op_serial_id()
+        .await?
+        .map_or(0, |serial_id| serial_id + 1);
+
+    let mut generator = Generator::new(config, next_serial_id);
+    let mut tx_hashes = Vec::new();
+    let mut priority_op_eth_hashes = Vec::new();
+    let genesis_time = Utc.timestamp(1_600_000_000, 0);
+
+    let ops_per_block = config.txs_per_block + config.priority_ops_per_block;
+    let blocks_per_chunk = std::cmp::max(1, OPERATIONS_PER_CHUNK / ops_per_block.max(1));
+    let mut chunk_start = *first_block;
+    while chunk_start <= *last_block {
+        let chunk_end = std::cmp::min(chunk_start + blocks_per_chunk - 1, *last_block);
+        let mut transaction = storage.start_transaction().await?;
+
+        let mut chunk = Chunk::default();
+        for block_number in chunk_start..=chunk_end {
+            let block_time =
+                genesis_time + chrono::Duration::from_std(BLOCK_INTERVAL * block_number).unwrap();
+            let mut block_index = 0;
+            for _ in 0..config.priority_ops_per_block {
+                let op = generator.priority_op(block_index, block_time);
+                if block_index == 0 {
+                    priority_op_eth_hashes.push(op.priority_op.eth_hash);
+                }
+                block_index += 1;
+                chunk
+                    .priority_ops
+                    .push(NewExecutedPriorityOperation::prepare_stored_priority_op(
+                        op,
+                        BlockNumber(block_number),
+                    ));
+                chunk.order.push(true);
+            }
+
+            let mut block_tx_hash = None;
+            for _ in 0..config.txs_per_block {
+                let tx = generator.tx(block_index, block_time);
+                if tx.success {
+                    block_index += 1;
+                    block_tx_hash.get_or_insert_with(|| tx.signed_tx.hash());
+                }
+                chunk.txs.push(
+                    NewExecutedTransaction::prepare_stored_tx(
+                        tx,
+                        BlockNumber(block_number),
+                        &mut transaction,
+                    )
+                    .await?,
+                );
+                chunk.order.push(false);
+            }
+            tx_hashes.extend(block_tx_hash);
+        }
+
+        store_blocks(&mut transaction, chunk_start, chunk_end, &mut generator.rng).await?;
+        store_chunk_operations(&mut transaction, chunk).await?;
+        store_confirmed_action(
+            &mut transaction,
+            AggregatedActionType::CommitBlocks,
+            chunk_start,
+            chunk_end,
+        )
+        .await?;
+        if chunk_start <= *last_executed_block {
+            store_confirmed_action(
+                &mut transaction,
+                AggregatedActionType::ExecuteBlocks,
+                chunk_start,
+                std::cmp::min(chunk_end, *last_executed_block),
+            )
+            .await?;
+        }
+        transaction.commit().await?;
+
+        vlog::info!(
+            "Generated blocks {}..={} of {}",
+            chunk_start,
+            chunk_end,
+            *last_block
+        );
+        chunk_start = chunk_end + 1;
+    }
+
+    vlog::info!(
+        "Generated the load test data in {:?}: {} blocks with {} operations each",
+        start.elapsed(),
+        config.blocks,
+        ops_per_block
+    );
+    Ok(LoadTestData {
+        first_block,
+        last_block,
+        accounts: generator.accounts,
+        tx_hashes,
+        priority_op_eth_hashes,
+    })
+}
+
+async fn store_blocks<R: Rng>(
+    storage: &mut StorageProcessor<'_>,
+    from_block: u32,
+    to_block: u32,
+    rng: &mut R,
+) -> QueryResult<()> {
+    let numbers: Vec<i64> = (from_block..=to_block).map(i64::from).collect();
+    let root_hashes: Vec<Vec<u8>> = numbers
+        .iter()
+        .map(|_| rng.gen::<[u8; 32]>().to_vec())
+        .collect();
+    sqlx::query!(
+        "
+        INSERT INTO blocks (number, root_hash, fee_account_id, unprocessed_prior_op_before,
+            unprocessed_prior_op_after, block_size, commit_gas_limit, verify_gas_limit, timestamp)
+        SELECT u.number, u.root_hash, 0, 0, 0, 690, 3000000, 3000000,
+            1600000000 + u.number * $3
+        FROM UNNEST ($1::bigint[], $2::bytea[]) AS u(number, root_hash)
+        ",
+        &numbers,
+        &root_hashes,
+        BLOCK_INTERVAL.as_secs() as i64,
+    )
+    .execute(storage.conn())
+    .await?;
+    Ok(())
+}
+
+/// Stores the executed operations with the sequence numbers assigned in the order of execution,
+/// along with their `tx_filters`.
+async fn store_chunk_operations(
+    storage: &mut StorageProcessor<'_>,
+    chunk: Chunk,
+) -> QueryResult<()> {
+    let mut sequence_numbers = sqlx::query!(
+        r#"SELECT nextval('executed_operations_seq_number') AS "seq_no!"
+        FROM generate_series(1, $1)"#,
+        chunk.order.len() as i64,
+    )
+    .fetch_all(storage.conn())
+    .await?
+    .into_iter()
+    .map(|record| record.seq_no)
+    .collect::<Vec<_>>();
+    sequence_numbers.sort_unstable();
+
+    let mut txs = chunk.txs.iter();
+    let mut priority_ops = chunk.priority_ops.iter();
+    let mut tx_records = Vec::with_capacity(chunk.txs.len());
+    let mut priority_op_records = Vec::with_capacity(chunk.priority_ops.len());
+    let mut addresses = Vec::new();
+    let mut tokens = Vec::new();
+    let mut filter_hashes = Vec::new();
+    let mut filter_sequence_numbers = Vec::new();
+    let mut is_priority = Vec::new();
+    for (&priority, &seq_no) in chunk.order.iter().zip(&sequence_numbers) {
+        if priority {
+            let op = priority_ops.next().expect("order of the chunk is broken");
+            priority_op_records.push(serde_json::json!({
+                "block_number": op.block_number,
+                "block_index": op.block_index,
+                "operation": op.operation,
+                "from_account": bytea_literal(&op.from_account),
+                "to_account": bytea_literal(&op.to_account),
+                "priority_op_serialid": op.priority_op_serialid,
+                "deadline_block": op.deadline_block,
+                "eth_hash": bytea_literal(&op.eth_hash),
+                "eth_block": op.eth_block,
+                "created_at": op.created_at.to_rfc3339(),
+                "eth_block_index": op.eth_block_index,
+                "tx_hash": bytea_literal(&op.tx_hash),
+                "sequence_number": seq_no,
+            }));
+            for address in op.affected_accounts.iter() {
+                addresses.push(address.clone());
+                tokens.push(op.token);
+                filter_hashes.push(op.tx_hash.clone());
+                filter_sequence_numbers.push(seq_no);
+                is_priority.push(true);
+            }
+        } else {
+            let tx = txs.next().expect("order of the chunk is broken");
+            tx_records.push(serde_json::json!({
+                "block_number": tx.block_number,
+                "block_index": tx.block_index,
+                "tx": tx.tx,
+                "operation": tx.operation,
+                "tx_hash": bytea_literal(&tx.tx_hash),
+                "from_account": bytea_literal(&tx.from_account),
+                "to_account": tx.to_account.as_deref().map(bytea_literal),
+                "success": tx.success,
+                "fail_reason": tx.fail_reason,
+                "primary_account_address": bytea_literal(&tx.primary_account_address),
+                "nonce": tx.nonce,
+                "created_at": tx.created_at.to_rfc3339(),
+                "eth_sign_data": tx.eth_sign_data,
+                "batch_id": tx.batch_id,
+                "sequence_number": seq_no,
+            }));
+            for address in tx.affected_accounts.iter() {
+                for token in tx.used_tokens.iter() {
+                    addresses.push(address.clone());
+                    tokens.push(*token);
+                    filter_hashes.push(tx.tx_hash.clone());
+                    filter_sequence_numbers.push(seq_no);
+                    is_priority.push(false);
+                }
+            }
+        }
+    }
+
+    sqlx::query!(
+        "
+        INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id, sequence_number)
+        SELECT block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id, sequence_number
+            FROM jsonb_populate_recordset(NULL::executed_transactions, $1)
+        ",
+        serde_json::Value::Array(tx_records)
+    )
+    .execute(storage.conn())
+    .await?;
+    sqlx::query!(
+        "
+        INSERT INTO executed_priority_operations (block_number, block_index, operation, from_account, to_account, priority_op_serialid, deadline_block, eth_hash, eth_block, created_at, eth_block_index, tx_hash, sequence_number)
+        SELECT block_number, block_index, operation, from_account, to_account, priority_op_serialid, deadline_block, eth_hash, eth_block, created_at, eth_block_index, tx_hash, sequence_number
+            FROM jsonb_populate_recordset(NULL::executed_priority_operations, $1)
+        ",
+        serde_json::Value::Array(priority_op_records)
+    )
+    .execute(storage.conn())
+    .await?;
+    sqlx::query!(
+        "
+        INSERT INTO tx_filters (address, token, tx_hash, sequence_number, is_priority)
+        SELECT * FROM UNNEST ($1::bytea[], $2::integer[], $3::bytea[], $4::bigint[], $5::boolean[])
+        ON CONFLICT ON CONSTRAINT tx_filters_pkey DO NOTHING
+        ",
+        &addresses,
+        &tokens,
+        &filter_hashes,
+        &filter_sequence_numbers,
+        &is_priority,
+    )
+    .execute(storage.conn())
+    .await?;
+    Ok(())
+}
+
+/// Stores the confirmed aggregated operation of the blocks range with the empty arguments,
+/// which is enough for the queries of the blocks status.
+async fn store_confirmed_action(
+    storage: &mut StorageProcessor<'_>,
+    action_type: AggregatedActionType,
+    from_block: u32,
+    to_block: u32,
+) -> QueryResult<()> {
+    let op_id = sqlx::query!(
+        "INSERT INTO aggregate_operations (action_type, arguments, from_block, to_block, confirmed)
+        VALUES ($1, '{}', $2, $3, true)
+        RETURNING id",
+        action_type.to_string(),
+        i64::from(from_block),
+        i64::from(to_block),
+    )
+    .fetch_one(storage.conn())
+    .await?
+    .id;
+
+    match action_type {
+        AggregatedActionType::CommitBlocks => {
+            sqlx::query!(
+                "INSERT INTO commit_aggregated_blocks_binding (op_id, block_number)
+                SELECT $1, u.block_number FROM generate_series($2::bigint, $3::bigint) AS u(block_number)",
+                op_id,
+                i64::from(from_block),
+                i64::from(to_block),
+            )
+            .execute(storage.conn())
+            .await?;
+        }
+        AggregatedActionType::ExecuteBlocks => {
+            sqlx::query!(
+                "INSERT INTO execute_aggregated_blocks_binding (op_id, block_number)
+                SELECT $1, u.block_number FROM generate_series($2::bigint, $3::bigint) AS u(block_number)",
+                op_id,
+                i64::from(from_block),
+                i64::from(to_block),
+            )
+            .execute(storage.conn())
+            .await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
@@ -28,6 +28,8 @@ use zksync_types::{
 };
 // Local imports
 
+pub mod load_test;
+
 static SAMPLE_PROOF: Lazy<PrecomputedSampleProofs> =
     Lazy::new(|| load_precomputed_proofs().unwrap());
 
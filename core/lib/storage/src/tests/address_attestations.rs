@@ -0,0 +1,98 @@
+// External imports
+use chrono::{Duration, Utc};
+// Workspace imports
+use zksync_types::{event::outbox::OutboxEventType, Address};
+// Local imports
+use crate::test_data::gen_eth_sign_data;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the attestation can be replaced only by a newer one and every stored
+/// attestation is announced to the webhooks.
+#[db_test]
+async fn test_address_attestations(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    storage
+        .outbox_schema()
+        .add_webhook_subscription(
+            "http://kyc.example",
+            "secret",
+            &[OutboxEventType::AddressAttested],
+        )
+        .await?;
+    let address = Address::repeat_byte(0x01);
+    let signature = gen_eth_sign_data("attestation".to_string()).signature;
+    let signed_at = Utc::now();
+
+    assert!(
+        !storage
+            .address_attestations_schema()
+            .is_address_attested(address)
+            .await?
+    );
+    assert!(
+        storage
+            .address_attestations_schema()
+            .store_attestation(address, Some("kyc-1"), &signature, signed_at)
+            .await?
+    );
+    // The older attestation can't replace the stored one.
+    assert!(
+        !storage
+            .address_attestations_schema()
+            .store_attestation(
+                address,
+                Some("kyc-0"),
+                &signature,
+                signed_at - Duration::minutes(1)
+            )
+            .await?
+    );
+    assert!(
+        storage
+            .address_attestations_schema()
+            .is_address_attested(address)
+            .await?
+    );
+    assert!(
+        !storage
+            .address_attestations_schema()
+            .is_address_attested(Address::repeat_byte(0x02))
+            .await?
+    );
+
+    let attestation = storage
+        .address_attestations_schema()
+        .get_attestation(address)
+        .await?
+        .expect("attestation should be stored");
+    assert_eq!(attestation.reference.as_deref(), Some("kyc-1"));
+    assert_eq!(
+        attestation.signed_at.timestamp_millis(),
+        signed_at.timestamp_millis()
+    );
+
+    assert!(
+        storage
+            .address_attestations_schema()
+            .store_attestation(address, None, &signature, signed_at + Duration::minutes(1))
+            .await?
+    );
+    let attestation = storage
+        .address_attestations_schema()
+        .get_attestation(address)
+        .await?
+        .unwrap();
+    assert!(attestation.reference.is_none());
+
+    // Only the stored attestations are delivered.
+    let deliveries = storage
+        .outbox_schema()
+        .load_due_webhook_deliveries(10)
+        .await?;
+    assert_eq!(deliveries.len(), 2);
+    assert!(deliveries
+        .iter()
+        .all(|delivery| delivery.event_type == "address_attested"));
+
+    Ok(())
+}
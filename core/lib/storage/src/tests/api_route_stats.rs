@@ -0,0 +1,100 @@
+// External imports
+use chrono::{Duration, Utc};
+// Workspace imports
+// Local imports
+use crate::api_route_stats::records::{NewRouteStats, StoredRouteQueryTime};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the statistics of the routes are summed across the minutes and the API servers.
+#[db_test]
+async fn test_api_route_stats(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now();
+    let stats = |route: &str, minute, requests, latency_threshold_ms, queries| NewRouteStats {
+        route: route.to_owned(),
+        minute,
+        requests,
+        errors: 1,
+        slow_requests: 2,
+        latency_buckets: vec![requests - 1, 1, 0],
+        storage_ms: 100,
+        queries,
+        latency_threshold_ms,
+        slo_target: 99.0,
+    };
+    storage
+        .api_route_stats_schema()
+        .store_route_stats(&[
+            stats(
+                "/api/v0.2/blocks",
+                now - Duration::hours(2),
+                100,
+                500,
+                serde_json::json!({ "chain.block::load_block_range": 100 }),
+            ),
+            stats(
+                "/api/v0.2/blocks",
+                now - Duration::minutes(1),
+                10,
+                1000,
+                serde_json::json!({ "chain.block::load_block_range": 60, "tokens::load_tokens": 40 }),
+            ),
+            stats(
+                "/api/v0.2/blocks",
+                now,
+                20,
+                1000,
+                serde_json::json!({ "tokens::load_tokens": 30, "chain.block::get_block": 70 }),
+            ),
+            stats("/api/v0.2/tokens", now, 5, 1000, serde_json::json!({})),
+        ])
+        .await?;
+
+    let since = now - Duration::hours(1);
+    let route_stats = storage
+        .api_route_stats_schema()
+        .load_route_stats(since)
+        .await?;
+    assert_eq!(route_stats.len(), 2);
+    assert_eq!(route_stats[0].route, "/api/v0.2/blocks");
+    assert_eq!(route_stats[0].requests, 30);
+    assert_eq!(route_stats[0].errors, 2);
+    assert_eq!(route_stats[0].slow_requests, 4);
+    assert_eq!(route_stats[0].latency_buckets, vec![28, 2, 0]);
+    assert_eq!(route_stats[0].storage_ms, 200);
+    assert_eq!(route_stats[0].latency_threshold_ms, 1000);
+    assert_eq!(route_stats[1].route, "/api/v0.2/tokens");
+    assert_eq!(route_stats[1].requests, 5);
+
+    let query_time = |method: &str, duration_ms| StoredRouteQueryTime {
+        route: "/api/v0.2/blocks".to_owned(),
+        method: method.to_owned(),
+        duration_ms,
+    };
+    assert_eq!(
+        storage
+            .api_route_stats_schema()
+            .load_route_queries(since)
+            .await?,
+        vec![
+            query_time("chain.block::get_block", 70),
+            query_time("tokens::load_tokens", 70),
+            query_time("chain.block::load_block_range", 60),
+        ]
+    );
+
+    assert_eq!(
+        storage
+            .api_route_stats_schema()
+            .remove_route_stats(since)
+            .await?,
+        1
+    );
+    assert!(storage
+        .api_route_stats_schema()
+        .load_route_stats(now - Duration::days(1))
+        .await?
+        .iter()
+        .all(|stats| stats.latency_threshold_ms == 1000));
+    Ok(())
+}
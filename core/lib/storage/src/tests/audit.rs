@@ -0,0 +1,53 @@
+// External imports
+use serde_json::json;
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the audit entries are loaded newest first and can be paginated.
+#[db_test]
+async fn test_audit_log(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut ids = Vec::new();
+    for job_id in 1..=3 {
+        let id = storage
+            .audit_schema()
+            .store_audit_entry(
+                "alice",
+                "set_prover_job_priority",
+                &json!({ "job_id": job_id, "job_priority": 0 }),
+            )
+            .await?;
+        ids.push(id);
+    }
+
+    let entries = storage.audit_schema().load_audit_entries(None, 2).await?;
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].id, ids[2]);
+    assert_eq!(entries[1].id, ids[1]);
+    assert_eq!(entries[0].actor, "alice");
+    assert_eq!(entries[0].action, "set_prover_job_priority");
+    assert_eq!(entries[0].parameters["job_id"], 3);
+
+    let entries = storage
+        .audit_schema()
+        .load_audit_entries(Some(ids[1]), 10)
+        .await?;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].id, ids[0]);
+    Ok(())
+}
+
+/// Checks that the stored audit entries can't be changed or removed.
+#[db_test]
+async fn test_audit_log_is_append_only(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    storage
+        .audit_schema()
+        .store_audit_entry("alice", "reassign_prover_jobs", &json!({}))
+        .await?;
+
+    let result = sqlx::query!("DELETE FROM audit_log")
+        .execute(storage.conn())
+        .await;
+    assert!(result.is_err(), "audit entries must not be removable");
+    Ok(())
+}
@@ -1,14 +1,15 @@
 // External imports
+use chrono::Utc;
 use num::{BigUint, Zero};
 // Workspace imports
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID};
 use zksync_types::{
     aggregated_operations::AggregatedActionType, helpers::apply_updates, AccountId, AccountMap,
-    AccountUpdate, Address, BlockNumber, Nonce, Token, TokenId, TokenKind,
+    AccountUpdate, Address, BlockNumber, Nonce, PubKeyHash, Token, TokenId, TokenKind,
 };
 // Local imports
 use super::block::apply_random_updates;
-use crate::chain::operations::OperationsSchema;
+use crate::chain::operations::{records::NewExecutedTransaction, OperationsSchema};
 use crate::test_data::{gen_sample_block, gen_unique_aggregated_operation, generate_nft};
 use crate::tests::{create_rng, db_test, ACCOUNT_MUTEX};
 use crate::{
@@ -320,6 +321,25 @@ async fn test_get_balance(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     assert_eq!(balance04, BigUint::from(300u32));
     assert_eq!(balance14, BigUint::from(10000u32));
 
+    let balances2 = storage
+        .chain()
+        .account_schema()
+        .get_account_balances_for_block(AccountId(1), BlockNumber(2))
+        .await?;
+    let balances4 = storage
+        .chain()
+        .account_schema()
+        .get_account_balances_for_block(AccountId(1), BlockNumber(4))
+        .await?;
+    assert_eq!(balances2, vec![(TokenId(0), BigUint::from(100u32))]);
+    assert_eq!(
+        balances4,
+        vec![
+            (TokenId(0), BigUint::from(300u32)),
+            (TokenId(1), BigUint::from(10000u32))
+        ]
+    );
+
     Ok(())
 }
 
@@ -534,3 +554,191 @@ async fn test_get_nft_owner(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     Ok(())
 }
+
+/// Checks that the accounts are resolved by both ids and addresses at once,
+/// taking the deleted accounts into account.
+#[db_test]
+async fn resolve_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (address1, address2) = (Address::repeat_byte(1), Address::repeat_byte(2));
+    let create = |address| AccountUpdate::Create {
+        address,
+        nonce: Nonce(0),
+    };
+    let updates1 = vec![
+        (AccountId(1), create(address1)),
+        (AccountId(2), create(address2)),
+    ];
+    let updates2 = vec![
+        (
+            AccountId(2),
+            AccountUpdate::Delete {
+                address: address2,
+                nonce: Nonce(0),
+            },
+        ),
+        (AccountId(3), create(address2)),
+    ];
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates1, 0)
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(2), &updates2, 0)
+        .await?;
+
+    let mut accounts = AccountSchema(&mut storage)
+        .resolve_accounts(
+            &[AccountId(1), AccountId(2), AccountId(9)],
+            &[address2, Address::repeat_byte(9)],
+        )
+        .await?;
+    accounts.sort();
+    assert_eq!(
+        accounts,
+        vec![(AccountId(1), address1), (AccountId(3), address2)]
+    );
+
+    assert!(AccountSchema(&mut storage)
+        .resolve_accounts(&[], &[])
+        .await?
+        .is_empty());
+    Ok(())
+}
+
+/// Checks that the accounts are found by both the current and the previous public key hashes.
+#[db_test]
+async fn accounts_by_pubkey_hash(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (address1, address2) = (Address::repeat_byte(1), Address::repeat_byte(2));
+    let (hash1, hash2) = (
+        PubKeyHash::from_hex("sync:0101010101010101010101010101010101010101").unwrap(),
+        PubKeyHash::from_hex("sync:0202020202020202020202020202020202020202").unwrap(),
+    );
+    let create = |address| AccountUpdate::Create {
+        address,
+        nonce: Nonce(0),
+    };
+    let change_pubkey = |old_pub_key_hash, new_pub_key_hash| AccountUpdate::ChangePubKeyHash {
+        old_pub_key_hash,
+        new_pub_key_hash,
+        old_nonce: Nonce(0),
+        new_nonce: Nonce(1),
+    };
+    let updates1 = vec![
+        (AccountId(1), create(address1)),
+        (AccountId(2), create(address2)),
+        (AccountId(1), change_pubkey(PubKeyHash::default(), hash1)),
+    ];
+    // The first account rotates the key to the second hash, which the second account uses too.
+    let updates2 = vec![
+        (AccountId(1), change_pubkey(hash1, hash2)),
+        (AccountId(2), change_pubkey(PubKeyHash::default(), hash2)),
+    ];
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates1, 0)
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(2), &updates2, 0)
+        .await?;
+
+    let accounts = AccountSchema(&mut storage)
+        .accounts_by_pubkey_hash(&hash1)
+        .await?;
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].account_id, 1);
+    assert_eq!(accounts[0].address, address1.as_bytes().to_vec());
+    assert!(!accounts[0].is_current);
+    assert_eq!((accounts[0].first_block, accounts[0].last_block), (1, 1));
+
+    let accounts = AccountSchema(&mut storage)
+        .accounts_by_pubkey_hash(&hash2)
+        .await?;
+    assert_eq!(accounts.len(), 2);
+    assert!(accounts.iter().all(|account| account.is_current));
+    assert_eq!(accounts[1].address, address2.as_bytes().to_vec());
+
+    assert!(AccountSchema(&mut storage)
+        .accounts_by_pubkey_hash(
+            &PubKeyHash::from_hex("sync:0303030303030303030303030303030303030303").unwrap()
+        )
+        .await?
+        .is_empty());
+    Ok(())
+}
+
+/// Checks that the nonce of the account is reconstructed from its successful transactions
+/// and swap orders up to the given block.
+#[db_test]
+async fn account_nonce_at(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (address, other) = (Address::repeat_byte(1), Address::repeat_byte(2));
+    let updates = vec![(
+        AccountId(1),
+        AccountUpdate::Create {
+            address,
+            nonce: Nonce(0),
+        },
+    )];
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates, 0)
+        .await?;
+
+    let executed_tx =
+        |block_number, hash: u8, primary: Address, nonce, success, tx| NewExecutedTransaction {
+            block_number,
+            block_index: None,
+            tx,
+            operation: Default::default(),
+            tx_hash: vec![hash; 32],
+            from_account: primary.as_bytes().to_vec(),
+            to_account: None,
+            success,
+            fail_reason: None,
+            primary_account_address: primary.as_bytes().to_vec(),
+            nonce,
+            created_at: Utc::now(),
+            eth_sign_data: None,
+            batch_id: None,
+            affected_accounts: vec![address.as_bytes().to_vec(), other.as_bytes().to_vec()],
+            used_tokens: vec![0],
+        };
+    let transfer = serde_json::json!({ "type": "Transfer" });
+    let swap = |amount: &str| {
+        serde_json::json!({
+            "type": "Swap",
+            "orders": [
+                { "accountId": 2, "nonce": 7, "amount": "1" },
+                { "accountId": 1, "nonce": 5, "amount": amount },
+            ],
+        })
+    };
+    storage
+        .chain()
+        .operations_schema()
+        .store_executed_txs(vec![
+            executed_tx(1, 1, address, 0, true, transfer.clone()),
+            executed_tx(2, 2, address, 1, true, transfer.clone()),
+            // Failed transactions don't use the nonce.
+            executed_tx(3, 3, address, 2, false, transfer.clone()),
+            executed_tx(4, 4, other, 3, true, swap("0")),
+            executed_tx(5, 5, other, 4, true, swap("1")),
+        ])
+        .await?;
+
+    let expected = [(0, 0), (1, 1), (2, 2), (3, 2), (4, 2), (5, 6)];
+    for &(block_number, nonce) in expected.iter() {
+        assert_eq!(
+            AccountSchema(&mut storage)
+                .get_account_nonce_at(address, BlockNumber(block_number))
+                .await?,
+            Nonce(nonce),
+            "Unexpected nonce at block {}",
+            block_number
+        );
+    }
+    // Unknown accounts never used a nonce.
+    assert_eq!(
+        AccountSchema(&mut storage)
+            .get_account_nonce_at(Address::repeat_byte(9), BlockNumber(5))
+            .await?,
+        Nonce(0)
+    );
+    Ok(())
+}
@@ -13,7 +13,8 @@ use zksync_types::{
 };
 // Local imports
 use super::operations_ext::{
-    commit_block, commit_schema_data, setup::TransactionsHistoryTestSetup, verify_block,
+    commit_block, commit_schema_data, confirm_eth_op, setup::TransactionsHistoryTestSetup,
+    verify_block,
 };
 use crate::{
     chain::{
@@ -1053,6 +1054,76 @@ async fn test_is_block_finalized(mut storage: StorageProcessor<'_>) -> QueryResu
     Ok(())
 }
 
+/// Checks that `load_block_finality` collects the L1 transactions and the proof of the block
+/// as they get confirmed.
+#[db_test]
+async fn test_load_block_finality(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let block_number = BlockNumber(1);
+    assert!(BlockSchema(&mut storage)
+        .load_block_finality(block_number)
+        .await?
+        .is_none());
+
+    BlockSchema(&mut storage)
+        .save_full_block(gen_sample_block(block_number, BLOCK_SIZE_CHUNKS, vec![]))
+        .await?;
+    commit_block(&mut storage, block_number).await?;
+
+    let finality = BlockSchema(&mut storage)
+        .load_block_finality(block_number)
+        .await?
+        .unwrap();
+    assert_eq!(finality.block_number, 1);
+    assert_eq!(finality.commitment, vec![0; 32]);
+    assert!(finality.commit_tx_hash.is_some());
+    assert!(finality.prove_tx_hash.is_none());
+    assert!(finality.execute_tx_hash.is_none());
+    assert!(finality.proof_first_block.is_none());
+
+    sqlx::query!(
+        "INSERT INTO aggregated_proofs (first_block, last_block, proof) VALUES (1, 2, '{}')"
+    )
+    .execute(storage.conn())
+    .await?;
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            block_number,
+            AggregatedActionType::PublishProofBlocksOnchain,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let op = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(
+            AggregatedActionType::PublishProofBlocksOnchain,
+            block_number,
+        )
+        .await?
+        .unwrap();
+    let prove_tx_hash = dummy_ethereum_tx_hash(op.0);
+    confirm_eth_op(
+        &mut storage,
+        op,
+        AggregatedActionType::PublishProofBlocksOnchain,
+    )
+    .await?;
+    verify_block(&mut storage, block_number).await?;
+
+    let finality = BlockSchema(&mut storage)
+        .load_block_finality(block_number)
+        .await?
+        .unwrap();
+    assert_eq!(
+        finality.prove_tx_hash,
+        Some(prove_tx_hash.as_bytes().to_vec())
+    );
+    assert!(finality.execute_tx_hash.is_some());
+    assert_ne!(finality.execute_tx_hash, finality.commit_tx_hash);
+    assert_eq!(finality.proof_first_block, Some(1));
+    assert_eq!(finality.proof_last_block, Some(2));
+
+    Ok(())
+}
+
 /// Check that blocks are removed correctly.
 #[db_test]
 async fn test_remove_blocks(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
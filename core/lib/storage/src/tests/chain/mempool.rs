@@ -1,6 +1,10 @@
 // External imports
-use chrono::Utc;
+use chrono::{TimeZone, Utc};
 // Workspace imports
+use zksync_api_types::v02::{
+    pagination::PaginationDirection,
+    transaction::{L2Receipt, Receipt, TxInBlockStatus},
+};
 use zksync_crypto::rand::{Rng, SeedableRng, XorShiftRng};
 use zksync_types::{
     block::{Block, ExecutedOperations},
@@ -271,6 +275,127 @@ async fn remove_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that the queued and successfully executed txs are reported as known,
+/// while the rejected ones may be submitted again.
+#[db_test]
+async fn known_tx(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(3);
+    MempoolSchema(&mut storage).insert_tx(&txs[0]).await?;
+    let executed_txs = [(&txs[1], true), (&txs[2], false)]
+        .iter()
+        .map(|(tx, success)| {
+            ExecutedOperations::Tx(Box::new(ExecutedTx {
+                signed_tx: (*tx).clone(),
+                success: *success,
+                op: None,
+                fail_reason: None,
+                block_index: None,
+                created_at: tx.created_at,
+                batch_id: None,
+            }))
+        })
+        .collect();
+    storage
+        .chain()
+        .block_schema()
+        .save_block_transactions(BlockNumber(1), executed_txs)
+        .await?;
+
+    let (status, created_at) = OperationsExtSchema(&mut storage)
+        .known_tx(txs[0].hash())
+        .await?
+        .expect("Queued tx should be known");
+    assert_eq!(status, TxInBlockStatus::Queued);
+    assert_eq!(created_at.timestamp(), txs[0].created_at.timestamp());
+
+    let (status, _) = OperationsExtSchema(&mut storage)
+        .known_tx(txs[1].hash())
+        .await?
+        .expect("Executed tx should be known");
+    assert_eq!(status, TxInBlockStatus::Committed);
+
+    assert!(OperationsExtSchema(&mut storage)
+        .known_tx(txs[2].hash())
+        .await?
+        .is_none());
+    assert!(OperationsExtSchema(&mut storage)
+        .known_tx(TxHash::default())
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+/// Checks that the expired txs are removed from the mempool and served by the receipts.
+#[db_test]
+async fn expire_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = zksync_txs();
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+
+    let expired_tx_hash = txs[0].hash();
+    let initiator = txs[0].tx.account();
+    let valid_until = Utc.timestamp(1_000_000, 0);
+    MempoolSchema(&mut storage)
+        .expire_txs(&[(expired_tx_hash, initiator, valid_until)])
+        .await?;
+
+    assert!(
+        !MempoolSchema(&mut storage)
+            .contains_tx(expired_tx_hash)
+            .await?
+    );
+    assert_eq!(
+        MempoolSchema(&mut storage).load_txs(&[]).await?.len(),
+        txs.len() - 1
+    );
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .get_expired_tx(expired_tx_hash.as_ref())
+            .await?,
+        Some(valid_until)
+    );
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .get_expired_tx(txs[1].hash().as_ref())
+            .await?,
+        None
+    );
+
+    // The expired tx is searchable by its initiator.
+    let dropped = storage
+        .dropped_txs_schema()
+        .load_dropped_txs(Some(initiator), None, None, 10)
+        .await?;
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].tx_hash, expired_tx_hash.as_ref().to_vec());
+    assert_eq!(dropped[0].kind, "expired");
+    assert_eq!(
+        dropped[0].tx,
+        crate::canonical_json::canonical_tx(&txs[0].tx)
+    );
+
+    let receipt = OperationsExtSchema(&mut storage)
+        .tx_receipt_api_v02(expired_tx_hash.as_ref())
+        .await?
+        .expect("Receipt of the expired tx should exist");
+    assert_eq!(
+        receipt,
+        Receipt::L2(L2Receipt {
+            tx_hash: expired_tx_hash,
+            rollup_block: None,
+            status: TxInBlockStatus::Expired,
+            fail_reason: None,
+            expected_finality_secs: None,
+            valid_until: Some(valid_until),
+            withdrawal_hook: None,
+        })
+    );
+
+    Ok(())
+}
+
 /// Checks that already committed txs are removed by `collect_garbage` method.
 #[db_test]
 async fn collect_garbage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -415,6 +540,7 @@ async fn test_return_executed_txs_to_mempool(mut storage: StorageProcessor<'_>)
             eth_hash: H256::zero(),
             eth_block: 0,
             eth_block_index: None,
+            l1_sender: None,
         },
         op: ZkSyncOp::FullExit(Box::new(FullExitOp {
             priority_op: priority_op.clone(),
@@ -525,3 +651,149 @@ async fn test_return_executed_txs_to_mempool(mut storage: StorageProcessor<'_>)
     assert_eq!(block_tx.variance_name(), "FullExit");
     Ok(())
 }
+
+/// Checks that the stuck priority operations are loaded correctly.
+#[db_test]
+async fn test_get_stuck_priority_ops(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let full_exit = FullExit {
+        account_id: AccountId(0),
+        eth_address: Address::zero(),
+        token: TokenId(0),
+        is_legacy: false,
+    };
+    let priority_op = |serial_id| PriorityOp {
+        serial_id,
+        data: ZkSyncPriorityOp::FullExit(full_exit.clone()),
+        deadline_block: 0,
+        eth_hash: H256::from_low_u64_be(serial_id),
+        eth_block: 0,
+        eth_block_index: None,
+        l1_sender: None,
+    };
+    let ops: Vec<_> = (1..=3).map(priority_op).collect();
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&ops, true)
+        .await?;
+    // Operation which is not confirmed yet is not considered stuck.
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&[priority_op(4)], false)
+        .await?;
+
+    // The second operation is executed, but not yet removed from the mempool.
+    let exec_priority_op = ExecutedPriorityOp {
+        priority_op: ops[1].clone(),
+        op: ZkSyncOp::FullExit(Box::new(FullExitOp {
+            priority_op: full_exit.clone(),
+            withdraw_amount: None,
+            creator_account_id: None,
+            creator_address: None,
+            serial_id: None,
+            content_hash: None,
+        })),
+        block_index: 0,
+        created_at: Utc::now(),
+    };
+    storage
+        .chain()
+        .operations_schema()
+        .store_executed_priority_op(NewExecutedPriorityOperation::prepare_stored_priority_op(
+            exec_priority_op,
+            BlockNumber(1),
+        ))
+        .await?;
+
+    let stuck_ops = MempoolSchema(&mut storage)
+        .get_stuck_priority_ops(Utc::now() + chrono::Duration::minutes(1))
+        .await?;
+    let serial_ids: Vec<_> = stuck_ops.iter().map(|(op, _)| op.serial_id).collect();
+    assert_eq!(serial_ids, vec![1, 3]);
+
+    // Operations received after the given time are not returned.
+    let stuck_ops = MempoolSchema(&mut storage)
+        .get_stuck_priority_ops(Utc::now() - chrono::Duration::minutes(1))
+        .await?;
+    assert!(stuck_ops.is_empty());
+
+    Ok(())
+}
+
+/// Checks that the pending priority operations are paginated correctly and the executed ones are skipped.
+#[db_test]
+async fn test_get_pending_priority_ops_page(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let full_exit = FullExit {
+        account_id: AccountId(0),
+        eth_address: Address::zero(),
+        token: TokenId(0),
+        is_legacy: false,
+    };
+    let priority_op = |serial_id| PriorityOp {
+        serial_id,
+        data: ZkSyncPriorityOp::FullExit(full_exit.clone()),
+        deadline_block: 0,
+        eth_hash: H256::from_low_u64_be(serial_id),
+        eth_block: serial_id,
+        eth_block_index: None,
+        l1_sender: None,
+    };
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .get_max_pending_priority_op_serial_id()
+            .await?,
+        None
+    );
+
+    let ops: Vec<_> = (1..=3).map(priority_op).collect();
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&ops, true)
+        .await?;
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&[priority_op(4)], false)
+        .await?;
+
+    // The first operation is executed, but not yet removed from the mempool.
+    let exec_priority_op = ExecutedPriorityOp {
+        priority_op: ops[0].clone(),
+        op: ZkSyncOp::FullExit(Box::new(FullExitOp {
+            priority_op: full_exit.clone(),
+            withdraw_amount: None,
+            creator_account_id: None,
+            creator_address: None,
+            serial_id: None,
+            content_hash: None,
+        })),
+        block_index: 0,
+        created_at: Utc::now(),
+    };
+    storage
+        .chain()
+        .operations_schema()
+        .store_executed_priority_op(NewExecutedPriorityOperation::prepare_stored_priority_op(
+            exec_priority_op,
+            BlockNumber(1),
+        ))
+        .await?;
+
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .get_max_pending_priority_op_serial_id()
+            .await?,
+        Some(4)
+    );
+
+    let page = MempoolSchema(&mut storage)
+        .get_pending_priority_ops_page(4, 2, PaginationDirection::Older)
+        .await?;
+    let page: Vec<_> = page
+        .iter()
+        .map(|(op, confirmed)| (op.serial_id, *confirmed))
+        .collect();
+    assert_eq!(page, vec![(4, false), (3, true)]);
+
+    let page = MempoolSchema(&mut storage)
+        .get_pending_priority_ops_page(0, 10, PaginationDirection::Newer)
+        .await?;
+    let serial_ids: Vec<_> = page.iter().map(|(op, _)| op.serial_id).collect();
+    assert_eq!(serial_ids, vec![2, 3, 4]);
+
+    Ok(())
+}
@@ -7,3 +7,6 @@ mod state;
 mod tree_cache;
 
 pub use block::apply_random_updates;
+pub use operations_ext::{
+    commit_block, commit_schema_data, setup::TransactionsHistoryTestSetup, verify_block,
+};
@@ -2,8 +2,8 @@
 use chrono::{Duration, Utc};
 // Workspace imports
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, Address, BlockNumber, Deposit, SequentialTxId,
-    ZkSyncPriorityOp, H256,
+    aggregated_operations::AggregatedActionType, Address, BlockNumber, Deposit, PriorityOp,
+    SequentialTxId, TokenId, ZkSyncPriorityOp, H256,
 };
 // Local imports
 use crate::chain::mempool::MempoolSchema;
@@ -112,6 +112,7 @@ async fn executed_priority_operations(mut storage: StorageProcessor<'_>) -> Quer
         created_at: chrono::Utc::now(),
         tx_hash: Default::default(),
         eth_block_index: Some(1),
+        l1_sender: None,
         affected_accounts: Default::default(),
         token: Default::default(),
     };
@@ -139,6 +140,129 @@ async fn executed_priority_operations(mut storage: StorageProcessor<'_>) -> Quer
     Ok(())
 }
 
+/// Checks that the first deposit to the account is loaded.
+#[db_test]
+async fn first_deposit(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::from_low_u64_be(1);
+    assert!(OperationsSchema(&mut storage)
+        .get_first_deposit(address)
+        .await?
+        .is_none());
+
+    for (serial_id, token, amount) in [(1, 1, "1000"), (0, 2, "500")] {
+        let deposit = NewExecutedPriorityOperation {
+            block_number: 1,
+            block_index: serial_id as i32,
+            operation: serde_json::json!({
+                "type": "Deposit",
+                "account_id": 1,
+                "priority_op": {
+                    "from": address,
+                    "token": token,
+                    "amount": amount,
+                    "to": address,
+                },
+            }),
+            from_account: address.as_bytes().to_vec(),
+            to_account: address.as_bytes().to_vec(),
+            priority_op_serialid: serial_id,
+            deadline_block: 100,
+            eth_hash: vec![serial_id as u8],
+            eth_block: 10,
+            created_at: chrono::Utc::now(),
+            tx_hash: vec![serial_id as u8],
+            eth_block_index: Some(1),
+            l1_sender: None,
+            affected_accounts: vec![address.as_bytes().to_vec()],
+            token: token as i32,
+        };
+        OperationsSchema(&mut storage)
+            .store_executed_priority_op(deposit)
+            .await?;
+    }
+
+    let (token, amount) = OperationsSchema(&mut storage)
+        .get_first_deposit(address)
+        .await?
+        .expect("Deposit was not found");
+    assert_eq!(token, TokenId(2));
+    assert_eq!(amount, 500.into());
+
+    Ok(())
+}
+
+/// Checks that the L1 sender of the priority operation is kept once it's executed.
+#[db_test]
+async fn priority_op_l1_sender(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let l1_sender = Address::repeat_byte(0x11);
+    let recipient = Address::repeat_byte(0x22);
+    let priority_op = PriorityOp {
+        serial_id: 0,
+        data: ZkSyncPriorityOp::Deposit(Deposit {
+            from: Address::repeat_byte(0x33),
+            token: TokenId(0),
+            amount: 100u32.into(),
+            to: recipient,
+        }),
+        deadline_block: 100,
+        eth_hash: H256::repeat_byte(0xDE),
+        eth_block: 10,
+        eth_block_index: Some(1),
+        l1_sender: Some(l1_sender),
+    };
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&[priority_op.clone()], false)
+        .await?;
+    // Confirmation of the operation doesn't erase its sender.
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(
+            &[PriorityOp {
+                l1_sender: None,
+                ..priority_op.clone()
+            }],
+            true,
+        )
+        .await?;
+    let pending_op = MempoolSchema(&mut storage)
+        .get_pending_operation_by_hash(priority_op.eth_hash)
+        .await?
+        .expect("Pending operation was not found");
+    assert_eq!(pending_op.l1_sender, Some(l1_sender));
+
+    let executed_op = NewExecutedPriorityOperation {
+        block_number: 1,
+        block_index: 1,
+        operation: Default::default(),
+        from_account: Address::repeat_byte(0x33).as_bytes().to_vec(),
+        to_account: recipient.as_bytes().to_vec(),
+        priority_op_serialid: 0,
+        deadline_block: 100,
+        eth_hash: priority_op.eth_hash.as_bytes().to_vec(),
+        eth_block: 10,
+        created_at: chrono::Utc::now(),
+        tx_hash: priority_op.tx_hash().as_ref().to_vec(),
+        eth_block_index: Some(1),
+        l1_sender: Some(l1_sender.as_bytes().to_vec()),
+        affected_accounts: Default::default(),
+        token: Default::default(),
+    };
+    OperationsSchema(&mut storage)
+        .store_executed_priority_op(executed_op)
+        .await?;
+
+    let ops = OperationsSchema(&mut storage)
+        .get_executed_priority_operations_by_l1_sender(l1_sender, 10)
+        .await?;
+    assert_eq!(ops.len(), 1);
+    assert_eq!(ops[0].to_account, recipient.as_bytes().to_vec());
+    assert!(OperationsSchema(&mut storage)
+        .get_executed_priority_operations_by_l1_sender(recipient, 10)
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
 /// Checks that attempt to save the duplicate txs is ignored by the DB.
 #[db_test]
 async fn duplicated_operations(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -176,6 +300,7 @@ async fn duplicated_operations(mut storage: StorageProcessor<'_>) -> QueryResult
         created_at: chrono::Utc::now(),
         tx_hash: Default::default(),
         eth_block_index: Some(1),
+        l1_sender: None,
         affected_accounts: Default::default(),
         token: Default::default(),
     };
@@ -293,6 +418,85 @@ async fn transaction_resent(mut storage: StorageProcessor<'_>) -> QueryResult<()
     Ok(())
 }
 
+/// Checks that the bulk insert of the executed transactions has the same result
+/// as storing them one by one.
+#[db_test]
+async fn store_executed_txs_batch(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const BLOCK_NUMBER: i64 = 1;
+    let executed_tx = |tx_hash: u8, success: bool| NewExecutedTransaction {
+        block_number: BLOCK_NUMBER,
+        tx_hash: vec![tx_hash; 32],
+        tx: serde_json::json!({ "type": "Transfer" }),
+        operation: serde_json::json!({ "type": "Transfer" }),
+        from_account: Address::zero().as_bytes().to_vec(),
+        to_account: if success {
+            Some(Address::repeat_byte(tx_hash).as_bytes().to_vec())
+        } else {
+            None
+        },
+        success,
+        fail_reason: if success {
+            None
+        } else {
+            Some("Not enough balance".to_string())
+        },
+        block_index: Some(tx_hash as i32),
+        primary_account_address: Address::zero().as_bytes().to_vec(),
+        nonce: tx_hash as i64,
+        created_at: Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+        affected_accounts: vec![Address::zero().as_bytes().to_vec()],
+        used_tokens: vec![0, 1],
+    };
+
+    // Successful transaction stored beforehand must not be replaced by the failed one.
+    OperationsSchema(&mut storage)
+        .store_executed_tx(executed_tx(3, true))
+        .await?;
+
+    OperationsSchema(&mut storage)
+        .store_executed_txs(vec![
+            executed_tx(1, true),
+            // Failed and then resent transaction is stored only once.
+            executed_tx(2, false),
+            executed_tx(2, true),
+            executed_tx(3, false),
+            executed_tx(4, false),
+        ])
+        .await?;
+
+    let block_txs = BlockSchema(&mut storage)
+        .get_block_transactions(BlockNumber(BLOCK_NUMBER as u32))
+        .await?;
+    assert_eq!(block_txs.len(), 4);
+
+    let mut sequence_numbers = Vec::new();
+    for (tx_hash, success) in [(1, true), (2, true), (3, true), (4, false)].iter() {
+        let loaded_tx = OperationsSchema(&mut storage)
+            .get_executed_operation(&[*tx_hash; 32])
+            .await?
+            .unwrap();
+        assert_eq!(loaded_tx.success, *success);
+        assert_eq!(loaded_tx.block_index, Some(*tx_hash as i32));
+        sequence_numbers.push(loaded_tx.sequence_number.unwrap());
+    }
+    // Transactions keep the order of the first insertion.
+    assert!(sequence_numbers[0] < sequence_numbers[1]);
+    assert!(sequence_numbers[1] < sequence_numbers[3]);
+    assert!(sequence_numbers[2] < sequence_numbers[0]);
+
+    // Every stored transaction is added to the account history.
+    let count_tx_filters = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_count(Default::default(), None, None)
+        .await?;
+    assert_eq!(count_tx_filters, 4);
+
+    Ok(())
+}
+
 /// Checks that rejected transactions are removed correctly depending on the given age limit.
 #[db_test]
 async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -443,6 +647,7 @@ async fn priority_ops_hashes(mut storage: StorageProcessor<'_>) -> QueryResult<(
         created_at: chrono::Utc::now(),
         tx_hash: vec![0xBB, 0xBB, 0xBB, 0xBB],
         eth_block_index: Some(1),
+        l1_sender: None,
         affected_accounts: Default::default(),
         token: Default::default(),
     };
@@ -499,6 +704,7 @@ async fn test_remove_executed_priority_operations(
             created_at: chrono::Utc::now(),
             eth_block_index: Some(1),
             tx_hash: H256::zero().as_bytes().to_vec(),
+            l1_sender: None,
             affected_accounts: Default::default(),
             token: Default::default(),
         };
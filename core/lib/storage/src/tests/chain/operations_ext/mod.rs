@@ -4,14 +4,14 @@ use std::collections::HashMap;
 // Workspace imports
 use zksync_api_types::v02::{
     pagination::{AccountTxsRequest, ApiEither, PaginationDirection, PaginationQuery},
-    transaction::{Receipt, TxInBlockStatus},
+    transaction::{Receipt, TxHashSerializeWrapper, TxInBlockStatus},
 };
 use zksync_crypto::{franklin_crypto::bellman::pairing::ff::Field, Fr};
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
     tx::TxHash,
-    AccountId, AccountUpdate, BlockNumber, ExecutedOperations, Nonce, ZkSyncOp, H256,
+    AccountId, AccountUpdate, Address, BlockNumber, ExecutedOperations, Nonce, ZkSyncOp, H256,
 };
 // Local imports
 use self::setup::TransactionsHistoryTestSetup;
@@ -24,6 +24,8 @@ use crate::{
     QueryResult, StorageProcessor,
 };
 
+mod query_plans;
+mod round_trip;
 pub mod setup;
 
 /// Commits the data from the test setup to the database.
@@ -99,7 +101,7 @@ pub async fn commit_schema_data(
     Ok(())
 }
 
-async fn confirm_eth_op(
+pub async fn confirm_eth_op(
     storage: &mut StorageProcessor<'_>,
     op: (i64, AggregatedOperation),
     op_type: AggregatedActionType,
@@ -815,6 +817,41 @@ async fn account_last_tx_hash(mut storage: StorageProcessor<'_>) -> QueryResult<
     Ok(())
 }
 
+/// Test `address_has_activity` method
+#[db_test]
+async fn address_has_activity(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    let addresses = [
+        setup.from_zksync_account.address,
+        Address::repeat_byte(0xff),
+        setup.to_zksync_account.address,
+    ];
+    let activity = storage
+        .chain()
+        .operations_ext_schema()
+        .address_has_activity(&addresses)
+        .await?;
+    assert_eq!(activity, vec![false, false, false]);
+
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let activity = storage
+        .chain()
+        .operations_ext_schema()
+        .address_has_activity(&addresses)
+        .await?;
+    assert_eq!(activity, vec![true, false, true]);
+    assert!(storage
+        .chain()
+        .operations_ext_schema()
+        .address_has_activity(&[])
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
 /// Test `get_block_last_tx_hash` method
 #[db_test]
 async fn block_last_tx_hash(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -1055,6 +1092,64 @@ async fn tx_data_for_web3(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     Ok(())
 }
 
+/// Checks that the L2 activity is found by the hash of the Ethereum transaction
+/// requesting the priority operation and by the one executing the block.
+#[db_test]
+async fn find_by_l1_hash(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+    verify_block(&mut storage, BlockNumber(1)).await?;
+
+    let deposit_eth_hash = match &setup.blocks[0].block_transactions[0] {
+        ExecutedOperations::PriorityOp(op) => op.priority_op.eth_hash,
+        ExecutedOperations::Tx(_) => panic!("Should be L1 op"),
+    };
+    let activity = storage
+        .chain()
+        .operations_ext_schema()
+        .find_by_l1_hash(deposit_eth_hash)
+        .await?
+        .expect("Deposit should be found");
+    assert_eq!(activity.eth_hash, deposit_eth_hash);
+    assert_eq!(
+        activity.priority_ops,
+        vec![TxHashSerializeWrapper(setup.get_tx_hash(0, 0))]
+    );
+    assert!(activity.completed_withdrawals.is_empty());
+
+    // Both withdrawals of the block are completed by the transaction executing it.
+    let (execute_op_id, _) = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(AggregatedActionType::ExecuteBlocks, BlockNumber(1))
+        .await?
+        .unwrap();
+    let execute_eth_hash = dummy_ethereum_tx_hash(execute_op_id);
+    let activity = storage
+        .chain()
+        .operations_ext_schema()
+        .find_by_l1_hash(execute_eth_hash)
+        .await?
+        .expect("Withdrawals should be found");
+    assert!(activity.priority_ops.is_empty());
+    assert_eq!(
+        activity.completed_withdrawals,
+        vec![
+            TxHashSerializeWrapper(setup.get_tx_hash(0, 5)),
+            TxHashSerializeWrapper(setup.get_tx_hash(0, 7)),
+        ]
+    );
+
+    let activity = storage
+        .chain()
+        .operations_ext_schema()
+        .find_by_l1_hash(H256::repeat_byte(0xff))
+        .await?;
+    assert!(activity.is_none());
+
+    Ok(())
+}
+
 /// Test web3 receipts methods
 #[db_test]
 async fn web3_receipts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -0,0 +1,256 @@
+//! Query plan regression tests of the account history queries.
+//!
+//! Every query is explained with the sequential scans disabled, so the planner only resorts to
+//! them if the query can't be served by any index. Such a sequential scan of a large table means
+//! that the query slows down as the chain grows, so the test fails.
+//!
+//! Queries built at runtime are taken from the schema, the static ones repeat the SQL of the
+//! corresponding schema methods and must be kept in sync with them.
+
+// Built-in imports
+// External imports
+use serde_json::Value;
+// Workspace imports
+use zksync_api_types::v02::pagination::PaginationDirection;
+use zksync_types::TokenId;
+// Local imports
+use super::{commit_schema_data, setup::TransactionsHistoryTestSetup};
+use crate::{
+    chain::operations_ext::{
+        account_sequence_numbers_query, two_accounts_sequence_numbers_query,
+        two_accounts_transactions_query,
+    },
+    tests::db_test,
+    QueryResult, StorageProcessor,
+};
+
+/// Tables growing with the chain, which must never be scanned sequentially.
+const LARGE_TABLES: &[&str] = &[
+    "executed_transactions",
+    "executed_priority_operations",
+    "tx_filters",
+];
+
+/// `get_account_transactions_history`, without the tokens and the verification status.
+const ACCOUNT_HISTORY_QUERY: &str = r#"
+    WITH tx_hashes AS (
+        SELECT DISTINCT sequence_number FROM tx_filters
+        WHERE address = $1
+        ORDER BY sequence_number desc
+        OFFSET $2
+        LIMIT $3
+    )
+    SELECT executed_transactions.tx_hash, executed_transactions.sequence_number
+    FROM executed_transactions
+    INNER JOIN tx_hashes
+        ON tx_hashes.sequence_number = executed_transactions.sequence_number
+    UNION ALL
+    SELECT executed_priority_operations.tx_hash, executed_priority_operations.sequence_number
+    FROM executed_priority_operations
+    INNER JOIN tx_hashes
+        ON tx_hashes.sequence_number = executed_priority_operations.sequence_number
+"#;
+
+/// `get_account_last_tx_hash`.
+const ACCOUNT_LAST_TX_HASH_QUERY: &str = r#"
+    SELECT tx_hash FROM tx_filters
+    WHERE address = $1
+    ORDER BY sequence_number DESC
+    LIMIT 1
+"#;
+
+/// `get_executed_txs_for_account`.
+const EXECUTED_TXS_BY_SEQUENCE_NUMBERS_QUERY: &str = r#"
+    SELECT sequence_number, tx_hash, tx FROM executed_transactions
+    WHERE sequence_number IN (SELECT u.sequence_number
+        FROM UNNEST ($1::bigint[])
+        AS u(sequence_number)
+    )
+"#;
+
+/// `get_priority_operations_for_account`.
+const PRIORITY_OPS_BY_SEQUENCE_NUMBERS_QUERY: &str = r#"
+    SELECT sequence_number, tx_hash, operation FROM executed_priority_operations
+    WHERE sequence_number IN (SELECT u.sequence_number
+        FROM UNNEST ($1::bigint[])
+        AS u(sequence_number)
+    )
+"#;
+
+/// Lookup of the transaction by its position in the block done by `get_closest_sequence_number`.
+const SEQUENCE_NUMBER_BY_BLOCK_INDEX_QUERY: &str = r#"
+    SELECT GREATEST(
+        (
+            SELECT MAX(sequence_number) FROM executed_transactions
+            WHERE block_number = $1 AND block_index = $2
+        ),
+        (
+            SELECT MAX(sequence_number) FROM executed_priority_operations
+            WHERE block_number = $1 AND block_index = $2
+        )
+    )
+"#;
+
+/// `get_account_transactions_count` for two accounts.
+const TWO_ACCOUNTS_TXS_COUNT_QUERY: &str = r#"
+    WITH tx_hashes AS (
+        SELECT DISTINCT tx_hash FROM tx_filters
+        WHERE address = $1 AND ($2::boolean OR token = $3)
+        INTERSECT
+        SELECT DISTINCT tx_hash FROM tx_filters
+        WHERE address = $4 AND ($2::boolean OR token = $3)
+    )
+    SELECT COUNT(*) FROM tx_hashes
+"#;
+
+/// `address_has_activity`.
+const ADDRESS_ACTIVITY_QUERY: &str = r#"
+    SELECT EXISTS(SELECT 1 FROM tx_filters WHERE tx_filters.address = u.address)
+    FROM UNNEST($1::bytea[]) WITH ORDINALITY AS u(address, idx)
+    ORDER BY u.idx
+"#;
+
+/// Collects the large tables scanned sequentially by the plan node or its children.
+fn seq_scanned_tables(plan: &Value, tables: &mut Vec<String>) {
+    if plan["Node Type"] == "Seq Scan" {
+        if let Some(table) = plan["Relation Name"].as_str() {
+            if LARGE_TABLES.contains(&table) {
+                tables.push(table.to_owned());
+            }
+        }
+    }
+    if let Some(children) = plan["Plans"].as_array() {
+        for child in children {
+            seq_scanned_tables(child, tables);
+        }
+    }
+}
+
+/// Asserts that the explained plan doesn't scan the large tables sequentially.
+fn assert_no_seq_scans(name: &str, explained: Value) {
+    let mut tables = Vec::new();
+    seq_scanned_tables(&explained[0]["Plan"], &mut tables);
+    assert!(
+        tables.is_empty(),
+        "Query `{}` scans {:?} sequentially, plan: {:#}",
+        name,
+        tables,
+        explained
+    );
+}
+
+fn explain(query: &str) -> String {
+    format!("EXPLAIN (FORMAT JSON) {}", query)
+}
+
+/// Checks that the account history queries are served by the indexes.
+#[db_test]
+async fn account_history_query_plans(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    // The setting is reset once the test transaction is rolled back.
+    sqlx::query("SET LOCAL enable_seqscan = off")
+        .execute(storage.conn())
+        .await?;
+
+    let address = setup.from_zksync_account.address;
+    let second_address = setup.to_zksync_account.address;
+    let token = TokenId(1);
+
+    let explained = sqlx::query_scalar(&explain(ACCOUNT_HISTORY_QUERY))
+        .bind(address.as_bytes())
+        .bind(0i64)
+        .bind(10i64)
+        .fetch_one(storage.conn())
+        .await?;
+    assert_no_seq_scans("account_history", explained);
+
+    let explained = sqlx::query_scalar(&explain(ACCOUNT_LAST_TX_HASH_QUERY))
+        .bind(address.as_bytes())
+        .fetch_one(storage.conn())
+        .await?;
+    assert_no_seq_scans("account_last_tx_hash", explained);
+
+    for with_token in [false, true].iter().copied() {
+        for direction in [PaginationDirection::Newer, PaginationDirection::Older]
+            .iter()
+            .copied()
+        {
+            let query = account_sequence_numbers_query(with_token, direction);
+            let explained = sqlx::query_scalar(&explain(&query))
+                .bind(address.as_bytes())
+                .bind(token.0 as i32)
+                .bind(0i64)
+                .bind(10i64)
+                .fetch_one(storage.conn())
+                .await?;
+            assert_no_seq_scans("account_sequence_numbers", explained);
+
+            let query = two_accounts_transactions_query(with_token, direction);
+            let explained = sqlx::query_scalar(&explain(&query))
+                .bind(address.as_bytes())
+                .bind(second_address.as_bytes())
+                .bind(token.0 as i32)
+                .bind(0i64)
+                .bind(10i64)
+                .fetch_one(storage.conn())
+                .await?;
+            assert_no_seq_scans("two_accounts_transactions", explained);
+
+            let query = two_accounts_sequence_numbers_query(with_token, direction);
+            let explained = sqlx::query_scalar(&explain(&query))
+                .bind(address.as_bytes())
+                .bind(second_address.as_bytes())
+                .bind(token.0 as i32)
+                .bind(0i64)
+                .bind(10i64)
+                .fetch_one(storage.conn())
+                .await?;
+            assert_no_seq_scans("two_accounts_sequence_numbers", explained);
+        }
+    }
+
+    let sequence_numbers = vec![1i64, 2, 3];
+    let explained = sqlx::query_scalar(&explain(EXECUTED_TXS_BY_SEQUENCE_NUMBERS_QUERY))
+        .bind(&sequence_numbers)
+        .fetch_one(storage.conn())
+        .await?;
+    assert_no_seq_scans("executed_txs_by_sequence_numbers", explained);
+
+    let explained = sqlx::query_scalar(&explain(PRIORITY_OPS_BY_SEQUENCE_NUMBERS_QUERY))
+        .bind(&sequence_numbers)
+        .fetch_one(storage.conn())
+        .await?;
+    assert_no_seq_scans("priority_ops_by_sequence_numbers", explained);
+
+    let explained = sqlx::query_scalar(&explain(SEQUENCE_NUMBER_BY_BLOCK_INDEX_QUERY))
+        .bind(1i64)
+        .bind(0i32)
+        .fetch_one(storage.conn())
+        .await?;
+    assert_no_seq_scans("sequence_number_by_block_index", explained);
+
+    let explained = sqlx::query_scalar(&explain(TWO_ACCOUNTS_TXS_COUNT_QUERY))
+        .bind(address.as_bytes())
+        .bind(false)
+        .bind(token.0 as i32)
+        .bind(second_address.as_bytes())
+        .fetch_one(storage.conn())
+        .await?;
+    assert_no_seq_scans("two_accounts_txs_count", explained);
+
+    let addresses = vec![
+        address.as_bytes().to_vec(),
+        second_address.as_bytes().to_vec(),
+    ];
+    let explained = sqlx::query_scalar(&explain(ADDRESS_ACTIVITY_QUERY))
+        .bind(&addresses)
+        .fetch_one(storage.conn())
+        .await?;
+    assert_no_seq_scans("address_activity", explained);
+
+    Ok(())
+}
@@ -0,0 +1,594 @@
+//! Property-based tests checking that the executed operations of every type survive
+//! being stored to the database, and that the API representations built from the stored
+//! records reproduce all the fields of the original operations.
+
+// Built-in imports
+// External imports
+use chrono::{DateTime, TimeZone, Utc};
+use num::BigUint;
+use proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+// Workspace imports
+use zksync_api_types::v02::transaction::{
+    ApiDeposit, ApiFullExit, L1Receipt, L1Transaction, L2Receipt, Receipt, TransactionData,
+    TxInBlockStatus,
+};
+use zksync_crypto::params::{max_processable_token, MIN_NFT_TOKEN_ID};
+use zksync_types::{
+    block::{ExecutedPriorityOp, ExecutedTx},
+    helpers::{closest_packable_fee_amount, closest_packable_token_amount},
+    tx::{ChangePubKeyType, TimeRange},
+    BlockNumber, ChangePubKeyOp, CloseOp, Deposit, DepositOp, EthBlockId, ExecutedOperations,
+    ForcedExitOp, FullExit, FullExitOp, MintNFTOp, Nonce, PriorityOp, SignedZkSyncTx, SwapOp,
+    TokenId, TransferOp, TransferToNewOp, WithdrawNFTOp, WithdrawOp, ZkSyncOp, ZkSyncTx, H256,
+};
+// Local imports
+use super::{commit_schema_data, setup::TransactionsHistoryTestSetup};
+use crate::{
+    test_data::{gen_eth_sign_data, gen_sample_block, BLOCK_SIZE_CHUNKS},
+    tests::{db_test, ACCOUNT_MUTEX},
+    QueryResult, StorageProcessor,
+};
+
+/// Number of the generated operations of every type.
+const CASES_PER_KIND: u32 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OpKind {
+    Transfer,
+    TransferToNew,
+    Withdraw,
+    Close,
+    ChangePubKey,
+    ForcedExit,
+    MintNFT,
+    WithdrawNFT,
+    Swap,
+    Deposit,
+    FullExit,
+}
+
+impl OpKind {
+    const ALL: [OpKind; 11] = [
+        OpKind::Transfer,
+        OpKind::TransferToNew,
+        OpKind::Withdraw,
+        OpKind::Close,
+        OpKind::ChangePubKey,
+        OpKind::ForcedExit,
+        OpKind::MintNFT,
+        OpKind::WithdrawNFT,
+        OpKind::Swap,
+        OpKind::Deposit,
+        OpKind::FullExit,
+    ];
+
+    /// Intentionally lists every variant without a wildcard, so a new operation type
+    /// doesn't compile until it's covered by the tests.
+    fn of(op: &ZkSyncOp) -> Self {
+        match op {
+            ZkSyncOp::Transfer(_) => OpKind::Transfer,
+            ZkSyncOp::TransferToNew(_) => OpKind::TransferToNew,
+            ZkSyncOp::Withdraw(_) => OpKind::Withdraw,
+            ZkSyncOp::Close(_) => OpKind::Close,
+            ZkSyncOp::ChangePubKeyOffchain(_) => OpKind::ChangePubKey,
+            ZkSyncOp::ForcedExit(_) => OpKind::ForcedExit,
+            ZkSyncOp::MintNFTOp(_) => OpKind::MintNFT,
+            ZkSyncOp::WithdrawNFT(_) => OpKind::WithdrawNFT,
+            ZkSyncOp::Swap(_) => OpKind::Swap,
+            ZkSyncOp::Deposit(_) => OpKind::Deposit,
+            ZkSyncOp::FullExit(_) => OpKind::FullExit,
+            ZkSyncOp::Noop(_) => unreachable!("Noop operations are not stored"),
+        }
+    }
+}
+
+/// Randomized fields of the generated operation.
+#[derive(Debug, Clone)]
+struct OpParams {
+    token: TokenId,
+    second_token: TokenId,
+    amount: BigUint,
+    fee: BigUint,
+    nonce: Nonce,
+    time_range: TimeRange,
+    success: bool,
+    eth_signed: bool,
+    batch_id: Option<i64>,
+    /// Timestamps are stored with the microsecond precision, so whole seconds are generated.
+    created_at: DateTime<Utc>,
+    content_hash: H256,
+    serial_id: u32,
+}
+
+prop_compose! {
+    fn op_params()(
+        token in 0..*max_processable_token(),
+        token_offset in 1..*max_processable_token(),
+        amount in any::<u64>(),
+        fee in any::<u64>(),
+        nonce in any::<u32>(),
+        time_range in (any::<u32>(), any::<u32>()),
+        success in prop::bool::weighted(0.8),
+        eth_signed in any::<bool>(),
+        batch_id in prop::option::of(0..1_000_000i64),
+        created_at in 1_500_000_000..2_000_000_000i64,
+        content_hash in any::<[u8; 32]>(),
+        serial_id in any::<u32>(),
+    ) -> OpParams {
+        let (valid_from, valid_until) = if time_range.0 <= time_range.1 {
+            time_range
+        } else {
+            (time_range.1, time_range.0)
+        };
+        OpParams {
+            token: TokenId(token),
+            second_token: TokenId((token + token_offset) % *max_processable_token()),
+            amount: closest_packable_token_amount(&BigUint::from(amount)),
+            fee: closest_packable_fee_amount(&BigUint::from(fee)),
+            nonce: Nonce(nonce),
+            time_range: TimeRange::new(valid_from.into(), valid_until.into()),
+            success,
+            eth_signed,
+            batch_id,
+            created_at: Utc.timestamp(created_at, 0),
+            content_hash: H256(content_hash),
+            serial_id,
+        }
+    }
+}
+
+fn gen_op(setup: &TransactionsHistoryTestSetup, kind: OpKind, params: &OpParams) -> ZkSyncOp {
+    let from = &setup.from_zksync_account;
+    let to = &setup.to_zksync_account;
+    let from_id = from.get_account_id().unwrap();
+    let to_id = to.get_account_id().unwrap();
+    let nonce = Some(params.nonce);
+
+    match kind {
+        OpKind::Transfer | OpKind::TransferToNew => {
+            let tx = from
+                .sign_transfer(
+                    params.token,
+                    "TOKEN",
+                    params.amount.clone(),
+                    params.fee.clone(),
+                    &to.address,
+                    nonce,
+                    false,
+                    params.time_range,
+                )
+                .0;
+            if kind == OpKind::Transfer {
+                ZkSyncOp::Transfer(Box::new(TransferOp {
+                    tx,
+                    from: from_id,
+                    to: to_id,
+                }))
+            } else {
+                ZkSyncOp::TransferToNew(Box::new(TransferToNewOp {
+                    tx,
+                    from: from_id,
+                    to: to_id,
+                }))
+            }
+        }
+        OpKind::Withdraw => ZkSyncOp::Withdraw(Box::new(WithdrawOp {
+            tx: from
+                .sign_withdraw(
+                    params.token,
+                    "TOKEN",
+                    params.amount.clone(),
+                    params.fee.clone(),
+                    &to.address,
+                    nonce,
+                    false,
+                    params.time_range,
+                )
+                .0,
+            account_id: from_id,
+        })),
+        OpKind::Close => ZkSyncOp::Close(Box::new(CloseOp {
+            tx: from.sign_close(nonce, false),
+            account_id: from_id,
+        })),
+        OpKind::ChangePubKey => ZkSyncOp::ChangePubKeyOffchain(Box::new(ChangePubKeyOp {
+            tx: from.sign_change_pubkey_tx(
+                nonce,
+                false,
+                params.token,
+                params.fee.clone(),
+                ChangePubKeyType::ECDSA,
+                params.time_range,
+            ),
+            account_id: from_id,
+        })),
+        OpKind::ForcedExit => ZkSyncOp::ForcedExit(Box::new(ForcedExitOp {
+            tx: from.sign_forced_exit(
+                params.token,
+                params.fee.clone(),
+                &to.address,
+                nonce,
+                false,
+                params.time_range,
+            ),
+            target_account_id: to_id,
+            withdraw_amount: Some(params.amount.clone().into()),
+        })),
+        OpKind::MintNFT => ZkSyncOp::MintNFTOp(Box::new(MintNFTOp {
+            tx: from
+                .sign_mint_nft(
+                    params.token,
+                    "TOKEN",
+                    params.content_hash,
+                    params.fee.clone(),
+                    &to.address,
+                    nonce,
+                    false,
+                )
+                .0,
+            creator_account_id: from_id,
+            recipient_account_id: to_id,
+        })),
+        OpKind::WithdrawNFT => ZkSyncOp::WithdrawNFT(Box::new(WithdrawNFTOp {
+            tx: from
+                .sign_withdraw_nft(
+                    TokenId(MIN_NFT_TOKEN_ID + *params.second_token),
+                    params.token,
+                    "TOKEN",
+                    params.fee.clone(),
+                    &to.address,
+                    nonce,
+                    false,
+                    params.time_range,
+                )
+                .0,
+            creator_id: to_id,
+            creator_address: to.address,
+            serial_id: params.serial_id,
+            content_hash: params.content_hash,
+        })),
+        OpKind::Swap => {
+            let order1 = from.sign_order(
+                params.token,
+                params.second_token,
+                params.amount.clone(),
+                params.fee.clone(),
+                params.amount.clone(),
+                &from.address,
+                nonce,
+                false,
+                params.time_range,
+            );
+            let order2 = to.sign_order(
+                params.second_token,
+                params.token,
+                params.fee.clone(),
+                params.amount.clone(),
+                params.amount.clone(),
+                &to.address,
+                nonce,
+                false,
+                params.time_range,
+            );
+            ZkSyncOp::Swap(Box::new(SwapOp {
+                tx: from
+                    .sign_swap(
+                        (order1, order2),
+                        (params.amount.clone(), params.amount.clone()),
+                        nonce,
+                        false,
+                        params.token,
+                        "TOKEN",
+                        params.fee.clone(),
+                    )
+                    .0,
+                submitter: from_id,
+                accounts: (from_id, to_id),
+                recipients: (from_id, to_id),
+            }))
+        }
+        OpKind::Deposit => ZkSyncOp::Deposit(Box::new(DepositOp {
+            priority_op: Deposit {
+                from: from.address,
+                token: params.token,
+                amount: params.amount.clone(),
+                to: to.address,
+            },
+            account_id: to_id,
+        })),
+        OpKind::FullExit => ZkSyncOp::FullExit(Box::new(FullExitOp {
+            priority_op: FullExit {
+                account_id: from_id,
+                eth_address: from.address,
+                token: params.token,
+                is_legacy: false,
+            },
+            withdraw_amount: Some(params.amount.clone().into()),
+            creator_account_id: None,
+            creator_address: None,
+            serial_id: None,
+            content_hash: None,
+        })),
+    }
+}
+
+/// Wraps the operation into the executed one, the transactions are rejected
+/// according to the generated parameters.
+fn gen_executed_op(op: ZkSyncOp, params: &OpParams, serial_id: u64) -> ExecutedOperations {
+    if let Ok(tx) = op.try_get_tx() {
+        let eth_sign_data = if params.eth_signed {
+            Some(gen_eth_sign_data(format!("Round trip {}", serial_id)))
+        } else {
+            None
+        };
+        let executed_tx = ExecutedTx {
+            signed_tx: SignedZkSyncTx {
+                tx,
+                eth_sign_data,
+                created_at: params.created_at,
+            },
+            success: params.success,
+            op: if params.success { Some(op) } else { None },
+            fail_reason: if params.success {
+                None
+            } else {
+                Some("Not enough balance".to_string())
+            },
+            block_index: if params.success { Some(0) } else { None },
+            created_at: params.created_at,
+            batch_id: params.batch_id,
+        };
+        ExecutedOperations::Tx(Box::new(executed_tx))
+    } else {
+        let executed_op = ExecutedPriorityOp {
+            priority_op: PriorityOp {
+                serial_id,
+                data: op.try_get_priority_op().unwrap(),
+                deadline_block: u64::from(params.serial_id),
+                eth_hash: H256::from_low_u64_be(serial_id),
+                eth_block: u64::from(*params.nonce),
+                eth_block_index: Some(u64::from(*params.second_token)),
+                l1_sender: None,
+            },
+            op,
+            block_index: 0,
+            created_at: params.created_at,
+        };
+        ExecutedOperations::PriorityOp(Box::new(executed_op))
+    }
+}
+
+/// Accounts the `web3` API reports as the sender and the recipient of the transaction.
+fn web3_accounts(tx: &ZkSyncTx) -> (Vec<u8>, Vec<u8>) {
+    let (from, to) = match tx {
+        ZkSyncTx::Transfer(tx) => (tx.from, tx.to),
+        ZkSyncTx::Withdraw(tx) => (tx.from, tx.to),
+        ZkSyncTx::WithdrawNFT(tx) => (tx.from, tx.to),
+        ZkSyncTx::ChangePubKey(tx) => {
+            return (tx.account.as_bytes().to_vec(), tx.new_pk_hash.data.to_vec())
+        }
+        ZkSyncTx::Close(tx) => (tx.account, tx.account),
+        ZkSyncTx::ForcedExit(tx) => (tx.target, tx.target),
+        ZkSyncTx::MintNFT(tx) => (tx.creator_address, tx.recipient),
+        ZkSyncTx::Swap(tx) => (tx.submitter_address, tx.submitter_address),
+    };
+    (from.as_bytes().to_vec(), to.as_bytes().to_vec())
+}
+
+async fn check_stored_tx(
+    storage: &mut StorageProcessor<'_>,
+    block_number: BlockNumber,
+    tx: &ExecutedTx,
+) -> QueryResult<()> {
+    let tx_hash = tx.signed_tx.hash();
+    let status = if tx.success {
+        TxInBlockStatus::Committed
+    } else {
+        TxInBlockStatus::Rejected
+    };
+
+    let tx_data = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_data_api_v02(tx_hash.as_ref())
+        .await?
+        .expect("stored transaction");
+    assert_eq!(tx_data.tx.tx_hash, tx_hash);
+    assert_eq!(tx_data.tx.block_number, Some(block_number));
+    assert_eq!(tx_data.tx.block_index, tx.block_index);
+    assert_eq!(tx_data.tx.status, status);
+    assert_eq!(tx_data.tx.fail_reason, tx.fail_reason);
+    assert_eq!(tx_data.tx.created_at, Some(tx.created_at));
+    assert_eq!(tx_data.tx.batch_id, tx.batch_id.map(|id| id as u32));
+    assert_eq!(
+        tx_data.eth_signature,
+        tx.signed_tx
+            .eth_sign_data
+            .as_ref()
+            .map(|sign_data| sign_data.signature.to_string())
+    );
+    // The API transaction is the original one extended with the additional fields.
+    assert!(matches!(tx_data.tx.op, TransactionData::L2(_)));
+    let expected_op = serde_json::to_value(&tx.signed_tx.tx).unwrap();
+    let op = serde_json::to_value(&tx_data.tx.op).unwrap();
+    for (field, value) in expected_op.as_object().unwrap() {
+        assert_eq!(&op[field], value, "Field `{}` of {}", field, expected_op);
+    }
+
+    let receipt = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_receipt_api_v02(tx_hash.as_ref())
+        .await?;
+    assert_eq!(
+        receipt,
+        Some(Receipt::L2(L2Receipt {
+            tx_hash,
+            rollup_block: Some(block_number),
+            status,
+            fail_reason: tx.fail_reason.clone(),
+            expected_finality_secs: None,
+            valid_until: None,
+            withdrawal_hook: None,
+        }))
+    );
+
+    let web3_data = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_data_for_web3(tx_hash.as_ref())
+        .await?
+        .expect("stored transaction");
+    let (from, to) = web3_accounts(&tx.signed_tx.tx);
+    assert_eq!(web3_data.block_number, i64::from(*block_number));
+    assert_eq!(
+        web3_data.block_index,
+        tx.block_index.map(|index| index as i32)
+    );
+    assert_eq!(web3_data.nonce, i64::from(*tx.signed_tx.nonce()));
+    assert_eq!(web3_data.from_account, from);
+    assert_eq!(web3_data.to_account, Some(to));
+    Ok(())
+}
+
+async fn check_stored_priority_op(
+    storage: &mut StorageProcessor<'_>,
+    block_number: BlockNumber,
+    op: &ExecutedPriorityOp,
+) -> QueryResult<()> {
+    let tx_hash = op.priority_op.tx_hash();
+    let eth_hash = op.priority_op.eth_hash;
+    let id = op.priority_op.serial_id;
+    let (expected_op, from, to) = match &op.op {
+        ZkSyncOp::Deposit(deposit) => (
+            L1Transaction::Deposit(ApiDeposit {
+                from: deposit.priority_op.from,
+                token_id: deposit.priority_op.token,
+                amount: deposit.priority_op.amount.clone(),
+                to: deposit.priority_op.to,
+                account_id: Some(deposit.account_id),
+                eth_hash,
+                id,
+                tx_hash,
+            }),
+            deposit.priority_op.from,
+            deposit.priority_op.to,
+        ),
+        ZkSyncOp::FullExit(full_exit) => (
+            L1Transaction::FullExit(ApiFullExit {
+                account_id: full_exit.priority_op.account_id,
+                token_id: full_exit.priority_op.token,
+                eth_hash,
+                id,
+                tx_hash,
+            }),
+            full_exit.priority_op.eth_address,
+            full_exit.priority_op.eth_address,
+        ),
+        _ => unreachable!("Not a priority operation"),
+    };
+
+    let tx_data = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_data_api_v02(tx_hash.as_ref())
+        .await?
+        .expect("stored priority operation");
+    assert_eq!(tx_data.tx.tx_hash, tx_hash);
+    assert_eq!(tx_data.tx.block_number, Some(block_number));
+    assert_eq!(tx_data.tx.block_index, Some(op.block_index));
+    assert_eq!(tx_data.tx.status, TxInBlockStatus::Committed);
+    assert_eq!(tx_data.tx.fail_reason, None);
+    assert_eq!(tx_data.tx.created_at, Some(op.created_at));
+    assert_eq!(tx_data.tx.batch_id, None);
+    assert_eq!(tx_data.eth_signature, None);
+    match tx_data.tx.op {
+        TransactionData::L1(l1_op) => assert_eq!(l1_op, expected_op),
+        TransactionData::L2(l2_op) => panic!("Unexpected L2 transaction: {:?}", l2_op),
+    }
+
+    // Priority operations are also looked up by the hash of the Ethereum transaction.
+    let expected_receipt = Some(Receipt::L1(L1Receipt {
+        status: TxInBlockStatus::Committed,
+        eth_block: EthBlockId(op.priority_op.eth_block),
+        rollup_block: Some(block_number),
+        id,
+        expected_finality_secs: None,
+    }));
+    for hash in [tx_hash.as_ref(), eth_hash.as_bytes()].iter() {
+        let receipt = storage
+            .chain()
+            .operations_ext_schema()
+            .tx_receipt_api_v02(hash)
+            .await?;
+        assert_eq!(receipt, expected_receipt);
+    }
+
+    let web3_data = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_data_for_web3(tx_hash.as_ref())
+        .await?
+        .expect("stored priority operation");
+    assert_eq!(web3_data.block_number, i64::from(*block_number));
+    assert_eq!(web3_data.block_index, Some(op.block_index as i32));
+    assert_eq!(web3_data.nonce, id as i64);
+    assert_eq!(web3_data.from_account, from.as_bytes().to_vec());
+    assert_eq!(web3_data.to_account, Some(to.as_bytes().to_vec()));
+    Ok(())
+}
+
+/// Stores the randomly generated operations of every type, and checks that
+/// they're loaded back without changes by the block schema and the API methods.
+#[db_test]
+async fn stored_operations_round_trip(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let setup = TransactionsHistoryTestSetup::new();
+    commit_schema_data(&mut storage, &setup).await?;
+
+    // Cases are generated manually since the test runner doesn't support async tests.
+    let mut runner = TestRunner::deterministic();
+    let mut block_number = BlockNumber(0);
+    for kind in OpKind::ALL.iter().copied() {
+        for _ in 0..CASES_PER_KIND {
+            let params = op_params().new_tree(&mut runner).unwrap().current();
+            let op = gen_op(&setup, kind, &params);
+            assert_eq!(OpKind::of(&op), kind);
+
+            *block_number += 1;
+            let executed_op = gen_executed_op(op, &params, u64::from(*block_number));
+            storage
+                .chain()
+                .block_schema()
+                .save_full_block(gen_sample_block(
+                    block_number,
+                    BLOCK_SIZE_CHUNKS,
+                    vec![executed_op.clone()],
+                ))
+                .await?;
+
+            let stored_ops = storage
+                .chain()
+                .block_schema()
+                .get_block_executed_ops(block_number)
+                .await?;
+            assert_eq!(stored_ops.len(), 1);
+            assert_eq!(
+                serde_json::to_value(&stored_ops[0]).unwrap(),
+                serde_json::to_value(&executed_op).unwrap(),
+                "{:?} operation is changed by the storage",
+                kind
+            );
+
+            match &executed_op {
+                ExecutedOperations::Tx(tx) => {
+                    check_stored_tx(&mut storage, block_number, tx).await?
+                }
+                ExecutedOperations::PriorityOp(op) => {
+                    check_stored_priority_op(&mut storage, block_number, op).await?
+                }
+            }
+        }
+    }
+    Ok(())
+}
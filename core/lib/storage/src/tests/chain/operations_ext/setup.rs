@@ -209,6 +209,7 @@ impl TransactionsHistoryTestSetup {
                 ),
                 eth_block: 10,
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
             op: deposit_op,
             block_index,
@@ -248,6 +249,7 @@ impl TransactionsHistoryTestSetup {
                 ),
                 eth_block: 11,
                 eth_block_index: Some(1),
+                l1_sender: None,
             },
             op: full_exit_op,
             block_index,
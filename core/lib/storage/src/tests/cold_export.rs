@@ -0,0 +1,64 @@
+// Built-in imports
+use std::fs::File;
+// External imports
+use chrono::Utc;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+// Workspace imports
+use zksync_types::BlockNumber;
+// Local imports
+use crate::chain::operations::{records::NewExecutedTransaction, OperationsSchema};
+use crate::cold_export::ColdExportSchema;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the history is exported to the partitioned Parquet files.
+#[db_test]
+async fn test_export_block_range(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for (block_number, tx_hash) in vec![(1, 1), (2, 2), (2, 3), (3, 4)] {
+        OperationsSchema(&mut storage)
+            .store_executed_tx(NewExecutedTransaction {
+                block_number,
+                tx_hash: vec![tx_hash; 32],
+                tx: serde_json::json!({ "type": "Transfer" }),
+                operation: Default::default(),
+                from_account: Default::default(),
+                to_account: None,
+                success: true,
+                fail_reason: None,
+                block_index: Some(tx_hash as i32),
+                primary_account_address: Default::default(),
+                nonce: Default::default(),
+                created_at: Utc::now(),
+                eth_sign_data: None,
+                batch_id: None,
+                affected_accounts: Vec::new(),
+                used_tokens: Vec::new(),
+            })
+            .await?;
+    }
+
+    let output_dir = std::env::temp_dir().join(format!(
+        "zksync-cold-export-{}",
+        Utc::now().timestamp_nanos()
+    ));
+    let files = ColdExportSchema(&mut storage)
+        .export_block_range(BlockNumber(1), BlockNumber(3), 2, &output_dir)
+        .await?;
+
+    // Two partitions with three tables in each.
+    assert_eq!(files.len(), 6);
+    let expected_rows = vec![
+        ("executed_transactions/blocks=1-2", 3),
+        ("executed_transactions/blocks=3-3", 1),
+        ("executed_priority_operations/blocks=1-2", 0),
+    ];
+    for (partition, rows) in expected_rows {
+        let path = output_dir.join(partition).join("data.parquet");
+        assert!(files.contains(&path), "{:?} is not exported", path);
+        let reader = SerializedFileReader::new(File::open(&path)?)?;
+        assert_eq!(reader.metadata().file_metadata().num_rows(), rows);
+    }
+
+    std::fs::remove_dir_all(output_dir)?;
+    Ok(())
+}
@@ -0,0 +1,114 @@
+// External imports
+// Workspace imports
+use zksync_types::{AccountId, AccountUpdate, BlockNumber, Nonce, TokenId};
+// Local imports
+use crate::consistency::{ConsistencySchema, Invariant};
+use crate::tests::{
+    chain::{commit_block, commit_schema_data, verify_block, TransactionsHistoryTestSetup},
+    db_test, ACCOUNT_MUTEX,
+};
+use crate::{QueryResult, StorageProcessor};
+
+/// Id of the account created by `commit_schema_data`.
+const ACCOUNT_ID: AccountId = AccountId(0xbabe);
+
+/// Checks that the violations of every invariant are found and reported.
+#[db_test]
+async fn test_check_invariants(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let mut setup = TransactionsHistoryTestSetup::new();
+    for block_number in 1..=3 {
+        setup.add_block(block_number);
+    }
+    commit_schema_data(&mut storage, &setup).await?;
+    for block_number in 1..=3 {
+        commit_block(&mut storage, BlockNumber(block_number)).await?;
+    }
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(
+            BlockNumber(1),
+            &[(
+                ACCOUNT_ID,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: Nonce(0),
+                    new_nonce: Nonce(0),
+                    balance_update: (TokenId(0), 0u32.into(), 100u32.into()),
+                },
+            )],
+            2,
+        )
+        .await?;
+    for block_number in 0..=1 {
+        storage
+            .chain()
+            .state_schema()
+            .apply_state_update(BlockNumber(block_number))
+            .await?;
+    }
+    // The second block is not executed.
+    verify_block(&mut storage, BlockNumber(1)).await?;
+    verify_block(&mut storage, BlockNumber(3)).await?;
+
+    // Consistent data has no violations.
+    assert!(ConsistencySchema(&mut storage)
+        .check_tx_filters(BlockNumber(1), BlockNumber(3), 10)
+        .await?
+        .is_empty());
+    assert!(ConsistencySchema(&mut storage)
+        .check_balances(BlockNumber(1), 10)
+        .await?
+        .is_empty());
+
+    let violations = ConsistencySchema(&mut storage)
+        .check_executed_blocks(BlockNumber(1), BlockNumber(3), 10)
+        .await?;
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].invariant, Invariant::ExecutedBlocks);
+    assert_eq!(violations[0].block_number, BlockNumber(2));
+
+    // Break the rest of the invariants.
+    let tx_hash: Vec<u8> = sqlx::query_scalar(
+        "SELECT tx_hash FROM executed_transactions WHERE block_number = 2 LIMIT 1",
+    )
+    .fetch_one(storage.conn())
+    .await?;
+    sqlx::query("DELETE FROM tx_filters WHERE tx_hash = $1")
+        .bind(&tx_hash)
+        .execute(storage.conn())
+        .await?;
+    sqlx::query("UPDATE balances SET balance = 1 WHERE account_id = $1")
+        .bind(i64::from(*ACCOUNT_ID))
+        .execute(storage.conn())
+        .await?;
+
+    let violations = ConsistencySchema(&mut storage)
+        .check_tx_filters(BlockNumber(1), BlockNumber(3), 10)
+        .await?;
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].invariant, Invariant::TxFilters);
+    assert_eq!(violations[0].block_number, BlockNumber(2));
+    assert!(violations[0].description.contains(&hex::encode(&tx_hash)));
+
+    let violations = ConsistencySchema(&mut storage)
+        .check_balances(BlockNumber(1), 10)
+        .await?;
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].invariant, Invariant::Balances);
+    assert_eq!(violations[0].block_number, BlockNumber(1));
+
+    // All the violations are included in the report, and only the verified blocks are checked.
+    let report = ConsistencySchema(&mut storage)
+        .check_invariants(BlockNumber(1), 10)
+        .await?;
+    assert_eq!(report.last_checked_block, BlockNumber(3));
+    assert_eq!(report.violations.len(), 3);
+
+    let report = ConsistencySchema(&mut storage)
+        .check_invariants(BlockNumber(4), 10)
+        .await?;
+    assert!(report.violations.is_empty());
+
+    Ok(())
+}
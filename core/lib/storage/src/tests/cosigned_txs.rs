@@ -0,0 +1,135 @@
+// External imports
+use chrono::{Duration, Utc};
+use num::BigUint;
+// Workspace imports
+use zksync_types::{
+    tx::{TimeRange, TxEthSignatureVariant},
+    AccountId, Address, Nonce, TokenId, Transfer, ZkSyncTx,
+};
+// Local imports
+use crate::test_data::gen_eth_sign_data;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn transfer() -> ZkSyncTx {
+    ZkSyncTx::Transfer(Box::new(Transfer::new(
+        AccountId(1),
+        Address::repeat_byte(0x01),
+        Address::repeat_byte(0xff),
+        TokenId(0),
+        BigUint::from(100u32),
+        BigUint::from(1u32),
+        Nonce(0),
+        TimeRange::default(),
+        None,
+    )))
+}
+
+/// Checks that the co-signatures are stored once per signer and the submission result
+/// is recorded.
+#[db_test]
+async fn test_cosigned_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let tx = transfer();
+    let tx_hash = tx.hash();
+    let cosigners = [Address::repeat_byte(0x0a), Address::repeat_byte(0x0b)];
+    let expires_at = Utc::now() + Duration::hours(1);
+
+    assert!(storage
+        .cosigned_txs_schema()
+        .get_cosigned_tx(tx_hash)
+        .await?
+        .is_none());
+    assert!(
+        storage
+            .cosigned_txs_schema()
+            .store_cosigned_tx(
+                &tx,
+                AccountId(1),
+                &TxEthSignatureVariant::Single(None),
+                &cosigners,
+                2,
+                expires_at,
+            )
+            .await?
+    );
+    // The same transaction can't be stored twice.
+    assert!(
+        !storage
+            .cosigned_txs_schema()
+            .store_cosigned_tx(
+                &tx,
+                AccountId(1),
+                &TxEthSignatureVariant::Single(None),
+                &cosigners[..1],
+                1,
+                expires_at,
+            )
+            .await?
+    );
+
+    let stored = storage
+        .cosigned_txs_schema()
+        .get_cosigned_tx(tx_hash)
+        .await?
+        .expect("co-signed transaction should be stored");
+    assert_eq!(stored.tx_hash, tx_hash.as_ref().to_vec());
+    assert_eq!(stored.threshold, 2);
+    assert_eq!(stored.cosigners, vec![vec![0x0a; 20], vec![0x0b; 20]],);
+    assert!(stored.submitted_at.is_none());
+
+    let signature = gen_eth_sign_data("co-sign".to_string()).signature;
+    let mut transaction = storage.start_transaction().await?;
+    assert!(transaction
+        .cosigned_txs_schema()
+        .lock_cosigned_tx(tx_hash)
+        .await?
+        .is_some());
+    assert!(
+        transaction
+            .cosigned_txs_schema()
+            .store_cosignature(tx_hash, cosigners[0], &signature)
+            .await?
+    );
+    // The second signature of the same signer is ignored.
+    assert!(
+        !transaction
+            .cosigned_txs_schema()
+            .store_cosignature(tx_hash, cosigners[0], &signature)
+            .await?
+    );
+    transaction.commit().await?;
+
+    let signatures = storage
+        .cosigned_txs_schema()
+        .get_cosignatures(tx_hash)
+        .await?;
+    assert_eq!(signatures.len(), 1);
+    assert_eq!(signatures[0].signer, cosigners[0].as_bytes().to_vec());
+
+    // Failed submission leaves the transaction pending.
+    storage
+        .cosigned_txs_schema()
+        .store_submission_result(tx_hash, Some("nonce mismatch"))
+        .await?;
+    let stored = storage
+        .cosigned_txs_schema()
+        .get_cosigned_tx(tx_hash)
+        .await?
+        .unwrap();
+    assert!(stored.submitted_at.is_none());
+    assert_eq!(stored.error.as_deref(), Some("nonce mismatch"));
+
+    storage
+        .cosigned_txs_schema()
+        .store_submission_result(tx_hash, None)
+        .await?;
+    let stored = storage
+        .cosigned_txs_schema()
+        .get_cosigned_tx(tx_hash)
+        .await?
+        .unwrap();
+    assert!(stored.submitted_at.is_some());
+    assert!(stored.error.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+// External imports
+// Workspace imports
+use zksync_types::{Address, H256};
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the factory and code hash pairs are registered and removed.
+#[db_test]
+async fn test_create2_factories(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let factory = Address::repeat_byte(0x01);
+    let code_hash = H256::repeat_byte(0x02);
+    let other_code_hash = H256::repeat_byte(0x03);
+
+    assert!(storage
+        .create2_factories_schema()
+        .load_factories()
+        .await?
+        .is_empty());
+    storage
+        .create2_factories_schema()
+        .store_factory(factory, code_hash, "Vendor", "admin")
+        .await?;
+    // Registering the pair again only renames it.
+    storage
+        .create2_factories_schema()
+        .store_factory(factory, code_hash, "Vendor wallet", "admin")
+        .await?;
+    storage
+        .create2_factories_schema()
+        .store_factory(factory, other_code_hash, "Vendor wallet v2", "admin")
+        .await?;
+
+    let factories = storage.create2_factories_schema().load_factories().await?;
+    assert_eq!(factories.len(), 2);
+    assert_eq!(factories[0].creator_address, factory.as_bytes().to_vec());
+    assert_eq!(factories[0].code_hash, code_hash.as_bytes().to_vec());
+    assert_eq!(factories[0].name, "Vendor wallet");
+    assert!(
+        storage
+            .create2_factories_schema()
+            .is_factory_registered(factory, other_code_hash)
+            .await?
+    );
+    assert!(
+        !storage
+            .create2_factories_schema()
+            .is_factory_registered(Address::repeat_byte(0x04), code_hash)
+            .await?
+    );
+
+    assert!(
+        storage
+            .create2_factories_schema()
+            .remove_factory(factory, other_code_hash)
+            .await?
+    );
+    assert!(
+        !storage
+            .create2_factories_schema()
+            .remove_factory(factory, other_code_hash)
+            .await?
+    );
+    assert!(
+        !storage
+            .create2_factories_schema()
+            .is_factory_registered(factory, other_code_hash)
+            .await?
+    );
+    assert_eq!(
+        storage
+            .create2_factories_schema()
+            .load_factories()
+            .await?
+            .len(),
+        1
+    );
+
+    Ok(())
+}
@@ -2,7 +2,10 @@
 // Workspace imports
 // Local imports
 use crate::tests::db_test;
-use crate::{data_restore::DataRestoreSchema, QueryResult, StorageProcessor};
+use crate::{
+    data_restore::{records::NewDataRestoreCheckpoint, DataRestoreSchema},
+    QueryResult, StorageProcessor,
+};
 
 /// Checks that storing and loading the last watched block number
 /// works as expected.
@@ -40,3 +43,45 @@ async fn last_watched_block(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     Ok(())
 }
+
+/// Checks that the latest checkpoint is loaded and re-saving a checkpoint overwrites it.
+#[db_test]
+async fn checkpoints(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(DataRestoreSchema(&mut storage)
+        .load_last_checkpoint()
+        .await?
+        .is_none());
+
+    for &block_number in &[10, 20] {
+        DataRestoreSchema(&mut storage)
+            .save_checkpoint(NewDataRestoreCheckpoint {
+                block_number,
+                last_watched_eth_block: block_number * 100,
+                root_hash: vec![block_number as u8; 32],
+            })
+            .await?;
+    }
+    let checkpoint = DataRestoreSchema(&mut storage)
+        .load_last_checkpoint()
+        .await?
+        .expect("checkpoint must be stored");
+    assert_eq!(checkpoint.block_number, 20);
+    assert_eq!(checkpoint.last_watched_eth_block, 2000);
+    assert_eq!(checkpoint.root_hash, vec![20; 32]);
+
+    DataRestoreSchema(&mut storage)
+        .save_checkpoint(NewDataRestoreCheckpoint {
+            block_number: 20,
+            last_watched_eth_block: 2500,
+            root_hash: vec![21; 32],
+        })
+        .await?;
+    let checkpoint = DataRestoreSchema(&mut storage)
+        .load_last_checkpoint()
+        .await?
+        .expect("checkpoint must be stored");
+    assert_eq!(checkpoint.last_watched_eth_block, 2500);
+    assert_eq!(checkpoint.root_hash, vec![21; 32]);
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+// External imports
+use chrono::{Duration, Utc};
+// Workspace imports
+use zksync_types::{Address, BlockNumber, TokenId};
+// Local imports
+use crate::chain::operations::{records::NewExecutedPriorityOperation, OperationsSchema};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn executed_op(
+    serial_id: i64,
+    block_number: i64,
+    op_type: &str,
+    from: Address,
+    token: i32,
+    amount: &str,
+) -> NewExecutedPriorityOperation {
+    NewExecutedPriorityOperation {
+        block_number,
+        block_index: serial_id as i32,
+        operation: serde_json::json!({
+            "type": op_type,
+            "account_id": 1,
+            "priority_op": {
+                "from": from,
+                "token": token,
+                "amount": amount,
+                "to": from,
+            },
+        }),
+        from_account: from.as_bytes().to_vec(),
+        to_account: from.as_bytes().to_vec(),
+        priority_op_serialid: serial_id,
+        deadline_block: 100,
+        eth_hash: vec![serial_id as u8],
+        eth_block: 10,
+        created_at: Utc::now(),
+        tx_hash: vec![serial_id as u8],
+        eth_block_index: Some(1),
+        l1_sender: None,
+        affected_accounts: vec![from.as_bytes().to_vec()],
+        token,
+    }
+}
+
+/// Checks that the deposits are aggregated as they are executed and reverted.
+#[db_test]
+async fn test_deposit_aggregates(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let bridge = Address::repeat_byte(0x01);
+    let user = Address::repeat_byte(0x02);
+    let ops = vec![
+        executed_op(0, 1, "Deposit", bridge, 0, "100"),
+        executed_op(1, 1, "Deposit", bridge, 0, "200"),
+        executed_op(2, 1, "Deposit", user, 0, "50"),
+        executed_op(3, 1, "Deposit", bridge, 1, "1000"),
+        executed_op(4, 1, "FullExit", bridge, 0, "0"),
+        executed_op(5, 2, "Deposit", bridge, 0, "400"),
+    ];
+    for op in ops.iter().cloned() {
+        OperationsSchema(&mut storage)
+            .store_executed_priority_op(op)
+            .await?;
+    }
+    // The operation stored twice is counted once.
+    OperationsSchema(&mut storage)
+        .store_executed_priority_op(ops[0].clone())
+        .await?;
+
+    let today = Utc::today().naive_utc();
+    let deposits = storage
+        .deposit_aggregates_schema()
+        .load_daily_deposits(None, None, today, today)
+        .await?;
+    assert_eq!(deposits.len(), 2);
+    assert_eq!(deposits[0].day, today);
+    assert_eq!(deposits[0].token_id, 0);
+    assert_eq!(deposits[0].amount, 750.into());
+    assert_eq!(deposits[0].deposits_count, 4);
+    assert_eq!(deposits[0].senders_count, 2);
+    assert_eq!(deposits[1].token_id, 1);
+    assert_eq!(deposits[1].amount, 1000.into());
+
+    let deposits = storage
+        .deposit_aggregates_schema()
+        .load_daily_deposits(Some(bridge), Some(TokenId(0)), today, today)
+        .await?;
+    assert_eq!(deposits.len(), 1);
+    assert_eq!(deposits[0].amount, 700.into());
+    assert_eq!(deposits[0].deposits_count, 3);
+    assert!(storage
+        .deposit_aggregates_schema()
+        .load_daily_deposits(
+            None,
+            None,
+            today - Duration::days(7),
+            today - Duration::days(1)
+        )
+        .await?
+        .is_empty());
+
+    // Deposits of the reverted block are subtracted.
+    OperationsSchema(&mut storage)
+        .remove_executed_priority_operations(BlockNumber(1))
+        .await?;
+    let deposits = storage
+        .deposit_aggregates_schema()
+        .load_daily_deposits(Some(bridge), None, today, today)
+        .await?;
+    assert_eq!(deposits.len(), 2);
+    assert_eq!(deposits[0].amount, 300.into());
+    assert_eq!(deposits[0].deposits_count, 2);
+
+    OperationsSchema(&mut storage)
+        .remove_executed_priority_operations(BlockNumber(0))
+        .await?;
+    assert!(storage
+        .deposit_aggregates_schema()
+        .load_daily_deposits(None, None, today, today)
+        .await?
+        .is_empty());
+
+    Ok(())
+}
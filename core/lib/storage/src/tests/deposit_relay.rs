@@ -0,0 +1,132 @@
+// External imports
+use chrono::{Duration, Utc};
+use num::BigUint;
+// Workspace imports
+use zksync_types::{tx::PackedEthSignature, Address, TokenId, H256};
+// Local imports
+use crate::deposit_relay::{JOB_STATUS_COMPLETED, JOB_STATUS_FAILED, JOB_STATUS_PROCESSING};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the jobs are taken in order and their results are recorded.
+#[db_test]
+async fn test_deposit_relay_jobs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let owner = Address::repeat_byte(0x01);
+    let token_address = Address::repeat_byte(0xaa);
+    let signature = PackedEthSignature::sign(&H256::repeat_byte(0x01), b"permit").unwrap();
+    let deadline = Utc::now() + Duration::hours(1);
+
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let id = storage
+            .deposit_relay_schema()
+            .store_job(
+                owner,
+                TokenId(1),
+                token_address,
+                &BigUint::from(1000u32),
+                &BigUint::from(10u32),
+                deadline,
+                &signature,
+            )
+            .await?;
+        ids.push(id);
+    }
+    assert_eq!(
+        storage
+            .deposit_relay_schema()
+            .count_jobs_since(owner, Utc::now() - Duration::days(1))
+            .await?,
+        3
+    );
+    assert_eq!(
+        storage
+            .deposit_relay_schema()
+            .count_jobs_since(Address::repeat_byte(0x02), Utc::now() - Duration::days(1))
+            .await?,
+        0
+    );
+    assert_eq!(
+        storage.deposit_relay_schema().count_pending_jobs().await?,
+        3
+    );
+
+    // Jobs are taken in the order they were requested.
+    let job = storage
+        .deposit_relay_schema()
+        .take_next_pending_job()
+        .await?
+        .expect("job should be pending");
+    assert_eq!(job.id, ids[0]);
+    assert_eq!(job.status, JOB_STATUS_PROCESSING);
+    assert_eq!(job.amount.to_string(), "1000");
+    assert_eq!(job.fee.to_string(), "10");
+    assert_eq!(
+        PackedEthSignature::deserialize_packed(&job.signature).unwrap(),
+        signature
+    );
+
+    let deposit_hash = H256::repeat_byte(0x0d);
+    storage
+        .deposit_relay_schema()
+        .store_deposit_hash(job.id, deposit_hash)
+        .await?;
+    storage.deposit_relay_schema().complete_job(job.id).await?;
+    let job = storage
+        .deposit_relay_schema()
+        .get_job(ids[0])
+        .await?
+        .unwrap();
+    assert_eq!(job.status, JOB_STATUS_COMPLETED);
+    assert_eq!(job.eth_tx_hash, Some(deposit_hash.as_bytes().to_vec()));
+
+    let job = storage
+        .deposit_relay_schema()
+        .take_next_pending_job()
+        .await?
+        .unwrap();
+    assert_eq!(job.id, ids[1]);
+    storage
+        .deposit_relay_schema()
+        .fail_job(job.id, "permit is invalid")
+        .await?;
+    let job = storage
+        .deposit_relay_schema()
+        .get_job(ids[1])
+        .await?
+        .unwrap();
+    assert_eq!(job.status, JOB_STATUS_FAILED);
+    assert_eq!(job.error.as_deref(), Some("permit is invalid"));
+
+    // The job left processing is failed on the relayer start.
+    let job = storage
+        .deposit_relay_schema()
+        .take_next_pending_job()
+        .await?
+        .unwrap();
+    assert_eq!(job.id, ids[2]);
+    assert_eq!(
+        storage
+            .deposit_relay_schema()
+            .fail_interrupted_jobs()
+            .await?,
+        1
+    );
+    let job = storage
+        .deposit_relay_schema()
+        .get_job(ids[2])
+        .await?
+        .unwrap();
+    assert_eq!(job.status, JOB_STATUS_FAILED);
+    assert!(storage
+        .deposit_relay_schema()
+        .take_next_pending_job()
+        .await?
+        .is_none());
+    assert_eq!(
+        storage.deposit_relay_schema().count_pending_jobs().await?,
+        0
+    );
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+// External imports
+use chrono::Duration;
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use crate::chain::operations::records::NewExecutedTransaction;
+use crate::tests::{db_test, executed_tx, tx_hash};
+use crate::{QueryResult, StorageProcessor};
+
+fn transfer(hash: u8, address: Address, success: bool) -> NewExecutedTransaction {
+    NewExecutedTransaction {
+        block_index: None,
+        tx: serde_json::json!({ "type": "Transfer" }),
+        from_account: address.as_bytes().to_vec(),
+        primary_account_address: address.as_bytes().to_vec(),
+        affected_accounts: vec![address.as_bytes().to_vec()],
+        used_tokens: vec![0],
+        ..executed_tx(1, hash, success)
+    }
+}
+
+/// Checks that every rejection is kept searchable until it's cleaned up.
+#[db_test]
+async fn test_dropped_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let sender = Address::repeat_byte(0x01);
+    let other = Address::repeat_byte(0x02);
+
+    // The rejected transaction is resubmitted and executed afterwards.
+    storage
+        .chain()
+        .operations_schema()
+        .store_executed_tx(transfer(1, sender, false))
+        .await?;
+    storage
+        .chain()
+        .operations_schema()
+        .store_executed_txs(vec![
+            transfer(1, sender, false),
+            transfer(1, sender, true),
+            transfer(2, other, true),
+            transfer(3, other, false),
+        ])
+        .await?;
+
+    let dropped = storage
+        .dropped_txs_schema()
+        .load_dropped_txs(None, Some(tx_hash(1)), None, 10)
+        .await?;
+    assert_eq!(dropped.len(), 2);
+    assert!(dropped[0].id > dropped[1].id);
+    assert_eq!(dropped[0].kind, "rejected");
+    assert_eq!(dropped[0].address, sender.as_bytes().to_vec());
+    assert_eq!(dropped[0].reason.as_deref(), Some("Not enough balance"));
+    assert_eq!(dropped[0].tx, serde_json::json!({ "type": "Transfer" }));
+
+    let dropped = storage
+        .dropped_txs_schema()
+        .load_dropped_txs(Some(other), None, None, 10)
+        .await?;
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].tx_hash, vec![3; 32]);
+    let last_id = dropped[0].id;
+    let dropped = storage
+        .dropped_txs_schema()
+        .load_dropped_txs(None, None, Some(last_id), 1)
+        .await?;
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].tx_hash, vec![1; 32]);
+
+    // Only the newest transactions are kept once the limit is exceeded.
+    let removed = storage
+        .dropped_txs_schema()
+        .remove_old_dropped_txs(Duration::days(1), 2)
+        .await?;
+    assert_eq!(removed, 1);
+    let dropped = storage
+        .dropped_txs_schema()
+        .load_dropped_txs(None, None, None, 10)
+        .await?;
+    assert_eq!(dropped.len(), 2);
+    assert_eq!(dropped[0].id, last_id);
+
+    // Old transactions are removed regardless of the limit.
+    sqlx::query!(
+        "UPDATE dropped_transactions SET dropped_at = dropped_at - interval '2 days' WHERE id < $1",
+        last_id
+    )
+    .execute(storage.conn())
+    .await?;
+    let removed = storage
+        .dropped_txs_schema()
+        .remove_old_dropped_txs(Duration::days(1), 10)
+        .await?;
+    assert_eq!(removed, 1);
+    let dropped = storage
+        .dropped_txs_schema()
+        .load_dropped_txs(None, None, None, 10)
+        .await?;
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].id, last_id);
+
+    Ok(())
+}
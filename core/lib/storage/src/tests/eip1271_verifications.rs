@@ -0,0 +1,60 @@
+// External imports
+// Workspace imports
+use zksync_types::{tx::TxHash, Address, H256};
+// Local imports
+use crate::eip1271_verifications::records::EIP1271Verification;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the verifications are stored and replaced on the resubmission.
+#[db_test]
+async fn test_eip1271_verifications(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let tx_hash = TxHash::from_slice(&[1; 32]).unwrap();
+    let other_tx_hash = TxHash::from_slice(&[2; 32]).unwrap();
+    let verification = |tx_hash: TxHash, byte: u8, l1_block_number: u64| EIP1271Verification {
+        tx_hash,
+        address: Address::repeat_byte(byte),
+        message_hash: H256::repeat_byte(byte),
+        signature: vec![byte; 65],
+        l1_block_number,
+    };
+
+    storage
+        .eip1271_verifications_schema()
+        .store_verifications(&[
+            verification(tx_hash, 0x01, 100),
+            verification(tx_hash, 0x02, 100),
+            verification(other_tx_hash, 0x01, 101),
+        ])
+        .await?;
+    let verifications = storage
+        .eip1271_verifications_schema()
+        .load_verifications(tx_hash)
+        .await?;
+    assert_eq!(verifications.len(), 2);
+    assert_eq!(verifications[0].address, vec![0x01; 20]);
+    assert_eq!(verifications[0].message_hash, vec![0x01; 32]);
+    assert_eq!(verifications[0].signature, vec![0x01; 65]);
+    assert_eq!(verifications[0].l1_block_number, 100);
+
+    // The resubmitted transaction is verified at the later block.
+    storage
+        .eip1271_verifications_schema()
+        .store_verifications(&[verification(tx_hash, 0x01, 120)])
+        .await?;
+    let verifications = storage
+        .eip1271_verifications_schema()
+        .load_verifications(tx_hash)
+        .await?;
+    assert_eq!(verifications.len(), 2);
+    assert_eq!(verifications[0].l1_block_number, 120);
+    assert_eq!(verifications[1].l1_block_number, 100);
+
+    assert!(storage
+        .eip1271_verifications_schema()
+        .load_verifications(TxHash::from_slice(&[3; 32]).unwrap())
+        .await?
+        .is_empty());
+
+    Ok(())
+}
@@ -331,3 +331,96 @@ async fn test_account_events(mut storage: StorageProcessor<'_>) -> QueryResult<(
             && check_account_event(event, AccountStateChangeStatus::Finalized)));
     Ok(())
 }
+
+/// Checks that events can be read page by page after a cursor and that
+/// the retention policy removes old events and records the pruned cursor.
+#[db_test]
+async fn test_events_after_cursor(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    storage.ethereum_schema().initialize_eth_data().await?;
+    // Commit 3 blocks, each commit creates a single block event.
+    for block_number in 1..=3 {
+        let block_number = BlockNumber(block_number);
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(gen_sample_block(
+                block_number,
+                BLOCK_SIZE_CHUNKS,
+                Vec::new(),
+            ))
+            .await?;
+        store_operation(
+            &mut storage,
+            AggregatedActionType::CommitBlocks,
+            block_number,
+        )
+        .await?;
+    }
+    let all_events = fetch_new_events(&mut storage, EventId(0)).await?;
+    assert_eq!(all_events.len(), 3);
+
+    // Read events page by page.
+    let mut cursor = EventId(0);
+    for expected in &all_events {
+        let page = storage
+            .event_schema()
+            .load_events_after(cursor, 1)
+            .await?
+            .expect("cursor event is not removed");
+        assert_eq!(page.len(), 1);
+        let event = ZkSyncEvent::try_from(page[0].clone())?;
+        assert_eq!(event.id, expected.id);
+        cursor = event.id;
+    }
+    assert_eq!(
+        storage
+            .event_schema()
+            .load_events_after(cursor, 1)
+            .await?
+            .map(|page| page.len()),
+        Some(0)
+    );
+    assert_eq!(
+        storage.event_schema().get_first_event_id().await?,
+        Some(all_events[0].id)
+    );
+    assert_eq!(
+        storage.event_schema().get_last_visible_event_id().await?,
+        Some(cursor)
+    );
+
+    // Events are not old enough to be removed.
+    assert_eq!(
+        storage
+            .event_schema()
+            .remove_old_events(chrono::Duration::days(1))
+            .await?,
+        0
+    );
+    // Remove all the events.
+    assert_eq!(
+        storage
+            .event_schema()
+            .remove_old_events(chrono::Duration::zero())
+            .await?,
+        3
+    );
+    // Removed cursor is reported, the log is read from the start again.
+    assert!(storage
+        .event_schema()
+        .load_events_after(cursor, 1)
+        .await?
+        .is_none());
+    assert_eq!(
+        storage
+            .event_schema()
+            .load_events_after(EventId(0), 1)
+            .await?
+            .map(|page| page.len()),
+        Some(0)
+    );
+    assert_eq!(storage.event_schema().get_first_event_id().await?, None);
+    assert!(fetch_new_events(&mut storage, EventId(0)).await?.is_empty());
+
+    Ok(())
+}
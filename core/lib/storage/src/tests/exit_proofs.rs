@@ -0,0 +1,118 @@
+// External imports
+use num::BigUint;
+// Workspace imports
+use zksync_crypto::proof::EncodedSingleProof;
+use zksync_types::{
+    exit_proof::{ExitProofData, StoredBlockInfo},
+    AccountId, Address, BlockNumber, TokenId, H256,
+};
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn proof_data(
+    account_id: AccountId,
+    token_id: TokenId,
+    block_number: BlockNumber,
+) -> ExitProofData {
+    ExitProofData {
+        stored_block_info: StoredBlockInfo {
+            block_number,
+            priority_operations: 0,
+            pending_onchain_operations_hash: H256::zero(),
+            timestamp: 0,
+            state_hash: H256::zero(),
+            commitment: H256::zero(),
+        },
+        owner: Address::from_low_u64_be(1),
+        account_id,
+        token_id,
+        amount: BigUint::from(100u32),
+        nft_creator_id: AccountId(0),
+        nft_creator_address: Address::zero(),
+        nft_serial_id: 0,
+        nft_content_hash: H256::zero(),
+        proof: EncodedSingleProof::default(),
+        token_address: Address::zero(),
+    }
+}
+
+/// Checks the lifecycle of the exit proof request.
+#[db_test]
+async fn test_exit_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (account_id, token_id) = (AccountId(1), TokenId(0));
+
+    storage
+        .exit_proofs_schema()
+        .request_exit_proof(account_id, token_id, BlockNumber(1))
+        .await?;
+    let request = storage
+        .exit_proofs_schema()
+        .take_pending_exit_proof()
+        .await?
+        .expect("request is not stored");
+    assert_eq!(request.account_id, 1);
+    assert!(request.started_at.is_some());
+    // The request is taken already.
+    assert!(storage
+        .exit_proofs_schema()
+        .take_pending_exit_proof()
+        .await?
+        .is_none());
+
+    // Failed requests are restarted on the next request.
+    storage
+        .exit_proofs_schema()
+        .store_exit_proof_error(account_id, token_id, "failed")
+        .await?;
+    let request = storage
+        .exit_proofs_schema()
+        .get_exit_proof(account_id, token_id)
+        .await?
+        .unwrap();
+    assert_eq!(request.error.as_deref(), Some("failed"));
+    storage
+        .exit_proofs_schema()
+        .request_exit_proof(account_id, token_id, BlockNumber(1))
+        .await?;
+    assert!(storage
+        .exit_proofs_schema()
+        .take_pending_exit_proof()
+        .await?
+        .is_some());
+
+    let data = proof_data(account_id, token_id, BlockNumber(1));
+    storage.exit_proofs_schema().store_exit_proof(&data).await?;
+    let request = storage
+        .exit_proofs_schema()
+        .get_exit_proof(account_id, token_id)
+        .await?
+        .unwrap();
+    assert!(request.error.is_none());
+    assert_eq!(request.block_number, Some(1));
+    let stored_data: ExitProofData = serde_json::from_value(request.proof_data.unwrap()).unwrap();
+    assert_eq!(stored_data, data);
+
+    // The proof is regenerated only once a new block is verified.
+    storage
+        .exit_proofs_schema()
+        .request_exit_proof(account_id, token_id, BlockNumber(1))
+        .await?;
+    assert!(storage
+        .exit_proofs_schema()
+        .take_pending_exit_proof()
+        .await?
+        .is_none());
+    storage
+        .exit_proofs_schema()
+        .request_exit_proof(account_id, token_id, BlockNumber(2))
+        .await?;
+    let request = storage
+        .exit_proofs_schema()
+        .take_pending_exit_proof()
+        .await?
+        .unwrap();
+    assert!(request.proof_data.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+// External imports
+use num::BigUint;
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{tx::TxHash, TokenId};
+// Local imports
+use crate::fee_breakdowns::records::TxFeeQuote;
+use crate::tests::{db_test, tx_hash};
+use crate::{QueryResult, StorageProcessor};
+
+fn quote(tx_hash: TxHash, zkp_fee: u64, gas_fee: u64, subsidy: u64) -> TxFeeQuote {
+    TxFeeQuote {
+        tx_hash,
+        token_id: TokenId(1),
+        zkp_fee: BigDecimal::from(zkp_fee),
+        gas_fee: BigDecimal::from(gas_fee),
+        subsidy: BigDecimal::from(subsidy),
+    }
+}
+
+/// Checks that the charged fees are split according to the stored quotes.
+#[db_test]
+async fn fee_breakdowns(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    storage
+        .fee_breakdowns_schema()
+        .store_fee_quotes(&[quote(tx_hash(1), 100, 300, 0), quote(tx_hash(2), 0, 0, 50)])
+        .await?;
+
+    let breakdown = storage
+        .fee_breakdowns_schema()
+        .get_fee_breakdown(tx_hash(1))
+        .await?
+        .unwrap();
+    assert_eq!(breakdown.quoted_zkp_fee, BigDecimal::from(100));
+    assert_eq!(breakdown.charged_fee, None);
+
+    // The transaction without the quote is skipped.
+    storage
+        .fee_breakdowns_schema()
+        .store_charged_fees(&[
+            (tx_hash(1), BigUint::from(1001u32)),
+            (tx_hash(2), BigUint::from(10u32)),
+            (tx_hash(3), BigUint::from(10u32)),
+        ])
+        .await?;
+
+    let breakdown = storage
+        .fee_breakdowns_schema()
+        .get_fee_breakdown(tx_hash(1))
+        .await?
+        .unwrap();
+    assert_eq!(breakdown.charged_fee, Some(BigDecimal::from(1001)));
+    assert_eq!(breakdown.zkp_fee, Some(BigDecimal::from(250)));
+    assert_eq!(breakdown.gas_fee, Some(BigDecimal::from(751)));
+    assert!(breakdown.executed_at.is_some());
+
+    let breakdown = storage
+        .fee_breakdowns_schema()
+        .get_fee_breakdown(tx_hash(2))
+        .await?
+        .unwrap();
+    assert_eq!(breakdown.zkp_fee, Some(BigDecimal::from(0)));
+    assert_eq!(breakdown.gas_fee, Some(BigDecimal::from(10)));
+    assert_eq!(breakdown.subsidy, BigDecimal::from(50));
+
+    assert!(storage
+        .fee_breakdowns_schema()
+        .get_fee_breakdown(tx_hash(3))
+        .await?
+        .is_none());
+
+    // The quote of the executed transaction is never replaced.
+    storage
+        .fee_breakdowns_schema()
+        .store_fee_quotes(&[quote(tx_hash(1), 1, 1, 1)])
+        .await?;
+    let breakdown = storage
+        .fee_breakdowns_schema()
+        .get_fee_breakdown(tx_hash(1))
+        .await?
+        .unwrap();
+    assert_eq!(breakdown.quoted_zkp_fee, BigDecimal::from(100));
+
+    Ok(())
+}
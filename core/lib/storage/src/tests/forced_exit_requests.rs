@@ -12,7 +12,7 @@ use num::{BigUint, FromPrimitive};
 use zksync_types::{
     forced_exit_requests::{ForcedExitRequest, SaveForcedExitRequestQuery},
     tx::TxHash,
-    Address,
+    Address, H256,
 };
 
 use std::ops::Add;
@@ -49,6 +49,7 @@ async fn get_oldest_unfulfilled_request(mut storage: StorageProcessor<'_>) -> Qu
             price_in_wei: BigUint::from_i32(212).unwrap(),
             created_at: now,
             valid_until: now,
+            refund_address: None,
         },
         SaveForcedExitRequestQuery {
             target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
@@ -56,6 +57,7 @@ async fn get_oldest_unfulfilled_request(mut storage: StorageProcessor<'_>) -> Qu
             price_in_wei: BigUint::from_i32(1).unwrap(),
             created_at: now,
             valid_until: now,
+            refund_address: None,
         },
         SaveForcedExitRequestQuery {
             target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
@@ -63,6 +65,7 @@ async fn get_oldest_unfulfilled_request(mut storage: StorageProcessor<'_>) -> Qu
             price_in_wei: BigUint::from_str("1000000000000000").unwrap(),
             created_at: now,
             valid_until: now,
+            refund_address: None,
         },
     ];
 
@@ -134,6 +137,7 @@ async fn delete_old_requests(mut storage: StorageProcessor<'_>) -> QueryResult<(
             created_at: now.sub(day.mul(8)),
             // Invalid for 6 days => should be deleted
             valid_until: now.sub(day.mul(6)),
+            refund_address: None,
         },
         SaveForcedExitRequestQuery {
             target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
@@ -142,6 +146,7 @@ async fn delete_old_requests(mut storage: StorageProcessor<'_>) -> QueryResult<(
             created_at: now.sub(day.mul(5)).sub(minute),
             // Invalid for 3 days and 1 minutes => should be deleted
             valid_until: now.sub(day.mul(3)).sub(minute),
+            refund_address: None,
         },
         SaveForcedExitRequestQuery {
             target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
@@ -150,6 +155,7 @@ async fn delete_old_requests(mut storage: StorageProcessor<'_>) -> QueryResult<(
             created_at: now.sub(day.mul(5)).add(minute.mul(5)),
             // Invalid for 3 days minus 5 minutes => should not be deleted
             valid_until: now.sub(day.mul(3)).add(minute.mul(5)),
+            refund_address: None,
         },
         SaveForcedExitRequestQuery {
             target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
@@ -158,6 +164,7 @@ async fn delete_old_requests(mut storage: StorageProcessor<'_>) -> QueryResult<(
             created_at: now.sub(day.mul(5)).add(minute.mul(5)),
             // Is valid => should not be deleted
             valid_until: now.sub(day.mul(3)).add(minute.mul(5)),
+            refund_address: None,
         },
     ];
 
@@ -201,3 +208,120 @@ async fn delete_old_requests(mut storage: StorageProcessor<'_>) -> QueryResult<(
 
     Ok(())
 }
+
+// Checks that the refunds are stored and returned as pending until they are completed
+#[db_test]
+async fn refunds(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now().with_nanosecond(0).unwrap();
+
+    let refund_address = Address::random();
+    let request = SaveForcedExitRequestQuery {
+        target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
+        tokens: vec![TokenId(1), TokenId(2)],
+        price_in_wei: BigUint::from_i32(212).unwrap(),
+        created_at: now,
+        valid_until: now.add(Duration::days(1)),
+        refund_address: Some(refund_address),
+    };
+    let stored_requests = store_requests(&mut storage, vec![request.clone(), request]).await;
+    // The refund address is set on the request creation, the refund itself on the fulfillment
+    assert_eq!(stored_requests[0].refund_address, Some(refund_address));
+    assert!(stored_requests[0].refund.is_none());
+
+    for request in &stored_requests {
+        ForcedExitRequestsSchema(&mut storage)
+            .set_refund(request.id, refund_address, BigUint::from_i32(100).unwrap())
+            .await?;
+    }
+
+    // Refunds are pending only for the fulfilled requests
+    let pending_refunds = ForcedExitRequestsSchema(&mut storage)
+        .get_pending_refunds()
+        .await?;
+    assert!(pending_refunds.is_empty());
+
+    ForcedExitRequestsSchema(&mut storage)
+        .set_fulfilled_at(stored_requests[0].id, now)
+        .await?;
+    let pending_refunds = ForcedExitRequestsSchema(&mut storage)
+        .get_pending_refunds()
+        .await?;
+    assert_eq!(pending_refunds.len(), 1);
+    assert_eq!(pending_refunds[0].id, stored_requests[0].id);
+
+    let transaction_hash = TxHash::from_str(
+        "sync-tx:796018689b3e323894f44fb0093856ec3832908c626dea357a9bd1b25f9d11bf",
+    )
+    .unwrap();
+    ForcedExitRequestsSchema(&mut storage)
+        .set_refunded_by(stored_requests[0].id, Some(transaction_hash))
+        .await?;
+    let refund = ForcedExitRequestsSchema(&mut storage)
+        .get_request_by_id(stored_requests[0].id)
+        .await?
+        .unwrap()
+        .refund
+        .unwrap();
+    assert_eq!(refund.address, refund_address);
+    assert_eq!(refund.amount, BigUint::from_i32(100).unwrap());
+    assert_eq!(refund.refunded_by, Some(transaction_hash));
+    assert!(refund.refunded_at.is_none());
+
+    ForcedExitRequestsSchema(&mut storage)
+        .set_refunded_at(stored_requests[0].id, now)
+        .await?;
+    let pending_refunds = ForcedExitRequestsSchema(&mut storage)
+        .get_pending_refunds()
+        .await?;
+    assert!(pending_refunds.is_empty());
+
+    Ok(())
+}
+
+// Checks that the payment of the request is stored
+#[db_test]
+async fn payment(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now().with_nanosecond(0).unwrap();
+
+    let request = SaveForcedExitRequestQuery {
+        target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
+        tokens: vec![TokenId(1)],
+        price_in_wei: BigUint::from_i32(212).unwrap(),
+        created_at: now,
+        valid_until: now.add(Duration::days(1)),
+        refund_address: None,
+    };
+    let stored_request = store_requests(&mut storage, vec![request]).await.remove(0);
+    assert!(stored_request.payment_tx_hash.is_none());
+
+    let payment_tx_hash = H256::random();
+    ForcedExitRequestsSchema(&mut storage)
+        .set_paid(stored_request.id, payment_tx_hash, now)
+        .await?;
+
+    let stored_request = ForcedExitRequestsSchema(&mut storage)
+        .get_request_by_id(stored_request.id)
+        .await?
+        .unwrap();
+    assert_eq!(stored_request.payment_tx_hash, Some(payment_tx_hash));
+    assert_eq!(stored_request.paid_at, Some(now));
+
+    Ok(())
+}
+
+// Checks the backlog of the request which is not included into a block yet
+#[db_test]
+async fn processing_backlog(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let backlog = ForcedExitRequestsSchema(&mut storage)
+        .get_processing_backlog(None)
+        .await?;
+
+    // There are no pending jobs and operations, but the block of the request
+    // has to be committed, proven and executed.
+    assert_eq!(backlog.pending_prover_jobs, 0);
+    assert_eq!(backlog.pending_eth_operations, 3);
+    assert_eq!(backlog.prover_jobs_done_last_hour, 0);
+    assert_eq!(backlog.eth_operations_confirmed_last_hour, 0);
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+// External imports
+// Workspace imports
+use zksync_types::BlockNumber;
+// Local imports
+use crate::history::HistorySchema;
+use crate::tests::{
+    chain::{commit_schema_data, TransactionsHistoryTestSetup},
+    db_test, ACCOUNT_MUTEX,
+};
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the executed transactions are moved to the history database and loaded from it.
+/// The test database plays the role of both databases, since they have the same schema.
+#[db_test]
+async fn test_move_transactions_to_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    // Only the transactions of the first block are loaded.
+    let transactions = HistorySchema(&mut storage)
+        .load_transactions_to_move(BlockNumber(1), 100)
+        .await?;
+    assert_eq!(transactions.len(), 3);
+    assert!(transactions
+        .iter()
+        .all(|tx| tx.row["block_number"] == serde_json::json!(1)));
+
+    // Batches are limited.
+    let batch = HistorySchema(&mut storage)
+        .load_transactions_to_move(BlockNumber(2), 2)
+        .await?;
+    assert_eq!(batch.len(), 2);
+
+    let tx_hashes: Vec<_> = transactions.iter().map(|tx| tx.tx_hash.clone()).collect();
+    let removed = HistorySchema(&mut storage)
+        .remove_moved_transactions(&tx_hashes)
+        .await?;
+    assert_eq!(removed, 3);
+    assert!(HistorySchema(&mut storage)
+        .tx_receipt(&tx_hashes[0])
+        .await?
+        .is_none());
+
+    // Storing the batch twice doesn't fail, so an interrupted move can be repeated.
+    HistorySchema(&mut storage)
+        .store_transactions(&transactions)
+        .await?;
+    HistorySchema(&mut storage)
+        .store_transactions(&transactions)
+        .await?;
+
+    let receipt = HistorySchema(&mut storage)
+        .tx_receipt(&tx_hashes[0])
+        .await?
+        .expect("Transaction is stored");
+    assert_eq!(receipt.block_number, Some(1));
+
+    let data = HistorySchema(&mut storage)
+        .tx_data(&tx_hashes[0])
+        .await?
+        .expect("Transaction is stored");
+    let block_index = data.block_index.expect("Transaction is executed") as u64;
+    let data_by_index = HistorySchema(&mut storage)
+        .tx_data_by_block_and_index(BlockNumber(1), block_index)
+        .await?;
+    assert_eq!(data_by_index, Some(data));
+
+    // Sequence numbers are preserved.
+    let sequence_numbers: Vec<i64> = transactions
+        .iter()
+        .map(|tx| tx.row["sequence_number"].as_i64().unwrap())
+        .collect();
+    let txs = HistorySchema(&mut storage)
+        .txs_by_sequence_numbers(&sequence_numbers)
+        .await?;
+    assert_eq!(txs.len(), 3);
+
+    Ok(())
+}
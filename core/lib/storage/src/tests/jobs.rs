@@ -0,0 +1,84 @@
+// Built-in imports
+use std::time::Duration;
+// External imports
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the job lock is held by a single session at a time.
+#[db_test]
+async fn test_job_locks(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut other_storage = StorageProcessor::establish_connection().await?;
+
+    assert!(storage.jobs_schema().try_lock_job("test_job").await?);
+    assert!(!other_storage.jobs_schema().try_lock_job("test_job").await?);
+    // Locks of the different jobs are independent.
+    assert!(
+        other_storage
+            .jobs_schema()
+            .try_lock_job("other_job")
+            .await?
+    );
+
+    storage.jobs_schema().unlock_job("test_job").await?;
+    assert!(other_storage.jobs_schema().try_lock_job("test_job").await?);
+
+    other_storage.jobs_schema().unlock_job("test_job").await?;
+    other_storage.jobs_schema().unlock_job("other_job").await?;
+    Ok(())
+}
+
+/// Checks the bookkeeping of the job runs.
+#[db_test]
+async fn test_job_runs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(storage.jobs_schema().load_job("pruner").await?.is_none());
+
+    let interval = Duration::from_secs(60);
+    storage
+        .jobs_schema()
+        .start_job_run("pruner", interval)
+        .await?;
+    let job = storage.jobs_schema().load_job("pruner").await?.unwrap();
+    assert_eq!(job.interval_secs, 60);
+    assert!(job.last_started_at.is_some());
+    assert!(job.last_finished_at.is_none());
+    assert_eq!(job.runs_count, 0);
+
+    storage
+        .jobs_schema()
+        .finish_job_run("pruner", Some("database is unavailable"))
+        .await?;
+    storage
+        .jobs_schema()
+        .start_job_run("pruner", interval)
+        .await?;
+    storage
+        .jobs_schema()
+        .finish_job_run("pruner", Some("database is unavailable"))
+        .await?;
+    let job = storage.jobs_schema().load_job("pruner").await?.unwrap();
+    assert_eq!(job.last_error.as_deref(), Some("database is unavailable"));
+    assert_eq!(job.consecutive_failures, 2);
+    assert_eq!(job.runs_count, 2);
+
+    // A successful run resets the failures.
+    storage
+        .jobs_schema()
+        .start_job_run("pruner", interval)
+        .await?;
+    storage.jobs_schema().finish_job_run("pruner", None).await?;
+    storage
+        .jobs_schema()
+        .start_job_run("events_cleaner", interval)
+        .await?;
+    let jobs = storage.jobs_schema().load_jobs().await?;
+    assert_eq!(jobs.len(), 2);
+    assert_eq!(jobs[0].name, "events_cleaner");
+    assert_eq!(jobs[1].name, "pruner");
+    assert_eq!(jobs[1].last_error, None);
+    assert_eq!(jobs[1].consecutive_failures, 0);
+    assert_eq!(jobs[1].runs_count, 3);
+    assert!(jobs[1].last_finished_at.is_some());
+    Ok(())
+}
@@ -0,0 +1,139 @@
+// External imports
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use crate::labels::records::AddressLabel;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn label(address: Address, label: &str, scope: Option<&str>) -> AddressLabel {
+    AddressLabel {
+        address,
+        label: label.to_string(),
+        category: String::from("userDefined"),
+        scope: scope.map(String::from),
+    }
+}
+
+/// Checks that the scoped labels are visible only in their scope.
+#[db_test]
+async fn test_address_labels(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::from_low_u64_be(1);
+    let other_address = Address::from_low_u64_be(2);
+
+    assert!(
+        storage
+            .labels_schema()
+            .store_label(&label(address, "Exchange", None))
+            .await?
+    );
+    assert!(
+        storage
+            .labels_schema()
+            .store_label(&label(address, "Exchange", Some("alice")))
+            .await?
+    );
+    assert!(
+        storage
+            .labels_schema()
+            .store_label(&label(address, "Friend", Some("bob")))
+            .await?
+    );
+    assert!(
+        storage
+            .labels_schema()
+            .store_label(&label(other_address, "Contract", None))
+            .await?
+    );
+    // Duplicates are not stored, including the operator's ones.
+    assert!(
+        !storage
+            .labels_schema()
+            .store_label(&label(address, "Exchange", None))
+            .await?
+    );
+
+    let labels = storage
+        .labels_schema()
+        .load_labels(&[address], Some("alice"))
+        .await?;
+    assert_eq!(labels.len(), 2);
+    assert_eq!(labels[0].scope, None);
+    assert_eq!(labels[1].scope.as_deref(), Some("alice"));
+
+    let labels = storage
+        .labels_schema()
+        .load_labels(&[address, other_address], None)
+        .await?;
+    assert_eq!(labels.len(), 2);
+    assert!(labels.iter().all(|label| label.scope.is_none()));
+
+    assert!(
+        storage
+            .labels_schema()
+            .remove_label(address, "Exchange", None)
+            .await?
+    );
+    assert!(
+        !storage
+            .labels_schema()
+            .remove_label(address, "Friend", Some("alice"))
+            .await?
+    );
+    let labels = storage
+        .labels_schema()
+        .load_labels(&[address], Some("bob"))
+        .await?;
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].label, "Friend");
+    Ok(())
+}
+
+/// Checks the issuance and revocation of the API keys.
+#[db_test]
+async fn test_label_api_keys(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    storage
+        .labels_schema()
+        .store_api_key(&[1; 32], "alice")
+        .await?;
+    storage
+        .labels_schema()
+        .store_api_key(&[2; 32], "alice")
+        .await?;
+
+    let scope = storage.labels_schema().get_api_key_scope(&[1; 32]).await?;
+    assert_eq!(scope.as_deref(), Some("alice"));
+    let scope = storage.labels_schema().get_api_key_scope(&[3; 32]).await?;
+    assert_eq!(scope, None);
+
+    let max_page_size = storage
+        .labels_schema()
+        .get_api_key_max_page_size(&[1; 32])
+        .await?;
+    assert_eq!(max_page_size, None);
+    let updated = storage
+        .labels_schema()
+        .set_api_keys_max_page_size("alice", Some(500))
+        .await?;
+    assert_eq!(updated, 2);
+    let max_page_size = storage
+        .labels_schema()
+        .get_api_key_max_page_size(&[2; 32])
+        .await?;
+    assert_eq!(max_page_size, Some(500));
+    storage
+        .labels_schema()
+        .set_api_keys_max_page_size("alice", None)
+        .await?;
+    let max_page_size = storage
+        .labels_schema()
+        .get_api_key_max_page_size(&[2; 32])
+        .await?;
+    assert_eq!(max_page_size, None);
+
+    let removed = storage.labels_schema().remove_api_keys("alice").await?;
+    assert_eq!(removed, 2);
+    let scope = storage.labels_schema().get_api_key_scope(&[2; 32]).await?;
+    assert_eq!(scope, None);
+    Ok(())
+}
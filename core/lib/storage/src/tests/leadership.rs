@@ -0,0 +1,65 @@
+// External imports
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the leadership is held by a single session at a time.
+#[db_test]
+async fn test_leadership_lock(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut standby_storage = StorageProcessor::establish_connection().await?;
+
+    assert!(storage.leadership_schema().try_acquire_leadership().await?);
+    assert!(
+        !standby_storage
+            .leadership_schema()
+            .try_acquire_leadership()
+            .await?
+    );
+    // The leadership lock doesn't collide with the locks of the jobs.
+    assert!(
+        standby_storage
+            .jobs_schema()
+            .try_lock_job("core_leader")
+            .await?
+    );
+    standby_storage
+        .jobs_schema()
+        .unlock_job("core_leader")
+        .await?;
+    storage.leadership_schema().ping().await?;
+
+    storage.leadership_schema().release_leadership().await?;
+    assert!(
+        standby_storage
+            .leadership_schema()
+            .try_acquire_leadership()
+            .await?
+    );
+    standby_storage
+        .leadership_schema()
+        .release_leadership()
+        .await?;
+    Ok(())
+}
+
+/// Checks the history of the leadership changes.
+#[db_test]
+async fn test_leadership_changes(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for instance_id in &["core-1", "core-2", "core-1"] {
+        storage
+            .leadership_schema()
+            .record_leadership_change(instance_id)
+            .await?;
+    }
+
+    let changes = storage
+        .leadership_schema()
+        .load_leadership_changes(2)
+        .await?;
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].instance_id, "core-1");
+    assert_eq!(changes[1].instance_id, "core-2");
+    assert!(changes[0].id > changes[1].id);
+    Ok(())
+}
@@ -0,0 +1,151 @@
+// External imports
+use num::BigUint;
+use serde_json::json;
+// Workspace imports
+use zksync_types::{tx::TxHash, Address, Nonce, TokenId};
+// Local imports
+use crate::mass_payouts::{
+    RECIPIENT_STATUS_EXECUTED, RECIPIENT_STATUS_FAILED, RECIPIENT_STATUS_PENDING,
+    RECIPIENT_STATUS_SUBMITTED,
+};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the statuses of the recipients follow their batches.
+#[db_test]
+async fn test_mass_payouts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let sender = Address::repeat_byte(0x01);
+    let recipients: Vec<_> = (0..4u8)
+        .map(|i| {
+            (
+                Address::repeat_byte(0x10 + i),
+                TokenId(i as u32 % 2),
+                BigUint::from(1000u32 + i as u32),
+            )
+        })
+        .collect();
+    let payout_id = storage
+        .mass_payouts_schema()
+        .create_payout("airdrop", sender, &recipients)
+        .await?;
+    let payout = storage
+        .mass_payouts_schema()
+        .get_payout("airdrop")
+        .await?
+        .expect("payout should be stored");
+    assert_eq!(payout.id, payout_id);
+    assert_eq!(payout.sender, sender.as_bytes().to_vec());
+    assert!(storage
+        .mass_payouts_schema()
+        .get_payout("unknown")
+        .await?
+        .is_none());
+
+    let stored = storage
+        .mass_payouts_schema()
+        .load_recipients(payout_id)
+        .await?;
+    assert_eq!(stored.len(), 4);
+    for (idx, recipient) in stored.iter().enumerate() {
+        assert_eq!(recipient.idx, idx as i32);
+        assert_eq!(recipient.address, recipients[idx].0.as_bytes().to_vec());
+        assert_eq!(recipient.token_id, *recipients[idx].1 as i32);
+        assert_eq!(recipient.amount.to_string(), recipients[idx].2.to_string());
+        assert_eq!(recipient.status, RECIPIENT_STATUS_PENDING);
+    }
+
+    let tx_hash = |byte: u8| TxHash::from_slice(&[byte; 32]).unwrap();
+    let first_batch = storage
+        .mass_payouts_schema()
+        .store_batch(
+            payout_id,
+            Nonce(5),
+            &json!(["tx0", "tx1"]),
+            None,
+            &[(0, tx_hash(0)), (1, tx_hash(1))],
+        )
+        .await?;
+    let second_batch = storage
+        .mass_payouts_schema()
+        .store_batch(
+            payout_id,
+            Nonce(8),
+            &json!(["tx2", "tx3"]),
+            Some(&json!("signature")),
+            &[(2, tx_hash(2)), (3, tx_hash(3))],
+        )
+        .await?;
+    let batch = storage
+        .mass_payouts_schema()
+        .get_batch(second_batch)
+        .await?
+        .expect("batch should be stored");
+    assert_eq!(batch.nonce, 8);
+    assert_eq!(batch.eth_signature, Some(json!("signature")));
+
+    let stored = storage
+        .mass_payouts_schema()
+        .load_recipients(payout_id)
+        .await?;
+    assert!(stored
+        .iter()
+        .all(|recipient| recipient.status == RECIPIENT_STATUS_SUBMITTED));
+    assert_eq!(stored[1].batch_id, Some(first_batch));
+    assert_eq!(stored[1].tx_hash, Some(vec![1; 32]));
+
+    // The first batch is executed, the second one is replaced by another transaction.
+    storage
+        .mass_payouts_schema()
+        .complete_batch(first_batch, None)
+        .await?;
+    storage
+        .mass_payouts_schema()
+        .discard_batch(second_batch, "nonce is used")
+        .await?;
+    let stored = storage
+        .mass_payouts_schema()
+        .load_recipients(payout_id)
+        .await?;
+    assert_eq!(stored[0].status, RECIPIENT_STATUS_EXECUTED);
+    assert_eq!(stored[2].status, RECIPIENT_STATUS_PENDING);
+    assert_eq!(stored[2].batch_id, None);
+    assert_eq!(stored[2].tx_hash, None);
+
+    // Resubmitted recipients fail and are retried.
+    let third_batch = storage
+        .mass_payouts_schema()
+        .store_batch(
+            payout_id,
+            Nonce(7),
+            &json!(["tx2", "tx3"]),
+            None,
+            &[(2, tx_hash(4)), (3, tx_hash(5))],
+        )
+        .await?;
+    storage
+        .mass_payouts_schema()
+        .complete_batch(third_batch, Some("not enough balance"))
+        .await?;
+    let stored = storage
+        .mass_payouts_schema()
+        .load_recipients(payout_id)
+        .await?;
+    assert_eq!(stored[3].status, RECIPIENT_STATUS_FAILED);
+    assert_eq!(stored[3].error.as_deref(), Some("not enough balance"));
+
+    assert_eq!(
+        storage
+            .mass_payouts_schema()
+            .retry_failed_recipients(payout_id)
+            .await?,
+        2
+    );
+    let stored = storage
+        .mass_payouts_schema()
+        .load_recipients(payout_id)
+        .await?;
+    assert_eq!(stored[0].status, RECIPIENT_STATUS_EXECUTED);
+    assert_eq!(stored[3].status, RECIPIENT_STATUS_PENDING);
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+// Built-in imports
+use std::fs;
+// External imports
+// Workspace imports
+use zksync_types::{tx::Transfer, AccountId, Address, Nonce, SignedZkSyncTx, TokenId, ZkSyncTx};
+// Local imports
+use crate::canonical_json::canonical_tx;
+use crate::chain::mempool::MempoolSchema;
+use crate::migrations::{
+    backfill::{Backfill, CanonicalTxsBackfill, StoredTxsTable, TxsCountBackfill},
+    LockImpact, Migration, MigrationsSchema,
+};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the pending migrations are reported along with the locks they take
+/// and the changes made by them are rolled back.
+#[db_test]
+async fn test_migrations_dry_run(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let dir = std::env::temp_dir().join("zksync_migrations_dry_run");
+    let migrations = [
+        (
+            "2099-01-01-000000_new-table",
+            "CREATE TABLE dry_run_table (id BIGINT PRIMARY KEY);",
+        ),
+        (
+            "2099-01-01-000001_new-column",
+            "ALTER TABLE tx_filters ADD COLUMN dry_run_column BIGINT;
+            CREATE INDEX dry_run_idx ON tx_filters (dry_run_column);",
+        ),
+        ("2099-01-01-000002_failing", "SELECT * FROM missing_table;"),
+        ("2099-01-01-000003_skipped", "SELECT 1;"),
+    ];
+    for (name, up_sql) in migrations.iter() {
+        fs::create_dir_all(dir.join(name))?;
+        fs::write(dir.join(name).join("up.sql"), up_sql)?;
+    }
+    let migrations = Migration::load_dir(&dir)?;
+    fs::remove_dir_all(&dir)?;
+    assert_eq!(migrations.len(), 4);
+    assert_eq!(migrations[0].version, "20990101000000");
+
+    let reports = MigrationsSchema(&mut storage).dry_run(&migrations).await?;
+    assert_eq!(reports.len(), 3);
+
+    assert_eq!(reports[0].name, "2099-01-01-000000_new-table");
+    assert!(reports[0].error.is_none());
+    assert_eq!(reports[0].impact(), LockImpact::BlocksReads);
+    assert!(reports[0]
+        .locks
+        .iter()
+        .any(|lock| lock.relation == "dry_run_table"));
+
+    assert!(reports[1].error.is_none());
+    assert_eq!(reports[1].impact(), LockImpact::BlocksReads);
+    assert!(reports[1]
+        .locks
+        .iter()
+        .any(|lock| lock.relation == "tx_filters" && lock.mode == "AccessExclusiveLock"));
+    // Locks taken by the previous migrations are not reported again.
+    assert!(reports[1]
+        .locks
+        .iter()
+        .all(|lock| lock.relation != "dry_run_table"));
+
+    assert!(reports[2].error.as_ref().unwrap().contains("missing_table"));
+    assert!(reports[2].locks.is_empty());
+
+    // Changes are rolled back.
+    let columns: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM information_schema.columns WHERE column_name = 'dry_run_column'",
+    )
+    .fetch_one(storage.conn())
+    .await?;
+    assert_eq!(columns, 0);
+    Ok(())
+}
+
+/// Checks that the `txs_count` backfill processes the accounts in batches
+/// and its progress is stored.
+#[db_test]
+async fn test_txs_count_backfill(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let rows = [(1u8, 0, 1u8), (1, 0, 2), (1, 1, 2), (2, 0, 3), (3, 1, 4)];
+    for (address, token, tx_hash) in rows.iter().copied() {
+        sqlx::query("INSERT INTO tx_filters (address, token, tx_hash) VALUES ($1, $2, $3)")
+            .bind(vec![address; 20])
+            .bind(token)
+            .bind(vec![tx_hash; 32])
+            .execute(storage.conn())
+            .await?;
+    }
+    // Counters are maintained by the triggers, so they have to be removed to be backfilled.
+    sqlx::query("DELETE FROM txs_count")
+        .execute(storage.conn())
+        .await?;
+
+    let backfill = TxsCountBackfill;
+    MigrationsSchema(&mut storage)
+        .start_backfill(backfill.name(), Some(3))
+        .await?;
+
+    let batch = backfill.process_batch(&mut storage, None, 2).await?;
+    assert_eq!(batch.processed_rows, 2);
+    assert_eq!(batch.next_cursor, Some(hex::encode([2u8; 20])));
+    MigrationsSchema(&mut storage)
+        .save_backfill_batch(backfill.name(), batch.next_cursor.as_deref(), 2)
+        .await?;
+
+    let batch = backfill
+        .process_batch(&mut storage, batch.next_cursor.as_deref(), 2)
+        .await?;
+    assert_eq!(batch.processed_rows, 1);
+    assert_eq!(batch.next_cursor, Some(hex::encode([3u8; 20])));
+    let cursor = batch.next_cursor.unwrap();
+
+    let batch = backfill
+        .process_batch(&mut storage, Some(&cursor), 2)
+        .await?;
+    assert_eq!(batch.next_cursor, None);
+    MigrationsSchema(&mut storage)
+        .save_backfill_batch(backfill.name(), None, 1)
+        .await?;
+
+    let counts: Vec<(Vec<u8>, i32, i64)> =
+        sqlx::query_as("SELECT address, token, count FROM txs_count ORDER BY address, token")
+            .fetch_all(storage.conn())
+            .await?;
+    let expected = vec![
+        (vec![1; 20], -1, 2),
+        (vec![1; 20], 0, 2),
+        (vec![1; 20], 1, 1),
+        (vec![2; 20], -1, 1),
+        (vec![2; 20], 0, 1),
+        (vec![3; 20], -1, 1),
+        (vec![3; 20], 1, 1),
+    ];
+    assert_eq!(counts, expected);
+
+    let progress = MigrationsSchema(&mut storage)
+        .load_backfill_progress(backfill.name())
+        .await?
+        .unwrap();
+    assert_eq!(progress.processed_rows, 3);
+    assert_eq!(progress.estimated_rows, Some(3));
+    // The cursor of the last batch is kept.
+    assert_eq!(progress.cursor, Some(hex::encode([2u8; 20])));
+    assert!(progress.finished_at.is_some());
+
+    let all_progress = MigrationsSchema(&mut storage)
+        .load_backfills_progress()
+        .await?;
+    assert_eq!(all_progress.len(), 1);
+    Ok(())
+}
+
+/// Checks that the transactions stored in the legacy format are rewritten to the canonical one.
+#[db_test]
+async fn test_canonical_txs_backfill(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let transfer = Transfer::new(
+        AccountId(1),
+        Address::repeat_byte(1),
+        Address::repeat_byte(2),
+        TokenId(0),
+        100u32.into(),
+        10u32.into(),
+        Nonce(0),
+        Default::default(),
+        None,
+    );
+    let tx = ZkSyncTx::Transfer(Box::new(transfer));
+    MempoolSchema(&mut storage)
+        .insert_tx(&SignedZkSyncTx::from(tx.clone()))
+        .await?;
+    // Field removed from the transaction format since the row was stored.
+    sqlx::query("UPDATE mempool_txs SET tx = tx || '{\"legacyField\": 1}'")
+        .execute(storage.conn())
+        .await?;
+
+    let backfill = CanonicalTxsBackfill(StoredTxsTable::MempoolTxs);
+    let batch = backfill.process_batch(&mut storage, None, 10).await?;
+    assert_eq!(batch.processed_rows, 1);
+    let cursor = batch.next_cursor.unwrap();
+
+    let stored: serde_json::Value = sqlx::query_scalar("SELECT tx FROM mempool_txs")
+        .fetch_one(storage.conn())
+        .await?;
+    assert_eq!(stored, canonical_tx(&tx));
+
+    let batch = backfill
+        .process_batch(&mut storage, Some(&cursor), 10)
+        .await?;
+    assert_eq!(batch.processed_rows, 0);
+    assert_eq!(batch.next_cursor, None);
+    Ok(())
+}
@@ -54,3 +54,44 @@ async fn stored_subsidy(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
 
     Ok(())
 }
+
+/// Checks that the maintenance mode can be enabled and disabled.
+#[db_test]
+async fn maintenance_mode(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert_eq!(
+        MiscSchema(&mut storage).load_maintenance_mode().await?,
+        None
+    );
+    assert!(!MiscSchema(&mut storage).disable_maintenance_mode().await?);
+
+    MiscSchema(&mut storage)
+        .enable_maintenance_mode("database upgrade")
+        .await?;
+    let maintenance_mode = MiscSchema(&mut storage)
+        .load_maintenance_mode()
+        .await?
+        .expect("Maintenance mode must be enabled");
+    assert_eq!(maintenance_mode.reason, "database upgrade");
+
+    // Enabling it again only changes the reason.
+    MiscSchema(&mut storage)
+        .enable_maintenance_mode("prover upgrade")
+        .await?;
+    let updated_maintenance_mode = MiscSchema(&mut storage)
+        .load_maintenance_mode()
+        .await?
+        .expect("Maintenance mode must be enabled");
+    assert_eq!(updated_maintenance_mode.reason, "prover upgrade");
+    assert_eq!(
+        updated_maintenance_mode.enabled_at,
+        maintenance_mode.enabled_at
+    );
+
+    assert!(MiscSchema(&mut storage).disable_maintenance_mode().await?);
+    assert_eq!(
+        MiscSchema(&mut storage).load_maintenance_mode().await?,
+        None
+    );
+
+    Ok(())
+}
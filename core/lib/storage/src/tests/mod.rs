@@ -18,20 +18,61 @@
 //! The file hierarchy is designed to mirror the actual project structure.
 
 // External imports
+use chrono::Utc;
 use once_cell::sync::Lazy;
 use tokio::sync::Mutex;
 // Workspace imports
 use zksync_crypto::rand::{SeedableRng, XorShiftRng};
+use zksync_types::tx::TxHash;
+// Local imports
+use crate::chain::operations::records::NewExecutedTransaction;
 
+mod address_attestations;
+mod api_route_stats;
+mod audit;
 pub(crate) mod chain;
+mod cold_export;
 mod config;
+mod consistency;
+mod cosigned_txs;
+mod create2_factories;
 mod data_restore;
+mod deposit_aggregates;
+mod deposit_relay;
+mod dropped_txs;
+mod eip1271_verifications;
 mod ethereum;
 mod event;
+mod exit_proofs;
+mod fee_breakdowns;
 mod forced_exit_requests;
+mod history;
+mod jobs;
+mod labels;
+mod leadership;
+mod mass_payouts;
+mod migrations;
 mod misc;
+mod nonce_reservations;
+mod order_book;
+mod outbox;
+mod payment_requests;
 mod prover;
+mod pruning;
+mod reports;
+mod revenue;
+mod runtime_config;
+mod screening;
+mod session_keys;
+mod slow_queries;
+mod sponsorship;
+mod standing_orders;
+mod test_data;
 mod tokens;
+mod tx_callbacks;
+mod tx_memos;
+mod withdrawal_hooks;
+mod withdrawal_policy;
 mod withdrawals;
 
 pub use db_test_macro::test as db_test;
@@ -41,6 +82,38 @@ pub fn create_rng() -> XorShiftRng {
     XorShiftRng::from_seed([0, 1, 2, 3])
 }
 
+/// Creates a transaction hash filled with the given byte.
+pub fn tx_hash(byte: u8) -> TxHash {
+    TxHash::from_slice(&[byte; 32]).unwrap()
+}
+
+/// Creates an executed transaction with the hash filled with the given byte.
+/// Failed transactions are not included into the block.
+pub fn executed_tx(block_number: i64, hash: u8, success: bool) -> NewExecutedTransaction {
+    NewExecutedTransaction {
+        block_number,
+        tx_hash: vec![hash; 32],
+        tx: Default::default(),
+        operation: Default::default(),
+        from_account: Default::default(),
+        to_account: None,
+        success,
+        fail_reason: if success {
+            None
+        } else {
+            Some("Not enough balance".to_string())
+        },
+        block_index: if success { Some(0) } else { None },
+        primary_account_address: Default::default(),
+        nonce: Default::default(),
+        created_at: Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+        affected_accounts: Vec::new(),
+        used_tokens: Vec::new(),
+    }
+}
+
 /// Mutex that's used to avoid database deadlock when accessing
 /// accounts state concurrently in tests.
 static ACCOUNT_MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
@@ -0,0 +1,67 @@
+// External imports
+use chrono::{Duration, Utc};
+// Workspace imports
+use zksync_types::{AccountId, Nonce};
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the reservations of the same account don't overlap and the expired
+/// ones are not loaded.
+#[db_test]
+async fn test_nonce_reservations(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let account_id = AccountId(1);
+    let expires_at = Utc::now() + Duration::minutes(10);
+
+    let first = storage
+        .nonce_reservations_schema()
+        .reserve_nonces(account_id, Nonce(5), 10, expires_at)
+        .await?;
+    assert_eq!((first.first_nonce, first.last_nonce), (5, 14));
+
+    // The next reservation follows the previous one even if the account nonce is lower.
+    let second = storage
+        .nonce_reservations_schema()
+        .reserve_nonces(account_id, Nonce(7), 3, expires_at)
+        .await?;
+    assert_eq!((second.first_nonce, second.last_nonce), (15, 17));
+
+    // And the account nonce if it's higher.
+    let third = storage
+        .nonce_reservations_schema()
+        .reserve_nonces(account_id, Nonce(30), 1, expires_at)
+        .await?;
+    assert_eq!((third.first_nonce, third.last_nonce), (30, 30));
+
+    // Reservations of the other accounts are independent.
+    let other = storage
+        .nonce_reservations_schema()
+        .reserve_nonces(AccountId(2), Nonce(0), 2, Utc::now() - Duration::minutes(1))
+        .await?;
+    assert_eq!((other.first_nonce, other.last_nonce), (0, 1));
+
+    let reservations = storage
+        .nonce_reservations_schema()
+        .get_account_reservations(account_id)
+        .await?;
+    assert_eq!(reservations, vec![first, second, third]);
+    // The reservation of the other account is expired.
+    let active = storage
+        .nonce_reservations_schema()
+        .load_active_reservations()
+        .await?;
+    assert_eq!(active.len(), 3);
+    assert!(storage
+        .nonce_reservations_schema()
+        .get_account_reservations(AccountId(2))
+        .await?
+        .is_empty());
+
+    // Nonces of the expired reservation can be reserved again.
+    let renewed = storage
+        .nonce_reservations_schema()
+        .reserve_nonces(AccountId(2), Nonce(0), 2, expires_at)
+        .await?;
+    assert_eq!((renewed.first_nonce, renewed.last_nonce), (0, 1));
+    Ok(())
+}
@@ -0,0 +1,199 @@
+// External imports
+use num::BigUint;
+// Workspace imports
+use zksync_api_types::v02::pagination::PaginationDirection;
+use zksync_types::{
+    tx::{TimeRange, TxHash},
+    AccountId, Address, Nonce, Order, Swap, TokenId,
+};
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn order(account_id: u32, token_sell: u32, token_buy: u32, amount: u64, valid_until: u64) -> Order {
+    Order {
+        account_id: AccountId(account_id),
+        recipient_address: Address::from_low_u64_be(account_id as u64),
+        nonce: Nonce(0),
+        token_buy: TokenId(token_buy),
+        token_sell: TokenId(token_sell),
+        price: (BigUint::from(1u32), BigUint::from(1u32)),
+        amount: BigUint::from(amount),
+        time_range: TimeRange::new(0, valid_until),
+        signature: Default::default(),
+    }
+}
+
+fn swap(orders: (Order, Order), amounts: (u64, u64)) -> Swap {
+    Swap::new(
+        AccountId(0),
+        Address::zero(),
+        Nonce(0),
+        orders,
+        (BigUint::from(amounts.0), BigUint::from(amounts.1)),
+        BigUint::from(0u32),
+        TokenId(0),
+        None,
+    )
+}
+
+/// Checks the status transitions of the swap orders.
+#[db_test]
+async fn test_order_statuses(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let sell_order = order(1, 0, 1, 100, u64::MAX);
+    let buy_order = order(2, 1, 0, 100, u64::MAX);
+    let limit_order = order(3, 1, 0, 0, u64::MAX);
+    let small_order = order(4, 0, 1, 40, u64::MAX);
+    for order in &[&sell_order, &buy_order, &limit_order, &small_order] {
+        assert!(storage.order_book_schema().store_order(order, None).await?);
+    }
+    // The same order can't be stored twice.
+    assert!(
+        !storage
+            .order_book_schema()
+            .store_order(&sell_order, None)
+            .await?
+    );
+
+    let swap_tx_hash = TxHash::from_slice(&[1u8; 32]).unwrap();
+    storage
+        .order_book_schema()
+        .mark_orders_matched(&[sell_order.hash(), buy_order.hash()], swap_tx_hash)
+        .await?;
+    let stored = storage
+        .order_book_schema()
+        .get_order(sell_order.hash())
+        .await?
+        .expect("order is not stored");
+    assert_eq!(stored.status, "matched");
+    assert_eq!(stored.swap_tx_hash, Some(swap_tx_hash.as_ref().to_vec()));
+    // Matched orders can't be cancelled.
+    assert!(
+        !storage
+            .order_book_schema()
+            .cancel_order(sell_order.hash())
+            .await?
+    );
+
+    // Failed swap opens the orders again.
+    let executed_swap = swap((sell_order.clone(), buy_order.clone()), (100, 100));
+    storage
+        .order_book_schema()
+        .apply_executed_swaps(&[(swap_tx_hash, executed_swap.clone(), false)])
+        .await?;
+    let stored = storage
+        .order_book_schema()
+        .get_order(buy_order.hash())
+        .await?
+        .unwrap();
+    assert_eq!(stored.status, "open");
+
+    // The limit order stays open after being filled.
+    let second_tx_hash = TxHash::from_slice(&[2u8; 32]).unwrap();
+    let limit_swap = swap((buy_order.clone(), limit_order.clone()), (0, 0));
+    storage
+        .order_book_schema()
+        .apply_executed_swaps(&[
+            (swap_tx_hash, executed_swap, true),
+            (
+                second_tx_hash,
+                swap((small_order, limit_order.clone()), (40, 40)),
+                true,
+            ),
+            (second_tx_hash, limit_swap, false),
+        ])
+        .await?;
+    let stored = storage
+        .order_book_schema()
+        .get_order(sell_order.hash())
+        .await?
+        .unwrap();
+    assert_eq!(stored.status, "filled");
+    assert_eq!(stored.filled_amount, 100.into());
+    let stored = storage
+        .order_book_schema()
+        .get_order(limit_order.hash())
+        .await?
+        .unwrap();
+    assert_eq!(stored.status, "open");
+    assert_eq!(stored.filled_amount, 40.into());
+    assert_eq!(stored.swap_tx_hash, Some(second_tx_hash.as_ref().to_vec()));
+
+    assert!(
+        storage
+            .order_book_schema()
+            .cancel_order(limit_order.hash())
+            .await?
+    );
+    let stored = storage
+        .order_book_schema()
+        .get_order(limit_order.hash())
+        .await?
+        .unwrap();
+    assert_eq!(stored.status, "cancelled");
+
+    Ok(())
+}
+
+/// Checks the pagination of the open orders and the orders of the account.
+#[db_test]
+async fn test_open_orders(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let orders = vec![
+        order(1, 0, 1, 10, u64::MAX),
+        order(1, 0, 1, 20, u64::MAX),
+        // Expired orders are not returned.
+        order(1, 0, 1, 30, 1),
+        order(2, 0, 1, 40, u64::MAX),
+        // Orders of the other token pair.
+        order(1, 1, 0, 50, u64::MAX),
+    ];
+    for order in &orders {
+        storage.order_book_schema().store_order(order, None).await?;
+    }
+
+    let (token_sell, token_buy) = (TokenId(0), TokenId(1));
+    let count = storage
+        .order_book_schema()
+        .get_open_orders_count(token_sell, token_buy)
+        .await?;
+    assert_eq!(count, 3);
+    let max_id = storage
+        .order_book_schema()
+        .get_max_open_order_id(token_sell, token_buy)
+        .await?
+        .expect("there are open orders");
+
+    let page = storage
+        .order_book_schema()
+        .get_open_orders_page(token_sell, token_buy, max_id, 2, PaginationDirection::Older)
+        .await?;
+    let amounts: Vec<_> = page.iter().map(|order| order.amount.clone()).collect();
+    assert_eq!(amounts, vec![40.into(), 20.into()]);
+    let page = storage
+        .order_book_schema()
+        .get_open_orders_page(token_sell, token_buy, 0, 10, PaginationDirection::Newer)
+        .await?;
+    let amounts: Vec<_> = page.iter().map(|order| order.amount.clone()).collect();
+    assert_eq!(amounts, vec![10.into(), 20.into(), 40.into()]);
+
+    // Orders of the account are returned regardless of their status.
+    let account_id = AccountId(1);
+    let count = storage
+        .order_book_schema()
+        .get_account_orders_count(account_id)
+        .await?;
+    assert_eq!(count, 4);
+    let max_id = storage
+        .order_book_schema()
+        .get_max_account_order_id(account_id)
+        .await?
+        .unwrap();
+    let page = storage
+        .order_book_schema()
+        .get_account_orders_page(account_id, max_id, 10, PaginationDirection::Older)
+        .await?;
+    let amounts: Vec<_> = page.iter().map(|order| order.amount.clone()).collect();
+    assert_eq!(amounts, vec![50.into(), 30.into(), 20.into(), 10.into()]);
+
+    Ok(())
+}
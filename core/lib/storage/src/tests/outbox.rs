@@ -0,0 +1,248 @@
+// External imports
+use chrono::{Duration, Utc};
+// Workspace imports
+use zksync_types::{
+    event::outbox::{OutboxEvent, OutboxEventType, TokenListedEvent},
+    Address, Token, TokenId, TokenKind,
+};
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn token_listed_event(id: u32) -> OutboxEvent {
+    OutboxEvent::TokenListed(TokenListedEvent {
+        token: Token::new(
+            TokenId(id),
+            Address::random(),
+            &format!("TKN{}", id),
+            18,
+            TokenKind::ERC20,
+        ),
+    })
+}
+
+/// Checks that the stored events are scheduled for delivery to the matching subscriptions only.
+#[db_test]
+async fn test_outbox_event_fan_out(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let all_events = storage
+        .outbox_schema()
+        .add_webhook_subscription("http://all.example", "secret", &[])
+        .await?;
+    let blocks_only = storage
+        .outbox_schema()
+        .add_webhook_subscription(
+            "http://blocks.example",
+            "secret",
+            &[OutboxEventType::NewBlock],
+        )
+        .await?;
+    let subscriptions = storage.outbox_schema().load_webhook_subscriptions().await?;
+    assert_eq!(subscriptions.len(), 2);
+    assert_eq!(subscriptions[1].event_types, vec!["new_block".to_string()]);
+
+    storage
+        .outbox_schema()
+        .store_outbox_events(&[token_listed_event(1), token_listed_event(2)])
+        .await?;
+
+    // Only the subscription for all the events should receive tokens, in the order of events.
+    let deliveries = storage
+        .outbox_schema()
+        .load_due_webhook_deliveries(10)
+        .await?;
+    assert_eq!(deliveries.len(), 2);
+    assert!(deliveries.iter().all(|d| d.url == "http://all.example"));
+    assert!(deliveries[0].event_id < deliveries[1].event_id);
+    assert_eq!(deliveries[0].event_type, "token_listed");
+    assert_eq!(deliveries[0].payload, token_listed_event(1).payload());
+
+    // Removing the subscription removes its deliveries as well.
+    assert!(
+        storage
+            .outbox_schema()
+            .remove_webhook_subscription(all_events)
+            .await?
+    );
+    assert!(
+        !storage
+            .outbox_schema()
+            .remove_webhook_subscription(all_events)
+            .await?
+    );
+    assert!(storage
+        .outbox_schema()
+        .load_due_webhook_deliveries(10)
+        .await?
+        .is_empty());
+    assert_eq!(
+        storage.outbox_schema().load_webhook_subscriptions().await?[0].id,
+        blocks_only
+    );
+
+    Ok(())
+}
+
+/// Checks the retries and dead-lettering of the webhook deliveries.
+#[db_test]
+async fn test_webhook_delivery_retries(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let subscription_id = storage
+        .outbox_schema()
+        .add_webhook_subscription("http://example", "secret", &[OutboxEventType::TokenListed])
+        .await?;
+    // Token storing produces the outbox event.
+    storage
+        .tokens_schema()
+        .store_token(Token::new(
+            TokenId(1),
+            Address::random(),
+            "ABC",
+            18,
+            TokenKind::ERC20,
+        ))
+        .await
+        .expect("failed to store token");
+
+    let delivery = storage
+        .outbox_schema()
+        .load_due_webhook_deliveries(10)
+        .await?[0]
+        .clone();
+    assert_eq!(delivery.attempts, 0);
+
+    // Failed delivery is not due until the retry time.
+    storage
+        .outbox_schema()
+        .fail_webhook_delivery(
+            delivery.id,
+            "timeout",
+            Some(Utc::now() + Duration::hours(1)),
+        )
+        .await?;
+    assert!(storage
+        .outbox_schema()
+        .load_due_webhook_deliveries(10)
+        .await?
+        .is_empty());
+
+    // Delivery without the retry time becomes a dead letter.
+    storage
+        .outbox_schema()
+        .fail_webhook_delivery(delivery.id, "timeout", None)
+        .await?;
+    let dead = storage
+        .outbox_schema()
+        .load_dead_webhook_deliveries(subscription_id)
+        .await?;
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0].attempts, 2);
+    assert_eq!(dead[0].last_error.as_deref(), Some("timeout"));
+
+    // Dead letters can be returned to the queue.
+    assert_eq!(
+        storage
+            .outbox_schema()
+            .retry_dead_webhook_deliveries(subscription_id)
+            .await?,
+        1
+    );
+    let delivery = storage
+        .outbox_schema()
+        .load_due_webhook_deliveries(10)
+        .await?[0]
+        .clone();
+    assert_eq!(delivery.attempts, 0);
+
+    // Confirmed delivery is removed.
+    storage
+        .outbox_schema()
+        .confirm_webhook_delivery(delivery.id)
+        .await?;
+    assert!(storage
+        .outbox_schema()
+        .load_due_webhook_deliveries(10)
+        .await?
+        .is_empty());
+    assert!(storage
+        .outbox_schema()
+        .load_dead_webhook_deliveries(subscription_id)
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+/// Checks that the events nobody is subscribed to are skipped and the delivered events are removed.
+#[db_test]
+async fn test_outbox_retention(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let in_future = Utc::now() + Duration::hours(1);
+
+    // There are no subscriptions, so the event is not stored.
+    storage
+        .outbox_schema()
+        .store_outbox_events(&[token_listed_event(1)])
+        .await?;
+    assert_eq!(
+        storage
+            .outbox_schema()
+            .remove_old_outbox_events(in_future)
+            .await?,
+        0
+    );
+
+    storage
+        .outbox_schema()
+        .add_webhook_subscription("http://example", "secret", &[])
+        .await?;
+    storage
+        .outbox_schema()
+        .store_outbox_events(&[token_listed_event(2), token_listed_event(3)])
+        .await?;
+    let deliveries = storage
+        .outbox_schema()
+        .load_due_webhook_deliveries(10)
+        .await?;
+    assert_eq!(deliveries.len(), 2);
+
+    // Events are retained for the retention period even if they are delivered.
+    storage
+        .outbox_schema()
+        .confirm_webhook_delivery(deliveries[0].id)
+        .await?;
+    assert_eq!(
+        storage
+            .outbox_schema()
+            .remove_old_outbox_events(Utc::now() - Duration::hours(1))
+            .await?,
+        0
+    );
+
+    // Only the delivered event is removed, the pending one is kept.
+    assert_eq!(
+        storage
+            .outbox_schema()
+            .remove_old_outbox_events(in_future)
+            .await?,
+        1
+    );
+    let pending = storage
+        .outbox_schema()
+        .load_due_webhook_deliveries(10)
+        .await?;
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].event_id, deliveries[1].event_id);
+
+    // Dead deliveries don't prevent the removal.
+    storage
+        .outbox_schema()
+        .fail_webhook_delivery(pending[0].id, "timeout", None)
+        .await?;
+    assert_eq!(
+        storage
+            .outbox_schema()
+            .remove_old_outbox_events(in_future)
+            .await?,
+        1
+    );
+
+    Ok(())
+}
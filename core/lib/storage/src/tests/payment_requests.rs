@@ -0,0 +1,103 @@
+// External imports
+use chrono::{Duration, Utc};
+use num::BigUint;
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{Address, BlockNumber, TokenId};
+// Local imports
+use crate::payment_requests::records::ExecutedTransfer;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn transfer(byte: u8, to_address: Address, amount: u32, memo: Option<&str>) -> ExecutedTransfer {
+    ExecutedTransfer {
+        tx_hash: vec![byte; 32],
+        to_address: to_address.as_bytes().to_vec(),
+        token_id: 1,
+        amount: BigDecimal::from(amount),
+        memo: memo.map(str::to_string),
+        created_at: Utc::now(),
+    }
+}
+
+/// Checks that the transfers fulfill the matching requests only, preferring the ones
+/// with the reference.
+#[db_test]
+async fn payment_requests(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let merchant = Address::repeat_byte(0x01);
+    let expires_at = Utc::now() + Duration::hours(1);
+    let plain_id = storage
+        .payment_requests_schema()
+        .store_payment_request(
+            merchant,
+            TokenId(1),
+            &BigUint::from(100u32),
+            None,
+            expires_at,
+        )
+        .await?;
+    let invoice_id = storage
+        .payment_requests_schema()
+        .store_payment_request(
+            merchant,
+            TokenId(1),
+            &BigUint::from(100u32),
+            Some("INV-1"),
+            expires_at,
+        )
+        .await?;
+
+    // The amount is too low.
+    assert_eq!(
+        storage
+            .payment_requests_schema()
+            .fulfill_payment_request(&transfer(1, merchant, 50, Some("INV-1")))
+            .await?,
+        None
+    );
+    // The recipient doesn't match.
+    assert_eq!(
+        storage
+            .payment_requests_schema()
+            .fulfill_payment_request(&transfer(2, Address::repeat_byte(0x02), 100, None))
+            .await?,
+        None
+    );
+    // The request with the reference can't be fulfilled by the transfer without the memo.
+    assert_eq!(
+        storage
+            .payment_requests_schema()
+            .fulfill_payment_request(&transfer(3, merchant, 150, None))
+            .await?,
+        Some(plain_id)
+    );
+    assert_eq!(
+        storage
+            .payment_requests_schema()
+            .fulfill_payment_request(&transfer(4, merchant, 100, Some("INV-1")))
+            .await?,
+        Some(invoice_id)
+    );
+
+    let request = storage
+        .payment_requests_schema()
+        .get_payment_request(invoice_id)
+        .await?
+        .unwrap();
+    assert_eq!(request.fulfilled_tx_hash, Some(vec![4; 32]));
+    assert!(request.fulfilled_at.is_some());
+
+    storage
+        .payment_requests_schema()
+        .update_last_matched_block(BlockNumber(5))
+        .await?;
+    assert_eq!(
+        storage
+            .payment_requests_schema()
+            .lock_last_matched_block()
+            .await?,
+        BlockNumber(5)
+    );
+
+    Ok(())
+}
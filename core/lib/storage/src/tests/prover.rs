@@ -4,7 +4,7 @@ use once_cell::sync::Lazy;
 use tokio::sync::Mutex;
 // Workspace imports
 use zksync_types::{
-    prover::{ProverJob, ProverJobType},
+    prover::{ProverJob, ProverJobType, HIGH_PRIORITY_JOB_PRIORITY, SINGLE_PROOF_JOB_PRIORITY},
     BlockNumber,
 };
 // Local imports
@@ -213,6 +213,82 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
     Ok(())
 }
 
+/// Checks that the operator can change the order in which prover jobs are served
+/// and return the jobs of a stalled prover back to the queue.
+#[db_test]
+async fn test_prover_job_priority(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    for block in 1..=3 {
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block),
+                BlockNumber(block),
+                Default::default(),
+                SINGLE_PROOF_JOB_PRIORITY,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+    }
+
+    // Mark the already queued block and the block without job as high priority ones.
+    ProverSchema(&mut storage)
+        .mark_block_as_high_priority(BlockNumber(2))
+        .await?;
+    ProverSchema(&mut storage)
+        .mark_block_as_high_priority(BlockNumber(4))
+        .await?;
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(4),
+            BlockNumber(4),
+            Default::default(),
+            SINGLE_PROOF_JOB_PRIORITY,
+            ProverJobType::SingleProof,
+        )
+        .await?;
+
+    let jobs = ProverSchema(&mut storage)
+        .load_pending_prover_jobs()
+        .await?;
+    assert_eq!(jobs.len(), 4);
+    assert_eq!(jobs[0].first_block, 2);
+    assert_eq!(jobs[0].job_priority, HIGH_PRIORITY_JOB_PRIORITY);
+    assert_eq!(jobs[1].first_block, 4);
+    assert_eq!(jobs[1].job_priority, HIGH_PRIORITY_JOB_PRIORITY);
+
+    // Move the job for the third block to the top of the queue.
+    let third_block_job = jobs.iter().find(|job| job.first_block == 3).unwrap().id;
+    assert!(
+        ProverSchema(&mut storage)
+            .set_prover_job_priority(third_block_job, HIGH_PRIORITY_JOB_PRIORITY - 1)
+            .await?
+    );
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    assert_eq!(job.job_id, third_block_job);
+
+    // Priority of the job that is in progress can't be changed.
+    assert!(
+        !ProverSchema(&mut storage)
+            .set_prover_job_priority(third_block_job, SINGLE_PROOF_JOB_PRIORITY)
+            .await?
+    );
+
+    // Return the job of the stalled prover to the queue.
+    ProverSchema(&mut storage)
+        .record_prover_is_working(job.job_id, "stalled_prover")
+        .await?;
+    let reassigned = ProverSchema(&mut storage)
+        .reassign_prover_jobs("stalled_prover")
+        .await?;
+    assert_eq!(reassigned, 1);
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    assert_eq!(job.job_id, third_block_job);
+
+    Ok(())
+}
+
 /// Checks that the witness can be stored and loaded.
 #[db_test]
 async fn test_store_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -441,3 +517,62 @@ async fn test_remove_prover_jobs(mut storage: StorageProcessor<'_>) -> QueryResu
 
     Ok(())
 }
+
+/// Checks that the timestamp of the oldest unproven block is loaded correctly.
+#[db_test]
+async fn test_oldest_unproven_block_timestamp(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    // No blocks, no unproven blocks.
+    assert!(storage
+        .prover_schema()
+        .oldest_unproven_block_timestamp()
+        .await?
+        .is_none());
+
+    for block_number in 1..=3 {
+        let mut block = gen_sample_block(BlockNumber(block_number), 100, Default::default());
+        block.timestamp = 1_000 + block_number as u64;
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(block)
+            .await?;
+    }
+    assert_eq!(
+        storage
+            .prover_schema()
+            .oldest_unproven_block_timestamp()
+            .await?,
+        Some(1_001)
+    );
+
+    // Prove the first block, so the second one becomes the oldest unproven.
+    storage
+        .prover_schema()
+        .add_prover_job_to_job_queue(
+            BlockNumber(1),
+            BlockNumber(1),
+            serde_json::Value::default(),
+            SINGLE_PROOF_JOB_PRIORITY,
+            ProverJobType::SingleProof,
+        )
+        .await?;
+    let job_id = get_idle_job_from_queue(&mut storage).await?.job_id;
+    storage
+        .prover_schema()
+        .store_proof(job_id, BlockNumber(1), &get_sample_single_proof())
+        .await?;
+    assert_eq!(
+        storage
+            .prover_schema()
+            .oldest_unproven_block_timestamp()
+            .await?,
+        Some(1_002)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+// External imports
+use chrono::Utc;
+// Workspace imports
+use zksync_api_types::v02::transaction::{Receipt, TxInBlockStatus};
+use zksync_types::BlockNumber;
+// Local imports
+use crate::chain::{
+    operations::{records::NewExecutedPriorityOperation, OperationsSchema},
+    operations_ext::records::StorageTxData,
+};
+use crate::pruning::{PrunedTable, PruningSchema};
+use crate::tests::{db_test, executed_tx};
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the transactions are moved to the archive in batches and
+/// the last pruned block is updated once all the rows are pruned.
+#[db_test]
+async fn test_prune_executed_transactions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for (block_number, tx_hash) in vec![(1, 1), (1, 2), (2, 3), (3, 4)] {
+        OperationsSchema(&mut storage)
+            .store_executed_tx(executed_tx(block_number, tx_hash, true))
+            .await?;
+    }
+    let table = PrunedTable::ExecutedTransactions;
+
+    let pruned = PruningSchema(&mut storage)
+        .prune_table(table, BlockNumber(2), 2)
+        .await?;
+    assert_eq!(pruned, 2);
+    // Not all the rows of the blocks are pruned yet.
+    assert_eq!(
+        PruningSchema(&mut storage)
+            .get_last_pruned_block(table)
+            .await?,
+        BlockNumber(0)
+    );
+    let pruned = PruningSchema(&mut storage)
+        .prune_table(table, BlockNumber(2), 2)
+        .await?;
+    assert_eq!(pruned, 1);
+    assert_eq!(
+        PruningSchema(&mut storage)
+            .get_last_pruned_block(table)
+            .await?,
+        BlockNumber(2)
+    );
+
+    // Pruned transaction is only available in the archive.
+    let pruned_hash = vec![3u8; 32];
+    assert!(OperationsSchema(&mut storage)
+        .get_executed_operation(&pruned_hash)
+        .await?
+        .is_none());
+    match PruningSchema(&mut storage)
+        .archived_tx_receipt(&pruned_hash)
+        .await?
+    {
+        Some(Receipt::L2(receipt)) => {
+            assert_eq!(receipt.status, TxInBlockStatus::Pruned);
+            assert_eq!(receipt.rollup_block, Some(BlockNumber(2)));
+        }
+        receipt => panic!("Unexpected receipt: {:?}", receipt),
+    }
+
+    // Transactions of the newer blocks are kept.
+    let kept_hash = vec![4u8; 32];
+    assert!(OperationsSchema(&mut storage)
+        .get_executed_operation(&kept_hash)
+        .await?
+        .is_some());
+    assert!(PruningSchema(&mut storage)
+        .archived_tx_receipt(&kept_hash)
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+/// Checks that the transactions loaded for the archive are exactly the ones pruned next
+/// and their data survives the round trip through the archive format.
+#[db_test]
+async fn test_load_txs_to_prune(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for (block_number, tx_hash) in vec![(2, 3), (1, 2), (1, 1), (3, 4)] {
+        OperationsSchema(&mut storage)
+            .store_executed_tx(executed_tx(block_number, tx_hash, true))
+            .await?;
+    }
+    let table = PrunedTable::ExecutedTransactions;
+
+    let txs = PruningSchema(&mut storage)
+        .load_txs_to_prune(table, BlockNumber(2), 2)
+        .await?;
+    let tx_hashes: Vec<_> = txs.iter().map(|tx| tx.tx_hash.clone()).collect();
+    assert_eq!(tx_hashes, vec![vec![1u8; 32], vec![2u8; 32]]);
+
+    PruningSchema(&mut storage)
+        .prune_table(table, BlockNumber(2), 2)
+        .await?;
+    for tx_hash in &tx_hashes {
+        assert!(OperationsSchema(&mut storage)
+            .get_executed_operation(tx_hash)
+            .await?
+            .is_none());
+    }
+    assert!(OperationsSchema(&mut storage)
+        .get_executed_operation(&[3u8; 32])
+        .await?
+        .is_some());
+
+    let archived = serde_json::to_vec(&txs[0]).unwrap();
+    let restored: StorageTxData = serde_json::from_slice(&archived).unwrap();
+    assert_eq!(restored, txs[0]);
+
+    // Only the tables with the transactions are archived.
+    assert!(PruningSchema(&mut storage)
+        .load_txs_to_prune(PrunedTable::BlockWitness, BlockNumber(2), 2)
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+/// Checks that the priority operations moved to the archive are still taken
+/// into account by the highest serial id of the executed priority operations.
+#[db_test]
+async fn test_max_priority_op_serial_id_after_pruning(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    for serial_id in 0..2 {
+        let op = NewExecutedPriorityOperation {
+            block_number: serial_id + 1,
+            block_index: 0,
+            operation: Default::default(),
+            from_account: Default::default(),
+            to_account: Default::default(),
+            priority_op_serialid: serial_id,
+            deadline_block: 100,
+            eth_hash: vec![serial_id as u8; 32],
+            eth_block: 10,
+            created_at: Utc::now(),
+            tx_hash: vec![serial_id as u8; 32],
+            eth_block_index: Some(1),
+            l1_sender: None,
+            affected_accounts: Default::default(),
+            token: Default::default(),
+        };
+        OperationsSchema(&mut storage)
+            .store_executed_priority_op(op)
+            .await?;
+    }
+
+    let pruned = PruningSchema(&mut storage)
+        .prune_table(PrunedTable::ExecutedPriorityOperations, BlockNumber(2), 10)
+        .await?;
+    assert_eq!(pruned, 2);
+    assert_eq!(
+        OperationsSchema(&mut storage)
+            .get_max_priority_op_serial_id()
+            .await?,
+        Some(1)
+    );
+
+    Ok(())
+}
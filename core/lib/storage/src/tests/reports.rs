@@ -0,0 +1,78 @@
+// Built-in deps
+use std::time::Duration;
+// External imports
+use serde_json::json;
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the reports return the columns in order and the rows are capped.
+#[db_test]
+async fn test_run_report(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let timeout = Duration::from_secs(10);
+    let sql = "SELECT n AS number, $1 AS label, n * $2::bigint AS product, \
+        12345678901234567890::numeric AS amount \
+        FROM generate_series(1, 3) AS n";
+    let parameters = ["x".to_owned(), "2".to_owned()];
+
+    let report = storage
+        .reports_schema()
+        .run_report(sql, &parameters, 10, timeout)
+        .await?;
+    assert_eq!(report.columns, vec!["number", "label", "product", "amount"]);
+    assert_eq!(report.rows.len(), 3);
+    // Numbers which don't fit into the double precision are returned as strings.
+    assert_eq!(
+        report.rows[1],
+        vec![
+            json!(2),
+            json!("x"),
+            json!(4),
+            json!("12345678901234567890")
+        ]
+    );
+    assert!(!report.truncated);
+
+    let report = storage
+        .reports_schema()
+        .run_report(sql, &parameters, 2, timeout)
+        .await?;
+    assert_eq!(report.rows.len(), 2);
+    assert!(report.truncated);
+
+    let report = storage
+        .reports_schema()
+        .run_report("SELECT 1 AS one WHERE false", &[], 2, timeout)
+        .await?;
+    assert!(report.columns.is_empty());
+    assert!(report.rows.is_empty());
+
+    Ok(())
+}
+
+/// Checks that the reports can't change the data and are interrupted by the timeout.
+#[db_test]
+async fn test_report_restrictions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let timeout = Duration::from_millis(500);
+    let modifying = [
+        "WITH removed AS (DELETE FROM tokens RETURNING id) SELECT * FROM removed",
+        "SELECT nextval('api_route_stats_id_seq')",
+        "SELECT 1) report; DELETE FROM tokens; SELECT * FROM (SELECT 1",
+    ];
+    for sql in modifying.iter() {
+        assert!(storage
+            .reports_schema()
+            .run_report(sql, &[], 10, timeout)
+            .await
+            .is_err());
+    }
+
+    assert!(storage
+        .reports_schema()
+        .run_report("SELECT pg_sleep(5)", &[], 10, timeout)
+        .await
+        .is_err());
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+// External imports
+use chrono::{Duration, Utc};
+use num::BigUint;
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{aggregated_operations::AggregatedActionType, tx::TxHash, TokenId, H256};
+// Local imports
+use crate::fee_breakdowns::records::TxFeeQuote;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn quote(byte: u8, token_id: u32, zkp_fee: u64, subsidy: u64) -> TxFeeQuote {
+    TxFeeQuote {
+        tx_hash: TxHash::from_slice(&[byte; 32]).unwrap(),
+        token_id: TokenId(token_id),
+        zkp_fee: BigDecimal::from(zkp_fee),
+        gas_fee: BigDecimal::from(100),
+        subsidy: BigDecimal::from(subsidy),
+    }
+}
+
+/// Checks that the charged fees and the spent L1 gas are aggregated per day.
+#[db_test]
+async fn test_revenue_report(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let quotes = vec![quote(1, 0, 100, 0), quote(2, 0, 100, 10), quote(3, 1, 0, 0)];
+    storage
+        .fee_breakdowns_schema()
+        .store_fee_quotes(&quotes)
+        .await?;
+    // The last quote is never charged, so it's not reported.
+    storage
+        .fee_breakdowns_schema()
+        .store_charged_fees(&[
+            (quotes[0].tx_hash, BigUint::from(200u32)),
+            (quotes[1].tx_hash, BigUint::from(400u32)),
+        ])
+        .await?;
+
+    let eth_op = storage
+        .ethereum_schema()
+        .save_new_eth_tx(
+            AggregatedActionType::CommitBlocks,
+            None,
+            100,
+            1000u32.into(),
+            Default::default(),
+        )
+        .await?;
+    let eth_tx_hash = H256::repeat_byte(0x01);
+    storage
+        .ethereum_schema()
+        .add_hash_entry(eth_op.id, &eth_tx_hash)
+        .await?;
+    storage
+        .ethereum_schema()
+        .store_gas_spent(&eth_tx_hash, 50_000.into(), 3.into())
+        .await?;
+    // The gas of the operation is stored once.
+    storage
+        .ethereum_schema()
+        .store_gas_spent(&eth_tx_hash, 50_000.into(), 5.into())
+        .await?;
+
+    let today = Utc::today().naive_utc();
+    let fees = storage
+        .revenue_schema()
+        .load_daily_fees(today, today)
+        .await?;
+    assert_eq!(fees.len(), 1);
+    assert_eq!(fees[0].day, today);
+    assert_eq!(fees[0].token_id, 0);
+    assert_eq!(fees[0].transactions_count, 2);
+    assert_eq!(fees[0].charged_fee, BigDecimal::from(600));
+    assert_eq!(fees[0].zkp_fee, BigDecimal::from(300));
+    assert_eq!(fees[0].gas_fee, BigDecimal::from(300));
+    assert_eq!(fees[0].subsidy, BigDecimal::from(10));
+
+    let gas_spent = storage
+        .revenue_schema()
+        .load_daily_gas_spent(today, today)
+        .await?;
+    assert_eq!(gas_spent.len(), 1);
+    assert_eq!(gas_spent[0].transactions_count, 1);
+    assert_eq!(gas_spent[0].gas_used, BigDecimal::from(50_000));
+    assert_eq!(gas_spent[0].gas_cost, BigDecimal::from(150_000));
+
+    // Nothing is reported outside of the range.
+    let yesterday = today - Duration::days(1);
+    assert!(storage
+        .revenue_schema()
+        .load_daily_fees(yesterday, yesterday)
+        .await?
+        .is_empty());
+    assert!(storage
+        .revenue_schema()
+        .load_daily_gas_spent(yesterday, yesterday)
+        .await?
+        .is_empty());
+
+    Ok(())
+}
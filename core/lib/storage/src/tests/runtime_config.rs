@@ -0,0 +1,110 @@
+// External imports
+use serde_json::json;
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the overrides are replaced and removed with every change recorded.
+#[db_test]
+async fn test_runtime_config(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let old_value = storage
+        .runtime_config_schema()
+        .set_param("fee_scale_percent", &json!(120), "alice")
+        .await?;
+    assert_eq!(old_value, None);
+    let old_value = storage
+        .runtime_config_schema()
+        .set_param("fee_scale_percent", &json!(150), "bob")
+        .await?;
+    assert_eq!(old_value, Some(json!(120)));
+    storage
+        .runtime_config_schema()
+        .set_param("log_filter", &json!("zksync_core=debug"), "bob")
+        .await?;
+
+    let params = storage.runtime_config_schema().load_params().await?;
+    let params: Vec<_> = params
+        .into_iter()
+        .map(|param| (param.key, param.value, param.updated_by))
+        .collect();
+    assert_eq!(
+        params,
+        vec![
+            (
+                "fee_scale_percent".to_string(),
+                json!(150),
+                "bob".to_string()
+            ),
+            (
+                "log_filter".to_string(),
+                json!("zksync_core=debug"),
+                "bob".to_string()
+            ),
+        ]
+    );
+
+    let removed = storage
+        .runtime_config_schema()
+        .remove_param("log_filter", "alice")
+        .await?;
+    assert_eq!(removed, Some(json!("zksync_core=debug")));
+    let removed = storage
+        .runtime_config_schema()
+        .remove_param("log_filter", "alice")
+        .await?;
+    assert_eq!(removed, None);
+    assert_eq!(
+        storage.runtime_config_schema().load_params().await?.len(),
+        1
+    );
+
+    // Changes are loaded newest first, the repeated removal isn't recorded.
+    let changes = storage
+        .runtime_config_schema()
+        .load_changes(None, 10)
+        .await?;
+    let changes: Vec<_> = changes
+        .iter()
+        .map(|change| {
+            (
+                change.key.as_str(),
+                change.old_value.clone(),
+                change.new_value.clone(),
+                change.changed_by.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (
+                "log_filter",
+                Some(json!("zksync_core=debug")),
+                None,
+                "alice"
+            ),
+            ("log_filter", None, Some(json!("zksync_core=debug")), "bob"),
+            (
+                "fee_scale_percent",
+                Some(json!(120)),
+                Some(json!(150)),
+                "bob"
+            ),
+            ("fee_scale_percent", None, Some(json!(120)), "alice"),
+        ]
+    );
+
+    let before = storage
+        .runtime_config_schema()
+        .load_changes(None, 10)
+        .await?[1]
+        .id;
+    let changes = storage
+        .runtime_config_schema()
+        .load_changes(Some(before), 10)
+        .await?;
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].new_value, Some(json!(150)));
+    Ok(())
+}
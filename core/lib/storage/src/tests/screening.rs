@@ -0,0 +1,100 @@
+// External imports
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+use crate::screening::records::ScreeningMatch;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks the management of the denylist.
+#[db_test]
+async fn test_screening_denylist(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::from_low_u64_be(1);
+    let other_address = Address::from_low_u64_be(2);
+
+    assert!(
+        storage
+            .screening_schema()
+            .add_to_denylist(address, "sanctioned")
+            .await?
+    );
+    assert!(
+        !storage
+            .screening_schema()
+            .add_to_denylist(address, "sanctioned")
+            .await?
+    );
+
+    let denylisted = storage
+        .screening_schema()
+        .get_denylisted(&[address, other_address])
+        .await?;
+    assert_eq!(denylisted.len(), 1);
+    assert_eq!(denylisted[0].address, address.as_bytes());
+    assert_eq!(denylisted[0].reason, "sanctioned");
+    assert_eq!(storage.screening_schema().load_denylist().await?.len(), 1);
+
+    assert!(
+        storage
+            .screening_schema()
+            .remove_from_denylist(address)
+            .await?
+    );
+    assert!(
+        !storage
+            .screening_schema()
+            .remove_from_denylist(address)
+            .await?
+    );
+    let denylisted = storage
+        .screening_schema()
+        .get_denylisted(&[address, other_address])
+        .await?;
+    assert!(denylisted.is_empty());
+    Ok(())
+}
+
+/// Checks that the matches are loaded newest first and filtered by the address.
+#[db_test]
+async fn test_screening_matches(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::from_low_u64_be(1);
+    let other_address = Address::from_low_u64_be(2);
+
+    for (address, role) in [
+        (address, "sender"),
+        (other_address, "sender"),
+        (address, "recipient"),
+    ] {
+        storage
+            .screening_schema()
+            .store_match(&ScreeningMatch {
+                tx_hash: TxHash::default(),
+                address,
+                role: role.to_string(),
+                source: "denylist".to_string(),
+                reason: "sanctioned".to_string(),
+            })
+            .await?;
+    }
+
+    let matches = storage
+        .screening_schema()
+        .load_matches(None, None, 10)
+        .await?;
+    assert_eq!(matches.len(), 3);
+
+    let matches = storage
+        .screening_schema()
+        .load_matches(Some(address), None, 1)
+        .await?;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].role, "recipient");
+
+    let matches = storage
+        .screening_schema()
+        .load_matches(Some(address), Some(matches[0].id), 10)
+        .await?;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].role, "sender");
+    Ok(())
+}
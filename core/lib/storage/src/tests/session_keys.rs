@@ -0,0 +1,162 @@
+// External imports
+use chrono::{Duration, TimeZone, Utc};
+use num::BigUint;
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{AccountId, Address, TokenId};
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the spending caps of the session key are enforced and reset by the new
+/// registration.
+#[db_test]
+async fn test_session_key_limits(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let account_id = AccountId(1);
+    let session_address = Address::repeat_byte(0x11);
+    // Requests are signed with the millisecond timestamps.
+    let signed_at = Utc.timestamp_millis(Utc::now().timestamp_millis());
+    let limits = [(TokenId(0), BigUint::from(100u32))];
+    assert!(
+        storage
+            .session_keys_schema()
+            .store_session_key(
+                account_id,
+                session_address,
+                &["Transfer".to_string()],
+                &limits,
+                signed_at + Duration::days(1),
+                signed_at,
+            )
+            .await?
+    );
+
+    let spend = |token: u32, amount: u32| vec![(TokenId(token), BigUint::from(amount))];
+    assert!(
+        storage
+            .session_keys_schema()
+            .spend_session_key(account_id, session_address, &spend(0, 60))
+            .await?
+    );
+    // The cap is exceeded, nothing is spent.
+    assert!(
+        !storage
+            .session_keys_schema()
+            .spend_session_key(account_id, session_address, &spend(0, 50))
+            .await?
+    );
+    // Tokens without the cap can't be spent.
+    assert!(
+        !storage
+            .session_keys_schema()
+            .spend_session_key(account_id, session_address, &spend(1, 1))
+            .await?
+    );
+    storage
+        .session_keys_schema()
+        .refund_session_key(account_id, session_address, &spend(0, 20))
+        .await?;
+    let limits = storage
+        .session_keys_schema()
+        .get_session_key_limits(account_id, session_address)
+        .await?;
+    assert_eq!(limits.len(), 1);
+    assert_eq!(limits[0].spent, BigDecimal::from(40));
+
+    // The same registration can't be replayed to reset the spent amounts.
+    assert!(
+        !storage
+            .session_keys_schema()
+            .store_session_key(
+                account_id,
+                session_address,
+                &["Transfer".to_string()],
+                &[(TokenId(0), BigUint::from(100u32))],
+                signed_at + Duration::days(1),
+                signed_at,
+            )
+            .await?
+    );
+    assert!(
+        storage
+            .session_keys_schema()
+            .store_session_key(
+                account_id,
+                session_address,
+                &["Transfer".to_string(), "Withdraw".to_string()],
+                &[(TokenId(1), BigUint::from(10u32))],
+                signed_at + Duration::days(1),
+                signed_at + Duration::seconds(1),
+            )
+            .await?
+    );
+    let limits = storage
+        .session_keys_schema()
+        .get_session_key_limits(account_id, session_address)
+        .await?;
+    assert_eq!(limits.len(), 1);
+    assert_eq!(limits[0].token_id, 1);
+    assert_eq!(limits[0].spent, BigDecimal::from(0));
+
+    Ok(())
+}
+
+/// Checks the revocation of the session key.
+#[db_test]
+async fn test_revoke_session_key(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let account_id = AccountId(1);
+    let session_address = Address::repeat_byte(0x11);
+    let signed_at = Utc.timestamp_millis(Utc::now().timestamp_millis());
+    storage
+        .session_keys_schema()
+        .store_session_key(
+            account_id,
+            session_address,
+            &["Transfer".to_string()],
+            &[],
+            signed_at + Duration::days(1),
+            signed_at,
+        )
+        .await?;
+
+    // Revocation signed before the registration is not applied.
+    assert!(
+        !storage
+            .session_keys_schema()
+            .revoke_session_key(
+                account_id,
+                session_address,
+                signed_at - Duration::seconds(1)
+            )
+            .await?
+    );
+    assert!(
+        storage
+            .session_keys_schema()
+            .revoke_session_key(
+                account_id,
+                session_address,
+                signed_at + Duration::seconds(1)
+            )
+            .await?
+    );
+    let session_key = storage
+        .session_keys_schema()
+        .get_session_key(account_id, session_address)
+        .await?
+        .expect("session key is not stored");
+    assert!(session_key.revoked);
+
+    let session_keys = storage
+        .session_keys_schema()
+        .get_account_session_keys(account_id)
+        .await?;
+    assert_eq!(session_keys, vec![session_key]);
+    assert!(storage
+        .session_keys_schema()
+        .get_account_session_keys(AccountId(2))
+        .await?
+        .is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+// Built-in imports
+use std::time::Duration;
+// External imports
+use chrono::Utc;
+// Local imports
+use crate::slow_queries::SlowQuery;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the slow queries are stored and loaded newest first.
+#[db_test]
+async fn test_store_slow_queries(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let queries = vec![
+        SlowQuery {
+            schema: "chain.block",
+            method: "save_block",
+            duration: Duration::from_millis(1500),
+            created_at: Utc::now(),
+            sql: Some("INSERT INTO blocks VALUES ($1, $2)".to_owned()),
+        },
+        SlowQuery {
+            schema: "tokens",
+            method: "load_tokens",
+            duration: Duration::from_secs(3),
+            created_at: Utc::now(),
+            sql: None,
+        },
+    ];
+    storage
+        .slow_queries_schema()
+        .store_slow_queries(&queries)
+        .await?;
+    // Storing an empty batch is a no-op.
+    storage
+        .slow_queries_schema()
+        .store_slow_queries(&[])
+        .await?;
+
+    let stored = storage.slow_queries_schema().load_slow_queries(10).await?;
+    assert_eq!(stored.len(), 2);
+    assert_eq!(stored[0].schema_name, "tokens");
+    assert_eq!(stored[0].method, "load_tokens");
+    assert_eq!(stored[0].duration_ms, 3000);
+    assert_eq!(stored[1].schema_name, "chain.block");
+    assert_eq!(stored[0].sql, None);
+    assert_eq!(stored[1].duration_ms, 1500);
+    assert_eq!(
+        stored[1].sql.as_deref(),
+        Some("INSERT INTO blocks VALUES ($1, $2)")
+    );
+
+    let stored = storage.slow_queries_schema().load_slow_queries(1).await?;
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].method, "load_tokens");
+    Ok(())
+}
+
+/// Checks that only the slow queries recorded before the given time are removed.
+#[db_test]
+async fn test_remove_old_slow_queries(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now();
+    let queries: Vec<_> = [3, 1]
+        .iter()
+        .map(|&days| SlowQuery {
+            schema: "tokens",
+            method: "load_tokens",
+            duration: Duration::from_secs(days),
+            created_at: now - chrono::Duration::days(days as i64),
+            sql: None,
+        })
+        .collect();
+    storage
+        .slow_queries_schema()
+        .store_slow_queries(&queries)
+        .await?;
+
+    let removed = storage
+        .slow_queries_schema()
+        .remove_old_slow_queries(now - chrono::Duration::days(2))
+        .await?;
+    assert_eq!(removed, 1);
+    let stored = storage.slow_queries_schema().load_slow_queries(10).await?;
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].duration_ms, 1000);
+    Ok(())
+}
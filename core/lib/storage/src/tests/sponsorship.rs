@@ -0,0 +1,148 @@
+// External imports
+// Workspace imports
+use zksync_types::{tx::TxHash, AccountId, Address, TokenId};
+// Local imports
+use crate::sponsorship::records::{CpkSponsorship, FeeFreeTransfer};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn cpk_sponsorship(account_id: u32, waived_fee_usd_scaled: u64) -> CpkSponsorship {
+    CpkSponsorship {
+        account_id: AccountId(account_id),
+        tx_hash: TxHash::from_slice(&[account_id as u8; 32]).unwrap(),
+        fee_token: TokenId(0),
+        waived_fee: 1000.into(),
+        waived_fee_usd_scaled,
+    }
+}
+
+/// Checks that every account is sponsored only once and the sponsored fees are summed up.
+#[db_test]
+async fn test_cpk_sponsorships(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert_eq!(
+        storage
+            .sponsorship_schema()
+            .get_total_sponsored_usd()
+            .await?,
+        0
+    );
+
+    assert!(
+        storage
+            .sponsorship_schema()
+            .store_cpk_sponsorship(&cpk_sponsorship(1, 100))
+            .await?
+    );
+    assert!(
+        storage
+            .sponsorship_schema()
+            .store_cpk_sponsorship(&cpk_sponsorship(2, 200))
+            .await?
+    );
+    // The account is already sponsored.
+    assert!(
+        !storage
+            .sponsorship_schema()
+            .store_cpk_sponsorship(&cpk_sponsorship(1, 300))
+            .await?
+    );
+    assert_eq!(
+        storage
+            .sponsorship_schema()
+            .get_total_sponsored_usd()
+            .await?,
+        300
+    );
+
+    let sponsorships = storage
+        .sponsorship_schema()
+        .load_cpk_sponsorships(None, 1)
+        .await?;
+    assert_eq!(sponsorships.len(), 1);
+    assert_eq!(sponsorships[0].account_id, 2);
+    assert_eq!(sponsorships[0].waived_fee_usd_scale6, 200);
+
+    let sponsorships = storage
+        .sponsorship_schema()
+        .load_cpk_sponsorships(Some(sponsorships[0].id), 10)
+        .await?;
+    assert_eq!(sponsorships.len(), 1);
+    assert_eq!(sponsorships[0].account_id, 1);
+    assert_eq!(sponsorships[0].waived_fee_usd_scale6, 100);
+    Ok(())
+}
+
+fn fee_free_transfer(address: Address, tx_byte: u8) -> FeeFreeTransfer {
+    FeeFreeTransfer {
+        address,
+        tx_hash: TxHash::from_slice(&[tx_byte; 32]).unwrap(),
+        fee_token: TokenId(0),
+        waived_fee: 1000.into(),
+    }
+}
+
+/// Checks that the fee-free transfers of every operator address are limited by the daily quota.
+#[db_test]
+async fn test_fee_free_transfers(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const DAILY_QUOTA: u64 = 2;
+    let operator = Address::repeat_byte(1);
+    let other_operator = Address::repeat_byte(2);
+
+    for tx_byte in 1..=2 {
+        assert!(
+            storage
+                .sponsorship_schema()
+                .store_fee_free_transfer(&fee_free_transfer(operator, tx_byte), DAILY_QUOTA)
+                .await?
+        );
+    }
+    // The resubmitted transfer doesn't use up the quota.
+    assert!(
+        storage
+            .sponsorship_schema()
+            .store_fee_free_transfer(&fee_free_transfer(operator, 2), DAILY_QUOTA)
+            .await?
+    );
+    // The quota is exhausted, but the quota of the other address is not affected.
+    assert!(
+        !storage
+            .sponsorship_schema()
+            .store_fee_free_transfer(&fee_free_transfer(operator, 3), DAILY_QUOTA)
+            .await?
+    );
+    assert!(
+        storage
+            .sponsorship_schema()
+            .store_fee_free_transfer(&fee_free_transfer(other_operator, 4), DAILY_QUOTA)
+            .await?
+    );
+    // Nothing is waived with the zero quota.
+    assert!(
+        !storage
+            .sponsorship_schema()
+            .store_fee_free_transfer(&fee_free_transfer(Address::repeat_byte(3), 5), 0)
+            .await?
+    );
+    assert_eq!(
+        storage
+            .sponsorship_schema()
+            .get_fee_free_transfers_used(operator)
+            .await?,
+        DAILY_QUOTA
+    );
+
+    let transfers = storage
+        .sponsorship_schema()
+        .load_fee_free_transfers(Some(operator), None, 10)
+        .await?;
+    assert_eq!(transfers.len(), 2);
+    assert_eq!(transfers[0].tx_hash, vec![2u8; 32]);
+    let transfers = storage
+        .sponsorship_schema()
+        .load_fee_free_transfers(None, Some(transfers[0].id), 10)
+        .await?;
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].tx_hash, vec![1u8; 32]);
+
+    Ok(())
+}
@@ -0,0 +1,238 @@
+// External imports
+use chrono::{Duration, Utc};
+use num::BigUint;
+// Workspace imports
+use zksync_api_types::v02::pagination::PaginationDirection;
+use zksync_types::{
+    tx::{TimeRange, TxEthSignature, TxHash},
+    AccountId, Address, Nonce, PubKeyHash, TokenId, Transfer,
+};
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn transfers(account_id: u32, count: u32) -> Vec<(Transfer, Option<TxEthSignature>)> {
+    (0..count)
+        .map(|nonce| {
+            let transfer = Transfer::new(
+                AccountId(account_id),
+                Address::from_low_u64_be(account_id as u64),
+                Address::repeat_byte(0xff),
+                TokenId(0),
+                BigUint::from(100u32),
+                BigUint::from(1u32),
+                Nonce(nonce),
+                TimeRange::default(),
+                None,
+            );
+            (transfer, None)
+        })
+        .collect()
+}
+
+/// Checks that the due payments are taken one by one and the order is completed
+/// after the last one.
+#[db_test]
+async fn test_standing_order_payments(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now();
+    // Payments of the first order are due, only the first payment of the second one is.
+    let first_id = storage
+        .standing_orders_schema()
+        .store_standing_order(
+            &transfers(1, 2),
+            PubKeyHash::default(),
+            now - Duration::hours(2),
+            60,
+            now + Duration::hours(1),
+        )
+        .await?;
+    let second_id = storage
+        .standing_orders_schema()
+        .store_standing_order(
+            &transfers(2, 2),
+            PubKeyHash::default(),
+            now - Duration::hours(1),
+            86400,
+            now + Duration::days(2),
+        )
+        .await?;
+
+    let payments = storage
+        .standing_orders_schema()
+        .get_standing_order_payments(first_id)
+        .await?;
+    assert_eq!(payments.len(), 2);
+    assert_eq!(
+        payments[1].scheduled_at,
+        payments[0].scheduled_at + Duration::seconds(60)
+    );
+    assert_eq!(payments[1].nonce, 1);
+
+    let mut processed = Vec::new();
+    loop {
+        let mut transaction = storage.start_transaction().await?;
+        let payment = transaction
+            .standing_orders_schema()
+            .lock_due_payment()
+            .await?;
+        let payment = match payment {
+            Some(payment) => payment,
+            None => break,
+        };
+        transaction
+            .standing_orders_schema()
+            .store_payment_submitted(
+                payment.standing_order_id,
+                payment.sequence,
+                TxHash::from_slice(&[payment.sequence as u8; 32]).unwrap(),
+            )
+            .await?;
+        transaction.commit().await?;
+        processed.push((payment.standing_order_id, payment.sequence));
+    }
+    assert_eq!(
+        processed,
+        vec![(first_id, 0), (first_id, 1), (second_id, 0)]
+    );
+
+    let first = storage
+        .standing_orders_schema()
+        .get_standing_order(first_id)
+        .await?
+        .expect("order is not stored");
+    assert_eq!(first.status, "completed");
+    let second = storage
+        .standing_orders_schema()
+        .get_standing_order(second_id)
+        .await?
+        .expect("order is not stored");
+    assert_eq!(second.status, "active");
+
+    // Cancellation skips the remaining payment.
+    assert!(
+        storage
+            .standing_orders_schema()
+            .cancel_standing_order(second_id)
+            .await?
+    );
+    assert!(
+        !storage
+            .standing_orders_schema()
+            .cancel_standing_order(first_id)
+            .await?
+    );
+    let statuses: Vec<_> = storage
+        .standing_orders_schema()
+        .get_standing_order_payments(second_id)
+        .await?
+        .into_iter()
+        .map(|payment| payment.status)
+        .collect();
+    assert_eq!(statuses, vec!["submitted", "skipped"]);
+
+    Ok(())
+}
+
+/// Checks that the rejected payment fails the order and skips its remaining payments.
+#[db_test]
+async fn test_standing_order_failure(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now();
+    let id = storage
+        .standing_orders_schema()
+        .store_standing_order(
+            &transfers(1, 3),
+            PubKeyHash::default(),
+            now - Duration::hours(1),
+            60,
+            now + Duration::hours(1),
+        )
+        .await?;
+
+    let payment = storage
+        .standing_orders_schema()
+        .lock_due_payment()
+        .await?
+        .expect("payment is not due");
+    assert_eq!(payment.sequence, 0);
+    storage
+        .standing_orders_schema()
+        .store_payment_failure(id, payment.sequence, "Not enough balance")
+        .await?;
+
+    assert!(storage
+        .standing_orders_schema()
+        .lock_due_payment()
+        .await?
+        .is_none());
+    let order = storage
+        .standing_orders_schema()
+        .get_standing_order(id)
+        .await?
+        .expect("order is not stored");
+    assert_eq!(order.status, "failed");
+    assert_eq!(order.failure_reason.as_deref(), Some("Not enough balance"));
+    let statuses: Vec<_> = storage
+        .standing_orders_schema()
+        .get_standing_order_payments(id)
+        .await?
+        .into_iter()
+        .map(|payment| payment.status)
+        .collect();
+    assert_eq!(statuses, vec!["failed", "skipped", "skipped"]);
+
+    Ok(())
+}
+
+/// Checks the pagination over the orders of the account.
+#[db_test]
+async fn test_account_standing_orders(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now();
+    let mut ids = Vec::new();
+    for account_id in &[1, 2, 1, 1] {
+        let id = storage
+            .standing_orders_schema()
+            .store_standing_order(
+                &transfers(*account_id, 1),
+                PubKeyHash::default(),
+                now + Duration::hours(1),
+                60,
+                now + Duration::hours(2),
+            )
+            .await?;
+        ids.push(id);
+    }
+
+    let account_id = AccountId(1);
+    assert_eq!(
+        storage
+            .standing_orders_schema()
+            .get_account_standing_orders_count(account_id)
+            .await?,
+        3
+    );
+    assert_eq!(
+        storage
+            .standing_orders_schema()
+            .get_max_account_standing_order_id(account_id)
+            .await?,
+        Some(ids[3])
+    );
+    let page: Vec<_> = storage
+        .standing_orders_schema()
+        .get_account_standing_orders_page(account_id, ids[3], 2, PaginationDirection::Older)
+        .await?
+        .into_iter()
+        .map(|order| order.id)
+        .collect();
+    assert_eq!(page, vec![ids[3], ids[2]]);
+    let page: Vec<_> = storage
+        .standing_orders_schema()
+        .get_account_standing_orders_page(account_id, ids[0], 10, PaginationDirection::Newer)
+        .await?
+        .into_iter()
+        .map(|order| order.id)
+        .collect();
+    assert_eq!(page, vec![ids[0], ids[2], ids[3]]);
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+// External imports
+// Workspace imports
+use zksync_types::BlockNumber;
+// Local imports
+use crate::test_data::load_test::{populate_load_test_data, LoadTestConfig};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the generated load test data is consistent with the queries reading it.
+#[db_test]
+async fn test_populate_load_test_data(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let config = LoadTestConfig {
+        blocks: 5,
+        txs_per_block: 20,
+        priority_ops_per_block: 3,
+        accounts: 10,
+        tokens: 3,
+        pending_blocks: 2,
+        ..Default::default()
+    };
+    let data = populate_load_test_data(&mut storage, &config).await?;
+    assert_eq!(data.first_block, BlockNumber(1));
+    assert_eq!(data.last_block, BlockNumber(5));
+    assert_eq!(data.accounts, config.accounts());
+    assert_eq!(data.priority_op_eth_hashes.len(), 5);
+
+    assert_eq!(
+        storage
+            .chain()
+            .block_schema()
+            .get_last_saved_block()
+            .await?,
+        BlockNumber(5)
+    );
+    assert_eq!(
+        storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?,
+        BlockNumber(3)
+    );
+
+    let receipt = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_receipt_api_v02(data.tx_hashes[0].as_ref())
+        .await?;
+    assert!(receipt.is_some());
+    let receipt = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_receipt_api_v02(data.priority_op_eth_hashes[0].as_bytes())
+        .await?;
+    assert!(receipt.is_some());
+
+    // The counters maintained by the `tx_filters` triggers match the history.
+    let address = data.accounts[0];
+    let count = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_count(address, None, None)
+        .await?;
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(&address, 0, 1000)
+        .await?;
+    assert!(count > 0);
+    assert_eq!(history.len(), count as usize);
+
+    // Data is appended after the existing blocks.
+    let data = populate_load_test_data(
+        &mut storage,
+        &LoadTestConfig {
+            seed: [5, 6, 7, 8],
+            ..config
+        },
+    )
+    .await?;
+    assert_eq!(data.first_block, BlockNumber(6));
+    assert_eq!(data.last_block, BlockNumber(10));
+    Ok(())
+}
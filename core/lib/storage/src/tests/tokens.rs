@@ -1,7 +1,7 @@
 // Built-in imports
 use std::str::FromStr;
 // External imports
-use chrono::Utc;
+use chrono::{TimeZone, Utc};
 use num::{rational::Ratio, BigUint};
 // Workspace imports
 use zksync_test_account::ZkSyncAccount;
@@ -179,6 +179,116 @@ async fn test_ticker_price(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     Ok(())
 }
 
+/// Checks that the price updates are recorded to the hourly price history.
+#[db_test]
+async fn test_ticker_price_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+    let hour_start = Utc.ymd(2021, 5, 1).and_hms(10, 0, 0);
+    let prices = vec![
+        (hour_start + chrono::Duration::minutes(5), 1u32),
+        (hour_start + chrono::Duration::minutes(50), 2u32),
+        (hour_start + chrono::Duration::minutes(70), 3u32),
+    ];
+    for (last_updated, usd_price) in prices {
+        let price = TokenPrice {
+            usd_price: Ratio::from_integer(BigUint::from(usd_price)),
+            last_updated,
+        };
+        storage
+            .tokens_schema()
+            .update_historical_ticker_price(TOKEN_ID, price)
+            .await?;
+    }
+
+    let mut prices_at = Vec::new();
+    for moment in vec![
+        hour_start - chrono::Duration::seconds(1),
+        hour_start + chrono::Duration::minutes(1),
+        hour_start + chrono::Duration::days(1),
+    ] {
+        let price = storage
+            .tokens_schema()
+            .get_ticker_price_at(TOKEN_ID, moment)
+            .await?;
+        prices_at.push(price.map(|price| price.usd_price.to_integer()));
+    }
+    // There is no price before the first recorded hour, and the last price observed
+    // within the hour is kept.
+    assert_eq!(
+        prices_at,
+        vec![None, Some(BigUint::from(2u32)), Some(BigUint::from(3u32))]
+    );
+
+    Ok(())
+}
+
+/// Checks that the manual prices are returned until they expire.
+#[db_test]
+async fn test_ticker_price_overrides(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+    let usd_price = Ratio::new(BigUint::from(3u32), BigUint::from(2u32));
+    storage
+        .tokens_schema()
+        .store_ticker_price_override(
+            TOKEN_ID,
+            &usd_price,
+            "stale price source",
+            "alice",
+            Utc::now() + chrono::Duration::hours(1),
+        )
+        .await?;
+
+    let price = storage
+        .tokens_schema()
+        .get_ticker_price_override(TOKEN_ID)
+        .await?
+        .expect("override must be active");
+    assert_eq!(price.usd_price, usd_price);
+
+    // The expired override is listed but not used.
+    storage
+        .tokens_schema()
+        .store_ticker_price_override(
+            TOKEN_ID,
+            &usd_price,
+            "stale price source",
+            "bob",
+            Utc::now() - chrono::Duration::seconds(1),
+        )
+        .await?;
+    assert!(storage
+        .tokens_schema()
+        .get_ticker_price_override(TOKEN_ID)
+        .await?
+        .is_none());
+    let overrides = storage
+        .tokens_schema()
+        .load_ticker_price_overrides()
+        .await?;
+    assert_eq!(overrides.len(), 1);
+    assert_eq!(overrides[0].created_by, "bob");
+
+    assert!(
+        storage
+            .tokens_schema()
+            .remove_ticker_price_override(TOKEN_ID)
+            .await?
+    );
+    assert!(
+        !storage
+            .tokens_schema()
+            .remove_ticker_price_override(TOKEN_ID)
+            .await?
+    );
+    assert!(storage
+        .tokens_schema()
+        .load_ticker_price_overrides()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
 /// Checks the store/load routine for `ticker_market_volume` table and load tokens by market volume.
 #[db_test]
 async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
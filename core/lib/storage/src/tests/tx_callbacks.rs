@@ -0,0 +1,167 @@
+// External imports
+use chrono::{Duration, Utc};
+// Workspace imports
+use zksync_types::{event::account::AccountStateChangeStatus, BlockNumber};
+// Local imports
+use crate::chain::operations::OperationsSchema;
+use crate::tests::{db_test, executed_tx, tx_hash};
+use crate::tx_callbacks::TX_STATUS_REJECTED;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that only the transactions of the scopes with the endpoint are tracked,
+/// and that the endpoint is replaced on the repeated registration.
+#[db_test]
+async fn tx_callback_endpoints(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(
+        !storage
+            .tx_callbacks_schema()
+            .track_txs("exchange", &[tx_hash(1)])
+            .await?
+    );
+
+    storage
+        .tx_callbacks_schema()
+        .set_endpoint("exchange", "http://old.example", "old")
+        .await?;
+    let endpoint = storage
+        .tx_callbacks_schema()
+        .set_endpoint("exchange", "http://new.example", "new")
+        .await?;
+    assert_eq!(endpoint.url, "http://new.example");
+    assert_eq!(
+        storage
+            .tx_callbacks_schema()
+            .get_endpoint("exchange")
+            .await?,
+        Some(endpoint)
+    );
+    assert!(
+        storage
+            .tx_callbacks_schema()
+            .track_txs("exchange", &[tx_hash(1)])
+            .await?
+    );
+
+    assert!(
+        storage
+            .tx_callbacks_schema()
+            .remove_endpoint("exchange")
+            .await?
+    );
+    assert!(
+        !storage
+            .tx_callbacks_schema()
+            .remove_endpoint("exchange")
+            .await?
+    );
+    assert!(storage
+        .tx_callbacks_schema()
+        .get_endpoint("exchange")
+        .await?
+        .is_none());
+    Ok(())
+}
+
+/// Checks that every status of the tracked transaction is enqueued once
+/// and the delivery attempts are recorded.
+#[db_test]
+async fn tx_callbacks_delivery(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    storage
+        .tx_callbacks_schema()
+        .set_endpoint("exchange", "http://exchange.example", "secret")
+        .await?;
+    storage
+        .tx_callbacks_schema()
+        .track_txs("exchange", &[tx_hash(1), tx_hash(2), tx_hash(3)])
+        .await?;
+    storage
+        .tx_callbacks_schema()
+        .untrack_txs("exchange", &[tx_hash(3)])
+        .await?;
+    for tx in vec![
+        executed_tx(1, 1, true),
+        executed_tx(1, 2, false),
+        executed_tx(1, 3, true),
+        executed_tx(1, 4, true),
+    ] {
+        OperationsSchema(&mut storage).store_executed_tx(tx).await?;
+    }
+
+    // Confirmation of the same blocks may be processed twice, e.g. after the restart.
+    for _ in 0..2 {
+        storage
+            .tx_callbacks_schema()
+            .enqueue_block_callbacks(
+                BlockNumber(1),
+                BlockNumber(1),
+                AccountStateChangeStatus::Committed,
+            )
+            .await?;
+    }
+    let callbacks = storage.tx_callbacks_schema().load_due_callbacks(10).await?;
+    assert_eq!(callbacks.len(), 2);
+    assert_eq!(callbacks[0].tx_hash, vec![1; 32]);
+    assert_eq!(callbacks[0].tx_status, "committed");
+    assert_eq!(callbacks[0].url, "http://exchange.example");
+    assert_eq!(callbacks[1].tx_hash, vec![2; 32]);
+    assert_eq!(callbacks[1].tx_status, TX_STATUS_REJECTED);
+    assert_eq!(
+        callbacks[1].fail_reason.as_deref(),
+        Some("Not enough balance")
+    );
+
+    storage
+        .tx_callbacks_schema()
+        .confirm_callback(callbacks[0].id)
+        .await?;
+    storage
+        .tx_callbacks_schema()
+        .fail_callback(
+            callbacks[1].id,
+            "connection refused",
+            Some(Utc::now() + Duration::hours(1)),
+        )
+        .await?;
+    assert!(storage
+        .tx_callbacks_schema()
+        .load_due_callbacks(10)
+        .await?
+        .is_empty());
+
+    // Executed transactions are reported as finalized, the rejected ones are not reported again.
+    for _ in 0..2 {
+        storage
+            .tx_callbacks_schema()
+            .enqueue_block_callbacks(
+                BlockNumber(1),
+                BlockNumber(1),
+                AccountStateChangeStatus::Finalized,
+            )
+            .await?;
+    }
+    let callbacks = storage
+        .tx_callbacks_schema()
+        .get_tx_callbacks("exchange", tx_hash(1))
+        .await?;
+    assert_eq!(callbacks.len(), 2);
+    assert_eq!(callbacks[0].status, "delivered");
+    assert_eq!(callbacks[1].tx_status, "finalized");
+    assert_eq!(callbacks[1].status, "pending");
+
+    let rejected = storage
+        .tx_callbacks_schema()
+        .get_tx_callbacks("exchange", tx_hash(2))
+        .await?;
+    assert_eq!(rejected.len(), 1);
+    assert_eq!(rejected[0].attempts, 1);
+    assert_eq!(
+        rejected[0].last_error.as_deref(),
+        Some("connection refused")
+    );
+    assert!(storage
+        .tx_callbacks_schema()
+        .get_tx_callbacks("exchange", tx_hash(3))
+        .await?
+        .is_empty());
+    Ok(())
+}
@@ -0,0 +1,65 @@
+// External imports
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+use crate::tests::{db_test, tx_hash};
+use crate::tx_memos::records::TxMemo;
+use crate::{QueryResult, StorageProcessor};
+
+fn memo(tx_hash: TxHash, to_address: Address, memo: &str) -> TxMemo {
+    TxMemo {
+        tx_hash,
+        to_address,
+        memo: memo.to_string(),
+    }
+}
+
+/// Checks that the memos are loaded by the transaction hashes and found by the recipient.
+#[db_test]
+async fn tx_memos(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let merchant = Address::repeat_byte(0x01);
+    let other = Address::repeat_byte(0x02);
+    storage
+        .tx_memos_schema()
+        .store_tx_memos(&[
+            memo(tx_hash(1), merchant, "INV-1"),
+            memo(tx_hash(2), merchant, "INV-2"),
+            memo(tx_hash(3), other, "INV-1"),
+        ])
+        .await?;
+
+    let memos = storage
+        .tx_memos_schema()
+        .get_tx_memos(&[tx_hash(1), tx_hash(4)])
+        .await?;
+    assert_eq!(memos.len(), 1);
+    assert_eq!(memos[0].memo, "INV-1");
+
+    let found = storage
+        .tx_memos_schema()
+        .find_tx_memos(merchant, "INV-1", 10)
+        .await?;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].tx_hash, tx_hash(1).as_ref().to_vec());
+
+    // The memo of the resubmitted transaction is replaced.
+    storage
+        .tx_memos_schema()
+        .store_tx_memos(&[memo(tx_hash(1), merchant, "INV-3")])
+        .await?;
+    assert!(storage
+        .tx_memos_schema()
+        .find_tx_memos(merchant, "INV-1", 10)
+        .await?
+        .is_empty());
+    assert_eq!(
+        storage
+            .tx_memos_schema()
+            .find_tx_memos(merchant, "INV-3", 10)
+            .await?
+            .len(),
+        1
+    );
+
+    Ok(())
+}
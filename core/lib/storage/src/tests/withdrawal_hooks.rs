@@ -0,0 +1,123 @@
+// External imports
+// Workspace imports
+use zksync_types::{Address, BlockNumber, H256};
+// Local imports
+use crate::tests::{db_test, executed_tx, tx_hash};
+use crate::withdrawal_hooks::{
+    records::NewWithdrawalHook, HOOK_STATUS_CANCELLED, HOOK_STATUS_FAILED, HOOK_STATUS_PENDING,
+    HOOK_STATUS_READY, HOOK_STATUS_SENT,
+};
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks the lifecycle of the hooks: they are scheduled once the blocks are executed
+/// and are sent one by one.
+#[db_test]
+async fn withdrawal_hooks(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let recipient = Address::repeat_byte(0x02);
+    let hook = |hash: u8, calldata: &[u8]| NewWithdrawalHook {
+        tx_hash: tx_hash(hash),
+        recipient,
+        calldata: calldata.to_vec(),
+    };
+    storage
+        .withdrawal_hooks_schema()
+        .store_withdrawal_hooks(&[hook(1, &[1]), hook(2, &[2]), hook(3, &[3])])
+        .await?;
+    // The hook of the resubmitted withdrawal is replaced.
+    storage
+        .withdrawal_hooks_schema()
+        .store_withdrawal_hooks(&[hook(1, &[1, 1])])
+        .await?;
+    storage
+        .chain()
+        .operations_schema()
+        .store_executed_txs(vec![
+            executed_tx(1, 1, true),
+            executed_tx(1, 2, false),
+            executed_tx(2, 3, true),
+        ])
+        .await?;
+
+    // Nothing to send until the blocks are executed.
+    assert!(storage
+        .withdrawal_hooks_schema()
+        .load_next_withdrawal_hook()
+        .await?
+        .is_none());
+    let scheduled = storage
+        .withdrawal_hooks_schema()
+        .schedule_withdrawal_hooks(BlockNumber(1), BlockNumber(1))
+        .await?;
+    assert_eq!(scheduled, 2);
+
+    let hooks = storage
+        .withdrawal_hooks_schema()
+        .get_withdrawal_hooks(&[tx_hash(1), tx_hash(2), tx_hash(3), tx_hash(4)])
+        .await?;
+    let status = |hash: u8| {
+        hooks
+            .iter()
+            .find(|hook| hook.tx_hash == tx_hash(hash).as_ref().to_vec())
+            .map(|hook| hook.status.as_str())
+    };
+    assert_eq!(hooks.len(), 3);
+    assert_eq!(status(1), Some(HOOK_STATUS_READY));
+    assert_eq!(status(2), Some(HOOK_STATUS_CANCELLED));
+    assert_eq!(status(3), Some(HOOK_STATUS_PENDING));
+
+    let next = storage
+        .withdrawal_hooks_schema()
+        .load_next_withdrawal_hook()
+        .await?
+        .unwrap();
+    assert_eq!(next.tx_hash, tx_hash(1).as_ref().to_vec());
+    assert_eq!(next.calldata, vec![1, 1]);
+    assert_eq!(next.block_number, Some(1));
+
+    // The sent hook is loaded again until it's completed, e.g. after the restart.
+    storage
+        .withdrawal_hooks_schema()
+        .schedule_withdrawal_hooks(BlockNumber(2), BlockNumber(2))
+        .await?;
+    storage
+        .withdrawal_hooks_schema()
+        .mark_withdrawal_hook_sent(tx_hash(3), H256::repeat_byte(0x03))
+        .await?;
+    let next = storage
+        .withdrawal_hooks_schema()
+        .load_next_withdrawal_hook()
+        .await?
+        .unwrap();
+    assert_eq!(next.tx_hash, tx_hash(3).as_ref().to_vec());
+    assert_eq!(next.status, HOOK_STATUS_SENT);
+    assert_eq!(next.eth_tx_hash, Some(vec![0x03; 32]));
+
+    storage
+        .withdrawal_hooks_schema()
+        .complete_withdrawal_hook(tx_hash(3), Some("execution reverted"))
+        .await?;
+    storage
+        .withdrawal_hooks_schema()
+        .mark_withdrawal_hook_sent(tx_hash(1), H256::repeat_byte(0x01))
+        .await?;
+    storage
+        .withdrawal_hooks_schema()
+        .complete_withdrawal_hook(tx_hash(1), None)
+        .await?;
+    assert!(storage
+        .withdrawal_hooks_schema()
+        .load_next_withdrawal_hook()
+        .await?
+        .is_none());
+
+    let failed = storage
+        .withdrawal_hooks_schema()
+        .get_withdrawal_hooks(&[tx_hash(3)])
+        .await?
+        .pop()
+        .unwrap();
+    assert_eq!(failed.status, HOOK_STATUS_FAILED);
+    assert_eq!(failed.fail_reason.as_deref(), Some("execution reverted"));
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+// External imports
+use chrono::{Duration, Utc};
+// Workspace imports
+use zksync_types::{tx::TxHash, Address, TokenId, H256};
+// Local imports
+use crate::tests::db_test;
+use crate::withdrawal_policy::records::WithdrawalPolicyDecision;
+use crate::{QueryResult, StorageProcessor};
+
+fn decision(address: Address, amount_usd_scaled: u64, allowed: bool) -> WithdrawalPolicyDecision {
+    WithdrawalPolicyDecision {
+        tx_hash: TxHash::from_slice(&H256::random().0).unwrap(),
+        address,
+        token_id: TokenId(0),
+        amount: 1000.into(),
+        amount_usd_scaled,
+        allowed,
+        reason: (!allowed).then(|| String::from("daily withdrawal limit is exceeded")),
+    }
+}
+
+/// Checks that only the allowed withdrawals of the address are counted to the withdrawn value.
+#[db_test]
+async fn test_withdrawal_policy_decisions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::from_low_u64_be(1);
+    let other_address = Address::from_low_u64_be(2);
+    let since = Utc::now() - Duration::days(1);
+
+    for decision in [
+        decision(address, 100, true),
+        decision(address, 200, true),
+        decision(address, 400, false),
+        decision(other_address, 800, true),
+    ] {
+        storage
+            .withdrawal_policy_schema()
+            .store_decision(&decision)
+            .await?;
+    }
+
+    let withdrawn = storage
+        .withdrawal_policy_schema()
+        .get_withdrawn_usd(address, since)
+        .await?;
+    assert_eq!(withdrawn, 300);
+    let withdrawn = storage
+        .withdrawal_policy_schema()
+        .get_withdrawn_usd(address, Utc::now())
+        .await?;
+    assert_eq!(withdrawn, 0);
+
+    let decisions = storage
+        .withdrawal_policy_schema()
+        .load_decisions(address, None, 2)
+        .await?;
+    assert_eq!(decisions.len(), 2);
+    assert!(!decisions[0].allowed);
+    assert_eq!(
+        decisions[0].reason.as_deref(),
+        Some("daily withdrawal limit is exceeded")
+    );
+    assert_eq!(decisions[1].amount_usd_scale6, 200);
+
+    let decisions = storage
+        .withdrawal_policy_schema()
+        .load_decisions(address, Some(decisions[1].id), 10)
+        .await?;
+    assert_eq!(decisions.len(), 1);
+    assert_eq!(decisions[0].amount_usd_scale6, 100);
+    Ok(())
+}
+
+/// Checks that the decision on the transaction submitted again is not overwritten.
+#[db_test]
+async fn test_withdrawal_policy_decision_reused(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    let address = Address::from_low_u64_be(1);
+    let allowed = decision(address, 100, true);
+    let rejected = WithdrawalPolicyDecision {
+        allowed: false,
+        ..allowed.clone()
+    };
+
+    storage
+        .withdrawal_policy_schema()
+        .store_decision(&allowed)
+        .await?;
+    storage
+        .withdrawal_policy_schema()
+        .store_decision(&rejected)
+        .await?;
+
+    let stored = storage
+        .withdrawal_policy_schema()
+        .get_decision(allowed.tx_hash)
+        .await?
+        .expect("decision is stored");
+    assert!(stored.allowed);
+    let withdrawn = storage
+        .withdrawal_policy_schema()
+        .get_withdrawn_usd(address, Utc::now() - Duration::days(1))
+        .await?;
+    assert_eq!(withdrawn, 100);
+
+    let decision = storage
+        .withdrawal_policy_schema()
+        .get_decision(TxHash::default())
+        .await?;
+    assert!(decision.is_none());
+    Ok(())
+}
@@ -2,6 +2,7 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 // External imports
+use chrono::{DateTime, Utc};
 use num::{rational::Ratio, BigUint};
 
 use thiserror::Error;
@@ -10,11 +11,18 @@ use zksync_api_types::v02::{
     pagination::{PaginationDirection, PaginationQuery},
     token::ApiNFT,
 };
-use zksync_types::{AccountId, Address, Token, TokenId, TokenLike, TokenPrice, NFT};
+use zksync_types::{
+    event::outbox::{OutboxEvent, TokenListedEvent},
+    AccountId, Address, Token, TokenId, TokenLike, TokenPrice, NFT,
+};
 use zksync_utils::ratio_to_big_decimal;
 // Local imports
-use self::records::{DBMarketVolume, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, TokenKind};
+use self::records::{
+    DBMarketVolume, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, StoredTickerPriceOverride,
+    TokenKind,
+};
 
+use crate::outbox::OutboxSchema;
 use crate::utils::address_to_stored_string;
 use crate::{QueryResult, StorageProcessor};
 use zksync_types::tokens::TokenMarketVolume;
@@ -39,8 +47,14 @@ pub enum StoreTokenError {
 
 impl<'a, 'c> TokensSchema<'a, 'c> {
     /// Persists the new token in the database.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn store_token(&mut self, token: Token) -> Result<(), StoreTokenError> {
         let start = Instant::now();
+        let mut transaction = self
+            .0
+            .start_transaction()
+            .await
+            .map_err(StoreTokenError::Other)?;
 
         let token_from_db: Option<Token> = sqlx::query_as!(
             DbToken,
@@ -53,7 +67,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             address_to_stored_string(&token.address),
             token.symbol,
         )
-        .fetch_optional(self.0.conn())
+        .fetch_optional(transaction.conn())
         .await
         .map_err(|err| StoreTokenError::Other(err.into()))?
         .map(|db_token| db_token.into());
@@ -91,16 +105,23 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             i16::from(token.decimals),
             kind as TokenKind
         )
-        .execute(self.0.conn())
+        .execute(transaction.conn())
         .await
         .map_err(|err| StoreTokenError::Other(err.into()))?;
 
-        metrics::histogram!("sql.token.store_token", start.elapsed());
+        OutboxSchema(&mut transaction)
+            .store_outbox_events(&[OutboxEvent::TokenListed(TokenListedEvent { token })])
+            .await
+            .map_err(StoreTokenError::Other)?;
+        transaction.commit().await.map_err(StoreTokenError::Other)?;
+
+        crate::slow_queries::report_query("tokens", "store_token", start);
         Ok(())
     }
 
     /// If a token with a given ID exists, then it replaces the information about the
     /// token with a new one, otherwise, saves the token.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn store_or_update_token(&mut self, token: Token) -> QueryResult<()> {
         let start = Instant::now();
         let kind: TokenKind = token.kind.into();
@@ -121,11 +142,12 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.store_token", start.elapsed());
+        crate::slow_queries::report_query("tokens", "store_token", start);
         Ok(())
     }
 
     /// Loads tokens from the database starting from the given id with the given limit in the ascending order.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn load_tokens_asc(
         &mut self,
         from: TokenId,
@@ -148,11 +170,12 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .await?;
 
         let result = tokens.into_iter().map(Token::from).collect();
-        metrics::histogram!("sql.token.load_tokens_asc", start.elapsed());
+        crate::slow_queries::report_query("tokens", "load_tokens_asc", start);
         Ok(result)
     }
 
     /// Loads tokens from the database starting from the given id with the given limit in the descending order.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn load_tokens_desc(
         &mut self,
         from: TokenId,
@@ -175,19 +198,21 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .await?;
 
         let result = tokens.into_iter().map(Token::from).collect();
-        metrics::histogram!("sql.token.load_tokens_desc", start.elapsed());
+        crate::slow_queries::report_query("tokens", "load_tokens_desc", start);
         Ok(result)
     }
 
     /// Loads all the stored tokens from the database.
     /// Alongside with the tokens added via `store_token` method, the default `ETH` token
     /// is returned.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn load_tokens(&mut self) -> QueryResult<HashMap<TokenId, Token>> {
         let tokens = self.load_tokens_asc(TokenId(0), None).await?;
         Ok(tokens.into_iter().map(|token| (token.id, token)).collect())
     }
 
     /// Loads tokens for the given pagination query
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn load_token_page(
         &mut self,
         query: &PaginationQuery<TokenId>,
@@ -204,6 +229,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
     }
 
     /// Loads all finalized NFTs.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn load_nfts(&mut self) -> QueryResult<HashMap<TokenId, NFT>> {
         let start = Instant::now();
 
@@ -226,13 +252,14 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .map(|nft| (TokenId(nft.token_id as u32), nft.into()))
         .collect();
 
-        metrics::histogram!("sql.token.load_nfts", start.elapsed());
+        crate::slow_queries::report_query("tokens", "load_nfts", start);
         Ok(nfts)
     }
 
     /// Loads all the stored tokens, which have market_volume (ticker_market_volume table)
     /// not less than parameter (min_market_volume)
     /// And have not null price in ticker_price
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn load_tokens_by_market_volume(
         &mut self,
         min_market_volume: Ratio<BigUint>,
@@ -265,11 +292,12 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             })
             .collect());
 
-        metrics::histogram!("sql.token.load_tokens_by_market_volume", start.elapsed());
+        crate::slow_queries::report_query("tokens", "load_tokens_by_market_volume", start);
         result
     }
 
     /// Filters out tokens whose market volume is less than the specified limit (min_market_volume).
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn filter_tokens_by_market_volume(
         &mut self,
         tokens_to_check: Vec<TokenId>,
@@ -302,14 +330,12 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             result.insert(TokenId(0));
         }
 
-        metrics::histogram!(
-            "sql.token.load_token_ids_that_enabled_for_fees",
-            start.elapsed()
-        );
+        crate::slow_queries::report_query("tokens", "load_token_ids_that_enabled_for_fees", start);
         Ok(result)
     }
 
     /// Get the number of ERC20 tokens from Database
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn get_count(&mut self) -> QueryResult<u32> {
         let start = Instant::now();
         let count = sqlx::query!(
@@ -321,11 +347,12 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .await?
         .count;
 
-        metrics::histogram!("sql.token.get_count", start.elapsed());
+        crate::slow_queries::report_query("tokens", "get_count", start);
         Ok(count as u32)
     }
 
     /// Get the max token ID of ERC20 tokens from Database
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn get_max_erc20_token_id(&mut self) -> QueryResult<u32> {
         let start = Instant::now();
         let last_token_id = sqlx::query!(
@@ -338,11 +365,12 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .map(|token| token.id)
         .unwrap_or(0);
 
-        metrics::histogram!("sql.token.get_max_erc20_token_id", start.elapsed());
+        crate::slow_queries::report_query("tokens", "get_max_erc20_token_id", start);
         Ok(last_token_id as u32)
     }
 
     /// Get the max token ID of non-NFT tokens from Database
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn get_max_token_id(&mut self) -> QueryResult<u32> {
         let start = Instant::now();
         let last_token_id = sqlx::query!(
@@ -355,10 +383,11 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .map(|token| token.id)
         .unwrap_or(0);
 
-        metrics::histogram!("sql.token.get_max_token_id", start.elapsed());
+        crate::slow_queries::report_query("tokens", "get_max_token_id", start);
         Ok(last_token_id as u32)
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn get_nft(&mut self, token_id: TokenId) -> QueryResult<Option<NFT>> {
         let start = Instant::now();
         let db_token = sqlx::query_as!(
@@ -374,10 +403,11 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!("sql.token.get_nft", start.elapsed());
+        crate::slow_queries::report_query("tokens", "get_nft", start);
         Ok(db_token.map(|t| t.into()))
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn get_nft_with_factories(
         &mut self,
         token_id: TokenId,
@@ -404,11 +434,12 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!("sql.token.get_nft_with_factories", start.elapsed());
+        crate::slow_queries::report_query("tokens", "get_nft_with_factories", start);
         Ok(db_token.map(|t| t.into()))
     }
 
     /// Given the numeric token ID, symbol or address, returns token.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn get_token(&mut self, token_like: TokenLike) -> QueryResult<Option<Token>> {
         let start = Instant::now();
 
@@ -460,10 +491,11 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             }
         };
 
-        metrics::histogram!("sql.token.get_token", start.elapsed());
+        crate::slow_queries::report_query("tokens", "get_token", start);
         Ok(db_token.map(|t| t.into()))
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn get_token_market_volume(
         &mut self,
         token_id: TokenId,
@@ -484,10 +516,11 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.get_market_volume", start.elapsed());
+        crate::slow_queries::report_query("tokens", "get_market_volume", start);
         Ok(db_market_volume.map(|p| p.into()))
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn update_token_market_volume(
         &mut self,
         token_id: TokenId,
@@ -511,11 +544,12 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.update_market_volume", start.elapsed());
+        crate::slow_queries::report_query("tokens", "update_market_volume", start);
         Ok(())
     }
 
     /// Given token id, returns its price in USD and a timestamp of the last update.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn get_historical_ticker_price(
         &mut self,
         token_id: TokenId,
@@ -533,14 +567,15 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.get_historical_ticker_price", start.elapsed());
+        crate::slow_queries::report_query("tokens", "get_historical_ticker_price", start);
         Ok(db_price.map(|p| p.into()))
     }
 
-    /// Updates price in USD for the given token.
+    /// Updates price in USD for the given token and records it to the hourly price history.
     ///
     /// Note, that the price precision cannot be greater than `STORED_USD_PRICE_PRECISION`,
     /// so the number might get rounded.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn update_historical_ticker_price(
         &mut self,
         token_id: TokenId,
@@ -548,6 +583,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
     ) -> QueryResult<()> {
         let start = Instant::now();
         let usd_price_rounded = ratio_to_big_decimal(&price.usd_price, STORED_USD_PRICE_PRECISION);
+        let mut transaction = self.0.start_transaction().await?;
         sqlx::query!(
             r#"
             INSERT INTO ticker_price ( token_id, usd_price, last_updated )
@@ -560,13 +596,149 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             usd_price_rounded.clone(),
             price.last_updated
         )
+        .fetch_optional(transaction.conn())
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO ticker_price_history ( token_id, recorded_at, usd_price )
+            VALUES ( $1, date_trunc('hour', $2::timestamptz), $3 )
+            ON CONFLICT (token_id, recorded_at)
+            DO
+              UPDATE SET usd_price = $3
+            "#,
+            *token_id as i32,
+            price.last_updated,
+            usd_price_rounded
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("tokens", "update_historical_ticker_price", start);
+        Ok(())
+    }
+
+    /// Given token id, returns its price in USD as of the given moment, i.e. the last price
+    /// recorded to the hourly price history before it.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
+    pub async fn get_ticker_price_at(
+        &mut self,
+        token_id: TokenId,
+        moment: DateTime<Utc>,
+    ) -> QueryResult<Option<TokenPrice>> {
+        let start = Instant::now();
+        let db_price = sqlx::query_as!(
+            DbTickerPrice,
+            r#"
+            SELECT token_id, usd_price, recorded_at AS last_updated
+            FROM ticker_price_history
+            WHERE token_id = $1 AND recorded_at <= $2
+            ORDER BY recorded_at DESC
+            LIMIT 1
+            "#,
+            *token_id as i32,
+            moment
+        )
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.update_historical_ticker_price", start.elapsed());
+        crate::slow_queries::report_query("tokens", "get_ticker_price_at", start);
+        Ok(db_price.map(|p| p.into()))
+    }
+
+    /// Sets the manual price of the token, replacing the previous one.
+    ///
+    /// Note, that the price precision cannot be greater than `STORED_USD_PRICE_PRECISION`,
+    /// so the number might get rounded.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
+    pub async fn store_ticker_price_override(
+        &mut self,
+        token_id: TokenId,
+        usd_price: &Ratio<BigUint>,
+        reason: &str,
+        created_by: &str,
+        expires_at: DateTime<Utc>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let usd_price_rounded = ratio_to_big_decimal(usd_price, STORED_USD_PRICE_PRECISION);
+        sqlx::query!(
+            r#"
+            INSERT INTO ticker_price_overrides ( token_id, usd_price, reason, created_by, expires_at )
+            VALUES ( $1, $2, $3, $4, $5 )
+            ON CONFLICT (token_id)
+            DO
+              UPDATE SET usd_price = $2, reason = $3, created_by = $4, created_at = now(), expires_at = $5
+            "#,
+            *token_id as i32,
+            usd_price_rounded,
+            reason,
+            created_by,
+            expires_at
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tokens", "store_ticker_price_override", start);
         Ok(())
     }
 
+    /// Removes the manual price of the token. Returns `false` if there was none.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
+    pub async fn remove_ticker_price_override(&mut self, token_id: TokenId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let rows_affected = sqlx::query!(
+            "DELETE FROM ticker_price_overrides WHERE token_id = $1",
+            *token_id as i32
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        crate::slow_queries::report_query("tokens", "remove_ticker_price_override", start);
+        Ok(rows_affected > 0)
+    }
+
+    /// Loads the manual prices of the tokens, including the expired ones.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
+    pub async fn load_ticker_price_overrides(
+        &mut self,
+    ) -> QueryResult<Vec<StoredTickerPriceOverride>> {
+        let start = Instant::now();
+        let overrides = sqlx::query_as!(
+            StoredTickerPriceOverride,
+            "SELECT * FROM ticker_price_overrides ORDER BY token_id"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tokens", "load_ticker_price_overrides", start);
+        Ok(overrides)
+    }
+
+    /// Given token id, returns its manual price unless it has expired.
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
+    pub async fn get_ticker_price_override(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<TokenPrice>> {
+        let start = Instant::now();
+        let db_price = sqlx::query_as!(
+            DbTickerPrice,
+            r#"
+            SELECT token_id, usd_price, created_at AS last_updated
+            FROM ticker_price_overrides
+            WHERE token_id = $1 AND expires_at > now()
+            "#,
+            *token_id as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tokens", "get_ticker_price_override", start);
+        Ok(db_price.map(|p| p.into()))
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "tokens"))]
     pub async fn store_nft_factory(
         &mut self,
         creator_id: AccountId,
@@ -589,7 +761,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.store_nft_factory", start.elapsed());
+        crate::slow_queries::report_query("tokens", "store_nft_factory", start);
         Ok(())
     }
 }
@@ -82,6 +82,17 @@ pub struct DbTickerPrice {
     pub last_updated: DateTime<Utc>,
 }
 
+/// Manual token price set by the operator.
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredTickerPriceOverride {
+    pub token_id: i32,
+    pub usd_price: BigDecimal,
+    pub reason: String,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
 #[derive(Debug, FromRow)]
 pub struct StorageNFT {
     // Unique token id in zksync
@@ -0,0 +1,298 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+use zksync_types::{event::account::AccountStateChangeStatus, tx::TxHash, BlockNumber};
+// Local imports
+use self::records::{
+    PendingTxCallback, StoredTxCallback, StoredTxCallbackEndpoint, TxCallbackStatus,
+};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Status of the transaction reported when its block is committed or executed on L1.
+pub const TX_STATUS_COMMITTED: &str = "committed";
+pub const TX_STATUS_FINALIZED: &str = "finalized";
+/// Status of the failed transaction, reported when its block is committed.
+pub const TX_STATUS_REJECTED: &str = "rejected";
+
+/// Schema of the status callbacks of the transactions submitted with the API keys.
+///
+/// Callbacks are enqueued by `EthereumSchema::confirm_eth_tx` within the same database
+/// transaction as the block status change, and every status of the transaction is enqueued
+/// only once per scope. Delivered callbacks are kept, so the id of the callback can be used by
+/// the receivers to deduplicate the retried deliveries.
+#[derive(Debug)]
+pub struct TxCallbacksSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> TxCallbacksSchema<'a, 'c> {
+    /// Registers the callback endpoint of the scope, replacing the existing one.
+    /// Transactions submitted earlier keep being reported to the new endpoint.
+    #[tracing::instrument(skip_all, fields(schema = "tx_callbacks"))]
+    pub async fn set_endpoint(
+        &mut self,
+        scope: &str,
+        url: &str,
+        secret: &str,
+    ) -> QueryResult<StoredTxCallbackEndpoint> {
+        let start = Instant::now();
+        let endpoint = sqlx::query_as!(
+            StoredTxCallbackEndpoint,
+            "INSERT INTO tx_callback_endpoints (scope, url, secret)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (scope) DO UPDATE
+            SET (url, secret, created_at) = (EXCLUDED.url, EXCLUDED.secret, now())
+            RETURNING *",
+            scope,
+            url,
+            secret,
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tx_callbacks", "set_endpoint", start);
+        Ok(endpoint)
+    }
+
+    #[tracing::instrument(skip_all, fields(schema = "tx_callbacks"))]
+    pub async fn get_endpoint(
+        &mut self,
+        scope: &str,
+    ) -> QueryResult<Option<StoredTxCallbackEndpoint>> {
+        let start = Instant::now();
+        let endpoint = sqlx::query_as!(
+            StoredTxCallbackEndpoint,
+            "SELECT * FROM tx_callback_endpoints WHERE scope = $1",
+            scope,
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tx_callbacks", "get_endpoint", start);
+        Ok(endpoint)
+    }
+
+    /// Removes the endpoint along with the tracked transactions and the callbacks of the scope.
+    /// Returns `false` if the scope has no endpoint.
+    #[tracing::instrument(skip_all, fields(schema = "tx_callbacks"))]
+    pub async fn remove_endpoint(&mut self, scope: &str) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!("DELETE FROM tx_callback_endpoints WHERE scope = $1", scope)
+            .execute(self.0.conn())
+            .await?
+            .rows_affected()
+            > 0;
+
+        crate::slow_queries::report_query("tx_callbacks", "remove_endpoint", start);
+        Ok(removed)
+    }
+
+    /// Starts tracking the transactions submitted with the API key of the scope.
+    /// Does nothing and returns `false` if the scope has no endpoint.
+    #[tracing::instrument(skip_all, fields(schema = "tx_callbacks"))]
+    pub async fn track_txs(&mut self, scope: &str, tx_hashes: &[TxHash]) -> QueryResult<bool> {
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = tx_hashes
+            .iter()
+            .map(|tx_hash| tx_hash.as_ref().to_vec())
+            .collect();
+        let tracked = sqlx::query!(
+            "INSERT INTO tx_callback_txs (tx_hash, scope)
+            SELECT u.tx_hash, tx_callback_endpoints.scope
+                FROM UNNEST ($1::bytea[]) AS u(tx_hash)
+                INNER JOIN tx_callback_endpoints ON tx_callback_endpoints.scope = $2
+            ON CONFLICT DO NOTHING",
+            &tx_hashes,
+            scope,
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        crate::slow_queries::report_query("tx_callbacks", "track_txs", start);
+        Ok(tracked)
+    }
+
+    /// Stops tracking the transactions, e.g. the ones which were not accepted by the server.
+    #[tracing::instrument(skip_all, fields(schema = "tx_callbacks"))]
+    pub async fn untrack_txs(&mut self, scope: &str, tx_hashes: &[TxHash]) -> QueryResult<()> {
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = tx_hashes
+            .iter()
+            .map(|tx_hash| tx_hash.as_ref().to_vec())
+            .collect();
+        sqlx::query!(
+            "DELETE FROM tx_callback_txs WHERE scope = $1 AND tx_hash = ANY($2)",
+            scope,
+            &tx_hashes,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tx_callbacks", "untrack_txs", start);
+        Ok(())
+    }
+
+    /// Enqueues the callbacks for the tracked transactions of the blocks, which were committed
+    /// or executed on L1. Failed transactions are reported as rejected once their block is
+    /// committed. Tracking of the transactions is finished once their block is executed.
+    #[tracing::instrument(skip_all, fields(schema = "tx_callbacks"))]
+    pub async fn enqueue_block_callbacks(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        status: AccountStateChangeStatus,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let (tx_status, report_rejected) = match status {
+            AccountStateChangeStatus::Committed => (TX_STATUS_COMMITTED, true),
+            AccountStateChangeStatus::Finalized => (TX_STATUS_FINALIZED, false),
+        };
+        sqlx::query!(
+            "
+            INSERT INTO tx_callbacks (scope, tx_hash, tx_status, block_number, fail_reason, status)
+            SELECT tx_callback_txs.scope, executed_transactions.tx_hash,
+                CASE WHEN executed_transactions.success THEN $3 ELSE $4 END,
+                executed_transactions.block_number, executed_transactions.fail_reason, $5
+            FROM executed_transactions
+            INNER JOIN tx_callback_txs
+                ON tx_callback_txs.tx_hash = executed_transactions.tx_hash
+            WHERE executed_transactions.block_number BETWEEN $1 AND $2
+                AND (executed_transactions.success OR $6)
+            ON CONFLICT (scope, tx_hash, tx_status) DO NOTHING
+            ",
+            i64::from(*from_block),
+            i64::from(*to_block),
+            tx_status,
+            TX_STATUS_REJECTED,
+            TxCallbackStatus::Pending.to_string(),
+            report_rejected,
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        if status == AccountStateChangeStatus::Finalized {
+            sqlx::query!(
+                "
+                DELETE FROM tx_callback_txs
+                USING executed_transactions
+                WHERE tx_callback_txs.tx_hash = executed_transactions.tx_hash
+                    AND executed_transactions.block_number BETWEEN $1 AND $2
+                    AND executed_transactions.success
+                ",
+                i64::from(*from_block),
+                i64::from(*to_block),
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        crate::slow_queries::report_query("tx_callbacks", "enqueue_block_callbacks", start);
+        Ok(())
+    }
+
+    /// Loads the pending callbacks which should be attempted at the moment, oldest first.
+    #[tracing::instrument(skip_all, fields(schema = "tx_callbacks"))]
+    pub async fn load_due_callbacks(&mut self, limit: u32) -> QueryResult<Vec<PendingTxCallback>> {
+        let start = Instant::now();
+        let callbacks = sqlx::query_as!(
+            PendingTxCallback,
+            "
+            SELECT tx_callbacks.id, tx_callbacks.attempts,
+                tx_callback_endpoints.url, tx_callback_endpoints.secret,
+                tx_callbacks.tx_hash, tx_callbacks.tx_status, tx_callbacks.block_number,
+                tx_callbacks.fail_reason, tx_callbacks.created_at
+            FROM tx_callbacks
+            INNER JOIN tx_callback_endpoints
+                ON tx_callback_endpoints.scope = tx_callbacks.scope
+            WHERE tx_callbacks.status = $1 AND tx_callbacks.next_attempt_at <= now()
+            ORDER BY tx_callbacks.id ASC
+            LIMIT $2
+            ",
+            TxCallbackStatus::Pending.to_string(),
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tx_callbacks", "load_due_callbacks", start);
+        Ok(callbacks)
+    }
+
+    /// Marks the callback as delivered.
+    #[tracing::instrument(skip_all, fields(schema = "tx_callbacks"))]
+    pub async fn confirm_callback(&mut self, callback_id: i64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE tx_callbacks
+            SET status = $2, attempts = attempts + 1, updated_at = now()
+            WHERE id = $1",
+            callback_id,
+            TxCallbackStatus::Delivered.to_string(),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tx_callbacks", "confirm_callback", start);
+        Ok(())
+    }
+
+    /// Records the failed delivery attempt. The callback is retried at `retry_at`,
+    /// or moved to the dead letters if it's `None`.
+    #[tracing::instrument(skip_all, fields(schema = "tx_callbacks"))]
+    pub async fn fail_callback(
+        &mut self,
+        callback_id: i64,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let status = if retry_at.is_some() {
+            TxCallbackStatus::Pending
+        } else {
+            TxCallbackStatus::Dead
+        };
+        sqlx::query!(
+            "UPDATE tx_callbacks
+            SET status = $2, attempts = attempts + 1, last_error = $3,
+                next_attempt_at = COALESCE($4, next_attempt_at), updated_at = now()
+            WHERE id = $1",
+            callback_id,
+            status.to_string(),
+            error,
+            retry_at,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tx_callbacks", "fail_callback", start);
+        Ok(())
+    }
+
+    /// Loads the callbacks of the transaction enqueued for the scope, in the order of enqueueing.
+    #[tracing::instrument(skip_all, fields(schema = "tx_callbacks"))]
+    pub async fn get_tx_callbacks(
+        &mut self,
+        scope: &str,
+        tx_hash: TxHash,
+    ) -> QueryResult<Vec<StoredTxCallback>> {
+        let start = Instant::now();
+        let callbacks = sqlx::query_as!(
+            StoredTxCallback,
+            "SELECT * FROM tx_callbacks WHERE scope = $1 AND tx_hash = $2 ORDER BY id ASC",
+            scope,
+            tx_hash.as_ref(),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tx_callbacks", "get_tx_callbacks", start);
+        Ok(callbacks)
+    }
+}
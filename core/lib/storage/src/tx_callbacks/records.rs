@@ -0,0 +1,65 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredTxCallbackEndpoint {
+    pub scope: String,
+    pub url: String,
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredTxCallback {
+    pub id: i64,
+    pub scope: String,
+    pub tx_hash: Vec<u8>,
+    pub tx_status: String,
+    pub block_number: i64,
+    pub fail_reason: Option<String>,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Callback along with the endpoint data required to send it.
+#[derive(Debug, Clone, FromRow)]
+pub struct PendingTxCallback {
+    pub id: i64,
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+    pub tx_hash: Vec<u8>,
+    pub tx_status: String,
+    pub block_number: i64,
+    pub fail_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Status of the callback delivery. Delivered callbacks are kept, so the same status
+/// of the transaction is never enqueued twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxCallbackStatus {
+    /// Callback is waiting to be delivered (for the first time or after a failed attempt).
+    Pending,
+    /// Callback was accepted by the endpoint.
+    Delivered,
+    /// Callback couldn't be delivered within the allowed amount of attempts.
+    Dead,
+}
+
+impl ToString for TxCallbackStatus {
+    fn to_string(&self) -> String {
+        match self {
+            TxCallbackStatus::Pending => String::from("pending"),
+            TxCallbackStatus::Delivered => String::from("delivered"),
+            TxCallbackStatus::Dead => String::from("dead"),
+        }
+    }
+}
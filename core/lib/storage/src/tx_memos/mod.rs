@@ -0,0 +1,98 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+use self::records::{StoredTxMemo, TxMemo};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the memos attached to the transfers, e.g. the payment references which merchants
+/// use to reconcile the payments. Memos are stored alongside the transactions only and are not
+/// a part of the signed data.
+#[derive(Debug)]
+pub struct TxMemosSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> TxMemosSchema<'a, 'c> {
+    /// Stores the memos of the submitted transfers. The memo of the transaction which is
+    /// resubmitted after the rejection is replaced.
+    #[tracing::instrument(skip_all, fields(schema = "tx_memos"))]
+    pub async fn store_tx_memos(&mut self, memos: &[TxMemo]) -> QueryResult<()> {
+        if memos.is_empty() {
+            return Ok(());
+        }
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = memos
+            .iter()
+            .map(|memo| memo.tx_hash.as_ref().to_vec())
+            .collect();
+        let to_addresses: Vec<_> = memos
+            .iter()
+            .map(|memo| memo.to_address.as_bytes().to_vec())
+            .collect();
+        let texts: Vec<_> = memos.iter().map(|memo| memo.memo.clone()).collect();
+        sqlx::query!(
+            "INSERT INTO tx_memos (tx_hash, to_address, memo)
+            SELECT u.tx_hash, u.to_address, u.memo
+            FROM UNNEST($1::bytea[], $2::bytea[], $3::text[]) AS u(tx_hash, to_address, memo)
+            ON CONFLICT (tx_hash) DO UPDATE
+            SET (to_address, memo, created_at) = (EXCLUDED.to_address, EXCLUDED.memo, now())",
+            &tx_hashes,
+            &to_addresses,
+            &texts,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tx_memos", "store_tx_memos", start);
+        Ok(())
+    }
+
+    /// Loads the memos of the given transactions, transactions without the memo are skipped.
+    #[tracing::instrument(skip_all, fields(schema = "tx_memos"))]
+    pub async fn get_tx_memos(&mut self, tx_hashes: &[TxHash]) -> QueryResult<Vec<StoredTxMemo>> {
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = tx_hashes
+            .iter()
+            .map(|tx_hash| tx_hash.as_ref().to_vec())
+            .collect();
+        let memos = sqlx::query_as!(
+            StoredTxMemo,
+            "SELECT * FROM tx_memos WHERE tx_hash = ANY($1)",
+            &tx_hashes,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tx_memos", "get_tx_memos", start);
+        Ok(memos)
+    }
+
+    /// Looks up the transfers to the `to_address` with the given memo, the latest first.
+    #[tracing::instrument(skip_all, fields(schema = "tx_memos"))]
+    pub async fn find_tx_memos(
+        &mut self,
+        to_address: Address,
+        memo: &str,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredTxMemo>> {
+        let start = Instant::now();
+        let memos = sqlx::query_as!(
+            StoredTxMemo,
+            "SELECT * FROM tx_memos
+            WHERE to_address = $1 AND memo = $2
+            ORDER BY created_at DESC
+            LIMIT $3",
+            to_address.as_bytes(),
+            memo,
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("tx_memos", "find_tx_memos", start);
+        Ok(memos)
+    }
+}
@@ -0,0 +1,22 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+
+/// Memo attached to the submitted transfer.
+#[derive(Debug, Clone)]
+pub struct TxMemo {
+    pub tx_hash: TxHash,
+    pub to_address: Address,
+    pub memo: String,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredTxMemo {
+    pub tx_hash: Vec<u8>,
+    pub to_address: Vec<u8>,
+    pub memo: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,191 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{tx::TxHash, BlockNumber, H256};
+// Local imports
+use self::records::{NewWithdrawalHook, StoredWithdrawalHook};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Withdrawal is not executed on L1 yet.
+pub const HOOK_STATUS_PENDING: &str = "pending";
+/// Withdrawal is executed on L1, the call is waiting to be sent.
+pub const HOOK_STATUS_READY: &str = "ready";
+/// Call is sent, but not confirmed yet.
+pub const HOOK_STATUS_SENT: &str = "sent";
+/// Call is confirmed.
+pub const HOOK_STATUS_SUCCEEDED: &str = "succeeded";
+/// Call is reverted or is not confirmed in time.
+pub const HOOK_STATUS_FAILED: &str = "failed";
+/// Withdrawal has failed, so there is nothing to notify about.
+pub const HOOK_STATUS_CANCELLED: &str = "cancelled";
+
+/// Schema of the calls made to the recipient contracts once the withdrawals are executed on L1.
+///
+/// Hooks are attached to the withdrawals on submission. Once the block with the withdrawal
+/// is executed, `eth_sender` marks the hook ready and the hook sender makes the calls one by one
+/// from its own account, so the failing calls don't affect the operator transactions.
+#[derive(Debug)]
+pub struct WithdrawalHooksSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> WithdrawalHooksSchema<'a, 'c> {
+    /// Stores the hooks of the submitted withdrawals. The hook of the transaction which is
+    /// resubmitted after the rejection is replaced.
+    #[tracing::instrument(skip_all, fields(schema = "withdrawal_hooks"))]
+    pub async fn store_withdrawal_hooks(&mut self, hooks: &[NewWithdrawalHook]) -> QueryResult<()> {
+        if hooks.is_empty() {
+            return Ok(());
+        }
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = hooks
+            .iter()
+            .map(|hook| hook.tx_hash.as_ref().to_vec())
+            .collect();
+        let recipients: Vec<_> = hooks
+            .iter()
+            .map(|hook| hook.recipient.as_bytes().to_vec())
+            .collect();
+        let calldata: Vec<_> = hooks.iter().map(|hook| hook.calldata.clone()).collect();
+        sqlx::query!(
+            "INSERT INTO withdrawal_hooks (tx_hash, recipient, calldata)
+            SELECT u.tx_hash, u.recipient, u.calldata
+            FROM UNNEST($1::bytea[], $2::bytea[], $3::bytea[]) AS u(tx_hash, recipient, calldata)
+            ON CONFLICT (tx_hash) DO UPDATE
+            SET (recipient, calldata, created_at, updated_at) =
+                (EXCLUDED.recipient, EXCLUDED.calldata, now(), now())
+            WHERE withdrawal_hooks.status = 'pending'",
+            &tx_hashes,
+            &recipients,
+            &calldata,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("withdrawal_hooks", "store_withdrawal_hooks", start);
+        Ok(())
+    }
+
+    /// Loads the hooks of the given transactions, transactions without the hook are skipped.
+    #[tracing::instrument(skip_all, fields(schema = "withdrawal_hooks"))]
+    pub async fn get_withdrawal_hooks(
+        &mut self,
+        tx_hashes: &[TxHash],
+    ) -> QueryResult<Vec<StoredWithdrawalHook>> {
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = tx_hashes
+            .iter()
+            .map(|tx_hash| tx_hash.as_ref().to_vec())
+            .collect();
+        let hooks = sqlx::query_as!(
+            StoredWithdrawalHook,
+            "SELECT * FROM withdrawal_hooks WHERE tx_hash = ANY($1)",
+            &tx_hashes,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("withdrawal_hooks", "get_withdrawal_hooks", start);
+        Ok(hooks)
+    }
+
+    /// Marks the hooks of the withdrawals in the executed blocks ready to be sent, the hooks of
+    /// the failed withdrawals are cancelled. Called by `eth_sender` along with the confirmation
+    /// of the blocks execution.
+    #[tracing::instrument(skip_all, fields(schema = "withdrawal_hooks"))]
+    pub async fn schedule_withdrawal_hooks(
+        &mut self,
+        first_block: BlockNumber,
+        last_block: BlockNumber,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let scheduled = sqlx::query!(
+            "UPDATE withdrawal_hooks
+            SET status = CASE WHEN executed_transactions.success THEN 'ready' ELSE 'cancelled' END,
+                block_number = executed_transactions.block_number,
+                fail_reason = executed_transactions.fail_reason,
+                updated_at = now()
+            FROM executed_transactions
+            WHERE withdrawal_hooks.tx_hash = executed_transactions.tx_hash
+                AND withdrawal_hooks.status = 'pending'
+                AND executed_transactions.block_number BETWEEN $1 AND $2",
+            i64::from(*first_block),
+            i64::from(*last_block),
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        crate::slow_queries::report_query("withdrawal_hooks", "schedule_withdrawal_hooks", start);
+        Ok(scheduled)
+    }
+
+    /// Loads the earliest hook which is ready or is already sent, but not confirmed yet,
+    /// so the sender continues with it after the restart.
+    #[tracing::instrument(skip_all, fields(schema = "withdrawal_hooks"))]
+    pub async fn load_next_withdrawal_hook(&mut self) -> QueryResult<Option<StoredWithdrawalHook>> {
+        let start = Instant::now();
+        let hook = sqlx::query_as!(
+            StoredWithdrawalHook,
+            "SELECT * FROM withdrawal_hooks
+            WHERE status IN ('ready', 'sent')
+            ORDER BY status = 'sent' DESC, block_number, created_at
+            LIMIT 1",
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("withdrawal_hooks", "load_next_withdrawal_hook", start);
+        Ok(hook)
+    }
+
+    /// Stores the hash of the sent call. Must be called before sending the transaction,
+    /// so it's not sent twice.
+    #[tracing::instrument(skip_all, fields(schema = "withdrawal_hooks"))]
+    pub async fn mark_withdrawal_hook_sent(
+        &mut self,
+        tx_hash: TxHash,
+        eth_tx_hash: H256,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE withdrawal_hooks SET status = 'sent', eth_tx_hash = $2, updated_at = now()
+            WHERE tx_hash = $1",
+            tx_hash.as_ref(),
+            eth_tx_hash.as_bytes(),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("withdrawal_hooks", "mark_withdrawal_hook_sent", start);
+        Ok(())
+    }
+
+    /// Completes the sent hook, the failure reason is set for the failed calls.
+    #[tracing::instrument(skip_all, fields(schema = "withdrawal_hooks"))]
+    pub async fn complete_withdrawal_hook(
+        &mut self,
+        tx_hash: TxHash,
+        fail_reason: Option<&str>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let status = if fail_reason.is_some() {
+            HOOK_STATUS_FAILED
+        } else {
+            HOOK_STATUS_SUCCEEDED
+        };
+        sqlx::query!(
+            "UPDATE withdrawal_hooks SET status = $2, fail_reason = $3, updated_at = now()
+            WHERE tx_hash = $1",
+            tx_hash.as_ref(),
+            status,
+            fail_reason,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("withdrawal_hooks", "complete_withdrawal_hook", start);
+        Ok(())
+    }
+}
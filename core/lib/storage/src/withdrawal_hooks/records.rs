@@ -0,0 +1,27 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+
+/// Call to the recipient contract attached to the submitted withdrawal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewWithdrawalHook {
+    pub tx_hash: TxHash,
+    pub recipient: Address,
+    pub calldata: Vec<u8>,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredWithdrawalHook {
+    pub tx_hash: Vec<u8>,
+    pub recipient: Vec<u8>,
+    pub calldata: Vec<u8>,
+    pub status: String,
+    pub block_number: Option<i64>,
+    pub eth_tx_hash: Option<Vec<u8>>,
+    pub fail_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,128 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+use self::records::{StoredWithdrawalPolicyDecision, WithdrawalPolicyDecision};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the decisions of the withdrawal policies applied on the transactions submission,
+/// e.g. the daily withdrawal limits of the addresses.
+#[derive(Debug)]
+pub struct WithdrawalPolicySchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> WithdrawalPolicySchema<'a, 'c> {
+    /// Serializes the policy checks of the withdrawals from the address until the end of
+    /// the current transaction, so the concurrent withdrawals can't exceed the daily limit.
+    #[tracing::instrument(skip_all, fields(schema = "withdrawal_policy"))]
+    pub async fn lock_address(&mut self, address: Address) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"SELECT 1 AS "locked!" FROM pg_advisory_xact_lock(hashtext('withdrawal_policy'), hashtext(encode($1, 'hex')))"#,
+            address.as_bytes()
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("withdrawal_policy", "lock_address", start);
+        Ok(())
+    }
+
+    /// Stores the decision on the submitted withdrawal.
+    /// The decision already stored for the same transaction is kept.
+    #[tracing::instrument(skip_all, fields(schema = "withdrawal_policy"))]
+    pub async fn store_decision(&mut self, decision: &WithdrawalPolicyDecision) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO withdrawal_policy_decisions (tx_hash, address, token_id, amount, amount_usd_scale6, allowed, reason)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (tx_hash) DO NOTHING",
+            decision.tx_hash.as_ref(),
+            decision.address.as_bytes(),
+            decision.token_id.0 as i32,
+            decision.amount.clone(),
+            decision.amount_usd_scaled as i64,
+            decision.allowed,
+            decision.reason,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("withdrawal_policy", "store_decision", start);
+        Ok(())
+    }
+
+    /// Loads the decision on the withdrawal of the transaction.
+    #[tracing::instrument(skip_all, fields(schema = "withdrawal_policy"))]
+    pub async fn get_decision(
+        &mut self,
+        tx_hash: TxHash,
+    ) -> QueryResult<Option<StoredWithdrawalPolicyDecision>> {
+        let start = Instant::now();
+        let decision = sqlx::query_as!(
+            StoredWithdrawalPolicyDecision,
+            "SELECT * FROM withdrawal_policy_decisions WHERE tx_hash = $1",
+            tx_hash.as_ref(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("withdrawal_policy", "get_decision", start);
+        Ok(decision)
+    }
+
+    /// Returns the total value of the withdrawals from the address allowed since the given time,
+    /// in USD scaled by `SUBSIDY_USD_AMOUNTS_SCALE`.
+    #[tracing::instrument(skip_all, fields(schema = "withdrawal_policy"))]
+    pub async fn get_withdrawn_usd(
+        &mut self,
+        address: Address,
+        since: DateTime<Utc>,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let total = sqlx::query!(
+            r#"SELECT COALESCE(SUM(amount_usd_scale6), 0)::BIGINT AS "total!"
+            FROM withdrawal_policy_decisions
+            WHERE address = $1 AND created_at > $2 AND allowed"#,
+            address.as_bytes(),
+            since,
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .total;
+
+        crate::slow_queries::report_query("withdrawal_policy", "get_withdrawn_usd", start);
+        Ok(total as u64)
+    }
+
+    /// Loads the decisions on the withdrawals from the address stored before the one with
+    /// the `before` id (or the latest ones if it's `None`), newest first.
+    #[tracing::instrument(skip_all, fields(schema = "withdrawal_policy"))]
+    pub async fn load_decisions(
+        &mut self,
+        address: Address,
+        before: Option<i64>,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredWithdrawalPolicyDecision>> {
+        let start = Instant::now();
+        let decisions = sqlx::query_as!(
+            StoredWithdrawalPolicyDecision,
+            "SELECT * FROM withdrawal_policy_decisions
+            WHERE address = $1 AND ($2::bigint IS NULL OR id < $2)
+            ORDER BY id DESC
+            LIMIT $3",
+            address.as_bytes(),
+            before,
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        crate::slow_queries::report_query("withdrawal_policy", "load_decisions", start);
+        Ok(decisions)
+    }
+}
@@ -0,0 +1,34 @@
+// External imports
+use chrono::prelude::*;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+use zksync_types::{tx::TxHash, Address, TokenId};
+// Local imports
+
+/// Decision of the withdrawal policies on the submitted withdrawal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithdrawalPolicyDecision {
+    pub tx_hash: TxHash,
+    pub address: Address,
+    pub token_id: TokenId,
+    pub amount: BigDecimal,
+    /// Value of the withdrawal in USD, scaled by `SUBSIDY_USD_AMOUNTS_SCALE`.
+    pub amount_usd_scaled: u64,
+    pub allowed: bool,
+    /// Reason of the rejection, or the note on the accepted withdrawal (e.g. the policy hook
+    /// was not available).
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredWithdrawalPolicyDecision {
+    pub id: i64,
+    pub tx_hash: Vec<u8>,
+    pub address: Vec<u8>,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub amount_usd_scale6: i64,
+    pub allowed: bool,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -1,8 +1,10 @@
+use crate::outbox::OutboxSchema;
 use crate::withdrawals::records::{ExtendedFinalizedWithdrawal, PendingWithdrawal};
 use crate::{BigDecimal, QueryResult, StorageProcessor};
 use num::{BigUint, Zero};
 use std::str::FromStr;
 use std::time::Instant;
+use zksync_types::event::outbox::{OutboxEvent, WithdrawalFinalizedEvent};
 use zksync_types::withdrawals::{WithdrawalEvent, WithdrawalPendingEvent};
 use zksync_types::H256;
 use zksync_utils::biguint_to_big_decimal;
@@ -12,6 +14,7 @@ pub mod records;
 pub struct WithdrawalsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> WithdrawalsSchema<'a, 'c> {
+    #[tracing::instrument(skip_all, fields(schema = "withdrawals"))]
     pub async fn save_pending_withdrawals(
         &mut self,
         withdrawals: &[WithdrawalPendingEvent],
@@ -40,10 +43,11 @@ impl<'a, 'c> WithdrawalsSchema<'a, 'c> {
         }
         transaction.commit().await?;
 
-        metrics::histogram!("sql.withdrawals.save_pending_withdrawals", start.elapsed());
+        crate::slow_queries::report_query("withdrawals", "save_pending_withdrawals", start);
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "withdrawals"))]
     pub async fn finalize_withdrawal(&mut self, withdrawal: &WithdrawalEvent) -> QueryResult<()> {
         let mut transaction = self.0.start_transaction().await?;
         // Try to find this log in already processed logs
@@ -75,8 +79,8 @@ impl<'a, 'c> WithdrawalsSchema<'a, 'c> {
         .fetch_all(transaction.conn())
         .await?;
 
-        let withdrawal_amount =
-            biguint_to_big_decimal(BigUint::from_str(&withdrawal.amount.to_string()).unwrap());
+        let finalized_amount = BigUint::from_str(&withdrawal.amount.to_string()).unwrap();
+        let withdrawal_amount = biguint_to_big_decimal(finalized_amount.clone());
         let mut amount = BigDecimal::zero();
         for pending_withdrawal in pending_withdrawals {
             let mut remaining_amount = pending_withdrawal.remaining_amount;
@@ -121,11 +125,25 @@ impl<'a, 'c> WithdrawalsSchema<'a, 'c> {
                 withdrawal_amount
             );
         }
+
+        if !amount.is_zero() {
+            let event = WithdrawalFinalizedEvent {
+                recipient: withdrawal.recipient,
+                token_id: withdrawal.token_id,
+                amount: finalized_amount,
+                eth_tx_hash: withdrawal.tx_hash,
+                eth_block: withdrawal.block_number,
+            };
+            OutboxSchema(&mut transaction)
+                .store_outbox_events(&[OutboxEvent::WithdrawalFinalized(event)])
+                .await?;
+        }
         transaction.commit().await?;
 
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(schema = "withdrawals"))]
     pub async fn get_finalized_withdrawals(
         &mut self,
         tx_hash: H256,
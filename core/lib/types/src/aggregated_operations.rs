@@ -74,6 +74,59 @@ pub struct BlocksCreateProofOperation {
     pub proofs_to_pad: usize,
 }
 
+impl BlocksCreateProofOperation {
+    /// Returns the amount of block proofs in the aggregated proof, including the padding ones.
+    pub fn aggregated_proof_size(&self) -> usize {
+        self.blocks.len() + self.proofs_to_pad
+    }
+
+    pub fn block_range(&self) -> (BlockNumber, BlockNumber) {
+        let BlocksCreateProofOperation { blocks, .. } = self;
+        (
+            blocks.first().map(|c| c.block_number).unwrap_or_default(),
+            blocks.last().map(|c| c.block_number).unwrap_or_default(),
+        )
+    }
+}
+
+/// Reason for which the aggregated proof creation was started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProofAggregationTrigger {
+    /// There are enough block proofs to fill the largest aggregated proof.
+    Full,
+    /// Block proofs fill the configured minimal part of the largest aggregated proof.
+    MinFill,
+    /// One of the blocks waits for the aggregation longer than allowed.
+    Deadline,
+    /// Fast processing was requested for one of the blocks.
+    FastProcessing,
+}
+
+impl std::string::ToString for ProofAggregationTrigger {
+    fn to_string(&self) -> String {
+        match self {
+            ProofAggregationTrigger::Full => "Full".to_owned(),
+            ProofAggregationTrigger::MinFill => "MinFill".to_owned(),
+            ProofAggregationTrigger::Deadline => "Deadline".to_owned(),
+            ProofAggregationTrigger::FastProcessing => "FastProcessing".to_owned(),
+        }
+    }
+}
+
+impl std::str::FromStr for ProofAggregationTrigger {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Full" => Ok(Self::Full),
+            "MinFill" => Ok(Self::MinFill),
+            "Deadline" => Ok(Self::Deadline),
+            "FastProcessing" => Ok(Self::FastProcessing),
+            _ => Err("Incorrect proof aggregation trigger".to_owned()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlocksProofOperation {
     pub blocks: Vec<Block>,
@@ -193,12 +246,7 @@ impl AggregatedOperation {
     pub fn get_block_range(&self) -> (BlockNumber, BlockNumber) {
         match self {
             AggregatedOperation::CommitBlocks(op) => op.block_range(),
-            AggregatedOperation::CreateProofBlocks(BlocksCreateProofOperation {
-                blocks, ..
-            }) => (
-                blocks.first().map(|c| c.block_number).unwrap_or_default(),
-                blocks.last().map(|c| c.block_number).unwrap_or_default(),
-            ),
+            AggregatedOperation::CreateProofBlocks(op) => op.block_range(),
             AggregatedOperation::PublishProofBlocksOnchain(op) => op.block_range(),
             AggregatedOperation::ExecuteBlocks(op) => op.block_range(),
         }
@@ -0,0 +1,82 @@
+//! Proof that an operation is a part of the public data committed on L1.
+
+use super::Block;
+use crate::{tx::TxHash, BlockNumber};
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::H256;
+use zksync_crypto::params::CHUNK_BYTES;
+use zksync_utils::{BytesToHexSerde, ZeroxPrefix};
+
+/// Proof that the public data of an operation is a part of the block commitment.
+///
+/// The commitment is `sha256(header_hash || public_data || onchain_op_commitment)`, so the whole
+/// public data of the block is required to recompute it: a SHA-256 preimage can't be opened partially.
+/// The commitment itself is stored by the contract as a part of the committed block info.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxInclusionProof {
+    pub block_number: BlockNumber,
+    pub block_index: u32,
+    pub commitment: H256,
+    /// Hash of the block number, the fee account, the old and new state roots and the timestamp.
+    pub header_hash: H256,
+    #[serde(with = "BytesToHexSerde::<ZeroxPrefix>")]
+    pub public_data: Vec<u8>,
+    #[serde(with = "BytesToHexSerde::<ZeroxPrefix>")]
+    pub onchain_op_commitment: Vec<u8>,
+    /// Position of the operation in the public data, in bytes.
+    pub public_data_offset: u32,
+    pub public_data_size: u32,
+}
+
+impl TxInclusionProof {
+    /// Returns the public data of the proven operation, `None` if the offsets are out of the block.
+    pub fn op_public_data(&self) -> Option<&[u8]> {
+        let start = self.public_data_offset as usize;
+        let end = start.checked_add(self.public_data_size as usize)?;
+        self.public_data.get(start..end)
+    }
+
+    /// Checks that the operation with the given public data is located at the chunk boundary
+    /// of the block public data, and that the public data hashes into the commitment.
+    pub fn verify(&self, op_public_data: &[u8]) -> bool {
+        self.public_data_offset as usize % CHUNK_BYTES == 0
+            && self.op_public_data() == Some(op_public_data)
+            && Block::commitment_from_parts(
+                self.header_hash,
+                &self.public_data,
+                &self.onchain_op_commitment,
+            ) == self.commitment
+    }
+}
+
+impl Block {
+    /// Creates the proof that the successfully executed operation with the given hash is a part
+    /// of the block public data. Returns `None` if there is no such operation in the block.
+    pub fn tx_inclusion_proof(
+        &self,
+        previous_block_root_hash: H256,
+        tx_hash: TxHash,
+    ) -> Option<TxInclusionProof> {
+        let (op, public_data_offset) = self
+            .get_public_data_offsets()
+            .into_iter()
+            .find(|(op, _)| op.tx_hash() == tx_hash)?;
+        Some(TxInclusionProof {
+            block_number: self.block_number,
+            block_index: op.block_index().unwrap_or_default(),
+            commitment: self.block_commitment,
+            header_hash: Block::get_header_hash(
+                self.block_number,
+                self.fee_account,
+                previous_block_root_hash,
+                self.get_eth_encoded_root(),
+                self.timestamp,
+            ),
+            public_data: self.get_eth_public_data(),
+            onchain_op_commitment: self.get_onchain_op_commitment(),
+            public_data_offset,
+            public_data_size: op.get_eth_public_data().len() as u32,
+        })
+    }
+}
@@ -1,7 +1,10 @@
 //! zkSync network block definition.
 
 use super::{AccountId, BlockNumber, Fr, PriorityOp, ZkSyncOp};
-use crate::{tx::error::CloseOperationsDisabled, SignedZkSyncTx, TokenId};
+use crate::{
+    tx::{error::CloseOperationsDisabled, TxHash},
+    SignedZkSyncTx, TokenId,
+};
 use chrono::Utc;
 use chrono::{DateTime, TimeZone};
 use parity_crypto::digest::sha256;
@@ -13,8 +16,10 @@ use zksync_crypto::franklin_crypto::bellman::pairing::ff::{PrimeField, PrimeFiel
 use zksync_crypto::params::{CHUNK_BIT_WIDTH, CHUNK_BYTES};
 use zksync_crypto::serialization::FrSerde;
 
+mod inclusion_proof;
 mod incomplete_block;
 
+pub use inclusion_proof::TxInclusionProof;
 pub use incomplete_block::IncompleteBlock;
 
 /// An intermediate state of the block in the zkSync network.
@@ -168,6 +173,20 @@ impl ExecutedOperations {
     pub fn is_priority(&self) -> bool {
         matches!(self, Self::PriorityOp(_))
     }
+
+    pub fn tx_hash(&self) -> TxHash {
+        match self {
+            ExecutedOperations::Tx(tx) => tx.signed_tx.tx.hash(),
+            ExecutedOperations::PriorityOp(op) => op.priority_op.tx_hash(),
+        }
+    }
+
+    pub fn block_index(&self) -> Option<u32> {
+        match self {
+            ExecutedOperations::Tx(tx) => tx.block_index,
+            ExecutedOperations::PriorityOp(op) => Some(op.block_index),
+        }
+    }
 }
 
 /// zkSync network block.
@@ -337,6 +356,21 @@ impl Block {
         executed_tx_pub_data
     }
 
+    /// Returns the operations included into the public data along with their offsets in it, in bytes.
+    /// Failed transactions have no public data and are skipped.
+    pub fn get_public_data_offsets(&self) -> Vec<(&ExecutedOperations, u32)> {
+        let mut public_data_offset = 0;
+        self.block_transactions
+            .iter()
+            .filter_map(|op| {
+                let executed_op = op.get_executed_op()?;
+                let offset = public_data_offset;
+                public_data_offset += (CHUNK_BYTES * executed_op.chunks()) as u32;
+                Some((op, offset))
+            })
+            .collect()
+    }
+
     /// Returns eth_witness data and data_size for each operation that has it.
     pub fn get_eth_witness_data(&self) -> (Vec<u8>, Vec<u64>) {
         let mut eth_witness = Vec::new();
@@ -454,6 +488,24 @@ impl Block {
         timestamp: u64,
         onchain_op_commitment: &[u8],
         public_data: &[u8],
+    ) -> H256 {
+        let header_hash = Self::get_header_hash(
+            block_number,
+            fee_account,
+            old_state_hash,
+            new_state_hash,
+            timestamp,
+        );
+        Self::commitment_from_parts(header_hash, public_data, onchain_op_commitment)
+    }
+
+    /// Hashes the fields of the block which precede the public data in the commitment.
+    fn get_header_hash(
+        block_number: BlockNumber,
+        fee_account: AccountId,
+        old_state_hash: H256,
+        new_state_hash: H256,
+        timestamp: u64,
     ) -> H256 {
         let mut hash_arg = vec![0u8; 64];
         U256::from(*block_number).to_big_endian(&mut hash_arg[0..32]);
@@ -468,8 +520,15 @@ impl Block {
 
         hash_arg.resize(64, 0u8);
         U256::from(timestamp).to_big_endian(&mut hash_arg[32..]);
-        hash_arg = sha256(&hash_arg).to_vec();
+        H256::from_slice(&sha256(&hash_arg))
+    }
 
+    fn commitment_from_parts(
+        header_hash: H256,
+        public_data: &[u8],
+        onchain_op_commitment: &[u8],
+    ) -> H256 {
+        let mut hash_arg = header_hash.as_bytes().to_vec();
         hash_arg.extend_from_slice(public_data);
         hash_arg.extend_from_slice(onchain_op_commitment);
         H256::from_slice(&sha256(&hash_arg))
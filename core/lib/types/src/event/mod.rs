@@ -1,6 +1,6 @@
 // Built-in uses
 // External uses
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 // Workspace uses
 use zksync_basic_types::BlockNumber;
 // Local uses
@@ -10,11 +10,12 @@ pub use crate::EventId;
 
 pub mod account;
 pub mod block;
+pub mod outbox;
 pub mod transaction;
 
 pub mod test_data;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "type", content = "data")]
 pub enum EventData {
@@ -23,9 +24,14 @@ pub enum EventData {
     Transaction(TransactionEvent),
 }
 
+/// Version of the events layout exposed to the external consumers (WebSocket subscribers,
+/// message brokers, events API). Must be increased on every breaking change of the
+/// [`ZkSyncEvent`] serialization.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
 // An event that happened in the zkSync network.
 // Only created by the `storage`.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ZkSyncEvent {
     // Id of the event. This value is equal to
     // the id of the corresponding row in the database.
@@ -35,3 +41,23 @@ pub struct ZkSyncEvent {
     #[serde(flatten)]
     pub data: EventData,
 }
+
+/// Event in the form it's exposed to the external consumers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionedEvent {
+    pub schema_version: u32,
+    /// Id of the event, increases monotonically. May be used by consumers for deduplication.
+    pub event_id: EventId,
+    #[serde(flatten)]
+    pub event: ZkSyncEvent,
+}
+
+impl From<ZkSyncEvent> for VersionedEvent {
+    fn from(event: ZkSyncEvent) -> Self {
+        Self {
+            schema_version: EVENT_SCHEMA_VERSION,
+            event_id: event.id,
+            event,
+        }
+    }
+}
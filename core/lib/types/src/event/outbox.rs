@@ -0,0 +1,209 @@
+//! Events stored in the durable event outbox.
+//!
+//! Outbox events are written in the same database transaction as the change they describe,
+//! so no event can be lost or emitted for a change that was rolled back. They are delivered
+//! to the external consumers (e.g. webhook subscribers) asynchronously.
+
+// Built-in uses
+use std::str::FromStr;
+// External uses
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+// Workspace uses
+use zksync_basic_types::{Address, BlockNumber, TokenId, H256};
+use zksync_crypto::convert::FeConvert;
+use zksync_utils::BigUintSerdeAsRadix10Str;
+// Local uses
+use crate::{block::Block, tx::TxHash, ExecutedOperations, Token};
+
+/// Type of the outbox event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxEventType {
+    NewBlock,
+    TransactionExecuted,
+    WithdrawalFinalized,
+    TokenListed,
+    AddressAttested,
+}
+
+impl OutboxEventType {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::NewBlock,
+            Self::TransactionExecuted,
+            Self::WithdrawalFinalized,
+            Self::TokenListed,
+            Self::AddressAttested,
+        ]
+    }
+}
+
+impl ToString for OutboxEventType {
+    fn to_string(&self) -> String {
+        match self {
+            OutboxEventType::NewBlock => String::from("new_block"),
+            OutboxEventType::TransactionExecuted => String::from("transaction_executed"),
+            OutboxEventType::WithdrawalFinalized => String::from("withdrawal_finalized"),
+            OutboxEventType::TokenListed => String::from("token_listed"),
+            OutboxEventType::AddressAttested => String::from("address_attested"),
+        }
+    }
+}
+
+impl FromStr for OutboxEventType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "new_block" => Ok(Self::NewBlock),
+            "transaction_executed" => Ok(Self::TransactionExecuted),
+            "withdrawal_finalized" => Ok(Self::WithdrawalFinalized),
+            "token_listed" => Ok(Self::TokenListed),
+            "address_attested" => Ok(Self::AddressAttested),
+            _ => Err(format!("Incorrect outbox event type: {}", s)),
+        }
+    }
+}
+
+/// Block was sealed and its root hash was calculated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewBlockEvent {
+    pub block_number: BlockNumber,
+    pub new_root_hash: String,
+    pub block_size: usize,
+    pub timestamp: u64,
+    pub transactions_count: usize,
+}
+
+/// Transaction or priority operation was executed in the sealed block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionExecutedEvent {
+    pub tx_hash: TxHash,
+    pub block_number: BlockNumber,
+    pub block_index: Option<u32>,
+    pub tx_type: String,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+}
+
+/// Withdrawal was finalized on the Ethereum side.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WithdrawalFinalizedEvent {
+    pub recipient: Address,
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    pub eth_tx_hash: H256,
+    pub eth_block: u64,
+}
+
+/// New token was added to the network.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenListedEvent {
+    #[serde(flatten)]
+    pub token: Token,
+}
+
+/// User has proved the control of the address, e.g. for the KYC workflow of the operator.
+/// The reference is an opaque identifier the operator matches with its own records.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressAttestedEvent {
+    pub address: Address,
+    pub reference: Option<String>,
+    /// Timestamp of the attestation signed by the user, in milliseconds.
+    pub signed_at: i64,
+}
+
+/// An event stored in the outbox.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "type", content = "data")]
+pub enum OutboxEvent {
+    NewBlock(NewBlockEvent),
+    TransactionExecuted(TransactionExecutedEvent),
+    WithdrawalFinalized(WithdrawalFinalizedEvent),
+    TokenListed(TokenListedEvent),
+    AddressAttested(AddressAttestedEvent),
+}
+
+impl OutboxEvent {
+    pub fn event_type(&self) -> OutboxEventType {
+        match self {
+            OutboxEvent::NewBlock(_) => OutboxEventType::NewBlock,
+            OutboxEvent::TransactionExecuted(_) => OutboxEventType::TransactionExecuted,
+            OutboxEvent::WithdrawalFinalized(_) => OutboxEventType::WithdrawalFinalized,
+            OutboxEvent::TokenListed(_) => OutboxEventType::TokenListed,
+            OutboxEvent::AddressAttested(_) => OutboxEventType::AddressAttested,
+        }
+    }
+
+    /// Creates events for the sealed block and the operations executed in it.
+    pub fn from_block(block: &Block) -> Vec<Self> {
+        let new_block = Self::NewBlock(NewBlockEvent {
+            block_number: block.block_number,
+            new_root_hash: format!("sync-bl:{}", block.new_root_hash.to_hex()),
+            block_size: block.block_chunks_size,
+            timestamp: block.timestamp,
+            transactions_count: block.block_transactions.len(),
+        });
+        let executed_operations = block.block_transactions.iter().map(|op| {
+            let (tx_hash, block_index, success, fail_reason) = match op {
+                ExecutedOperations::Tx(tx) => (
+                    tx.signed_tx.tx.hash(),
+                    tx.block_index,
+                    tx.success,
+                    tx.fail_reason.clone(),
+                ),
+                ExecutedOperations::PriorityOp(op) => {
+                    (op.priority_op.tx_hash(), Some(op.block_index), true, None)
+                }
+            };
+            Self::TransactionExecuted(TransactionExecutedEvent {
+                tx_hash,
+                block_number: block.block_number,
+                block_index,
+                tx_type: op.variance_name(),
+                success,
+                fail_reason,
+            })
+        });
+
+        std::iter::once(new_block)
+            .chain(executed_operations)
+            .collect()
+    }
+
+    /// Returns the event data without the type tag.
+    pub fn payload(&self) -> serde_json::Value {
+        let result = match self {
+            OutboxEvent::NewBlock(event) => serde_json::to_value(event),
+            OutboxEvent::TransactionExecuted(event) => serde_json::to_value(event),
+            OutboxEvent::WithdrawalFinalized(event) => serde_json::to_value(event),
+            OutboxEvent::TokenListed(event) => serde_json::to_value(event),
+            OutboxEvent::AddressAttested(event) => serde_json::to_value(event),
+        };
+        result.expect("Outbox event serialization can't fail")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outbox_event_type_str_roundtrip() {
+        for event_type in OutboxEventType::all() {
+            assert_eq!(
+                OutboxEventType::from_str(&event_type.to_string()),
+                Ok(event_type)
+            );
+            // String representation matches the serde one.
+            assert_eq!(
+                serde_json::to_value(event_type).unwrap(),
+                serde_json::Value::String(event_type.to_string())
+            );
+        }
+        assert!(OutboxEventType::from_str("unknown").is_err());
+    }
+}
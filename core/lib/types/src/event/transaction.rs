@@ -1,12 +1,30 @@
 // Built-in uses
+use std::str::FromStr;
 // External uses
 use chrono::{DateTime, Utc};
+use num::BigUint;
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 // Workspace uses
 // Local uses
 use super::account::AccountStateChangeStatus;
-use crate::{block::ExecutedOperations, AccountId, BlockNumber, TokenId};
+use crate::{block::ExecutedOperations, AccountId, Address, BlockNumber, TokenId};
+
+/// Fields of the serialized operation (or of its priority operation) that contain addresses.
+const ADDRESS_FIELDS: &[&str] = &[
+    "from",
+    "to",
+    "target",
+    "recipient",
+    "account",
+    "eth_address",
+    "submitterAddress",
+    "creatorAddress",
+];
+
+/// Fields of the serialized operation (or of its priority operation) that contain the amount.
+/// `withdraw_amount` is only set for the successful `FullExit`.
+const AMOUNT_FIELDS: &[&str] = &["amount", "withdraw_amount"];
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -94,6 +112,49 @@ impl TransactionEvent {
             .tx_type
             .get_or_init(|| serde_json::from_value(self.tx["type"].clone()).unwrap())
     }
+
+    /// Returns the amount of funds moved by the operation.
+    /// For `Swap` the greater one of the exchanged amounts is returned.
+    /// Returns [`None`] for operations without an amount (e.g. `ChangePubKey`).
+    pub fn amount(&self) -> Option<BigUint> {
+        let parse = |value: &serde_json::Value| BigUint::from_str(value.as_str()?).ok();
+        if let Some(amounts) = self
+            .tx
+            .get("amounts")
+            .and_then(|amounts| amounts.as_array())
+        {
+            return amounts.iter().filter_map(parse).max();
+        }
+        let priority_op = self.tx.get("priority_op");
+        AMOUNT_FIELDS.iter().find_map(|field| {
+            self.tx
+                .get(field)
+                .or_else(|| priority_op?.get(field))
+                .and_then(parse)
+        })
+    }
+
+    /// Returns the L1 addresses featured in the operation, e.g. the sender
+    /// and the recipient of a transfer.
+    pub fn addresses(&self) -> Vec<Address> {
+        let priority_op = self.tx.get("priority_op");
+        let order_recipients = self
+            .tx
+            .get("orders")
+            .and_then(|orders| orders.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|order| order.get("recipient"));
+        ADDRESS_FIELDS
+            .iter()
+            .flat_map(|field| {
+                let priority_op_field = priority_op.and_then(|op| op.get(field));
+                self.tx.get(field).into_iter().chain(priority_op_field)
+            })
+            .chain(order_recipients)
+            .filter_map(|value| serde_json::from_value(value.clone()).ok())
+            .collect()
+    }
 }
 
 impl From<AccountStateChangeStatus> for TransactionStatus {
@@ -104,3 +165,217 @@ impl From<AccountStateChangeStatus> for TransactionStatus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        operations::{DepositOp, FullExitOp},
+        tx::{ChangePubKey, ForcedExit, MintNFT, Order, Swap, Transfer, Withdraw, WithdrawNFT},
+        Deposit, FullExit, Nonce, PubKeyHash, ZkSyncOp, ZkSyncTx, H256,
+    };
+
+    fn tx_event(tx: serde_json::Value) -> TransactionEvent {
+        TransactionEvent {
+            tx_hash: String::new(),
+            account_id: AccountId(1),
+            token_id: TokenId(0),
+            block_number: BlockNumber(1),
+            tx,
+            status: TransactionStatus::Committed,
+            fail_reason: None,
+            created_at: Utc::now(),
+            tx_type: OnceCell::default(),
+        }
+    }
+
+    fn from_tx(tx: impl Into<ZkSyncTx>) -> TransactionEvent {
+        tx_event(serde_json::to_value(tx.into()).unwrap())
+    }
+
+    fn from_op(op: ZkSyncOp) -> TransactionEvent {
+        tx_event(serde_json::to_value(op).unwrap())
+    }
+
+    fn address(byte: u8) -> Address {
+        Address::repeat_byte(byte)
+    }
+
+    fn order(recipient: Address, amount: u32) -> Order {
+        Order {
+            account_id: AccountId(1),
+            recipient_address: recipient,
+            nonce: Nonce(0),
+            token_buy: TokenId(1),
+            token_sell: TokenId(0),
+            price: (1u32.into(), 1u32.into()),
+            amount: amount.into(),
+            time_range: Default::default(),
+            signature: Default::default(),
+        }
+    }
+
+    #[test]
+    fn transfer_fields() {
+        let event = from_tx(Transfer::new(
+            AccountId(1),
+            address(1),
+            address(2),
+            TokenId(0),
+            100u32.into(),
+            1u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        ));
+        assert_eq!(event.tx_type(), TransactionType::Transfer);
+        assert_eq!(event.amount(), Some(100u32.into()));
+        assert_eq!(event.addresses(), vec![address(1), address(2)]);
+    }
+
+    #[test]
+    fn withdraw_fields() {
+        let event = from_tx(Withdraw::new(
+            AccountId(1),
+            address(1),
+            address(2),
+            TokenId(0),
+            100u32.into(),
+            1u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        ));
+        assert_eq!(event.tx_type(), TransactionType::Withdraw);
+        assert_eq!(event.amount(), Some(100u32.into()));
+        assert_eq!(event.addresses(), vec![address(1), address(2)]);
+    }
+
+    #[test]
+    fn withdraw_nft_fields() {
+        let event = from_tx(WithdrawNFT::new(
+            AccountId(1),
+            address(1),
+            address(2),
+            TokenId(70000),
+            TokenId(0),
+            1u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        ));
+        assert_eq!(event.tx_type(), TransactionType::WithdrawNFT);
+        assert_eq!(event.amount(), None);
+        assert_eq!(event.addresses(), vec![address(1), address(2)]);
+    }
+
+    #[test]
+    fn mint_nft_fields() {
+        let event = from_tx(MintNFT::new(
+            AccountId(1),
+            address(1),
+            H256::zero(),
+            address(2),
+            1u32.into(),
+            TokenId(0),
+            Nonce(0),
+            None,
+        ));
+        assert_eq!(event.tx_type(), TransactionType::MintNFT);
+        assert_eq!(event.amount(), None);
+        assert_eq!(event.addresses(), vec![address(2), address(1)]);
+    }
+
+    #[test]
+    fn swap_fields() {
+        let event = from_tx(Swap::new(
+            AccountId(1),
+            address(1),
+            Nonce(0),
+            (order(address(2), 100), order(address(3), 200)),
+            (100u32.into(), 200u32.into()),
+            1u32.into(),
+            TokenId(0),
+            None,
+        ));
+        assert_eq!(event.tx_type(), TransactionType::Swap);
+        assert_eq!(event.amount(), Some(200u32.into()));
+        assert_eq!(event.addresses(), vec![address(1), address(2), address(3)]);
+    }
+
+    #[test]
+    fn change_pubkey_fields() {
+        let event = from_tx(ChangePubKey::new(
+            AccountId(1),
+            address(1),
+            PubKeyHash::default(),
+            TokenId(0),
+            1u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+            None,
+            None,
+        ));
+        assert_eq!(event.tx_type(), TransactionType::ChangePubKey);
+        assert_eq!(event.amount(), None);
+        assert_eq!(event.addresses(), vec![address(1)]);
+    }
+
+    #[test]
+    fn forced_exit_fields() {
+        let event = from_tx(ForcedExit::new(
+            AccountId(1),
+            address(1),
+            TokenId(0),
+            1u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        ));
+        assert_eq!(event.tx_type(), TransactionType::ForcedExit);
+        assert_eq!(event.amount(), None);
+        assert_eq!(event.addresses(), vec![address(1)]);
+    }
+
+    #[test]
+    fn full_exit_fields() {
+        let full_exit = |withdraw_amount: Option<u32>| {
+            from_op(ZkSyncOp::FullExit(Box::new(FullExitOp {
+                priority_op: FullExit {
+                    account_id: AccountId(1),
+                    eth_address: address(1),
+                    token: TokenId(0),
+                    is_legacy: false,
+                },
+                withdraw_amount: withdraw_amount.map(|amount| BigUint::from(amount).into()),
+                creator_account_id: None,
+                creator_address: None,
+                serial_id: None,
+                content_hash: None,
+            })))
+        };
+        let event = full_exit(Some(100));
+        assert_eq!(event.tx_type(), TransactionType::FullExit);
+        assert_eq!(event.amount(), Some(100u32.into()));
+        assert_eq!(event.addresses(), vec![address(1)]);
+        // Unsuccessful full exit doesn't move any funds.
+        assert_eq!(full_exit(None).amount(), None);
+    }
+
+    #[test]
+    fn deposit_fields() {
+        let event = from_op(ZkSyncOp::Deposit(Box::new(DepositOp {
+            priority_op: Deposit {
+                from: address(1),
+                token: TokenId(0),
+                amount: 100u32.into(),
+                to: address(2),
+            },
+            account_id: AccountId(1),
+        })));
+        assert_eq!(event.tx_type(), TransactionType::Deposit);
+        assert_eq!(event.amount(), Some(100u32.into()));
+        assert_eq!(event.addresses(), vec![address(1), address(2)]);
+    }
+}
@@ -0,0 +1,54 @@
+//! Data of the exit transaction performed on the zkSync contract in the exodus mode.
+
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{AccountId, Address, BlockNumber, TokenId, H256};
+use zksync_crypto::proof::EncodedSingleProof;
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+use crate::block::Block;
+
+/// Block information as it's stored in the contract, the exit is performed
+/// against the state of this block.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredBlockInfo {
+    pub block_number: BlockNumber,
+    pub priority_operations: u64,
+    pub pending_onchain_operations_hash: H256,
+    pub timestamp: u64,
+    pub state_hash: H256,
+    pub commitment: H256,
+}
+
+impl StoredBlockInfo {
+    pub fn from_block(block: &Block) -> Self {
+        Self {
+            block_number: block.block_number,
+            priority_operations: block.number_of_processed_prior_ops(),
+            pending_onchain_operations_hash: block.get_onchain_operations_block_info().1,
+            timestamp: block.timestamp,
+            state_hash: block.get_eth_encoded_root(),
+            commitment: block.block_commitment,
+        }
+    }
+}
+
+/// Inputs of the exit transaction. The public inputs of the circuit
+/// are the part of the encoded proof.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitProofData {
+    pub stored_block_info: StoredBlockInfo,
+    pub owner: Address,
+    pub account_id: AccountId,
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    pub nft_creator_id: AccountId,
+    pub nft_creator_address: Address,
+    pub nft_serial_id: u32,
+    pub nft_content_hash: H256,
+    pub proof: EncodedSingleProof,
+    pub token_address: Address,
+}
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use num::BigUint;
 use thiserror::Error;
-use zksync_basic_types::{Address, TokenId};
+use zksync_basic_types::{Address, TokenId, H256};
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
 use serde::{Deserialize, Serialize};
@@ -26,6 +26,27 @@ pub struct ForcedExitRequest {
     pub created_at: DateTime<Utc>,
     pub fulfilled_by: Option<Vec<TxHash>>,
     pub fulfilled_at: Option<DateTime<Utc>>,
+    /// Hash of the Ethereum transaction that has paid for the request.
+    pub payment_tx_hash: Option<H256>,
+    /// Time when the payment was processed by the server.
+    pub paid_at: Option<DateTime<Utc>>,
+    /// Address set by the request creator to receive the refund of the fee paid in excess.
+    /// The excess is not refunded if the address is not set.
+    pub refund_address: Option<Address>,
+    /// Refund of the fee paid in excess of the request price.
+    pub refund: Option<ForcedExitRefund>,
+}
+
+/// The request fee paid in excess of its price is refunded on L2 to the refund address
+/// of the request once the request is fulfilled.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedExitRefund {
+    pub address: Address,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    pub refunded_by: Option<TxHash>,
+    pub refunded_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
@@ -36,12 +57,15 @@ pub struct SaveForcedExitRequestQuery {
     pub price_in_wei: BigUint,
     pub created_at: DateTime<Utc>,
     pub valid_until: DateTime<Utc>,
+    pub refund_address: Option<Address>,
 }
 
 #[derive(Debug, Clone)]
 pub struct FundsReceivedEvent {
     pub amount: BigUint,
     pub block_number: u64,
+    /// Hash of the Ethereum transaction that has paid for the request.
+    pub tx_hash: H256,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -65,10 +89,14 @@ impl TryFrom<Log> for FundsReceivedEvent {
             .block_number
             .ok_or(FundsReceivedEventParseError::UnfinalizedBlockAccess)?
             .as_u64();
+        let tx_hash = event
+            .transaction_hash
+            .ok_or(FundsReceivedEventParseError::UnfinalizedBlockAccess)?;
 
         Ok(FundsReceivedEvent {
             amount: BigUint::from(amount.as_u128()),
             block_number,
+            tx_hash,
         })
     }
 }
@@ -0,0 +1,150 @@
+//! Prediction of the L1 gas spent on the block, based on the operations it contains.
+//!
+//! Unlike the `GasCounter`, which guards the gas limits of the L1 transactions and scales the
+//! estimations up, the predictor aims at the expected costs: the per-operation costs measured by the
+//! `gas_price_test` are adjusted by the calibration factors from the config, and the per-block costs
+//! include the block's share of the aggregated `commitBlocks`, `proveBlocks` and `executeBlocks` calls.
+// External deps
+use serde::{Deserialize, Serialize};
+// Local deps
+use crate::{
+    gas_counter::{CommitCost, VerifyCost},
+    ZkSyncOp,
+};
+
+/// Predicted L1 gas of the block, per block processing stage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockGasPrediction {
+    pub commit: u64,
+    pub prove: u64,
+    pub execute: u64,
+}
+
+impl BlockGasPrediction {
+    pub fn total(&self) -> u64 {
+        self.commit + self.prove + self.execute
+    }
+}
+
+impl std::ops::Add for BlockGasPrediction {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            commit: self.commit + other.commit,
+            prove: self.prove + other.prove,
+            execute: self.execute + other.execute,
+        }
+    }
+}
+
+impl std::ops::AddAssign for BlockGasPrediction {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+/// Calibrated costs used to predict the L1 gas of the block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GasPredictor {
+    /// Calibration of the per-operation costs of the `commitBlocks` call, in percents.
+    pub commit_scale_percent: u64,
+    /// Calibration of the per-operation costs of the `executeBlocks` call, in percents.
+    pub execute_scale_percent: u64,
+    /// Cost of committing an empty block.
+    pub commit_block_cost: u64,
+    /// Cost of proving the block, which doesn't depend on the operations.
+    pub prove_block_cost: u64,
+    /// Cost of executing an empty block.
+    pub execute_block_cost: u64,
+}
+
+impl Default for GasPredictor {
+    /// Shares of the aggregated calls of 5 committed, 10 proven and 5 executed blocks.
+    fn default() -> Self {
+        Self {
+            commit_scale_percent: 100,
+            execute_scale_percent: 100,
+            commit_block_cost: 90_000,
+            prove_block_cost: 150_000,
+            execute_block_cost: 90_000,
+        }
+    }
+}
+
+impl GasPredictor {
+    /// Returns the predicted gas of an empty block.
+    pub fn fixed_cost(&self) -> BlockGasPrediction {
+        BlockGasPrediction {
+            commit: self.commit_block_cost,
+            prove: self.prove_block_cost,
+            execute: self.execute_block_cost,
+        }
+    }
+
+    /// Returns the predicted gas added to the block by the operation.
+    pub fn op_cost(&self, op: &ZkSyncOp) -> BlockGasPrediction {
+        BlockGasPrediction {
+            commit: CommitCost::op_cost(op).as_u64() * self.commit_scale_percent / 100,
+            prove: 0,
+            execute: VerifyCost::op_cost(op).as_u64() * self.execute_scale_percent / 100,
+        }
+    }
+
+    /// Returns the predicted gas of the block containing the given operations.
+    pub fn predict<'a>(&self, ops: impl IntoIterator<Item = &'a ZkSyncOp>) -> BlockGasPrediction {
+        ops.into_iter().fold(self.fixed_cost(), |prediction, op| {
+            prediction + self.op_cost(op)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        operations::{DepositOp, NoopOp},
+        priority_ops::Deposit,
+        AccountId, TokenId,
+    };
+
+    #[test]
+    fn block_prediction() {
+        let deposit = ZkSyncOp::from(DepositOp {
+            priority_op: Deposit {
+                from: Default::default(),
+                token: TokenId(0),
+                amount: Default::default(),
+                to: Default::default(),
+            },
+            account_id: AccountId(1),
+        });
+        let noop = ZkSyncOp::from(NoopOp {});
+
+        let predictor = GasPredictor {
+            commit_scale_percent: 200,
+            execute_scale_percent: 50,
+            ..Default::default()
+        };
+        assert_eq!(
+            predictor.predict(std::iter::empty()),
+            predictor.fixed_cost()
+        );
+        assert_eq!(
+            predictor.op_cost(&deposit),
+            BlockGasPrediction {
+                commit: CommitCost::DEPOSIT_COST * 2,
+                prove: 0,
+                execute: VerifyCost::DEPOSIT_COST / 2,
+            }
+        );
+
+        let prediction = predictor.predict(&[deposit.clone(), noop, deposit]);
+        assert_eq!(
+            prediction.total(),
+            predictor.fixed_cost().total()
+                + CommitCost::DEPOSIT_COST * 4
+                + VerifyCost::DEPOSIT_COST
+        );
+    }
+}
@@ -42,9 +42,11 @@ pub mod aggregated_operations;
 pub mod block;
 pub mod ethereum;
 pub mod event;
+pub mod exit_proof;
 pub mod fee;
 pub mod forced_exit_requests;
 pub mod gas_counter;
+pub mod gas_prediction;
 pub mod helpers;
 pub mod mempool;
 pub mod network;
@@ -52,6 +54,7 @@ pub mod operations;
 pub mod priority_ops;
 pub mod prover;
 pub mod register_factory;
+pub mod runtime_config;
 pub mod tokens;
 pub mod tx;
 mod utils;
@@ -402,6 +402,11 @@ pub struct PriorityOp {
     /// Transaction index in Ethereum block.
     /// This field must be optional because of backward compatibility.
     pub eth_block_index: Option<u64>,
+    /// Sender of the corresponding Ethereum transaction. It differs from the operation
+    /// initiator if the operation was requested by a contract, e.g. a bridge or a wallet.
+    /// `None` for the operations received before the sender was tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_sender: Option<Address>,
 }
 
 impl TryFrom<Log> for PriorityOp {
@@ -473,6 +478,8 @@ impl TryFrom<Log> for PriorityOp {
                 .expect("Event block number is missing")
                 .as_u64(),
             eth_block_index: event.transaction_index.map(|index| index.as_u64()),
+            // Logs don't contain the transaction sender, it's loaded by the watcher.
+            l1_sender: None,
         })
     }
 }
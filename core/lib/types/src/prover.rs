@@ -29,6 +29,9 @@ impl ProverJobStatus {
 
 pub const SINGLE_PROOF_JOB_PRIORITY: i32 = 1;
 pub const AGGREGATED_PROOF_JOB_PRIORITY: i32 = 0;
+/// Priority assigned to the jobs that contain blocks marked as high priority by the operator.
+/// Jobs with the lower priority value are served first.
+pub const HIGH_PRIORITY_JOB_PRIORITY: i32 = -1;
 
 #[derive(Debug, Clone)]
 pub struct ProverJob {
@@ -0,0 +1,114 @@
+//! Reload-safe parameters which can be overridden at runtime without restarting the server.
+//!
+//! The operator stores the overrides in the database via the private API, and the watcher
+//! of the server reloads them periodically into the `SharedRuntimeConfig` read by the components.
+//! A parameter without an override keeps the value from the environment config.
+// Built-in deps
+use std::sync::{Arc, RwLock};
+// External deps
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Overrides of the reload-safe parameters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeOverrides {
+    /// Coefficient (in percents) applied to the gas fees of the L2 operations.
+    pub fee_scale_percent: Option<u64>,
+    /// Amount of the miniblock iterations after which the block is sealed.
+    pub miniblock_iterations: Option<u64>,
+    /// Amount of the miniblock iterations after which the block with the fast withdrawals is sealed.
+    pub fast_block_miniblock_iterations: Option<u64>,
+    /// Predicted L1 gas of the block above which the block is sealed, zero disables the limit.
+    pub max_block_gas: Option<u64>,
+    /// Log filter directives in the `RUST_LOG` format.
+    pub log_filter: Option<String>,
+}
+
+impl RuntimeOverrides {
+    /// Builds the overrides from the stored parameters.
+    pub fn from_params(
+        params: impl IntoIterator<Item = (String, Value)>,
+    ) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(Value::Object(params.into_iter().collect()))
+    }
+
+    /// Checks that the parameter is known and the value has the expected type.
+    pub fn validate_param(key: &str, value: &Value) -> Result<(), String> {
+        if value.is_null() {
+            return Err(format!("value of the `{}` parameter must be set", key));
+        }
+        Self::from_params(std::iter::once((key.to_string(), value.clone())))
+            .map(drop)
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// Overrides shared between the components. The watcher replaces all of them at once,
+/// so the readers never observe a partially applied change.
+#[derive(Debug, Clone, Default)]
+pub struct SharedRuntimeConfig(Arc<RwLock<RuntimeOverrides>>);
+
+impl SharedRuntimeConfig {
+    pub fn get(&self) -> RuntimeOverrides {
+        self.0
+            .read()
+            .expect("runtime config lock is poisoned")
+            .clone()
+    }
+
+    /// Replaces the overrides. Returns `true` if they have changed.
+    pub fn replace(&self, overrides: RuntimeOverrides) -> bool {
+        let mut current = self.0.write().expect("runtime config lock is poisoned");
+        if *current == overrides {
+            return false;
+        }
+        *current = overrides;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn params_validation() {
+        assert!(RuntimeOverrides::validate_param("fee_scale_percent", &json!(150)).is_ok());
+        assert!(
+            RuntimeOverrides::validate_param("log_filter", &json!("zksync_core=debug")).is_ok()
+        );
+        assert!(RuntimeOverrides::validate_param("fee_scale_percent", &json!("150")).is_err());
+        assert!(RuntimeOverrides::validate_param("fee_scale_percent", &json!(-1)).is_err());
+        assert!(RuntimeOverrides::validate_param("fee_scale_percent", &Value::Null).is_err());
+        assert!(RuntimeOverrides::validate_param("unknown", &json!(1)).is_err());
+
+        let overrides = RuntimeOverrides::from_params(vec![
+            ("miniblock_iterations".to_string(), json!(5)),
+            ("max_block_gas".to_string(), json!(0)),
+        ])
+        .unwrap();
+        assert_eq!(
+            overrides,
+            RuntimeOverrides {
+                miniblock_iterations: Some(5),
+                max_block_gas: Some(0),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn shared_overrides() {
+        let shared = SharedRuntimeConfig::default();
+        let overrides = RuntimeOverrides {
+            fee_scale_percent: Some(120),
+            ..Default::default()
+        };
+        // The overrides are shared between the clones.
+        assert!(shared.clone().replace(overrides.clone()));
+        assert!(!shared.replace(overrides.clone()));
+        assert_eq!(shared.get(), overrides);
+    }
+}
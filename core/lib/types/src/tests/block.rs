@@ -3,7 +3,7 @@ use zksync_crypto::ff::Field;
 use zksync_crypto::Fr;
 
 use super::utils::*;
-use crate::block::Block;
+use crate::{block::Block, tx::TxHash};
 
 /// Checks that we cannot create a block with invalid block sizes provided.
 #[test]
@@ -173,3 +173,90 @@ fn test_get_withdrawals_data() {
     // No more corresponding operations left.
     assert!(block.get_withdrawals_data().is_empty());
 }
+
+/// Checks that the inclusion proof of the operation recomputes the block commitment.
+#[test]
+fn test_tx_inclusion_proof() {
+    let operations = vec![
+        create_change_pubkey_tx(),
+        create_full_exit_op(),
+        create_withdraw_tx(),
+    ];
+    let previous_root_hash = H256::repeat_byte(1);
+    let block = Block::new_from_available_block_sizes(
+        BlockNumber(5),
+        Fr::one(),
+        AccountId(0),
+        operations.clone(),
+        (0, 1),
+        &[100],
+        1_000_000.into(),
+        1_500_000.into(),
+        previous_root_hash,
+        10,
+    );
+
+    let withdraw = operations[2].get_executed_tx().unwrap();
+    let withdraw_pubdata = withdraw.op.as_ref().unwrap().public_data();
+    let proof = block
+        .tx_inclusion_proof(previous_root_hash, withdraw.signed_tx.tx.hash())
+        .unwrap();
+    assert_eq!(proof.commitment, block.block_commitment);
+    let expected_offset = operations[..2]
+        .iter()
+        .map(|op| op.get_eth_public_data().len())
+        .sum::<usize>();
+    assert_eq!(proof.public_data_offset as usize, expected_offset);
+    assert!(proof.verify(&withdraw_pubdata));
+
+    // The proof can't be reused for the other operation or with the tampered public data.
+    let full_exit_pubdata = operations[1].get_eth_public_data();
+    assert!(!proof.verify(&full_exit_pubdata));
+    let mut tampered = proof.clone();
+    tampered.public_data[0] ^= 1;
+    assert!(!tampered.verify(tampered.op_public_data().unwrap()));
+
+    // The proof made with the wrong previous root doesn't match the commitment.
+    let proof = block
+        .tx_inclusion_proof(H256::zero(), withdraw.signed_tx.tx.hash())
+        .unwrap();
+    assert!(!proof.verify(&withdraw_pubdata));
+
+    assert!(block
+        .tx_inclusion_proof(previous_root_hash, TxHash::default())
+        .is_none());
+}
+
+/// Checks that the offsets of the operations point to their public data.
+#[test]
+fn test_get_public_data_offsets() {
+    let block = Block::new(
+        BlockNumber(0),
+        Fr::one(),
+        AccountId(0),
+        vec![
+            create_change_pubkey_tx(),
+            create_full_exit_op(),
+            create_withdraw_tx(),
+        ],
+        (0, 0),
+        100,
+        1_000_000.into(),
+        1_500_000.into(),
+        H256::default(),
+        0,
+    );
+
+    let public_data = block.get_eth_public_data();
+    let offsets = block.get_public_data_offsets();
+    assert_eq!(offsets.len(), 3);
+    assert_eq!(offsets[0].1, 0);
+    for (op, offset) in offsets {
+        let op_public_data = op.get_eth_public_data();
+        let offset = offset as usize;
+        assert_eq!(
+            &public_data[offset..offset + op_public_data.len()],
+            op_public_data.as_slice()
+        );
+    }
+}
@@ -22,6 +22,7 @@ pub fn create_full_exit_op() -> ExecutedOperations {
             eth_hash: H256::zero(),
             eth_block: 0,
             eth_block_index: None,
+            l1_sender: None,
         },
         op: ZkSyncOp::FullExit(Box::new(FullExitOp {
             priority_op,
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 // External uses
 // Workspace uses
-use zksync_basic_types::Address;
+use zksync_basic_types::{Address, H256};
 use zksync_utils::format_units;
 // Local uses
 use crate::{tx::*, AccountId, Nonce, Token, TokenId, TokenKind, Transfer, Withdraw, ZkSyncTx};
@@ -131,3 +131,30 @@ fn test_batch_message() {
     let message = EthBatchSignData::get_batch_sign_message(txs);
     assert_eq!(message, expected.into_bytes());
 }
+
+/// Checks that the hash of the L1 calldata attached to the withdrawal is a part of the message.
+#[test]
+fn test_withdraw_l1_calldata_hash_message() {
+    let token = Token::new(TokenId(0), Default::default(), "ETH", 18, TokenKind::ERC20);
+    let mut withdraw = get_withdraw();
+    withdraw.l1_calldata_hash = Some(H256::repeat_byte(0xab));
+    let expected = format!(
+        "Withdraw {amount} {token} to: {to:?}\n\
+        Fee: {fee} {token}\n\
+        L1 calldata hash: 0x{hash}\n\
+        Nonce: 12",
+        amount = format_units(&withdraw.amount, 18),
+        token = "ETH",
+        to = withdraw.to,
+        fee = format_units(&withdraw.fee, 18),
+        hash = "ab".repeat(32),
+    );
+    let withdraw = ZkSyncTx::from(withdraw);
+    assert_eq!(
+        withdraw.get_ethereum_sign_message(token.clone()),
+        Some(expected)
+    );
+    // The old message doesn't contain the hash, so it's not accepted for such withdrawals.
+    assert!(!withdraw.is_backwards_compatible());
+    assert!(withdraw.get_old_ethereum_sign_message(token).is_none());
+}
@@ -1,4 +1,5 @@
 use num::{BigUint, Zero};
+use parity_crypto::digest::sha256;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
@@ -13,7 +14,7 @@ use zksync_crypto::{
 };
 use zksync_utils::{format_units, BigUintPairSerdeAsRadix10Str, BigUintSerdeAsRadix10Str};
 
-use super::{TxSignature, VerifiedSignatureCache};
+use super::{TxHash, TxSignature, VerifiedSignatureCache};
 use crate::account::PubKeyHash;
 use crate::tx::error::{
     AMOUNT_IS_NOT_PACKABLE, FEE_AMOUNT_IS_NOT_PACKABLE, WRONG_ACCOUNT_ID, WRONG_AMOUNT_ERROR,
@@ -90,6 +91,18 @@ impl Order {
         out
     }
 
+    /// Returns the hash of the order, which identifies it in the order book.
+    pub fn hash(&self) -> TxHash {
+        TxHash::from_slice(&sha256(&self.get_bytes())).unwrap()
+    }
+
+    /// Encodes the message signed by the order owner to cancel the order in the order book.
+    pub fn get_cancellation_bytes(&self) -> Vec<u8> {
+        let mut out = b"cancel".to_vec();
+        out.extend_from_slice(self.hash().as_ref());
+        out
+    }
+
     pub fn verify_signature(&self) -> Option<PubKeyHash> {
         self.signature
             .verify_musig(&self.get_bytes())
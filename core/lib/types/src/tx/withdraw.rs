@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
 
-use zksync_basic_types::Address;
+use zksync_basic_types::{Address, H256};
 use zksync_crypto::{
     franklin_crypto::eddsa::PrivateKey,
     params::{
@@ -56,6 +56,11 @@ pub struct Withdraw {
     /// rejected.
     #[serde(default)]
     pub fast: bool,
+    /// Keccak-256 hash of the calldata of the call made to the recipient once the withdrawal
+    /// is executed on L1. It is not a part of the zkSync transaction, but it is a part of the
+    /// Ethereum signature message, so the calldata can't be replaced after signing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_calldata_hash: Option<H256>,
     /// Time range when the transaction is valid
     /// This fields must be Option<...> because of backward compatibility with first version of ZkSync
     #[serde(flatten)]
@@ -93,6 +98,7 @@ impl Withdraw {
             signature: signature.clone().unwrap_or_default(),
             cached_signer: VerifiedSignatureCache::NotCached,
             fast: false,
+            l1_calldata_hash: None,
             time_range: Some(time_range),
         };
         if signature.is_some() {
@@ -123,8 +129,10 @@ impl Withdraw {
         Ok(tx)
     }
 
+    /// The withdrawals with the L1 calldata are never backwards compatible, since the old
+    /// messages don't contain the calldata hash.
     pub fn is_backwards_compatible(&self) -> bool {
-        self.token.0 < MIN_NFT_TOKEN_ID
+        self.token.0 < MIN_NFT_TOKEN_ID && self.l1_calldata_hash.is_none()
     }
 
     /// Encodes the transaction data as the byte sequence according to the old zkSync protocol with 2 bytes token.
@@ -192,14 +200,21 @@ impl Withdraw {
     /// The only difference is the missing `nonce` since it's added at the end of the transactions
     /// batch message.
     pub fn get_ethereum_sign_message_part(&self, token_symbol: &str, decimals: u8) -> String {
-        ethereum_sign_message_part(
+        let mut message = ethereum_sign_message_part(
             "Withdraw",
             token_symbol,
             decimals,
             &self.amount,
             &self.fee,
             &self.to,
-        )
+        );
+        if let Some(l1_calldata_hash) = &self.l1_calldata_hash {
+            if !message.is_empty() {
+                message.push('\n');
+            }
+            message.push_str(format!("L1 calldata hash: {:?}", l1_calldata_hash).as_str());
+        }
+        message
     }
 
     /// Get message that should be signed by Ethereum keys of the account for 2-Factor authentication.
@@ -304,7 +304,8 @@ impl ZkSyncTx {
             ZkSyncTx::Transfer(tx) => {
                 Some(tx.get_old_ethereum_sign_message(&token.symbol, token.decimals))
             }
-            ZkSyncTx::Withdraw(tx) => {
+            // The old message doesn't contain the L1 calldata hash, so it can't authorize it.
+            ZkSyncTx::Withdraw(tx) if tx.l1_calldata_hash.is_none() => {
                 Some(tx.get_old_ethereum_sign_message(&token.symbol, token.decimals))
             }
             _ => None,
@@ -11,9 +11,35 @@
 //! Integration with sentry for catching errors and react on them immediately
 //! https://docs.sentry.io/platforms/rust/
 //!
+//! If the OpenTelemetry collector endpoint is provided via the `MISC_OTLP_ENDPOINT` env variable,
+//! spans are exported to it, so the requests can be traced across the components.
+//! https://opentelemetry.io/docs/
+//!
+//! The log filter is initialized from the `RUST_LOG` env variable and can be replaced at runtime
+//! with `set_log_filter`.
+//!
+//! Slow SQL statements logged by `sqlx` are attached to the span they were executed within,
+//! so the caller can retrieve the statement with `slow_statement`.
+//!
 
 use chrono::Duration;
-use std::{borrow::Cow, str::FromStr};
+use std::{borrow::Cow, collections::HashMap, fmt::Debug, str::FromStr};
+
+use once_cell::sync::OnceCell;
+use opentelemetry::{
+    global,
+    sdk::{propagation::TraceContextPropagator, trace, Resource},
+    KeyValue,
+};
+use tracing::{field::Field, Subscriber};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{
+    fmt,
+    layer::{Context, Layer},
+    prelude::*,
+    registry::LookupSpan,
+    reload, EnvFilter, Registry,
+};
 
 pub use sentry;
 use sentry::protocol::Event;
@@ -48,9 +74,28 @@ macro_rules! error {
 }
 
 /// When this is dropped sentry and logger stops working
+#[must_use = "logging stops once the guard is dropped"]
 pub struct VlogGuard {
     _sentry_guard: Option<ClientInitGuard>,
     _logger_guard: WorkerGuard,
+    _otlp_guard: Option<OtlpGuard>,
+}
+
+/// Owns the runtime exporting the spans, flushes the spans which were not exported yet when dropped.
+#[must_use = "spans are not exported once the guard is dropped"]
+struct OtlpGuard {
+    runtime: Option<tokio::runtime::Runtime>,
+}
+
+impl Drop for OtlpGuard {
+    fn drop(&mut self) {
+        global::shutdown_tracer_provider();
+        // The guard may be dropped within the async context of the binary, where the runtime
+        // can't be dropped in a blocking way.
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
 }
 
 fn get_sentry_url() -> Option<Dsn> {
@@ -62,10 +107,171 @@ fn get_sentry_url() -> Option<Dsn> {
     None
 }
 
+fn get_otlp_endpoint() -> Option<String> {
+    std::env::var("MISC_OTLP_ENDPOINT")
+        .ok()
+        .filter(|endpoint| endpoint != "unset")
+}
+
+/// Creates the tracer exporting the spans to the OpenTelemetry collector.
+/// Spans are exported in batches by the background task, which runs on the dedicated runtime
+/// since the logger is initialized both within and outside of the tokio runtime of the binary.
+fn init_otlp_tracer(endpoint: String) -> (trace::Tracer, OtlpGuard) {
+    // Name of the binary is used to distinguish the components, e.g. `zksync_server` and `zksync_prover`.
+    let service_name = std::env::current_exe()
+        .ok()
+        .and_then(|path| path.file_stem()?.to_str().map(String::from))
+        .unwrap_or_else(|| "zksync".to_string());
+    let trace_config = trace::config().with_resource(Resource::new(vec![KeyValue::new(
+        "service.name",
+        service_name,
+    )]));
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("otlp-exporter")
+        .enable_all()
+        .build()
+        .expect("failed to create the OpenTelemetry exporter runtime");
+    // The exporter and the batch task are spawned on the runtime entered here.
+    let tracer = {
+        let _runtime_guard = runtime.enter();
+        opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .tonic()
+                    .with_endpoint(endpoint),
+            )
+            .with_trace_config(trace_config)
+            .install_batch(opentelemetry::runtime::Tokio)
+            .expect("failed to install the OpenTelemetry tracer")
+    };
+    let guard = OtlpGuard {
+        runtime: Some(runtime),
+    };
+    (tracer, guard)
+}
+
+/// Sets the parent of the span to the remote trace context extracted from the
+/// W3C `traceparent` and `tracestate` headers of the incoming request.
+/// The span remains the root one if there are no such headers.
+pub fn set_remote_parent<'a>(
+    span: &tracing::Span,
+    headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+) {
+    let carrier: HashMap<String, String> = headers
+        .into_iter()
+        .map(|(name, value)| (name.to_lowercase(), value.to_string()))
+        .collect();
+    let parent = global::get_text_map_propagator(|propagator| propagator.extract(&carrier));
+    span.set_parent(parent);
+}
+
+/// Target of the statements logged by `sqlx`, passed by the `log` records in the `log.target` field.
+const SQLX_QUERY_TARGET: &str = "sqlx::query";
+
+/// Text of the last slow SQL statement executed within the span.
+struct SlowStatement(String);
+
+/// Collects the message and the target of the events converted from the `log` records.
+#[derive(Default)]
+struct LogRecordVisitor {
+    message: Option<String>,
+    target: Option<String>,
+}
+
+impl tracing::field::Visit for LogRecordVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "log.target" {
+            self.target = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{:?}", value));
+        }
+    }
+}
+
+/// Attaches the slow statements logged by `sqlx` (on the `WARN` level) to the current span.
+struct SlowStatementLayer;
+
+impl<S> Layer<S> for SlowStatementLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        if *event.metadata().level() != tracing::Level::WARN {
+            return;
+        }
+        let span = match ctx.lookup_current() {
+            Some(span) => span,
+            None => return,
+        };
+        let mut visitor = LogRecordVisitor::default();
+        event.record(&mut visitor);
+        if visitor.target.as_deref() != Some(SQLX_QUERY_TARGET) {
+            return;
+        }
+        // The message is the summary of the statement followed by its formatted text,
+        // the text is omitted if the statement is short enough to fit into the summary.
+        let statement = visitor
+            .message
+            .and_then(|message| match message.split_once("\n\n") {
+                Some((_, statement)) => Some(statement.to_string()),
+                None => message.split("; rows affected").next().map(String::from),
+            });
+        if let Some(statement) = statement {
+            span.extensions_mut().replace(SlowStatement(statement));
+        }
+    }
+}
+
+/// Returns the text of the last slow SQL statement executed within the current span,
+/// if `sqlx` logged it (i.e. it took longer than the slow statement threshold of the connection).
+pub fn slow_statement() -> Option<String> {
+    let id = tracing::Span::current().id()?;
+    tracing::dispatcher::get_default(|dispatch| {
+        let registry = dispatch.downcast_ref::<Registry>()?;
+        let span = registry.span(&id)?;
+        let extensions = span.extensions();
+        extensions
+            .get::<SlowStatement>()
+            .map(|statement| statement.0.clone())
+    })
+}
+
+/// Handle replacing the log filter of the initialized logger.
+static LOG_FILTER: OnceCell<reload::Handle<EnvFilter, Registry>> = OnceCell::new();
+
+/// Checks that the directives in the `RUST_LOG` format can be parsed.
+pub fn validate_log_filter(directives: &str) -> Result<(), String> {
+    EnvFilter::try_new(directives)
+        .map(drop)
+        .map_err(|err| err.to_string())
+}
+
+/// Replaces the log filter with the directives in the `RUST_LOG` format,
+/// `None` restores the filter from the environment.
+pub fn set_log_filter(directives: Option<&str>) -> Result<(), String> {
+    let filter = match directives {
+        Some(directives) => EnvFilter::try_new(directives).map_err(|err| err.to_string())?,
+        None => EnvFilter::from_default_env(),
+    };
+    LOG_FILTER
+        .get()
+        .ok_or_else(|| "logger is not initialized".to_string())?
+        .reload(filter)
+        .map_err(|err| err.to_string())
+}
+
 /// Initialize logging with non blocking tracing and set up log format
 ///
 /// If the sentry URL is provided via an environment variable, this function will also initialize sentry.
-/// Returns a VlogGuard guard. Which contains Sentry Guard and Logger Guard
+/// If the OpenTelemetry collector endpoint is provided, spans are exported to it.
+/// Returns a VlogGuard guard. Which contains Sentry Guard, Logger Guard and the guard flushing the spans
 ///
 /// The full description can be found in the official documentation:
 /// https://docs.sentry.io/platforms/rust/#configure
@@ -73,20 +279,35 @@ fn get_sentry_url() -> Option<Dsn> {
 pub fn init() -> VlogGuard {
     let log_format = std::env::var("MISC_LOG_FORMAT").unwrap_or_else(|_| "plain".to_string());
     let (non_blocking, _logger_guard) = tracing_appender::non_blocking(std::io::stdout());
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    let (otlp_layer, _otlp_guard) = match get_otlp_endpoint().map(init_otlp_tracer) {
+        Some((tracer, guard)) => (
+            Some(tracing_opentelemetry::layer().with_tracer(tracer)),
+            Some(guard),
+        ),
+        None => (None, None),
+    };
+
+    let (filter, filter_handle) = reload::Layer::new(EnvFilter::from_default_env());
+    // The logger may only be initialized once, so the handle is never replaced.
+    let _ = LOG_FILTER.set(filter_handle);
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(otlp_layer)
+        .with(SlowStatementLayer);
     match log_format.as_str() {
         "plain" => {
-            tracing_subscriber::fmt::Subscriber::builder()
-                .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-                .with_writer(non_blocking)
-                .init();
+            registry.with(fmt::layer().with_writer(non_blocking)).init();
         }
         "json" => {
-            let timer = tracing_subscriber::fmt::time::ChronoUtc::rfc3339();
-            tracing_subscriber::fmt::Subscriber::builder()
-                .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-                .with_writer(non_blocking)
-                .with_timer(timer)
-                .json()
+            let timer = fmt::time::ChronoUtc::rfc3339();
+            registry
+                .with(
+                    fmt::layer()
+                        .with_writer(non_blocking)
+                        .with_timer(timer)
+                        .json(),
+                )
                 .init();
         }
         _ => panic!("MISC_LOG_FORMAT has an unexpected value {}", log_format),
@@ -110,6 +331,7 @@ pub fn init() -> VlogGuard {
     VlogGuard {
         _sentry_guard,
         _logger_guard,
+        _otlp_guard,
     }
 }
 
@@ -5,7 +5,11 @@ use zksync::{
     error::ClientError, ethereum::PriorityOpHolder, operations::SyncTransactionHandle,
     provider::Provider,
 };
-use zksync_types::{tokens::ETH_TOKEN_ID, tx::PackedEthSignature, Nonce, ZkSyncTx, H256};
+use zksync_types::{
+    tokens::ETH_TOKEN_ID,
+    tx::{PackedEthSignature, TxEthSignature},
+    Nonce, ZkSyncTx, H256,
+};
 
 use crate::{
     account::AccountLifespan,
@@ -48,15 +52,24 @@ impl AccountLifespan {
     fn apply_modifier(
         &self,
         tx: ZkSyncTx,
-        eth_signature: Option<PackedEthSignature>,
+        eth_signature: Option<TxEthSignature>,
         modifier: IncorrectnessModifier,
-    ) -> (ZkSyncTx, Option<PackedEthSignature>) {
-        (tx, eth_signature).apply_modifier(
-            modifier,
-            self.eth_pk,
-            self.main_token.symbol.as_ref(),
-            self.main_token.decimals,
-        )
+    ) -> (ZkSyncTx, Option<TxEthSignature>) {
+        // Accounts are controlled by the private keys, so the signatures are always ECDSA ones.
+        let eth_signature = eth_signature.map(|signature| match signature {
+            TxEthSignature::EthereumSignature(signature) => signature,
+            TxEthSignature::EIP1271Signature(_) => {
+                unreachable!("Accounts don't use EIP-1271 signatures")
+            }
+        });
+        let (tx, eth_signature): (ZkSyncTx, Option<PackedEthSignature>) = (tx, eth_signature)
+            .apply_modifier(
+                modifier,
+                self.eth_pk,
+                self.main_token.symbol.as_ref(),
+                self.main_token.decimals,
+            );
+        (tx, eth_signature.map(TxEthSignature::EthereumSignature))
     }
 
     /// Returns the balances for ETH and the main token on the L1.
@@ -198,7 +211,7 @@ impl AccountLifespan {
         &self,
         command: &TxCommand,
         nonce: Option<Nonce>,
-    ) -> Result<(ZkSyncTx, Option<PackedEthSignature>), ClientError> {
+    ) -> Result<(ZkSyncTx, Option<TxEthSignature>), ClientError> {
         let mut builder = self
             .wallet
             .start_change_pubkey()
@@ -229,7 +242,7 @@ impl AccountLifespan {
         &self,
         command: &TxCommand,
         nonce: Option<Nonce>,
-    ) -> Result<(ZkSyncTx, Option<PackedEthSignature>), ClientError> {
+    ) -> Result<(ZkSyncTx, Option<TxEthSignature>), ClientError> {
         let mut builder = self
             .wallet
             .start_transfer()
@@ -262,7 +275,7 @@ impl AccountLifespan {
         &self,
         command: &TxCommand,
         nonce: Option<Nonce>,
-    ) -> Result<(ZkSyncTx, Option<PackedEthSignature>), ClientError> {
+    ) -> Result<(ZkSyncTx, Option<TxEthSignature>), ClientError> {
         let mut builder = self
             .wallet
             .start_withdraw()
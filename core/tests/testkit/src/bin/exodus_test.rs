@@ -406,7 +406,7 @@ async fn check_exit_correct_proof_incorrect_sender(
 }
 
 async fn exit_test() {
-    vlog::init();
+    let _vlog_guard = vlog::init();
     let testkit_config = TestkitConfig::from_env();
 
     let fee_account = ZkSyncAccount::rand();
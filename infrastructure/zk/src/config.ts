@@ -23,7 +23,10 @@ const CONFIG_FILES = [
     'private.toml',
     'forced_exit_requests.toml',
     'token_handler.toml',
-    'nft_factory.toml'
+    'nft_factory.toml',
+    'webhooks.toml',
+    'deposit_relayer.toml',
+    'object_store.toml'
 ];
 
 async function getEnvironment(): Promise<string> {
@@ -32,6 +32,11 @@ export async function run() {
     await utils.spawn('cargo run --bin zksync_data_restore --release -- --genesis --finite');
 }
 
+export async function fromSnapshot(snapshotPath: string) {
+    await db.reset();
+    await utils.spawn(`cargo run --bin zksync_data_restore --release -- --snapshot ${snapshotPath} --finite`);
+}
+
 export async function check(expectedHash: string) {
     await db.reset();
     await utils.spawn(
@@ -49,6 +54,10 @@ export const command = new Command('data-restore');
 command.command('restart').description('wipe the database and run data restore in finite mode').action(restart);
 command.command('resume').description('run data restore in "resume" mode').action(resume);
 command.command('run').description('do not wipe the database and run data restore in finite mode').action(run);
+command
+    .command('from-snapshot <path>')
+    .description('wipe the database and run data restore in finite mode starting from the state snapshot')
+    .action(fromSnapshot);
 
 command
     .command('check <hash>')
@@ -42,6 +42,21 @@ export async function rustApi(reset: boolean, ...args: string[]) {
     );
 }
 
+export async function dbBench(reset: boolean, ...args: string[]) {
+    // Benchmarks use a dedicated database, since the generated data is kept between the runs.
+    const databaseUrl = process.env.DATABASE_URL as string;
+    process.env.DATABASE_URL = databaseUrl.replace(/plasma/g, 'plasma_bench');
+    process.chdir('core/lib/storage');
+    if (reset) {
+        console.info('Performing database reset...');
+        await utils.exec('diesel database reset');
+    } else {
+        await utils.exec('diesel database setup');
+    }
+    await utils.spawn(`cargo bench -p zksync_storage --features db_bench -- ${args.join(' ')}`);
+    process.chdir(process.env.ZKSYNC_HOME as string);
+}
+
 export async function contracts() {
     await utils.spawn('yarn contracts test');
 }
@@ -116,6 +131,15 @@ command
         await rustApi(cmd.reset, ...(options || []));
     });
 
+command
+    .command('db-bench')
+    .description('run benchmarks of the database queries on the large generated dataset')
+    .option('--reset', 'reset the database to regenerate the dataset')
+    .allowUnknownOption()
+    .action(async (cmd: Command, options: string[] | undefined) => {
+        await dbBench(cmd.reset, ...(options || []));
+    });
+
 command
     .command('circuit [threads] [test_name] [options...]')
     .description('run unit-tests for the circuit')
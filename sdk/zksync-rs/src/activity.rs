@@ -0,0 +1,319 @@
+//! Stream of the account activity.
+//!
+//! Activity is built from the events emitted by the server: the live ones are received
+//! from the event listener WebSocket endpoint, and the ones emitted while the stream was
+//! disconnected are fetched from the events REST API. Every activity event carries the id
+//! of the server event, which can be stored and used as the cursor to resume the stream
+//! after the restart:
+//!
+//! ```ignore
+//! let source = EventsClient::new("http://127.0.0.1:3001", "ws://127.0.0.1:3032");
+//! let mut activity = wallet.activity(source, Commitment::Committed, last_processed_event);
+//! while let Some(event) = activity.next().await {
+//!     let event = event?;
+//!     process(&event.activity);
+//!     last_processed_event = Some(event.event_id);
+//! }
+//! ```
+
+// Built-in imports
+use std::{collections::VecDeque, time::Duration};
+
+// External uses
+use async_trait::async_trait;
+use futures::{stream::BoxStream, SinkExt, StreamExt};
+use num::BigUint;
+use serde_json::json;
+use tokio_tungstenite::tungstenite::Message;
+
+// Workspace uses
+use zksync_api_types::v02::{
+    event::{EventsPage, EventsQuery},
+    Response,
+};
+use zksync_types::{
+    event::{transaction::TransactionStatus, EventData, EventId, VersionedEvent},
+    Address, BlockNumber, PubKeyHash, TokenId, ZkSyncTx,
+};
+
+// Local uses
+use crate::{error::ClientError, provider::ResponseResult, types::Commitment};
+
+/// Amount of the events requested from the events API at once.
+const EVENTS_PAGE_LIMIT: u32 = 100;
+const MIN_RECONNECT_DELAY: Duration = Duration::from_millis(100);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Stream of the events received via the subscription.
+pub type EventsSubscription = BoxStream<'static, ResponseResult<VersionedEvent>>;
+
+/// Source of the server events the activity stream is built from.
+#[async_trait]
+pub trait EventSource: Send + Sync {
+    /// Requests the stored events with ids greater than `after`, ordered by their ids.
+    async fn events_after(&self, after: EventId, limit: u32) -> ResponseResult<EventsPage>;
+
+    /// Subscribes to the live events matching the filters. The subscription stream ends
+    /// or yields `ClientError::NetworkError` once the connection is lost.
+    async fn subscribe(&self, filters: serde_json::Value) -> ResponseResult<EventsSubscription>;
+}
+
+/// `EventsClient` receives the events from the event listener WebSocket server
+/// and the events REST API.
+#[derive(Debug, Clone)]
+pub struct EventsClient {
+    rest_addr: String,
+    ws_addr: String,
+    client: reqwest::Client,
+}
+
+impl EventsClient {
+    /// Creates a client for the REST API server and the event listener at the given addresses.
+    pub fn new(rest_addr: impl Into<String>, ws_addr: impl Into<String>) -> Self {
+        Self {
+            rest_addr: rest_addr.into(),
+            ws_addr: ws_addr.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl EventSource for EventsClient {
+    async fn events_after(&self, after: EventId, limit: u32) -> ResponseResult<EventsPage> {
+        let url = format!("{}/api/v0.2/events", self.rest_addr.trim_end_matches('/'));
+        let response: Response = self
+            .client
+            .get(&url)
+            .query(&EventsQuery { after, limit })
+            .send()
+            .await
+            .map_err(|err| ClientError::NetworkError(err.to_string()))?
+            .json()
+            .await
+            .map_err(|err| ClientError::MalformedResponse(err.to_string()))?;
+
+        match response.result {
+            Some(result) => serde_json::from_value(result)
+                .map_err(|err| ClientError::MalformedResponse(err.to_string())),
+            None => Err(ClientError::ApiError(
+                response.error.unwrap_or_default().to_string(),
+            )),
+        }
+    }
+
+    async fn subscribe(&self, filters: serde_json::Value) -> ResponseResult<EventsSubscription> {
+        let (mut socket, _) = tokio_tungstenite::connect_async(self.ws_addr.as_str())
+            .await
+            .map_err(|err| ClientError::NetworkError(err.to_string()))?;
+        // Server doesn't send anything until the filters are received.
+        socket
+            .send(Message::Text(filters.to_string()))
+            .await
+            .map_err(|err| ClientError::NetworkError(err.to_string()))?;
+
+        let events = socket.filter_map(|message| async move {
+            match message {
+                Ok(Message::Text(text)) => Some(
+                    serde_json::from_str(&text)
+                        .map_err(|err| ClientError::MalformedResponse(err.to_string())),
+                ),
+                // Pings are answered by the socket itself.
+                Ok(_) => None,
+                Err(err) => Some(Err(ClientError::NetworkError(err.to_string()))),
+            }
+        });
+        Ok(events.boxed())
+    }
+}
+
+/// Activity of the account.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountActivity {
+    /// Transfer from another account.
+    IncomingTransfer {
+        from: Address,
+        token: TokenId,
+        amount: BigUint,
+    },
+    /// Withdrawal from the account included into the finalized block,
+    /// so the funds can be claimed on L1.
+    WithdrawalFinalized {
+        to: Address,
+        token: TokenId,
+        amount: BigUint,
+    },
+    /// Change of the account signing key.
+    ChangePubKey { new_pub_key_hash: PubKeyHash },
+}
+
+/// Account activity along with the server event it's built from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityEvent {
+    /// Id of the server event, the cursor to resume the stream after.
+    pub event_id: EventId,
+    pub block_number: BlockNumber,
+    pub tx_hash: String,
+    pub activity: AccountActivity,
+}
+
+impl ActivityEvent {
+    /// Returns the activity of the account described by the event, if any.
+    /// Transfers and key changes are reported once they reach the given commitment,
+    /// while withdrawals are only reported once finalized.
+    pub fn from_event(
+        address: Address,
+        commitment: Commitment,
+        event: &VersionedEvent,
+    ) -> Option<Self> {
+        let tx_event = match &event.event.data {
+            EventData::Transaction(tx_event) => tx_event,
+            _ => return None,
+        };
+        let reached_commitment = match commitment {
+            Commitment::Committed => tx_event.status == TransactionStatus::Committed,
+            Commitment::Finalized => tx_event.status == TransactionStatus::Finalized,
+        };
+        // Priority operations are not deserialized, none of them is reported.
+        let tx: ZkSyncTx = serde_json::from_value(tx_event.tx.clone()).ok()?;
+
+        let activity = match tx {
+            ZkSyncTx::Transfer(transfer)
+                if transfer.to == address && transfer.from != address && reached_commitment =>
+            {
+                AccountActivity::IncomingTransfer {
+                    from: transfer.from,
+                    token: transfer.token,
+                    amount: transfer.amount,
+                }
+            }
+            ZkSyncTx::Withdraw(withdraw)
+                if withdraw.from == address && tx_event.status == TransactionStatus::Finalized =>
+            {
+                AccountActivity::WithdrawalFinalized {
+                    to: withdraw.to,
+                    token: withdraw.token,
+                    amount: withdraw.amount,
+                }
+            }
+            ZkSyncTx::ChangePubKey(change_pubkey)
+                if change_pubkey.account == address && reached_commitment =>
+            {
+                AccountActivity::ChangePubKey {
+                    new_pub_key_hash: change_pubkey.new_pk_hash,
+                }
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            event_id: event.event_id,
+            block_number: tx_event.block_number,
+            tx_hash: tx_event.tx_hash.clone(),
+            activity,
+        })
+    }
+}
+
+/// Creates the stream of the account activity.
+///
+/// The stream reconnects on the network errors and fetches the events emitted while
+/// it was disconnected, so no activity is lost or reported twice. If `cursor` is provided,
+/// the stream starts with the activity after the given event, otherwise with the live one.
+/// The stream ends after the first error it can't recover from, e.g. when the events
+/// after the cursor were already removed by the server.
+pub fn account_activity<E: EventSource + 'static>(
+    source: E,
+    address: Address,
+    commitment: Commitment,
+    cursor: Option<EventId>,
+) -> BoxStream<'static, ResponseResult<ActivityEvent>> {
+    let state = ActivityState {
+        source,
+        address,
+        commitment,
+        cursor,
+        subscription: None,
+        catching_up: false,
+        pending: VecDeque::new(),
+        reconnect_delay: MIN_RECONNECT_DELAY,
+    };
+
+    futures::stream::unfold(Some(state), |state| async move {
+        let mut state = state?;
+        match state.next_activity().await {
+            Ok(event) => Some((Ok(event), Some(state))),
+            Err(err) => Some((Err(err), None)),
+        }
+    })
+    .boxed()
+}
+
+struct ActivityState<E> {
+    source: E,
+    address: Address,
+    commitment: Commitment,
+    /// Id of the last processed event.
+    cursor: Option<EventId>,
+    subscription: Option<EventsSubscription>,
+    /// Whether the events emitted before the subscription are being fetched.
+    catching_up: bool,
+    pending: VecDeque<VersionedEvent>,
+    reconnect_delay: Duration,
+}
+
+impl<E: EventSource> ActivityState<E> {
+    async fn next_activity(&mut self) -> ResponseResult<ActivityEvent> {
+        loop {
+            while let Some(event) = self.pending.pop_front() {
+                // Events fetched while catching up are delivered via the subscription as well.
+                if matches!(self.cursor, Some(cursor) if event.event_id <= cursor) {
+                    continue;
+                }
+                self.cursor = Some(event.event_id);
+
+                if let Some(activity) =
+                    ActivityEvent::from_event(self.address, self.commitment, &event)
+                {
+                    return Ok(activity);
+                }
+            }
+
+            match self.fetch_events().await {
+                Ok(()) => {}
+                Err(ClientError::NetworkError(_)) => {
+                    self.subscription = None;
+                    tokio::time::sleep(self.reconnect_delay).await;
+                    self.reconnect_delay =
+                        std::cmp::min(self.reconnect_delay * 2, MAX_RECONNECT_DELAY);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Fetches the next events, either from the events API or from the subscription.
+    async fn fetch_events(&mut self) -> ResponseResult<()> {
+        if self.subscription.is_none() {
+            // Subscribe before fetching the stored events, so nothing is missed in between.
+            let filters = json!({ "transaction": { "addresses": [self.address] } });
+            self.subscription = Some(self.source.subscribe(filters).await?);
+            self.catching_up = self.cursor.is_some();
+            return Ok(());
+        }
+
+        if self.catching_up {
+            let cursor = self.cursor.unwrap_or_default();
+            let page = self.source.events_after(cursor, EVENTS_PAGE_LIMIT).await?;
+            self.catching_up = page.events.len() as u32 == EVENTS_PAGE_LIMIT;
+            self.pending.extend(page.events);
+        } else if let Some(subscription) = self.subscription.as_mut() {
+            let event = subscription.next().await.ok_or_else(|| {
+                ClientError::NetworkError("Events subscription was closed".to_string())
+            })?;
+            self.pending.push_back(event?);
+        }
+        self.reconnect_delay = MIN_RECONNECT_DELAY;
+        Ok(())
+    }
+}
@@ -50,23 +50,22 @@ impl<S: EthereumSigner> WalletCredentials<S> {
             .await
             .map_err(ClientError::SigningError)?;
 
-        let packed_signature =
-            if let TxEthSignature::EthereumSignature(packed_signature) = signature {
-                packed_signature
-            } else {
-                return Err(ClientError::IncorrectCredentials);
-            };
-
         // Check that signature is correct and corresponds to the provided address.
-        let address_from_pk = packed_signature
-            .signature_recover_signer_from_raw_message(&eth_sign_message)
-            .map_err(|_| ClientError::IncorrectCredentials)?;
-        if eth_address != address_from_pk {
-            return Err(ClientError::IncorrectAddress);
-        }
+        // Signatures of the contract wallets are checked by the wallet contract itself.
+        let signature_bytes = match signature {
+            TxEthSignature::EthereumSignature(packed_signature) => {
+                let address_from_pk = packed_signature
+                    .signature_recover_signer_from_raw_message(&eth_sign_message)
+                    .map_err(|_| ClientError::IncorrectCredentials)?;
+                if eth_address != address_from_pk {
+                    return Err(ClientError::IncorrectAddress);
+                }
+                packed_signature.serialize_packed().to_vec()
+            }
+            TxEthSignature::EIP1271Signature(signature) => signature.0,
+        };
 
         // Generate seed, and then zkSync private key.
-        let signature_bytes = packed_signature.serialize_packed();
         let zksync_pk = private_key_from_seed(&signature_bytes)?;
 
         Ok(Self {
@@ -12,6 +12,8 @@ pub enum ClientError {
     RpcError(RpcFailure),
     #[error("Network error: {0}")]
     NetworkError(String),
+    #[error("API error: {0}")]
+    ApiError(String),
 
     #[error("Provided account credentials are incorrect")]
     IncorrectCredentials,
@@ -26,6 +28,12 @@ pub enum ClientError {
     OperationTimeout,
     #[error("Polling interval is too small")]
     PollingIntervalIsTooSmall,
+    #[error("Transaction was rejected: {0}")]
+    TransactionRejected(String),
+    #[error("Transaction expired before being executed")]
+    TransactionExpired,
+    #[error("Transaction was replaced by another one with the same nonce")]
+    TransactionReplaced,
 
     #[error("Signing error: {0}")]
     SigningError(SignerError),
@@ -41,6 +49,9 @@ pub enum ClientError {
     #[error("Provided function arguments are incorrect")]
     IncorrectInput,
 
+    #[error("Operation requires the network access and is not available offline: {0}")]
+    OfflineOperation(String),
+
     #[error("Other")]
     Other,
 }
@@ -1,6 +1,8 @@
+pub mod activity;
 pub mod credentials;
 pub mod error;
 pub mod ethereum;
+pub mod offline;
 pub mod operations;
 pub mod provider;
 pub mod signer;
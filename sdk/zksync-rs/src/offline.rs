@@ -0,0 +1,208 @@
+//! Offline construction and signing of the transactions.
+//!
+//! Transactions can be signed on the machine without the network access, e.g. the cold wallet,
+//! by the wallet created with [`Wallet::new_offline`]. Such wallet doesn't request anything
+//! from the server, so the account id and the tokens are provided on its creation, and the
+//! fees and nonces are set on the transaction builders explicitly:
+//!
+//! ```ignore
+//! let wallet = Wallet::new_offline(credentials, Network::Mainnet, account_id, tokens);
+//! let (tx, eth_signature) = wallet
+//!     .start_transfer()
+//!     .token("ETH")?
+//!     .amount(amount)
+//!     .to(recipient)
+//!     .fee(fee)
+//!     .nonce(nonce)
+//!     .tx()
+//!     .await?;
+//! let payload = serde_json::to_string(&SignedPayload::transaction(tx, eth_signature))?;
+//! ```
+//!
+//! The serialized payload is then transferred to the online machine and submitted:
+//!
+//! ```ignore
+//! let payload: SignedPayload = serde_json::from_str(&payload)?;
+//! let handles = payload.submit(&RpcProvider::new(Network::Mainnet)).await?;
+//! ```
+
+use async_trait::async_trait;
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+use zksync_types::{
+    network::Network,
+    tx::{TxEthSignature, TxHash, ZkSyncTx},
+    Address, TokenLike, TxFeeTypes,
+};
+
+use crate::{
+    error::ClientError,
+    operations::SyncTransactionHandle,
+    provider::{Provider, ResponseResult},
+    types::*,
+};
+
+/// Provider of the offline wallet. Every request fails with `ClientError::OfflineOperation`,
+/// so the builders fail if some of the transaction parameters are not set explicitly.
+#[derive(Debug, Clone, Copy)]
+pub struct OfflineProvider {
+    network: Network,
+}
+
+impl OfflineProvider {
+    pub fn new(network: Network) -> Self {
+        Self { network }
+    }
+
+    fn unavailable<T>(operation: &str) -> ResponseResult<T> {
+        Err(ClientError::OfflineOperation(operation.to_string()))
+    }
+}
+
+#[async_trait]
+impl Provider for OfflineProvider {
+    async fn account_info(&self, _address: Address) -> ResponseResult<AccountInfo> {
+        Self::unavailable("account_info")
+    }
+
+    async fn tokens(&self) -> ResponseResult<Tokens> {
+        Self::unavailable("tokens")
+    }
+
+    async fn tx_info(&self, _tx_hash: TxHash) -> ResponseResult<TransactionInfo> {
+        Self::unavailable("tx_info")
+    }
+
+    async fn get_tx_fee(
+        &self,
+        _tx_type: TxFeeTypes,
+        _address: Address,
+        _token: impl Into<TokenLike> + Send + 'async_trait,
+    ) -> ResponseResult<Fee> {
+        Self::unavailable("get_tx_fee")
+    }
+
+    async fn get_txs_batch_fee(
+        &self,
+        _tx_types: Vec<TxFeeTypes>,
+        _addresses: Vec<Address>,
+        _token: impl Into<TokenLike> + Send + 'async_trait,
+    ) -> ResponseResult<BigUint> {
+        Self::unavailable("get_txs_batch_fee")
+    }
+
+    async fn ethop_info(&self, _serial_id: u32) -> ResponseResult<EthOpInfo> {
+        Self::unavailable("ethop_info")
+    }
+
+    async fn get_eth_tx_for_withdrawal(
+        &self,
+        _withdrawal_hash: TxHash,
+    ) -> ResponseResult<Option<String>> {
+        Self::unavailable("get_eth_tx_for_withdrawal")
+    }
+
+    async fn contract_address(&self) -> ResponseResult<ContractAddress> {
+        Self::unavailable("contract_address")
+    }
+
+    async fn send_tx(
+        &self,
+        _tx: ZkSyncTx,
+        _eth_signature: Option<TxEthSignature>,
+    ) -> ResponseResult<TxHash> {
+        Self::unavailable("send_tx")
+    }
+
+    async fn send_txs_batch(
+        &self,
+        _txs_signed: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
+        _eth_signature: Option<TxEthSignature>,
+    ) -> ResponseResult<Vec<TxHash>> {
+        Self::unavailable("send_txs_batch")
+    }
+
+    async fn subscribe_tx(
+        &self,
+        _tx_hash: TxHash,
+        _commitment: Commitment,
+    ) -> ResponseResult<Option<TransactionInfo>> {
+        Self::unavailable("subscribe_tx")
+    }
+
+    fn network(&self) -> Network {
+        self.network
+    }
+}
+
+/// Signed transaction of the batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedTransaction {
+    pub tx: ZkSyncTx,
+    pub signature: Option<TxEthSignature>,
+}
+
+/// Signed transaction or batch ready to be submitted, which can be serialized
+/// to be transferred from the offline machine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SignedPayload {
+    Transaction {
+        tx: ZkSyncTx,
+        signature: Option<TxEthSignature>,
+    },
+    Batch {
+        txs: Vec<SignedTransaction>,
+        signature: Option<TxEthSignature>,
+    },
+}
+
+impl SignedPayload {
+    /// Creates the payload from the output of the transaction builder `tx` method.
+    pub fn transaction(tx: ZkSyncTx, signature: Option<TxEthSignature>) -> Self {
+        Self::Transaction { tx, signature }
+    }
+
+    /// Creates the payload from the output of the batch builder `txs` method.
+    pub fn batch(
+        txs: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
+        signature: Option<TxEthSignature>,
+    ) -> Self {
+        let txs = txs
+            .into_iter()
+            .map(|(tx, signature)| SignedTransaction { tx, signature })
+            .collect();
+        Self::Batch { txs, signature }
+    }
+
+    /// Returns the hashes of the signed transactions.
+    pub fn tx_hashes(&self) -> Vec<TxHash> {
+        match self {
+            Self::Transaction { tx, .. } => vec![tx.hash()],
+            Self::Batch { txs, .. } => txs.iter().map(|signed| signed.tx.hash()).collect(),
+        }
+    }
+
+    /// Submits the transactions, returning the handles for their awaiting.
+    pub async fn submit<P: Provider + Clone>(
+        self,
+        provider: &P,
+    ) -> Result<Vec<SyncTransactionHandle<P>>, ClientError> {
+        let tx_hashes = match self {
+            Self::Transaction { tx, signature } => vec![provider.send_tx(tx, signature).await?],
+            Self::Batch { txs, signature } => {
+                let txs = txs
+                    .into_iter()
+                    .map(|signed| (signed.tx, signed.signature))
+                    .collect();
+                provider.send_txs_batch(txs, signature).await?
+            }
+        };
+
+        Ok(tx_hashes
+            .into_iter()
+            .map(|tx_hash| SyncTransactionHandle::new(tx_hash, provider.clone()))
+            .collect())
+    }
+}
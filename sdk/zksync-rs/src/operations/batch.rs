@@ -0,0 +1,326 @@
+use num::BigUint;
+use zksync_eth_signer::EthereumSigner;
+use zksync_types::{
+    helpers::{
+        closest_greater_or_eq_packable_fee_amount, closest_packable_fee_amount,
+        closest_packable_token_amount, is_fee_amount_packable,
+    },
+    tokens::{ChangePubKeyFeeTypeArg, TxFeeTypes},
+    tx::{ChangePubKeyType, TimeRange, TxEthSignature},
+    Address, Nonce, Token, TokenLike, ZkSyncTx,
+};
+
+use crate::{
+    error::ClientError, operations::SyncTransactionHandle, provider::Provider, wallet::Wallet,
+};
+
+/// Transaction added to the batch, signed once the batch fee is known.
+#[derive(Debug, Clone)]
+enum BatchTx {
+    Transfer {
+        token: Token,
+        amount: BigUint,
+        to: Address,
+    },
+    Withdraw {
+        token: Token,
+        amount: BigUint,
+        to: Address,
+    },
+    ChangePubKey {
+        onchain_auth: bool,
+    },
+}
+
+/// Builder of the transactions batch paying the fee for all its transactions at once.
+///
+/// The fee of the whole batch is requested once and assigned to a single fee-paying
+/// transaction, the rest of the transactions have zero fees. If no fee payer is designated,
+/// the zero transfer to the wallet itself is appended to the batch to pay the fee.
+/// The batch is signed with a single Ethereum signature and executed atomically.
+#[derive(Debug)]
+pub struct BatchBuilder<'a, S: EthereumSigner, P: Provider> {
+    wallet: &'a Wallet<S, P>,
+    txs: Vec<BatchTx>,
+    fee_token: Option<Token>,
+    fee: Option<BigUint>,
+    fee_payer: Option<usize>,
+    nonce: Option<Nonce>,
+    valid_from: Option<u64>,
+    valid_until: Option<u64>,
+}
+
+impl<'a, S, P> BatchBuilder<'a, S, P>
+where
+    S: EthereumSigner,
+    P: Provider + Clone,
+{
+    /// Initializes a transactions batch building process.
+    pub fn new(wallet: &'a Wallet<S, P>) -> Self {
+        Self {
+            wallet,
+            txs: Vec::new(),
+            fee_token: None,
+            fee: None,
+            fee_payer: None,
+            nonce: None,
+            valid_from: None,
+            valid_until: None,
+        }
+    }
+
+    /// Directly returns the signed transactions of the batch and the Ethereum signature
+    /// of the whole batch for the subsequent usage.
+    pub async fn txs(
+        self,
+    ) -> Result<
+        (
+            Vec<(ZkSyncTx, Option<TxEthSignature>)>,
+            Option<TxEthSignature>,
+        ),
+        ClientError,
+    > {
+        let fee_token = self
+            .fee_token
+            .clone()
+            .ok_or_else(|| ClientError::MissingRequiredField("fee_token".into()))?;
+
+        let mut txs = self.txs.clone();
+        let fee_payer = match self.fee_payer {
+            Some(fee_payer) => {
+                // `ChangePubKey` can pay the fee in any token.
+                match txs.get(fee_payer) {
+                    Some(BatchTx::Transfer { token, .. })
+                    | Some(BatchTx::Withdraw { token, .. })
+                        if token.id != fee_token.id =>
+                    {
+                        return Err(ClientError::IncorrectInput)
+                    }
+                    Some(_) => fee_payer,
+                    None => return Err(ClientError::IncorrectInput),
+                }
+            }
+            None => {
+                txs.push(BatchTx::Transfer {
+                    token: fee_token.clone(),
+                    amount: BigUint::from(0u32),
+                    to: self.wallet.address(),
+                });
+                txs.len() - 1
+            }
+        };
+
+        let fee = match self.fee.clone() {
+            Some(fee) => fee,
+            None => {
+                let (tx_types, addresses) = txs
+                    .iter()
+                    .map(|tx| self.fee_type(tx))
+                    .unzip::<_, _, Vec<_>, Vec<_>>();
+                let fee = self
+                    .wallet
+                    .provider
+                    .get_txs_batch_fee(tx_types, addresses, fee_token.id)
+                    .await?;
+                closest_greater_or_eq_packable_fee_amount(&fee)
+            }
+        };
+
+        let mut nonce = match self.nonce {
+            Some(nonce) => nonce,
+            None => {
+                let account_info = self
+                    .wallet
+                    .provider
+                    .account_info(self.wallet.address())
+                    .await?;
+                account_info.committed.nonce
+            }
+        };
+        let time_range = TimeRange::new(
+            self.valid_from.unwrap_or(0),
+            self.valid_until.unwrap_or(u64::MAX),
+        );
+
+        let signer = &self.wallet.signer;
+        let mut signed_txs = Vec::with_capacity(txs.len());
+        let mut message_txs = Vec::with_capacity(txs.len());
+        for (idx, tx) in txs.into_iter().enumerate() {
+            let tx_fee = if idx == fee_payer {
+                fee.clone()
+            } else {
+                BigUint::from(0u32)
+            };
+
+            let (signed_tx, message_token) = match tx {
+                BatchTx::Transfer { token, amount, to } => {
+                    let transfer = signer
+                        .sign_batch_transfer(&token, amount, tx_fee, to, nonce, time_range)
+                        .map_err(ClientError::SigningError)?;
+                    (ZkSyncTx::Transfer(Box::new(transfer)), token)
+                }
+                BatchTx::Withdraw { token, amount, to } => {
+                    let withdraw = signer
+                        .sign_batch_withdraw(&token, amount, tx_fee, to, nonce, time_range)
+                        .map_err(ClientError::SigningError)?;
+                    (ZkSyncTx::Withdraw(Box::new(withdraw)), token)
+                }
+                BatchTx::ChangePubKey { onchain_auth } => {
+                    let change_pubkey = signer
+                        .sign_change_pubkey_tx(
+                            nonce,
+                            onchain_auth,
+                            fee_token.clone(),
+                            tx_fee,
+                            time_range,
+                            Some(self.wallet.provider.network().chain_id()),
+                        )
+                        .await
+                        .map_err(ClientError::SigningError)?;
+                    (ZkSyncTx::from(change_pubkey), fee_token.clone())
+                }
+            };
+            message_txs.push((signed_tx.clone(), message_token));
+            signed_txs.push((signed_tx, None));
+            *nonce += 1;
+        }
+
+        let batch_signature = signer
+            .sign_batch(message_txs)
+            .await
+            .map_err(ClientError::SigningError)?;
+
+        Ok((signed_txs, batch_signature))
+    }
+
+    /// Sends the batch, returning the handles for awaiting its transactions.
+    pub async fn send(self) -> Result<Vec<SyncTransactionHandle<P>>, ClientError> {
+        let provider = self.wallet.provider.clone();
+
+        let (txs, batch_signature) = self.txs().await?;
+        let tx_hashes = provider.send_txs_batch(txs, batch_signature).await?;
+
+        Ok(tx_hashes
+            .into_iter()
+            .map(|tx_hash| SyncTransactionHandle::new(tx_hash, provider.clone()))
+            .collect())
+    }
+
+    /// Adds the transfer to the batch. Returns an error if token is not supported by zkSync.
+    /// If the provided amount is not packable, rounds it to the closest packable amount.
+    pub fn add_transfer(
+        mut self,
+        token: impl Into<TokenLike>,
+        amount: impl Into<BigUint>,
+        to: Address,
+    ) -> Result<Self, ClientError> {
+        let token = self.resolve_token(token)?;
+        let amount = closest_packable_token_amount(&amount.into());
+        self.txs.push(BatchTx::Transfer { token, amount, to });
+
+        Ok(self)
+    }
+
+    /// Adds the withdrawal to the batch. Returns an error if token is not supported by zkSync.
+    /// If the provided amount is not packable, rounds it to the closest packable amount.
+    pub fn add_withdraw(
+        mut self,
+        token: impl Into<TokenLike>,
+        amount: impl Into<BigUint>,
+        to: Address,
+    ) -> Result<Self, ClientError> {
+        let token = self.resolve_token(token)?;
+        let amount = closest_packable_token_amount(&amount.into());
+        self.txs.push(BatchTx::Withdraw { token, amount, to });
+
+        Ok(self)
+    }
+
+    /// Adds the `ChangePubKey` transaction to the batch. Its fee is paid in the fee token of the batch.
+    pub fn add_change_pubkey(mut self, onchain_auth: bool) -> Self {
+        self.txs.push(BatchTx::ChangePubKey { onchain_auth });
+        self
+    }
+
+    /// Sets the token the batch fee is paid in. Returns an error if token is not supported by zkSync.
+    pub fn fee_token(mut self, token: impl Into<TokenLike>) -> Result<Self, ClientError> {
+        self.fee_token = Some(self.resolve_token(token)?);
+        Ok(self)
+    }
+
+    /// Designates the transaction paying the fee of the whole batch by its index in the batch.
+    /// Transfers and withdrawals can only pay the fee if they are made in the fee token.
+    pub fn fee_payer(mut self, index: usize) -> Self {
+        self.fee_payer = Some(index);
+        self
+    }
+
+    /// Set the fee of the whole batch. If the provided fee is not packable,
+    /// rounds it to the closest packable fee amount.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn fee(mut self, fee: impl Into<BigUint>) -> Self {
+        let fee = closest_packable_fee_amount(&fee.into());
+        self.fee = Some(fee);
+
+        self
+    }
+
+    /// Set the fee of the whole batch. If the provided fee is not packable,
+    /// returns an error.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn fee_exact(mut self, fee: impl Into<BigUint>) -> Result<Self, ClientError> {
+        let fee = fee.into();
+        if !is_fee_amount_packable(&fee) {
+            return Err(ClientError::NotPackableValue);
+        }
+        self.fee = Some(fee);
+
+        Ok(self)
+    }
+
+    /// Sets the nonce of the first transaction, the following ones get the consecutive nonces.
+    pub fn nonce(mut self, nonce: Nonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Sets the unix format timestamp of the first moment when the batch execution is valid.
+    pub fn valid_from(mut self, valid_from: u64) -> Self {
+        self.valid_from = Some(valid_from);
+        self
+    }
+
+    /// Sets the unix format timestamp of the last moment when the batch execution is valid.
+    pub fn valid_until(mut self, valid_until: u64) -> Self {
+        self.valid_until = Some(valid_until);
+        self
+    }
+
+    fn resolve_token(&self, token: impl Into<TokenLike>) -> Result<Token, ClientError> {
+        self.wallet
+            .tokens
+            .resolve(token.into())
+            .ok_or(ClientError::UnknownToken)
+    }
+
+    /// Returns the fee type and the address used to request the fee of the transaction.
+    fn fee_type(&self, tx: &BatchTx) -> (TxFeeTypes, Address) {
+        match tx {
+            BatchTx::Transfer { to, .. } => (TxFeeTypes::Transfer, *to),
+            BatchTx::Withdraw { to, .. } => (TxFeeTypes::Withdraw, *to),
+            BatchTx::ChangePubKey { onchain_auth } => {
+                let auth_type = if *onchain_auth {
+                    ChangePubKeyType::Onchain
+                } else {
+                    ChangePubKeyType::EIP712
+                };
+                (
+                    TxFeeTypes::ChangePubKey(ChangePubKeyFeeTypeArg::ContractsV4Version(auth_type)),
+                    self.wallet.address(),
+                )
+            }
+        }
+    }
+}
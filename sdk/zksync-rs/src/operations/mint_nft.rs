@@ -3,7 +3,7 @@ use zksync_eth_signer::EthereumSigner;
 use zksync_types::{
     helpers::{closest_packable_fee_amount, is_fee_amount_packable},
     tokens::TxFeeTypes,
-    tx::PackedEthSignature,
+    tx::TxEthSignature,
     Address, Nonce, Token, TokenLike, ZkSyncTx, H256,
 };
 
@@ -39,7 +39,7 @@ where
     }
 
     /// Directly returns the signed mint nft transaction for the subsequent usage.
-    pub async fn tx(self) -> Result<(ZkSyncTx, Option<PackedEthSignature>), ClientError> {
+    pub async fn tx(self) -> Result<(ZkSyncTx, Option<TxEthSignature>), ClientError> {
         let recipient = self
             .recipient
             .ok_or_else(|| ClientError::MissingRequiredField("recipient".into()))?;
@@ -7,14 +7,16 @@ use zksync_types::tx::TxHash;
 use crate::{
     error::ClientError,
     provider::Provider,
-    types::{BlockInfo, TransactionInfo},
+    types::{BlockInfo, Commitment, TransactionInfo},
 };
 
 pub use self::{
-    change_pubkey::ChangePubKeyBuilder, mint_nft::MintNFTBuilder, transfer::TransferBuilder,
-    transfer_nft::TransferNFTBuilder, withdraw::WithdrawBuilder, withdraw_nft::WithdrawNFTBuilder,
+    batch::BatchBuilder, change_pubkey::ChangePubKeyBuilder, mint_nft::MintNFTBuilder,
+    transfer::TransferBuilder, transfer_nft::TransferNFTBuilder, withdraw::WithdrawBuilder,
+    withdraw_nft::WithdrawNFTBuilder,
 };
 
+mod batch;
 mod change_pubkey;
 mod mint_nft;
 mod transfer;
@@ -22,6 +24,11 @@ mod transfer_nft;
 mod withdraw;
 mod withdraw_nft;
 
+/// Part of the fail reason of the transactions executed out of their time range.
+const EXPIRED_FAIL_REASON: &str = "invalid timestamp";
+/// Part of the fail reason of the transactions which nonce has been taken by another transaction.
+const REPLACED_FAIL_REASON: &str = "Nonce mismatch";
+
 /// Handle for transaction, providing an interface to control its execution.
 /// For obtained handle it's possible to set the polling interval, commit timeout
 /// and verify timeout values.
@@ -49,6 +56,8 @@ impl<P: Provider> SyncTransactionHandle<P> {
     }
 
     const MIN_POLLING_INTERVAL: Duration = Duration::from_millis(200);
+    /// Polling interval the adaptive polling of `wait_for_receipt` backs off to.
+    const MAX_POLLING_INTERVAL: Duration = Duration::from_secs(30);
 
     /// Sets the polling interval. Must be at least 200 milliseconds.
     pub fn polling_interval(&mut self, polling_interval: Duration) -> Result<(), ClientError> {
@@ -122,4 +131,80 @@ impl<P: Provider> SyncTransactionHandle<P> {
             }
         }
     }
+
+    /// Awaits for the transaction to reach the given commitment and returns the information
+    /// about its execution.
+    ///
+    /// The subscriptions endpoint of the provider is used when it's available, otherwise
+    /// the transaction status is polled starting with the polling interval of the handle
+    /// and backing off up to 30 seconds. Returns `TransactionRejected`, `TransactionExpired`
+    /// or `TransactionReplaced` error if the transaction fails, and `OperationTimeout` if
+    /// the commitment is not reached within the `timeout`.
+    pub async fn wait_for_receipt(
+        &self,
+        commitment: Commitment,
+        timeout: Option<Duration>,
+    ) -> Result<TransactionInfo, ClientError> {
+        let receipt = self.wait_for_commitment(commitment);
+        match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, receipt)
+                .await
+                .map_err(|_| ClientError::OperationTimeout)?,
+            None => receipt.await,
+        }
+    }
+
+    async fn wait_for_commitment(
+        &self,
+        commitment: Commitment,
+    ) -> Result<TransactionInfo, ClientError> {
+        // Failed transactions never get finalized, so the status is checked before subscribing.
+        let response = self.provider.tx_info(self.hash).await?;
+        if Self::has_reached(&response, commitment)? {
+            return Ok(response);
+        }
+
+        // Subscription errors are not fatal, the status is polled instead.
+        if let Ok(Some(response)) = self.provider.subscribe_tx(self.hash, commitment).await {
+            if Self::has_reached(&response, commitment)? {
+                return Ok(response);
+            }
+        }
+
+        let max_polling_interval = Self::MAX_POLLING_INTERVAL.max(self.polling_interval);
+        let mut polling_interval = self.polling_interval;
+        loop {
+            tokio::time::sleep(polling_interval).await;
+
+            let response = self.provider.tx_info(self.hash).await?;
+            if Self::has_reached(&response, commitment)? {
+                return Ok(response);
+            }
+            polling_interval = (polling_interval * 2).min(max_polling_interval);
+        }
+    }
+
+    /// Checks whether the transaction has reached the commitment.
+    /// Returns an error describing the failure if the transaction has failed.
+    fn has_reached(
+        response: &TransactionInfo,
+        commitment: Commitment,
+    ) -> Result<bool, ClientError> {
+        if response.executed && response.success == Some(false) {
+            let fail_reason = response.fail_reason.clone().unwrap_or_default();
+            return Err(if fail_reason.contains(EXPIRED_FAIL_REASON) {
+                ClientError::TransactionExpired
+            } else if fail_reason.contains(REPLACED_FAIL_REASON) {
+                ClientError::TransactionReplaced
+            } else {
+                ClientError::TransactionRejected(fail_reason)
+            });
+        }
+
+        Ok(match (&response.block, commitment) {
+            (Some(block), Commitment::Committed) => block.committed,
+            (Some(block), Commitment::Finalized) => block.verified,
+            (None, _) => false,
+        })
+    }
 }
@@ -5,7 +5,7 @@ use zksync_types::{
         closest_packable_fee_amount, closest_packable_token_amount, is_fee_amount_packable,
         is_token_amount_packable,
     },
-    tx::PackedEthSignature,
+    tx::TxEthSignature,
     Address, Nonce, Token, TokenLike, TxFeeTypes, ZkSyncTx,
 };
 
@@ -46,7 +46,7 @@ where
     }
 
     /// Directly returns the signed transfer transaction for the subsequent usage.
-    pub async fn tx(self) -> Result<(ZkSyncTx, Option<PackedEthSignature>), ClientError> {
+    pub async fn tx(self) -> Result<(ZkSyncTx, Option<TxEthSignature>), ClientError> {
         let token = self
             .token
             .ok_or_else(|| ClientError::MissingRequiredField("token".into()))?;
@@ -2,7 +2,7 @@ use num::BigUint;
 use zksync_eth_signer::EthereumSigner;
 use zksync_types::{
     helpers::{closest_packable_fee_amount, is_fee_amount_packable},
-    tx::PackedEthSignature,
+    tx::TxEthSignature,
     Address, Nonce, Token, TokenLike, TxFeeTypes, ZkSyncTx,
 };
 
@@ -48,8 +48,8 @@ where
         self,
     ) -> Result<
         (
-            (ZkSyncTx, Option<PackedEthSignature>),
-            (ZkSyncTx, Option<PackedEthSignature>),
+            (ZkSyncTx, Option<TxEthSignature>),
+            (ZkSyncTx, Option<TxEthSignature>),
         ),
         ClientError,
     > {
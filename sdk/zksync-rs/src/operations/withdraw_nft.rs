@@ -3,7 +3,7 @@ use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 use zksync_eth_signer::EthereumSigner;
 use zksync_types::{
     helpers::{closest_packable_fee_amount, is_fee_amount_packable},
-    tx::{PackedEthSignature, TimeRange},
+    tx::{TimeRange, TxEthSignature},
     Address, Nonce, Token, TokenId, TokenLike, TxFeeTypes, ZkSyncTx,
 };
 
@@ -43,7 +43,7 @@ where
     }
 
     /// Directly returns the signed withdraw transaction for the subsequent usage.
-    pub async fn tx(self) -> Result<(ZkSyncTx, Option<PackedEthSignature>), ClientError> {
+    pub async fn tx(self) -> Result<(ZkSyncTx, Option<TxEthSignature>), ClientError> {
         let to = self
             .to
             .ok_or_else(|| ClientError::MissingRequiredField("to".into()))?;
@@ -6,14 +6,16 @@ use std::time::Duration;
 
 // External uses
 use async_trait::async_trait;
+use futures::StreamExt;
 use jsonrpc_core::{types::response::Output, ErrorCode};
+use jsonrpc_core_client::{transports::ws, TypedClient};
 use num::BigUint;
 
 // Workspace uses
 use zksync_types::{
     network::Network,
-    tx::{PackedEthSignature, TxHash, ZkSyncTx},
-    Address, TokenLike, TxFeeTypes,
+    tx::{TxEthSignature, TxHash, ZkSyncTx},
+    ActionType, Address, TokenLike, TxFeeTypes,
 };
 
 // Local uses
@@ -33,6 +35,18 @@ pub fn get_rpc_addr(network: Network) -> &'static str {
     }
 }
 
+/// Returns a corresponding WebSocket address of the subscriptions endpoint for a provided network name.
+pub fn get_ws_addr(network: Network) -> Option<&'static str> {
+    match network {
+        Network::Mainnet => Some("wss://api.zksync.io/jsrpc-ws"),
+        Network::Rinkeby => Some("wss://rinkeby-api.zksync.io/jsrpc-ws"),
+        Network::Ropsten => Some("wss://ropsten-api.zksync.io/jsrpc-ws"),
+        Network::Localhost => Some("ws://127.0.0.1:3031"),
+        Network::Goerli => Some("wss://goerli-api.zksync.io/jsrpc-ws"),
+        Network::Unknown | Network::Test => None,
+    }
+}
+
 pub type ResponseResult<T> = Result<T, ClientError>;
 
 #[async_trait]
@@ -82,17 +96,27 @@ pub trait Provider {
     async fn send_tx(
         &self,
         tx: ZkSyncTx,
-        eth_signature: Option<PackedEthSignature>,
+        eth_signature: Option<TxEthSignature>,
     ) -> ResponseResult<TxHash>;
 
     /// Submits a batch of transactions to the zkSync network.
     /// Returns the hashes of the created transactions.
     async fn send_txs_batch(
         &self,
-        txs_signed: Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
-        eth_signature: Option<PackedEthSignature>,
+        txs_signed: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
+        eth_signature: Option<TxEthSignature>,
     ) -> ResponseResult<Vec<TxHash>>;
 
+    /// Awaits for the notification about the transaction reaching the given commitment
+    /// via the subscriptions endpoint and returns the information about its execution.
+    /// Returns `None` if the provider doesn't support subscriptions, so the caller has to poll
+    /// the transaction status instead.
+    async fn subscribe_tx(
+        &self,
+        tx_hash: TxHash,
+        commitment: Commitment,
+    ) -> ResponseResult<Option<TransactionInfo>>;
+
     /// Type of network this provider is allowing access to.
     fn network(&self) -> Network;
 }
@@ -102,6 +126,7 @@ pub trait Provider {
 #[derive(Debug, Clone)]
 pub struct RpcProvider {
     rpc_addr: String,
+    ws_addr: Option<String>,
     client: reqwest::Client,
     network: Network,
 }
@@ -167,7 +192,7 @@ impl Provider for RpcProvider {
     async fn send_tx(
         &self,
         tx: ZkSyncTx,
-        eth_signature: Option<PackedEthSignature>,
+        eth_signature: Option<TxEthSignature>,
     ) -> ResponseResult<TxHash> {
         let msg = JsonRpcRequest::submit_tx(tx, eth_signature);
         self.send_and_deserialize(&msg).await
@@ -175,13 +200,51 @@ impl Provider for RpcProvider {
 
     async fn send_txs_batch(
         &self,
-        txs_signed: Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
-        eth_signature: Option<PackedEthSignature>,
+        txs_signed: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
+        eth_signature: Option<TxEthSignature>,
     ) -> ResponseResult<Vec<TxHash>> {
         let msg = JsonRpcRequest::submit_tx_batch(txs_signed, eth_signature);
         self.send_and_deserialize(&msg).await
     }
 
+    async fn subscribe_tx(
+        &self,
+        tx_hash: TxHash,
+        commitment: Commitment,
+    ) -> ResponseResult<Option<TransactionInfo>> {
+        let ws_addr = match &self.ws_addr {
+            Some(ws_addr) => ws_addr,
+            None => return Ok(None),
+        };
+        let action = match commitment {
+            Commitment::Committed => ActionType::COMMIT,
+            Commitment::Finalized => ActionType::VERIFY,
+        };
+
+        let client: TypedClient = ws::try_connect(ws_addr)
+            .map_err(|err| ClientError::NetworkError(err.to_string()))?
+            .await
+            .map_err(|err| ClientError::NetworkError(err.to_string()))?;
+        let mut notifications = client
+            .subscribe::<_, TransactionInfo>(
+                "tx_subscribe",
+                (tx_hash, action),
+                "tx",
+                "tx_unsubscribe",
+                "TransactionInfo",
+            )
+            .map_err(|err| ClientError::NetworkError(err.to_string()))?;
+
+        match notifications.next().await {
+            Some(tx_info) => tx_info
+                .map(Some)
+                .map_err(|err| ClientError::NetworkError(err.to_string())),
+            None => Err(ClientError::NetworkError(
+                "Subscription was closed".to_string(),
+            )),
+        }
+    }
+
     fn network(&self) -> Network {
         self.network
     }
@@ -192,6 +255,7 @@ impl RpcProvider {
     pub fn new(network: Network) -> Self {
         Self {
             rpc_addr: get_rpc_addr(network).into(),
+            ws_addr: get_ws_addr(network).map(String::from),
             client: reqwest::Client::new(),
             network,
         }
@@ -201,6 +265,7 @@ impl RpcProvider {
     pub fn from_addr(rpc_addr: impl Into<String>) -> Self {
         Self {
             rpc_addr: rpc_addr.into(),
+            ws_addr: None,
             client: reqwest::Client::new(),
             network: Network::Unknown,
         }
@@ -210,17 +275,25 @@ impl RpcProvider {
     pub fn from_addr_and_network(rpc_addr: impl Into<String>, network: Network) -> Self {
         Self {
             rpc_addr: rpc_addr.into(),
+            ws_addr: None,
             client: reqwest::Client::new(),
             network,
         }
     }
 
+    /// Sets the address of the WebSocket subscriptions endpoint used to await
+    /// for the transactions without polling.
+    pub fn with_ws_addr(mut self, ws_addr: impl Into<String>) -> Self {
+        self.ws_addr = Some(ws_addr.into());
+        self
+    }
+
     /// Submits a batch transaction to the zkSync network.
     /// Returns the hashes of the created transactions.
     pub async fn send_txs_batch(
         &self,
-        txs_signed: Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
-        eth_signature: Option<PackedEthSignature>,
+        txs_signed: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
+        eth_signature: Option<TxEthSignature>,
     ) -> Result<Vec<TxHash>, ClientError> {
         let msg = JsonRpcRequest::submit_tx_batch(txs_signed, eth_signature);
         self.send_and_deserialize(&msg).await
@@ -323,7 +396,7 @@ impl RpcProvider {
 mod messages {
     use serde::Serialize;
     use zksync_types::{
-        tx::{PackedEthSignature, TxEthSignature, TxHash, ZkSyncTx},
+        tx::{TxEthSignature, TxHash, ZkSyncTx},
         Address, TokenLike, TxFeeTypes,
     };
 
@@ -367,27 +440,28 @@ mod messages {
             Self::create("account_info", json_values![address])
         }
 
-        pub fn submit_tx(tx: ZkSyncTx, eth_signature: Option<PackedEthSignature>) -> Self {
-            let params = json_values![tx, eth_signature.map(TxEthSignature::EthereumSignature)];
+        pub fn submit_tx(tx: ZkSyncTx, eth_signature: Option<TxEthSignature>) -> Self {
+            let params = json_values![tx, eth_signature];
             Self::create("tx_submit", params)
         }
 
         pub fn submit_tx_batch(
-            txs_signed: Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
-            eth_signature: Option<PackedEthSignature>,
+            txs_signed: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
+            eth_signature: Option<TxEthSignature>,
         ) -> Self {
             let mut params = Vec::with_capacity(2);
 
-            let txs_signed = txs_signed.into_iter().map(|(tx, eth_signature)| {
-                serde_json::json!({
-                    "tx": to_json_value(tx),
-                    "signature": to_json_value(eth_signature.map(TxEthSignature::EthereumSignature)),
+            let txs_signed = txs_signed
+                .into_iter()
+                .map(|(tx, eth_signature)| {
+                    serde_json::json!({
+                        "tx": to_json_value(tx),
+                        "signature": to_json_value(eth_signature),
+                    })
                 })
-            }).collect();
+                .collect();
             params.push(serde_json::Value::Array(txs_signed));
-            params.push(to_json_value(
-                eth_signature.map(TxEthSignature::EthereumSignature),
-            ));
+            params.push(to_json_value(eth_signature));
 
             Self::create("submit_txs_batch", params)
         }
@@ -8,11 +8,11 @@ use zksync_eth_signer::{error::SignerError, EthereumSigner};
 use zksync_types::{
     tx::{
         eip712_signature::Eip712Domain, ChangePubKey, ChangePubKeyECDSAData,
-        ChangePubKeyEIP712Data, ChangePubKeyEthAuthData, PackedEthSignature, TimeRange,
+        ChangePubKeyEIP712Data, ChangePubKeyEthAuthData, EthBatchSignData, TimeRange,
         TxEthSignature,
     },
     AccountId, Address, ChainId, ForcedExit, MintNFT, Nonce, PubKeyHash, Token, TokenId, Transfer,
-    Withdraw, WithdrawNFT, H256,
+    Withdraw, WithdrawNFT, ZkSyncTx, H256,
 };
 // Local imports
 use crate::WalletCredentials;
@@ -202,7 +202,7 @@ impl<S: EthereumSigner> Signer<S> {
         to: Address,
         nonce: Nonce,
         time_range: TimeRange,
-    ) -> Result<(Transfer, Option<PackedEthSignature>), SignerError> {
+    ) -> Result<(Transfer, Option<TxEthSignature>), SignerError> {
         let account_id = self.account_id.ok_or(SignerError::NoSigningKey)?;
 
         let transfer = Transfer::new_signed(
@@ -221,13 +221,7 @@ impl<S: EthereumSigner> Signer<S> {
         let eth_signature = match &self.eth_signer {
             Some(signer) => {
                 let message = transfer.get_ethereum_sign_message(&token.symbol, token.decimals);
-                let signature = signer.sign_message(message.as_bytes()).await?;
-
-                if let TxEthSignature::EthereumSignature(packed_signature) = signature {
-                    Some(packed_signature)
-                } else {
-                    return Err(SignerError::MissingEthSigner);
-                }
+                Some(signer.sign_message(message.as_bytes()).await?)
             }
             _ => None,
         };
@@ -243,7 +237,7 @@ impl<S: EthereumSigner> Signer<S> {
         eth_address: Address,
         nonce: Nonce,
         time_range: TimeRange,
-    ) -> Result<(Withdraw, Option<PackedEthSignature>), SignerError> {
+    ) -> Result<(Withdraw, Option<TxEthSignature>), SignerError> {
         let account_id = self.account_id.ok_or(SignerError::NoSigningKey)?;
 
         let withdraw = Withdraw::new_signed(
@@ -262,13 +256,7 @@ impl<S: EthereumSigner> Signer<S> {
         let eth_signature = match &self.eth_signer {
             Some(signer) => {
                 let message = withdraw.get_ethereum_sign_message(&token.symbol, token.decimals);
-                let signature = signer.sign_message(message.as_bytes()).await?;
-
-                if let TxEthSignature::EthereumSignature(packed_signature) = signature {
-                    Some(packed_signature)
-                } else {
-                    return Err(SignerError::MissingEthSigner);
-                }
+                Some(signer.sign_message(message.as_bytes()).await?)
             }
             _ => None,
         };
@@ -276,6 +264,78 @@ impl<S: EthereumSigner> Signer<S> {
         Ok((withdraw, eth_signature))
     }
 
+    /// Signs the transfer without the Ethereum signature, which is provided for the whole batch instead.
+    pub(crate) fn sign_batch_transfer(
+        &self,
+        token: &Token,
+        amount: BigUint,
+        fee: BigUint,
+        to: Address,
+        nonce: Nonce,
+        time_range: TimeRange,
+    ) -> Result<Transfer, SignerError> {
+        let account_id = self.account_id.ok_or(SignerError::NoSigningKey)?;
+
+        Transfer::new_signed(
+            account_id,
+            self.address,
+            to,
+            token.id,
+            amount,
+            fee,
+            nonce,
+            time_range,
+            &self.private_key,
+        )
+        .map_err(signing_failed_error)
+    }
+
+    /// Signs the withdrawal without the Ethereum signature, which is provided for the whole batch instead.
+    pub(crate) fn sign_batch_withdraw(
+        &self,
+        token: &Token,
+        amount: BigUint,
+        fee: BigUint,
+        eth_address: Address,
+        nonce: Nonce,
+        time_range: TimeRange,
+    ) -> Result<Withdraw, SignerError> {
+        let account_id = self.account_id.ok_or(SignerError::NoSigningKey)?;
+
+        Withdraw::new_signed(
+            account_id,
+            self.address,
+            eth_address,
+            token.id,
+            amount,
+            fee,
+            nonce,
+            time_range,
+            &self.private_key,
+        )
+        .map_err(signing_failed_error)
+    }
+
+    /// Signs the message of the transactions batch with the Ethereum key.
+    /// Each transaction is accompanied by the token used in its message, e.g. the fee token of `ChangePubKey`.
+    /// Returns `None` if the signer has no Ethereum key, same as the methods signing the single transactions.
+    pub async fn sign_batch(
+        &self,
+        txs: Vec<(ZkSyncTx, Token)>,
+    ) -> Result<Option<TxEthSignature>, SignerError> {
+        let signer = match &self.eth_signer {
+            Some(signer) => signer,
+            None => return Ok(None),
+        };
+
+        let txs = txs
+            .into_iter()
+            .map(|(tx, token)| (tx, token, self.address))
+            .collect();
+        let message = EthBatchSignData::get_batch_sign_message(txs);
+        Ok(Some(signer.sign_message(&message).await?))
+    }
+
     pub async fn sign_forced_exit(
         &self,
         target: Address,
@@ -283,7 +343,7 @@ impl<S: EthereumSigner> Signer<S> {
         fee: BigUint,
         nonce: Nonce,
         time_range: TimeRange,
-    ) -> Result<(ForcedExit, Option<PackedEthSignature>), SignerError> {
+    ) -> Result<(ForcedExit, Option<TxEthSignature>), SignerError> {
         let account_id = self.account_id.ok_or(SignerError::NoSigningKey)?;
 
         let forced_exit = ForcedExit::new_signed(
@@ -300,13 +360,7 @@ impl<S: EthereumSigner> Signer<S> {
         let eth_signature = match &self.eth_signer {
             Some(signer) => {
                 let message = forced_exit.get_ethereum_sign_message(&token.symbol, token.decimals);
-                let signature = signer.sign_message(message.as_bytes()).await?;
-
-                if let TxEthSignature::EthereumSignature(packed_signature) = signature {
-                    Some(packed_signature)
-                } else {
-                    return Err(SignerError::MissingEthSigner);
-                }
+                Some(signer.sign_message(message.as_bytes()).await?)
             }
             _ => None,
         };
@@ -321,7 +375,7 @@ impl<S: EthereumSigner> Signer<S> {
         fee_token: Token,
         fee: BigUint,
         nonce: Nonce,
-    ) -> Result<(MintNFT, Option<PackedEthSignature>), SignerError> {
+    ) -> Result<(MintNFT, Option<TxEthSignature>), SignerError> {
         let account_id = self.account_id.ok_or(SignerError::NoSigningKey)?;
 
         let mint_nft = MintNFT::new_signed(
@@ -340,13 +394,7 @@ impl<S: EthereumSigner> Signer<S> {
             Some(signer) => {
                 let message =
                     mint_nft.get_ethereum_sign_message(&fee_token.symbol, fee_token.decimals);
-                let signature = signer.sign_message(message.as_bytes()).await?;
-
-                if let TxEthSignature::EthereumSignature(packed_signature) = signature {
-                    Some(packed_signature)
-                } else {
-                    return Err(SignerError::MissingEthSigner);
-                }
+                Some(signer.sign_message(message.as_bytes()).await?)
             }
             _ => None,
         };
@@ -362,7 +410,7 @@ impl<S: EthereumSigner> Signer<S> {
         fee: BigUint,
         nonce: Nonce,
         time_range: TimeRange,
-    ) -> Result<(WithdrawNFT, Option<PackedEthSignature>), SignerError> {
+    ) -> Result<(WithdrawNFT, Option<TxEthSignature>), SignerError> {
         let account_id = self.account_id.ok_or(SignerError::NoSigningKey)?;
 
         let withdraw_nft = WithdrawNFT::new_signed(
@@ -382,13 +430,7 @@ impl<S: EthereumSigner> Signer<S> {
             Some(signer) => {
                 let message =
                     withdraw_nft.get_ethereum_sign_message(&fee_token.symbol, fee_token.decimals);
-                let signature = signer.sign_message(message.as_bytes()).await?;
-
-                if let TxEthSignature::EthereumSignature(packed_signature) = signature {
-                    Some(packed_signature)
-                } else {
-                    return Err(SignerError::MissingEthSigner);
-                }
+                Some(signer.sign_message(message.as_bytes()).await?)
             }
             _ => None,
         };
@@ -47,6 +47,15 @@ pub enum BlockStatus {
     Verified,
 }
 
+/// Level of the transaction finality to await for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Commitment {
+    /// The block with the transaction is committed on Ethereum.
+    Committed,
+    /// The block with the transaction is verified on Ethereum, so it can't be reverted.
+    Finalized,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountInfo {
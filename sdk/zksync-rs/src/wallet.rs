@@ -1,16 +1,23 @@
+use std::time::Duration;
+
+use futures::stream::BoxStream;
 use num::BigUint;
 use zksync_eth_signer::EthereumSigner;
-use zksync_types::{AccountId, Address, TokenId, TokenLike};
+use zksync_types::{
+    event::EventId, network::Network, tx::TxHash, AccountId, Address, TokenId, TokenLike,
+};
 
 use crate::{
+    activity::{account_activity, ActivityEvent, EventSource},
     credentials::WalletCredentials,
     error::ClientError,
     ethereum::EthereumProvider,
+    offline::OfflineProvider,
     operations::*,
     provider::Provider,
     signer::Signer,
     tokens_cache::TokensCache,
-    types::{AccountInfo, BlockStatus, NFT},
+    types::{AccountInfo, BlockStatus, Commitment, Tokens, TransactionInfo, NFT},
 };
 
 #[derive(Debug)]
@@ -56,6 +63,17 @@ where
         self.signer.address
     }
 
+    /// Returns the stream of the wallet account activity, see the
+    /// [activity](../activity/index.html) module for details.
+    pub fn activity<E: EventSource + 'static>(
+        &self,
+        source: E,
+        commitment: Commitment,
+        cursor: Option<EventId>,
+    ) -> BoxStream<'static, Result<ActivityEvent, ClientError>> {
+        account_activity(source, self.address(), commitment, cursor)
+    }
+
     /// Returns account state info.
     pub async fn account_info(&self) -> Result<AccountInfo, ClientError> {
         self.provider.account_info(self.address()).await
@@ -158,6 +176,24 @@ where
         WithdrawNFTBuilder::new(self)
     }
 
+    /// Initializes the transactions batch sending, the fee of the whole batch is paid by a single transaction.
+    pub fn start_batch(&self) -> BatchBuilder<'_, S, P> {
+        BatchBuilder::new(self)
+    }
+
+    /// Awaits for the transaction to reach the given commitment, see
+    /// [`SyncTransactionHandle::wait_for_receipt`] for details.
+    pub async fn wait_for_receipt(
+        &self,
+        tx_hash: TxHash,
+        commitment: Commitment,
+        timeout: Option<Duration>,
+    ) -> Result<TransactionInfo, ClientError> {
+        SyncTransactionHandle::new(tx_hash, self.provider.clone())
+            .wait_for_receipt(commitment, timeout)
+            .await
+    }
+
     /// Creates an `EthereumProvider` to interact with the Ethereum network.
     ///
     /// Returns an error if wallet was created without providing an Ethereum private key.
@@ -181,3 +217,27 @@ where
         }
     }
 }
+
+impl<S: EthereumSigner> Wallet<S, OfflineProvider> {
+    /// Creates the wallet signing the transactions without the network access, see
+    /// the [offline](../offline/index.html) module for details.
+    ///
+    /// The account id and the supported tokens are normally requested from the server,
+    /// so they have to be provided. Transactions built by this wallet must have the fee
+    /// and the nonce set explicitly.
+    pub fn new_offline(
+        credentials: WalletCredentials<S>,
+        network: Network,
+        account_id: AccountId,
+        tokens: Tokens,
+    ) -> Self {
+        let mut signer = Signer::with_credentials(credentials);
+        signer.set_account_id(Some(account_id));
+
+        Wallet {
+            provider: OfflineProvider::new(network),
+            signer,
+            tokens: TokensCache::new(tokens),
+        }
+    }
+}
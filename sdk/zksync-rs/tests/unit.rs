@@ -155,9 +155,16 @@ mod signatures_with_vectors {
     use zksync::{signer::Signer, WalletCredentials};
     use zksync_config::test_config::unit_vectors::TxData;
     use zksync_eth_signer::PrivateKeySigner;
-    use zksync_types::tx::{ChangePubKeyECDSAData, ChangePubKeyEthAuthData};
+    use zksync_types::tx::{ChangePubKeyECDSAData, ChangePubKeyEthAuthData, TxEthSignature};
     use zksync_types::{network::Network, AccountId, Address, H256};
 
+    fn serialize_eth_signature(signature: Option<TxEthSignature>) -> [u8; 65] {
+        match signature {
+            Some(TxEthSignature::EthereumSignature(signature)) => signature.serialize_packed(),
+            _ => panic!("Expected ECDSA signature"),
+        }
+    }
+
     async fn get_signer(
         eth_private_key_raw: &[u8],
         from_address: Address,
@@ -225,7 +232,7 @@ mod signatures_with_vectors {
                 );
 
                 if let Some(expected_eth_signature) = outputs.eth_signature {
-                    let eth_signature = eth_signature.unwrap().serialize_packed();
+                    let eth_signature = serialize_eth_signature(eth_signature);
                     assert_eq!(&eth_signature[..], expected_eth_signature.as_slice());
                 }
             }
@@ -282,7 +289,7 @@ mod signatures_with_vectors {
                 );
 
                 if let Some(expected_eth_signature) = outputs.eth_signature {
-                    let eth_signature = eth_signature.unwrap().serialize_packed();
+                    let eth_signature = serialize_eth_signature(eth_signature);
                     assert_eq!(&eth_signature[..], expected_eth_signature.as_slice());
                 }
             }
@@ -340,7 +347,7 @@ mod signatures_with_vectors {
                 );
 
                 if let Some(expected_eth_signature) = outputs.eth_signature {
-                    let eth_signature = eth_signature.unwrap().serialize_packed();
+                    let eth_signature = serialize_eth_signature(eth_signature);
                     assert_eq!(&eth_signature[..], expected_eth_signature.as_slice());
                 }
             }
@@ -397,7 +404,7 @@ mod signatures_with_vectors {
                 );
 
                 if let Some(expected_eth_signature) = outputs.eth_signature {
-                    let eth_signature = eth_signature.unwrap().serialize_packed();
+                    let eth_signature = serialize_eth_signature(eth_signature);
                     assert_eq!(&eth_signature[..], expected_eth_signature.as_slice());
                 }
             }
@@ -512,20 +519,22 @@ mod signatures_with_vectors {
 mod wallet_tests {
     use super::*;
     use num::{BigUint, ToPrimitive};
+    use std::time::Duration;
     use zksync::{
         error::ClientError,
+        offline::SignedPayload,
         provider::Provider,
         signer::Signer,
         types::{
-            AccountInfo, AccountState, BlockStatus, ContractAddress, EthOpInfo, Fee, Tokens,
-            TransactionInfo,
+            AccountInfo, AccountState, BlockInfo, BlockStatus, Commitment, ContractAddress,
+            EthOpInfo, Fee, Tokens, TransactionInfo,
         },
         Network, Wallet, WalletCredentials,
     };
     use zksync_eth_signer::PrivateKeySigner;
     use zksync_types::{
         tokens::get_genesis_token_list,
-        tx::{PackedEthSignature, TxHash},
+        tx::{EthBatchSignData, PackedEthSignature, TxEthSignature, TxHash},
         Address, PubKeyHash, TokenId, TokenLike, TxFeeTypes, ZkSyncTx, H256,
     };
 
@@ -603,8 +612,38 @@ mod wallet_tests {
             Ok(tokens)
         }
 
-        async fn tx_info(&self, _tx_hash: TxHash) -> Result<TransactionInfo, ClientError> {
-            unreachable!()
+        /// Returns the status of the transaction depending on the first byte of its hash:
+        ///  - 1 for the transaction in the committed block;
+        ///  - 2 for the transaction failed because of the nonce mismatch;
+        ///  - 3 for the transaction failed because of the invalid timestamp;
+        ///  - 4 for the transaction failed because of another reason;
+        ///  - the transaction is not executed otherwise.
+        async fn tx_info(&self, tx_hash: TxHash) -> Result<TransactionInfo, ClientError> {
+            let fail_reason = match tx_hash.as_ref()[0] {
+                1 => None,
+                2 => Some("Nonce mismatch"),
+                3 => Some("The transaction can't be executed in the block because of an invalid timestamp"),
+                4 => Some("Not enough balance"),
+                _ => {
+                    return Ok(TransactionInfo {
+                        executed: false,
+                        success: None,
+                        fail_reason: None,
+                        block: None,
+                    })
+                }
+            };
+
+            Ok(TransactionInfo {
+                executed: true,
+                success: Some(fail_reason.is_none()),
+                fail_reason: fail_reason.map(String::from),
+                block: Some(BlockInfo {
+                    block_number: 1,
+                    committed: fail_reason.is_none(),
+                    verified: false,
+                }),
+            })
         }
 
         async fn get_tx_fee(
@@ -616,13 +655,14 @@ mod wallet_tests {
             unreachable!()
         }
 
+        /// Returns the fee of 1000 per each transaction of the batch.
         async fn get_txs_batch_fee(
             &self,
-            _tx_types: Vec<TxFeeTypes>,
+            tx_types: Vec<TxFeeTypes>,
             _addresses: Vec<Address>,
             _token: impl Into<TokenLike> + Send + 'async_trait,
         ) -> Result<BigUint, ClientError> {
-            unreachable!()
+            Ok(BigUint::from(1000u32 * tx_types.len() as u32))
         }
 
         async fn ethop_info(&self, _serial_id: u32) -> Result<EthOpInfo, ClientError> {
@@ -649,19 +689,28 @@ mod wallet_tests {
         async fn send_tx(
             &self,
             _tx: ZkSyncTx,
-            _eth_signature: Option<PackedEthSignature>,
+            _eth_signature: Option<TxEthSignature>,
         ) -> Result<TxHash, ClientError> {
             unreachable!()
         }
 
         async fn send_txs_batch(
             &self,
-            _txs_signed: Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
-            _eth_signature: Option<PackedEthSignature>,
+            _txs_signed: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
+            _eth_signature: Option<TxEthSignature>,
         ) -> Result<Vec<TxHash>, ClientError> {
             unreachable!()
         }
 
+        /// Subscriptions are not supported, so the transactions status is polled.
+        async fn subscribe_tx(
+            &self,
+            _tx_hash: TxHash,
+            _commitment: Commitment,
+        ) -> Result<Option<TransactionInfo>, ClientError> {
+            Ok(None)
+        }
+
         fn network(&self) -> Network {
             self.network
         }
@@ -767,6 +816,105 @@ mod wallet_tests {
         assert!(wallet.is_signing_key_set().await.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_wallet_batch() {
+        let wallet = get_test_wallet(&[60; 32], Network::Mainnet).await;
+        let dai = wallet
+            .tokens
+            .resolve(TokenLike::Symbol("DAI".into()))
+            .unwrap();
+        let recipient = Address::repeat_byte(0x11);
+
+        let (txs, batch_signature) = wallet
+            .start_batch()
+            .add_transfer("DAI", 100u32, recipient)
+            .unwrap()
+            .add_withdraw("DAI", 200u32, recipient)
+            .unwrap()
+            .fee_token("DAI")
+            .unwrap()
+            .txs()
+            .await
+            .unwrap();
+
+        // The fee of the whole batch is paid by the transfer to the wallet itself appended to the batch.
+        assert_eq!(txs.len(), 3);
+        let fees: Vec<_> = txs
+            .iter()
+            .map(|(tx, _)| match tx {
+                ZkSyncTx::Transfer(tx) => tx.fee.clone(),
+                ZkSyncTx::Withdraw(tx) => tx.fee.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            fees,
+            vec![
+                BigUint::from(0u32),
+                BigUint::from(0u32),
+                BigUint::from(3000u32)
+            ]
+        );
+        match &txs[2].0 {
+            ZkSyncTx::Transfer(tx) => assert_eq!(tx.to, wallet.address()),
+            _ => panic!("Fee is not paid by the transfer"),
+        }
+        let nonces: Vec<_> = txs.iter().map(|(tx, _)| tx.nonce()).collect();
+        assert_eq!(nonces, vec![Nonce(0), Nonce(1), Nonce(2)]);
+        assert!(txs.iter().all(|(_, signature)| signature.is_none()));
+
+        // The whole batch is signed at once.
+        let message = EthBatchSignData::get_batch_sign_message(
+            txs.into_iter()
+                .map(|(tx, _)| (tx, dai.clone(), wallet.address()))
+                .collect(),
+        );
+        let batch_signature = match batch_signature {
+            Some(TxEthSignature::EthereumSignature(signature)) => signature,
+            _ => panic!("Expected ECDSA signature"),
+        };
+        let signer = batch_signature
+            .signature_recover_signer_from_raw_message(&message)
+            .unwrap();
+        assert_eq!(signer, wallet.address());
+    }
+
+    #[tokio::test]
+    async fn test_wallet_batch_fee_payer() {
+        let wallet = get_test_wallet(&[60; 32], Network::Mainnet).await;
+        let recipient = Address::repeat_byte(0x11);
+
+        // The designated fee payer must be made in the fee token.
+        let result = wallet
+            .start_batch()
+            .add_transfer("DAI", 100u32, recipient)
+            .unwrap()
+            .fee_token("USDC")
+            .unwrap()
+            .fee_payer(0)
+            .txs()
+            .await;
+        assert_eq!(result.unwrap_err(), ClientError::IncorrectInput);
+
+        let (txs, _) = wallet
+            .start_batch()
+            .add_transfer("DAI", 100u32, recipient)
+            .unwrap()
+            .add_transfer("DAI", 200u32, recipient)
+            .unwrap()
+            .fee_token("DAI")
+            .unwrap()
+            .fee_payer(0)
+            .txs()
+            .await
+            .unwrap();
+        assert_eq!(txs.len(), 2);
+        match &txs[0].0 {
+            ZkSyncTx::Transfer(tx) => assert_eq!(tx.fee, BigUint::from(2000u32)),
+            _ => unreachable!(),
+        }
+    }
+
     #[tokio::test]
     async fn test_wallet_ethereum() {
         let wallet = get_test_wallet(&[50; 32], Network::Mainnet).await;
@@ -774,4 +922,295 @@ mod wallet_tests {
         let expected_address: Vec<_> = (0..20).collect();
         assert_eq!(eth_provider.contract_address().as_bytes(), expected_address);
     }
+
+    #[tokio::test]
+    async fn test_wallet_wait_for_receipt() {
+        let wallet = get_test_wallet(&[60; 32], Network::Mainnet).await;
+        let tx_hash = |status: u8| TxHash::from_slice(&[status; 32]).unwrap();
+        let timeout = Some(Duration::from_millis(100));
+
+        let receipt = wallet
+            .wait_for_receipt(tx_hash(1), Commitment::Committed, timeout)
+            .await
+            .unwrap();
+        assert!(receipt.block.unwrap().committed);
+
+        // The block is never verified, and the mock provider doesn't support subscriptions,
+        // so the status is polled until the timeout.
+        let result = wallet
+            .wait_for_receipt(tx_hash(1), Commitment::Finalized, timeout)
+            .await;
+        assert_eq!(result.unwrap_err(), ClientError::OperationTimeout);
+        let result = wallet
+            .wait_for_receipt(tx_hash(0), Commitment::Committed, timeout)
+            .await;
+        assert_eq!(result.unwrap_err(), ClientError::OperationTimeout);
+
+        // Failures are reported regardless of the awaited commitment.
+        let result = wallet
+            .wait_for_receipt(tx_hash(2), Commitment::Finalized, timeout)
+            .await;
+        assert_eq!(result.unwrap_err(), ClientError::TransactionReplaced);
+        let result = wallet
+            .wait_for_receipt(tx_hash(3), Commitment::Committed, timeout)
+            .await;
+        assert_eq!(result.unwrap_err(), ClientError::TransactionExpired);
+        let result = wallet
+            .wait_for_receipt(tx_hash(4), Commitment::Committed, None)
+            .await;
+        assert_eq!(
+            result.unwrap_err(),
+            ClientError::TransactionRejected("Not enough balance".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_offline_wallet() {
+        let private_key = H256::from([70; 32]);
+        let address = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+        let creds = WalletCredentials::from_eth_signer(
+            address,
+            PrivateKeySigner::new(private_key),
+            Network::Mainnet,
+        )
+        .await
+        .unwrap();
+        let tokens = MockProvider {
+            network: Network::Mainnet,
+            eth_private_key: private_key,
+        }
+        .tokens()
+        .await
+        .unwrap();
+        let wallet = Wallet::new_offline(creds, Network::Mainnet, AccountId(42), tokens);
+        let recipient = Address::repeat_byte(0x11);
+
+        // The nonce can't be requested offline.
+        let result = wallet
+            .start_transfer()
+            .token("DAI")
+            .unwrap()
+            .amount(100u32)
+            .to(recipient)
+            .fee(10u32)
+            .tx()
+            .await;
+        assert_eq!(
+            result.unwrap_err(),
+            ClientError::OfflineOperation("account_info".to_string())
+        );
+
+        let (tx, eth_signature) = wallet
+            .start_transfer()
+            .token("DAI")
+            .unwrap()
+            .amount(100u32)
+            .to(recipient)
+            .fee(10u32)
+            .nonce(Nonce(5))
+            .tx()
+            .await
+            .unwrap();
+        assert!(eth_signature.is_some());
+        let tx_hash = tx.hash();
+
+        // The signed transaction survives the serialization roundtrip.
+        let payload = SignedPayload::transaction(tx, eth_signature);
+        let serialized = serde_json::to_string(&payload).unwrap();
+        let payload: SignedPayload = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(payload.tx_hashes(), vec![tx_hash]);
+        match payload {
+            SignedPayload::Transaction {
+                tx: ZkSyncTx::Transfer(transfer),
+                signature: Some(_),
+            } => {
+                assert_eq!(transfer.account_id, AccountId(42));
+                assert_eq!(transfer.nonce, Nonce(5));
+                assert!(transfer.verify_signature().is_some());
+            }
+            _ => panic!("Unexpected payload"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod activity_tests {
+    use super::*;
+    use futures::{stream, StreamExt};
+    use num::BigUint;
+    use serde_json::json;
+    use std::{collections::VecDeque, sync::Mutex};
+    use zksync::{
+        activity::{AccountActivity, EventSource, EventsSubscription},
+        error::ClientError,
+        provider::ResponseResult,
+        types::Commitment,
+    };
+    use zksync_api_types::v02::event::EventsPage;
+    use zksync_types::{
+        event::{transaction::TransactionStatus, EventData, EventId, VersionedEvent, ZkSyncEvent},
+        tx::ChangePubKey,
+        Address, BlockNumber, PubKeyHash, Transfer, Withdraw, ZkSyncTx,
+    };
+
+    /// Serves the events from the predefined list, every subscription delivers
+    /// the given events and then loses the connection.
+    struct MockEventSource {
+        stored_events: Vec<VersionedEvent>,
+        subscriptions: Mutex<VecDeque<Vec<VersionedEvent>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl EventSource for MockEventSource {
+        async fn events_after(&self, after: EventId, limit: u32) -> ResponseResult<EventsPage> {
+            let events: Vec<_> = self
+                .stored_events
+                .iter()
+                .filter(|event| event.event_id > after)
+                .take(limit as usize)
+                .cloned()
+                .collect();
+            let next_cursor = events.last().map_or(after, |event| event.event_id);
+            Ok(EventsPage {
+                events,
+                next_cursor,
+            })
+        }
+
+        async fn subscribe(
+            &self,
+            _filters: serde_json::Value,
+        ) -> ResponseResult<EventsSubscription> {
+            match self.subscriptions.lock().unwrap().pop_front() {
+                Some(events) => Ok(stream::iter(events.into_iter().map(Ok)).boxed()),
+                None => Err(ClientError::NetworkError("Connection refused".to_string())),
+            }
+        }
+    }
+
+    fn tx_event(event_id: u64, tx: ZkSyncTx, status: TransactionStatus) -> VersionedEvent {
+        let tx_event = serde_json::from_value(json!({
+            "tx_hash": tx.hash().to_string(),
+            "account_id": 1,
+            "token_id": 0,
+            "block_number": event_id,
+            "tx": tx,
+            "status": status,
+            "created_at": "2021-10-01T00:00:00Z",
+        }))
+        .unwrap();
+        ZkSyncEvent {
+            id: EventId(event_id),
+            block_number: BlockNumber(event_id as u32),
+            data: EventData::Transaction(tx_event),
+        }
+        .into()
+    }
+
+    #[tokio::test]
+    async fn test_account_activity_stream() {
+        let address = Address::repeat_byte(0x01);
+        let other = Address::repeat_byte(0x02);
+        let transfer = |from, to| {
+            ZkSyncTx::from(Transfer::new(
+                AccountId(1),
+                from,
+                to,
+                TokenId(0),
+                BigUint::from(100u32),
+                BigUint::from(1u32),
+                Nonce(0),
+                Default::default(),
+                None,
+            ))
+        };
+        let withdraw = ZkSyncTx::from(Withdraw::new(
+            AccountId(1),
+            address,
+            other,
+            TokenId(1),
+            BigUint::from(50u32),
+            BigUint::from(1u32),
+            Nonce(1),
+            Default::default(),
+            None,
+        ));
+        let change_pubkey = ZkSyncTx::from(ChangePubKey::new(
+            AccountId(1),
+            address,
+            PubKeyHash::default(),
+            TokenId(0),
+            BigUint::from(1u32),
+            Nonce(2),
+            Default::default(),
+            None,
+            None,
+            None,
+        ));
+
+        let events = vec![
+            tx_event(1, transfer(other, address), TransactionStatus::Committed),
+            tx_event(2, transfer(address, other), TransactionStatus::Committed),
+            // Emitted while the stream is disconnected.
+            tx_event(3, change_pubkey, TransactionStatus::Committed),
+            tx_event(4, withdraw.clone(), TransactionStatus::Committed),
+            tx_event(5, withdraw, TransactionStatus::Finalized),
+            tx_event(6, transfer(other, address), TransactionStatus::Finalized),
+        ];
+        let source = MockEventSource {
+            stored_events: events.clone(),
+            subscriptions: Mutex::new(VecDeque::from(vec![
+                events[..2].to_vec(),
+                events[4..].to_vec(),
+            ])),
+        };
+
+        let activity: Vec<_> =
+            zksync::activity::account_activity(source, address, Commitment::Committed, None)
+                .take(3)
+                .map(Result::unwrap)
+                .collect()
+                .await;
+        let event_ids: Vec<_> = activity.iter().map(|event| event.event_id).collect();
+        assert_eq!(event_ids, vec![EventId(1), EventId(3), EventId(5)]);
+        assert_eq!(
+            activity[0].activity,
+            AccountActivity::IncomingTransfer {
+                from: other,
+                token: TokenId(0),
+                amount: BigUint::from(100u32),
+            }
+        );
+        assert_eq!(
+            activity[1].activity,
+            AccountActivity::ChangePubKey {
+                new_pub_key_hash: PubKeyHash::default(),
+            }
+        );
+        assert_eq!(
+            activity[2].activity,
+            AccountActivity::WithdrawalFinalized {
+                to: other,
+                token: TokenId(1),
+                amount: BigUint::from(50u32),
+            }
+        );
+
+        // The stream resumes after the cursor.
+        let source = MockEventSource {
+            stored_events: events.clone(),
+            subscriptions: Mutex::new(VecDeque::from(vec![Vec::new()])),
+        };
+        let activity = zksync::activity::account_activity(
+            source,
+            address,
+            Commitment::Finalized,
+            Some(EventId(4)),
+        )
+        .next()
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(activity.event_id, EventId(5));
+    }
 }
@@ -455,6 +455,10 @@ export class RestProvider extends SyncProvider {
     async notifyAnyTransaction(hash: string, action: 'COMMIT' | 'VERIFY'): Promise<types.ApiTxReceipt> {
         while (true) {
             let transactionStatus = await this.txStatus(hash);
+            if (transactionStatus && transactionStatus.status === 'expired') {
+                // Expired transactions are never executed.
+                return transactionStatus;
+            }
             let notifyDone;
             if (action === 'COMMIT') {
                 notifyDone = transactionStatus && transactionStatus.rollupBlock;
@@ -498,19 +498,21 @@ export interface SubmitBatchResponse {
 }
 
 export interface ApiL1TxReceipt {
-    status: 'queued' | 'committed' | 'finalized';
+    status: 'queued' | 'committed' | 'finalized' | 'pruned';
     ethBlock: number;
     rollupBlock?: number;
     id: number;
 }
 
-export type L2TxStatus = 'queued' | 'committed' | 'finalized' | 'rejected';
+export type L2TxStatus = 'queued' | 'committed' | 'finalized' | 'rejected' | 'pruned' | 'expired';
 
 export interface ApiL2TxReceipt {
     txHash: string;
     rollupBlock?: number;
     status: L2TxStatus;
     failReason?: string;
+    // Set only for the transactions evicted from the mempool as expired.
+    validUntil?: string;
 }
 
 export type ApiTxReceipt = ApiL1TxReceipt | ApiL2TxReceipt;